@@ -0,0 +1,118 @@
+//! Compare-OCR command - Run two OCR backends on the same pages
+//!
+//! This module provides the command that compares two OCR backends on a sample of
+//! pages of a scan and reports how much their outputs agree.
+
+use anyhow::{Context, Result};
+use kreuzberg::diff::{OcrComparison, OcrComparisonOptions, compare_ocr_backends};
+use kreuzberg::{ExtractionConfig, PageSelection, detect_mime_type};
+use std::path::PathBuf;
+
+use crate::OutputFormat;
+
+/// Execute compare-ocr command
+///
+/// Both backends use the OCR settings of `config` (language, DPI, Tesseract options)
+/// with only the backend replaced.
+#[allow(clippy::too_many_arguments)]
+pub fn compare_ocr_command(
+    path: PathBuf,
+    config: ExtractionConfig,
+    first: String,
+    second: String,
+    pages: Option<String>,
+    sample: Option<usize>,
+    threshold: f64,
+    format: OutputFormat,
+) -> Result<()> {
+    let path_str = path.to_string_lossy().to_string();
+    let mime_type = detect_mime_type(&path_str, true)
+        .with_context(|| format!("Failed to detect MIME type for file '{}'", path.display()))?;
+    let content = std::fs::read(&path).with_context(|| format!("Failed to read file '{}'", path.display()))?;
+
+    let base = config.ocr.unwrap_or_default();
+    let first_config = kreuzberg::OcrConfig {
+        backend: first,
+        ..base.clone()
+    };
+    let second_config = kreuzberg::OcrConfig {
+        backend: second,
+        ..base
+    };
+    let options = OcrComparisonOptions {
+        pages: pages
+            .map(|spec| spec.parse::<PageSelection>())
+            .transpose()
+            .context("Invalid --pages")?,
+        sample_size: sample,
+        divergence_threshold: threshold,
+    };
+
+    let rt = tokio::runtime::Runtime::new()?;
+    let comparison = rt
+        .block_on(compare_ocr_backends(
+            &content,
+            &mime_type,
+            &first_config,
+            &second_config,
+            &options,
+        ))
+        .with_context(|| format!("Failed to compare OCR backends on '{}'", path.display()))?;
+
+    match format {
+        OutputFormat::Text => print_comparison(&comparison),
+        OutputFormat::Json => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&comparison).context("Failed to serialize OCR comparison to JSON")?
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn print_comparison(comparison: &OcrComparison) {
+    let (first, second) = (&comparison.first_backend, &comparison.second_backend);
+
+    println!(
+        "{:>6}  {:>6}  {:>6}  {:>12}  {:>12}",
+        "page", "chars", "words", first, second
+    );
+    for page in &comparison.pages {
+        println!(
+            "{:>6}  {:>6.3}  {:>6.3}  {:>9.0} ms  {:>9.0} ms",
+            page.page, page.character_agreement, page.word_agreement, page.first.duration_ms, page.second.duration_ms
+        );
+    }
+    println!(
+        "{:>6}  {:>6.3}  {:>6.3}  {:>9.0} ms  {:>9.0} ms",
+        "mean",
+        comparison.character_agreement,
+        comparison.word_agreement,
+        comparison.first_duration_ms,
+        comparison.second_duration_ms
+    );
+
+    for page in comparison
+        .pages
+        .iter()
+        .filter(|page| comparison.divergent_pages.contains(&page.page))
+    {
+        println!("\n--- page {} ---", page.page);
+        for (name, output) in [(first, &page.first), (second, &page.second)] {
+            if let Some(error) = &output.error {
+                println!("{}: failed: {}", name, error);
+            }
+        }
+        for region in &page.differences {
+            println!(
+                "{}: {:?}\n{}: {:?}",
+                first,
+                region.old_text.as_deref().unwrap_or(""),
+                second,
+                region.new_text.as_deref().unwrap_or("")
+            );
+        }
+    }
+}
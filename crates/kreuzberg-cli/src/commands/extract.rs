@@ -4,20 +4,27 @@
 //! or multiple documents with customizable extraction configurations.
 
 use anyhow::{Context, Result};
+use kreuzberg::store::BlobStore;
 use kreuzberg::{
-    ChunkingConfig, ExtractionConfig, LanguageDetectionConfig, OcrConfig, batch_extract_file_sync, extract_file_sync,
+    ChunkingConfig, ExtractionConfig, ExtractionResult, LanguageDetectionConfig, OcrConfig, Query,
+    batch_extract_file_sync, extract_file_sync,
 };
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::{ContentOutputFormatArg, OutputFormat};
 
 /// Execute single document extraction command
+///
+/// With `query`, only the value the JMESPath expression selects from the result is
+/// printed.
 pub fn extract_command(
     path: PathBuf,
     config: ExtractionConfig,
     mime_type: Option<String>,
     format: OutputFormat,
+    query: Option<String>,
 ) -> Result<()> {
+    let query = query.as_deref().map(Query::parse).transpose()?;
     let path_str = path.to_string_lossy().to_string();
 
     let result = extract_file_sync(&path_str, mime_type.as_deref(), &config).with_context(|| {
@@ -27,6 +34,11 @@ pub fn extract_command(
         )
     })?;
 
+    if let Some(query) = query {
+        let value = serde_json::to_value(&result).context("Failed to serialize extraction result to JSON")?;
+        return print_query_result(&query, &value, format);
+    }
+
     match format {
         OutputFormat::Text => {
             println!("{}", result.content);
@@ -44,16 +56,91 @@ pub fn extract_command(
 }
 
 /// Execute batch extraction command
-pub fn batch_command(paths: Vec<PathBuf>, config: ExtractionConfig, format: OutputFormat) -> Result<()> {
+///
+/// With `query`, only the value the JMESPath expression selects from the list of
+/// results is printed. With `store`, the results are written to the content-addressed
+/// store at that directory and only their index entries are printed. With `catalog`,
+/// every file is recorded in the SQLite catalog at that path.
+pub fn batch_command(
+    paths: Vec<PathBuf>,
+    config: ExtractionConfig,
+    format: OutputFormat,
+    query: Option<String>,
+    store: Option<PathBuf>,
+    catalog: Option<PathBuf>,
+) -> Result<()> {
+    let query = query.as_deref().map(Query::parse).transpose()?;
+    if let Some(catalog) = catalog {
+        return catalog_batch_command(paths, config, format, query.as_ref(), store, &catalog);
+    }
+
     let path_strs: Vec<String> = paths.iter().map(|p| p.to_string_lossy().to_string()).collect();
 
-    let results = batch_extract_file_sync(path_strs, &config).with_context(|| {
+    let results = batch_extract_file_sync(path_strs.clone(), &config).with_context(|| {
         format!(
             "Failed to batch extract {} documents. Check that all files are readable and formats are supported.",
             paths.len()
         )
     })?;
 
+    if let Some(store) = store {
+        return store_results(&store, &config, &path_strs, &results, format, |_, _| Ok(()));
+    }
+    print_results(&results, format, query.as_ref())
+}
+
+/// Execute batch extraction, recording every file in the catalog at `db`.
+#[cfg(feature = "catalog")]
+fn catalog_batch_command(
+    paths: Vec<PathBuf>,
+    config: ExtractionConfig,
+    format: OutputFormat,
+    query: Option<&Query>,
+    store: Option<PathBuf>,
+    db: &Path,
+) -> Result<()> {
+    use kreuzberg::catalog::Catalog;
+
+    let catalog = Catalog::open(db).with_context(|| format!("Failed to open catalog '{}'", db.display()))?;
+    let run = catalog.start_run(None)?;
+
+    let rt = tokio::runtime::Runtime::new()?;
+    let recorded = rt
+        .block_on(catalog.extract_batch(run, &paths, &config))
+        .with_context(|| format!("Failed to batch extract {} documents", paths.len()))?;
+    let (records, results): (Vec<i64>, Vec<ExtractionResult>) = recorded.into_iter().unzip();
+
+    let outcome = match store {
+        Some(store) => {
+            let path_strs: Vec<String> = paths.iter().map(|p| p.to_string_lossy().to_string()).collect();
+            store_results(&store, &config, &path_strs, &results, format, |index, manifest| {
+                Ok(catalog.set_location(records[index], &manifest.to_string_lossy())?)
+            })
+        }
+        None => print_results(&results, format, query),
+    };
+    catalog.finish_run(run)?;
+    outcome
+}
+
+#[cfg(not(feature = "catalog"))]
+fn catalog_batch_command(
+    _paths: Vec<PathBuf>,
+    _config: ExtractionConfig,
+    _format: OutputFormat,
+    _query: Option<&Query>,
+    _store: Option<PathBuf>,
+    _db: &Path,
+) -> Result<()> {
+    anyhow::bail!("Recording batches in a catalog requires the 'catalog' feature")
+}
+
+fn print_results(results: &[ExtractionResult], format: OutputFormat, query: Option<&Query>) -> Result<()> {
+    if let Some(query) = query {
+        let value = serde_json::to_value(results).context("Failed to serialize batch extraction results to JSON")?;
+        return print_query_result(query, &value, format);
+    }
+
     match format {
         OutputFormat::Text => {
             for (i, result) in results.iter().enumerate() {
@@ -76,6 +163,70 @@ pub fn batch_command(paths: Vec<PathBuf>, config: ExtractionConfig, format: Outp
     Ok(())
 }
 
+/// Print the value `query` selects from `value`.
+///
+/// Strings are printed as they are with `OutputFormat::Text`, so scripts can use them
+/// directly; everything else is printed as JSON.
+fn print_query_result(query: &Query, value: &serde_json::Value, format: OutputFormat) -> Result<()> {
+    let selected = query.search(value)?;
+    match (&selected, format) {
+        (serde_json::Value::String(text), OutputFormat::Text) => println!("{}", text),
+        _ => println!(
+            "{}",
+            serde_json::to_string_pretty(&selected).context("Failed to serialize query result to JSON")?
+        ),
+    }
+    Ok(())
+}
+
+/// Write batch results to the store at `dir` and print their index entries.
+///
+/// Blobs are compressed with `config.cache`. `stored` is called with the index and
+/// manifest path of every stored result. Failed files are reported but not stored;
+/// the command fails if any file failed.
+fn store_results(
+    dir: &Path,
+    config: &ExtractionConfig,
+    paths: &[String],
+    results: &[ExtractionResult],
+    format: OutputFormat,
+    mut stored: impl FnMut(usize, &Path) -> Result<()>,
+) -> Result<()> {
+    let compression = config.cache.as_ref().map(|cache| cache.compression).unwrap_or_default();
+    let store = BlobStore::open(dir)
+        .with_context(|| format!("Failed to open store '{}'", dir.display()))?
+        .with_compression(compression);
+
+    let mut entries = Vec::new();
+    let mut failed = 0;
+    for (index, (path, result)) in paths.iter().zip(results).enumerate() {
+        if let Some(error) = &result.metadata.error {
+            eprintln!("Failed to extract '{}': {}", path, error.message);
+            failed += 1;
+            continue;
+        }
+        let entry = store
+            .put_result(path, result)
+            .with_context(|| format!("Failed to store the result of '{}' in '{}'", path, dir.display()))?;
+        stored(index, &store.blob_path(&entry.manifest)?)?;
+        if format == OutputFormat::Text {
+            println!("{}  {}", entry.manifest, entry.source);
+        }
+        entries.push(entry);
+    }
+
+    if format == OutputFormat::Json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&entries).context("Failed to serialize store index entries to JSON")?
+        );
+    }
+    if failed > 0 {
+        anyhow::bail!("{} of {} documents failed to extract", failed, paths.len());
+    }
+    Ok(())
+}
+
 /// Apply extraction CLI overrides to config
 ///
 /// # Deprecation Notices
@@ -103,6 +254,7 @@ pub fn apply_extraction_overrides(
                 language: "eng".to_string(),
                 tesseract_config: None,
                 output_format: None,
+                ..Default::default()
             });
         } else {
             config.ocr = None;
@@ -125,6 +277,10 @@ pub fn apply_extraction_overrides(
                 chunker_type: kreuzberg::chunking::ChunkerType::Text,
                 embedding: None,
                 preset: None,
+                overlap_strategy: kreuzberg::chunking::OverlapStrategy::Characters,
+                heading_context: false,
+                tables: None,
+                sentence_segmenter: None,
             });
         } else {
             config.chunking = None;
@@ -3,19 +3,27 @@
 //! This module organizes the CLI commands into focused submodules:
 //! - `extract` - Document extraction commands
 //! - `cache` - Cache management operations
+//! - `compare_ocr` - A/B comparison of OCR backends
 //! - `server` - API and MCP server commands
 //! - `config` - Configuration loading and discovery
+//! - `watch` - Drop-folder watching (requires `watch` feature)
 
 pub mod cache;
+pub mod compare_ocr;
 pub mod config;
 pub mod extract;
 pub mod server;
+#[cfg(feature = "watch")]
+pub mod watch;
 
 // Re-export command functions for convenience
 pub use cache::{clear_command, stats_command};
+pub use compare_ocr::compare_ocr_command;
 pub use config::load_config;
 pub use extract::{apply_extraction_overrides, batch_command, extract_command};
 #[cfg(feature = "mcp")]
 pub use server::mcp_command;
 #[cfg(feature = "api")]
 pub use server::serve_command;
+#[cfg(feature = "watch")]
+pub use watch::watch_command;
@@ -0,0 +1,51 @@
+//! Watch command - Extract the files written to drop folders
+//!
+//! This module provides the command that watches directories and extracts new and
+//! changed files until the process is stopped.
+
+use anyhow::{Context, Result};
+use kreuzberg::watch::{WatchConfig, WatchSink};
+use std::path::PathBuf;
+
+/// Execute watch command
+#[allow(clippy::too_many_arguments)]
+pub fn watch_command(
+    paths: Vec<PathBuf>,
+    extraction_config: kreuzberg::ExtractionConfig,
+    output_dir: Option<PathBuf>,
+    jsonl: Option<PathBuf>,
+    store: Option<PathBuf>,
+    debounce_ms: u64,
+    recursive: bool,
+    initial_scan: bool,
+) -> Result<()> {
+    let sink = match (output_dir, jsonl, store) {
+        (Some(path), _, _) => WatchSink::Directory { path },
+        (None, Some(path), _) => WatchSink::JsonLines { path },
+        (None, None, Some(path)) => WatchSink::Store { path },
+        (None, None, None) => WatchSink::Stdout,
+    };
+    let config = WatchConfig {
+        recursive,
+        debounce_ms,
+        initial_scan,
+        sink,
+        ..WatchConfig::new(paths)
+    };
+
+    tracing::info!("Watching {} for new files", format_paths(&config.paths));
+
+    let rt = tokio::runtime::Runtime::new()?;
+    rt.block_on(kreuzberg::watch::run(&config, &extraction_config))
+        .with_context(|| format!("Failed to watch {}", format_paths(&config.paths)))?;
+
+    Ok(())
+}
+
+fn format_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|path| format!("'{}'", path.display()))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -9,8 +9,10 @@
 //! - `extract`: Extract text/data from a single document
 //! - `batch`: Process multiple documents in parallel
 //! - `detect`: Identify MIME type of a file
+//! - `compare-ocr`: Compare two OCR backends on a sample of pages
 //! - `cache`: Manage cache (clear, stats)
 //! - `serve`: Start API server (requires `api` feature)
+//! - `watch`: Extract files written to drop folders (requires `watch` feature)
 //! - `version`: Show version information
 //!
 //! # Configuration
@@ -49,6 +51,9 @@
 //!
 //! # Detect MIME type
 //! kreuzberg detect unknown-file.bin
+//!
+//! # Compare two OCR backends on 10 pages of a scan
+//! kreuzberg compare-ocr scan.pdf --first tesseract --second paddleocr --sample 10
 //! ```
 
 #![deny(unsafe_code)]
@@ -62,11 +67,17 @@ use clap::{Parser, Subcommand};
 use commands::mcp_command;
 #[cfg(feature = "api")]
 use commands::serve_command;
-use commands::{apply_extraction_overrides, batch_command, clear_command, extract_command, load_config, stats_command};
+#[cfg(feature = "watch")]
+use commands::watch_command;
+use commands::{
+    apply_extraction_overrides, batch_command, clear_command, compare_ocr_command, extract_command, load_config,
+    stats_command,
+};
 use kreuzberg::{OutputFormat as ContentOutputFormat, detect_mime_type};
 use serde_json::json;
 use std::path::{Path, PathBuf};
 use tracing_subscriber::EnvFilter;
+use tracing_subscriber::prelude::*;
 
 /// Kreuzberg document intelligence CLI
 #[derive(Parser)]
@@ -110,6 +121,12 @@ enum Commands {
         #[arg(short, long, default_value = "text")]
         format: OutputFormat,
 
+        /// JMESPath expression selecting the part of the result to print, e.g. `metadata.authors[0]`.
+        ///
+        /// With `--format text`, strings are printed as they are and other values as JSON.
+        #[arg(long)]
+        query: Option<String>,
+
         /// Enable OCR (overrides config file)
         #[arg(long)]
         ocr: Option<bool>,
@@ -158,7 +175,8 @@ enum Commands {
 
     /// Batch extract from multiple documents
     Batch {
-        /// Paths to documents
+        /// Paths to documents, or directories whose files are extracted as configured by
+        /// the `[walk]` section of the config file
         paths: Vec<PathBuf>,
 
         /// Path to config file (TOML, YAML, or JSON). If not specified, searches for kreuzberg.toml/yaml/json in current and parent directories.
@@ -183,6 +201,12 @@ enum Commands {
         #[arg(short, long, default_value = "json")]
         format: OutputFormat,
 
+        /// JMESPath expression evaluated against the list of results, e.g. `[*].metadata.title`.
+        ///
+        /// With `--format text`, strings are printed as they are and other values as JSON.
+        #[arg(long, conflicts_with = "store")]
+        query: Option<String>,
+
         /// Enable OCR (overrides config file)
         #[arg(long)]
         ocr: Option<bool>,
@@ -211,6 +235,16 @@ enum Commands {
         /// This flag is maintained for backward compatibility. Use --output-format for new code.
         #[arg(long, value_enum, hide = true)]
         content_format: Option<ContentOutputFormatArg>,
+
+        /// Write the results to a content-addressed store in this directory and print
+        /// their index entries instead of the results
+        #[arg(long)]
+        store: Option<PathBuf>,
+
+        /// Record every file, its timing, error and result location in this SQLite catalog
+        #[cfg(feature = "catalog")]
+        #[arg(long)]
+        catalog: Option<PathBuf>,
     },
 
     /// Detect MIME type of a file
@@ -223,6 +257,44 @@ enum Commands {
         format: OutputFormat,
     },
 
+    /// Compare two OCR backends on a sample of pages
+    ///
+    /// Both backends run on the same rendered pages with the OCR settings of the
+    /// config file. Reports the character and word agreement and the time of each
+    /// backend per page, and the differing text of pages below --threshold.
+    CompareOcr {
+        /// Path to the image or PDF
+        path: PathBuf,
+
+        /// First OCR backend (e.g., "tesseract")
+        #[arg(long)]
+        first: String,
+
+        /// Second OCR backend (e.g., "paddleocr")
+        #[arg(long)]
+        second: String,
+
+        /// Path to config file (TOML, YAML, or JSON). If not specified, searches for kreuzberg.toml/yaml/json in current and parent directories.
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Pages to sample from (e.g., "1-20,25"). Default: all pages
+        #[arg(long)]
+        pages: Option<String>,
+
+        /// Maximum number of pages compared, spread evenly over the selected pages
+        #[arg(long)]
+        sample: Option<usize>,
+
+        /// Character agreement below which a page is reported as divergent
+        #[arg(long, default_value = "0.9")]
+        threshold: f64,
+
+        /// Output format (text or json)
+        #[arg(short, long, default_value = "text")]
+        format: OutputFormat,
+    },
+
     /// Show version information
     Version {
         /// Output format (text or json)
@@ -279,6 +351,53 @@ enum Commands {
         #[arg(long, default_value = "8001")]
         port: u16,
     },
+
+    /// Watch directories and extract the files written to them
+    ///
+    /// Runs until stopped. A file is extracted once it has gone without writes for
+    /// --debounce-ms, and again whenever it changes. Hidden files are ignored.
+    #[cfg(feature = "watch")]
+    Watch {
+        /// Directories to watch
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Path to config file (TOML, YAML, or JSON). If not specified, searches for kreuzberg.toml/yaml/json in current and parent directories.
+        #[arg(short, long)]
+        config: Option<PathBuf>,
+
+        /// Write one JSON result file per input file into this directory (default: JSON lines on stdout)
+        #[arg(short, long, conflicts_with = "jsonl")]
+        output_dir: Option<PathBuf>,
+
+        /// Append one JSON result line per input file to this file (default: stdout)
+        #[arg(long, conflicts_with = "store")]
+        jsonl: Option<PathBuf>,
+
+        /// Write the artifacts of each result to a content-addressed store in this directory
+        #[arg(long, conflicts_with = "output_dir")]
+        store: Option<PathBuf>,
+
+        /// Milliseconds a file must go without writes before it is extracted
+        #[arg(long, default_value_t = 500)]
+        debounce_ms: u64,
+
+        /// Only watch the top level of the directories, not their subdirectories
+        #[arg(long)]
+        no_recursive: bool,
+
+        /// Also extract the files already in the directories at startup
+        #[arg(long)]
+        initial_scan: bool,
+    },
+
+    /// Serve isolated extractions on stdin and stdout (started by `isolation = "subprocess"`)
+    #[command(hide = true)]
+    Worker {
+        /// Largest address space of the worker, in MiB (0 = unlimited)
+        #[arg(long, default_value_t = 0)]
+        max_memory_mb: u64,
+    },
 }
 
 #[derive(Subcommand)]
@@ -418,21 +537,24 @@ fn validate_chunk_params(chunk_size: Option<usize>, chunk_overlap: Option<usize>
 
 /// Validates batch extraction paths for correctness.
 ///
-/// Ensures that at least one file path is provided and that all paths point to valid,
-/// accessible files. This prevents processing empty batches or failing mid-batch due
-/// to invalid paths.
+/// Ensures that at least one path is provided and that all paths point to valid,
+/// accessible files or directories. This prevents processing empty batches or failing
+/// mid-batch due to invalid paths.
 ///
 /// # Errors
 ///
 /// Returns an error if:
 /// - The paths array is empty (at least one file is required)
-/// - Any path does not exist or is not a regular file
+/// - Any path does not exist or is neither a regular file nor a directory
 fn validate_batch_paths(paths: &[PathBuf]) -> Result<()> {
     if paths.is_empty() {
         anyhow::bail!("No files provided for batch extraction. Please provide at least one file path.");
     }
 
     for (i, path) in paths.iter().enumerate() {
+        if path.is_dir() {
+            continue;
+        }
         validate_file_exists(path).with_context(|| format!("Invalid file at position {}", i + 1))?;
     }
 
@@ -476,10 +598,27 @@ fn merge_json_into_config(
 }
 
 fn main() -> Result<()> {
-    let _ = tracing_subscriber::fmt()
-        .with_env_filter(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
-        .with_writer(std::io::stderr)
-        .try_init();
+    let subscriber = tracing_subscriber::registry()
+        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("info")))
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr));
+
+    // Export spans when an OpenTelemetry collector is configured; the guard flushes them on exit.
+    #[cfg(feature = "otel")]
+    let (subscriber, _telemetry) = {
+        let otlp_configured = ["OTEL_EXPORTER_OTLP_ENDPOINT", "OTEL_EXPORTER_OTLP_TRACES_ENDPOINT"]
+            .iter()
+            .any(|var| std::env::var_os(var).is_some());
+        let (layer, guard) = if otlp_configured {
+            let (layer, guard) =
+                kreuzberg::telemetry::otlp_layer("kreuzberg").context("Failed to set up OpenTelemetry export")?;
+            (Some(layer), Some(guard))
+        } else {
+            (None, None)
+        };
+        (subscriber.with(layer), guard)
+    };
+
+    let _ = subscriber.try_init();
 
     let cli = Cli::parse();
 
@@ -491,6 +630,7 @@ fn main() -> Result<()> {
             config_json_base64,
             mime_type,
             format,
+            query,
             ocr,
             force_ocr,
             no_cache,
@@ -540,7 +680,7 @@ fn main() -> Result<()> {
                 content_format,
             );
 
-            extract_command(path, config, mime_type, format)?;
+            extract_command(path, config, mime_type, format, query)?;
         }
 
         Commands::Batch {
@@ -549,12 +689,16 @@ fn main() -> Result<()> {
             config_json,
             config_json_base64,
             format,
+            query,
             ocr,
             force_ocr,
             no_cache,
             quality,
             output_format,
             content_format,
+            store,
+            #[cfg(feature = "catalog")]
+            catalog,
         } => {
             validate_batch_paths(&paths)?;
 
@@ -593,7 +737,15 @@ fn main() -> Result<()> {
                 content_format,
             );
 
-            batch_command(paths, config, format)?;
+            let paths = kreuzberg::core::walk::walk(&paths, &config.walk.clone().unwrap_or_default())
+                .context("Failed to collect the files to extract")?;
+            if paths.is_empty() {
+                anyhow::bail!("No files to extract were found in the given directories.");
+            }
+
+            #[cfg(not(feature = "catalog"))]
+            let catalog = None;
+            batch_command(paths, config, format, query, store, catalog)?;
         }
 
         Commands::Detect { path, format } => {
@@ -625,6 +777,21 @@ fn main() -> Result<()> {
             }
         }
 
+        Commands::CompareOcr {
+            path,
+            first,
+            second,
+            config: config_path,
+            pages,
+            sample,
+            threshold,
+            format,
+        } => {
+            validate_file_exists(&path)?;
+            let config = load_config(config_path)?;
+            compare_ocr_command(path, config, first, second, pages, sample, threshold, format)?;
+        }
+
         Commands::Version { format } => {
             let version = env!("CARGO_PKG_VERSION");
             let name = env!("CARGO_PKG_NAME");
@@ -674,6 +841,34 @@ fn main() -> Result<()> {
             mcp_command(config, transport, host, port)?;
         }
 
+        #[cfg(feature = "watch")]
+        Commands::Watch {
+            paths,
+            config: config_path,
+            output_dir,
+            jsonl,
+            store,
+            debounce_ms,
+            no_recursive,
+            initial_scan,
+        } => {
+            let config = load_config(config_path)?;
+            watch_command(
+                paths,
+                config,
+                output_dir,
+                jsonl,
+                store,
+                debounce_ms,
+                !no_recursive,
+                initial_scan,
+            )?;
+        }
+
+        Commands::Worker { max_memory_mb } => {
+            kreuzberg::core::isolation::run_worker(max_memory_mb)?;
+        }
+
         Commands::Cache { command } => match command {
             CacheCommands::Stats { cache_dir, format } => {
                 stats_command(cache_dir, format)?;
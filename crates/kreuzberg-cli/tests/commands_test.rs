@@ -93,6 +93,36 @@ fn test_extract_with_json_output() {
     assert!(json.get("mime_type").is_some(), "JSON should have 'mime_type' field");
 }
 
+#[test]
+fn test_extract_in_worker_subprocess() {
+    build_binary();
+
+    let test_file = get_test_file("images/test_hello_world.png");
+    if !PathBuf::from(&test_file).exists() {
+        tracing::debug!("Skipping test: {} not found", test_file);
+        return;
+    }
+
+    let config = serde_json::json!({
+        "isolation": "subprocess",
+        "worker": { "command": get_binary_path() },
+    });
+    let output = Command::new(get_binary_path())
+        .args(["extract", test_file.as_str(), "--format", "json"])
+        .args(["--config-json", &config.to_string()])
+        .output()
+        .expect("Failed to execute extract command");
+
+    assert!(
+        output.status.success(),
+        "Extract command failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let result: serde_json::Value = serde_json::from_slice(&output.stdout).expect("Output should be valid JSON");
+    assert_eq!(result["mime_type"], "image/png");
+}
+
 #[test]
 fn test_extract_with_chunking() {
     build_binary();
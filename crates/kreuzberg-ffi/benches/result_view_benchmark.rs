@@ -53,6 +53,8 @@ fn create_test_result(content_size: usize, chunk_count: usize) -> ExtractionResu
                             total_chunks: chunk_count,
                             first_page: Some(1 + (i / 10)),
                             last_page: Some(1 + (i / 10)),
+                            chunk_type: kreuzberg::types::ChunkType::Text,
+                            additional: Default::default(),
                         },
                     }
                 })
@@ -73,6 +75,7 @@ fn create_test_result(content_size: usize, chunk_count: usize) -> ExtractionResu
         pages: None,
         elements: None,
         djot_content: None,
+        scripts: None,
     }
 }
 
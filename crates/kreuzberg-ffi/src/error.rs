@@ -669,6 +669,7 @@ pub unsafe extern "C" fn kreuzberg_classify_error(error_message: *const c_char)
 
     if lower.contains("invalid")
         || lower.contains("validation")
+        || lower.contains("security")
         || lower.contains("parameter")
         || lower.contains("constraint")
         || lower.contains("format mismatch")
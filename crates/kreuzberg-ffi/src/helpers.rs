@@ -67,6 +67,7 @@ pub fn to_c_extraction_result(result: ExtractionResult) -> std::result::Result<*
         images,
         pages,
         djot_content: _,
+        scripts: _,
         elements,
     } = result;
 
@@ -361,6 +362,7 @@ mod tests {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
@@ -400,6 +402,7 @@ mod tests {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
@@ -449,6 +452,7 @@ mod tests {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
@@ -515,6 +519,8 @@ mod tests {
                 total_chunks: 1,
                 first_page: Some(1),
                 last_page: Some(1),
+                chunk_type: kreuzberg::types::ChunkType::Text,
+                additional: Default::default(),
             },
         };
 
@@ -528,6 +534,7 @@ mod tests {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
@@ -168,6 +168,7 @@ impl OcrBackend for FfiOcrBackend {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -407,7 +408,7 @@ pub unsafe extern "C" fn kreuzberg_list_ocr_backends() -> *mut c_char {
         clear_last_error();
 
         match kreuzberg::plugins::list_ocr_backends() {
-            Ok(backends) => match serde_json::to_string(&backends) {
+            Ok(backends) => match serde_json::to_string(&backends.into_iter().map(|b| b.name).collect::<Vec<_>>()) {
                 Ok(json) => match CString::new(json) {
                     Ok(cstr) => cstr.into_raw(),
                     Err(e) => {
@@ -406,6 +406,8 @@ mod tests {
                         total_chunks: 2,
                         first_page: None,
                         last_page: None,
+                        chunk_type: kreuzberg::types::ChunkType::Text,
+                        additional: Default::default(),
                     },
                 },
                 kreuzberg::types::Chunk {
@@ -419,12 +421,15 @@ mod tests {
                         total_chunks: 2,
                         first_page: None,
                         last_page: None,
+                        chunk_type: kreuzberg::types::ChunkType::Text,
+                        additional: Default::default(),
                     },
                 },
             ]),
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         }
     }
@@ -436,6 +436,8 @@ mod tests {
                         total_chunks: 2,
                         first_page: None,
                         last_page: None,
+                        chunk_type: kreuzberg::types::ChunkType::Text,
+                        additional: Default::default(),
                     },
                 },
                 kreuzberg::types::Chunk {
@@ -449,12 +451,15 @@ mod tests {
                         total_chunks: 2,
                         first_page: None,
                         last_page: None,
+                        chunk_type: kreuzberg::types::ChunkType::Text,
+                        additional: Default::default(),
                     },
                 },
             ]),
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         }
     }
@@ -721,6 +726,7 @@ mod tests {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
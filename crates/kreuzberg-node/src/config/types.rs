@@ -57,6 +57,7 @@ impl From<JsOcrConfig> for RustOcrConfig {
             language: val.language.unwrap_or_else(|| "eng".to_string()),
             tesseract_config: val.tesseract_config.map(Into::into),
             output_format: None,
+            ..Default::default()
         }
     }
 }
@@ -167,6 +168,10 @@ impl From<JsChunkingConfig> for RustChunkingConfig {
             chunker_type: ChunkerType::Text,
             embedding: val.embedding.map(Into::into),
             preset: val.preset,
+            overlap_strategy: kreuzberg::OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         }
     }
 }
@@ -237,6 +242,7 @@ impl From<JsPdfConfig> for RustPdfConfig {
             passwords: val.passwords,
             extract_metadata: val.extract_metadata.unwrap_or(true),
             hierarchy: val.hierarchy.map(|h| h.into()),
+            ..Default::default()
         }
     }
 }
@@ -866,6 +872,7 @@ impl TryFrom<JsPageConfig> for kreuzberg::core::config::PageConfig {
             marker_format: val
                 .marker_format
                 .unwrap_or_else(|| "\n\n<!-- PAGE {page_num} -->\n\n".to_string()),
+            selection: None,
         })
     }
 }
@@ -930,6 +937,7 @@ impl TryFrom<JsExtractionConfig> for ExtractionConfig {
                 })
                 .transpose()?
                 .unwrap_or_default(),
+            ..Default::default()
         })
     }
 }
@@ -46,6 +46,10 @@ pub(crate) fn convert_error(err: kreuzberg::KreuzbergError) -> napi::Error {
             Error::new(Status::InvalidArg, format!("Validation error: {}", message))
         }
 
+        KreuzbergError::Security { message, .. } => {
+            Error::new(Status::InvalidArg, format!("Security error: {}", message))
+        }
+
         KreuzbergError::Cache { message, .. } => {
             Error::new(Status::GenericFailure, format!("Cache error: {}", message))
         }
@@ -67,6 +71,11 @@ pub(crate) fn convert_error(err: kreuzberg::KreuzbergError) -> napi::Error {
             format!("Plugin error in '{}': {}", plugin_name, message),
         ),
 
+        KreuzbergError::ExtractorPanicked { name, payload } => Error::new(
+            Status::GenericFailure,
+            format!("Extractor '{}' panicked: {}", name, payload),
+        ),
+
         KreuzbergError::LockPoisoned(msg) => Error::new(Status::GenericFailure, format!("Lock poisoned: {}", msg)),
 
         KreuzbergError::UnsupportedFormat(format) => {
@@ -5,7 +5,9 @@ use crate::error_handling::convert_error;
 
 #[napi]
 pub fn list_document_extractors() -> Result<Vec<String>> {
-    kreuzberg::plugins::list_extractors().map_err(convert_error)
+    kreuzberg::plugins::list_extractors()
+        .map(|extractors| extractors.into_iter().map(|extractor| extractor.name).collect())
+        .map_err(convert_error)
 }
 
 /// Unregister a document extractor by name.
@@ -141,6 +141,7 @@ impl RustOcrBackend for JsOcrBackend {
             pages: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         })
     }
 
@@ -286,7 +287,9 @@ pub fn unregister_ocr_backend(name: String) -> Result<()> {
 /// List all registered OCR backends
 #[napi]
 pub fn list_ocr_backends() -> Result<Vec<String>> {
-    kreuzberg::plugins::list_ocr_backends().map_err(convert_error)
+    kreuzberg::plugins::list_ocr_backends()
+        .map(|backends| backends.into_iter().map(|backend| backend.name).collect())
+        .map_err(convert_error)
 }
 
 /// Clear all registered OCR backends
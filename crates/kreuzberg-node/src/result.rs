@@ -479,6 +479,8 @@ impl TryFrom<JsExtractionResult> for RustExtractionResult {
                         total_chunks: chunk.metadata.total_chunks as usize,
                         first_page: chunk.metadata.first_page.map(|v| v as usize),
                         last_page: chunk.metadata.last_page.map(|v| v as usize),
+                        chunk_type: kreuzberg::types::ChunkType::Text,
+                        additional: Default::default(),
                     },
                 });
             }
@@ -537,6 +539,7 @@ impl TryFrom<JsExtractionResult> for RustExtractionResult {
                     .collect()
             }),
             djot_content: None,
+            scripts: None,
         })
     }
 }
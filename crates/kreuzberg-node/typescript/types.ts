@@ -691,6 +691,10 @@ export interface PageInfo {
 	tableCount?: number | null;
 	/** Whether this page is hidden (e.g., in presentations) */
 	hidden?: boolean | null;
+	/** Clockwise rotation in degrees (0, 90, 180 or 270) applied to make the page upright for OCR */
+	rotation?: number | null;
+	/** Whether the page was detected as blank and skipped by OCR */
+	blank?: boolean | null;
 }
 
 /**
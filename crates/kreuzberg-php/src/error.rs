@@ -15,11 +15,13 @@ pub fn to_php_exception(error: kreuzberg::KreuzbergError) -> PhpException {
 
     match error {
         KreuzbergError::Validation { .. } => PhpException::default(format!("[Validation] {}", message)),
+        KreuzbergError::Security { .. } => PhpException::default(format!("[Security] {}", message)),
         KreuzbergError::UnsupportedFormat(_) => PhpException::default(format!("[UnsupportedFormat] {}", message)),
         KreuzbergError::Parsing { .. } => PhpException::default(format!("[Parsing] {}", message)),
         KreuzbergError::Io(_) => PhpException::default(format!("[IO] {}", message)),
         KreuzbergError::Ocr { .. } => PhpException::default(format!("[OCR] {}", message)),
         KreuzbergError::Plugin { .. } => PhpException::default(format!("[Plugin] {}", message)),
+        KreuzbergError::ExtractorPanicked { .. } => PhpException::default(format!("[ExtractorPanicked] {}", message)),
         KreuzbergError::LockPoisoned(_) => PhpException::default(format!("[LockPoisoned] {}", message)),
         KreuzbergError::Cache { .. } => PhpException::default(format!("[Cache] {}", message)),
         KreuzbergError::ImageProcessing { .. } => PhpException::default(format!("[ImageProcessing] {}", message)),
@@ -41,6 +43,13 @@ fn format_error_message(error: &kreuzberg::KreuzbergError) -> String {
                 message.clone()
             }
         }
+        KreuzbergError::Security { message, source } => {
+            if let Some(src) = source {
+                format!("{}: {}", message, src)
+            } else {
+                message.clone()
+            }
+        }
         KreuzbergError::UnsupportedFormat(msg) => msg.clone(),
         KreuzbergError::Parsing { message, source } => {
             if let Some(src) = source {
@@ -60,6 +69,9 @@ fn format_error_message(error: &kreuzberg::KreuzbergError) -> String {
         KreuzbergError::Plugin { message, plugin_name } => {
             format!("Plugin error in '{}': {}", plugin_name, message)
         }
+        KreuzbergError::ExtractorPanicked { name, payload } => {
+            format!("Extractor '{}' panicked: {}", name, payload)
+        }
         KreuzbergError::LockPoisoned(msg) => msg.clone(),
         KreuzbergError::Cache { message, source } => {
             if let Some(src) = source {
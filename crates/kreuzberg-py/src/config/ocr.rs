@@ -193,6 +193,7 @@ impl OcrConfig {
                 language: language.unwrap_or_else(|| "eng".to_string()),
                 tesseract_config: tesseract_config.map(Into::into),
                 output_format: None,
+                ..Default::default()
             },
         }
     }
@@ -124,6 +124,7 @@ impl ExtractionConfig {
                 } else {
                     kreuzberg::core::config::formats::OutputFormat::Plain
                 },
+                ..Default::default()
             },
             html_options_dict,
         })
@@ -405,6 +406,7 @@ impl OcrConfig {
                 language: language.unwrap_or_else(|| "eng".to_string()),
                 tesseract_config: tesseract_config.map(Into::into),
                 output_format: None,
+                ..Default::default()
             },
         }
     }
@@ -644,6 +646,10 @@ impl ChunkingConfig {
                 chunker_type: kreuzberg::ChunkerType::Text,
                 embedding: embedding.map(Into::into),
                 preset,
+                overlap_strategy: kreuzberg::OverlapStrategy::Characters,
+                heading_context: false,
+                tables: None,
+                sentence_segmenter: None,
             },
         }
     }
@@ -840,6 +846,7 @@ impl PdfConfig {
                 passwords,
                 extract_metadata: extract_metadata.unwrap_or(true),
                 hierarchy: hierarchy.map(|h| h.inner),
+                ..Default::default()
             },
         }
     }
@@ -1553,6 +1560,7 @@ impl PageConfig {
                 extract_pages: extract_pages.unwrap_or(false),
                 insert_page_markers: insert_page_markers.unwrap_or(false),
                 marker_format: marker_format.unwrap_or_else(|| "\n\n<!-- PAGE {page_num} -->\n\n".to_string()),
+                selection: None,
             },
         }
     }
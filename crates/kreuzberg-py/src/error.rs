@@ -110,6 +110,10 @@ pub fn to_py_err(error: kreuzberg::KreuzbergError) -> PyErr {
         KreuzbergError::Validation { message, source } => {
             exception_from_module("ValidationError", format_error_with_source(message, source))
         }
+        KreuzbergError::Security { message, source } => exception_from_module(
+            "ValidationError",
+            format!("Security error: {}", format_error_with_source(message, source)),
+        ),
         KreuzbergError::UnsupportedFormat(msg) => exception_from_module("ValidationError", msg),
         KreuzbergError::Parsing { message, source } => {
             exception_from_module("ParsingError", format_error_with_source(message, source))
@@ -122,6 +126,9 @@ pub fn to_py_err(error: kreuzberg::KreuzbergError) -> PyErr {
         KreuzbergError::Plugin { message, plugin_name } => {
             exception_from_module("PluginError", format!("Plugin error in '{}': {}", plugin_name, message))
         }
+        KreuzbergError::ExtractorPanicked { name, payload } => {
+            exception_from_module("PluginError", format!("Extractor '{}' panicked: {}", name, payload))
+        }
         // RuntimeError must bubble up - lock poisoning is a system error ~keep
         KreuzbergError::LockPoisoned(msg) => PyRuntimeError::new_err(format!("Lock poisoned: {}", msg)),
         KreuzbergError::Cache { message, source } => {
@@ -137,7 +137,9 @@ pub fn unregister_ocr_backend(name: &str) -> pyo3::PyResult<()> {
 
 #[pyo3::pyfunction]
 pub fn list_ocr_backends() -> pyo3::PyResult<Vec<String>> {
-    kreuzberg::plugins::list_ocr_backends().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+    kreuzberg::plugins::list_ocr_backends()
+        .map(|backends| backends.into_iter().map(|backend| backend.name).collect())
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
 #[pyo3::pyfunction]
@@ -148,7 +150,9 @@ pub fn clear_ocr_backends() -> pyo3::PyResult<()> {
 /// Document extractor management functions re-exported from kreuzberg
 #[pyo3::pyfunction]
 pub fn list_document_extractors() -> pyo3::PyResult<Vec<String>> {
-    kreuzberg::plugins::list_extractors().map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
+    kreuzberg::plugins::list_extractors()
+        .map(|extractors| extractors.into_iter().map(|extractor| extractor.name).collect())
+        .map_err(|e| pyo3::exceptions::PyRuntimeError::new_err(e.to_string()))
 }
 
 #[pyo3::pyfunction]
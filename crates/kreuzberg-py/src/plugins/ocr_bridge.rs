@@ -386,6 +386,7 @@ fn dict_to_extraction_result(_py: Python<'_>, dict: &Bound<'_, PyAny>) -> Result
         pages: None,
         elements: None,
         djot_content: None,
+        scripts: None,
     })
 }
 
@@ -28,6 +28,10 @@ pub struct PythonPostProcessor {
     name: String,
     /// Processing stage (cached from Python or default to Middle)
     stage: ProcessingStage,
+    /// Cached names of processors this one runs before
+    runs_before: Vec<String>,
+    /// Cached names of processors this one runs after
+    runs_after: Vec<String>,
 }
 
 impl PythonPostProcessor {
@@ -71,10 +75,23 @@ impl PythonPostProcessor {
             ProcessingStage::Middle
         };
 
+        let runs_before = if obj.hasattr("runs_before")? {
+            obj.call_method0("runs_before")?.extract()?
+        } else {
+            Vec::new()
+        };
+        let runs_after = if obj.hasattr("runs_after")? {
+            obj.call_method0("runs_after")?.extract()?
+        } else {
+            Vec::new()
+        };
+
         Ok(Self {
             python_obj,
             name,
             stage,
+            runs_before,
+            runs_after,
         })
     }
 }
@@ -165,6 +182,14 @@ impl PostProcessor for PythonPostProcessor {
     fn processing_stage(&self) -> ProcessingStage {
         self.stage
     }
+
+    fn runs_before(&self) -> Vec<String> {
+        self.runs_before.clone()
+    }
+
+    fn runs_after(&self) -> Vec<String> {
+        self.runs_after.clone()
+    }
 }
 
 /// Convert Rust ExtractionResult to Python dict.
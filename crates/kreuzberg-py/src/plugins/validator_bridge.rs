@@ -28,6 +28,10 @@ pub struct PythonValidator {
     name: String,
     /// Cached priority
     priority: i32,
+    /// Cached names of validators this one runs before
+    runs_before: Vec<String>,
+    /// Cached names of validators this one runs after
+    runs_after: Vec<String>,
 }
 
 impl PythonValidator {
@@ -65,10 +69,23 @@ impl PythonValidator {
             50
         };
 
+        let runs_before = if obj.hasattr("runs_before")? {
+            obj.call_method0("runs_before")?.extract()?
+        } else {
+            Vec::new()
+        };
+        let runs_after = if obj.hasattr("runs_after")? {
+            obj.call_method0("runs_after")?.extract()?
+        } else {
+            Vec::new()
+        };
+
         Ok(Self {
             python_obj,
             name,
             priority,
+            runs_before,
+            runs_after,
         })
     }
 }
@@ -195,6 +212,14 @@ impl Validator for PythonValidator {
     fn priority(&self) -> i32 {
         self.priority
     }
+
+    fn runs_before(&self) -> Vec<String> {
+        self.runs_before.clone()
+    }
+
+    fn runs_after(&self) -> Vec<String> {
+        self.runs_after.clone()
+    }
 }
 
 /// Convert Rust ExtractionResult to Python dict.
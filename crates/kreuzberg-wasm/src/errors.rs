@@ -35,6 +35,8 @@ pub fn convert_error(err: KreuzbergError) -> JsValue {
 
         KreuzbergError::Validation { message, .. } => ("ValidationError", format!("Validation error: {}", message)),
 
+        KreuzbergError::Security { message, .. } => ("SecurityError", format!("Security error: {}", message)),
+
         KreuzbergError::Cache { message, .. } => ("CacheError", format!("Cache error: {}", message)),
 
         KreuzbergError::ImageProcessing { message, .. } => {
@@ -51,6 +53,11 @@ pub fn convert_error(err: KreuzbergError) -> JsValue {
             ("PluginError", format!("Plugin error in '{}': {}", plugin_name, message))
         }
 
+        KreuzbergError::ExtractorPanicked { name, payload } => (
+            "ExtractorPanickedError",
+            format!("Extractor '{}' panicked: {}", name, payload),
+        ),
+
         KreuzbergError::LockPoisoned(msg) => ("LockPoisonedError", format!("Lock poisoned: {}", msg)),
 
         KreuzbergError::UnsupportedFormat(format) => {
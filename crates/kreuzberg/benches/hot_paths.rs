@@ -0,0 +1,105 @@
+//! Benchmarks of allocation-heavy extraction paths on large generated documents.
+//!
+//! ```text
+//! cargo bench -p kreuzberg --features html,chunking,quality --bench hot_paths
+//! ```
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use kreuzberg::chunking::{ChunkerType, chunk_text_with_type};
+use kreuzberg::core::config::NormalizationProfile;
+use kreuzberg::extraction::html::extract_html_tables;
+use kreuzberg::text::utf8_validation::decode_lossy;
+use kreuzberg::text::{clean_extracted_text, normalize_text};
+use std::hint::black_box;
+
+/// An HTML page of `tables` tables of `rows` rows, with inline markup, entities and spans.
+fn large_html(tables: usize, rows: usize) -> String {
+    let mut html = String::from("<html><body>");
+    for table in 0..tables {
+        html.push_str("<table><thead><tr><th rowspan=\"2\">Region</th><th colspan=\"3\">Sales</th></tr>");
+        html.push_str("<tr><th>Q1</th><th>Q2</th><th>Q3</th></tr></thead><tbody>");
+        for row in 0..rows {
+            html.push_str(&format!(
+                "<tr><td><b>Region {table}-{row}</b></td><td>{row}&nbsp;000 <i>units</i></td>\
+                 <td><p>Line one</p><p>line   two<br>line three</p></td><td><code>id_{row}</code> &amp; more</td></tr>"
+            ));
+        }
+        html.push_str("</tbody></table>");
+    }
+    html.push_str("</body></html>");
+    html
+}
+
+fn large_text(paragraphs: usize) -> String {
+    (0..paragraphs)
+        .map(|i| {
+            format!(
+                "## Section {i}\n\nLorem ipsum dolor sit amet, consectetur adipiscing elit. Sed do eiusmod tempor \
+                 incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation.\n\n"
+            )
+        })
+        .collect()
+}
+
+/// Plain text as extracted from a PDF: ragged spacing, tabs, trailing spaces and runs of blank lines.
+fn messy_text(lines: usize, non_ascii: bool) -> String {
+    let word = if non_ascii { "Gr\u{fc}\u{df}e" } else { "Regards" };
+    (0..lines)
+        .map(|i| match i % 8 {
+            7 => "  \n\n\n".to_string(),
+            3 => format!("Item {i}:\t\t{word},  the quick brown fox jumps over the lazy dog.   \n"),
+            _ => format!("The quick brown fox jumps over the lazy dog near line {i}, {word} from the river bank.\n"),
+        })
+        .collect()
+}
+
+fn bench_text_cleanup(c: &mut Criterion) {
+    let ascii = messy_text(50_000, false);
+    let unicode = messy_text(50_000, true);
+    let display = NormalizationProfile::Display.options();
+    let mut invalid = ascii.clone().into_bytes();
+    for index in (0..invalid.len()).step_by(4096) {
+        invalid[index] = 0xE9;
+    }
+
+    let mut group = c.benchmark_group("text_cleanup");
+    group.throughput(Throughput::Bytes(ascii.len() as u64));
+    group.bench_function("clean_extracted_text", |b| {
+        b.iter(|| clean_extracted_text(black_box(&ascii)))
+    });
+    group.bench_function("normalize_text_ascii", |b| {
+        b.iter(|| normalize_text(black_box(&ascii), &display).len())
+    });
+    group.bench_function("normalize_text_unicode", |b| {
+        b.iter(|| normalize_text(black_box(&unicode), &display).len())
+    });
+    group.bench_function("decode_lossy_invalid", |b| {
+        b.iter(|| decode_lossy(black_box(&invalid)).len())
+    });
+    group.finish();
+}
+
+fn bench_html_tables(c: &mut Criterion) {
+    let html = large_html(20, 500);
+    let mut group = c.benchmark_group("html_tables");
+    group.throughput(Throughput::Bytes(html.len() as u64));
+    group.bench_function("extract_html_tables", |b| {
+        b.iter(|| extract_html_tables(black_box(&html)))
+    });
+    group.finish();
+}
+
+fn bench_chunking(c: &mut Criterion) {
+    let text = large_text(5_000);
+    let mut group = c.benchmark_group("chunking");
+    group.throughput(Throughput::Bytes(text.len() as u64));
+    for (name, chunker_type) in [("text", ChunkerType::Text), ("markdown", ChunkerType::Markdown)] {
+        group.bench_function(name, |b| {
+            b.iter(|| chunk_text_with_type(black_box(&text), 1000, 100, true, chunker_type).unwrap())
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_html_tables, bench_chunking, bench_text_cleanup);
+criterion_main!(benches);
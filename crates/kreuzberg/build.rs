@@ -6,6 +6,19 @@ use std::process::Command;
 use std::thread;
 use std::time::Duration;
 
+/// Generate the gRPC service code from `proto/`, with the vendored `protoc`.
+#[cfg(feature = "grpc")]
+fn compile_protos() {
+    let protoc = protoc_bin_vendored::protoc_bin_path().expect("vendored protoc is available for this platform");
+    let mut config = tonic_prost_build::Config::new();
+    config.protoc_executable(protoc);
+    tonic_prost_build::configure()
+        .build_transport(false)
+        .compile_with_config(config, &["proto/kreuzberg/v1/extraction.proto"], &["proto"])
+        .expect("failed to compile proto/kreuzberg/v1/extraction.proto");
+    println!("cargo:rerun-if-changed=proto");
+}
+
 /// PDFium linking strategy
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum PdfiumLinkStrategy {
@@ -23,6 +36,9 @@ fn main() {
 
     println!("cargo::rustc-check-cfg=cfg(coverage)");
 
+    #[cfg(feature = "grpc")]
+    compile_protos();
+
     if !cfg!(feature = "pdf") {
         tracing::debug!("PDF feature not enabled, skipping pdfium linking");
         return;
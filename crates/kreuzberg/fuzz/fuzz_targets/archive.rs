@@ -0,0 +1,10 @@
+#![no_main]
+
+use kreuzberg_fuzz::{config, extract};
+use libfuzzer_sys::fuzz_target;
+
+const MIME_TYPES: &[&str] = &["application/zip", "application/x-tar", "application/x-7z-compressed"];
+
+fuzz_target!(|data: &[u8]| {
+    extract(data, MIME_TYPES, &config());
+});
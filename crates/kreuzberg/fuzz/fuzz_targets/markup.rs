@@ -0,0 +1,23 @@
+#![no_main]
+
+use kreuzberg_fuzz::{config, extract};
+use libfuzzer_sys::fuzz_target;
+
+/// Text formats, parsed from the raw bytes, which need not be valid UTF-8.
+const MIME_TYPES: &[&str] = &[
+    "text/html",
+    "application/xml",
+    "text/markdown",
+    "text/x-rst",
+    "application/x-latex",
+    "text/x-org",
+    "application/x-fictionbook+xml",
+    "application/x-jats+xml",
+    "application/docbook+xml",
+    "application/x-bibtex",
+    "text/x-typst",
+];
+
+fuzz_target!(|data: &[u8]| {
+    extract(data, MIME_TYPES, &config());
+});
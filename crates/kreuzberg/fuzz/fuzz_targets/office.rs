@@ -0,0 +1,17 @@
+#![no_main]
+
+use kreuzberg_fuzz::{config, extract};
+use libfuzzer_sys::fuzz_target;
+
+/// ZIP-based Office packages and RTF.
+const MIME_TYPES: &[&str] = &[
+    "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+    "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+    "application/vnd.oasis.opendocument.text",
+    "application/epub+zip",
+    "application/rtf",
+];
+
+fuzz_target!(|data: &[u8]| {
+    extract(data, MIME_TYPES, &config());
+});
@@ -0,0 +1,18 @@
+#![no_main]
+
+use kreuzberg::{PdfBackendKind, PdfConfig};
+use kreuzberg_fuzz::{config, extract};
+use libfuzzer_sys::fuzz_target;
+
+// The lopdf backend: Pdfium is C++ and crashes in it are not caught by the panic
+// boundary, so they are better found by Pdfium's own fuzzers.
+fuzz_target!(|data: &[u8]| {
+    let config = kreuzberg::ExtractionConfig {
+        pdf_options: Some(PdfConfig {
+            backend: PdfBackendKind::Lopdf,
+            ..Default::default()
+        }),
+        ..config()
+    };
+    extract(data, &["application/pdf"], &config);
+});
@@ -0,0 +1,28 @@
+//! Shared driver of the fuzz targets.
+//!
+//! Extractors run behind a panic boundary that turns panics into
+//! [`KreuzbergError::ExtractorPanicked`], so a target has to raise the panic again
+//! for libFuzzer to record the input as a crash.
+
+use kreuzberg::{ExtractionConfig, KreuzbergError, extract_bytes_sync};
+
+/// Configuration of the targets: no cache, and salvage mode so that the repair of
+/// damaged PDFs, ZIP archives and Office packages is fuzzed too.
+pub fn config() -> ExtractionConfig {
+    ExtractionConfig {
+        use_cache: false,
+        salvage: true,
+        ..Default::default()
+    }
+}
+
+/// Extract `data` as each of `mime_types`, panicking when an extractor panicked.
+///
+/// Other errors are expected on malformed input and ignored.
+pub fn extract(data: &[u8], mime_types: &[&str], config: &ExtractionConfig) {
+    for mime_type in mime_types {
+        if let Err(KreuzbergError::ExtractorPanicked { name, payload }) = extract_bytes_sync(data, mime_type, config) {
+            panic!("Extractor '{name}' panicked on {mime_type}: {payload}");
+        }
+    }
+}
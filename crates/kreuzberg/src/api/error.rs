@@ -105,6 +105,7 @@ impl ApiError {
     pub fn new(status: StatusCode, error: KreuzbergError) -> Self {
         let error_type = match &error {
             KreuzbergError::Validation { .. } => "ValidationError",
+            KreuzbergError::Security { .. } => "SecurityError",
             KreuzbergError::Parsing { .. } => "ParsingError",
             KreuzbergError::Ocr { .. } => "OCRError",
             KreuzbergError::Io(_) => "IOError",
@@ -113,6 +114,7 @@ impl ApiError {
             KreuzbergError::Serialization { .. } => "SerializationError",
             KreuzbergError::MissingDependency(_) => "MissingDependencyError",
             KreuzbergError::Plugin { .. } => "PluginError",
+            KreuzbergError::ExtractorPanicked { .. } => "ExtractorPanickedError",
             KreuzbergError::LockPoisoned(_) => "LockPoisonedError",
             KreuzbergError::UnsupportedFormat(_) => "UnsupportedFormatError",
             KreuzbergError::Other(_) => "Error",
@@ -155,7 +157,9 @@ impl From<KreuzbergError> for ApiError {
     fn from(error: KreuzbergError) -> Self {
         match &error {
             KreuzbergError::Validation { .. } => Self::validation(error),
-            KreuzbergError::Parsing { .. } | KreuzbergError::Ocr { .. } => Self::unprocessable(error),
+            KreuzbergError::Parsing { .. } | KreuzbergError::Ocr { .. } | KreuzbergError::Security { .. } => {
+                Self::unprocessable(error)
+            }
             _ => Self::internal(error),
         }
     }
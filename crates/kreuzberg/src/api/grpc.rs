@@ -0,0 +1,244 @@
+//! gRPC service of the API server.
+//!
+//! With the `grpc` feature, the router returned by [`create_router`](super::create_router)
+//! also serves the `kreuzberg.v1.Extraction` service of `proto/kreuzberg/v1/extraction.proto`,
+//! so gRPC clients and HTTP clients share one address. Documents are uploaded as a
+//! stream of messages, and `ExtractChunks` streams the chunks of the result back.
+
+use std::sync::Arc;
+
+use tonic::server::NamedService;
+use tonic::{Request, Response, Status, Streaming};
+
+use crate::core::config::{ChunkingConfig, ExtractionConfig};
+use crate::core::extractor::extract_bytes;
+use crate::core::mime::detect_mime_type_from_bytes;
+use crate::error::KreuzbergError;
+use crate::types::{Chunk, ExtractedImage, ExtractionResult, PageContent, Table};
+
+use super::tenants::Tenant;
+use super::types::ApiSizeLimits;
+
+/// Messages, service and client generated from `proto/kreuzberg/v1/extraction.proto`.
+#[allow(clippy::all, missing_docs)]
+pub mod proto {
+    tonic::include_proto!("kreuzberg.v1");
+}
+
+use proto::extract_chunks_response::Event;
+use proto::extract_request::Part;
+use proto::extraction_server::{Extraction, ExtractionServer};
+use proto::{ExtractChunksResponse, ExtractRequest, ExtractResponse};
+
+/// Implementation of the `kreuzberg.v1.Extraction` service.
+pub struct ExtractionService {
+    default_config: Arc<ExtractionConfig>,
+    max_upload_bytes: usize,
+}
+
+impl ExtractionService {
+    /// Create the service with the default extraction configuration of the server.
+    ///
+    /// Requests of a tenant use the tenant's configuration instead.
+    pub fn new(default_config: Arc<ExtractionConfig>, limits: ApiSizeLimits) -> Self {
+        Self {
+            default_config,
+            max_upload_bytes: limits.max_request_body_bytes,
+        }
+    }
+
+    /// Wrap the service into a server accepting uploads up to the size limit in one message.
+    pub fn into_server(self) -> ExtractionServer<Self> {
+        let max_message_size = self.max_upload_bytes;
+        ExtractionServer::new(self).max_decoding_message_size(max_message_size)
+    }
+}
+
+/// Router serving the gRPC service, to be merged into the HTTP router.
+///
+/// Only the service's own paths are routed, so unknown paths still get the HTTP router's 404.
+pub(super) fn routes(default_config: Arc<ExtractionConfig>, limits: ApiSizeLimits) -> axum::Router {
+    let server = ExtractionService::new(default_config, limits).into_server();
+    axum::Router::new().route_service(
+        &format!("/{}/{{*method}}", ExtractionServer::<ExtractionService>::NAME),
+        server,
+    )
+}
+
+/// A received upload.
+struct Upload {
+    data: Vec<u8>,
+    mime_type: String,
+    config: ExtractionConfig,
+}
+
+impl ExtractionService {
+    async fn receive(&self, request: Request<Streaming<ExtractRequest>>) -> Result<Upload, Status> {
+        let default_config = request
+            .extensions()
+            .get::<Arc<Tenant>>()
+            .map_or(&self.default_config, |tenant| &tenant.config)
+            .clone();
+        let mut messages = request.into_inner();
+        let header = match messages.message().await? {
+            Some(ExtractRequest {
+                part: Some(Part::Header(header)),
+            }) => header,
+            _ => return Err(Status::invalid_argument("The first message must be an upload header")),
+        };
+
+        let mut data = Vec::new();
+        while let Some(message) = messages.message().await? {
+            let Some(Part::Data(part)) = message.part else {
+                return Err(Status::invalid_argument(
+                    "Only the first message may be an upload header",
+                ));
+            };
+            if data.len() + part.len() > self.max_upload_bytes {
+                return Err(Status::resource_exhausted(format!(
+                    "Upload exceeds the limit of {} bytes",
+                    self.max_upload_bytes
+                )));
+            }
+            data.extend_from_slice(&part);
+        }
+
+        let config = if header.config_json.is_empty() {
+            (*default_config).clone()
+        } else {
+            let overrides: crate::core::config::ConfigOverrides = serde_json::from_str(&header.config_json)
+                .map_err(|e| Status::invalid_argument(format!("Invalid extraction configuration: {e}")))?;
+            default_config.merge(overrides)
+        };
+        let mime_type = if header.mime_type.is_empty() {
+            detect_mime_type_from_bytes(&data).map_err(status)?
+        } else {
+            header.mime_type
+        };
+        tracing::debug!(
+            "Received gRPC upload '{}' ({} bytes, {})",
+            header.file_name,
+            data.len(),
+            mime_type
+        );
+
+        Ok(Upload {
+            data,
+            mime_type,
+            config,
+        })
+    }
+}
+
+#[tonic::async_trait]
+impl Extraction for ExtractionService {
+    async fn extract(&self, request: Request<Streaming<ExtractRequest>>) -> Result<Response<ExtractResponse>, Status> {
+        let upload = self.receive(request).await?;
+        let result = extract_bytes(&upload.data, &upload.mime_type, &upload.config)
+            .await
+            .map_err(status)?;
+        Ok(Response::new(ExtractResponse {
+            result: Some(to_proto(result)?),
+        }))
+    }
+
+    type ExtractChunksStream = futures::stream::Iter<std::vec::IntoIter<Result<ExtractChunksResponse, Status>>>;
+
+    async fn extract_chunks(
+        &self,
+        request: Request<Streaming<ExtractRequest>>,
+    ) -> Result<Response<Self::ExtractChunksStream>, Status> {
+        let mut upload = self.receive(request).await?;
+        upload.config.chunking.get_or_insert_with(ChunkingConfig::default);
+        let mut result = extract_bytes(&upload.data, &upload.mime_type, &upload.config)
+            .await
+            .map_err(status)?;
+
+        let chunks = result.chunks.take().unwrap_or_default();
+        let mut events: Vec<_> = chunks
+            .into_iter()
+            .map(|chunk| {
+                Ok(ExtractChunksResponse {
+                    event: Some(Event::Chunk(chunk.into())),
+                })
+            })
+            .collect();
+        events.push(Ok(ExtractChunksResponse {
+            event: Some(Event::Result(to_proto(result)?)),
+        }));
+        Ok(Response::new(futures::stream::iter(events)))
+    }
+}
+
+/// gRPC status of an extraction error, matching the HTTP status of the REST API.
+fn status(error: KreuzbergError) -> Status {
+    match &error {
+        KreuzbergError::Validation { .. } | KreuzbergError::UnsupportedFormat(_) => {
+            Status::invalid_argument(error.to_string())
+        }
+        KreuzbergError::Parsing { .. } | KreuzbergError::Ocr { .. } | KreuzbergError::Security { .. } => {
+            Status::failed_precondition(error.to_string())
+        }
+        _ => Status::internal(error.to_string()),
+    }
+}
+
+fn to_proto(result: ExtractionResult) -> Result<proto::ExtractionResult, Status> {
+    let metadata_json = serde_json::to_string(&result.metadata)
+        .map_err(|e| Status::internal(format!("Failed to serialize metadata: {e}")))?;
+    Ok(proto::ExtractionResult {
+        content: result.content,
+        mime_type: result.mime_type.into_owned(),
+        metadata_json,
+        tables: result.tables.into_iter().map(Into::into).collect(),
+        detected_languages: result.detected_languages.unwrap_or_default(),
+        chunks: result.chunks.unwrap_or_default().into_iter().map(Into::into).collect(),
+        pages: result.pages.unwrap_or_default().into_iter().map(Into::into).collect(),
+        images: result.images.unwrap_or_default().into_iter().map(Into::into).collect(),
+    })
+}
+
+impl From<Table> for proto::Table {
+    fn from(table: Table) -> Self {
+        Self {
+            rows: table.cells.into_iter().map(|cells| proto::TableRow { cells }).collect(),
+            markdown: table.markdown,
+            page_number: table.page_number as u32,
+        }
+    }
+}
+
+impl From<Chunk> for proto::Chunk {
+    fn from(chunk: Chunk) -> Self {
+        Self {
+            content: chunk.content,
+            embedding: chunk.embedding.unwrap_or_default(),
+            byte_start: chunk.metadata.byte_start as u64,
+            byte_end: chunk.metadata.byte_end as u64,
+            token_count: chunk.metadata.token_count.map(|count| count as u64),
+            chunk_index: chunk.metadata.chunk_index as u32,
+            total_chunks: chunk.metadata.total_chunks as u32,
+            first_page: chunk.metadata.first_page.map(|page| page as u32),
+            last_page: chunk.metadata.last_page.map(|page| page as u32),
+        }
+    }
+}
+
+impl From<PageContent> for proto::Page {
+    fn from(page: PageContent) -> Self {
+        Self {
+            page_number: page.page_number as u32,
+            content: page.content,
+        }
+    }
+}
+
+impl From<ExtractedImage> for proto::Image {
+    fn from(image: ExtractedImage) -> Self {
+        Self {
+            data: image.data.to_vec(),
+            format: image.format.into_owned(),
+            page_number: image.page_number.map(|page| page as u32),
+        }
+    }
+}
@@ -1,14 +1,22 @@
 //! API request handlers.
 
-use axum::{Json, extract::State};
+use std::sync::Arc;
 
-use crate::{batch_extract_bytes, cache, extract_bytes};
+use axum::{
+    Extension, Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+
+use crate::{SelfTestReport, batch_extract_bytes, cache, extract_bytes};
 
 use super::{
     error::{ApiError, JsonApi, MultipartApi},
+    jobs::Jobs,
+    tenants::Tenant,
     types::{
         ApiState, CacheClearResponse, CacheStatsResponse, ChunkRequest, ChunkResponse, EmbedRequest, EmbedResponse,
-        ExtractResponse, HealthResponse, InfoResponse,
+        ErrorResponse, ExtractResponse, HealthResponse, InfoResponse, JobResponse,
     },
 };
 
@@ -56,9 +64,36 @@ pub async fn info_handler() -> Json<InfoResponse> {
     Json(InfoResponse {
         version: env!("CARGO_PKG_VERSION").to_string(),
         rust_backend: true,
+        capabilities: crate::capabilities(),
     })
 }
 
+/// Readiness endpoint handler.
+///
+/// GET /ready
+///
+/// Runs the self-test with the server's default configuration, answering 503 when a
+/// component fails, for use as a container readiness probe.
+#[utoipa::path(
+    get,
+    path = "/ready",
+    tag = "health",
+    responses(
+        (status = 200, description = "All components passed the self-test", body = SelfTestReport),
+        (status = 503, description = "A component failed the self-test", body = SelfTestReport),
+    )
+)]
+#[cfg_attr(feature = "otel", tracing::instrument(name = "api.ready", skip(state)))]
+pub async fn ready_handler(State(state): State<ApiState>) -> (StatusCode, Json<SelfTestReport>) {
+    let report = crate::self_test(&state.default_config).await;
+    let status = if report.passed {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (status, Json(report))
+}
+
 /// Extract endpoint handler.
 ///
 /// POST /extract
@@ -80,7 +115,8 @@ pub async fn info_handler() -> Json<InfoResponse> {
 /// If a request exceeds the size limit, it will be rejected with HTTP 413 (Payload Too Large).
 ///
 /// The server's default config (loaded from kreuzberg.toml/yaml/json via discovery)
-/// is used as the base, and any per-request config overrides those defaults.
+/// is used as the base, and the fields set in the per-request config override those
+/// defaults (see [`ConfigOverrides`](crate::ConfigOverrides)).
 #[utoipa::path(
     post,
     path = "/extract",
@@ -89,7 +125,9 @@ pub async fn info_handler() -> Json<InfoResponse> {
     responses(
         (status = 200, description = "Extraction successful", body = ExtractResponse),
         (status = 400, description = "Bad request", body = crate::api::types::ErrorResponse),
+        (status = 401, description = "Missing or invalid API key", body = crate::api::types::ErrorResponse),
         (status = 413, description = "Payload too large", body = crate::api::types::ErrorResponse),
+        (status = 429, description = "Quota exceeded", body = crate::api::types::ErrorResponse),
         (status = 500, description = "Internal server error", body = crate::api::types::ErrorResponse),
     )
 )]
@@ -97,16 +135,44 @@ pub async fn info_handler() -> Json<InfoResponse> {
     feature = "otel",
     tracing::instrument(
         name = "api.extract",
-        skip(state, multipart),
+        skip(state, tenant, multipart),
         fields(files_count = tracing::field::Empty)
     )
 )]
 pub async fn extract_handler(
     State(state): State<ApiState>,
-    MultipartApi(mut multipart): MultipartApi,
+    tenant: Option<Extension<Arc<Tenant>>>,
+    MultipartApi(multipart): MultipartApi,
 ) -> Result<Json<ExtractResponse>, ApiError> {
+    let default_config = tenant.map_or(state.default_config, |Extension(tenant)| Arc::clone(&tenant.config));
+    let form = read_extract_form(multipart, &default_config).await?;
+
+    #[cfg(feature = "otel")]
+    tracing::Span::current().record("files_count", form.files.len());
+
+    // Use provided config or fall back to the default of the tenant or server
+    let final_config = form.config.as_ref().unwrap_or(&default_config);
+    Ok(Json(extract_files(form.files, final_config).await?))
+}
+
+/// Multipart form of `POST /extract` and `POST /jobs`.
+struct ExtractForm {
+    /// File contents and MIME types
+    files: Vec<(Vec<u8>, String)>,
+    /// Default config with the per-request overrides applied, None = default config
+    config: Option<crate::core::config::ExtractionConfig>,
+    /// Callback URL of an async job
+    callback_url: Option<String>,
+}
+
+/// Read the files, `config`, `output_format` and `callback_url` fields of an extraction form.
+async fn read_extract_form(
+    mut multipart: axum::extract::Multipart,
+    default_config: &crate::core::config::ExtractionConfig,
+) -> Result<ExtractForm, ApiError> {
     let mut files = Vec::new();
     let mut config: Option<crate::core::config::ExtractionConfig> = None;
+    let mut callback_url = None;
 
     while let Some(field) = multipart
         .next_field()
@@ -117,7 +183,6 @@ pub async fn extract_handler(
 
         match field_name.as_str() {
             "files" => {
-                let file_name = field.file_name().map(|s| s.to_string());
                 let content_type = field.content_type().map(|s| s.to_string());
                 let data = field
                     .bytes()
@@ -126,7 +191,7 @@ pub async fn extract_handler(
 
                 let mime_type = content_type.unwrap_or_else(|| "application/octet-stream".to_string());
 
-                files.push((data.to_vec(), mime_type, file_name));
+                files.push((data.to_vec(), mime_type));
             }
             "config" => {
                 let config_str = field
@@ -134,12 +199,14 @@ pub async fn extract_handler(
                     .await
                     .map_err(|e| ApiError::validation(crate::error::KreuzbergError::validation(e.to_string())))?;
 
-                config = Some(serde_json::from_str(&config_str).map_err(|e| {
-                    ApiError::validation(crate::error::KreuzbergError::validation(format!(
-                        "Invalid extraction configuration: {}",
-                        e
-                    )))
-                })?);
+                let overrides: crate::core::config::ConfigOverrides =
+                    serde_json::from_str(&config_str).map_err(|e| {
+                        ApiError::validation(crate::error::KreuzbergError::validation(format!(
+                            "Invalid extraction configuration: {}",
+                            e
+                        )))
+                    })?;
+                config = Some(config.as_ref().unwrap_or(default_config).merge(overrides));
             }
             "output_format" => {
                 let format_str = field
@@ -148,7 +215,7 @@ pub async fn extract_handler(
                     .map_err(|e| ApiError::validation(crate::error::KreuzbergError::validation(e.to_string())))?;
 
                 // Ensure config exists before modifying output_format
-                let cfg = config.get_or_insert_with(|| (*state.default_config).clone());
+                let cfg = config.get_or_insert_with(|| default_config.clone());
                 cfg.output_format = match format_str.to_lowercase().as_str() {
                     "plain" => crate::core::config::OutputFormat::Plain,
                     "markdown" => crate::core::config::OutputFormat::Markdown,
@@ -162,6 +229,14 @@ pub async fn extract_handler(
                     }
                 };
             }
+            "callback_url" => {
+                callback_url = Some(
+                    field
+                        .text()
+                        .await
+                        .map_err(|e| ApiError::validation(crate::error::KreuzbergError::validation(e.to_string())))?,
+                );
+            }
             _ => {}
         }
     }
@@ -172,25 +247,112 @@ pub async fn extract_handler(
         )));
     }
 
-    #[cfg(feature = "otel")]
-    tracing::Span::current().record("files_count", files.len());
-
-    // Use provided config or fall back to default from state
-    let final_config = config.as_ref().unwrap_or(&state.default_config);
+    Ok(ExtractForm {
+        files,
+        config,
+        callback_url,
+    })
+}
 
+/// Extract `files` with `config`, one result per file.
+pub(super) async fn extract_files(
+    files: Vec<(Vec<u8>, String)>,
+    config: &crate::core::config::ExtractionConfig,
+) -> crate::Result<ExtractResponse> {
     if files.len() == 1 {
-        let (data, mime_type, _file_name) = files
+        let (data, mime_type) = files
             .into_iter()
             .next()
             .expect("files.len() == 1 guarantees one element exists");
-        let result = extract_bytes(&data, mime_type.as_str(), final_config).await?;
-        return Ok(Json(vec![result]));
+        let result = extract_bytes(&data, mime_type.as_str(), config).await?;
+        return Ok(vec![result]);
     }
 
-    let files_data: Vec<(Vec<u8>, String)> = files.into_iter().map(|(data, mime, _name)| (data, mime)).collect();
+    batch_extract_bytes(files, config).await
+}
+
+/// Create job endpoint handler.
+///
+/// POST /jobs
+///
+/// Accepts the multipart form of `POST /extract`, plus:
+/// - `callback_url` (optional): URL the finished job is POSTed to
+///
+/// Returns the pending job at once; the extraction runs in the background.
+/// Poll `GET /jobs/{id}` or wait for the callback to get the results.
+#[utoipa::path(
+    post,
+    path = "/jobs",
+    tag = "extraction",
+    request_body(content_type = "multipart/form-data"),
+    responses(
+        (status = 202, description = "Job created", body = JobResponse),
+        (status = 400, description = "Bad request", body = crate::api::types::ErrorResponse),
+        (status = 401, description = "Missing or invalid API key", body = crate::api::types::ErrorResponse),
+        (status = 413, description = "Payload too large", body = crate::api::types::ErrorResponse),
+        (status = 429, description = "Quota exceeded", body = crate::api::types::ErrorResponse),
+    )
+)]
+#[cfg_attr(
+    feature = "otel",
+    tracing::instrument(name = "api.create_job", skip(state, jobs, tenant, multipart))
+)]
+pub async fn create_job_handler(
+    State(state): State<ApiState>,
+    Extension(jobs): Extension<Arc<Jobs>>,
+    tenant: Option<Extension<Arc<Tenant>>>,
+    MultipartApi(multipart): MultipartApi,
+) -> Result<(StatusCode, Json<JobResponse>), ApiError> {
+    let tenant = tenant.map(|Extension(tenant)| tenant);
+    let default_config = tenant
+        .as_ref()
+        .map_or(state.default_config, |tenant| Arc::clone(&tenant.config));
+    let form = read_extract_form(multipart, &default_config).await?;
+    let callback_url = form.callback_url.map(|url| jobs.check_callback_url(&url)).transpose()?;
+
+    let config = form.config.map_or(default_config, Arc::new);
+    let job = jobs.submit(
+        tenant.map(|tenant| tenant.name.clone()),
+        form.files,
+        config,
+        callback_url,
+    );
+    Ok((StatusCode::ACCEPTED, Json(job)))
+}
 
-    let results = batch_extract_bytes(files_data, final_config).await?;
-    Ok(Json(results))
+/// Job status endpoint handler.
+///
+/// GET /jobs/{id}
+///
+/// Returns the job with its results or error once finished. Jobs are only visible
+/// to the tenant that created them.
+#[utoipa::path(
+    get,
+    path = "/jobs/{id}",
+    tag = "extraction",
+    params(("id" = String, Path, description = "Job ID")),
+    responses(
+        (status = 200, description = "Job status", body = JobResponse),
+        (status = 401, description = "Missing or invalid API key", body = crate::api::types::ErrorResponse),
+        (status = 404, description = "Job not found", body = crate::api::types::ErrorResponse),
+    )
+)]
+#[cfg_attr(feature = "otel", tracing::instrument(name = "api.job", skip(jobs, tenant)))]
+pub async fn job_handler(
+    Path(id): Path<String>,
+    Extension(jobs): Extension<Arc<Jobs>>,
+    tenant: Option<Extension<Arc<Tenant>>>,
+) -> Result<Json<JobResponse>, ApiError> {
+    let tenant = tenant.as_ref().map(|Extension(tenant)| tenant.name.as_str());
+    jobs.get(&id, tenant).map(Json).ok_or_else(|| ApiError {
+        status: StatusCode::NOT_FOUND,
+        body: ErrorResponse {
+            error_type: "NotFoundError".to_string(),
+            message: format!("Job '{id}' not found"),
+            traceback: None,
+            status_code: StatusCode::NOT_FOUND.as_u16(),
+        },
+    })
 }
 
 /// Cache stats endpoint handler.
@@ -364,6 +526,8 @@ pub async fn embed_handler(JsonApi(request): JsonApi<EmbedRequest>) -> Result<Js
                 total_chunks: request.texts.len(),
                 first_page: None,
                 last_page: None,
+                chunk_type: crate::types::ChunkType::Text,
+                additional: Default::default(),
             },
         })
         .collect();
@@ -454,7 +618,7 @@ pub async fn embed_handler(JsonApi(_request): JsonApi<EmbedRequest>) -> Result<J
 )]
 pub async fn chunk_handler(JsonApi(request): JsonApi<ChunkRequest>) -> Result<Json<ChunkResponse>, ApiError> {
     use super::types::{ChunkItem, ChunkingConfigResponse};
-    use crate::chunking::{ChunkerType, ChunkingConfig, chunk_text};
+    use crate::chunking::{ChunkerType, ChunkingConfig, OverlapStrategy, chunk_text};
 
     // Validate input
     if request.text.is_empty() {
@@ -495,6 +659,10 @@ pub async fn chunk_handler(JsonApi(request): JsonApi<ChunkRequest>) -> Result<Js
         chunker_type,
         embedding: None,
         preset: None,
+        overlap_strategy: OverlapStrategy::Characters,
+        heading_context: false,
+        tables: None,
+        sentence_segmenter: None,
     };
 
     // Perform chunking - convert any remaining errors to validation errors since they're likely config issues
@@ -0,0 +1,289 @@
+//! Async extraction jobs of the API server and their result callbacks.
+//!
+//! Jobs are kept in memory: `GET /jobs/{id}` serves them until
+//! [`JobsConfig::retention_secs`] after they finished, and jobs still running when
+//! the server stops are lost.
+//!
+//! Callbacks are POSTed as the JSON of the finished job, with the headers
+//!
+//! - `X-Kreuzberg-Job-Id`: the job ID
+//! - `X-Kreuzberg-Timestamp`: Unix time of the attempt in seconds
+//! - `X-Kreuzberg-Signature`: `sha256=<hex>`, the HMAC-SHA256 of `<timestamp>.<body>`
+//!   with [`JobsConfig::signing_key`], when one is configured
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use parking_lot::Mutex;
+use sha2::Sha256;
+use tracing::Instrument;
+
+use crate::core::JobsConfig;
+use crate::{ExtractionConfig, KreuzbergError, Result};
+
+use super::error::ApiError;
+use super::handlers::extract_files;
+use super::types::{CallbackStatus, JobCallback, JobResponse, JobStatus};
+
+/// Async jobs of a router, by ID.
+pub(super) struct Jobs {
+    config: JobsConfig,
+    client: reqwest::Client,
+    jobs: Mutex<HashMap<String, StoredJob>>,
+}
+
+struct StoredJob {
+    /// Tenant that created the job, None without tenants
+    tenant: Option<String>,
+    job: JobResponse,
+    finished: Option<Instant>,
+}
+
+impl Jobs {
+    pub(super) fn new(config: JobsConfig) -> Self {
+        // Redirects are not followed, so callbacks cannot be sent to hosts that are not allowed.
+        let client = reqwest::Client::builder()
+            .redirect(reqwest::redirect::Policy::none())
+            .timeout(Duration::from_secs(config.callback_timeout_secs))
+            .build()
+            .unwrap_or_else(|e| {
+                tracing::warn!("Failed to create the job callback client, using defaults: {e}");
+                reqwest::Client::new()
+            });
+
+        Self {
+            config,
+            client,
+            jobs: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Parse a callback URL and check its scheme and host against the configuration.
+    pub(super) fn check_callback_url(&self, url: &str) -> Result<reqwest::Url> {
+        if crate::core::config::NetworkConfig::denied_by_environment() {
+            return Err(KreuzbergError::Security {
+                message: "Callbacks are not sent because network access is denied".to_string(),
+                source: None,
+            });
+        }
+        let parsed = reqwest::Url::parse(url.trim())
+            .map_err(|e| KreuzbergError::validation(format!("Invalid callback_url '{url}': {e}")))?;
+        if !matches!(parsed.scheme(), "http" | "https") {
+            return Err(KreuzbergError::validation(format!(
+                "callback_url must be an http or https URL, got '{url}'"
+            )));
+        }
+        let host = parsed.host_str().unwrap_or_default();
+        if !self.config.callback_hosts.is_empty()
+            && !self
+                .config
+                .callback_hosts
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(host))
+        {
+            return Err(KreuzbergError::validation(format!(
+                "Callbacks to host '{host}' are not allowed"
+            )));
+        }
+        Ok(parsed)
+    }
+
+    /// Queue the extraction of `files` and return the new job.
+    pub(super) fn submit(
+        self: &Arc<Self>,
+        tenant: Option<String>,
+        files: Vec<(Vec<u8>, String)>,
+        config: Arc<ExtractionConfig>,
+        callback_url: Option<reqwest::Url>,
+    ) -> JobResponse {
+        let job = JobResponse {
+            id: uuid::Uuid::new_v4().to_string(),
+            status: JobStatus::Pending,
+            created_at: unix_time(),
+            finished_at: None,
+            results: None,
+            error: None,
+            callback: callback_url.as_ref().map(|url| JobCallback {
+                url: url.to_string(),
+                status: CallbackStatus::Pending,
+                attempts: 0,
+                error: None,
+            }),
+        };
+
+        {
+            let mut jobs = self.jobs.lock();
+            let retention = Duration::from_secs(self.config.retention_secs);
+            jobs.retain(|_, stored| stored.finished.is_none_or(|finished| finished.elapsed() < retention));
+            jobs.insert(
+                job.id.clone(),
+                StoredJob {
+                    tenant,
+                    job: job.clone(),
+                    finished: None,
+                },
+            );
+        }
+
+        let span = tracing::info_span!("job", job.id = %job.id);
+        tokio::spawn(
+            Arc::clone(self)
+                .run(job.id.clone(), files, config, callback_url)
+                .instrument(span),
+        );
+        job
+    }
+
+    /// The job `id`, if it exists and belongs to `tenant`.
+    pub(super) fn get(&self, id: &str, tenant: Option<&str>) -> Option<JobResponse> {
+        self.jobs
+            .lock()
+            .get(id)
+            .filter(|stored| stored.tenant.as_deref() == tenant)
+            .map(|stored| stored.job.clone())
+    }
+
+    fn update(&self, id: &str, update: impl FnOnce(&mut StoredJob)) {
+        if let Some(stored) = self.jobs.lock().get_mut(id) {
+            update(stored);
+        }
+    }
+
+    async fn run(
+        self: Arc<Self>,
+        id: String,
+        files: Vec<(Vec<u8>, String)>,
+        config: Arc<ExtractionConfig>,
+        callback_url: Option<reqwest::Url>,
+    ) {
+        self.update(&id, |stored| stored.job.status = JobStatus::Running);
+
+        let outcome = extract_files(files, &config).await;
+        self.update(&id, |stored| {
+            match outcome {
+                Ok(results) => {
+                    stored.job.status = JobStatus::Succeeded;
+                    stored.job.results = Some(results);
+                }
+                Err(e) => {
+                    tracing::debug!("Job failed: {e}");
+                    stored.job.status = JobStatus::Failed;
+                    stored.job.error = Some(ApiError::from(e).body);
+                }
+            }
+            stored.job.finished_at = Some(unix_time());
+            stored.finished = Some(Instant::now());
+        });
+
+        if let Some(url) = callback_url {
+            self.deliver(&id, url).await;
+        }
+    }
+
+    /// POST the finished job to its callback URL, retrying failed attempts with backoff.
+    async fn deliver(&self, id: &str, url: reqwest::Url) {
+        let Some(mut job) = self.jobs.lock().get(id).map(|stored| stored.job.clone()) else {
+            return;
+        };
+        job.callback = None;
+        let body = match serde_json::to_vec(&job) {
+            Ok(body) => body,
+            Err(e) => {
+                tracing::warn!("Failed to serialize the callback of job {id}: {e}");
+                return;
+            }
+        };
+
+        let attempts = self.config.callback_attempts;
+        for attempt in 1..=attempts {
+            if attempt > 1 {
+                tokio::time::sleep(Duration::from_secs(1 << (attempt - 2).min(6))).await;
+            }
+
+            let timestamp = unix_time().to_string();
+            let mut request = self
+                .client
+                .post(url.clone())
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .header("x-kreuzberg-job-id", id)
+                .header("x-kreuzberg-timestamp", &timestamp);
+            if let Some(secret) = &self.config.signing_key {
+                request = request.header("x-kreuzberg-signature", signature(secret, &timestamp, &body));
+            }
+            let error = match request
+                .body(body.clone())
+                .send()
+                .await
+                .and_then(reqwest::Response::error_for_status)
+            {
+                Ok(_) => None,
+                Err(e) => Some(e.to_string()),
+            };
+
+            let status = match &error {
+                None => CallbackStatus::Delivered,
+                Some(_) if attempt == attempts => CallbackStatus::Failed,
+                Some(_) => CallbackStatus::Pending,
+            };
+            if let Some(e) = &error {
+                tracing::warn!("Callback attempt {attempt}/{attempts} of job {id} failed: {e}");
+            }
+            self.update(id, |stored| {
+                if let Some(callback) = &mut stored.job.callback {
+                    callback.status = status;
+                    callback.attempts = attempt;
+                    callback.error = error;
+                }
+            });
+            if status != CallbackStatus::Pending {
+                return;
+            }
+        }
+    }
+}
+
+/// `X-Kreuzberg-Signature` of a callback: the HMAC-SHA256 of `<timestamp>.<body>`.
+fn signature(secret: &str, timestamp: &str, body: &[u8]) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_matches_reference_hmac() {
+        // HMAC-SHA256("secret", "1700000000.{}"), computed with `openssl dgst -sha256 -hmac secret`.
+        assert_eq!(
+            signature("secret", "1700000000", b"{}"),
+            "sha256=b8569b78799ff9e3cbff0fc2d63a33a2b57f3282abd07c37ae5e8e7d79a5f163"
+        );
+    }
+
+    #[test]
+    fn test_check_callback_url() {
+        let jobs = Jobs::new(JobsConfig {
+            callback_hosts: vec!["hooks.example.com".to_string()],
+            ..Default::default()
+        });
+        assert!(jobs.check_callback_url("https://hooks.example.com/kreuzberg").is_ok());
+        assert!(jobs.check_callback_url("https://other.example.com/kreuzberg").is_err());
+        assert!(jobs.check_callback_url("file:///etc/passwd").is_err());
+        assert!(jobs.check_callback_url("not a url").is_err());
+
+        let jobs = Jobs::new(JobsConfig::default());
+        assert!(jobs.check_callback_url("http://localhost:9000/done").is_ok());
+    }
+}
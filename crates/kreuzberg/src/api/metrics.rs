@@ -0,0 +1,113 @@
+//! Request metrics of the API server, served at `GET /metrics` in the Prometheus text format.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::Duration;
+
+use axum::http::StatusCode;
+use parking_lot::Mutex;
+
+/// Request counts and durations by tenant, route and status.
+#[derive(Default)]
+pub(super) struct ApiMetrics {
+    requests: Mutex<BTreeMap<(String, String, u16), RequestStats>>,
+    rejections: Mutex<BTreeMap<(String, &'static str), u64>>,
+}
+
+#[derive(Default)]
+struct RequestStats {
+    count: u64,
+    duration_seconds: f64,
+}
+
+impl ApiMetrics {
+    pub(super) fn record_request(&self, tenant: &str, route: &str, status: StatusCode, duration: Duration) {
+        let mut requests = self.requests.lock();
+        let stats = requests
+            .entry((tenant.to_string(), route.to_string(), status.as_u16()))
+            .or_default();
+        stats.count += 1;
+        stats.duration_seconds += duration.as_secs_f64();
+    }
+
+    /// Count a request rejected before it was handled, for an unknown tenant when `tenant` is empty.
+    pub(super) fn record_rejection(&self, tenant: &str, reason: &'static str) {
+        *self.rejections.lock().entry((tenant.to_string(), reason)).or_default() += 1;
+    }
+
+    pub(super) fn render(&self) -> String {
+        let requests = self.requests.lock();
+        let mut out = String::new();
+
+        out.push_str("# HELP kreuzberg_requests_total Requests handled, by tenant, route and status.\n");
+        out.push_str("# TYPE kreuzberg_requests_total counter\n");
+        for ((tenant, route, status), stats) in requests.iter() {
+            let _ = writeln!(
+                out,
+                "kreuzberg_requests_total{{tenant=\"{}\",route=\"{}\",status=\"{status}\"}} {}",
+                escape(tenant),
+                escape(route),
+                stats.count
+            );
+        }
+
+        let mut durations: BTreeMap<(&str, &str), (f64, u64)> = BTreeMap::new();
+        for ((tenant, route, _), stats) in requests.iter() {
+            let entry = durations.entry((tenant, route)).or_default();
+            entry.0 += stats.duration_seconds;
+            entry.1 += stats.count;
+        }
+        out.push_str("# HELP kreuzberg_request_duration_seconds Time spent handling requests, by tenant and route.\n");
+        out.push_str("# TYPE kreuzberg_request_duration_seconds summary\n");
+        for ((tenant, route), (sum, count)) in &durations {
+            let labels = format!("tenant=\"{}\",route=\"{}\"", escape(tenant), escape(route));
+            let _ = writeln!(out, "kreuzberg_request_duration_seconds_sum{{{labels}}} {sum}");
+            let _ = writeln!(out, "kreuzberg_request_duration_seconds_count{{{labels}}} {count}");
+        }
+        drop(requests);
+
+        out.push_str("# HELP kreuzberg_rejected_requests_total Requests rejected by authentication or quotas.\n");
+        out.push_str("# TYPE kreuzberg_rejected_requests_total counter\n");
+        for ((tenant, reason), count) in self.rejections.lock().iter() {
+            let _ = writeln!(
+                out,
+                "kreuzberg_rejected_requests_total{{tenant=\"{}\",reason=\"{reason}\"}} {count}",
+                escape(tenant)
+            );
+        }
+
+        out
+    }
+}
+
+/// Escape a Prometheus label value.
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_groups_by_tenant_and_route() {
+        let metrics = ApiMetrics::default();
+        metrics.record_request("team-a", "/extract", StatusCode::OK, Duration::from_millis(250));
+        metrics.record_request(
+            "team-a",
+            "/extract",
+            StatusCode::BAD_REQUEST,
+            Duration::from_millis(250),
+        );
+        metrics.record_request("team-\"b\"", "/extract", StatusCode::OK, Duration::from_secs(1));
+        metrics.record_rejection("team-a", "quota");
+
+        let text = metrics.render();
+        assert!(text.contains("kreuzberg_requests_total{tenant=\"team-a\",route=\"/extract\",status=\"200\"} 1\n"));
+        assert!(text.contains("kreuzberg_requests_total{tenant=\"team-a\",route=\"/extract\",status=\"400\"} 1\n"));
+        assert!(text.contains("kreuzberg_request_duration_seconds_sum{tenant=\"team-a\",route=\"/extract\"} 0.5\n"));
+        assert!(text.contains("kreuzberg_request_duration_seconds_count{tenant=\"team-a\",route=\"/extract\"} 2\n"));
+        assert!(text.contains("tenant=\"team-\\\"b\\\"\""));
+        assert!(text.contains("kreuzberg_rejected_requests_total{tenant=\"team-a\",reason=\"quota\"} 1\n"));
+    }
+}
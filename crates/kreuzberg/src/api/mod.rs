@@ -6,12 +6,21 @@
 //! # Endpoints
 //!
 //! - `POST /extract` - Extract text from uploaded files (multipart form data)
+//! - `POST /jobs` - Extract uploaded files in the background, with an optional result callback
+//! - `GET /jobs/{id}` - Status and results of a job
 //! - `POST /embed` - Generate embeddings for text (JSON body with texts array)
 //! - `POST /chunk` - Chunk text into smaller pieces (JSON body with text and config)
 //! - `GET /health` - Health check endpoint
 //! - `GET /info` - Server information
 //! - `GET /cache/stats` - Get cache statistics
 //! - `DELETE /cache/clear` - Clear all cached files
+//! - `GET /metrics` - Request metrics by tenant (Prometheus text format)
+//!
+//! With tenants configured in [`ServerConfig`](crate::core::ServerConfig), requests need
+//! the API key of a tenant and use the tenant's extraction configuration and quotas.
+//!
+//! With the `grpc` feature, the same address also serves the `kreuzberg.v1.Extraction`
+//! gRPC service (see [`grpc`]).
 //!
 //! # Examples
 //!
@@ -61,6 +70,10 @@
 //!      -F 'config={"ocr":{"language":"eng"}}' \
 //!      http://localhost:8000/extract
 //!
+//! # Async job with a result callback
+//! curl -F "files=@document.pdf" -F "callback_url=https://example.com/hooks/kreuzberg" \
+//!      http://localhost:8000/jobs
+//!
 //! # Health check
 //! curl http://localhost:8000/health
 //!
@@ -86,11 +99,17 @@
 
 mod config;
 mod error;
+#[cfg(feature = "grpc")]
+pub mod grpc;
 mod handlers;
+mod jobs;
+mod metrics;
 #[cfg(feature = "api")]
 pub mod openapi;
 mod router;
 mod startup;
+mod tenants;
+mod trace;
 mod types;
 
 pub use config::load_server_config;
@@ -98,6 +117,7 @@ pub use error::ApiError;
 pub use router::{create_router, create_router_with_limits, create_router_with_limits_and_server_config};
 pub use startup::{serve, serve_default, serve_with_config, serve_with_config_and_limits, serve_with_server_config};
 pub use types::{
-    ApiSizeLimits, ApiState, CacheClearResponse, CacheStatsResponse, ChunkRequest, ChunkResponse, EmbedRequest,
-    EmbedResponse, ErrorResponse, ExtractResponse, HealthResponse, InfoResponse,
+    ApiSizeLimits, ApiState, CacheClearResponse, CacheStatsResponse, CallbackStatus, ChunkRequest, ChunkResponse,
+    EmbedRequest, EmbedResponse, ErrorResponse, ExtractResponse, HealthResponse, InfoResponse, JobCallback,
+    JobResponse, JobStatus,
 };
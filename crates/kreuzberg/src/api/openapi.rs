@@ -32,7 +32,10 @@ use utoipa::OpenApi;
     paths(
         crate::api::handlers::health_handler,
         crate::api::handlers::info_handler,
+        crate::api::handlers::ready_handler,
         crate::api::handlers::extract_handler,
+        crate::api::handlers::create_job_handler,
+        crate::api::handlers::job_handler,
         crate::api::handlers::cache_stats_handler,
         crate::api::handlers::cache_clear_handler,
         crate::api::handlers::embed_handler,
@@ -43,6 +46,10 @@ use utoipa::OpenApi;
             crate::api::types::HealthResponse,
             crate::api::types::PluginStatus,
             crate::api::types::InfoResponse,
+            crate::capabilities::Capabilities,
+            crate::capabilities::RuntimeDependency,
+            crate::self_test::SelfTestReport,
+            crate::self_test::ComponentCheck,
             crate::api::types::ErrorResponse,
             crate::api::types::CacheStatsResponse,
             crate::api::types::CacheClearResponse,
@@ -53,6 +60,10 @@ use utoipa::OpenApi;
             crate::api::types::ChunkItem,
             crate::api::types::ChunkingConfigRequest,
             crate::api::types::ChunkingConfigResponse,
+            crate::api::types::JobResponse,
+            crate::api::types::JobStatus,
+            crate::api::types::JobCallback,
+            crate::api::types::CallbackStatus,
             crate::types::extraction::ExtractionResult,
             crate::types::extraction::Chunk,
             crate::types::extraction::ChunkMetadata,
@@ -131,6 +142,7 @@ mod tests {
         // Health endpoints
         assert!(schema.contains("/health"));
         assert!(schema.contains("/info"));
+        assert!(schema.contains("/ready"));
         // Extraction
         assert!(schema.contains("/extract"));
         // Cache
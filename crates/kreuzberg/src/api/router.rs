@@ -3,8 +3,9 @@
 use std::sync::Arc;
 
 use axum::{
-    Router,
+    Extension, Router,
     extract::DefaultBodyLimit,
+    middleware,
     routing::{delete, get, post},
 };
 use tower_http::{
@@ -17,9 +18,12 @@ use crate::{ExtractionConfig, core::ServerConfig};
 
 use super::{
     handlers::{
-        cache_clear_handler, cache_stats_handler, chunk_handler, embed_handler, extract_handler, health_handler,
-        info_handler,
+        cache_clear_handler, cache_stats_handler, chunk_handler, create_job_handler, embed_handler, extract_handler,
+        health_handler, info_handler, job_handler, ready_handler,
     },
+    jobs::Jobs,
+    tenants::{Access, access_middleware},
+    trace::request_span,
     types::{ApiSizeLimits, ApiState},
 };
 
@@ -94,7 +98,7 @@ pub fn create_router_with_limits(config: ExtractionConfig, limits: ApiSizeLimits
 ///
 /// * `config` - Default extraction configuration. Per-request configs override these defaults.
 /// * `limits` - Size limits for request bodies and multipart uploads.
-/// * `server_config` - Server configuration including host, port, and CORS settings.
+/// * `server_config` - Server configuration including host, port, CORS settings and tenants.
 ///
 /// # Examples
 ///
@@ -122,6 +126,10 @@ pub fn create_router_with_limits_and_server_config(
     let state = ApiState {
         default_config: Arc::new(config),
     };
+    let access = Arc::new(Access::new(&server_config, &state.default_config));
+    let jobs = Arc::new(Jobs::new(server_config.jobs.clone()));
+    #[cfg(feature = "grpc")]
+    let grpc_routes = super::grpc::routes(Arc::clone(&state.default_config), limits);
 
     // CORS configuration based on ServerConfig
     let cors_layer = if server_config.cors_allows_all() {
@@ -155,12 +163,22 @@ pub fn create_router_with_limits_and_server_config(
 
     let mut router = Router::new()
         .route("/extract", post(extract_handler))
+        .route("/jobs", post(create_job_handler))
+        .route("/jobs/{id}", get(job_handler))
         .route("/embed", post(embed_handler))
         .route("/chunk", post(chunk_handler))
         .route("/health", get(health_handler))
         .route("/info", get(info_handler))
+        .route("/ready", get(ready_handler))
         .route("/cache/stats", get(cache_stats_handler))
-        .route("/cache/clear", delete(cache_clear_handler));
+        .route("/cache/clear", delete(cache_clear_handler))
+        .route(
+            "/metrics",
+            get({
+                let access = Arc::clone(&access);
+                move || async move { access.metrics.render() }
+            }),
+        );
 
     // Add OpenAPI schema endpoint if API feature is enabled
     #[cfg(feature = "api")]
@@ -168,12 +186,24 @@ pub fn create_router_with_limits_and_server_config(
         router = router.route("/openapi.json", get(openapi_schema_handler));
     }
 
-    router
+    let router = router
+        .route_layer(middleware::from_fn_with_state(Arc::clone(&access), access_middleware))
+        .layer(Extension(jobs))
         .layer(DefaultBodyLimit::max(limits.max_request_body_bytes))
         .layer(RequestBodyLimitLayer::new(limits.max_request_body_bytes))
         .layer(cors_layer)
-        .layer(TraceLayer::new_for_http())
-        .with_state(state)
+        .layer(TraceLayer::new_for_http().make_span_with(request_span))
+        .with_state(state);
+
+    // gRPC uploads are size-limited by the service itself, while it reads the stream.
+    #[cfg(feature = "grpc")]
+    let router = router.merge(
+        grpc_routes
+            .route_layer(middleware::from_fn_with_state(access, access_middleware))
+            .layer(TraceLayer::new_for_grpc().make_span_with(request_span)),
+    );
+
+    router
 }
 
 /// OpenAPI schema handler.
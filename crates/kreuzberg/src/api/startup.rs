@@ -220,6 +220,7 @@ pub async fn serve_with_server_config(extraction_config: ExtractionConfig, serve
         .host
         .parse()
         .map_err(|e| crate::error::KreuzbergError::validation(format!("Invalid host address: {}", e)))?;
+    server_config.validate()?;
 
     let limits = ApiSizeLimits::new(
         server_config.max_request_body_bytes,
@@ -0,0 +1,273 @@
+//! API key authentication, per-key quotas and request metrics of the API server.
+//!
+//! When [`ServerConfig::tenants`] is not empty, every route except `/health` and
+//! `/metrics` requires the API key of a tenant. The tenant of a request selects its
+//! default extraction configuration and labels its metrics and trace span.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::{MatchedPath, Request, State};
+use axum::http::{HeaderMap, HeaderValue, StatusCode, header};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+
+use crate::ExtractionConfig;
+use crate::core::{ApiQuota, ServerConfig};
+
+use super::error::ApiError;
+use super::metrics::ApiMetrics;
+use super::types::ErrorResponse;
+
+/// Tenant label of requests made without an API key.
+const DEFAULT_TENANT: &str = "default";
+
+/// Routes served without an API key.
+const PUBLIC_ROUTES: &[&str] = &["/health", "/ready", "/metrics"];
+
+/// Tenant of an authenticated request, stored in the request extensions.
+pub(super) struct Tenant {
+    pub(super) name: String,
+    pub(super) config: Arc<ExtractionConfig>,
+}
+
+/// Authentication, quotas and metrics shared by all routes of a router.
+pub(super) struct Access {
+    /// API keys by their SHA-256 hash; empty when authentication is disabled
+    keys: HashMap<[u8; 32], ApiKey>,
+    pub(super) metrics: ApiMetrics,
+}
+
+struct ApiKey {
+    tenant: Arc<Tenant>,
+    quota: Arc<KeyQuota>,
+}
+
+impl Access {
+    pub(super) fn new(server_config: &ServerConfig, default_config: &Arc<ExtractionConfig>) -> Self {
+        let mut keys = HashMap::new();
+        for tenant_config in &server_config.tenants {
+            let tenant = Arc::new(Tenant {
+                name: tenant_config.name.clone(),
+                config: tenant_config
+                    .config
+                    .clone()
+                    .map_or_else(|| Arc::clone(default_config), Arc::new),
+            });
+            for key in &tenant_config.api_keys {
+                keys.entry(hash_key(key)).or_insert_with(|| ApiKey {
+                    tenant: Arc::clone(&tenant),
+                    quota: Arc::new(KeyQuota::new(tenant_config.quota)),
+                });
+            }
+        }
+
+        Self {
+            keys,
+            metrics: ApiMetrics::default(),
+        }
+    }
+}
+
+/// Authenticate the request, enforce the quotas of its API key and record its metrics.
+pub(super) async fn access_middleware(State(access): State<Arc<Access>>, mut request: Request, next: Next) -> Response {
+    let started = Instant::now();
+    let route = request
+        .extensions()
+        .get::<MatchedPath>()
+        .map_or_else(|| request.uri().path().to_string(), |path| path.as_str().to_string());
+
+    let mut permit = None;
+    let tenant = if access.keys.is_empty() || PUBLIC_ROUTES.contains(&route.as_str()) {
+        DEFAULT_TENANT.to_string()
+    } else {
+        let grpc = is_grpc(request.headers());
+        let Some(key) = api_key(request.headers()).and_then(|key| access.keys.get(&hash_key(key))) else {
+            access.metrics.record_rejection("", "unauthenticated");
+            return reject(grpc, StatusCode::UNAUTHORIZED, "Missing or invalid API key", None);
+        };
+        match key.quota.acquire() {
+            Ok(acquired) => permit = Some(acquired),
+            Err(retry_after) => {
+                access.metrics.record_rejection(&key.tenant.name, "quota");
+                return reject(
+                    grpc,
+                    StatusCode::TOO_MANY_REQUESTS,
+                    &format!("Quota of tenant '{}' exceeded", key.tenant.name),
+                    Some(retry_after),
+                );
+            }
+        }
+        request.extensions_mut().insert(Arc::clone(&key.tenant));
+        key.tenant.name.clone()
+    };
+    tracing::Span::current().record("tenant", tenant.as_str());
+
+    let response = next.run(request).await;
+    drop(permit);
+    access
+        .metrics
+        .record_request(&tenant, &route, response.status(), started.elapsed());
+    response
+}
+
+/// The API key of `X-API-Key` or an `Authorization: Bearer` header.
+fn api_key(headers: &HeaderMap) -> Option<&str> {
+    if let Some(key) = headers.get("x-api-key") {
+        return key.to_str().ok();
+    }
+    let authorization = headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, key) = authorization.split_once(' ')?;
+    scheme.eq_ignore_ascii_case("bearer").then(|| key.trim())
+}
+
+fn hash_key(key: &str) -> [u8; 32] {
+    Sha256::digest(key.as_bytes()).into()
+}
+
+fn is_grpc(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("application/grpc"))
+}
+
+/// Error response for a rejected request, as a gRPC status for gRPC requests.
+fn reject(grpc: bool, status: StatusCode, message: &str, retry_after: Option<Duration>) -> Response {
+    #[cfg(feature = "grpc")]
+    if grpc {
+        let status = if status == StatusCode::UNAUTHORIZED {
+            tonic::Status::unauthenticated(message)
+        } else {
+            tonic::Status::resource_exhausted(message)
+        };
+        return status.into_http::<axum::body::Body>();
+    }
+    #[cfg(not(feature = "grpc"))]
+    let _ = grpc;
+
+    let error_type = if status == StatusCode::UNAUTHORIZED {
+        "AuthenticationError"
+    } else {
+        "QuotaExceededError"
+    };
+    let mut response = ApiError {
+        status,
+        body: ErrorResponse {
+            error_type: error_type.to_string(),
+            message: message.to_string(),
+            traceback: None,
+            status_code: status.as_u16(),
+        },
+    }
+    .into_response();
+    if let Some(retry_after) = retry_after {
+        let seconds = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+        response
+            .headers_mut()
+            .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+    }
+    response
+}
+
+/// Quota state of an API key: a token bucket for the request rate and an in-flight count.
+struct KeyQuota {
+    quota: ApiQuota,
+    state: Mutex<QuotaState>,
+}
+
+struct QuotaState {
+    tokens: f64,
+    updated: Instant,
+    in_flight: u32,
+}
+
+/// Releases the concurrency slot of a request when dropped.
+struct QuotaPermit(Arc<KeyQuota>);
+
+impl Drop for QuotaPermit {
+    fn drop(&mut self) {
+        self.0.state.lock().in_flight -= 1;
+    }
+}
+
+impl KeyQuota {
+    fn new(quota: ApiQuota) -> Self {
+        Self {
+            quota,
+            state: Mutex::new(QuotaState {
+                tokens: quota.requests_per_minute.map_or(0.0, f64::from),
+                updated: Instant::now(),
+                in_flight: 0,
+            }),
+        }
+    }
+
+    /// Take a request slot, or return how long to wait before retrying.
+    fn acquire(self: &Arc<Self>) -> std::result::Result<QuotaPermit, Duration> {
+        let mut state = self.state.lock();
+
+        if let Some(max) = self.quota.max_concurrent_requests
+            && state.in_flight >= max
+        {
+            return Err(Duration::from_secs(1));
+        }
+
+        if let Some(per_minute) = self.quota.requests_per_minute {
+            let capacity = f64::from(per_minute);
+            let per_second = capacity / 60.0;
+            let now = Instant::now();
+            let elapsed = now.saturating_duration_since(state.updated).as_secs_f64();
+            state.tokens = (state.tokens + elapsed * per_second).min(capacity);
+            state.updated = now;
+            if state.tokens < 1.0 {
+                return Err(Duration::from_secs_f64((1.0 - state.tokens) / per_second));
+            }
+            state.tokens -= 1.0;
+        }
+
+        state.in_flight += 1;
+        Ok(QuotaPermit(Arc::clone(self)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_key_from_headers() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(api_key(&headers), None);
+
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Bearer secret"));
+        assert_eq!(api_key(&headers), Some("secret"));
+
+        headers.insert("x-api-key", HeaderValue::from_static("other"));
+        assert_eq!(api_key(&headers), Some("other"));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::AUTHORIZATION, HeaderValue::from_static("Basic dXNlcg=="));
+        assert_eq!(api_key(&headers), None);
+    }
+
+    #[test]
+    fn test_quota_limits_rate_and_concurrency() {
+        let quota = Arc::new(KeyQuota::new(ApiQuota {
+            requests_per_minute: Some(2),
+            max_concurrent_requests: Some(1),
+        }));
+
+        let first = quota.acquire().expect("first request is allowed");
+        assert_eq!(quota.acquire().err(), Some(Duration::from_secs(1)));
+        drop(first);
+
+        let second = quota.acquire().expect("second request is allowed");
+        drop(second);
+        let retry_after = quota.acquire().err().expect("rate limit is reached");
+        assert!(retry_after > Duration::from_secs(25) && retry_after <= Duration::from_secs(30));
+    }
+}
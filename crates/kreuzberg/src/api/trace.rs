@@ -0,0 +1,71 @@
+//! Request spans of the API server.
+
+use axum::http::Request;
+use tracing::Span;
+
+/// Span of an HTTP or gRPC request, the parent of the extraction spans.
+///
+/// With the `otel` feature, the span continues the trace of the W3C `traceparent`
+/// header of the request, if present.
+pub(super) fn request_span<B>(request: &Request<B>) -> Span {
+    let span = tracing::info_span!(
+        "request",
+        method = %request.method(),
+        uri = %request.uri(),
+        version = ?request.version(),
+        otel.kind = "server",
+        tenant = tracing::field::Empty,
+    );
+
+    #[cfg(feature = "otel")]
+    {
+        use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+        let headers = request
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| Some((name.as_str(), value.to_str().ok()?)));
+        let parent = crate::telemetry::parent_context(headers);
+        if let Err(e) = span.set_parent(parent) {
+            tracing::debug!("Failed to continue the request trace: {e}");
+        }
+    }
+
+    span
+}
+
+#[cfg(all(test, feature = "otel"))]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{TraceContextExt, TracerProvider as _};
+    use opentelemetry_sdk::trace::SdkTracerProvider;
+    use tracing_opentelemetry::OpenTelemetrySpanExt;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn test_request_span_continues_traceparent() {
+        let provider = SdkTracerProvider::builder().build();
+        let subscriber =
+            tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+
+        tracing::subscriber::with_default(subscriber, || {
+            let request = Request::builder()
+                .uri("/extract")
+                .header("traceparent", "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01")
+                .body(())
+                .unwrap();
+            let context = request_span(&request).context();
+            assert_eq!(
+                context.span().span_context().trace_id().to_string(),
+                "4bf92f3577b34da6a3ce929d0e0e4736"
+            );
+
+            let request = Request::builder().uri("/extract").body(()).unwrap();
+            let context = request_span(&request).context();
+            assert_ne!(
+                context.span().span_context().trace_id().to_string(),
+                "4bf92f3577b34da6a3ce929d0e0e4736"
+            );
+        });
+    }
+}
@@ -147,6 +147,8 @@ pub struct InfoResponse {
     pub version: String,
     /// Whether using Rust backend
     pub rust_backend: bool,
+    /// Compiled features and runtime dependencies found
+    pub capabilities: crate::Capabilities,
 }
 
 /// Extraction response (list of results).
@@ -328,3 +330,72 @@ pub struct ChunkingConfigResponse {
     #[cfg_attr(feature = "api", schema(example = "text"))]
     pub chunker_type: String,
 }
+
+/// Status of an async extraction job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    /// Waiting to start
+    Pending,
+    /// Extraction in progress
+    Running,
+    /// Extraction finished, `results` holds one result per file
+    Succeeded,
+    /// Extraction failed, `error` holds the reason
+    Failed,
+}
+
+/// Delivery status of a job's result callback.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum CallbackStatus {
+    /// Not delivered yet
+    Pending,
+    /// Accepted by the callback URL with a 2xx response
+    Delivered,
+    /// All delivery attempts failed
+    Failed,
+}
+
+/// Result callback of an async extraction job.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct JobCallback {
+    /// URL the finished job is POSTed to
+    #[cfg_attr(feature = "api", schema(example = "https://example.com/hooks/kreuzberg"))]
+    pub url: String,
+    /// Delivery status
+    pub status: CallbackStatus,
+    /// Delivery attempts made so far
+    pub attempts: u32,
+    /// Error of the last failed attempt
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Async extraction job, returned by `POST /jobs` and `GET /jobs/{id}` and sent to callbacks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct JobResponse {
+    /// Job identifier
+    #[cfg_attr(feature = "api", schema(example = "0b8e8f1c-5d4e-4c1a-9a57-2f1f3b0c6d2e"))]
+    pub id: String,
+    /// Job status
+    pub status: JobStatus,
+    /// Creation time (Unix timestamp in seconds)
+    pub created_at: u64,
+    /// Completion time (Unix timestamp in seconds)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<u64>,
+    /// Extraction results, one per file, when the job succeeded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub results: Option<ExtractResponse>,
+    /// Error, when the job failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<ErrorResponse>,
+    /// Result callback, when the job was created with a `callback_url`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callback: Option<JobCallback>,
+}
@@ -0,0 +1,337 @@
+//! Audit log of plugin registrations and configuration changes.
+//!
+//! Every registration, unregistration and clear of a plugin registry is recorded as an
+//! [`AuditEvent`], as is every configuration reload reported with [`record_config_reload`].
+//! Events carry the [`AuditContext`] the caller set with [`with_context`], saying who made
+//! the change and why, and are kept in memory, the latest [`AUDIT_LOG_CAPACITY`] of them,
+//! to be queried with [`events`]. Each event is also logged at info level with the
+//! `kreuzberg::audit` tracing target, for a permanent record.
+//!
+//! # Example
+//!
+//! ```rust
+//! use kreuzberg::ExtractionConfig;
+//! use kreuzberg::audit::{self, AuditAction, AuditContext};
+//!
+//! let previous = ExtractionConfig::default();
+//! let config = ExtractionConfig {
+//!     use_cache: false,
+//!     ..Default::default()
+//! };
+//! audit::with_context(AuditContext::new("deploy-bot").with_reason("CHG-1042"), || {
+//!     audit::record_config_reload("kreuzberg.toml", Some(&previous), &config)
+//! })?;
+//!
+//! let event = audit::events()
+//!     .into_iter()
+//!     .rev()
+//!     .find(|event| event.action == AuditAction::ConfigReload)
+//!     .unwrap();
+//! assert_eq!(event.actor.as_deref(), Some("deploy-bot"));
+//! assert_eq!(event.details, ["use_cache"]);
+//! # Ok::<(), kreuzberg::KreuzbergError>(())
+//! ```
+
+use crate::{ExtractionConfig, Result};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::collections::{BTreeSet, VecDeque};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Number of events kept in memory; older events are dropped.
+pub const AUDIT_LOG_CAPACITY: usize = 10_000;
+
+static LOG: Lazy<Mutex<AuditLog>> = Lazy::new(|| Mutex::new(AuditLog::default()));
+
+thread_local! {
+    static CONTEXT: RefCell<Option<AuditContext>> = const { RefCell::new(None) };
+}
+
+/// Who makes a change, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditContext {
+    /// User, service or process making the change
+    pub actor: String,
+    /// Reason for the change, such as a change ticket
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl AuditContext {
+    /// A context for changes made by `actor`.
+    pub fn new(actor: impl Into<String>) -> Self {
+        Self {
+            actor: actor.into(),
+            reason: None,
+        }
+    }
+
+    /// Set the reason for the changes.
+    pub fn with_reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+}
+
+/// What changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    /// A plugin was registered, or replaced a plugin of the same name
+    Register,
+    /// A plugin was unregistered
+    Unregister,
+    /// All plugins of a registry were removed
+    Clear,
+    /// A configuration was reloaded
+    ConfigReload,
+}
+
+/// A recorded change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    /// Position of the event in the log, starting at 1
+    pub sequence: u64,
+    /// Unix time in milliseconds of the change
+    pub timestamp_ms: u64,
+    /// What changed
+    pub action: AuditAction,
+    /// Registry of the plugin (`extractor`, `ocr_backend`, `post_processor`, ...), or
+    /// `config` for configuration reloads
+    pub target: String,
+    /// Plugin name for registrations and unregistrations, source of the configuration
+    /// for reloads, None for clears
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Version of a registered plugin, SHA-256 hash of a reloaded configuration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Plugins removed by a clear, top-level configuration fields changed by a reload
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+    /// Actor of the [`AuditContext`] of the change, None without a context
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub actor: Option<String>,
+    /// Reason of the [`AuditContext`] of the change
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Error of a change that failed, such as a plugin whose `initialize()` failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+#[derive(Default)]
+struct AuditLog {
+    events: VecDeque<AuditEvent>,
+    last_sequence: u64,
+}
+
+/// Run `f` with `context` as the audit context of the changes it makes on this thread.
+///
+/// Contexts nest: the innermost one applies, and the outer one is restored when `f`
+/// returns or panics.
+pub fn with_context<R>(context: AuditContext, f: impl FnOnce() -> R) -> R {
+    struct Restore(Option<AuditContext>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            CONTEXT.with(|context| *context.borrow_mut() = previous);
+        }
+    }
+
+    let _restore = Restore(CONTEXT.with(|current| current.borrow_mut().replace(context)));
+    f()
+}
+
+/// All events in the log, oldest first.
+pub fn events() -> Vec<AuditEvent> {
+    LOG.lock().events.iter().cloned().collect()
+}
+
+/// The events recorded after the event numbered `sequence`, oldest first.
+pub fn events_since(sequence: u64) -> Vec<AuditEvent> {
+    LOG.lock()
+        .events
+        .iter()
+        .filter(|event| event.sequence > sequence)
+        .cloned()
+        .collect()
+}
+
+/// Record that `config` was loaded from `source` in place of `previous`.
+///
+/// Kreuzberg does not reload configurations itself: services that do, for example
+/// on SIGHUP, call this after each reload. The event records the SHA-256 hash of
+/// `config`, as in provenance manifests, and the top-level fields that differ from
+/// `previous`.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Serialization` if a configuration cannot be serialized.
+pub fn record_config_reload(
+    source: &str,
+    previous: Option<&ExtractionConfig>,
+    config: &ExtractionConfig,
+) -> Result<()> {
+    // Through `Value`, so maps are hashed with sorted keys.
+    let value = serde_json::to_value(config)?;
+    let sha256 = hex::encode(Sha256::digest(serde_json::to_vec(&value)?));
+
+    let mut details = Vec::new();
+    let previous = previous.map(serde_json::to_value).transpose()?;
+    if let (Some(old), Some(new)) = (previous.as_ref().and_then(|p| p.as_object()), value.as_object()) {
+        let keys: BTreeSet<_> = old.keys().chain(new.keys()).collect();
+        details = keys
+            .into_iter()
+            .filter(|key| old.get(*key) != new.get(*key))
+            .cloned()
+            .collect();
+    }
+
+    record(
+        AuditAction::ConfigReload,
+        "config",
+        Some(source),
+        Some(sha256),
+        details,
+        None,
+    );
+    Ok(())
+}
+
+/// Record the registration of plugin `name` in the `target` registry, with its outcome.
+pub(crate) fn record_registration<T>(target: &str, name: &str, version: String, outcome: &Result<T>) {
+    let error = outcome.as_ref().err().map(ToString::to_string);
+    record(
+        AuditAction::Register,
+        target,
+        Some(name),
+        Some(version),
+        Vec::new(),
+        error,
+    );
+}
+
+/// Record the unregistration of plugin `name` from the `target` registry, with its outcome.
+pub(crate) fn record_unregistration<T>(target: &str, name: &str, outcome: &Result<T>) {
+    let error = outcome.as_ref().err().map(ToString::to_string);
+    record(AuditAction::Unregister, target, Some(name), None, Vec::new(), error);
+}
+
+/// Record the removal of the plugins `names` from the `target` registry, with its outcome.
+pub(crate) fn record_clear<T>(target: &str, names: Vec<String>, outcome: &Result<T>) {
+    let error = outcome.as_ref().err().map(ToString::to_string);
+    record(AuditAction::Clear, target, None, None, names, error);
+}
+
+fn record(
+    action: AuditAction,
+    target: &str,
+    name: Option<&str>,
+    version: Option<String>,
+    details: Vec<String>,
+    error: Option<String>,
+) {
+    let context = CONTEXT.with(|context| context.borrow().clone());
+    let (actor, reason) = context.map_or((None, None), |context| (Some(context.actor), context.reason));
+    let timestamp_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64);
+
+    let mut log = LOG.lock();
+    log.last_sequence += 1;
+    let event = AuditEvent {
+        sequence: log.last_sequence,
+        timestamp_ms,
+        action,
+        target: target.to_string(),
+        name: name.map(str::to_string),
+        version,
+        details,
+        actor,
+        reason,
+        error,
+    };
+    tracing::info!(
+        target: "kreuzberg::audit",
+        sequence = event.sequence,
+        action = ?event.action,
+        target_registry = %event.target,
+        name = event.name.as_deref(),
+        version = event.version.as_deref(),
+        details = ?event.details,
+        actor = event.actor.as_deref(),
+        reason = event.reason.as_deref(),
+        error = event.error.as_deref(),
+        "audit event"
+    );
+    if log.events.len() == AUDIT_LOG_CAPACITY {
+        log.events.pop_front();
+    }
+    log.events.push_back(event);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn own_events(actor: &str) -> Vec<AuditEvent> {
+        events()
+            .into_iter()
+            .filter(|event| event.actor.as_deref() == Some(actor))
+            .collect()
+    }
+
+    #[test]
+    fn test_with_context_nests() {
+        let outer = AuditContext::new("audit-test-outer");
+        with_context(outer, || {
+            record(AuditAction::Clear, "translator", None, None, Vec::new(), None);
+            with_context(AuditContext::new("audit-test-inner").with_reason("nested"), || {
+                record(AuditAction::Clear, "translator", None, None, Vec::new(), None);
+            });
+            record(AuditAction::Clear, "translator", None, None, Vec::new(), None);
+        });
+        record(AuditAction::Clear, "audit-test-none", None, None, Vec::new(), None);
+
+        assert_eq!(own_events("audit-test-outer").len(), 2);
+        let inner = own_events("audit-test-inner");
+        assert_eq!(inner.len(), 1);
+        assert_eq!(inner[0].reason.as_deref(), Some("nested"));
+        let none = events()
+            .into_iter()
+            .find(|event| event.target == "audit-test-none")
+            .unwrap();
+        assert_eq!(none.actor, None);
+        assert!(
+            events_since(none.sequence)
+                .iter()
+                .all(|event| event.sequence > none.sequence)
+        );
+    }
+
+    #[test]
+    fn test_record_config_reload() {
+        let previous = ExtractionConfig::default();
+        let config = ExtractionConfig {
+            use_cache: !previous.use_cache,
+            max_concurrent_extractions: Some(3),
+            ..Default::default()
+        };
+        with_context(AuditContext::new("audit-test-reload"), || {
+            record_config_reload("/etc/kreuzberg.toml", Some(&previous), &config)
+        })
+        .unwrap();
+
+        let events = own_events("audit-test-reload");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].action, AuditAction::ConfigReload);
+        assert_eq!(events[0].name.as_deref(), Some("/etc/kreuzberg.toml"));
+        assert_eq!(events[0].details, ["max_concurrent_extractions", "use_cache"]);
+        assert_eq!(events[0].version.as_ref().unwrap().len(), 64);
+    }
+}
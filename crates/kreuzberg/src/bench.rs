@@ -0,0 +1,467 @@
+//! Throughput benchmarks of extraction pipelines.
+//!
+//! [`run_profile`] extracts a corpus with the configuration of a [`BenchProfile`] and
+//! reports the throughput, the latency percentiles of whole files and of each pipeline
+//! stage, and the memory high-water mark of the process. Running several profiles
+//! over the same corpus compares OCR backends or configuration profiles.
+//!
+//! Stage latencies come from the pipeline stage spans of the `otel` feature, collected
+//! by a [`StageTimings`] layer that must be part of the global tracing subscriber.
+//! The `kreuzberg-bench` binary wraps this module.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use kreuzberg::ExtractionConfig;
+//! use kreuzberg::bench::{self, BenchOptions, BenchProfile, StageTimings};
+//! use tracing_subscriber::prelude::*;
+//!
+//! # async fn example() -> kreuzberg::Result<()> {
+//! let timings = StageTimings::new();
+//! tracing_subscriber::registry().with(timings.clone()).init();
+//!
+//! let corpus = bench::collect_corpus(&["test_documents/pdfs".into()])?;
+//! let profile = BenchProfile::new("default", ExtractionConfig::default());
+//! let report = bench::run_profile(&profile, &corpus, &BenchOptions::default(), &timings).await?;
+//! println!("{:.1} files/s, p99 {:.1} ms", report.files_per_sec, report.latency.p99_ms);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::config::ExtractionConfig;
+use crate::core::extractor::extract_file;
+use crate::{KreuzbergError, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+use tracing::Subscriber;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Span field naming the pipeline stage of a span.
+const STAGE_FIELD: &str = "pipeline.stage";
+
+/// A named extraction configuration to benchmark.
+#[derive(Debug, Clone)]
+pub struct BenchProfile {
+    pub name: String,
+    pub config: ExtractionConfig,
+}
+
+impl BenchProfile {
+    /// Profile `name` with `config`. The result cache is disabled, so every
+    /// iteration runs the whole pipeline.
+    pub fn new(name: impl Into<String>, mut config: ExtractionConfig) -> Self {
+        config.use_cache = false;
+        Self {
+            name: name.into(),
+            config,
+        }
+    }
+}
+
+/// How a profile is run over the corpus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BenchOptions {
+    /// Measured passes over the corpus. Default: 1
+    pub iterations: usize,
+    /// Passes over the corpus before measuring, to warm up models and pools. Default: 0
+    pub warmup: usize,
+    /// Files extracted at once. Default: 1
+    pub concurrency: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            iterations: 1,
+            warmup: 0,
+            concurrency: 1,
+        }
+    }
+}
+
+/// Latency distribution of a set of samples, in milliseconds.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct LatencySummary {
+    pub count: usize,
+    pub mean_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+}
+
+impl LatencySummary {
+    /// Summary of `samples`, with nearest-rank percentiles.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+        let ms = |duration: Duration| duration.as_secs_f64() * 1000.0;
+        let percentile = |p: f64| {
+            let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+            ms(sorted[rank.clamp(1, sorted.len()) - 1])
+        };
+        Self {
+            count: sorted.len(),
+            mean_ms: ms(sorted.iter().sum::<Duration>()) / sorted.len() as f64,
+            p50_ms: percentile(50.0),
+            p90_ms: percentile(90.0),
+            p99_ms: percentile(99.0),
+            max_ms: ms(sorted[sorted.len() - 1]),
+        }
+    }
+}
+
+/// Results of a profile over a corpus.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ProfileReport {
+    pub profile: String,
+    /// Files in the corpus
+    pub files: usize,
+    /// Failed extractions, over all iterations
+    pub failures: usize,
+    /// Bytes extracted, over all iterations
+    pub bytes: u64,
+    /// Wall time of the measured iterations
+    pub wall_secs: f64,
+    pub files_per_sec: f64,
+    pub mb_per_sec: f64,
+    /// Latency of whole files, failures included
+    pub latency: LatencySummary,
+    /// Latency of each pipeline stage that ran, by stage name
+    pub stages: BTreeMap<String, LatencySummary>,
+    /// Peak resident memory of the process during the measured iterations, when the
+    /// platform reports it
+    pub peak_rss_bytes: Option<u64>,
+}
+
+/// Files of `paths`, with directories walked recursively, in path order. Hidden files
+/// and directories are skipped.
+///
+/// # Errors
+///
+/// Returns an I/O error if a path cannot be read.
+pub fn collect_corpus(paths: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    fn walk(dir: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path
+                .file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with('.'))
+            {
+                continue;
+            }
+            if path.is_dir() {
+                walk(&path, files)?;
+            } else {
+                files.push(path);
+            }
+        }
+        Ok(())
+    }
+
+    let mut files = Vec::new();
+    for path in paths {
+        if path.is_dir() {
+            walk(path, &mut files)?;
+        } else {
+            std::fs::metadata(path)?;
+            files.push(path.clone());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Extract `corpus` with `profile` and report the measured iterations.
+///
+/// `timings` must be a layer of the global tracing subscriber for the report to have
+/// stage latencies; samples it collected before the call are discarded.
+///
+/// # Errors
+///
+/// Returns a validation error if the corpus is empty or an option is zero. Failed
+/// extractions are counted, not returned.
+pub async fn run_profile(
+    profile: &BenchProfile,
+    corpus: &[PathBuf],
+    options: &BenchOptions,
+    timings: &StageTimings,
+) -> Result<ProfileReport> {
+    if corpus.is_empty() {
+        return Err(KreuzbergError::validation("The benchmark corpus has no files"));
+    }
+    if options.iterations == 0 || options.concurrency == 0 {
+        return Err(KreuzbergError::validation(
+            "Benchmark iterations and concurrency must be greater than 0",
+        ));
+    }
+
+    let config = Arc::new(profile.config.clone());
+    for _ in 0..options.warmup {
+        run_pass(corpus, &config, options.concurrency).await?;
+    }
+
+    timings.take();
+    memory::reset_peak();
+    let start = Instant::now();
+    let mut samples = Vec::new();
+    for _ in 0..options.iterations {
+        samples.extend(run_pass(corpus, &config, options.concurrency).await?);
+    }
+    let wall_secs = start.elapsed().as_secs_f64();
+
+    let latencies: Vec<Duration> = samples.iter().map(|sample| sample.latency).collect();
+    let bytes = samples.iter().map(|sample| sample.bytes).sum::<u64>();
+    Ok(ProfileReport {
+        profile: profile.name.clone(),
+        files: corpus.len(),
+        failures: samples.iter().filter(|sample| !sample.succeeded).count(),
+        bytes,
+        wall_secs,
+        files_per_sec: samples.len() as f64 / wall_secs,
+        mb_per_sec: bytes as f64 / (1024.0 * 1024.0) / wall_secs,
+        latency: LatencySummary::from_samples(&latencies),
+        stages: timings
+            .take()
+            .iter()
+            .map(|(stage, samples)| (stage.clone(), LatencySummary::from_samples(samples)))
+            .collect(),
+        peak_rss_bytes: memory::peak(),
+    })
+}
+
+struct Sample {
+    latency: Duration,
+    bytes: u64,
+    succeeded: bool,
+}
+
+/// Extract every file of `corpus` once, `concurrency` at a time.
+async fn run_pass(corpus: &[PathBuf], config: &Arc<ExtractionConfig>, concurrency: usize) -> Result<Vec<Sample>> {
+    let semaphore = Arc::new(Semaphore::new(concurrency));
+    let mut tasks = JoinSet::new();
+    for path in corpus {
+        let path = path.clone();
+        let config = Arc::clone(config);
+        let semaphore = Arc::clone(&semaphore);
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire().await.expect("the semaphore is never closed");
+            let bytes = std::fs::metadata(&path).map_or(0, |metadata| metadata.len());
+            let start = Instant::now();
+            let outcome = extract_file(&path, None, &config).await;
+            if let Err(e) = &outcome {
+                tracing::debug!("Benchmark extraction of '{}' failed: {}", path.display(), e);
+            }
+            Sample {
+                latency: start.elapsed(),
+                bytes,
+                succeeded: outcome.is_ok(),
+            }
+        });
+    }
+
+    let mut samples = Vec::with_capacity(corpus.len());
+    while let Some(task) = tasks.join_next().await {
+        samples.push(task.map_err(|e| KreuzbergError::Other(format!("Task panicked: {e}")))?);
+    }
+    Ok(samples)
+}
+
+/// Tracing layer that collects the durations of the pipeline stage spans.
+///
+/// Clones share their samples, so the layer can be installed in a subscriber while a
+/// clone is passed to [`run_profile`].
+#[derive(Debug, Clone, Default)]
+pub struct StageTimings {
+    samples: Arc<Mutex<HashMap<String, Vec<Duration>>>>,
+}
+
+impl StageTimings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The samples collected so far, by stage, leaving the layer empty.
+    pub fn take(&self) -> HashMap<String, Vec<Duration>> {
+        std::mem::take(&mut *self.samples.lock())
+    }
+}
+
+/// Stage and creation time of an open stage span.
+struct StageSpan {
+    stage: String,
+    opened: Instant,
+}
+
+struct StageVisitor(Option<String>);
+
+impl Visit for StageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == STAGE_FIELD {
+            self.0 = Some(value.to_string());
+        }
+    }
+
+    fn record_debug(&mut self, _field: &Field, _value: &dyn std::fmt::Debug) {}
+}
+
+impl<S> Layer<S> for StageTimings
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let mut visitor = StageVisitor(None);
+        attrs.record(&mut visitor);
+        if let (Some(stage), Some(span)) = (visitor.0, ctx.span(id)) {
+            span.extensions_mut().insert(StageSpan {
+                stage,
+                opened: Instant::now(),
+            });
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else {
+            return;
+        };
+        if let Some(stage) = span.extensions_mut().remove::<StageSpan>() {
+            self.samples
+                .lock()
+                .entry(stage.stage)
+                .or_default()
+                .push(stage.opened.elapsed());
+        }
+    }
+}
+
+/// Peak resident memory of the process.
+mod memory {
+    /// Reset the peak to the current resident memory, where the platform allows it.
+    pub(super) fn reset_peak() {
+        #[cfg(target_os = "linux")]
+        {
+            // Writing 5 to clear_refs resets VmHWM (Linux 4.0+).
+            if let Err(e) = std::fs::write("/proc/self/clear_refs", "5") {
+                tracing::debug!("Failed to reset the memory high-water mark: {e}");
+            }
+        }
+    }
+
+    /// Peak resident memory in bytes since the last reset or the start of the process.
+    pub(super) fn peak() -> Option<u64> {
+        #[cfg(target_os = "linux")]
+        {
+            let status = std::fs::read_to_string("/proc/self/status").ok()?;
+            let kb = status
+                .lines()
+                .find_map(|line| line.strip_prefix("VmHWM:"))?
+                .trim()
+                .trim_end_matches("kB")
+                .trim()
+                .parse::<u64>()
+                .ok()?;
+            Some(kb * 1024)
+        }
+
+        #[cfg(all(unix, not(target_os = "linux")))]
+        {
+            let mut usage = std::mem::MaybeUninit::<libc::rusage>::uninit();
+            // SAFETY: getrusage fills `usage` when it returns 0.
+            if unsafe { libc::getrusage(libc::RUSAGE_SELF, usage.as_mut_ptr()) } != 0 {
+                return None;
+            }
+            // SAFETY: initialized by the successful call above.
+            let max_rss = unsafe { usage.assume_init() }.ru_maxrss as u64;
+            // ru_maxrss is in bytes on macOS and in kilobytes elsewhere.
+            Some(if cfg!(target_os = "macos") {
+                max_rss
+            } else {
+                max_rss * 1024
+            })
+        }
+
+        #[cfg(not(unix))]
+        {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+    use tracing_subscriber::prelude::*;
+
+    #[test]
+    fn test_latency_summary_percentiles() {
+        let samples: Vec<Duration> = (1..=100).rev().map(Duration::from_millis).collect();
+        let summary = LatencySummary::from_samples(&samples);
+        assert_eq!(summary.count, 100);
+        assert_eq!(summary.p50_ms, 50.0);
+        assert_eq!(summary.p90_ms, 90.0);
+        assert_eq!(summary.p99_ms, 99.0);
+        assert_eq!(summary.max_ms, 100.0);
+        assert!((summary.mean_ms - 50.5).abs() < 1e-9);
+
+        assert_eq!(LatencySummary::from_samples(&[]), LatencySummary::default());
+        assert_eq!(LatencySummary::from_samples(&[Duration::from_millis(7)]).p99_ms, 7.0);
+    }
+
+    #[test]
+    fn test_collect_corpus_walks_directories() {
+        let dir = tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("nested/.git")).unwrap();
+        std::fs::write(dir.path().join("b.txt"), "b").unwrap();
+        std::fs::write(dir.path().join("nested/a.txt"), "a").unwrap();
+        std::fs::write(dir.path().join("nested/.git/config"), "").unwrap();
+        std::fs::write(dir.path().join(".hidden"), "").unwrap();
+
+        let corpus = collect_corpus(&[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(corpus, vec![dir.path().join("b.txt"), dir.path().join("nested/a.txt")]);
+        assert!(collect_corpus(&[dir.path().join("missing.pdf")]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_run_profile_reports_stages() {
+        let timings = StageTimings::new();
+        let _guard = tracing_subscriber::registry().with(timings.clone()).set_default();
+
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("a.txt"), "Benchmark me").unwrap();
+        std::fs::write(dir.path().join("b.unknown-format"), "Not extractable").unwrap();
+        let corpus = collect_corpus(&[dir.path().to_path_buf()]).unwrap();
+
+        let profile = BenchProfile::new("default", ExtractionConfig::default());
+        assert!(!profile.config.use_cache);
+        let options = BenchOptions {
+            iterations: 2,
+            warmup: 1,
+            concurrency: 2,
+        };
+        let report = run_profile(&profile, &corpus, &options, &timings).await.unwrap();
+        assert_eq!(report.profile, "default");
+        assert_eq!(report.files, 2);
+        assert_eq!(report.failures, 2);
+        assert_eq!(report.latency.count, 4);
+        assert!(report.files_per_sec > 0.0);
+        assert_eq!(report.stages["extraction"].count, 2, "{:?}", report.stages);
+        #[cfg(target_os = "linux")]
+        assert!(report.peak_rss_bytes.is_some_and(|bytes| bytes > 0));
+
+        let empty = run_profile(&profile, &[], &options, &timings).await;
+        assert!(matches!(empty, Err(KreuzbergError::Validation { .. })));
+    }
+}
@@ -0,0 +1,144 @@
+//! Benchmark extraction pipelines over a corpus.
+//!
+//! Runs the files of a corpus through one or more configuration profiles and reports,
+//! per profile, the throughput, the latency percentiles of whole files and of each
+//! pipeline stage, and the memory high-water mark:
+//!
+//! ```text
+//! kreuzberg-bench test_documents/pdfs --profile tesseract=tesseract.toml --profile paddle=paddle.toml
+//! ```
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use clap::{Parser, ValueEnum};
+use kreuzberg::ExtractionConfig;
+use kreuzberg::bench::{self, BenchOptions, BenchProfile, LatencySummary, ProfileReport, StageTimings};
+use tracing_subscriber::prelude::*;
+
+#[derive(Parser)]
+#[command(
+    name = "kreuzberg-bench",
+    version,
+    about = "Benchmark Kreuzberg extraction pipelines"
+)]
+struct Args {
+    /// Files and directories of the corpus; directories are walked recursively
+    #[arg(required = true)]
+    corpus: Vec<PathBuf>,
+
+    /// Profile to benchmark, as NAME=CONFIG_FILE (TOML, YAML, or JSON). Repeat to
+    /// compare profiles. Default: the discovered kreuzberg.toml, or the default config
+    #[arg(short, long = "profile", value_name = "NAME=CONFIG_FILE")]
+    profiles: Vec<String>,
+
+    /// Measured passes over the corpus per profile
+    #[arg(short, long, default_value_t = 1)]
+    iterations: usize,
+
+    /// Unmeasured passes over the corpus per profile before measuring
+    #[arg(short, long, default_value_t = 0)]
+    warmup: usize,
+
+    /// Files extracted at once
+    #[arg(short, long, default_value_t = 1)]
+    concurrency: usize,
+
+    /// Report format
+    #[arg(short, long, value_enum, default_value = "text")]
+    format: Format,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Format {
+    Text,
+    Json,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    match run(args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("kreuzberg-bench: {e}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: Args) -> kreuzberg::Result<()> {
+    let timings = StageTimings::new();
+    tracing_subscriber::registry().with(timings.clone()).init();
+
+    let profiles = load_profiles(&args.profiles)?;
+    let corpus = bench::collect_corpus(&args.corpus)?;
+    let options = BenchOptions {
+        iterations: args.iterations,
+        warmup: args.warmup,
+        concurrency: args.concurrency,
+    };
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    let mut reports = Vec::new();
+    for profile in &profiles {
+        eprintln!("Benchmarking profile '{}' on {} files", profile.name, corpus.len());
+        let report = runtime.block_on(bench::run_profile(profile, &corpus, &options, &timings))?;
+        if args.format == Format::Text {
+            print_report(&report);
+        }
+        reports.push(report);
+    }
+
+    if args.format == Format::Json {
+        println!("{}", serde_json::to_string_pretty(&reports)?);
+    }
+    Ok(())
+}
+
+fn load_profiles(specs: &[String]) -> kreuzberg::Result<Vec<BenchProfile>> {
+    if specs.is_empty() {
+        let config = ExtractionConfig::discover()?.unwrap_or_default();
+        return Ok(vec![BenchProfile::new("default", config)]);
+    }
+    specs
+        .iter()
+        .map(|spec| {
+            let (name, path) = spec.split_once('=').ok_or_else(|| {
+                kreuzberg::KreuzbergError::validation(format!("Invalid profile '{spec}', expected NAME=CONFIG_FILE"))
+            })?;
+            Ok(BenchProfile::new(name, ExtractionConfig::from_file(path)?))
+        })
+        .collect()
+}
+
+fn print_report(report: &ProfileReport) {
+    println!("Profile: {}", report.profile);
+    println!(
+        "  {} files x {} samples, {} failed, {:.2} s",
+        report.files, report.latency.count, report.failures, report.wall_secs
+    );
+    println!(
+        "  Throughput: {:.2} files/s, {:.2} MB/s",
+        report.files_per_sec, report.mb_per_sec
+    );
+    match report.peak_rss_bytes {
+        Some(bytes) => println!("  Peak memory: {:.1} MB", bytes as f64 / (1024.0 * 1024.0)),
+        None => println!("  Peak memory: n/a"),
+    }
+    println!(
+        "  {:<24} {:>7} {:>10} {:>10} {:>10} {:>10} {:>10}",
+        "stage", "count", "mean ms", "p50 ms", "p90 ms", "p99 ms", "max ms"
+    );
+    print_latency("file", &report.latency);
+    for (stage, latency) in &report.stages {
+        print_latency(stage, latency);
+    }
+    println!();
+}
+
+fn print_latency(name: &str, latency: &LatencySummary) {
+    println!(
+        "  {:<24} {:>7} {:>10.2} {:>10.2} {:>10.2} {:>10.2} {:>10.2}",
+        name, latency.count, latency.mean_ms, latency.p50_ms, latency.p90_ms, latency.p99_ms, latency.max_ms
+    );
+}
@@ -23,8 +23,11 @@
 //! This approach ensures that lock poisoning (rare in practice) is surfaced to users
 //! rather than causing panics, maintaining system stability during concurrent operations.
 
+use crate::compression;
+use crate::core::config::Compression;
 use crate::error::{KreuzbergError, Result};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -66,6 +69,8 @@ pub struct GenericCache {
     deleting_files: Arc<Mutex<HashSet<PathBuf>>>,
     /// Counter for triggering periodic cleanup (every 100 writes)
     write_counter: Arc<AtomicUsize>,
+    /// Compression of entries written by `set`; `get` reads entries of any compression
+    compression: Compression,
 }
 
 impl GenericCache {
@@ -95,9 +100,19 @@ impl GenericCache {
             processing_locks: Arc::new(Mutex::new(HashSet::new())),
             deleting_files: Arc::new(Mutex::new(HashSet::new())),
             write_counter: Arc::new(AtomicUsize::new(0)),
+            compression: Compression::None,
         })
     }
 
+    /// Compress entries written from now on with `compression`.
+    ///
+    /// Entries are MessagePack, which never starts with the gzip or Zstandard magic
+    /// bytes, so `get` tells compressed entries apart from uncompressed ones.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
     fn get_cache_path(&self, cache_key: &str) -> PathBuf {
         self.cache_dir.join(format!("{}.msgpack", cache_key))
     }
@@ -226,7 +241,13 @@ impl GenericCache {
             return Ok(None);
         }
 
-        match fs::read(&cache_path) {
+        let content = fs::read(&cache_path).map_err(KreuzbergError::from).and_then(|content| {
+            match compression::decompress(&content)? {
+                Cow::Borrowed(_) => Ok(content),
+                Cow::Owned(decompressed) => Ok(decompressed),
+            }
+        });
+        match content {
             Ok(content) => {
                 #[cfg(feature = "otel")]
                 tracing::Span::current().record("cache.hit", true);
@@ -257,6 +278,7 @@ impl GenericCache {
     pub fn set(&self, cache_key: &str, data: Vec<u8>, source_file: Option<&str>) -> Result<()> {
         let cache_path = self.get_cache_path(cache_key);
 
+        let data = compression::compress(&data, self.compression)?;
         fs::write(&cache_path, &data)
             .map_err(|e| KreuzbergError::cache(format!("Failed to write cache file: {}", e)))?;
 
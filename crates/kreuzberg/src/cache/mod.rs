@@ -172,6 +172,30 @@ mod tests {
         assert_eq!(result, Some(data));
     }
 
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_generic_cache_compression() {
+        let temp_dir = tempdir().unwrap();
+        let cache = GenericCache::new(
+            "test".to_string(),
+            Some(temp_dir.path().to_str().unwrap().to_string()),
+            30.0,
+            500.0,
+            1000.0,
+        )
+        .unwrap();
+        cache.set("plain", b"plain data".to_vec(), None).unwrap();
+
+        let cache = cache.with_compression(crate::Compression::Gzip);
+        let data = b"compressible data ".repeat(100);
+        cache.set("compressed", data.clone(), None).unwrap();
+
+        let written = std::fs::read(cache.cache_dir().join("compressed.msgpack")).unwrap();
+        assert!(written.len() < data.len());
+        assert_eq!(cache.get("compressed", None).unwrap(), Some(data));
+        assert_eq!(cache.get("plain", None).unwrap(), Some(b"plain data".to_vec()));
+    }
+
     #[test]
     fn test_generic_cache_get_miss() {
         let temp_dir = tempdir().unwrap();
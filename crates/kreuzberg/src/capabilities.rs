@@ -0,0 +1,293 @@
+//! Compiled features and runtime dependencies.
+//!
+//! [`capabilities`] reports what this build of Kreuzberg can do: the optional Cargo
+//! features it was compiled with, grouped into OCR backends, codecs, servers and
+//! queue brokers, and whether the native libraries and programs those features load
+//! at runtime (Tesseract, ONNX Runtime, Pdfium, LibreOffice) were found, so that
+//! applications can hide what is unavailable instead of failing on first use.
+//!
+//! # Example
+//!
+//! ```rust
+//! let capabilities = kreuzberg::capabilities();
+//! if !capabilities.ocr_backends.is_empty() {
+//!     println!("OCR with {}", capabilities.ocr_backends.join(", "));
+//! }
+//! for dependency in capabilities.dependencies.iter().filter(|d| !d.found) {
+//!     println!("{} unavailable: {:?}", dependency.name, dependency.message);
+//! }
+//! ```
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// The optional Cargo features, with whether each was compiled in.
+macro_rules! features {
+    ($($feature:literal),* $(,)?) => {
+        [$((cfg!(feature = $feature), $feature)),*]
+    };
+}
+
+const FEATURES: &[(bool, &str)] = &features![
+    "tokio-runtime",
+    "simd-utf8",
+    "pdf",
+    "static-pdfium",
+    "bundled-pdfium",
+    "system-pdfium",
+    "excel",
+    "office",
+    "email",
+    "html",
+    "xml",
+    "archives",
+    "ocr",
+    "heif",
+    "jxl",
+    "barcodes",
+    "onnx-ocr",
+    "onnx-ocr-cuda",
+    "onnx-ocr-directml",
+    "language-detection",
+    "chunking",
+    "embeddings",
+    "remote-resources",
+    "models",
+    "stopwords",
+    "quality",
+    "keywords-yake",
+    "keywords-rake",
+    "api",
+    "mcp",
+    "grpc",
+    "mcp-http",
+    "otel",
+    "queue",
+    "watch",
+    "catalog",
+    "signing",
+    "compression",
+    "bench",
+    "profiling",
+    "pool-metrics",
+    "wasm-threads",
+];
+
+/// What this build of Kreuzberg can do.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct Capabilities {
+    /// Version of the Kreuzberg library
+    pub version: String,
+    /// Optional Cargo features compiled in, such as `pdf`, `ocr` or `api`
+    pub features: Vec<String>,
+    /// OCR backends compiled in: `tesseract`, `onnx`
+    pub ocr_backends: Vec<String>,
+    /// Execution providers of the ONNX OCR backend: `cpu`, `cuda`, `directml`
+    pub execution_providers: Vec<String>,
+    /// Optional codecs compiled in: `heif`, `jxl` and `barcodes` for images, `gzip`
+    /// and `zstd` for compressed results
+    pub codecs: Vec<String>,
+    /// Servers compiled in: `api`, `grpc`, `mcp`, `mcp-http`
+    pub servers: Vec<String>,
+    /// Brokers of the queue connector compiled in: `kafka`, `nats`
+    pub brokers: Vec<String>,
+    /// Whether embeddings can be generated
+    pub embeddings: bool,
+    /// Native libraries and programs needed at runtime by the compiled features
+    pub dependencies: Vec<RuntimeDependency>,
+}
+
+/// A native library or program loaded at runtime.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct RuntimeDependency {
+    /// Dependency name: `tesseract`, `onnxruntime`, `pdfium`, `libreoffice`
+    pub name: String,
+    /// Whether the dependency was found
+    pub found: bool,
+    /// Version of the dependency, when it can be told without loading it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Where the dependency was found
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "api", schema(value_type = Option<String>))]
+    pub path: Option<PathBuf>,
+    /// Why the dependency was not found, with installation instructions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+// Unused in builds without features that load native dependencies.
+#[allow(dead_code)]
+impl RuntimeDependency {
+    fn found(name: &str, version: Option<String>, path: Option<PathBuf>) -> Self {
+        Self {
+            name: name.to_string(),
+            found: true,
+            version,
+            path,
+            message: None,
+        }
+    }
+
+    fn missing(name: &str, message: String) -> Self {
+        Self {
+            name: name.to_string(),
+            found: false,
+            version: None,
+            path: None,
+            message: Some(message),
+        }
+    }
+}
+
+/// The features this build was compiled with and the runtime dependencies found.
+///
+/// Runtime dependencies are looked for on every call: libraries are located on disk
+/// and Pdfium is bound, but LibreOffice is not run.
+pub fn capabilities() -> Capabilities {
+    let enabled = |features: &[(&str, &str)]| -> Vec<String> {
+        features
+            .iter()
+            .filter(|(feature, _)| FEATURES.contains(&(true, *feature)))
+            .map(|(_, name)| name.to_string())
+            .collect()
+    };
+
+    let mut execution_providers = enabled(&[("onnx-ocr-cuda", "cuda"), ("onnx-ocr-directml", "directml")]);
+    if cfg!(feature = "onnx-ocr") {
+        execution_providers.insert(0, "cpu".to_string());
+    }
+
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        features: FEATURES
+            .iter()
+            .filter(|(compiled, _)| *compiled)
+            .map(|(_, feature)| feature.to_string())
+            .collect(),
+        ocr_backends: enabled(&[("ocr", "tesseract"), ("onnx-ocr", "onnx")]),
+        execution_providers,
+        codecs: enabled(&[
+            ("heif", "heif"),
+            ("jxl", "jxl"),
+            ("barcodes", "barcodes"),
+            ("compression", "gzip"),
+            ("compression", "zstd"),
+        ]),
+        servers: enabled(&[
+            ("api", "api"),
+            ("grpc", "grpc"),
+            ("mcp", "mcp"),
+            ("mcp-http", "mcp-http"),
+        ]),
+        brokers: enabled(&[("queue", "kafka"), ("queue", "nats")]),
+        embeddings: cfg!(feature = "embeddings"),
+        dependencies: dependencies(),
+    }
+}
+
+fn dependencies() -> Vec<RuntimeDependency> {
+    vec![
+        #[cfg(feature = "ocr")]
+        RuntimeDependency::found("tesseract", Some(kreuzberg_tesseract::TesseractAPI::version()), None),
+        #[cfg(any(feature = "onnx-ocr", feature = "embeddings"))]
+        match onnx_runtime_path() {
+            Some(path) => RuntimeDependency::found("onnxruntime", library_version(&path), Some(path)),
+            None => RuntimeDependency::missing(
+                "onnxruntime",
+                "ONNX Runtime library not found; install it or set ORT_DYLIB_PATH to its path".to_string(),
+            ),
+        },
+        #[cfg(feature = "pdf")]
+        match crate::pdf::bindings::bind_pdfium(crate::pdf::error::PdfError::InvalidPdf, "capability check") {
+            Ok(_) => RuntimeDependency::found("pdfium", None, None),
+            Err(e) => RuntimeDependency::missing("pdfium", e.to_string()),
+        },
+        #[cfg(feature = "office")]
+        match crate::extraction::libreoffice::locate_soffice_binary() {
+            Ok(path) => RuntimeDependency::found("libreoffice", None, Some(path)),
+            Err(e) => RuntimeDependency::missing("libreoffice", e.to_string()),
+        },
+    ]
+}
+
+/// The ONNX Runtime library loaded by the `ort` crate: `ORT_DYLIB_PATH`, or the first
+/// library found in the usual installation paths.
+#[cfg(any(feature = "onnx-ocr", feature = "embeddings"))]
+fn onnx_runtime_path() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os("ORT_DYLIB_PATH").map(PathBuf::from) {
+        return path.exists().then_some(path);
+    }
+
+    let candidates: &[&str] = if cfg!(target_os = "macos") {
+        &[
+            "/opt/homebrew/lib/libonnxruntime.dylib",
+            "/usr/local/lib/libonnxruntime.dylib",
+        ]
+    } else if cfg!(target_os = "windows") {
+        &[
+            "C:\\Program Files\\onnxruntime\\bin\\onnxruntime.dll",
+            "C:\\Windows\\System32\\onnxruntime.dll",
+        ]
+    } else {
+        &[
+            "/usr/lib/libonnxruntime.so",
+            "/usr/local/lib/libonnxruntime.so",
+            "/usr/lib/x86_64-linux-gnu/libonnxruntime.so",
+            "/usr/lib/aarch64-linux-gnu/libonnxruntime.so",
+        ]
+    };
+    candidates.iter().map(PathBuf::from).find(|path| path.exists())
+}
+
+/// The version in the file name of a shared library, following symbolic links:
+/// `1.20.1` for `libonnxruntime.so.1.20.1` or `libonnxruntime.1.20.1.dylib`.
+#[cfg(any(feature = "onnx-ocr", feature = "embeddings", test))]
+fn library_version(path: &std::path::Path) -> Option<String> {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let name = path.file_name()?.to_string_lossy().into_owned();
+    name.split(|c: char| !c.is_ascii_digit() && c != '.')
+        .map(|part| part.trim_matches('.'))
+        .find(|part| part.contains('.') && part.starts_with(|c: char| c.is_ascii_digit()))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_capabilities() {
+        let capabilities = capabilities();
+        assert_eq!(capabilities.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(
+            capabilities.features.contains(&"html".to_string()),
+            cfg!(feature = "html")
+        );
+        assert_eq!(capabilities.ocr_backends.is_empty(), !cfg!(feature = "ocr"));
+        assert_eq!(
+            capabilities.dependencies.iter().any(|d| d.name == "libreoffice"),
+            cfg!(feature = "office")
+        );
+        assert!(capabilities.dependencies.iter().all(|d| d.found != d.message.is_some()));
+
+        let json = serde_json::to_value(&capabilities).unwrap();
+        assert_eq!(serde_json::from_value::<Capabilities>(json).unwrap(), capabilities);
+    }
+
+    #[test]
+    fn test_library_version() {
+        assert_eq!(
+            library_version(Path::new("/nonexistent/libonnxruntime.so.1.20.1")).as_deref(),
+            Some("1.20.1")
+        );
+        assert_eq!(
+            library_version(Path::new("/nonexistent/libonnxruntime.1.19.2.dylib")).as_deref(),
+            Some("1.19.2")
+        );
+        assert_eq!(library_version(Path::new("/nonexistent/onnxruntime.dll")), None);
+    }
+}
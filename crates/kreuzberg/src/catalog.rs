@@ -0,0 +1,566 @@
+//! SQLite catalog of the files processed by batch runs.
+//!
+//! A [`Catalog`] records every file of a run: its status, MIME type, metadata, when
+//! and how long it was extracted, the error class and message of a failure, and where
+//! the result was written. [`Catalog::query`] answers questions like "which files
+//! failed OCR last night" without going through logs:
+//!
+//! ```rust,no_run
+//! use kreuzberg::catalog::{Catalog, CatalogQuery, FileStatus};
+//! use kreuzberg::ErrorClass;
+//!
+//! # fn example(last_night: u64) -> kreuzberg::Result<()> {
+//! let catalog = Catalog::open("catalog.db")?;
+//! let failed = catalog.query(&CatalogQuery {
+//!     status: Some(FileStatus::Failed),
+//!     error_type: Some(ErrorClass::Ocr),
+//!     since: Some(last_night),
+//!     ..Default::default()
+//! })?;
+//! for record in failed {
+//!     println!("{}: {}", record.entry.path, record.entry.error_message.unwrap_or_default());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The database has two tables, `runs` and `files`, and can also be queried with any
+//! SQLite client. Times are Unix seconds.
+
+use crate::core::config::ExtractionConfig;
+use crate::error::ErrorClass;
+use crate::types::ExtractionResult;
+use crate::{KreuzbergError, Result};
+use parking_lot::Mutex;
+use rusqlite::types::Value as SqlValue;
+use rusqlite::{Connection, OptionalExtension, Row, params};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS runs (
+    id INTEGER PRIMARY KEY,
+    label TEXT,
+    started_at INTEGER NOT NULL,
+    finished_at INTEGER
+);
+CREATE TABLE IF NOT EXISTS files (
+    id INTEGER PRIMARY KEY,
+    run_id INTEGER NOT NULL REFERENCES runs(id),
+    path TEXT NOT NULL,
+    mime_type TEXT,
+    status TEXT NOT NULL,
+    error_type TEXT,
+    error_message TEXT,
+    started_at INTEGER NOT NULL,
+    duration_ms INTEGER NOT NULL,
+    metadata TEXT,
+    location TEXT
+);
+CREATE INDEX IF NOT EXISTS files_run_id ON files(run_id);
+CREATE INDEX IF NOT EXISTS files_started_at ON files(started_at);
+";
+
+const FILE_COLUMNS: &str =
+    "id, run_id, path, mime_type, status, error_type, error_message, started_at, duration_ms, metadata, location";
+
+/// Outcome of the extraction of a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileStatus {
+    Extracted,
+    Failed,
+}
+
+impl FileStatus {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Extracted => "extracted",
+            Self::Failed => "failed",
+        }
+    }
+}
+
+/// A batch run of the catalog.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CatalogRun {
+    pub id: i64,
+    pub label: Option<String>,
+    pub started_at: u64,
+    /// None while the run is in progress, or if it was interrupted
+    pub finished_at: Option<u64>,
+}
+
+/// What the catalog records about a processed file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogEntry {
+    pub path: String,
+    /// MIME type of the file, None if extraction failed
+    pub mime_type: Option<String>,
+    pub status: FileStatus,
+    /// Class of the error of a failed file
+    pub error_type: Option<ErrorClass>,
+    pub error_message: Option<String>,
+    /// When the extraction started
+    pub started_at: u64,
+    pub duration_ms: u64,
+    /// Metadata of the result, None if extraction failed
+    pub metadata: Option<serde_json::Value>,
+    /// Where the result was written, e.g. a file or store manifest path
+    pub location: Option<String>,
+}
+
+impl CatalogEntry {
+    /// Entry of the extraction of `path` that started at `started_at` and took `duration`.
+    pub fn new(
+        path: impl Into<String>,
+        outcome: &Result<ExtractionResult>,
+        started_at: u64,
+        duration: Duration,
+    ) -> Self {
+        let mut entry = Self {
+            path: path.into(),
+            mime_type: None,
+            status: FileStatus::Extracted,
+            error_type: None,
+            error_message: None,
+            started_at,
+            duration_ms: duration.as_millis() as u64,
+            metadata: None,
+            location: None,
+        };
+        match outcome {
+            Ok(result) => {
+                entry.mime_type = Some(result.mime_type.to_string());
+                entry.metadata = serde_json::to_value(&result.metadata).ok();
+            }
+            Err(e) => {
+                entry.status = FileStatus::Failed;
+                entry.error_type = Some(e.class());
+                entry.error_message = Some(e.to_string());
+            }
+        }
+        entry
+    }
+}
+
+/// A file of the catalog.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CatalogRecord {
+    pub id: i64,
+    pub run: i64,
+    #[serde(flatten)]
+    pub entry: CatalogEntry,
+}
+
+/// Filter of [`Catalog::query`]. The default matches every file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CatalogQuery {
+    pub run: Option<i64>,
+    pub status: Option<FileStatus>,
+    pub error_type: Option<ErrorClass>,
+    /// Only files whose path starts with this prefix
+    pub path_prefix: Option<String>,
+    /// Only files whose extraction started at or after this time
+    pub since: Option<u64>,
+    /// Only files whose extraction started before this time
+    pub until: Option<u64>,
+    /// Maximum number of records (None = all)
+    pub limit: Option<usize>,
+}
+
+/// SQLite catalog of batch runs and their files.
+#[derive(Debug)]
+pub struct Catalog {
+    connection: Mutex<Connection>,
+}
+
+impl Catalog {
+    /// Open the catalog at `path`, creating the database and its tables if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database cannot be opened or created.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self> {
+        let connection = Connection::open(path).map_err(sqlite_error)?;
+        // Several processes may record runs into the same catalog.
+        connection.busy_timeout(Duration::from_secs(5)).map_err(sqlite_error)?;
+        connection
+            .pragma_update(None, "journal_mode", "WAL")
+            .map_err(sqlite_error)?;
+        connection.execute_batch(SCHEMA).map_err(sqlite_error)?;
+        Ok(Self {
+            connection: Mutex::new(connection),
+        })
+    }
+
+    /// Record the start of a run and return its ID.
+    pub fn start_run(&self, label: Option<&str>) -> Result<i64> {
+        let connection = self.connection.lock();
+        connection
+            .execute(
+                "INSERT INTO runs (label, started_at) VALUES (?1, ?2)",
+                params![label, unix_time() as i64],
+            )
+            .map_err(sqlite_error)?;
+        Ok(connection.last_insert_rowid())
+    }
+
+    /// Record the end of the run `run`.
+    pub fn finish_run(&self, run: i64) -> Result<()> {
+        let updated = self
+            .connection
+            .lock()
+            .execute(
+                "UPDATE runs SET finished_at = ?1 WHERE id = ?2",
+                params![unix_time() as i64, run],
+            )
+            .map_err(sqlite_error)?;
+        if updated == 0 {
+            return Err(KreuzbergError::validation(format!("No catalog run {run}")));
+        }
+        Ok(())
+    }
+
+    /// Runs of the catalog, newest first.
+    pub fn runs(&self) -> Result<Vec<CatalogRun>> {
+        let connection = self.connection.lock();
+        let mut statement = connection
+            .prepare("SELECT id, label, started_at, finished_at FROM runs ORDER BY id DESC")
+            .map_err(sqlite_error)?;
+        let runs = statement
+            .query_map([], |row| {
+                Ok(CatalogRun {
+                    id: row.get(0)?,
+                    label: row.get(1)?,
+                    started_at: row.get::<_, i64>(2)? as u64,
+                    finished_at: row.get::<_, Option<i64>>(3)?.map(|t| t as u64),
+                })
+            })
+            .map_err(sqlite_error)?
+            .collect::<rusqlite::Result<_>>()
+            .map_err(sqlite_error)?;
+        Ok(runs)
+    }
+
+    /// Record a file of the run `run` and return the ID of its record.
+    pub fn record(&self, run: i64, entry: &CatalogEntry) -> Result<i64> {
+        let metadata = entry.metadata.as_ref().map(serde_json::to_string).transpose()?;
+        let connection = self.connection.lock();
+        connection
+            .execute(
+                "INSERT INTO files (run_id, path, mime_type, status, error_type, error_message, \
+                 started_at, duration_ms, metadata, location) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                params![
+                    run,
+                    entry.path,
+                    entry.mime_type,
+                    entry.status.as_str(),
+                    entry.error_type.map(error_class_name),
+                    entry.error_message,
+                    entry.started_at as i64,
+                    entry.duration_ms as i64,
+                    metadata,
+                    entry.location,
+                ],
+            )
+            .map_err(sqlite_error)?;
+        Ok(connection.last_insert_rowid())
+    }
+
+    /// Set where the result of the record `id` was written.
+    pub fn set_location(&self, id: i64, location: &str) -> Result<()> {
+        self.connection
+            .lock()
+            .execute("UPDATE files SET location = ?1 WHERE id = ?2", params![location, id])
+            .map_err(sqlite_error)?;
+        Ok(())
+    }
+
+    /// The record `id`, if it exists.
+    pub fn get(&self, id: i64) -> Result<Option<CatalogRecord>> {
+        self.connection
+            .lock()
+            .query_row(
+                &format!("SELECT {FILE_COLUMNS} FROM files WHERE id = ?1"),
+                [id],
+                read_record,
+            )
+            .optional()
+            .map_err(sqlite_error)
+    }
+
+    /// Records matching `query`, newest first.
+    pub fn query(&self, query: &CatalogQuery) -> Result<Vec<CatalogRecord>> {
+        let mut conditions = Vec::new();
+        let mut values = Vec::new();
+        if let Some(run) = query.run {
+            conditions.push("run_id = ?");
+            values.push(SqlValue::Integer(run));
+        }
+        if let Some(status) = query.status {
+            conditions.push("status = ?");
+            values.push(SqlValue::Text(status.as_str().to_string()));
+        }
+        if let Some(error_type) = query.error_type {
+            conditions.push("error_type = ?");
+            values.push(SqlValue::Text(error_class_name(error_type)));
+        }
+        if let Some(prefix) = &query.path_prefix {
+            // Compared with substr rather than LIKE, so `%` and `_` in paths match literally.
+            conditions.push("substr(path, 1, length(?)) = ?");
+            values.push(SqlValue::Text(prefix.clone()));
+            values.push(SqlValue::Text(prefix.clone()));
+        }
+        if let Some(since) = query.since {
+            conditions.push("started_at >= ?");
+            values.push(SqlValue::Integer(since as i64));
+        }
+        if let Some(until) = query.until {
+            conditions.push("started_at < ?");
+            values.push(SqlValue::Integer(until as i64));
+        }
+
+        let mut sql = format!("SELECT {FILE_COLUMNS} FROM files");
+        if !conditions.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&conditions.join(" AND "));
+        }
+        sql.push_str(" ORDER BY id DESC");
+        if let Some(limit) = query.limit {
+            sql.push_str(&format!(" LIMIT {limit}"));
+        }
+        let connection = self.connection.lock();
+        let mut statement = connection.prepare(&sql).map_err(sqlite_error)?;
+        let records = statement
+            .query_map(rusqlite::params_from_iter(values), read_record)
+            .map_err(sqlite_error)?
+            .collect::<rusqlite::Result<_>>()
+            .map_err(sqlite_error)?;
+        Ok(records)
+    }
+
+    /// Extract `paths` concurrently, like [`batch_extract_file`](crate::batch_extract_file),
+    /// and record each file in the run `run`.
+    ///
+    /// Returns the record ID and result of each path, in the order of `paths`. A file
+    /// that failed has an error result, as in `batch_extract_file`.
+    #[cfg(feature = "tokio-runtime")]
+    pub async fn extract_batch(
+        &self,
+        run: i64,
+        paths: &[PathBuf],
+        config: &ExtractionConfig,
+    ) -> Result<Vec<(i64, ExtractionResult)>> {
+        use crate::core::extractor::batch::extract_with_retries;
+        use crate::core::extractor::extract_file;
+        use std::sync::Arc;
+        use tokio::sync::Semaphore;
+        use tokio::task::JoinSet;
+
+        let config = Arc::new(config.clone());
+        let max_concurrent = config
+            .max_concurrent_extractions
+            .unwrap_or_else(|| (num_cpus::get() as f64 * 1.5).ceil() as usize);
+        let semaphore = Arc::new(Semaphore::new(max_concurrent));
+
+        let mut tasks = JoinSet::new();
+        for (index, path) in paths.iter().enumerate() {
+            let path = path.clone();
+            let config = Arc::clone(&config);
+            let semaphore = Arc::clone(&semaphore);
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire().await.expect("the semaphore is never closed");
+                let started_at = unix_time();
+                let start = std::time::Instant::now();
+                let outcome = crate::core::batch_mode::with_batch_mode(extract_with_retries(
+                    &config,
+                    Some(&path),
+                    Some(index),
+                    || extract_file(&path, None, &config),
+                ))
+                .await;
+                let entry = CatalogEntry::new(path.to_string_lossy(), &outcome, started_at, start.elapsed());
+                (index, entry, outcome)
+            });
+        }
+
+        let mut results: Vec<Option<(i64, ExtractionResult)>> = vec![None; paths.len()];
+        while let Some(task) = tasks.join_next().await {
+            let (index, entry, outcome) = task.map_err(|e| KreuzbergError::Other(format!("Task panicked: {e}")))?;
+            let id = self.record(run, &entry)?;
+            results[index] = Some((id, outcome.unwrap_or_else(|e| error_result(&e))));
+        }
+        Ok(results.into_iter().flatten().collect())
+    }
+}
+
+/// Result of a file that failed in a batch, as returned by `batch_extract_file`.
+#[cfg(feature = "tokio-runtime")]
+fn error_result(error: &KreuzbergError) -> ExtractionResult {
+    use crate::types::{ErrorMetadata, Metadata};
+
+    ExtractionResult {
+        content: format!("Error: {error}"),
+        mime_type: std::borrow::Cow::Borrowed("text/plain"),
+        metadata: Metadata {
+            error: Some(ErrorMetadata {
+                error_type: format!("{error:?}"),
+                message: error.to_string(),
+            }),
+            ..Default::default()
+        },
+        tables: vec![],
+        detected_languages: None,
+        chunks: None,
+        images: None,
+        djot_content: None,
+        scripts: None,
+        pages: None,
+        elements: None,
+    }
+}
+
+fn read_record(row: &Row<'_>) -> rusqlite::Result<CatalogRecord> {
+    let status: String = row.get(4)?;
+    let error_type: Option<String> = row.get(5)?;
+    let metadata: Option<String> = row.get(9)?;
+    Ok(CatalogRecord {
+        id: row.get(0)?,
+        run: row.get(1)?,
+        entry: CatalogEntry {
+            path: row.get(2)?,
+            mime_type: row.get(3)?,
+            status: if status == FileStatus::Failed.as_str() {
+                FileStatus::Failed
+            } else {
+                FileStatus::Extracted
+            },
+            error_type: error_type.and_then(|name| serde_json::from_value(serde_json::Value::String(name)).ok()),
+            error_message: row.get(6)?,
+            started_at: row.get::<_, i64>(7)? as u64,
+            duration_ms: row.get::<_, i64>(8)? as u64,
+            metadata: metadata.and_then(|json| serde_json::from_str(&json).ok()),
+            location: row.get(10)?,
+        },
+    })
+}
+
+/// Name of `class` in the catalog, as in configuration, e.g. `missing_dependency`.
+fn error_class_name(class: ErrorClass) -> String {
+    serde_json::to_value(class)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+fn sqlite_error(e: rusqlite::Error) -> KreuzbergError {
+    KreuzbergError::Other(format!("Catalog error: {e}"))
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn failed(path: &str, error: KreuzbergError, started_at: u64) -> CatalogEntry {
+        CatalogEntry::new(path, &Err(error), started_at, Duration::from_millis(40))
+    }
+
+    #[test]
+    fn test_query_filters_records() {
+        let dir = tempdir().unwrap();
+        let catalog = Catalog::open(dir.path().join("catalog.db")).unwrap();
+
+        let monday = catalog.start_run(Some("monday")).unwrap();
+        catalog
+            .record(monday, &failed("scans/a_1.png", KreuzbergError::ocr("no text"), 1_000))
+            .unwrap();
+        let tuesday = catalog.start_run(Some("tuesday")).unwrap();
+        let ocr = catalog
+            .record(tuesday, &failed("scans/b.png", KreuzbergError::ocr("no text"), 2_000))
+            .unwrap();
+        catalog
+            .record(
+                tuesday,
+                &failed("docs/c.pdf", KreuzbergError::parsing("broken xref"), 2_010),
+            )
+            .unwrap();
+        catalog.finish_run(tuesday).unwrap();
+
+        let runs = catalog.runs().unwrap();
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].label.as_deref(), Some("tuesday"));
+        assert!(runs[0].finished_at.is_some());
+        assert!(runs[1].finished_at.is_none());
+
+        let records = catalog
+            .query(&CatalogQuery {
+                status: Some(FileStatus::Failed),
+                error_type: Some(ErrorClass::Ocr),
+                since: Some(1_500),
+                ..Default::default()
+            })
+            .unwrap();
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].id, ocr);
+        assert_eq!(records[0].run, tuesday);
+        assert_eq!(records[0].entry.error_type, Some(ErrorClass::Ocr));
+        assert_eq!(records[0].entry.duration_ms, 40);
+
+        let scans = CatalogQuery {
+            path_prefix: Some("scans/a_".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(catalog.query(&scans).unwrap().len(), 1);
+        let newest = CatalogQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let records = catalog.query(&newest).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].entry.path, "docs/c.pdf");
+        assert_eq!(catalog.query(&CatalogQuery::default()).unwrap().len(), 3);
+
+        assert!(catalog.finish_run(42).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_extract_batch_records_files() {
+        let dir = tempdir().unwrap();
+        let text = dir.path().join("notes.txt");
+        std::fs::write(&text, "Cataloged").unwrap();
+        let missing = dir.path().join("missing.txt");
+
+        let catalog = Catalog::open(dir.path().join("catalog.db")).unwrap();
+        let run = catalog.start_run(None).unwrap();
+        let results = catalog
+            .extract_batch(run, &[text.clone(), missing], &ExtractionConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[0].1.content.contains("Cataloged"));
+        assert!(results[1].1.metadata.error.is_some());
+
+        catalog.set_location(results[0].0, "out/notes.json").unwrap();
+        let record = catalog.get(results[0].0).unwrap().unwrap();
+        assert_eq!(record.entry.path, text.to_string_lossy());
+        assert_eq!(record.entry.status, FileStatus::Extracted);
+        assert_eq!(record.entry.mime_type.as_deref(), Some("text/plain"));
+        assert!(record.entry.metadata.is_some());
+        assert_eq!(record.entry.location.as_deref(), Some("out/notes.json"));
+
+        let record = catalog.get(results[1].0).unwrap().unwrap();
+        assert_eq!(record.entry.status, FileStatus::Failed);
+        assert_eq!(record.entry.error_type, Some(ErrorClass::Io));
+        assert!(catalog.get(999).unwrap().is_none());
+    }
+}
@@ -4,7 +4,8 @@
 //! including overlap calculation, offset tracking, and metadata assembly.
 
 use crate::error::{KreuzbergError, Result};
-use crate::types::{Chunk, ChunkMetadata, PageBoundary};
+use crate::types::{Chunk, ChunkMetadata, ChunkType, PageBoundary};
+use std::ops::Range;
 use text_splitter::{Characters, ChunkCapacity, ChunkConfig};
 
 use super::boundaries::calculate_page_range;
@@ -80,6 +81,49 @@ where
     Ok(chunks)
 }
 
+/// Build chunks from byte spans of `text` with optional page boundary tracking.
+///
+/// Unlike [`build_chunks`], the offsets are exact, since every span says where its
+/// chunk lies in the source text.
+///
+/// # Errors
+///
+/// Returns an error if page boundary calculation fails.
+pub fn build_span_chunks(
+    text: &str,
+    spans: &[Range<usize>],
+    page_boundaries: Option<&[PageBoundary]>,
+) -> Result<Vec<Chunk>> {
+    let total_chunks = spans.len();
+    spans
+        .iter()
+        .enumerate()
+        .map(|(index, span)| {
+            let (first_page, last_page) = if let Some(boundaries) = page_boundaries {
+                calculate_page_range(span.start, span.end, boundaries)?
+            } else {
+                (None, None)
+            };
+
+            Ok(Chunk {
+                content: text[span.clone()].to_string(),
+                embedding: None,
+                metadata: ChunkMetadata {
+                    byte_start: span.start,
+                    byte_end: span.end,
+                    token_count: None,
+                    chunk_index: index,
+                    total_chunks,
+                    first_page,
+                    last_page,
+                    chunk_type: ChunkType::Text,
+                    additional: Default::default(),
+                },
+            })
+        })
+        .collect()
+}
+
 /// Build a single chunk with metadata.
 ///
 /// # Arguments
@@ -138,6 +182,8 @@ fn build_single_chunk(
             total_chunks,
             first_page,
             last_page,
+            chunk_type: ChunkType::Text,
+            additional: Default::default(),
         },
     })
 }
@@ -274,6 +320,19 @@ mod tests {
         assert_eq!(offset, 17); // Last chunk, no overlap subtracted
     }
 
+    #[test]
+    fn test_build_span_chunks_exact_offsets() {
+        let text = "AAAAA BBBBB CCCCC";
+        let result = build_span_chunks(text, &[0..11, 6..17], None).unwrap();
+
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].content, "AAAAA BBBBB");
+        assert_eq!(result[1].content, "BBBBB CCCCC");
+        assert_eq!(result[1].metadata.byte_start, 6);
+        assert_eq!(result[1].metadata.byte_end, 17);
+        assert_eq!(result[1].metadata.total_chunks, 2);
+    }
+
     #[test]
     fn test_build_chunks_no_overlap() {
         let text_chunks = vec!["AAAAA", "BBBBB", "CCCCC"];
@@ -2,8 +2,10 @@
 
 use serde::{Deserialize, Serialize};
 
-// Re-export ChunkingConfig and ChunkerType from core config (canonical location)
-pub use crate::core::config::processing::{ChunkerType, ChunkingConfig};
+// Re-export chunking configuration types from core config (canonical location)
+pub use crate::core::config::processing::{
+    ChunkerType, ChunkingConfig, OverlapStrategy, TableChunkFormat, TableChunkingConfig,
+};
 
 /// Result of a text chunking operation.
 ///
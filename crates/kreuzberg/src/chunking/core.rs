@@ -3,12 +3,16 @@
 //! This module implements the main chunking algorithms and provides the primary
 //! public API functions for splitting text into chunks.
 
-use crate::error::Result;
-use crate::types::PageBoundary;
-use text_splitter::{MarkdownSplitter, TextSplitter};
-
-use super::builder::{build_chunk_config, build_chunks};
-use super::config::{ChunkerType, ChunkingConfig, ChunkingResult};
+use crate::error::{KreuzbergError, Result};
+use crate::types::{Chunk, PageBoundary};
+use std::ops::Range;
+use text_splitter::{Characters, ChunkConfig, MarkdownSplitter, TextSplitter};
+
+use super::builder::{build_chunk_config, build_chunks, build_span_chunks};
+use super::config::{ChunkerType, ChunkingConfig, ChunkingResult, OverlapStrategy};
+use super::headings::prefix_headings;
+use super::overlap::{sentence_overlap, sliding_windows};
+use super::tables::chunk_with_tables;
 use super::validation::validate_utf8_boundaries;
 
 /// Split text into chunks with optional page boundary tracking.
@@ -16,6 +20,12 @@ use super::validation::validate_utf8_boundaries;
 /// This is the primary API function for chunking text. It supports both plain text
 /// and Markdown with configurable chunk size, overlap, and page boundary mapping.
 ///
+/// `config.overlap_strategy` selects how chunks overlap. Sentence overlap splits at
+/// `max_characters - overlap` and then repeats whole sentences of the previous chunk.
+/// Sliding windows ignore the chunker type and cut plain windows snapped to words.
+/// Both report exact byte offsets. With `config.tables`, Markdown tables are taken out
+/// of the text and emitted as table chunks.
+///
 /// # Arguments
 ///
 /// * `text` - The text to split into chunks
@@ -59,25 +69,97 @@ pub fn chunk_text(
         validate_utf8_boundaries(text, boundaries)?;
     }
 
-    let chunk_config = build_chunk_config(config.max_characters, config.overlap, config.trim)?;
-
-    let text_chunks: Vec<&str> = match config.chunker_type {
-        ChunkerType::Text => {
-            let splitter = TextSplitter::new(chunk_config);
-            splitter.chunks(text).collect()
-        }
-        ChunkerType::Markdown => {
-            let splitter = MarkdownSplitter::new(chunk_config);
-            splitter.chunks(text).collect()
-        }
+    let (mut chunks, starts) = match &config.tables {
+        Some(tables) => chunk_with_tables(text, config, tables, page_boundaries)?,
+        None => split_chunks(text, config, page_boundaries)?,
     };
 
-    let chunks = build_chunks(text_chunks.into_iter(), config.overlap, page_boundaries)?;
+    if config.heading_context {
+        prefix_headings(text, &mut chunks, &starts);
+    }
+
     let chunk_count = chunks.len();
 
     Ok(ChunkingResult { chunks, chunk_count })
 }
 
+/// Split `text` with the overlap strategy of `config`, returning the chunks along with
+/// their start offsets.
+pub(super) fn split_chunks(
+    text: &str,
+    config: &ChunkingConfig,
+    page_boundaries: Option<&[PageBoundary]>,
+) -> Result<(Vec<Chunk>, Vec<usize>)> {
+    match config.overlap_strategy {
+        OverlapStrategy::Characters => {
+            let chunk_config = build_chunk_config(config.max_characters, config.overlap, config.trim)?;
+            let text_chunks = split(text, chunk_config, config.chunker_type);
+            let starts = text_chunks.iter().map(|&(start, _)| start).collect();
+            let chunks = build_chunks(
+                text_chunks.into_iter().map(|(_, chunk)| chunk),
+                config.overlap,
+                page_boundaries,
+            )?;
+            Ok((chunks, starts))
+        }
+        OverlapStrategy::Sentences { count } => {
+            if count == 0 || config.overlap >= config.max_characters {
+                return Err(KreuzbergError::validation(format!(
+                    "Invalid chunking configuration: sentence overlap needs a count of at least 1 and max_overlap ({}) below max_chars ({})",
+                    config.overlap, config.max_characters
+                )));
+            }
+            let chunk_config = build_chunk_config(config.max_characters - config.overlap, 0, config.trim)?;
+            let spans: Vec<Range<usize>> = split(text, chunk_config, config.chunker_type)
+                .into_iter()
+                .map(|(start, chunk)| start..start + chunk.len())
+                .collect();
+            let segmenter = crate::plugins::resolve_sentence_segmenter(config.sentence_segmenter.as_deref())?;
+            let mut sentence_starts: Vec<usize> = segmenter
+                .segment(text)?
+                .into_iter()
+                .map(|sentence| sentence.start)
+                .filter(|&start| text.is_char_boundary(start))
+                .collect();
+            sentence_starts.sort_unstable();
+            sentence_starts.dedup();
+            span_chunks(
+                text,
+                sentence_overlap(text, &spans, &sentence_starts, count, config.overlap),
+                page_boundaries,
+            )
+        }
+        OverlapStrategy::SlidingWindow { stride } => {
+            if stride == 0 || stride > config.max_characters {
+                return Err(KreuzbergError::validation(format!(
+                    "Invalid chunking configuration: sliding window stride ({}) must be between 1 and max_chars ({})",
+                    stride, config.max_characters
+                )));
+            }
+            let windows = sliding_windows(text, config.max_characters, stride, config.trim);
+            span_chunks(text, windows, page_boundaries)
+        }
+    }
+}
+
+/// Split `text` into `(byte offset, chunk)` pairs.
+fn split(text: &str, chunk_config: ChunkConfig<Characters>, chunker_type: ChunkerType) -> Vec<(usize, &str)> {
+    match chunker_type {
+        ChunkerType::Text => TextSplitter::new(chunk_config).chunk_indices(text).collect(),
+        ChunkerType::Markdown => MarkdownSplitter::new(chunk_config).chunk_indices(text).collect(),
+    }
+}
+
+/// Build chunks with exact offsets from spans, along with their start offsets.
+fn span_chunks(
+    text: &str,
+    spans: Vec<Range<usize>>,
+    page_boundaries: Option<&[PageBoundary]>,
+) -> Result<(Vec<Chunk>, Vec<usize>)> {
+    let chunks = build_span_chunks(text, &spans, page_boundaries)?;
+    Ok((chunks, spans.into_iter().map(|span| span.start).collect()))
+}
+
 /// Chunk text with explicit type specification.
 ///
 /// This is a convenience function that constructs a ChunkingConfig from individual
@@ -120,6 +202,10 @@ pub fn chunk_text_with_type(
         chunker_type,
         embedding: None,
         preset: None,
+        overlap_strategy: OverlapStrategy::Characters,
+        heading_context: false,
+        tables: None,
+        sentence_segmenter: None,
     };
     chunk_text(text, &config, None)
 }
@@ -181,6 +267,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "This is a short text.";
         let result = chunk_text(text, &config, None).unwrap();
@@ -198,6 +288,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ";
         let result = chunk_text(text, &config, None).unwrap();
@@ -215,6 +309,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "abcdefghijklmnopqrstuvwxyz0123456789";
         let result = chunk_text(text, &config, None).unwrap();
@@ -240,6 +338,10 @@ mod tests {
             chunker_type: ChunkerType::Markdown,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let markdown = "# Title\n\nParagraph one.\n\n## Section\n\nParagraph two.";
         let result = chunk_text(markdown, &config, None).unwrap();
@@ -256,6 +358,10 @@ mod tests {
             chunker_type: ChunkerType::Markdown,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let markdown = "# Code Example\n\n```python\nprint('hello')\n```\n\nSome text after code.";
         let result = chunk_text(markdown, &config, None).unwrap();
@@ -272,6 +378,10 @@ mod tests {
             chunker_type: ChunkerType::Markdown,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let markdown = "Check out [this link](https://example.com) for more info.";
         let result = chunk_text(markdown, &config, None).unwrap();
@@ -288,6 +398,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "  Leading and trailing spaces  should be trimmed  ";
         let result = chunk_text(text, &config, None).unwrap();
@@ -304,6 +418,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "  Text with spaces  ";
         let result = chunk_text(text, &config, None).unwrap();
@@ -320,6 +438,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let result = chunk_text("Some text", &config, None);
         assert!(result.is_err());
@@ -359,6 +481,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let texts = vec!["First text", "Second text", "Third text"];
         let results = chunk_texts_batch(&texts, &config).unwrap();
@@ -375,6 +501,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let texts = vec![
             "Short",
@@ -397,6 +527,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let texts = vec!["Text one", "Text two"];
         let result = chunk_texts_batch(&texts, &config);
@@ -421,6 +555,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "a".repeat(1000);
         let result = chunk_text(&text, &config, None).unwrap();
@@ -437,6 +575,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "Line one\nLine two\nLine three\nLine four\nLine five";
         let result = chunk_text(text, &config, None).unwrap();
@@ -452,6 +594,10 @@ mod tests {
             chunker_type: ChunkerType::Markdown,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let markdown = "# List Example\n\n- Item 1\n- Item 2\n- Item 3\n\nMore text.";
         let result = chunk_text(markdown, &config, None).unwrap();
@@ -468,6 +614,10 @@ mod tests {
             chunker_type: ChunkerType::Markdown,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let markdown = "# Table\n\n| Col1 | Col2 |\n|------|------|\n| A    | B    |\n| C    | D    |";
         let result = chunk_text(markdown, &config, None).unwrap();
@@ -484,6 +634,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "Special chars: @#$%^&*()[]{}|\\<>?/~`";
         let result = chunk_text(text, &config, None).unwrap();
@@ -500,6 +654,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "Unicode: 你好世界 🌍 café résumé";
         let result = chunk_text(text, &config, None).unwrap();
@@ -517,6 +675,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "日本語のテキストです。これは長い文章で、複数のチャンクに分割されるべきです。";
         let result = chunk_text(text, &config, None).unwrap();
@@ -532,6 +694,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "English text mixed with 中文文本 and some français";
         let result = chunk_text(text, &config, None).unwrap();
@@ -547,6 +713,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "AAAAA BBBBB CCCCC DDDDD EEEEE FFFFF";
         let result = chunk_text(text, &config, None).unwrap();
@@ -601,6 +771,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "AAAAA BBBBB CCCCC DDDDD EEEEE FFFFF";
         let result = chunk_text(text, &config, None).unwrap();
@@ -629,6 +803,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "0123456789 ABCDEFGHIJ KLMNOPQRST UVWXYZ";
         let result = chunk_text(text, &config, None).unwrap();
@@ -665,6 +843,10 @@ mod tests {
                 chunker_type: ChunkerType::Text,
                 embedding: None,
                 preset: None,
+                overlap_strategy: OverlapStrategy::Characters,
+                heading_context: false,
+                tables: None,
+                sentence_segmenter: None,
             };
             let text = "Word ".repeat(30);
             let result = chunk_text(&text, &config, None).unwrap();
@@ -699,6 +881,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "AAAAA BBBBB CCCCC DDDDD EEEEE";
         let result = chunk_text(text, &config, None).unwrap();
@@ -728,6 +914,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "Page one content here. Page two starts here and continues.";
 
@@ -762,6 +952,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "This is some test content that should be split into multiple chunks.";
 
@@ -783,6 +977,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "Some text content here.";
         let boundaries: Vec<PageBoundary> = vec![];
@@ -803,6 +1001,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "0123456789 AAAAAAAAAA 1111111111 BBBBBBBBBB 2222222222";
 
@@ -841,6 +1043,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "Page one content here. Page two content.";
 
@@ -866,6 +1072,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "Page one content here. Page two content.";
 
@@ -898,6 +1108,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "Page one content here. Page two content.";
 
@@ -930,6 +1144,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "First page content here.Second page content here.Third page.";
 
@@ -967,6 +1185,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "All content on single page fits in one chunk.";
 
@@ -991,6 +1213,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "AAAAA BBBBB CCCCC DDDDD";
 
@@ -1026,6 +1252,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "Page One Content Here.Page Two.";
 
@@ -1058,6 +1288,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         let text = "0123456789ABCDEFGHIJ";
 
@@ -1090,4 +1324,94 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn test_chunk_sentence_overlap() {
+        let config = ChunkingConfig {
+            max_characters: 60,
+            overlap: 25,
+            overlap_strategy: OverlapStrategy::Sentences { count: 1 },
+            ..Default::default()
+        };
+        let text = "The cat sat down. The dog ran off. A bird sang loudly. Rain fell all day.";
+        let result = chunk_text(text, &config, None).unwrap();
+
+        assert!(result.chunk_count > 1);
+        for pair in result.chunks.windows(2) {
+            let previous = &pair[0].content;
+            let repeated = pair[1].content.split_inclusive(". ").next().unwrap().trim_end();
+            assert!(previous.ends_with(repeated), "{previous:?} / {repeated:?}");
+        }
+        for chunk in &result.chunks {
+            assert!(chunk.content.chars().count() <= 60);
+            assert_eq!(&text[chunk.metadata.byte_start..chunk.metadata.byte_end], chunk.content);
+        }
+    }
+
+    #[test]
+    fn test_chunk_sentence_overlap_invalid() {
+        let config = ChunkingConfig {
+            max_characters: 100,
+            overlap: 100,
+            overlap_strategy: OverlapStrategy::Sentences { count: 2 },
+            ..Default::default()
+        };
+        assert!(chunk_text("Some text.", &config, None).is_err());
+    }
+
+    #[test]
+    fn test_chunk_sliding_window() {
+        let config = ChunkingConfig {
+            max_characters: 30,
+            overlap_strategy: OverlapStrategy::SlidingWindow { stride: 10 },
+            ..Default::default()
+        };
+        let text = "one two three four five six seven eight nine ten eleven twelve";
+        let result = chunk_text(text, &config, None).unwrap();
+
+        assert!(result.chunk_count > 3);
+        for chunk in &result.chunks {
+            assert!(chunk.content.len() <= 30);
+            assert_eq!(&text[chunk.metadata.byte_start..chunk.metadata.byte_end], chunk.content);
+        }
+        for pair in result.chunks.windows(2) {
+            assert!(pair[1].metadata.byte_start < pair[0].metadata.byte_end);
+        }
+
+        let invalid = ChunkingConfig {
+            overlap_strategy: OverlapStrategy::SlidingWindow { stride: 0 },
+            ..config
+        };
+        assert!(chunk_text(text, &invalid, None).is_err());
+    }
+
+    #[test]
+    fn test_chunk_heading_context() {
+        let config = ChunkingConfig {
+            max_characters: 40,
+            overlap: 0,
+            chunker_type: ChunkerType::Markdown,
+            heading_context: true,
+            ..Default::default()
+        };
+        let text = "# Manual\n\n## Setup\n\nInstall the package first.\n\nThen configure the server.";
+        let result = chunk_text(text, &config, None).unwrap();
+
+        let last = result.chunks.last().unwrap();
+        assert_eq!(last.content, "# Manual\n## Setup\n\nThen configure the server.");
+        assert!(result.chunks[0].content.starts_with("# Manual"));
+    }
+
+    #[test]
+    fn test_overlap_strategy_deserialize() {
+        let config: ChunkingConfig =
+            serde_json::from_str(r#"{"overlap_strategy": {"type": "sentences"}, "heading_context": true}"#).unwrap();
+        assert_eq!(config.overlap_strategy, OverlapStrategy::Sentences { count: 1 });
+        assert!(config.heading_context);
+
+        let config: ChunkingConfig =
+            serde_json::from_str(r#"{"overlap_strategy": {"type": "sliding_window", "stride": 300}}"#).unwrap();
+        assert_eq!(config.overlap_strategy, OverlapStrategy::SlidingWindow { stride: 300 });
+        assert_eq!(ChunkingConfig::default().overlap_strategy, OverlapStrategy::Characters);
+    }
 }
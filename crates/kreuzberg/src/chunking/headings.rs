@@ -0,0 +1,105 @@
+//! Heading context for chunks.
+//!
+//! A chunk cut from the middle of a section loses the heading that says what the
+//! section is about. With `heading_context`, every chunk is prefixed with the Markdown
+//! headings of the sections enclosing its start, outermost first.
+
+use crate::types::Chunk;
+
+/// Prefix every chunk with the headings enclosing its start offset in `text`.
+///
+/// `starts` are the byte offsets of the chunks in `text`. Only ATX headings (`#` to
+/// `######`) outside code fences are recognized. Headings inside the chunk itself are
+/// left where they are. Chunk offsets keep pointing into `text`.
+pub(crate) fn prefix_headings(text: &str, chunks: &mut [Chunk], starts: &[usize]) {
+    let headings = headings(text);
+    for (chunk, &start) in chunks.iter_mut().zip(starts) {
+        let trail = trail(&headings, start);
+        if !trail.is_empty() {
+            chunk.content = format!("{}\n\n{}", trail.join("\n"), chunk.content);
+        }
+    }
+}
+
+/// A heading: byte offset, level and line.
+type Heading<'a> = (usize, usize, &'a str);
+
+fn headings(text: &str) -> Vec<Heading<'_>> {
+    let mut headings = Vec::new();
+    let mut fence: Option<&str> = None;
+    let mut offset = 0;
+    for line in text.split_inclusive('\n') {
+        let line_offset = offset;
+        offset += line.len();
+        let line = line.trim_end();
+        let indented = line.trim_start_matches(' ');
+        if line.len() - indented.len() > 3 {
+            continue;
+        }
+
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|marker| indented.starts_with(marker)) {
+            fence = match fence {
+                Some(open) if open == marker => None,
+                Some(open) => Some(open),
+                None => Some(marker),
+            };
+            continue;
+        }
+        if fence.is_some() {
+            continue;
+        }
+
+        let level = indented.chars().take_while(|&ch| ch == '#').count();
+        let rest = &indented[level..];
+        if (1..=6).contains(&level) && (rest.is_empty() || rest.starts_with([' ', '\t'])) && !rest.trim().is_empty() {
+            headings.push((line_offset, level, indented));
+        }
+    }
+    headings
+}
+
+/// Headings of the sections enclosing `offset`, outermost first.
+fn trail<'a>(headings: &[Heading<'a>], offset: usize) -> Vec<&'a str> {
+    let mut stack: Vec<(usize, &str)> = Vec::new();
+    for &(start, level, line) in headings.iter().take_while(|(start, _, _)| *start <= offset) {
+        while stack.last().is_some_and(|&(open, _)| open >= level) {
+            stack.pop();
+        }
+        // A heading at the chunk start closes the sections it ends but is part of the chunk.
+        if start < offset {
+            stack.push((level, line));
+        }
+    }
+    stack.into_iter().map(|(_, line)| line).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "# Guide\n\nIntro.\n\n## Install\n\n```sh\n# not a heading\n```\n\nRun it.\n\n## Usage\n\n### Flags\n\nUse -v.\n";
+
+    #[test]
+    fn test_trail_follows_nesting() {
+        let headings = headings(TEXT);
+        assert_eq!(headings.len(), 4);
+
+        assert!(trail(&headings, 0).is_empty());
+        assert_eq!(trail(&headings, TEXT.find("Intro").unwrap()), vec!["# Guide"]);
+        assert_eq!(
+            trail(&headings, TEXT.find("Run it").unwrap()),
+            vec!["# Guide", "## Install"]
+        );
+        assert_eq!(
+            trail(&headings, TEXT.find("Use -v").unwrap()),
+            vec!["# Guide", "## Usage", "### Flags"]
+        );
+        assert_eq!(trail(&headings, TEXT.find("## Usage").unwrap()), vec!["# Guide"]);
+    }
+
+    #[test]
+    fn test_headings_need_space() {
+        assert!(headings("#hashtag\n####### seven\n").is_empty());
+        assert_eq!(headings("  ## Two\n").len(), 1);
+    }
+}
@@ -55,12 +55,15 @@ pub mod boundaries;
 mod builder;
 pub mod config;
 pub mod core;
+mod headings;
+mod overlap;
 pub mod processor;
+mod tables;
 pub mod validation;
 
 // Re-export submodule types and functions
 pub use boundaries::{calculate_page_range, validate_page_boundaries};
-pub use config::{ChunkerType, ChunkingConfig, ChunkingResult}; // ChunkingConfig re-exported from core::config::processing
+pub use config::{ChunkerType, ChunkingConfig, ChunkingResult, OverlapStrategy, TableChunkFormat, TableChunkingConfig}; // ChunkingConfig re-exported from core::config::processing
 pub use core::{chunk_text, chunk_text_with_type, chunk_texts_batch};
 pub use processor::ChunkingProcessor;
 pub use validation::{ADAPTIVE_VALIDATION_THRESHOLD, precompute_utf8_boundaries, validate_utf8_boundaries};
@@ -0,0 +1,164 @@
+//! Sentence and sliding-window overlap between chunks.
+//!
+//! Both strategies work on byte spans of the source text instead of chunk copies, so
+//! the chunks they produce carry exact byte offsets.
+
+use std::ops::Range;
+
+/// Spans for `OverlapStrategy::Sentences`.
+///
+/// `spans` are the chunks of a split without overlap, and `sentence_starts` the sorted
+/// offsets at which the sentences of `text` start. Every chunk after the first is
+/// extended backwards over the last `count` sentences of the previous chunk, or fewer
+/// when they do not fit in `max_overlap` characters. Sentences are never cut, so a
+/// chunk whose previous chunk ends in a long sentence gets no overlap at all.
+pub(crate) fn sentence_overlap(
+    text: &str,
+    spans: &[Range<usize>],
+    sentence_starts: &[usize],
+    count: usize,
+    max_overlap: usize,
+) -> Vec<Range<usize>> {
+    spans
+        .iter()
+        .enumerate()
+        .map(|(index, span)| {
+            let start = index
+                .checked_sub(1)
+                .and_then(|previous| {
+                    let previous = &spans[previous];
+                    let first = sentence_starts.partition_point(|&start| start <= previous.start);
+                    let last = sentence_starts.partition_point(|&start| start < previous.end);
+                    std::iter::once(previous.start)
+                        .chain(sentence_starts[first..last].iter().copied())
+                        .rev()
+                        .take(count)
+                        .take_while(|&start| text[start..previous.end].chars().count() <= max_overlap)
+                        .last()
+                })
+                .unwrap_or(span.start);
+            start..span.end
+        })
+        .collect()
+}
+
+/// Spans for `OverlapStrategy::SlidingWindow`.
+///
+/// A window of `max_characters` starts every `stride` characters. A window starting
+/// inside a word moves forward to the next word, and a window ending inside a word
+/// ends at the previous word boundary instead, unless the window is a single word.
+/// Windows already covered by the previous one are skipped.
+pub(crate) fn sliding_windows(text: &str, max_characters: usize, stride: usize, trim: bool) -> Vec<Range<usize>> {
+    let offsets: Vec<usize> = text
+        .char_indices()
+        .map(|(offset, _)| offset)
+        .chain(std::iter::once(text.len()))
+        .collect();
+    let char_count = offsets.len() - 1;
+
+    let mut windows: Vec<Range<usize>> = Vec::new();
+    let mut first_char = 0;
+    while first_char < char_count {
+        let raw_start = offsets[first_char];
+        let raw_end = offsets[(first_char + max_characters).min(char_count)];
+
+        let mut start = raw_start;
+        if starts_inside_word(text, raw_start) {
+            let rest = &text[raw_start..raw_end];
+            if let Some(space) = rest.find(char::is_whitespace) {
+                let after = &rest[space..];
+                start = raw_end - after.trim_start().len();
+            }
+        }
+
+        let mut end = raw_end;
+        if starts_inside_word(text, raw_end)
+            && let Some((space, _)) = text[start..raw_end]
+                .char_indices()
+                .rev()
+                .find(|(_, ch)| ch.is_whitespace())
+                .filter(|&(space, _)| space > 0)
+        {
+            end = start + space;
+        }
+
+        if trim {
+            let window = &text[start..end];
+            end = start + window.trim_end().len();
+            start = end - window[..end - start].trim_start().len();
+        }
+        if start < end && windows.last().is_none_or(|last| end > last.end) {
+            windows.push(start..end);
+        }
+
+        if raw_end == text.len() {
+            break;
+        }
+        first_char += stride;
+    }
+    windows
+}
+
+/// Whether `offset` falls between two non-whitespace characters.
+fn starts_inside_word(text: &str, offset: usize) -> bool {
+    let before = text[..offset].chars().next_back();
+    let after = text[offset..].chars().next();
+    matches!((before, after), (Some(before), Some(after)) if !before.is_whitespace() && !after.is_whitespace())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::text::sentences::RuleBasedSegmenter;
+
+    fn slices<'a>(text: &'a str, spans: &[Range<usize>]) -> Vec<&'a str> {
+        spans.iter().map(|span| &text[span.clone()]).collect()
+    }
+
+    #[test]
+    fn test_sentence_overlap_keeps_whole_sentences() {
+        let text = "First one. Second one. Third one. Fourth one.";
+        let spans = vec![0..22, 23..45];
+        let starts: Vec<usize> = RuleBasedSegmenter::new()
+            .split(text)
+            .into_iter()
+            .map(|sentence| sentence.start)
+            .collect();
+
+        let overlapped = sentence_overlap(text, &spans, &starts, 1, 20);
+        assert_eq!(
+            slices(text, &overlapped),
+            vec!["First one. Second one.", "Second one. Third one. Fourth one."]
+        );
+
+        let overlapped = sentence_overlap(text, &spans, &starts, 2, 30);
+        assert_eq!(
+            slices(text, &overlapped)[1],
+            "First one. Second one. Third one. Fourth one."
+        );
+
+        let overlapped = sentence_overlap(text, &spans, &starts, 2, 5);
+        assert_eq!(overlapped, spans);
+    }
+
+    #[test]
+    fn test_sliding_windows_snap_to_words() {
+        let text = "alpha beta gamma delta epsilon";
+        let windows = sliding_windows(text, 12, 6, true);
+        assert_eq!(
+            slices(text, &windows),
+            vec!["alpha beta", "beta gamma", "delta", "epsilon"]
+        );
+        assert!(windows.windows(2).all(|pair| pair[0].start < pair[1].start));
+    }
+
+    #[test]
+    fn test_sliding_windows_cover_text() {
+        let text = "word ".repeat(40);
+        let windows = sliding_windows(&text, 50, 20, true);
+        assert_eq!(windows.first().unwrap().start, 0);
+        assert_eq!(windows.last().unwrap().end, text.trim_end().len());
+        assert!(windows.iter().all(|window| window.len() <= 50));
+        assert!(windows.windows(2).all(|pair| pair[1].start < pair[0].end));
+    }
+}
@@ -93,6 +93,10 @@ mod tests {
                 chunker_type: crate::chunking::ChunkerType::Text,
                 embedding: None,
                 preset: None,
+                overlap_strategy: crate::chunking::OverlapStrategy::Characters,
+                heading_context: false,
+                tables: None,
+                sentence_segmenter: None,
             }),
             ..Default::default()
         };
@@ -106,6 +110,7 @@ mod tests {
 	            chunks: None,
 	            images: None,
             djot_content: None,
+            scripts: None,
 	            pages: None,
 	            elements: None,
 	        };
@@ -131,6 +136,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -168,6 +174,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -180,6 +187,10 @@ mod tests {
                 chunker_type: crate::chunking::ChunkerType::Text,
                 embedding: None,
                 preset: None,
+                overlap_strategy: crate::chunking::OverlapStrategy::Characters,
+                heading_context: false,
+                tables: None,
+                sentence_segmenter: None,
             }),
             ..Default::default()
         };
@@ -202,6 +213,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -215,6 +227,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -0,0 +1,326 @@
+//! Table-aware chunking.
+//!
+//! Text splitters know nothing about tables and cut them wherever the size limit
+//! falls, leaving rows without their header or split in half. With
+//! `ChunkingConfig::tables`, Markdown pipe tables are taken out of the text first:
+//! the text between them is chunked as usual, and every table becomes one chunk, or
+//! several groups of whole rows each under the repeated header, marked with
+//! `ChunkType::Table`.
+
+use crate::core::config::processing::{ChunkingConfig, TableChunkFormat, TableChunkingConfig};
+use crate::error::Result;
+use crate::types::{Chunk, ChunkMetadata, ChunkType, PageBoundary};
+use std::ops::Range;
+
+use super::boundaries::calculate_page_range;
+use super::core::split_chunks;
+
+/// A Markdown pipe table in the text.
+#[derive(Debug)]
+struct TableBlock {
+    /// Header row and delimiter row
+    header: Range<usize>,
+    /// Body rows, one range per line
+    rows: Vec<Range<usize>>,
+}
+
+impl TableBlock {
+    fn range(&self) -> Range<usize> {
+        self.header.start..self.rows.last().map_or(self.header.end, |row| row.end)
+    }
+}
+
+/// Chunk `text`, emitting every table as table chunks and splitting the text around
+/// them with `config`. Returns the chunks along with their start offsets.
+pub(crate) fn chunk_with_tables(
+    text: &str,
+    config: &ChunkingConfig,
+    tables: &TableChunkingConfig,
+    page_boundaries: Option<&[PageBoundary]>,
+) -> Result<(Vec<Chunk>, Vec<usize>)> {
+    let mut chunks = Vec::new();
+    let mut starts = Vec::new();
+    let mut position = 0;
+
+    for table in find_tables(text) {
+        let range = table.range();
+        split_segment(text, position..range.start, config, &mut chunks, &mut starts)?;
+        for (span, content) in table_chunks(text, &table, config.max_characters, tables) {
+            starts.push(span.start);
+            chunks.push(Chunk {
+                content,
+                embedding: None,
+                metadata: ChunkMetadata {
+                    byte_start: span.start,
+                    byte_end: span.end,
+                    token_count: None,
+                    chunk_index: 0,
+                    total_chunks: 0,
+                    first_page: None,
+                    last_page: None,
+                    chunk_type: ChunkType::Table,
+                    additional: Default::default(),
+                },
+            });
+        }
+        position = range.end;
+    }
+    split_segment(text, position..text.len(), config, &mut chunks, &mut starts)?;
+
+    let total_chunks = chunks.len();
+    for (index, chunk) in chunks.iter_mut().enumerate() {
+        chunk.metadata.chunk_index = index;
+        chunk.metadata.total_chunks = total_chunks;
+        if let Some(boundaries) = page_boundaries {
+            (chunk.metadata.first_page, chunk.metadata.last_page) =
+                calculate_page_range(chunk.metadata.byte_start, chunk.metadata.byte_end, boundaries)?;
+        }
+    }
+
+    Ok((chunks, starts))
+}
+
+/// Chunk the text in `segment` and append the chunks with offsets into `text`.
+fn split_segment(
+    text: &str,
+    segment: Range<usize>,
+    config: &ChunkingConfig,
+    chunks: &mut Vec<Chunk>,
+    starts: &mut Vec<usize>,
+) -> Result<()> {
+    let offset = segment.start;
+    let segment = &text[segment];
+    if segment.trim().is_empty() {
+        return Ok(());
+    }
+
+    let (segment_chunks, segment_starts) = split_chunks(segment, config, None)?;
+    chunks.extend(segment_chunks.into_iter().map(|mut chunk| {
+        chunk.metadata.byte_start += offset;
+        chunk.metadata.byte_end += offset;
+        chunk
+    }));
+    starts.extend(segment_starts.into_iter().map(|start| start + offset));
+    Ok(())
+}
+
+/// Markdown pipe tables of `text`, outside code fences.
+///
+/// A table is a run of lines starting with `|` whose second line is a delimiter row.
+fn find_tables(text: &str) -> Vec<TableBlock> {
+    let mut tables = Vec::new();
+    let mut run: Vec<Range<usize>> = Vec::new();
+    let mut in_fence = false;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let start = offset;
+        offset += line.len();
+        let content = line.trim_end();
+        let trimmed = content.trim_start();
+
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        }
+        if !in_fence && trimmed.starts_with('|') {
+            run.push(start..start + content.len());
+            continue;
+        }
+        close_run(text, &mut run, &mut tables);
+    }
+    close_run(text, &mut run, &mut tables);
+    tables
+}
+
+fn close_run(text: &str, run: &mut Vec<Range<usize>>, tables: &mut Vec<TableBlock>) {
+    let lines = std::mem::take(run);
+    if lines.len() >= 2 && is_delimiter_row(&text[lines[1].clone()]) {
+        tables.push(TableBlock {
+            header: lines[0].start..lines[1].end,
+            rows: lines[2..].to_vec(),
+        });
+    }
+}
+
+fn is_delimiter_row(line: &str) -> bool {
+    let line = line.trim();
+    line.contains('-') && line.chars().all(|ch| matches!(ch, '|' | '-' | ':' | ' ' | '\t'))
+}
+
+/// Spans and contents of the chunks of one table.
+fn table_chunks(
+    text: &str,
+    table: &TableBlock,
+    max_characters: usize,
+    config: &TableChunkingConfig,
+) -> Vec<(Range<usize>, String)> {
+    let header = match config.format {
+        TableChunkFormat::Markdown => text[table.header.clone()].to_string(),
+        TableChunkFormat::Csv => {
+            let first_line = text[table.header.clone()].lines().next().unwrap_or_default();
+            csv_row(first_line)
+        }
+    };
+    let rows: Vec<String> = table
+        .rows
+        .iter()
+        .map(|row| match config.format {
+            TableChunkFormat::Markdown => text[row.clone()].trim().to_string(),
+            TableChunkFormat::Csv => csv_row(&text[row.clone()]),
+        })
+        .collect();
+
+    let header_chars = header.chars().count();
+    let total_chars = header_chars + rows.iter().map(|row| row.chars().count() + 1).sum::<usize>();
+    if !config.split_rows || rows.is_empty() || total_chars <= max_characters {
+        let mut content = header;
+        for row in &rows {
+            content.push('\n');
+            content.push_str(row);
+        }
+        return vec![(table.range(), content)];
+    }
+
+    let mut chunks = Vec::new();
+    let mut first = 0;
+    while first < rows.len() {
+        let mut last = first;
+        let mut chars = header_chars + rows[first].chars().count() + 1;
+        while let Some(next) = rows.get(last + 1) {
+            let next_chars = next.chars().count() + 1;
+            if chars + next_chars > max_characters {
+                break;
+            }
+            chars += next_chars;
+            last += 1;
+        }
+
+        let start = if first == 0 {
+            table.header.start
+        } else {
+            table.rows[first].start
+        };
+        let mut content = header.clone();
+        for row in &rows[first..=last] {
+            content.push('\n');
+            content.push_str(row);
+        }
+        chunks.push((start..table.rows[last].end, content));
+        first = last + 1;
+    }
+    chunks
+}
+
+/// The cells of a Markdown table row as a CSV record.
+fn csv_row(line: &str) -> String {
+    let line = line.trim();
+    let line = line.strip_prefix('|').unwrap_or(line);
+    let line = line
+        .strip_suffix('|')
+        .filter(|_| !line.ends_with("\\|"))
+        .unwrap_or(line);
+
+    let mut cells = vec![String::new()];
+    let mut chars = line.chars().peekable();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '\\' if chars.peek() == Some(&'|') => {
+                cells.last_mut().unwrap().push('|');
+                chars.next();
+            }
+            '|' => cells.push(String::new()),
+            _ => cells.last_mut().unwrap().push(ch),
+        }
+    }
+
+    cells
+        .iter()
+        .map(|cell| {
+            let cell = cell.trim();
+            if cell.contains([',', '"', '\n']) {
+                format!("\"{}\"", cell.replace('"', "\"\""))
+            } else {
+                cell.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEXT: &str = "Quarterly results.\n\n| Region | Sales |\n|---|---:|\n| North | 1,200 |\n| South | 950 |\n| East | 1,010 |\n\nSales grew in every region.";
+
+    fn config(max_characters: usize, tables: TableChunkingConfig) -> ChunkingConfig {
+        ChunkingConfig {
+            max_characters,
+            overlap: 0,
+            tables: Some(tables),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_find_tables() {
+        let tables = find_tables(TEXT);
+        assert_eq!(tables.len(), 1);
+        assert_eq!(&TEXT[tables[0].header.clone()], "| Region | Sales |\n|---|---:|");
+        assert_eq!(tables[0].rows.len(), 3);
+        assert!(TEXT[tables[0].range()].ends_with("| East | 1,010 |"));
+
+        assert!(find_tables("| not | a table |\nplain text").is_empty());
+        assert!(find_tables("```\n| a | b |\n|---|---|\n```").is_empty());
+    }
+
+    #[test]
+    fn test_table_is_one_chunk() {
+        let (chunks, starts) = chunk_with_tables(
+            TEXT,
+            &config(200, TableChunkingConfig::default()),
+            &TableChunkingConfig::default(),
+            None,
+        )
+        .unwrap();
+
+        let types: Vec<ChunkType> = chunks.iter().map(|chunk| chunk.metadata.chunk_type).collect();
+        assert_eq!(types, vec![ChunkType::Text, ChunkType::Table, ChunkType::Text]);
+        assert!(chunks[1].content.starts_with("| Region | Sales |"));
+        assert!(chunks[1].content.ends_with("| East | 1,010 |"));
+        assert_eq!(chunks[2].content, "Sales grew in every region.");
+        assert_eq!(starts[2], TEXT.find("Sales grew").unwrap());
+        assert!(
+            chunks
+                .iter()
+                .enumerate()
+                .all(|(index, chunk)| chunk.metadata.chunk_index == index && chunk.metadata.total_chunks == 3)
+        );
+    }
+
+    #[test]
+    fn test_long_table_split_with_header() {
+        let tables = TableChunkingConfig {
+            format: TableChunkFormat::Csv,
+            split_rows: true,
+        };
+        let (chunks, _) = chunk_with_tables(TEXT, &config(40, tables), &tables, None).unwrap();
+
+        let table_chunks: Vec<&Chunk> = chunks
+            .iter()
+            .filter(|chunk| chunk.metadata.chunk_type == ChunkType::Table)
+            .collect();
+        assert_eq!(table_chunks.len(), 2);
+        assert_eq!(table_chunks[0].content, "Region,Sales\nNorth,\"1,200\"\nSouth,950");
+        assert_eq!(table_chunks[1].content, "Region,Sales\nEast,\"1,010\"");
+        assert_eq!(
+            &TEXT[table_chunks[1].metadata.byte_start..table_chunks[1].metadata.byte_end],
+            "| East | 1,010 |"
+        );
+    }
+
+    #[test]
+    fn test_csv_row_escapes() {
+        assert_eq!(csv_row("| a \\| b | say \"hi\" |  |"), "a | b,\"say \"\"hi\"\"\",");
+    }
+}
@@ -0,0 +1,244 @@
+//! Compression of serialized results.
+//!
+//! Results written to disk sinks and the cache are compressed with the
+//! [`Compression`] of [`CacheConfig`](crate::CacheConfig). Values are serialized
+//! straight into the encoder, so a large result never exists uncompressed in memory
+//! next to its compressed form.
+//!
+//! Reading detects gzip and Zstandard data by its magic bytes, so compressed and
+//! uncompressed files can be mixed. Codecs require the `compression` feature; without
+//! it, compressing or reading compressed data fails with a missing dependency error.
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[cfg(feature = "compression")]
+//! # {
+//! use kreuzberg::compression::{compress_json, decompress};
+//! use kreuzberg::Compression;
+//!
+//! let compressed = compress_json(&serde_json::json!({"content": "Hello"}), Compression::Zstd)?;
+//! let json = decompress(&compressed)?;
+//! assert_eq!(&*json, br#"{"content":"Hello"}"#);
+//! # }
+//! # Ok::<(), kreuzberg::KreuzbergError>(())
+//! ```
+
+use crate::Result;
+use crate::core::config::Compression;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::io::{BufRead, Read, Write};
+
+/// Magic bytes at the start of gzip data.
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Magic bytes at the start of a Zstandard frame.
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// A writer that compresses everything written to it.
+///
+/// Call [`finish`](Self::finish) after the last write; dropping the writer without
+/// finishing it leaves the compressed data incomplete.
+pub struct CompressedWriter<W: Write> {
+    inner: Encoder<W>,
+}
+
+enum Encoder<W: Write> {
+    Plain(W),
+    #[cfg(feature = "compression")]
+    Gzip(flate2::write::GzEncoder<W>),
+    #[cfg(feature = "compression")]
+    Zstd(zstd::stream::write::Encoder<'static, W>),
+}
+
+impl<W: Write> CompressedWriter<W> {
+    /// Wrap `writer` so everything written to it is compressed with `compression`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::MissingDependency` when `compression` is not `None` and
+    /// the `compression` feature is not enabled.
+    pub fn new(writer: W, compression: Compression) -> Result<Self> {
+        let inner = match compression {
+            Compression::None => Encoder::Plain(writer),
+            #[cfg(feature = "compression")]
+            Compression::Gzip => Encoder::Gzip(flate2::write::GzEncoder::new(writer, flate2::Compression::default())),
+            #[cfg(feature = "compression")]
+            Compression::Zstd => Encoder::Zstd(zstd::stream::write::Encoder::new(writer, 0)?),
+            #[cfg(not(feature = "compression"))]
+            Compression::Gzip | Compression::Zstd => return Err(missing_feature()),
+        };
+        Ok(Self { inner })
+    }
+
+    /// Complete the compressed data and return the underlying writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the end of the compressed data cannot be written.
+    pub fn finish(self) -> Result<W> {
+        Ok(match self.inner {
+            Encoder::Plain(writer) => writer,
+            #[cfg(feature = "compression")]
+            Encoder::Gzip(encoder) => encoder.finish()?,
+            #[cfg(feature = "compression")]
+            Encoder::Zstd(encoder) => encoder.finish()?,
+        })
+    }
+}
+
+impl<W: Write> Write for CompressedWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        match &mut self.inner {
+            Encoder::Plain(writer) => writer.write(buf),
+            #[cfg(feature = "compression")]
+            Encoder::Gzip(encoder) => encoder.write(buf),
+            #[cfg(feature = "compression")]
+            Encoder::Zstd(encoder) => encoder.write(buf),
+        }
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        match &mut self.inner {
+            Encoder::Plain(writer) => writer.flush(),
+            #[cfg(feature = "compression")]
+            Encoder::Gzip(encoder) => encoder.flush(),
+            #[cfg(feature = "compression")]
+            Encoder::Zstd(encoder) => encoder.flush(),
+        }
+    }
+}
+
+/// Serialize `value` as JSON into `writer`, compressed with `compression`.
+///
+/// # Errors
+///
+/// Returns an error if `value` cannot be serialized or `writer` fails, and
+/// `KreuzbergError::MissingDependency` as [`CompressedWriter::new`].
+pub fn write_json<T: Serialize + ?Sized, W: Write>(value: &T, writer: W, compression: Compression) -> Result<W> {
+    let mut writer = CompressedWriter::new(writer, compression)?;
+    serde_json::to_writer(&mut writer, value)?;
+    writer.finish()
+}
+
+/// Serialize `value` as JSON, compressed with `compression`.
+///
+/// # Errors
+///
+/// Same as [`write_json`].
+pub fn compress_json<T: Serialize + ?Sized>(value: &T, compression: Compression) -> Result<Vec<u8>> {
+    write_json(value, Vec::new(), compression)
+}
+
+/// Compress `data` with `compression`.
+///
+/// # Errors
+///
+/// Same as [`CompressedWriter::new`].
+pub fn compress(data: &[u8], compression: Compression) -> Result<Cow<'_, [u8]>> {
+    if compression == Compression::None {
+        return Ok(Cow::Borrowed(data));
+    }
+    let mut writer = CompressedWriter::new(Vec::new(), compression)?;
+    writer.write_all(data)?;
+    Ok(Cow::Owned(writer.finish()?))
+}
+
+/// The compression `data` starts with, `None` for data that is not compressed.
+pub fn detect(data: &[u8]) -> Compression {
+    if data.starts_with(&ZSTD_MAGIC) {
+        Compression::Zstd
+    } else if data.starts_with(&GZIP_MAGIC) {
+        Compression::Gzip
+    } else {
+        Compression::None
+    }
+}
+
+/// Decompress `data` if it is gzip or Zstandard data, or return it as is.
+///
+/// # Errors
+///
+/// Returns an I/O error if compressed data is corrupt, and
+/// `KreuzbergError::MissingDependency` if it is compressed and the `compression`
+/// feature is not enabled.
+pub fn decompress(data: &[u8]) -> Result<Cow<'_, [u8]>> {
+    if detect(data) == Compression::None {
+        return Ok(Cow::Borrowed(data));
+    }
+    let mut decompressed = Vec::new();
+    decompressing_reader(data)?.read_to_end(&mut decompressed)?;
+    Ok(Cow::Owned(decompressed))
+}
+
+/// Wrap `reader` so gzip and Zstandard data is decompressed while it is read.
+///
+/// The format is detected from the first bytes of `reader`. Concatenated gzip members
+/// and Zstandard frames, as written by appending compressed records, are read as one
+/// stream.
+///
+/// # Errors
+///
+/// Returns an I/O error if `reader` fails, and `KreuzbergError::MissingDependency` if
+/// the data is compressed and the `compression` feature is not enabled.
+pub fn decompressing_reader<'a, R: BufRead + 'a>(mut reader: R) -> Result<Box<dyn Read + 'a>> {
+    let compression = detect(reader.fill_buf()?);
+    Ok(match compression {
+        Compression::None => Box::new(reader),
+        #[cfg(feature = "compression")]
+        Compression::Gzip => Box::new(flate2::bufread::MultiGzDecoder::new(reader)),
+        #[cfg(feature = "compression")]
+        Compression::Zstd => Box::new(zstd::stream::read::Decoder::with_buffer(reader)?),
+        #[cfg(not(feature = "compression"))]
+        Compression::Gzip | Compression::Zstd => return Err(missing_feature()),
+    })
+}
+
+#[cfg(not(feature = "compression"))]
+fn missing_feature() -> crate::KreuzbergError {
+    crate::KreuzbergError::MissingDependency("Compressed results require the `compression` feature".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uncompressed_data_passes_through() {
+        let json = compress_json(&serde_json::json!({"a": 1}), Compression::None).unwrap();
+        assert_eq!(json, br#"{"a":1}"#);
+        assert!(matches!(decompress(&json).unwrap(), Cow::Borrowed(_)));
+        assert!(matches!(compress(&json, Compression::None).unwrap(), Cow::Borrowed(_)));
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_compressed_round_trip() {
+        let value = serde_json::json!({"content": "text ".repeat(1000)});
+        let plain = serde_json::to_vec(&value).unwrap();
+
+        for compression in [Compression::Gzip, Compression::Zstd] {
+            let compressed = compress_json(&value, compression).unwrap();
+            assert_eq!(detect(&compressed), compression);
+            assert!(compressed.len() < plain.len() / 10);
+            assert_eq!(decompress(&compressed).unwrap(), plain);
+
+            let mut records = compress(b"one\n", compression).unwrap().into_owned();
+            records.extend_from_slice(&compress(b"two\n", compression).unwrap());
+            let mut read = String::new();
+            decompressing_reader(records.as_slice())
+                .unwrap()
+                .read_to_string(&mut read)
+                .unwrap();
+            assert_eq!(read, "one\ntwo\n");
+        }
+    }
+
+    #[cfg(not(feature = "compression"))]
+    #[test]
+    fn test_compression_requires_feature() {
+        assert!(compress_json(&1, Compression::Zstd).is_err());
+        assert!(decompress(&GZIP_MAGIC).is_err());
+    }
+}
@@ -0,0 +1,617 @@
+//! Scheduling of extraction jobs from many tenants.
+//!
+//! A [`BatchScheduler`] runs the extraction jobs of a shared extraction service. Every
+//! tenant (a customer, an upload source) has its own queue, ordered by job priority,
+//! and the queues are served in proportion to their weights, so a tenant flooding the
+//! scheduler with uploads slows down its own jobs rather than everyone else's. A
+//! tenant can also be given a rate limit, which caps how many of its jobs start per
+//! second no matter how idle the scheduler is.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use kreuzberg::core::batch_scheduler::{BatchJob, BatchScheduler, BatchSchedulerConfig};
+//! use kreuzberg::core::config::ExtractionConfig;
+//!
+//! # async fn example() -> kreuzberg::Result<()> {
+//! let config: BatchSchedulerConfig = toml::from_str(
+//!     r#"
+//!     max_concurrent = 8
+//!
+//!     [tenants.acme]
+//!     weight = 3
+//!
+//!     [tenants.trial]
+//!     rate_limit = { per_second = 2.0, burst = 10 }
+//!     "#,
+//! )
+//! .unwrap();
+//! let scheduler = BatchScheduler::new(config, ExtractionConfig::default())?;
+//!
+//! let urgent = scheduler.submit(BatchJob::file("acme", "invoice.pdf").with_priority(10));
+//! let routine = scheduler.submit(BatchJob::file("trial", "report.docx"));
+//! println!("{}", urgent.await?.content);
+//! println!("{}", routine.await?.content);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::config::ExtractionConfig;
+use crate::types::ExtractionResult;
+use crate::{KreuzbergError, Result};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+use std::future::Future;
+use std::path::PathBuf;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+use tokio::sync::{Notify, Semaphore, oneshot};
+
+use super::extractor::batch::extract_with_retries;
+use super::extractor::{extract_bytes, extract_file};
+
+/// Rate at which the jobs of a tenant may start, as a token bucket.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct RateLimit {
+    /// Jobs started per second on average
+    pub per_second: f64,
+
+    /// Jobs that may start at once after an idle period
+    ///
+    /// Default: 1
+    #[serde(default = "default_burst")]
+    pub burst: u32,
+}
+
+/// Scheduling settings of a tenant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TenantConfig {
+    /// Share of the extraction slots the tenant gets relative to other busy tenants
+    ///
+    /// Default: 1
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+
+    /// Limit on how fast the jobs of the tenant start (None = unlimited)
+    #[serde(default)]
+    pub rate_limit: Option<RateLimit>,
+}
+
+impl Default for TenantConfig {
+    fn default() -> Self {
+        Self {
+            weight: default_weight(),
+            rate_limit: None,
+        }
+    }
+}
+
+/// Configuration of a [`BatchScheduler`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BatchSchedulerConfig {
+    /// Maximum concurrent extractions (None = 1.5 × the number of CPUs)
+    #[serde(default)]
+    pub max_concurrent: Option<usize>,
+
+    /// Settings of individual tenants, by name
+    #[serde(default)]
+    pub tenants: HashMap<String, TenantConfig>,
+
+    /// Settings of the tenants not listed in `tenants`
+    #[serde(default)]
+    pub default_tenant: TenantConfig,
+}
+
+impl BatchSchedulerConfig {
+    fn tenant(&self, name: &str) -> &TenantConfig {
+        self.tenants.get(name).unwrap_or(&self.default_tenant)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.max_concurrent == Some(0) {
+            return Err(KreuzbergError::validation("max_concurrent must be at least 1"));
+        }
+        let tenants = self
+            .tenants
+            .iter()
+            .map(|(name, tenant)| (name.as_str(), tenant))
+            .chain([("default_tenant", &self.default_tenant)]);
+        for (name, tenant) in tenants {
+            if tenant.weight == 0 {
+                return Err(KreuzbergError::validation(format!(
+                    "Weight of tenant '{}' must be at least 1",
+                    name
+                )));
+            }
+            if let Some(limit) = tenant.rate_limit
+                && (!limit.per_second.is_finite() || limit.per_second <= 0.0 || limit.burst == 0)
+            {
+                return Err(KreuzbergError::validation(format!(
+                    "Rate limit of tenant '{}' must allow a positive number of jobs per second and a burst of at least 1",
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+fn default_burst() -> u32 {
+    1
+}
+
+fn default_weight() -> u32 {
+    1
+}
+
+/// What a [`BatchJob`] extracts.
+#[derive(Debug, Clone)]
+enum JobInput {
+    File(PathBuf),
+    Bytes { content: Vec<u8>, mime_type: String },
+}
+
+/// An extraction job submitted to a [`BatchScheduler`].
+#[derive(Debug, Clone)]
+pub struct BatchJob {
+    tenant: String,
+    priority: i32,
+    input: JobInput,
+    config: Option<Arc<ExtractionConfig>>,
+}
+
+impl BatchJob {
+    /// A job of `tenant` extracting the file at `path`.
+    pub fn file(tenant: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self::new(tenant, JobInput::File(path.into()))
+    }
+
+    /// A job of `tenant` extracting `content` of type `mime_type`.
+    pub fn bytes(tenant: impl Into<String>, content: Vec<u8>, mime_type: impl Into<String>) -> Self {
+        Self::new(
+            tenant,
+            JobInput::Bytes {
+                content,
+                mime_type: mime_type.into(),
+            },
+        )
+    }
+
+    fn new(tenant: impl Into<String>, input: JobInput) -> Self {
+        Self {
+            tenant: tenant.into(),
+            priority: 0,
+            input,
+            config: None,
+        }
+    }
+
+    /// Run the job before the jobs of its tenant with a lower priority (default 0).
+    ///
+    /// Priorities order the queue of a tenant; they do not let a tenant overtake others.
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
+    /// Extract with `config` instead of the configuration of the scheduler.
+    pub fn with_config(mut self, config: ExtractionConfig) -> Self {
+        self.config = Some(Arc::new(config));
+        self
+    }
+
+    /// Extract the input, retrying as the retry policy of the job's configuration allows.
+    async fn run(&self, default_config: &ExtractionConfig) -> Result<ExtractionResult> {
+        let config = self.config.as_deref().unwrap_or(default_config);
+        let path = match &self.input {
+            JobInput::File(path) => Some(path.as_path()),
+            JobInput::Bytes { .. } => None,
+        };
+        extract_with_retries(config, path, None, || async {
+            match &self.input {
+                JobInput::File(path) => extract_file(path, None, config).await,
+                JobInput::Bytes { content, mime_type } => extract_bytes(content, mime_type, config).await,
+            }
+        })
+        .await
+    }
+}
+
+/// Result of a job submitted to a [`BatchScheduler`]; await it for the extraction result.
+///
+/// Dropping the handle does not cancel the job.
+#[derive(Debug)]
+pub struct JobHandle {
+    receiver: oneshot::Receiver<Result<ExtractionResult>>,
+}
+
+impl Future for JobHandle {
+    type Output = Result<ExtractionResult>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.receiver).poll(cx).map(|received| {
+            received.unwrap_or_else(|_| {
+                Err(KreuzbergError::Other(
+                    "Batch scheduler was dropped before the job finished".to_string(),
+                ))
+            })
+        })
+    }
+}
+
+/// Runs extraction jobs of many tenants fairly, with per-tenant priorities and rate limits.
+///
+/// Busy tenants share the extraction slots in proportion to their weights (weighted
+/// fair queuing): with weights 3 and 1, the first tenant starts three jobs for every
+/// job of the second while both have jobs queued. Within a tenant, jobs start by
+/// priority, and in submission order among equal priorities.
+///
+/// Dropping the scheduler discards the jobs that have not started yet.
+pub struct BatchScheduler {
+    shared: Arc<Shared>,
+    dispatcher: tokio::task::JoinHandle<()>,
+}
+
+impl BatchScheduler {
+    /// Create a scheduler running jobs with `extraction_config` unless they bring their own.
+    ///
+    /// Must be called within a Tokio runtime, which runs the jobs.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if a weight, rate limit or `max_concurrent` is zero.
+    pub fn new(config: BatchSchedulerConfig, extraction_config: ExtractionConfig) -> Result<Self> {
+        config.validate()?;
+        let max_concurrent = config
+            .max_concurrent
+            .unwrap_or_else(|| (num_cpus::get() as f64 * 1.5).ceil() as usize)
+            .max(1);
+
+        let shared = Arc::new(Shared {
+            state: Mutex::new(State::new(config)),
+            notify: Notify::new(),
+        });
+        let dispatcher = tokio::spawn(dispatch(
+            Arc::clone(&shared),
+            max_concurrent,
+            Arc::new(extraction_config),
+        ));
+        Ok(Self { shared, dispatcher })
+    }
+
+    /// Queue `job` and return a handle to its result.
+    pub fn submit(&self, job: BatchJob) -> JobHandle {
+        let receiver = self.shared.state.lock().push(job, Instant::now());
+        self.shared.notify.notify_one();
+        JobHandle { receiver }
+    }
+
+    /// Number of jobs of `tenant` waiting to start.
+    pub fn queued(&self, tenant: &str) -> usize {
+        self.shared
+            .state
+            .lock()
+            .tenants
+            .get(tenant)
+            .map_or(0, |queue| queue.jobs.len())
+    }
+}
+
+impl Drop for BatchScheduler {
+    fn drop(&mut self) {
+        self.dispatcher.abort();
+    }
+}
+
+struct Shared {
+    state: Mutex<State>,
+    notify: Notify,
+}
+
+/// Start the queued jobs as extraction slots become free.
+async fn dispatch(shared: Arc<Shared>, max_concurrent: usize, config: Arc<ExtractionConfig>) {
+    let slots = Arc::new(Semaphore::new(max_concurrent));
+    loop {
+        let Ok(slot) = Arc::clone(&slots).acquire_owned().await else {
+            return;
+        };
+        let queued = loop {
+            let next = shared.state.lock().next(Instant::now());
+            match next {
+                Next::Job(queued) => break queued,
+                Next::Wait(until) => {
+                    tokio::select! {
+                        _ = shared.notify.notified() => {}
+                        _ = tokio::time::sleep_until(until.into()) => {}
+                    }
+                }
+                Next::Idle => shared.notify.notified().await,
+            }
+        };
+
+        let config = Arc::clone(&config);
+        tokio::spawn(async move {
+            let _slot = slot;
+            let result = crate::core::batch_mode::with_batch_mode(queued.job.run(&config)).await;
+            let _ = queued.sender.send(result);
+        });
+    }
+}
+
+/// A job waiting in the queue of its tenant.
+struct Queued {
+    job: BatchJob,
+    sequence: u64,
+    sender: oneshot::Sender<Result<ExtractionResult>>,
+}
+
+impl PartialEq for Queued {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Queued {}
+
+impl PartialOrd for Queued {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Queued {
+    /// Higher priority first, then earlier submission.
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.job
+            .priority
+            .cmp(&other.job.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+/// Token bucket enforcing a [`RateLimit`].
+struct TokenBucket {
+    limit: RateLimit,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl TokenBucket {
+    fn new(limit: RateLimit, now: Instant) -> Self {
+        Self {
+            limit,
+            tokens: f64::from(limit.burst),
+            updated: now,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.limit.per_second).min(f64::from(self.limit.burst));
+        self.updated = now;
+    }
+
+    /// When the next job may start, after a refill at `now`.
+    fn ready_at(&self, now: Instant) -> Instant {
+        let missing = (1.0 - self.tokens).max(0.0);
+        now + Duration::from_secs_f64(missing / self.limit.per_second)
+    }
+
+    fn is_full(&self) -> bool {
+        self.tokens >= f64::from(self.limit.burst)
+    }
+}
+
+/// Queue of one tenant.
+struct TenantQueue {
+    jobs: BinaryHeap<Queued>,
+    weight: u32,
+    bucket: Option<TokenBucket>,
+    /// Virtual time at which the next job of the tenant is due; advances by
+    /// `1 / weight` with every job started.
+    pass: f64,
+}
+
+/// What the dispatcher should do next.
+enum Next {
+    Job(Queued),
+    /// Jobs are queued, but only by rate-limited tenants, the first of which may
+    /// start a job at this instant
+    Wait(Instant),
+    Idle,
+}
+
+/// Queues of all tenants with queued jobs, or with a rate limit still recovering.
+struct State {
+    config: BatchSchedulerConfig,
+    tenants: HashMap<String, TenantQueue>,
+    sequence: u64,
+    /// Pass of the job started last; tenants becoming busy start from it.
+    virtual_time: f64,
+}
+
+impl State {
+    fn new(config: BatchSchedulerConfig) -> Self {
+        Self {
+            config,
+            tenants: HashMap::new(),
+            sequence: 0,
+            virtual_time: 0.0,
+        }
+    }
+
+    fn push(&mut self, job: BatchJob, now: Instant) -> oneshot::Receiver<Result<ExtractionResult>> {
+        let (sender, receiver) = oneshot::channel();
+        let tenant = self.config.tenant(&job.tenant);
+        let queue = self.tenants.entry(job.tenant.clone()).or_insert_with(|| TenantQueue {
+            jobs: BinaryHeap::new(),
+            weight: tenant.weight,
+            bucket: tenant.rate_limit.map(|limit| TokenBucket::new(limit, now)),
+            pass: 0.0,
+        });
+        if queue.jobs.is_empty() {
+            // An idle tenant does not save up turns to spend later.
+            queue.pass = queue.pass.max(self.virtual_time);
+        }
+        queue.jobs.push(Queued {
+            job,
+            sequence: self.sequence,
+            sender,
+        });
+        self.sequence += 1;
+        receiver
+    }
+
+    fn next(&mut self, now: Instant) -> Next {
+        let mut wait: Option<Instant> = None;
+        let mut chosen: Option<(&String, f64)> = None;
+        for (name, queue) in &mut self.tenants {
+            if queue.jobs.is_empty() {
+                continue;
+            }
+            if let Some(bucket) = &mut queue.bucket {
+                bucket.refill(now);
+                if bucket.tokens < 1.0 {
+                    let ready = bucket.ready_at(now);
+                    wait = Some(wait.map_or(ready, |wait| wait.min(ready)));
+                    continue;
+                }
+            }
+            let earlier = chosen
+                .is_none_or(|(chosen_name, pass)| queue.pass < pass || (queue.pass == pass && name < chosen_name));
+            if earlier {
+                chosen = Some((name, queue.pass));
+            }
+        }
+
+        let Some((name, _)) = chosen else {
+            return wait.map_or(Next::Idle, Next::Wait);
+        };
+        let name = name.clone();
+        let Some(queue) = self.tenants.get_mut(&name) else {
+            return Next::Idle;
+        };
+        let Some(queued) = queue.jobs.pop() else {
+            return Next::Idle;
+        };
+        self.virtual_time = queue.pass;
+        queue.pass += 1.0 / f64::from(queue.weight);
+        if let Some(bucket) = &mut queue.bucket {
+            bucket.tokens -= 1.0;
+        }
+        if queue.jobs.is_empty() && queue.bucket.as_ref().is_none_or(TokenBucket::is_full) {
+            self.tenants.remove(&name);
+        }
+        Next::Job(queued)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(tenant: &str, marker: &str) -> BatchJob {
+        BatchJob::bytes(tenant, marker.as_bytes().to_vec(), "text/plain")
+    }
+
+    fn marker(next: Next) -> String {
+        match next {
+            Next::Job(queued) => match queued.job.input {
+                JobInput::Bytes { content, .. } => String::from_utf8(content).unwrap(),
+                JobInput::File(_) => unreachable!(),
+            },
+            Next::Wait(_) => "wait".to_string(),
+            Next::Idle => "idle".to_string(),
+        }
+    }
+
+    fn config(toml: &str) -> BatchSchedulerConfig {
+        toml::from_str(toml).unwrap()
+    }
+
+    #[test]
+    fn test_weighted_fairness() {
+        let mut state = State::new(config("[tenants.big]\nweight = 3"));
+        let now = Instant::now();
+        for i in 0..8 {
+            state.push(job("flood", &format!("flood{}", i)), now);
+        }
+        for i in 0..8 {
+            state.push(job("big", &format!("big{}", i)), now);
+        }
+
+        let order: Vec<String> = (0..8).map(|_| marker(state.next(now))).collect();
+        assert_eq!(order.iter().filter(|m| m.starts_with("big")).count(), 6);
+        assert_eq!(order[..4], ["big0", "flood0", "big1", "big2"]);
+
+        // A tenant arriving late is served right away rather than after the backlog.
+        state.push(job("late", "late0"), now);
+        assert_eq!(marker(state.next(now)), "late0");
+    }
+
+    #[test]
+    fn test_priority_within_tenant() {
+        let mut state = State::new(BatchSchedulerConfig::default());
+        let now = Instant::now();
+        state.push(job("a", "low"), now);
+        state.push(job("a", "high1").with_priority(5), now);
+        state.push(job("a", "mid").with_priority(3), now);
+        state.push(job("a", "high2").with_priority(5), now);
+
+        let order: Vec<String> = (0..5).map(|_| marker(state.next(now))).collect();
+        assert_eq!(order, ["high1", "high2", "mid", "low", "idle"]);
+    }
+
+    #[test]
+    fn test_rate_limit() {
+        let mut state = State::new(config("[tenants.slow]\nrate_limit = { per_second = 2.0 }"));
+        let now = Instant::now();
+        state.push(job("slow", "slow0"), now);
+        state.push(job("slow", "slow1"), now);
+        state.push(job("fast", "fast0"), now);
+        state.push(job("fast", "fast1"), now);
+
+        let order: Vec<String> = (0..3).map(|_| marker(state.next(now))).collect();
+        assert_eq!(order, ["fast0", "slow0", "fast1"]);
+        match state.next(now) {
+            Next::Wait(until) => assert_eq!(until, now + Duration::from_millis(500)),
+            _ => panic!("expected the rate-limited tenant to wait"),
+        }
+        assert_eq!(marker(state.next(now + Duration::from_millis(500))), "slow1");
+    }
+
+    #[test]
+    fn test_config_validation() {
+        assert!(config("[tenants.a]\nweight = 2").validate().is_ok());
+        assert!(config("[tenants.a]\nweight = 0").validate().is_err());
+        assert!(
+            config("[default_tenant]\nrate_limit = { per_second = 0.0 }")
+                .validate()
+                .is_err()
+        );
+        assert!(config("max_concurrent = 0").validate().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_scheduler_runs_jobs() {
+        let scheduler = BatchScheduler::new(config("max_concurrent = 2"), ExtractionConfig::default()).unwrap();
+        let handles: Vec<JobHandle> = ["one", "two", "three"]
+            .into_iter()
+            .map(|text| scheduler.submit(job("a", text)))
+            .collect();
+
+        let mut contents = Vec::new();
+        for handle in handles {
+            contents.push(handle.await.unwrap().content.trim_end().to_string());
+        }
+        assert_eq!(contents, ["one", "two", "three"]);
+
+        let missing = scheduler.submit(BatchJob::file("a", "/nonexistent/file.txt")).await;
+        assert!(missing.is_err());
+        assert_eq!(scheduler.queued("a"), 0);
+    }
+}
@@ -0,0 +1,57 @@
+//! Barcode and QR code detection configuration.
+//!
+//! Selects the symbologies decoded from images and PDF pages and the resolution
+//! PDF pages are scanned at.
+
+use serde::{Deserialize, Serialize};
+
+/// Barcode and QR code detection configuration.
+///
+/// When `None` on `ExtractionConfig`, no barcode detection runs. Requires the
+/// `barcodes` feature; decoded symbols are listed in `metadata.additional["barcodes"]`.
+///
+/// # Example
+///
+/// ```toml
+/// [barcodes]
+/// formats = ["qr_code", "code_128"]
+/// pdf_dpi = 200
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BarcodeConfig {
+    /// Symbologies to decode, such as `qr_code`, `data_matrix`, `pdf_417`, `code_128`,
+    /// `code_39`, `ean_13` or `upc_a` (empty = all supported symbologies)
+    #[serde(default)]
+    pub formats: Vec<String>,
+
+    /// Resolution PDF pages are rendered at for detection
+    ///
+    /// Default: 200
+    #[serde(default = "default_pdf_dpi")]
+    pub pdf_dpi: i32,
+}
+
+impl Default for BarcodeConfig {
+    fn default() -> Self {
+        Self {
+            formats: Vec::new(),
+            pdf_dpi: default_pdf_dpi(),
+        }
+    }
+}
+
+fn default_pdf_dpi() -> i32 {
+    200
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_barcode_config_from_toml() {
+        let config: BarcodeConfig = toml::from_str(r#"formats = ["qr_code"]"#).unwrap();
+        assert_eq!(config.formats, vec!["qr_code"]);
+        assert_eq!(config.pdf_dpi, 200);
+    }
+}
@@ -0,0 +1,72 @@
+//! Boilerplate removal configuration.
+//!
+//! Controls the removal of phrases that appear in every document of a collection,
+//! such as e-mail disclaimers, confidentiality notices and copyright footers.
+
+use serde::{Deserialize, Serialize};
+
+/// Boilerplate phrase removal.
+///
+/// Text matching any of the phrases or patterns is removed from the content and the
+/// page contents before chunking, so it does not end up in every chunk and in every
+/// search hit.
+///
+/// # Example
+///
+/// ```toml
+/// [boilerplate]
+/// phrases = ["This message is intended only for the named recipient"]
+/// patterns = ["(?i)copyright \\d{4} acme corp\\.?"]
+/// fuzzy_phrases = ["This e-mail and any attachments are confidential and may be privileged. If you are not the intended recipient, please notify the sender and delete it."]
+/// similarity_threshold = 0.8
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BoilerplateConfig {
+    /// Phrases removed wherever they appear, ignoring case and differences in whitespace
+    #[serde(default)]
+    pub phrases: Vec<String>,
+
+    /// Regular expressions whose matches are removed
+    #[serde(default)]
+    pub patterns: Vec<String>,
+
+    /// Phrases whose near-copies are removed: every paragraph whose words are at least
+    /// `similarity_threshold` similar to one of them
+    #[serde(default)]
+    pub fuzzy_phrases: Vec<String>,
+
+    /// Minimum word-level similarity (0.0-1.0) of a paragraph to a fuzzy phrase
+    ///
+    /// Default: 0.85
+    #[serde(default = "default_similarity_threshold")]
+    pub similarity_threshold: f64,
+}
+
+impl Default for BoilerplateConfig {
+    fn default() -> Self {
+        Self {
+            phrases: Vec::new(),
+            patterns: Vec::new(),
+            fuzzy_phrases: Vec::new(),
+            similarity_threshold: default_similarity_threshold(),
+        }
+    }
+}
+
+fn default_similarity_threshold() -> f64 {
+    0.85
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boilerplate_config_from_toml() {
+        let config: BoilerplateConfig = toml::from_str(r#"phrases = ["All rights reserved."]"#).unwrap();
+        assert_eq!(config.phrases, vec!["All rights reserved.".to_string()]);
+        assert!(config.patterns.is_empty());
+        assert!(config.fuzzy_phrases.is_empty());
+        assert_eq!(config.similarity_threshold, 0.85);
+    }
+}
@@ -0,0 +1,67 @@
+//! Storage configuration for serialized results.
+//!
+//! Controls how results written to disk — batch job outputs, watch sinks, the blob
+//! store and the cache — are encoded.
+
+use serde::{Deserialize, Serialize};
+
+/// Compression of serialized results written to disk.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compression {
+    /// Write results uncompressed
+    #[default]
+    None,
+    /// gzip, readable by `zcat` and most tools
+    Gzip,
+    /// Zstandard, faster and smaller than gzip
+    Zstd,
+}
+
+impl Compression {
+    /// Suffix appended to the names of files written with this compression, e.g. `.zst`.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Compression::None => "",
+            Compression::Gzip => ".gz",
+            Compression::Zstd => ".zst",
+        }
+    }
+}
+
+/// Storage configuration for serialized results.
+///
+/// Compression requires the `compression` feature. Compressed and uncompressed files
+/// can be mixed: reading detects the format, so turning compression on or off keeps
+/// earlier results readable.
+///
+/// # Example
+///
+/// ```toml
+/// [cache]
+/// compression = "zstd"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CacheConfig {
+    /// Compression of results written to disk sinks and the cache
+    ///
+    /// Default: none
+    #[serde(default)]
+    pub compression: Compression,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_config_from_toml() {
+        let config: CacheConfig = toml::from_str(r#"compression = "zstd""#).unwrap();
+        assert_eq!(config.compression, Compression::Zstd);
+        assert_eq!(config.compression.extension(), ".zst");
+
+        let config: CacheConfig = toml::from_str("").unwrap();
+        assert_eq!(config.compression, Compression::None);
+        assert!(toml::from_str::<CacheConfig>(r#"compression = "lz4""#).is_err());
+    }
+}
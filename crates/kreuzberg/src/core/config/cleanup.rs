@@ -0,0 +1,86 @@
+//! Content cleanup configuration.
+//!
+//! Controls removal of document artifacts that pollute extracted text, such as
+//! watermark overlays repeated on every page.
+
+use serde::{Deserialize, Serialize};
+
+/// Content cleanup configuration.
+///
+/// When `None` on `ExtractionConfig`, no cleanup beyond the standard quality
+/// processing is applied.
+///
+/// # Example
+///
+/// ```toml
+/// [cleanup]
+/// strip_watermarks = true
+/// report_watermarks = true
+/// watermark_min_page_ratio = 0.5
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CleanupConfig {
+    /// Remove detected watermark text (e.g. "CONFIDENTIAL DRAFT") from content
+    #[serde(default)]
+    pub strip_watermarks: bool,
+
+    /// Record detected watermark text in `metadata.additional["watermarks"]`
+    ///
+    /// Combined with `strip_watermarks`, this moves watermarks from the content
+    /// into metadata. On its own it only reports them.
+    #[serde(default = "default_true")]
+    pub report_watermarks: bool,
+
+    /// Minimum fraction of pages (0.0-1.0) a repeated line must appear on to be
+    /// treated as a watermark
+    ///
+    /// Default: 0.5
+    #[serde(default = "default_watermark_min_page_ratio")]
+    pub watermark_min_page_ratio: f64,
+}
+
+impl Default for CleanupConfig {
+    fn default() -> Self {
+        Self {
+            strip_watermarks: false,
+            report_watermarks: true,
+            watermark_min_page_ratio: default_watermark_min_page_ratio(),
+        }
+    }
+}
+
+impl CleanupConfig {
+    /// Whether watermark detection needs to run at all.
+    pub fn detects_watermarks(&self) -> bool {
+        self.strip_watermarks || self.report_watermarks
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_watermark_min_page_ratio() -> f64 {
+    0.5
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cleanup_config_default() {
+        let config = CleanupConfig::default();
+        assert!(!config.strip_watermarks);
+        assert!(config.report_watermarks);
+        assert_eq!(config.watermark_min_page_ratio, 0.5);
+    }
+
+    #[test]
+    fn test_cleanup_config_from_toml() {
+        let config: CleanupConfig = toml::from_str("strip_watermarks = true").unwrap();
+        assert!(config.strip_watermarks);
+        assert!(config.report_watermarks);
+        assert!(config.detects_watermarks());
+    }
+}
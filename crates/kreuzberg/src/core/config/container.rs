@@ -0,0 +1,52 @@
+//! Nested container extraction configuration.
+//!
+//! Archives can hold other archives and emails, and emails can carry archives and
+//! emails as attachments. This configuration controls how deep such nested
+//! containers are extracted.
+
+use serde::{Deserialize, Serialize};
+
+/// Nested container extraction configuration.
+///
+/// # Example
+///
+/// ```toml
+/// [containers]
+/// max_depth = 5
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContainerConfig {
+    /// Deepest nesting level extracted, counting the input document as level 1
+    /// (1 = nested containers are not extracted)
+    ///
+    /// Default: 3
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+}
+
+impl Default for ContainerConfig {
+    fn default() -> Self {
+        Self {
+            max_depth: default_max_depth(),
+        }
+    }
+}
+
+fn default_max_depth() -> usize {
+    3
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_container_config_from_toml() {
+        let config: ContainerConfig = toml::from_str("max_depth = 1").unwrap();
+        assert_eq!(config.max_depth, 1);
+
+        let config: ContainerConfig = toml::from_str("").unwrap();
+        assert_eq!(config, ContainerConfig::default());
+        assert_eq!(config.max_depth, 3);
+    }
+}
@@ -0,0 +1,63 @@
+//! Content filter configuration.
+//!
+//! Controls the built-in content filters applied to extracted text before it is
+//! chunked and returned.
+
+use serde::{Deserialize, Serialize};
+
+/// Built-in content filter configuration.
+///
+/// When `None` on `ExtractionConfig`, only registered
+/// [`ContentFilter`](crate::plugins::ContentFilter) plugins run.
+///
+/// # Example
+///
+/// ```toml
+/// [content_filter]
+/// mask_profanity = true
+/// denylist = ["(?i)project\\s+falcon", "\\b\\d{3}-\\d{2}-\\d{4}\\b"]
+/// denylist_replacement = "[FILTERED]"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentFilterConfig {
+    /// Replace profane words with asterisks of the same length
+    #[serde(default)]
+    pub mask_profanity: bool,
+
+    /// Regular expressions whose matches are replaced with `denylist_replacement`
+    #[serde(default)]
+    pub denylist: Vec<String>,
+
+    /// Replacement for denylist matches
+    ///
+    /// Default: "[FILTERED]"
+    #[serde(default = "default_denylist_replacement")]
+    pub denylist_replacement: String,
+}
+
+impl Default for ContentFilterConfig {
+    fn default() -> Self {
+        Self {
+            mask_profanity: false,
+            denylist: Vec::new(),
+            denylist_replacement: default_denylist_replacement(),
+        }
+    }
+}
+
+fn default_denylist_replacement() -> String {
+    "[FILTERED]".to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_filter_config_from_toml() {
+        let config: ContentFilterConfig = toml::from_str("denylist = [\"secret\"]").unwrap();
+        assert!(!config.mask_profanity);
+        assert_eq!(config.denylist, vec!["secret".to_string()]);
+        assert_eq!(config.denylist_replacement, "[FILTERED]");
+    }
+}
@@ -0,0 +1,47 @@
+//! Word processing document configuration.
+//!
+//! Controls how tables of DOCX and ODT documents appear in the extracted content.
+
+use serde::{Deserialize, Serialize};
+
+/// How tables of DOCX and ODT documents are rendered in the content.
+///
+/// Every mode returns the tables in `ExtractionResult::tables`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DocxTableMode {
+    /// Markdown tables at the table's position in the content
+    #[default]
+    Markdown,
+    /// Tables only in `ExtractionResult::tables`, left out of the content
+    Structured,
+    /// Tab-separated rows at the table's position in the content
+    Text,
+}
+
+/// DOCX and ODT extraction configuration.
+///
+/// # Example
+///
+/// ```toml
+/// [docx]
+/// tables = "structured"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DocxConfig {
+    /// Table rendering in the content (default: markdown)
+    #[serde(default)]
+    pub tables: DocxTableMode,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_docx_config_from_toml() {
+        let config: DocxConfig = toml::from_str("tables = \"text\"").unwrap();
+        assert_eq!(config.tables, DocxTableMode::Text);
+        assert_eq!(toml::from_str::<DocxConfig>("").unwrap(), DocxConfig::default());
+    }
+}
@@ -4,11 +4,39 @@
 //! configuration options for the extraction process.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
+use super::super::barcode::BarcodeConfig;
+use super::super::boilerplate::BoilerplateConfig;
+use super::super::cache::CacheConfig;
+use super::super::cleanup::CleanupConfig;
+use super::super::container::ContainerConfig;
+use super::super::content_filter::ContentFilterConfig;
+use super::super::docx::DocxConfig;
 use super::super::formats::OutputFormat;
+use super::super::glossary::GlossaryConfig;
+use super::super::io::IoConfig;
+use super::super::isolation::{IsolationMode, WorkerConfig};
+use super::super::metadata::MetadataMode;
+use super::super::network::NetworkConfig;
 use super::super::ocr::OcrConfig;
+use super::super::output::OutputConfig;
 use super::super::page::PageConfig;
+use super::super::plugin_guard::PluginGuardConfig;
 use super::super::processing::{ChunkingConfig, PostProcessorConfig};
+use super::super::provenance::ProvenanceConfig;
+use super::super::retry::RetryConfig;
+use super::super::rules::RulesConfig;
+use super::super::security::SecurityConfig;
+use super::super::signature::SignatureConfig;
+use super::super::spellcheck::SpellcheckConfig;
+use super::super::spreadsheet::SpreadsheetConfig;
+use super::super::token_count::TokenCountConfig;
+use super::super::translation::TranslationConfig;
+use super::super::transliteration::TransliterationConfig;
+use super::super::value_normalization::ValueNormalizationConfig;
+use super::super::walk::WalkConfig;
+use super::super::xml::XmlConfig;
 use super::types::{ImageExtractionConfig, LanguageDetectionConfig, TokenReductionConfig};
 
 /// Main extraction configuration.
@@ -66,8 +94,45 @@ pub struct ExtractionConfig {
     #[serde(default)]
     pub language_detection: Option<LanguageDetectionConfig>,
 
-    /// Page extraction configuration (None = no page tracking)
+    /// Detect the writing scripts of the content and of OCR results (default: false)
+    ///
+    /// Records the share of each script (Latin, Cyrillic, Arabic, Han, ...) among the
+    /// letters in `metadata.additional["writing_scripts"]`.
+    #[serde(default)]
+    pub script_detection: bool,
+
+    /// Transliteration of Cyrillic, Greek, Hangul and kana to Latin (None = no transliteration)
+    ///
+    /// Stores a romanized copy of the content in `metadata.additional["content_transliterated"]`
+    /// for search indexes that only handle Latin script.
+    #[serde(default)]
+    pub transliteration: Option<TransliterationConfig>,
+
+    /// Machine translation with a registered `Translator` (None = no translation)
+    ///
+    /// Stores the translated content or chunks in `metadata.additional["translation"]`.
+    #[serde(default)]
+    pub translation: Option<TranslationConfig>,
+
+    /// Spellcheck-based OCR quality estimation (None = no spellcheck)
+    ///
+    /// Stores the share of unknown words of the content and of every page in
+    /// `metadata.additional["spellcheck"]`.
     #[serde(default)]
+    pub spellcheck: Option<SpellcheckConfig>,
+
+    /// Date, time, currency and number normalization (None = no normalization)
+    ///
+    /// Stores the ISO 8601 and decimal forms of the values found in the content in
+    /// `metadata.additional["normalized_values"]`, or appends them inline.
+    #[serde(default)]
+    pub value_normalization: Option<ValueNormalizationConfig>,
+
+    /// Page extraction configuration (None = no page tracking)
+    ///
+    /// A page selection string such as `"1-10,15,20-"` is accepted in place of the
+    /// configuration, selecting those pages without other page tracking options.
+    #[serde(default, deserialize_with = "crate::core::config::page::deserialize_page_config")]
     pub pages: Option<PageConfig>,
 
     /// Keyword extraction configuration (None = no keyword extraction)
@@ -79,6 +144,115 @@ pub struct ExtractionConfig {
     #[serde(default)]
     pub postprocessor: Option<PostProcessorConfig>,
 
+    /// Content cleanup configuration (None = no cleanup such as watermark stripping)
+    #[serde(default)]
+    pub cleanup: Option<CleanupConfig>,
+
+    /// Boilerplate phrase removal (None = boilerplate is kept)
+    #[serde(default)]
+    pub boilerplate: Option<BoilerplateConfig>,
+
+    /// Terminology matching (None = no glossary matching)
+    ///
+    /// Stores the matched term IDs with their byte offsets in
+    /// `metadata.additional["glossary_matches"]`.
+    #[serde(default)]
+    pub glossary: Option<GlossaryConfig>,
+
+    /// Regex field extraction rules (None = no rules)
+    ///
+    /// Stores the named fields in `metadata.additional["extracted_fields"]`.
+    #[serde(default)]
+    pub rules: Option<RulesConfig>,
+
+    /// Built-in content filters (None = only registered `ContentFilter` plugins run)
+    #[serde(default)]
+    pub content_filter: Option<ContentFilterConfig>,
+
+    /// Output text normalization (None = text as extracted)
+    #[serde(default)]
+    pub output: Option<OutputConfig>,
+
+    /// Token counting and cost estimation (None = no token usage is reported)
+    #[serde(default)]
+    pub token_count: Option<TokenCountConfig>,
+
+    /// DOCX and ODT options (None = use defaults)
+    #[serde(default)]
+    pub docx: Option<DocxConfig>,
+
+    /// Spreadsheet sheet and row selection (None = all sheets and rows)
+    #[serde(default)]
+    pub spreadsheet: Option<SpreadsheetConfig>,
+
+    /// Barcode and QR code detection for images and PDF pages (None = no detection)
+    #[serde(default)]
+    pub barcodes: Option<BarcodeConfig>,
+
+    /// Signature and stamp detection for images and PDF pages (None = no detection)
+    #[serde(default)]
+    pub signatures: Option<SignatureConfig>,
+
+    /// File input configuration (None = memory-map files of 64 MiB and larger)
+    #[serde(default)]
+    pub io: Option<IoConfig>,
+
+    /// Nested container extraction (None = archives and emails inside archives and
+    /// emails are listed but not extracted)
+    #[serde(default)]
+    pub containers: Option<ContainerConfig>,
+
+    /// Input security policy (None = inputs are not inspected for macros and active content)
+    #[serde(default)]
+    pub security: Option<SecurityConfig>,
+
+    /// Remote resource policy (None = remote resources are never fetched)
+    ///
+    /// The string `"deny"` is accepted in place of the policy, denying all network
+    /// access: extractions that would need the network fail before they start.
+    #[serde(
+        default,
+        deserialize_with = "crate::core::config::network::deserialize_network_config"
+    )]
+    pub network: Option<NetworkConfig>,
+
+    /// XML parsing limits (None = default limits)
+    #[serde(default)]
+    pub xml: Option<XmlConfig>,
+
+    /// Where native extractors (PDF rendering, OCR) run (default: InProcess)
+    #[serde(default)]
+    pub isolation: IsolationMode,
+
+    /// Limits of the worker subprocesses used with subprocess isolation (None = default limits)
+    #[serde(default)]
+    pub worker: Option<WorkerConfig>,
+
+    /// Retries and dead-letter output of batch extraction (None = failed inputs are not retried)
+    #[serde(default)]
+    pub retry: Option<RetryConfig>,
+
+    /// Which files in the directories of a batch are extracted (None = ripgrep-like
+    /// defaults: symbolic links, hidden files and special files are skipped)
+    #[serde(default)]
+    pub walk: Option<WalkConfig>,
+
+    /// Provenance manifests written next to batch job results (None = no manifests)
+    #[serde(default)]
+    pub provenance: Option<ProvenanceConfig>,
+
+    /// Timeouts and circuit breaking for plugin calls (None = plugin calls are not guarded)
+    #[serde(default)]
+    pub plugin_guard: Option<PluginGuardConfig>,
+
+    /// Compression of results written to disk sinks and the cache (None = uncompressed)
+    #[serde(default)]
+    pub cache: Option<CacheConfig>,
+
+    /// How much document metadata is extracted (default: Full)
+    #[serde(default)]
+    pub metadata: MetadataMode,
+
     /// HTML to Markdown conversion options (None = use defaults)
     ///
     /// Configure how HTML documents are converted to Markdown, including heading styles,
@@ -115,6 +289,40 @@ pub struct ExtractionConfig {
     /// when format conversion is applied.
     #[serde(default)]
     pub output_format: OutputFormat,
+
+    /// Deterministic output mode (default: false).
+    ///
+    /// Serialized results are always written with sorted map keys. When enabled,
+    /// `metadata.additional` is additionally rebuilt with a fixed hash seed in sorted
+    /// key order, so its in-memory iteration order (as seen by language bindings and
+    /// custom serializers) is identical across processes for identical inputs.
+    #[serde(default)]
+    pub determinism: bool,
+
+    /// Attach a processing report to each result (default: false).
+    ///
+    /// The report lists the stages that ran with their plugins, durations and content
+    /// sizes, the OCR of each page, warnings and cache hits; it is stored in
+    /// `metadata.additional["processing_report"]`. See [`crate::types::ProcessingReport`].
+    #[serde(default)]
+    pub processing_report: bool,
+
+    /// Fallback chains of extractors, by MIME type (default: none).
+    ///
+    /// For a MIME type with a chain, the extractors of the chain are tried in order
+    /// instead of the highest-priority extractor, and the first to succeed is used. The
+    /// entries are extractor names, and `ocr-full` for the default extractor with
+    /// `force_ocr`. The attempts are recorded in the processing report.
+    #[serde(default)]
+    pub fallbacks: HashMap<String, Vec<String>>,
+
+    /// Recover what is readable from damaged inputs instead of failing (default: false).
+    ///
+    /// When extraction fails, truncated PDFs, ZIP archives with a damaged central
+    /// directory and Office packages with truncated parts are repaired and extracted
+    /// again. The damage is reported in `metadata.additional["salvage_warnings"]`.
+    #[serde(default)]
+    pub salvage: bool,
 }
 
 impl Default for ExtractionConfig {
@@ -130,15 +338,48 @@ impl Default for ExtractionConfig {
             pdf_options: None,
             token_reduction: None,
             language_detection: None,
+            script_detection: false,
+            transliteration: None,
+            translation: None,
+            spellcheck: None,
+            value_normalization: None,
             pages: None,
             #[cfg(any(feature = "keywords-yake", feature = "keywords-rake"))]
             keywords: None,
             postprocessor: None,
+            cleanup: None,
+            boilerplate: None,
+            glossary: None,
+            rules: None,
+            content_filter: None,
+            output: None,
+            token_count: None,
+            docx: None,
+            spreadsheet: None,
+            barcodes: None,
+            signatures: None,
+            io: None,
+            containers: None,
+            security: None,
+            network: None,
+            xml: None,
+            isolation: IsolationMode::InProcess,
+            worker: None,
+            retry: None,
+            walk: None,
+            provenance: None,
+            plugin_guard: None,
+            cache: None,
+            metadata: MetadataMode::Full,
             #[cfg(feature = "html")]
             html_options: None,
             max_concurrent_extractions: None,
             result_format: crate::types::OutputFormat::Unified,
             output_format: OutputFormat::Plain,
+            determinism: false,
+            processing_report: false,
+            fallbacks: HashMap::new(),
+            salvage: false,
         }
     }
 }
@@ -162,6 +403,12 @@ impl ExtractionConfig {
 
         ocr_enabled || image_extraction_enabled
     }
+
+    /// Whether network access is denied, by `network = "deny"` or by
+    /// `KREUZBERG_NETWORK=deny` for the whole process.
+    pub fn denies_network(&self) -> bool {
+        self.network.as_ref().is_some_and(|network| network.deny) || NetworkConfig::denied_by_environment()
+    }
 }
 
 fn default_true() -> bool {
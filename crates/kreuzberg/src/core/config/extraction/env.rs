@@ -100,6 +100,10 @@ impl ExtractionConfig {
                     chunker_type: super::super::processing::ChunkerType::Text,
                     embedding: None,
                     preset: None,
+                    overlap_strategy: super::super::processing::OverlapStrategy::Characters,
+                    heading_context: false,
+                    tables: None,
+                    sentence_segmenter: None,
                 });
             }
 
@@ -128,6 +132,10 @@ impl ExtractionConfig {
                     chunker_type: super::super::processing::ChunkerType::Text,
                     embedding: None,
                     preset: None,
+                    overlap_strategy: super::super::processing::OverlapStrategy::Characters,
+                    heading_context: false,
+                    tables: None,
+                    sentence_segmenter: None,
                 });
             }
 
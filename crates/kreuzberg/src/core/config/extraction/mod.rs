@@ -8,14 +8,17 @@
 //! - `core`: Main ExtractionConfig struct and implementation
 //! - `env`: Environment variable override support
 //! - `loaders`: Configuration file loading with caching
+//! - `overrides`: Per-request partial configurations merged onto a base configuration
 
 mod core;
 mod env;
 mod loaders;
+mod overrides;
 mod types;
 
 // Re-export all public types for backward compatibility
 pub use self::core::ExtractionConfig;
+pub use self::overrides::ConfigOverrides;
 pub use self::types::{ImageExtractionConfig, LanguageDetectionConfig, TokenReductionConfig};
 
 #[cfg(test)]
@@ -0,0 +1,441 @@
+//! Per-request configuration overrides.
+//!
+//! Servers keep one base configuration and receive partial configurations with each
+//! request. [`ConfigOverrides`] holds such a partial configuration, and
+//! [`ExtractionConfig::merge`] applies it on top of the base configuration.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashMap;
+
+use super::super::barcode::BarcodeConfig;
+use super::super::boilerplate::BoilerplateConfig;
+use super::super::cache::CacheConfig;
+use super::super::cleanup::CleanupConfig;
+use super::super::container::ContainerConfig;
+use super::super::content_filter::ContentFilterConfig;
+use super::super::docx::DocxConfig;
+use super::super::formats::OutputFormat;
+use super::super::glossary::GlossaryConfig;
+use super::super::io::IoConfig;
+use super::super::isolation::{IsolationMode, WorkerConfig};
+use super::super::metadata::MetadataMode;
+use super::super::network::NetworkConfig;
+use super::super::ocr::OcrConfig;
+use super::super::output::OutputConfig;
+use super::super::page::PageConfig;
+use super::super::plugin_guard::PluginGuardConfig;
+use super::super::processing::{ChunkingConfig, PostProcessorConfig};
+use super::super::provenance::ProvenanceConfig;
+use super::super::retry::RetryConfig;
+use super::super::rules::RulesConfig;
+use super::super::security::SecurityConfig;
+use super::super::signature::SignatureConfig;
+use super::super::spellcheck::SpellcheckConfig;
+use super::super::spreadsheet::SpreadsheetConfig;
+use super::super::token_count::TokenCountConfig;
+use super::super::translation::TranslationConfig;
+use super::super::transliteration::TransliterationConfig;
+use super::super::value_normalization::ValueNormalizationConfig;
+use super::super::walk::WalkConfig;
+use super::super::xml::XmlConfig;
+use super::core::ExtractionConfig;
+use super::types::{ImageExtractionConfig, LanguageDetectionConfig, TokenReductionConfig};
+
+/// A partial [`ExtractionConfig`], with every field optional.
+///
+/// Fields left out keep the value of the base configuration. A field that is present
+/// replaces the base value as a whole: `{"ocr": {"language": "deu"}}` sets an OCR
+/// configuration with default settings besides the language, rather than changing the
+/// language of the base OCR configuration. `null` turns an optional setting off, so
+/// `{"chunking": null}` disables chunking even when the base configuration enables it.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::{ConfigOverrides, ExtractionConfig};
+///
+/// let base = ExtractionConfig {
+///     use_cache: false,
+///     ..Default::default()
+/// };
+/// let overrides: ConfigOverrides = serde_json::from_str(r#"{"force_ocr": true, "pages": "1-3"}"#).unwrap();
+///
+/// let config = base.merge(overrides);
+/// assert!(config.force_ocr);
+/// assert!(!config.use_cache);
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ConfigOverrides {
+    /// Enable caching of extraction results
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub use_cache: Option<bool>,
+
+    /// Enable quality post-processing
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enable_quality_processing: Option<bool>,
+
+    /// OCR configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub ocr: Option<Option<OcrConfig>>,
+
+    /// Force OCR even for searchable PDFs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub force_ocr: Option<bool>,
+
+    /// Text chunking configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub chunking: Option<Option<ChunkingConfig>>,
+
+    /// Image extraction configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub images: Option<Option<ImageExtractionConfig>>,
+
+    /// PDF-specific options
+    #[cfg(feature = "pdf")]
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub pdf_options: Option<Option<super::super::pdf::PdfConfig>>,
+
+    /// Token reduction configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub token_reduction: Option<Option<TokenReductionConfig>>,
+
+    /// Language detection configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub language_detection: Option<Option<LanguageDetectionConfig>>,
+
+    /// Writing script detection
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_detection: Option<bool>,
+
+    /// Transliteration configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub transliteration: Option<Option<TransliterationConfig>>,
+
+    /// Machine translation configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub translation: Option<Option<TranslationConfig>>,
+
+    /// Spellcheck configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub spellcheck: Option<Option<SpellcheckConfig>>,
+
+    /// Value normalization configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub value_normalization: Option<Option<ValueNormalizationConfig>>,
+
+    /// Page extraction configuration, or a page selection string
+    #[serde(default, deserialize_with = "present_pages", skip_serializing_if = "Option::is_none")]
+    pub pages: Option<Option<PageConfig>>,
+
+    /// Keyword extraction configuration
+    #[cfg(any(feature = "keywords-yake", feature = "keywords-rake"))]
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<Option<crate::keywords::KeywordConfig>>,
+
+    /// Post-processor configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub postprocessor: Option<Option<PostProcessorConfig>>,
+
+    /// Text cleanup configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub cleanup: Option<Option<CleanupConfig>>,
+
+    /// Boilerplate removal configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub boilerplate: Option<Option<BoilerplateConfig>>,
+
+    /// Glossary matching configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub glossary: Option<Option<GlossaryConfig>>,
+
+    /// Extraction rules configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub rules: Option<Option<RulesConfig>>,
+
+    /// Content filter configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub content_filter: Option<Option<ContentFilterConfig>>,
+
+    /// Output text configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub output: Option<Option<OutputConfig>>,
+
+    /// Token counting configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub token_count: Option<Option<TokenCountConfig>>,
+
+    /// DOCX configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub docx: Option<Option<DocxConfig>>,
+
+    /// Spreadsheet configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub spreadsheet: Option<Option<SpreadsheetConfig>>,
+
+    /// Barcode detection configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub barcodes: Option<Option<BarcodeConfig>>,
+
+    /// Signature and stamp detection configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub signatures: Option<Option<SignatureConfig>>,
+
+    /// File reading configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub io: Option<Option<IoConfig>>,
+
+    /// Container recursion configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub containers: Option<Option<ContainerConfig>>,
+
+    /// Security scanning configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub security: Option<Option<SecurityConfig>>,
+
+    /// Remote resource configuration, or `"deny"`
+    ///
+    /// Cannot lift a denial of network access by the base configuration.
+    #[serde(
+        default,
+        deserialize_with = "present_network",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub network: Option<Option<NetworkConfig>>,
+
+    /// XML parsing limits
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub xml: Option<Option<XmlConfig>>,
+
+    /// Where extraction runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub isolation: Option<IsolationMode>,
+
+    /// Worker process configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub worker: Option<Option<WorkerConfig>>,
+
+    /// Retry configuration
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub retry: Option<Option<RetryConfig>>,
+
+    /// Directory walking of batch extraction
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub walk: Option<Option<WalkConfig>>,
+
+    /// Provenance manifests of batch job results
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<Option<ProvenanceConfig>>,
+
+    /// Plugin timeouts and circuit breaking
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub plugin_guard: Option<Option<PluginGuardConfig>>,
+
+    /// Compression of results written to disk
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub cache: Option<Option<CacheConfig>>,
+
+    /// How much document metadata is extracted
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<MetadataMode>,
+
+    /// HTML to Markdown conversion options
+    #[cfg(feature = "html")]
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub html_options: Option<Option<html_to_markdown_rs::ConversionOptions>>,
+
+    /// Maximum concurrent extractions in batch operations
+    #[serde(default, deserialize_with = "present", skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_extractions: Option<Option<usize>>,
+
+    /// Result structure format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result_format: Option<crate::types::OutputFormat>,
+
+    /// Content text format
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<OutputFormat>,
+
+    /// Deterministic output
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub determinism: Option<bool>,
+
+    /// Processing report
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub processing_report: Option<bool>,
+
+    /// Fallback chains of extractors, by MIME type
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fallbacks: Option<HashMap<String, Vec<String>>>,
+
+    /// Salvage mode for damaged inputs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salvage: Option<bool>,
+}
+
+impl ExtractionConfig {
+    /// Apply `overrides` on top of this configuration.
+    ///
+    /// Returns a new configuration with the fields set in `overrides` replaced and all
+    /// other fields as in `self`. See [`ConfigOverrides`] for how fields are replaced.
+    pub fn merge(&self, overrides: ConfigOverrides) -> ExtractionConfig {
+        let mut config = self.clone();
+
+        macro_rules! apply {
+            ($($field:ident),* $(,)?) => {
+                $(
+                    if let Some(value) = overrides.$field {
+                        config.$field = value;
+                    }
+                )*
+            };
+        }
+
+        apply!(
+            use_cache,
+            enable_quality_processing,
+            ocr,
+            force_ocr,
+            chunking,
+            images,
+            token_reduction,
+            language_detection,
+            script_detection,
+            transliteration,
+            translation,
+            spellcheck,
+            value_normalization,
+            pages,
+            postprocessor,
+            cleanup,
+            boilerplate,
+            glossary,
+            rules,
+            content_filter,
+            output,
+            token_count,
+            docx,
+            spreadsheet,
+            barcodes,
+            signatures,
+            io,
+            containers,
+            security,
+            network,
+            xml,
+            isolation,
+            worker,
+            retry,
+            walk,
+            provenance,
+            plugin_guard,
+            cache,
+            metadata,
+            max_concurrent_extractions,
+            result_format,
+            output_format,
+            determinism,
+            processing_report,
+            fallbacks,
+            salvage,
+        );
+        #[cfg(feature = "pdf")]
+        apply!(pdf_options);
+        #[cfg(any(feature = "keywords-yake", feature = "keywords-rake"))]
+        apply!(keywords);
+        #[cfg(feature = "html")]
+        apply!(html_options);
+
+        if self.network.as_ref().is_some_and(|network| network.deny)
+            && !config.network.as_ref().is_some_and(|network| network.deny)
+        {
+            config.network = self.network.clone();
+        }
+
+        config
+    }
+}
+
+/// Deserialize a field that is present, `null` included, as `Some`.
+fn present<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    T::deserialize(deserializer).map(Some)
+}
+
+/// Deserialize a present `pages` field, accepting a page selection string.
+fn present_pages<'de, D>(deserializer: D) -> Result<Option<Option<PageConfig>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    super::super::page::deserialize_page_config(deserializer).map(Some)
+}
+
+/// Deserialize a present `network` field, accepting `"deny"`.
+fn present_network<'de, D>(deserializer: D) -> Result<Option<Option<NetworkConfig>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    super::super::network::deserialize_network_config(deserializer).map(Some)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_keeps_base_fields_not_overridden() {
+        let base = ExtractionConfig {
+            use_cache: false,
+            chunking: Some(ChunkingConfig::default()),
+            output_format: OutputFormat::Markdown,
+            ..Default::default()
+        };
+        let overrides: ConfigOverrides =
+            serde_json::from_str(r#"{"force_ocr": true, "metadata": "basic", "pages": "2-"}"#).unwrap();
+
+        let config = base.merge(overrides);
+        assert!(config.force_ocr);
+        assert_eq!(config.metadata, MetadataMode::Basic);
+        assert!(config.pages.unwrap().includes_page(5));
+        assert!(!config.use_cache);
+        assert!(config.chunking.is_some());
+        assert_eq!(config.output_format, OutputFormat::Markdown);
+    }
+
+    #[test]
+    fn test_merge_null_clears_optional_settings() {
+        let base = ExtractionConfig {
+            chunking: Some(ChunkingConfig::default()),
+            max_concurrent_extractions: Some(4),
+            ..Default::default()
+        };
+        let overrides: ConfigOverrides =
+            serde_json::from_str(r#"{"chunking": null, "max_concurrent_extractions": null}"#).unwrap();
+        assert_eq!(overrides.chunking.as_ref().map(Option::is_none), Some(true));
+
+        let config = base.merge(overrides);
+        assert!(config.chunking.is_none());
+        assert!(config.max_concurrent_extractions.is_none());
+
+        let config = base.merge(ConfigOverrides::default());
+        assert!(config.chunking.is_some());
+        assert_eq!(config.max_concurrent_extractions, Some(4));
+    }
+
+    #[test]
+    fn test_merge_cannot_lift_network_denial() {
+        let base = ExtractionConfig {
+            network: Some(NetworkConfig::denied()),
+            ..Default::default()
+        };
+        for overrides in [r#"{"network": null}"#, r#"{"network": {"allow_remote": true}}"#] {
+            let config = base.merge(serde_json::from_str(overrides).unwrap());
+            assert_eq!(config.network, Some(NetworkConfig::denied()));
+        }
+
+        let config = ExtractionConfig::default().merge(serde_json::from_str(r#"{"network": "deny"}"#).unwrap());
+        assert!(config.network.is_some_and(|network| network.deny));
+    }
+}
@@ -0,0 +1,101 @@
+//! Glossary matching configuration.
+//!
+//! Controls the terminology lists that the content is matched against, so terms of a
+//! domain taxonomy can be linked to their identifiers.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// A term of a terminology list with its identifier and synonyms.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct GlossaryTerm {
+    /// Identifier recorded for every match, such as a taxonomy or ontology ID
+    pub id: String,
+
+    /// Preferred form of the term
+    pub term: String,
+
+    /// Other forms matched as the same term, such as abbreviations and spelling variants
+    #[serde(default)]
+    pub synonyms: Vec<String>,
+}
+
+/// Glossary matching.
+///
+/// Terms are loaded from `files` and `terms`. CSV files need an `id` and a `term`
+/// column and may have a `synonyms` column with synonyms separated by `|`; TOML files
+/// list the terms in `[[terms]]` tables with the fields of [`GlossaryTerm`].
+///
+/// # Example
+///
+/// ```toml
+/// [glossary]
+/// files = ["/etc/kreuzberg/mesh.csv"]
+/// case_sensitive = false
+///
+/// [[glossary.terms]]
+/// id = "D006333"
+/// term = "heart failure"
+/// synonyms = ["cardiac failure", "HF"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlossaryConfig {
+    /// Terminology files, as `.csv` or `.toml`
+    #[serde(default)]
+    pub files: Vec<PathBuf>,
+
+    /// Terms given inline, in addition to those of the files
+    #[serde(default)]
+    pub terms: Vec<GlossaryTerm>,
+
+    /// Match terms with their exact case; otherwise case is ignored
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub case_sensitive: bool,
+
+    /// Only match terms that are not part of a longer word
+    ///
+    /// Default: true
+    #[serde(default = "default_whole_words")]
+    pub whole_words: bool,
+}
+
+impl Default for GlossaryConfig {
+    fn default() -> Self {
+        Self {
+            files: Vec::new(),
+            terms: Vec::new(),
+            case_sensitive: false,
+            whole_words: default_whole_words(),
+        }
+    }
+}
+
+fn default_whole_words() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glossary_config_from_toml() {
+        let config: GlossaryConfig = toml::from_str(
+            r#"
+            files = ["terms.csv"]
+
+            [[terms]]
+            id = "D006333"
+            term = "heart failure"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.files, vec![PathBuf::from("terms.csv")]);
+        assert_eq!(config.terms[0].id, "D006333");
+        assert!(config.terms[0].synonyms.is_empty());
+        assert!(!config.case_sensitive);
+        assert!(config.whole_words);
+    }
+}
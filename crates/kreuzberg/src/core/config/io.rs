@@ -0,0 +1,125 @@
+//! Input/output configuration.
+//!
+//! Controls how input files are read from disk, when intermediate text is staged on
+//! disk instead of in memory, and where and how much temporary data is written.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Default size from which files are memory-mapped instead of read (64 MiB).
+pub const DEFAULT_MMAP_THRESHOLD_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Default size of intermediate text from which it is spilled to disk (256 MiB).
+pub const DEFAULT_SPILL_THRESHOLD_BYTES: u64 = 256 * 1024 * 1024;
+
+/// File input configuration.
+///
+/// When `None` on `ExtractionConfig`, the defaults apply: files of 64 MiB and larger
+/// are memory-mapped, and intermediate text beyond 256 MiB is spilled to disk.
+///
+/// # Example
+///
+/// ```toml
+/// [io]
+/// use_mmap = true
+/// mmap_threshold_bytes = 16777216
+/// spill_threshold_bytes = 134217728
+/// temp_dir = "/scratch/kreuzberg"
+/// temp_quota_bytes = 2147483648
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IoConfig {
+    /// Memory-map large input files instead of reading them into memory
+    #[serde(default = "default_true")]
+    pub use_mmap: bool,
+
+    /// Minimum file size in bytes for memory-mapping (0 = map every file)
+    ///
+    /// Default: 67108864 (64 MiB)
+    #[serde(default = "default_mmap_threshold_bytes")]
+    pub mmap_threshold_bytes: u64,
+
+    /// Size in bytes from which intermediate text, such as the text files of an archive,
+    /// is staged in a temporary file and assembled from there
+    ///
+    /// Default: 268435456 (256 MiB)
+    #[serde(default = "default_spill_threshold_bytes")]
+    pub spill_threshold_bytes: u64,
+
+    /// Directory temporary workspaces are created in (None = `$KREUZBERG_TEMP_DIR`, or
+    /// the system temporary directory)
+    #[serde(default)]
+    pub temp_dir: Option<PathBuf>,
+
+    /// Most bytes the intermediate files of one extraction may take on disk
+    /// (None = unlimited)
+    #[serde(default)]
+    pub temp_quota_bytes: Option<u64>,
+}
+
+impl Default for IoConfig {
+    fn default() -> Self {
+        Self {
+            use_mmap: true,
+            mmap_threshold_bytes: DEFAULT_MMAP_THRESHOLD_BYTES,
+            spill_threshold_bytes: DEFAULT_SPILL_THRESHOLD_BYTES,
+            temp_dir: None,
+            temp_quota_bytes: None,
+        }
+    }
+}
+
+impl IoConfig {
+    /// Whether a file of the given size should be memory-mapped.
+    pub fn should_mmap(&self, file_size: u64) -> bool {
+        self.use_mmap && file_size > 0 && file_size >= self.mmap_threshold_bytes
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_mmap_threshold_bytes() -> u64 {
+    DEFAULT_MMAP_THRESHOLD_BYTES
+}
+
+fn default_spill_threshold_bytes() -> u64 {
+    DEFAULT_SPILL_THRESHOLD_BYTES
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_should_mmap_threshold() {
+        let config = IoConfig::default();
+        assert!(!config.should_mmap(1024));
+        assert!(config.should_mmap(DEFAULT_MMAP_THRESHOLD_BYTES));
+
+        let always = IoConfig {
+            mmap_threshold_bytes: 0,
+            ..Default::default()
+        };
+        assert!(always.should_mmap(1));
+        assert!(!always.should_mmap(0));
+
+        let disabled = IoConfig {
+            use_mmap: false,
+            mmap_threshold_bytes: 0,
+            ..Default::default()
+        };
+        assert!(!disabled.should_mmap(DEFAULT_MMAP_THRESHOLD_BYTES));
+    }
+
+    #[test]
+    fn test_io_config_from_toml() {
+        let config: IoConfig = toml::from_str("mmap_threshold_bytes = 1024").unwrap();
+        assert!(config.use_mmap);
+        assert_eq!(config.mmap_threshold_bytes, 1024);
+        assert_eq!(config.spill_threshold_bytes, DEFAULT_SPILL_THRESHOLD_BYTES);
+        assert_eq!(config.temp_dir, None);
+        assert_eq!(config.temp_quota_bytes, None);
+    }
+}
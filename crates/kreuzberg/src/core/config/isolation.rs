@@ -0,0 +1,98 @@
+//! Process isolation configuration.
+//!
+//! PDF rendering and OCR run native libraries (PDFium, Tesseract, ONNX Runtime) that a
+//! malformed file can crash or drive out of memory. With [`IsolationMode::Subprocess`]
+//! they run in worker subprocesses instead, so such a file fails its own extraction
+//! without taking down the process that requested it.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Where native extractors run.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IsolationMode {
+    /// In the calling process (default)
+    #[default]
+    InProcess,
+    /// In worker subprocesses limited by [`WorkerConfig`]
+    Subprocess,
+}
+
+/// Worker subprocesses used with [`IsolationMode::Subprocess`].
+///
+/// # Example
+///
+/// ```toml
+/// isolation = "subprocess"
+///
+/// [worker]
+/// max_memory_mb = 2048
+/// timeout_secs = 120
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkerConfig {
+    /// Worker executable, started with the `worker` argument (None = the
+    /// `KREUZBERG_WORKER_COMMAND` environment variable, or `kreuzberg` on the `PATH`)
+    #[serde(default)]
+    pub command: Option<PathBuf>,
+
+    /// Largest address space of a worker, in MiB (0 = unlimited); enforced on Unix
+    ///
+    /// Default: 4096
+    #[serde(default = "default_max_memory_mb")]
+    pub max_memory_mb: u64,
+
+    /// Longest time a worker may take for one extraction, in seconds (0 = unlimited)
+    ///
+    /// Default: 300
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Extractions a worker runs before it is replaced by a fresh one
+    ///
+    /// Default: 100
+    #[serde(default = "default_max_jobs")]
+    pub max_jobs: u32,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            command: None,
+            max_memory_mb: default_max_memory_mb(),
+            timeout_secs: default_timeout_secs(),
+            max_jobs: default_max_jobs(),
+        }
+    }
+}
+
+fn default_max_memory_mb() -> u64 {
+    4096
+}
+
+fn default_timeout_secs() -> u64 {
+    300
+}
+
+fn default_max_jobs() -> u32 {
+    100
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_worker_config_from_toml() {
+        let config: WorkerConfig =
+            toml::from_str("command = \"/opt/kreuzberg/bin/kreuzberg\"\ntimeout_secs = 30").unwrap();
+        assert_eq!(config.command, Some(PathBuf::from("/opt/kreuzberg/bin/kreuzberg")));
+        assert_eq!(config.timeout_secs, 30);
+        assert_eq!(config.max_memory_mb, 4096);
+        assert_eq!(config.max_jobs, 100);
+
+        let config: WorkerConfig = toml::from_str("").unwrap();
+        assert_eq!(config, WorkerConfig::default());
+    }
+}
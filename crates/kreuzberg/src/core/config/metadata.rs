@@ -0,0 +1,60 @@
+//! Metadata extraction configuration.
+//!
+//! Reading document properties can cost as much as reading the text: XMP packets,
+//! custom property parts, and format-specific details. [`MetadataMode`] lets callers
+//! who only need the text skip that work. Callers who only need the metadata can use
+//! [`extract_metadata_only`](crate::extract_metadata_only), which skips the text.
+
+use serde::{Deserialize, Serialize};
+
+/// How much document metadata is extracted.
+///
+/// The page structure (`metadata.pages`) is kept in every mode, since page-aware
+/// chunking and page markers rely on it. Metadata added by configured features, such
+/// as keywords, security findings, or token usage, is kept as well.
+///
+/// # Example
+///
+/// ```toml
+/// metadata = "basic"
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MetadataMode {
+    /// No document metadata
+    None,
+    /// The common typed fields (title, authors, dates, page and word counts, ...),
+    /// without format-specific metadata and `additional` entries
+    Basic,
+    /// Everything the extractor reports (default)
+    #[default]
+    Full,
+}
+
+impl MetadataMode {
+    /// Whether the common typed fields are extracted.
+    pub fn includes_basic(self) -> bool {
+        self != Self::None
+    }
+
+    /// Whether format-specific metadata and `additional` entries are extracted.
+    pub fn includes_full(self) -> bool {
+        self == Self::Full
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_mode_serde() {
+        assert_eq!(MetadataMode::default(), MetadataMode::Full);
+        let mode: MetadataMode = serde_json::from_str("\"basic\"").unwrap();
+        assert_eq!(mode, MetadataMode::Basic);
+        assert_eq!(serde_json::to_string(&MetadataMode::None).unwrap(), "\"none\"");
+        assert!(MetadataMode::Basic.includes_basic());
+        assert!(!MetadataMode::Basic.includes_full());
+        assert!(!MetadataMode::None.includes_basic());
+    }
+}
@@ -3,18 +3,84 @@
 //! This module provides utilities for loading extraction configuration from various
 //! sources (TOML, YAML, JSON) and discovering configuration files in the project hierarchy.
 
+pub mod barcode;
+pub mod boilerplate;
+pub mod cache;
+pub mod cleanup;
+pub mod container;
+pub mod content_filter;
+pub mod docx;
 pub mod extraction;
 pub mod formats;
+pub mod glossary;
+pub mod io;
+pub mod isolation;
+pub mod metadata;
+pub mod models;
+pub mod network;
 pub mod ocr;
+pub mod output;
 pub mod page;
 pub mod pdf;
+pub mod plugin_guard;
 pub mod processing;
+pub mod provenance;
+pub mod retry;
+pub mod rules;
+pub mod security;
+pub mod signature;
+pub mod spellcheck;
+pub mod spreadsheet;
+pub mod thread_pool;
+pub mod token_count;
+pub mod translation;
+pub mod transliteration;
+pub mod value_normalization;
+pub mod walk;
+pub mod xml;
 
 // Re-export main types for backward compatibility
-pub use extraction::{ExtractionConfig, ImageExtractionConfig, LanguageDetectionConfig, TokenReductionConfig};
+pub use barcode::BarcodeConfig;
+pub use boilerplate::BoilerplateConfig;
+pub use cache::{CacheConfig, Compression};
+pub use cleanup::CleanupConfig;
+pub use container::ContainerConfig;
+pub use content_filter::ContentFilterConfig;
+pub use docx::{DocxConfig, DocxTableMode};
+pub use extraction::{
+    ConfigOverrides, ExtractionConfig, ImageExtractionConfig, LanguageDetectionConfig, TokenReductionConfig,
+};
 pub use formats::OutputFormat;
-pub use ocr::OcrConfig;
-pub use page::PageConfig;
+pub use glossary::{GlossaryConfig, GlossaryTerm};
+pub use io::IoConfig;
+pub use isolation::{IsolationMode, WorkerConfig};
+pub use metadata::MetadataMode;
+pub use models::{ModelAsset, ModelKind, ModelsConfig};
+pub use network::NetworkConfig;
+pub use ocr::{
+    BlankPageConfig, DuplicatePageConfig, HandwritingMode, OcrConfig, OcrPageContext, OcrPageOverrideCallback,
+    OcrPageOverrides,
+};
+pub use output::{NewlinePolicy, NormalizationOptions, NormalizationProfile, OutputConfig};
+pub use page::{PageConfig, PageSelection};
 #[cfg(feature = "pdf")]
-pub use pdf::{HierarchyConfig, PdfConfig};
-pub use processing::{ChunkerType, ChunkingConfig, EmbeddingConfig, EmbeddingModelType, PostProcessorConfig};
+pub use pdf::{ConformanceLevel, HierarchyConfig, PdfBackendKind, PdfConfig, PdfStandard, RedactionConfig, RubyMode};
+pub use plugin_guard::PluginGuardConfig;
+pub use processing::{
+    ChunkerType, ChunkingConfig, EmbeddingConfig, EmbeddingModelType, OverlapStrategy, PostProcessorConfig,
+    TableChunkFormat, TableChunkingConfig,
+};
+pub use provenance::ProvenanceConfig;
+pub use retry::RetryConfig;
+pub use rules::{ExtractRule, RulesConfig};
+pub use security::SecurityConfig;
+pub use signature::SignatureConfig;
+pub use spellcheck::SpellcheckConfig;
+pub use spreadsheet::SpreadsheetConfig;
+pub use thread_pool::ThreadPoolConfig;
+pub use token_count::{TokenCountConfig, TokenCounterCallback, TokenPricing, TokenizerKind};
+pub use translation::{TranslationConfig, TranslationScope};
+pub use transliteration::{TransliterationConfig, TransliterationScheme};
+pub use value_normalization::{UnitSystem, ValueAnnotationMode, ValueKind, ValueNormalizationConfig};
+pub use walk::{SpecialFilePolicy, SymlinkPolicy, WalkConfig};
+pub use xml::XmlConfig;
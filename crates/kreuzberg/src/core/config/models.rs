@@ -0,0 +1,244 @@
+//! Model asset configuration.
+//!
+//! Tessdata packs, ONNX models and tokenizer files are not shipped with Kreuzberg.
+//! The assets listed here are downloaded and verified by the
+//! [`ModelManager`](crate::models::ModelManager) (feature `models`) into a versioned
+//! directory layout, so deployments do not have to manage model files by hand.
+//!
+//! The configuration is deployment-level and not part of
+//! [`ExtractionConfig`](super::ExtractionConfig), so per-request configuration cannot
+//! choose where models are downloaded from.
+
+use crate::{KreuzbergError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Model assets and where they are kept.
+///
+/// # Example
+///
+/// ```toml
+/// [models]
+/// dir = "/var/lib/kreuzberg/models"
+///
+/// [[models.assets]]
+/// name = "ppocr-v4"
+/// kind = "onnx"
+/// version = "1"
+/// url = "https://models.example.com/ppocr-v4/det.onnx"
+/// sha256 = "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelsConfig {
+    /// Directory of the model assets (None = `$KREUZBERG_MODELS_DIR`, or
+    /// `.kreuzberg/models` in the current directory)
+    #[serde(default)]
+    pub dir: Option<PathBuf>,
+
+    /// Never download; assets that are not installed are an error. Also enabled by
+    /// `KREUZBERG_MODELS_OFFLINE=1`
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub offline: bool,
+
+    /// Timeout of each download, in seconds
+    ///
+    /// Default: 600
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+
+    /// Assets installed by `ModelManager::ensure_all`
+    #[serde(default)]
+    pub assets: Vec<ModelAsset>,
+}
+
+impl Default for ModelsConfig {
+    fn default() -> Self {
+        Self {
+            dir: None,
+            offline: false,
+            timeout_secs: default_timeout_secs(),
+            assets: Vec::new(),
+        }
+    }
+}
+
+/// Kind of a model asset, the top-level directory of its files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ModelKind {
+    /// Tesseract trained data (`*.traineddata`)
+    Tessdata,
+    /// ONNX models and their dictionaries
+    Onnx,
+    /// Tokenizer files
+    Tokenizer,
+}
+
+impl ModelKind {
+    /// Directory name of the kind.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Tessdata => "tessdata",
+            Self::Onnx => "onnx",
+            Self::Tokenizer => "tokenizer",
+        }
+    }
+}
+
+/// A model file.
+///
+/// Files of the same kind, name and version are installed in one directory,
+/// `<dir>/<kind>/<name>/<version>/`, so a model made of several files (an ONNX
+/// detection model, recognition model and dictionary, or the trained data of several
+/// languages) is a set of assets sharing these three fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModelAsset {
+    /// Name of the model, e.g. `tessdata_fast` or `ppocr-v4`
+    pub name: String,
+
+    /// Kind of the model
+    pub kind: ModelKind,
+
+    /// Version of the model; installed versions are kept side by side
+    pub version: String,
+
+    /// Source of the file: an `https://`, `http://` or `file://` URL
+    pub url: String,
+
+    /// Expected lowercase hex SHA-256 of the file (None = not verified)
+    #[serde(default)]
+    pub sha256: Option<String>,
+
+    /// File name (None = the last path segment of `url`)
+    #[serde(default)]
+    pub file: Option<String>,
+}
+
+impl ModelAsset {
+    /// The trained data of `language` from the Tesseract repository `repo`
+    /// (`tessdata`, `tessdata_fast` or `tessdata_best`) at the tag `version`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kreuzberg::core::config::ModelAsset;
+    ///
+    /// let asset = ModelAsset::tessdata("tessdata_fast", "4.1.0", "deu");
+    /// assert_eq!(asset.file_name(), "deu.traineddata");
+    /// ```
+    pub fn tessdata(repo: &str, version: &str, language: &str) -> Self {
+        Self {
+            name: repo.to_string(),
+            kind: ModelKind::Tessdata,
+            version: version.to_string(),
+            url: format!(
+                "https://github.com/tesseract-ocr/{}/raw/{}/{}.traineddata",
+                repo, version, language
+            ),
+            sha256: None,
+            file: Some(format!("{}.traineddata", language)),
+        }
+    }
+
+    /// Pin the expected SHA-256 of the file.
+    pub fn with_sha256(mut self, sha256: impl Into<String>) -> Self {
+        self.sha256 = Some(sha256.into());
+        self
+    }
+
+    /// File name of the asset.
+    pub fn file_name(&self) -> &str {
+        self.file.as_deref().unwrap_or_else(|| {
+            let path = self.url.split(['?', '#']).next().unwrap_or_default();
+            path.rsplit('/').next().unwrap_or_default()
+        })
+    }
+
+    /// Check that the name, version and file name are single path segments and that
+    /// the checksum is a SHA-256 hex digest.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` describing the first invalid field.
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [
+            ("name", self.name.as_str()),
+            ("version", self.version.as_str()),
+            ("file", self.file_name()),
+        ] {
+            if value.is_empty() || value.starts_with('.') || value.contains(['/', '\\', '\0']) {
+                return Err(KreuzbergError::validation(format!(
+                    "Model asset {} '{}' must be a non-empty file name not starting with '.'",
+                    field, value
+                )));
+            }
+        }
+        if let Some(sha256) = &self.sha256
+            && (sha256.len() != 64 || !sha256.bytes().all(|b| b.is_ascii_hexdigit()))
+        {
+            return Err(KreuzbergError::validation(format!(
+                "Model asset '{}' sha256 is not a SHA-256 hex digest: '{}'",
+                self.name, sha256
+            )));
+        }
+        Ok(())
+    }
+}
+
+fn default_timeout_secs() -> u64 {
+    600
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_models_config_from_toml() {
+        let config: ModelsConfig = toml::from_str(
+            r#"
+            dir = "/models"
+
+            [[assets]]
+            name = "ppocr-v4"
+            kind = "onnx"
+            version = "1"
+            url = "https://models.example.com/ppocr-v4/det.onnx?download=1"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.dir, Some(PathBuf::from("/models")));
+        assert_eq!(config.timeout_secs, 600);
+        assert_eq!(config.assets[0].kind, ModelKind::Onnx);
+        assert_eq!(config.assets[0].file_name(), "det.onnx");
+        assert!(config.assets[0].validate().is_ok());
+        assert_eq!(toml::from_str::<ModelsConfig>("").unwrap(), ModelsConfig::default());
+
+        let asset = ModelAsset::tessdata("tessdata_fast", "4.1.0", "eng");
+        assert_eq!(
+            asset.url,
+            "https://github.com/tesseract-ocr/tessdata_fast/raw/4.1.0/eng.traineddata"
+        );
+        assert!(asset.validate().is_ok());
+        assert!(asset.clone().with_sha256("abc").validate().is_err());
+        assert!(
+            ModelAsset {
+                version: "../1".to_string(),
+                ..asset.clone()
+            }
+            .validate()
+            .is_err()
+        );
+        assert!(
+            ModelAsset {
+                file: None,
+                url: "https://example.com/".to_string(),
+                ..asset
+            }
+            .validate()
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Remote resource policy configuration.
+//!
+//! Documents can reference resources on other hosts: external images and stylesheets
+//! in HTML, SVG and EPUB files, or external entities in XML. Fetching them on behalf
+//! of an uploaded document lets its author make the server send requests (SSRF), so
+//! remote resources are never fetched unless this policy allows it.
+//!
+//! Air-gapped deployments can go further and deny all network access with
+//! `network = "deny"` or `KREUZBERG_NETWORK=deny`: extractions that would need the
+//! network, for a plugin calling a remote service or an embedding model that is not
+//! downloaded yet, then fail before they start.
+
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Environment variable denying network access to the whole process when set to `deny`.
+pub const NETWORK_ENV: &str = "KREUZBERG_NETWORK";
+
+/// Remote resource policy.
+///
+/// # Example
+///
+/// ```toml
+/// [network]
+/// allow_remote = true
+/// allowed_hosts = ["cdn.example.com", "*.images.example.com"]
+/// max_fetch_bytes = 5242880
+/// ```
+///
+/// `network = "deny"` is shorthand for a policy with `deny = true`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkConfig {
+    /// Deny all network access: nothing is fetched, and extractions that would need the
+    /// network fail with a security error. Overrides `allow_remote`
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub deny: bool,
+
+    /// Fetch remote resources allowed by this policy (false = never fetch)
+    #[serde(default)]
+    pub allow_remote: bool,
+
+    /// Hosts remote resources may be fetched from; `*.example.com` matches the
+    /// subdomains of `example.com` (empty = any public host)
+    #[serde(default)]
+    pub allowed_hosts: Vec<String>,
+
+    /// Largest response body fetched, in bytes
+    ///
+    /// Default: 10 MiB
+    #[serde(default = "default_max_fetch_bytes")]
+    pub max_fetch_bytes: u64,
+
+    /// Timeout of each fetch, in seconds
+    ///
+    /// Default: 10
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for NetworkConfig {
+    fn default() -> Self {
+        Self {
+            deny: false,
+            allow_remote: false,
+            allowed_hosts: Vec::new(),
+            max_fetch_bytes: default_max_fetch_bytes(),
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+impl NetworkConfig {
+    /// A policy denying all network access.
+    pub fn denied() -> Self {
+        Self {
+            deny: true,
+            ..Default::default()
+        }
+    }
+
+    /// Whether `KREUZBERG_NETWORK=deny` denies network access to the whole process.
+    pub fn denied_by_environment() -> bool {
+        std::env::var(NETWORK_ENV).is_ok_and(|value| value.trim().eq_ignore_ascii_case("deny"))
+    }
+
+    /// Whether `host` is allowed by `allowed_hosts`.
+    pub fn allows_host(&self, host: &str) -> bool {
+        let host = host.trim_end_matches('.');
+        self.allowed_hosts.is_empty()
+            || self
+                .allowed_hosts
+                .iter()
+                .any(|pattern| match pattern.strip_prefix("*.") {
+                    Some(domain) => host.len().checked_sub(domain.len() + 1).is_some_and(|dot| {
+                        host.as_bytes()[dot] == b'.' && host[dot + 1..].eq_ignore_ascii_case(domain)
+                    }),
+                    None => host.eq_ignore_ascii_case(pattern),
+                })
+    }
+}
+
+/// Deserialize `ExtractionConfig::network` from either a policy or the string
+/// `"deny"`, the shorthand for a policy denying all network access.
+pub(crate) fn deserialize_network_config<'de, D>(
+    deserializer: D,
+) -> std::result::Result<Option<NetworkConfig>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum NetworkConfigInput {
+        Mode(String),
+        Config(NetworkConfig),
+    }
+
+    match Option::<NetworkConfigInput>::deserialize(deserializer)? {
+        None => Ok(None),
+        Some(NetworkConfigInput::Config(config)) => Ok(Some(config)),
+        Some(NetworkConfigInput::Mode(mode)) if mode.eq_ignore_ascii_case("deny") => Ok(Some(NetworkConfig::denied())),
+        Some(NetworkConfigInput::Mode(mode)) => Err(serde::de::Error::custom(format!(
+            "unknown network mode '{}', expected \"deny\" or a network policy",
+            mode
+        ))),
+    }
+}
+
+fn default_max_fetch_bytes() -> u64 {
+    10 * 1024 * 1024
+}
+
+fn default_timeout_secs() -> u64 {
+    10
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_config_from_toml() {
+        let config: NetworkConfig =
+            toml::from_str("allow_remote = true\nallowed_hosts = [\"cdn.example.com\", \"*.example.org\"]").unwrap();
+        assert!(config.allow_remote);
+        assert_eq!(config.max_fetch_bytes, 10 * 1024 * 1024);
+        assert!(config.allows_host("CDN.example.com"));
+        assert!(config.allows_host("img.example.org"));
+        assert!(!config.allows_host("example.org"));
+        assert!(!config.allows_host("evilexample.org"));
+        assert!(!config.allows_host("cdn.example.com.evil.net"));
+
+        let config: NetworkConfig = toml::from_str("").unwrap();
+        assert_eq!(config, NetworkConfig::default());
+        assert!(!config.allow_remote);
+        assert!(config.allows_host("anything.example"));
+        assert!(!config.deny);
+    }
+
+    #[test]
+    fn test_network_deny_shorthand() {
+        #[derive(Deserialize)]
+        struct Wrapper {
+            #[serde(default, deserialize_with = "deserialize_network_config")]
+            network: Option<NetworkConfig>,
+        }
+
+        let parse = |toml: &str| toml::from_str::<Wrapper>(toml).map(|wrapper| wrapper.network);
+        assert_eq!(parse("network = \"deny\"").unwrap(), Some(NetworkConfig::denied()));
+        assert_eq!(
+            parse("[network]\nallow_remote = true")
+                .unwrap()
+                .map(|network| network.allow_remote),
+            Some(true)
+        );
+        assert_eq!(parse("").unwrap(), None);
+        assert!(parse("network = \"sometimes\"").is_err());
+    }
+}
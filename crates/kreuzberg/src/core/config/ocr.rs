@@ -4,8 +4,12 @@
 //! and Tesseract-specific parameters.
 
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
 
 use super::formats::OutputFormat;
+use super::thread_pool::ThreadPoolConfig;
+use crate::types::TesseractConfig;
 
 /// OCR configuration.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -14,26 +18,306 @@ pub struct OcrConfig {
     #[serde(default = "default_tesseract_backend")]
     pub backend: String,
 
+    /// OCR backends whose outputs are merged by voting (empty = only `backend`)
+    ///
+    /// Every image and page is recognized by each backend; the outputs are aligned
+    /// word by word and every word is chosen by a vote weighted by the confidence of
+    /// the backends. Improves accuracy on degraded scans at the cost of running every
+    /// backend. `backend` is ignored when set.
+    #[serde(default)]
+    pub ensemble: Vec<String>,
+
     /// Language code (e.g., "eng", "deu")
     #[serde(default = "default_eng")]
     pub language: String,
 
     /// Tesseract-specific configuration (optional)
     #[serde(default)]
-    pub tesseract_config: Option<crate::types::TesseractConfig>,
+    pub tesseract_config: Option<TesseractConfig>,
 
     /// Output format for OCR results (optional, for format conversion)
     #[serde(default)]
     pub output_format: Option<OutputFormat>,
+
+    /// DPI used to render PDF pages before OCR (None = 300)
+    ///
+    /// Higher values improve accuracy on small text at the cost of speed and memory.
+    /// Pages are still scaled down when the rendered image would exceed size limits.
+    #[serde(default)]
+    pub dpi: Option<i32>,
+
+    /// Callback returning per-page overrides (programmatic only, never serialized)
+    #[serde(skip)]
+    pub page_overrides: Option<OcrPageOverrideCallback>,
+
+    /// Maximum number of pages OCRed in parallel (None = number of CPU cores)
+    ///
+    /// Pages of multi-page documents are dispatched to a bounded worker pool and
+    /// reassembled in page order. Use 1 for sequential processing.
+    #[serde(default)]
+    pub concurrency: Option<usize>,
+
+    /// Dedicated worker threads for OCR (None = the Tokio blocking pool)
+    ///
+    /// Sets the thread count, CPU core affinity and nice level of the workers.
+    /// `concurrency` defaults to the thread count of the pool.
+    #[serde(default)]
+    pub thread_pool: Option<ThreadPoolConfig>,
+
+    /// Handwritten text recognition: `off`, `on` or `auto`
+    ///
+    /// `on` reads every text region with the backend's handwriting model, `auto`
+    /// only the regions the printed-text model reads poorly. Requires a backend with
+    /// handwriting support, such as `onnx-ocr` with a handwriting model; `auto`
+    /// falls back to printed-text recognition on other backends.
+    #[serde(default)]
+    pub handwriting: HandwritingMode,
+
+    /// Detect sideways and upside-down pages and rotate them upright before OCR
+    ///
+    /// The applied rotation (0, 90, 180 or 270 degrees clockwise) is recorded per
+    /// page in `metadata.pages`.
+    #[serde(default)]
+    pub auto_rotate: bool,
+
+    /// Skip OCR on blank and near-blank pages (None = OCR every page)
+    ///
+    /// Skipped pages yield no text and are marked `blank` in `metadata.pages`.
+    #[serde(default)]
+    pub skip_blank_pages: Option<BlankPageConfig>,
+
+    /// Detect pages repeating an earlier page of the document, such as double-fed scans
+    /// (None = no detection)
+    ///
+    /// Duplicates are listed in `metadata.additional["duplicate_pages"]`.
+    #[serde(default)]
+    pub duplicate_pages: Option<DuplicatePageConfig>,
+}
+
+/// Blank page detection settings.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BlankPageConfig {
+    /// Share of the page covered by ink (0.0-1.0) up to which a page counts as blank
+    ///
+    /// Margins and isolated specks are not counted. The default of 0.0005 treats pages
+    /// with scanner artifacts as blank but keeps pages with a single line of text.
+    pub ink_threshold: f64,
+}
+
+impl Default for BlankPageConfig {
+    fn default() -> Self {
+        Self { ink_threshold: 0.0005 }
+    }
+}
+
+/// Duplicate page detection settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DuplicatePageConfig {
+    /// Maximum number of differing bits between the 255-bit perceptual hashes of two
+    /// pages for them to count as duplicates
+    ///
+    /// Rescans of the same sheet typically differ by less than 16 bits, different pages
+    /// of the same layout by more than 70.
+    pub max_distance: u32,
+
+    /// Leave duplicate pages out of the OCR output
+    ///
+    /// Dropped pages are reported in `metadata.additional["duplicate_page_warnings"]`.
+    pub drop_duplicates: bool,
+}
+
+impl Default for DuplicatePageConfig {
+    fn default() -> Self {
+        Self {
+            max_distance: 32,
+            drop_duplicates: false,
+        }
+    }
+}
+
+/// Whether text regions are read with a handwriting recognition model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HandwritingMode {
+    /// Read regions the printed-text model is unsure about with the handwriting model
+    Auto,
+    /// Read every region with the handwriting model
+    On,
+    /// Printed-text recognition only
+    #[default]
+    Off,
+}
+
+/// Information about the page being OCRed, passed to [`OcrPageOverrideCallback`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OcrPageContext {
+    /// Page number (1-indexed)
+    pub page_number: usize,
+    /// Total number of pages in the document
+    pub page_count: usize,
+    /// Page width in PDF points (0.0 for images)
+    pub width_points: f32,
+    /// Page height in PDF points (0.0 for images)
+    pub height_points: f32,
+}
+
+/// Settings overriding the OCR configuration for a single page.
+///
+/// Unset fields keep the value from [`OcrConfig`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OcrPageOverrides {
+    /// Rendering DPI for this page (PDF only)
+    pub dpi: Option<i32>,
+    /// Language code for this page (e.g., "eng+deu")
+    pub language: Option<String>,
+    /// Tesseract Page Segmentation Mode (0-13)
+    pub psm: Option<i32>,
+    /// Tesseract OCR Engine Mode (0-3)
+    pub oem: Option<i32>,
+    /// Whitelist of allowed characters
+    pub tessedit_char_whitelist: Option<String>,
+    /// Blacklist of forbidden characters
+    pub tessedit_char_blacklist: Option<String>,
+    /// Handwriting recognition mode for this page
+    pub handwriting: Option<HandwritingMode>,
+    /// Orientation correction for this page
+    pub auto_rotate: Option<bool>,
+}
+
+impl OcrPageOverrides {
+    /// Apply the overrides on top of `config`, returning the effective page configuration.
+    pub fn apply(&self, config: &OcrConfig) -> OcrConfig {
+        let mut page_config = config.clone();
+        page_config.page_overrides = None;
+
+        if let Some(dpi) = self.dpi {
+            page_config.dpi = Some(dpi);
+        }
+        if let Some(language) = &self.language {
+            page_config.language = language.clone();
+            if let Some(tess) = page_config.tesseract_config.as_mut() {
+                tess.language = language.clone();
+            }
+        }
+
+        if let Some(handwriting) = self.handwriting {
+            page_config.handwriting = handwriting;
+        }
+        if let Some(auto_rotate) = self.auto_rotate {
+            page_config.auto_rotate = auto_rotate;
+        }
+
+        let touches_tesseract = self.psm.is_some()
+            || self.oem.is_some()
+            || self.tessedit_char_whitelist.is_some()
+            || self.tessedit_char_blacklist.is_some();
+        if touches_tesseract {
+            let language = page_config.language.clone();
+            let tess = page_config.tesseract_config.get_or_insert_with(|| TesseractConfig {
+                language,
+                ..Default::default()
+            });
+            if let Some(psm) = self.psm {
+                tess.psm = psm;
+            }
+            if let Some(oem) = self.oem {
+                tess.oem = oem;
+            }
+            if let Some(whitelist) = &self.tessedit_char_whitelist {
+                tess.tessedit_char_whitelist = whitelist.clone();
+            }
+            if let Some(blacklist) = &self.tessedit_char_blacklist {
+                tess.tessedit_char_blacklist = blacklist.clone();
+            }
+        }
+
+        page_config
+    }
+}
+
+type PageOverrideFn = dyn Fn(&OcrPageContext) -> Option<OcrPageOverrides> + Send + Sync;
+
+/// Callback deciding per-page OCR overrides.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::core::config::{OcrConfig, OcrPageOverrideCallback, OcrPageOverrides};
+///
+/// let config = OcrConfig {
+///     // Render the cover page at a higher resolution
+///     page_overrides: Some(OcrPageOverrideCallback::new(|page| {
+///         (page.page_number == 1).then(|| OcrPageOverrides {
+///             dpi: Some(600),
+///             ..Default::default()
+///         })
+///     })),
+///     ..Default::default()
+/// };
+/// # assert!(config.page_overrides.is_some());
+/// ```
+#[derive(Clone)]
+pub struct OcrPageOverrideCallback(Arc<PageOverrideFn>);
+
+impl OcrPageOverrideCallback {
+    /// Wrap a closure as a page override callback.
+    pub fn new<F>(callback: F) -> Self
+    where
+        F: Fn(&OcrPageContext) -> Option<OcrPageOverrides> + Send + Sync + 'static,
+    {
+        Self(Arc::new(callback))
+    }
+
+    /// Get the overrides for a page, if any.
+    pub fn overrides_for(&self, page: &OcrPageContext) -> Option<OcrPageOverrides> {
+        (self.0)(page)
+    }
+}
+
+impl fmt::Debug for OcrPageOverrideCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OcrPageOverrideCallback(..)")
+    }
+}
+
+impl OcrConfig {
+    /// Resolve the effective configuration for a page, applying the override callback.
+    pub fn for_page(&self, page: &OcrPageContext) -> OcrConfig {
+        match self.page_overrides.as_ref().and_then(|cb| cb.overrides_for(page)) {
+            Some(overrides) => overrides.apply(self),
+            None => self.clone(),
+        }
+    }
+
+    /// Number of pages to OCR in parallel, resolving `None` to the thread count of
+    /// `thread_pool`, or the number of CPU cores.
+    pub fn effective_concurrency(&self) -> usize {
+        self.concurrency
+            .or_else(|| self.thread_pool.as_ref().map(ThreadPoolConfig::effective_threads))
+            .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1))
+            .max(1)
+    }
 }
 
 impl Default for OcrConfig {
     fn default() -> Self {
         Self {
             backend: default_tesseract_backend(),
+            ensemble: Vec::new(),
             language: default_eng(),
             tesseract_config: None,
             output_format: None,
+            dpi: None,
+            page_overrides: None,
+            concurrency: None,
+            thread_pool: None,
+            handwriting: HandwritingMode::Off,
+            auto_rotate: false,
+            skip_blank_pages: None,
+            duplicate_pages: None,
         }
     }
 }
@@ -66,8 +350,131 @@ mod tests {
             language: "fra".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         };
         assert_eq!(config.backend, "tesseract");
         assert_eq!(config.language, "fra");
     }
+
+    #[test]
+    fn test_ocr_config_dpi_from_toml() {
+        let config: OcrConfig = toml::from_str("dpi = 400").unwrap();
+        assert_eq!(config.dpi, Some(400));
+        assert!(config.page_overrides.is_none());
+    }
+
+    #[test]
+    fn test_page_overrides_apply_creates_tesseract_config() {
+        let config = OcrConfig {
+            language: "deu".to_string(),
+            ..Default::default()
+        };
+        let overrides = OcrPageOverrides {
+            psm: Some(6),
+            tessedit_char_whitelist: Some("0123456789".to_string()),
+            ..Default::default()
+        };
+
+        let page_config = overrides.apply(&config);
+        let tess = page_config.tesseract_config.unwrap();
+        assert_eq!(tess.language, "deu");
+        assert_eq!(tess.psm, 6);
+        assert_eq!(tess.tessedit_char_whitelist, "0123456789");
+    }
+
+    #[test]
+    fn test_for_page_uses_callback() {
+        let config = OcrConfig {
+            page_overrides: Some(OcrPageOverrideCallback::new(|page| {
+                (page.page_number == 2).then(|| OcrPageOverrides {
+                    dpi: Some(600),
+                    language: Some("fra".to_string()),
+                    ..Default::default()
+                })
+            })),
+            ..Default::default()
+        };
+        let page = |page_number| OcrPageContext {
+            page_number,
+            page_count: 2,
+            width_points: 612.0,
+            height_points: 792.0,
+        };
+
+        let first = config.for_page(&page(1));
+        assert_eq!(first.dpi, None);
+        assert_eq!(first.language, "eng");
+
+        let second = config.for_page(&page(2));
+        assert_eq!(second.dpi, Some(600));
+        assert_eq!(second.language, "fra");
+        assert!(second.page_overrides.is_none());
+    }
+
+    #[test]
+    fn test_handwriting_mode_from_toml_and_overrides() {
+        let config: OcrConfig = toml::from_str(r#"handwriting = "auto""#).unwrap();
+        assert_eq!(config.handwriting, HandwritingMode::Auto);
+        assert_eq!(OcrConfig::default().handwriting, HandwritingMode::Off);
+
+        let overrides = OcrPageOverrides {
+            handwriting: Some(HandwritingMode::On),
+            ..Default::default()
+        };
+        let page_config = overrides.apply(&config);
+        assert_eq!(page_config.handwriting, HandwritingMode::On);
+        assert!(page_config.tesseract_config.is_none());
+    }
+
+    #[test]
+    fn test_auto_rotate_from_toml_and_overrides() {
+        let config: OcrConfig = toml::from_str("auto_rotate = true").unwrap();
+        assert!(config.auto_rotate);
+        assert!(!OcrConfig::default().auto_rotate);
+
+        let overrides = OcrPageOverrides {
+            auto_rotate: Some(false),
+            ..Default::default()
+        };
+        assert!(!overrides.apply(&config).auto_rotate);
+    }
+
+    #[test]
+    fn test_skip_blank_pages_from_toml() {
+        let config: OcrConfig = toml::from_str("[skip_blank_pages]\nink_threshold = 0.002").unwrap();
+        assert_eq!(config.skip_blank_pages, Some(BlankPageConfig { ink_threshold: 0.002 }));
+
+        let config: OcrConfig = toml::from_str("[skip_blank_pages]").unwrap();
+        assert_eq!(config.skip_blank_pages, Some(BlankPageConfig::default()));
+        assert!(OcrConfig::default().skip_blank_pages.is_none());
+    }
+
+    #[test]
+    fn test_duplicate_pages_from_toml() {
+        let config: OcrConfig = toml::from_str("[duplicate_pages]\ndrop_duplicates = true").unwrap();
+        assert_eq!(
+            config.duplicate_pages,
+            Some(DuplicatePageConfig {
+                max_distance: 32,
+                drop_duplicates: true,
+            })
+        );
+        assert!(OcrConfig::default().duplicate_pages.is_none());
+    }
+
+    #[test]
+    fn test_effective_concurrency() {
+        let sequential = OcrConfig {
+            concurrency: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(sequential.effective_concurrency(), 1);
+
+        let config: OcrConfig = serde_json::from_str(r#"{"concurrency": 4}"#).unwrap();
+        assert_eq!(config.effective_concurrency(), 4);
+        assert!(OcrConfig::default().effective_concurrency() >= 1);
+
+        let config: OcrConfig = serde_json::from_str(r#"{"thread_pool": {"cpu_affinity": "0-5"}}"#).unwrap();
+        assert_eq!(config.effective_concurrency(), 6);
+    }
 }
@@ -0,0 +1,129 @@
+//! Output text normalization configuration.
+//!
+//! Named profiles bundle the whitespace, casing, punctuation and newline handling
+//! applied to extracted text, so consumers indexing or displaying text get the same
+//! normalization instead of each implementing their own.
+
+use serde::{Deserialize, Serialize};
+
+/// Output text configuration.
+///
+/// # Example
+///
+/// ```toml
+/// [output]
+/// profile = "search"
+/// max_chars = 20000
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputConfig {
+    /// Normalization applied to the content, page contents and chunks
+    #[serde(default)]
+    pub profile: NormalizationProfile,
+
+    /// Maximum number of characters of content (None = no limit)
+    ///
+    /// Longer content is cut at the last paragraph or sentence boundary within the
+    /// limit, never mid-word, and the truncation is recorded in
+    /// `metadata.additional["truncation_warnings"]`. Metadata, such as the word and page
+    /// counts, still describes the whole document.
+    #[serde(default)]
+    pub max_chars: Option<usize>,
+}
+
+/// Named bundle of text normalization options.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NormalizationProfile {
+    /// Text as extracted
+    #[default]
+    Raw,
+    /// Lowercased single-line text with plain punctuation and collapsed whitespace, for
+    /// indexing and matching
+    Search,
+    /// Collapsed whitespace and at most one blank line between paragraphs, for showing
+    /// text to people
+    Display,
+}
+
+/// How a normalization profile treats line breaks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NewlinePolicy {
+    /// Keep line breaks as they are
+    Preserve,
+    /// Unify line breaks to `\n`, keep at most one blank line between paragraphs and
+    /// drop leading and trailing blank lines
+    Paragraphs,
+    /// Join all lines with single spaces
+    Join,
+}
+
+/// Text normalization options bundled by a [`NormalizationProfile`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NormalizationOptions {
+    /// Replace runs of spaces, tabs and other whitespace with a single space, trim lines
+    /// and remove control characters
+    pub collapse_whitespace: bool,
+    /// Lowercase the text
+    pub casefold: bool,
+    /// Replace typographic quotes, dashes and ellipses with their ASCII forms and remove
+    /// soft hyphens and zero-width spaces
+    pub normalize_punctuation: bool,
+    /// Line break handling
+    pub newlines: NewlinePolicy,
+}
+
+impl NormalizationOptions {
+    /// Whether the options leave text unchanged.
+    pub fn is_identity(&self) -> bool {
+        *self == NormalizationProfile::Raw.options()
+    }
+}
+
+impl NormalizationProfile {
+    /// The normalization options the profile stands for.
+    pub fn options(self) -> NormalizationOptions {
+        match self {
+            Self::Raw => NormalizationOptions {
+                collapse_whitespace: false,
+                casefold: false,
+                normalize_punctuation: false,
+                newlines: NewlinePolicy::Preserve,
+            },
+            Self::Search => NormalizationOptions {
+                collapse_whitespace: true,
+                casefold: true,
+                normalize_punctuation: true,
+                newlines: NewlinePolicy::Join,
+            },
+            Self::Display => NormalizationOptions {
+                collapse_whitespace: true,
+                casefold: false,
+                normalize_punctuation: false,
+                newlines: NewlinePolicy::Paragraphs,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_config_from_toml() {
+        let config: OutputConfig = toml::from_str("profile = \"search\"").unwrap();
+        assert_eq!(config.profile, NormalizationProfile::Search);
+        assert!(config.profile.options().casefold);
+
+        let config: OutputConfig = toml::from_str("").unwrap();
+        assert_eq!(config.profile, NormalizationProfile::Raw);
+        assert!(config.profile.options().is_identity());
+        assert!(toml::from_str::<OutputConfig>("profile = \"fancy\"").is_err());
+
+        let config: OutputConfig = toml::from_str("max_chars = 500").unwrap();
+        assert_eq!(config.max_chars, Some(500));
+        assert_eq!(config.profile, NormalizationProfile::Raw);
+    }
+}
@@ -3,7 +3,10 @@
 //! Controls how pages are extracted, tracked, and represented in extraction results.
 //! When `None`, page tracking is disabled.
 
-use serde::{Deserialize, Serialize};
+use crate::{KreuzbergError, Result};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
 
 /// Page extraction and tracking configuration.
 ///
@@ -27,6 +30,14 @@ pub struct PageConfig {
     /// Default: "\n\n<!-- PAGE {page_num} -->\n\n"
     #[serde(default = "default_page_marker_format")]
     pub marker_format: String,
+
+    /// Pages to extract, such as `"1-10,15,20-"` (None = all pages)
+    ///
+    /// Pages outside the selection are skipped by paginated extractors and OCR. The
+    /// selected pages keep their original page numbers in boundaries, page content,
+    /// and page metadata.
+    #[serde(default)]
+    pub selection: Option<PageSelection>,
 }
 
 impl Default for PageConfig {
@@ -35,10 +46,163 @@ impl Default for PageConfig {
             extract_pages: false,
             insert_page_markers: false,
             marker_format: "\n\n<!-- PAGE {page_num} -->\n\n".to_string(),
+            selection: None,
+        }
+    }
+}
+
+impl PageConfig {
+    /// Append the marker of page `page_number` to `content`, writing the page number in
+    /// place of every `{page_num}` without building the marker as a separate string.
+    pub fn push_marker(&self, content: &mut String, page_number: usize) {
+        use std::fmt::Write;
+
+        let mut parts = self.marker_format.split("{page_num}");
+        content.push_str(parts.next().unwrap_or_default());
+        for part in parts {
+            let _ = write!(content, "{page_number}");
+            content.push_str(part);
+        }
+    }
+
+    /// Whether page `page_number` (1-indexed) is selected for extraction.
+    pub fn includes_page(&self, page_number: usize) -> bool {
+        self.selection
+            .as_ref()
+            .is_none_or(|selection| selection.contains(page_number))
+    }
+}
+
+/// A set of 1-indexed pages, written as comma-separated page numbers and ranges.
+///
+/// A range is inclusive and may be open-ended: `"3-"` selects page 3 through the last
+/// page, and `"-3"` the first three pages. Selections serialize to and from this
+/// string form.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::PageSelection;
+///
+/// let selection: PageSelection = "1-10,15,20-".parse().unwrap();
+/// assert!(selection.contains(15));
+/// assert!(selection.contains(2000));
+/// assert!(!selection.contains(11));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PageSelection {
+    /// Inclusive ranges in the order written; `None` ends a range at the last page
+    ranges: Vec<(usize, Option<usize>)>,
+}
+
+impl PageSelection {
+    /// Whether page `page_number` (1-indexed) is selected.
+    pub fn contains(&self, page_number: usize) -> bool {
+        self.ranges
+            .iter()
+            .any(|&(start, end)| page_number >= start && end.is_none_or(|end| page_number <= end))
+    }
+
+    /// The selected pages of a document of `page_count` pages, in ascending order.
+    pub fn pages(&self, page_count: usize) -> Vec<usize> {
+        (1..=page_count).filter(|&page| self.contains(page)).collect()
+    }
+}
+
+impl FromStr for PageSelection {
+    type Err = KreuzbergError;
+
+    fn from_str(spec: &str) -> Result<Self> {
+        let invalid = |reason: &str| KreuzbergError::validation(format!("Invalid page selection '{spec}': {reason}"));
+        let page = |number: &str| match number.trim().parse::<usize>() {
+            Ok(0) => Err(invalid("pages are numbered from 1")),
+            Ok(page) => Ok(page),
+            Err(_) => Err(invalid(&format!("'{}' is not a page number", number.trim()))),
+        };
+
+        let mut ranges = Vec::new();
+        for part in spec.split(',') {
+            let part = part.trim();
+            if part.is_empty() {
+                return Err(invalid("empty page range"));
+            }
+            let range = match part.split_once('-') {
+                None => {
+                    let page = page(part)?;
+                    (page, Some(page))
+                }
+                Some((start, end)) => {
+                    let start = if start.trim().is_empty() { 1 } else { page(start)? };
+                    let end = if end.trim().is_empty() { None } else { Some(page(end)?) };
+                    if end.is_some_and(|end| end < start) {
+                        return Err(invalid(&format!("range '{part}' ends before it starts")));
+                    }
+                    if end.is_none() && part == "-" {
+                        return Err(invalid("range '-' has no bounds"));
+                    }
+                    (start, end)
+                }
+            };
+            ranges.push(range);
         }
+
+        Ok(Self { ranges })
     }
 }
 
+impl fmt::Display for PageSelection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, &(start, end)) in self.ranges.iter().enumerate() {
+            if index > 0 {
+                f.write_str(",")?;
+            }
+            match end {
+                Some(end) if end == start => write!(f, "{start}")?,
+                Some(end) => write!(f, "{start}-{end}")?,
+                None => write!(f, "{start}-")?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for PageSelection {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PageSelection {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        let spec = String::deserialize(deserializer)?;
+        spec.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Deserialize `ExtractionConfig::pages` from either a page configuration or a page
+/// selection string, the shorthand for a configuration selecting those pages.
+pub(crate) fn deserialize_page_config<'de, D>(deserializer: D) -> std::result::Result<Option<PageConfig>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum PageConfigInput {
+        Selection(PageSelection),
+        Config(PageConfig),
+    }
+
+    Ok(
+        Option::<PageConfigInput>::deserialize(deserializer)?.map(|input| match input {
+            PageConfigInput::Selection(selection) => PageConfig {
+                selection: Some(selection),
+                ..Default::default()
+            },
+            PageConfigInput::Config(config) => config,
+        }),
+    )
+}
+
 fn default_page_marker_format() -> String {
     "\n\n<!-- PAGE {page_num} -->\n\n".to_string()
 }
@@ -54,4 +218,53 @@ mod tests {
         assert!(!config.insert_page_markers);
         assert_eq!(config.marker_format, "\n\n<!-- PAGE {page_num} -->\n\n");
     }
+
+    #[test]
+    fn test_push_marker_replaces_every_placeholder() {
+        let config = PageConfig {
+            marker_format: "[{page_num}/{page_num}]".to_string(),
+            ..Default::default()
+        };
+        let mut content = "text".to_string();
+        config.push_marker(&mut content, 12);
+        assert_eq!(content, "text[12/12]");
+    }
+
+    #[test]
+    fn test_page_selection_parse() {
+        let selection: PageSelection = "1-10, 15,20-".parse().unwrap();
+        assert_eq!(selection.to_string(), "1-10,15,20-");
+        assert!(selection.contains(1) && selection.contains(10) && selection.contains(15));
+        assert!(selection.contains(20) && selection.contains(2000));
+        assert!(!selection.contains(11) && !selection.contains(19));
+        assert_eq!(selection.pages(22), vec![1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 15, 20, 21, 22]);
+
+        let selection: PageSelection = "-3".parse().unwrap();
+        assert_eq!(selection.pages(10), vec![1, 2, 3]);
+
+        for invalid in ["", "0", "1,,2", "5-3", "a-b", "-", "1-2-3"] {
+            assert!(
+                invalid.parse::<PageSelection>().is_err(),
+                "{invalid:?} should be rejected"
+            );
+        }
+    }
+
+    #[test]
+    fn test_page_config_selection_shorthand() {
+        let config: crate::ExtractionConfig = serde_json::from_str(r#"{"pages": "2-3"}"#).unwrap();
+        let pages = config.pages.unwrap();
+        assert!(!pages.extract_pages);
+        assert!(pages.includes_page(2) && !pages.includes_page(4));
+
+        let config: crate::ExtractionConfig =
+            serde_json::from_str(r#"{"pages": {"extract_pages": true, "selection": "5"}}"#).unwrap();
+        let pages = config.pages.unwrap();
+        assert!(pages.extract_pages);
+        assert_eq!(pages.selection.unwrap().to_string(), "5");
+
+        assert!(serde_json::from_str::<crate::ExtractionConfig>(r#"{"pages": "3-1"}"#).is_err());
+        let config: crate::ExtractionConfig = serde_json::from_str(r#"{"pages": null}"#).unwrap();
+        assert!(config.pages.is_none());
+    }
 }
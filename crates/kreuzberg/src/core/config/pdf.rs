@@ -24,6 +24,225 @@ pub struct PdfConfig {
     /// Hierarchy extraction configuration (None = hierarchy extraction disabled)
     #[serde(default)]
     pub hierarchy: Option<HierarchyConfig>,
+
+    /// How to handle ruby (furigana) annotations in CJK text
+    #[serde(default)]
+    pub ruby: RubyMode,
+
+    /// Redaction detection (None = redactions are not inspected)
+    #[serde(default)]
+    pub redaction: Option<RedactionConfig>,
+
+    /// Conformance the document must claim, such as "PDF/A-2b" or "PDF/UA-1"
+    /// (None = conformance is reported but not required)
+    ///
+    /// Extraction fails with a validation error when the document does not claim it.
+    #[serde(default)]
+    pub required_conformance: Option<ConformanceLevel>,
+
+    /// Engine that parses the documents
+    #[serde(default)]
+    pub backend: PdfBackendKind,
+}
+
+#[cfg(feature = "pdf")]
+impl Default for PdfConfig {
+    fn default() -> Self {
+        Self {
+            extract_images: false,
+            passwords: None,
+            extract_metadata: true,
+            hierarchy: None,
+            ruby: RubyMode::default(),
+            redaction: None,
+            required_conformance: None,
+            backend: PdfBackendKind::default(),
+        }
+    }
+}
+
+/// Handling of ruby (furigana) annotations set in a smaller font next to CJK base text.
+///
+/// Without special handling, ruby characters are interleaved with the base text
+/// they annotate (e.g. "漢かん字じ").
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RubyMode {
+    /// Drop ruby annotations, keeping only the base text ("漢字")
+    #[default]
+    Strip,
+    /// Emit ruby in parentheses after the base text it annotates ("漢字(かんじ)")
+    Inline,
+    /// Keep ruby as separate text without associating it with the base text
+    Keep,
+}
+
+/// Engine that parses PDF documents.
+///
+/// Engines fail on different malformed documents, so a document one engine cannot
+/// open may still be extracted with the other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PdfBackendKind {
+    /// Pdfium: text with layout, tables, hierarchy, watermarks and redactions
+    #[default]
+    Pdfium,
+    /// lopdf, in pure Rust: text and metadata, without tables, hierarchy, watermarks
+    /// and redactions
+    Lopdf,
+}
+
+/// Standard a PDF declares conformance to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PdfStandard {
+    /// PDF/A (ISO 19005), for long-term archiving
+    PdfA,
+    /// PDF/UA (ISO 14289), for accessibility
+    PdfUa,
+}
+
+/// A PDF/A or PDF/UA conformance level, written as "PDF/A-2b", "PDF/A-4", "PDF/UA-1".
+///
+/// As a requirement, the part and the PDF/A level may be left out: "PDF/A-2" accepts
+/// any PDF/A-2 level and "PDF/A" any PDF/A document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ConformanceLevel {
+    /// Standard the level belongs to
+    pub standard: PdfStandard,
+    /// Part of the standard, such as 2 for PDF/A-2
+    pub part: Option<u8>,
+    /// PDF/A conformance level, lowercase ('a', 'b', 'u', 'e' or 'f')
+    pub level: Option<char>,
+}
+
+impl ConformanceLevel {
+    /// Whether a document claiming `claim` meets this level.
+    ///
+    /// PDF/A levels "a" and "u" include the requirements of "b", and "a" those of "u",
+    /// so a PDF/A-2a document meets PDF/A-2b. Different parts are different standards
+    /// and never meet each other.
+    pub fn is_met_by(&self, claim: &ConformanceLevel) -> bool {
+        let level_met = match (self.level, claim.level) {
+            (None, _) => true,
+            (Some(required), Some(claimed)) if required == claimed => true,
+            (Some('b'), Some('u' | 'a')) | (Some('u'), Some('a')) => true,
+            _ => false,
+        };
+        self.standard == claim.standard && self.part.is_none_or(|part| claim.part == Some(part)) && level_met
+    }
+}
+
+impl std::str::FromStr for ConformanceLevel {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid PDF conformance level '{value}', expected e.g. \"PDF/A-2b\" or \"PDF/UA-1\"");
+        let normalized = value.trim().to_ascii_lowercase();
+        let (standard, rest) = if let Some(rest) = normalized.strip_prefix("pdf/ua") {
+            (PdfStandard::PdfUa, rest)
+        } else if let Some(rest) = normalized.strip_prefix("pdf/a") {
+            (PdfStandard::PdfA, rest)
+        } else {
+            return Err(invalid());
+        };
+        if rest.is_empty() {
+            return Ok(Self {
+                standard,
+                part: None,
+                level: None,
+            });
+        }
+
+        let rest = rest.strip_prefix('-').ok_or_else(invalid)?;
+        let digits = rest.find(|c: char| !c.is_ascii_digit()).unwrap_or(rest.len());
+        let part = rest[..digits].parse::<u8>().map_err(|_| invalid())?;
+        let level = match &rest[digits..] {
+            "" => None,
+            level if standard == PdfStandard::PdfA && level.len() == 1 && "abuef".contains(level) => {
+                level.chars().next()
+            }
+            _ => return Err(invalid()),
+        };
+        Ok(Self {
+            standard,
+            part: Some(part),
+            level,
+        })
+    }
+}
+
+impl std::fmt::Display for ConformanceLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self.standard {
+            PdfStandard::PdfA => "PDF/A",
+            PdfStandard::PdfUa => "PDF/UA",
+        })?;
+        if let Some(part) = self.part {
+            write!(f, "-{part}")?;
+        }
+        if let Some(level) = self.level {
+            write!(f, "{level}")?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<String> for ConformanceLevel {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<ConformanceLevel> for String {
+    fn from(level: ConformanceLevel) -> Self {
+        level.to_string()
+    }
+}
+
+/// Redaction detection configuration.
+///
+/// Redactions are redaction annotations and filled black rectangles painted over
+/// text. A redaction that only draws a box leaves the original text in the content
+/// stream, where it is still extractable. Detected redactions are reported in
+/// `metadata.additional["redactions"]`, and covered text that is still extractable
+/// produces an entry in `metadata.additional["redaction_warnings"]`.
+///
+/// # Example
+///
+/// ```toml
+/// [pdf_options.redaction]
+/// suppress_covered_text = true
+/// marker = "[REDACTED]"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RedactionConfig {
+    /// Treat filled black rectangles drawn over text as redactions, in addition to
+    /// redaction annotations
+    #[serde(default = "default_true")]
+    pub detect_filled_rectangles: bool,
+
+    /// Remove text covered by a redaction from the extracted content
+    #[serde(default)]
+    pub suppress_covered_text: bool,
+
+    /// Replacement for each run of suppressed text (empty = remove without a marker)
+    ///
+    /// Default: "[REDACTED]"
+    #[serde(default = "default_redaction_marker")]
+    pub marker: String,
+}
+
+impl Default for RedactionConfig {
+    fn default() -> Self {
+        Self {
+            detect_filled_rectangles: true,
+            suppress_covered_text: false,
+            marker: default_redaction_marker(),
+        }
+    }
 }
 
 /// Hierarchy extraction configuration for PDF text structure analysis.
@@ -72,6 +291,10 @@ fn default_true() -> bool {
     true
 }
 
+fn default_redaction_marker() -> String {
+    "[REDACTED]".to_string()
+}
+
 fn default_k_clusters() -> usize {
     6
 }
@@ -84,6 +307,44 @@ fn default_ocr_coverage_threshold() -> Option<f32> {
 mod tests {
     use super::*;
 
+    #[test]
+    #[cfg(feature = "pdf")]
+    fn test_pdf_config_ruby_from_toml() {
+        let config: PdfConfig = toml::from_str("ruby = \"inline\"").unwrap();
+        assert_eq!(config.ruby, RubyMode::Inline);
+        assert!(config.extract_metadata);
+
+        let config: PdfConfig = toml::from_str("").unwrap();
+        assert_eq!(config.ruby, RubyMode::Strip);
+    }
+
+    #[test]
+    fn test_conformance_level_parsing_and_requirements() {
+        let level: ConformanceLevel = "pdf/a-2B".parse().unwrap();
+        assert_eq!(level.to_string(), "PDF/A-2b");
+        assert_eq!("PDF/UA-1".parse::<ConformanceLevel>().unwrap().part, Some(1));
+        for invalid in ["PDF/X-4", "PDF/A-", "PDF/A-2z", "PDF/UA-1a"] {
+            assert!(invalid.parse::<ConformanceLevel>().is_err(), "{invalid}");
+        }
+
+        let claim = |s: &str| s.parse::<ConformanceLevel>().unwrap();
+        assert!(claim("PDF/A-2b").is_met_by(&claim("PDF/A-2a")));
+        assert!(claim("PDF/A-2u").is_met_by(&claim("PDF/A-2a")));
+        assert!(!claim("PDF/A-2a").is_met_by(&claim("PDF/A-2b")));
+        assert!(!claim("PDF/A-2b").is_met_by(&claim("PDF/A-3b")));
+        assert!(claim("PDF/A-4").is_met_by(&claim("PDF/A-4f")));
+        assert!(claim("PDF/A").is_met_by(&claim("PDF/A-1b")));
+        assert!(!claim("PDF/A").is_met_by(&claim("PDF/UA-1")));
+    }
+
+    #[test]
+    fn test_redaction_config_from_toml() {
+        let config: RedactionConfig = toml::from_str("suppress_covered_text = true").unwrap();
+        assert!(config.suppress_covered_text);
+        assert!(config.detect_filled_rectangles);
+        assert_eq!(config.marker, "[REDACTED]");
+    }
+
     #[test]
     #[cfg(feature = "pdf")]
     fn test_hierarchy_config_default() {
@@ -0,0 +1,97 @@
+//! Timeouts and circuit breaking for plugins.
+//!
+//! A third-party plugin that hangs or fails on every call should not stall a whole
+//! service. With a [`PluginGuardConfig`], every call into an extractor, OCR backend,
+//! post-processor or validator is bounded by a timeout, and a plugin that keeps failing
+//! is disabled for a cooldown period.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Timeouts and circuit breaking for plugin calls.
+///
+/// # Example
+///
+/// ```toml
+/// [plugin_guard]
+/// timeout_ms = 30000
+/// failure_threshold = 3
+/// cooldown_ms = 120000
+///
+/// [plugin_guard.plugin_timeouts_ms]
+/// cloud-ocr = 90000
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginGuardConfig {
+    /// Longest time a single plugin call may take, in milliseconds (0 = no timeout)
+    ///
+    /// Default: 60000
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+
+    /// Timeouts of individual plugins by name, in milliseconds, overriding `timeout_ms`
+    #[serde(default)]
+    pub plugin_timeouts_ms: HashMap<String, u64>,
+
+    /// Consecutive failures after which a plugin is disabled (0 = never disable)
+    ///
+    /// Default: 5
+    #[serde(default = "default_failure_threshold")]
+    pub failure_threshold: u32,
+
+    /// How long a disabled plugin stays disabled, in milliseconds
+    ///
+    /// Default: 60000
+    #[serde(default = "default_cooldown_ms")]
+    pub cooldown_ms: u64,
+}
+
+impl PluginGuardConfig {
+    /// Timeout of a call into the plugin `name`, if any.
+    pub(crate) fn timeout_for(&self, name: &str) -> Option<Duration> {
+        let timeout_ms = self.plugin_timeouts_ms.get(name).copied().unwrap_or(self.timeout_ms);
+        (timeout_ms > 0).then(|| Duration::from_millis(timeout_ms))
+    }
+}
+
+impl Default for PluginGuardConfig {
+    fn default() -> Self {
+        Self {
+            timeout_ms: default_timeout_ms(),
+            plugin_timeouts_ms: HashMap::new(),
+            failure_threshold: default_failure_threshold(),
+            cooldown_ms: default_cooldown_ms(),
+        }
+    }
+}
+
+fn default_timeout_ms() -> u64 {
+    60_000
+}
+
+fn default_failure_threshold() -> u32 {
+    5
+}
+
+fn default_cooldown_ms() -> u64 {
+    60_000
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plugin_guard_config_from_toml() {
+        let config: PluginGuardConfig =
+            toml::from_str("timeout_ms = 0\n[plugin_timeouts_ms]\ncloud-ocr = 90000").unwrap();
+        assert_eq!(config.timeout_for("cloud-ocr"), Some(Duration::from_secs(90)));
+        assert_eq!(config.timeout_for("keyword-extraction"), None);
+        assert_eq!(config.failure_threshold, 5);
+
+        let config: PluginGuardConfig = toml::from_str("").unwrap();
+        assert_eq!(config, PluginGuardConfig::default());
+        assert_eq!(config.timeout_for("any"), Some(Duration::from_secs(60)));
+    }
+}
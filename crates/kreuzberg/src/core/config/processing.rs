@@ -108,6 +108,31 @@ pub struct ChunkingConfig {
     /// Use a preset configuration (overrides individual settings if provided)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub preset: Option<String>,
+
+    /// How consecutive chunks overlap
+    ///
+    /// Default: Characters (`overlap` characters, regardless of sentences)
+    #[serde(default)]
+    pub overlap_strategy: OverlapStrategy,
+
+    /// Prefix every chunk with the Markdown headings of the section it starts in
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub heading_context: bool,
+
+    /// Keep Markdown tables out of text chunks and emit them as table chunks
+    ///
+    /// Default: None (tables are split like any other text)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tables: Option<TableChunkingConfig>,
+
+    /// Name of the registered sentence segmenter for sentence overlap
+    ///
+    /// Default: None (the segmenter with the highest priority, `rule-based` unless
+    /// another is registered)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sentence_segmenter: Option<String>,
 }
 
 impl Default for ChunkingConfig {
@@ -119,10 +144,77 @@ impl Default for ChunkingConfig {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
+        }
+    }
+}
+
+/// Table-aware chunking configuration.
+///
+/// Markdown pipe tables in the content become chunks of their own, marked with
+/// `ChunkType::Table`, instead of being cut mid-row by the text splitter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableChunkingConfig {
+    /// Serialization of table chunks
+    ///
+    /// Default: Markdown
+    #[serde(default)]
+    pub format: TableChunkFormat,
+
+    /// Split tables longer than `max_characters` into groups of rows, each under the
+    /// repeated header row (false keeps every table in one chunk)
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub split_rows: bool,
+}
+
+impl Default for TableChunkingConfig {
+    fn default() -> Self {
+        Self {
+            format: TableChunkFormat::Markdown,
+            split_rows: true,
         }
     }
 }
 
+/// Serialization of table chunks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum TableChunkFormat {
+    /// The Markdown table as it appears in the content
+    #[default]
+    Markdown,
+    /// Comma-separated values, header row first
+    Csv,
+}
+
+/// How consecutive chunks overlap.
+///
+/// # Variants
+///
+/// * `Characters` - The last `overlap` characters of a chunk start the next one
+/// * `Sentences` - The last `count` whole sentences of a chunk start the next one, as
+///   long as they fit in `overlap` characters
+/// * `SlidingWindow` - Chunks of `max_characters` start every `stride` characters,
+///   snapped to word boundaries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OverlapStrategy {
+    #[default]
+    Characters,
+    Sentences {
+        #[serde(default = "default_overlap_sentences")]
+        count: usize,
+    },
+    SlidingWindow {
+        stride: usize,
+    },
+}
+
 /// Embedding configuration for text chunks.
 ///
 /// Configures embedding generation using ONNX models via fastembed-rs.
@@ -194,6 +286,10 @@ fn default_chunk_overlap() -> usize {
     200
 }
 
+fn default_overlap_sentences() -> usize {
+    1
+}
+
 fn default_trim() -> bool {
     true
 }
@@ -255,6 +351,10 @@ mod tests {
             chunker_type: ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         };
         assert_eq!(config.max_characters, 1000);
         assert_eq!(config.overlap, 200);
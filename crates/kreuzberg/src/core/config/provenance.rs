@@ -0,0 +1,40 @@
+//! Provenance manifest configuration.
+//!
+//! With a [`ProvenanceConfig`], resumable batch jobs write a provenance manifest next to
+//! every result, recording what the result was extracted from and with which version,
+//! plugins and configuration, and optionally an Ed25519 signature over it.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Provenance manifests of written results.
+///
+/// # Example
+///
+/// ```toml
+/// [provenance]
+/// signing_key = "/etc/kreuzberg/signing.key"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvenanceConfig {
+    /// File with the Ed25519 private key manifests are signed with, as 32 raw bytes or 64
+    /// hex characters (None = manifests are not signed)
+    ///
+    /// Signing requires the `signing` feature.
+    #[serde(default)]
+    pub signing_key: Option<PathBuf>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provenance_config_from_toml() {
+        let config: ProvenanceConfig = toml::from_str("signing_key = \"keys/result.key\"").unwrap();
+        assert_eq!(config.signing_key, Some(PathBuf::from("keys/result.key")));
+
+        let config: ProvenanceConfig = toml::from_str("").unwrap();
+        assert_eq!(config, ProvenanceConfig::default());
+    }
+}
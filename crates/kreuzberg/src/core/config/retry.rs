@@ -0,0 +1,118 @@
+//! Retry and dead-letter configuration for batch extraction.
+//!
+//! Transient failures (a flaky network share, an overloaded cloud OCR backend) should
+//! not permanently lose a document in a long batch run. With a [`RetryConfig`], the
+//! batch functions retry such failures with exponential backoff, and record the inputs
+//! that still fail in a dead-letter file for later reprocessing.
+
+use crate::error::ErrorClass;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// Retry policy and dead-letter output of batch extraction.
+///
+/// # Example
+///
+/// ```toml
+/// [retry]
+/// max_attempts = 5
+/// retry_on = ["io", "ocr", "plugin"]
+/// dead_letter = "failed.jsonl"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryConfig {
+    /// Attempts per input, including the first (1 = no retries)
+    ///
+    /// Default: 3
+    #[serde(default = "default_max_attempts")]
+    pub max_attempts: u32,
+
+    /// Delay before the first retry, in milliseconds; doubles with every further retry
+    ///
+    /// Default: 1000
+    #[serde(default = "default_initial_backoff_ms")]
+    pub initial_backoff_ms: u64,
+
+    /// Longest delay between two attempts, in milliseconds
+    ///
+    /// Default: 30000
+    #[serde(default = "default_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+
+    /// Error classes worth retrying; other errors fail the input at once
+    ///
+    /// Default: `["io", "ocr", "plugin"]`
+    #[serde(default = "default_retry_on")]
+    pub retry_on: Vec<ErrorClass>,
+
+    /// JSON Lines file to append inputs that failed for good to (None = no dead-letter output)
+    #[serde(default)]
+    pub dead_letter: Option<PathBuf>,
+}
+
+impl RetryConfig {
+    /// Delay before attempt `attempt + 1`, after `attempt` failed attempts.
+    pub(crate) fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u64 << attempt.saturating_sub(1).min(32);
+        Duration::from_millis(self.initial_backoff_ms.saturating_mul(factor).min(self.max_backoff_ms))
+    }
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_max_attempts(),
+            initial_backoff_ms: default_initial_backoff_ms(),
+            max_backoff_ms: default_max_backoff_ms(),
+            retry_on: default_retry_on(),
+            dead_letter: None,
+        }
+    }
+}
+
+fn default_max_attempts() -> u32 {
+    3
+}
+
+fn default_initial_backoff_ms() -> u64 {
+    1000
+}
+
+fn default_max_backoff_ms() -> u64 {
+    30_000
+}
+
+fn default_retry_on() -> Vec<ErrorClass> {
+    vec![ErrorClass::Io, ErrorClass::Ocr, ErrorClass::Plugin]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_retry_config_from_toml() {
+        let config: RetryConfig = toml::from_str("max_attempts = 5\nretry_on = [\"ocr\", \"parsing\"]").unwrap();
+        assert_eq!(config.max_attempts, 5);
+        assert_eq!(config.retry_on, vec![ErrorClass::Ocr, ErrorClass::Parsing]);
+        assert_eq!(config.initial_backoff_ms, 1000);
+        assert_eq!(config.dead_letter, None);
+
+        let config: RetryConfig = toml::from_str("").unwrap();
+        assert_eq!(config, RetryConfig::default());
+    }
+
+    #[test]
+    fn test_backoff() {
+        let config = RetryConfig {
+            max_backoff_ms: 5000,
+            ..Default::default()
+        };
+        assert_eq!(config.backoff(1), Duration::from_secs(1));
+        assert_eq!(config.backoff(2), Duration::from_secs(2));
+        assert_eq!(config.backoff(3), Duration::from_secs(4));
+        assert_eq!(config.backoff(4), Duration::from_secs(5));
+        assert_eq!(config.backoff(60), Duration::from_secs(5));
+    }
+}
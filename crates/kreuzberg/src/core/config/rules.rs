@@ -0,0 +1,90 @@
+//! Extraction rules configuration.
+//!
+//! Controls the regular expressions that pull named fields, such as purchase order
+//! numbers or invoice totals, out of the extracted content.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// A rule that extracts a named field from the content.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractRule {
+    /// Name of the field in `metadata.additional["extracted_fields"]`
+    pub name: String,
+
+    /// Regular expression matched against the content
+    pub regex: String,
+
+    /// Regex flags: `i` (ignore case), `m` (`^` and `$` match at lines), `s` (`.`
+    /// matches newlines), `x` (ignore whitespace and `#` comments), `U` (lazy quantifiers)
+    #[serde(default)]
+    pub flags: String,
+
+    /// Output keys mapped to capture groups, by name or number
+    ///
+    /// Empty: the field is the first capture group, or the whole match if the regex
+    /// has no groups. Otherwise the field is an object with one entry per key.
+    #[serde(default)]
+    pub captures: BTreeMap<String, String>,
+
+    /// Collect every match into a list instead of taking the first
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub multiple: bool,
+}
+
+/// Extraction rules.
+///
+/// # Example
+///
+/// ```toml
+/// [[rules.extract]]
+/// name = "po_number"
+/// regex = 'P\.?O\.?\s*(?:No\.?|Number)?[:#]?\s*([A-Z0-9-]+)'
+/// flags = "i"
+///
+/// [[rules.extract]]
+/// name = "line_items"
+/// regex = '^(?P<sku>[A-Z]{3}-\d+)\s+(?P<qty>\d+)$'
+/// flags = "m"
+/// captures = { sku = "sku", quantity = "qty" }
+/// multiple = true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RulesConfig {
+    /// Field extraction rules, applied in order
+    #[serde(default)]
+    pub extract: Vec<ExtractRule>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rules_config_from_toml() {
+        let config: RulesConfig = toml::from_str(
+            r#"
+            [[extract]]
+            name = "po_number"
+            regex = 'PO-(\d+)'
+
+            [[extract]]
+            name = "total"
+            regex = '(?P<currency>[A-Z]{3}) (?P<amount>[\d.]+)'
+            flags = "i"
+            captures = { currency = "currency", amount = "2" }
+            multiple = true
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.extract.len(), 2);
+        assert_eq!(config.extract[0].name, "po_number");
+        assert!(config.extract[0].flags.is_empty());
+        assert!(config.extract[0].captures.is_empty());
+        assert!(!config.extract[0].multiple);
+        assert_eq!(config.extract[1].captures["amount"], "2");
+        assert!(config.extract[1].multiple);
+    }
+}
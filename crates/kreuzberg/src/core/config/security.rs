@@ -0,0 +1,66 @@
+//! Input security policy configuration.
+//!
+//! Documents can carry content that runs code when they are opened: VBA macros in
+//! Office documents, JavaScript and launch actions in PDFs, executables in archives.
+//! With this configuration set, inputs are inspected for such content before
+//! extraction, findings are reported in the result metadata, and inputs can be
+//! rejected instead of extracted.
+
+use crate::types::SecurityFindingKind;
+use serde::{Deserialize, Serialize};
+
+/// Input security policy.
+///
+/// Setting the section enables inspection; inputs are only rejected for the kinds of
+/// content blocked here.
+///
+/// # Example
+///
+/// ```toml
+/// [security]
+/// block_macros = true
+/// block_active_content = true
+/// extract_scripts = true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SecurityConfig {
+    /// Reject Office documents containing macros
+    #[serde(default)]
+    pub block_macros: bool,
+
+    /// Reject PDFs with JavaScript or launch actions and archives with executable members
+    #[serde(default)]
+    pub block_active_content: bool,
+
+    /// Extract the source code of macros and PDF JavaScript into `ExtractionResult::scripts`
+    #[serde(default)]
+    pub extract_scripts: bool,
+}
+
+impl SecurityConfig {
+    /// Whether inputs with a finding of this kind are rejected.
+    pub fn blocks(&self, kind: SecurityFindingKind) -> bool {
+        match kind {
+            SecurityFindingKind::Macros => self.block_macros,
+            SecurityFindingKind::JavaScript | SecurityFindingKind::LaunchAction | SecurityFindingKind::Executable => {
+                self.block_active_content
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_security_config_from_toml() {
+        let config: SecurityConfig = toml::from_str("block_macros = true").unwrap();
+        assert!(config.blocks(SecurityFindingKind::Macros));
+        assert!(!config.blocks(SecurityFindingKind::JavaScript));
+
+        let config: SecurityConfig = toml::from_str("").unwrap();
+        assert_eq!(config, SecurityConfig::default());
+        assert!(!config.blocks(SecurityFindingKind::Executable));
+    }
+}
@@ -0,0 +1,55 @@
+//! Signature and stamp detection configuration.
+
+use serde::{Deserialize, Serialize};
+
+/// Signature and stamp detection configuration.
+///
+/// When `None` on `ExtractionConfig`, no detection runs. Requires the `ocr` feature;
+/// detected marks are listed in `metadata.additional["signatures"]`.
+///
+/// # Example
+///
+/// ```toml
+/// [signatures]
+/// signatures = true
+/// stamps = false
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureConfig {
+    /// Report handwritten signatures
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub signatures: bool,
+
+    /// Report ink stamps and seals
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub stamps: bool,
+}
+
+impl Default for SignatureConfig {
+    fn default() -> Self {
+        Self {
+            signatures: true,
+            stamps: true,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_config_from_toml() {
+        let config: SignatureConfig = toml::from_str("stamps = false").unwrap();
+        assert!(config.signatures);
+        assert!(!config.stamps);
+    }
+}
@@ -0,0 +1,71 @@
+//! Spellcheck-based quality estimation configuration.
+//!
+//! Controls the dictionaries OCR output is checked against and the error rate above
+//! which a page is reported as low quality.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Spellcheck-based quality estimation.
+///
+/// Words of the content and of every page are looked up in the dictionaries; the share
+/// of unknown words estimates the OCR error rate. Pages above `max_error_rate` are
+/// listed so a pipeline can OCR them again, for example at a higher DPI or with
+/// another backend.
+///
+/// # Example
+///
+/// ```toml
+/// [spellcheck]
+/// dictionaries = ["/usr/share/hunspell/en_US.dic", "/usr/share/hunspell/de_DE.dic"]
+/// words = ["Kreuzberg", "OCR"]
+/// max_error_rate = 0.15
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpellcheckConfig {
+    /// Word lists with one word per line, for the languages of the documents
+    ///
+    /// Hunspell `.dic` files are accepted; their affix flags are ignored, so only the
+    /// listed forms of a word are known.
+    pub dictionaries: Vec<PathBuf>,
+
+    /// Additional known words, such as names and domain terms
+    #[serde(default)]
+    pub words: Vec<String>,
+
+    /// Words with fewer letters are not checked
+    ///
+    /// Default: 3
+    #[serde(default = "default_min_word_length")]
+    pub min_word_length: usize,
+
+    /// Error rate above which a page is reported in `low_quality_pages`
+    ///
+    /// Default: 0.2
+    #[serde(default = "default_max_error_rate")]
+    pub max_error_rate: f64,
+}
+
+fn default_min_word_length() -> usize {
+    3
+}
+
+fn default_max_error_rate() -> f64 {
+    0.2
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spellcheck_config_from_toml() {
+        let config: SpellcheckConfig = toml::from_str(r#"dictionaries = ["en_US.dic"]"#).unwrap();
+        assert_eq!(config.dictionaries, vec![PathBuf::from("en_US.dic")]);
+        assert!(config.words.is_empty());
+        assert_eq!(config.min_word_length, 3);
+        assert_eq!(config.max_error_rate, 0.2);
+
+        assert!(toml::from_str::<SpellcheckConfig>("max_error_rate = 0.1").is_err());
+    }
+}
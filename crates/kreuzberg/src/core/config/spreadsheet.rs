@@ -0,0 +1,132 @@
+//! Spreadsheet extraction configuration.
+//!
+//! Selects which sheets of a workbook are extracted and how many rows are read,
+//! so large workbooks can be extracted partially, and whether formulas, cell
+//! comments and defined names are included.
+
+use serde::{Deserialize, Serialize};
+
+/// Spreadsheet extraction configuration.
+///
+/// Applies to Excel (.xlsx, .xlsm, .xls, .xlsb) and OpenDocument (.ods) workbooks.
+/// Sheets that are not selected are listed in the per-sheet metadata but not read.
+///
+/// # Example
+///
+/// ```toml
+/// [spreadsheet]
+/// sheets = ["Summary", "Q*"]
+/// max_rows = 10000
+/// skip_hidden = true
+/// formulas = true
+/// comments = true
+/// defined_names = true
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpreadsheetConfig {
+    /// Sheet names or patterns to extract, matched case-insensitively; `*` matches
+    /// any run of characters and `?` a single character (empty = all sheets)
+    #[serde(default)]
+    pub sheets: Vec<String>,
+
+    /// Maximum rows read per sheet, including the header row (None = all rows)
+    #[serde(default)]
+    pub max_rows: Option<usize>,
+
+    /// Skip hidden and very hidden sheets
+    #[serde(default)]
+    pub skip_hidden: bool,
+
+    /// Annotate formula cells with their formula, as in `15.0 (=SUM(A1:A5))`
+    #[serde(default)]
+    pub formulas: bool,
+
+    /// Include cell comments and notes in the content and metadata (Office Open XML workbooks only)
+    #[serde(default)]
+    pub comments: bool,
+
+    /// Include the workbook's defined names (named ranges and constants) in the metadata
+    #[serde(default)]
+    pub defined_names: bool,
+}
+
+impl SpreadsheetConfig {
+    /// Whether a sheet is extracted. Hidden sheets are only filtered when `skip_hidden` is set.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kreuzberg::core::config::SpreadsheetConfig;
+    ///
+    /// let config = SpreadsheetConfig {
+    ///     sheets: vec!["Summary".to_string(), "Q*".to_string()],
+    ///     skip_hidden: true,
+    ///     ..Default::default()
+    /// };
+    /// assert!(config.selects_sheet("summary", false));
+    /// assert!(config.selects_sheet("Q3 2024", false));
+    /// assert!(!config.selects_sheet("Q4 2024", true));
+    /// assert!(!config.selects_sheet("Raw data", false));
+    /// ```
+    pub fn selects_sheet(&self, name: &str, hidden: bool) -> bool {
+        if hidden && self.skip_hidden {
+            return false;
+        }
+        self.sheets.is_empty() || self.sheets.iter().any(|pattern| wildcard_match(pattern, name))
+    }
+}
+
+/// Case-insensitive match of `*` / `?` wildcard patterns.
+fn wildcard_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+    let name: Vec<char> = name.chars().flat_map(char::to_lowercase).collect();
+
+    let (mut p, mut n) = (0, 0);
+    // Position after the last `*` and the name position it was tried at, for backtracking.
+    let mut star: Option<(usize, usize)> = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p + 1, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match star {
+                Some((after_star, tried)) => {
+                    p = after_star;
+                    n = tried + 1;
+                    star = Some((after_star, tried + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wildcard_match() {
+        assert!(wildcard_match("Q*", "Q1"));
+        assert!(wildcard_match("*report*", "Monthly Report 2024"));
+        assert!(wildcard_match("Sheet?", "sheet2"));
+        assert!(!wildcard_match("Sheet?", "Sheet10"));
+        assert!(wildcard_match("*", ""));
+        assert!(!wildcard_match("Summary", "Summary 2"));
+    }
+
+    #[test]
+    fn test_spreadsheet_config_from_toml() {
+        let config: SpreadsheetConfig = toml::from_str("sheets = [\"Summary\"]\nmax_rows = 100").unwrap();
+        assert_eq!(config.sheets, vec!["Summary"]);
+        assert_eq!(config.max_rows, Some(100));
+        assert!(!config.skip_hidden);
+        assert!(!config.formulas && !config.comments && !config.defined_names);
+    }
+}
@@ -0,0 +1,165 @@
+//! Worker thread pool configuration.
+//!
+//! Controls the threads that OCR backends run on: how many there are, which CPU cores
+//! they may use and their scheduling priority. On machines with many cores and several
+//! NUMA nodes, confining the workers to the cores of one node avoids oversubscription
+//! and cross-socket memory traffic.
+
+use crate::{KreuzbergError, Result};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// Worker thread pool.
+///
+/// Core affinity and the nice level are applied on Linux and ignored elsewhere.
+///
+/// # Example
+///
+/// ```toml
+/// [ocr.thread_pool]
+/// threads = 16
+/// cpu_affinity = "0-15"
+/// pin_threads = true
+/// nice = 10
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ThreadPoolConfig {
+    /// Number of worker threads (None = the number of cores in `cpu_affinity`, or of
+    /// all CPU cores)
+    #[serde(default)]
+    pub threads: Option<usize>,
+
+    /// CPU cores the workers may run on, as a list of core numbers or a CPU list
+    /// string such as `"0-15,32-47"` (empty = all cores)
+    #[serde(default, deserialize_with = "deserialize_cpu_list")]
+    pub cpu_affinity: Vec<usize>,
+
+    /// Pin every worker to a single core of `cpu_affinity`, assigned in turn, instead
+    /// of letting the workers move between them
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub pin_threads: bool,
+
+    /// Nice level of the workers, from -20 (highest priority) to 19 (lowest); negative
+    /// levels need the `CAP_SYS_NICE` capability
+    #[serde(default)]
+    pub nice: Option<i32>,
+}
+
+impl ThreadPoolConfig {
+    /// Number of worker threads, resolving `None` to the number of cores in
+    /// `cpu_affinity`, or of all CPU cores.
+    pub fn effective_threads(&self) -> usize {
+        self.threads
+            .or_else(|| (!self.cpu_affinity.is_empty()).then_some(self.cpu_affinity.len()))
+            .unwrap_or_else(|| std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1))
+            .max(1)
+    }
+
+    /// Check the thread count and the nice level.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if `threads` is zero, `nice` is outside
+    /// -20..=19 or `pin_threads` is set without `cpu_affinity`.
+    pub fn validate(&self) -> Result<()> {
+        if self.threads == Some(0) {
+            return Err(KreuzbergError::validation("thread_pool.threads must be at least 1"));
+        }
+        if let Some(nice) = self.nice
+            && !(-20..=19).contains(&nice)
+        {
+            return Err(KreuzbergError::validation(format!(
+                "thread_pool.nice must be between -20 and 19, got {}",
+                nice
+            )));
+        }
+        if self.pin_threads && self.cpu_affinity.is_empty() {
+            return Err(KreuzbergError::validation(
+                "thread_pool.pin_threads requires thread_pool.cpu_affinity",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Parse a CPU list such as `"0-3,8,10-11"`.
+fn parse_cpu_list(list: &str) -> std::result::Result<Vec<usize>, String> {
+    let mut cores = Vec::new();
+    for range in list.split(',').map(str::trim).filter(|range| !range.is_empty()) {
+        let core = |number: &str| {
+            number
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| format!("invalid CPU core '{}' in '{}'", number.trim(), list))
+        };
+        match range.split_once('-') {
+            Some((first, last)) => {
+                let (first, last) = (core(first)?, core(last)?);
+                if first > last {
+                    return Err(format!("invalid CPU range '{}' in '{}'", range, list));
+                }
+                cores.extend(first..=last);
+            }
+            None => cores.push(core(range)?),
+        }
+    }
+    cores.sort_unstable();
+    cores.dedup();
+    Ok(cores)
+}
+
+fn deserialize_cpu_list<'de, D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Vec<usize>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum CpuList {
+        Cores(Vec<usize>),
+        List(String),
+    }
+
+    match CpuList::deserialize(deserializer)? {
+        CpuList::Cores(cores) => Ok(cores),
+        CpuList::List(list) => parse_cpu_list(&list).map_err(serde::de::Error::custom),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_thread_pool_config_from_toml() {
+        let config: ThreadPoolConfig = toml::from_str("cpu_affinity = \"0-3, 8,10-11\"\nnice = 5").unwrap();
+        assert_eq!(config.cpu_affinity, vec![0, 1, 2, 3, 8, 10, 11]);
+        assert_eq!(config.effective_threads(), 7);
+        assert_eq!(config.nice, Some(5));
+        assert!(!config.pin_threads);
+        assert!(config.validate().is_ok());
+
+        let config: ThreadPoolConfig =
+            toml::from_str("threads = 2\ncpu_affinity = [4, 5]\npin_threads = true").unwrap();
+        assert_eq!(config.cpu_affinity, vec![4, 5]);
+        assert_eq!(config.effective_threads(), 2);
+
+        assert!(toml::from_str::<ThreadPoolConfig>("cpu_affinity = \"3-1\"").is_err());
+        assert!(toml::from_str::<ThreadPoolConfig>("cpu_affinity = \"a\"").is_err());
+        assert!(
+            toml::from_str::<ThreadPoolConfig>("nice = 25")
+                .unwrap()
+                .validate()
+                .is_err()
+        );
+        assert!(
+            toml::from_str::<ThreadPoolConfig>("threads = 0")
+                .unwrap()
+                .validate()
+                .is_err()
+        );
+        assert!(
+            toml::from_str::<ThreadPoolConfig>("pin_threads = true")
+                .unwrap()
+                .validate()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Token counting and cost estimation configuration.
+//!
+//! Controls how tokens are counted for documents and chunks and which prices are
+//! used to estimate embedding and LLM ingestion costs.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::sync::Arc;
+
+/// Built-in tokenizer used when no [`TokenCounterCallback`] is set.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenizerKind {
+    /// Approximation of BPE tokenizers: about four characters per token for
+    /// alphabetic scripts and one token per CJK character
+    #[default]
+    Heuristic,
+    /// One token per whitespace-separated word
+    Whitespace,
+    /// One token per character
+    Characters,
+}
+
+/// Prices used to estimate ingestion costs.
+///
+/// Prices are per million tokens. Unset prices are not estimated.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenPricing {
+    /// Embedding price per million tokens, applied to chunk tokens
+    /// (or document tokens when chunking is disabled)
+    #[serde(default)]
+    pub embedding_per_million: Option<f64>,
+
+    /// LLM input price per million tokens, applied to document tokens
+    #[serde(default)]
+    pub llm_input_per_million: Option<f64>,
+
+    /// Currency of the prices
+    ///
+    /// Default: "USD"
+    #[serde(default = "default_currency")]
+    pub currency: String,
+}
+
+impl Default for TokenPricing {
+    fn default() -> Self {
+        Self {
+            embedding_per_million: None,
+            llm_input_per_million: None,
+            currency: default_currency(),
+        }
+    }
+}
+
+fn default_currency() -> String {
+    "USD".to_string()
+}
+
+/// Token counting configuration.
+///
+/// When set on `ExtractionConfig`, results carry a `token_usage` entry in
+/// `metadata.additional` and chunks without a token count get one.
+///
+/// # Example
+///
+/// ```toml
+/// [token_count]
+/// tokenizer = "heuristic"
+///
+/// [token_count.pricing]
+/// embedding_per_million = 0.02
+/// llm_input_per_million = 3.0
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TokenCountConfig {
+    /// Built-in tokenizer (default: heuristic)
+    #[serde(default)]
+    pub tokenizer: TokenizerKind,
+
+    /// Custom token counter overriding `tokenizer` (programmatic only, never serialized)
+    #[serde(skip)]
+    pub counter: Option<TokenCounterCallback>,
+
+    /// Prices for cost estimation (None = no cost estimate)
+    #[serde(default)]
+    pub pricing: Option<TokenPricing>,
+}
+
+impl TokenCountConfig {
+    /// Count the tokens of `text` with the custom counter or the built-in tokenizer.
+    pub fn count_tokens(&self, text: &str) -> usize {
+        match &self.counter {
+            Some(counter) => counter.count(text),
+            None => crate::text::token_count::count_tokens(text, self.tokenizer),
+        }
+    }
+
+    /// Name of the tokenizer in use, as reported in `token_usage`.
+    pub fn tokenizer_name(&self) -> &str {
+        match &self.counter {
+            Some(counter) => counter.name(),
+            None => match self.tokenizer {
+                TokenizerKind::Heuristic => "heuristic",
+                TokenizerKind::Whitespace => "whitespace",
+                TokenizerKind::Characters => "characters",
+            },
+        }
+    }
+}
+
+type TokenCounterFn = dyn Fn(&str) -> usize + Send + Sync;
+
+/// Custom token counter, typically wrapping the tokenizer of the target model.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::core::config::{TokenCountConfig, TokenCounterCallback};
+///
+/// let config = TokenCountConfig {
+///     counter: Some(TokenCounterCallback::new("bytes", |text| text.len())),
+///     ..Default::default()
+/// };
+/// assert_eq!(config.count_tokens("hello"), 5);
+/// assert_eq!(config.tokenizer_name(), "bytes");
+/// ```
+#[derive(Clone)]
+pub struct TokenCounterCallback {
+    name: String,
+    counter: Arc<TokenCounterFn>,
+}
+
+impl TokenCounterCallback {
+    /// Wrap a closure as a named token counter.
+    pub fn new<F>(name: impl Into<String>, counter: F) -> Self
+    where
+        F: Fn(&str) -> usize + Send + Sync + 'static,
+    {
+        Self {
+            name: name.into(),
+            counter: Arc::new(counter),
+        }
+    }
+
+    /// Name reported as the tokenizer in `token_usage`.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Count the tokens of `text`.
+    pub fn count(&self, text: &str) -> usize {
+        (self.counter)(text)
+    }
+}
+
+impl fmt::Debug for TokenCounterCallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TokenCounterCallback({:?})", self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_count_config_from_toml() {
+        let config: TokenCountConfig =
+            toml::from_str("tokenizer = \"whitespace\"\n[pricing]\nembedding_per_million = 0.02").unwrap();
+        assert_eq!(config.tokenizer, TokenizerKind::Whitespace);
+        assert_eq!(config.tokenizer_name(), "whitespace");
+        let pricing = config.pricing.unwrap();
+        assert_eq!(pricing.embedding_per_million, Some(0.02));
+        assert_eq!(pricing.llm_input_per_million, None);
+        assert_eq!(pricing.currency, "USD");
+    }
+}
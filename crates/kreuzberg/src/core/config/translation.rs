@@ -0,0 +1,84 @@
+//! Machine translation configuration.
+//!
+//! Controls the target language of the translation stage, whether the whole
+//! content or each chunk is translated, and which registered `Translator` is used.
+
+use serde::{Deserialize, Serialize};
+
+/// What the translation stage translates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TranslationScope {
+    /// The whole content in one piece
+    #[default]
+    Document,
+    /// Each chunk separately, keeping chunk boundaries aligned with the originals
+    Chunks,
+    /// Both the whole content and each chunk
+    Both,
+}
+
+impl TranslationScope {
+    /// Whether the whole content is translated.
+    pub fn document(self) -> bool {
+        matches!(self, TranslationScope::Document | TranslationScope::Both)
+    }
+
+    /// Whether chunks are translated.
+    pub fn chunks(self) -> bool {
+        matches!(self, TranslationScope::Chunks | TranslationScope::Both)
+    }
+}
+
+/// Machine translation configuration.
+///
+/// Translations are produced by a registered `Translator` plugin and stored next to
+/// the originals in `metadata.additional["translation"]`.
+///
+/// # Example
+///
+/// ```toml
+/// [translation]
+/// target_language = "eng"
+/// scope = "chunks"
+/// translator = "deepl"
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TranslationConfig {
+    /// Language to translate into, passed to the translator as is (e.g. `"eng"` or `"en"`)
+    pub target_language: String,
+
+    /// What is translated
+    ///
+    /// Default: document
+    #[serde(default)]
+    pub scope: TranslationScope,
+
+    /// Name of the registered translator to use (None = the one with the highest priority)
+    #[serde(default)]
+    pub translator: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translation_config_from_toml() {
+        let config: TranslationConfig = toml::from_str(
+            r#"
+            target_language = "deu"
+            scope = "both"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.target_language, "deu");
+        assert!(config.scope.document() && config.scope.chunks());
+        assert_eq!(config.translator, None);
+
+        let config: TranslationConfig = toml::from_str(r#"target_language = "eng""#).unwrap();
+        assert_eq!(config.scope, TranslationScope::Document);
+        assert!(!config.scope.chunks());
+        assert!(toml::from_str::<TranslationConfig>(r#"scope = "chunks""#).is_err());
+    }
+}
@@ -0,0 +1,76 @@
+//! Transliteration configuration.
+//!
+//! Controls which scripts are transliterated to Latin for the parallel
+//! `content_transliterated` text and which romanization scheme is used.
+
+use crate::text::script::Script;
+use serde::{Deserialize, Serialize};
+
+/// Romanization scheme of the transliterated text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransliterationScheme {
+    /// Plain ASCII digraphs as used by search engines and passports, e.g. `Щукино` →
+    /// `Shchukino`, `ラーメン` → `ramen`
+    #[default]
+    Simplified,
+    /// Reversible scholarly schemes with diacritics: ISO 9 for Cyrillic, ISO 843 for
+    /// Greek and Hepburn with macrons for kana, e.g. `Щукино` → `Ŝukino`, `ラーメン` → `rāmen`
+    Iso,
+}
+
+/// Transliteration configuration.
+///
+/// Cyrillic, Greek, Hangul, Hiragana and Katakana are supported. Hangul is romanized
+/// with the Revised Romanization in both schemes. Han characters are kept, as reading
+/// them needs a dictionary.
+///
+/// # Example
+///
+/// ```toml
+/// [transliteration]
+/// scheme = "iso"
+/// scripts = ["cyrillic", "greek"]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TransliterationConfig {
+    /// Romanization scheme
+    ///
+    /// Default: simplified
+    #[serde(default)]
+    pub scheme: TransliterationScheme,
+
+    /// Scripts to transliterate (empty = all supported scripts)
+    #[serde(default)]
+    pub scripts: Vec<Script>,
+}
+
+impl TransliterationConfig {
+    /// Whether letters of `script` are transliterated.
+    pub fn includes(&self, script: Script) -> bool {
+        self.scripts.is_empty() || self.scripts.contains(&script)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transliteration_config_from_toml() {
+        let config: TransliterationConfig = toml::from_str(
+            r#"
+            scheme = "iso"
+            scripts = ["cyrillic", "katakana"]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.scheme, TransliterationScheme::Iso);
+        assert!(config.includes(Script::Katakana));
+        assert!(!config.includes(Script::Greek));
+
+        let config: TransliterationConfig = toml::from_str("").unwrap();
+        assert_eq!(config.scheme, TransliterationScheme::Simplified);
+        assert!(config.includes(Script::Greek));
+    }
+}
@@ -0,0 +1,124 @@
+//! Value normalization configuration.
+//!
+//! Controls which dates, times, currency amounts, quantities and numbers are normalized
+//! to ISO 8601 and plain decimal notation, and how the normalized values are emitted.
+
+use serde::{Deserialize, Serialize};
+
+/// Kind of a normalized value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueKind {
+    /// Calendar date, normalized to `YYYY-MM-DD`
+    Date,
+    /// Time of day, normalized to `HH:MM` or `HH:MM:SS`
+    Time,
+    /// Amount of money, normalized to a decimal with an ISO 4217 currency code
+    Currency,
+    /// Physical quantity such as `30 psi`, converted to the unit of its dimension in
+    /// the configured unit system
+    Quantity,
+    /// Number, normalized to a decimal with `.` as the decimal separator
+    Number,
+}
+
+/// Where the normalized values are emitted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueAnnotationMode {
+    /// Store the values with their byte spans in `metadata.additional["normalized_values"]`
+    #[default]
+    Metadata,
+    /// Append the normalized value in brackets after the original text, e.g.
+    /// `5. März 2024 [2024-03-05]`, in the content and the page contents
+    Inline,
+}
+
+/// Unit system that quantities are converted to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum UnitSystem {
+    /// SI units: m, kg, Pa, K, m³, m², m/s, J, W and N
+    #[default]
+    Si,
+    /// US customary units: ft, lb, psi, °F, gal, ft², mph, BTU, hp and lbf
+    Imperial,
+}
+
+/// Date and number normalization.
+///
+/// Numeric dates and numbers are read in the conventions of the document's locale:
+/// `03/05/2024` is the 5th of March in English and the 3rd of May in French, and
+/// `1.234` is one thousand two hundred thirty-four in German. The locale is `locale`
+/// if set, otherwise the first detected language, otherwise English. Quantities are
+/// converted to the unit of their dimension in `unit_system`, so `30 psi` and `2 bar`
+/// can be compared in pascals.
+///
+/// # Example
+///
+/// ```toml
+/// [value_normalization]
+/// mode = "inline"
+/// kinds = ["date", "currency", "quantity"]
+/// locale = "de-DE"
+/// unit_system = "si"
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ValueNormalizationConfig {
+    /// Where the normalized values are emitted
+    ///
+    /// Default: metadata
+    #[serde(default)]
+    pub mode: ValueAnnotationMode,
+
+    /// Kinds of values to normalize (empty = all kinds)
+    #[serde(default)]
+    pub kinds: Vec<ValueKind>,
+
+    /// Language or locale of the document, such as `"de"`, `"en-GB"` or `"fra"`
+    /// (None = the first detected language)
+    #[serde(default)]
+    pub locale: Option<String>,
+
+    /// Unit system that quantities are converted to
+    ///
+    /// Default: si
+    #[serde(default)]
+    pub unit_system: UnitSystem,
+}
+
+impl ValueNormalizationConfig {
+    /// Whether values of `kind` are normalized.
+    pub fn includes(&self, kind: ValueKind) -> bool {
+        self.kinds.is_empty() || self.kinds.contains(&kind)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value_normalization_config_from_toml() {
+        let config: ValueNormalizationConfig = toml::from_str(
+            r#"
+            mode = "inline"
+            kinds = ["date", "time"]
+            locale = "en-GB"
+            unit_system = "imperial"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.mode, ValueAnnotationMode::Inline);
+        assert!(config.includes(ValueKind::Time));
+        assert!(!config.includes(ValueKind::Number));
+        assert_eq!(config.locale.as_deref(), Some("en-GB"));
+        assert_eq!(config.unit_system, UnitSystem::Imperial);
+
+        let config: ValueNormalizationConfig = toml::from_str("").unwrap();
+        assert_eq!(config.mode, ValueAnnotationMode::Metadata);
+        assert!(config.includes(ValueKind::Currency));
+        assert_eq!(config.locale, None);
+        assert_eq!(config.unit_system, UnitSystem::Si);
+    }
+}
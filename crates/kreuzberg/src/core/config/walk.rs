@@ -0,0 +1,126 @@
+//! Directory walking configuration for batch extraction.
+//!
+//! When a batch is given directories, the files in them are found with a
+//! [`WalkConfig`]. The defaults follow what tools like ripgrep do: symbolic links are
+//! not followed, hidden files and special files are skipped, and a file linked more
+//! than once is extracted once.
+
+use serde::{Deserialize, Serialize};
+
+/// What to do with symbolic links found while walking a directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymlinkPolicy {
+    /// Walk linked directories and include linked files
+    Follow,
+    /// Leave symbolic links out
+    #[default]
+    Skip,
+    /// Fail the walk
+    Error,
+}
+
+/// What to do with devices, FIFOs and sockets found while walking a directory.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpecialFilePolicy {
+    /// Leave special files out
+    #[default]
+    Skip,
+    /// Fail the walk
+    Error,
+}
+
+/// Which files in the directories of a batch are extracted.
+///
+/// Glob patterns without a `/` match file and directory names at any depth, and
+/// patterns with a `/` match paths relative to the walked directory, such as
+/// `reports/**/*.pdf`. Excluded directories are not walked.
+///
+/// # Example
+///
+/// ```toml
+/// [walk]
+/// symlinks = "follow"
+/// hidden = true
+/// include = ["*.pdf", "*.docx"]
+/// exclude = ["drafts", "archive/**"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WalkConfig {
+    /// Walk subdirectories
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub recursive: bool,
+
+    /// Symbolic link handling
+    ///
+    /// Default: `skip`
+    #[serde(default)]
+    pub symlinks: SymlinkPolicy,
+
+    /// Extract files with several hard links, or reached through several symbolic
+    /// links, once (Unix only)
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub dedup_hardlinks: bool,
+
+    /// Include hidden files and directories: names starting with `.`, and on Windows
+    /// also entries with the hidden attribute
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub hidden: bool,
+
+    /// Handling of devices, FIFOs and sockets
+    ///
+    /// Default: `skip`
+    #[serde(default)]
+    pub special_files: SpecialFilePolicy,
+
+    /// Glob patterns of the files to include (empty = all files)
+    #[serde(default)]
+    pub include: Vec<String>,
+
+    /// Glob patterns of the files and directories to leave out
+    #[serde(default)]
+    pub exclude: Vec<String>,
+}
+
+impl Default for WalkConfig {
+    fn default() -> Self {
+        Self {
+            recursive: true,
+            symlinks: SymlinkPolicy::default(),
+            dedup_hardlinks: true,
+            hidden: false,
+            special_files: SpecialFilePolicy::default(),
+            include: Vec::new(),
+            exclude: Vec::new(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_walk_config_from_toml() {
+        let config: WalkConfig = toml::from_str("symlinks = \"error\"\ninclude = [\"*.pdf\"]").unwrap();
+        assert_eq!(config.symlinks, SymlinkPolicy::Error);
+        assert_eq!(config.include, vec!["*.pdf".to_string()]);
+        assert!(config.recursive);
+        assert!(config.dedup_hardlinks);
+        assert!(!config.hidden);
+
+        let config: WalkConfig = toml::from_str("").unwrap();
+        assert_eq!(config, WalkConfig::default());
+    }
+}
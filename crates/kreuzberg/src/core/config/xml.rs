@@ -0,0 +1,97 @@
+//! XML parsing limits configuration.
+//!
+//! XML-based formats (generic XML, SVG, EPUB, DocBook, JATS, FictionBook, OPML) are
+//! parsed under these limits, so documents built to exhaust memory (entity expansion
+//! "billion laughs", deeply nested elements) or to read local files through external
+//! entities (XXE) fail with a security error instead. Office and OpenDocument package
+//! parts are parsed under the default limits.
+
+use serde::{Deserialize, Serialize};
+
+/// XML parsing limits.
+///
+/// # Example
+///
+/// ```toml
+/// [xml]
+/// allow_dtd = false
+/// max_depth = 256
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XmlConfig {
+    /// Accept documents with a `DOCTYPE` declaration (false = reject any DTD)
+    ///
+    /// Default: true
+    #[serde(default = "default_true")]
+    pub allow_dtd: bool,
+
+    /// Accept documents declaring external entities (false = reject them); external
+    /// entities are never resolved either way
+    #[serde(default)]
+    pub allow_external_entities: bool,
+
+    /// Largest number of entity references expanded in a document, counting
+    /// references inside entity values
+    ///
+    /// Default: 10000
+    #[serde(default = "default_max_entity_expansions")]
+    pub max_entity_expansions: u64,
+
+    /// Largest amount of text produced by entity expansion, in bytes
+    ///
+    /// Default: 1 MiB
+    #[serde(default = "default_max_entity_expansion_bytes")]
+    pub max_entity_expansion_bytes: u64,
+
+    /// Deepest element nesting allowed
+    ///
+    /// Default: 1024
+    #[serde(default = "default_max_depth")]
+    pub max_depth: usize,
+}
+
+impl Default for XmlConfig {
+    fn default() -> Self {
+        Self {
+            allow_dtd: true,
+            allow_external_entities: false,
+            max_entity_expansions: default_max_entity_expansions(),
+            max_entity_expansion_bytes: default_max_entity_expansion_bytes(),
+            max_depth: default_max_depth(),
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_max_entity_expansions() -> u64 {
+    10_000
+}
+
+fn default_max_entity_expansion_bytes() -> u64 {
+    1024 * 1024
+}
+
+fn default_max_depth() -> usize {
+    1024
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xml_config_from_toml() {
+        let config: XmlConfig = toml::from_str("allow_dtd = false\nmax_depth = 64").unwrap();
+        assert!(!config.allow_dtd);
+        assert!(!config.allow_external_entities);
+        assert_eq!(config.max_depth, 64);
+        assert_eq!(config.max_entity_expansions, 10_000);
+
+        let config: XmlConfig = toml::from_str("").unwrap();
+        assert_eq!(config, XmlConfig::default());
+        assert!(config.allow_dtd);
+    }
+}
@@ -94,6 +94,7 @@ mod tests {
         assert!(validate_ocr_backend("tesseract").is_ok());
         assert!(validate_ocr_backend("easyocr").is_ok());
         assert!(validate_ocr_backend("paddleocr").is_ok());
+        assert!(validate_ocr_backend("onnx-ocr").is_ok());
     }
 
     #[test]
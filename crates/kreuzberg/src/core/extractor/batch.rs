@@ -2,12 +2,27 @@
 //!
 //! This module provides parallel extraction capabilities for processing
 //! multiple files or byte arrays concurrently with automatic resource management.
+//!
+//! Long runs can be checkpointed to a manifest with [`start`] and continued after an
+//! interruption with [`resume`]. [`extract_files`] keeps the combined memory of the
+//! extractions running at once under a [`MemoryBudget`], for corpora mixing small
+//! files with very large ones.
+//!
+//! With `ExtractionConfig::retry` set, all of them retry inputs that fail with a
+//! transient error, and append the inputs that fail for good to a dead-letter file.
 
-use crate::core::config::ExtractionConfig;
+use crate::compression::CompressedWriter;
+use crate::core::config::{Compression, ExtractionConfig};
+use crate::error::ErrorClass;
+use crate::provenance::{ManifestSigner, ProvenanceRecorder};
 use crate::types::{ErrorMetadata, ExtractionResult, Metadata};
 use crate::{KreuzbergError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::borrow::Cow;
-use std::path::Path;
+use std::collections::{BTreeMap, HashSet};
+use std::future::Future;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use super::bytes::extract_bytes;
@@ -82,9 +97,13 @@ pub async fn batch_extract_file(
 
         tasks.spawn(async move {
             let _permit = semaphore_clone.acquire().await.unwrap();
-            let result =
-                crate::core::batch_mode::with_batch_mode(async { extract_file(&path_buf, None, &config_clone).await })
-                    .await;
+            let result = crate::core::batch_mode::with_batch_mode(extract_with_retries(
+                &config_clone,
+                Some(&path_buf),
+                Some(index),
+                || extract_file(&path_buf, None, &config_clone),
+            ))
+            .await;
             (index, result)
         });
     }
@@ -116,6 +135,7 @@ pub async fn batch_extract_file(
                     chunks: None,
                     images: None,
                     djot_content: None,
+                    scripts: None,
                     pages: None,
                     elements: None,
                 });
@@ -196,9 +216,12 @@ pub async fn batch_extract_bytes(
 
         tasks.spawn(async move {
             let _permit = semaphore_clone.acquire().await.unwrap();
-            let result = crate::core::batch_mode::with_batch_mode(async {
-                extract_bytes(&bytes, &mime_type, &config_clone).await
-            })
+            let result = crate::core::batch_mode::with_batch_mode(extract_with_retries(
+                &config_clone,
+                None,
+                Some(index),
+                || extract_bytes(&bytes, &mime_type, &config_clone),
+            ))
             .await;
             (index, result)
         });
@@ -231,6 +254,7 @@ pub async fn batch_extract_bytes(
                     chunks: None,
                     images: None,
                     djot_content: None,
+                    scripts: None,
                     pages: None,
                     elements: None,
                 });
@@ -244,3 +268,568 @@ pub async fn batch_extract_bytes(
     #[allow(clippy::unwrap_used)]
     Ok(results.into_iter().map(|r| r.unwrap()).collect())
 }
+
+/// Working set assumed for every input on top of its size-based estimate.
+const BASE_WORKING_SET: u64 = 4 * 1024 * 1024;
+
+/// Limit on the combined working set of the extractions run at once by [`extract_files`].
+///
+/// The working set of an input is estimated from its file size and format: images are
+/// decoded into raw pixels, PDFs render pages and office documents and archives are
+/// decompressed, so these need a multiple of their size on disk.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MemoryBudget {
+    /// Bytes the estimated working sets of the running extractions may add up to
+    pub max_bytes: u64,
+
+    /// Working set of an input as a multiple of its file size (None = by format)
+    pub multiplier: Option<f64>,
+}
+
+impl MemoryBudget {
+    /// A budget of `max_bytes`, with working sets estimated by format.
+    pub fn new(max_bytes: u64) -> Self {
+        Self {
+            max_bytes,
+            multiplier: None,
+        }
+    }
+
+    /// Estimate working sets as `multiplier` times the file size, whatever the format.
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = Some(multiplier);
+        self
+    }
+
+    /// Estimated working set of extracting the `size` bytes of the file at `path`.
+    pub fn estimate(&self, path: &Path, size: u64) -> u64 {
+        let multiplier = self.multiplier.unwrap_or_else(|| format_multiplier(path));
+        BASE_WORKING_SET.saturating_add((size as f64 * multiplier) as u64)
+    }
+}
+
+/// Working set of a file of the format of `path` as a multiple of its size.
+fn format_multiplier(path: &Path) -> f64 {
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_ascii_lowercase)
+        .unwrap_or_default();
+    match extension.as_str() {
+        "png" | "jpg" | "jpeg" | "tif" | "tiff" | "bmp" | "gif" | "webp" | "jp2" | "pnm" => 16.0,
+        "docx" | "xlsx" | "xlsm" | "pptx" | "odt" | "ods" | "odp" | "epub" | "zip" | "gz" | "tgz" | "7z" => 10.0,
+        "pdf" => 8.0,
+        _ => 4.0,
+    }
+}
+
+/// Extract content from files concurrently, within a memory budget.
+///
+/// Inputs are started largest first, whenever their estimated working set fits in what
+/// the running extractions leave of `budget`, so large files run alone or next to a few
+/// small ones while small files run in parallel. An input estimated above the whole
+/// budget runs alone. `ExtractionConfig::max_concurrent_extractions` still caps the
+/// number of extractions at once. The budget covers the extractions, not the results,
+/// which are all returned.
+///
+/// # Returns
+///
+/// A vector of `ExtractionResult` in the same order as the input paths. Failed files
+/// have an error result, as with [`batch_extract_file`].
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Other` if an extraction task panics.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use kreuzberg::core::config::ExtractionConfig;
+/// use kreuzberg::core::extractor::batch::{self, MemoryBudget};
+///
+/// # async fn example() -> kreuzberg::Result<()> {
+/// let paths = ["scan.tiff", "report.pdf", "notes.txt"];
+/// let budget = MemoryBudget::new(2 * 1024 * 1024 * 1024);
+/// let results = batch::extract_files(paths, &ExtractionConfig::default(), budget).await?;
+/// println!("Processed {} files", results.len());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn extract_files<P: AsRef<Path>>(
+    paths: impl IntoIterator<Item = P>,
+    config: &ExtractionConfig,
+    budget: MemoryBudget,
+) -> Result<Vec<ExtractionResult>> {
+    use tokio::task::JoinSet;
+
+    // Pending inputs by estimate, so the largest one that fits is found in log time.
+    let mut pending = BTreeMap::new();
+    for (index, path) in paths.into_iter().enumerate() {
+        let path = path.as_ref().to_path_buf();
+        let size = tokio::fs::metadata(&path).await.map_or(0, |metadata| metadata.len());
+        pending.insert((budget.estimate(&path, size), index), path);
+    }
+
+    let config = Arc::new(config.clone());
+    let max_concurrent = config
+        .max_concurrent_extractions
+        .unwrap_or_else(|| (num_cpus::get() as f64 * 1.5).ceil() as usize)
+        .max(1);
+
+    let mut results: Vec<Option<ExtractionResult>> = vec![None; pending.len()];
+    let mut in_use = 0;
+    let mut tasks = JoinSet::new();
+
+    loop {
+        while tasks.len() < max_concurrent {
+            let next = if tasks.is_empty() {
+                pending.keys().next_back()
+            } else {
+                let free = budget.max_bytes.saturating_sub(in_use);
+                pending.range(..=(free, usize::MAX)).map(|(key, _)| key).next_back()
+            };
+            let Some(&key) = next else {
+                break;
+            };
+            let (estimate, index) = key;
+            #[allow(clippy::unwrap_used)]
+            let path = pending.remove(&key).unwrap();
+            in_use += estimate;
+
+            let config = Arc::clone(&config);
+            tasks.spawn(async move {
+                let result = crate::core::batch_mode::with_batch_mode(extract_with_retries(
+                    &config,
+                    Some(&path),
+                    Some(index),
+                    || extract_file(&path, None, &config),
+                ))
+                .await;
+                (index, estimate, result)
+            });
+        }
+
+        let Some(task_result) = tasks.join_next().await else {
+            break;
+        };
+        let (index, estimate, result) =
+            task_result.map_err(|e| KreuzbergError::Other(format!("Task panicked: {}", e)))?;
+        in_use -= estimate;
+        results[index] = Some(result.unwrap_or_else(|e| error_result(&e)));
+    }
+
+    #[allow(clippy::unwrap_used)]
+    Ok(results.into_iter().map(|r| r.unwrap()).collect())
+}
+
+/// The result recorded for an input whose extraction failed.
+fn error_result(error: &KreuzbergError) -> ExtractionResult {
+    ExtractionResult {
+        content: format!("Error: {}", error),
+        mime_type: Cow::Borrowed("text/plain"),
+        metadata: Metadata {
+            error: Some(ErrorMetadata {
+                error_type: format!("{:?}", error),
+                message: error.to_string(),
+            }),
+            ..Default::default()
+        },
+        tables: vec![],
+        detected_languages: None,
+        chunks: None,
+        images: None,
+        djot_content: None,
+        scripts: None,
+        pages: None,
+        elements: None,
+    }
+}
+
+/// Version of the batch job manifest format.
+const MANIFEST_VERSION: u32 = 1;
+
+/// First line of a batch job manifest: what the job extracts, and how.
+#[derive(Serialize, Deserialize)]
+struct ManifestHeader {
+    version: u32,
+    output_dir: PathBuf,
+    config: ExtractionConfig,
+    inputs: Vec<PathBuf>,
+}
+
+/// A line of a batch job manifest recording the outcome of one input.
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum ManifestEntry {
+    Completed {
+        index: usize,
+        result: String,
+        sha256: String,
+    },
+    Failed {
+        index: usize,
+        error: String,
+    },
+}
+
+/// Outcome of a run of a batch job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BatchJobSummary {
+    /// Inputs in the job
+    pub total: usize,
+    /// Inputs extracted by this run
+    pub extracted: usize,
+    /// Inputs whose extraction failed in this run
+    pub failed: usize,
+    /// Inputs already recorded by an earlier run
+    pub skipped: usize,
+}
+
+/// Start a resumable batch extraction of `paths`, checkpointed to `manifest_path`.
+///
+/// Each result is written as JSON to `output_dir` (`00000000.json` for the first
+/// input, and so on), and its file name and SHA-256 hash are appended to the manifest
+/// as soon as it is written. An interrupted job continues with [`resume`], which only
+/// extracts the inputs the manifest does not record yet. Failed extractions are
+/// recorded with their error and not retried.
+///
+/// With `config.cache.compression` set, result files are compressed and named
+/// `00000000.json.zst` or `00000000.json.gz`.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Validation` if the manifest already exists, and
+/// `KreuzbergError::Io` if the manifest or a result cannot be written. Extraction
+/// errors of individual inputs are recorded in the manifest instead.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use kreuzberg::core::config::ExtractionConfig;
+/// use kreuzberg::core::extractor::batch;
+///
+/// # async fn example() -> kreuzberg::Result<()> {
+/// let paths = vec!["doc1.pdf", "doc2.pdf"];
+/// let summary = batch::start(paths, &ExtractionConfig::default(), "job.jsonl", "results").await?;
+/// println!("Extracted {} of {} files", summary.extracted, summary.total);
+///
+/// // After an interruption:
+/// let summary = batch::resume("job.jsonl").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn start(
+    paths: Vec<impl AsRef<Path>>,
+    config: &ExtractionConfig,
+    manifest_path: impl AsRef<Path>,
+    output_dir: impl AsRef<Path>,
+) -> Result<BatchJobSummary> {
+    let manifest_path = manifest_path.as_ref();
+    if manifest_path.exists() {
+        return Err(KreuzbergError::validation(format!(
+            "Batch manifest {} already exists; resume the job instead",
+            manifest_path.display()
+        )));
+    }
+
+    let header = ManifestHeader {
+        version: MANIFEST_VERSION,
+        output_dir: std::path::absolute(output_dir.as_ref())?,
+        config: config.clone(),
+        inputs: paths
+            .iter()
+            .map(|path| std::path::absolute(path.as_ref()))
+            .collect::<std::io::Result<_>>()?,
+    };
+    let mut line = serde_json::to_vec(&header)?;
+    line.push(b'\n');
+    tokio::fs::create_dir_all(&header.output_dir).await?;
+    tokio::fs::write(manifest_path, line).await?;
+
+    run_job(manifest_path, header, HashSet::new()).await
+}
+
+/// Continue the batch job checkpointed to `manifest_path` by [`start`].
+///
+/// Inputs recorded as completed are skipped when their result file still has the
+/// recorded hash, and extracted again otherwise.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Validation` if the manifest is not a batch job manifest,
+/// and `KreuzbergError::Io` if it cannot be read or written.
+pub async fn resume(manifest_path: impl AsRef<Path>) -> Result<BatchJobSummary> {
+    let manifest_path = manifest_path.as_ref();
+    let manifest = tokio::fs::read_to_string(manifest_path).await?;
+    let mut lines = manifest.lines();
+
+    let header: ManifestHeader = lines
+        .next()
+        .and_then(|line| serde_json::from_str(line).ok())
+        .ok_or_else(|| {
+            KreuzbergError::validation(format!("{} is not a batch job manifest", manifest_path.display()))
+        })?;
+    if header.version != MANIFEST_VERSION {
+        return Err(KreuzbergError::validation(format!(
+            "Unsupported batch manifest version {} (expected {})",
+            header.version, MANIFEST_VERSION
+        )));
+    }
+
+    let mut done = HashSet::new();
+    for line in lines {
+        // A line cut short by an interruption is skipped; its input is extracted again.
+        match serde_json::from_str(line) {
+            Ok(ManifestEntry::Completed { index, result, sha256 }) => {
+                let recorded = tokio::fs::read(header.output_dir.join(&result)).await;
+                if recorded.is_ok_and(|bytes| hex::encode(Sha256::digest(bytes)) == sha256) {
+                    done.insert(index);
+                } else {
+                    done.remove(&index);
+                }
+            }
+            Ok(ManifestEntry::Failed { index, .. }) => {
+                done.insert(index);
+            }
+            Err(_) => tracing::debug!("Skipping unreadable batch manifest line"),
+        }
+    }
+
+    if !manifest.ends_with('\n') {
+        use tokio::io::AsyncWriteExt;
+        let mut file = tokio::fs::OpenOptions::new().append(true).open(manifest_path).await?;
+        file.write_all(b"\n").await?;
+    }
+    run_job(manifest_path, header, done).await
+}
+
+/// Extract the inputs of `header` not in `done`, recording each outcome in the manifest.
+async fn run_job(manifest_path: &Path, header: ManifestHeader, done: HashSet<usize>) -> Result<BatchJobSummary> {
+    use tokio::io::AsyncWriteExt;
+    use tokio::task::JoinSet;
+
+    let mut summary = BatchJobSummary {
+        total: header.inputs.len(),
+        extracted: 0,
+        failed: 0,
+        skipped: done.len(),
+    };
+    let signer = header
+        .config
+        .provenance
+        .as_ref()
+        .map(ManifestSigner::new)
+        .transpose()?
+        .map(Arc::new);
+    let config = Arc::new(header.config);
+    let output_dir = Arc::new(header.output_dir);
+    let max_concurrent = config
+        .max_concurrent_extractions
+        .unwrap_or_else(|| (num_cpus::get() as f64 * 1.5).ceil() as usize)
+        .max(1);
+
+    let mut manifest = tokio::fs::OpenOptions::new().append(true).open(manifest_path).await?;
+    let mut pending = header
+        .inputs
+        .into_iter()
+        .enumerate()
+        .filter(|(index, _)| !done.contains(index));
+    let mut tasks = JoinSet::new();
+
+    loop {
+        while tasks.len() < max_concurrent
+            && let Some((index, path)) = pending.next()
+        {
+            let config = Arc::clone(&config);
+            let output_dir = Arc::clone(&output_dir);
+            let signer = signer.clone();
+            let compression = config.cache.as_ref().map(|cache| cache.compression).unwrap_or_default();
+            tasks.spawn(async move {
+                // Hashed before extraction, so the manifest records the input as it was read.
+                let recorder = match signer
+                    .as_ref()
+                    .map(|_| ProvenanceRecorder::for_file(&path, &config))
+                    .transpose()
+                {
+                    Ok(recorder) => recorder,
+                    Err(e) => {
+                        return Ok(ManifestEntry::Failed {
+                            index,
+                            error: e.to_string(),
+                        });
+                    }
+                };
+                let result = crate::core::batch_mode::with_batch_mode(extract_with_retries(
+                    &config,
+                    Some(&path),
+                    Some(index),
+                    || extract_file(&path, None, &config),
+                ))
+                .await;
+                match result {
+                    Ok(result) => {
+                        let provenance = recorder.zip(signer.as_deref());
+                        write_result(&output_dir, index, &result, compression, provenance).await
+                    }
+                    Err(e) => Ok(ManifestEntry::Failed {
+                        index,
+                        error: e.to_string(),
+                    }),
+                }
+            });
+        }
+
+        let Some(task_result) = tasks.join_next().await else {
+            break;
+        };
+        let entry = task_result.map_err(|e| KreuzbergError::Other(format!("Task panicked: {}", e)))??;
+        match entry {
+            ManifestEntry::Completed { .. } => summary.extracted += 1,
+            ManifestEntry::Failed { .. } => summary.failed += 1,
+        }
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+        manifest.write_all(&line).await?;
+        manifest.flush().await?;
+    }
+
+    Ok(summary)
+}
+
+/// Write the result of input `index` to `output_dir`, replacing any earlier one atomically.
+///
+/// With `provenance`, the provenance manifest of the result file is written next to it,
+/// as `00000000.provenance.json` for the first input.
+async fn write_result(
+    output_dir: &Path,
+    index: usize,
+    result: &ExtractionResult,
+    compression: Compression,
+    provenance: Option<(ProvenanceRecorder, &ManifestSigner)>,
+) -> Result<ManifestEntry> {
+    let name = format!("{:08}.json{}", index, compression.extension());
+    let mut writer = CompressedWriter::new(Vec::new(), compression)?;
+    result.write_versioned_json(&mut writer)?;
+    let json = writer.finish()?;
+    let sha256 = hex::encode(Sha256::digest(&json));
+
+    if let Some((recorder, signer)) = provenance {
+        let mut manifest = recorder.finish(&result.mime_type, &json)?;
+        signer.seal(&mut manifest)?;
+        write_atomically(
+            output_dir,
+            &format!("{:08}.provenance.json", index),
+            &serde_json::to_vec(&manifest)?,
+        )
+        .await?;
+    }
+    write_atomically(output_dir, &name, &json).await?;
+    Ok(ManifestEntry::Completed {
+        index,
+        result: name,
+        sha256,
+    })
+}
+
+async fn write_atomically(dir: &Path, name: &str, content: &[u8]) -> Result<()> {
+    let partial = dir.join(format!("{}.partial", name));
+    tokio::fs::write(&partial, content).await?;
+    tokio::fs::rename(&partial, dir.join(name)).await?;
+    Ok(())
+}
+
+/// A line of the dead-letter file of a retry policy.
+#[derive(Serialize)]
+struct DeadLetter<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    path: Option<&'a Path>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    index: Option<usize>,
+    stage: &'static str,
+    error_type: ErrorClass,
+    error: String,
+    attempts: u32,
+}
+
+/// Run `extract` until it succeeds or the retry policy of `config` gives up, and record
+/// an input that failed for good in the dead-letter file of the policy.
+///
+/// `path` and `index` identify the input in the dead-letter file.
+pub(crate) async fn extract_with_retries<F, Fut>(
+    config: &ExtractionConfig,
+    path: Option<&Path>,
+    index: Option<usize>,
+    mut extract: F,
+) -> Result<ExtractionResult>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<ExtractionResult>>,
+{
+    let mut attempts = 1;
+    loop {
+        let error = match extract().await {
+            Ok(result) => return Ok(result),
+            Err(error) => error,
+        };
+        let Some(retry) = &config.retry else {
+            return Err(error);
+        };
+
+        if attempts < retry.max_attempts && retry.retry_on.contains(&error.class()) {
+            let delay = retry.backoff(attempts);
+            tracing::debug!(
+                "Extraction attempt {} failed, retrying in {:?}: {}",
+                attempts,
+                delay,
+                error
+            );
+            tokio::time::sleep(delay).await;
+            attempts += 1;
+            continue;
+        }
+
+        if let Some(dead_letter) = &retry.dead_letter {
+            let letter = DeadLetter {
+                path,
+                index,
+                stage: stage(error.class()),
+                error_type: error.class(),
+                error: error.to_string(),
+                attempts,
+            };
+            if let Err(e) = append_dead_letter(dead_letter, &letter).await {
+                tracing::warn!(
+                    "Failed to record failed input in dead-letter file {}: {}",
+                    dead_letter.display(),
+                    e
+                );
+            }
+        }
+        return Err(error);
+    }
+}
+
+/// Pipeline stage errors of `class` come from, as recorded in dead-letter files.
+fn stage(class: ErrorClass) -> &'static str {
+    match class {
+        ErrorClass::Io | ErrorClass::Validation | ErrorClass::Security | ErrorClass::UnsupportedFormat => "input",
+        ErrorClass::Ocr | ErrorClass::ImageProcessing => "ocr",
+        ErrorClass::Plugin => "plugin",
+        _ => "extraction",
+    }
+}
+
+async fn append_dead_letter(dead_letter: &Path, letter: &DeadLetter<'_>) -> Result<()> {
+    use tokio::io::AsyncWriteExt;
+
+    let mut line = serde_json::to_vec(letter)?;
+    line.push(b'\n');
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(dead_letter)
+        .await?;
+    file.write_all(&line).await?;
+    file.flush().await?;
+    Ok(())
+}
@@ -11,14 +11,16 @@ use crate::Result;
 use crate::core::config::ExtractionConfig;
 use crate::core::mime::{LEGACY_POWERPOINT_MIME_TYPE, LEGACY_WORD_MIME_TYPE};
 #[cfg(feature = "office")]
-use crate::extraction::libreoffice::{convert_doc_to_docx, convert_ppt_to_pptx};
+use crate::core::temp::TempWorkspace;
+#[cfg(feature = "office")]
+use crate::extraction::libreoffice::{convert_doc_to_docx_in, convert_ppt_to_pptx_in};
 use crate::types::ExtractionResult;
 
-#[cfg(feature = "office")]
-use super::file::apply_libreoffice_metadata;
 use super::file::extract_bytes_with_extractor;
 #[cfg(feature = "otel")]
 use super::file::record_error;
+#[cfg(feature = "office")]
+use super::file::{apply_libreoffice_metadata, inspect_original};
 
 /// Extract content from a byte array.
 ///
@@ -74,10 +76,15 @@ pub async fn extract_bytes(content: &[u8], mime_type: &str, config: &ExtractionC
         match validated_mime.as_str() {
             #[cfg(feature = "office")]
             LEGACY_WORD_MIME_TYPE => {
-                let conversion = convert_doc_to_docx(content).await?;
+                let inspection = inspect_original(content, LEGACY_WORD_MIME_TYPE, config)?;
+                let workspace = TempWorkspace::new(config.io.as_ref());
+                let conversion = convert_doc_to_docx_in(content, &workspace).await?;
                 let mut result =
                     extract_bytes_with_extractor(&conversion.converted_bytes, &conversion.target_mime, config).await?;
                 apply_libreoffice_metadata(&mut result, LEGACY_WORD_MIME_TYPE, &conversion);
+                if let Some(inspection) = inspection {
+                    inspection.apply(&mut result);
+                }
                 return Ok(result);
             }
             #[cfg(not(feature = "office"))]
@@ -88,10 +95,15 @@ pub async fn extract_bytes(content: &[u8], mime_type: &str, config: &ExtractionC
             }
             #[cfg(feature = "office")]
             LEGACY_POWERPOINT_MIME_TYPE => {
-                let conversion = convert_ppt_to_pptx(content).await?;
+                let inspection = inspect_original(content, LEGACY_POWERPOINT_MIME_TYPE, config)?;
+                let workspace = TempWorkspace::new(config.io.as_ref());
+                let conversion = convert_ppt_to_pptx_in(content, &workspace).await?;
                 let mut result =
                     extract_bytes_with_extractor(&conversion.converted_bytes, &conversion.target_mime, config).await?;
                 apply_libreoffice_metadata(&mut result, LEGACY_POWERPOINT_MIME_TYPE, &conversion);
+                if let Some(inspection) = inspection {
+                    inspection.apply(&mut result);
+                }
                 return Ok(result);
             }
             #[cfg(not(feature = "office"))]
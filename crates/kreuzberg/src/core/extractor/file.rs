@@ -6,26 +6,33 @@
 //! - File validation and reading
 //! - Extraction pipeline orchestration
 
-#[cfg(any(feature = "otel", not(feature = "office")))]
-use crate::KreuzbergError;
-use crate::Result;
-use crate::core::config::ExtractionConfig;
+use crate::core::config::{ExtractionConfig, IsolationMode};
 use crate::core::mime::{LEGACY_POWERPOINT_MIME_TYPE, LEGACY_WORD_MIME_TYPE};
+use crate::core::pipeline::ReportRecorder;
 #[cfg(feature = "office")]
-use crate::extraction::libreoffice::{convert_doc_to_docx, convert_ppt_to_pptx};
-use crate::types::ExtractionResult;
+use crate::core::temp::TempWorkspace;
+use crate::error::ErrorClass;
+use crate::extraction::inspection::Inspection;
+#[cfg(feature = "office")]
+use crate::extraction::libreoffice::{convert_doc_to_docx_in, convert_ppt_to_pptx_in};
+use crate::plugins::guard::{PluginKind, catch_extractor_panic, guarded};
+use crate::plugins::{DocumentExtractor, ExtractionRequest, PipelineHook};
 #[cfg(feature = "office")]
 use crate::types::LibreOfficeConversionResult;
+use crate::types::{ExtractionResult, FallbackAttempt};
+use crate::{KreuzbergError, Result};
 #[cfg(feature = "office")]
 use serde_json::json;
 #[cfg(feature = "office")]
 use std::borrow::Cow;
 use std::path::Path;
+use std::sync::Arc;
+use std::time::Instant;
 
 #[cfg(feature = "office")]
 use super::helpers::pool_mime_type;
 
-use super::helpers::get_extractor;
+use super::helpers::{OCR_FULL_FALLBACK, get_extractor, get_fallback_extractor};
 
 /// Sanitize a file path to return only the filename.
 ///
@@ -153,11 +160,16 @@ pub async fn extract_file(
         match detected_mime.as_str() {
             #[cfg(feature = "office")]
             LEGACY_WORD_MIME_TYPE => {
-                let original_bytes = tokio::fs::read(path).await?;
-                let conversion = convert_doc_to_docx(&original_bytes).await?;
+                let original_bytes = io::open_file_async(path, config.io.as_ref()).await?;
+                let inspection = inspect_original(&original_bytes, LEGACY_WORD_MIME_TYPE, config)?;
+                let workspace = TempWorkspace::new(config.io.as_ref());
+                let conversion = convert_doc_to_docx_in(&original_bytes, &workspace).await?;
                 let mut result =
                     extract_bytes_with_extractor(&conversion.converted_bytes, &conversion.target_mime, config).await?;
                 apply_libreoffice_metadata(&mut result, LEGACY_WORD_MIME_TYPE, &conversion);
+                if let Some(inspection) = inspection {
+                    inspection.apply(&mut result);
+                }
                 return Ok(result);
             }
             #[cfg(not(feature = "office"))]
@@ -168,11 +180,16 @@ pub async fn extract_file(
             }
             #[cfg(feature = "office")]
             LEGACY_POWERPOINT_MIME_TYPE => {
-                let original_bytes = tokio::fs::read(path).await?;
-                let conversion = convert_ppt_to_pptx(&original_bytes).await?;
+                let original_bytes = io::open_file_async(path, config.io.as_ref()).await?;
+                let inspection = inspect_original(&original_bytes, LEGACY_POWERPOINT_MIME_TYPE, config)?;
+                let workspace = TempWorkspace::new(config.io.as_ref());
+                let conversion = convert_ppt_to_pptx_in(&original_bytes, &workspace).await?;
                 let mut result =
                     extract_bytes_with_extractor(&conversion.converted_bytes, &conversion.target_mime, config).await?;
                 apply_libreoffice_metadata(&mut result, LEGACY_POWERPOINT_MIME_TYPE, &conversion);
+                if let Some(inspection) = inspection {
+                    inspection.apply(&mut result);
+                }
                 return Ok(result);
             }
             #[cfg(not(feature = "office"))]
@@ -201,25 +218,387 @@ pub(in crate::core::extractor) async fn extract_file_with_extractor(
     mime_type: &str,
     config: &ExtractionConfig,
 ) -> Result<ExtractionResult> {
-    crate::extractors::ensure_initialized()?;
-
-    let extractor = get_extractor(mime_type)?;
-    let mut result = extractor.extract_file(path, mime_type, config).await?;
-    result = crate::core::pipeline::run_pipeline(result, config).await?;
-    Ok(result)
+    run_extraction(ExtractionSource::File(path), mime_type, config).await
 }
 
 pub(in crate::core::extractor) async fn extract_bytes_with_extractor(
     content: &[u8],
     mime_type: &str,
     config: &ExtractionConfig,
+) -> Result<ExtractionResult> {
+    run_extraction(ExtractionSource::Bytes(content), mime_type, config).await
+}
+
+enum ExtractionSource<'a> {
+    File(&'a Path),
+    Bytes(&'a [u8]),
+}
+
+/// Run the extractor and pipeline for `source`, wrapped in the registered pipeline hooks.
+async fn run_extraction(
+    source: ExtractionSource<'_>,
+    mime_type: &str,
+    config: &ExtractionConfig,
 ) -> Result<ExtractionResult> {
     crate::extractors::ensure_initialized()?;
 
-    let extractor = get_extractor(mime_type)?;
-    let mut result = extractor.extract_bytes(content, mime_type, config).await?;
-    result = crate::core::pipeline::run_pipeline(result, config).await?;
-    Ok(result)
+    let hooks = crate::plugins::registered_pipeline_hooks()?;
+    if hooks.is_empty() {
+        return extract_source(&source, mime_type, config, None, &hooks).await;
+    }
+
+    let mut request = match source {
+        ExtractionSource::File(path) => ExtractionRequest {
+            path: Some(path.to_path_buf()),
+            size_bytes: std::fs::metadata(path).ok().map(|m| m.len()),
+            mime_type: mime_type.to_string(),
+            config: config.clone(),
+        },
+        ExtractionSource::Bytes(content) => ExtractionRequest {
+            path: None,
+            size_bytes: Some(content.len() as u64),
+            mime_type: mime_type.to_string(),
+            config: config.clone(),
+        },
+    };
+
+    let result = async {
+        for hook in &hooks {
+            hook.before_extract(&mut request).await?;
+        }
+
+        let mut result = extract_source(&source, &request.mime_type, &request.config, Some(&request), &hooks).await?;
+
+        for hook in &hooks {
+            hook.after_extract(&request, &mut result).await?;
+        }
+        Ok(result)
+    }
+    .await;
+
+    if let Err(ref e) = result {
+        for hook in &hooks {
+            hook.on_error(&request, e).await;
+        }
+    }
+
+    result
+}
+
+async fn extract_source(
+    source: &ExtractionSource<'_>,
+    mime_type: &str,
+    config: &ExtractionConfig,
+    request: Option<&ExtractionRequest>,
+    hooks: &[Arc<dyn PipelineHook>],
+) -> Result<ExtractionResult> {
+    crate::core::pipeline::check_network_denied(config, mime_type)?;
+    let mut report = ReportRecorder::new(config);
+    let chain = config.fallbacks.get(mime_type).map_or(&[][..], Vec::as_slice);
+    let extractor = if chain.is_empty() {
+        Some(get_extractor(mime_type)?)
+    } else {
+        None
+    };
+    let inspection = inspect_source(source, mime_type, config, request, hooks).await?;
+
+    if report.is_enabled() {
+        report.start_input(match source {
+            ExtractionSource::File(path) => std::fs::metadata(path).map_or(0, |metadata| metadata.len()),
+            ExtractionSource::Bytes(content) => content.len() as u64,
+        });
+    }
+    let extraction = async {
+        match extract_with(extractor.as_ref(), chain, source, mime_type, config).await {
+            Err(e) if config.salvage => salvage_source(e, extractor.as_ref(), chain, source, mime_type, config).await,
+            outcome => outcome,
+        }
+    };
+    #[cfg(feature = "tokio-runtime")]
+    let (extractor, mut result, attempts) = report.collect_ocr(extraction).await?;
+    #[cfg(not(feature = "tokio-runtime"))]
+    let (extractor, mut result, attempts) = extraction.await?;
+    report.fallbacks(attempts);
+    report.plugin_stage("extraction", extractor.as_ref(), &result);
+
+    inspection.apply(&mut result);
+    #[cfg(feature = "remote-resources")]
+    crate::core::network::fetch_remote_images(&mut result, config).await;
+    crate::core::pipeline::run_pipeline_with_report(result, config, report).await
+}
+
+/// Run `extractor` on `source`, or the fallback `chain` of `mime_type` when it has one.
+async fn extract_with(
+    extractor: Option<&Arc<dyn DocumentExtractor>>,
+    chain: &[String],
+    source: &ExtractionSource<'_>,
+    mime_type: &str,
+    config: &ExtractionConfig,
+) -> Result<(Arc<dyn DocumentExtractor>, ExtractionResult, Vec<FallbackAttempt>)> {
+    match extractor {
+        Some(extractor) => {
+            let result = run_extractor(extractor.as_ref(), source, mime_type, config).await?;
+            Ok((Arc::clone(extractor), result, Vec::new()))
+        }
+        None => extract_with_fallbacks(chain, source, mime_type, config).await,
+    }
+}
+
+/// Repair the damaged `source` after its extraction failed with `error`, and extract the
+/// repaired input, reporting the damage in `metadata.additional["salvage_warnings"]`.
+///
+/// `error` is returned when the input cannot be repaired or the repaired input fails too.
+async fn salvage_source(
+    error: KreuzbergError,
+    extractor: Option<&Arc<dyn DocumentExtractor>>,
+    chain: &[String],
+    source: &ExtractionSource<'_>,
+    mime_type: &str,
+    config: &ExtractionConfig,
+) -> Result<(Arc<dyn DocumentExtractor>, ExtractionResult, Vec<FallbackAttempt>)> {
+    if !matches!(
+        error.class(),
+        ErrorClass::Parsing | ErrorClass::ExtractorPanicked | ErrorClass::Plugin | ErrorClass::Other
+    ) {
+        return Err(error);
+    }
+
+    let file_content;
+    let content = match source {
+        ExtractionSource::File(path) => {
+            #[cfg(feature = "tokio-runtime")]
+            let read = crate::core::io::open_file_async(path, config.io.as_ref()).await;
+            #[cfg(not(feature = "tokio-runtime"))]
+            let read = crate::core::io::open_file_sync(path, config.io.as_ref());
+            let Ok(read) = read else {
+                return Err(error);
+            };
+            file_content = read;
+            &file_content
+        }
+        ExtractionSource::Bytes(content) => *content,
+    };
+    let Some(salvage) = crate::extraction::salvage::repair(content, mime_type) else {
+        return Err(error);
+    };
+
+    tracing::warn!(
+        "Extraction of the damaged {} input failed ({}), extracting the repaired input",
+        mime_type,
+        error
+    );
+    let repaired = ExtractionSource::Bytes(&salvage.content);
+    let (extractor, mut result, attempts) = match extract_with(extractor, chain, &repaired, mime_type, config).await {
+        Ok(extraction) => extraction,
+        Err(e) => {
+            tracing::warn!("Extraction of the repaired {} input failed: {}", mime_type, e);
+            return Err(error);
+        }
+    };
+
+    let mut warnings = vec![format!("Extraction of the input failed: {error}")];
+    warnings.extend(salvage.warnings);
+    result.metadata.additional.insert(
+        std::borrow::Cow::Borrowed(crate::extraction::salvage::SALVAGE_WARNINGS_KEY),
+        serde_json::json!(warnings),
+    );
+    Ok((extractor, result, attempts))
+}
+
+/// Run the extractors of the fallback `chain` of `mime_type` on `source` in order, until
+/// one succeeds, returning it with its result and the attempts.
+///
+/// Errors reading or rejecting the input end the chain, as every extractor would fail
+/// on them; when all extractors fail, the error of the last one is returned.
+async fn extract_with_fallbacks(
+    chain: &[String],
+    source: &ExtractionSource<'_>,
+    mime_type: &str,
+    config: &ExtractionConfig,
+) -> Result<(Arc<dyn DocumentExtractor>, ExtractionResult, Vec<FallbackAttempt>)> {
+    let mut attempts = Vec::with_capacity(chain.len());
+    let mut last_error = None;
+
+    for entry in chain {
+        let started = Instant::now();
+        let ocr_config;
+        let attempt_config = if entry == OCR_FULL_FALLBACK {
+            ocr_config = ExtractionConfig {
+                force_ocr: true,
+                ..config.clone()
+            };
+            &ocr_config
+        } else {
+            config
+        };
+        let outcome = match get_fallback_extractor(entry, mime_type) {
+            Ok(extractor) => run_extractor(extractor.as_ref(), source, mime_type, attempt_config)
+                .await
+                .map(|result| (extractor, result)),
+            Err(e) => Err(e),
+        };
+        let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+
+        match outcome {
+            Ok((extractor, result)) => {
+                attempts.push(FallbackAttempt {
+                    extractor: entry.clone(),
+                    duration_ms,
+                    error: None,
+                });
+                return Ok((extractor, result, attempts));
+            }
+            Err(e) => {
+                if matches!(e.class(), ErrorClass::Io | ErrorClass::Security) {
+                    return Err(e);
+                }
+                tracing::warn!(
+                    "Extractor '{}' of the fallback chain for {} failed: {}",
+                    entry,
+                    mime_type,
+                    e
+                );
+                attempts.push(FallbackAttempt {
+                    extractor: entry.clone(),
+                    duration_ms,
+                    error: Some(e.to_string()),
+                });
+                last_error = Some(e);
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or_else(|| KreuzbergError::UnsupportedFormat(mime_type.to_string())))
+}
+
+/// Run `extractor` on `source`, in a worker subprocess when `config.isolation` asks for it.
+#[cfg_attr(feature = "otel", tracing::instrument(
+    skip_all,
+    fields(pipeline.stage = "extraction", extractor.name = extractor.name(), extraction.mime_type = mime_type)
+))]
+async fn run_extractor(
+    extractor: &dyn DocumentExtractor,
+    source: &ExtractionSource<'_>,
+    mime_type: &str,
+    config: &ExtractionConfig,
+) -> Result<ExtractionResult> {
+    if config.isolation == IsolationMode::Subprocess {
+        #[cfg(all(feature = "tokio-runtime", not(target_arch = "wasm32")))]
+        if crate::core::isolation::isolates(config, extractor.name()) {
+            let file_content;
+            let content = match source {
+                ExtractionSource::File(path) => {
+                    file_content = crate::core::io::open_file_async(path, config.io.as_ref()).await?;
+                    &file_content
+                }
+                ExtractionSource::Bytes(content) => *content,
+            };
+            if !config.fallbacks.contains_key(mime_type) {
+                return crate::core::isolation::extract_in_worker(content, mime_type, config).await;
+            }
+            // Narrow the fallback chain to this extractor, which the worker runs.
+            let config = ExtractionConfig {
+                fallbacks: std::collections::HashMap::from([(
+                    mime_type.to_string(),
+                    vec![extractor.name().to_string()],
+                )]),
+                ..config.clone()
+            };
+            return crate::core::isolation::extract_in_worker(content, mime_type, &config).await;
+        }
+
+        #[cfg(not(all(feature = "tokio-runtime", not(target_arch = "wasm32"))))]
+        return Err(KreuzbergError::validation(
+            "Subprocess isolation requires the tokio-runtime feature and is not available on WebAssembly",
+        ));
+    }
+
+    let guard = config.plugin_guard.as_ref();
+    match source {
+        ExtractionSource::File(path) => {
+            guarded(
+                PluginKind::Extractor,
+                extractor.name(),
+                guard,
+                catch_extractor_panic(extractor.name(), extractor.extract_file(path, mime_type, config)),
+            )
+            .await
+        }
+        ExtractionSource::Bytes(content) => {
+            guarded(
+                PluginKind::Extractor,
+                extractor.name(),
+                guard,
+                catch_extractor_panic(extractor.name(), extractor.extract_bytes(content, mime_type, config)),
+            )
+            .await
+        }
+    }
+}
+
+/// Inspect the raw input with the built-in heuristics (when `config.security` is set) and
+/// the hooks that opt in, rejecting it when the security policy blocks a finding.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "inspection")))]
+async fn inspect_source(
+    source: &ExtractionSource<'_>,
+    mime_type: &str,
+    config: &ExtractionConfig,
+    request: Option<&ExtractionRequest>,
+    hooks: &[Arc<dyn PipelineHook>],
+) -> Result<Inspection> {
+    let inspecting: Vec<_> = match request {
+        Some(request) => hooks.iter().filter(|hook| hook.should_inspect(request)).collect(),
+        None => Vec::new(),
+    };
+    if config.security.is_none() && inspecting.is_empty() {
+        return Ok(Inspection::default());
+    }
+
+    let file_content;
+    let content = match source {
+        ExtractionSource::File(path) => {
+            #[cfg(feature = "tokio-runtime")]
+            {
+                file_content = crate::core::io::open_file_async(path, config.io.as_ref()).await?;
+            }
+            #[cfg(not(feature = "tokio-runtime"))]
+            {
+                file_content = crate::core::io::open_file_sync(path, config.io.as_ref())?;
+            }
+            &file_content
+        }
+        ExtractionSource::Bytes(content) => *content,
+    };
+
+    let mut inspection = match &config.security {
+        Some(security) => Inspection::run(security, content, mime_type),
+        None => Inspection::default(),
+    };
+    if let Some(request) = request {
+        for hook in inspecting {
+            inspection.findings.extend(hook.inspect_input(request, content).await?);
+        }
+    }
+    if let Some(security) = &config.security {
+        inspection.enforce(security)?;
+    }
+    Ok(inspection)
+}
+
+/// Inspect a legacy Office file before it is converted, since macros do not survive
+/// the conversion.
+#[cfg(feature = "office")]
+pub(in crate::core::extractor) fn inspect_original(
+    content: &[u8],
+    mime_type: &str,
+    config: &ExtractionConfig,
+) -> Result<Option<Inspection>> {
+    let Some(security) = &config.security else {
+        return Ok(None);
+    };
+    let inspection = Inspection::run(security, content, mime_type);
+    inspection.enforce(security)?;
+    Ok(Some(inspection))
 }
 
 #[cfg(feature = "office")]
@@ -18,7 +18,7 @@ use std::sync::Arc;
 ///
 /// RwLock read + HashMap lookup is ~100ns, fast enough without caching.
 /// Removed thread-local cache to avoid Tokio work-stealing scheduler issues.
-pub(in crate::core::extractor) fn get_extractor(mime_type: &str) -> Result<Arc<dyn DocumentExtractor>> {
+pub(crate) fn get_extractor(mime_type: &str) -> Result<Arc<dyn DocumentExtractor>> {
     let registry = crate::plugins::registry::get_document_extractor_registry();
     let registry_read = registry
         .read()
@@ -26,6 +26,28 @@ pub(in crate::core::extractor) fn get_extractor(mime_type: &str) -> Result<Arc<d
     registry_read.get(mime_type)
 }
 
+/// Entry of a fallback chain standing for the default extractor of the MIME type,
+/// run with `force_ocr`.
+pub(crate) const OCR_FULL_FALLBACK: &str = "ocr-full";
+
+/// Get the extractor named by an entry of a fallback chain for `mime_type`.
+pub(crate) fn get_fallback_extractor(entry: &str, mime_type: &str) -> Result<Arc<dyn DocumentExtractor>> {
+    if entry == OCR_FULL_FALLBACK {
+        return get_extractor(mime_type);
+    }
+    let registry = crate::plugins::registry::get_document_extractor_registry();
+    let registry_read = registry
+        .read()
+        .map_err(|e| KreuzbergError::Other(format!("Document extractor registry lock poisoned: {}", e)))?;
+    registry_read.get_by_name(entry).ok_or_else(|| KreuzbergError::Plugin {
+        message: format!(
+            "Extractor '{}' of the fallback chain for {} is not registered",
+            entry, mime_type
+        ),
+        plugin_name: entry.to_string(),
+    })
+}
+
 /// Get optimal pool sizing hint for a document.
 ///
 /// This function calculates recommended pool sizes based on the document's
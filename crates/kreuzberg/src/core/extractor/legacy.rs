@@ -54,7 +54,9 @@ pub(super) fn extract_bytes_sync_impl(
         ))
     })?;
 
-    let mut result = sync_extractor.extract_sync(content, &validated_mime, &cfg)?;
+    let mut result = crate::plugins::guard::catch_extractor_panic_sync(extractor.name(), || {
+        sync_extractor.extract_sync(content, &validated_mime, &cfg)
+    })?;
 
     result = crate::core::pipeline::run_pipeline_sync(result, &cfg)?;
 
@@ -0,0 +1,73 @@
+//! Metadata-only extraction.
+//!
+//! Reads the metadata of a file without extracting its text, for formats whose
+//! extractor can read the document properties on their own, and falls back to a full
+//! extraction for the others.
+
+use crate::Result;
+use crate::core::config::ExtractionConfig;
+use crate::core::pipeline::apply_metadata_mode;
+use crate::types::Metadata;
+use std::path::Path;
+
+use super::file::extract_file;
+use super::helpers::get_extractor;
+
+/// Extract the metadata of a file, skipping its text where the format allows it.
+///
+/// When the extractor for the file's MIME type implements
+/// [`DocumentExtractor::extract_metadata`](crate::plugins::DocumentExtractor::extract_metadata),
+/// only the document properties are read, and the post-processing pipeline does not
+/// run. Otherwise the file is fully extracted and the metadata of the result is
+/// returned. Either way, `config.metadata` selects which metadata is returned.
+///
+/// Metadata computed from the content, such as the word count of formats that do not
+/// declare one, or the page structure of formats that paginate while extracting the
+/// text, is only present when the file had to be fully extracted.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Io` if the file doesn't exist or cannot be read.
+/// Returns `KreuzbergError::UnsupportedFormat` if MIME type is not supported.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use kreuzberg::{ExtractionConfig, extract_metadata_only};
+///
+/// # async fn example() -> kreuzberg::Result<()> {
+/// let metadata = extract_metadata_only("report.docx", None, &ExtractionConfig::default()).await?;
+/// println!("Title: {:?}", metadata.title);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn extract_metadata_only(
+    path: impl AsRef<Path>,
+    mime_type: Option<&str>,
+    config: &ExtractionConfig,
+) -> Result<Metadata> {
+    use crate::core::{io, mime};
+
+    let path = path.as_ref();
+    io::validate_file_exists(path)?;
+    let detected_mime = mime::detect_or_validate(Some(path), mime_type)?;
+
+    crate::extractors::ensure_initialized()?;
+    // Legacy formats have no extractor of their own; they are converted by `extract_file`.
+    if let Ok(extractor) = get_extractor(&detected_mime) {
+        #[cfg(feature = "tokio-runtime")]
+        let content = io::open_file_async(path, config.io.as_ref()).await?;
+        #[cfg(not(feature = "tokio-runtime"))]
+        let content = io::open_file_sync(path, config.io.as_ref())?;
+
+        if let Some(mut metadata) = extractor.extract_metadata(&content, &detected_mime, config).await? {
+            apply_metadata_mode(&mut metadata, config.metadata);
+            if config.metadata.includes_basic() {
+                metadata.promote_common_fields();
+            }
+            return Ok(metadata);
+        }
+    }
+
+    Ok(extract_file(path, Some(&detected_mime), config).await?.metadata)
+}
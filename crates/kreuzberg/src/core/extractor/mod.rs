@@ -8,26 +8,33 @@
 //!
 //! - [`extract_file`] - Extract content from a file path
 //! - [`extract_bytes`] - Extract content from a byte array
+//! - [`extract_metadata_only`] - Extract the metadata of a file without its text
 //! - [`batch_extract_file`] - Extract content from multiple files concurrently
 //! - [`batch_extract_bytes`] - Extract content from multiple byte arrays concurrently
+//! - [`batch::start`] / [`batch::resume`] - Run a batch extraction that can resume after an interruption
+//! - [`batch::extract_files`] - Extract content from multiple files within a memory budget
 
 mod bytes;
 mod file;
 mod helpers;
 mod legacy;
+mod metadata;
 mod sync;
 
 #[cfg(feature = "tokio-runtime")]
-mod batch;
+pub mod batch;
 
 // Re-export public API
 pub use bytes::extract_bytes;
 pub use file::extract_file;
 pub use helpers::get_pool_sizing_hint;
+#[cfg(all(feature = "tokio-runtime", not(target_arch = "wasm32")))]
+pub(crate) use helpers::{get_extractor, get_fallback_extractor};
+pub use metadata::extract_metadata_only;
 pub use sync::{batch_extract_bytes_sync, extract_bytes_sync};
 
 #[cfg(feature = "tokio-runtime")]
-pub use sync::extract_file_sync;
+pub use sync::{extract_file_sync, extract_metadata_only_sync};
 
 #[cfg(feature = "tokio-runtime")]
 pub use batch::{batch_extract_bytes, batch_extract_file};
@@ -64,6 +71,43 @@ mod tests {
         assert_eq!(result.mime_type, "text/plain");
     }
 
+    #[tokio::test]
+    async fn test_extract_metadata_only_falls_back_to_full_extraction() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("test.txt");
+        std::fs::write(&file_path, b"Hello, metadata world!").unwrap();
+
+        let metadata = extract_metadata_only(&file_path, None, &ExtractionConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(metadata.word_count, Some(3));
+    }
+
+    #[cfg(feature = "office")]
+    #[tokio::test]
+    async fn test_extract_metadata_only_reads_docx_properties() {
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../test_documents/documents/word_sample.docx");
+        if !path.exists() {
+            return;
+        }
+
+        let metadata = extract_metadata_only(&path, None, &ExtractionConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(metadata.authors, Some(vec!["Christoph Auer".to_string()]));
+        assert_eq!(metadata.additional["custom_AssetID"], "TF10002040");
+        assert_eq!(metadata.word_count, Some(108));
+
+        let config = ExtractionConfig {
+            metadata: crate::core::config::MetadataMode::Basic,
+            ..Default::default()
+        };
+        let metadata = extract_metadata_only(&path, None, &config).await.unwrap();
+        assert_eq!(metadata.created_by.as_deref(), Some("Christoph Auer"));
+        assert!(metadata.additional.is_empty());
+    }
+
     #[tokio::test]
     async fn test_extract_file_with_mime_override() {
         let dir = tempdir().unwrap();
@@ -135,6 +179,369 @@ mod tests {
         assert_eq!(results.unwrap().len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_extract_files_within_memory_budget() {
+        let dir = tempdir().unwrap();
+        let small = dir.path().join("small.txt");
+        let large = dir.path().join("large.txt");
+        let missing = dir.path().join("missing.txt");
+        std::fs::write(&small, b"small").unwrap();
+        std::fs::write(&large, "large ".repeat(10_000)).unwrap();
+
+        // A budget below every estimate runs one input at a time; a large one runs all at once.
+        for budget in [batch::MemoryBudget::new(1), batch::MemoryBudget::new(u64::MAX)] {
+            let paths = [&large, &missing, &small];
+            let results = batch::extract_files(paths, &ExtractionConfig::default(), budget)
+                .await
+                .unwrap();
+            assert_eq!(results.len(), 3);
+            assert!(results[0].content.starts_with("large large"));
+            assert!(results[1].metadata.error.is_some());
+            assert_text_content(&results[2].content, "small");
+        }
+    }
+
+    #[test]
+    fn test_memory_budget_estimate() {
+        let budget = batch::MemoryBudget::new(1 << 30);
+        let path = std::path::Path::new;
+        assert!(budget.estimate(path("scan.TIFF"), 1 << 20) > budget.estimate(path("scan.pdf"), 1 << 20));
+        assert!(budget.estimate(path("scan.pdf"), 1 << 20) > budget.estimate(path("notes.txt"), 1 << 20));
+        assert!(budget.estimate(path("notes.txt"), 2 << 20) > budget.estimate(path("notes.txt"), 1 << 20));
+
+        let budget = budget.with_multiplier(1.0);
+        assert_eq!(
+            budget.estimate(path("scan.tiff"), 1 << 20),
+            budget.estimate(path("notes.txt"), 1 << 20)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_job_resume() {
+        let dir = tempdir().unwrap();
+        let manifest = dir.path().join("job.jsonl");
+        let output_dir = dir.path().join("results");
+
+        let file1 = dir.path().join("test1.txt");
+        let file2 = dir.path().join("test2.txt");
+        let missing = dir.path().join("missing.txt");
+        File::create(&file1).unwrap().write_all(b"content 1").unwrap();
+        File::create(&file2).unwrap().write_all(b"content 2").unwrap();
+
+        let config = ExtractionConfig::default();
+        let paths = vec![file1, file2, missing];
+        let summary = batch::start(paths.clone(), &config, &manifest, &output_dir)
+            .await
+            .unwrap();
+        assert_eq!(
+            summary,
+            batch::BatchJobSummary {
+                total: 3,
+                extracted: 2,
+                failed: 1,
+                skipped: 0
+            }
+        );
+        let result: crate::types::ExtractionResult =
+            serde_json::from_slice(&std::fs::read(output_dir.join("00000001.json")).unwrap()).unwrap();
+        assert_text_content(&result.content, "content 2");
+
+        let summary = batch::resume(&manifest).await.unwrap();
+        assert_eq!((summary.extracted, summary.failed, summary.skipped), (0, 0, 3));
+
+        let err = batch::start(paths, &config, &manifest, &output_dir).await.unwrap_err();
+        assert!(matches!(err, crate::KreuzbergError::Validation { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_batch_job_resume_after_interruption() {
+        let dir = tempdir().unwrap();
+        let manifest = dir.path().join("job.jsonl");
+        let output_dir = dir.path().join("results");
+
+        let file1 = dir.path().join("test1.txt");
+        let file2 = dir.path().join("test2.txt");
+        File::create(&file1).unwrap().write_all(b"content 1").unwrap();
+        File::create(&file2).unwrap().write_all(b"content 2").unwrap();
+
+        // One extraction at a time, so the manifest records the inputs in order.
+        let config = ExtractionConfig {
+            max_concurrent_extractions: Some(1),
+            ..Default::default()
+        };
+        batch::start(vec![file1, file2], &config, &manifest, &output_dir)
+            .await
+            .unwrap();
+
+        // Cut the manifest off in the middle of its last line, and damage the first result.
+        let contents = std::fs::read_to_string(&manifest).unwrap();
+        std::fs::write(&manifest, &contents[..contents.len() - 10]).unwrap();
+        std::fs::write(output_dir.join("00000000.json"), b"{}").unwrap();
+
+        let summary = batch::resume(&manifest).await.unwrap();
+        assert_eq!((summary.extracted, summary.failed, summary.skipped), (2, 0, 0));
+        let result: crate::types::ExtractionResult =
+            serde_json::from_slice(&std::fs::read(output_dir.join("00000000.json")).unwrap()).unwrap();
+        assert_text_content(&result.content, "content 1");
+
+        let summary = batch::resume(&manifest).await.unwrap();
+        assert_eq!(summary.skipped, 2);
+    }
+
+    #[cfg(feature = "compression")]
+    #[tokio::test]
+    async fn test_batch_job_compressed_results() {
+        let dir = tempdir().unwrap();
+        let manifest = dir.path().join("job.jsonl");
+        let output_dir = dir.path().join("results");
+        let file = dir.path().join("test.txt");
+        File::create(&file).unwrap().write_all(b"compressed content").unwrap();
+
+        let config = ExtractionConfig {
+            cache: Some(crate::CacheConfig {
+                compression: crate::Compression::Zstd,
+            }),
+            ..Default::default()
+        };
+        batch::start(vec![file], &config, &manifest, &output_dir).await.unwrap();
+
+        let written = std::fs::read(output_dir.join("00000000.json.zst")).unwrap();
+        let json = crate::compression::decompress(&written).unwrap();
+        let result = crate::types::ExtractionResult::from_versioned_json(std::str::from_utf8(&json).unwrap()).unwrap();
+        assert_text_content(&result.content, "compressed content");
+
+        let summary = batch::resume(&manifest).await.unwrap();
+        assert_eq!(summary.skipped, 1);
+    }
+
+    #[tokio::test]
+    async fn test_batch_job_provenance() {
+        let dir = tempdir().unwrap();
+        let manifest = dir.path().join("job.jsonl");
+        let output_dir = dir.path().join("results");
+        let file = dir.path().join("test.txt");
+        let missing = dir.path().join("missing.txt");
+        File::create(&file).unwrap().write_all(b"provenance content").unwrap();
+
+        let config = ExtractionConfig {
+            provenance: Some(crate::ProvenanceConfig::default()),
+            ..Default::default()
+        };
+        let summary = batch::start(vec![file, missing], &config, &manifest, &output_dir)
+            .await
+            .unwrap();
+        assert_eq!((summary.extracted, summary.failed), (1, 1));
+
+        let provenance: crate::provenance::ProvenanceManifest =
+            serde_json::from_slice(&std::fs::read(output_dir.join("00000000.provenance.json")).unwrap()).unwrap();
+        assert_eq!(provenance.input.size, 18);
+        assert_eq!(provenance.input.mime_type, "text/plain");
+        assert!(provenance.signature.is_none());
+        provenance
+            .check_result(&std::fs::read(output_dir.join("00000000.json")).unwrap())
+            .unwrap();
+        assert!(!output_dir.join("00000001.provenance.json").exists());
+    }
+
+    #[tokio::test]
+    async fn test_extract_bytes_processing_report() {
+        let content = b"First paragraph of the report.\n\nSecond paragraph, which is cut off.";
+        let plain = extract_bytes(content, "text/plain", &ExtractionConfig::default())
+            .await
+            .unwrap();
+        assert!(plain.processing_report().is_none());
+
+        let config = ExtractionConfig {
+            processing_report: true,
+            output: Some(crate::core::config::OutputConfig {
+                max_chars: Some(40),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let result = extract_bytes(content, "text/plain", &config).await.unwrap();
+        let report = result.processing_report().unwrap();
+
+        assert_eq!(report.mime_type, "text/plain");
+        assert_eq!(report.bytes_in, Some(content.len() as u64));
+        assert_eq!(report.bytes_out, result.content.len() as u64);
+        assert!(report.ocr.is_none());
+        assert_eq!(report.cache_hits, 0);
+
+        let extraction = &report.stages[0];
+        assert_eq!(extraction.name, "extraction");
+        assert!(extraction.plugin.is_some() && extraction.version.is_some());
+        assert_eq!(extraction.bytes_in, content.len() as u64);
+        let truncation = report.stages.iter().find(|stage| stage.name == "truncation").unwrap();
+        assert!(truncation.bytes_out < truncation.bytes_in);
+        assert!(!report.stages.iter().any(|stage| stage.name == "chunking"));
+        let total: f64 = report.stages.iter().map(|stage| stage.duration_ms).sum();
+        assert!(total <= report.duration_ms);
+    }
+
+    #[tokio::test]
+    async fn test_extract_bytes_fallback_chain() {
+        let content = b"# Fallback";
+        let config = ExtractionConfig {
+            processing_report: true,
+            fallbacks: [(
+                "text/plain".to_string(),
+                vec!["poppler".to_string(), "markdown-extractor".to_string()],
+            )]
+            .into(),
+            ..Default::default()
+        };
+        let result = extract_bytes(content, "text/plain", &config).await.unwrap();
+        let report = result.processing_report().unwrap();
+
+        assert_eq!(report.stages[0].plugin.as_deref(), Some("markdown-extractor"));
+        assert_eq!(report.fallbacks.len(), 2);
+        assert_eq!(report.fallbacks[0].extractor, "poppler");
+        assert!(report.fallbacks[0].error.as_ref().unwrap().contains("not registered"));
+        assert_eq!(report.fallbacks[1].extractor, "markdown-extractor");
+        assert!(report.fallbacks[1].error.is_none());
+
+        let config = ExtractionConfig {
+            fallbacks: [("text/plain".to_string(), vec!["poppler".to_string()])].into(),
+            ..Default::default()
+        };
+        let error = extract_bytes(content, "text/plain", &config).await.unwrap_err();
+        assert!(matches!(error, crate::KreuzbergError::Plugin { ref plugin_name, .. } if plugin_name == "poppler"));
+    }
+
+    struct PanickingExtractor;
+
+    impl crate::plugins::Plugin for PanickingExtractor {
+        fn name(&self) -> &str {
+            "panicking-extractor"
+        }
+
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+
+        fn initialize(&self) -> crate::Result<()> {
+            Ok(())
+        }
+
+        fn shutdown(&self) -> crate::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl crate::plugins::DocumentExtractor for PanickingExtractor {
+        async fn extract_bytes(
+            &self,
+            _content: &[u8],
+            _mime_type: &str,
+            _config: &ExtractionConfig,
+        ) -> crate::Result<crate::types::ExtractionResult> {
+            panic!("malformed document")
+        }
+
+        fn supported_mime_types(&self) -> &[&str] {
+            &["application/x-panicking"]
+        }
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_extract_bytes_fallback_chain_after_panic() {
+        crate::plugins::register_extractor(Arc::new(PanickingExtractor)).unwrap();
+        let config = ExtractionConfig {
+            processing_report: true,
+            fallbacks: [(
+                "text/plain".to_string(),
+                vec!["panicking-extractor".to_string(), "markdown-extractor".to_string()],
+            )]
+            .into(),
+            ..Default::default()
+        };
+        let result = extract_bytes(b"# Fallback", "text/plain", &config).await;
+
+        let config = ExtractionConfig {
+            fallbacks: [("text/plain".to_string(), vec!["panicking-extractor".to_string()])].into(),
+            ..Default::default()
+        };
+        let error = extract_bytes(b"# Fallback", "text/plain", &config).await.unwrap_err();
+        crate::plugins::unregister_extractor("panicking-extractor").unwrap();
+
+        let result = result.unwrap();
+        let report = result.processing_report().unwrap();
+        assert_eq!(report.stages[0].plugin.as_deref(), Some("markdown-extractor"));
+        assert!(
+            report.fallbacks[0]
+                .error
+                .as_ref()
+                .unwrap()
+                .contains("Extractor 'panicking-extractor' panicked: malformed document")
+        );
+        assert!(matches!(
+            error,
+            crate::KreuzbergError::ExtractorPanicked { ref name, .. } if name == "panicking-extractor"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_batch_retry_and_dead_letter() {
+        let dir = tempdir().unwrap();
+        let dead_letter = dir.path().join("failed.jsonl");
+        let missing = dir.path().join("missing.txt");
+        let config = ExtractionConfig {
+            retry: Some(crate::core::config::RetryConfig {
+                initial_backoff_ms: 1,
+                retry_on: vec![crate::ErrorClass::Validation, crate::ErrorClass::Io],
+                dead_letter: Some(dead_letter.clone()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let results = batch_extract_file(vec![missing.clone()], &config).await.unwrap();
+        assert!(results[0].metadata.error.is_some());
+        let results = batch_extract_bytes(vec![(b"data".to_vec(), "invalid/mime".to_string())], &config)
+            .await
+            .unwrap();
+        assert!(results[0].metadata.error.is_some());
+
+        let letters: Vec<serde_json::Value> = std::fs::read_to_string(&dead_letter)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(letters.len(), 2);
+        assert_eq!(letters[0]["path"], missing.to_str().unwrap());
+        assert_eq!(letters[0]["stage"], "input");
+        assert_eq!(letters[0]["attempts"], 3);
+        assert_eq!(letters[1]["index"], 0);
+        assert_eq!(letters[1]["error_type"], "unsupported_format");
+        assert_eq!(letters[1]["attempts"], 1);
+    }
+
+    #[tokio::test]
+    async fn test_extract_with_retries_recovers() {
+        let config = ExtractionConfig {
+            retry: Some(crate::core::config::RetryConfig {
+                initial_backoff_ms: 1,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let attempts = std::sync::atomic::AtomicU32::new(0);
+        let result = batch::extract_with_retries(&config, None, None, || async {
+            if attempts.fetch_add(1, std::sync::atomic::Ordering::SeqCst) < 2 {
+                Err(crate::KreuzbergError::ocr("backend unavailable"))
+            } else {
+                extract_bytes(b"recovered", "text/plain", &config).await
+            }
+        })
+        .await
+        .unwrap();
+        assert_text_content(&result.content, "recovered");
+        assert_eq!(attempts.into_inner(), 3);
+    }
+
     #[tokio::test]
     async fn test_batch_extract_bytes() {
         let config = ExtractionConfig::default();
@@ -267,6 +674,48 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_extract_file_non_utf8_filename() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join(std::ffi::OsStr::from_bytes(b"r\xe9sum\xe9.txt"));
+        std::fs::write(&file_path, b"content").unwrap();
+
+        let result = extract_file(&file_path, None, &ExtractionConfig::default())
+            .await
+            .unwrap();
+        assert_text_content(&result.content, "content");
+        assert_eq!(result.mime_type, "text/plain");
+    }
+
+    #[cfg(all(unix, feature = "office"))]
+    #[tokio::test]
+    async fn test_extract_office_file_non_utf8_filename() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let fixtures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../test_documents");
+        let dir = tempdir().unwrap();
+        let mut documents = vec!["presentations/simple.pptx"];
+        if cfg!(feature = "excel") {
+            documents.push("spreadsheets/test_01.xlsx");
+        }
+        for fixture in documents {
+            let source = fixtures.join(fixture);
+            if !source.exists() {
+                continue;
+            }
+            let mut name = b"caf\xe9.".to_vec();
+            name.extend_from_slice(source.extension().unwrap().as_bytes());
+            let file_path = dir.path().join(std::ffi::OsStr::from_bytes(&name));
+            std::fs::copy(&source, &file_path).unwrap();
+
+            let result = extract_file(&file_path, None, &ExtractionConfig::default()).await;
+            assert!(result.is_ok(), "{fixture}: {:?}", result.err());
+        }
+    }
+
     #[tokio::test]
     async fn test_extract_bytes_unsupported_mime() {
         let config = ExtractionConfig::default();
@@ -19,6 +19,10 @@ use super::batch::{batch_extract_bytes, batch_extract_file};
 use super::bytes::extract_bytes;
 #[cfg(feature = "tokio-runtime")]
 use super::file::extract_file;
+#[cfg(feature = "tokio-runtime")]
+use super::metadata::extract_metadata_only;
+#[cfg(feature = "tokio-runtime")]
+use crate::types::Metadata;
 
 /// Global Tokio runtime for synchronous operations.
 ///
@@ -76,6 +80,28 @@ pub fn extract_file_sync(
     GLOBAL_RUNTIME.block_on(extract_file(path, mime_type, config))
 }
 
+/// Synchronous wrapper for `extract_metadata_only`.
+///
+/// Blocks the current thread on the global Tokio runtime until the metadata is read.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use kreuzberg::{ExtractionConfig, extract_metadata_only_sync};
+///
+/// let metadata = extract_metadata_only_sync("report.docx", None, &ExtractionConfig::default())?;
+/// println!("Authors: {:?}", metadata.authors);
+/// # Ok::<(), kreuzberg::KreuzbergError>(())
+/// ```
+#[cfg(feature = "tokio-runtime")]
+pub fn extract_metadata_only_sync(
+    path: impl AsRef<Path>,
+    mime_type: Option<&str>,
+    config: &ExtractionConfig,
+) -> Result<Metadata> {
+    GLOBAL_RUNTIME.block_on(extract_metadata_only(path, mime_type, config))
+}
+
 /// Synchronous wrapper for `extract_bytes`.
 ///
 /// Uses the global Tokio runtime for 100x+ performance improvement over creating
@@ -200,6 +226,7 @@ pub fn batch_extract_bytes_sync(
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         }));
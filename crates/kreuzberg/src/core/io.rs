@@ -1,9 +1,18 @@
 //! File I/O utilities.
 //!
-//! This module provides async and sync file reading utilities with proper error handling.
-
+//! This module provides async and sync file reading utilities with proper error handling,
+//! including memory-mapped access to large input files, and a [`SpillBuffer`] that
+//! stages large intermediate text on disk, in a
+//! [`TempWorkspace`](crate::core::temp::TempWorkspace) when one is given.
+
+use crate::core::config::IoConfig;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::core::temp::TempWorkspace;
 use crate::{KreuzbergError, Result};
+use std::io::Read;
 use std::path::Path;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
 
 /// Read a file asynchronously.
 ///
@@ -40,6 +49,436 @@ pub fn read_file_sync(path: impl AsRef<Path>) -> Result<Vec<u8>> {
     std::fs::read(path.as_ref()).map_err(KreuzbergError::Io)
 }
 
+/// Contents of an input file, either read into memory or memory-mapped.
+///
+/// Dereferences to `&[u8]`, so it can be passed to any API taking a byte slice
+/// without copying.
+pub enum FileContent {
+    /// File read into an owned buffer
+    Owned(Vec<u8>),
+    /// Memory-mapped file
+    #[cfg(not(target_arch = "wasm32"))]
+    Mapped(memmap2::Mmap),
+}
+
+impl FileContent {
+    /// Whether the content is memory-mapped.
+    pub fn is_mapped(&self) -> bool {
+        match self {
+            FileContent::Owned(_) => false,
+            #[cfg(not(target_arch = "wasm32"))]
+            FileContent::Mapped(_) => true,
+        }
+    }
+
+    /// Convert into an owned buffer, copying if the content is memory-mapped.
+    pub fn into_vec(self) -> Vec<u8> {
+        match self {
+            FileContent::Owned(bytes) => bytes,
+            #[cfg(not(target_arch = "wasm32"))]
+            FileContent::Mapped(map) => map.to_vec(),
+        }
+    }
+}
+
+impl std::ops::Deref for FileContent {
+    type Target = [u8];
+
+    fn deref(&self) -> &[u8] {
+        match self {
+            FileContent::Owned(bytes) => bytes,
+            #[cfg(not(target_arch = "wasm32"))]
+            FileContent::Mapped(map) => map,
+        }
+    }
+}
+
+impl AsRef<[u8]> for FileContent {
+    fn as_ref(&self) -> &[u8] {
+        self
+    }
+}
+
+impl std::fmt::Debug for FileContent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FileContent")
+            .field("len", &self.len())
+            .field("mapped", &self.is_mapped())
+            .finish()
+    }
+}
+
+/// Open a file for extraction, memory-mapping it when it is at least
+/// `mmap_threshold_bytes` large.
+///
+/// Smaller files, and all files when `use_mmap` is disabled, are read into memory.
+/// Falls back to reading when mapping fails (e.g. on special files).
+///
+/// The mapping assumes the file is not modified while the content is alive; a
+/// concurrently truncated file may crash the process on access.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Io` for I/O errors (these always bubble up).
+pub fn open_file_sync(path: impl AsRef<Path>, config: Option<&IoConfig>) -> Result<FileContent> {
+    let path = path.as_ref();
+    let default_config = IoConfig::default();
+    let config = config.unwrap_or(&default_config);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let file = std::fs::File::open(path).map_err(KreuzbergError::Io)?;
+        let size = file.metadata().map_err(KreuzbergError::Io)?.len();
+
+        if config.should_mmap(size) {
+            // SAFETY: the mapping is read-only; callers must not modify the file while it is mapped.
+            #[allow(unsafe_code)]
+            let mapped = unsafe { memmap2::Mmap::map(&file) };
+            match mapped {
+                Ok(map) => return Ok(FileContent::Mapped(map)),
+                Err(e) => tracing::debug!("Memory-mapping {} failed, reading instead: {}", path.display(), e),
+            }
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    let _ = config;
+
+    read_file_sync(path).map(FileContent::Owned)
+}
+
+/// Async variant of [`open_file_sync`].
+///
+/// Mapping is done inline (it only sets up the mapping); files below the threshold
+/// are read with `tokio::fs`.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Io` for I/O errors (these always bubble up).
+#[cfg(feature = "tokio-runtime")]
+pub async fn open_file_async(path: impl AsRef<Path>, config: Option<&IoConfig>) -> Result<FileContent> {
+    let path = path.as_ref();
+    let default_config = IoConfig::default();
+    let config = config.unwrap_or(&default_config);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        let size = tokio::fs::metadata(path).await.map_err(KreuzbergError::Io)?.len();
+        if config.should_mmap(size) {
+            return open_file_sync(path, Some(config));
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    let _ = config;
+
+    read_file_async(path).await.map(FileContent::Owned)
+}
+
+/// Text assembled from parts, staged in a temporary file once it outgrows a threshold.
+///
+/// Parts are kept in memory while the buffer holds at most `threshold` bytes; the parts
+/// after that are written to an anonymous temporary file, which is removed when the
+/// buffer is dropped. [`into_string`](Self::into_string) assembles the text with one
+/// allocation of its final size, so a huge document is held in memory once rather than
+/// once per intermediate copy. On wasm32 everything is kept in memory.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::core::io::SpillBuffer;
+///
+/// let mut buffer = SpillBuffer::new(8);
+/// buffer.push_str("Files:\n")?;
+/// assert!(buffer.read_text("a large text file".as_bytes(), 17)?);
+/// assert!(!buffer.read_text(&[0xff, 0xfe][..], 2)?);
+/// assert_eq!(buffer.into_string()?, "Files:\na large text file");
+/// # Ok::<(), kreuzberg::KreuzbergError>(())
+/// ```
+#[derive(Debug)]
+pub struct SpillBuffer {
+    threshold: u64,
+    parts: Vec<SpillPart>,
+    len: u64,
+    #[cfg(not(target_arch = "wasm32"))]
+    file: Option<std::fs::File>,
+    #[cfg(not(target_arch = "wasm32"))]
+    workspace: Option<Arc<TempWorkspace>>,
+    spilled: u64,
+}
+
+#[derive(Debug)]
+enum SpillPart {
+    Memory(String),
+    Spilled { offset: u64, len: u64 },
+}
+
+/// State of a [`SpillBuffer`] that it can be rolled back to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpillCheckpoint {
+    parts: usize,
+    len: u64,
+    spilled: u64,
+}
+
+impl SpillBuffer {
+    /// An empty buffer that spills once it holds more than `threshold` bytes.
+    pub fn new(threshold: u64) -> Self {
+        Self {
+            threshold,
+            parts: Vec::new(),
+            len: 0,
+            #[cfg(not(target_arch = "wasm32"))]
+            file: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            workspace: None,
+            spilled: 0,
+        }
+    }
+
+    /// Stage the text in a file of `workspace`, counted against its quota, instead of
+    /// the system temporary directory.
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn in_workspace(mut self, workspace: Arc<TempWorkspace>) -> Self {
+        self.workspace = Some(workspace);
+        self
+    }
+
+    /// Bytes of text in the buffer.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Whether the buffer holds no text.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Bytes of text staged in the temporary file.
+    pub fn spilled_bytes(&self) -> u64 {
+        self.spilled
+    }
+
+    /// Bytes that can still be added before the buffer spills.
+    fn room(&self) -> u64 {
+        if cfg!(target_arch = "wasm32") {
+            u64::MAX
+        } else {
+            self.threshold.saturating_sub(self.len)
+        }
+    }
+
+    /// Append `text`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Io` if the text cannot be written to the temporary file.
+    pub fn push(&mut self, text: String) -> Result<()> {
+        if text.len() as u64 <= self.room() {
+            self.len += text.len() as u64;
+            self.parts.push(SpillPart::Memory(text));
+            return Ok(());
+        }
+        self.spill(text.as_bytes()).map(|_| ())
+    }
+
+    /// Append a copy of `text`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Io` if the text cannot be written to the temporary file.
+    pub fn push_str(&mut self, text: &str) -> Result<()> {
+        if text.len() as u64 <= self.room() {
+            return self.push(text.to_string());
+        }
+        self.spill(text.as_bytes()).map(|_| ())
+    }
+
+    /// Append the text read from `reader`, whose length is expected to be `size_hint`.
+    ///
+    /// Text that does not fit in memory is streamed to the temporary file, so it is
+    /// never read into memory as a whole. Returns `false`, leaving the buffer as it
+    /// was, if `reader` fails or its content is not valid UTF-8.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Io` if the text cannot be written to the temporary file
+    /// or exceeds the quota of the workspace.
+    pub fn read_text(&mut self, mut reader: impl Read, size_hint: u64) -> Result<bool> {
+        let room = self.room();
+        if size_hint > room {
+            return self.spill(reader);
+        }
+
+        let mut bytes = Vec::with_capacity(size_hint.min(10 * 1024 * 1024) as usize);
+        if (&mut reader)
+            .take(room.saturating_add(1))
+            .read_to_end(&mut bytes)
+            .is_err()
+        {
+            return Ok(false);
+        }
+        if bytes.len() as u64 > room {
+            // Longer than announced: stage what was read and the rest on disk.
+            return self.spill(std::io::Cursor::new(bytes).chain(reader));
+        }
+        match String::from_utf8(bytes) {
+            Ok(text) => self.push(text).map(|_| true),
+            Err(_) => Ok(false),
+        }
+    }
+
+    /// Stream the UTF-8 text of `reader` to the temporary file.
+    ///
+    /// Returns `false`, leaving the file as it was, if `reader` fails or its content is
+    /// not valid UTF-8.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn spill(&mut self, mut reader: impl Read) -> Result<bool> {
+        use std::io::{Seek, SeekFrom, Write};
+
+        let file = match &mut self.file {
+            Some(file) => file,
+            file => file.insert(match &self.workspace {
+                Some(workspace) => workspace.tempfile()?,
+                None => tempfile::tempfile().map_err(KreuzbergError::Io)?,
+            }),
+        };
+        let offset = self.spilled;
+
+        // Bytes at the start of `chunk` left over from an incomplete UTF-8 sequence.
+        let mut chunk = vec![0u8; 64 * 1024];
+        let mut carried = 0;
+        let mut written = 0u64;
+        let valid = loop {
+            let read = match reader.read(&mut chunk[carried..]) {
+                Ok(0) => break carried == 0,
+                Ok(read) => carried + read,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(_) => break false,
+            };
+            let complete = match std::str::from_utf8(&chunk[..read]) {
+                Ok(_) => read,
+                Err(e) if e.error_len().is_none() => e.valid_up_to(),
+                Err(_) => break false,
+            };
+            if let Some(workspace) = &self.workspace {
+                workspace.charge(complete as u64)?;
+            }
+            file.write_all(&chunk[..complete]).map_err(KreuzbergError::Io)?;
+            written += complete as u64;
+            chunk.copy_within(complete..read, 0);
+            carried = read - complete;
+        };
+
+        if !valid {
+            file.set_len(offset).map_err(KreuzbergError::Io)?;
+            file.seek(SeekFrom::Start(offset)).map_err(KreuzbergError::Io)?;
+            return Ok(false);
+        }
+        self.parts.push(SpillPart::Spilled { offset, len: written });
+        self.spilled += written;
+        self.len += written;
+        Ok(true)
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn spill(&mut self, mut reader: impl Read) -> Result<bool> {
+        let mut text = String::new();
+        if reader.read_to_string(&mut text).is_err() {
+            return Ok(false);
+        }
+        self.push(text).map(|_| true)
+    }
+
+    /// The current state of the buffer, for [`rollback`](Self::rollback).
+    pub fn checkpoint(&self) -> SpillCheckpoint {
+        SpillCheckpoint {
+            parts: self.parts.len(),
+            len: self.len,
+            spilled: self.spilled,
+        }
+    }
+
+    /// Remove the text appended since `checkpoint`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Io` if the temporary file cannot be truncated.
+    pub fn rollback(&mut self, checkpoint: SpillCheckpoint) -> Result<()> {
+        self.parts.truncate(checkpoint.parts);
+        self.len = checkpoint.len;
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(file) = &mut self.file
+            && self.spilled != checkpoint.spilled
+        {
+            use std::io::{Seek, SeekFrom};
+            file.set_len(checkpoint.spilled).map_err(KreuzbergError::Io)?;
+            file.seek(SeekFrom::Start(checkpoint.spilled))
+                .map_err(KreuzbergError::Io)?;
+        }
+        self.spilled = checkpoint.spilled;
+        Ok(())
+    }
+
+    /// Assemble the text.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Io` if the temporary file cannot be read.
+    pub fn into_string(self) -> Result<String> {
+        let mut text = String::with_capacity(self.len as usize);
+        #[cfg(not(target_arch = "wasm32"))]
+        let mut file = self.file;
+        for part in self.parts {
+            match part {
+                SpillPart::Memory(part) => text.push_str(&part),
+                #[cfg(not(target_arch = "wasm32"))]
+                SpillPart::Spilled { offset, len } => {
+                    use std::io::{Seek, SeekFrom};
+                    #[allow(clippy::unwrap_used)]
+                    let file = file.as_mut().unwrap();
+                    file.seek(SeekFrom::Start(offset)).map_err(KreuzbergError::Io)?;
+                    file.take(len).read_to_string(&mut text).map_err(KreuzbergError::Io)?;
+                }
+                #[cfg(target_arch = "wasm32")]
+                SpillPart::Spilled { .. } => {}
+            }
+        }
+        Ok(text)
+    }
+}
+
+/// Threshold and workspace of the [`SpillBuffer`]s of one extraction.
+#[cfg(any(feature = "archives", feature = "email"))]
+#[derive(Debug, Clone)]
+pub(crate) struct Spill {
+    threshold: u64,
+    #[cfg(not(target_arch = "wasm32"))]
+    workspace: Arc<TempWorkspace>,
+}
+
+#[cfg(any(feature = "archives", feature = "email"))]
+impl Spill {
+    /// Spill settings of an `IoConfig`, or the default ones.
+    pub(crate) fn new(config: Option<&IoConfig>) -> Self {
+        Self {
+            threshold: config.map_or(crate::core::config::io::DEFAULT_SPILL_THRESHOLD_BYTES, |io| {
+                io.spill_threshold_bytes
+            }),
+            #[cfg(not(target_arch = "wasm32"))]
+            workspace: Arc::new(TempWorkspace::new(config)),
+        }
+    }
+
+    /// An empty buffer with these settings.
+    pub(crate) fn buffer(&self) -> SpillBuffer {
+        let buffer = SpillBuffer::new(self.threshold);
+        #[cfg(not(target_arch = "wasm32"))]
+        let buffer = buffer.in_workspace(Arc::clone(&self.workspace));
+        buffer
+    }
+}
+
 /// Check if a file exists.
 ///
 /// # Arguments
@@ -72,6 +511,31 @@ pub fn validate_file_exists(path: impl AsRef<Path>) -> Result<()> {
     Ok(())
 }
 
+/// `path` without the `\\?\` prefix of Windows verbatim paths.
+///
+/// `std::fs::canonicalize` returns verbatim paths on Windows, which tools such as
+/// LibreOffice do not understand and which read badly in output. `\\?\C:\dir` becomes
+/// `C:\dir` and `\\?\UNC\server\share` becomes `\\server\share`. Other paths, and all
+/// paths on other platforms, are returned unchanged.
+pub fn strip_verbatim_prefix(path: &Path) -> std::borrow::Cow<'_, Path> {
+    #[cfg(windows)]
+    if let Some(stripped) = path.to_str().and_then(strip_verbatim) {
+        return std::borrow::Cow::Owned(stripped.into());
+    }
+    std::borrow::Cow::Borrowed(path)
+}
+
+#[cfg(any(windows, test))]
+fn strip_verbatim(path: &str) -> Option<String> {
+    if let Some(share) = path.strip_prefix(r"\\?\UNC\") {
+        return Some(format!(r"\\{share}"));
+    }
+    // Verbatim paths that are not on a drive, such as volume GUIDs, have no other form.
+    let rest = path.strip_prefix(r"\\?\")?;
+    let drive = rest.as_bytes();
+    (drive.len() >= 2 && drive[0].is_ascii_alphabetic() && drive[1] == b':').then(|| rest.to_string())
+}
+
 /// Traverse a directory and return all file paths matching a pattern.
 ///
 /// # Arguments
@@ -224,6 +688,35 @@ mod tests {
         assert!(validate_file_exists(dir.path().join("nonexistent.txt")).is_err());
     }
 
+    #[test]
+    fn test_strip_verbatim() {
+        assert_eq!(
+            strip_verbatim(r"\\?\C:\Users\docs\a.pdf").as_deref(),
+            Some(r"C:\Users\docs\a.pdf")
+        );
+        assert_eq!(
+            strip_verbatim(r"\\?\UNC\server\share\a.pdf").as_deref(),
+            Some(r"\\server\share\a.pdf")
+        );
+        assert_eq!(strip_verbatim(r"\\?\Volume{1234}\a.pdf"), None);
+        assert_eq!(strip_verbatim(r"C:\Users\docs\a.pdf"), None);
+        assert_eq!(strip_verbatim("/home/docs/a.pdf"), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_find_files_with_non_utf8_names() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let dir = tempdir().unwrap();
+        let name = std::ffi::OsStr::from_bytes(b"r\xe9sum\xe9.txt");
+        File::create(dir.path().join(name)).unwrap();
+
+        let files = find_files_by_extension(dir.path(), "txt", false).unwrap();
+        assert_eq!(files, [dir.path().join(name)]);
+        assert!(read_file_sync(&files[0]).is_ok());
+    }
+
     #[test]
     fn test_traverse_directory_non_recursive() {
         let dir = tempdir().unwrap();
@@ -326,4 +819,76 @@ mod tests {
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), KreuzbergError::Io(_)));
     }
+
+    #[test]
+    fn test_open_file_sync_maps_above_threshold() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("large.bin");
+        std::fs::write(&file_path, vec![7u8; 4096]).unwrap();
+
+        let mapped_config = IoConfig {
+            mmap_threshold_bytes: 1024,
+            ..Default::default()
+        };
+        let content = open_file_sync(&file_path, Some(&mapped_config)).unwrap();
+        assert!(content.is_mapped());
+        assert_eq!(content.len(), 4096);
+        assert!(content.iter().all(|&b| b == 7));
+
+        let content = open_file_sync(&file_path, None).unwrap();
+        assert!(!content.is_mapped());
+        assert_eq!(content.into_vec(), vec![7u8; 4096]);
+    }
+
+    #[cfg(feature = "tokio-runtime")]
+    #[tokio::test]
+    async fn test_open_file_async_empty_file_is_read() {
+        let dir = tempdir().unwrap();
+        let file_path = dir.path().join("empty.txt");
+        File::create(&file_path).unwrap();
+
+        let always = IoConfig {
+            mmap_threshold_bytes: 0,
+            ..Default::default()
+        };
+        let content = open_file_async(&file_path, Some(&always)).await.unwrap();
+        assert!(!content.is_mapped());
+        assert!(content.is_empty());
+    }
+
+    #[test]
+    fn test_spill_buffer_stages_text_above_threshold() {
+        let mut buffer = SpillBuffer::new(16);
+        buffer.push_str("header\n").unwrap();
+        assert_eq!(buffer.spilled_bytes(), 0);
+
+        let large = "é".repeat(40_000);
+        assert!(buffer.read_text(large.as_bytes(), large.len() as u64).unwrap());
+        // Announced as small, but longer than the room left.
+        assert!(buffer.read_text("more than sixteen bytes".as_bytes(), 4).unwrap());
+        buffer.push_str("\nfooter").unwrap();
+        assert_eq!(buffer.spilled_bytes() as usize, large.len() + 23 + 7);
+
+        let text = buffer.into_string().unwrap();
+        assert_eq!(text, format!("header\n{large}more than sixteen bytes\nfooter"));
+        assert_eq!(text.capacity(), text.len());
+    }
+
+    #[test]
+    fn test_spill_buffer_rejects_invalid_utf8() {
+        let mut buffer = SpillBuffer::new(4);
+        buffer.push_str("ok").unwrap();
+
+        let mut invalid = "x".repeat(100_000).into_bytes();
+        invalid.push(0xff);
+        assert!(!buffer.read_text(&invalid[..], invalid.len() as u64).unwrap());
+        assert!(!buffer.read_text(&[0xc3][..], 1).unwrap());
+        assert_eq!(buffer.len(), 2);
+
+        let checkpoint = buffer.checkpoint();
+        buffer.push_str("spilled text").unwrap();
+        buffer.rollback(checkpoint).unwrap();
+        buffer.push_str("!").unwrap();
+        assert_eq!(buffer.into_string().unwrap(), "ok!");
+    }
 }
@@ -0,0 +1,578 @@
+//! Worker subprocesses for extractors built on native libraries.
+//!
+//! With [`IsolationMode::Subprocess`](crate::core::config::IsolationMode), the PDF and
+//! image extractors run in worker subprocesses started as `<command> worker`, the hidden
+//! `worker` subcommand of the `kreuzberg` CLI, which calls [`run_worker`]. A worker that
+//! crashes, exceeds its memory limit or times out fails only the extraction it was
+//! running: it is killed and the next extraction starts a fresh worker. Healthy workers
+//! are kept for later extractions until they have run `worker.max_jobs` of them.
+//!
+//! The extractor alone runs in the worker; inspection, post-processing and the pipeline
+//! run in the calling process as usual. Plugins registered in the calling process are
+//! not available in the worker, which runs the built-in extractors and OCR backends.
+//!
+//! # Protocol
+//!
+//! Messages are frames of a big-endian `u64` length followed by that many bytes. The
+//! worker first sends a JSON handshake with its protocol version. Each request is then
+//! a JSON frame with the MIME type and configuration followed by a frame with the raw
+//! document, answered by a JSON frame with the extraction result or error.
+
+use crate::core::config::{ExtractionConfig, IsolationMode, WorkerConfig};
+use crate::types::ExtractionResult;
+use crate::{KreuzbergError, Result};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::io::{self, BufReader, BufWriter, Read, Write};
+use std::path::PathBuf;
+use std::process::{Child, ChildStdin, ChildStdout, Command, Stdio};
+use std::sync::Arc;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+
+/// Version of the worker protocol, checked when a worker starts.
+const PROTOCOL_VERSION: u32 = 1;
+
+/// Extractors that run in a worker with subprocess isolation.
+const ISOLATED_EXTRACTORS: &[&str] = &["pdf-extractor", "pdf-lopdf-extractor", "image-extractor"];
+
+/// Environment variable naming the worker executable when `worker.command` is not set.
+pub const WORKER_COMMAND_ENV: &str = "KREUZBERG_WORKER_COMMAND";
+
+/// Idle workers kept for later extractions.
+static IDLE_WORKERS: Lazy<Mutex<Vec<Worker>>> = Lazy::new(|| Mutex::new(Vec::new()));
+
+#[derive(Serialize, Deserialize)]
+struct Handshake {
+    version: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct Request {
+    mime_type: String,
+    config: ExtractionConfig,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum Response {
+    Ok { result: Box<ExtractionResult> },
+    Error { error: WorkerError },
+}
+
+/// A [`KreuzbergError`] sent from a worker.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum WorkerError {
+    Io { message: String },
+    Parsing { message: String },
+    Ocr { message: String },
+    Validation { message: String },
+    Security { message: String },
+    ImageProcessing { message: String },
+    MissingDependency { message: String },
+    Plugin { message: String, plugin_name: String },
+    ExtractorPanicked { name: String, payload: String },
+    UnsupportedFormat { message: String },
+    Other { message: String },
+}
+
+impl From<KreuzbergError> for WorkerError {
+    fn from(error: KreuzbergError) -> Self {
+        match error {
+            KreuzbergError::Io(e) => Self::Io { message: e.to_string() },
+            KreuzbergError::Parsing { message, .. } => Self::Parsing { message },
+            KreuzbergError::Ocr { message, .. } => Self::Ocr { message },
+            KreuzbergError::Validation { message, .. } => Self::Validation { message },
+            KreuzbergError::Security { message, .. } => Self::Security { message },
+            KreuzbergError::ImageProcessing { message, .. } => Self::ImageProcessing { message },
+            KreuzbergError::MissingDependency(message) => Self::MissingDependency { message },
+            KreuzbergError::Plugin { message, plugin_name } => Self::Plugin { message, plugin_name },
+            KreuzbergError::ExtractorPanicked { name, payload } => Self::ExtractorPanicked { name, payload },
+            KreuzbergError::UnsupportedFormat(message) => Self::UnsupportedFormat { message },
+            other => Self::Other {
+                message: other.to_string(),
+            },
+        }
+    }
+}
+
+impl From<WorkerError> for KreuzbergError {
+    fn from(error: WorkerError) -> Self {
+        match error {
+            WorkerError::Io { message } => KreuzbergError::Io(io::Error::other(message)),
+            WorkerError::Parsing { message } => KreuzbergError::parsing(message),
+            WorkerError::Ocr { message } => KreuzbergError::ocr(message),
+            WorkerError::Validation { message } => KreuzbergError::validation(message),
+            WorkerError::Security { message } => KreuzbergError::security(message),
+            WorkerError::ImageProcessing { message } => KreuzbergError::image_processing(message),
+            WorkerError::MissingDependency { message } => KreuzbergError::MissingDependency(message),
+            WorkerError::Plugin { message, plugin_name } => KreuzbergError::Plugin { message, plugin_name },
+            WorkerError::ExtractorPanicked { name, payload } => KreuzbergError::ExtractorPanicked { name, payload },
+            WorkerError::UnsupportedFormat { message } => KreuzbergError::UnsupportedFormat(message),
+            WorkerError::Other { message } => KreuzbergError::Other(message),
+        }
+    }
+}
+
+/// Whether `config` runs the extractor named `extractor` in a worker.
+pub(crate) fn isolates(config: &ExtractionConfig, extractor: &str) -> bool {
+    config.isolation == IsolationMode::Subprocess && ISOLATED_EXTRACTORS.contains(&extractor)
+}
+
+/// Run the extractor for `mime_type` on `content` in a worker subprocess.
+pub(crate) async fn extract_in_worker(
+    content: &[u8],
+    mime_type: &str,
+    config: &ExtractionConfig,
+) -> Result<ExtractionResult> {
+    let worker_config = config.worker.clone().unwrap_or_default();
+    let request = Request {
+        mime_type: mime_type.to_string(),
+        config: ExtractionConfig {
+            isolation: IsolationMode::InProcess,
+            ..config.clone()
+        },
+    };
+    let header = serde_json::to_vec(&request)?;
+    let content = content.to_vec();
+
+    tokio::task::spawn_blocking(move || {
+        let idle = {
+            let mut idle = IDLE_WORKERS.lock();
+            let position = idle.iter().position(|worker| worker.config == worker_config);
+            position.map(|position| idle.swap_remove(position))
+        };
+        let mut worker = match idle {
+            Some(worker) => worker,
+            None => Worker::start(worker_config)?,
+        };
+
+        let result = worker.extract(&header, &content, &request.mime_type);
+        if worker.healthy && worker.jobs < worker.config.max_jobs {
+            let mut idle = IDLE_WORKERS.lock();
+            if idle.len() < num_cpus::get() {
+                idle.push(worker);
+            }
+        }
+        result
+    })
+    .await
+    .map_err(|e| KreuzbergError::Other(format!("Extraction worker task failed: {}", e)))?
+}
+
+/// A running worker subprocess.
+struct Worker {
+    process: Arc<Mutex<Child>>,
+    stdin: BufWriter<ChildStdin>,
+    stdout: BufReader<ChildStdout>,
+    config: WorkerConfig,
+    command: PathBuf,
+    jobs: u32,
+    healthy: bool,
+}
+
+impl Worker {
+    /// Start a worker and wait for its handshake.
+    fn start(config: WorkerConfig) -> Result<Self> {
+        let command = config
+            .command
+            .clone()
+            .or_else(|| std::env::var_os(WORKER_COMMAND_ENV).map(PathBuf::from))
+            .unwrap_or_else(|| PathBuf::from("kreuzberg"));
+
+        let mut child = Command::new(&command)
+            .arg("worker")
+            .arg("--max-memory-mb")
+            .arg(config.max_memory_mb.to_string())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => KreuzbergError::MissingDependency(format!(
+                    "Extraction worker '{}' not found; install the kreuzberg CLI or set worker.command",
+                    command.display()
+                )),
+                _ => KreuzbergError::Io(e),
+            })?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(KreuzbergError::Other(
+                "Extraction worker has no stdio pipes".to_string(),
+            ));
+        };
+
+        let mut worker = Self {
+            process: Arc::new(Mutex::new(child)),
+            stdin: BufWriter::new(stdin),
+            stdout: BufReader::new(stdout),
+            config,
+            command,
+            jobs: 0,
+            healthy: true,
+        };
+        let handshake = read_frame(&mut worker.stdout)
+            .and_then(|frame| serde_json::from_slice::<Handshake>(&frame).map_err(io::Error::from));
+        match handshake {
+            Ok(handshake) if handshake.version == PROTOCOL_VERSION => Ok(worker),
+            Ok(handshake) => {
+                worker.stop();
+                Err(KreuzbergError::validation(format!(
+                    "Extraction worker '{}' speaks protocol version {}, expected {}",
+                    worker.command.display(),
+                    handshake.version,
+                    PROTOCOL_VERSION
+                )))
+            }
+            Err(_) => {
+                let ended = worker.stop();
+                Err(KreuzbergError::Other(format!(
+                    "Extraction worker '{}' {} before it was ready",
+                    worker.command.display(),
+                    ended
+                )))
+            }
+        }
+    }
+
+    /// Send one request and wait for its response, killing the worker when it times out.
+    fn extract(&mut self, header: &[u8], content: &[u8], mime_type: &str) -> Result<ExtractionResult> {
+        self.jobs += 1;
+
+        let (finished, watched) = mpsc::channel::<()>();
+        let watchdog = (self.config.timeout_secs > 0).then(|| {
+            let process = Arc::clone(&self.process);
+            let timeout = Duration::from_secs(self.config.timeout_secs);
+            thread::spawn(move || {
+                let expired = matches!(watched.recv_timeout(timeout), Err(RecvTimeoutError::Timeout));
+                if expired {
+                    let _ = process.lock().kill();
+                }
+                expired
+            })
+        });
+
+        let exchange = write_frame(&mut self.stdin, header)
+            .and_then(|()| write_frame(&mut self.stdin, content))
+            .and_then(|()| self.stdin.flush())
+            .and_then(|()| read_frame(&mut self.stdout))
+            .and_then(|frame| serde_json::from_slice::<Response>(&frame).map_err(io::Error::from));
+
+        drop(finished);
+        let timed_out = watchdog.is_some_and(|watchdog| watchdog.join().unwrap_or(false));
+
+        match exchange {
+            Ok(_) if timed_out => {
+                self.stop();
+                Err(self.timeout_error(mime_type))
+            }
+            Ok(Response::Ok { result }) => Ok(*result),
+            Ok(Response::Error { error }) => Err(error.into()),
+            Err(_) if timed_out => {
+                self.stop();
+                Err(self.timeout_error(mime_type))
+            }
+            Err(_) => Err(KreuzbergError::parsing(format!(
+                "Extraction worker {} while extracting {}",
+                self.stop(),
+                mime_type
+            ))),
+        }
+    }
+
+    fn timeout_error(&self, mime_type: &str) -> KreuzbergError {
+        KreuzbergError::parsing(format!(
+            "Extraction worker timed out after {} seconds while extracting {}",
+            self.config.timeout_secs, mime_type
+        ))
+    }
+
+    /// Kill the worker and describe how it ended.
+    fn stop(&mut self) -> String {
+        self.healthy = false;
+        let mut process = self.process.lock();
+        let _ = process.kill();
+        match process.wait() {
+            Ok(status) => format!("exited ({})", status),
+            Err(e) => format!("failed ({})", e),
+        }
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        if self.healthy {
+            self.stop();
+        }
+    }
+}
+
+/// Serve extraction requests on stdin and stdout until stdin is closed.
+///
+/// This is the entry point of worker subprocesses, run by the `kreuzberg worker`
+/// command. On Unix, the address space of the process is limited to `max_memory_mb`
+/// MiB (0 = unlimited), core dumps are disabled, and output native libraries write to
+/// stdout is sent to stderr so it cannot corrupt the protocol.
+pub fn run_worker(max_memory_mb: u64) -> Result<()> {
+    let output = protocol_output()?;
+    limit_resources(max_memory_mb);
+
+    let runtime = tokio::runtime::Builder::new_current_thread().enable_all().build()?;
+    serve(
+        &mut BufReader::new(io::stdin().lock()),
+        &mut BufWriter::new(output),
+        |content, mime_type, config| {
+            runtime.block_on(async {
+                crate::extractors::ensure_initialized()?;
+                // The parent narrows fallback chains to the extractor of the attempt.
+                let extractor = match config.fallbacks.get(mime_type).and_then(|chain| chain.first()) {
+                    Some(entry) => crate::core::extractor::get_fallback_extractor(entry, mime_type)?,
+                    None => crate::core::extractor::get_extractor(mime_type)?,
+                };
+                crate::plugins::guard::catch_extractor_panic(
+                    extractor.name(),
+                    extractor.extract_bytes(content, mime_type, config),
+                )
+                .await
+            })
+        },
+    )
+}
+
+/// Answer the requests read from `reader` with `extract`.
+fn serve(
+    reader: &mut impl Read,
+    writer: &mut impl Write,
+    mut extract: impl FnMut(&[u8], &str, &ExtractionConfig) -> Result<ExtractionResult>,
+) -> Result<()> {
+    write_frame(
+        writer,
+        &serde_json::to_vec(&Handshake {
+            version: PROTOCOL_VERSION,
+        })?,
+    )?;
+    writer.flush()?;
+
+    loop {
+        let header = match read_frame(reader) {
+            Ok(header) => header,
+            Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => return Ok(()),
+            Err(e) => return Err(e.into()),
+        };
+        let request: Request = serde_json::from_slice(&header)?;
+        let content = read_frame(reader)?;
+
+        let response = match extract(&content, &request.mime_type, &request.config) {
+            Ok(result) => Response::Ok {
+                result: Box::new(result),
+            },
+            Err(error) => Response::Error { error: error.into() },
+        };
+        write_frame(writer, &serde_json::to_vec(&response)?)?;
+        writer.flush()?;
+    }
+}
+
+fn write_frame(writer: &mut impl Write, payload: &[u8]) -> io::Result<()> {
+    writer.write_all(&(payload.len() as u64).to_be_bytes())?;
+    writer.write_all(payload)
+}
+
+fn read_frame(reader: &mut impl Read) -> io::Result<Vec<u8>> {
+    let mut length = [0u8; 8];
+    reader.read_exact(&mut length)?;
+    let length = u64::from_be_bytes(length);
+
+    let mut payload = Vec::new();
+    reader.take(length).read_to_end(&mut payload)?;
+    if (payload.len() as u64) < length {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(payload)
+}
+
+/// The stdout of the process, for protocol frames only: fd 1 is pointed at stderr.
+#[cfg(unix)]
+#[allow(unsafe_code)]
+fn protocol_output() -> Result<Box<dyn Write>> {
+    use std::os::fd::FromRawFd;
+
+    // SAFETY: `dup` returns a new descriptor that the returned `File` takes ownership of.
+    let descriptor = unsafe { libc::dup(libc::STDOUT_FILENO) };
+    if descriptor < 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+    let output = unsafe { std::fs::File::from_raw_fd(descriptor) };
+    // SAFETY: `dup2` only repoints fd 1; both descriptors stay open.
+    if unsafe { libc::dup2(libc::STDERR_FILENO, libc::STDOUT_FILENO) } < 0 {
+        return Err(io::Error::last_os_error().into());
+    }
+    Ok(Box::new(output))
+}
+
+#[cfg(not(unix))]
+fn protocol_output() -> Result<Box<dyn Write>> {
+    Ok(Box::new(io::stdout()))
+}
+
+#[cfg(unix)]
+#[allow(unsafe_code)]
+fn limit_resources(max_memory_mb: u64) {
+    let mut limits = vec![(libc::RLIMIT_CORE, 0)];
+    if max_memory_mb > 0 {
+        limits.push((libc::RLIMIT_AS, max_memory_mb.saturating_mul(1024 * 1024)));
+    }
+    for (resource, value) in limits {
+        let limit = libc::rlimit {
+            rlim_cur: value as libc::rlim_t,
+            rlim_max: value as libc::rlim_t,
+        };
+        // SAFETY: `setrlimit` only reads `limit`, which outlives the call.
+        if unsafe { libc::setrlimit(resource, &limit) } != 0 {
+            tracing::warn!("Failed to set worker resource limit: {}", io::Error::last_os_error());
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn limit_resources(max_memory_mb: u64) {
+    if max_memory_mb > 0 {
+        tracing::debug!("Worker memory limits are only enforced on Unix");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Metadata;
+    use std::io::Cursor;
+
+    fn request(mime_type: &str, content: &[u8]) -> Vec<u8> {
+        let mut input = Vec::new();
+        let header = Request {
+            mime_type: mime_type.to_string(),
+            config: ExtractionConfig::default(),
+        };
+        write_frame(&mut input, &serde_json::to_vec(&header).unwrap()).unwrap();
+        write_frame(&mut input, content).unwrap();
+        input
+    }
+
+    #[test]
+    fn test_serve_answers_requests() {
+        let mut input = request("text/plain", b"hello");
+        input.extend(request("application/pdf", b"broken"));
+
+        let mut output = Vec::new();
+        serve(&mut Cursor::new(input), &mut output, |content, mime_type, _| {
+            if mime_type == "application/pdf" {
+                return Err(KreuzbergError::parsing("Invalid PDF"));
+            }
+            Ok(ExtractionResult {
+                content: String::from_utf8_lossy(content).into_owned(),
+                mime_type: mime_type.to_string().into(),
+                metadata: Metadata::default(),
+                pages: None,
+                tables: vec![],
+                detected_languages: None,
+                chunks: None,
+                images: None,
+                elements: None,
+                djot_content: None,
+                scripts: None,
+            })
+        })
+        .unwrap();
+
+        let mut output = Cursor::new(output);
+        let handshake: Handshake = serde_json::from_slice(&read_frame(&mut output).unwrap()).unwrap();
+        assert_eq!(handshake.version, PROTOCOL_VERSION);
+
+        match serde_json::from_slice(&read_frame(&mut output).unwrap()).unwrap() {
+            Response::Ok { result } => {
+                assert_eq!(result.content, "hello");
+                assert_eq!(result.mime_type, "text/plain");
+            }
+            Response::Error { error } => panic!("unexpected error {:?}", error),
+        }
+        match serde_json::from_slice(&read_frame(&mut output).unwrap()).unwrap() {
+            Response::Error { error } => {
+                let error = KreuzbergError::from(error);
+                assert!(matches!(&error, KreuzbergError::Parsing { message, .. } if message == "Invalid PDF"));
+            }
+            Response::Ok { .. } => panic!("expected an error"),
+        }
+        assert!(read_frame(&mut output).is_err());
+    }
+
+    #[test]
+    fn test_read_frame_rejects_truncated_payload() {
+        let mut input = Vec::new();
+        write_frame(&mut input, b"payload").unwrap();
+        input.truncate(input.len() - 2);
+        let error = read_frame(&mut Cursor::new(input)).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn test_isolates_native_extractors_only() {
+        let config = ExtractionConfig {
+            isolation: IsolationMode::Subprocess,
+            ..Default::default()
+        };
+        assert!(isolates(&config, "pdf-extractor"));
+        assert!(isolates(&config, "image-extractor"));
+        assert!(!isolates(&config, "docx-extractor"));
+        assert!(!isolates(&ExtractionConfig::default(), "pdf-extractor"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_crashed_worker_fails_only_its_extraction() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // Completes the handshake, then dies from a segfault on the first request.
+        let dir = tempfile::tempdir().unwrap();
+        let script = dir.path().join("worker.sh");
+        std::fs::write(
+            &script,
+            "#!/bin/sh\nprintf '\\000\\000\\000\\000\\000\\000\\000\\015{\"version\":1}'\nhead -c 1 >/dev/null\nkill -SEGV $$\n",
+        )
+        .unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let config = ExtractionConfig {
+            isolation: IsolationMode::Subprocess,
+            worker: Some(WorkerConfig {
+                command: Some(script),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        for _ in 0..2 {
+            let error = extract_in_worker(b"%PDF-1.7\n", "application/pdf", &config)
+                .await
+                .unwrap_err();
+            assert!(
+                matches!(&error, KreuzbergError::Parsing { message, .. } if message.contains("SIGSEGV")),
+                "{error}"
+            );
+        }
+
+        let missing = ExtractionConfig {
+            worker: Some(WorkerConfig {
+                command: Some(dir.path().join("missing")),
+                ..Default::default()
+            }),
+            ..config
+        };
+        let error = extract_in_worker(b"%PDF-1.7\n", "application/pdf", &missing)
+            .await
+            .unwrap_err();
+        assert!(matches!(error, KreuzbergError::MissingDependency(_)));
+    }
+}
@@ -76,6 +76,10 @@ static EXT_TO_MIME: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| {
     m.insert("tiff", "image/tiff");
     m.insert("tif", "image/tiff");
     m.insert("webp", "image/webp");
+    m.insert("heic", "image/heic");
+    m.insert("heif", "image/heif");
+    m.insert("avif", "image/avif");
+    m.insert("jxl", "image/jxl");
     m.insert("jp2", "image/jp2");
     m.insert("jpx", "image/jpx");
     m.insert("jpm", "image/jpm");
@@ -125,12 +129,16 @@ static SUPPORTED_MIME_TYPES: Lazy<HashSet<&'static str>> = Lazy::new(|| {
     set.insert(MARKDOWN_MIME_TYPE);
     set.insert("text/x-markdown");
 
+    set.insert("image/avif");
     set.insert("image/bmp");
     set.insert("image/gif");
+    set.insert("image/heic");
+    set.insert("image/heif");
     set.insert("image/jp2");
     set.insert("image/jpeg");
     set.insert("image/jpm");
     set.insert("image/jpx");
+    set.insert("image/jxl");
     set.insert("image/mj2");
     set.insert("image/pjpeg");
     set.insert("image/png");
@@ -448,6 +456,9 @@ mod tests {
             ("test.bmp", "image/bmp"),
             ("test.webp", "image/webp"),
             ("test.tiff", "image/tiff"),
+            ("test.heic", "image/heic"),
+            ("test.avif", "image/avif"),
+            ("test.jxl", "image/jxl"),
         ];
 
         for (filename, expected_mime) in test_cases {
@@ -32,20 +32,32 @@
 pub(crate) mod batch_mode;
 #[cfg(feature = "tokio-runtime")]
 pub mod batch_optimizations;
+#[cfg(feature = "tokio-runtime")]
+pub mod batch_scheduler;
 pub mod config;
 pub mod config_validation;
 pub mod extractor;
 pub mod formats;
 pub mod io;
+#[cfg(all(feature = "tokio-runtime", not(target_arch = "wasm32")))]
+pub mod isolation;
 pub mod mime;
+#[cfg(any(feature = "html", feature = "xml"))]
+pub(crate) mod network;
 pub mod pipeline;
 pub mod server_config;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod temp;
+#[cfg(all(feature = "tokio-runtime", not(target_arch = "wasm32")))]
+pub mod thread_pool;
+pub mod walk;
 
 #[cfg(feature = "pdf")]
 pub use config::HierarchyConfig;
 pub use config::{
-    ChunkingConfig, EmbeddingConfig, EmbeddingModelType, ExtractionConfig, ImageExtractionConfig,
-    LanguageDetectionConfig, OcrConfig, OutputFormat, PageConfig, PostProcessorConfig, TokenReductionConfig,
+    ChunkingConfig, CleanupConfig, EmbeddingConfig, EmbeddingModelType, ExtractionConfig, ImageExtractionConfig,
+    IoConfig, LanguageDetectionConfig, OcrConfig, OcrPageContext, OcrPageOverrideCallback, OcrPageOverrides,
+    OutputFormat, PageConfig, PostProcessorConfig, TokenReductionConfig,
 };
 pub use config_validation::{
     validate_binarization_method, validate_chunking_params, validate_confidence, validate_dpi, validate_language_code,
@@ -53,12 +65,14 @@ pub use config_validation::{
     validate_token_reduction_level,
 };
 pub use formats::{KNOWN_FORMATS, is_valid_format_field};
-pub use server_config::ServerConfig;
+pub use server_config::{ApiQuota, ApiTenant, JobsConfig, ServerConfig};
 
 #[cfg(feature = "tokio-runtime")]
 pub use batch_optimizations::{BatchProcessor, BatchProcessorConfig};
+#[cfg(feature = "tokio-runtime")]
+pub use batch_scheduler::{BatchJob, BatchScheduler, BatchSchedulerConfig, JobHandle, RateLimit, TenantConfig};
 #[cfg(feature = "pdf")]
-pub use config::PdfConfig;
+pub use config::{PdfConfig, RubyMode};
 #[cfg(feature = "tokio-runtime")]
 pub use extractor::{batch_extract_bytes, batch_extract_file};
 pub use extractor::{extract_bytes, extract_file};
@@ -0,0 +1,458 @@
+//! Remote resource policy.
+//!
+//! Extractors never fetch the remote resources a document references on their own.
+//! They report them with [`record_references`], which applies the
+//! [`NetworkConfig`] policy of the extraction and records every resource with its
+//! outcome in `metadata.additional["remote_resources"]`:
+//!
+//! - only `http` and `https` URLs can be allowed; `file:`, `ftp:` and other schemes are
+//!   always blocked
+//! - external DTDs and entities are always blocked, since resolving them is how XXE
+//!   attacks read local files and reach internal services
+//! - loopback, private, link-local and other non-public addresses are always blocked,
+//!   both as literal hosts and, when fetching, as the addresses a host name resolves to
+//! - everything else is blocked unless `allow_remote` is set, and must match
+//!   `allowed_hosts` when that list is not empty
+//!
+//! With the `remote-resources` feature and image extraction enabled, allowed remote
+//! images are fetched after extraction (without following redirects, up to
+//! `max_fetch_bytes`) and added to the result's images.
+//!
+//! When network access is denied (`network = "deny"` or `KREUZBERG_NETWORK=deny`),
+//! nothing is allowed or fetched.
+
+use crate::core::config::NetworkConfig;
+use crate::types::{Metadata, RemoteResource, RemoteResourceKind, RemoteResourceStatus};
+use std::borrow::Cow;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+/// Schemes of references that do not leave the document.
+const LOCAL_SCHEMES: &[&str] = &["data", "javascript", "mailto", "tel", "cid", "mid", "about", "blob"];
+
+/// Whether `url` refers to a resource outside the document: an absolute URL with a
+/// network or file scheme, or a protocol-relative URL.
+pub(crate) fn is_remote_url(url: &str) -> bool {
+    let url = url.trim();
+    if url.starts_with("//") || url.starts_with("\\\\") {
+        return true;
+    }
+    match url.split_once(':') {
+        // Single letters are Windows drive letters rather than schemes.
+        Some((scheme, _)) if scheme.len() > 1 => {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+                && !LOCAL_SCHEMES.contains(&scheme.to_ascii_lowercase().as_str())
+        }
+        _ => false,
+    }
+}
+
+/// Scheme, host and port of a URL.
+#[derive(Debug, PartialEq, Eq)]
+struct Origin {
+    scheme: String,
+    host: String,
+    port: Option<u16>,
+}
+
+fn parse_origin(url: &str) -> Option<Origin> {
+    let url = url.trim();
+    let (scheme, rest) = match url.strip_prefix("//") {
+        Some(rest) => ("https".to_string(), rest),
+        None => {
+            let (scheme, rest) = url.split_once(':')?;
+            let rest = rest.strip_prefix("//").or_else(|| rest.strip_prefix("\\\\"))?;
+            (scheme.to_ascii_lowercase(), rest)
+        }
+    };
+
+    // Browsers and URL parsers treat `\` like `/` in http(s) URLs.
+    let authority = rest.split(['/', '\\', '?', '#']).next()?;
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, host)| host);
+    let (host, port) = match authority.strip_prefix('[') {
+        Some(ipv6) => {
+            let (host, rest) = ipv6.split_once(']')?;
+            (host, rest.strip_prefix(':'))
+        }
+        None => match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        },
+    };
+    let port = match port.filter(|port| !port.is_empty()) {
+        Some(port) => Some(port.parse().ok()?),
+        None => None,
+    };
+    if host.is_empty() {
+        return None;
+    }
+    Some(Origin {
+        scheme,
+        host: host.trim_end_matches('.').to_ascii_lowercase(),
+        port,
+    })
+}
+
+/// Check `url` against the policy `config`, returning why it may not be fetched.
+pub(crate) fn check_url(config: &NetworkConfig, url: &str) -> Result<(), String> {
+    let origin = parse_origin(url).ok_or_else(|| "Not a valid URL".to_string())?;
+    if origin.scheme != "http" && origin.scheme != "https" {
+        return Err(format!("The {} scheme is never fetched", origin.scheme));
+    }
+    if origin.host == "localhost" || origin.host.ends_with(".localhost") {
+        return Err("Local hosts are never fetched".to_string());
+    }
+    if let Ok(ip) = origin.host.parse::<IpAddr>()
+        && !is_public(ip)
+    {
+        return Err(format!("{ip} is not a public address"));
+    }
+    if config.deny || NetworkConfig::denied_by_environment() {
+        return Err("Network access is denied".to_string());
+    }
+    if !config.allow_remote {
+        return Err("Remote resources are disabled by the network policy".to_string());
+    }
+    if !config.allows_host(&origin.host) {
+        return Err(format!("{} is not in network.allowed_hosts", origin.host));
+    }
+    Ok(())
+}
+
+/// Whether `ip` is a public unicast address.
+pub(crate) fn is_public(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_v4(ip),
+        IpAddr::V6(ip) => match ip.to_ipv4_mapped() {
+            Some(ip) => is_public_v4(ip),
+            None => is_public_v6(ip),
+        },
+    }
+}
+
+fn is_public_v4(ip: Ipv4Addr) -> bool {
+    let [a, b, c, _] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_broadcast()
+        || ip.is_documentation()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || a == 0
+        || a >= 240
+        || (a == 100 && (64..128).contains(&b))
+        || (a == 192 && b == 0 && c == 0)
+        || (a == 198 && (18..20).contains(&b)))
+}
+
+fn is_public_v6(ip: Ipv6Addr) -> bool {
+    let first = ip.segments()[0];
+    !(ip.is_loopback()
+        || ip.is_unspecified()
+        || ip.is_multicast()
+        || first & 0xfe00 == 0xfc00
+        || first & 0xffc0 == 0xfe80
+        || first == 0x2001 && ip.segments()[1] == 0x0db8)
+}
+
+/// Apply the network policy to the remote resources a document references and record
+/// them in `metadata.additional["remote_resources"]`.
+///
+/// References that are not remote are ignored; repeated references are recorded once.
+pub(crate) fn record_references(
+    metadata: &mut Metadata,
+    references: impl IntoIterator<Item = (RemoteResourceKind, String)>,
+    config: Option<&NetworkConfig>,
+) {
+    let default_config = NetworkConfig::default();
+    let config = config.unwrap_or(&default_config);
+
+    let mut resources: Vec<RemoteResource> = Vec::new();
+    for (kind, url) in references {
+        let url = url.trim().to_string();
+        if !is_remote_url(&url)
+            || resources
+                .iter()
+                .any(|resource| resource.url == url && resource.kind == kind)
+        {
+            continue;
+        }
+        let verdict = match kind {
+            RemoteResourceKind::Entity => Err("External entities are never resolved".to_string()),
+            _ => check_url(config, &url),
+        };
+        let (status, message) = match verdict {
+            Ok(()) => (RemoteResourceStatus::Allowed, None),
+            Err(message) => (RemoteResourceStatus::Blocked, Some(message)),
+        };
+        resources.push(RemoteResource {
+            url,
+            kind,
+            status,
+            message,
+        });
+    }
+
+    if !resources.is_empty() {
+        metadata
+            .additional
+            .insert(Cow::Borrowed("remote_resources"), serde_json::json!(resources));
+    }
+}
+
+/// Fetch the allowed remote images of `result` into `result.images`, when image
+/// extraction is enabled.
+#[cfg(feature = "remote-resources")]
+pub(crate) async fn fetch_remote_images(
+    result: &mut crate::types::ExtractionResult,
+    config: &crate::core::config::ExtractionConfig,
+) {
+    use crate::types::ExtractedImage;
+
+    let Some(network) = config.network.as_ref().filter(|network| network.allow_remote) else {
+        return;
+    };
+    if config.denies_network() {
+        return;
+    }
+    if !config.images.as_ref().is_some_and(|images| images.extract_images) {
+        return;
+    }
+    let Some(mut resources) = result
+        .metadata
+        .additional
+        .get("remote_resources")
+        .and_then(|resources| serde_json::from_value::<Vec<RemoteResource>>(resources.clone()).ok())
+    else {
+        return;
+    };
+
+    for resource in resources.iter_mut().filter(|resource| {
+        resource.kind == RemoteResourceKind::Image && resource.status == RemoteResourceStatus::Allowed
+    }) {
+        match fetch(network, &resource.url).await {
+            Ok((data, content_type)) => {
+                let images = result.images.get_or_insert_with(Vec::new);
+                images.push(ExtractedImage {
+                    format: Cow::Owned(image_format(&data, content_type.as_deref())),
+                    data: data.into(),
+                    image_index: images.len(),
+                    page_number: None,
+                    width: None,
+                    height: None,
+                    colorspace: None,
+                    bits_per_component: None,
+                    is_mask: false,
+                    description: Some(resource.url.clone()),
+                    ocr_result: None,
+                });
+                resource.status = RemoteResourceStatus::Fetched;
+            }
+            Err(message) => {
+                resource.status = RemoteResourceStatus::Failed;
+                resource.message = Some(message);
+            }
+        }
+    }
+
+    result
+        .metadata
+        .additional
+        .insert(Cow::Borrowed("remote_resources"), serde_json::json!(resources));
+}
+
+/// Fetch `url` under the policy `config`, returning the body and its content type.
+///
+/// The host is resolved once, every resolved address must be public, and the request
+/// is pinned to the checked address so the host cannot resolve differently for the
+/// connection. Redirects are not followed.
+#[cfg(feature = "remote-resources")]
+async fn fetch(config: &NetworkConfig, url: &str) -> Result<(Vec<u8>, Option<String>), String> {
+    let url = if url.starts_with("//") {
+        format!("https:{url}")
+    } else {
+        url.to_string()
+    };
+    let parsed = reqwest::Url::parse(&url).map_err(|e| format!("Not a valid URL: {e}"))?;
+    check_url(config, parsed.as_str())?;
+    let host = parsed.host_str().ok_or_else(|| "URL has no host".to_string())?;
+    let port = parsed
+        .port_or_known_default()
+        .ok_or_else(|| "URL has no port".to_string())?;
+
+    let addresses: Vec<_> = tokio::net::lookup_host((host.trim_matches(['[', ']']), port))
+        .await
+        .map_err(|e| format!("Failed to resolve {host}: {e}"))?
+        .collect();
+    if let Some(address) = addresses.iter().find(|address| !is_public(address.ip())) {
+        return Err(format!(
+            "{host} resolves to {}, which is not a public address",
+            address.ip()
+        ));
+    }
+    let address = *addresses
+        .first()
+        .ok_or_else(|| format!("{host} does not resolve to any address"))?;
+
+    let client = reqwest::Client::builder()
+        .redirect(reqwest::redirect::Policy::none())
+        .timeout(std::time::Duration::from_secs(config.timeout_secs))
+        .resolve(host, address)
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+    let mut response = client
+        .get(parsed)
+        .send()
+        .await
+        .and_then(reqwest::Response::error_for_status)
+        .map_err(|e| format!("Failed to fetch: {e}"))?;
+
+    if response
+        .content_length()
+        .is_some_and(|length| length > config.max_fetch_bytes)
+    {
+        return Err(format!(
+            "Response exceeds network.max_fetch_bytes ({} bytes)",
+            config.max_fetch_bytes
+        ));
+    }
+    let content_type = response
+        .headers()
+        .get(reqwest::header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response.chunk().await.map_err(|e| format!("Failed to fetch: {e}"))? {
+        if (body.len() + chunk.len()) as u64 > config.max_fetch_bytes {
+            return Err(format!(
+                "Response exceeds network.max_fetch_bytes ({} bytes)",
+                config.max_fetch_bytes
+            ));
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok((body, content_type))
+}
+
+/// Image format of fetched image data, from its signature or content type.
+#[cfg(feature = "remote-resources")]
+fn image_format(data: &[u8], content_type: Option<&str>) -> String {
+    let format = if data.starts_with(b"\x89PNG") {
+        "png"
+    } else if data.starts_with(b"\xFF\xD8\xFF") {
+        "jpeg"
+    } else if data.starts_with(b"GIF8") {
+        "gif"
+    } else if data.starts_with(b"RIFF") && data.get(8..12) == Some(b"WEBP") {
+        "webp"
+    } else if data.starts_with(b"BM") {
+        "bmp"
+    } else {
+        return content_type
+            .and_then(|content_type| content_type.split(';').next())
+            .and_then(|mime| mime.trim().strip_prefix("image/"))
+            .map(|subtype| subtype.trim_end_matches("+xml").to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+    };
+    format.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allow_all() -> NetworkConfig {
+        NetworkConfig {
+            allow_remote: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_is_remote_url() {
+        assert!(is_remote_url("https://example.com/a.png"));
+        assert!(is_remote_url("//example.com/a.png"));
+        assert!(is_remote_url("file:///etc/passwd"));
+        assert!(is_remote_url(" FTP://example.com/a"));
+        assert!(!is_remote_url("images/a.png"));
+        assert!(!is_remote_url("/images/a.png"));
+        assert!(!is_remote_url("#section"));
+        assert!(!is_remote_url("data:image/png;base64,AAAA"));
+        assert!(!is_remote_url("mailto:someone@example.com"));
+        assert!(!is_remote_url("C:\\images\\a.png"));
+    }
+
+    #[test]
+    fn test_check_url_denies_by_default() {
+        let config = NetworkConfig::default();
+        assert!(check_url(&config, "https://example.com/a.png").is_err());
+        assert!(check_url(&allow_all(), "https://example.com/a.png").is_ok());
+        assert!(check_url(&allow_all(), "//example.com/a.png").is_ok());
+    }
+
+    #[test]
+    fn test_check_url_blocks_internal_targets() {
+        let config = allow_all();
+        for url in [
+            "http://127.0.0.1/",
+            "http://localhost:8080/admin",
+            "http://api.localhost/",
+            "http://169.254.169.254/latest/meta-data/",
+            "http://10.0.0.5/",
+            "http://192.168.1.1/",
+            "http://100.64.0.1/",
+            "http://0.0.0.0/",
+            "http://[::1]/",
+            "http://[fd00::1]/",
+            "http://[::ffff:127.0.0.1]/",
+            "http://user@127.0.0.1:80/",
+            "file:///etc/passwd",
+            "gopher://example.com/",
+        ] {
+            assert!(check_url(&config, url).is_err(), "{url} should be blocked");
+        }
+        assert!(check_url(&config, "http://93.184.216.34/").is_ok());
+    }
+
+    #[test]
+    fn test_check_url_allowed_hosts() {
+        let config = NetworkConfig {
+            allowed_hosts: vec!["cdn.example.com".to_string()],
+            ..allow_all()
+        };
+        assert!(check_url(&config, "https://CDN.example.com:443/a.png").is_ok());
+        assert!(check_url(&config, "https://cdn.example.com.evil.net/a.png").is_err());
+        assert!(check_url(&config, "https://cdn.example.com@evil.net/a.png").is_err());
+        assert!(check_url(&config, "https://evil.net\\@cdn.example.com/a.png").is_err());
+    }
+
+    #[test]
+    fn test_record_references() {
+        let mut metadata = Metadata::default();
+        record_references(
+            &mut metadata,
+            [
+                (RemoteResourceKind::Image, "https://example.com/a.png".to_string()),
+                (RemoteResourceKind::Image, "https://example.com/a.png".to_string()),
+                (RemoteResourceKind::Image, "local.png".to_string()),
+                (RemoteResourceKind::Entity, "https://example.com/x.dtd".to_string()),
+            ],
+            Some(&allow_all()),
+        );
+
+        let resources: Vec<RemoteResource> =
+            serde_json::from_value(metadata.additional["remote_resources"].clone()).unwrap();
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[0].status, RemoteResourceStatus::Allowed);
+        assert_eq!(resources[1].kind, RemoteResourceKind::Entity);
+        assert_eq!(resources[1].status, RemoteResourceStatus::Blocked);
+
+        let mut metadata = Metadata::default();
+        record_references(&mut metadata, [(RemoteResourceKind::Image, "a.png".to_string())], None);
+        assert!(!metadata.additional.contains_key("remote_resources"));
+    }
+}
@@ -0,0 +1,70 @@
+//! Deterministic output normalization.
+//!
+//! Applied as the last pipeline step when `ExtractionConfig::determinism` is set.
+
+use crate::types::ExtractionResult;
+use ahash::{AHashMap, RandomState};
+
+/// Fixed seeds so that maps with the same keys iterate in the same order in every process.
+const HASH_SEEDS: (u64, u64, u64, u64) = (
+    0x6b72_6575_7a62_6572,
+    0x6764_6574_6572_6d69,
+    0x6e69_7374_6963_6f75,
+    0x7470_7574_5f6d_6f64,
+);
+
+/// Rebuild `metadata.additional` with a fixed hash seed, inserting keys in sorted order.
+pub(super) fn apply_determinism(result: &mut ExtractionResult) {
+    let (k0, k1, k2, k3) = HASH_SEEDS;
+    let mut entries: Vec<_> = std::mem::take(&mut result.metadata.additional).into_iter().collect();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let mut additional = AHashMap::with_capacity_and_hasher(entries.len(), RandomState::with_seeds(k0, k1, k2, k3));
+    additional.extend(entries);
+    result.metadata.additional = additional;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Metadata;
+    use std::borrow::Cow;
+
+    fn result_with_keys(keys: &[&'static str]) -> ExtractionResult {
+        let mut metadata = Metadata::default();
+        for key in keys {
+            metadata
+                .additional
+                .insert(Cow::Borrowed(*key), serde_json::json!(key.len()));
+        }
+        ExtractionResult {
+            content: String::new(),
+            mime_type: Cow::Borrowed("text/plain"),
+            metadata,
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            scripts: None,
+            pages: None,
+            elements: None,
+        }
+    }
+
+    #[test]
+    fn test_iteration_order_is_independent_of_insertion_order() {
+        let mut a = result_with_keys(&["zeta", "alpha", "mid", "beta", "omega"]);
+        let mut b = result_with_keys(&["omega", "beta", "mid", "alpha", "zeta"]);
+        apply_determinism(&mut a);
+        apply_determinism(&mut b);
+
+        let keys_a: Vec<_> = a.metadata.additional.keys().cloned().collect();
+        let keys_b: Vec<_> = b.metadata.additional.keys().cloned().collect();
+        assert_eq!(keys_a, keys_b);
+        assert_eq!(
+            serde_json::to_string(&a.metadata).unwrap(),
+            serde_json::to_string(&b.metadata).unwrap()
+        );
+    }
+}
@@ -1,15 +1,18 @@
 //! Core processor execution logic.
 //!
-//! This module handles the execution of post-processors and validators
-//! in the correct order.
+//! This module handles the execution of post-processors, chunk processors,
+//! validators and translators in the correct order.
 
-use crate::core::config::ExtractionConfig;
+use super::report::ReportRecorder;
+use crate::core::config::{ExtractionConfig, TranslationConfig};
 use crate::plugins::ProcessingStage;
+use crate::plugins::guard::{PluginKind, disabled_for, guarded, skipped_warning};
 use crate::types::ExtractionResult;
 use crate::{KreuzbergError, Result};
 use std::borrow::Cow;
 
 /// Execute all registered post-processors by stage.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "post_processors")))]
 pub(super) async fn execute_processors(
     result: &mut ExtractionResult,
     config: &ExtractionConfig,
@@ -17,7 +20,11 @@ pub(super) async fn execute_processors(
     early_processors: std::sync::Arc<Vec<std::sync::Arc<dyn crate::plugins::PostProcessor>>>,
     middle_processors: std::sync::Arc<Vec<std::sync::Arc<dyn crate::plugins::PostProcessor>>>,
     late_processors: std::sync::Arc<Vec<std::sync::Arc<dyn crate::plugins::PostProcessor>>>,
+    report: &mut ReportRecorder,
 ) -> Result<()> {
+    let guard = config.plugin_guard.as_ref();
+    let mut skipped = Vec::new();
+
     for (_stage, processors_arc) in [
         (ProcessingStage::Early, early_processors),
         (ProcessingStage::Middle, middle_processors),
@@ -29,7 +36,22 @@ pub(super) async fn execute_processors(
             let should_run = should_processor_run(pp_config, processor_name);
 
             if should_run && processor.should_process(result, config) {
-                match processor.process(result, config).await {
+                if guard.is_some()
+                    && let Some(remaining) = disabled_for(PluginKind::PostProcessor, processor_name)
+                {
+                    skipped.push(skipped_warning(PluginKind::PostProcessor, processor_name, remaining));
+                    continue;
+                }
+
+                report.mark(result);
+                match guarded(
+                    PluginKind::PostProcessor,
+                    processor_name,
+                    guard,
+                    processor.process(result, config),
+                )
+                .await
+                {
                     Ok(_) => {}
                     Err(err @ KreuzbergError::Io(_))
                     | Err(err @ KreuzbergError::LockPoisoned(_))
@@ -43,14 +65,88 @@ pub(super) async fn execute_processors(
                         );
                     }
                 }
+                report.plugin_stage("post_processor", &**processor, result);
             }
         }
     }
+
+    record_plugin_warnings(result, skipped);
     Ok(())
 }
 
+/// Execute all registered chunk processors on the chunks, if there are any.
+///
+/// The chunks are taken out of the result while they are processed, so that
+/// processors can read the rest of the document. Errors are handled like those of
+/// post-processors.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "chunk_processors")))]
+pub(super) async fn execute_chunk_processors(
+    result: &mut ExtractionResult,
+    config: &ExtractionConfig,
+    report: &mut ReportRecorder,
+) -> Result<()> {
+    if result.chunks.as_ref().is_none_or(|chunks| chunks.is_empty()) {
+        return Ok(());
+    }
+    let processors = crate::plugins::registered_chunk_processors()?;
+    if processors.is_empty() {
+        return Ok(());
+    }
+
+    let pp_config = config.postprocessor.as_ref();
+    let guard = config.plugin_guard.as_ref();
+    let mut skipped = Vec::new();
+    let mut chunks = result.chunks.take().unwrap_or_default();
+    let mut outcome = Ok(());
+
+    for processor in processors {
+        let name = processor.name();
+        if !should_processor_run(&pp_config, name) || !processor.should_process(result, config) {
+            continue;
+        }
+        if guard.is_some()
+            && let Some(remaining) = disabled_for(PluginKind::ChunkProcessor, name)
+        {
+            skipped.push(skipped_warning(PluginKind::ChunkProcessor, name, remaining));
+            continue;
+        }
+
+        report.mark(result);
+        match guarded(
+            PluginKind::ChunkProcessor,
+            name,
+            guard,
+            processor.process_chunks(&mut chunks, result, config),
+        )
+        .await
+        {
+            Ok(()) => {}
+            Err(err @ KreuzbergError::Io(_))
+            | Err(err @ KreuzbergError::LockPoisoned(_))
+            | Err(err @ KreuzbergError::Plugin { .. }) => {
+                outcome = Err(err);
+                break;
+            }
+            Err(err) => {
+                result.metadata.additional.insert(
+                    Cow::Owned(format!("processing_error_{name}")),
+                    serde_json::Value::String(err.to_string()),
+                );
+            }
+        }
+        report.plugin_stage("chunk_processor", &*processor, result);
+    }
+
+    result.chunks = Some(chunks);
+    record_plugin_warnings(result, skipped);
+    outcome
+}
+
 /// Determine if a processor should run based on configuration.
-fn should_processor_run(pp_config: &Option<&crate::core::config::PostProcessorConfig>, processor_name: &str) -> bool {
+pub(super) fn should_processor_run(
+    pp_config: &Option<&crate::core::config::PostProcessorConfig>,
+    processor_name: &str,
+) -> bool {
     if let Some(config) = pp_config {
         if let Some(ref enabled_set) = config.enabled_set {
             enabled_set.contains(processor_name)
@@ -68,22 +164,146 @@ fn should_processor_run(pp_config: &Option<&crate::core::config::PostProcessorCo
     }
 }
 
-/// Execute all registered validators.
-pub(super) async fn execute_validators(result: &ExtractionResult, config: &ExtractionConfig) -> Result<()> {
+/// Execute the built-in validators enabled by the configuration, then all registered validators.
+///
+/// With `config.plugin_guard` set, validators disabled after repeated failures are skipped
+/// and reported in `metadata.additional["plugin_warnings"]`.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "validators")))]
+pub(super) async fn execute_validators(
+    result: &mut ExtractionResult,
+    config: &ExtractionConfig,
+    report: &mut ReportRecorder,
+) -> Result<()> {
     let validator_registry = crate::plugins::registry::get_validator_registry();
-    let validators = {
+    let mut validators = crate::plugins::configured_validators(config);
+    {
         let registry = validator_registry
             .read()
             .map_err(|e| crate::KreuzbergError::Other(format!("Validator registry lock poisoned: {}", e)))?;
-        registry.get_all()
-    };
+        validators.extend(registry.get_all());
+    }
 
-    if !validators.is_empty() {
-        for validator in validators {
-            if validator.should_validate(result, config) {
-                validator.validate(result, config).await?;
-            }
+    let guard = config.plugin_guard.as_ref();
+    let mut skipped = Vec::new();
+    for validator in validators {
+        if !validator.should_validate(result, config) {
+            continue;
+        }
+        if guard.is_some()
+            && let Some(remaining) = disabled_for(PluginKind::Validator, validator.name())
+        {
+            skipped.push(skipped_warning(PluginKind::Validator, validator.name(), remaining));
+            continue;
         }
+        report.mark(result);
+        guarded(
+            PluginKind::Validator,
+            validator.name(),
+            guard,
+            validator.validate(result, config),
+        )
+        .await?;
+        report.plugin_stage("validator", &*validator, result);
+    }
+
+    record_plugin_warnings(result, skipped);
+    Ok(())
+}
+
+/// Translate the content or chunks with the configured translator, if translation is set.
+///
+/// Errors, including a missing translator, are recorded in
+/// `metadata.additional["translation_error"]` instead of failing the extraction.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "translation")))]
+pub(super) async fn execute_translation(result: &mut ExtractionResult, config: &ExtractionConfig) {
+    let Some(translation) = &config.translation else {
+        return;
+    };
+    if result.content.trim().is_empty() {
+        return;
+    }
+
+    let (key, value) = match translate(result, config, translation).await {
+        Ok(entry) => ("translation", entry),
+        Err(e) => ("translation_error", serde_json::Value::String(e.to_string())),
+    };
+    result.metadata.additional.insert(Cow::Borrowed(key), value);
+}
+
+/// Translate in one call to the translator and build the `translation` metadata entry.
+async fn translate(
+    result: &ExtractionResult,
+    config: &ExtractionConfig,
+    translation: &TranslationConfig,
+) -> Result<serde_json::Value> {
+    let translator = crate::plugins::resolve_translator(translation.translator.as_deref())?;
+    let source_language = result
+        .detected_languages
+        .as_ref()
+        .and_then(|languages| languages.first())
+        .map(String::as_str);
+
+    let document = translation.scope.document();
+    let chunks = result.chunks.as_ref().filter(|_| translation.scope.chunks());
+    let mut texts = Vec::new();
+    if document {
+        texts.push(result.content.clone());
+    }
+    texts.extend(chunks.into_iter().flatten().map(|chunk| chunk.content.clone()));
+
+    let mut translated = guarded(
+        PluginKind::Translator,
+        translator.name(),
+        config.plugin_guard.as_ref(),
+        translator.translate(&texts, source_language, &translation.target_language),
+    )
+    .await?;
+    if translated.len() != texts.len() {
+        return Err(KreuzbergError::Plugin {
+            message: format!(
+                "Translator '{}' returned {} translations for {} texts",
+                translator.name(),
+                translated.len(),
+                texts.len()
+            ),
+            plugin_name: translator.name().to_string(),
+        });
+    }
+
+    let mut entry = serde_json::json!({
+        "language": translation.target_language,
+        "source_language": source_language,
+        "translator": translator.name(),
+    });
+    if document {
+        entry["content"] = serde_json::Value::String(translated.remove(0));
+    }
+    if chunks.is_some() {
+        entry["chunks"] = serde_json::json!(translated);
+    }
+    Ok(entry)
+}
+
+/// Append `warnings` about skipped plugins to `metadata.additional["plugin_warnings"]`.
+fn record_plugin_warnings(result: &mut ExtractionResult, warnings: Vec<String>) {
+    if warnings.is_empty() {
+        return;
+    }
+    let entry = result
+        .metadata
+        .additional
+        .entry(Cow::Borrowed("plugin_warnings"))
+        .or_insert_with(|| serde_json::json!([]));
+    if let Some(list) = entry.as_array_mut() {
+        list.extend(warnings.into_iter().map(serde_json::Value::String));
+    }
+}
+
+/// Execute the `before_chunk` stage of all registered pipeline hooks.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "before_chunk_hooks")))]
+pub(super) async fn execute_before_chunk_hooks(result: &mut ExtractionResult, config: &ExtractionConfig) -> Result<()> {
+    for hook in crate::plugins::registered_pipeline_hooks()? {
+        hook.before_chunk(result, config).await?;
     }
 
     Ok(())
@@ -4,11 +4,12 @@
 //! embedding generation, and language detection.
 
 use crate::Result;
-use crate::core::config::ExtractionConfig;
-use crate::types::ExtractionResult;
+use crate::core::config::{ExtractionConfig, MetadataMode};
+use crate::types::{ExtractionResult, Metadata, PageBoundary};
 use std::borrow::Cow;
 
 /// Execute chunking if configured.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "chunking")))]
 pub(super) fn execute_chunking(result: &mut ExtractionResult, config: &ExtractionConfig) -> Result<()> {
     #[cfg(feature = "chunking")]
     if let Some(ref chunking_config) = config.chunking {
@@ -73,11 +74,163 @@ pub(super) fn execute_chunking(result: &mut ExtractionResult, config: &Extractio
     Ok(())
 }
 
-/// Execute language detection if configured.
+/// Apply the output normalization profile if configured.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "normalization")))]
+pub(super) fn execute_normalization(result: &mut ExtractionResult, config: &ExtractionConfig) {
+    if let Some(output) = config.output.as_ref() {
+        crate::text::normalization::normalize_result(result, &output.profile.options());
+    }
+}
+
+/// Truncate the content to `output.max_chars` if configured.
+///
+/// Runs after the common metadata is filled, so word counts describe the whole
+/// document, and before chunking, so chunks only cover the truncated content.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "truncation")))]
+pub(super) fn execute_truncation(result: &mut ExtractionResult, config: &ExtractionConfig) {
+    if let Some(max_chars) = config.output.as_ref().and_then(|output| output.max_chars) {
+        crate::text::truncation::truncate_result(result, max_chars);
+    }
+}
+
+/// Normalize dates, times, amounts and numbers if configured.
+///
+/// Runs after truncation, so the metadata spans match the returned content, and before
+/// chunking, so inline annotations end up in the chunks. The language detected by the
+/// early language detection post-processor serves as the locale hint.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "value_normalization")))]
+pub(super) fn execute_value_normalization(result: &mut ExtractionResult, config: &ExtractionConfig) {
+    if let Some(value_normalization) = &config.value_normalization {
+        crate::text::values::normalize_values(result, value_normalization);
+    }
+}
+
+/// Keep only the pages selected by `config.pages.selection`.
+///
+/// Paginated extractors skip the pages outside the selection themselves; this covers
+/// the extractors that paginate without honoring it, and the parts of a result that
+/// are extracted for the whole document. The content is rebuilt from the page
+/// boundaries, so it is kept whole when there are none. Page content, page metadata,
+/// tables and images are filtered by their original page number.
+pub(super) fn apply_page_selection(result: &mut ExtractionResult, config: &ExtractionConfig) {
+    let Some(page_config) = config.pages.as_ref().filter(|pages| pages.selection.is_some()) else {
+        return;
+    };
+    let selected = |page_number: usize| page_config.includes_page(page_number);
+
+    if let Some(structure) = result.metadata.pages.as_mut() {
+        if let Some(boundaries) = structure.boundaries.as_mut()
+            && boundaries.iter().any(|boundary| !selected(boundary.page_number))
+        {
+            let mut content = String::new();
+            let mut kept = Vec::new();
+            for boundary in boundaries.iter().filter(|boundary| selected(boundary.page_number)) {
+                let Some(page_text) = result.content.get(boundary.byte_start..boundary.byte_end) else {
+                    continue;
+                };
+                if !kept.is_empty() {
+                    content.push_str("\n\n");
+                }
+                let byte_start = content.len();
+                content.push_str(page_text);
+                kept.push(PageBoundary {
+                    byte_start,
+                    byte_end: content.len(),
+                    page_number: boundary.page_number,
+                });
+            }
+            result.content = content;
+            *boundaries = kept;
+        }
+        if let Some(pages) = structure.pages.as_mut() {
+            pages.retain(|page| selected(page.number));
+        }
+    }
+
+    if let Some(pages) = result.pages.as_mut() {
+        pages.retain(|page| selected(page.page_number));
+    }
+    result.tables.retain(|table| selected(table.page_number));
+    if let Some(images) = result.images.as_mut() {
+        images.retain(|image| image.page_number.is_none_or(selected));
+    }
+}
+
+/// Drop the extracted metadata that `mode` does not ask for.
+///
+/// Runs on the extractor's metadata, before post-processors and features add theirs.
+/// The page structure and batch error metadata are always kept.
+pub(crate) fn apply_metadata_mode(metadata: &mut Metadata, mode: MetadataMode) {
+    match mode {
+        MetadataMode::Full => {}
+        MetadataMode::Basic => {
+            metadata.promote_common_fields();
+            metadata.format = None;
+            metadata.image_preprocessing = None;
+            metadata.json_schema = None;
+            metadata.additional.clear();
+        }
+        MetadataMode::None => {
+            *metadata = Metadata {
+                pages: metadata.pages.take(),
+                error: metadata.error.take(),
+                ..Default::default()
+            };
+        }
+    }
+}
+
+/// Fill the common metadata fields.
+///
+/// Common fields recorded in `additional` by extractors and post-processors are moved
+/// to the typed fields. Page and word counts not declared by the document are taken
+/// from the page structure and the content. Nothing is filled when metadata is
+/// disabled with [`MetadataMode::None`].
+pub(super) fn populate_common_metadata(result: &mut ExtractionResult, mode: MetadataMode) {
+    if !mode.includes_basic() {
+        return;
+    }
+    let metadata = &mut result.metadata;
+    metadata.promote_common_fields();
+
+    if metadata.page_count.is_none() {
+        metadata.page_count = metadata.pages.as_ref().map(|pages| pages.total_count);
+    }
+    if metadata.word_count.is_none() {
+        metadata.word_count = Some(result.content.split_whitespace().count());
+    }
+}
+
+/// Count tokens and estimate costs if configured.
+///
+/// Chunks without a token count (for example when no embedding tokenizer ran)
+/// get one from the configured tokenizer.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "token_counting")))]
+pub(super) fn execute_token_counting(result: &mut ExtractionResult, config: &ExtractionConfig) -> Result<()> {
+    let Some(ref token_config) = config.token_count else {
+        return Ok(());
+    };
+
+    if let Some(ref mut chunks) = result.chunks {
+        for chunk in chunks.iter_mut().filter(|chunk| chunk.metadata.token_count.is_none()) {
+            chunk.metadata.token_count = Some(token_config.count_tokens(&chunk.content));
+        }
+    }
+
+    let usage = crate::text::token_count::TokenUsage::measure(result, token_config);
+    result.metadata.additional.insert(
+        Cow::Borrowed(crate::text::token_count::TOKEN_USAGE_KEY),
+        serde_json::to_value(usage)?,
+    );
+
+    Ok(())
+}
+
+/// Execute language detection with the registered detector if configured.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "language_detection")))]
 pub(super) fn execute_language_detection(result: &mut ExtractionResult, config: &ExtractionConfig) -> Result<()> {
-    #[cfg(feature = "language-detection")]
     if let Some(ref lang_config) = config.language_detection {
-        match crate::language_detection::detect_languages(&result.content, lang_config) {
+        match crate::plugins::detect_languages_with_registered(&result.content, lang_config) {
             Ok(detected) => {
                 result.detected_languages = detected;
             }
@@ -90,13 +243,47 @@ pub(super) fn execute_language_detection(result: &mut ExtractionResult, config:
         }
     }
 
-    #[cfg(not(feature = "language-detection"))]
-    if config.language_detection.is_some() {
+    Ok(())
+}
+
+/// Record the writing scripts of the content and of image OCR results if configured.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "script_detection")))]
+pub(super) fn execute_script_detection(result: &mut ExtractionResult, config: &ExtractionConfig) {
+    if !config.script_detection {
+        return;
+    }
+
+    crate::text::script::annotate_scripts(&result.content, &mut result.metadata);
+    for ocr_result in result
+        .images
+        .iter_mut()
+        .flatten()
+        .filter_map(|image| image.ocr_result.as_deref_mut())
+    {
+        crate::text::script::annotate_scripts(&ocr_result.content, &mut ocr_result.metadata);
+    }
+}
+
+/// Estimate the OCR error rate of the content and pages by spellchecking, if configured.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "spellcheck")))]
+pub(super) fn execute_spellcheck(result: &mut ExtractionResult, config: &ExtractionConfig) -> Result<()> {
+    if let Some(spellcheck) = &config.spellcheck {
+        let report = crate::text::spellcheck::spellcheck(result, spellcheck)?;
         result.metadata.additional.insert(
-            Cow::Borrowed("language_detection_error"),
-            serde_json::Value::String("Language detection feature not enabled".to_string()),
+            Cow::Borrowed(crate::text::spellcheck::SPELLCHECK_KEY),
+            serde_json::to_value(report)?,
         );
     }
-
     Ok(())
 }
+
+/// Store a Latin transliteration of the content if configured.
+///
+/// Runs after content filters and truncation, so the transliteration matches the
+/// returned content.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "transliteration")))]
+pub(super) fn execute_transliteration(result: &mut ExtractionResult, config: &ExtractionConfig) {
+    if let Some(transliteration) = &config.transliteration {
+        crate::text::transliteration::annotate_transliteration(&result.content, transliteration, &mut result.metadata);
+    }
+}
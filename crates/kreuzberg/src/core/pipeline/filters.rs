@@ -0,0 +1,97 @@
+//! Content filter execution.
+//!
+//! Runs the built-in filters from `ExtractionConfig::content_filter` followed by
+//! registered [`ContentFilter`] plugins.
+
+use crate::Result;
+use crate::core::config::ExtractionConfig;
+use crate::plugins::ContentFilter;
+use crate::types::{ExtractionResult, Table};
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// Built-in filters enabled by the configuration, then registered filters by priority.
+pub(super) fn active_content_filters(config: &ExtractionConfig) -> Result<Vec<Arc<dyn ContentFilter>>> {
+    let mut filters = match config.content_filter.as_ref() {
+        Some(filter_config) => crate::plugins::configured_filters(filter_config)?,
+        None => Vec::new(),
+    };
+    filters.extend(crate::plugins::registered_content_filters()?);
+    Ok(filters)
+}
+
+/// Filter the content, per-page content and tables of a result.
+///
+/// The names of filters that changed any text are recorded in
+/// `metadata.additional["content_filters"]`.
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "content_filters")))]
+pub(super) fn execute_content_filters(
+    result: &mut ExtractionResult,
+    config: &ExtractionConfig,
+    filters: &[Arc<dyn ContentFilter>],
+) -> Result<()> {
+    let mut applied = Vec::new();
+
+    for filter in filters {
+        let filter = filter.as_ref();
+        let mut changed = apply_filter(filter, &mut result.content, config)?;
+        for table in &mut result.tables {
+            changed |= filter_table(filter, table, config)?;
+        }
+        if let Some(pages) = result.pages.as_mut() {
+            for page in pages {
+                changed |= apply_filter(filter, &mut page.content, config)?;
+                for table in &mut page.tables {
+                    changed |= filter_table(filter, Arc::make_mut(table), config)?;
+                }
+            }
+        }
+        if changed {
+            applied.push(filter.name().to_string());
+        }
+    }
+
+    if !applied.is_empty() {
+        result
+            .metadata
+            .additional
+            .insert(Cow::Borrowed("content_filters"), serde_json::json!(applied));
+    }
+    Ok(())
+}
+
+/// Pass every chunk to [`ContentFilter::filter_chunk`].
+#[cfg_attr(feature = "otel", tracing::instrument(skip_all, fields(pipeline.stage = "chunk_filters")))]
+pub(super) fn execute_chunk_filters(
+    result: &mut ExtractionResult,
+    config: &ExtractionConfig,
+    filters: &[Arc<dyn ContentFilter>],
+) -> Result<()> {
+    let Some(chunks) = result.chunks.as_mut() else {
+        return Ok(());
+    };
+    for filter in filters {
+        for chunk in chunks.iter_mut() {
+            filter.filter_chunk(chunk, config)?;
+        }
+    }
+    Ok(())
+}
+
+fn filter_table(filter: &dyn ContentFilter, table: &mut Table, config: &ExtractionConfig) -> Result<bool> {
+    let mut changed = apply_filter(filter, &mut table.markdown, config)?;
+    for cell in table.cells.iter_mut().flatten() {
+        changed |= apply_filter(filter, cell, config)?;
+    }
+    Ok(changed)
+}
+
+fn apply_filter(filter: &dyn ContentFilter, text: &mut String, config: &ExtractionConfig) -> Result<bool> {
+    match filter.filter(text, config)? {
+        Cow::Owned(filtered) if filtered != *text => {
+            *text = filtered;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
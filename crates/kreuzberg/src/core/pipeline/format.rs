@@ -137,6 +137,7 @@ mod tests {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
@@ -160,6 +161,7 @@ mod tests {
             images: None,
             pages: None,
             elements: None,
+            scripts: None,
             djot_content: Some(DjotContent {
                 plain_text: "Hello World".to_string(),
                 blocks: vec![FormattedBlock {
@@ -203,6 +205,7 @@ mod tests {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
@@ -225,6 +228,7 @@ mod tests {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
@@ -248,6 +252,7 @@ mod tests {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
@@ -271,6 +276,7 @@ mod tests {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
@@ -301,6 +307,7 @@ mod tests {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
@@ -334,6 +341,7 @@ mod tests {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
@@ -383,6 +391,7 @@ mod tests {
             pages: None,
             elements: None,
             djot_content: Some(djot_content),
+            scripts: None,
         };
 
         apply_output_format(&mut result, OutputFormat::Djot);
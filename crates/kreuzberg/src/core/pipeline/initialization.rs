@@ -31,6 +31,15 @@ pub(super) fn initialize_features() {
         let _ = crate::chunking::ensure_initialized();
     }
 
+    {
+        let registry = crate::plugins::registry::get_post_processor_registry();
+        if let Ok(mut reg) = registry.write() {
+            let _ = reg.register(std::sync::Arc::new(crate::text::boilerplate::BoilerplateRemover), 60);
+            let _ = reg.register(std::sync::Arc::new(crate::text::glossary::GlossaryMatcher), 50);
+            let _ = reg.register(std::sync::Arc::new(crate::text::rules::RulesExtractor), 50);
+        }
+    }
+
     #[cfg(feature = "quality")]
     {
         let registry = crate::plugins::registry::get_post_processor_registry();
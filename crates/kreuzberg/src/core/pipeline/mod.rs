@@ -4,32 +4,66 @@
 //! quality processing, chunking, and custom hooks in the correct order.
 
 mod cache;
+mod determinism;
 mod execution;
 mod features;
+mod filters;
 mod format;
 mod initialization;
+mod plan;
+mod report;
 
 #[cfg(test)]
 mod tests;
 
 pub use cache::clear_processor_cache;
 pub use format::apply_output_format;
+pub use plan::{ExtractionPlan, OcrTrigger, PlannedOcr, PlannedPlugin, PlannedPostProcessor};
 
 use crate::Result;
 use crate::core::config::ExtractionConfig;
 use crate::types::ExtractionResult;
 
-use execution::{execute_processors, execute_validators};
-use features::{execute_chunking, execute_language_detection};
+use determinism::apply_determinism;
+use execution::{
+    execute_before_chunk_hooks, execute_chunk_processors, execute_processors, execute_translation, execute_validators,
+};
+pub(crate) use features::apply_metadata_mode;
+use features::{
+    apply_page_selection, execute_chunking, execute_language_detection, execute_normalization,
+    execute_script_detection, execute_spellcheck, execute_token_counting, execute_transliteration, execute_truncation,
+    execute_value_normalization, populate_common_metadata,
+};
+use filters::{active_content_filters, execute_chunk_filters, execute_content_filters};
 use initialization::{get_processors_from_cache, initialize_features, initialize_processor_cache};
+pub(crate) use plan::check_network_denied;
+#[cfg(feature = "ocr")]
+pub(crate) use report::OCR_CACHE_HIT_KEY;
+pub(crate) use report::ReportRecorder;
+#[cfg(all(feature = "ocr", feature = "tokio-runtime"))]
+pub(crate) use report::record_ocr_page;
 
 /// Run the post-processing pipeline on an extraction result.
 ///
 /// Executes post-processing in the following order:
-/// 1. Post-Processors - Execute by stage (Early, Middle, Late) to modify/enhance the result
-/// 2. Quality Processing - Text cleaning and quality scoring
-/// 3. Chunking - Text splitting if enabled
-/// 4. Validators - Run validation hooks on the processed result (can fail fast)
+/// 1. Page Selection - Keep only the pages selected by `config.pages.selection`
+/// 2. Metadata Mode - Drop the extracted metadata not asked for by `config.metadata`
+/// 3. Post-Processors - Execute by stage (Early, Middle, Late) to modify/enhance the result
+/// 4. Quality Processing - Text cleaning and quality scoring
+/// 5. Content Filters - Built-in and registered filters on content, pages and tables
+/// 6. Text Normalization - Output normalization profile on content and pages
+/// 7. Common Metadata - Typed metadata fields filled for every format
+/// 8. Truncation - Content cut to `output.max_chars` at a paragraph or sentence boundary
+/// 9. Value Normalization - ISO 8601 dates and decimal numbers if configured
+/// 10. Chunking - Text splitting if enabled
+/// 11. Chunk Processors - Registered per-chunk enrichment plugins
+/// 12. Token Counting - Token usage and cost estimates if enabled
+/// 13. Spellcheck - OCR error rate estimates of the content and pages if configured
+/// 14. Translation - Content or chunks translated by a registered translator if configured
+/// 15. Validators - Run validation hooks on the processed result (can fail fast)
+///
+/// With `config.processing_report` set, a [`crate::types::ProcessingReport`] of the
+/// stages that ran is attached to the result.
 ///
 /// # Arguments
 ///
@@ -45,14 +79,29 @@ use initialization::{get_processors_from_cache, initialize_features, initialize_
 /// - Validator errors bubble up immediately
 /// - Post-processor errors are caught and recorded in metadata
 /// - System errors (IO, RuntimeError equivalents) always bubble up
+pub async fn run_pipeline(result: ExtractionResult, config: &ExtractionConfig) -> Result<ExtractionResult> {
+    run_pipeline_with_report(result, config, ReportRecorder::new(config)).await
+}
+
+/// Run the post-processing pipeline, recording its stages into `report`, which may
+/// already hold the extraction stage.
 #[cfg_attr(feature = "otel", tracing::instrument(
-    skip(result, config),
+    skip(result, config, report),
     fields(
         pipeline.stage = "post_processing",
         content.length = result.content.len(),
     )
 ))]
-pub async fn run_pipeline(mut result: ExtractionResult, config: &ExtractionConfig) -> Result<ExtractionResult> {
+pub(crate) async fn run_pipeline_with_report(
+    mut result: ExtractionResult,
+    config: &ExtractionConfig,
+    mut report: ReportRecorder,
+) -> Result<ExtractionResult> {
+    report.mark(&result);
+    apply_page_selection(&mut result, config);
+    report.stage_if(page_selection_enabled(config), "page_selection", &result);
+    apply_metadata_mode(&mut result.metadata, config.metadata);
+
     let pp_config = config.postprocessor.as_ref();
     let postprocessing_enabled = pp_config.is_none_or(|c| c.enabled);
 
@@ -69,13 +118,46 @@ pub async fn run_pipeline(mut result: ExtractionResult, config: &ExtractionConfi
             early_processors,
             middle_processors,
             late_processors,
+            &mut report,
         )
         .await?;
     }
 
+    report.mark(&result);
+    let filters = active_content_filters(config)?;
+    execute_content_filters(&mut result, config, &filters)?;
+    report.stage_if(!filters.is_empty(), "content_filters", &result);
+    execute_normalization(&mut result, config);
+    report.stage_if(config.output.is_some(), "normalization", &result);
+    populate_common_metadata(&mut result, config.metadata);
+    execute_truncation(&mut result, config);
+    report.stage_if(truncation_enabled(config), "truncation", &result);
+    execute_value_normalization(&mut result, config);
+    report.stage_if(config.value_normalization.is_some(), "value_normalization", &result);
+
+    if config.chunking.is_some() {
+        execute_before_chunk_hooks(&mut result, config).await?;
+    }
     execute_chunking(&mut result, config)?;
+    execute_chunk_filters(&mut result, config, &filters)?;
+    report.stage_if(config.chunking.is_some(), "chunking", &result);
+    if postprocessing_enabled {
+        execute_chunk_processors(&mut result, config, &mut report).await?;
+    }
+    report.mark(&result);
+    execute_token_counting(&mut result, config)?;
+    report.stage_if(config.token_count.is_some(), "token_counting", &result);
     execute_language_detection(&mut result, config)?;
-    execute_validators(&result, config).await?;
+    report.stage_if(config.language_detection.is_some(), "language_detection", &result);
+    execute_script_detection(&mut result, config);
+    report.stage_if(config.script_detection, "script_detection", &result);
+    execute_spellcheck(&mut result, config)?;
+    report.stage_if(config.spellcheck.is_some(), "spellcheck", &result);
+    execute_transliteration(&mut result, config);
+    report.stage_if(config.transliteration.is_some(), "transliteration", &result);
+    execute_translation(&mut result, config).await;
+    report.stage_if(config.translation.is_some(), "translation", &result);
+    execute_validators(&mut result, config, &mut report).await?;
 
     // Transform to element-based output if requested
     if config.result_format == crate::types::OutputFormat::ElementBased {
@@ -85,11 +167,30 @@ pub async fn run_pipeline(mut result: ExtractionResult, config: &ExtractionConfi
     }
 
     // Apply output format conversion as the final step
+    report.mark(&result);
     apply_output_format(&mut result, config.output_format);
+    report.stage_if(
+        config.output_format != crate::core::config::OutputFormat::Plain,
+        "output_format",
+        &result,
+    );
+    report.finish(&mut result);
+
+    if config.determinism {
+        apply_determinism(&mut result);
+    }
 
     Ok(result)
 }
 
+fn page_selection_enabled(config: &ExtractionConfig) -> bool {
+    config.pages.as_ref().is_some_and(|pages| pages.selection.is_some())
+}
+
+fn truncation_enabled(config: &ExtractionConfig) -> bool {
+    config.output.as_ref().is_some_and(|output| output.max_chars.is_some())
+}
+
 /// Run the post-processing pipeline synchronously (WASM-compatible version).
 ///
 /// This is a synchronous implementation for WASM and non-async contexts.
@@ -109,17 +210,55 @@ pub async fn run_pipeline(mut result: ExtractionResult, config: &ExtractionConfi
 ///
 /// This function is only available when the `tokio-runtime` feature is disabled.
 /// It handles:
+/// - Page selection
+/// - Metadata mode
 /// - Quality processing (if enabled)
+/// - Content filters
+/// - Text normalization (if configured)
+/// - Common metadata fields
+/// - Truncation (if configured)
+/// - Value normalization (if configured)
 /// - Chunking (if enabled)
+/// - Token counting (if enabled)
 /// - Language detection (if enabled)
+/// - Script detection (if enabled)
+/// - Spellcheck (if configured)
+/// - Processing report (if enabled)
 ///
 /// It does NOT handle:
 /// - Async post-processors
 /// - Async validators
+/// - Translation
 #[cfg(not(feature = "tokio-runtime"))]
 pub fn run_pipeline_sync(mut result: ExtractionResult, config: &ExtractionConfig) -> Result<ExtractionResult> {
+    let mut report = ReportRecorder::new(config);
+    report.mark(&result);
+    apply_page_selection(&mut result, config);
+    report.stage_if(page_selection_enabled(config), "page_selection", &result);
+    apply_metadata_mode(&mut result.metadata, config.metadata);
+    let filters = active_content_filters(config)?;
+    execute_content_filters(&mut result, config, &filters)?;
+    report.stage_if(!filters.is_empty(), "content_filters", &result);
+    execute_normalization(&mut result, config);
+    report.stage_if(config.output.is_some(), "normalization", &result);
+    populate_common_metadata(&mut result, config.metadata);
+    execute_truncation(&mut result, config);
+    report.stage_if(truncation_enabled(config), "truncation", &result);
+    execute_value_normalization(&mut result, config);
+    report.stage_if(config.value_normalization.is_some(), "value_normalization", &result);
     execute_chunking(&mut result, config)?;
+    execute_chunk_filters(&mut result, config, &filters)?;
+    report.stage_if(config.chunking.is_some(), "chunking", &result);
+    execute_token_counting(&mut result, config)?;
+    report.stage_if(config.token_count.is_some(), "token_counting", &result);
     execute_language_detection(&mut result, config)?;
+    report.stage_if(config.language_detection.is_some(), "language_detection", &result);
+    execute_script_detection(&mut result, config);
+    report.stage_if(config.script_detection, "script_detection", &result);
+    execute_spellcheck(&mut result, config)?;
+    report.stage_if(config.spellcheck.is_some(), "spellcheck", &result);
+    execute_transliteration(&mut result, config);
+    report.stage_if(config.transliteration.is_some(), "transliteration", &result);
 
     // Transform to element-based output if requested
     if config.result_format == crate::types::OutputFormat::ElementBased {
@@ -129,7 +268,18 @@ pub fn run_pipeline_sync(mut result: ExtractionResult, config: &ExtractionConfig
     }
 
     // Apply output format conversion as the final step
+    report.mark(&result);
     apply_output_format(&mut result, config.output_format);
+    report.stage_if(
+        config.output_format != crate::core::config::OutputFormat::Plain,
+        "output_format",
+        &result,
+    );
+    report.finish(&mut result);
+
+    if config.determinism {
+        apply_determinism(&mut result);
+    }
 
     Ok(result)
 }
@@ -0,0 +1,530 @@
+//! Dry-run resolution of the extraction pipeline.
+//!
+//! [`ExtractionConfig::plan`] resolves everything an extraction would use — MIME type,
+//! extractor, OCR backend, post-processors, validators and hooks — without reading
+//! or extracting the document.
+
+use crate::Result;
+use crate::core::config::ExtractionConfig;
+use crate::core::config::OutputFormat;
+use crate::core::mime::{
+    self, DOCX_MIME_TYPE, LEGACY_POWERPOINT_MIME_TYPE, LEGACY_WORD_MIME_TYPE, POWER_POINT_MIME_TYPE,
+};
+use crate::plugins::{ExtractorDescriptor, OcrBackendDescriptor, Plugin, ProcessingStage};
+use serde::Serialize;
+use std::path::Path;
+
+use super::execution::should_processor_run;
+use super::initialization::{get_processors_from_cache, initialize_features, initialize_processor_cache};
+
+/// How an extraction would be run, as resolved by [`ExtractionConfig::plan`].
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtractionPlan {
+    /// Resolved MIME type of the input
+    pub mime_type: String,
+    /// MIME type the extractor is selected for (differs for legacy formats converted with LibreOffice)
+    pub extractor_mime_type: String,
+    /// Extractor that would handle the document, with the MIME types and features it covers
+    pub extractor: ExtractorDescriptor,
+    /// OCR resolution, `None` when OCR cannot run for this format and configuration
+    pub ocr: Option<PlannedOcr>,
+    /// Post-processors in execution order, including the ones that are skipped
+    pub post_processors: Vec<PlannedPostProcessor>,
+    /// Validators in execution order
+    pub validators: Vec<PlannedPlugin>,
+    /// Pipeline hooks in execution order
+    pub pipeline_hooks: Vec<PlannedPlugin>,
+    /// Whether chunking runs
+    pub chunking: bool,
+    /// Chunk processors in execution order, empty when chunking does not run
+    pub chunk_processors: Vec<PlannedPlugin>,
+    /// Whether language detection runs
+    pub language_detection: bool,
+    /// Translator that would translate the content, `None` without translation or translator
+    pub translator: Option<PlannedPlugin>,
+    /// Output format of the content
+    pub output_format: OutputFormat,
+    /// Effective configuration passed to every stage
+    pub config: ExtractionConfig,
+    /// Human-readable explanations for skipped or unusual stages
+    pub notes: Vec<String>,
+}
+
+/// A plugin selected for a pipeline stage.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedPlugin {
+    /// Registered plugin name
+    pub name: String,
+    /// Plugin version
+    pub version: String,
+    /// Plugin priority
+    pub priority: i32,
+    /// Whether the plugin performs network I/O
+    pub requires_network: bool,
+}
+
+/// A post-processor and whether it would run.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedPostProcessor {
+    /// Registered processor name
+    pub name: String,
+    /// Processor version
+    pub version: String,
+    /// Stage the processor runs in
+    pub stage: ProcessingStage,
+    /// `false` when post-processing is disabled or the processor is filtered out by configuration
+    ///
+    /// Processors may still skip themselves at runtime via `should_process`.
+    pub enabled: bool,
+    /// Whether the processor performs network I/O
+    pub requires_network: bool,
+}
+
+/// When OCR runs for a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OcrTrigger {
+    /// Every image is OCRed
+    Always,
+    /// `force_ocr` is set, native text is ignored
+    Forced,
+    /// Only pages whose native text layer is missing or unusable are OCRed
+    Fallback,
+}
+
+/// OCR backend resolution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PlannedOcr {
+    /// Configured backend name, or the ensemble backends joined by `+`
+    pub backend: String,
+    /// Whether the backend is registered (extraction fails with OCR errors otherwise)
+    pub registered: bool,
+    /// Languages and capabilities of the backend, when it is registered
+    pub descriptor: Option<OcrBackendDescriptor>,
+    /// Configured language
+    pub language: String,
+    /// When OCR runs
+    pub trigger: OcrTrigger,
+}
+
+impl ExtractionPlan {
+    /// Stages of the plan that would perform network I/O.
+    ///
+    /// Covers the extractor, the OCR backend, enabled post-processors, validators,
+    /// pipeline hooks, chunk processors and the translator as reported by
+    /// [`Plugin::requires_network`], and embedding models that are not downloaded yet.
+    pub fn network_stages(&self) -> Vec<String> {
+        let mut stages = Vec::new();
+        if self.extractor.requires_network {
+            stages.push(format!("extractor '{}'", self.extractor.name));
+        }
+        if let Some(ocr) = &self.ocr
+            && ocr.descriptor.as_ref().is_some_and(|d| d.requires_network)
+        {
+            stages.push(format!("OCR backend '{}'", ocr.backend));
+        }
+        for processor in &self.post_processors {
+            if processor.enabled && processor.requires_network {
+                stages.push(format!("post-processor '{}'", processor.name));
+            }
+        }
+        for (kind, plugins) in [
+            ("validator", &self.validators),
+            ("pipeline hook", &self.pipeline_hooks),
+            ("chunk processor", &self.chunk_processors),
+        ] {
+            for plugin in plugins.iter().filter(|p| p.requires_network) {
+                stages.push(format!("{} '{}'", kind, plugin.name));
+            }
+        }
+        if let Some(translator) = self.translator.as_ref().filter(|t| t.requires_network) {
+            stages.push(format!("translator '{}'", translator.name));
+        }
+        #[cfg(feature = "embeddings")]
+        if self.chunking
+            && let Some(embedding) = self.config.chunking.as_ref().and_then(|c| c.embedding.as_ref())
+            && !crate::embeddings::is_model_downloaded(embedding)
+        {
+            stages.push("embedding model download".to_string());
+        }
+        stages
+    }
+}
+
+impl ExtractionConfig {
+    /// Resolve how a document would be extracted without extracting it.
+    ///
+    /// `path_or_mime` is either a MIME type (e.g. `"application/pdf"`) or a file path;
+    /// paths are resolved by extension and do not need to exist. Use this to debug
+    /// why a plugin was or was not used.
+    ///
+    /// The plan reflects the registries at the time of the call. Pipeline hooks may
+    /// still change the MIME type or configuration in `before_extract`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::UnsupportedFormat` if the MIME type is unsupported or
+    /// no extractor is registered for it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kreuzberg::ExtractionConfig;
+    ///
+    /// let plan = ExtractionConfig::default().plan("report.html")?;
+    /// assert_eq!(plan.mime_type, "text/html");
+    /// println!("{} runs, then {:?}", plan.extractor.name, plan.post_processors);
+    /// # Ok::<(), kreuzberg::KreuzbergError>(())
+    /// ```
+    pub fn plan(&self, path_or_mime: &str) -> Result<ExtractionPlan> {
+        let mime_type = resolve_mime_type(path_or_mime)?;
+        let mut notes = Vec::new();
+
+        let extractor_mime_type = match mime_type.as_str() {
+            LEGACY_WORD_MIME_TYPE => DOCX_MIME_TYPE,
+            LEGACY_POWERPOINT_MIME_TYPE => POWER_POINT_MIME_TYPE,
+            other => other,
+        }
+        .to_string();
+        if extractor_mime_type != mime_type {
+            notes.push(format!("{} is converted with LibreOffice before extraction", mime_type));
+        }
+
+        crate::extractors::ensure_initialized()?;
+        let extractor = {
+            let registry = crate::plugins::registry::get_document_extractor_registry();
+            let registry = registry.read().map_err(|e| {
+                crate::KreuzbergError::Other(format!("Document extractor registry lock poisoned: {}", e))
+            })?;
+            registry.get(&extractor_mime_type)?
+        };
+
+        let ocr = self.plan_ocr(&extractor_mime_type, &mut notes)?;
+        let post_processors = self.plan_post_processors(&mut notes)?;
+
+        let validators = {
+            let registry = crate::plugins::registry::get_validator_registry();
+            let registry = registry
+                .read()
+                .map_err(|e| crate::KreuzbergError::Other(format!("Validator registry lock poisoned: {}", e)))?;
+            crate::plugins::configured_validators(self)
+                .into_iter()
+                .chain(registry.get_all())
+                .map(|v| planned(v.as_ref(), v.priority()))
+                .collect()
+        };
+
+        let pipeline_hooks = crate::plugins::registered_pipeline_hooks()?
+            .into_iter()
+            .map(|h| planned(h.as_ref(), h.priority()))
+            .collect();
+
+        let chunking = self.chunking.is_some() && cfg!(feature = "chunking");
+        if self.chunking.is_some() && !chunking {
+            notes.push("chunking is configured but the `chunking` feature is not enabled".to_string());
+        }
+
+        let postprocessing = self.postprocessor.as_ref();
+        let chunk_processors = if chunking && postprocessing.is_none_or(|c| c.enabled) {
+            crate::plugins::registered_chunk_processors()?
+                .into_iter()
+                .filter(|p| super::execution::should_processor_run(&postprocessing, p.name()))
+                .map(|p| planned(p.as_ref(), p.priority()))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let language_detection = self.language_detection.as_ref().is_some_and(|c| c.enabled);
+
+        let translator = match &self.translation {
+            Some(translation) => match crate::plugins::resolve_translator(translation.translator.as_deref()) {
+                Ok(t) => Some(planned(t.as_ref(), t.priority())),
+                Err(e) => {
+                    notes.push(format!("translation is configured but cannot run: {}", e));
+                    None
+                }
+            },
+            None => None,
+        };
+
+        Ok(ExtractionPlan {
+            mime_type,
+            extractor_mime_type,
+            extractor: ExtractorDescriptor::new(extractor.as_ref()),
+            ocr,
+            post_processors,
+            validators,
+            pipeline_hooks,
+            chunking,
+            chunk_processors,
+            language_detection,
+            translator,
+            output_format: self.output_format,
+            config: self.clone(),
+            notes,
+        })
+    }
+
+    fn plan_ocr(&self, mime_type: &str, notes: &mut Vec<String>) -> Result<Option<PlannedOcr>> {
+        let trigger = if mime_type.starts_with("image/") {
+            OcrTrigger::Always
+        } else if mime_type == "application/pdf" {
+            if self.force_ocr {
+                OcrTrigger::Forced
+            } else {
+                OcrTrigger::Fallback
+            }
+        } else {
+            return Ok(None);
+        };
+
+        let Some(ocr_config) = self.ocr.as_ref() else {
+            if trigger == OcrTrigger::Always || self.force_ocr {
+                notes.push("OCR is not configured (`ocr` is None), so no text is recognized".to_string());
+            }
+            return Ok(None);
+        };
+
+        let backend_name = if ocr_config.ensemble.is_empty() {
+            ocr_config.backend.clone()
+        } else {
+            ocr_config.ensemble.join("+")
+        };
+        let backend = match crate::plugins::resolve_ocr_backend(ocr_config) {
+            Ok(backend) => Some(backend),
+            Err(crate::KreuzbergError::Plugin { plugin_name, .. }) if plugin_name != "ocr-registry" => {
+                notes.push(format!("OCR backend '{}' is not registered", plugin_name));
+                None
+            }
+            Err(e) => return Err(e),
+        };
+        if let Some(backend) = &backend {
+            for language in ocr_config.language.split('+').filter(|l| !l.is_empty()) {
+                if !backend.supports_language(language) {
+                    notes.push(format!(
+                        "OCR backend '{}' does not support language '{}'",
+                        backend_name, language
+                    ));
+                }
+            }
+        }
+
+        Ok(Some(PlannedOcr {
+            backend: backend_name,
+            registered: backend.is_some(),
+            descriptor: backend.map(|backend| OcrBackendDescriptor::new(backend.as_ref())),
+            language: ocr_config.language.clone(),
+            trigger,
+        }))
+    }
+
+    fn plan_post_processors(&self, notes: &mut Vec<String>) -> Result<Vec<PlannedPostProcessor>> {
+        let pp_config = self.postprocessor.as_ref();
+        let postprocessing_enabled = pp_config.is_none_or(|c| c.enabled);
+        if !postprocessing_enabled {
+            notes.push("post-processing is disabled (`postprocessor.enabled = false`)".to_string());
+        }
+
+        initialize_features();
+        initialize_processor_cache()?;
+        let (early, middle, late) = get_processors_from_cache()?;
+
+        let mut planned = Vec::new();
+        for (stage, processors) in [
+            (ProcessingStage::Early, early),
+            (ProcessingStage::Middle, middle),
+            (ProcessingStage::Late, late),
+        ] {
+            for processor in processors.iter() {
+                let name = processor.name();
+                let enabled = postprocessing_enabled && should_processor_run(&pp_config, name);
+                if postprocessing_enabled && !enabled {
+                    notes.push(format!(
+                        "post-processor '{}' is filtered out by `postprocessor` settings",
+                        name
+                    ));
+                }
+                planned.push(PlannedPostProcessor {
+                    name: name.to_string(),
+                    version: processor.version(),
+                    stage,
+                    enabled,
+                    requires_network: processor.requires_network(),
+                });
+            }
+        }
+
+        Ok(planned)
+    }
+}
+
+fn planned(plugin: &dyn Plugin, priority: i32) -> PlannedPlugin {
+    PlannedPlugin {
+        name: plugin.name().to_string(),
+        version: plugin.version(),
+        priority,
+        requires_network: plugin.requires_network(),
+    }
+}
+
+/// Fail if network access is denied and a stage of the extraction of `mime_type`
+/// would need it.
+///
+/// # Errors
+///
+/// Returns `crate::KreuzbergError::Security` naming the stages that would use the network.
+pub(crate) fn check_network_denied(config: &ExtractionConfig, mime_type: &str) -> Result<()> {
+    if !config.denies_network() {
+        return Ok(());
+    }
+    let stages = config.plan(mime_type)?.network_stages();
+    if stages.is_empty() {
+        return Ok(());
+    }
+    Err(crate::KreuzbergError::Security {
+        message: format!(
+            "Network access is denied, but the extraction would need it for {}",
+            stages.join(", ")
+        ),
+        source: None,
+    })
+}
+
+fn resolve_mime_type(path_or_mime: &str) -> Result<String> {
+    let path = Path::new(path_or_mime);
+    if !path.exists()
+        && let Ok(mime_type) = mime::validate_mime_type(path_or_mime)
+    {
+        return Ok(mime_type);
+    }
+
+    let detected = mime::detect_mime_type(path, false)?;
+    mime::validate_mime_type(&detected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{OcrConfig, PostProcessorConfig};
+
+    #[test]
+    fn test_plan_from_path_and_mime() {
+        let config = ExtractionConfig::default();
+
+        let from_path = config.plan("does/not/exist/report.html").unwrap();
+        assert_eq!(from_path.mime_type, "text/html");
+        assert!(from_path.extractor.mime_types.iter().any(|m| m == "text/html"));
+        assert!(from_path.ocr.is_none());
+
+        let from_mime = config.plan("text/html").unwrap();
+        assert_eq!(from_mime.extractor, from_path.extractor);
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn test_plan_reports_ocr_and_unknown_backend() {
+        let config = ExtractionConfig {
+            force_ocr: true,
+            ocr: Some(OcrConfig {
+                backend: "missing-backend".to_string(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let plan = config.plan("scan.pdf").unwrap();
+        let ocr = plan.ocr.unwrap();
+        assert_eq!(ocr.trigger, OcrTrigger::Forced);
+        assert!(!ocr.registered);
+        assert!(ocr.descriptor.is_none());
+        assert!(plan.notes.iter().any(|n| n.contains("missing-backend")));
+    }
+
+    #[test]
+    fn test_plan_marks_disabled_post_processors() {
+        let config = ExtractionConfig {
+            postprocessor: Some(PostProcessorConfig {
+                enabled: false,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let plan = config.plan("text/plain").unwrap();
+        assert!(plan.post_processors.iter().all(|p| !p.enabled));
+        assert!(plan.notes.iter().any(|n| n.contains("post-processing is disabled")));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_network_denial_fails_on_network_stages() {
+        use crate::core::config::{NetworkConfig, TranslationConfig};
+        use crate::plugins::{Translator, register_translator, unregister_translator};
+        use async_trait::async_trait;
+        use std::sync::Arc;
+
+        struct RemoteTranslator;
+
+        impl Plugin for RemoteTranslator {
+            fn name(&self) -> &str {
+                "remote-translator"
+            }
+            fn version(&self) -> String {
+                "1.0.0".to_string()
+            }
+            fn initialize(&self) -> Result<()> {
+                Ok(())
+            }
+            fn shutdown(&self) -> Result<()> {
+                Ok(())
+            }
+            fn requires_network(&self) -> bool {
+                true
+            }
+        }
+
+        #[async_trait]
+        impl Translator for RemoteTranslator {
+            async fn translate(&self, texts: &[String], _: Option<&str>, _: &str) -> Result<Vec<String>> {
+                Ok(texts.to_vec())
+            }
+        }
+
+        let mut config = ExtractionConfig {
+            translation: Some(TranslationConfig {
+                target_language: "eng".to_string(),
+                scope: Default::default(),
+                translator: Some("remote-translator".to_string()),
+            }),
+            network: Some(NetworkConfig::denied()),
+            ..Default::default()
+        };
+
+        register_translator(Arc::new(RemoteTranslator)).unwrap();
+        let plan = config.plan("text/plain");
+        let denied = check_network_denied(&config, "text/plain");
+        config.network = None;
+        let allowed = check_network_denied(&config, "text/plain");
+        unregister_translator("remote-translator").unwrap();
+
+        assert_eq!(plan.unwrap().network_stages(), vec!["translator 'remote-translator'"]);
+        match denied {
+            Err(crate::KreuzbergError::Security { message, .. }) => {
+                assert!(message.contains("translator 'remote-translator'"))
+            }
+            other => panic!("expected a security error, got {:?}", other),
+        }
+        assert!(allowed.is_ok());
+        assert!(check_network_denied(&ExtractionConfig::default(), "text/plain").is_ok());
+    }
+
+    #[test]
+    fn test_plan_unsupported_format() {
+        assert!(
+            ExtractionConfig::default()
+                .plan("application/x-unknown-format")
+                .is_err()
+        );
+    }
+}
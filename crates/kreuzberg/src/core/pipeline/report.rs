@@ -0,0 +1,251 @@
+//! Processing report recording.
+//!
+//! A [`ReportRecorder`] times the stages of one extraction when
+//! `ExtractionConfig::processing_report` is set, and does nothing otherwise. OCR
+//! pages are collected through a task-local list around the extractor, so that the
+//! page scheduler can report them without the recorder being passed through every
+//! extractor.
+
+use crate::core::config::ExtractionConfig;
+use crate::extraction::salvage::SALVAGE_WARNINGS_KEY;
+use crate::plugins::Plugin;
+use crate::types::{
+    ExtractionResult, FallbackAttempt, FormatMetadata, OcrPageReport, OcrReport, PROCESSING_REPORT_KEY,
+    ProcessingReport, StageReport,
+};
+use parking_lot::Mutex;
+use std::borrow::Cow;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Metadata key set by OCR backends on results served from their cache.
+pub(crate) const OCR_CACHE_HIT_KEY: &str = "ocr_cache_hit";
+
+#[cfg(feature = "tokio-runtime")]
+tokio::task_local! {
+    static OCR_PAGES: Arc<Mutex<Vec<OcrPageReport>>>;
+}
+
+/// Records the stages of one extraction into a [`ProcessingReport`].
+pub(crate) struct ReportRecorder {
+    recording: Option<Recording>,
+}
+
+struct Recording {
+    started: Instant,
+    /// Start of the current stage
+    mark: Instant,
+    /// Content size at the start of the current stage
+    mark_bytes: u64,
+    bytes_in: Option<u64>,
+    stages: Vec<StageReport>,
+    fallbacks: Vec<FallbackAttempt>,
+    ocr_pages: Arc<Mutex<Vec<OcrPageReport>>>,
+}
+
+impl ReportRecorder {
+    /// A recorder for an extraction with `config`, recording only if the report is enabled.
+    pub(crate) fn new(config: &ExtractionConfig) -> Self {
+        let now = Instant::now();
+        Self {
+            recording: config.processing_report.then(|| Recording {
+                started: now,
+                mark: now,
+                mark_bytes: 0,
+                bytes_in: None,
+                stages: Vec::new(),
+                fallbacks: Vec::new(),
+                ocr_pages: Arc::default(),
+            }),
+        }
+    }
+
+    pub(crate) fn is_enabled(&self) -> bool {
+        self.recording.is_some()
+    }
+
+    /// Start the first stage, on an input document of `bytes` bytes.
+    pub(crate) fn start_input(&mut self, bytes: u64) {
+        if let Some(recording) = &mut self.recording {
+            recording.bytes_in = Some(bytes);
+            recording.mark = Instant::now();
+            recording.mark_bytes = bytes;
+        }
+    }
+
+    /// Start a stage on `result`.
+    pub(crate) fn mark(&mut self, result: &ExtractionResult) {
+        if let Some(recording) = &mut self.recording {
+            recording.mark = Instant::now();
+            recording.mark_bytes = result.content.len() as u64;
+        }
+    }
+
+    /// End stage `name`, which left `result`, and start the next one; if the stage did
+    /// not `run`, only start the next one.
+    pub(crate) fn stage_if(&mut self, run: bool, name: &str, result: &ExtractionResult) {
+        if run {
+            self.push(name, None, result);
+        } else {
+            self.mark(result);
+        }
+    }
+
+    /// End stage `name`, in which `plugin` left `result`, and start the next one.
+    pub(crate) fn plugin_stage(&mut self, name: &str, plugin: &dyn Plugin, result: &ExtractionResult) {
+        if self.is_enabled() {
+            self.push(name, Some((plugin.name().to_string(), plugin.version())), result);
+        }
+    }
+
+    fn push(&mut self, name: &str, plugin: Option<(String, String)>, result: &ExtractionResult) {
+        let Some(recording) = &mut self.recording else {
+            return;
+        };
+        let now = Instant::now();
+        let bytes_out = result.content.len() as u64;
+        let (plugin, version) = plugin.unzip();
+        recording.stages.push(StageReport {
+            name: name.to_string(),
+            plugin,
+            version,
+            duration_ms: millis(now - recording.mark),
+            bytes_in: recording.mark_bytes,
+            bytes_out,
+        });
+        recording.mark = now;
+        recording.mark_bytes = bytes_out;
+    }
+
+    /// Record the `attempts` of a fallback chain.
+    pub(crate) fn fallbacks(&mut self, attempts: Vec<FallbackAttempt>) {
+        if let Some(recording) = &mut self.recording {
+            recording.fallbacks.extend(attempts);
+        }
+    }
+
+    /// Run `future`, collecting the OCR pages reported while it runs.
+    #[cfg(feature = "tokio-runtime")]
+    pub(crate) async fn collect_ocr<F: std::future::Future>(&self, future: F) -> F::Output {
+        match &self.recording {
+            Some(recording) => OCR_PAGES.scope(Arc::clone(&recording.ocr_pages), future).await,
+            None => future.await,
+        }
+    }
+
+    /// Attach the report to `result`, in `metadata.additional`.
+    pub(crate) fn finish(self, result: &mut ExtractionResult) {
+        let Some(recording) = self.recording else {
+            return;
+        };
+
+        let mut pages = std::mem::take(&mut *recording.ocr_pages.lock());
+        let ocr = ocr_report(result, &mut pages);
+        let report = ProcessingReport {
+            mime_type: result.mime_type.to_string(),
+            duration_ms: millis(recording.started.elapsed()),
+            bytes_in: recording.bytes_in,
+            bytes_out: result.content.len() as u64,
+            stages: recording.stages,
+            fallbacks: recording.fallbacks,
+            cache_hits: pages.iter().filter(|page| page.cache_hit).count(),
+            ocr,
+            warnings: warnings(result),
+        };
+
+        match serde_json::to_value(&report) {
+            Ok(value) => {
+                result
+                    .metadata
+                    .additional
+                    .insert(Cow::Borrowed(PROCESSING_REPORT_KEY), value);
+            }
+            Err(e) => tracing::warn!("Failed to serialize the processing report: {}", e),
+        }
+    }
+}
+
+/// Report the OCR of page `page_number`, whose result is `result`, to the report of
+/// the extraction running on this task, if any.
+#[cfg(all(feature = "ocr", feature = "tokio-runtime"))]
+pub(crate) fn record_ocr_page(page_number: usize, result: &ExtractionResult) {
+    let page = OcrPageReport {
+        page_number,
+        confidence: confidence(result),
+        cache_hit: cache_hit(result),
+    };
+    let _ = OCR_PAGES.try_with(|pages| pages.lock().push(page));
+}
+
+/// The OCR report of `result`, with the `pages` reported by the page scheduler, or the
+/// result itself as the only page when it is a single OCRed image.
+fn ocr_report(result: &ExtractionResult, pages: &mut Vec<OcrPageReport>) -> Option<OcrReport> {
+    let additional = &result.metadata.additional;
+    let backend = additional
+        .get("ocr_backend")
+        .and_then(|backend| backend.as_str())
+        .map(str::to_string);
+    let ocred = matches!(result.metadata.format, Some(FormatMetadata::Ocr(_))) || backend.is_some();
+
+    if pages.is_empty() {
+        if !ocred {
+            return None;
+        }
+        pages.push(OcrPageReport {
+            page_number: 1,
+            confidence: confidence(result),
+            cache_hit: cache_hit(result),
+        });
+    }
+    pages.sort_by_key(|page| page.page_number);
+
+    let confidences: Vec<f64> = pages.iter().filter_map(|page| page.confidence).collect();
+    let mean_confidence = (!confidences.is_empty()).then(|| confidences.iter().sum::<f64>() / confidences.len() as f64);
+    Some(OcrReport {
+        backend,
+        pages: pages.clone(),
+        mean_confidence,
+    })
+}
+
+fn confidence(result: &ExtractionResult) -> Option<f64> {
+    result
+        .metadata
+        .additional
+        .get("mean_confidence")
+        .and_then(|confidence| confidence.as_f64())
+}
+
+fn cache_hit(result: &ExtractionResult) -> bool {
+    result
+        .metadata
+        .additional
+        .get(OCR_CACHE_HIT_KEY)
+        .and_then(|hit| hit.as_bool())
+        .unwrap_or(false)
+}
+
+/// Damage repaired by the salvage mode, plugin warnings and the errors that stages
+/// recorded instead of failing, sorted by key.
+fn warnings(result: &ExtractionResult) -> Vec<String> {
+    let additional = &result.metadata.additional;
+    let mut warnings: Vec<String> = [SALVAGE_WARNINGS_KEY, "plugin_warnings"]
+        .into_iter()
+        .filter_map(|key| additional.get(key).and_then(|warnings| warnings.as_array()))
+        .flatten()
+        .filter_map(|warning| warning.as_str().map(str::to_string))
+        .collect();
+
+    let mut errors: Vec<_> = additional
+        .iter()
+        .filter(|(key, _)| key.starts_with("processing_error_") || key.ends_with("_error"))
+        .filter_map(|(key, value)| value.as_str().map(|message| format!("{key}: {message}")))
+        .collect();
+    errors.sort();
+    warnings.extend(errors);
+    warnings
+}
+
+fn millis(duration: std::time::Duration) -> f64 {
+    duration.as_secs_f64() * 1000.0
+}
@@ -27,6 +27,7 @@ async fn test_run_pipeline_basic() {
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         pages: None,
         elements: None,
     };
@@ -52,6 +53,7 @@ async fn test_pipeline_with_quality_processing() {
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         pages: None,
         elements: None,
     };
@@ -75,6 +77,7 @@ async fn test_pipeline_without_quality_processing() {
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         pages: None,
         elements: None,
     };
@@ -99,6 +102,7 @@ async fn test_pipeline_with_chunking() {
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         pages: None,
         elements: None,
     };
@@ -110,6 +114,10 @@ async fn test_pipeline_with_chunking() {
             chunker_type: crate::ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: crate::OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         }),
         ..Default::default()
     };
@@ -120,6 +128,503 @@ async fn test_pipeline_with_chunking() {
     assert!(chunk_count.as_u64().unwrap() > 1);
 }
 
+#[tokio::test]
+#[cfg(feature = "chunking")]
+async fn test_pipeline_with_token_counting() {
+    use crate::core::config::{TokenCountConfig, TokenPricing, TokenizerKind};
+    use crate::text::TokenUsage;
+
+    let result = ExtractionResult {
+        content: "one two three four ".repeat(50),
+        mime_type: Cow::Borrowed("text/plain"),
+        metadata: Metadata::default(),
+        tables: vec![],
+        detected_languages: None,
+        chunks: None,
+        images: None,
+        djot_content: None,
+        scripts: None,
+        pages: None,
+        elements: None,
+    };
+    let config = ExtractionConfig {
+        chunking: Some(crate::ChunkingConfig {
+            max_characters: 200,
+            overlap: 0,
+            trim: true,
+            chunker_type: crate::ChunkerType::Text,
+            embedding: None,
+            preset: None,
+            overlap_strategy: crate::OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
+        }),
+        token_count: Some(TokenCountConfig {
+            tokenizer: TokenizerKind::Whitespace,
+            pricing: Some(TokenPricing {
+                embedding_per_million: Some(1_000_000.0),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let processed = run_pipeline(result, &config).await.unwrap();
+    let chunks = processed.chunks.as_ref().unwrap();
+    assert!(chunks.iter().all(|chunk| chunk.metadata.token_count.is_some()));
+
+    let usage = TokenUsage::from_result(&processed).unwrap();
+    assert_eq!(usage.tokenizer, "whitespace");
+    assert_eq!(usage.document_tokens, 200);
+    assert_eq!(usage.chunk_count, Some(chunks.len()));
+    assert_eq!(usage.chunk_tokens, Some(200));
+    let cost = usage.estimated_cost.unwrap();
+    assert_eq!(cost.embedding, Some(200.0));
+    assert_eq!(cost.llm_input, None);
+}
+
+#[tokio::test]
+async fn test_pipeline_detects_scripts_of_content_and_ocr_results() {
+    let ocr_result = ExtractionResult {
+        content: "Привет, мир".to_string(),
+        mime_type: Cow::Borrowed("text/plain"),
+        metadata: Metadata::default(),
+        tables: vec![],
+        detected_languages: None,
+        chunks: None,
+        images: None,
+        djot_content: None,
+        scripts: None,
+        pages: None,
+        elements: None,
+    };
+    let result = ExtractionResult {
+        content: "Invoice 42 / Rechnung".to_string(),
+        mime_type: Cow::Borrowed("text/plain"),
+        images: Some(vec![crate::types::ExtractedImage {
+            data: bytes::Bytes::from_static(b"\x89PNG fake image"),
+            format: Cow::Borrowed("png"),
+            image_index: 0,
+            page_number: Some(1),
+            width: None,
+            height: None,
+            colorspace: None,
+            bits_per_component: None,
+            is_mask: false,
+            description: None,
+            ocr_result: Some(Box::new(ocr_result)),
+        }]),
+        metadata: Metadata::default(),
+        tables: vec![],
+        detected_languages: None,
+        chunks: None,
+        djot_content: None,
+        scripts: None,
+        pages: None,
+        elements: None,
+    };
+    let config = ExtractionConfig {
+        script_detection: true,
+        ..Default::default()
+    };
+
+    let processed = run_pipeline(result.clone(), &config).await.unwrap();
+    assert_eq!(
+        processed.metadata.additional["writing_scripts"],
+        serde_json::json!([{"script": "latin", "share": 1.0}])
+    );
+    let image = &processed.images.as_ref().unwrap()[0];
+    assert_eq!(
+        image.ocr_result.as_ref().unwrap().metadata.additional["writing_scripts"][0]["script"],
+        "cyrillic"
+    );
+
+    let processed = run_pipeline(result, &ExtractionConfig::default()).await.unwrap();
+    assert!(!processed.metadata.additional.contains_key("writing_scripts"));
+}
+
+#[tokio::test]
+async fn test_pipeline_spellchecks_content() {
+    use std::io::Write;
+
+    let mut dictionary = tempfile::NamedTempFile::new().unwrap();
+    writeln!(dictionary, "total\namount\ndue").unwrap();
+    let result = ExtractionResult {
+        content: "Tota1 amount dve".to_string(),
+        mime_type: Cow::Borrowed("image/png"),
+        metadata: Metadata::default(),
+        tables: vec![],
+        detected_languages: None,
+        chunks: None,
+        images: None,
+        djot_content: None,
+        scripts: None,
+        pages: None,
+        elements: None,
+    };
+    let mut config = ExtractionConfig {
+        spellcheck: Some(crate::core::config::SpellcheckConfig {
+            dictionaries: vec![dictionary.path().to_path_buf()],
+            words: vec![],
+            min_word_length: 3,
+            max_error_rate: 0.2,
+        }),
+        ..Default::default()
+    };
+
+    let processed = run_pipeline(result.clone(), &config).await.unwrap();
+    assert_eq!(
+        processed.metadata.additional["spellcheck"],
+        serde_json::json!({"checked_words": 3, "unknown_words": 2, "error_rate": 0.667, "low_quality_pages": []})
+    );
+
+    config.spellcheck.as_mut().unwrap().dictionaries = vec!["/nonexistent/en.dic".into()];
+    assert!(run_pipeline(result, &config).await.is_err());
+}
+
+#[cfg(feature = "chunking")]
+#[tokio::test]
+async fn test_pipeline_removes_boilerplate_before_chunking() {
+    let disclaimer = "This message is confidential and intended only for the addressee.";
+    let result = ExtractionResult {
+        content: format!("Meeting moved to Friday.\n\n{disclaimer}\n\nAgenda follows next week.\n\n{disclaimer}"),
+        mime_type: Cow::Borrowed("message/rfc822"),
+        metadata: Metadata::default(),
+        tables: vec![],
+        detected_languages: None,
+        chunks: None,
+        images: None,
+        djot_content: None,
+        scripts: None,
+        pages: None,
+        elements: None,
+    };
+    let config = ExtractionConfig {
+        boilerplate: Some(crate::core::config::BoilerplateConfig {
+            phrases: vec![disclaimer.to_string()],
+            ..Default::default()
+        }),
+        chunking: Some(crate::ChunkingConfig {
+            max_characters: 30,
+            overlap: 0,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let processed = run_pipeline(result, &config).await.unwrap();
+    assert_eq!(
+        processed.content,
+        "Meeting moved to Friday.\n\nAgenda follows next week."
+    );
+    assert_eq!(processed.metadata.additional["boilerplate_removed"], 2);
+    assert!(
+        processed
+            .chunks
+            .unwrap()
+            .iter()
+            .all(|chunk| !chunk.content.contains("confidential"))
+    );
+}
+
+#[cfg(feature = "chunking")]
+#[tokio::test]
+async fn test_pipeline_annotates_values_before_chunking() {
+    let result = ExtractionResult {
+        content: "Rechnung vom 03.04.2024.\n\nBetrag: 1.250,00 EUR".to_string(),
+        mime_type: Cow::Borrowed("text/plain"),
+        metadata: Metadata::default(),
+        tables: vec![],
+        detected_languages: Some(vec!["deu".to_string()]),
+        chunks: None,
+        images: None,
+        djot_content: None,
+        scripts: None,
+        pages: None,
+        elements: None,
+    };
+    let config = ExtractionConfig {
+        value_normalization: Some(crate::core::config::ValueNormalizationConfig {
+            mode: crate::core::config::ValueAnnotationMode::Inline,
+            ..Default::default()
+        }),
+        chunking: Some(crate::ChunkingConfig {
+            max_characters: 40,
+            overlap: 0,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let processed = run_pipeline(result, &config).await.unwrap();
+    assert_eq!(
+        processed.content,
+        "Rechnung vom 03.04.2024 [2024-04-03].\n\nBetrag: 1.250,00 EUR [1250.00 EUR]"
+    );
+    let chunks = processed.chunks.unwrap();
+    assert_eq!(chunks[0].content, "Rechnung vom 03.04.2024 [2024-04-03].");
+    assert_eq!(chunks[1].content, "Betrag: 1.250,00 EUR [1250.00 EUR]");
+}
+
+#[tokio::test]
+async fn test_pipeline_transliterates_content() {
+    let result = ExtractionResult {
+        content: "Счёт 42 / Λογαριασμός".to_string(),
+        mime_type: Cow::Borrowed("text/plain"),
+        metadata: Metadata::default(),
+        tables: vec![],
+        detected_languages: None,
+        chunks: None,
+        images: None,
+        djot_content: None,
+        scripts: None,
+        pages: None,
+        elements: None,
+    };
+    let config = ExtractionConfig {
+        transliteration: Some(crate::core::config::TransliterationConfig::default()),
+        ..Default::default()
+    };
+
+    let processed = run_pipeline(result.clone(), &config).await.unwrap();
+    assert_eq!(processed.content, "Счёт 42 / Λογαριασμός");
+    assert_eq!(
+        processed.metadata.additional["content_transliterated"],
+        "Schyot 42 / Logariasmos"
+    );
+
+    let processed = run_pipeline(result, &ExtractionConfig::default()).await.unwrap();
+    assert!(!processed.metadata.additional.contains_key("content_transliterated"));
+}
+
+#[tokio::test]
+#[cfg(feature = "chunking")]
+async fn test_pipeline_normalizes_text_before_chunking() {
+    use crate::core::config::{NormalizationProfile, OutputConfig};
+
+    let result = ExtractionResult {
+        content: "Search  \u{201c}Profile\u{201d}\nText. ".repeat(40),
+        mime_type: Cow::Borrowed("text/plain"),
+        metadata: Metadata::default(),
+        tables: vec![],
+        detected_languages: None,
+        chunks: None,
+        images: None,
+        djot_content: None,
+        scripts: None,
+        pages: None,
+        elements: None,
+    };
+    let config = ExtractionConfig {
+        output: Some(OutputConfig {
+            profile: NormalizationProfile::Search,
+            max_chars: None,
+        }),
+        chunking: Some(crate::ChunkingConfig {
+            max_characters: 200,
+            overlap: 0,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let processed = run_pipeline(result, &config).await.unwrap();
+    assert!(processed.content.starts_with("search \"profile\" text. search"));
+    assert!(!processed.content.contains('\n'));
+    for chunk in processed.chunks.unwrap() {
+        assert_eq!(chunk.content, chunk.content.to_lowercase());
+        assert!(!chunk.content.contains('\u{201c}'));
+    }
+}
+
+#[tokio::test]
+async fn test_pipeline_populates_common_metadata() {
+    let mut metadata = Metadata {
+        pages: Some(crate::types::PageStructure {
+            total_count: 3,
+            unit_type: crate::types::PageUnitType::Slide,
+            boundaries: None,
+            pages: None,
+        }),
+        ..Default::default()
+    };
+    metadata
+        .additional
+        .insert(Cow::Borrowed("title"), serde_json::json!("Quarterly Review"));
+    let result = ExtractionResult {
+        content: "Revenue grew in every region".to_string(),
+        mime_type: Cow::Borrowed("application/vnd.openxmlformats-officedocument.presentationml.presentation"),
+        metadata,
+        tables: vec![],
+        detected_languages: None,
+        chunks: None,
+        images: None,
+        djot_content: None,
+        scripts: None,
+        pages: None,
+        elements: None,
+    };
+
+    let processed = run_pipeline(result, &ExtractionConfig::default()).await.unwrap();
+    assert_eq!(processed.metadata.title.as_deref(), Some("Quarterly Review"));
+    assert_eq!(processed.metadata.page_count, Some(3));
+    assert_eq!(processed.metadata.word_count, Some(5));
+    assert!(!processed.metadata.additional.contains_key("title"));
+}
+
+#[tokio::test]
+async fn test_pipeline_applies_metadata_mode() {
+    let result = |content: &str| {
+        let mut metadata = Metadata {
+            pages: Some(crate::types::PageStructure {
+                total_count: 2,
+                unit_type: crate::types::PageUnitType::Page,
+                boundaries: None,
+                pages: None,
+            }),
+            ..Default::default()
+        };
+        metadata
+            .additional
+            .insert(Cow::Borrowed("title"), serde_json::json!("Quarterly Review"));
+        metadata
+            .additional
+            .insert(Cow::Borrowed("custom_AssetID"), serde_json::json!("TF10002040"));
+        ExtractionResult {
+            content: content.to_string(),
+            mime_type: Cow::Borrowed("text/plain"),
+            metadata,
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            scripts: None,
+            pages: None,
+            elements: None,
+        }
+    };
+
+    let config = ExtractionConfig {
+        metadata: crate::core::config::MetadataMode::Basic,
+        ..Default::default()
+    };
+    let processed = run_pipeline(result("Revenue grew"), &config).await.unwrap();
+    assert_eq!(processed.metadata.title.as_deref(), Some("Quarterly Review"));
+    assert_eq!(processed.metadata.word_count, Some(2));
+    assert!(!processed.metadata.additional.contains_key("custom_AssetID"));
+
+    let config = ExtractionConfig {
+        metadata: crate::core::config::MetadataMode::None,
+        ..Default::default()
+    };
+    let processed = run_pipeline(result("Revenue grew"), &config).await.unwrap();
+    assert_eq!(processed.metadata.title, None);
+    assert_eq!(processed.metadata.word_count, None);
+    assert_eq!(processed.metadata.page_count, None);
+    assert_eq!(processed.metadata.pages.map(|pages| pages.total_count), Some(2));
+    assert!(!processed.metadata.additional.contains_key("custom_AssetID"));
+}
+
+#[tokio::test]
+async fn test_pipeline_applies_page_selection() {
+    use crate::types::{PageBoundary, PageContent, PageStructure, PageUnitType, Table};
+
+    let boundary = |byte_start, byte_end, page_number| PageBoundary {
+        byte_start,
+        byte_end,
+        page_number,
+    };
+    let page = |page_number: usize, content: &str| PageContent {
+        page_number,
+        content: content.to_string(),
+        tables: vec![],
+        images: vec![],
+        hierarchy: None,
+    };
+    let table = |page_number| Table {
+        cells: vec![],
+        markdown: String::new(),
+        page_number,
+    };
+    let result = ExtractionResult {
+        content: "One\n\nTwo\n\nThree".to_string(),
+        mime_type: Cow::Borrowed("text/plain"),
+        metadata: Metadata {
+            pages: Some(PageStructure {
+                total_count: 3,
+                unit_type: PageUnitType::Page,
+                boundaries: Some(vec![boundary(0, 3, 1), boundary(5, 8, 2), boundary(10, 15, 3)]),
+                pages: None,
+            }),
+            ..Default::default()
+        },
+        tables: vec![table(2), table(3)],
+        detected_languages: None,
+        chunks: None,
+        images: None,
+        djot_content: None,
+        scripts: None,
+        pages: Some(vec![page(1, "One"), page(2, "Two"), page(3, "Three")]),
+        elements: None,
+    };
+
+    let config: ExtractionConfig =
+        serde_json::from_str(r#"{"pages": "1,3-", "enable_quality_processing": false}"#).unwrap();
+    let processed = run_pipeline(result, &config).await.unwrap();
+
+    assert_eq!(processed.content, "One\n\nThree");
+    let structure = processed.metadata.pages.unwrap();
+    assert_eq!(structure.total_count, 3);
+    let boundaries = structure.boundaries.unwrap();
+    assert_eq!(
+        boundaries
+            .iter()
+            .map(|b| (b.byte_start, b.byte_end, b.page_number))
+            .collect::<Vec<_>>(),
+        vec![(0, 3, 1), (5, 10, 3)]
+    );
+    let pages = processed.pages.unwrap();
+    assert_eq!(pages.iter().map(|p| p.page_number).collect::<Vec<_>>(), vec![1, 3]);
+    assert_eq!(processed.tables.len(), 1);
+    assert_eq!(processed.tables[0].page_number, 3);
+}
+
+#[tokio::test]
+async fn test_pipeline_truncates_content_after_metadata() {
+    let result = ExtractionResult {
+        content: "First paragraph of the report.\n\nSecond paragraph with more words in it.".to_string(),
+        mime_type: Cow::Borrowed("text/plain"),
+        metadata: Metadata::default(),
+        tables: vec![],
+        detected_languages: None,
+        chunks: None,
+        images: None,
+        djot_content: None,
+        scripts: None,
+        pages: None,
+        elements: None,
+    };
+    let config = ExtractionConfig {
+        enable_quality_processing: false,
+        output: Some(crate::core::config::OutputConfig {
+            max_chars: Some(50),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    let processed = run_pipeline(result, &config).await.unwrap();
+    assert_eq!(processed.content, "First paragraph of the report.");
+    assert_eq!(processed.metadata.word_count, Some(12));
+    assert_eq!(
+        processed.metadata.additional["truncation_warnings"],
+        serde_json::json!(["Content truncated to 30 of 71 characters"])
+    );
+}
+
 #[tokio::test]
 async fn test_pipeline_without_chunking() {
     let result = ExtractionResult {
@@ -131,6 +636,7 @@ async fn test_pipeline_without_chunking() {
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         pages: None,
         elements: None,
     };
@@ -163,6 +669,7 @@ async fn test_pipeline_preserves_metadata() {
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -197,6 +704,7 @@ async fn test_pipeline_preserves_tables() {
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         pages: None,
         elements: None,
     };
@@ -229,6 +737,7 @@ async fn test_pipeline_empty_content() {
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         pages: None,
         elements: None,
     };
@@ -252,6 +761,7 @@ async fn test_pipeline_with_all_features() {
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         pages: None,
         elements: None,
     };
@@ -264,6 +774,10 @@ async fn test_pipeline_with_all_features() {
             chunker_type: crate::ChunkerType::Text,
             embedding: None,
             preset: None,
+            overlap_strategy: crate::OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
         }),
         ..Default::default()
     };
@@ -307,6 +821,7 @@ Natural language processing enables computers to understand human language.
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         pages: None,
         elements: None,
     };
@@ -354,6 +869,7 @@ async fn test_pipeline_without_keyword_config() {
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         pages: None,
         elements: None,
     };
@@ -392,6 +908,7 @@ async fn test_pipeline_keyword_extraction_short_content() {
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         pages: None,
         elements: None,
     };
@@ -529,6 +1046,7 @@ async fn test_postprocessor_runs_before_validator() {
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         pages: None,
         elements: None,
     };
@@ -626,6 +1144,7 @@ async fn test_quality_processing_runs_before_validator() {
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         pages: None,
         elements: None,
     };
@@ -824,6 +1343,7 @@ async fn test_multiple_postprocessors_run_before_validator() {
         chunks: None,
         images: None,
         djot_content: None,
+        scripts: None,
         pages: None,
         elements: None,
     };
@@ -852,6 +1372,7 @@ async fn test_run_pipeline_with_output_format_plain() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
 
@@ -878,6 +1399,7 @@ async fn test_run_pipeline_with_output_format_djot() {
         images: None,
         pages: None,
         elements: None,
+        scripts: None,
         djot_content: Some(DjotContent {
             plain_text: "test content".to_string(),
             blocks: vec![FormattedBlock {
@@ -925,6 +1447,7 @@ async fn test_run_pipeline_with_output_format_html() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
 
@@ -955,6 +1478,7 @@ async fn test_run_pipeline_applies_output_format_last() {
         images: None,
         pages: None,
         elements: None,
+        scripts: None,
         djot_content: Some(DjotContent {
             plain_text: "test".to_string(),
             blocks: vec![],
@@ -978,3 +1502,87 @@ async fn test_run_pipeline_applies_output_format_last() {
     // The result should have gone through the pipeline successfully
     assert!(processed.djot_content.is_some());
 }
+
+#[tokio::test]
+async fn test_plugin_guard_skips_repeatedly_failing_post_processor() {
+    use crate::plugins::{Plugin, PostProcessor, ProcessingStage};
+    use async_trait::async_trait;
+    use std::sync::Arc;
+
+    struct FlakyProcessor;
+
+    impl Plugin for FlakyProcessor {
+        fn name(&self) -> &str {
+            "pipeline-guard-flaky"
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl PostProcessor for FlakyProcessor {
+        async fn process(&self, _result: &mut ExtractionResult, _config: &ExtractionConfig) -> Result<()> {
+            Err(crate::KreuzbergError::Other("backend unavailable".to_string()))
+        }
+
+        fn processing_stage(&self) -> ProcessingStage {
+            ProcessingStage::Middle
+        }
+    }
+
+    let config = ExtractionConfig {
+        plugin_guard: Some(crate::core::config::PluginGuardConfig {
+            failure_threshold: 2,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let processors: Arc<Vec<Arc<dyn PostProcessor>>> = Arc::new(vec![Arc::new(FlakyProcessor)]);
+
+    let mut runs = Vec::new();
+    for _ in 0..3 {
+        let mut result = ExtractionResult {
+            content: "text".to_string(),
+            mime_type: Cow::Borrowed("text/plain"),
+            metadata: Metadata::default(),
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            scripts: None,
+            pages: None,
+            elements: None,
+        };
+        execute_processors(
+            &mut result,
+            &config,
+            &None,
+            Arc::new(Vec::new()),
+            Arc::clone(&processors),
+            Arc::new(Vec::new()),
+            &mut ReportRecorder::new(&config),
+        )
+        .await
+        .unwrap();
+        runs.push(result.metadata.additional);
+    }
+
+    assert!(runs[1].contains_key("processing_error_pipeline-guard-flaky"));
+    assert!(!runs[1].contains_key("plugin_warnings"));
+    assert!(!runs[2].contains_key("processing_error_pipeline-guard-flaky"));
+    let warnings = runs[2]["plugin_warnings"].as_array().unwrap();
+    assert!(
+        warnings[0]
+            .as_str()
+            .unwrap()
+            .starts_with("Post-processor 'pipeline-guard-flaky' skipped")
+    );
+}
@@ -15,6 +15,7 @@ use crate::{KreuzbergError, Result};
 /// - `KREUZBERG_MAX_REQUEST_BODY_BYTES` - Max request body size in bytes
 /// - `KREUZBERG_MAX_MULTIPART_FIELD_BYTES` - Max multipart field size in bytes
 /// - `KREUZBERG_MAX_UPLOAD_SIZE_MB` - Max upload size in MB (legacy)
+/// - `KREUZBERG_JOBS_SIGNING_KEY` - Key of the job callback signatures
 ///
 /// # Errors
 ///
@@ -30,6 +31,7 @@ pub fn apply_env_overrides(
     max_request_body_bytes: &mut usize,
     max_multipart_field_bytes: &mut usize,
     max_upload_mb: &mut Option<usize>,
+    jobs_signing_key: &mut Option<String>,
 ) -> Result<()> {
     // Host override
     if let Ok(env_host) = std::env::var("KREUZBERG_HOST") {
@@ -86,5 +88,10 @@ pub fn apply_env_overrides(
         *max_upload_mb = Some(mb);
     }
 
+    // Job callback signing key override, kept out of config files
+    if let Ok(secret) = std::env::var("KREUZBERG_JOBS_SIGNING_KEY") {
+        *jobs_signing_key = Some(secret);
+    }
+
     Ok(())
 }
@@ -0,0 +1,74 @@
+//! Async extraction jobs of the API server.
+//!
+//! `POST /jobs` queues an extraction and returns at once. When the job finishes,
+//! its result or error is POSTed to the callback URL of the request, signed with
+//! HMAC-SHA256 when a signing key is configured.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{KreuzbergError, Result};
+
+/// Settings of async jobs and their result callbacks.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JobsConfig {
+    /// Key of the HMAC-SHA256 `X-Kreuzberg-Signature` header of callbacks (None = unsigned callbacks)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing_key: Option<String>,
+
+    /// Hosts callbacks may be sent to. Empty vector means any host.
+    pub callback_hosts: Vec<String>,
+
+    /// Delivery attempts of a callback, retried with exponential backoff. Default: 3
+    pub callback_attempts: u32,
+
+    /// Timeout of a callback request in seconds. Default: 30
+    pub callback_timeout_secs: u64,
+
+    /// Seconds a finished job stays available at `GET /jobs/{id}`. Default: 3600
+    pub retention_secs: u64,
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        Self {
+            signing_key: None,
+            callback_hosts: Vec::new(),
+            callback_attempts: 3,
+            callback_timeout_secs: 30,
+            retention_secs: 3600,
+        }
+    }
+}
+
+impl fmt::Debug for JobsConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JobsConfig")
+            .field("signing_key", &self.signing_key.as_ref().map(|_| "[redacted]"))
+            .field("callback_hosts", &self.callback_hosts)
+            .field("callback_attempts", &self.callback_attempts)
+            .field("callback_timeout_secs", &self.callback_timeout_secs)
+            .field("retention_secs", &self.retention_secs)
+            .finish()
+    }
+}
+
+/// Check that callbacks are attempted at least once and their timeout is positive.
+pub(super) fn validate_jobs(jobs: &JobsConfig) -> Result<()> {
+    if jobs.callback_attempts == 0 {
+        return Err(KreuzbergError::validation(
+            "jobs.callback_attempts must be greater than 0",
+        ));
+    }
+    if jobs.callback_timeout_secs == 0 {
+        return Err(KreuzbergError::validation(
+            "jobs.callback_timeout_secs must be greater than 0",
+        ));
+    }
+    if jobs.signing_key.as_ref().is_some_and(|secret| secret.is_empty()) {
+        return Err(KreuzbergError::validation("jobs.signing_key must not be empty"));
+    }
+    Ok(())
+}
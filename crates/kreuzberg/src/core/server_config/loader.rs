@@ -57,6 +57,7 @@ pub fn from_file(path: impl AsRef<Path>) -> Result<ServerConfig> {
 
     // Normalize legacy fields
     config.normalize_legacy_fields();
+    config.validate()?;
 
     Ok(config)
 }
@@ -80,6 +81,7 @@ pub fn from_toml_file(path: impl AsRef<Path>) -> Result<ServerConfig> {
         .map_err(|e| KreuzbergError::validation(format!("Invalid TOML in {}: {}", path.display(), e)))?;
 
     config.normalize_legacy_fields();
+    config.validate()?;
 
     Ok(config)
 }
@@ -103,6 +105,7 @@ pub fn from_yaml_file(path: impl AsRef<Path>) -> Result<ServerConfig> {
         .map_err(|e| KreuzbergError::validation(format!("Invalid YAML in {}: {}", path.display(), e)))?;
 
     config.normalize_legacy_fields();
+    config.validate()?;
 
     Ok(config)
 }
@@ -126,6 +129,7 @@ pub fn from_json_file(path: impl AsRef<Path>) -> Result<ServerConfig> {
         .map_err(|e| KreuzbergError::validation(format!("Invalid JSON in {}: {}", path.display(), e)))?;
 
     config.normalize_legacy_fields();
+    config.validate()?;
 
     Ok(config)
 }
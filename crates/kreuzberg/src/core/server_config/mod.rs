@@ -11,6 +11,8 @@
 //! - **Backward compatibility**: Supports legacy `max_upload_mb` field for smooth migrations
 //! - **Sensible defaults**: All fields have reasonable defaults matching current behavior
 //! - **Flexible CORS**: Support for all origins (default) or specific origin lists
+//! - **Tenants**: Optional API keys with per-tenant extraction config and quotas
+//! - **Jobs**: Signed result callbacks of async extraction jobs
 //!
 //! # Example
 //!
@@ -36,9 +38,14 @@ use serde::{Deserialize, Serialize};
 use std::path::Path;
 
 mod env;
+mod jobs;
 mod loader;
+mod tenants;
 mod validation;
 
+pub use jobs::JobsConfig;
+pub use tenants::{ApiQuota, ApiTenant};
+
 #[cfg(test)]
 mod tests;
 
@@ -67,6 +74,8 @@ const DEFAULT_MAX_MULTIPART_FIELD_BYTES: usize = 104_857_600;
 /// - `max_request_body_bytes`: 104_857_600 (100 MB)
 /// - `max_multipart_field_bytes`: 104_857_600 (100 MB)
 /// - `max_upload_mb`: None (legacy field, not used if other fields set)
+/// - `tenants`: empty vector (no authentication)
+/// - `jobs`: unsigned callbacks to any host, 3 attempts, results kept for 1 hour
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct ServerConfig {
@@ -101,6 +110,18 @@ pub struct ServerConfig {
     /// New configurations should use `max_multipart_field_bytes` directly.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_upload_mb: Option<usize>,
+
+    /// Tenants allowed to use the server. Empty vector means no authentication.
+    ///
+    /// If tenants are configured, every request except `/health` and `/metrics` must
+    /// carry the API key of a tenant, and is handled with the tenant's extraction
+    /// configuration and quotas.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tenants: Vec<ApiTenant>,
+
+    /// Async jobs (`POST /jobs`) and the delivery of their result callbacks.
+    #[serde(default)]
+    pub jobs: JobsConfig,
 }
 
 impl Default for ServerConfig {
@@ -112,6 +133,8 @@ impl Default for ServerConfig {
             max_request_body_bytes: default_max_request_body_bytes(),
             max_multipart_field_bytes: default_max_multipart_field_bytes(),
             max_upload_mb: None,
+            tenants: Vec::new(),
+            jobs: JobsConfig::default(),
         }
     }
 }
@@ -238,6 +261,19 @@ impl ServerConfig {
         validation::normalize_legacy_fields(self.max_upload_mb, &mut self.max_multipart_field_bytes);
     }
 
+    /// Check the tenants for missing or duplicate names and API keys, and zero quotas,
+    /// and the job settings for zero callback attempts or timeouts.
+    ///
+    /// This method is automatically called when loading the configuration from a file.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` describing the first invalid setting.
+    pub fn validate(&self) -> Result<()> {
+        tenants::validate_tenants(&self.tenants)?;
+        jobs::validate_jobs(&self.jobs)
+    }
+
     /// Apply environment variable overrides to the configuration.
     ///
     /// Reads the following environment variables and overrides config values if set:
@@ -248,6 +284,7 @@ impl ServerConfig {
     /// - `KREUZBERG_MAX_REQUEST_BODY_BYTES` - Max request body size in bytes
     /// - `KREUZBERG_MAX_MULTIPART_FIELD_BYTES` - Max multipart field size in bytes
     /// - `KREUZBERG_MAX_UPLOAD_SIZE_MB` - Max upload size in MB (legacy)
+    /// - `KREUZBERG_JOBS_SIGNING_KEY` - Key of the job callback signatures
     ///
     /// # Errors
     ///
@@ -284,6 +321,7 @@ impl ServerConfig {
             &mut self.max_request_body_bytes,
             &mut self.max_multipart_field_bytes,
             &mut self.max_upload_mb,
+            &mut self.jobs.signing_key,
         )?;
 
         // Apply legacy field normalization
@@ -0,0 +1,100 @@
+//! Tenants of the API server.
+//!
+//! When tenants are configured, every request must carry the API key of a tenant.
+//! The key selects the extraction configuration, the quotas and the metrics label
+//! of the request.
+
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::core::config::ExtractionConfig;
+use crate::{KreuzbergError, Result};
+
+/// A tenant of the API server, identified by its API keys.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ApiTenant {
+    /// Tenant name, used as the `tenant` label of metrics and traces
+    pub name: String,
+
+    /// API keys of the tenant, sent as `X-API-Key: <key>` or `Authorization: Bearer <key>`
+    pub api_keys: Vec<String>,
+
+    /// Extraction configuration of the tenant's requests, replacing the server's default
+    /// configuration (None = server default). Per-request configs still override it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub config: Option<ExtractionConfig>,
+
+    /// Limits applied to each API key of the tenant
+    #[serde(default)]
+    pub quota: ApiQuota,
+}
+
+impl fmt::Debug for ApiTenant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ApiTenant")
+            .field("name", &self.name)
+            .field("api_keys", &format_args!("[{} redacted]", self.api_keys.len()))
+            .field("config", &self.config)
+            .field("quota", &self.quota)
+            .finish()
+    }
+}
+
+/// Request limits of an API key.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ApiQuota {
+    /// Requests per minute, refilled continuously (None = unlimited)
+    pub requests_per_minute: Option<u32>,
+
+    /// Requests handled at the same time (None = unlimited)
+    pub max_concurrent_requests: Option<u32>,
+}
+
+/// Check that tenant names and API keys are present and unique, and quotas are positive.
+pub(super) fn validate_tenants(tenants: &[ApiTenant]) -> Result<()> {
+    let mut names = HashSet::new();
+    let mut keys = HashSet::new();
+
+    for tenant in tenants {
+        if tenant.name.trim().is_empty() {
+            return Err(KreuzbergError::validation("Tenant names must not be empty"));
+        }
+        if !names.insert(tenant.name.as_str()) {
+            return Err(KreuzbergError::validation(format!(
+                "Duplicate tenant name '{}'",
+                tenant.name
+            )));
+        }
+        if tenant.api_keys.is_empty() {
+            return Err(KreuzbergError::validation(format!(
+                "Tenant '{}' has no API keys",
+                tenant.name
+            )));
+        }
+        for key in &tenant.api_keys {
+            if key.trim().is_empty() {
+                return Err(KreuzbergError::validation(format!(
+                    "Tenant '{}' has an empty API key",
+                    tenant.name
+                )));
+            }
+            if !keys.insert(key.as_str()) {
+                return Err(KreuzbergError::validation(format!(
+                    "An API key of tenant '{}' is used more than once",
+                    tenant.name
+                )));
+            }
+        }
+        if tenant.quota.requests_per_minute == Some(0) || tenant.quota.max_concurrent_requests == Some(0) {
+            return Err(KreuzbergError::validation(format!(
+                "Quotas of tenant '{}' must be greater than 0",
+                tenant.name
+            )));
+        }
+    }
+
+    Ok(())
+}
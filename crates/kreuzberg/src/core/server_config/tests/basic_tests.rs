@@ -122,3 +122,72 @@ fn test_serde_with_max_upload_mb_serialization() {
     // Should serialize with max_upload_mb when Some
     assert!(json.contains("max_upload_mb"));
 }
+
+#[test]
+fn test_validate_tenants() {
+    use crate::core::{ApiQuota, ApiTenant};
+
+    let tenant = ApiTenant {
+        name: "search".to_string(),
+        api_keys: vec!["secret".to_string()],
+        config: None,
+        quota: ApiQuota::default(),
+    };
+    let mut config = ServerConfig {
+        tenants: vec![tenant.clone()],
+        ..Default::default()
+    };
+    assert!(config.validate().is_ok());
+    assert!(!format!("{:?}", config).contains("secret"));
+
+    config.tenants.push(ApiTenant {
+        api_keys: vec!["other".to_string()],
+        ..tenant.clone()
+    });
+    assert!(
+        config
+            .validate()
+            .unwrap_err()
+            .to_string()
+            .contains("Duplicate tenant name")
+    );
+
+    config.tenants = vec![ApiTenant {
+        api_keys: Vec::new(),
+        ..tenant.clone()
+    }];
+    assert!(config.validate().unwrap_err().to_string().contains("no API keys"));
+
+    config.tenants = vec![ApiTenant {
+        quota: ApiQuota {
+            requests_per_minute: Some(0),
+            max_concurrent_requests: None,
+        },
+        ..tenant
+    }];
+    assert!(config.validate().is_err());
+}
+
+#[test]
+fn test_validate_jobs() {
+    use crate::core::JobsConfig;
+
+    let mut config = ServerConfig {
+        jobs: JobsConfig {
+            signing_key: Some("signing-secret".to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    assert!(config.validate().is_ok());
+    assert!(!format!("{:?}", config).contains("signing-secret"));
+
+    config.jobs.callback_attempts = 0;
+    assert!(config.validate().unwrap_err().to_string().contains("callback_attempts"));
+
+    config.jobs = JobsConfig {
+        signing_key: Some(String::new()),
+        ..Default::default()
+    };
+    assert!(config.validate().is_err());
+}
@@ -214,3 +214,26 @@ fn test_apply_env_multiple_overrides() {
         }
     }
 }
+
+#[serial_test::serial]
+#[test]
+fn test_apply_env_jobs_signing_key_override() {
+    let original = std::env::var("KREUZBERG_JOBS_SIGNING_KEY").ok();
+    unsafe {
+        std::env::set_var("KREUZBERG_JOBS_SIGNING_KEY", "from-env");
+    }
+
+    let mut config = ServerConfig::default();
+    config.apply_env_overrides().unwrap();
+
+    assert_eq!(config.jobs.signing_key.as_deref(), Some("from-env"));
+
+    // Cleanup
+    unsafe {
+        if let Some(orig) = original {
+            std::env::set_var("KREUZBERG_JOBS_SIGNING_KEY", orig);
+        } else {
+            std::env::remove_var("KREUZBERG_JOBS_SIGNING_KEY");
+        }
+    }
+}
@@ -339,3 +339,87 @@ port = 6000
     assert_eq!(config.host, "192.168.1.1");
     assert_eq!(config.port, 6000);
 }
+
+#[test]
+fn test_from_file_with_tenants() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("kreuzberg.toml");
+
+    fs::write(
+        &config_path,
+        r#"
+[server]
+port = 3000
+
+[[server.tenants]]
+name = "search"
+api_keys = ["key-a", "key-b"]
+quota = { requests_per_minute = 120, max_concurrent_requests = 4 }
+
+[server.tenants.config]
+use_cache = false
+
+[[server.tenants]]
+name = "archive"
+api_keys = ["key-c"]
+        "#,
+    )
+    .unwrap();
+
+    let config = ServerConfig::from_file(&config_path).unwrap();
+    assert_eq!(config.tenants.len(), 2);
+    assert_eq!(config.tenants[0].name, "search");
+    assert_eq!(config.tenants[0].api_keys, vec!["key-a", "key-b"]);
+    assert_eq!(config.tenants[0].quota.requests_per_minute, Some(120));
+    assert_eq!(config.tenants[0].quota.max_concurrent_requests, Some(4));
+    assert!(!config.tenants[0].config.as_ref().unwrap().use_cache);
+    assert!(config.tenants[1].config.is_none());
+    assert_eq!(config.tenants[1].quota, Default::default());
+}
+
+#[test]
+fn test_from_file_rejects_duplicate_api_keys() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("kreuzberg.toml");
+
+    fs::write(
+        &config_path,
+        r#"
+[[tenants]]
+name = "search"
+api_keys = ["shared"]
+
+[[tenants]]
+name = "archive"
+api_keys = ["shared"]
+        "#,
+    )
+    .unwrap();
+
+    let error = ServerConfig::from_file(&config_path).unwrap_err();
+    assert!(error.to_string().contains("more than once"));
+}
+
+#[test]
+fn test_from_file_with_jobs() {
+    let dir = tempdir().unwrap();
+    let config_path = dir.path().join("kreuzberg.toml");
+
+    fs::write(
+        &config_path,
+        r#"
+[server.jobs]
+callback_hosts = ["hooks.example.com"]
+callback_attempts = 5
+retention_secs = 600
+        "#,
+    )
+    .unwrap();
+
+    let config = ServerConfig::from_file(&config_path).unwrap();
+    assert_eq!(config.jobs.callback_hosts, vec!["hooks.example.com"]);
+    assert_eq!(config.jobs.callback_attempts, 5);
+    assert_eq!(config.jobs.callback_timeout_secs, 30);
+    assert_eq!(config.jobs.retention_secs, 600);
+    assert!(config.jobs.signing_key.is_none());
+}
@@ -0,0 +1,365 @@
+//! Temporary workspaces.
+//!
+//! The intermediate files of an extraction (archive text spilled to disk, documents
+//! handed to LibreOffice and their conversions) are created in a [`TempWorkspace`], a
+//! directory of its own under a configurable base directory. The directory is created
+//! on first use and removed with everything in it when the workspace is dropped, which
+//! also happens when the extraction panics or its future is cancelled.
+//!
+//! A process that is killed cannot clean up after itself. Workspace directories are
+//! named after the process that created them, so the first workspace a process creates
+//! in a base directory removes the workspaces of processes that no longer run.
+
+use crate::core::config::IoConfig;
+use crate::{KreuzbergError, Result};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Environment variable setting the base directory of workspaces when `io.temp_dir` is
+/// not configured.
+pub const TEMP_DIR_ENV: &str = "KREUZBERG_TEMP_DIR";
+
+/// Prefix of workspace directories, followed by the process ID and a random suffix.
+const WORKSPACE_PREFIX: &str = "kreuzberg-ws-";
+
+/// Directory of the intermediate files of one extraction, removed when dropped.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::core::config::IoConfig;
+/// use kreuzberg::core::temp::TempWorkspace;
+///
+/// let base = std::env::temp_dir().join("kreuzberg-doc-example");
+/// let config = IoConfig {
+///     temp_dir: Some(base.clone()),
+///     temp_quota_bytes: Some(1024),
+///     ..Default::default()
+/// };
+///
+/// let workspace = TempWorkspace::new(Some(&config));
+/// let input = workspace.write_file("input.doc", b"legacy document")?;
+/// assert!(input.starts_with(&base));
+/// assert!(workspace.write_file("large.bin", &[0; 2048]).is_err());
+///
+/// let dir = workspace.path()?.to_path_buf();
+/// drop(workspace);
+/// assert!(!dir.exists());
+/// # Ok::<(), kreuzberg::KreuzbergError>(())
+/// ```
+#[derive(Debug)]
+pub struct TempWorkspace {
+    base: PathBuf,
+    dir: OnceLock<PathBuf>,
+    quota: Option<u64>,
+    used: AtomicU64,
+}
+
+impl TempWorkspace {
+    /// A workspace under the base directory of `config`. Nothing is created until the
+    /// workspace is first used.
+    pub fn new(config: Option<&IoConfig>) -> Self {
+        let base = config
+            .and_then(|io| io.temp_dir.clone())
+            .or_else(|| {
+                std::env::var_os(TEMP_DIR_ENV)
+                    .filter(|dir| !dir.is_empty())
+                    .map(PathBuf::from)
+            })
+            .unwrap_or_else(std::env::temp_dir);
+        Self {
+            base,
+            dir: OnceLock::new(),
+            quota: config.and_then(|io| io.temp_quota_bytes),
+            used: AtomicU64::new(0),
+        }
+    }
+
+    /// Base directory the workspace is created in.
+    pub fn base_dir(&self) -> &Path {
+        &self.base
+    }
+
+    /// Directory of the workspace, created on first use.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Io` if the directory cannot be created.
+    pub fn path(&self) -> Result<&Path> {
+        if let Some(dir) = self.dir.get() {
+            return Ok(dir);
+        }
+        std::fs::create_dir_all(&self.base)?;
+        sweep_once(&self.base);
+        let dir = self.base.join(format!(
+            "{}{}-{}",
+            WORKSPACE_PREFIX,
+            std::process::id(),
+            uuid::Uuid::new_v4().simple()
+        ));
+        std::fs::create_dir(&dir)?;
+        if let Err(dir) = self.dir.set(dir) {
+            // Created concurrently by another thread; keep theirs.
+            let _ = std::fs::remove_dir(dir);
+        }
+        Ok(self.dir.get().map(PathBuf::as_path).unwrap_or(&self.base))
+    }
+
+    /// Create an empty directory in the workspace whose name starts with `prefix`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Io` if the directory cannot be created.
+    pub fn create_dir(&self, prefix: &str) -> Result<PathBuf> {
+        let dir = self
+            .path()?
+            .join(format!("{}-{}", prefix, uuid::Uuid::new_v4().simple()));
+        std::fs::create_dir(&dir)?;
+        Ok(dir)
+    }
+
+    /// Write `contents` to the file `name` in the workspace, counting it against the
+    /// quota.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Io` if the quota would be exceeded (`QuotaExceeded`) or
+    /// the file cannot be written.
+    pub fn write_file(&self, name: &str, contents: &[u8]) -> Result<PathBuf> {
+        self.charge(contents.len() as u64)?;
+        let path = self.path()?.join(name);
+        std::fs::write(&path, contents)?;
+        Ok(path)
+    }
+
+    /// An anonymous file in the workspace, removed when closed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Io` if the file cannot be created.
+    pub fn tempfile(&self) -> Result<std::fs::File> {
+        Ok(tempfile::tempfile_in(self.path()?)?)
+    }
+
+    /// Count `bytes` about to be written to the workspace against the quota.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Io` (`QuotaExceeded`) if the workspace would exceed its
+    /// quota; the bytes are not counted then.
+    pub fn charge(&self, bytes: u64) -> Result<()> {
+        let Some(quota) = self.quota else {
+            self.used.fetch_add(bytes, Ordering::Relaxed);
+            return Ok(());
+        };
+        self.used
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |used| {
+                used.checked_add(bytes).filter(|total| *total <= quota)
+            })
+            .map(|_| ())
+            .map_err(|used| quota_exceeded(used.saturating_add(bytes), quota))
+    }
+
+    /// Bytes counted against the quota.
+    pub fn used_bytes(&self) -> u64 {
+        self.used.load(Ordering::Relaxed)
+    }
+
+    /// Measure the files in the workspace, for files written by other processes, and
+    /// check them against the quota.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Io` (`QuotaExceeded`) if the files take more than the
+    /// quota.
+    pub fn check_usage(&self) -> Result<u64> {
+        let Some(dir) = self.dir.get() else {
+            return Ok(0);
+        };
+        let size = dir_size(dir);
+        self.used.fetch_max(size, Ordering::Relaxed);
+        match self.quota {
+            Some(quota) if size > quota => Err(quota_exceeded(size, quota)),
+            _ => Ok(size),
+        }
+    }
+}
+
+impl Drop for TempWorkspace {
+    fn drop(&mut self) {
+        if let Some(dir) = self.dir.get()
+            && let Err(e) = std::fs::remove_dir_all(dir)
+            && e.kind() != std::io::ErrorKind::NotFound
+        {
+            tracing::warn!("Failed to remove temporary workspace {}: {}", dir.display(), e);
+        }
+    }
+}
+
+/// Remove the workspaces in `base` left behind by processes that no longer run,
+/// returning how many were removed.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Io` if `base` cannot be read.
+pub fn remove_orphaned_workspaces(base: &Path) -> Result<usize> {
+    let mut removed = 0;
+    for entry in std::fs::read_dir(base)?.flatten() {
+        let name = entry.file_name();
+        let Some(pid) = name
+            .to_str()
+            .and_then(|name| name.strip_prefix(WORKSPACE_PREFIX))
+            .and_then(|rest| rest.split_once('-'))
+            .and_then(|(pid, _)| pid.parse::<u32>().ok())
+        else {
+            continue;
+        };
+        if pid == std::process::id() || process_is_running(pid) {
+            continue;
+        }
+        match std::fs::remove_dir_all(entry.path()) {
+            Ok(()) => removed += 1,
+            Err(e) => tracing::debug!("Failed to remove orphaned workspace {}: {}", entry.path().display(), e),
+        }
+    }
+    if removed > 0 {
+        tracing::info!(
+            "Removed {} orphaned temporary workspaces in {}",
+            removed,
+            base.display()
+        );
+    }
+    Ok(removed)
+}
+
+/// Remove the orphaned workspaces of `base` the first time it is used by this process.
+fn sweep_once(base: &Path) {
+    static SWEPT: parking_lot::Mutex<Vec<PathBuf>> = parking_lot::Mutex::new(Vec::new());
+
+    let mut swept = SWEPT.lock();
+    if swept.iter().any(|dir| dir == base) {
+        return;
+    }
+    swept.push(base.to_path_buf());
+    drop(swept);
+    if let Err(e) = remove_orphaned_workspaces(base) {
+        tracing::debug!("Failed to look for orphaned workspaces in {}: {}", base.display(), e);
+    }
+}
+
+#[cfg(unix)]
+fn process_is_running(pid: u32) -> bool {
+    let Ok(pid) = libc::pid_t::try_from(pid) else {
+        return false;
+    };
+    // Signal 0 only checks whether the process exists; EPERM means it exists but
+    // belongs to another user.
+    // SAFETY: kill with signal 0 sends no signal and has no memory safety requirements.
+    #[allow(unsafe_code)]
+    let result = unsafe { libc::kill(pid, 0) };
+    result == 0 || std::io::Error::last_os_error().raw_os_error() == Some(libc::EPERM)
+}
+
+#[cfg(not(unix))]
+fn process_is_running(_pid: u32) -> bool {
+    // Without a portable liveness check, workspaces of other processes are kept.
+    true
+}
+
+fn dir_size(dir: &Path) -> u64 {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return 0;
+    };
+    entries
+        .flatten()
+        .map(|entry| match entry.file_type() {
+            Ok(kind) if kind.is_dir() => dir_size(&entry.path()),
+            Ok(_) => entry.metadata().map_or(0, |metadata| metadata.len()),
+            Err(_) => 0,
+        })
+        .sum()
+}
+
+fn quota_exceeded(bytes: u64, quota: u64) -> KreuzbergError {
+    KreuzbergError::Io(std::io::Error::new(
+        std::io::ErrorKind::QuotaExceeded,
+        format!(
+            "Intermediate files would take {} bytes, more than io.temp_quota_bytes ({} bytes)",
+            bytes, quota
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn config(base: &Path, quota: Option<u64>) -> IoConfig {
+        IoConfig {
+            temp_dir: Some(base.to_path_buf()),
+            temp_quota_bytes: quota,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_workspace_is_removed_on_drop_and_panic() {
+        let base = tempdir().unwrap();
+        let workspace = TempWorkspace::new(Some(&config(base.path(), None)));
+        assert_eq!(std::fs::read_dir(base.path()).unwrap().count(), 0);
+
+        let dir = workspace.path().unwrap().to_path_buf();
+        let nested = workspace.create_dir("lo_profile").unwrap();
+        std::fs::write(nested.join("registry.xcd"), b"profile").unwrap();
+        workspace.write_file("input.doc", b"document").unwrap();
+        assert_eq!(workspace.check_usage().unwrap(), 15);
+        drop(workspace);
+        assert!(!dir.exists());
+
+        let config = config(base.path(), None);
+        let panicked = std::panic::catch_unwind(|| {
+            let workspace = TempWorkspace::new(Some(&config));
+            workspace.write_file("input.ppt", b"slides").unwrap();
+            panic!("extraction failed");
+        });
+        assert!(panicked.is_err());
+        assert_eq!(std::fs::read_dir(base.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_workspace_quota() {
+        let base = tempdir().unwrap();
+        let workspace = TempWorkspace::new(Some(&config(base.path(), Some(10))));
+        workspace.write_file("a", b"12345").unwrap();
+        workspace.charge(5).unwrap();
+        let error = workspace.write_file("b", b"1").unwrap_err();
+        assert!(matches!(error, KreuzbergError::Io(ref e) if e.kind() == std::io::ErrorKind::QuotaExceeded));
+        assert_eq!(workspace.used_bytes(), 10);
+
+        std::fs::write(workspace.path().unwrap().join("converted"), [0u8; 16]).unwrap();
+        assert!(workspace.check_usage().is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_orphaned_workspaces_are_removed() {
+        let base = tempdir().unwrap();
+        // PIDs above the kernel's pid_max (at most 2^22) are never running.
+        let orphan = base.path().join(format!("{}4194999-abc", WORKSPACE_PREFIX));
+        std::fs::create_dir(&orphan).unwrap();
+        std::fs::write(orphan.join("spill"), b"text").unwrap();
+        let own = base
+            .path()
+            .join(format!("{}{}-abc", WORKSPACE_PREFIX, std::process::id()));
+        std::fs::create_dir(&own).unwrap();
+        let unrelated = base.path().join("kreuzberg-cache");
+        std::fs::create_dir(&unrelated).unwrap();
+
+        assert_eq!(remove_orphaned_workspaces(base.path()).unwrap(), 1);
+        assert!(!orphan.exists());
+        assert!(own.exists());
+        assert!(unrelated.exists());
+    }
+}
@@ -0,0 +1,199 @@
+//! Worker thread pools for CPU-bound work.
+//!
+//! OCR backends run on Tokio's blocking pool by default, which grows to hundreds of
+//! threads and lets them run on any core. With an
+//! [`OcrConfig::thread_pool`](crate::core::config::OcrConfig) they run on a dedicated
+//! pool instead, with a fixed number of threads confined to the configured cores and
+//! running at the configured nice level. Pools are created on first use and shared by
+//! all extractions with the same [`ThreadPoolConfig`].
+
+use crate::core::config::ThreadPoolConfig;
+use crate::{KreuzbergError, Result};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use rayon::ThreadPool;
+use std::collections::HashMap;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::sync::Arc;
+
+static POOLS: Lazy<Mutex<HashMap<ThreadPoolConfig, Arc<ThreadPool>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// The worker pool of `config`, created on first use.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Validation` if `config` is invalid, or
+/// `KreuzbergError::Other` if the worker threads cannot be started.
+pub fn thread_pool(config: &ThreadPoolConfig) -> Result<Arc<ThreadPool>> {
+    let mut pools = POOLS.lock();
+    if let Some(pool) = pools.get(config) {
+        return Ok(Arc::clone(pool));
+    }
+
+    config.validate()?;
+    let worker_config = config.clone();
+    let pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(config.effective_threads())
+        .thread_name(|index| format!("kreuzberg-worker-{}", index))
+        .start_handler(move |index| configure_worker(&worker_config, index))
+        .build()
+        .map_err(|e| KreuzbergError::Other(format!("Failed to start worker threads: {}", e)))?;
+    let pool = Arc::new(pool);
+    pools.insert(config.clone(), Arc::clone(&pool));
+    Ok(pool)
+}
+
+/// Run the blocking function `f` on the pool of `config`, or on Tokio's blocking pool
+/// without one.
+///
+/// # Errors
+///
+/// Returns the errors of [`thread_pool`], or `KreuzbergError::Other` if `f` panics.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::core::config::ThreadPoolConfig;
+/// use kreuzberg::core::thread_pool::run_blocking;
+///
+/// # #[tokio::main]
+/// # async fn main() -> kreuzberg::Result<()> {
+/// let config = ThreadPoolConfig {
+///     threads: Some(2),
+///     ..Default::default()
+/// };
+/// let sum = run_blocking(Some(&config), || (1..=10).sum::<u32>()).await?;
+/// assert_eq!(sum, 55);
+/// # Ok(())
+/// # }
+/// ```
+pub async fn run_blocking<F, R>(config: Option<&ThreadPoolConfig>, f: F) -> Result<R>
+where
+    F: FnOnce() -> R + Send + 'static,
+    R: Send + 'static,
+{
+    let Some(config) = config else {
+        return tokio::task::spawn_blocking(f)
+            .await
+            .map_err(|e| KreuzbergError::Other(format!("Worker task failed: {}", e)));
+    };
+
+    let pool = thread_pool(config)?;
+    let (sender, receiver) = tokio::sync::oneshot::channel();
+    pool.spawn(move || {
+        let _ = sender.send(catch_unwind(AssertUnwindSafe(f)));
+    });
+    match receiver.await {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(panic)) => {
+            let message = panic
+                .downcast_ref::<&str>()
+                .map(|message| message.to_string())
+                .or_else(|| panic.downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            Err(KreuzbergError::Other(format!("Worker task panicked: {}", message)))
+        }
+        Err(_) => Err(KreuzbergError::Other("Worker task was dropped".to_string())),
+    }
+}
+
+/// Apply the core affinity and nice level of `config` to the calling worker thread.
+#[cfg(target_os = "linux")]
+#[allow(unsafe_code)]
+fn configure_worker(config: &ThreadPoolConfig, index: usize) {
+    if !config.cpu_affinity.is_empty() {
+        let cores = if config.pin_threads {
+            std::slice::from_ref(&config.cpu_affinity[index % config.cpu_affinity.len()])
+        } else {
+            config.cpu_affinity.as_slice()
+        };
+        // SAFETY: `cpu_set_t` is a plain bit set, for which all zeroes is the empty set.
+        let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+        for &core in cores.iter().filter(|&&core| core < libc::CPU_SETSIZE as usize) {
+            // SAFETY: `core` is within the bounds of the set.
+            unsafe { libc::CPU_SET(core, &mut set) };
+        }
+        // SAFETY: `sched_setaffinity` only reads `set`, which outlives the call; pid 0 is
+        // the calling thread.
+        if unsafe { libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) } != 0 {
+            tracing::warn!(
+                "Failed to set CPU affinity of worker thread {}: {}",
+                index,
+                std::io::Error::last_os_error()
+            );
+        }
+    }
+
+    if let Some(nice) = config.nice {
+        // SAFETY: On Linux the nice level is a thread attribute; who 0 is the calling thread.
+        if unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, nice) } != 0 {
+            tracing::warn!(
+                "Failed to set nice level of worker thread {}: {}",
+                index,
+                std::io::Error::last_os_error()
+            );
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn configure_worker(config: &ThreadPoolConfig, index: usize) {
+    if index == 0 && (!config.cpu_affinity.is_empty() || config.nice.is_some()) {
+        tracing::debug!("CPU affinity and nice level of worker threads are only applied on Linux");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_blocking_on_configured_pool() {
+        let config = ThreadPoolConfig {
+            threads: Some(2),
+            cpu_affinity: vec![0],
+            pin_threads: true,
+            nice: Some(5),
+        };
+        let name = run_blocking(Some(&config), || std::thread::current().name().map(str::to_string))
+            .await
+            .unwrap();
+        assert!(name.unwrap().starts_with("kreuzberg-worker-"));
+        assert!(Arc::ptr_eq(
+            &thread_pool(&config).unwrap(),
+            &thread_pool(&config).unwrap()
+        ));
+        assert_eq!(thread_pool(&config).unwrap().current_num_threads(), 2);
+
+        #[cfg(target_os = "linux")]
+        {
+            #[allow(unsafe_code)]
+            let (affinity, nice) = run_blocking(Some(&config), || {
+                // SAFETY: see `configure_worker`.
+                let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+                unsafe { libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) };
+                let cores: Vec<usize> = (0..libc::CPU_SETSIZE as usize)
+                    .filter(|&core| unsafe { libc::CPU_ISSET(core, &set) })
+                    .collect();
+                (cores, unsafe { libc::getpriority(libc::PRIO_PROCESS, 0) })
+            })
+            .await
+            .unwrap();
+            assert_eq!(affinity, vec![0]);
+            assert_eq!(nice, 5);
+        }
+
+        let panicked = run_blocking(Some(&config), || panic!("boom")).await;
+        assert!(matches!(panicked, Err(KreuzbergError::Other(message)) if message.contains("boom")));
+        assert_eq!(run_blocking(None, || 42).await.unwrap(), 42);
+
+        let invalid = ThreadPoolConfig {
+            threads: Some(0),
+            ..Default::default()
+        };
+        assert!(matches!(
+            run_blocking(Some(&invalid), || ()).await,
+            Err(KreuzbergError::Validation { .. })
+        ));
+    }
+}
@@ -0,0 +1,339 @@
+//! Directory walking for batch extraction.
+//!
+//! [`walk`] expands the directories of a batch into the files to extract, following
+//! the policies of a [`WalkConfig`] for symbolic links, hard links, hidden files,
+//! special files and glob patterns. Paths given directly are kept as they are, like
+//! ripgrep searches files named on its command line whatever its filters.
+
+use crate::core::config::{SpecialFilePolicy, SymlinkPolicy, WalkConfig};
+use crate::{KreuzbergError, Result};
+use glob::{MatchOptions, Pattern};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+const MATCH_OPTIONS: MatchOptions = MatchOptions {
+    case_sensitive: true,
+    require_literal_separator: true,
+    require_literal_leading_dot: false,
+};
+
+/// The files to extract for `paths`: files as they are, and the files found in
+/// directories with `config`, in name order.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Validation` for invalid glob patterns, and for symbolic
+/// links and special files when their policy is `error`. I/O errors, such as a path
+/// that does not exist or a directory that cannot be read, bubble up.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use kreuzberg::core::config::WalkConfig;
+/// use kreuzberg::core::walk::walk;
+///
+/// # fn example() -> kreuzberg::Result<()> {
+/// let config = WalkConfig {
+///     include: vec!["*.pdf".to_string()],
+///     ..Default::default()
+/// };
+/// let files = walk(&["reports", "summary.docx"], &config)?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn walk(paths: &[impl AsRef<Path>], config: &WalkConfig) -> Result<Vec<PathBuf>> {
+    let mut walker = Walker {
+        config,
+        include: compile(&config.include)?,
+        exclude: compile(&config.exclude)?,
+        seen: HashSet::new(),
+        ancestors: Vec::new(),
+        files: Vec::new(),
+    };
+
+    for path in paths {
+        let path = path.as_ref();
+        if std::fs::metadata(path)?.is_dir() {
+            walker.walk_dir(path, Path::new(""))?;
+        } else if walker.first_sight(path)? {
+            walker.files.push(path.to_path_buf());
+        }
+    }
+    Ok(walker.files)
+}
+
+fn compile(patterns: &[String]) -> Result<Vec<Pattern>> {
+    patterns
+        .iter()
+        .map(|pattern| {
+            Pattern::new(pattern)
+                .map_err(|e| KreuzbergError::validation(format!("Invalid glob pattern '{pattern}': {e}")))
+        })
+        .collect()
+}
+
+struct Walker<'a> {
+    config: &'a WalkConfig,
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+    /// Device and inode of the files found so far, for hard link deduplication
+    seen: HashSet<(u64, u64)>,
+    /// Canonical paths of the directories being walked, to stop symbolic link loops
+    ancestors: Vec<PathBuf>,
+    files: Vec<PathBuf>,
+}
+
+impl Walker<'_> {
+    fn walk_dir(&mut self, dir: &Path, relative: &Path) -> Result<()> {
+        let canonical = std::fs::canonicalize(dir)?;
+        if self.ancestors.contains(&canonical) {
+            tracing::debug!("Not walking {} again: symbolic link loop", dir.display());
+            return Ok(());
+        }
+        self.ancestors.push(canonical);
+
+        let mut entries = std::fs::read_dir(dir)?.collect::<std::io::Result<Vec<_>>>()?;
+        entries.sort_by_key(|entry| entry.file_name());
+
+        for entry in entries {
+            let path = entry.path();
+            let relative = relative.join(entry.file_name());
+            let mut file_type = entry.file_type()?;
+
+            if !self.config.hidden && is_hidden(&entry) {
+                continue;
+            }
+            if self.exclude.iter().any(|pattern| matches(pattern, &relative)) {
+                continue;
+            }
+
+            if file_type.is_symlink() {
+                match self.config.symlinks {
+                    SymlinkPolicy::Skip => continue,
+                    SymlinkPolicy::Error => {
+                        return Err(KreuzbergError::validation(format!(
+                            "Symbolic link {} found while walking, and walk.symlinks is 'error'",
+                            path.display()
+                        )));
+                    }
+                    SymlinkPolicy::Follow => match std::fs::metadata(&path) {
+                        Ok(target) => file_type = target.file_type(),
+                        Err(e) => {
+                            tracing::debug!("Skipping broken symbolic link {}: {}", path.display(), e);
+                            continue;
+                        }
+                    },
+                }
+            }
+
+            if file_type.is_dir() {
+                if self.config.recursive {
+                    self.walk_dir(&path, &relative)?;
+                }
+            } else if file_type.is_file() {
+                let included =
+                    self.include.is_empty() || self.include.iter().any(|pattern| matches(pattern, &relative));
+                if included && self.first_sight(&path)? {
+                    self.files.push(path);
+                }
+            } else if self.config.special_files == SpecialFilePolicy::Error {
+                return Err(KreuzbergError::validation(format!(
+                    "Special file {} found while walking, and walk.special_files is 'error'",
+                    path.display()
+                )));
+            }
+        }
+
+        self.ancestors.pop();
+        Ok(())
+    }
+
+    /// Whether the file at `path` was not found before under another name.
+    #[cfg(unix)]
+    fn first_sight(&mut self, path: &Path) -> Result<bool> {
+        use std::os::unix::fs::MetadataExt;
+
+        if !self.config.dedup_hardlinks {
+            return Ok(true);
+        }
+        let metadata = std::fs::metadata(path)?;
+        Ok(self.seen.insert((metadata.dev(), metadata.ino())))
+    }
+
+    #[cfg(not(unix))]
+    fn first_sight(&mut self, _path: &Path) -> Result<bool> {
+        Ok(true)
+    }
+}
+
+/// Whether `pattern` matches `relative`: patterns without a `/` match the file name.
+fn matches(pattern: &Pattern, relative: &Path) -> bool {
+    if pattern.as_str().contains('/') {
+        let relative = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        pattern.matches_with(&relative, MATCH_OPTIONS)
+    } else {
+        relative
+            .file_name()
+            .is_some_and(|name| pattern.matches_with(&name.to_string_lossy(), MATCH_OPTIONS))
+    }
+}
+
+fn is_hidden(entry: &std::fs::DirEntry) -> bool {
+    if entry.file_name().as_encoded_bytes().first() == Some(&b'.') {
+        return true;
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        if entry
+            .metadata()
+            .is_ok_and(|metadata| metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0)
+        {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn names(files: &[PathBuf], root: &Path) -> Vec<String> {
+        files
+            .iter()
+            .map(|file| file.strip_prefix(root).unwrap().to_string_lossy().replace('\\', "/"))
+            .collect()
+    }
+
+    fn tree() -> tempfile::TempDir {
+        let dir = tempdir().unwrap();
+        for file in [
+            "a.pdf",
+            "b.txt",
+            ".hidden.txt",
+            "drafts/c.pdf",
+            "docs/d.pdf",
+            "docs/e.docx",
+        ] {
+            let path = dir.path().join(file);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, file).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_walk_defaults_and_globs() {
+        let dir = tree();
+        let root = dir.path();
+
+        let files = walk(&[root], &WalkConfig::default()).unwrap();
+        assert_eq!(
+            names(&files, root),
+            ["a.pdf", "b.txt", "docs/d.pdf", "docs/e.docx", "drafts/c.pdf"]
+        );
+
+        let config = WalkConfig {
+            hidden: true,
+            recursive: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            names(&walk(&[root], &config).unwrap(), root),
+            [".hidden.txt", "a.pdf", "b.txt"]
+        );
+
+        let config = WalkConfig {
+            include: vec!["*.pdf".to_string()],
+            exclude: vec!["drafts".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(names(&walk(&[root], &config).unwrap(), root), ["a.pdf", "docs/d.pdf"]);
+
+        let config = WalkConfig {
+            include: vec!["docs/*".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            names(&walk(&[root], &config).unwrap(), root),
+            ["docs/d.pdf", "docs/e.docx"]
+        );
+
+        // Files given directly are kept whatever the filters.
+        let hidden = root.join(".hidden.txt");
+        assert_eq!(walk(&[&hidden], &config).unwrap(), [hidden]);
+
+        let config = WalkConfig {
+            include: vec!["[".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(walk(&[root], &config), Err(KreuzbergError::Validation { .. })));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walk_symlinks_and_hardlinks() {
+        let dir = tree();
+        let root = dir.path();
+        std::os::unix::fs::symlink(root.join("docs"), root.join("linked")).unwrap();
+        std::os::unix::fs::symlink(root, root.join("docs/loop")).unwrap();
+        std::fs::hard_link(root.join("a.pdf"), root.join("z.pdf")).unwrap();
+
+        let files = walk(&[root], &WalkConfig::default()).unwrap();
+        assert_eq!(
+            names(&files, root),
+            ["a.pdf", "b.txt", "docs/d.pdf", "docs/e.docx", "drafts/c.pdf"]
+        );
+
+        let config = WalkConfig {
+            dedup_hardlinks: false,
+            ..Default::default()
+        };
+        assert_eq!(walk(&[root], &config).unwrap().len(), 6);
+
+        let config = WalkConfig {
+            symlinks: SymlinkPolicy::Follow,
+            dedup_hardlinks: false,
+            ..Default::default()
+        };
+        let files = walk(&[root], &config).unwrap();
+        assert!(files.contains(&root.join("linked/d.pdf")));
+        assert!(!files.iter().any(|file| file.starts_with(root.join("docs/loop"))));
+
+        let config = WalkConfig {
+            symlinks: SymlinkPolicy::Error,
+            ..Default::default()
+        };
+        assert!(matches!(walk(&[root], &config), Err(KreuzbergError::Validation { .. })));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_walk_special_files() {
+        let dir = tempdir().unwrap();
+        let socket = dir.path().join("socket");
+        let _listener = std::os::unix::net::UnixListener::bind(&socket).unwrap();
+        std::fs::write(dir.path().join("a.txt"), "a").unwrap();
+
+        assert_eq!(
+            walk(&[dir.path()], &WalkConfig::default()).unwrap(),
+            [dir.path().join("a.txt")]
+        );
+
+        let config = WalkConfig {
+            special_files: SpecialFilePolicy::Error,
+            ..Default::default()
+        };
+        assert!(matches!(
+            walk(&[dir.path()], &config),
+            Err(KreuzbergError::Validation { .. })
+        ));
+    }
+}
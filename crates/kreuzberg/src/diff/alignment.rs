@@ -0,0 +1,332 @@
+//! Format-independent document comparison.
+//!
+//! Text extracted from different formats of the same document (a signed PDF and the
+//! negotiated DOCX, for example) differs in line breaks, hyphenation, typographic
+//! punctuation and markup even when the wording is identical. This module compares
+//! normalized words instead of lines and reports a similarity score together with
+//! the aligned regions of both texts.
+
+use super::lines::{Edit, diff_lines};
+use crate::types::ExtractionResult;
+use serde::{Deserialize, Serialize};
+
+/// Options for [`compare_documents`] and [`compare_text`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComparisonOptions {
+    /// Compare case-insensitively
+    #[serde(default = "default_true")]
+    pub ignore_case: bool,
+    /// Ignore punctuation attached to words (`"Agreement,"` matches `"Agreement"`)
+    #[serde(default)]
+    pub ignore_punctuation: bool,
+    /// Strip Markdown/Djot markup characters (`*`, `_`, `#`, `|`, `>`, `` ` ``) from words
+    #[serde(default = "default_true")]
+    pub strip_markup: bool,
+}
+
+impl Default for ComparisonOptions {
+    fn default() -> Self {
+        Self {
+            ignore_case: true,
+            ignore_punctuation: false,
+            strip_markup: true,
+        }
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+/// Result of a format-independent comparison.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DocumentComparison {
+    /// Share of matching words, from 0.0 (nothing in common) to 1.0 (identical wording)
+    pub similarity: f64,
+    /// Number of normalized words in the old text
+    pub old_words: usize,
+    /// Number of normalized words in the new text
+    pub new_words: usize,
+    /// Consecutive regions covering both texts in order
+    pub regions: Vec<AlignedRegion>,
+}
+
+impl DocumentComparison {
+    /// Whether the wording of both documents is identical after normalization.
+    pub fn is_identical(&self) -> bool {
+        self.regions.iter().all(|region| region.kind == RegionKind::Matching)
+    }
+
+    /// Regions that are not [`RegionKind::Matching`].
+    pub fn differences(&self) -> impl Iterator<Item = &AlignedRegion> {
+        self.regions.iter().filter(|region| region.kind != RegionKind::Matching)
+    }
+}
+
+/// How a region of the two texts relates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegionKind {
+    /// Same words in both texts
+    Matching,
+    /// Words replaced by different words
+    Changed,
+    /// Words only present in the old text
+    Removed,
+    /// Words only present in the new text
+    Added,
+}
+
+/// A region of the comparison. Offsets are byte positions in the original (unnormalized) texts.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AlignedRegion {
+    /// Relation between the two sides
+    pub kind: RegionKind,
+    /// Start of the region in the old text
+    pub old_start: usize,
+    /// End of the region in the old text (exclusive)
+    pub old_end: usize,
+    /// Start of the region in the new text
+    pub new_start: usize,
+    /// End of the region in the new text (exclusive)
+    pub new_end: usize,
+    /// Original old text of the region (`None` for matching regions)
+    pub old_text: Option<String>,
+    /// Original new text of the region (`None` for matching regions)
+    pub new_text: Option<String>,
+}
+
+/// Compare the content of two results regardless of their source formats.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::diff::{ComparisonOptions, compare_text};
+///
+/// let pdf = "This Agree-\nment is entered into by the “Parties”.";
+/// let docx = "This **Agreement** is entered into by the \"Parties\".";
+/// let comparison = compare_text(pdf, docx, &ComparisonOptions::default());
+/// assert!(comparison.is_identical());
+/// assert_eq!(comparison.similarity, 1.0);
+/// ```
+pub fn compare_documents(
+    old: &ExtractionResult,
+    new: &ExtractionResult,
+    options: &ComparisonOptions,
+) -> DocumentComparison {
+    compare_text(&old.content, &new.content, options)
+}
+
+/// Compare two texts word by word after normalization. See [`compare_documents`].
+pub fn compare_text(old: &str, new: &str, options: &ComparisonOptions) -> DocumentComparison {
+    let old_words = tokenize(old, options);
+    let new_words = tokenize(new, options);
+
+    let old_keys: Vec<&str> = old_words.iter().map(|w| w.normalized.as_str()).collect();
+    let new_keys: Vec<&str> = new_words.iter().map(|w| w.normalized.as_str()).collect();
+    let edits = diff_lines(&old_keys, &new_keys);
+
+    let matched = edits.iter().filter(|edit| matches!(edit, Edit::Equal(..))).count();
+    let total = old_words.len() + new_words.len();
+    let similarity = if total == 0 {
+        1.0
+    } else {
+        (2 * matched) as f64 / total as f64
+    };
+
+    DocumentComparison {
+        similarity,
+        old_words: old_words.len(),
+        new_words: new_words.len(),
+        regions: build_regions(&edits, &old_words, &new_words, old, new),
+    }
+}
+
+/// A normalized word with its byte span in the original text.
+struct Word {
+    normalized: String,
+    start: usize,
+    end: usize,
+}
+
+fn tokenize(text: &str, options: &ComparisonOptions) -> Vec<Word> {
+    let mut words: Vec<Word> = Vec::new();
+    let mut join_next = false;
+
+    for (start, raw) in split_words(text) {
+        let end = start + raw.len();
+        let broken_hyphen = raw.len() > 1
+            && (raw.ends_with('-') || raw.ends_with('\u{00AD}'))
+            && text[end..].chars().take_while(|c| c.is_whitespace()).any(|c| c == '\n');
+
+        let mut normalized = normalize_word(raw, options);
+        if broken_hyphen && normalized.ends_with('-') {
+            normalized.pop();
+        }
+
+        if join_next && let Some(previous) = words.last_mut() {
+            previous.normalized.push_str(&normalized);
+            previous.end = end;
+        } else if !normalized.is_empty() {
+            words.push(Word { normalized, start, end });
+        }
+        join_next = broken_hyphen && !words.is_empty();
+    }
+
+    words
+}
+
+fn split_words(text: &str) -> impl Iterator<Item = (usize, &str)> {
+    text.split(char::is_whitespace)
+        .filter(|word| !word.is_empty())
+        .map(move |word| (word.as_ptr() as usize - text.as_ptr() as usize, word))
+}
+
+fn normalize_word(word: &str, options: &ComparisonOptions) -> String {
+    let mut normalized = String::with_capacity(word.len());
+    for c in word.chars() {
+        let mapped = match c {
+            '\u{00AD}' => continue,
+            '\u{2010}'..='\u{2015}' | '\u{2212}' => '-',
+            '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => '\'',
+            '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => '"',
+            '\u{FB01}' => {
+                normalized.push_str("fi");
+                continue;
+            }
+            '\u{FB02}' => {
+                normalized.push_str("fl");
+                continue;
+            }
+            '*' | '_' | '#' | '|' | '>' | '`' if options.strip_markup => continue,
+            c if options.ignore_punctuation && c.is_ascii_punctuation() => continue,
+            c => c,
+        };
+        if options.ignore_case {
+            normalized.extend(mapped.to_lowercase());
+        } else {
+            normalized.push(mapped);
+        }
+    }
+
+    // Quotes and brackets next to a word are layout-dependent: `“Parties”` vs `"Parties"`.
+    if !options.ignore_punctuation {
+        let trimmed = normalized.trim_matches(|c: char| matches!(c, '"' | '\'' | '(' | ')' | '[' | ']'));
+        if trimmed.len() != normalized.len() && !trimmed.is_empty() {
+            return trimmed.to_string();
+        }
+    }
+
+    normalized
+}
+
+fn build_regions(edits: &[Edit], old_words: &[Word], new_words: &[Word], old: &str, new: &str) -> Vec<AlignedRegion> {
+    let mut regions = Vec::new();
+    let mut i = 0;
+
+    while i < edits.len() {
+        let matching = matches!(edits[i], Edit::Equal(..));
+        let mut j = i;
+        while j < edits.len() && matches!(edits[j], Edit::Equal(..)) == matching {
+            j += 1;
+        }
+
+        let mut old_range: Option<(usize, usize)> = None;
+        let mut new_range: Option<(usize, usize)> = None;
+        let extend = |range: &mut Option<(usize, usize)>, word: &Word| {
+            let (start, end) = range.get_or_insert((word.start, word.end));
+            *start = (*start).min(word.start);
+            *end = (*end).max(word.end);
+        };
+        for edit in &edits[i..j] {
+            match *edit {
+                Edit::Equal(a, b) => {
+                    extend(&mut old_range, &old_words[a]);
+                    extend(&mut new_range, &new_words[b]);
+                }
+                Edit::Delete(a) => extend(&mut old_range, &old_words[a]),
+                Edit::Insert(b) => extend(&mut new_range, &new_words[b]),
+            }
+        }
+
+        let kind = match (matching, old_range.is_some(), new_range.is_some()) {
+            (true, _, _) => RegionKind::Matching,
+            (false, true, true) => RegionKind::Changed,
+            (false, true, false) => RegionKind::Removed,
+            (false, false, _) => RegionKind::Added,
+        };
+
+        let old_position = old_range.unwrap_or_else(|| {
+            let at = previous_end(&regions, |r: &AlignedRegion| r.old_end);
+            (at, at)
+        });
+        let new_position = new_range.unwrap_or_else(|| {
+            let at = previous_end(&regions, |r: &AlignedRegion| r.new_end);
+            (at, at)
+        });
+
+        let text = |source: &str, (start, end): (usize, usize)| (!matching).then(|| source[start..end].to_string());
+        regions.push(AlignedRegion {
+            kind,
+            old_start: old_position.0,
+            old_end: old_position.1,
+            new_start: new_position.0,
+            new_end: new_position.1,
+            old_text: text(old, old_position),
+            new_text: text(new, new_position),
+        });
+
+        i = j;
+    }
+
+    regions
+}
+
+fn previous_end(regions: &[AlignedRegion], end: impl Fn(&AlignedRegion) -> usize) -> usize {
+    regions.last().map(end).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_differences_are_ignored() {
+        let pdf = "1. The  Supplier shall de-\nliver the goods\nwithin 30 days — see “Annex A”.";
+        let docx = "## 1. The Supplier shall deliver the goods within 30 days - see \"Annex A\".";
+        let comparison = compare_text(pdf, docx, &ComparisonOptions::default());
+        assert!(comparison.is_identical(), "{:?}", comparison.regions);
+        assert_eq!(comparison.similarity, 1.0);
+    }
+
+    #[test]
+    fn test_changed_region_reports_original_text() {
+        let old = "Payment is due within 30 days of invoice.";
+        let new = "Payment is due within 60 days of invoice.";
+        let comparison = compare_text(old, new, &ComparisonOptions::default());
+
+        let changes: Vec<_> = comparison.differences().collect();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].kind, RegionKind::Changed);
+        assert_eq!(changes[0].old_text.as_deref(), Some("30"));
+        assert_eq!(changes[0].new_text.as_deref(), Some("60"));
+        assert_eq!(&old[changes[0].old_start..changes[0].old_end], "30");
+        assert!(comparison.similarity > 0.8 && comparison.similarity < 1.0);
+    }
+
+    #[test]
+    fn test_added_and_removed_regions() {
+        let comparison = compare_text("a b c", "a c d", &ComparisonOptions::default());
+        let kinds: Vec<RegionKind> = comparison.regions.iter().map(|r| r.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                RegionKind::Matching,
+                RegionKind::Removed,
+                RegionKind::Matching,
+                RegionKind::Added
+            ]
+        );
+        assert_eq!(comparison.regions[3].new_text.as_deref(), Some("d"));
+    }
+}
@@ -0,0 +1,159 @@
+//! Line-based diff using Myers' algorithm.
+
+/// A single edit operation over line indices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Edit {
+    /// Line present in both sides (old index, new index)
+    Equal(usize, usize),
+    /// Line only in the old side
+    Delete(usize),
+    /// Line only in the new side
+    Insert(usize),
+}
+
+/// Maximum edit distance explored before falling back to replacing the whole middle section.
+///
+/// Bounds memory to `MAX_EDIT_DISTANCE * (old + new)` for very different inputs.
+const MAX_EDIT_DISTANCE: usize = 4096;
+
+/// Compute the shortest edit script turning `old` into `new`.
+pub(crate) fn diff_lines(old: &[&str], new: &[&str]) -> Vec<Edit> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut edits: Vec<Edit> = (0..prefix).map(|i| Edit::Equal(i, i)).collect();
+
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+    match myers(old_mid, new_mid) {
+        Some(middle) => edits.extend(middle.into_iter().map(|edit| match edit {
+            Edit::Equal(a, b) => Edit::Equal(a + prefix, b + prefix),
+            Edit::Delete(a) => Edit::Delete(a + prefix),
+            Edit::Insert(b) => Edit::Insert(b + prefix),
+        })),
+        None => {
+            edits.extend((0..old_mid.len()).map(|i| Edit::Delete(i + prefix)));
+            edits.extend((0..new_mid.len()).map(|i| Edit::Insert(i + prefix)));
+        }
+    }
+
+    let (old_suffix_start, new_suffix_start) = (old.len() - suffix, new.len() - suffix);
+    edits.extend((0..suffix).map(|i| Edit::Equal(old_suffix_start + i, new_suffix_start + i)));
+    edits
+}
+
+/// Myers' O((N+M)D) shortest edit script; `None` if the distance exceeds [`MAX_EDIT_DISTANCE`].
+fn myers(old: &[&str], new: &[&str]) -> Option<Vec<Edit>> {
+    let (n, m) = (old.len() as isize, new.len() as isize);
+    if n == 0 || m == 0 {
+        let mut edits: Vec<Edit> = (0..old.len()).map(Edit::Delete).collect();
+        edits.extend((0..new.len()).map(Edit::Insert));
+        return Some(edits);
+    }
+
+    let max = ((n + m) as usize).min(MAX_EDIT_DISTANCE) as isize;
+    let offset = max + 1;
+    let mut v = vec![0isize; (2 * max + 3) as usize];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    for d in 0..=max {
+        trace.push(v.clone());
+        let mut k = -d;
+        while k <= d {
+            let index = (k + offset) as usize;
+            let mut x = if k == -d || (k != d && v[index - 1] < v[index + 1]) {
+                v[index + 1]
+            } else {
+                v[index - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old[x as usize] == new[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[index] = x;
+
+            if x >= n && y >= m {
+                return Some(backtrack(&trace, offset, n, m));
+            }
+            k += 2;
+        }
+    }
+
+    None
+}
+
+fn backtrack(trace: &[Vec<isize>], offset: isize, n: isize, m: isize) -> Vec<Edit> {
+    let (mut x, mut y) = (n, m);
+    let mut edits = Vec::new();
+
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && v[(k - 1 + offset) as usize] < v[(k + 1 + offset) as usize]) {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[(prev_k + offset) as usize];
+        let prev_y = prev_x - prev_k;
+
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            edits.push(Edit::Equal(x as usize, y as usize));
+        }
+
+        if d > 0 {
+            if x == prev_x {
+                edits.push(Edit::Insert(prev_y as usize));
+            } else {
+                edits.push(Edit::Delete(prev_x as usize));
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+
+    edits.reverse();
+    edits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn apply(old: &[&str], new: &[&str], edits: &[Edit]) -> Vec<String> {
+        edits
+            .iter()
+            .filter_map(|edit| match edit {
+                Edit::Equal(a, _) => Some(old[*a].to_string()),
+                Edit::Insert(b) => Some(new[*b].to_string()),
+                Edit::Delete(_) => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_lines_reconstructs_new() {
+        let old = ["a", "b", "c", "d", "e", "f"];
+        let new = ["a", "x", "c", "d", "f", "g"];
+        let edits = diff_lines(&old, &new);
+        assert_eq!(apply(&old, &new, &edits), new);
+
+        let changes = edits.iter().filter(|e| !matches!(e, Edit::Equal(..))).count();
+        assert_eq!(changes, 4);
+    }
+
+    #[test]
+    fn test_diff_lines_empty_sides() {
+        assert_eq!(diff_lines(&[], &["a"]), vec![Edit::Insert(0)]);
+        assert_eq!(diff_lines(&["a"], &[]), vec![Edit::Delete(0)]);
+        assert!(diff_lines(&[], &[]).is_empty());
+    }
+}
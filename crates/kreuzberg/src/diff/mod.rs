@@ -0,0 +1,419 @@
+//! Structured comparison of extraction results.
+//!
+//! [`compare`] reports what changed between two extractions, typically two revisions
+//! of the same document: line-based content hunks, metadata fields that were added,
+//! removed or changed, and table cells that differ. [`compare_documents`] compares the
+//! wording of the same document extracted from different formats (for example a signed
+//! PDF against the negotiated DOCX), ignoring line breaks, hyphenation and markup.
+//! [`compare_ocr_backends`] runs two OCR backends on the same pages and reports how
+//! much their outputs agree.
+//!
+//! # Example
+//!
+//! ```rust
+//! # tokio_test::block_on(async {
+//! use kreuzberg::{ExtractionConfig, extract_bytes};
+//!
+//! let config = ExtractionConfig::default();
+//! let v1 = extract_bytes(b"Term: 12 months\nFee: 100 EUR", "text/plain", &config).await?;
+//! let v2 = extract_bytes(b"Term: 24 months\nFee: 100 EUR", "text/plain", &config).await?;
+//!
+//! let diff = kreuzberg::diff::compare(&v1, &v2);
+//! assert_eq!(diff.content.len(), 1);
+//! println!("{}", diff.content[0]);
+//! # Ok::<(), kreuzberg::KreuzbergError>(())
+//! # });
+//! ```
+
+mod alignment;
+pub(crate) mod lines;
+mod ocr;
+
+pub use alignment::{
+    AlignedRegion, ComparisonOptions, DocumentComparison, RegionKind, compare_documents, compare_text,
+};
+pub use ocr::{BackendOutput, OcrComparison, OcrComparisonOptions, PageComparison, compare_ocr_backends};
+
+use crate::types::{ExtractionResult, Table};
+use lines::{Edit, diff_lines};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Number of unchanged lines shown around each change.
+pub const DEFAULT_CONTEXT_LINES: usize = 3;
+
+/// Differences between two extraction results.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResultDiff {
+    /// Changed regions of `content`
+    pub content: Vec<DiffHunk>,
+    /// Changed top-level metadata fields, sorted by key
+    pub metadata: Vec<MetadataChange>,
+    /// Added, removed and modified tables
+    pub tables: Vec<TableChange>,
+}
+
+impl ResultDiff {
+    /// Whether the two results are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.content.is_empty() && self.metadata.is_empty() && self.tables.is_empty()
+    }
+}
+
+/// A contiguous region of changed content lines with surrounding context.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DiffHunk {
+    /// First line of the hunk in the old content (1-indexed)
+    pub old_start: usize,
+    /// Number of old lines covered by the hunk
+    pub old_lines: usize,
+    /// First line of the hunk in the new content (1-indexed)
+    pub new_start: usize,
+    /// Number of new lines covered by the hunk
+    pub new_lines: usize,
+    /// Lines of the hunk
+    pub lines: Vec<DiffLine>,
+}
+
+/// A single line of a [`DiffHunk`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "text", rename_all = "snake_case")]
+pub enum DiffLine {
+    /// Unchanged line
+    Context(String),
+    /// Line only present in the old content
+    Removed(String),
+    /// Line only present in the new content
+    Added(String),
+}
+
+/// Formats the hunk in unified diff format.
+impl fmt::Display for DiffHunk {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "@@ -{},{} +{},{} @@",
+            self.old_start, self.old_lines, self.new_start, self.new_lines
+        )?;
+        for line in &self.lines {
+            match line {
+                DiffLine::Context(text) => writeln!(f, " {}", text)?,
+                DiffLine::Removed(text) => writeln!(f, "-{}", text)?,
+                DiffLine::Added(text) => writeln!(f, "+{}", text)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A metadata field that differs between the two results.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetadataChange {
+    /// Field name as serialized (e.g. `title`, `page_count`)
+    pub key: String,
+    /// Old value, `None` if the field was added
+    pub old: Option<serde_json::Value>,
+    /// New value, `None` if the field was removed
+    pub new: Option<serde_json::Value>,
+}
+
+/// A table-level difference. Tables are matched by position.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TableChange {
+    /// Table only present in the new result
+    Added {
+        /// Position of the table in the new result
+        index: usize,
+        /// Page of the table in the new result
+        page_number: usize,
+    },
+    /// Table only present in the old result
+    Removed {
+        /// Position of the table in the old result
+        index: usize,
+        /// Page of the table in the old result
+        page_number: usize,
+    },
+    /// Table present in both results with different cells
+    Modified {
+        /// Position of the table
+        index: usize,
+        /// Page of the table in the new result
+        page_number: usize,
+        /// Changed cells
+        cells: Vec<CellChange>,
+    },
+}
+
+/// A table cell that differs. Missing cells (rows or columns added or removed) are `None`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CellChange {
+    /// Row index (0-indexed)
+    pub row: usize,
+    /// Column index (0-indexed)
+    pub column: usize,
+    /// Old cell text
+    pub old: Option<String>,
+    /// New cell text
+    pub new: Option<String>,
+}
+
+/// Compare two extraction results.
+///
+/// `old` is the earlier revision; changes are reported as edits from `old` to `new`.
+pub fn compare(old: &ExtractionResult, new: &ExtractionResult) -> ResultDiff {
+    ResultDiff {
+        content: diff_content(&old.content, &new.content, DEFAULT_CONTEXT_LINES),
+        metadata: diff_metadata(old, new),
+        tables: diff_tables(&old.tables, &new.tables),
+    }
+}
+
+/// Line diff of two texts, grouped into hunks with `context` unchanged lines around each change.
+pub fn diff_content(old: &str, new: &str, context: usize) -> Vec<DiffHunk> {
+    let old_lines: Vec<&str> = old.lines().collect();
+    let new_lines: Vec<&str> = new.lines().collect();
+    let edits = diff_lines(&old_lines, &new_lines);
+
+    let changed: Vec<usize> = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, edit)| !matches!(edit, Edit::Equal(..)))
+        .map(|(i, _)| i)
+        .collect();
+
+    let mut hunks = Vec::new();
+    let mut i = 0;
+    while i < changed.len() {
+        let start = changed[i].saturating_sub(context);
+        let mut end = changed[i];
+        while i + 1 < changed.len() && changed[i + 1] <= end + 2 * context + 1 {
+            i += 1;
+            end = changed[i];
+        }
+        let end = (end + context).min(edits.len() - 1);
+        hunks.push(build_hunk(&edits[start..=end], &old_lines, &new_lines));
+        i += 1;
+    }
+
+    hunks
+}
+
+fn build_hunk(edits: &[Edit], old_lines: &[&str], new_lines: &[&str]) -> DiffHunk {
+    let mut old_start = None;
+    let mut new_start = None;
+    let (mut old_count, mut new_count) = (0, 0);
+    let mut lines = Vec::with_capacity(edits.len());
+
+    for edit in edits {
+        match *edit {
+            Edit::Equal(a, b) => {
+                old_start.get_or_insert(a);
+                new_start.get_or_insert(b);
+                old_count += 1;
+                new_count += 1;
+                lines.push(DiffLine::Context(old_lines[a].to_string()));
+            }
+            Edit::Delete(a) => {
+                old_start.get_or_insert(a);
+                old_count += 1;
+                lines.push(DiffLine::Removed(old_lines[a].to_string()));
+            }
+            Edit::Insert(b) => {
+                new_start.get_or_insert(b);
+                new_count += 1;
+                lines.push(DiffLine::Added(new_lines[b].to_string()));
+            }
+        }
+    }
+
+    // A side without lines in the hunk starts at the position of the matching edit.
+    let fallback = |edit: &Edit, old_side: bool| match (*edit, old_side) {
+        (Edit::Insert(b), true) => b.min(old_lines.len()),
+        (Edit::Delete(a), false) => a.min(new_lines.len()),
+        _ => 0,
+    };
+
+    DiffHunk {
+        old_start: old_start.map_or_else(|| fallback(&edits[0], true), |s| s + 1),
+        old_lines: old_count,
+        new_start: new_start.map_or_else(|| fallback(&edits[0], false), |s| s + 1),
+        new_lines: new_count,
+        lines,
+    }
+}
+
+fn diff_metadata(old: &ExtractionResult, new: &ExtractionResult) -> Vec<MetadataChange> {
+    let as_object = |result: &ExtractionResult| match serde_json::to_value(&result.metadata) {
+        Ok(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+    let old_map = as_object(old);
+    let new_map = as_object(new);
+
+    let mut keys: Vec<&String> = old_map.keys().chain(new_map.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let old_value = old_map.get(key).filter(|v| !v.is_null());
+            let new_value = new_map.get(key).filter(|v| !v.is_null());
+            (old_value != new_value).then(|| MetadataChange {
+                key: key.clone(),
+                old: old_value.cloned(),
+                new: new_value.cloned(),
+            })
+        })
+        .collect()
+}
+
+fn diff_tables(old: &[Table], new: &[Table]) -> Vec<TableChange> {
+    let mut changes = Vec::new();
+
+    for (index, (old_table, new_table)) in old.iter().zip(new).enumerate() {
+        let cells = diff_cells(&old_table.cells, &new_table.cells);
+        if !cells.is_empty() {
+            changes.push(TableChange::Modified {
+                index,
+                page_number: new_table.page_number,
+                cells,
+            });
+        }
+    }
+
+    for (index, table) in old.iter().enumerate().skip(new.len()) {
+        changes.push(TableChange::Removed {
+            index,
+            page_number: table.page_number,
+        });
+    }
+    for (index, table) in new.iter().enumerate().skip(old.len()) {
+        changes.push(TableChange::Added {
+            index,
+            page_number: table.page_number,
+        });
+    }
+
+    changes
+}
+
+fn diff_cells(old: &[Vec<String>], new: &[Vec<String>]) -> Vec<CellChange> {
+    let rows = old.len().max(new.len());
+    let mut changes = Vec::new();
+
+    for row in 0..rows {
+        let old_row = old.get(row).map(Vec::as_slice).unwrap_or_default();
+        let new_row = new.get(row).map(Vec::as_slice).unwrap_or_default();
+        for column in 0..old_row.len().max(new_row.len()) {
+            let old_cell = old_row.get(column);
+            let new_cell = new_row.get(column);
+            if old_cell != new_cell {
+                changes.push(CellChange {
+                    row,
+                    column,
+                    old: old_cell.cloned(),
+                    new: new_cell.cloned(),
+                });
+            }
+        }
+    }
+
+    changes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Metadata;
+    use std::borrow::Cow;
+
+    fn result(content: &str, title: Option<&str>, tables: Vec<Table>) -> ExtractionResult {
+        ExtractionResult {
+            content: content.to_string(),
+            mime_type: Cow::Borrowed("text/plain"),
+            metadata: Metadata {
+                title: title.map(str::to_string),
+                ..Default::default()
+            },
+            tables,
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            scripts: None,
+            pages: None,
+            elements: None,
+        }
+    }
+
+    fn table(cells: &[&[&str]]) -> Table {
+        Table {
+            cells: cells
+                .iter()
+                .map(|row| row.iter().map(|c| c.to_string()).collect())
+                .collect(),
+            markdown: String::new(),
+            page_number: 1,
+        }
+    }
+
+    #[test]
+    fn test_identical_results_have_empty_diff() {
+        let a = result("one\ntwo", Some("Contract"), vec![table(&[&["a", "b"]])]);
+        assert!(compare(&a, &a.clone()).is_empty());
+    }
+
+    #[test]
+    fn test_content_hunks_with_context() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15";
+        let new = "1\n2\nthree\n4\n5\n6\n7\n8\n9\n10\n11\n12\n13\n14\n15\n16";
+        let hunks = diff_content(old, new, 2);
+        assert_eq!(hunks.len(), 2);
+
+        let first = &hunks[0];
+        assert_eq!(
+            (first.old_start, first.old_lines, first.new_start, first.new_lines),
+            (1, 5, 1, 5)
+        );
+        assert!(first.lines.contains(&DiffLine::Removed("3".to_string())));
+        assert!(first.lines.contains(&DiffLine::Added("three".to_string())));
+
+        let second = &hunks[1];
+        assert_eq!(second.lines.last(), Some(&DiffLine::Added("16".to_string())));
+        assert_eq!(second.to_string().lines().next(), Some("@@ -14,2 +14,3 @@"));
+    }
+
+    #[test]
+    fn test_metadata_and_table_changes() {
+        let old = result("x", Some("Draft"), vec![table(&[&["Fee", "100"]]), table(&[&["t"]])]);
+        let new = result("x", None, vec![table(&[&["Fee", "120"], &["Term", "12"]])]);
+        let diff = compare(&old, &new);
+
+        assert_eq!(
+            diff.metadata,
+            vec![MetadataChange {
+                key: "title".to_string(),
+                old: Some(serde_json::json!("Draft")),
+                new: None,
+            }]
+        );
+
+        assert_eq!(diff.tables.len(), 2);
+        let TableChange::Modified { cells, .. } = &diff.tables[0] else {
+            panic!("expected modified table, got {:?}", diff.tables[0]);
+        };
+        assert_eq!(cells.len(), 3);
+        assert_eq!(cells[0].old.as_deref(), Some("100"));
+        assert_eq!(cells[0].new.as_deref(), Some("120"));
+        assert!(cells[1].old.is_none());
+        assert_eq!(
+            diff.tables[1],
+            TableChange::Removed {
+                index: 1,
+                page_number: 1
+            }
+        );
+    }
+}
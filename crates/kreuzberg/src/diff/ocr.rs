@@ -0,0 +1,542 @@
+//! A/B comparison of OCR backends.
+//!
+//! Which OCR backend works best depends on the corpus: scan quality, fonts, layout
+//! and languages. [`compare_ocr_backends`] runs two configured backends on a sample
+//! of pages of a document and reports how much their outputs agree, how long each
+//! backend took, and both texts side by side, so the pages where the backends
+//! diverge can be reviewed by hand.
+
+use super::alignment::{AlignedRegion, ComparisonOptions, compare_text};
+use crate::core::config::{OcrConfig, PageSelection};
+use crate::plugins::resolve_ocr_backend;
+use crate::{KreuzbergError, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Instant;
+
+/// Which pages are compared and when a page counts as divergent.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrComparisonOptions {
+    /// Pages to sample from (None = all pages)
+    pub pages: Option<PageSelection>,
+    /// Maximum number of pages compared, spread evenly over the selected pages (None = all)
+    pub sample_size: Option<usize>,
+    /// Character agreement below which a page is listed in `divergent_pages`. Default: 0.9
+    pub divergence_threshold: f64,
+}
+
+impl Default for OcrComparisonOptions {
+    fn default() -> Self {
+        Self {
+            pages: None,
+            sample_size: None,
+            divergence_threshold: 0.9,
+        }
+    }
+}
+
+/// Output of one backend for one page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BackendOutput {
+    /// Recognized text, empty if the backend failed
+    pub content: String,
+    /// Time spent in the backend, in milliseconds
+    pub duration_ms: f64,
+    /// Error message if the backend failed on the page
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Comparison of the two backends on one page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PageComparison {
+    /// Page number (1-indexed)
+    pub page: usize,
+    /// `1 - edit distance / length of the longer text`, on characters with whitespace collapsed
+    pub character_agreement: f64,
+    /// Share of matching words, as reported by [`compare_text`]
+    pub word_agreement: f64,
+    /// Output of the first backend
+    pub first: BackendOutput,
+    /// Output of the second backend
+    pub second: BackendOutput,
+    /// Regions where the two outputs differ, `old` being the first backend
+    pub differences: Vec<AlignedRegion>,
+}
+
+impl PageComparison {
+    /// Whether both backends processed the page.
+    pub fn is_complete(&self) -> bool {
+        self.first.error.is_none() && self.second.error.is_none()
+    }
+}
+
+/// Agreement and divergence of two OCR backends over the sampled pages.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OcrComparison {
+    /// Backend of the first configuration (ensemble backends joined by `+`)
+    pub first_backend: String,
+    /// Backend of the second configuration (ensemble backends joined by `+`)
+    pub second_backend: String,
+    /// Pages compared, in ascending order
+    pub pages: Vec<PageComparison>,
+    /// Mean character agreement of the pages both backends processed (0 without such pages)
+    pub character_agreement: f64,
+    /// Mean word agreement of the pages both backends processed (0 without such pages)
+    pub word_agreement: f64,
+    /// Total time spent in the first backend, in milliseconds
+    pub first_duration_ms: f64,
+    /// Total time spent in the second backend, in milliseconds
+    pub second_duration_ms: f64,
+    /// Pages whose character agreement is below the threshold, or that a backend failed on
+    pub divergent_pages: Vec<usize>,
+}
+
+/// Run the backends of `first` and `second` on a sample of pages of a document.
+///
+/// Either configuration may be an ensemble (`OcrConfig::ensemble`), to measure what
+/// voting changes against a single backend.
+/// Images are compared as a single page. PDF pages are rendered once at the DPI of
+/// `first` and the same image is given to both backends; rendering requires the `pdf`
+/// feature. Pages are processed one at a time and the backends one after the other,
+/// so the durations are comparable. A backend failing on a page does not end the
+/// comparison: the error is recorded and the page counts as divergent.
+///
+/// # Errors
+///
+/// - `KreuzbergError::Plugin` if a backend is not registered
+/// - `KreuzbergError::UnsupportedFormat` if `mime_type` is neither an image nor a PDF
+///   (or a PDF without the `pdf` feature)
+/// - `KreuzbergError::Parsing` if the PDF cannot be rendered
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use kreuzberg::OcrConfig;
+/// use kreuzberg::diff::{OcrComparisonOptions, compare_ocr_backends};
+///
+/// # async fn example() -> kreuzberg::Result<()> {
+/// let scan = std::fs::read("scan.pdf")?;
+/// let tesseract = OcrConfig::default();
+/// let paddle = OcrConfig {
+///     backend: "paddleocr".to_string(),
+///     ..Default::default()
+/// };
+/// let options = OcrComparisonOptions {
+///     sample_size: Some(20),
+///     ..Default::default()
+/// };
+///
+/// let comparison = compare_ocr_backends(&scan, "application/pdf", &tesseract, &paddle, &options).await?;
+/// println!("agreement: {:.3}", comparison.character_agreement);
+/// for page in comparison.pages.iter().filter(|p| comparison.divergent_pages.contains(&p.page)) {
+///     println!("page {}:\n{}\n---\n{}", page.page, page.first.content, page.second.content);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn compare_ocr_backends(
+    content: &[u8],
+    mime_type: &str,
+    first: &OcrConfig,
+    second: &OcrConfig,
+    options: &OcrComparisonOptions,
+) -> Result<OcrComparison> {
+    let first_backend = resolve_ocr_backend(first)?;
+    let second_backend = resolve_ocr_backend(second)?;
+
+    let images = page_images(content, mime_type, first, options)?;
+    let text_options = ComparisonOptions::default();
+
+    let mut pages = Vec::with_capacity(images.len());
+    for (page, image) in images {
+        let first_output = run_backend(first_backend.process_image(&image, first)).await;
+        let second_output = run_backend(second_backend.process_image(&image, second)).await;
+
+        let words = compare_text(&first_output.content, &second_output.content, &text_options);
+        pages.push(PageComparison {
+            page,
+            character_agreement: round(character_agreement(&first_output.content, &second_output.content)),
+            word_agreement: round(words.similarity),
+            differences: words.differences().cloned().collect(),
+            first: first_output,
+            second: second_output,
+        });
+    }
+
+    Ok(summarize(backend_label(first), backend_label(second), pages, options))
+}
+
+/// The backend of `config`, or its ensemble backends joined by `+`.
+fn backend_label(config: &OcrConfig) -> String {
+    if config.ensemble.is_empty() {
+        config.backend.clone()
+    } else {
+        config.ensemble.join("+")
+    }
+}
+
+async fn run_backend(call: impl Future<Output = Result<crate::types::ExtractionResult>>) -> BackendOutput {
+    let started = Instant::now();
+    let result = call.await;
+    let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+    match result {
+        Ok(result) => BackendOutput {
+            content: result.content,
+            duration_ms,
+            error: None,
+        },
+        Err(e) => BackendOutput {
+            content: String::new(),
+            duration_ms,
+            error: Some(e.to_string()),
+        },
+    }
+}
+
+fn summarize(
+    first_backend: String,
+    second_backend: String,
+    pages: Vec<PageComparison>,
+    options: &OcrComparisonOptions,
+) -> OcrComparison {
+    let complete: Vec<&PageComparison> = pages.iter().filter(|page| page.is_complete()).collect();
+    let mean = |value: fn(&PageComparison) -> f64| {
+        if complete.is_empty() {
+            0.0
+        } else {
+            round(complete.iter().map(|page| value(page)).sum::<f64>() / complete.len() as f64)
+        }
+    };
+
+    OcrComparison {
+        first_backend,
+        second_backend,
+        character_agreement: mean(|page| page.character_agreement),
+        word_agreement: mean(|page| page.word_agreement),
+        first_duration_ms: pages.iter().map(|page| page.first.duration_ms).sum(),
+        second_duration_ms: pages.iter().map(|page| page.second.duration_ms).sum(),
+        divergent_pages: pages
+            .iter()
+            .filter(|page| !page.is_complete() || page.character_agreement < options.divergence_threshold)
+            .map(|page| page.page)
+            .collect(),
+        pages,
+    }
+}
+
+/// Images of the sampled pages, with their page numbers.
+fn page_images(
+    content: &[u8],
+    mime_type: &str,
+    config: &OcrConfig,
+    options: &OcrComparisonOptions,
+) -> Result<Vec<(usize, Vec<u8>)>> {
+    if mime_type.starts_with("image/") {
+        return Ok(sample_pages(1, options)
+            .into_iter()
+            .map(|page| (page, content.to_vec()))
+            .collect());
+    }
+
+    #[cfg(feature = "pdf")]
+    if mime_type == "application/pdf" {
+        return render_pdf_pages(content, config, options);
+    }
+    #[cfg(not(feature = "pdf"))]
+    let _ = config;
+
+    Err(KreuzbergError::UnsupportedFormat(format!(
+        "Cannot compare OCR backends on '{}': expected an image or a PDF{}",
+        mime_type,
+        if cfg!(feature = "pdf") {
+            ""
+        } else {
+            " (PDF requires the 'pdf' feature)"
+        }
+    )))
+}
+
+#[cfg(feature = "pdf")]
+fn render_pdf_pages(
+    content: &[u8],
+    config: &OcrConfig,
+    options: &OcrComparisonOptions,
+) -> Result<Vec<(usize, Vec<u8>)>> {
+    use crate::pdf::rendering::{PageRenderOptions, PdfRenderer};
+    use image::ImageEncoder;
+    use image::codecs::png::PngEncoder;
+    use std::io::Cursor;
+
+    let render_error = |e: &dyn std::fmt::Display| KreuzbergError::Parsing {
+        message: format!("Failed to render PDF pages: {}", e),
+        source: None,
+    };
+
+    let renderer = PdfRenderer::new().map_err(|e| KreuzbergError::Parsing {
+        message: format!("Failed to initialize PDF renderer: {}", e),
+        source: None,
+    })?;
+    let page_count = renderer.page_sizes(content).map_err(|e| render_error(&e))?.len();
+
+    let defaults = PageRenderOptions::default();
+    let render_options = match config.dpi {
+        Some(dpi) => {
+            crate::core::config_validation::validate_dpi(dpi)?;
+            PageRenderOptions {
+                target_dpi: dpi,
+                max_dpi: defaults.max_dpi.max(dpi),
+                ..defaults
+            }
+        }
+        None => defaults,
+    };
+
+    sample_pages(page_count, options)
+        .into_iter()
+        .map(|page| {
+            let image = renderer
+                .render_page_to_image(content, page - 1, &render_options)
+                .map_err(|e| render_error(&e))?
+                .to_rgb8();
+            let (width, height) = image.dimensions();
+
+            let mut bytes = Cursor::new(Vec::new());
+            PngEncoder::new(&mut bytes)
+                .write_image(&image, width, height, image::ColorType::Rgb8.into())
+                .map_err(|e| KreuzbergError::Parsing {
+                    message: format!("Failed to encode image: {}", e),
+                    source: None,
+                })?;
+            Ok((page, bytes.into_inner()))
+        })
+        .collect()
+}
+
+/// The selected pages of a document of `page_count` pages, thinned out evenly to the sample size.
+fn sample_pages(page_count: usize, options: &OcrComparisonOptions) -> Vec<usize> {
+    let selected = match &options.pages {
+        Some(selection) => selection.pages(page_count),
+        None => (1..=page_count).collect(),
+    };
+    match options.sample_size {
+        Some(size) if size < selected.len() => (0..size).map(|i| selected[i * selected.len() / size]).collect(),
+        _ => selected,
+    }
+}
+
+/// `1 - edit distance / length of the longer text`, over characters with whitespace collapsed.
+fn character_agreement(first: &str, second: &str) -> f64 {
+    let collapse = |text: &str| -> Vec<char> {
+        let mut chars = Vec::with_capacity(text.len());
+        for word in text.split_whitespace() {
+            if !chars.is_empty() {
+                chars.push(' ');
+            }
+            chars.extend(word.chars());
+        }
+        chars
+    };
+    let (first, second) = (collapse(first), collapse(second));
+    let longest = first.len().max(second.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - edit_distance(&first, &second) as f64 / longest as f64
+}
+
+/// Levenshtein distance, with a single row of the matrix.
+fn edit_distance(first: &[char], second: &[char]) -> usize {
+    let mut row: Vec<usize> = (0..=second.len()).collect();
+    for (i, a) in first.iter().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, b) in second.iter().enumerate() {
+            let substitution = diagonal + usize::from(a != b);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[second.len()]
+}
+
+fn round(value: f64) -> f64 {
+    (value * 1000.0).round() / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::{OcrBackend, OcrBackendType, Plugin, register_ocr_backend, unregister_ocr_backend};
+    use crate::types::{ExtractionResult, Metadata};
+    use async_trait::async_trait;
+    use serial_test::serial;
+    use std::sync::Arc;
+
+    /// Returns the image bytes as text, with `l` read as `1` when `misreads` is set.
+    struct EchoBackend {
+        name: &'static str,
+        misreads: bool,
+    }
+
+    impl Plugin for EchoBackend {
+        fn name(&self) -> &str {
+            self.name
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl OcrBackend for EchoBackend {
+        async fn process_image(&self, image_bytes: &[u8], _config: &OcrConfig) -> Result<ExtractionResult> {
+            let text = String::from_utf8_lossy(image_bytes);
+            if text.is_empty() {
+                return Err(KreuzbergError::ocr("empty image"));
+            }
+            let content = if self.misreads {
+                text.replace('l', "1")
+            } else {
+                text.into_owned()
+            };
+            Ok(ExtractionResult {
+                content,
+                mime_type: std::borrow::Cow::Borrowed("text/plain"),
+                metadata: Metadata::default(),
+                tables: vec![],
+                detected_languages: None,
+                chunks: None,
+                images: None,
+                djot_content: None,
+                scripts: None,
+                pages: None,
+                elements: None,
+            })
+        }
+
+        fn supports_language(&self, _lang: &str) -> bool {
+            true
+        }
+
+        fn backend_type(&self) -> OcrBackendType {
+            OcrBackendType::Custom
+        }
+    }
+
+    fn config(backend: &str) -> OcrConfig {
+        OcrConfig {
+            backend: backend.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_character_agreement() {
+        assert_eq!(character_agreement("Total  amount\ndue", "Total amount due"), 1.0);
+        assert_eq!(character_agreement("", ""), 1.0);
+        assert_eq!(character_agreement("abc", ""), 0.0);
+        assert_eq!(round(character_agreement("kitten", "sitting")), 0.571);
+    }
+
+    #[test]
+    fn test_sample_pages_spreads_evenly() {
+        let options = OcrComparisonOptions {
+            sample_size: Some(4),
+            ..Default::default()
+        };
+        assert_eq!(sample_pages(100, &options), vec![1, 26, 51, 76]);
+        assert_eq!(sample_pages(3, &options), vec![1, 2, 3]);
+
+        let options = OcrComparisonOptions {
+            pages: Some("10-19".parse().unwrap()),
+            sample_size: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(sample_pages(100, &options), vec![10, 15]);
+        assert_eq!(sample_pages(5, &options), Vec::<usize>::new());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_compare_ocr_backends_on_image() {
+        register_ocr_backend(Arc::new(EchoBackend {
+            name: "ab-exact",
+            misreads: false,
+        }))
+        .unwrap();
+        register_ocr_backend(Arc::new(EchoBackend {
+            name: "ab-misreads",
+            misreads: true,
+        }))
+        .unwrap();
+
+        let image = b"Invoice total: 120 EUR, payable within thirty days";
+        let comparison = compare_ocr_backends(
+            image,
+            "image/png",
+            &config("ab-exact"),
+            &config("ab-misreads"),
+            &OcrComparisonOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(comparison.first_backend, "ab-exact");
+        assert_eq!(comparison.pages.len(), 1);
+        let page = &comparison.pages[0];
+        assert_eq!(page.page, 1);
+        assert_eq!(
+            page.second.content,
+            "Invoice tota1: 120 EUR, payab1e within thirty days"
+        );
+        assert_eq!(page.character_agreement, 0.96);
+        assert_eq!(page.word_agreement, 0.75);
+        assert_eq!(page.differences.len(), 2);
+        assert_eq!(page.differences[0].new_text.as_deref(), Some("tota1:"));
+        assert_eq!(comparison.character_agreement, 0.96);
+        assert!(comparison.divergent_pages.is_empty());
+
+        let failing = compare_ocr_backends(
+            b"",
+            "image/png",
+            &config("ab-exact"),
+            &config("ab-misreads"),
+            &OcrComparisonOptions::default(),
+        )
+        .await
+        .unwrap();
+        assert!(failing.pages[0].first.error.is_some());
+        assert_eq!(failing.divergent_pages, vec![1]);
+        assert_eq!(failing.character_agreement, 0.0);
+
+        let unknown = compare_ocr_backends(
+            image,
+            "image/png",
+            &config("ab-exact"),
+            &config("ab-unknown"),
+            &OcrComparisonOptions::default(),
+        )
+        .await;
+        assert!(unknown.is_err());
+        assert!(
+            compare_ocr_backends(
+                image,
+                "text/plain",
+                &config("ab-exact"),
+                &config("ab-misreads"),
+                &OcrComparisonOptions::default(),
+            )
+            .await
+            .is_err()
+        );
+
+        unregister_ocr_backend("ab-exact").unwrap();
+        unregister_ocr_backend("ab-misreads").unwrap();
+    }
+}
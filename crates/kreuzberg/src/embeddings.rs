@@ -170,6 +170,15 @@ fn onnx_runtime_install_message() -> String {
     }
 }
 
+/// Model cache directory used when `EmbeddingConfig::cache_dir` is not set.
+#[cfg(feature = "embeddings")]
+fn default_cache_dir() -> std::path::PathBuf {
+    let mut path = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
+    path.push(".kreuzberg");
+    path.push("embeddings");
+    path
+}
+
 /// Get or initialize a text embedding model from cache.
 ///
 /// This function ensures models are initialized only once and reused across
@@ -180,12 +189,7 @@ pub fn get_or_init_model(
     model: EmbeddingModel,
     cache_dir: Option<std::path::PathBuf>,
 ) -> crate::Result<CachedEmbedding> {
-    let cache_directory = cache_dir.unwrap_or_else(|| {
-        let mut path = std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."));
-        path.push(".kreuzberg");
-        path.push("embeddings");
-        path
-    });
+    let cache_directory = cache_dir.unwrap_or_else(default_cache_dir);
 
     let model_key = format!("{:?}_{}", model, cache_directory.display());
 
@@ -443,6 +447,61 @@ pub fn list_presets() -> Vec<&'static str> {
     EMBEDDING_PRESETS.iter().map(|p| p.name).collect()
 }
 
+/// fastembed model of an embedding configuration.
+#[cfg(feature = "embeddings")]
+fn resolve_model(config: &crate::core::config::EmbeddingConfig) -> crate::Result<EmbeddingModel> {
+    Ok(match &config.model {
+        crate::core::config::EmbeddingModelType::Preset { name } => {
+            let preset = get_preset(name).ok_or_else(|| crate::KreuzbergError::Plugin {
+                message: format!("Unknown embedding preset: {}", name),
+                plugin_name: "embeddings".to_string(),
+            })?;
+            preset.model.clone()
+        }
+        #[cfg(feature = "embeddings")]
+        crate::core::config::EmbeddingModelType::FastEmbed { model, .. } => match model.as_str() {
+            "AllMiniLML6V2Q" => fastembed::EmbeddingModel::AllMiniLML6V2Q,
+            "BGEBaseENV15" => fastembed::EmbeddingModel::BGEBaseENV15,
+            "BGELargeENV15" => fastembed::EmbeddingModel::BGELargeENV15,
+            "MultilingualE5Base" => fastembed::EmbeddingModel::MultilingualE5Base,
+            _ => {
+                return Err(crate::KreuzbergError::Plugin {
+                    message: format!("Unknown fastembed model: {}", model),
+                    plugin_name: "embeddings".to_string(),
+                });
+            }
+        },
+        crate::core::config::EmbeddingModelType::Custom { .. } => {
+            return Err(crate::KreuzbergError::Plugin {
+                message: "Custom ONNX models are not yet supported for embedding generation".to_string(),
+                plugin_name: "embeddings".to_string(),
+            });
+        }
+    })
+}
+
+/// Whether the model of `config` is loaded or in its cache directory, so using it
+/// does not download anything.
+#[cfg(feature = "embeddings")]
+pub(crate) fn is_model_downloaded(config: &crate::core::config::EmbeddingConfig) -> bool {
+    let Ok(model) = resolve_model(config) else {
+        return false;
+    };
+    let cache_directory = config.cache_dir.clone().unwrap_or_else(default_cache_dir);
+
+    let model_key = format!("{:?}_{}", model, cache_directory.display());
+    let loaded = match MODEL_CACHE.read() {
+        Ok(cache) => cache.contains_key(&model_key),
+        Err(poison_error) => poison_error.get_ref().contains_key(&model_key),
+    };
+    loaded
+        || TextEmbedding::get_model_info(&model).is_ok_and(|info| {
+            cache_directory
+                .join(format!("models--{}", info.model_code.replace('/', "--")))
+                .is_dir()
+        })
+}
+
 /// Generate embeddings for text chunks using the specified configuration.
 ///
 /// This function modifies chunks in-place, populating their `embedding` field
@@ -478,35 +537,7 @@ pub fn generate_embeddings_for_chunks(
         return Ok(());
     }
 
-    let fastembed_model = match &config.model {
-        crate::core::config::EmbeddingModelType::Preset { name } => {
-            let preset = get_preset(name).ok_or_else(|| crate::KreuzbergError::Plugin {
-                message: format!("Unknown embedding preset: {}", name),
-                plugin_name: "embeddings".to_string(),
-            })?;
-            preset.model.clone()
-        }
-        #[cfg(feature = "embeddings")]
-        crate::core::config::EmbeddingModelType::FastEmbed { model, .. } => match model.as_str() {
-            "AllMiniLML6V2Q" => fastembed::EmbeddingModel::AllMiniLML6V2Q,
-            "BGEBaseENV15" => fastembed::EmbeddingModel::BGEBaseENV15,
-            "BGELargeENV15" => fastembed::EmbeddingModel::BGELargeENV15,
-            "MultilingualE5Base" => fastembed::EmbeddingModel::MultilingualE5Base,
-            _ => {
-                return Err(crate::KreuzbergError::Plugin {
-                    message: format!("Unknown fastembed model: {}", model),
-                    plugin_name: "embeddings".to_string(),
-                });
-            }
-        },
-        crate::core::config::EmbeddingModelType::Custom { .. } => {
-            return Err(crate::KreuzbergError::Plugin {
-                message: "Custom ONNX models are not yet supported for embedding generation".to_string(),
-                plugin_name: "embeddings".to_string(),
-            });
-        }
-    };
-
+    let fastembed_model = resolve_model(config)?;
     let model = get_or_init_model(fastembed_model, config.cache_dir.clone())?;
 
     let texts: Vec<String> = chunks.iter().map(|chunk| chunk.content.clone()).collect();
@@ -17,6 +17,7 @@
 //! **Application errors are wrapped with context:**
 //! - `Parsing` - Document format errors, corrupt files
 //! - `Validation` - Invalid configuration or parameters
+//! - `Security` - Inputs rejected by a security limit or policy
 //! - `Ocr` - OCR processing failures
 //! - `MissingDependency` - Missing optional system dependencies
 //!
@@ -39,6 +40,7 @@
 //!     Ok(content)
 //! }
 //! ```
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Result type alias using `KreuzbergError`.
@@ -57,11 +59,13 @@ pub type Result<T> = std::result::Result<T, KreuzbergError>;
 /// - `Parsing` - Document parsing errors (corrupt files, unsupported features)
 /// - `Ocr` - OCR processing errors
 /// - `Validation` - Input validation errors (invalid paths, config, parameters)
+/// - `Security` - Inputs rejected by a security limit or policy (entity expansion, blocked macros)
 /// - `Cache` - Cache operation errors (non-fatal, can be ignored)
 /// - `ImageProcessing` - Image manipulation errors
 /// - `Serialization` - JSON/MessagePack serialization errors
 /// - `MissingDependency` - Missing optional dependencies (tesseract, etc.)
 /// - `Plugin` - Plugin-specific errors
+/// - `ExtractorPanicked` - An extractor panicked; the panic was caught and the process kept running
 /// - `LockPoisoned` - Mutex/RwLock poisoning (should not happen in normal operation)
 /// - `UnsupportedFormat` - Unsupported MIME type or file format
 /// - `Other` - Catch-all for uncommon errors
@@ -91,6 +95,13 @@ pub enum KreuzbergError {
         source: Option<Box<dyn std::error::Error + Send + Sync>>,
     },
 
+    #[error("Security error: {message}")]
+    Security {
+        message: String,
+        #[source]
+        source: Option<Box<dyn std::error::Error + Send + Sync>>,
+    },
+
     #[error("Cache error: {message}")]
     Cache {
         message: String,
@@ -118,6 +129,9 @@ pub enum KreuzbergError {
     #[error("Plugin error in '{plugin_name}': {message}")]
     Plugin { message: String, plugin_name: String },
 
+    #[error("Extractor '{name}' panicked: {payload}")]
+    ExtractorPanicked { name: String, payload: String },
+
     #[error("Lock poisoned: {0}")]
     LockPoisoned(String),
 
@@ -204,9 +218,50 @@ impl KreuzbergError {
     error_constructor!(parsing, Parsing);
     error_constructor!(ocr, Ocr);
     error_constructor!(validation, Validation);
+    error_constructor!(security, Security);
     error_constructor!(cache, Cache);
     error_constructor!(image_processing, ImageProcessing);
     error_constructor!(serialization, Serialization);
+
+    /// The variant of this error, without its details.
+    pub fn class(&self) -> ErrorClass {
+        match self {
+            Self::Io(_) => ErrorClass::Io,
+            Self::Parsing { .. } => ErrorClass::Parsing,
+            Self::Ocr { .. } => ErrorClass::Ocr,
+            Self::Validation { .. } => ErrorClass::Validation,
+            Self::Security { .. } => ErrorClass::Security,
+            Self::Cache { .. } => ErrorClass::Cache,
+            Self::ImageProcessing { .. } => ErrorClass::ImageProcessing,
+            Self::Serialization { .. } => ErrorClass::Serialization,
+            Self::MissingDependency(_) => ErrorClass::MissingDependency,
+            Self::Plugin { .. } => ErrorClass::Plugin,
+            Self::ExtractorPanicked { .. } => ErrorClass::ExtractorPanicked,
+            Self::LockPoisoned(_) => ErrorClass::LockPoisoned,
+            Self::UnsupportedFormat(_) => ErrorClass::UnsupportedFormat,
+            Self::Other(_) => ErrorClass::Other,
+        }
+    }
+}
+
+/// Variant of a [`KreuzbergError`], as named in configuration (e.g. retry policies).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorClass {
+    Io,
+    Parsing,
+    Ocr,
+    Validation,
+    Security,
+    Cache,
+    ImageProcessing,
+    Serialization,
+    MissingDependency,
+    Plugin,
+    ExtractorPanicked,
+    LockPoisoned,
+    UnsupportedFormat,
+    Other,
 }
 
 #[cfg(test)]
@@ -221,6 +276,28 @@ mod tests {
         assert!(krz_err.to_string().contains("IO error"));
     }
 
+    #[test]
+    fn test_error_class() {
+        assert_eq!(KreuzbergError::ocr("backend unavailable").class(), ErrorClass::Ocr);
+        assert_eq!(
+            KreuzbergError::from(std::io::Error::other("reset")).class(),
+            ErrorClass::Io
+        );
+        assert_eq!(
+            serde_json::to_string(&ErrorClass::ImageProcessing).unwrap(),
+            "\"image_processing\""
+        );
+        let panicked = KreuzbergError::ExtractorPanicked {
+            name: "docx-extractor".to_string(),
+            payload: "index out of bounds".to_string(),
+        };
+        assert_eq!(panicked.class(), ErrorClass::ExtractorPanicked);
+        assert_eq!(
+            panicked.to_string(),
+            "Extractor 'docx-extractor' panicked: index out of bounds"
+        );
+    }
+
     #[test]
     fn test_parsing_error() {
         let err = KreuzbergError::parsing("invalid format");
@@ -263,6 +340,13 @@ mod tests {
         assert!(std::error::Error::source(&err).is_some());
     }
 
+    #[test]
+    fn test_security_error() {
+        let err = KreuzbergError::security("entity expansion limit exceeded");
+        assert!(matches!(err, KreuzbergError::Security { .. }));
+        assert_eq!(err.to_string(), "Security error: entity expansion limit exceeded");
+    }
+
     #[test]
     fn test_cache_error() {
         let err = KreuzbergError::cache("cache write failed");
@@ -13,8 +13,11 @@ mod tar;
 mod zip;
 
 // Re-export all public functions for backward compatibility
+pub(crate) use sevenz::visit_7z_members;
 pub use sevenz::{extract_7z_metadata, extract_7z_text_content};
+pub(crate) use tar::visit_tar_members;
 pub use tar::{extract_tar_metadata, extract_tar_text_content};
+pub(crate) use zip::visit_zip_members;
 pub use zip::{extract_zip_metadata, extract_zip_text_content};
 
 /// Archive metadata extracted from an archive file.
@@ -46,6 +49,38 @@ pub(crate) const TEXT_EXTENSIONS: &[&str] = &[
     ".txt", ".md", ".json", ".xml", ".html", ".csv", ".log", ".yaml", ".toml",
 ];
 
+/// Summary of an archive that starts its text output: the file count, the total size
+/// and the file list.
+pub(crate) fn archive_summary(format_name: &str, metadata: &ArchiveMetadata) -> String {
+    let mut output = format!(
+        "{} Archive ({} files, {} bytes)\n\n",
+        format_name, metadata.file_count, metadata.total_size
+    );
+    output.push_str("Files:\n");
+    for entry in &metadata.file_list {
+        output.push_str(&format!("- {} ({} bytes)\n", entry.path, entry.size));
+    }
+    output
+}
+
+/// Name of an archive member from the raw bytes of its path.
+///
+/// TAR and ZIP store names without an encoding. Valid UTF-8 is taken as is and
+/// anything else is read as Latin-1, so distinct names stay distinct and the original
+/// bytes can be recovered.
+pub(crate) fn decode_member_name(raw: &[u8]) -> String {
+    match std::str::from_utf8(raw) {
+        Ok(name) => name.to_string(),
+        Err(_) => raw.iter().map(|&byte| char::from(byte)).collect(),
+    }
+}
+
+/// Whether the file at `path` is read as text.
+pub(crate) fn is_text_file(path: &str) -> bool {
+    let path = path.to_lowercase();
+    TEXT_EXTENSIONS.iter().any(|ext| path.ends_with(ext))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -641,6 +676,55 @@ mod tests {
         assert_eq!(contents.len(), 1);
     }
 
+    #[test]
+    fn test_extract_tar_non_utf8_names() {
+        let mut cursor = Cursor::new(Vec::new());
+        {
+            let mut tar = TarBuilder::new(&mut cursor);
+            for (name, data) in [(&b"caf\xe9.txt"[..], b"first"), (&b"caf\xe8.txt"[..], b"other")] {
+                let mut header = ::tar::Header::new_gnu();
+                header.as_gnu_mut().unwrap().name[..name.len()].copy_from_slice(name);
+                header.set_size(data.len() as u64);
+                header.set_cksum();
+                tar.append(&header, &data[..]).unwrap();
+            }
+            tar.finish().unwrap();
+        }
+
+        let bytes = cursor.into_inner();
+        let metadata = extract_tar_metadata(&bytes).unwrap();
+        let paths: Vec<_> = metadata.file_list.iter().map(|entry| entry.path.as_str()).collect();
+        assert_eq!(paths, ["caf\u{e9}.txt", "caf\u{e8}.txt"]);
+
+        let contents = extract_tar_text_content(&bytes).unwrap();
+        assert_eq!(contents.len(), 2);
+        assert_eq!(contents["caf\u{e9}.txt"], "first");
+    }
+
+    #[test]
+    fn test_extract_zip_utf8_names_without_flag() {
+        let mut cursor = Cursor::new(Vec::new());
+        {
+            let mut zip = ZipWriter::new(&mut cursor);
+            zip.start_file("r\u{e9}sum\u{e9}.txt", FileOptions::<'_, ()>::default())
+                .unwrap();
+            zip.write_all(b"Curriculum").unwrap();
+            zip.finish().unwrap();
+        }
+
+        // Clear the UTF-8 flag, as archivers on macOS and Linux do.
+        let mut bytes = cursor.into_inner();
+        for (signature, flags_offset) in [(&b"PK\x03\x04"[..], 6), (&b"PK\x01\x02"[..], 8)] {
+            let start = bytes.windows(4).position(|window| window == signature).unwrap();
+            bytes[start + flags_offset + 1] &= !(1 << 3);
+        }
+
+        let metadata = extract_zip_metadata(&bytes).unwrap();
+        assert_eq!(metadata.file_list[0].path, "r\u{e9}sum\u{e9}.txt");
+        let contents = extract_zip_text_content(&bytes).unwrap();
+        assert_eq!(contents["r\u{e9}sum\u{e9}.txt"], "Curriculum");
+    }
+
     #[test]
     fn test_extract_7z_text_content() {
         use sevenz_rust2::{ArchiveEntry as SevenzEntry, ArchiveWriter};
@@ -6,7 +6,7 @@ use super::{ArchiveEntry, ArchiveMetadata, TEXT_EXTENSIONS};
 use crate::error::{KreuzbergError, Result};
 use sevenz_rust2::{ArchiveReader, Password};
 use std::collections::HashMap;
-use std::io::Cursor;
+use std::io::{Cursor, Read};
 
 /// Extract metadata from a 7z archive.
 ///
@@ -96,3 +96,37 @@ pub fn extract_7z_text_content(bytes: &[u8]) -> Result<HashMap<String, String>>
 
     Ok(contents)
 }
+
+/// Visit the files within a 7z archive in archive order.
+///
+/// `visit` is called with the path, the size and a reader of the content of every
+/// file; it only reads the files it needs.
+///
+/// # Errors
+///
+/// Returns an error if the 7z archive cannot be read or `visit` fails.
+pub(crate) fn visit_7z_members(
+    bytes: &[u8],
+    mut visit: impl FnMut(&str, u64, &mut dyn Read) -> Result<()>,
+) -> Result<()> {
+    let cursor = Cursor::new(bytes);
+    let mut archive = ArchiveReader::new(cursor, Password::empty())
+        .map_err(|e| KreuzbergError::parsing(format!("Failed to read 7z archive: {}", e)))?;
+
+    let mut visit_error = None;
+    archive
+        .for_each_entries(|entry, reader| {
+            if !entry.is_directory() {
+                if let Err(e) = visit(entry.name(), entry.size(), reader) {
+                    visit_error = Some(e);
+                    return Ok(false);
+                }
+                // Entries of a solid block share one stream: skip what was not read.
+                std::io::copy(reader, &mut std::io::sink())?;
+            }
+            Ok(true)
+        })
+        .map_err(|e| KreuzbergError::parsing(format!("Failed to read 7z entries: {}", e)))?;
+
+    visit_error.map_or(Ok(()), Err)
+}
@@ -3,7 +3,7 @@
 //! Provides functions for extracting metadata and text content from TAR archives.
 //! Supports plain TAR as well as compressed variants (TAR.GZ, TAR.BZ2).
 
-use super::{ArchiveEntry, ArchiveMetadata, TEXT_EXTENSIONS};
+use super::{ArchiveEntry, ArchiveMetadata, TEXT_EXTENSIONS, decode_member_name};
 use crate::error::{KreuzbergError, Result};
 use std::collections::HashMap;
 use std::io::{Cursor, Read};
@@ -42,11 +42,7 @@ pub fn extract_tar_metadata(bytes: &[u8]) -> Result<ArchiveMetadata> {
     for entry_result in entries {
         let entry = entry_result.map_err(|e| KreuzbergError::parsing(format!("Failed to read TAR entry: {}", e)))?;
 
-        let path = entry
-            .path()
-            .map_err(|e| KreuzbergError::parsing(format!("Failed to read TAR entry path: {}", e)))?
-            .to_string_lossy()
-            .to_string();
+        let path = decode_member_name(&entry.path_bytes());
 
         let size = entry.size();
         let is_dir = entry.header().entry_type().is_dir();
@@ -98,11 +94,7 @@ pub fn extract_tar_text_content(bytes: &[u8]) -> Result<HashMap<String, String>>
         let mut entry =
             entry_result.map_err(|e| KreuzbergError::parsing(format!("Failed to read TAR entry: {}", e)))?;
 
-        let path = entry
-            .path()
-            .map_err(|e| KreuzbergError::parsing(format!("Failed to read TAR entry path: {}", e)))?
-            .to_string_lossy()
-            .to_string();
+        let path = decode_member_name(&entry.path_bytes());
 
         if !entry.header().entry_type().is_dir() && TEXT_EXTENSIONS.iter().any(|ext| path.to_lowercase().ends_with(ext))
         {
@@ -116,3 +108,37 @@ pub fn extract_tar_text_content(bytes: &[u8]) -> Result<HashMap<String, String>>
 
     Ok(contents)
 }
+
+/// Visit the files within a TAR archive in archive order.
+///
+/// `visit` is called with the path, the size and a reader of the content of every
+/// file; it only reads the files it needs.
+///
+/// # Errors
+///
+/// Returns an error if the TAR archive cannot be read or `visit` fails.
+pub(crate) fn visit_tar_members(
+    bytes: &[u8],
+    mut visit: impl FnMut(&str, u64, &mut dyn Read) -> Result<()>,
+) -> Result<()> {
+    let cursor = Cursor::new(bytes);
+    let mut archive = TarArchive::new(cursor);
+
+    let entries = archive
+        .entries()
+        .map_err(|e| KreuzbergError::parsing(format!("Failed to read TAR archive: {}", e)))?;
+
+    for entry_result in entries {
+        let mut entry =
+            entry_result.map_err(|e| KreuzbergError::parsing(format!("Failed to read TAR entry: {}", e)))?;
+
+        let path = decode_member_name(&entry.path_bytes());
+
+        if !entry.header().entry_type().is_dir() {
+            let size = entry.size();
+            visit(&path, size, &mut entry)?;
+        }
+    }
+
+    Ok(())
+}
@@ -7,6 +7,7 @@ use crate::error::{KreuzbergError, Result};
 use std::collections::HashMap;
 use std::io::{Cursor, Read};
 use zip::ZipArchive;
+use zip::read::ZipFile;
 
 /// Extract metadata from a ZIP archive.
 ///
@@ -38,7 +39,7 @@ pub fn extract_zip_metadata(bytes: &[u8]) -> Result<ArchiveMetadata> {
             .by_index(i)
             .map_err(|e| KreuzbergError::parsing(format!("Failed to read ZIP entry: {}", e)))?;
 
-        let path = file.name().to_string();
+        let path = member_name(&file);
         let size = file.size();
         let is_dir = file.is_dir();
 
@@ -86,7 +87,7 @@ pub fn extract_zip_text_content(bytes: &[u8]) -> Result<HashMap<String, String>>
             .by_index(i)
             .map_err(|e| KreuzbergError::parsing(format!("Failed to read ZIP entry: {}", e)))?;
 
-        let path = file.name().to_string();
+        let path = member_name(&file);
 
         if !file.is_dir() && TEXT_EXTENSIONS.iter().any(|ext| path.to_lowercase().ends_with(ext)) {
             let estimated_size = (file.size() as usize).min(10 * 1024 * 1024);
@@ -99,3 +100,46 @@ pub fn extract_zip_text_content(bytes: &[u8]) -> Result<HashMap<String, String>>
 
     Ok(contents)
 }
+
+/// Visit the files within a ZIP archive in archive order.
+///
+/// `visit` is called with the path, the uncompressed size and a reader of the content
+/// of every file; it only reads the files it needs.
+///
+/// # Errors
+///
+/// Returns an error if the ZIP archive cannot be read or `visit` fails.
+pub(crate) fn visit_zip_members(
+    bytes: &[u8],
+    mut visit: impl FnMut(&str, u64, &mut dyn Read) -> Result<()>,
+) -> Result<()> {
+    let cursor = Cursor::new(bytes);
+    let mut archive =
+        ZipArchive::new(cursor).map_err(|e| KreuzbergError::parsing(format!("Failed to read ZIP archive: {}", e)))?;
+
+    for i in 0..archive.len() {
+        let mut file = archive
+            .by_index(i)
+            .map_err(|e| KreuzbergError::parsing(format!("Failed to read ZIP entry: {}", e)))?;
+
+        if !file.is_dir() {
+            let path = member_name(&file);
+            let size = file.size();
+            visit(&path, size, &mut file)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Name of a ZIP member.
+///
+/// Archivers on macOS and Linux store UTF-8 names without setting the UTF-8 flag, which
+/// the `zip` crate then decodes as CP437. Raw names that are valid UTF-8 are used as is,
+/// and the CP437 name only for the rest.
+fn member_name<R: Read>(file: &ZipFile<'_, R>) -> String {
+    match std::str::from_utf8(file.name_raw()) {
+        Ok(name) => name.to_string(),
+        Err(_) => file.name().to_string(),
+    }
+}
@@ -0,0 +1,233 @@
+//! Barcode and QR code detection.
+//!
+//! Images, TIFF pages and rendered PDF pages are scanned with rxing for all supported
+//! symbologies, or for those selected in [`BarcodeConfig::formats`]. Several symbols
+//! can be decoded from one image; each is reported with its payload, symbology and
+//! position.
+
+use crate::core::config::BarcodeConfig;
+use crate::error::{KreuzbergError, Result};
+use crate::extraction::image::{TiffPages, decode_image};
+use crate::types::{Barcode, BarcodePoint};
+use image::DynamicImage;
+use rxing::{BarcodeFormat, DecodeHints, Exceptions};
+use std::collections::HashSet;
+
+/// Symbologies by the names used in configuration and results.
+const FORMATS: &[(&str, BarcodeFormat)] = &[
+    ("aztec", BarcodeFormat::AZTEC),
+    ("codabar", BarcodeFormat::CODABAR),
+    ("code_39", BarcodeFormat::CODE_39),
+    ("code_93", BarcodeFormat::CODE_93),
+    ("code_128", BarcodeFormat::CODE_128),
+    ("data_matrix", BarcodeFormat::DATA_MATRIX),
+    ("ean_8", BarcodeFormat::EAN_8),
+    ("ean_13", BarcodeFormat::EAN_13),
+    ("itf", BarcodeFormat::ITF),
+    ("maxicode", BarcodeFormat::MAXICODE),
+    ("pdf_417", BarcodeFormat::PDF_417),
+    ("qr_code", BarcodeFormat::QR_CODE),
+    ("micro_qr_code", BarcodeFormat::MICRO_QR_CODE),
+    ("rectangular_micro_qr_code", BarcodeFormat::RECTANGULAR_MICRO_QR_CODE),
+    ("rss_14", BarcodeFormat::RSS_14),
+    ("rss_expanded", BarcodeFormat::RSS_EXPANDED),
+    ("telepen", BarcodeFormat::TELEPEN),
+    ("upc_a", BarcodeFormat::UPC_A),
+    ("upc_e", BarcodeFormat::UPC_E),
+    ("upc_ean_extension", BarcodeFormat::UPC_EAN_EXTENSION),
+];
+
+/// Decode the barcodes in an image.
+///
+/// Points are in pixels of `image`. Returns an empty list when no symbol is found.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Validation` when `config.formats` names an unknown symbology.
+pub fn detect_barcodes(image: &DynamicImage, config: &BarcodeConfig) -> Result<Vec<Barcode>> {
+    let mut hints = DecodeHints {
+        TryHarder: Some(true),
+        ..Default::default()
+    };
+    if !config.formats.is_empty() {
+        hints.PossibleFormats = Some(possible_formats(&config.formats)?);
+    }
+
+    let luma = image.to_luma8();
+    let (width, height) = luma.dimensions();
+    if width == 0 || height == 0 {
+        return Ok(Vec::new());
+    }
+
+    let results = match rxing::helpers::detect_multiple_in_luma_with_hints(luma.into_raw(), width, height, &mut hints) {
+        Ok(results) => results,
+        Err(Exceptions::NotFoundException(_)) => return Ok(Vec::new()),
+        Err(e) => {
+            tracing::debug!("Barcode detection failed: {}", e);
+            return Ok(Vec::new());
+        }
+    };
+
+    Ok(results
+        .iter()
+        .map(|result| Barcode {
+            value: result.getText().to_string(),
+            format: format_name(result.getBarcodeFormat()),
+            page_number: None,
+            points: result
+                .getPoints()
+                .iter()
+                .map(|point| BarcodePoint {
+                    x: f64::from(point.x),
+                    y: f64::from(point.y),
+                })
+                .collect(),
+        })
+        .collect())
+}
+
+/// Decode the barcodes in image bytes; TIFF pages are scanned one by one.
+///
+/// Barcodes found in a TIFF carry their page number.
+pub fn detect_image_barcodes(content: &[u8], mime_type: &str, config: &BarcodeConfig) -> Result<Vec<Barcode>> {
+    if !mime_type.to_lowercase().contains("tiff") {
+        let image =
+            decode_image(content).map_err(|e| KreuzbergError::parsing(format!("Failed to decode image: {}", e)))?;
+        return detect_barcodes(&image, config);
+    }
+
+    let mut barcodes = Vec::new();
+    for (page_index, page) in TiffPages::new(content)?.enumerate() {
+        for mut barcode in detect_barcodes(&page?, config)? {
+            barcode.page_number = Some(page_index + 1);
+            barcodes.push(barcode);
+        }
+    }
+    Ok(barcodes)
+}
+
+/// Render every page of a PDF at `config.pdf_dpi` and decode its barcodes.
+///
+/// Points are converted to PDF points from the top-left corner of the page.
+#[cfg(feature = "pdf")]
+pub fn detect_pdf_barcodes(content: &[u8], config: &BarcodeConfig) -> Result<Vec<Barcode>> {
+    use crate::pdf::rendering::{PageRenderOptions, PdfRenderer};
+
+    let render_error = |e: crate::pdf::error::PdfError| KreuzbergError::Parsing {
+        message: format!("Failed to render PDF pages: {}", e),
+        source: None,
+    };
+    crate::core::config_validation::validate_dpi(config.pdf_dpi)?;
+    let defaults = PageRenderOptions::default();
+    let render_options = PageRenderOptions {
+        target_dpi: config.pdf_dpi,
+        max_dpi: defaults.max_dpi.max(config.pdf_dpi),
+        ..defaults
+    };
+
+    let renderer = PdfRenderer::new().map_err(render_error)?;
+    let page_sizes = renderer.page_sizes(content).map_err(render_error)?;
+
+    let mut barcodes = Vec::new();
+    for (page_index, (width_points, _)) in page_sizes.into_iter().enumerate() {
+        let image = renderer
+            .render_page_to_image(content, page_index, &render_options)
+            .map_err(render_error)?;
+        // The renderer may lower the resolution of large pages, so the scale is measured.
+        let scale = f64::from(width_points) / f64::from(image.width().max(1));
+        for mut barcode in detect_barcodes(&image, config)? {
+            barcode.page_number = Some(page_index + 1);
+            for point in &mut barcode.points {
+                point.x *= scale;
+                point.y *= scale;
+            }
+            barcodes.push(barcode);
+        }
+    }
+    Ok(barcodes)
+}
+
+fn possible_formats(names: &[String]) -> Result<HashSet<BarcodeFormat>> {
+    names
+        .iter()
+        .map(|name| {
+            FORMATS
+                .iter()
+                .find(|(known, _)| known.eq_ignore_ascii_case(name.trim()))
+                .map(|&(_, format)| format)
+                .ok_or_else(|| {
+                    let known: Vec<&str> = FORMATS.iter().map(|(known, _)| *known).collect();
+                    KreuzbergError::validation(format!(
+                        "Unknown barcode format '{}'. Supported formats: {}",
+                        name,
+                        known.join(", ")
+                    ))
+                })
+        })
+        .collect()
+}
+
+fn format_name(format: &BarcodeFormat) -> String {
+    FORMATS
+        .iter()
+        .find(|(_, known)| known == format)
+        .map_or_else(|| format.to_string().to_lowercase(), |(name, _)| name.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture() -> Vec<u8> {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../test_documents/images/barcodes.png");
+        std::fs::read(path).unwrap()
+    }
+
+    #[test]
+    fn test_detect_qr_code_and_code_128() {
+        let mut barcodes = detect_image_barcodes(&fixture(), "image/png", &BarcodeConfig::default()).unwrap();
+        barcodes.sort_by(|a, b| a.format.cmp(&b.format));
+
+        let found: Vec<_> = barcodes
+            .iter()
+            .map(|barcode| (barcode.format.as_str(), barcode.value.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("code_128", "ROUTE-0042"),
+                ("qr_code", "https://kreuzberg.dev/slip/4711")
+            ]
+        );
+        assert!(barcodes.iter().all(|barcode| barcode.page_number.is_none()));
+        // The QR code is drawn at the top left, the Code 128 symbol to its right.
+        assert!(barcodes[1].points.iter().all(|point| point.x < 250.0));
+        assert!(barcodes[0].points.iter().all(|point| point.x > 250.0));
+    }
+
+    #[test]
+    fn test_formats_restrict_symbologies() {
+        let config = BarcodeConfig {
+            formats: vec!["QR_CODE".to_string()],
+            ..Default::default()
+        };
+        let barcodes = detect_image_barcodes(&fixture(), "image/png", &config).unwrap();
+        assert_eq!(barcodes.len(), 1);
+        assert_eq!(barcodes[0].format, "qr_code");
+
+        let config = BarcodeConfig {
+            formats: vec!["qr".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            detect_image_barcodes(&fixture(), "image/png", &config),
+            Err(KreuzbergError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_image_without_barcodes() {
+        let image = DynamicImage::new_luma8(64, 64);
+        assert!(detect_barcodes(&image, &BarcodeConfig::default()).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,364 @@
+//! CCITT Group 3 fax decoding for TIFF pages.
+//!
+//! The `tiff` crate decodes Group 4 (T.6) fax pages but not Group 3, which most fax
+//! machines and fax servers produce. This module decodes the two Group 3 variants of
+//! TIFF: Modified Huffman rows (compression 2) and T.4 rows (compression 3), which
+//! start with an EOL code and may be coded relative to the previous row when
+//! `T4Options` enables two-dimensional coding.
+//!
+//! Rows are decoded strip by strip into 8-bit gray pixels, white as 255 and black as 0.
+
+use fax::maps::{Mode, black, mode, white};
+use fax::{BitReader, Color};
+
+/// Coding of the rows of a Group 3 fax strip.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Group3Coding {
+    /// TIFF compression 2: one-dimensional rows starting at byte boundaries, without EOL codes
+    ModifiedHuffman,
+    /// TIFF compression 3: T.4 rows starting with EOL codes
+    T4 {
+        /// Rows carry a tag bit after the EOL and may be coded relative to the previous row
+        two_dimensional: bool,
+    },
+}
+
+/// Decode a Group 3 strip of `rows` rows of `width` pixels into 8-bit gray pixels.
+///
+/// `lsb_first` is set for strips stored with TIFF `FillOrder` 2. Decoding stops at the
+/// first row that is not valid; it and the rows after it are left white, so a damaged
+/// transmission keeps the rows received before the damage.
+pub(crate) fn decode_group3_strip(
+    data: &[u8],
+    width: u32,
+    rows: u32,
+    coding: Group3Coding,
+    lsb_first: bool,
+) -> Vec<u8> {
+    let width_pixels = width as usize;
+    let mut pixels = vec![255u8; width_pixels * rows as usize];
+    let mut reader = StripReader {
+        data,
+        position: 0,
+        lsb_first,
+    };
+    let mut reference = Vec::new();
+
+    for row in pixels.chunks_exact_mut(width_pixels.max(1)) {
+        let changes = match coding {
+            Group3Coding::ModifiedHuffman => {
+                reader.align();
+                decode_1d_row(&mut reader, width)
+            }
+            Group3Coding::T4 { two_dimensional } => {
+                reader.skip_eol();
+                if two_dimensional && reader.read_bit() == Some(0) {
+                    decode_2d_row(&mut reader, &reference, width)
+                } else {
+                    decode_1d_row(&mut reader, width)
+                }
+            }
+        };
+        let Some(changes) = changes else {
+            tracing::debug!("Invalid CCITT Group 3 row, leaving the rest of the strip blank");
+            break;
+        };
+        paint_row(row, &changes);
+        reference = changes;
+    }
+
+    pixels
+}
+
+/// Bit reader over a strip, reading zero bits past its end.
+struct StripReader<'a> {
+    data: &'a [u8],
+    position: usize,
+    lsb_first: bool,
+}
+
+impl StripReader<'_> {
+    fn bit(&self, index: usize) -> u16 {
+        let Some(&byte) = self.data.get(index / 8) else {
+            return 0;
+        };
+        let byte = if self.lsb_first { byte.reverse_bits() } else { byte };
+        u16::from((byte >> (7 - index % 8)) & 1)
+    }
+
+    fn is_exhausted(&self) -> bool {
+        self.position >= self.data.len() * 8
+    }
+
+    fn read_bit(&mut self) -> Option<u16> {
+        let bit = self.peek(1)?;
+        self.position += 1;
+        Some(bit)
+    }
+
+    fn align(&mut self) {
+        self.position = self.position.div_ceil(8) * 8;
+    }
+
+    /// Skip fill bits and the EOL code at the reader position, if there is one.
+    ///
+    /// An EOL is eleven zero bits followed by a one; fill bits add zeros before it.
+    fn skip_eol(&mut self) {
+        let start = self.position;
+        let mut zeros = 0;
+        while self.peek(1) == Some(0) {
+            self.position += 1;
+            zeros += 1;
+        }
+        if zeros >= 11 && self.peek(1) == Some(1) {
+            self.position += 1;
+        } else {
+            self.position = start;
+        }
+    }
+}
+
+impl BitReader for StripReader<'_> {
+    type Error = std::convert::Infallible;
+
+    fn peek(&self, bits: u8) -> Option<u16> {
+        if self.is_exhausted() {
+            return None;
+        }
+        Some((0..usize::from(bits)).fold(0, |value, offset| value << 1 | self.bit(self.position + offset)))
+    }
+
+    fn consume(&mut self, bits: u8) -> Result<(), Self::Error> {
+        self.position += usize::from(bits);
+        Ok(())
+    }
+
+    fn bits_to_byte_boundary(&self) -> u8 {
+        ((8 - self.position % 8) % 8) as u8
+    }
+}
+
+/// Read a run length: makeup codes for multiples of 64 followed by a terminating code.
+fn read_run(reader: &mut StripReader<'_>, color: Color) -> Option<u32> {
+    let mut run = 0;
+    loop {
+        let code = match color {
+            Color::White => white::decode(reader)?,
+            Color::Black => black::decode(reader)?,
+        };
+        run += u32::from(code);
+        if code < 64 {
+            return Some(run);
+        }
+    }
+}
+
+/// Decode a row of alternating white and black runs into its changing elements.
+///
+/// Changing elements are the positions where the color changes, starting with a change
+/// to black; positions at or past the row end are left out.
+fn decode_1d_row(reader: &mut StripReader<'_>, width: u32) -> Option<Vec<u32>> {
+    let mut changes = Vec::new();
+    let mut position = 0;
+    let mut color = Color::White;
+    while position < width {
+        position += read_run(reader, color)?;
+        if position < width {
+            changes.push(position);
+        }
+        color = !color;
+    }
+    Some(changes)
+}
+
+/// Decode a row coded relative to the changing elements of the previous row (T.4 2D coding).
+fn decode_2d_row(reader: &mut StripReader<'_>, reference: &[u32], width: u32) -> Option<Vec<u32>> {
+    let mut changes = Vec::new();
+    // The coding position starts on an imaginary white pixel before the row.
+    let mut a0: Option<u32> = None;
+    let mut color = Color::White;
+
+    while a0.is_none_or(|a0| a0 < width) {
+        // b1 is the first change on the reference row right of a0 to the opposite color of
+        // a0; changes to black have even indices.
+        let mut index = reference.partition_point(|&change| a0.is_some_and(|a0| change <= a0));
+        if (index % 2 == 0) != (color == Color::White) {
+            index += 1;
+        }
+        let b1 = reference.get(index).copied().unwrap_or(width);
+        let b2 = reference.get(index + 1).copied().unwrap_or(width);
+
+        let next = match mode::decode(reader)? {
+            Mode::Pass => b2,
+            Mode::Horizontal => {
+                let a1 = a0.unwrap_or(0) + read_run(reader, color)?;
+                let a2 = a1 + read_run(reader, !color)?;
+                changes.extend([a1, a2].into_iter().filter(|&change| change < width));
+                a2
+            }
+            Mode::Vertical(delta) => {
+                let a1 = u32::try_from((i64::from(b1) + i64::from(delta)).clamp(0, i64::from(width))).ok()?;
+                if a1 < width {
+                    changes.push(a1);
+                }
+                color = !color;
+                a1
+            }
+            Mode::Extension | Mode::EOF => return None,
+        };
+        if a0.is_some_and(|a0| next <= a0) {
+            return None;
+        }
+        a0 = Some(next);
+    }
+    Some(changes)
+}
+
+fn paint_row(row: &mut [u8], changes: &[u32]) {
+    let width = row.len();
+    for black_run in changes.chunks(2) {
+        let start = (black_run[0] as usize).min(width);
+        let end = black_run.get(1).map_or(width, |&end| (end as usize).min(width));
+        row[start..end].fill(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fax::{BitWriter, Bits, VecWriter};
+
+    const EOL: Bits = Bits { data: 1, len: 12 };
+    const PASS: Bits = Bits { data: 1, len: 4 };
+    const HORIZONTAL: Bits = Bits { data: 1, len: 3 };
+    const VERTICAL_0: Bits = Bits { data: 1, len: 1 };
+    const VERTICAL_RIGHT_1: Bits = Bits { data: 3, len: 3 };
+
+    fn write_runs(writer: &mut VecWriter, runs: &[u16], first_color: Color) {
+        let mut color = first_color;
+        for &run in runs {
+            if run >= 64 {
+                write_code(writer, run / 64 * 64, color);
+            }
+            write_code(writer, run % 64, color);
+            color = !color;
+        }
+    }
+
+    fn write_code(writer: &mut VecWriter, run: u16, color: Color) {
+        let code = match color {
+            Color::White => white::encode(run),
+            Color::Black => black::encode(run),
+        };
+        writer.write(code.unwrap()).unwrap();
+    }
+
+    fn row_pattern(pixels: &[u8], width: usize) -> Vec<String> {
+        pixels
+            .chunks(width)
+            .map(|row| row.iter().map(|&pixel| if pixel == 0 { '#' } else { '.' }).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_modified_huffman_rows_are_byte_aligned() {
+        let mut writer = VecWriter::new();
+        write_runs(&mut writer, &[2, 3, 95], Color::White);
+        writer.pad();
+        write_runs(&mut writer, &[0, 100], Color::White);
+        let data = writer.finish();
+
+        let pixels = decode_group3_strip(&data, 100, 2, Group3Coding::ModifiedHuffman, false);
+        let rows = row_pattern(&pixels, 100);
+        assert_eq!(&rows[0][..8], "..###...");
+        assert_eq!(rows[0][8..].chars().filter(|&pixel| pixel == '#').count(), 0);
+        assert!(rows[1].chars().all(|pixel| pixel == '#'));
+    }
+
+    #[test]
+    fn test_t4_one_dimensional_rows_with_fill_order() {
+        let mut writer = VecWriter::new();
+        for runs in [[4u16, 4, 8], [0, 8, 8]] {
+            writer.write(EOL).unwrap();
+            write_runs(&mut writer, &runs, Color::White);
+        }
+        let data = writer.finish();
+        let reversed: Vec<u8> = data.iter().map(|byte| byte.reverse_bits()).collect();
+
+        let coding = Group3Coding::T4 { two_dimensional: false };
+        let expected = vec!["....####........", "########........"];
+        assert_eq!(
+            row_pattern(&decode_group3_strip(&data, 16, 2, coding, false), 16),
+            expected
+        );
+        assert_eq!(
+            row_pattern(&decode_group3_strip(&reversed, 16, 2, coding, true), 16),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_t4_two_dimensional_rows() {
+        let mut writer = VecWriter::new();
+        writer.write(EOL).unwrap();
+        writer.write(Bits { data: 1, len: 1 }).unwrap();
+        write_runs(&mut writer, &[4, 4, 8], Color::White);
+
+        // Same changes as the row above.
+        writer.write(EOL).unwrap();
+        writer.write(Bits { data: 0, len: 1 }).unwrap();
+        for code in [VERTICAL_0, VERTICAL_0, VERTICAL_0] {
+            writer.write(code).unwrap();
+        }
+
+        // Both changes one pixel to the right.
+        writer.write(EOL).unwrap();
+        writer.write(Bits { data: 0, len: 1 }).unwrap();
+        for code in [VERTICAL_RIGHT_1, VERTICAL_RIGHT_1, VERTICAL_0] {
+            writer.write(code).unwrap();
+        }
+
+        // All white: pass the black run above.
+        writer.write(EOL).unwrap();
+        writer.write(Bits { data: 0, len: 1 }).unwrap();
+        writer.write(PASS).unwrap();
+        writer.write(VERTICAL_0).unwrap();
+
+        // New black run without reference.
+        writer.write(EOL).unwrap();
+        writer.write(Bits { data: 0, len: 1 }).unwrap();
+        writer.write(HORIZONTAL).unwrap();
+        write_runs(&mut writer, &[2, 3], Color::White);
+        writer.write(VERTICAL_0).unwrap();
+        let data = writer.finish();
+
+        let pixels = decode_group3_strip(&data, 16, 5, Group3Coding::T4 { two_dimensional: true }, false);
+        assert_eq!(
+            row_pattern(&pixels, 16),
+            vec![
+                "....####........",
+                "....####........",
+                ".....####.......",
+                "................",
+                "..###...........",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_invalid_rows_are_left_white() {
+        let mut writer = VecWriter::new();
+        writer.write(EOL).unwrap();
+        write_runs(&mut writer, &[0, 16], Color::White);
+        writer.write(EOL).unwrap();
+        writer.write(Bits { data: 0, len: 16 }).unwrap();
+        writer.write(Bits { data: 0, len: 16 }).unwrap();
+        let data = writer.finish();
+
+        let pixels = decode_group3_strip(&data, 16, 3, Group3Coding::T4 { two_dimensional: false }, false);
+        assert_eq!(
+            row_pattern(&pixels, 16),
+            vec!["################", "................", "................"]
+        );
+    }
+}
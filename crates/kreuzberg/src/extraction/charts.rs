@@ -0,0 +1,427 @@
+//! Chart data extraction from Office Open XML packages.
+//!
+//! DOCX, PPTX and XLSX documents store charts as DrawingML chart parts
+//! (`word/charts/chart1.xml`, `ppt/charts/chart1.xml`, `xl/charts/chart1.xml`).
+//! Each series keeps a cache of its category labels and values, so the data can be
+//! read without evaluating the workbook the series refer to.
+
+use crate::extraction::cells_to_markdown;
+use crate::extraction::opc::{read_part, read_relationships};
+use crate::extraction::xml_parser;
+use crate::types::{Chart, Table};
+use roxmltree::Node;
+use std::collections::HashMap;
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+/// Upper bound on the points read per series, against corrupt point counts.
+const MAX_POINTS: usize = 100_000;
+
+/// Charts referenced by a part (a DOCX document or a PPTX slide), by relationship id.
+///
+/// The charts' `page_number` is 0; callers set it once the chart's position is known.
+pub(crate) fn part_charts<R: Read + Seek>(archive: &mut ZipArchive<R>, part: &str) -> HashMap<String, Chart> {
+    read_relationships(archive, part)
+        .into_iter()
+        .filter(|rel| rel.kind.ends_with("/chart"))
+        .filter_map(|rel| {
+            let xml = read_part(archive, &rel.target).ok()?;
+            Some((rel.id, parse_chart(&xml, 0)?))
+        })
+        .collect()
+}
+
+/// Charts placed on a worksheet through its drawing, in drawing order.
+///
+/// As with [`part_charts`], the charts' `page_number` is 0.
+#[cfg(feature = "excel")]
+pub(crate) fn worksheet_charts<R: Read + Seek>(archive: &mut ZipArchive<R>, sheet_part: &str) -> Vec<Chart> {
+    let mut charts = Vec::new();
+    let drawings: Vec<String> = read_relationships(archive, sheet_part)
+        .into_iter()
+        .filter(|rel| rel.kind.ends_with("/drawing"))
+        .map(|rel| rel.target)
+        .collect();
+
+    for drawing in drawings {
+        let Ok(xml) = read_part(archive, &drawing) else {
+            continue;
+        };
+        let Some(doc) = xml_parser::try_parse_document(&xml, None) else {
+            continue;
+        };
+        let mut by_id: HashMap<String, String> = read_relationships(archive, &drawing)
+            .into_iter()
+            .filter(|rel| rel.kind.ends_with("/chart"))
+            .map(|rel| (rel.id, rel.target))
+            .collect();
+        for id in doc
+            .descendants()
+            .filter(|n| is(n, "chart"))
+            .filter_map(|n| attr(&n, "id"))
+        {
+            if let Some(target) = by_id.remove(id)
+                && let Ok(chart_xml) = read_part(archive, &target)
+                && let Some(chart) = parse_chart(&chart_xml, 0)
+            {
+                charts.push(chart);
+            }
+        }
+    }
+    charts
+}
+
+/// Parse a chart part (`c:chartSpace`). Returns `None` for charts without series.
+pub(crate) fn parse_chart(xml: &str, page_number: usize) -> Option<Chart> {
+    let doc = xml_parser::try_parse_document(xml, None)?;
+    let chart = doc.root_element().children().find(|n| is(n, "chart"))?;
+    let plot_area = child(&chart, "plotArea")?;
+
+    let mut kinds: Vec<String> = Vec::new();
+    let mut series = Vec::new();
+    for group in plot_area
+        .children()
+        .filter(|n| n.is_element() && n.tag_name().name().ends_with("Chart"))
+    {
+        let kind = chart_kind(&group);
+        if !kinds.contains(&kind) {
+            kinds.push(kind);
+        }
+        for ser in group.children().filter(|n| is(n, "ser")) {
+            series.push(Series {
+                name: child(&ser, "tx").and_then(|tx| points(&tx).into_iter().next()),
+                categories: child(&ser, "cat")
+                    .or_else(|| child(&ser, "xVal"))
+                    .map(|node| points(&node))
+                    .unwrap_or_default(),
+                values: child(&ser, "val")
+                    .or_else(|| child(&ser, "yVal"))
+                    .map(|node| points(&node))
+                    .unwrap_or_default(),
+            });
+        }
+    }
+    if series.is_empty() {
+        return None;
+    }
+
+    // Excel titles a single-series chart with the series name unless the title was removed.
+    let title = child(&chart, "title").and_then(|title| title_text(&title)).or_else(|| {
+        let deleted = child(&chart, "autoTitleDeleted").is_some_and(|n| attr(&n, "val") != Some("0"));
+        let titled = child(&chart, "title").is_some();
+        (titled && !deleted && series.len() == 1)
+            .then(|| series[0].name.clone())
+            .flatten()
+    });
+    let (category_axis_title, value_axis_title) = axis_titles(&plot_area);
+
+    let categories = series
+        .iter()
+        .find(|s| !s.categories.is_empty())
+        .map(|s| s.categories.clone())
+        .unwrap_or_default();
+    let row_count = series
+        .iter()
+        .map(|s| s.values.len())
+        .chain([categories.len()])
+        .max()
+        .unwrap_or(0);
+
+    let mut header = vec![category_axis_title.clone().unwrap_or_else(|| "Category".to_string())];
+    header.extend(
+        series
+            .iter()
+            .enumerate()
+            .map(|(i, s)| s.name.clone().unwrap_or_else(|| format!("Series {}", i + 1))),
+    );
+    let mut cells = vec![header];
+    for row in 0..row_count {
+        let mut cells_row = vec![categories.get(row).cloned().unwrap_or_else(|| (row + 1).to_string())];
+        cells_row.extend(series.iter().map(|s| s.values.get(row).cloned().unwrap_or_default()));
+        cells.push(cells_row);
+    }
+
+    Some(Chart {
+        title,
+        chart_type: kinds.join("+"),
+        category_axis_title,
+        value_axis_title,
+        cells,
+        page_number,
+    })
+}
+
+/// Heading line shown above a chart's data, such as `Chart: Revenue (column chart)`.
+pub(crate) fn chart_caption(chart: &Chart) -> String {
+    match &chart.title {
+        Some(title) => format!("Chart: {} ({} chart)", title, chart.chart_type),
+        None => format!("Chart ({} chart)", chart.chart_type),
+    }
+}
+
+/// The chart's data as a table.
+pub(crate) fn chart_table(chart: &Chart) -> Table {
+    Table {
+        markdown: cells_to_markdown(&chart.cells),
+        cells: chart.cells.clone(),
+        page_number: chart.page_number,
+    }
+}
+
+/// Entry of the `charts` metadata list; `table_index` points into `ExtractionResult::tables`.
+pub(crate) fn chart_summary(chart: &Chart, table_index: usize) -> serde_json::Value {
+    let series: Vec<&String> = chart
+        .cells
+        .first()
+        .map(|header| header.iter().skip(1).collect())
+        .unwrap_or_default();
+    let mut summary = serde_json::json!({
+        "chart_type": chart.chart_type,
+        "series": series,
+        "page_number": chart.page_number,
+        "table_index": table_index,
+    });
+    for (key, value) in [
+        ("title", &chart.title),
+        ("category_axis_title", &chart.category_axis_title),
+        ("value_axis_title", &chart.value_axis_title),
+    ] {
+        if let Some(value) = value {
+            summary[key] = serde_json::Value::String(value.clone());
+        }
+    }
+    summary
+}
+
+struct Series {
+    name: Option<String>,
+    categories: Vec<String>,
+    values: Vec<String>,
+}
+
+/// Chart type of a plot group such as `c:barChart` or `c:line3DChart`.
+fn chart_kind(group: &Node<'_, '_>) -> String {
+    let kind = group.tag_name().name().trim_end_matches("Chart");
+    let columns = child(group, "barDir").is_some_and(|n| attr(&n, "val") == Some("col"));
+    match kind {
+        "bar" if columns => "column".to_string(),
+        "bar3D" if columns => "column3D".to_string(),
+        _ => kind.to_string(),
+    }
+}
+
+/// Cached points of a series' name, categories or values.
+///
+/// Multi-level category labels (`c:multiLvlStrCache`) are joined from the outer to the inner level.
+fn points(node: &Node<'_, '_>) -> Vec<String> {
+    let Some(cache) = node.descendants().find(|n| {
+        matches!(
+            n.tag_name().name(),
+            "strCache" | "numCache" | "strLit" | "numLit" | "multiLvlStrCache"
+        ) && n.is_element()
+    }) else {
+        // `c:tx` may hold the name directly in a `c:v`
+        return child(node, "v")
+            .and_then(|v| v.text())
+            .map(|t| vec![t.to_string()])
+            .unwrap_or_default();
+    };
+
+    let count = child(&cache, "ptCount")
+        .and_then(|n| attr(&n, "val"))
+        .and_then(|v| v.parse::<usize>().ok());
+    let levels: Vec<Node<'_, '_>> = if is(&cache, "multiLvlStrCache") {
+        cache.children().filter(|n| is(n, "lvl")).collect()
+    } else {
+        vec![cache]
+    };
+
+    let mut values: Vec<String> = vec![String::new(); count.unwrap_or(0).min(MAX_POINTS)];
+    // The first level is the innermost one, so outer levels are prepended.
+    for level in levels.iter().rev() {
+        let mut last_label = String::new();
+        let mut labels: Vec<(usize, String)> = level
+            .children()
+            .filter(|n| is(n, "pt"))
+            .filter_map(|pt| {
+                let idx = attr(&pt, "idx")?.parse::<usize>().ok()?;
+                let value = child(&pt, "v").and_then(|v| v.text()).unwrap_or_default();
+                (idx < MAX_POINTS).then(|| (idx, value.to_string()))
+            })
+            .collect();
+        labels.sort_by_key(|(idx, _)| *idx);
+        let len = labels.last().map_or(0, |(idx, _)| idx + 1);
+        if values.len() < len {
+            values.resize(len, String::new());
+        }
+        let outer = levels.len() > 1 && level.id() != levels[0].id();
+        let mut labels = labels.into_iter().peekable();
+        for (idx, value) in values.iter_mut().enumerate() {
+            // Outer levels only label the first category they span.
+            let label = match labels.next_if(|(i, _)| *i == idx) {
+                Some((_, label)) => {
+                    last_label = label.clone();
+                    label
+                }
+                None if outer => last_label.clone(),
+                None => continue,
+            };
+            if value.is_empty() {
+                *value = label;
+            } else if !label.is_empty() {
+                *value = format!("{} / {}", value, label);
+            }
+        }
+    }
+    values
+}
+
+/// Text of a `c:title`, from rich text runs or a cell reference cache.
+fn title_text(title: &Node<'_, '_>) -> Option<String> {
+    let tx = child(title, "tx")?;
+    let text = match child(&tx, "rich") {
+        Some(rich) => rich
+            .children()
+            .filter(|n| is(n, "p"))
+            .map(|p| {
+                p.descendants()
+                    .filter(|n| is(n, "t"))
+                    .filter_map(|t| t.text())
+                    .collect::<String>()
+            })
+            .filter(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join(" "),
+        None => points(&tx).into_iter().next().unwrap_or_default(),
+    };
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// Titles of the category and value axes.
+///
+/// Scatter and bubble charts have two value axes; the horizontal one is the category axis.
+fn axis_titles(plot_area: &Node<'_, '_>) -> (Option<String>, Option<String>) {
+    let axes: Vec<Node<'_, '_>> = plot_area
+        .children()
+        .filter(|n| matches!(n.tag_name().name(), "catAx" | "dateAx" | "valAx") && n.is_element())
+        .collect();
+    let horizontal = |axis: &Node<'_, '_>| {
+        child(axis, "axPos")
+            .and_then(|n| attr(&n, "val"))
+            .is_some_and(|pos| pos == "b" || pos == "t")
+    };
+
+    let category = axes
+        .iter()
+        .find(|axis| !is(axis, "valAx"))
+        .or_else(|| axes.iter().find(|axis| horizontal(axis)));
+    let value = axes
+        .iter()
+        .find(|axis| is(axis, "valAx") && Some(axis.id()) != category.map(|c| c.id()));
+
+    let title = |axis: Option<&Node<'_, '_>>| axis.and_then(|axis| child(axis, "title")).and_then(|t| title_text(&t));
+    (title(category), title(value))
+}
+
+/// Local-name match, so transitional and strict namespaces both work.
+fn is(node: &Node<'_, '_>, name: &str) -> bool {
+    node.is_element() && node.tag_name().name() == name
+}
+
+fn attr<'a>(node: &Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.attributes().find(|a| a.name() == name).map(|a| a.value())
+}
+
+fn child<'a, 'input>(node: &Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| is(n, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BAR_CHART: &str = r#"<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main">
+        <c:chart>
+            <c:title><c:tx><c:rich><a:p><a:r><a:t>Revenue by </a:t></a:r><a:r><a:t>quarter</a:t></a:r></a:p></c:rich></c:tx></c:title>
+            <c:autoTitleDeleted val="0"/>
+            <c:plotArea>
+                <c:barChart>
+                    <c:barDir val="col"/>
+                    <c:ser>
+                        <c:tx><c:strRef><c:f>Sheet1!$B$1</c:f><c:strCache><c:ptCount val="1"/><c:pt idx="0"><c:v>2023</c:v></c:pt></c:strCache></c:strRef></c:tx>
+                        <c:cat><c:strRef><c:strCache><c:ptCount val="3"/><c:pt idx="0"><c:v>Q1</c:v></c:pt><c:pt idx="1"><c:v>Q2</c:v></c:pt><c:pt idx="2"><c:v>Q3</c:v></c:pt></c:strCache></c:strRef></c:cat>
+                        <c:val><c:numRef><c:numCache><c:ptCount val="3"/><c:pt idx="0"><c:v>10</c:v></c:pt><c:pt idx="2"><c:v>14.5</c:v></c:pt></c:numCache></c:numRef></c:val>
+                    </c:ser>
+                </c:barChart>
+                <c:lineChart>
+                    <c:ser>
+                        <c:tx><c:v>Target</c:v></c:tx>
+                        <c:val><c:numLit><c:ptCount val="3"/><c:pt idx="0"><c:v>12</c:v></c:pt><c:pt idx="1"><c:v>12</c:v></c:pt><c:pt idx="2"><c:v>12</c:v></c:pt></c:numLit></c:val>
+                    </c:ser>
+                </c:lineChart>
+                <c:catAx><c:axId val="1"/><c:title><c:tx><c:rich><a:p><a:r><a:t>Quarter</a:t></a:r></a:p></c:rich></c:tx></c:title><c:axPos val="b"/></c:catAx>
+                <c:valAx><c:axId val="2"/><c:title><c:tx><c:rich><a:p><a:r><a:t>EUR (millions)</a:t></a:r></a:p></c:rich></c:tx></c:title><c:axPos val="l"/></c:valAx>
+            </c:plotArea>
+        </c:chart>
+    </c:chartSpace>"#;
+
+    #[test]
+    fn test_parse_combined_chart() {
+        let chart = parse_chart(BAR_CHART, 2).unwrap();
+        assert_eq!(chart.title.as_deref(), Some("Revenue by quarter"));
+        assert_eq!(chart.chart_type, "column+line");
+        assert_eq!(chart.category_axis_title.as_deref(), Some("Quarter"));
+        assert_eq!(chart.value_axis_title.as_deref(), Some("EUR (millions)"));
+        assert_eq!(
+            chart.cells,
+            vec![
+                vec!["Quarter", "2023", "Target"],
+                vec!["Q1", "10", "12"],
+                vec!["Q2", "", "12"],
+                vec!["Q3", "14.5", "12"],
+            ]
+        );
+        assert_eq!(chart_caption(&chart), "Chart: Revenue by quarter (column+line chart)");
+
+        let summary = chart_summary(&chart, 4);
+        assert_eq!(summary["series"], serde_json::json!(["2023", "Target"]));
+        assert_eq!(summary["table_index"], 4);
+        assert_eq!(chart_table(&chart).page_number, 2);
+    }
+
+    #[test]
+    fn test_parse_scatter_and_multi_level_categories() {
+        let scatter = r#"<chartSpace><chart><plotArea><scatterChart><ser>
+            <xVal><numLit><ptCount val="2"/><pt idx="0"><v>1</v></pt><pt idx="1"><v>2</v></pt></numLit></xVal>
+            <yVal><numLit><ptCount val="2"/><pt idx="0"><v>3</v></pt><pt idx="1"><v>5</v></pt></numLit></yVal>
+        </ser></scatterChart>
+        <valAx><axId val="1"/><title><tx><rich><p><r><t>Hours</t></r></p></rich></tx></title><axPos val="b"/></valAx>
+        <valAx><axId val="2"/><title><tx><rich><p><r><t>Score</t></r></p></rich></tx></title><axPos val="l"/></valAx>
+        </plotArea></chart></chartSpace>"#;
+        let chart = parse_chart(scatter, 1).unwrap();
+        assert_eq!(chart.chart_type, "scatter");
+        assert_eq!(chart.title, None);
+        assert_eq!(
+            (chart.category_axis_title.as_deref(), chart.value_axis_title.as_deref()),
+            (Some("Hours"), Some("Score"))
+        );
+        assert_eq!(
+            chart.cells,
+            vec![vec!["Hours", "Series 1"], vec!["1", "3"], vec!["2", "5"]]
+        );
+
+        let multi_level = r#"<chartSpace><chart><plotArea><pieChart><ser>
+            <cat><multiLvlStrRef><multiLvlStrCache><ptCount val="3"/>
+                <lvl><pt idx="0"><v>Jan</v></pt><pt idx="1"><v>Feb</v></pt><pt idx="2"><v>Jan</v></pt></lvl>
+                <lvl><pt idx="0"><v>2023</v></pt><pt idx="2"><v>2024</v></pt></lvl>
+            </multiLvlStrCache></multiLvlStrRef></cat>
+            <val><numLit><ptCount val="3"/><pt idx="0"><v>1</v></pt><pt idx="1"><v>2</v></pt><pt idx="2"><v>3</v></pt></numLit></val>
+        </ser></pieChart></plotArea></chart></chartSpace>"#;
+        let chart = parse_chart(multi_level, 1).unwrap();
+        let categories: Vec<&str> = chart.cells.iter().skip(1).map(|row| row[0].as_str()).collect();
+        assert_eq!(categories, vec!["2023 / Jan", "2023 / Feb", "2024 / Jan"]);
+
+        assert!(parse_chart("<chartSpace><chart><plotArea/></chart></chartSpace>", 1).is_none());
+    }
+}
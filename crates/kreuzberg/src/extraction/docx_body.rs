@@ -0,0 +1,792 @@
+//! Document-order DOCX body extraction.
+//!
+//! Walks `word/document.xml` with the numbering definitions of `word/numbering.xml`
+//! and the paragraph styles of `word/styles.xml`, so list items keep their rendered
+//! number (`1.`, `a.`, `i.`) and nesting, and tables stay at their position in the
+//! text. Merged cells (`w:gridSpan`, `w:vMerge`) are resolved into a regular grid.
+//! Charts are rendered from their chart parts like tables, at the paragraph that
+//! anchors them.
+
+use crate::core::config::DocxTableMode;
+use crate::error::{KreuzbergError, Result};
+use crate::extraction::cells_to_markdown;
+use crate::extraction::charts::{chart_caption, chart_table, part_charts};
+use crate::extraction::list_numbering::{ListCounters, NumberFormat};
+use crate::extraction::table_grid::{GridCell, MAX_COLSPAN, build_grid, cells_to_text};
+use crate::extraction::xml_parser;
+use crate::types::{Chart, PageBoundary, Table};
+use roxmltree::{Document, Node};
+use std::collections::{HashMap, HashSet};
+use std::io::{Cursor, Read};
+
+/// Marker of bulleted list items.
+const BULLET: &str = "•";
+
+/// Text, tables and page breaks of a DOCX body.
+#[derive(Debug, Default)]
+pub(crate) struct DocxBody {
+    pub text: String,
+    pub tables: Vec<Table>,
+    /// Charts with the index of their data table in `tables`
+    pub charts: Vec<(usize, Chart)>,
+    /// Byte offsets in `text` of explicit page breaks
+    pub page_breaks: Vec<usize>,
+}
+
+impl DocxBody {
+    /// Page boundaries from the explicit page breaks, if there are any.
+    pub fn page_boundaries(&self) -> Option<Vec<PageBoundary>> {
+        let mut starts = vec![0];
+        for &offset in &self.page_breaks {
+            if offset > *starts.last().unwrap_or(&0) && offset < self.text.len() {
+                starts.push(offset);
+            }
+        }
+        if starts.len() < 2 {
+            return None;
+        }
+
+        Some(
+            starts
+                .iter()
+                .enumerate()
+                .map(|(index, &byte_start)| PageBoundary {
+                    byte_start,
+                    byte_end: starts.get(index + 1).copied().unwrap_or(self.text.len()),
+                    page_number: index + 1,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Extract the body of a DOCX package.
+pub(crate) fn extract_docx_body(bytes: &[u8], table_mode: DocxTableMode) -> Result<DocxBody> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes))
+        .map_err(|e| KreuzbergError::parsing(format!("DOCX parsing failed: {}", e)))?;
+
+    let document_xml = read_part(&mut archive, "word/document.xml")?
+        .ok_or_else(|| KreuzbergError::parsing("DOCX parsing failed: word/document.xml not found"))?;
+    let numbering_xml = read_part(&mut archive, "word/numbering.xml")?;
+    let styles_xml = read_part(&mut archive, "word/styles.xml")?;
+
+    let numbering = match numbering_xml
+        .as_deref()
+        .and_then(|xml| xml_parser::try_parse_document(xml, None))
+    {
+        Some(doc) => Numbering::parse(&doc),
+        _ => Numbering::default(),
+    };
+    let styles = match styles_xml
+        .as_deref()
+        .and_then(|xml| xml_parser::try_parse_document(xml, None))
+    {
+        Some(doc) => StyleNumbering::parse(&doc),
+        _ => StyleNumbering::default(),
+    };
+    let charts = part_charts(&mut archive, "word/document.xml");
+
+    let document = xml_parser::parse_document(&document_xml, None, |e| {
+        KreuzbergError::parsing(format!("DOCX parsing failed: {}", e))
+    })?;
+    let Some(body) = document.root_element().children().find(|n| is(n, "body")) else {
+        return Ok(DocxBody::default());
+    };
+
+    let mut writer = BodyWriter {
+        numbering,
+        styles,
+        table_mode,
+        charts,
+        counters: HashMap::new(),
+        started: HashSet::new(),
+        body: DocxBody::default(),
+    };
+    writer.blocks(body);
+
+    let mut body = writer.body;
+    let trimmed = body.text.trim_end().len();
+    body.text.truncate(trimmed);
+    Ok(body)
+}
+
+fn read_part(archive: &mut zip::ZipArchive<Cursor<&[u8]>>, name: &str) -> Result<Option<String>> {
+    let Ok(mut file) = archive.by_name(name) else {
+        return Ok(None);
+    };
+    let mut content = String::with_capacity(file.size() as usize);
+    file.read_to_string(&mut content)
+        .map_err(|e| KreuzbergError::parsing(format!("Failed to read {}: {}", name, e)))?;
+    Ok(Some(content))
+}
+
+/// Local-name match, so both transitional and strict OOXML namespaces work.
+fn is(node: &Node<'_, '_>, name: &str) -> bool {
+    node.is_element() && node.tag_name().name() == name
+}
+
+fn attr<'a>(node: &Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.attributes().find(|a| a.name() == name).map(|a| a.value())
+}
+
+fn child<'a, 'input>(node: &Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| is(n, name))
+}
+
+fn child_val<'a>(node: &Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name).and_then(|n| attr(&n, "val"))
+}
+
+fn child_number(node: &Node<'_, '_>, name: &str) -> Option<usize> {
+    child_val(node, name).and_then(|v| v.trim().parse().ok())
+}
+
+/// A list level of `word/numbering.xml`.
+#[derive(Debug, Clone)]
+struct Level {
+    start: usize,
+    format: NumberFormat,
+    /// Marker template such as `%1.` or `%1.%2)`
+    text: Option<String>,
+}
+
+impl Level {
+    fn parse(lvl: &Node<'_, '_>) -> Self {
+        Self {
+            start: child_number(lvl, "start").unwrap_or(1),
+            format: child_val(lvl, "numFmt").map_or(NumberFormat::Decimal, NumberFormat::from_docx),
+            text: child_val(lvl, "lvlText").map(str::to_string),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct NumInstance {
+    abstract_id: String,
+    /// Levels restarted by `w:startOverride` when the instance is first used
+    start_overrides: Vec<(usize, usize)>,
+    level_overrides: HashMap<usize, Level>,
+}
+
+#[derive(Debug, Default)]
+struct Numbering {
+    abstracts: HashMap<String, HashMap<usize, Level>>,
+    instances: HashMap<String, NumInstance>,
+}
+
+impl Numbering {
+    fn parse(doc: &Document<'_>) -> Self {
+        let mut numbering = Self::default();
+        for node in doc.root_element().children() {
+            if is(&node, "abstractNum") {
+                let Some(id) = attr(&node, "abstractNumId") else {
+                    continue;
+                };
+                let levels = node
+                    .children()
+                    .filter(|n| is(n, "lvl"))
+                    .filter_map(|lvl| Some((attr(&lvl, "ilvl")?.parse().ok()?, Level::parse(&lvl))))
+                    .collect();
+                numbering.abstracts.insert(id.to_string(), levels);
+            } else if is(&node, "num") {
+                let (Some(id), Some(abstract_id)) = (attr(&node, "numId"), child_val(&node, "abstractNumId")) else {
+                    continue;
+                };
+                let mut instance = NumInstance {
+                    abstract_id: abstract_id.to_string(),
+                    ..Default::default()
+                };
+                for level_override in node.children().filter(|n| is(n, "lvlOverride")) {
+                    let Some(ilvl) = attr(&level_override, "ilvl").and_then(|v| v.parse().ok()) else {
+                        continue;
+                    };
+                    if let Some(start) = child_number(&level_override, "startOverride") {
+                        instance.start_overrides.push((ilvl, start));
+                    }
+                    if let Some(lvl) = child(&level_override, "lvl") {
+                        instance.level_overrides.insert(ilvl, Level::parse(&lvl));
+                    }
+                }
+                instance.start_overrides.sort_unstable();
+                numbering.instances.insert(id.to_string(), instance);
+            }
+        }
+        numbering
+    }
+
+    fn level(&self, num_id: &str, ilvl: usize) -> Option<&Level> {
+        let instance = self.instances.get(num_id)?;
+        instance
+            .level_overrides
+            .get(&ilvl)
+            .or_else(|| self.abstracts.get(&instance.abstract_id)?.get(&ilvl))
+    }
+}
+
+/// Numbering of paragraph styles (`w:style/w:pPr/w:numPr`), following `w:basedOn`.
+#[derive(Debug, Default)]
+struct StyleNumbering {
+    styles: HashMap<String, StyleEntry>,
+}
+
+#[derive(Debug)]
+struct StyleEntry {
+    num_id: Option<String>,
+    ilvl: Option<usize>,
+    based_on: Option<String>,
+}
+
+impl StyleNumbering {
+    fn parse(doc: &Document<'_>) -> Self {
+        let mut styles = HashMap::new();
+        for style in doc.root_element().children().filter(|n| is(n, "style")) {
+            let Some(id) = attr(&style, "styleId") else {
+                continue;
+            };
+            let num_pr = child(&style, "pPr").and_then(|p| child(&p, "numPr"));
+            styles.insert(
+                id.to_string(),
+                StyleEntry {
+                    num_id: num_pr.and_then(|n| child_val(&n, "numId")).map(str::to_string),
+                    ilvl: num_pr.and_then(|n| child_number(&n, "ilvl")),
+                    based_on: child_val(&style, "basedOn").map(str::to_string),
+                },
+            );
+        }
+        Self { styles }
+    }
+
+    /// Numbering instance and level of a style.
+    fn numbering(&self, style_id: &str) -> (Option<&str>, Option<usize>) {
+        let mut num_id = None;
+        let mut ilvl = None;
+        let mut current = Some(style_id);
+        // Bounded walk; style inheritance chains are short and may be cyclic in broken files.
+        for _ in 0..16 {
+            let Some(style) = current.and_then(|s| self.styles.get(s)) else {
+                break;
+            };
+            num_id = num_id.or(style.num_id.as_deref());
+            ilvl = ilvl.or(style.ilvl);
+            current = style.based_on.as_deref();
+        }
+        (num_id, ilvl)
+    }
+}
+
+struct BodyWriter {
+    numbering: Numbering,
+    styles: StyleNumbering,
+    table_mode: DocxTableMode,
+    /// Charts of the document by relationship id
+    charts: HashMap<String, Chart>,
+    /// List counters per abstract numbering definition
+    counters: HashMap<String, ListCounters>,
+    /// Numbering instances whose start overrides have been applied
+    started: HashSet<String>,
+    body: DocxBody,
+}
+
+/// Paragraph text with the offsets of page breaks inside it.
+#[derive(Default)]
+struct ParagraphText {
+    text: String,
+    page_breaks: Vec<usize>,
+}
+
+impl BodyWriter {
+    /// Block-level content of the body, a content control or a custom XML element.
+    fn blocks(&mut self, parent: Node<'_, '_>) {
+        for node in parent.children().filter(Node::is_element) {
+            match node.tag_name().name() {
+                "p" => self.paragraph(node),
+                "tbl" => self.table(node),
+                "sdt" => {
+                    if let Some(content) = child(&node, "sdtContent") {
+                        self.blocks(content);
+                    }
+                }
+                "customXml" => self.blocks(node),
+                _ => {}
+            }
+        }
+    }
+
+    fn paragraph(&mut self, node: Node<'_, '_>) {
+        let (line, paragraph) = self.render_paragraph(node, true);
+        let text = &mut self.body.text;
+        let offset = text.len();
+        match line {
+            Some(line) => {
+                let prefix = line.len() - paragraph.text.len();
+                self.body
+                    .page_breaks
+                    .extend(paragraph.page_breaks.iter().map(|b| offset + prefix + b));
+                text.push_str(&line);
+                text.push('\n');
+            }
+            None => self
+                .body
+                .page_breaks
+                .extend(paragraph.page_breaks.iter().map(|_| offset)),
+        }
+
+        let chart_ids: Vec<&str> = node
+            .descendants()
+            .filter(|n| is(n, "chart"))
+            .filter_map(|n| attr(&n, "id"))
+            .collect();
+        for id in chart_ids {
+            self.chart(id);
+        }
+    }
+
+    /// Render a chart anchored in a paragraph, with a caption line, per the table mode.
+    fn chart(&mut self, id: &str) {
+        let Some(mut chart) = self.charts.get(id).cloned() else {
+            return;
+        };
+        chart.page_number = self.body.tables.len() + 1;
+
+        let rendered = match self.table_mode {
+            DocxTableMode::Markdown => Some(cells_to_markdown(&chart.cells)),
+            DocxTableMode::Text => Some(cells_to_text(&chart.cells)),
+            DocxTableMode::Structured => None,
+        };
+        if let Some(rendered) = rendered {
+            let text = &mut self.body.text;
+            if !text.is_empty() && !text.ends_with("\n\n") {
+                text.push('\n');
+            }
+            text.push_str(&chart_caption(&chart));
+            text.push_str("\n\n");
+            text.push_str(rendered.trim_end());
+            text.push_str("\n\n");
+        }
+        self.body.tables.push(chart_table(&chart));
+        self.body.charts.push((self.body.tables.len() - 1, chart));
+    }
+
+    /// Render a paragraph as a line with its list marker, or `None` if it has no text.
+    fn render_paragraph(&mut self, node: Node<'_, '_>, indent: bool) -> (Option<String>, ParagraphText) {
+        let mut paragraph = ParagraphText::default();
+        let properties = child(&node, "pPr");
+        if properties
+            .and_then(|p| child(&p, "pageBreakBefore"))
+            .is_some_and(|b| !matches!(attr(&b, "val"), Some("0" | "false" | "off")))
+        {
+            paragraph.page_breaks.push(0);
+        }
+        collect_text(node, &mut paragraph);
+        if paragraph.text.trim().is_empty() {
+            return (None, paragraph);
+        }
+
+        let (marker, level) = match properties.and_then(|p| self.list_marker(p)) {
+            Some((marker, level)) => (marker, level),
+            None => return (Some(paragraph.text.clone()), paragraph),
+        };
+        let mut line = if indent { "  ".repeat(level) } else { String::new() };
+        if !marker.is_empty() {
+            line.push_str(&marker);
+            line.push(' ');
+        }
+        line.push_str(&paragraph.text);
+        (Some(line), paragraph)
+    }
+
+    /// Advance the list counters for a numbered paragraph and render its marker.
+    fn list_marker(&mut self, properties: Node<'_, '_>) -> Option<(String, usize)> {
+        let num_pr = child(&properties, "numPr");
+        let (style_num, style_level) = child_val(&properties, "pStyle")
+            .map(|style| self.styles.numbering(style))
+            .unwrap_or_default();
+        let num_id = num_pr.and_then(|n| child_val(&n, "numId")).or(style_num)?;
+        if num_id == "0" {
+            return None;
+        }
+        let ilvl = num_pr
+            .and_then(|n| child_number(&n, "ilvl"))
+            .or(style_level)
+            .unwrap_or(0)
+            .min(8);
+
+        let instance = self.numbering.instances.get(num_id)?;
+        let counters = self.counters.entry(instance.abstract_id.clone()).or_default();
+        if self.started.insert(num_id.to_string()) {
+            for &(level, start) in &instance.start_overrides {
+                counters.restart(level, start);
+            }
+        }
+        let numbering = &self.numbering;
+        let values = counters.next(ilvl, |l| numbering.level(num_id, l).map_or(1, |level| level.start));
+
+        let level = numbering.level(num_id, ilvl)?;
+        let marker = match level.format {
+            NumberFormat::Bullet => BULLET.to_string(),
+            _ => match &level.text {
+                Some(template) => expand_level_text(template, &values, |l| {
+                    numbering
+                        .level(num_id, l)
+                        .map_or(NumberFormat::Decimal, |level| level.format)
+                }),
+                None => format!("{}.", level.format.format(values[ilvl])),
+            },
+        };
+        Some((marker, ilvl))
+    }
+
+    fn table(&mut self, node: Node<'_, '_>) {
+        let mut nested = Vec::new();
+        let cells = self.table_cells(node, &mut nested);
+
+        if !cells.iter().flatten().all(|cell| cell.is_empty()) {
+            let rendered = match self.table_mode {
+                DocxTableMode::Markdown => Some(cells_to_markdown(&cells)),
+                DocxTableMode::Text => Some(cells_to_text(&cells)),
+                DocxTableMode::Structured => None,
+            };
+            if let Some(rendered) = rendered {
+                let text = &mut self.body.text;
+                if !text.is_empty() && !text.ends_with("\n\n") {
+                    text.push('\n');
+                }
+                text.push_str(rendered.trim_end());
+                text.push_str("\n\n");
+            }
+            self.push_table(cells);
+        }
+
+        for cells in nested {
+            self.push_table(cells);
+        }
+    }
+
+    fn push_table(&mut self, cells: Vec<Vec<String>>) {
+        if cells.iter().flatten().all(|cell| cell.is_empty()) {
+            return;
+        }
+        self.body.tables.push(Table {
+            markdown: cells_to_markdown(&cells),
+            cells,
+            page_number: self.body.tables.len() + 1,
+        });
+    }
+
+    /// Cells of a table; nested tables are appended to `nested` after their parent.
+    fn table_cells(&mut self, table: Node<'_, '_>, nested: &mut Vec<Vec<Vec<String>>>) -> Vec<Vec<String>> {
+        let mut rows: Vec<Vec<SourceCell>> = Vec::new();
+        for row in content_children(table, "tr") {
+            let mut cells = Vec::new();
+            let mut column = 0;
+            if let Some(before) = child(&row, "trPr").and_then(|p| child_number(&p, "gridBefore")) {
+                let span = before.clamp(1, MAX_COLSPAN);
+                cells.push(SourceCell {
+                    column,
+                    col_span: span,
+                    merge: Merge::None,
+                    text: String::new(),
+                });
+                column += span;
+            }
+            for cell in content_children(row, "tc") {
+                let properties = child(&cell, "tcPr");
+                let col_span = properties
+                    .and_then(|p| child_number(&p, "gridSpan"))
+                    .unwrap_or(1)
+                    .clamp(1, MAX_COLSPAN);
+                let merge = match properties.and_then(|p| child(&p, "vMerge")) {
+                    Some(v) if attr(&v, "val") == Some("restart") => Merge::Restart,
+                    Some(_) => Merge::Continue,
+                    None => Merge::None,
+                };
+                let text = self.cell_text(cell, nested);
+                cells.push(SourceCell {
+                    column,
+                    col_span,
+                    merge,
+                    text,
+                });
+                column += col_span;
+            }
+            rows.push(cells);
+        }
+
+        build_grid(resolve_vertical_merges(rows))
+    }
+
+    fn cell_text(&mut self, cell: Node<'_, '_>, nested: &mut Vec<Vec<Vec<String>>>) -> String {
+        let mut parts = Vec::new();
+        self.cell_blocks(cell, &mut parts, nested);
+        parts.join(" ")
+    }
+
+    fn cell_blocks(&mut self, parent: Node<'_, '_>, parts: &mut Vec<String>, nested: &mut Vec<Vec<Vec<String>>>) {
+        for node in parent.children().filter(Node::is_element) {
+            match node.tag_name().name() {
+                "p" => {
+                    if let (Some(line), _) = self.render_paragraph(node, false) {
+                        parts.push(line.trim().to_string());
+                    }
+                }
+                "tbl" => {
+                    let mut inner = Vec::new();
+                    let cells = self.table_cells(node, &mut inner);
+                    parts.extend(cells.iter().flatten().filter(|c| !c.is_empty()).cloned());
+                    nested.push(cells);
+                    nested.extend(inner);
+                }
+                "sdt" => {
+                    if let Some(content) = child(&node, "sdtContent") {
+                        self.cell_blocks(content, parts, nested);
+                    }
+                }
+                "customXml" => self.cell_blocks(node, parts, nested),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Children named `name`, looking through content controls and custom XML.
+fn content_children<'a, 'input>(parent: Node<'a, 'input>, name: &str) -> Vec<Node<'a, 'input>> {
+    let mut nodes = Vec::new();
+    for node in parent.children().filter(Node::is_element) {
+        if is(&node, name) {
+            nodes.push(node);
+        } else if is(&node, "sdt") {
+            if let Some(content) = child(&node, "sdtContent") {
+                nodes.extend(content_children(content, name));
+            }
+        } else if is(&node, "customXml") {
+            nodes.extend(content_children(node, name));
+        }
+    }
+    nodes
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Merge {
+    None,
+    Restart,
+    Continue,
+}
+
+#[derive(Debug)]
+struct SourceCell {
+    /// Grid column the cell starts at
+    column: usize,
+    col_span: usize,
+    merge: Merge,
+    text: String,
+}
+
+/// Turn `w:vMerge` chains into row spans, dropping the continuation cells.
+fn resolve_vertical_merges(rows: Vec<Vec<SourceCell>>) -> Vec<Vec<GridCell>> {
+    let continues = |row: Option<&Vec<SourceCell>>, column: usize| {
+        row.is_some_and(|cells| {
+            cells
+                .iter()
+                .any(|cell| cell.column == column && cell.merge == Merge::Continue)
+        })
+    };
+
+    rows.iter()
+        .enumerate()
+        .map(|(index, row)| {
+            row.iter()
+                .filter(|cell| {
+                    // A continuation is covered by the cell above, unless the chain has no start.
+                    cell.merge != Merge::Continue
+                        || index == 0
+                        || !rows[index - 1]
+                            .iter()
+                            .any(|above| above.column == cell.column && above.merge != Merge::None)
+                })
+                .map(|cell| {
+                    let mut row_span = 1;
+                    if cell.merge != Merge::None {
+                        while continues(rows.get(index + row_span), cell.column) {
+                            row_span += 1;
+                        }
+                    }
+                    GridCell {
+                        text: cell.text.clone(),
+                        row_span,
+                        col_span: cell.col_span,
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Replace `%1`..`%9` in a `w:lvlText` template with the formatted counters.
+fn expand_level_text(template: &str, values: &[usize], format: impl Fn(usize) -> NumberFormat) -> String {
+    let mut output = String::new();
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '%'
+            && let Some(level) = chars
+                .peek()
+                .and_then(|d| d.to_digit(10))
+                .filter(|d| (1..=9).contains(d))
+        {
+            chars.next();
+            let level = level as usize - 1;
+            if let Some(&value) = values.get(level) {
+                output.push_str(&format(level).format(value));
+            }
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+/// Collect the visible text of a paragraph.
+fn collect_text(node: Node<'_, '_>, paragraph: &mut ParagraphText) {
+    for child in node.children().filter(Node::is_element) {
+        match child.tag_name().name() {
+            "t" => paragraph.text.push_str(child.text().unwrap_or_default()),
+            "tab" => paragraph.text.push('\t'),
+            "br" if attr(&child, "type") == Some("page") => paragraph.page_breaks.push(paragraph.text.len()),
+            "br" | "cr" => paragraph.text.push('\n'),
+            "noBreakHyphen" => paragraph.text.push('-'),
+            // Properties, deleted text, field codes and duplicate fallback renderings
+            "pPr" | "rPr" | "del" | "delText" | "instrText" | "Fallback" => {}
+            "p" => {
+                if !paragraph.text.is_empty() && !paragraph.text.ends_with(char::is_whitespace) {
+                    paragraph.text.push(' ');
+                }
+                collect_text(child, paragraph);
+            }
+            _ => collect_text(child, paragraph),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const W_NS: &str = r#"xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main""#;
+
+    fn docx(body: &str, numbering: Option<&str>) -> Vec<u8> {
+        docx_with_parts(body, numbering, &[])
+    }
+
+    fn docx_with_parts(body: &str, numbering: Option<&str>, parts: &[(&str, &str)]) -> Vec<u8> {
+        let mut buffer = Cursor::new(Vec::new());
+        {
+            let mut zip = zip::ZipWriter::new(&mut buffer);
+            let options = zip::write::SimpleFileOptions::default();
+            zip.start_file("word/document.xml", options).unwrap();
+            write!(zip, r#"<w:document {W_NS}><w:body>{body}</w:body></w:document>"#).unwrap();
+            if let Some(numbering) = numbering {
+                zip.start_file("word/numbering.xml", options).unwrap();
+                write!(zip, r#"<w:numbering {W_NS}>{numbering}</w:numbering>"#).unwrap();
+            }
+            for (name, content) in parts {
+                zip.start_file(*name, options).unwrap();
+                zip.write_all(content.as_bytes()).unwrap();
+            }
+            zip.finish().unwrap();
+        }
+        buffer.into_inner()
+    }
+
+    fn item(text: &str, level: usize) -> String {
+        format!(
+            r#"<w:p><w:pPr><w:numPr><w:ilvl w:val="{level}"/><w:numId w:val="1"/></w:numPr></w:pPr><w:r><w:t>{text}</w:t></w:r></w:p>"#
+        )
+    }
+
+    #[test]
+    fn test_nested_list_numbering() {
+        let numbering = r#"<w:abstractNum w:abstractNumId="0">
+            <w:lvl w:ilvl="0"><w:start w:val="1"/><w:numFmt w:val="decimal"/><w:lvlText w:val="%1."/></w:lvl>
+            <w:lvl w:ilvl="1"><w:start w:val="1"/><w:numFmt w:val="lowerLetter"/><w:lvlText w:val="%2."/></w:lvl>
+            <w:lvl w:ilvl="2"><w:start w:val="1"/><w:numFmt w:val="lowerRoman"/><w:lvlText w:val="%3)"/></w:lvl>
+        </w:abstractNum>
+        <w:num w:numId="1"><w:abstractNumId w:val="0"/></w:num>"#;
+        let body = [
+            item("First", 0),
+            item("Sub one", 1),
+            item("Deep", 2),
+            item("Sub two", 1),
+            item("Second", 0),
+            item("Sub again", 1),
+        ]
+        .concat();
+
+        let result = extract_docx_body(&docx(&body, Some(numbering)), DocxTableMode::Markdown).unwrap();
+        assert_eq!(
+            result.text,
+            "1. First\n  a. Sub one\n    i) Deep\n  b. Sub two\n2. Second\n  a. Sub again"
+        );
+    }
+
+    #[test]
+    fn test_merged_table_cells_in_document_order() {
+        let body = r#"<w:p><w:r><w:t>Before</w:t></w:r></w:p>
+            <w:tbl>
+                <w:tr><w:tc><w:tcPr><w:vMerge w:val="restart"/></w:tcPr><w:p><w:r><w:t>Region</w:t></w:r></w:p></w:tc>
+                      <w:tc><w:tcPr><w:gridSpan w:val="2"/></w:tcPr><w:p><w:r><w:t>Sales</w:t></w:r></w:p></w:tc></w:tr>
+                <w:tr><w:tc><w:tcPr><w:vMerge/></w:tcPr><w:p/></w:tc>
+                      <w:tc><w:p><w:r><w:t>2023</w:t></w:r></w:p></w:tc>
+                      <w:tc><w:p><w:r><w:t>2024</w:t></w:r></w:p></w:tc></w:tr>
+            </w:tbl>
+            <w:p><w:r><w:br w:type="page"/><w:t>After</w:t></w:r></w:p>"#;
+        let bytes = docx(body, None);
+
+        let result = extract_docx_body(&bytes, DocxTableMode::Text).unwrap();
+        assert_eq!(
+            result.text,
+            "Before\n\nRegion\tSales\tSales\nRegion\t2023\t2024\n\nAfter"
+        );
+        assert_eq!(result.tables.len(), 1);
+        assert_eq!(result.tables[0].cells[1], vec!["Region", "2023", "2024"]);
+        let pages = result.page_boundaries().unwrap();
+        assert_eq!(&result.text[pages[1].byte_start..pages[1].byte_end], "After");
+
+        let structured = extract_docx_body(&bytes, DocxTableMode::Structured).unwrap();
+        assert_eq!(structured.text, "Before\nAfter");
+        assert_eq!(structured.tables.len(), 1);
+
+        let markdown = extract_docx_body(&bytes, DocxTableMode::Markdown).unwrap();
+        assert!(markdown.text.contains("| Region | Sales | Sales |"));
+    }
+
+    #[test]
+    fn test_chart_rendered_at_anchor() {
+        let body = r#"<w:p><w:r><w:t>Sales split</w:t></w:r></w:p>
+            <w:p><w:r><w:drawing><wp:inline xmlns:wp="http://schemas.openxmlformats.org/drawingml/2006/wordprocessingDrawing"><a:graphic xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/chart"><c:chart xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" r:id="rId7"/></a:graphicData></a:graphic></wp:inline></w:drawing></w:r></w:p>
+            <w:p><w:r><w:t>Done</w:t></w:r></w:p>"#;
+        let rels = r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships"><Relationship Id="rId7" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/chart" Target="charts/chart1.xml"/></Relationships>"#;
+        let chart = r#"<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart"><c:chart><c:title/><c:plotArea><c:pieChart><c:ser>
+            <c:tx><c:v>Share</c:v></c:tx>
+            <c:cat><c:strLit><c:ptCount val="2"/><c:pt idx="0"><c:v>Online</c:v></c:pt><c:pt idx="1"><c:v>Retail</c:v></c:pt></c:strLit></c:cat>
+            <c:val><c:numLit><c:ptCount val="2"/><c:pt idx="0"><c:v>60</c:v></c:pt><c:pt idx="1"><c:v>40</c:v></c:pt></c:numLit></c:val>
+        </c:ser></c:pieChart></c:plotArea></c:chart></c:chartSpace>"#;
+        let bytes = docx_with_parts(
+            body,
+            None,
+            &[
+                ("word/_rels/document.xml.rels", rels),
+                ("word/charts/chart1.xml", chart),
+            ],
+        );
+
+        let result = extract_docx_body(&bytes, DocxTableMode::Text).unwrap();
+        assert_eq!(
+            result.text,
+            "Sales split\n\nChart: Share (pie chart)\n\nCategory\tShare\nOnline\t60\nRetail\t40\n\nDone"
+        );
+        assert_eq!(result.tables.len(), 1);
+        assert_eq!(result.charts.len(), 1);
+        assert_eq!(result.charts[0].0, 0);
+        assert_eq!(result.charts[0].1.title.as_deref(), Some("Share"));
+    }
+}
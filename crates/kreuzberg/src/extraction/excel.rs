@@ -7,7 +7,11 @@
 //! # Features
 //!
 //! - **Multiple formats**: XLSX, XLSM, XLS, XLSB, ODS
-//! - **Sheet extraction**: Reads all sheets from workbook
+//! - **Sheet extraction**: Reads all sheets from workbook, or the sheets and rows selected
+//!   by a [`SpreadsheetConfig`]
+//! - **Formulas, comments and defined names**: Optional formula annotations, cell comments
+//!   (Office Open XML only) and named ranges for auditing spreadsheet models
+//! - **Charts**: Series data of charts placed on Office Open XML worksheets
 //! - **Markdown conversion**: Converts spreadsheet data to Markdown tables
 //! - **Office metadata**: Extracts core properties, custom properties (when `office` feature enabled)
 //! - **Error handling**: Distinguishes between format errors and true I/O errors
@@ -27,15 +31,16 @@
 //! # Ok(())
 //! # }
 //! ```
-use calamine::{Data, DataRef, Range, Reader, open_workbook_auto};
+use calamine::{Cell, Data, DataRef, Range, Reader, SheetVisible, open_workbook_auto};
 use std::collections::HashMap;
 use std::fmt::Write as FmtWrite;
 use std::io::{Cursor, Read, Seek};
 use std::path::Path;
 
+use crate::core::config::SpreadsheetConfig;
 use crate::error::{KreuzbergError, Result};
 use crate::extraction::capacity;
-use crate::types::{ExcelSheet, ExcelWorkbook};
+use crate::types::{CellComment, DefinedName, ExcelSheet, ExcelWorkbook, SheetMetadata, SheetVisibility};
 
 /// Maximum number of cells in a Range's bounding box before we consider it pathological.
 /// This threshold is set to prevent OOM when processing files with sparse data at extreme
@@ -44,22 +49,49 @@ use crate::types::{ExcelSheet, ExcelWorkbook};
 /// 100 million cells at ~64 bytes each = ~6.4 GB, which is a reasonable upper limit.
 const MAX_BOUNDING_BOX_CELLS: u64 = 100_000_000;
 
+/// Formulas of a sheet without the leading `=`, keyed by absolute (row, column).
+type Formulas = HashMap<(u32, u32), String>;
+
+/// Parts of an Office Open XML workbook that calamine does not read.
+#[derive(Debug, Default)]
+struct XlsxParts {
+    comments: Vec<CellComment>,
+    /// Worksheet charts with their sheet names, in workbook and drawing order
+    #[cfg(feature = "office")]
+    charts: Vec<(String, Chart)>,
+}
+
+#[cfg(feature = "office")]
+use crate::extraction::charts::{chart_caption, worksheet_charts};
 #[cfg(feature = "office")]
 use crate::extraction::office_metadata::{
     extract_core_properties, extract_custom_properties, extract_xlsx_app_properties,
 };
 #[cfg(feature = "office")]
+use crate::extraction::opc::{RELATIONSHIPS_NS, read_part, read_relationships};
+#[cfg(feature = "office")]
+use crate::extraction::xml_parser;
+#[cfg(feature = "office")]
+use crate::types::Chart;
+#[cfg(feature = "office")]
 use serde_json::Value;
 
-pub fn read_excel_file(file_path: &str) -> Result<ExcelWorkbook> {
-    let lower_path = file_path.to_lowercase();
+pub fn read_excel_file(file_path: impl AsRef<Path>) -> Result<ExcelWorkbook> {
+    read_excel_file_with_config(file_path, &SpreadsheetConfig::default())
+}
+
+/// Read the sheets and rows of a workbook file selected by `config`.
+///
+/// The path does not need to be valid UTF-8.
+pub fn read_excel_file_with_config(file_path: impl AsRef<Path>, config: &SpreadsheetConfig) -> Result<ExcelWorkbook> {
+    let file_path = file_path.as_ref();
+    let is_xlsx = file_path
+        .extension()
+        .map(|ext| ext.to_string_lossy().to_lowercase())
+        .is_some_and(|ext| matches!(ext.as_str(), "xlsx" | "xlsm" | "xlam" | "xltm"));
 
     #[cfg(feature = "office")]
-    let office_metadata = if lower_path.ends_with(".xlsx")
-        || lower_path.ends_with(".xlsm")
-        || lower_path.ends_with(".xlam")
-        || lower_path.ends_with(".xltm")
-    {
+    let office_metadata = if is_xlsx {
         extract_xlsx_office_metadata_from_file(file_path).ok()
     } else {
         None
@@ -69,19 +101,21 @@ pub fn read_excel_file(file_path: &str) -> Result<ExcelWorkbook> {
     let office_metadata: Option<HashMap<String, String>> = None;
 
     // For XLSX files, use specialized handler with OOM protection
-    if lower_path.ends_with(".xlsx")
-        || lower_path.ends_with(".xlsm")
-        || lower_path.ends_with(".xlam")
-        || lower_path.ends_with(".xltm")
-    {
+    if is_xlsx {
+        #[cfg(feature = "office")]
+        let parts = read_xlsx_parts_from_file(file_path, config).unwrap_or_default();
+
+        #[cfg(not(feature = "office"))]
+        let parts = XlsxParts::default();
+
         let file = std::fs::File::open(file_path)?;
         let workbook = calamine::Xlsx::new(std::io::BufReader::new(file))
             .map_err(|e| KreuzbergError::parsing(format!("Failed to parse XLSX: {}", e)))?;
-        return process_xlsx_workbook(workbook, office_metadata);
+        return process_xlsx_workbook(workbook, office_metadata, parts, config);
     }
 
     // For other formats, use open_workbook_auto
-    let workbook = match open_workbook_auto(Path::new(file_path)) {
+    let workbook = match open_workbook_auto(file_path) {
         Ok(wb) => wb,
         Err(calamine::Error::Io(io_err)) => {
             if io_err.kind() == std::io::ErrorKind::InvalidData {
@@ -96,10 +130,19 @@ pub fn read_excel_file(file_path: &str) -> Result<ExcelWorkbook> {
         Err(e) => return Err(KreuzbergError::parsing(format!("Failed to parse Excel file: {}", e))),
     };
 
-    process_workbook(workbook, office_metadata)
+    process_workbook(workbook, office_metadata, config)
 }
 
 pub fn read_excel_bytes(data: &[u8], file_extension: &str) -> Result<ExcelWorkbook> {
+    read_excel_bytes_with_config(data, file_extension, &SpreadsheetConfig::default())
+}
+
+/// Read the sheets and rows of an in-memory workbook selected by `config`.
+pub fn read_excel_bytes_with_config(
+    data: &[u8],
+    file_extension: &str,
+    config: &SpreadsheetConfig,
+) -> Result<ExcelWorkbook> {
     #[cfg(feature = "office")]
     let office_metadata = match file_extension.to_lowercase().as_str() {
         ".xlsx" | ".xlsm" | ".xlam" | ".xltm" => extract_xlsx_office_metadata_from_bytes(data).ok(),
@@ -113,24 +156,30 @@ pub fn read_excel_bytes(data: &[u8], file_extension: &str) -> Result<ExcelWorkbo
 
     match file_extension.to_lowercase().as_str() {
         ".xlsx" | ".xlsm" | ".xlam" | ".xltm" => {
+            #[cfg(feature = "office")]
+            let parts = read_xlsx_parts_from_bytes(data, config).unwrap_or_default();
+
+            #[cfg(not(feature = "office"))]
+            let parts = XlsxParts::default();
+
             let workbook = calamine::Xlsx::new(cursor)
                 .map_err(|e| KreuzbergError::parsing(format!("Failed to parse XLSX: {}", e)))?;
-            process_xlsx_workbook(workbook, office_metadata)
+            process_xlsx_workbook(workbook, office_metadata, parts, config)
         }
         ".xls" | ".xla" => {
             let workbook = calamine::Xls::new(cursor)
                 .map_err(|e| KreuzbergError::parsing(format!("Failed to parse XLS: {}", e)))?;
-            process_workbook(workbook, office_metadata)
+            process_workbook(workbook, office_metadata, config)
         }
         ".xlsb" => {
             let workbook = calamine::Xlsb::new(cursor)
                 .map_err(|e| KreuzbergError::parsing(format!("Failed to parse XLSB: {}", e)))?;
-            process_workbook(workbook, office_metadata)
+            process_workbook(workbook, office_metadata, config)
         }
         ".ods" => {
             let workbook = calamine::Ods::new(cursor)
                 .map_err(|e| KreuzbergError::parsing(format!("Failed to parse ODS: {}", e)))?;
-            process_workbook(workbook, office_metadata)
+            process_workbook(workbook, office_metadata, config)
         }
         _ => Err(KreuzbergError::parsing(format!(
             "Unsupported file extension: {}",
@@ -148,32 +197,216 @@ pub fn read_excel_bytes(data: &[u8], file_extension: &str) -> Result<ExcelWorkbo
 fn process_xlsx_workbook<RS: Read + Seek>(
     mut workbook: calamine::Xlsx<RS>,
     office_metadata: Option<HashMap<String, String>>,
+    parts: XlsxParts,
+    config: &SpreadsheetConfig,
 ) -> Result<ExcelWorkbook> {
     let sheet_names = workbook.sheet_names();
     let mut sheets = Vec::with_capacity(sheet_names.len());
+    let mut sheet_metadata = Vec::with_capacity(sheet_names.len());
 
-    for name in &sheet_names {
+    for (name, visibility) in sheet_visibilities(&workbook) {
+        let mut info = unread_sheet(&name, visibility);
+        if !config.selects_sheet(&name, visibility != SheetVisibility::Visible) {
+            sheet_metadata.push(info);
+            continue;
+        }
         // Use worksheet_cells_reader to stream cells and detect pathological bounding boxes
-        match process_xlsx_sheet_safe(&mut workbook, name) {
-            Ok(sheet) => sheets.push(sheet),
+        match process_xlsx_sheet_safe(&mut workbook, &name, config) {
+            Ok((sheet, used)) => {
+                info.extracted = true;
+                used.apply(&mut info);
+                sheets.push(sheet);
+            }
             Err(e) => {
                 // Log but don't fail - continue with other sheets
                 tracing::warn!("Failed to process sheet '{}': {}", name, e);
             }
         }
+        sheet_metadata.push(info);
     }
 
+    #[cfg(feature = "office")]
+    attach_charts(&mut sheets, parts.charts);
+    let comments = attach_comments(&mut sheets, parts.comments);
+    let defined_names = defined_names(&workbook, config);
     let metadata = extract_metadata(&workbook, &sheet_names, office_metadata);
-    Ok(ExcelWorkbook { sheets, metadata })
+    Ok(ExcelWorkbook {
+        sheets,
+        metadata,
+        sheet_metadata,
+        defined_names,
+        comments,
+    })
+}
+
+/// Sheet names with their visibility, in workbook order.
+fn sheet_visibilities<RS, R>(workbook: &R) -> Vec<(String, SheetVisibility)>
+where
+    RS: Read + Seek,
+    R: Reader<RS>,
+{
+    workbook
+        .sheets_metadata()
+        .iter()
+        .map(|sheet| {
+            let visibility = match sheet.visible {
+                SheetVisible::Visible => SheetVisibility::Visible,
+                SheetVisible::Hidden => SheetVisibility::Hidden,
+                SheetVisible::VeryHidden => SheetVisibility::VeryHidden,
+            };
+            (sheet.name.clone(), visibility)
+        })
+        .collect()
+}
+
+/// The workbook's defined names, if requested.
+fn defined_names<RS, R>(workbook: &R, config: &SpreadsheetConfig) -> Vec<DefinedName>
+where
+    RS: Read + Seek,
+    R: Reader<RS>,
+{
+    if !config.defined_names {
+        return Vec::new();
+    }
+    workbook
+        .defined_names()
+        .iter()
+        .map(|(name, refers_to)| DefinedName {
+            name: name.clone(),
+            refers_to: refers_to.clone(),
+        })
+        .collect()
+}
+
+/// Give extracted sheets their charts and render each chart's data below the sheet's table.
+///
+/// Chart page numbers match the sheet's table page number.
+#[cfg(feature = "office")]
+fn attach_charts(sheets: &mut [ExcelSheet], charts: Vec<(String, Chart)>) {
+    for (sheet_name, mut chart) in charts {
+        let Some((index, sheet)) = sheets
+            .iter_mut()
+            .enumerate()
+            .find(|(_, sheet)| sheet.name == sheet_name)
+        else {
+            continue;
+        };
+        chart.page_number = index + 1;
+        sheet.markdown.truncate(sheet.markdown.trim_end().len());
+        write!(
+            sheet.markdown,
+            "\n\n### {}\n\n{}",
+            chart_caption(&chart),
+            crate::extraction::cells_to_markdown(&chart.cells).trim_end()
+        )
+        .expect("write to String cannot fail");
+        sheet.charts.push(chart);
+    }
+}
+
+/// Keep the comments of extracted sheets and list them below each sheet's table.
+fn attach_comments(sheets: &mut [ExcelSheet], comments: Vec<CellComment>) -> Vec<CellComment> {
+    let comments: Vec<CellComment> = comments
+        .into_iter()
+        .filter(|comment| sheets.iter().any(|sheet| sheet.name == comment.sheet))
+        .collect();
+
+    for sheet in sheets.iter_mut() {
+        let mut sheet_comments = comments.iter().filter(|comment| comment.sheet == sheet.name).peekable();
+        if sheet_comments.peek().is_none() {
+            continue;
+        }
+        let mut section = String::from("\n\n### Comments\n\n");
+        for comment in sheet_comments {
+            let text = comment.text.split_whitespace().collect::<Vec<_>>().join(" ");
+            match &comment.author {
+                Some(author) => writeln!(section, "- **{}** ({}): {}", comment.cell, author, text),
+                None => writeln!(section, "- **{}**: {}", comment.cell, text),
+            }
+            .expect("write to String cannot fail");
+        }
+        sheet.markdown.truncate(sheet.markdown.trim_end().len());
+        sheet.markdown.push_str(&section);
+    }
+
+    comments
+}
+
+fn unread_sheet(name: &str, visibility: SheetVisibility) -> SheetMetadata {
+    SheetMetadata {
+        name: name.to_owned(),
+        visibility,
+        extracted: false,
+        row_count: None,
+        col_count: None,
+        dimensions: None,
+        truncated: false,
+    }
+}
+
+/// Used range of a sheet before row truncation, as 0-indexed inclusive bounds.
+struct UsedRange {
+    bounds: Option<((u32, u32), (u32, u32))>,
+    truncated: bool,
+}
+
+impl UsedRange {
+    fn of(range: &Range<Data>, truncated: bool) -> Self {
+        Self {
+            bounds: range.start().zip(range.end()),
+            truncated,
+        }
+    }
+
+    fn apply(&self, info: &mut SheetMetadata) {
+        info.truncated = self.truncated;
+        let Some(((row_min, col_min), (row_max, col_max))) = self.bounds else {
+            info.row_count = Some(0);
+            info.col_count = Some(0);
+            return;
+        };
+        info.row_count = Some((row_max - row_min + 1) as usize);
+        info.col_count = Some((col_max - col_min + 1) as usize);
+        info.dimensions = Some(format!(
+            "{}{}:{}{}",
+            col_to_excel_letter(col_min),
+            row_min + 1,
+            col_to_excel_letter(col_max),
+            row_max + 1
+        ));
+    }
+}
+
+/// Keep the first `max_rows` rows of a range.
+fn truncate_rows(range: Range<Data>, max_rows: Option<usize>) -> (Range<Data>, bool) {
+    match (max_rows, range.start(), range.end()) {
+        (Some(max_rows), Some(start), Some(end)) if range.height() > max_rows => {
+            if max_rows == 0 {
+                return (Range::empty(), true);
+            }
+            let last_row = start.0 + (max_rows as u32 - 1);
+            (range.range(start, (last_row, end.1)), true)
+        }
+        _ => (range, false),
+    }
 }
 
 /// Process a single XLSX sheet safely by pre-checking the bounding box.
 ///
 /// This function streams cells to compute the actual bounding box without allocating
 /// a full Range, then only creates the Range if the bounding box is within safe limits.
-fn process_xlsx_sheet_safe<RS: Read + Seek>(workbook: &mut calamine::Xlsx<RS>, sheet_name: &str) -> Result<ExcelSheet> {
+///
+/// With `max_rows`, only the cells of the first `max_rows` rows are kept; the used range
+/// of the whole sheet is still measured for the sheet metadata.
+fn process_xlsx_sheet_safe<RS: Read + Seek>(
+    workbook: &mut calamine::Xlsx<RS>,
+    sheet_name: &str,
+    config: &SpreadsheetConfig,
+) -> Result<(ExcelSheet, UsedRange)> {
+    let max_rows = config.max_rows;
+
     // First pass: stream cells to compute actual bounding box and collect cell data
-    let (cells, row_min, row_max, col_min, col_max) = {
+    let (cells, row_min, row_max, col_min, col_max, full_bounds) = {
         let mut cell_reader = workbook
             .worksheet_cells_reader(sheet_name)
             .map_err(|e| KreuzbergError::parsing(format!("Failed to read sheet '{}': {}", sheet_name, e)))?;
@@ -183,10 +416,25 @@ fn process_xlsx_sheet_safe<RS: Read + Seek>(workbook: &mut calamine::Xlsx<RS>, s
         let mut row_max = 0u32;
         let mut col_min = u32::MAX;
         let mut col_max = 0u32;
+        let mut full_bounds: Option<((u32, u32), (u32, u32))> = None;
+        let mut first_row: Option<u32> = None;
 
         // Stream through all cells, tracking bounds
         while let Ok(Some(cell)) = cell_reader.next_cell() {
             let (row, col) = cell.get_position();
+            let ((full_row_min, full_col_min), (full_row_max, full_col_max)) =
+                full_bounds.get_or_insert(((row, col), (row, col)));
+            *full_row_min = (*full_row_min).min(row);
+            *full_col_min = (*full_col_min).min(col);
+            *full_row_max = (*full_row_max).max(row);
+            *full_col_max = (*full_col_max).max(col);
+
+            // Rows are stored in order, so rows past the limit follow the first `max_rows` rows.
+            let first_row = *first_row.get_or_insert(row);
+            if max_rows.is_some_and(|max_rows| row.saturating_sub(first_row) as usize >= max_rows) {
+                continue;
+            }
+
             row_min = row_min.min(row);
             row_max = row_max.max(row);
             col_min = col_min.min(col);
@@ -207,19 +455,34 @@ fn process_xlsx_sheet_safe<RS: Read + Seek>(workbook: &mut calamine::Xlsx<RS>, s
             };
             cells.push(((row, col), data));
         }
-        (cells, row_min, row_max, col_min, col_max)
+        (cells, row_min, row_max, col_min, col_max, full_bounds)
     }; // cell_reader is dropped here, releasing the borrow
 
+    let used = UsedRange {
+        bounds: full_bounds,
+        truncated: full_bounds.is_some_and(|((first, _), (last, _))| {
+            max_rows.is_some_and(|max_rows| (last - first) as usize >= max_rows)
+        }),
+    };
+
+    let formulas = if config.formulas {
+        read_xlsx_formulas(workbook, sheet_name)?
+    } else {
+        Formulas::new()
+    };
+
     // Check if sheet is empty
     if cells.is_empty() {
-        return Ok(ExcelSheet {
+        let sheet = ExcelSheet {
             name: sheet_name.to_owned(),
             markdown: format!("## {}\n\n*Empty sheet*", sheet_name),
             row_count: 0,
             col_count: 0,
             cell_count: 0,
             table_cells: None,
-        });
+            charts: Vec::new(),
+        };
+        return Ok((sheet, used));
     }
 
     // Calculate bounding box size
@@ -230,16 +493,84 @@ fn process_xlsx_sheet_safe<RS: Read + Seek>(workbook: &mut calamine::Xlsx<RS>, s
     // Check for pathological bounding box
     if bb_cells > MAX_BOUNDING_BOX_CELLS {
         // Sheet has sparse data at extreme positions - process directly from cells
-        return process_sparse_sheet_from_cells(sheet_name, cells, row_min, row_max, col_min, col_max);
+        let sheet = process_sparse_sheet_from_cells(sheet_name, cells, row_min, row_max, col_min, col_max, &formulas)?;
+        return Ok((sheet, used));
     }
 
     // Safe to create a Range - bounding box is within limits
-    // Use calamine's normal worksheet_range which will create the Range
-    let range = workbook
-        .worksheet_range(sheet_name)
-        .map_err(|e| KreuzbergError::parsing(format!("Failed to parse sheet '{}': {}", sheet_name, e)))?;
+    let range = if used.truncated {
+        // Build the range from the kept cells instead of reading the whole sheet again
+        Range::from_sparse(
+            cells
+                .into_iter()
+                .map(|(position, data)| Cell::new(position, data))
+                .collect(),
+        )
+    } else {
+        // Use calamine's normal worksheet_range which will create the Range
+        workbook
+            .worksheet_range(sheet_name)
+            .map_err(|e| KreuzbergError::parsing(format!("Failed to parse sheet '{}': {}", sheet_name, e)))?
+    };
+
+    Ok((process_sheet(sheet_name, &range, &formulas), used))
+}
+
+/// Stream the formulas of an XLSX sheet without building a dense range.
+fn read_xlsx_formulas<RS: Read + Seek>(workbook: &mut calamine::Xlsx<RS>, sheet_name: &str) -> Result<Formulas> {
+    let mut cell_reader = workbook
+        .worksheet_cells_reader(sheet_name)
+        .map_err(|e| KreuzbergError::parsing(format!("Failed to read sheet '{}': {}", sheet_name, e)))?;
+
+    let mut formulas = Formulas::new();
+    while let Ok(Some(cell)) = cell_reader.next_formula() {
+        if !cell.get_value().is_empty() {
+            formulas.insert(cell.get_position(), cell.get_value().clone());
+        }
+    }
+    Ok(formulas)
+}
+
+/// Formulas of a sheet of the other workbook formats.
+fn sheet_formulas<RS, R>(workbook: &mut R, sheet_name: &str) -> Formulas
+where
+    RS: Read + Seek,
+    R: Reader<RS>,
+{
+    let Ok(range) = workbook.worksheet_formula(sheet_name) else {
+        return Formulas::new();
+    };
+    let (first_row, first_col) = range.start().unwrap_or_default();
+    range
+        .used_cells()
+        .map(|(row, col, formula)| {
+            // OpenDocument formulas are stored as `of:=SUM([.A1:.A5])`
+            let formula = formula.strip_prefix("of:").unwrap_or(formula);
+            (
+                (first_row + row as u32, first_col + col as u32),
+                formula.trim_start_matches('=').to_owned(),
+            )
+        })
+        .collect()
+}
+
+/// Formula of a cell, if formulas were read.
+#[inline]
+fn formula_at(formulas: &Formulas, position: (u32, u32)) -> Option<&str> {
+    if formulas.is_empty() {
+        return None;
+    }
+    formulas.get(&position).map(String::as_str)
+}
 
-    Ok(process_sheet(sheet_name, &range))
+/// Cell text followed by the cell's formula, as in `15.0 (=SUM(A1:A5))`.
+fn format_cell_with_formula(data: &Data, formula: Option<&str>) -> String {
+    let value = format_cell_to_string(data);
+    match formula {
+        Some(formula) if value.is_empty() => format!("={}", formula),
+        Some(formula) => format!("{} (={})", value, formula),
+        None => value,
+    }
 }
 
 /// Process a sparse sheet directly from collected cells without creating a full Range.
@@ -253,6 +584,7 @@ fn process_sparse_sheet_from_cells(
     row_max: u32,
     col_min: u32,
     col_max: u32,
+    formulas: &Formulas,
 ) -> Result<ExcelSheet> {
     let cell_count = cells.len();
     let bb_rows = (row_max - row_min + 1) as usize;
@@ -297,7 +629,7 @@ fn process_sparse_sheet_from_cells(
                 break;
             }
             let cell_ref = col_to_excel_letter(col);
-            let cell_str = format_cell_to_string(data);
+            let cell_str = format_cell_with_formula(data, formula_at(formulas, (row, col)));
             if !cell_str.is_empty() {
                 writeln!(markdown, "- **{}{}**: {}", cell_ref, row + 1, cell_str).expect("write to String cannot fail");
                 output_count += 1;
@@ -312,6 +644,7 @@ fn process_sparse_sheet_from_cells(
         col_count: bb_cols,
         cell_count,
         table_cells: None, // No structured table for sparse sheets
+        charts: Vec::new(),
     })
 }
 
@@ -327,7 +660,11 @@ fn col_to_excel_letter(col: u32) -> String {
     result
 }
 
-fn process_workbook<RS, R>(mut workbook: R, office_metadata: Option<HashMap<String, String>>) -> Result<ExcelWorkbook>
+fn process_workbook<RS, R>(
+    mut workbook: R,
+    office_metadata: Option<HashMap<String, String>>,
+    config: &SpreadsheetConfig,
+) -> Result<ExcelWorkbook>
 where
     RS: std::io::Read + std::io::Seek,
     R: Reader<RS>,
@@ -335,20 +672,45 @@ where
     let sheet_names = workbook.sheet_names();
 
     let mut sheets = Vec::with_capacity(sheet_names.len());
-
-    for name in &sheet_names {
-        if let Ok(range) = workbook.worksheet_range(name) {
-            sheets.push(process_sheet(name, &range));
+    let mut sheet_metadata = Vec::with_capacity(sheet_names.len());
+
+    for (name, visibility) in sheet_visibilities(&workbook) {
+        let mut info = unread_sheet(&name, visibility);
+        if config.selects_sheet(&name, visibility != SheetVisibility::Visible)
+            && let Ok(range) = workbook.worksheet_range(&name)
+        {
+            let used_before = UsedRange::of(&range, false);
+            let (range, truncated) = truncate_rows(range, config.max_rows);
+            info.extracted = true;
+            UsedRange {
+                truncated,
+                ..used_before
+            }
+            .apply(&mut info);
+            let formulas = if config.formulas {
+                sheet_formulas(&mut workbook, &name)
+            } else {
+                Formulas::new()
+            };
+            sheets.push(process_sheet(&name, &range, &formulas));
         }
+        sheet_metadata.push(info);
     }
 
+    let defined_names = defined_names(&workbook, config);
     let metadata = extract_metadata(&workbook, &sheet_names, office_metadata);
 
-    Ok(ExcelWorkbook { sheets, metadata })
+    Ok(ExcelWorkbook {
+        sheets,
+        metadata,
+        sheet_metadata,
+        defined_names,
+        comments: Vec::new(),
+    })
 }
 
 #[inline]
-fn process_sheet(name: &str, range: &Range<Data>) -> ExcelSheet {
+fn process_sheet(name: &str, range: &Range<Data>, formulas: &Formulas) -> ExcelSheet {
     let (rows, cols) = range.get_size();
     let cell_count = range.used_cells().count();
 
@@ -366,9 +728,10 @@ fn process_sheet(name: &str, range: &Range<Data>) -> ExcelSheet {
             col_count: cols,
             cell_count,
             table_cells: None,
+            charts: Vec::new(),
         }
     } else {
-        let (markdown, table_cells) = generate_markdown_and_cells(name, range, estimated_capacity);
+        let (markdown, table_cells) = generate_markdown_and_cells(name, range, estimated_capacity, formulas);
         ExcelSheet {
             name: name.to_owned(),
             markdown,
@@ -376,6 +739,7 @@ fn process_sheet(name: &str, range: &Range<Data>) -> ExcelSheet {
             col_count: cols,
             cell_count,
             table_cells: Some(table_cells),
+            charts: Vec::new(),
         }
     }
 }
@@ -387,7 +751,12 @@ fn process_sheet(name: &str, range: &Range<Data>) -> ExcelSheet {
 /// was previously done in `sheets_to_tables()`.
 ///
 /// Returns (markdown, table_cells) where table_cells is a 2D vector of strings.
-fn generate_markdown_and_cells(sheet_name: &str, range: &Range<Data>, capacity: usize) -> (String, Vec<Vec<String>>) {
+fn generate_markdown_and_cells(
+    sheet_name: &str,
+    range: &Range<Data>,
+    capacity: usize,
+    formulas: &Formulas,
+) -> (String, Vec<Vec<String>>) {
     // Fix for issue #331: Protect against extreme declared dimensions.
     // Excel Solver files can declare A1:XFD1048575 (1M+ rows) but only have ~26 actual cells.
     // Calling range.rows().collect() would iterate ALL declared rows causing OOM.
@@ -421,6 +790,7 @@ fn generate_markdown_and_cells(sheet_name: &str, range: &Range<Data>, capacity:
         return (result, Vec::new());
     }
 
+    let (first_row, first_col) = range.start().unwrap_or_default();
     let header = &rows[0];
     let header_len = header.len();
     let row_count = rows.len();
@@ -447,7 +817,7 @@ fn generate_markdown_and_cells(sheet_name: &str, range: &Range<Data>, capacity:
         if i > 0 {
             markdown.push_str(" | ");
         }
-        let cell_str = format_cell_to_string(cell);
+        let cell_str = format_cell_with_formula(cell, formula_at(formulas, (first_row, first_col + i as u32)));
 
         if cell_str.contains('|') || cell_str.contains('\\') {
             escape_markdown_into(&mut markdown, &cell_str);
@@ -468,7 +838,7 @@ fn generate_markdown_and_cells(sheet_name: &str, range: &Range<Data>, capacity:
     }
     markdown.push_str(" |\n");
 
-    for row in rows.iter().skip(1) {
+    for (row_index, row) in rows.iter().enumerate().skip(1) {
         let mut row_cells = Vec::with_capacity(header_len);
         markdown.push_str("| ");
         for i in 0..header_len {
@@ -476,7 +846,8 @@ fn generate_markdown_and_cells(sheet_name: &str, range: &Range<Data>, capacity:
                 markdown.push_str(" | ");
             }
             let cell_str = if let Some(cell) = row.get(i) {
-                let cell_str = format_cell_to_string(cell);
+                let position = (first_row + row_index as u32, first_col + i as u32);
+                let cell_str = format_cell_with_formula(cell, formula_at(formulas, position));
 
                 if cell_str.contains('|') || cell_str.contains('\\') {
                     escape_markdown_into(&mut markdown, &cell_str);
@@ -601,7 +972,7 @@ pub fn excel_to_markdown(workbook: &ExcelWorkbook) -> String {
 }
 
 #[cfg(feature = "office")]
-fn extract_xlsx_office_metadata_from_file(file_path: &str) -> Result<HashMap<String, String>> {
+fn extract_xlsx_office_metadata_from_file(file_path: &Path) -> Result<HashMap<String, String>> {
     use std::fs::File;
     use zip::ZipArchive;
 
@@ -632,6 +1003,9 @@ fn extract_xlsx_office_metadata_from_archive<R: std::io::Read + std::io::Seek>(
     let mut metadata = HashMap::new();
 
     if let Ok(core) = extract_core_properties(archive) {
+        for (key, value) in core.document_property_entries() {
+            metadata.insert(key.to_string(), value);
+        }
         if let Some(title) = core.title {
             metadata.insert("title".to_string(), title);
         }
@@ -645,30 +1019,6 @@ fn extract_xlsx_office_metadata_from_archive<R: std::io::Read + std::io::Seek>(
         if let Some(keywords) = core.keywords {
             metadata.insert("keywords".to_string(), keywords);
         }
-        if let Some(description) = core.description {
-            metadata.insert("description".to_string(), description);
-        }
-        if let Some(modified_by) = core.last_modified_by {
-            metadata.insert("modified_by".to_string(), modified_by);
-        }
-        if let Some(created) = core.created {
-            metadata.insert("created_at".to_string(), created);
-        }
-        if let Some(modified) = core.modified {
-            metadata.insert("modified_at".to_string(), modified);
-        }
-        if let Some(revision) = core.revision {
-            metadata.insert("revision".to_string(), revision);
-        }
-        if let Some(category) = core.category {
-            metadata.insert("category".to_string(), category);
-        }
-        if let Some(content_status) = core.content_status {
-            metadata.insert("content_status".to_string(), content_status);
-        }
-        if let Some(language) = core.language {
-            metadata.insert("language".to_string(), language);
-        }
     }
 
     if let Ok(app) = extract_xlsx_app_properties(archive) {
@@ -676,7 +1026,7 @@ fn extract_xlsx_office_metadata_from_archive<R: std::io::Read + std::io::Seek>(
             metadata.insert("worksheet_names".to_string(), app.worksheet_names.join(", "));
         }
         if let Some(company) = app.company {
-            metadata.insert("organization".to_string(), company);
+            metadata.insert("company".to_string(), company);
         }
         if let Some(application) = app.application {
             metadata.insert("application".to_string(), application);
@@ -702,6 +1052,179 @@ fn extract_xlsx_office_metadata_from_archive<R: std::io::Read + std::io::Seek>(
     Ok(metadata)
 }
 
+#[cfg(feature = "office")]
+fn read_xlsx_parts_from_file(file_path: &Path, config: &SpreadsheetConfig) -> Result<XlsxParts> {
+    let file = std::fs::File::open(file_path)?;
+    let mut archive = zip::ZipArchive::new(file)
+        .map_err(|e| KreuzbergError::parsing(format!("Failed to open ZIP archive: {}", e)))?;
+
+    read_xlsx_parts_from_archive(&mut archive, config)
+}
+
+#[cfg(feature = "office")]
+fn read_xlsx_parts_from_bytes(data: &[u8], config: &SpreadsheetConfig) -> Result<XlsxParts> {
+    let mut archive = zip::ZipArchive::new(Cursor::new(data))
+        .map_err(|e| KreuzbergError::parsing(format!("Failed to open ZIP archive: {}", e)))?;
+
+    read_xlsx_parts_from_archive(&mut archive, config)
+}
+
+#[cfg(feature = "office")]
+fn read_xlsx_parts_from_archive<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    config: &SpreadsheetConfig,
+) -> Result<XlsxParts> {
+    let worksheets = worksheet_parts(archive)?;
+    let comments = if config.comments {
+        read_xlsx_comments(archive, &worksheets)
+    } else {
+        Vec::new()
+    };
+    let mut charts = Vec::new();
+    for (name, part) in &worksheets {
+        charts.extend(
+            worksheet_charts(archive, part)
+                .into_iter()
+                .map(|chart| (name.clone(), chart)),
+        );
+    }
+    Ok(XlsxParts { comments, charts })
+}
+
+/// Worksheet names with their part paths, in workbook order.
+#[cfg(feature = "office")]
+fn worksheet_parts<R: Read + Seek>(archive: &mut zip::ZipArchive<R>) -> Result<Vec<(String, String)>> {
+    let workbook_xml = read_part(archive, "xl/workbook.xml")?;
+    let workbook = xml_parser::parse_document(&workbook_xml, None, |e| {
+        KreuzbergError::parsing(format!("Failed to parse workbook.xml: {}", e))
+    })?;
+    let workbook_rels = read_relationships(archive, "xl/workbook.xml");
+
+    Ok(workbook
+        .descendants()
+        .filter(|node| node.has_tag_name("sheet"))
+        .filter_map(|sheet| {
+            let id = sheet.attribute((RELATIONSHIPS_NS, "id"))?;
+            let part = workbook_rels.iter().find(|rel| rel.id == id)?;
+            Some((sheet.attribute("name")?.to_owned(), part.target.clone()))
+        })
+        .collect())
+}
+
+/// Read the comments and notes of every worksheet, in workbook order.
+///
+/// Excel also saves threaded comments as legacy notes with placeholder text, so a
+/// cell's threaded comments replace its legacy note.
+#[cfg(feature = "office")]
+fn read_xlsx_comments<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    worksheets: &[(String, String)],
+) -> Vec<CellComment> {
+    let persons: HashMap<String, String> = read_relationships(archive, "xl/workbook.xml")
+        .iter()
+        .filter(|rel| rel.kind.ends_with("/person"))
+        .filter_map(|rel| read_part(archive, &rel.target).ok())
+        .flat_map(|xml| {
+            let Some(doc) = xml_parser::try_parse_document(&xml, None) else {
+                return Vec::new();
+            };
+            doc.descendants()
+                .filter(|node| node.has_tag_name("person"))
+                .filter_map(|node| {
+                    Some((
+                        node.attribute("id")?.to_owned(),
+                        node.attribute("displayName")?.to_owned(),
+                    ))
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut comments = Vec::new();
+    for (name, sheet_part) in worksheets {
+        let sheet_rels = read_relationships(archive, sheet_part);
+        let mut threaded = Vec::new();
+        for rel in sheet_rels.iter().filter(|rel| rel.kind.ends_with("/threadedComment")) {
+            if let Ok(xml) = read_part(archive, &rel.target) {
+                threaded.extend(parse_threaded_comments(&xml, name, &persons));
+            }
+        }
+        for rel in sheet_rels.iter().filter(|rel| rel.kind.ends_with("/comments")) {
+            if let Ok(xml) = read_part(archive, &rel.target) {
+                comments.extend(
+                    parse_legacy_comments(&xml, name)
+                        .into_iter()
+                        .filter(|note| !threaded.iter().any(|comment: &CellComment| comment.cell == note.cell)),
+                );
+            }
+        }
+        comments.extend(threaded);
+    }
+
+    comments
+}
+
+/// Parse `xl/commentsN.xml`.
+#[cfg(feature = "office")]
+fn parse_legacy_comments(xml: &str, sheet: &str) -> Vec<CellComment> {
+    let Some(doc) = xml_parser::try_parse_document(xml, None) else {
+        return Vec::new();
+    };
+    let authors: Vec<&str> = doc
+        .descendants()
+        .filter(|node| node.has_tag_name("author"))
+        .map(|node| node.text().unwrap_or_default())
+        .collect();
+
+    doc.descendants()
+        .filter(|node| node.has_tag_name("comment"))
+        .filter_map(|comment| {
+            // Phonetic runs (`rPh`) repeat the text as a reading aid.
+            let text: String = comment
+                .descendants()
+                .filter(|node| node.has_tag_name("t") && !node.ancestors().any(|a| a.has_tag_name("rPh")))
+                .filter_map(|node| node.text())
+                .collect();
+            let author = comment
+                .attribute("authorId")
+                .and_then(|id| id.parse::<usize>().ok())
+                .and_then(|id| authors.get(id))
+                .filter(|author| !author.is_empty())
+                .map(|author| author.to_string());
+            Some(CellComment {
+                sheet: sheet.to_owned(),
+                cell: comment.attribute("ref")?.to_owned(),
+                author,
+                text: text.trim().to_owned(),
+            })
+        })
+        .collect()
+}
+
+/// Parse `xl/threadedComments/threadedCommentN.xml`; replies follow the comment they answer.
+#[cfg(feature = "office")]
+fn parse_threaded_comments(xml: &str, sheet: &str, persons: &HashMap<String, String>) -> Vec<CellComment> {
+    let Some(doc) = xml_parser::try_parse_document(xml, None) else {
+        return Vec::new();
+    };
+    doc.descendants()
+        .filter(|node| node.has_tag_name("threadedComment"))
+        .filter_map(|comment| {
+            let text = comment
+                .children()
+                .find(|node| node.has_tag_name("text"))
+                .and_then(|node| node.text())
+                .unwrap_or_default();
+            Some(CellComment {
+                sheet: sheet.to_owned(),
+                cell: comment.attribute("ref")?.to_owned(),
+                author: comment.attribute("personId").and_then(|id| persons.get(id)).cloned(),
+                text: text.trim().to_owned(),
+            })
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -781,7 +1304,7 @@ mod tests {
     #[test]
     fn test_process_sheet_empty() {
         let range: Range<Data> = Range::empty();
-        let sheet = process_sheet("EmptySheet", &range);
+        let sheet = process_sheet("EmptySheet", &range, &Formulas::new());
 
         assert_eq!(sheet.name, "EmptySheet");
         assert_eq!(sheet.row_count, 0);
@@ -795,7 +1318,7 @@ mod tests {
         let mut range: Range<Data> = Range::new((0, 0), (0, 0));
         range.set_value((0, 0), Data::String("Single Cell".to_owned()));
 
-        let sheet = process_sheet("Sheet1", &range);
+        let sheet = process_sheet("Sheet1", &range, &Formulas::new());
 
         assert_eq!(sheet.name, "Sheet1");
         assert_eq!(sheet.row_count, 1);
@@ -814,7 +1337,7 @@ mod tests {
         range.set_value((2, 0), Data::String("Bob".to_owned()));
         range.set_value((2, 1), Data::Int(25));
 
-        let sheet = process_sheet("People", &range);
+        let sheet = process_sheet("People", &range, &Formulas::new());
 
         assert_eq!(sheet.name, "People");
         assert_eq!(sheet.row_count, 3);
@@ -828,7 +1351,7 @@ mod tests {
     #[test]
     fn test_generate_markdown_and_cells_empty() {
         let range: Range<Data> = Range::empty();
-        let (markdown, cells) = generate_markdown_and_cells("Test", &range, 100);
+        let (markdown, cells) = generate_markdown_and_cells("Test", &range, 100, &Formulas::new());
 
         assert!(markdown.contains("## Test"));
         assert!(cells.is_empty());
@@ -844,7 +1367,7 @@ mod tests {
         range.set_value((1, 1), Data::String("B".to_owned()));
         range.set_value((1, 2), Data::String("C".to_owned()));
 
-        let (markdown, cells) = generate_markdown_and_cells("Sheet1", &range, 200);
+        let (markdown, cells) = generate_markdown_and_cells("Sheet1", &range, 200, &Formulas::new());
 
         assert!(markdown.contains("## Sheet1"));
         assert!(markdown.contains("Col1"));
@@ -861,7 +1384,7 @@ mod tests {
         range.set_value((1, 0), Data::String("X".to_owned()));
         range.set_value((1, 2), Data::String("Z".to_owned()));
 
-        let (markdown, cells) = generate_markdown_and_cells("Sparse", &range, 200);
+        let (markdown, cells) = generate_markdown_and_cells("Sparse", &range, 200, &Formulas::new());
 
         assert!(markdown.contains("X"));
         assert!(markdown.contains("Z"));
@@ -908,7 +1431,7 @@ mod tests {
         range.set_value((1, 0), Data::String("A".to_owned()));
         range.set_value((1, 1), Data::String("B".to_owned()));
 
-        let (markdown, _cells) = generate_markdown_and_cells("Test", &range, 100);
+        let (markdown, _cells) = generate_markdown_and_cells("Test", &range, 100, &Formulas::new());
 
         let lines: Vec<&str> = markdown.lines().collect();
         assert!(lines[0].contains("## Test"));
@@ -926,10 +1449,300 @@ mod tests {
             }
         }
 
-        let sheet = process_sheet("Data", &range);
+        let sheet = process_sheet("Data", &range, &Formulas::new());
 
         assert_eq!(sheet.row_count, 10);
         assert_eq!(sheet.col_count, 5);
         assert_eq!(sheet.cell_count, 50);
     }
+
+    const WORKBOOK_NS: &str = r#"xmlns="http://schemas.openxmlformats.org/spreadsheetml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships""#;
+
+    fn zip_parts(parts: &[(String, String)]) -> Vec<u8> {
+        use std::io::Write;
+
+        let mut buffer = Cursor::new(Vec::new());
+        {
+            let mut zip = zip::ZipWriter::new(&mut buffer);
+            let options = zip::write::SimpleFileOptions::default();
+            for (name, content) in parts {
+                zip.start_file(name.as_str(), options).unwrap();
+                zip.write_all(content.as_bytes()).unwrap();
+            }
+            zip.finish().unwrap();
+        }
+        buffer.into_inner()
+    }
+
+    fn relationships(entries: &[(&str, &str)]) -> String {
+        let entries: String = entries
+            .iter()
+            .enumerate()
+            .map(|(i, (kind, target))| {
+                format!(
+                    r#"<Relationship Id="rId{}" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/{kind}" Target="{target}"/>"#,
+                    i + 1
+                )
+            })
+            .collect();
+        format!(
+            r#"<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">{entries}</Relationships>"#
+        )
+    }
+
+    /// Minimal XLSX package with inline-string sheets; `(name, state, rows)`.
+    fn build_xlsx(sheets: &[(&str, &str, usize)]) -> Vec<u8> {
+        let entries: String = sheets
+            .iter()
+            .enumerate()
+            .map(|(i, (name, state, _))| {
+                format!(
+                    r#"<sheet name="{name}" sheetId="{}" state="{state}" r:id="rId{}"/>"#,
+                    i + 1,
+                    i + 1
+                )
+            })
+            .collect();
+        let targets: Vec<String> = (1..=sheets.len()).map(|i| format!("worksheets/sheet{i}.xml")).collect();
+        let rels: Vec<(&str, &str)> = targets.iter().map(|target| ("worksheet", target.as_str())).collect();
+
+        let mut parts = vec![
+            (
+                "xl/workbook.xml".to_string(),
+                format!(r#"<workbook {WORKBOOK_NS}><sheets>{entries}</sheets></workbook>"#),
+            ),
+            ("xl/_rels/workbook.xml.rels".to_string(), relationships(&rels)),
+        ];
+        for (i, (_, _, rows)) in sheets.iter().enumerate() {
+            let data: String = (1..=*rows)
+                .map(|r| format!(r#"<row r="{r}"><c r="A{r}" t="inlineStr"><is><t>a{r}</t></is></c><c r="B{r}"><v>{r}</v></c></row>"#))
+                .collect();
+            parts.push((
+                format!("xl/worksheets/sheet{}.xml", i + 1),
+                format!(r#"<worksheet {WORKBOOK_NS}><sheetData>{data}</sheetData></worksheet>"#),
+            ));
+        }
+        zip_parts(&parts)
+    }
+
+    #[test]
+    fn test_sheet_selection_and_row_limit() {
+        let bytes = build_xlsx(&[
+            ("Summary", "visible", 3),
+            ("Q1", "visible", 10),
+            ("Q2", "hidden", 2),
+            ("Raw", "visible", 4),
+        ]);
+        let config = SpreadsheetConfig {
+            sheets: vec!["Summary".to_string(), "Q*".to_string()],
+            max_rows: Some(5),
+            skip_hidden: true,
+            ..Default::default()
+        };
+
+        let workbook = read_excel_bytes_with_config(&bytes, ".xlsx", &config).unwrap();
+        let names: Vec<_> = workbook.sheets.iter().map(|sheet| sheet.name.as_str()).collect();
+        assert_eq!(names, vec!["Summary", "Q1"]);
+        assert_eq!(workbook.sheets[1].row_count, 5);
+        assert_eq!(workbook.sheets[1].table_cells.as_ref().unwrap().len(), 5);
+
+        let info = &workbook.sheet_metadata;
+        assert_eq!(info.len(), 4);
+        assert!(!info[0].truncated);
+        assert_eq!(info[0].dimensions.as_deref(), Some("A1:B3"));
+        assert!(info[1].extracted && info[1].truncated);
+        assert_eq!((info[1].row_count, info[1].col_count), (Some(10), Some(2)));
+        assert_eq!(info[2].visibility, SheetVisibility::Hidden);
+        assert!(!info[2].extracted && !info[3].extracted);
+        assert_eq!(info[3].row_count, None);
+
+        let all = read_excel_bytes(&bytes, ".xlsx").unwrap();
+        assert_eq!(all.sheets.len(), 4);
+        assert!(
+            all.sheet_metadata
+                .iter()
+                .all(|sheet| sheet.extracted && !sheet.truncated)
+        );
+    }
+
+    #[test]
+    fn test_truncate_rows() {
+        let mut range: Range<Data> = Range::new((2, 0), (9, 1));
+        range.set_value((2, 0), Data::Int(1));
+        let (truncated, was_truncated) = truncate_rows(range.clone(), Some(3));
+        assert!(was_truncated);
+        assert_eq!(truncated.get_size(), (3, 2));
+        assert!(!truncate_rows(range, Some(8)).1);
+    }
+
+    #[cfg(feature = "office")]
+    #[test]
+    fn test_worksheet_charts() {
+        let chart = r#"<c:chartSpace xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart"><c:chart><c:plotArea><c:barChart><c:barDir val="col"/><c:ser>
+            <c:tx><c:strRef><c:strCache><c:ptCount val="1"/><c:pt idx="0"><c:v>Revenue</c:v></c:pt></c:strCache></c:strRef></c:tx>
+            <c:cat><c:strRef><c:strCache><c:ptCount val="2"/><c:pt idx="0"><c:v>a1</c:v></c:pt><c:pt idx="1"><c:v>a2</c:v></c:pt></c:strCache></c:strRef></c:cat>
+            <c:val><c:numRef><c:numCache><c:ptCount val="2"/><c:pt idx="0"><c:v>1</c:v></c:pt><c:pt idx="1"><c:v>2</c:v></c:pt></c:numCache></c:numRef></c:val>
+        </c:ser></c:barChart></c:plotArea></c:chart></c:chartSpace>"#;
+        let drawing = r#"<xdr:wsDr xmlns:xdr="http://schemas.openxmlformats.org/drawingml/2006/spreadsheetDrawing"><xdr:twoCellAnchor><xdr:graphicFrame><a:graphic xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main"><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/chart"><c:chart xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships" r:id="rId1"/></a:graphicData></a:graphic></xdr:graphicFrame></xdr:twoCellAnchor></xdr:wsDr>"#;
+        let data = r#"<sheetData><row r="1"><c r="A1" t="inlineStr"><is><t>a1</t></is></c><c r="B1"><v>1</v></c></row></sheetData>"#;
+        let bytes = zip_parts(&[
+            (
+                "xl/workbook.xml".to_string(),
+                format!(
+                    r#"<workbook {WORKBOOK_NS}><sheets><sheet name="Data" sheetId="1" r:id="rId1"/><sheet name="Dashboard" sheetId="2" r:id="rId2"/></sheets></workbook>"#
+                ),
+            ),
+            (
+                "xl/_rels/workbook.xml.rels".to_string(),
+                relationships(&[
+                    ("worksheet", "worksheets/sheet1.xml"),
+                    ("worksheet", "worksheets/sheet2.xml"),
+                ]),
+            ),
+            (
+                "xl/worksheets/sheet1.xml".to_string(),
+                format!(r#"<worksheet {WORKBOOK_NS}>{data}</worksheet>"#),
+            ),
+            (
+                "xl/worksheets/sheet2.xml".to_string(),
+                format!(r#"<worksheet {WORKBOOK_NS}>{data}<drawing r:id="rId1"/></worksheet>"#),
+            ),
+            (
+                "xl/worksheets/_rels/sheet2.xml.rels".to_string(),
+                relationships(&[("drawing", "../drawings/drawing1.xml")]),
+            ),
+            ("xl/drawings/drawing1.xml".to_string(), drawing.to_string()),
+            (
+                "xl/drawings/_rels/drawing1.xml.rels".to_string(),
+                relationships(&[("chart", "../charts/chart1.xml")]),
+            ),
+            ("xl/charts/chart1.xml".to_string(), chart.to_string()),
+        ]);
+
+        let workbook = read_excel_bytes(&bytes, ".xlsx").unwrap();
+        assert!(workbook.sheets[0].charts.is_empty());
+        let dashboard = &workbook.sheets[1];
+        assert_eq!(dashboard.charts.len(), 1);
+        assert_eq!(dashboard.charts[0].page_number, 2);
+        assert_eq!(dashboard.charts[0].chart_type, "column");
+        assert_eq!(dashboard.charts[0].cells[2], vec!["a2", "2"]);
+        assert!(
+            dashboard
+                .markdown
+                .contains("### Chart (column chart)\n\n| Category | Revenue |")
+        );
+
+        let config = SpreadsheetConfig {
+            sheets: vec!["Dashboard".to_string()],
+            ..Default::default()
+        };
+        let selected = read_excel_bytes_with_config(&bytes, ".xlsx", &config).unwrap();
+        assert_eq!(selected.sheets[0].charts[0].page_number, 1);
+    }
+
+    /// Workbook with a formula, a legacy note, a threaded comment and a defined name.
+    fn build_audited_xlsx() -> Vec<u8> {
+        let cell = |r: &str, value: &str| format!(r#"<c r="{r}" t="inlineStr"><is><t>{value}</t></is></c>"#);
+        let rows = format!(
+            r#"<row r="1">{}{}</row><row r="2">{}<c r="B2"><v>2</v></c></row><row r="3">{}<c r="B3"><v>3</v></c></row><row r="4">{}<c r="B4"><f>SUM(B2:B3)</f><v>5</v></c></row>"#,
+            cell("A1", "Item"),
+            cell("B1", "Amount"),
+            cell("A2", "Rent"),
+            cell("A3", "Power"),
+            cell("A4", "Total"),
+        );
+        zip_parts(&[
+            (
+                "xl/workbook.xml".to_string(),
+                format!(
+                    r#"<workbook {WORKBOOK_NS}><sheets><sheet name="Model" sheetId="1" r:id="rId1"/></sheets><definedNames><definedName name="Total">Model!$B$4</definedName></definedNames></workbook>"#
+                ),
+            ),
+            (
+                "xl/_rels/workbook.xml.rels".to_string(),
+                relationships(&[("worksheet", "worksheets/sheet1.xml"), ("person", "persons/person.xml")]),
+            ),
+            (
+                "xl/persons/person.xml".to_string(),
+                r#"<personList xmlns="http://schemas.microsoft.com/office/spreadsheetml/2018/threadedcomments"><person displayName="Dana" id="{P1}"/></personList>"#.to_string(),
+            ),
+            (
+                "xl/worksheets/sheet1.xml".to_string(),
+                format!(r#"<worksheet {WORKBOOK_NS}><sheetData>{rows}</sheetData></worksheet>"#),
+            ),
+            (
+                "xl/worksheets/_rels/sheet1.xml.rels".to_string(),
+                relationships(&[
+                    ("comments", "../comments1.xml"),
+                    ("threadedComment", "/xl/threadedComments/threadedComment1.xml"),
+                ]),
+            ),
+            (
+                "xl/comments1.xml".to_string(),
+                format!(
+                    r#"<comments {WORKBOOK_NS}><authors><author>Lee</author><author>tc={{C1}}</author></authors><commentList><comment ref="B2" authorId="0"><text><r><t>Based on</t></r><r><t xml:space="preserve"> the 2024 lease</t></r><rPh><t>lease</t></rPh></text></comment><comment ref="B4" authorId="1"><text><t>[Threaded comment] Comment: Check the total</t></text></comment></commentList></comments>"#
+                ),
+            ),
+            (
+                "xl/threadedComments/threadedComment1.xml".to_string(),
+                r#"<ThreadedComments xmlns="http://schemas.microsoft.com/office/spreadsheetml/2018/threadedcomments"><threadedComment ref="B4" personId="{P1}" id="{C1}"><text>Check the total</text></threadedComment></ThreadedComments>"#.to_string(),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_formulas_comments_and_defined_names() {
+        let bytes = build_audited_xlsx();
+
+        let plain = read_excel_bytes(&bytes, ".xlsx").unwrap();
+        assert!(plain.sheets[0].markdown.contains("| Total | 5.0 |"));
+        assert!(plain.defined_names.is_empty() && plain.comments.is_empty());
+
+        let config = SpreadsheetConfig {
+            formulas: true,
+            comments: true,
+            defined_names: true,
+            ..Default::default()
+        };
+        let workbook = read_excel_bytes_with_config(&bytes, ".xlsx", &config).unwrap();
+        let sheet = &workbook.sheets[0];
+        assert!(sheet.markdown.contains("| Total | 5.0 (=SUM(B2:B3)) |"));
+        assert_eq!(sheet.table_cells.as_ref().unwrap()[3][1], "5.0 (=SUM(B2:B3))");
+        assert_eq!(
+            workbook.defined_names,
+            vec![DefinedName {
+                name: "Total".to_string(),
+                refers_to: "Model!$B$4".to_string(),
+            }]
+        );
+
+        #[cfg(feature = "office")]
+        {
+            let comments: Vec<_> = workbook
+                .comments
+                .iter()
+                .map(|comment| (comment.cell.as_str(), comment.author.as_deref(), comment.text.as_str()))
+                .collect();
+            assert_eq!(
+                comments,
+                vec![
+                    ("B2", Some("Lee"), "Based on the 2024 lease"),
+                    ("B4", Some("Dana"), "Check the total"),
+                ]
+            );
+            assert!(sheet.markdown.ends_with(
+                "### Comments\n\n- **B2** (Lee): Based on the 2024 lease\n- **B4** (Dana): Check the total\n"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_format_cell_with_formula() {
+        assert_eq!(
+            format_cell_with_formula(&Data::Float(5.0), Some("SUM(B2:B3)")),
+            "5.0 (=SUM(B2:B3))"
+        );
+        assert_eq!(format_cell_with_formula(&Data::Empty, Some("NOW()")), "=NOW()");
+        assert_eq!(format_cell_with_formula(&Data::Int(3), None), "3");
+    }
 }
@@ -1,5 +1,6 @@
 //! HTML to Markdown conversion functionality.
 
+use super::inline_svg::inline_svg_text;
 use super::stack_management::check_wasm_size_limit;
 #[cfg(not(target_arch = "wasm32"))]
 use super::stack_management::{html_requires_large_stack, run_on_dedicated_stack};
@@ -85,14 +86,15 @@ pub fn convert_html_to_markdown(
 
     let format = output_format.unwrap_or(KreuzbergOutputFormat::Markdown);
     let options = resolve_conversion_options(options, format);
+    let html = inline_svg_text(html);
 
     #[cfg(not(target_arch = "wasm32"))]
     if html_requires_large_stack(html.len()) {
-        let html = html.to_string();
+        let html = html.into_owned();
         return run_on_dedicated_stack(move || convert_html_with_options(&html, options));
     }
 
-    convert_html_with_options(html, options)
+    convert_html_with_options(&html, options)
 }
 
 /// Convert HTML with direct metadata extraction and output format support.
@@ -128,10 +130,11 @@ pub fn convert_html_to_markdown_with_metadata(
     let format = output_format.unwrap_or(KreuzbergOutputFormat::Markdown);
     let options = resolve_conversion_options(options, format);
     let metadata_config = MetadataConfig::default();
+    let html = inline_svg_text(html);
 
     #[cfg(not(target_arch = "wasm32"))]
     if html_requires_large_stack(html.len()) {
-        let html = html.to_string();
+        let html = html.into_owned();
         return run_on_dedicated_stack(move || {
             convert_with_metadata(&html, Some(options), metadata_config, None)
                 .map_err(|e| KreuzbergError::parsing(format!("HTML metadata extraction failed: {}", e)))
@@ -149,7 +152,7 @@ pub fn convert_html_to_markdown_with_metadata(
         });
     }
 
-    let (content, extended_metadata) = convert_with_metadata(html, Some(options), metadata_config, None)
+    let (content, extended_metadata) = convert_with_metadata(&html, Some(options), metadata_config, None)
         .map_err(|e| KreuzbergError::parsing(format!("HTML metadata extraction failed: {}", e)))?;
 
     let html_metadata = HtmlMetadata::from(extended_metadata);
@@ -203,6 +206,13 @@ mod tests {
         assert!(result.contains("Data"));
     }
 
+    #[test]
+    fn test_inline_svg_text() {
+        let html = r#"<p>Before</p><svg width="100"><title>Flow</title><text x="10" y="20">Start</text><text x="10" y="40">End</text></svg><p>After</p>"#;
+        let result = convert_html_to_markdown(html, None, None).unwrap();
+        assert_eq!(result.trim(), "Before\n\nStart\n\nEnd\n\nAfter");
+    }
+
     #[test]
     fn test_preprocessing_config() {
         let html = "<nav>Navigation</nav><p>Content</p>";
@@ -0,0 +1,80 @@
+//! Text of inline `<svg>` elements.
+//!
+//! html-to-markdown turns inline SVG into a data URI image, which hides the text of
+//! diagrams and charts. Before conversion, SVG elements with text are replaced by
+//! paragraphs holding their text lines, read as for standalone SVG files.
+
+use crate::extraction::svg::parse_svg;
+use std::borrow::Cow;
+use tl::{Node, ParserOptions};
+
+/// Replace inline SVG elements that contain text with their text lines.
+///
+/// SVG elements without text, or that are not well-formed XML, are left as they are.
+pub(crate) fn inline_svg_text(html: &str) -> Cow<'_, str> {
+    if !html.contains("<svg") && !html.contains("<SVG") {
+        return Cow::Borrowed(html);
+    }
+    let Ok(dom) = tl::parse(html, ParserOptions::default()) else {
+        return Cow::Borrowed(html);
+    };
+    let parser = dom.parser();
+
+    let mut svg_ranges: Vec<(usize, usize)> = dom
+        .nodes()
+        .iter()
+        .filter_map(Node::as_tag)
+        .filter(|tag| tag.name().as_utf8_str().eq_ignore_ascii_case("svg"))
+        .map(|tag| tag.boundaries(parser))
+        .collect();
+    svg_ranges.sort_unstable();
+
+    let mut output = String::with_capacity(html.len());
+    let mut position = 0;
+    for (start, end) in svg_ranges {
+        // Nested drawings are read with the drawing containing them.
+        if start < position {
+            continue;
+        }
+        let Some(markup) = html.get(start..=end) else {
+            continue;
+        };
+        let Ok(svg) = parse_svg(markup.as_bytes()) else {
+            continue;
+        };
+        if svg.texts.is_empty() {
+            continue;
+        }
+
+        output.push_str(&html[position..start]);
+        output.push_str("<div>");
+        for text in &svg.texts {
+            output.push_str("<p>");
+            output.push_str(&html_escape::encode_text(&text.text));
+            output.push_str("</p>");
+        }
+        output.push_str("</div>");
+        position = end + 1;
+    }
+
+    if position == 0 {
+        return Cow::Borrowed(html);
+    }
+    output.push_str(&html[position..]);
+    Cow::Owned(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inline_svg_text() {
+        let html = r#"<p>Before</p><svg width="100"><text x="1" y="2">A &amp; B</text><svg><text>C</text></svg></svg><svg><rect/></svg><p>After</p>"#;
+        assert_eq!(
+            inline_svg_text(html),
+            "<p>Before</p><div><p>A &amp; B</p><p>C</p></div><svg><rect/></svg><p>After</p>"
+        );
+        assert!(matches!(inline_svg_text("<p>No drawings</p>"), Cow::Borrowed(_)));
+    }
+}
@@ -10,6 +10,8 @@
 //! - **YAML frontmatter**: Parse YAML metadata from Markdown output
 //! - **Customizable conversion**: Full access to `html-to-markdown-rs` options
 //! - **Output format selection**: Choose between Markdown and Djot formats
+//! - **Structured tables**: Parse `<table>` elements with resolved row and column spans
+//! - **Inline SVG text**: Text of inline `<svg>` drawings is kept as paragraphs
 //!
 //! # Example
 //!
@@ -35,14 +37,17 @@
 
 mod converter;
 mod image_handling;
+mod inline_svg;
 mod processor;
 mod stack_management;
+mod tables;
 mod types;
 
 // Public API re-exports
 pub use converter::convert_html_to_markdown;
 pub use converter::convert_html_to_markdown_with_metadata;
 pub use processor::process_html;
+pub use tables::extract_html_tables;
 pub use types::{
     CodeBlockStyle, HeadingStyle, HighlightStyle, ListIndentType, NewlineStyle, PreprocessingOptions,
     PreprocessingPreset, WhitespaceMode,
@@ -0,0 +1,257 @@
+//! Structured table extraction from HTML.
+//!
+//! Tables are read from the parsed DOM rather than from the converted Markdown, so
+//! `rowspan`/`colspan` are resolved into a regular grid and nested tables are
+//! returned as tables of their own. A spanning cell's text is repeated in every
+//! grid position it covers.
+
+use crate::extraction::cells_to_markdown;
+use crate::extraction::table_grid::{GridCell, MAX_COLSPAN, MAX_ROWSPAN, build_grid};
+use crate::types::Table;
+use bumpalo::Bump;
+use bumpalo::collections::String as BumpString;
+use tl::{Node, NodeHandle, Parser, ParserOptions};
+
+/// Extract every `<table>` of an HTML document, in document order.
+///
+/// Nested tables follow the table that contains them; the containing cell keeps
+/// the nested table's text. Cell content keeps bold, italic and code markup as
+/// Markdown. Tables without any text are skipped.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::extraction::html::extract_html_tables;
+///
+/// let html = r#"<table>
+///     <tr><th rowspan="2">Region</th><th colspan="2">Sales</th></tr>
+///     <tr><th>2023</th><th>2024</th></tr>
+///     <tr><td>North</td><td>10</td><td>12</td></tr>
+/// </table>"#;
+///
+/// let tables = extract_html_tables(html);
+/// assert_eq!(tables[0].cells[0], vec!["Region", "Sales", "Sales"]);
+/// assert_eq!(tables[0].cells[1], vec!["Region", "2023", "2024"]);
+/// ```
+pub fn extract_html_tables(html: &str) -> Vec<Table> {
+    let Ok(dom) = tl::parse(html, ParserOptions::default()) else {
+        return Vec::new();
+    };
+    let parser = dom.parser();
+
+    // Sort by source position so nested tables follow the table containing them.
+    let mut table_tags: Vec<_> = dom
+        .nodes()
+        .iter()
+        .filter(|node| is_tag(node, "table"))
+        .filter_map(Node::as_tag)
+        .collect();
+    table_tags.sort_by_key(|tag| tag.boundaries(parser).0);
+
+    // Cell text is rendered into scratch buffers of an arena reused for every table, so
+    // documents with many cells do not pay for an allocation per tag.
+    let mut arena = Bump::new();
+    let mut tables = Vec::new();
+    for tag in table_tags {
+        let rows = table_rows(tag.children().top().as_slice(), parser, &arena);
+        arena.reset();
+        let cells = build_grid(rows);
+        if cells.iter().flatten().all(|cell| cell.is_empty()) {
+            continue;
+        }
+
+        tables.push(Table {
+            markdown: cells_to_markdown(&cells),
+            cells,
+            page_number: tables.len() + 1,
+        });
+    }
+
+    tables
+}
+
+/// Rows of a table, looking through `thead`, `tbody` and `tfoot` but not into nested tables.
+fn table_rows(children: &[NodeHandle], parser: &Parser<'_>, arena: &Bump) -> Vec<Vec<GridCell>> {
+    let mut rows = Vec::new();
+    for node in children.iter().filter_map(|handle| handle.get(parser)) {
+        let Some(tag) = node.as_tag() else {
+            continue;
+        };
+        if is_tag(node, "tr") {
+            rows.push(row_cells(tag.children().top().as_slice(), parser, arena));
+        } else if is_tag(node, "thead") || is_tag(node, "tbody") || is_tag(node, "tfoot") {
+            rows.extend(table_rows(tag.children().top().as_slice(), parser, arena));
+        }
+    }
+    rows
+}
+
+fn row_cells(children: &[NodeHandle], parser: &Parser<'_>, arena: &Bump) -> Vec<GridCell> {
+    children
+        .iter()
+        .filter_map(|handle| handle.get(parser))
+        .filter(|node| is_tag(node, "td") || is_tag(node, "th"))
+        .filter_map(|node| {
+            let tag = node.as_tag()?;
+            let span = |name: &str, max: usize| {
+                tag.attributes()
+                    .get(name)
+                    .flatten()
+                    .and_then(|value| value.as_utf8_str().trim().parse::<usize>().ok())
+                    .map(|span| span.min(max))
+            };
+
+            let mut text = BumpString::new_in(arena);
+            render_children(tag.children().top().as_slice(), parser, arena, &mut text);
+            Some(GridCell {
+                text: collapse_whitespace(&text),
+                // rowspan="0" spans the remaining rows
+                row_span: span("rowspan", MAX_ROWSPAN).unwrap_or(1),
+                col_span: span("colspan", MAX_COLSPAN).unwrap_or(1).max(1),
+            })
+        })
+        .collect()
+}
+
+/// Render cell content as inline Markdown text.
+fn render_children<'a>(children: &[NodeHandle], parser: &Parser<'_>, arena: &'a Bump, output: &mut BumpString<'a>) {
+    for node in children.iter().filter_map(|handle| handle.get(parser)) {
+        match node {
+            Node::Raw(text) => output.push_str(&html_escape::decode_html_entities(&text.as_utf8_str())),
+            Node::Tag(tag) => {
+                let mut name = BumpString::from_str_in(&tag.name().as_utf8_str(), arena);
+                name.make_ascii_lowercase();
+                let marker = match name.as_str() {
+                    "script" | "style" | "template" => continue,
+                    "strong" | "b" => Some("**"),
+                    "em" | "i" => Some("*"),
+                    "code" => Some("`"),
+                    _ => None,
+                };
+                let children = tag.children();
+                let children = children.top().as_slice();
+
+                match marker {
+                    Some(marker) => {
+                        let mut inner = BumpString::new_in(arena);
+                        render_children(children, parser, arena, &mut inner);
+                        if !inner.trim().is_empty() {
+                            output.push_str(marker);
+                            output.push_str(inner.trim());
+                            output.push_str(marker);
+                        }
+                    }
+                    // Block boundaries (line breaks, paragraphs, nested cells) separate words.
+                    None if is_block(&name) => {
+                        output.push(' ');
+                        render_children(children, parser, arena, output);
+                        output.push(' ');
+                    }
+                    None => render_children(children, parser, arena, output),
+                }
+            }
+            Node::Comment(_) => {}
+        }
+    }
+}
+
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "br" | "hr"
+            | "p"
+            | "div"
+            | "section"
+            | "blockquote"
+            | "pre"
+            | "ul"
+            | "ol"
+            | "li"
+            | "dl"
+            | "dt"
+            | "dd"
+            | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "table"
+            | "caption"
+            | "thead"
+            | "tbody"
+            | "tfoot"
+            | "tr"
+            | "td"
+            | "th"
+    )
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    for word in text.split_whitespace() {
+        if !collapsed.is_empty() {
+            collapsed.push(' ');
+        }
+        collapsed.push_str(word);
+    }
+    collapsed
+}
+
+fn is_tag(node: &Node<'_>, name: &str) -> bool {
+    node.as_tag()
+        .is_some_and(|tag| tag.name().as_bytes().eq_ignore_ascii_case(name.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spans_are_resolved() {
+        let html = r#"<table>
+            <tr><td rowspan="2">A</td><td>B</td><td>C</td></tr>
+            <tr><td colspan="2">D</td></tr>
+            <tr><td>E</td><td rowspan="0">F</td><td>G</td></tr>
+            <tr><td>H</td><td>I</td></tr>
+        </table>"#;
+
+        let tables = extract_html_tables(html);
+        assert_eq!(tables.len(), 1);
+        assert_eq!(
+            tables[0].cells,
+            vec![
+                vec!["A", "B", "C"],
+                vec!["A", "D", "D"],
+                vec!["E", "F", "G"],
+                vec!["H", "F", "I"],
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_tables_are_separate() {
+        let html = r#"<table>
+            <thead><tr><th>Name</th><th>Details</th></tr></thead>
+            <tbody><tr><td>Widget</td><td><table><tr><td>Size</td><td>10&nbsp;cm</td></tr></table></td></tr></tbody>
+        </table>"#;
+
+        let tables = extract_html_tables(html);
+        assert_eq!(tables.len(), 2);
+        assert_eq!(
+            tables[0].cells,
+            vec![vec!["Name", "Details"], vec!["Widget", "Size 10 cm"]]
+        );
+        assert_eq!(tables[1].cells, vec![vec!["Size", "10 cm"]]);
+        assert_eq!(tables[1].page_number, 2);
+    }
+
+    #[test]
+    fn test_irregular_rows_and_empty_cells() {
+        let html = "<table><tr><td>a</td><td></td><td>c</td></tr><tr><td>d</td></tr><tr></tr></table><table></table>";
+        let tables = extract_html_tables(html);
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].cells, vec![vec!["a", "", "c"], vec!["d", "", ""]]);
+        assert!(tables[0].markdown.starts_with("| a |  | c |"));
+    }
+}
@@ -1,13 +1,25 @@
 //! Image extraction functionality.
 //!
 //! This module provides functions for extracting metadata and EXIF data from images,
-//! including support for multi-frame TIFF files.
+//! and decodes the pages of multi-page TIFF files, including CCITT Group 3 and
+//! Group 4 fax pages.
+//!
+//! Formats without a decoder in the `image` crate are decoded by optional codecs:
+//! HEIC/HEIF and AVIF through libheif (`heif` feature, requires the system library)
+//! and JPEG XL through jxl-oxide (`jxl` feature). The codecs are registered as
+//! `image` format hooks, so the OCR backends decode these formats too.
 
 use crate::error::{KreuzbergError, Result};
+use crate::extraction::ccitt::{self, Group3Coding};
 use exif::{In, Reader, Tag};
-use image::ImageReader;
+use image::imageops::FilterType;
+use image::{DynamicImage, GrayImage, ImageBuffer, ImageFormat, ImageReader};
 use std::collections::HashMap;
 use std::io::Cursor;
+use tiff::ColorType;
+use tiff::decoder::ifd::Value;
+use tiff::decoder::{Decoder, DecodingResult, Limits};
+use tiff::tags::Tag as TiffTag;
 
 /// Image metadata extracted from an image file.
 #[derive(Debug, Clone)]
@@ -25,22 +37,43 @@ pub struct ImageMetadata {
 /// Extract metadata from image bytes.
 ///
 /// Extracts dimensions, format, and EXIF data from the image.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::MissingDependency` for HEIC/HEIF, AVIF or JPEG XL images
+/// when the codec feature for the format is not enabled.
 pub fn extract_image_metadata(bytes: &[u8]) -> Result<ImageMetadata> {
+    register_image_codecs();
+
     let reader = ImageReader::new(Cursor::new(bytes))
         .with_guessed_format()
         .map_err(|e| KreuzbergError::parsing(format!("Failed to read image format: {}", e)))?;
 
-    let format = reader
-        .format()
-        .ok_or_else(|| KreuzbergError::parsing("Could not determine image format".to_string()))?;
-
-    let image = reader
-        .decode()
-        .map_err(|e| KreuzbergError::parsing(format!("Failed to decode image: {}", e)))?;
+    // Formats decoded through codec hooks have no `ImageFormat` and are named from their signature.
+    let format_str = match codec_format(bytes) {
+        Some((format, feature)) if !feature.enabled => {
+            return Err(KreuzbergError::MissingDependency(format!(
+                "{} image decoding is not enabled. Rebuild with --features {}",
+                format, feature.name
+            )));
+        }
+        Some((format, _)) => format.to_string(),
+        None => reader
+            .format()
+            .map(|format| format!("{:?}", format).to_uppercase())
+            .ok_or_else(|| KreuzbergError::parsing("Could not determine image format".to_string()))?,
+    };
 
-    let width = image.width();
-    let height = image.height();
-    let format_str = format!("{:?}", format).to_uppercase();
+    // Group 3 fax pages have no decoder in the `image` crate, so TIFF dimensions are read
+    // from the first page's directory.
+    let (width, height) = if reader.format() == Some(ImageFormat::Tiff) {
+        TiffPages::first_dimensions(bytes)?
+    } else {
+        let image = reader
+            .decode()
+            .map_err(|e| KreuzbergError::parsing(format!("Failed to decode image: {}", e)))?;
+        (image.width(), image.height())
+    };
 
     let exif_data = extract_exif_data(bytes);
 
@@ -52,6 +85,58 @@ pub fn extract_image_metadata(bytes: &[u8]) -> Result<ImageMetadata> {
     })
 }
 
+/// Cargo feature providing the decoder for an image format.
+#[derive(Debug, Clone, Copy)]
+struct CodecFeature {
+    name: &'static str,
+    enabled: bool,
+}
+
+const HEIF_CODEC: CodecFeature = CodecFeature {
+    name: "heif",
+    enabled: cfg!(feature = "heif"),
+};
+
+const JXL_CODEC: CodecFeature = CodecFeature {
+    name: "jxl",
+    enabled: cfg!(feature = "jxl"),
+};
+
+/// Format name and codec feature of images that need an optional codec.
+fn codec_format(bytes: &[u8]) -> Option<(&'static str, CodecFeature)> {
+    match infer::get(bytes)?.mime_type() {
+        "image/heif" => Some(("HEIF", HEIF_CODEC)),
+        "image/avif" => Some(("AVIF", HEIF_CODEC)),
+        "image/jxl" => Some(("JXL", JXL_CODEC)),
+        _ => None,
+    }
+}
+
+/// Decode image bytes, including formats read by the enabled optional codecs.
+///
+/// `image::load_from_memory` skips the format hooks the codecs register, so image bytes
+/// are decoded through an [`ImageReader`] with a guessed format instead.
+pub(crate) fn decode_image(bytes: &[u8]) -> image::ImageResult<image::DynamicImage> {
+    register_image_codecs();
+    ImageReader::new(Cursor::new(bytes)).with_guessed_format()?.decode()
+}
+
+/// Register the decoders of the enabled optional codecs with the `image` crate.
+///
+/// Registration happens once; later calls return immediately.
+fn register_image_codecs() {
+    #[cfg(any(feature = "heif", feature = "jxl"))]
+    {
+        static REGISTER: std::sync::Once = std::sync::Once::new();
+        REGISTER.call_once(|| {
+            #[cfg(feature = "heif")]
+            libheif_rs::integration::image::register_all_decoding_hooks();
+            #[cfg(feature = "jxl")]
+            jxl_oxide::integration::register_image_decoding_hook();
+        });
+    }
+}
+
 /// Extract EXIF data from image bytes.
 ///
 /// Returns a HashMap of EXIF tags and their values.
@@ -95,127 +180,241 @@ fn extract_exif_data(bytes: &[u8]) -> HashMap<String, String> {
     exif_map
 }
 
-/// Result of OCR extraction from an image with optional page tracking.
-#[derive(Debug, Clone)]
-pub struct ImageOcrResult {
-    /// Extracted text content
-    pub content: String,
-    /// Character byte boundaries per frame (for multi-frame TIFFs)
-    pub boundaries: Option<Vec<crate::types::PageBoundary>>,
-    /// Per-frame content information
-    pub page_contents: Option<Vec<crate::types::PageContent>>,
-}
+/// Tag number of `T4Options`, which the `tiff` crate has no name for.
+const T4_OPTIONS_TAG: u16 = 292;
 
-/// Detects the number of frames in a TIFF file.
-///
-/// Returns the count of image frames/pages in a TIFF. Single-frame TIFFs return 1.
-/// Invalid or non-TIFF data returns an error.
-///
-/// # Arguments
-/// * `bytes` - Raw TIFF file bytes
+/// Pages of a TIFF file, decoded in page order.
 ///
-/// # Returns
-/// Frame count if valid TIFF, error otherwise.
-#[cfg(feature = "ocr")]
-fn detect_tiff_frame_count(bytes: &[u8]) -> Result<usize> {
-    use tiff::decoder::Decoder;
-    let mut decoder =
-        Decoder::new(Cursor::new(bytes)).map_err(|e| KreuzbergError::parsing(format!("TIFF decode: {}", e)))?;
-
-    let mut count = 1;
-    while decoder.next_image().is_ok() {
-        count += 1;
-    }
-    Ok(count)
+/// Group 3 fax pages (compression 2 and 3) are decoded by [`crate::extraction::ccitt`],
+/// all other pages, including Group 4 fax pages, by the `tiff` crate. Reduced-resolution
+/// images such as thumbnails are skipped. Fax pages sent in normal mode, with half the
+/// vertical resolution, are stretched to square pixels so text keeps its proportions for OCR.
+pub(crate) struct TiffPages<'a> {
+    bytes: &'a [u8],
+    decoder: Decoder<Cursor<&'a [u8]>>,
+    started: bool,
 }
 
-/// Extract text from image bytes using OCR with optional page tracking for multi-frame TIFFs.
-///
-/// This function:
-/// - Detects if the image is a multi-frame TIFF
-/// - For multi-frame TIFFs with PageConfig enabled, iterates frames and tracks boundaries
-/// - For single-frame images or when page tracking is disabled, runs OCR on the whole image
-/// - Returns (content, boundaries, page_contents) tuple
-///
-/// # Arguments
-/// * `bytes` - Image file bytes
-/// * `mime_type` - MIME type (e.g., "image/tiff")
-/// * `ocr_result` - OCR backend result containing the text
-/// * `page_config` - Optional page configuration for boundary tracking
-///
-/// # Returns
-/// ImageOcrResult with content and optional boundaries for pagination
-#[cfg(feature = "ocr")]
-pub fn extract_text_from_image_with_ocr(
-    bytes: &[u8],
-    mime_type: &str,
-    ocr_result: String,
-    page_config: Option<&crate::core::config::PageConfig>,
-) -> Result<ImageOcrResult> {
-    let is_tiff = mime_type.to_lowercase().contains("tiff");
-    let should_track_pages = page_config.is_some() && is_tiff;
-
-    if !should_track_pages {
-        return Ok(ImageOcrResult {
-            content: ocr_result,
-            boundaries: None,
-            page_contents: None,
-        });
+impl<'a> TiffPages<'a> {
+    pub(crate) fn new(bytes: &'a [u8]) -> Result<Self> {
+        Ok(Self {
+            bytes,
+            decoder: Decoder::new(Cursor::new(bytes)).map_err(tiff_error)?,
+            started: false,
+        })
     }
 
-    let frame_count = detect_tiff_frame_count(bytes)?;
+    /// Number of pages, without decoding them.
+    pub(crate) fn count(bytes: &[u8]) -> Result<usize> {
+        let mut pages = TiffPages::new(bytes)?;
+        let mut count = 0;
+        while let Some(page) = pages.advance() {
+            page?;
+            count += 1;
+        }
+        Ok(count)
+    }
 
-    if frame_count <= 1 {
-        return Ok(ImageOcrResult {
-            content: ocr_result,
-            boundaries: None,
-            page_contents: None,
-        });
+    /// Dimensions of the first image in pixels, without decoding it.
+    fn first_dimensions(bytes: &[u8]) -> Result<(u32, u32)> {
+        TiffPages::new(bytes)?.decoder.dimensions().map_err(tiff_error)
     }
 
-    let content_len = ocr_result.len();
-    let content_per_frame = if frame_count > 0 {
-        content_len / frame_count
-    } else {
-        content_len
-    };
+    /// Move the decoder to the next full-resolution image.
+    fn advance(&mut self) -> Option<Result<()>> {
+        loop {
+            if self.started {
+                if !self.decoder.more_images() {
+                    return None;
+                }
+                if let Err(e) = self.decoder.next_image() {
+                    return Some(Err(tiff_error(e)));
+                }
+            }
+            self.started = true;
+            match self.tag::<u32>(TiffTag::NewSubfileType) {
+                Ok(Some(subfile_type)) if subfile_type & 1 != 0 => continue,
+                Ok(_) => return Some(Ok(())),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+
+    /// Move past the next page without decoding it.
+    pub(crate) fn skip_page(&mut self) -> Option<Result<()>> {
+        self.advance()
+    }
+
+    fn read_page(&mut self) -> Result<DynamicImage> {
+        let compression = self.tag::<u16>(TiffTag::Compression)?.unwrap_or(1);
+        let page = match compression {
+            2 | 3 => self.read_group3_page(compression)?,
+            _ => self.read_decoded_page()?,
+        };
+
+        let row_repeat = match (
+            compression,
+            self.resolution(TiffTag::XResolution),
+            self.resolution(TiffTag::YResolution),
+        ) {
+            (2..=4, Some(x), Some(y)) if y > 0.0 => (x / y).round().clamp(1.0, 4.0) as u32,
+            _ => 1,
+        };
+        if row_repeat > 1 {
+            return Ok(page.resize_exact(page.width(), page.height() * row_repeat, FilterType::Nearest));
+        }
+        Ok(page)
+    }
 
-    let mut boundaries = Vec::new();
-    let mut page_contents = Vec::new();
-    let mut byte_offset = 0;
+    fn read_group3_page(&mut self, compression: u16) -> Result<DynamicImage> {
+        let (width, height) = self.decoder.dimensions().map_err(tiff_error)?;
+        if u64::from(width) * u64::from(height) > Limits::default().decoding_buffer_size as u64 {
+            return Err(KreuzbergError::parsing(format!(
+                "TIFF fax page of {}x{} pixels exceeds the decoding limit",
+                width, height
+            )));
+        }
 
-    for frame_num in 1..=frame_count {
-        let frame_end = if frame_num == frame_count {
-            content_len
+        let coding = if compression == 2 {
+            Group3Coding::ModifiedHuffman
         } else {
-            let raw_end = (frame_num * content_per_frame).min(content_len);
-            (raw_end..=content_len)
-                .find(|&i| ocr_result.is_char_boundary(i))
-                .unwrap_or(content_len)
+            let options = self.tag::<u32>(TiffTag::Unknown(T4_OPTIONS_TAG))?.unwrap_or(0);
+            Group3Coding::T4 {
+                two_dimensional: options & 1 != 0,
+            }
         };
+        let lsb_first = self.tag::<u16>(TiffTag::FillOrder)? == Some(2);
+        let black_is_zero = self.tag::<u16>(TiffTag::PhotometricInterpretation)? == Some(1);
+        let rows_per_strip = self
+            .tag::<u32>(TiffTag::RowsPerStrip)?
+            .unwrap_or(height)
+            .clamp(1, height.max(1));
+        let offsets = self
+            .decoder
+            .get_tag_u64_vec(TiffTag::StripOffsets)
+            .map_err(tiff_error)?;
+        let byte_counts = self
+            .decoder
+            .get_tag_u64_vec(TiffTag::StripByteCounts)
+            .map_err(tiff_error)?;
+
+        let page_size = width as usize * height as usize;
+        let mut pixels = Vec::with_capacity(page_size);
+        for (strip, (&offset, &byte_count)) in offsets.iter().zip(&byte_counts).enumerate() {
+            let first_row = strip as u64 * u64::from(rows_per_strip);
+            if first_row >= u64::from(height) {
+                break;
+            }
+            let rows = (u64::from(height) - first_row).min(u64::from(rows_per_strip)) as u32;
+            let data = usize::try_from(offset)
+                .ok()
+                .zip(usize::try_from(byte_count).ok())
+                .and_then(|(start, len)| self.bytes.get(start..start.checked_add(len)?))
+                .ok_or_else(|| KreuzbergError::parsing("TIFF strip lies outside the file".to_string()))?;
+            pixels.extend(ccitt::decode_group3_strip(data, width, rows, coding, lsb_first));
+        }
+        pixels.resize(page_size, 255);
+        if black_is_zero {
+            pixels.iter_mut().for_each(|pixel| *pixel = !*pixel);
+        }
 
-        boundaries.push(crate::types::PageBoundary {
-            byte_start: byte_offset,
-            byte_end: frame_end,
-            page_number: frame_num,
-        });
+        page_image(GrayImage::from_raw(width, height, pixels).map(DynamicImage::ImageLuma8))
+    }
 
-        page_contents.push(crate::types::PageContent {
-            page_number: frame_num,
-            content: ocr_result[byte_offset..frame_end].to_string(),
-            tables: vec![],
-            images: vec![],
-            hierarchy: None,
-        });
+    fn read_decoded_page(&mut self) -> Result<DynamicImage> {
+        let (width, height) = self.decoder.dimensions().map_err(tiff_error)?;
+        let color_type = self.decoder.colortype().map_err(tiff_error)?;
+        let data = self.decoder.read_image().map_err(tiff_error)?;
+
+        let page = match (color_type, data) {
+            // Bilevel rows are packed eight pixels to a byte, with white as 1 after the
+            // decoder has applied the photometric interpretation.
+            (ColorType::Gray(1), DecodingResult::U8(data)) => {
+                let row_bytes = (width as usize).div_ceil(8);
+                let pixels = data
+                    .chunks(row_bytes.max(1))
+                    .take(height as usize)
+                    .flat_map(|row| {
+                        (0..width as usize).map(move |x| {
+                            if row.get(x / 8).is_some_and(|byte| byte >> (7 - x % 8) & 1 == 1) {
+                                255
+                            } else {
+                                0
+                            }
+                        })
+                    })
+                    .collect();
+                GrayImage::from_raw(width, height, pixels).map(DynamicImage::ImageLuma8)
+            }
+            (ColorType::Gray(8), DecodingResult::U8(data)) => {
+                GrayImage::from_raw(width, height, data).map(DynamicImage::ImageLuma8)
+            }
+            (ColorType::Gray(16), DecodingResult::U16(data)) => {
+                ImageBuffer::from_raw(width, height, data).map(DynamicImage::ImageLuma16)
+            }
+            (ColorType::GrayA(8), DecodingResult::U8(data)) => {
+                ImageBuffer::from_raw(width, height, data).map(DynamicImage::ImageLumaA8)
+            }
+            (ColorType::RGB(8), DecodingResult::U8(data)) => {
+                ImageBuffer::from_raw(width, height, data).map(DynamicImage::ImageRgb8)
+            }
+            (ColorType::RGB(16), DecodingResult::U16(data)) => {
+                ImageBuffer::from_raw(width, height, data).map(DynamicImage::ImageRgb16)
+            }
+            (ColorType::RGBA(8), DecodingResult::U8(data)) => {
+                ImageBuffer::from_raw(width, height, data).map(DynamicImage::ImageRgba8)
+            }
+            (ColorType::RGBA(16), DecodingResult::U16(data)) => {
+                ImageBuffer::from_raw(width, height, data).map(DynamicImage::ImageRgba16)
+            }
+            (ColorType::CMYK(8), DecodingResult::U8(data)) => {
+                let pixels = data
+                    .chunks_exact(4)
+                    .flat_map(|cmyk| {
+                        let black = 255 - u16::from(cmyk[3]);
+                        [cmyk[0], cmyk[1], cmyk[2]].map(|ink| ((255 - u16::from(ink)) * black / 255) as u8)
+                    })
+                    .collect();
+                ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgb8)
+            }
+            (color_type, _) => {
+                return Err(KreuzbergError::parsing(format!(
+                    "Unsupported TIFF page color type: {:?}",
+                    color_type
+                )));
+            }
+        };
+        page_image(page)
+    }
 
-        byte_offset = frame_end;
+    fn tag<T: TryFrom<u64>>(&mut self, tag: TiffTag) -> Result<Option<T>> {
+        self.decoder.find_tag_unsigned(tag).map_err(tiff_error)
     }
 
-    Ok(ImageOcrResult {
-        content: ocr_result,
-        boundaries: Some(boundaries),
-        page_contents: Some(page_contents),
-    })
+    fn resolution(&mut self, tag: TiffTag) -> Option<f64> {
+        match self.decoder.find_tag(tag).ok()?? {
+            Value::Rational(numerator, denominator) if denominator != 0 => {
+                Some(f64::from(numerator) / f64::from(denominator))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Iterator for TiffPages<'_> {
+    type Item = Result<DynamicImage>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.advance()?.and_then(|()| self.read_page()))
+    }
+}
+
+fn page_image(page: Option<DynamicImage>) -> Result<DynamicImage> {
+    page.ok_or_else(|| KreuzbergError::parsing("TIFF page data does not match its dimensions".to_string()))
+}
+
+fn tiff_error(error: tiff::TiffError) -> KreuzbergError {
+    KreuzbergError::parsing(format!("TIFF decode: {}", error))
 }
 
 #[cfg(test)]
@@ -238,6 +437,14 @@ mod tests {
         bytes
     }
 
+    #[cfg(not(feature = "heif"))]
+    #[test]
+    fn test_heif_image_without_codec_reports_missing_feature() {
+        let heic = b"\x00\x00\x00\x18ftypheic\x00\x00\x00\x00mif1heic";
+        let err = extract_image_metadata(heic).unwrap_err();
+        assert!(matches!(err, KreuzbergError::MissingDependency(ref message) if message.contains("--features heif")));
+    }
+
     #[test]
     fn test_extract_png_image_returns_correct_metadata() {
         let bytes = create_test_image(100, 80, ImageFormat::Png);
@@ -298,6 +505,49 @@ mod tests {
         assert_eq!(metadata.format, "TIFF");
     }
 
+    #[test]
+    fn test_tiff_pages_decode_fax_compressions() {
+        // Written by libtiff: Group 3 1D, Group 3 2D in normal resolution with fill bits and
+        // reversed fill order, Modified Huffman, Group 4 (decoded by the `tiff` crate) and
+        // Group 3 1D with fill bits.
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../test_documents/images/fax_multipage.tif");
+        let bytes = std::fs::read(path).unwrap();
+
+        assert_eq!(TiffPages::count(&bytes).unwrap(), 5);
+        let pages: Vec<DynamicImage> = TiffPages::new(&bytes).unwrap().map(|page| page.unwrap()).collect();
+        let dimensions: Vec<_> = pages.iter().map(|page| (page.width(), page.height())).collect();
+        assert_eq!(
+            dimensions,
+            vec![(1728, 120), (1728, 240), (1728, 120), (1728, 120), (1728, 120)]
+        );
+
+        // All pages hold the same drawing; the normal-resolution page has every row twice.
+        let reference = pages[3].to_luma8();
+        assert!(reference.pixels().any(|pixel| pixel.0[0] == 0));
+        for page in [&pages[0], &pages[2], &pages[4]] {
+            assert_eq!(page.to_luma8(), reference);
+        }
+        let stretched = pages[1].to_luma8();
+        assert!(
+            reference
+                .enumerate_pixels()
+                .all(|(x, y, pixel)| stretched.get_pixel(x, y * 2) == pixel)
+        );
+
+        let metadata = extract_image_metadata(&bytes).unwrap();
+        assert_eq!((metadata.width, metadata.height), (1728, 120));
+    }
+
+    #[test]
+    fn test_tiff_pages_decode_color_pages() {
+        let bytes = create_test_image(40, 30, ImageFormat::Tiff);
+        let pages: Vec<DynamicImage> = TiffPages::new(&bytes).unwrap().map(|page| page.unwrap()).collect();
+
+        assert_eq!(pages.len(), 1);
+        assert_eq!(pages[0].to_rgb8(), image::load_from_memory(&bytes).unwrap().to_rgb8());
+    }
+
     #[test]
     fn test_extract_gif_image_returns_correct_metadata() {
         let bytes = create_test_image(64, 64, ImageFormat::Gif);
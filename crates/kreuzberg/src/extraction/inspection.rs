@@ -0,0 +1,568 @@
+//! Pre-extraction inspection of inputs for active content.
+//!
+//! With [`ExtractionConfig::security`](crate::core::config::ExtractionConfig) set, inputs
+//! are inspected before they are extracted for content that can run code when the
+//! document is opened:
+//!
+//! - macros in Office documents (`vbaProject.bin` parts in OOXML, `Basic/` and `Scripts/`
+//!   libraries in OpenDocument, VBA storages in legacy OLE files)
+//! - JavaScript and launch actions in PDFs
+//! - executable and script members in archives, by file extension
+//!
+//! Findings are reported in `metadata.additional["security_findings"]`; inputs with
+//! findings blocked by [`SecurityConfig`] are rejected with a security error. With
+//! [`SecurityConfig::extract_scripts`], the source code of VBA and Basic macros and of
+//! PDF JavaScript is collected into [`ExtractionResult::scripts`].
+
+use crate::core::config::SecurityConfig;
+#[cfg(any(feature = "office", feature = "pdf"))]
+use crate::types::ScriptLanguage;
+use crate::types::{ExtractedScript, ExtractionResult, SecurityFinding, SecurityFindingKind};
+use crate::{KreuzbergError, Result};
+use memchr::memmem;
+use std::borrow::Cow;
+
+/// Signature of OLE compound files (legacy `.doc`, `.xls`, `.ppt`).
+const OLE_MAGIC: &[u8] = b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1";
+
+/// Archive member extensions reported as executables.
+#[cfg(feature = "archives")]
+const EXECUTABLE_EXTENSIONS: &[&str] = &[
+    "exe", "dll", "scr", "com", "pif", "cpl", "msi", "bat", "cmd", "ps1", "vbs", "vbe", "jse", "wsf", "hta", "jar",
+    "lnk", "app", "apk", "sh",
+];
+
+/// Findings and scripts of an inspected input.
+#[derive(Debug, Default)]
+pub(crate) struct Inspection {
+    pub(crate) findings: Vec<SecurityFinding>,
+    /// Scripts, when script extraction is enabled
+    pub(crate) scripts: Option<Vec<ExtractedScript>>,
+}
+
+impl Inspection {
+    /// Inspect `content` of type `mime_type` with the built-in heuristics, extracting its
+    /// scripts when `config.extract_scripts` is set.
+    pub(crate) fn run(config: &SecurityConfig, content: &[u8], mime_type: &str) -> Self {
+        Self {
+            findings: inspect_input(content, mime_type),
+            scripts: config.extract_scripts.then(|| extract_scripts(content, mime_type)),
+        }
+    }
+
+    /// Reject the input when `config` blocks any of the findings.
+    pub(crate) fn enforce(&self, config: &SecurityConfig) -> Result<()> {
+        match self.findings.iter().find(|finding| config.blocks(finding.kind)) {
+            Some(finding) => Err(KreuzbergError::security(format!(
+                "Input rejected by security policy: {}",
+                finding.message
+            ))),
+            None => Ok(()),
+        }
+    }
+
+    /// Add the findings to `metadata.additional["security_findings"]` and the scripts to
+    /// `result.scripts`, after those already recorded.
+    pub(crate) fn apply(self, result: &mut ExtractionResult) {
+        if !self.findings.is_empty() {
+            let mut findings: Vec<SecurityFinding> = result
+                .metadata
+                .additional
+                .get("security_findings")
+                .and_then(|recorded| serde_json::from_value(recorded.clone()).ok())
+                .unwrap_or_default();
+            findings.extend(self.findings);
+            result
+                .metadata
+                .additional
+                .insert(Cow::Borrowed("security_findings"), serde_json::json!(findings));
+        }
+        if let Some(scripts) = self.scripts {
+            result.scripts.get_or_insert_with(Vec::new).extend(scripts);
+        }
+    }
+}
+
+/// Inspect `content` of type `mime_type` with the built-in heuristics.
+fn inspect_input(content: &[u8], mime_type: &str) -> Vec<SecurityFinding> {
+    match mime_type {
+        "application/pdf" => pdf_findings(content),
+        #[cfg(feature = "archives")]
+        "application/zip" | "application/x-zip-compressed" => {
+            archive_findings(crate::extraction::archive::extract_zip_metadata(content))
+        }
+        #[cfg(feature = "archives")]
+        "application/x-tar" | "application/tar" | "application/x-gtar" | "application/x-ustar" => {
+            archive_findings(crate::extraction::archive::extract_tar_metadata(content))
+        }
+        #[cfg(feature = "archives")]
+        "application/x-7z-compressed" => archive_findings(crate::extraction::archive::extract_7z_metadata(content)),
+        _ if is_office_mime_type(mime_type) => office_findings(content),
+        _ => Vec::new(),
+    }
+}
+
+/// Source code of the macros and scripts in `content` of type `mime_type`.
+///
+/// Scripts that cannot be read are left out.
+#[cfg_attr(not(any(feature = "office", feature = "pdf")), allow(unused_variables))]
+fn extract_scripts(content: &[u8], mime_type: &str) -> Vec<ExtractedScript> {
+    match mime_type {
+        #[cfg(feature = "pdf")]
+        "application/pdf" => pdf_scripts(content),
+        #[cfg(feature = "office")]
+        _ if is_office_mime_type(mime_type) => office_scripts(content),
+        _ => Vec::new(),
+    }
+}
+
+fn is_office_mime_type(mime_type: &str) -> bool {
+    mime_type == "application/msword"
+        || mime_type.starts_with("application/vnd.openxmlformats-officedocument.")
+        || mime_type.starts_with("application/vnd.ms-word")
+        || mime_type.starts_with("application/vnd.ms-excel")
+        || mime_type.starts_with("application/vnd.ms-powerpoint")
+        || mime_type.starts_with("application/vnd.oasis.opendocument.")
+}
+
+fn office_findings(content: &[u8]) -> Vec<SecurityFinding> {
+    if content.starts_with(OLE_MAGIC) {
+        // OLE directory entry names are UTF-16LE; VBA projects live in a `_VBA_PROJECT` stream.
+        let name: Vec<u8> = "_VBA_PROJECT".encode_utf16().flat_map(u16::to_le_bytes).collect();
+        return if memmem::find(content, &name).is_some() {
+            vec![finding(
+                SecurityFindingKind::Macros,
+                None,
+                "Document contains a VBA project".to_string(),
+            )]
+        } else {
+            Vec::new()
+        };
+    }
+
+    #[cfg(any(feature = "office", feature = "archives"))]
+    if let Ok(archive) = zip::ZipArchive::new(std::io::Cursor::new(content)) {
+        return archive
+            .file_names()
+            .filter(|name| is_macro_part(name))
+            .map(|name| {
+                finding(
+                    SecurityFindingKind::Macros,
+                    Some(name.to_string()),
+                    format!("Document contains macros ({name})"),
+                )
+            })
+            .collect();
+    }
+
+    Vec::new()
+}
+
+/// Whether an OOXML or OpenDocument package part holds macros.
+#[cfg(any(feature = "office", feature = "archives"))]
+fn is_macro_part(name: &str) -> bool {
+    let lower = name.to_ascii_lowercase();
+    if lower.ends_with("vbaproject.bin") {
+        return true;
+    }
+    // OpenDocument keeps Basic libraries under `Basic/<library>/<module>.xml`, next to
+    // the `script-lc.xml` and `script-lb.xml` indexes, and other script languages under `Scripts/`.
+    (name.starts_with("Basic/")
+        && lower.ends_with(".xml")
+        && !lower.ends_with("/script-lc.xml")
+        && !lower.ends_with("/script-lb.xml")
+        && name.matches('/').count() == 2)
+        || (name.starts_with("Scripts/") && !name.ends_with('/'))
+}
+
+#[cfg(feature = "office")]
+fn office_scripts(content: &[u8]) -> Vec<ExtractedScript> {
+    use std::io::Read;
+
+    if content.starts_with(OLE_MAGIC) {
+        return vba_scripts(content, None);
+    }
+
+    let Ok(mut archive) = zip::ZipArchive::new(std::io::Cursor::new(content)) else {
+        return Vec::new();
+    };
+    let parts: Vec<String> = archive
+        .file_names()
+        .filter(|name| is_macro_part(name))
+        .map(str::to_string)
+        .collect();
+
+    let mut scripts = Vec::new();
+    for part in parts {
+        let mut data = Vec::new();
+        let Ok(mut file) = archive.by_name(&part) else {
+            continue;
+        };
+        if file.read_to_end(&mut data).is_err() {
+            continue;
+        }
+        if part.to_ascii_lowercase().ends_with("vbaproject.bin") {
+            scripts.extend(vba_scripts(&data, Some(&part)));
+        } else if let Some(script) = basic_script(&data, &part) {
+            scripts.push(script);
+        }
+    }
+    scripts
+}
+
+/// VBA modules of the OLE file `ole`, read from the package part `location`.
+#[cfg(feature = "office")]
+fn vba_scripts(ole: &[u8], location: Option<&str>) -> Vec<ExtractedScript> {
+    match crate::extraction::vba::vba_modules(ole) {
+        Ok(modules) => modules
+            .into_iter()
+            .map(|(name, source)| ExtractedScript {
+                language: ScriptLanguage::Vba,
+                name,
+                location: location.map(str::to_string),
+                source,
+            })
+            .collect(),
+        Err(e) => {
+            tracing::debug!("Failed to read VBA project: {}", e);
+            Vec::new()
+        }
+    }
+}
+
+/// Basic module stored in the OpenDocument part `part` as a `script:module` element.
+#[cfg(feature = "office")]
+fn basic_script(data: &[u8], part: &str) -> Option<ExtractedScript> {
+    let xml = std::str::from_utf8(data).ok()?;
+    let document = crate::extraction::xml_parser::try_parse_document(xml, None)?;
+    let module = document.root_element();
+    if module.tag_name().name() != "module" {
+        return None;
+    }
+    let name = module
+        .attributes()
+        .find(|attribute| attribute.name() == "name")
+        .map(|attribute| attribute.value().to_string())
+        .unwrap_or_else(|| {
+            part.rsplit('/')
+                .next()
+                .unwrap_or(part)
+                .trim_end_matches(".xml")
+                .to_string()
+        });
+    Some(ExtractedScript {
+        language: ScriptLanguage::Basic,
+        name,
+        location: Some(part.to_string()),
+        source: module.text().unwrap_or_default().to_string(),
+    })
+}
+
+fn pdf_findings(content: &[u8]) -> Vec<SecurityFinding> {
+    #[cfg(feature = "pdf")]
+    if let Some(findings) = pdf_object_findings(content) {
+        return findings;
+    }
+    pdf_token_findings(content)
+}
+
+/// Findings from the parsed objects of a PDF, including objects in object streams.
+///
+/// Returns `None` when the document cannot be parsed.
+#[cfg(feature = "pdf")]
+fn pdf_object_findings(content: &[u8]) -> Option<Vec<SecurityFinding>> {
+    use lopdf::Object;
+
+    let document = lopdf::Document::load_mem(content).ok()?;
+    let mut findings = Vec::new();
+    for (&(number, _), object) in &document.objects {
+        let dictionary = match object {
+            Object::Dictionary(dictionary) => dictionary,
+            Object::Stream(stream) => &stream.dict,
+            _ => continue,
+        };
+        let location = Some(format!("object {number}"));
+        let action = dictionary.get(b"S").and_then(Object::as_name).ok();
+        if action == Some(b"JavaScript".as_slice()) || dictionary.has(b"JS") {
+            findings.push(finding(
+                SecurityFindingKind::JavaScript,
+                location,
+                format!("PDF contains JavaScript (object {number})"),
+            ));
+        } else if action == Some(b"Launch".as_slice()) {
+            findings.push(finding(
+                SecurityFindingKind::LaunchAction,
+                location,
+                format!("PDF contains a launch action (object {number})"),
+            ));
+        }
+    }
+    Some(findings)
+}
+
+/// JavaScript of the `JS` entries of a PDF's actions.
+#[cfg(feature = "pdf")]
+fn pdf_scripts(content: &[u8]) -> Vec<ExtractedScript> {
+    use lopdf::Object;
+
+    let Ok(document) = lopdf::Document::load_mem(content) else {
+        return Vec::new();
+    };
+    let mut scripts = Vec::new();
+    for (&(number, _), object) in &document.objects {
+        let dictionary = match object {
+            Object::Dictionary(dictionary) => dictionary,
+            Object::Stream(stream) => &stream.dict,
+            _ => continue,
+        };
+        let Ok(script) = dictionary.get(b"JS") else {
+            continue;
+        };
+        let script = match script {
+            Object::Reference(id) => document.get_object(*id).unwrap_or(script),
+            _ => script,
+        };
+        let source = match script {
+            Object::Stream(stream) if !stream.dict.has(b"Filter") => {
+                Some(String::from_utf8_lossy(&stream.content).into_owned())
+            }
+            Object::Stream(stream) => stream
+                .decompressed_content()
+                .map(|content| String::from_utf8_lossy(&content).into_owned())
+                .ok(),
+            _ => lopdf::decode_text_string(script).ok(),
+        };
+        if let Some(source) = source {
+            scripts.push(ExtractedScript {
+                language: ScriptLanguage::JavaScript,
+                name: format!("object {number}"),
+                location: None,
+                source,
+            });
+        }
+    }
+    scripts
+}
+
+/// Findings from scanning the raw bytes of a PDF for action names.
+fn pdf_token_findings(content: &[u8]) -> Vec<SecurityFinding> {
+    let mut findings = Vec::new();
+    if contains_pdf_name(content, b"/JavaScript") || contains_pdf_name(content, b"/JS") {
+        findings.push(finding(
+            SecurityFindingKind::JavaScript,
+            None,
+            "PDF contains JavaScript".to_string(),
+        ));
+    }
+    if contains_pdf_name(content, b"/Launch") {
+        findings.push(finding(
+            SecurityFindingKind::LaunchAction,
+            None,
+            "PDF contains a launch action".to_string(),
+        ));
+    }
+    findings
+}
+
+/// Whether `content` contains the PDF name `name` as a whole token.
+fn contains_pdf_name(content: &[u8], name: &[u8]) -> bool {
+    memmem::find_iter(content, name).any(|start| {
+        content
+            .get(start + name.len())
+            .is_none_or(|&next| next.is_ascii_whitespace() || b"\0()<>[]{}/%".contains(&next))
+    })
+}
+
+#[cfg(feature = "archives")]
+fn archive_findings(metadata: Result<crate::extraction::archive::ArchiveMetadata>) -> Vec<SecurityFinding> {
+    let Ok(metadata) = metadata else {
+        return Vec::new();
+    };
+    metadata
+        .file_list
+        .into_iter()
+        .filter(|entry| !entry.is_dir)
+        .map(|entry| entry.path)
+        .filter(|path| {
+            path.rsplit_once('.')
+                .is_some_and(|(_, extension)| EXECUTABLE_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str()))
+        })
+        .map(|path| {
+            let message = format!("Archive contains an executable ({path})");
+            finding(SecurityFindingKind::Executable, Some(path), message)
+        })
+        .collect()
+}
+
+fn finding(kind: SecurityFindingKind, location: Option<String>, message: String) -> SecurityFinding {
+    SecurityFinding {
+        kind,
+        location,
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(any(feature = "office", feature = "archives"))]
+    fn zip_with_contents(parts: &[(&str, &[u8])]) -> Vec<u8> {
+        use std::io::Write;
+
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (name, content) in parts {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[cfg(any(feature = "office", feature = "archives"))]
+    fn zip_with(names: &[&str]) -> Vec<u8> {
+        let parts: Vec<(&str, &[u8])> = names.iter().map(|name| (*name, b"content".as_slice())).collect();
+        zip_with_contents(&parts)
+    }
+
+    #[cfg(any(feature = "office", feature = "archives"))]
+    #[test]
+    fn test_office_macros() {
+        let docm = zip_with(&["[Content_Types].xml", "word/document.xml", "word/vbaProject.bin"]);
+        let findings = inspect_input(&docm, "application/vnd.ms-word.document.macroEnabled.12");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, SecurityFindingKind::Macros);
+        assert_eq!(findings[0].location.as_deref(), Some("word/vbaProject.bin"));
+
+        let odt = zip_with(&[
+            "content.xml",
+            "Basic/script-lc.xml",
+            "Basic/Standard/script-lb.xml",
+            "Basic/Standard/Module1.xml",
+        ]);
+        let findings = inspect_input(&odt, "application/vnd.oasis.opendocument.text");
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].location.as_deref(), Some("Basic/Standard/Module1.xml"));
+
+        let docx = zip_with(&["[Content_Types].xml", "word/document.xml"]);
+        assert!(
+            inspect_input(
+                &docx,
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document"
+            )
+            .is_empty()
+        );
+    }
+
+    #[cfg(feature = "office")]
+    #[test]
+    fn test_office_scripts() {
+        let source = "Sub AutoOpen()\r\n    MsgBox \"hi\"\r\nEnd Sub\r\n";
+        let project = crate::extraction::vba::tests::vba_project("Module1", source);
+        let docm = zip_with_contents(&[
+            ("word/document.xml", b"<w:document/>"),
+            ("word/vbaProject.bin", &project),
+        ]);
+        let scripts = extract_scripts(&docm, "application/vnd.ms-word.document.macroEnabled.12");
+        assert_eq!(
+            scripts,
+            vec![ExtractedScript {
+                language: ScriptLanguage::Vba,
+                name: "Module1".to_string(),
+                location: Some("word/vbaProject.bin".to_string()),
+                source: source.to_string(),
+            }]
+        );
+
+        let module = br#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE script:module PUBLIC "-//OpenOffice.org//DTD OfficeDocument 1.0//EN" "module.dtd">
+<script:module xmlns:script="http://openoffice.org/2000/script" script:name="Module1" script:language="StarBasic">Sub Main
+  If 1 &lt; 2 Then Print &quot;hi&quot;
+End Sub
+</script:module>"#;
+        let odt = zip_with_contents(&[
+            ("content.xml", b"<office:document-content/>"),
+            ("Basic/Standard/Module1.xml", module),
+        ]);
+        let scripts = extract_scripts(&odt, "application/vnd.oasis.opendocument.text");
+        assert_eq!(scripts.len(), 1);
+        assert_eq!(scripts[0].language, ScriptLanguage::Basic);
+        assert_eq!(scripts[0].name, "Module1");
+        assert_eq!(scripts[0].source, "Sub Main\n  If 1 < 2 Then Print \"hi\"\nEnd Sub\n");
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn test_pdf_scripts() {
+        use lopdf::{Document, Object, Stream, dictionary};
+
+        let mut document = Document::with_version("1.5");
+        let stream = document.add_object(Stream::new(dictionary! {}, b"app.alert('stream');".to_vec()));
+        let open_action = document.add_object(dictionary! { "S" => "JavaScript", "JS" => stream });
+        let named = document.add_object(dictionary! {
+            "S" => "JavaScript",
+            "JS" => Object::string_literal("app.alert('string');"),
+        });
+        let catalog = document.add_object(dictionary! {
+            "Type" => "Catalog",
+            "OpenAction" => open_action,
+            "AA" => dictionary! { "WC" => named },
+        });
+        document.trailer.set("Root", catalog);
+        let mut bytes = Vec::new();
+        document.save_to(&mut bytes).unwrap();
+
+        let mut sources: Vec<_> = extract_scripts(&bytes, "application/pdf")
+            .into_iter()
+            .map(|script| script.source)
+            .collect();
+        sources.sort();
+        assert_eq!(sources, vec!["app.alert('stream');", "app.alert('string');"]);
+    }
+
+    #[test]
+    fn test_pdf_active_content() {
+        let pdf =
+            b"%PDF-1.4\n1 0 obj\n<< /Type /Catalog /OpenAction << /S /JavaScript /JS (app.alert(1)) >> >>\nendobj\n\
+                    2 0 obj\n<< /S /Launch /F (calc.exe) >>\nendobj\n%%EOF";
+        let kinds: Vec<_> = pdf_token_findings(pdf)
+            .into_iter()
+            .map(|finding| finding.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![SecurityFindingKind::JavaScript, SecurityFindingKind::LaunchAction]
+        );
+
+        assert!(pdf_token_findings(b"%PDF-1.4\n1 0 obj\n<< /Type /Catalog /JSONData 1 >>\nendobj\n%%EOF").is_empty());
+    }
+
+    #[cfg(all(feature = "archives", feature = "tokio-runtime"))]
+    #[tokio::test]
+    async fn test_security_policy_in_extraction() {
+        use crate::core::config::ExtractionConfig;
+
+        let archive = zip_with(&["readme.txt", "tools/setup.EXE"]);
+        let mut config = ExtractionConfig {
+            security: Some(SecurityConfig::default()),
+            ..Default::default()
+        };
+
+        let result = crate::extract_bytes(&archive, "application/zip", &config)
+            .await
+            .unwrap();
+        let findings: Vec<SecurityFinding> =
+            serde_json::from_value(result.metadata.additional["security_findings"].clone()).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].kind, SecurityFindingKind::Executable);
+        assert_eq!(findings[0].location.as_deref(), Some("tools/setup.EXE"));
+
+        config.security = Some(SecurityConfig {
+            block_active_content: true,
+            ..Default::default()
+        });
+        let rejected = crate::extract_bytes(&archive, "application/zip", &config).await;
+        assert!(matches!(rejected, Err(KreuzbergError::Security { .. })));
+    }
+}
@@ -43,6 +43,7 @@
 //! # }
 //! ```
 
+use crate::core::temp::TempWorkspace;
 use crate::error::{KreuzbergError, Result};
 use crate::types::LibreOfficeConversionResult;
 use std::borrow::Cow;
@@ -54,27 +55,6 @@ use tokio::fs;
 use tokio::process::Command;
 use tokio::time::{Duration, timeout};
 
-/// RAII guard for automatic temporary directory cleanup
-struct TempDir {
-    path: PathBuf,
-}
-
-impl TempDir {
-    async fn new(path: PathBuf) -> Result<Self> {
-        fs::create_dir_all(&path).await?;
-        Ok(Self { path })
-    }
-}
-
-impl Drop for TempDir {
-    fn drop(&mut self) {
-        let path = self.path.clone();
-        tokio::spawn(async move {
-            let _ = fs::remove_dir_all(&path).await;
-        });
-    }
-}
-
 /// Default timeout for LibreOffice conversion (300 seconds)
 pub const DEFAULT_CONVERSION_TIMEOUT: u64 = 300;
 
@@ -87,22 +67,42 @@ If LibreOffice is installed in a custom location, set the KREUZBERG_LIBREOFFICE_
         .to_string()
 }
 
+/// `file://` URI of `path`, with the bytes outside of unreserved characters percent-encoded.
 fn path_to_file_uri(path: &Path) -> String {
     let canonical = std_fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let canonical = crate::core::io::strip_verbatim_prefix(&canonical);
 
-    #[cfg(windows)]
-    {
-        let mut normalized = canonical.to_string_lossy().replace('\\', "/");
-        if !normalized.starts_with('/') {
-            normalized = format!("/{}", normalized);
-        }
-        format!("file://{}", normalized)
-    }
+    #[cfg(unix)]
+    let bytes = {
+        use std::os::unix::ffi::OsStrExt;
+        canonical.as_os_str().as_bytes().to_vec()
+    };
+    #[cfg(not(unix))]
+    let bytes = canonical.to_string_lossy().into_owned().into_bytes();
 
-    #[cfg(not(windows))]
-    {
-        format!("file://{}", canonical.to_string_lossy())
+    file_uri_from_bytes(&bytes, std::path::MAIN_SEPARATOR as u8)
+}
+
+fn file_uri_from_bytes(path: &[u8], separator: u8) -> String {
+    let mut uri = String::from("file://");
+    // UNC paths (`\\server\share`) keep the server as the URI host.
+    let path = match path.strip_prefix(&[separator, separator][..]) {
+        Some(unc) if separator == b'\\' => unc,
+        _ => {
+            if path.first() != Some(&separator) {
+                uri.push('/');
+            }
+            path
+        }
+    };
+    for &byte in path {
+        match byte {
+            b if b == separator => uri.push('/'),
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' | b'/' | b':' => uri.push(byte as char),
+            _ => uri.push_str(&format!("%{byte:02X}")),
+        }
     }
+    uri
 }
 
 fn soffice_candidates() -> Vec<PathBuf> {
@@ -158,7 +158,8 @@ fn soffice_candidates() -> Vec<PathBuf> {
     candidates
 }
 
-fn locate_soffice_binary() -> Result<PathBuf> {
+/// The path of the first LibreOffice executable found, without running it.
+pub(crate) fn locate_soffice_binary() -> Result<PathBuf> {
     for candidate in soffice_candidates() {
         if candidate.exists()
             && let Ok(metadata) = std_fs::metadata(&candidate)
@@ -199,11 +200,23 @@ pub async fn convert_office_doc(
     output_dir: &Path,
     target_format: &str,
     timeout_seconds: u64,
+) -> Result<Vec<u8>> {
+    let workspace = TempWorkspace::new(None);
+    convert_office_doc_in(input_path, output_dir, target_format, timeout_seconds, &workspace).await
+}
+
+/// Convert an Office document with a LibreOffice profile in `workspace`, checking the
+/// files of the workspace against its quota afterwards.
+async fn convert_office_doc_in(
+    input_path: &Path,
+    output_dir: &Path,
+    target_format: &str,
+    timeout_seconds: u64,
+    workspace: &TempWorkspace,
 ) -> Result<Vec<u8>> {
     let soffice_path = check_libreoffice_available().await?;
 
-    let profile_dir = std::env::temp_dir().join(format!("kreuzberg_lo_profile_{}", uuid::Uuid::new_v4()));
-    let _profile_guard = TempDir::new(profile_dir.clone()).await?;
+    let profile_dir = workspace.create_dir("lo_profile")?;
     let user_install_arg = format!("-env:UserInstallation={}", path_to_file_uri(&profile_dir));
 
     fs::create_dir_all(output_dir).await?;
@@ -219,7 +232,9 @@ pub async fn convert_office_doc(
         .arg(target_format)
         .arg("--outdir")
         .arg(output_dir)
-        .arg(input_path);
+        .arg(input_path)
+        // A cancelled or timed out conversion must not keep writing to the workspace.
+        .kill_on_drop(true);
 
     let child = command
         .stdout(std::process::Stdio::piped())
@@ -287,6 +302,8 @@ pub async fn convert_office_doc(
         return Err(KreuzbergError::Io(std::io::Error::other(error_details)));
     }
 
+    workspace.check_usage()?;
+
     let input_stem = input_path
         .file_stem()
         .ok_or_else(|| KreuzbergError::parsing("Invalid input file name".to_string()))?;
@@ -311,19 +328,15 @@ pub async fn convert_office_doc(
 
 /// Convert .doc to .docx using LibreOffice
 pub async fn convert_doc_to_docx(doc_bytes: &[u8]) -> Result<LibreOfficeConversionResult> {
-    let temp_dir = std::env::temp_dir();
-    let unique_id = uuid::Uuid::new_v4();
-    let input_dir_path = temp_dir.join(format!("kreuzberg_doc_{}", unique_id));
-    let output_dir_path = temp_dir.join(format!("kreuzberg_doc_{}_out", unique_id));
-
-    // RAII guards ensure cleanup on all paths including panic ~keep
-    let _input_guard = TempDir::new(input_dir_path.clone()).await?;
-    let _output_guard = TempDir::new(output_dir_path.clone()).await?;
-
-    let input_path = input_dir_path.join("input.doc");
-    fs::write(&input_path, doc_bytes).await?;
+    convert_doc_to_docx_in(doc_bytes, &TempWorkspace::new(None)).await
+}
 
-    let converted_bytes = convert_office_doc(&input_path, &output_dir_path, "docx", DEFAULT_CONVERSION_TIMEOUT).await?;
+/// Convert .doc to .docx using LibreOffice, with the intermediate files in `workspace`
+pub async fn convert_doc_to_docx_in(
+    doc_bytes: &[u8],
+    workspace: &TempWorkspace,
+) -> Result<LibreOfficeConversionResult> {
+    let converted_bytes = convert_legacy(doc_bytes, "doc", "docx", workspace).await?;
 
     Ok(LibreOfficeConversionResult {
         converted_bytes,
@@ -335,19 +348,15 @@ pub async fn convert_doc_to_docx(doc_bytes: &[u8]) -> Result<LibreOfficeConversi
 
 /// Convert .ppt to .pptx using LibreOffice
 pub async fn convert_ppt_to_pptx(ppt_bytes: &[u8]) -> Result<LibreOfficeConversionResult> {
-    let temp_dir = std::env::temp_dir();
-    let unique_id = uuid::Uuid::new_v4();
-    let input_dir_path = temp_dir.join(format!("kreuzberg_ppt_{}", unique_id));
-    let output_dir_path = temp_dir.join(format!("kreuzberg_ppt_{}_out", unique_id));
-
-    // RAII guards ensure cleanup on all paths including panic ~keep
-    let _input_guard = TempDir::new(input_dir_path.clone()).await?;
-    let _output_guard = TempDir::new(output_dir_path.clone()).await?;
-
-    let input_path = input_dir_path.join("input.ppt");
-    fs::write(&input_path, ppt_bytes).await?;
+    convert_ppt_to_pptx_in(ppt_bytes, &TempWorkspace::new(None)).await
+}
 
-    let converted_bytes = convert_office_doc(&input_path, &output_dir_path, "pptx", DEFAULT_CONVERSION_TIMEOUT).await?;
+/// Convert .ppt to .pptx using LibreOffice, with the intermediate files in `workspace`
+pub async fn convert_ppt_to_pptx_in(
+    ppt_bytes: &[u8],
+    workspace: &TempWorkspace,
+) -> Result<LibreOfficeConversionResult> {
+    let converted_bytes = convert_legacy(ppt_bytes, "ppt", "pptx", workspace).await?;
 
     Ok(LibreOfficeConversionResult {
         converted_bytes,
@@ -357,10 +366,52 @@ pub async fn convert_ppt_to_pptx(ppt_bytes: &[u8]) -> Result<LibreOfficeConversi
     })
 }
 
+/// Write `bytes` to an input directory of `workspace` and convert them from
+/// `original_format` to `target_format` into an output directory of it.
+async fn convert_legacy(
+    bytes: &[u8],
+    original_format: &str,
+    target_format: &str,
+    workspace: &TempWorkspace,
+) -> Result<Vec<u8>> {
+    let input_dir_path = workspace.create_dir(&format!("kreuzberg_{}", original_format))?;
+    let output_dir_path = workspace.create_dir(&format!("kreuzberg_{}_out", original_format))?;
+
+    workspace.charge(bytes.len() as u64)?;
+    let input_path = input_dir_path.join(format!("input.{}", original_format));
+    fs::write(&input_path, bytes).await?;
+
+    convert_office_doc_in(
+        &input_path,
+        &output_dir_path,
+        target_format,
+        DEFAULT_CONVERSION_TIMEOUT,
+        workspace,
+    )
+    .await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_file_uri_from_bytes() {
+        assert_eq!(file_uri_from_bytes(b"/tmp/profile", b'/'), "file:///tmp/profile");
+        assert_eq!(
+            file_uri_from_bytes(b"/tmp/my docs/r\xe9sum\xe9#1", b'/'),
+            "file:///tmp/my%20docs/r%E9sum%E9%231"
+        );
+        assert_eq!(
+            file_uri_from_bytes(br"C:\Users\me\AppData", b'\\'),
+            "file:///C:/Users/me/AppData"
+        );
+        assert_eq!(
+            file_uri_from_bytes(br"\\server\share\profile", b'\\'),
+            "file://server/share/profile"
+        );
+    }
+
     #[tokio::test]
     #[cfg(not(target_os = "windows"))]
     async fn test_check_libreoffice_available() {
@@ -560,16 +611,15 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_tempdir_raii_cleanup_on_error() {
-        let temp_path = std::env::temp_dir().join(format!("test_raii_{}", uuid::Uuid::new_v4()));
+    async fn test_workspace_cleanup_on_error() {
+        let workspace = TempWorkspace::new(None);
+        let input_dir = workspace.create_dir("kreuzberg_doc").unwrap();
+        let workspace_dir = workspace.path().unwrap().to_path_buf();
 
-        {
-            let _guard = TempDir::new(temp_path.clone()).await.unwrap();
-            assert!(temp_path.exists());
-        }
-
-        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+        let result = convert_office_doc_in(&input_dir.join("missing.doc"), &input_dir, "docx", 1, &workspace).await;
+        assert!(result.is_err());
+        drop(workspace);
 
-        assert!(!temp_path.exists() || fs::read_dir(&temp_path).await.is_err());
+        assert!(!workspace_dir.exists());
     }
 }
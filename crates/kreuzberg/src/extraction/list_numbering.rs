@@ -0,0 +1,174 @@
+//! List numbering for word processing formats.
+//!
+//! DOCX and ODT store list items without their numbers; the number is derived
+//! from the item's level, the numbering format of each level and the items that
+//! came before it. [`ListCounters`] tracks those counters and [`NumberFormat`]
+//! renders them (`1`, `a`, `iv`, ...).
+
+/// How the counter of a list level is rendered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum NumberFormat {
+    Decimal,
+    LowerLetter,
+    UpperLetter,
+    LowerRoman,
+    UpperRoman,
+    Bullet,
+    None,
+}
+
+impl NumberFormat {
+    /// Parse a DOCX `w:numFmt` value. Unsupported formats fall back to decimal.
+    pub fn from_docx(value: &str) -> Self {
+        match value {
+            "lowerLetter" => Self::LowerLetter,
+            "upperLetter" => Self::UpperLetter,
+            "lowerRoman" => Self::LowerRoman,
+            "upperRoman" => Self::UpperRoman,
+            "bullet" => Self::Bullet,
+            "none" => Self::None,
+            _ => Self::Decimal,
+        }
+    }
+
+    /// Parse an ODF `style:num-format` value.
+    pub fn from_odf(value: &str) -> Self {
+        match value {
+            "a" => Self::LowerLetter,
+            "A" => Self::UpperLetter,
+            "i" => Self::LowerRoman,
+            "I" => Self::UpperRoman,
+            "" => Self::None,
+            _ => Self::Decimal,
+        }
+    }
+
+    /// Render a counter value.
+    pub fn format(self, value: usize) -> String {
+        match self {
+            Self::Decimal => value.to_string(),
+            Self::LowerLetter => letters(value),
+            Self::UpperLetter => letters(value).to_uppercase(),
+            Self::LowerRoman => roman(value),
+            Self::UpperRoman => roman(value).to_uppercase(),
+            Self::Bullet | Self::None => String::new(),
+        }
+    }
+}
+
+/// `a`..`z`, then `aa`, `bb`, ... as Word and LibreOffice number lettered lists.
+fn letters(value: usize) -> String {
+    if value == 0 {
+        return "0".to_string();
+    }
+    let letter = (b'a' + ((value - 1) % 26) as u8) as char;
+    letter.to_string().repeat((value - 1) / 26 + 1)
+}
+
+fn roman(mut value: usize) -> String {
+    if value == 0 || value >= 4000 {
+        return value.to_string();
+    }
+    const NUMERALS: [(usize, &str); 13] = [
+        (1000, "m"),
+        (900, "cm"),
+        (500, "d"),
+        (400, "cd"),
+        (100, "c"),
+        (90, "xc"),
+        (50, "l"),
+        (40, "xl"),
+        (10, "x"),
+        (9, "ix"),
+        (5, "v"),
+        (4, "iv"),
+        (1, "i"),
+    ];
+    let mut output = String::new();
+    for (amount, numeral) in NUMERALS {
+        while value >= amount {
+            output.push_str(numeral);
+            value -= amount;
+        }
+    }
+    output
+}
+
+/// Counters of a multi-level list.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ListCounters {
+    levels: Vec<Counter>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+enum Counter {
+    /// No item of the level yet; the first one gets the level's start value
+    #[default]
+    Unset,
+    /// The next item gets this value
+    Next(usize),
+    /// Value of the last item
+    Current(usize),
+}
+
+impl ListCounters {
+    /// Advance the counter of `level` (0-based), restarting deeper levels.
+    ///
+    /// `start` is the value of the first item of a level. Returns the counters of
+    /// levels `0..=level`; levels that were skipped count as their start value.
+    pub fn next(&mut self, level: usize, start: impl Fn(usize) -> usize) -> Vec<usize> {
+        self.levels.resize(level + 1, Counter::Unset);
+        let value = match self.levels[level] {
+            Counter::Unset => start(level),
+            Counter::Next(value) => value,
+            Counter::Current(value) => value + 1,
+        };
+        self.levels[level] = Counter::Current(value);
+
+        (0..=level)
+            .map(|l| match self.levels[l] {
+                Counter::Current(value) | Counter::Next(value) => value,
+                Counter::Unset => {
+                    self.levels[l] = Counter::Current(start(l));
+                    start(l)
+                }
+            })
+            .collect()
+    }
+
+    /// Restart `level` so its next item gets `value`.
+    pub fn restart(&mut self, level: usize, value: usize) {
+        self.levels.resize(level + 1, Counter::Unset);
+        self.levels[level] = Counter::Next(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_number_formats() {
+        assert_eq!(NumberFormat::LowerLetter.format(1), "a");
+        assert_eq!(NumberFormat::LowerLetter.format(28), "bb");
+        assert_eq!(NumberFormat::UpperRoman.format(14), "XIV");
+        assert_eq!(NumberFormat::from_odf("i").format(4), "iv");
+        assert_eq!(NumberFormat::from_docx("decimalZero").format(7), "7");
+        assert_eq!(NumberFormat::Bullet.format(3), "");
+    }
+
+    #[test]
+    fn test_list_counters_restart_deeper_levels() {
+        let mut counters = ListCounters::default();
+        let start = |_| 1;
+        assert_eq!(counters.next(0, start), vec![1]);
+        assert_eq!(counters.next(1, start), vec![1, 1]);
+        assert_eq!(counters.next(1, start), vec![1, 2]);
+        assert_eq!(counters.next(0, start), vec![2]);
+        assert_eq!(counters.next(1, start), vec![2, 1]);
+        assert_eq!(counters.next(2, start), vec![2, 1, 1]);
+
+        counters.restart(0, 5);
+        assert_eq!(counters.next(0, start), vec![5]);
+    }
+}
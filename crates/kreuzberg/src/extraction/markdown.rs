@@ -54,8 +54,7 @@ pub fn cells_to_markdown(cells: &[Vec<String>]) -> String {
         markdown.push('|');
         for cell in header {
             markdown.push(' ');
-            let escaped = cell.replace('|', "\\|");
-            markdown.push_str(&escaped);
+            push_escaped(&mut markdown, cell);
             markdown.push_str(" |");
         }
         markdown.push('\n');
@@ -74,8 +73,7 @@ pub fn cells_to_markdown(cells: &[Vec<String>]) -> String {
                 break;
             }
             markdown.push(' ');
-            let escaped = cell.replace('|', "\\|");
-            markdown.push_str(&escaped);
+            push_escaped(&mut markdown, cell);
             markdown.push_str(" |");
         }
         for _ in row.len()..num_cols {
@@ -87,6 +85,16 @@ pub fn cells_to_markdown(cells: &[Vec<String>]) -> String {
     markdown
 }
 
+/// Append `cell` to `markdown`, escaping pipes without allocating a copy of the cell.
+fn push_escaped(markdown: &mut String, cell: &str) {
+    for (index, part) in cell.split('|').enumerate() {
+        if index > 0 {
+            markdown.push_str("\\|");
+        }
+        markdown.push_str(part);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -5,6 +5,15 @@ pub mod transform;
 #[cfg(feature = "ocr")]
 pub mod image;
 
+#[cfg(feature = "ocr")]
+pub(crate) mod ccitt;
+
+#[cfg(feature = "barcodes")]
+pub mod barcode;
+
+#[cfg(feature = "ocr")]
+pub mod signature;
+
 /// Capacity estimation utilities for string pre-allocation.
 ///
 /// This module provides functions to estimate the capacity needed for string buffers
@@ -18,6 +27,16 @@ pub mod archive;
 #[cfg(feature = "email")]
 pub mod email;
 
+#[cfg(any(feature = "archives", feature = "email"))]
+pub(crate) mod nested;
+
+pub(crate) mod inspection;
+
+pub(crate) mod salvage;
+
+#[cfg(feature = "office")]
+pub(crate) mod vba;
+
 #[cfg(feature = "excel")]
 pub mod excel;
 
@@ -27,12 +46,21 @@ pub mod html;
 #[cfg(feature = "office")]
 pub mod docx;
 
+#[cfg(feature = "office")]
+pub(crate) mod docx_body;
+
 #[cfg(feature = "office")]
 pub mod libreoffice;
 
 #[cfg(feature = "office")]
 pub mod office_metadata;
 
+#[cfg(feature = "office")]
+pub(crate) mod opc;
+
+#[cfg(feature = "office")]
+pub(crate) mod charts;
+
 #[cfg(feature = "office")]
 pub mod pptx;
 
@@ -42,9 +70,24 @@ pub mod table;
 #[cfg(feature = "xml")]
 pub mod xml;
 
+#[cfg(any(feature = "xml", feature = "html"))]
+pub mod svg;
+
+#[cfg(any(feature = "xml", feature = "html"))]
+pub(crate) mod remote;
+
+#[cfg(any(feature = "xml", feature = "html"))]
+pub(crate) mod xml_parser;
+
 #[cfg(any(feature = "office", feature = "html", feature = "xml"))]
 pub mod markdown;
 
+#[cfg(feature = "office")]
+pub(crate) mod list_numbering;
+
+#[cfg(any(feature = "office", feature = "html"))]
+pub(crate) mod table_grid;
+
 pub use structured::{JsonExtractionConfig, StructuredDataResult, parse_json, parse_toml, parse_yaml};
 pub use text::parse_text;
 pub use transform::{
@@ -64,13 +107,18 @@ pub use archive::{
 pub use email::{build_email_text_output, extract_email_content, parse_eml_content, parse_msg_content};
 
 #[cfg(feature = "excel")]
-pub use excel::{excel_to_markdown, read_excel_bytes, read_excel_file};
+pub use excel::{
+    excel_to_markdown, read_excel_bytes, read_excel_bytes_with_config, read_excel_file, read_excel_file_with_config,
+};
 
 #[cfg(feature = "html")]
 pub use html::{convert_html_to_markdown, process_html};
 
 #[cfg(feature = "office")]
-pub use libreoffice::{check_libreoffice_available, convert_doc_to_docx, convert_ppt_to_pptx};
+pub use libreoffice::{
+    check_libreoffice_available, convert_doc_to_docx, convert_doc_to_docx_in, convert_ppt_to_pptx,
+    convert_ppt_to_pptx_in,
+};
 
 #[cfg(feature = "office")]
 pub use office_metadata::{
@@ -88,6 +136,9 @@ pub use table::table_from_arrow_to_markdown;
 #[cfg(feature = "xml")]
 pub use xml::parse_xml;
 
+#[cfg(any(feature = "xml", feature = "html"))]
+pub use svg::parse_svg;
+
 #[cfg(any(feature = "office", feature = "html", feature = "xml"))]
 pub use markdown::cells_to_markdown;
 
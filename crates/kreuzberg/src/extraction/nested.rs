@@ -0,0 +1,388 @@
+//! Nested container extraction.
+//!
+//! With [`ExtractionConfig::containers`](crate::core::config::ExtractionConfig) set,
+//! archive members and email attachments that are themselves archives or emails are
+//! extracted recursively, and their text is added to the enclosing document's text.
+//! Containers nested deeper than [`ContainerConfig::max_depth`], containers with the
+//! same content as a container enclosing them (such as archives that contain
+//! themselves) and containers that cannot be read are skipped and reported in
+//! `metadata.additional["nesting_warnings"]`.
+
+use crate::Result;
+use crate::core::config::ContainerConfig;
+use crate::core::io::Spill;
+#[cfg(feature = "archives")]
+use crate::extraction::archive::ArchiveMetadata;
+use crate::types::{Metadata, NestingWarning, NestingWarningKind};
+use std::borrow::Cow;
+use std::hash::{DefaultHasher, Hasher};
+#[cfg(feature = "archives")]
+use std::io::Read;
+
+/// Kind of a container that is extracted when nested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ContainerKind {
+    #[cfg(feature = "archives")]
+    Zip,
+    #[cfg(feature = "archives")]
+    Tar,
+    #[cfg(feature = "archives")]
+    SevenZ,
+    #[cfg(feature = "email")]
+    Eml,
+    #[cfg(feature = "email")]
+    Msg,
+}
+
+impl ContainerKind {
+    /// Container kind of an archive member or attachment, from its file name or MIME type.
+    pub(crate) fn detect(name: &str, mime_type: Option<&str>) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        let extension = name.rsplit_once('.').map(|(_, extension)| extension);
+        match (extension, mime_type) {
+            #[cfg(feature = "archives")]
+            (Some("zip"), _) | (_, Some("application/zip" | "application/x-zip-compressed")) => Some(Self::Zip),
+            #[cfg(feature = "archives")]
+            (Some("tar"), _) | (_, Some("application/x-tar" | "application/tar" | "application/x-gtar")) => {
+                Some(Self::Tar)
+            }
+            #[cfg(feature = "archives")]
+            (Some("7z"), _) | (_, Some("application/x-7z-compressed")) => Some(Self::SevenZ),
+            #[cfg(feature = "email")]
+            (Some("eml"), _) | (_, Some("message/rfc822")) => Some(Self::Eml),
+            #[cfg(feature = "email")]
+            (Some("msg"), _) | (_, Some("application/vnd.ms-outlook")) => Some(Self::Msg),
+            _ => None,
+        }
+    }
+
+    /// Text of a container of this kind, extracting the containers nested in it.
+    fn text(self, bytes: &[u8], nesting: &mut Nesting<'_>) -> Result<String> {
+        #[cfg(feature = "archives")]
+        use crate::extraction::archive::{extract_7z_metadata, extract_tar_metadata, extract_zip_metadata};
+
+        #[cfg(feature = "archives")]
+        let spill = nesting.spill.clone();
+        match self {
+            #[cfg(feature = "archives")]
+            Self::Zip => {
+                let metadata = extract_zip_metadata(bytes)?;
+                archive_text(self, bytes, &metadata, Some(nesting), &spill)
+            }
+            #[cfg(feature = "archives")]
+            Self::Tar => {
+                let metadata = extract_tar_metadata(bytes)?;
+                archive_text(self, bytes, &metadata, Some(nesting), &spill)
+            }
+            #[cfg(feature = "archives")]
+            Self::SevenZ => {
+                let metadata = extract_7z_metadata(bytes)?;
+                archive_text(self, bytes, &metadata, Some(nesting), &spill)
+            }
+            #[cfg(feature = "email")]
+            Self::Eml | Self::Msg => {
+                let mime_type = if self == Self::Eml {
+                    "message/rfc822"
+                } else {
+                    "application/vnd.ms-outlook"
+                };
+                let email = crate::extraction::email::extract_email_content(bytes, mime_type)?;
+                Ok(email_text(&email, Some(nesting)))
+            }
+        }
+    }
+}
+
+/// Recursion state of a nested extraction.
+pub(crate) struct Nesting<'a> {
+    config: &'a ContainerConfig,
+    /// Content hashes of the input and the nested containers being extracted, outermost first
+    ancestors: Vec<u64>,
+    /// Names of the nested containers being extracted, outermost first
+    path: Vec<String>,
+    warnings: Vec<NestingWarning>,
+    /// Where the text of nested archives is staged on disk
+    #[cfg_attr(not(feature = "archives"), allow(dead_code))]
+    spill: Spill,
+}
+
+impl<'a> Nesting<'a> {
+    /// Start a nested extraction of the input document `input`.
+    pub(crate) fn new(config: &'a ContainerConfig, input: &[u8]) -> Self {
+        Self {
+            config,
+            ancestors: vec![content_hash(input)],
+            path: Vec::new(),
+            warnings: Vec::new(),
+            spill: Spill::new(None),
+        }
+    }
+
+    /// Stage the text of nested archives on disk as `spill` says.
+    pub(crate) fn with_spill(mut self, spill: Spill) -> Self {
+        self.spill = spill;
+        self
+    }
+
+    /// Text of the nested container `name`, or `None` when it is skipped.
+    pub(crate) fn container_text(&mut self, name: &str, kind: ContainerKind, bytes: &[u8]) -> Option<String> {
+        let depth = self.ancestors.len() + 1;
+        if depth > self.config.max_depth {
+            self.warn(
+                name,
+                NestingWarningKind::MaxDepth,
+                format!(
+                    "Not extracted: nesting level {depth} exceeds the maximum of {}",
+                    self.config.max_depth
+                ),
+            );
+            return None;
+        }
+
+        let hash = content_hash(bytes);
+        if self.ancestors.contains(&hash) {
+            self.warn(
+                name,
+                NestingWarningKind::Cycle,
+                "Not extracted: same content as an enclosing container".to_string(),
+            );
+            return None;
+        }
+
+        self.ancestors.push(hash);
+        self.path.push(name.to_string());
+        let text = kind.text(bytes, self);
+        self.path.pop();
+        self.ancestors.pop();
+
+        match text {
+            Ok(text) => Some(text),
+            Err(error) => {
+                self.warn(name, NestingWarningKind::Unreadable, format!("Not extracted: {error}"));
+                None
+            }
+        }
+    }
+
+    fn warn(&mut self, name: &str, kind: NestingWarningKind, message: String) {
+        let mut path = self.path.join("/");
+        if !path.is_empty() {
+            path.push('/');
+        }
+        path.push_str(name);
+        self.warnings.push(NestingWarning { path, kind, message });
+    }
+
+    /// Record the skipped containers in `metadata.additional["nesting_warnings"]`.
+    pub(crate) fn record(self, metadata: &mut Metadata) {
+        if !self.warnings.is_empty() {
+            metadata
+                .additional
+                .insert(Cow::Borrowed("nesting_warnings"), serde_json::json!(self.warnings));
+        }
+    }
+}
+
+fn content_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    hasher.write(bytes);
+    hasher.finish()
+}
+
+/// Text output of an archive: its summary, the contents of its text files and, with
+/// `nesting`, the text of the containers in it, in archive order.
+///
+/// The text is assembled in a [`SpillBuffer`](crate::core::io::SpillBuffer), so the contents of huge members are
+/// staged in the workspace of `spill` once the text exceeds its threshold.
+#[cfg(feature = "archives")]
+pub(crate) fn archive_text(
+    kind: ContainerKind,
+    bytes: &[u8],
+    metadata: &ArchiveMetadata,
+    mut nesting: Option<&mut Nesting<'_>>,
+    spill: &Spill,
+) -> Result<String> {
+    use crate::extraction::archive::{
+        archive_summary, is_text_file, visit_7z_members, visit_tar_members, visit_zip_members,
+    };
+
+    let mut buffer = spill.buffer();
+    buffer.push(archive_summary(&metadata.format, metadata))?;
+
+    let mut has_contents = false;
+    let mut visit = |path: &str, size: u64, reader: &mut dyn Read| -> Result<()> {
+        let is_text = is_text_file(path);
+        let container = nesting
+            .as_ref()
+            .and_then(|_| ContainerKind::detect(path, None))
+            .filter(|_| !is_text);
+        if !is_text && container.is_none() {
+            return Ok(());
+        }
+
+        let checkpoint = buffer.checkpoint();
+        if !has_contents {
+            buffer.push_str("\n\nText File Contents:\n\n")?;
+        }
+        buffer.push(format!("=== {} ===\n", path))?;
+        let added = match (container, nesting.as_deref_mut()) {
+            (Some(member_kind), Some(nesting)) => {
+                let mut data = Vec::with_capacity(size.min(10 * 1024 * 1024) as usize);
+                match reader.read_to_end(&mut data) {
+                    Ok(_) => match nesting.container_text(path, member_kind, &data) {
+                        Some(text) => buffer.push(text).map(|_| true)?,
+                        None => false,
+                    },
+                    Err(_) => false,
+                }
+            }
+            _ => buffer.read_text(reader, size)?,
+        };
+        if added {
+            buffer.push_str("\n\n")?;
+            has_contents = true;
+        } else {
+            buffer.rollback(checkpoint)?;
+        }
+        Ok(())
+    };
+
+    match kind {
+        ContainerKind::Zip => visit_zip_members(bytes, &mut visit)?,
+        ContainerKind::Tar => visit_tar_members(bytes, &mut visit)?,
+        ContainerKind::SevenZ => visit_7z_members(bytes, &mut visit)?,
+        #[cfg(feature = "email")]
+        ContainerKind::Eml | ContainerKind::Msg => {}
+    }
+    buffer.into_string()
+}
+
+/// Text output of an email, followed with `nesting` by the text of the archives and
+/// emails attached to it.
+#[cfg(feature = "email")]
+pub(crate) fn email_text(email: &crate::types::EmailExtractionResult, nesting: Option<&mut Nesting<'_>>) -> String {
+    let mut text = crate::extraction::email::build_email_text_output(email);
+
+    if let Some(nesting) = nesting {
+        for (index, attachment) in email.attachments.iter().enumerate() {
+            let name = attachment
+                .filename
+                .clone()
+                .or_else(|| attachment.name.clone())
+                .unwrap_or_else(|| format!("attachment {}", index + 1));
+            if let Some(data) = attachment.data.as_ref()
+                && let Some(kind) = ContainerKind::detect(&name, attachment.mime_type.as_deref())
+                && let Some(attachment_text) = nesting.container_text(&name, kind, data)
+            {
+                text.push_str(&format!("\n\n=== {} ===\n{}", name, attachment_text));
+            }
+        }
+    }
+    text
+}
+
+#[cfg(all(test, feature = "archives"))]
+mod tests {
+    use super::*;
+    use crate::core::config::ExtractionConfig;
+    use std::io::{Cursor, Write};
+    use zip::write::{FileOptions, ZipWriter};
+
+    fn zip(files: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut cursor = Cursor::new(Vec::new());
+        let mut zip = ZipWriter::new(&mut cursor);
+        for (name, data) in files {
+            zip.start_file(*name, FileOptions::<'_, ()>::default()).unwrap();
+            zip.write_all(data).unwrap();
+        }
+        zip.finish().unwrap();
+        cursor.into_inner()
+    }
+
+    fn warnings(metadata: &Metadata) -> Vec<NestingWarning> {
+        serde_json::from_value(metadata.additional["nesting_warnings"].clone()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_nested_archives_are_extracted_to_max_depth() {
+        let deepest = zip(&[("deepest.txt", b"too deep")]);
+        let deep = zip(&[("deep.txt", b"deep text"), ("deepest.zip", &deepest)]);
+        let inner = zip(&[
+            ("inner.txt", b"inner text"),
+            ("deep.zip", &deep),
+            ("broken.zip", b"not a zip"),
+        ]);
+        let outer = zip(&[("outer.txt", b"outer text"), ("inner.zip", &inner)]);
+
+        let config = ExtractionConfig {
+            containers: Some(ContainerConfig::default()),
+            ..Default::default()
+        };
+        let result = crate::extract_bytes(&outer, "application/zip", &config).await.unwrap();
+        assert!(result.content.contains("outer text"));
+        assert!(result.content.contains("inner text"));
+        assert!(result.content.contains("deep text"));
+        assert!(!result.content.contains("too deep"));
+
+        let warnings = warnings(&result.metadata);
+        assert_eq!(warnings.len(), 2);
+        assert!(
+            warnings
+                .iter()
+                .any(|warning| warning.path == "inner.zip/deep.zip/deepest.zip"
+                    && warning.kind == NestingWarningKind::MaxDepth)
+        );
+        assert!(warnings
+            .iter()
+            .any(|warning| warning.path == "inner.zip/broken.zip" && warning.kind == NestingWarningKind::Unreadable));
+
+        let result = crate::extract_bytes(&outer, "application/zip", &ExtractionConfig::default())
+            .await
+            .unwrap();
+        assert!(!result.content.contains("inner text"));
+        assert!(!result.metadata.additional.contains_key("nesting_warnings"));
+    }
+
+    #[test]
+    fn test_container_repeating_an_ancestor_is_a_cycle() {
+        let archive = zip(&[("readme.txt", b"hello")]);
+        let config = ContainerConfig::default();
+        let mut nesting = Nesting::new(&config, &archive);
+
+        assert_eq!(nesting.container_text("self.zip", ContainerKind::Zip, &archive), None);
+        assert!(
+            nesting
+                .container_text("copy.zip", ContainerKind::Zip, &zip(&[("other.txt", b"x")]))
+                .is_some()
+        );
+
+        let mut metadata = Metadata::default();
+        nesting.record(&mut metadata);
+        let warnings = warnings(&metadata);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].path, "self.zip");
+        assert_eq!(warnings[0].kind, NestingWarningKind::Cycle);
+    }
+
+    #[cfg(feature = "email")]
+    #[tokio::test]
+    async fn test_email_attachments_are_extracted() {
+        let attached = "From: inner@example.com\r\nSubject: Forwarded\r\n\r\nForwarded body text\r\n";
+        let email = format!(
+            "From: outer@example.com\r\nSubject: Outer\r\nMIME-Version: 1.0\r\n\
+             Content-Type: multipart/mixed; boundary=\"b\"\r\n\r\n\
+             --b\r\nContent-Type: text/plain\r\n\r\nOuter body\r\n\
+             --b\r\nContent-Type: message/rfc822\r\n\r\n{attached}\r\n--b--\r\n"
+        );
+
+        let config = ExtractionConfig {
+            containers: Some(ContainerConfig::default()),
+            ..Default::default()
+        };
+        let result = crate::extract_bytes(email.as_bytes(), "message/rfc822", &config)
+            .await
+            .unwrap();
+        assert!(result.content.contains("Outer body"));
+        assert!(result.content.contains("Forwarded body text"), "{}", result.content);
+    }
+}
@@ -3,6 +3,7 @@
 //! Extracts format-specific metadata from Office Open XML documents.
 
 use crate::error::{KreuzbergError, Result};
+use crate::extraction::xml_parser;
 use roxmltree::Node;
 use std::io::Read;
 use zip::ZipArchive;
@@ -124,8 +125,9 @@ pub fn extract_docx_app_properties<R: Read + std::io::Seek>(archive: &mut ZipArc
         }
     }
 
-    let doc = roxmltree::Document::parse(&xml_content)
-        .map_err(|e| KreuzbergError::parsing(format!("Failed to parse app.xml: {}", e)))?;
+    let doc = xml_parser::parse_document(&xml_content, None, |e| {
+        KreuzbergError::parsing(format!("Failed to parse app.xml: {}", e))
+    })?;
 
     let root = doc.root_element();
 
@@ -165,8 +167,9 @@ pub fn extract_xlsx_app_properties<R: Read + std::io::Seek>(archive: &mut ZipArc
         }
     }
 
-    let doc = roxmltree::Document::parse(&xml_content)
-        .map_err(|e| KreuzbergError::parsing(format!("Failed to parse app.xml: {}", e)))?;
+    let doc = xml_parser::parse_document(&xml_content, None, |e| {
+        KreuzbergError::parsing(format!("Failed to parse app.xml: {}", e))
+    })?;
 
     let root = doc.root_element();
 
@@ -201,8 +204,9 @@ pub fn extract_pptx_app_properties<R: Read + std::io::Seek>(archive: &mut ZipArc
         }
     }
 
-    let doc = roxmltree::Document::parse(&xml_content)
-        .map_err(|e| KreuzbergError::parsing(format!("Failed to parse app.xml: {}", e)))?;
+    let doc = xml_parser::parse_document(&xml_content, None, |e| {
+        KreuzbergError::parsing(format!("Failed to parse app.xml: {}", e))
+    })?;
 
     let root = doc.root_element();
 
@@ -3,6 +3,7 @@
 //! Extracts Dublin Core metadata from Office Open XML documents.
 
 use crate::error::{KreuzbergError, Result};
+use crate::extraction::xml_parser;
 use std::io::Read;
 use zip::ZipArchive;
 
@@ -44,6 +45,34 @@ pub struct CoreProperties {
     pub last_printed: Option<String>,
 }
 
+impl CoreProperties {
+    /// Metadata entries for the properties that all office formats record under the
+    /// same keys.
+    ///
+    /// Returns `description`, `modified_by`, `created_at`, `modified_at`, `revision`,
+    /// `category`, `content_status`, `language`, `identifier`, `version` and
+    /// `last_printed` when set. Title, creator, subject and keywords are left to the
+    /// extractors, which record them in format-specific ways.
+    pub fn document_property_entries(&self) -> Vec<(&'static str, String)> {
+        [
+            ("description", &self.description),
+            ("modified_by", &self.last_modified_by),
+            ("created_at", &self.created),
+            ("modified_at", &self.modified),
+            ("revision", &self.revision),
+            ("category", &self.category),
+            ("content_status", &self.content_status),
+            ("language", &self.language),
+            ("identifier", &self.identifier),
+            ("version", &self.version),
+            ("last_printed", &self.last_printed),
+        ]
+        .into_iter()
+        .filter_map(|(key, value)| value.clone().map(|value| (key, value)))
+        .collect()
+    }
+}
+
 /// Extract core properties from an Office Open XML document
 ///
 /// Parses `docProps/core.xml` from the ZIP archive and extracts Dublin Core metadata.
@@ -93,8 +122,9 @@ pub fn extract_core_properties<R: Read + std::io::Seek>(archive: &mut ZipArchive
         }
     }
 
-    let doc = roxmltree::Document::parse(&xml_content)
-        .map_err(|e| KreuzbergError::parsing(format!("Failed to parse core.xml: {}", e)))?;
+    let doc = xml_parser::parse_document(&xml_content, None, |e| {
+        KreuzbergError::parsing(format!("Failed to parse core.xml: {}", e))
+    })?;
 
     let root = doc.root_element();
 
@@ -190,6 +220,13 @@ mod tests {
         assert_eq!(props.category, Some("Documents".to_string()));
         assert_eq!(props.content_status, Some("Final".to_string()));
         assert_eq!(props.language, Some("en-US".to_string()));
+
+        let entries = props.document_property_entries();
+        assert_eq!(entries.len(), 8);
+        assert!(entries.contains(&("modified_by", "Jane Doe".to_string())));
+        assert!(entries.contains(&("revision", "5".to_string())));
+        assert!(entries.contains(&("category", "Documents".to_string())));
+        assert!(!entries.iter().any(|(key, _)| *key == "title"));
     }
 
     #[test]
@@ -3,6 +3,7 @@
 //! Extracts user-defined custom metadata from Office Open XML documents.
 
 use crate::error::{KreuzbergError, Result};
+use crate::extraction::xml_parser;
 use serde_json::Value;
 use std::collections::HashMap;
 use std::io::Read;
@@ -26,11 +27,11 @@ pub type CustomProperties = HashMap<String, Value>;
 /// # Returns
 ///
 /// Returns a `HashMap` of property names to JSON values. Supported VT types:
-/// - `vt:lpwstr` / `vt:lpstr` → String
-/// - `vt:i4` → Number (integer)
-/// - `vt:r8` → Number (float)
+/// - `vt:lpwstr` / `vt:lpstr` / `vt:bstr` → String
+/// - `vt:i1` to `vt:i8`, `vt:ui1` to `vt:ui8`, `vt:int` / `vt:uint` → Number (integer)
+/// - `vt:r4` / `vt:r8` / `vt:decimal` → Number (float)
 /// - `vt:bool` → Boolean
-/// - `vt:filetime` → String (ISO 8601 timestamp)
+/// - `vt:filetime` / `vt:date` → String (ISO 8601 timestamp)
 ///
 /// # Example
 ///
@@ -61,8 +62,9 @@ pub fn extract_custom_properties<R: Read + std::io::Seek>(archive: &mut ZipArchi
         }
     }
 
-    let doc = roxmltree::Document::parse(&xml_content)
-        .map_err(|e| KreuzbergError::parsing(format!("Failed to parse custom.xml: {}", e)))?;
+    let doc = xml_parser::parse_document(&xml_content, None, |e| {
+        KreuzbergError::parsing(format!("Failed to parse custom.xml: {}", e))
+    })?;
 
     let root = doc.root_element();
     let mut properties = HashMap::new();
@@ -86,15 +88,20 @@ fn extract_vt_value(node: roxmltree::Node) -> Option<Value> {
         let tag = child.tag_name().name();
 
         match tag {
-            "lpwstr" | "lpstr" => {
+            "lpwstr" | "lpstr" | "bstr" => {
                 return child.text().map(|s| Value::String(s.to_string()));
             }
-            "i4" => {
+            "i1" | "i2" | "i4" | "i8" | "int" => {
                 return child
                     .text()
                     .and_then(|s| s.trim().parse::<i64>().ok().map(|n| Value::Number(n.into())));
             }
-            "r8" => {
+            "ui1" | "ui2" | "ui4" | "ui8" | "uint" => {
+                return child
+                    .text()
+                    .and_then(|s| s.trim().parse::<u64>().ok().map(|n| Value::Number(n.into())));
+            }
+            "r4" | "r8" | "decimal" => {
                 return child.text().and_then(|s| {
                     s.trim()
                         .parse::<f64>()
@@ -109,7 +116,7 @@ fn extract_vt_value(node: roxmltree::Node) -> Option<Value> {
                     _ => None,
                 });
             }
-            "filetime" => {
+            "filetime" | "date" => {
                 return child.text().map(|s| Value::String(s.to_string()));
             }
             _ => {
@@ -176,6 +183,12 @@ mod tests {
     <property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="5" name="FloatProp">
         <vt:r8>3.14159</vt:r8>
     </property>
+    <property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="6" name="LongProp">
+        <vt:i8>9000000000</vt:i8>
+    </property>
+    <property fmtid="{D5CDD505-2E9C-101B-9397-08002B2CF9AE}" pid="7" name="DateProp">
+        <vt:date>2024-03-01T00:00:00Z</vt:date>
+    </property>
 </Properties>"#;
 
         let mut archive = create_test_zip_with_custom_xml(custom_xml);
@@ -185,6 +198,11 @@ mod tests {
         assert_eq!(props.get("IntProp"), Some(&Value::Number(42.into())));
         assert_eq!(props.get("BoolProp"), Some(&Value::Bool(true)));
         assert!(matches!(props.get("FloatProp"), Some(Value::Number(_))));
+        assert_eq!(props.get("LongProp"), Some(&Value::Number(9_000_000_000_i64.into())));
+        assert_eq!(
+            props.get("DateProp"),
+            Some(&Value::String("2024-03-01T00:00:00Z".to_string()))
+        );
     }
 
     #[test]
@@ -2,7 +2,10 @@
 //!
 //! Extracts metadata from OpenDocument Text files following the OASIS OpenDocument standard.
 
+use super::CustomProperties;
 use crate::error::{KreuzbergError, Result};
+use crate::extraction::xml_parser;
+use serde_json::Value;
 use std::io::Read;
 use zip::ZipArchive;
 
@@ -48,6 +51,10 @@ pub struct OdtProperties {
     pub table_count: Option<i32>,
     /// Document statistics - image count (meta:image-count)
     pub image_count: Option<i32>,
+    /// Last print date (meta:print-date)
+    pub print_date: Option<String>,
+    /// User-defined properties (meta:user-defined), typed by their meta:value-type
+    pub user_defined: CustomProperties,
 }
 
 /// Extract ODT metadata from an OpenDocument file
@@ -99,8 +106,9 @@ pub fn extract_odt_properties<R: Read + std::io::Seek>(archive: &mut ZipArchive<
         }
     }
 
-    let doc = roxmltree::Document::parse(&xml_content)
-        .map_err(|e| KreuzbergError::parsing(format!("Failed to parse meta.xml: {}", e)))?;
+    let doc = xml_parser::parse_document(&xml_content, None, |e| {
+        KreuzbergError::parsing(format!("Failed to parse meta.xml: {}", e))
+    })?;
 
     let root = doc.root_element();
 
@@ -124,6 +132,21 @@ pub fn extract_odt_properties<R: Read + std::io::Seek>(archive: &mut ZipArchive<
     let paragraph_count = super::parse_xml_int(root, "paragraph-count");
     let table_count = super::parse_xml_int(root, "table-count");
     let image_count = super::parse_xml_int(root, "image-count");
+    let print_date = super::parse_xml_text(root, "print-date");
+
+    let user_defined = root
+        .descendants()
+        .filter(|n| n.has_tag_name("user-defined"))
+        .filter_map(|node| {
+            let name = node
+                .attributes()
+                .find(|attribute| attribute.name() == "name")?
+                .value()
+                .to_string();
+            let value = user_defined_value(node)?;
+            Some((name, value))
+        })
+        .collect();
 
     Ok(OdtProperties {
         title,
@@ -144,9 +167,36 @@ pub fn extract_odt_properties<R: Read + std::io::Seek>(archive: &mut ZipArchive<
         paragraph_count,
         table_count,
         image_count,
+        print_date,
+        user_defined,
     })
 }
 
+/// Convert a meta:user-defined element to a JSON value according to its meta:value-type.
+fn user_defined_value(node: roxmltree::Node) -> Option<Value> {
+    let text = node.text().unwrap_or_default().trim();
+    let value_type = node
+        .attributes()
+        .find(|attribute| attribute.name() == "value-type")
+        .map(|attribute| attribute.value())
+        .unwrap_or("string");
+
+    match value_type {
+        "float" => text.parse::<i64>().map(|n| Value::Number(n.into())).ok().or_else(|| {
+            text.parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+                .map(Value::Number)
+        }),
+        "boolean" => match text {
+            "true" => Some(Value::Bool(true)),
+            "false" => Some(Value::Bool(false)),
+            _ => None,
+        },
+        _ => Some(Value::String(text.to_string())),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,6 +242,10 @@ mod tests {
     <meta:paragraph-count>45</meta:paragraph-count>
     <meta:table-count>3</meta:table-count>
     <meta:image-count>7</meta:image-count>
+    <meta:print-date>2024-01-03T09:00:00</meta:print-date>
+    <meta:user-defined meta:name="Client">ACME Corp</meta:user-defined>
+    <meta:user-defined meta:name="Retention Years" meta:value-type="float">7</meta:user-defined>
+    <meta:user-defined meta:name="Approved" meta:value-type="boolean">true</meta:user-defined>
   </office:meta>
 </office:document-meta>"#;
 
@@ -216,6 +270,11 @@ mod tests {
         assert_eq!(props.paragraph_count, Some(45));
         assert_eq!(props.table_count, Some(3));
         assert_eq!(props.image_count, Some(7));
+        assert_eq!(props.print_date, Some("2024-01-03T09:00:00".to_string()));
+        assert_eq!(props.user_defined.len(), 3);
+        assert_eq!(props.user_defined["Client"], Value::String("ACME Corp".to_string()));
+        assert_eq!(props.user_defined["Retention Years"], Value::Number(7.into()));
+        assert_eq!(props.user_defined["Approved"], Value::Bool(true));
     }
 
     #[test]
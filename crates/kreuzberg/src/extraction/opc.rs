@@ -0,0 +1,97 @@
+//! Open Packaging Conventions helpers for Office Open XML packages.
+//!
+//! DOCX, XLSX and PPTX files are ZIP packages whose parts reference each other
+//! through relationship parts (`_rels/<part>.rels`). These helpers read parts and
+//! resolve relationship targets to part paths.
+
+use crate::error::{KreuzbergError, Result};
+use crate::extraction::xml_parser;
+use std::io::{Read, Seek};
+use zip::ZipArchive;
+
+/// Namespace of relationship id attributes such as `r:id` and `r:embed`.
+#[cfg(feature = "excel")]
+pub(crate) const RELATIONSHIPS_NS: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
+
+/// Package relationship with its target resolved to a part path.
+#[derive(Debug, Clone)]
+pub(crate) struct Relationship {
+    pub id: String,
+    /// Relationship type URI, such as `.../relationships/chart`
+    pub kind: String,
+    pub target: String,
+}
+
+/// Relationships of a package part, read from its `_rels/<part>.rels`.
+///
+/// External targets are left out. Missing or malformed relationship parts yield no relationships.
+pub(crate) fn read_relationships<R: Read + Seek>(archive: &mut ZipArchive<R>, part: &str) -> Vec<Relationship> {
+    let (dir, file) = part.rsplit_once('/').unwrap_or(("", part));
+    let rels_path = format!("{}/_rels/{}.rels", dir, file);
+    let Ok(xml) = read_part(archive, rels_path.trim_start_matches('/')) else {
+        return Vec::new();
+    };
+    let Some(doc) = xml_parser::try_parse_document(&xml, None) else {
+        return Vec::new();
+    };
+    doc.descendants()
+        .filter(|node| node.has_tag_name("Relationship") && node.attribute("TargetMode") != Some("External"))
+        .filter_map(|node| {
+            Some(Relationship {
+                id: node.attribute("Id")?.to_owned(),
+                kind: node.attribute("Type")?.to_owned(),
+                target: resolve_part_path(dir, node.attribute("Target")?),
+            })
+        })
+        .collect()
+}
+
+/// Resolve a relationship target relative to the directory of its source part.
+pub(crate) fn resolve_part_path(dir: &str, target: &str) -> String {
+    if let Some(absolute) = target.strip_prefix('/') {
+        return absolute.to_owned();
+    }
+    let mut segments: Vec<&str> = dir.split('/').filter(|segment| !segment.is_empty()).collect();
+    for segment in target.split('/') {
+        match segment {
+            ".." => {
+                segments.pop();
+            }
+            "." | "" => {}
+            segment => segments.push(segment),
+        }
+    }
+    segments.join("/")
+}
+
+/// Read a part of the package as text.
+pub(crate) fn read_part<R: Read + Seek>(archive: &mut ZipArchive<R>, path: &str) -> Result<String> {
+    let mut file = archive
+        .by_name(path)
+        .map_err(|e| KreuzbergError::parsing(format!("Missing package part '{}': {}", path, e)))?;
+    let mut content = String::new();
+    file.read_to_string(&mut content)?;
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_part_path() {
+        assert_eq!(
+            resolve_part_path("xl/worksheets", "../comments1.xml"),
+            "xl/comments1.xml"
+        );
+        assert_eq!(
+            resolve_part_path("xl", "worksheets/sheet1.xml"),
+            "xl/worksheets/sheet1.xml"
+        );
+        assert_eq!(
+            resolve_part_path("ppt/slides", "/ppt/charts/chart2.xml"),
+            "ppt/charts/chart2.xml"
+        );
+        assert_eq!(resolve_part_path("", "./word/document.xml"), "word/document.xml");
+    }
+}
@@ -11,7 +11,9 @@ use zip::ZipArchive;
 
 use super::elements::Slide;
 use super::image_handling::get_full_image_path;
+use crate::core::config::PageSelection;
 use crate::error::{KreuzbergError, Result};
+use crate::extraction::charts::part_charts;
 
 pub(super) struct PptxContainer {
     pub(super) archive: ZipArchive<File>,
@@ -106,6 +108,7 @@ pub(super) struct SlideIterator {
     container: PptxContainer,
     current_index: usize,
     total_slides: usize,
+    selection: Option<PageSelection>,
 }
 
 impl SlideIterator {
@@ -115,14 +118,25 @@ impl SlideIterator {
             container,
             current_index: 0,
             total_slides,
+            selection: None,
         }
     }
 
+    /// Only yield the slides in `selection`; the other slides are not read.
+    pub(super) fn select(&mut self, selection: Option<PageSelection>) {
+        self.selection = selection;
+    }
+
     pub(super) fn slide_count(&self) -> usize {
         self.total_slides
     }
 
     pub(super) fn next_slide(&mut self) -> Result<Option<Slide>> {
+        if let Some(selection) = &self.selection {
+            while self.current_index < self.total_slides && !selection.contains(self.current_index + 1) {
+                self.current_index += 1;
+            }
+        }
         if self.current_index >= self.total_slides {
             return Ok(None);
         }
@@ -135,7 +149,14 @@ impl SlideIterator {
         let rels_path = self.container.get_slide_rels_path(slide_path);
         let rels_data = self.container.read_file(&rels_path).ok();
 
-        let slide = Slide::from_xml(slide_number, &xml_data, rels_data.as_deref())?;
+        let mut slide = Slide::from_xml(slide_number, &xml_data, rels_data.as_deref())?;
+        slide.charts = part_charts(&mut self.container.archive, slide_path)
+            .into_iter()
+            .map(|(id, mut chart)| {
+                chart.page_number = slide_number as usize;
+                (id, chart)
+            })
+            .collect();
 
         self.current_index += 1;
 
@@ -43,8 +43,7 @@ impl ContentBuilder {
         if let Some(ref cfg) = self.config
             && cfg.insert_page_markers
         {
-            let marker = cfg.marker_format.replace("{page_num}", &slide_number.to_string());
-            self.content.push_str(&marker);
+            cfg.push_marker(&mut self.content, slide_number as usize);
         }
 
         byte_start
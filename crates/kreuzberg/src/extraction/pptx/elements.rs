@@ -3,6 +3,10 @@
 //! This module defines the internal data structures used to represent
 //! slide elements, formatting, and text runs during XML parsing.
 
+use std::collections::HashMap;
+
+use crate::types::Chart;
+
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
 pub(super) struct ElementPosition {
     pub(super) x: i64,
@@ -89,6 +93,8 @@ pub(super) enum SlideElement {
     Table(TableElement, ElementPosition),
     Image(ImageReference, ElementPosition),
     List(ListElement, ElementPosition),
+    /// Chart frame, by the relationship id of its chart part
+    Chart(String, ElementPosition),
     Unknown,
 }
 
@@ -98,7 +104,8 @@ impl SlideElement {
             SlideElement::Text(_, pos)
             | SlideElement::Table(_, pos)
             | SlideElement::Image(_, pos)
-            | SlideElement::List(_, pos) => *pos,
+            | SlideElement::List(_, pos)
+            | SlideElement::Chart(_, pos) => *pos,
             SlideElement::Unknown => ElementPosition::default(),
         }
     }
@@ -109,6 +116,8 @@ pub(super) struct Slide {
     pub(super) slide_number: u32,
     pub(super) elements: Vec<SlideElement>,
     pub(super) images: Vec<ImageReference>,
+    /// Charts referenced by the slide, keyed by relationship id
+    pub(super) charts: HashMap<String, Chart>,
 }
 
 #[derive(Debug, Clone)]
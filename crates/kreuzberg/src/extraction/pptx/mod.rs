@@ -48,7 +48,8 @@ mod parser;
 use bytes::Bytes;
 
 use crate::error::Result;
-use crate::types::{ExtractedImage, PptxExtractionResult};
+use crate::extraction::charts::chart_caption;
+use crate::types::{Chart, ExtractedImage, PptxExtractionResult};
 
 use container::{PptxContainer, SlideIterator};
 use content_builder::ContentBuilder;
@@ -60,15 +61,15 @@ use metadata::{extract_all_notes, extract_metadata};
 ///
 /// # Arguments
 ///
-/// * `path` - Path to the PPTX file
+/// * `path` - Path to the PPTX file, which does not need to be valid UTF-8
 /// * `extract_images` - Whether to extract embedded images
-/// * `page_config` - Optional page configuration for boundary tracking
+/// * `page_config` - Optional page configuration for boundary tracking and slide selection
 ///
 /// # Returns
 ///
 /// A `PptxExtractionResult` containing extracted content, metadata, and images.
 pub fn extract_pptx_from_path(
-    path: &str,
+    path: impl AsRef<std::path::Path>,
     extract_images: bool,
     page_config: Option<&crate::core::config::PageConfig>,
 ) -> Result<PptxExtractionResult> {
@@ -79,11 +80,12 @@ pub fn extract_pptx_from_path(
 
     let mut container = PptxContainer::open(path)?;
 
-    let metadata = extract_metadata(&mut container.archive);
+    let (metadata, properties) = extract_metadata(&mut container.archive);
 
     let notes = extract_all_notes(&mut container)?;
 
     let mut iterator = SlideIterator::new(container);
+    iterator.select(page_config.and_then(|config| config.selection.clone()));
     let slide_count = iterator.slide_count();
 
     let estimated_capacity = slide_count.saturating_mul(1000).max(8192);
@@ -92,6 +94,7 @@ pub fn extract_pptx_from_path(
     let mut total_image_count = 0;
     let mut total_table_count = 0;
     let mut extracted_images = Vec::new();
+    let mut charts = Vec::new();
 
     while let Some(slide) = iterator.next_slide()? {
         let byte_start = if page_config.is_some() {
@@ -136,6 +139,7 @@ pub fn extract_pptx_from_path(
 
         total_image_count += slide.image_count();
         total_table_count += slide.table_count();
+        charts.extend(slide.ordered_charts().cloned());
     }
 
     let (content, boundaries, page_contents) = content_builder.build();
@@ -153,6 +157,8 @@ pub fn extract_pptx_from_path(
                     image_count: None,
                     table_count: None,
                     hidden: None,
+                    rotation: None,
+                    blank: None,
                 })
                 .collect()
         }),
@@ -161,10 +167,12 @@ pub fn extract_pptx_from_path(
     Ok(PptxExtractionResult {
         content,
         metadata,
+        properties,
         slide_count,
         image_count: total_image_count,
         table_count: total_table_count,
         images: extracted_images,
+        charts,
         page_structure,
         page_contents,
     })
@@ -186,27 +194,11 @@ pub fn extract_pptx_from_bytes(
     extract_images: bool,
     page_config: Option<&crate::core::config::PageConfig>,
 ) -> Result<PptxExtractionResult> {
-    use std::sync::atomic::{AtomicU64, Ordering};
-    static COUNTER: AtomicU64 = AtomicU64::new(0);
-    let unique_id = COUNTER.fetch_add(1, Ordering::SeqCst);
-    let temp_path = std::env::temp_dir().join(format!("temp_pptx_{}_{}.pptx", std::process::id(), unique_id));
-
-    // IO errors must bubble up - temp file write issues need user reports ~keep
-    std::fs::write(&temp_path, data)?;
-
-    let result = extract_pptx_from_path(
-        temp_path.to_str().ok_or_else(|| {
-            crate::KreuzbergError::validation("Invalid temp path - contains invalid UTF-8".to_string())
-        })?,
-        extract_images,
-        page_config,
-    );
-
-    if let Err(e) = std::fs::remove_file(&temp_path) {
-        tracing::warn!("Failed to remove temp PPTX file: {}", e);
-    }
+    // The workspace is removed when dropped, also when extraction panics.
+    let workspace = crate::core::temp::TempWorkspace::new(None);
+    let temp_path = workspace.write_file("input.pptx", data)?;
 
-    result
+    extract_pptx_from_path(&temp_path, extract_images, page_config)
 }
 
 // Re-export Slide implementation methods for internal use
@@ -224,6 +216,25 @@ impl elements::Slide {
             slide_number,
             elements,
             images,
+            charts: std::collections::HashMap::new(),
+        })
+    }
+
+    /// Elements in reading order, top to bottom and left to right.
+    fn ordered_elements(&self) -> Vec<&SlideElement> {
+        let mut elements: Vec<&SlideElement> = self.elements.iter().collect();
+        elements.sort_by_key(|element| {
+            let pos = element.position();
+            (pos.y, pos.x)
+        });
+        elements
+    }
+
+    /// Charts of the slide in reading order.
+    fn ordered_charts(&self) -> impl Iterator<Item = &Chart> {
+        self.ordered_elements().into_iter().filter_map(|element| match element {
+            SlideElement::Chart(id, _) => self.charts.get(id),
+            _ => None,
         })
     }
 
@@ -234,14 +245,8 @@ impl elements::Slide {
             builder.add_slide_header(self.slide_number);
         }
 
-        let mut element_indices: Vec<usize> = (0..self.elements.len()).collect();
-        element_indices.sort_by_key(|&i| {
-            let pos = self.elements[i].position();
-            (pos.y, pos.x)
-        });
-
-        for &idx in &element_indices {
-            match &self.elements[idx] {
+        for element in self.ordered_elements() {
+            match element {
                 SlideElement::Text(text, _) => {
                     let text_content: String = text.runs.iter().map(|run| run.render_as_md()).collect();
 
@@ -276,6 +281,12 @@ impl elements::Slide {
                 SlideElement::Image(img_ref, _) => {
                     builder.add_image(&img_ref.id, self.slide_number);
                 }
+                SlideElement::Chart(id, _) => {
+                    if let Some(chart) = self.charts.get(id) {
+                        builder.add_text(&format!("\n{}\n", chart_caption(chart)));
+                        builder.add_table(&chart.cells);
+                    }
+                }
                 SlideElement::Unknown => {}
             }
         }
@@ -431,6 +442,29 @@ mod tests {
         assert!(result.content.contains("Slide 3"));
     }
 
+    #[test]
+    fn test_extract_pptx_from_bytes_slide_selection() {
+        let pptx_bytes = create_test_pptx_bytes(vec!["Slide 1", "Slide 2", "Slide 3"]);
+        let page_config = crate::core::config::PageConfig {
+            selection: Some("2-".parse().unwrap()),
+            ..Default::default()
+        };
+        let result = extract_pptx_from_bytes(&pptx_bytes, false, Some(&page_config)).unwrap();
+
+        assert_eq!(result.slide_count, 3);
+        assert!(!result.content.contains("Slide 1"));
+        assert!(result.content.contains("Slide 2") && result.content.contains("Slide 3"));
+        let structure = result.page_structure.unwrap();
+        assert_eq!(structure.total_count, 3);
+        let slide_numbers: Vec<usize> = structure
+            .boundaries
+            .unwrap()
+            .iter()
+            .map(|boundary| boundary.page_number)
+            .collect();
+        assert_eq!(slide_numbers, vec![2, 3]);
+    }
+
     #[test]
     fn test_extract_pptx_metadata() {
         let pptx_bytes = create_test_pptx_bytes(vec!["Content"]);
@@ -559,4 +593,39 @@ mod tests {
             "ppt/slides/image1.png"
         );
     }
+
+    #[test]
+    fn test_slide_chart_frame() {
+        let slide_xml = br#"<p:sld xmlns:a="http://schemas.openxmlformats.org/drawingml/2006/main" xmlns:p="http://schemas.openxmlformats.org/presentationml/2006/main" xmlns:r="http://schemas.openxmlformats.org/officeDocument/2006/relationships">
+    <p:cSld><p:spTree>
+        <p:graphicFrame>
+            <p:xfrm><a:off x="0" y="2000"/></p:xfrm>
+            <a:graphic><a:graphicData uri="http://schemas.openxmlformats.org/drawingml/2006/chart">
+                <c:chart xmlns:c="http://schemas.openxmlformats.org/drawingml/2006/chart" r:id="rId2"/>
+            </a:graphicData></a:graphic>
+        </p:graphicFrame>
+    </p:spTree></p:cSld>
+</p:sld>"#;
+        let mut slide = elements::Slide::from_xml(3, slide_xml, None).unwrap();
+        slide.charts.insert(
+            "rId2".to_string(),
+            Chart {
+                title: Some("Growth".to_string()),
+                chart_type: "line".to_string(),
+                category_axis_title: None,
+                value_axis_title: None,
+                cells: vec![
+                    vec!["Category".to_string(), "Users".to_string()],
+                    vec!["2024".to_string(), "10".to_string()],
+                ],
+                page_number: 3,
+            },
+        );
+
+        let markdown = slide.to_markdown(&ParserConfig::default());
+        assert!(markdown.contains("Chart: Growth (line chart)"));
+        assert!(markdown.contains("<tr><th>Category</th><th>Users</th></tr>"));
+        assert_eq!(slide.ordered_charts().count(), 1);
+        assert_eq!(slide.table_count(), 0);
+    }
 }
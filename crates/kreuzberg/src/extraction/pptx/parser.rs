@@ -3,9 +3,10 @@
 //! This module handles parsing slide XML, extracting text, tables, lists, images,
 //! and relationships from PowerPoint presentations.
 
-use roxmltree::{Document, Node};
+use roxmltree::Node;
 
 use crate::error::{KreuzbergError, Result};
+use crate::extraction::xml_parser;
 use crate::text::utf8_validation;
 
 use super::elements::{
@@ -15,14 +16,16 @@ use super::elements::{
 
 const P_NAMESPACE: &str = "http://schemas.openxmlformats.org/presentationml/2006/main";
 const A_NAMESPACE: &str = "http://schemas.openxmlformats.org/drawingml/2006/main";
+const C_NAMESPACE: &str = "http://schemas.openxmlformats.org/drawingml/2006/chart";
 const RELS_NAMESPACE: &str = "http://schemas.openxmlformats.org/officeDocument/2006/relationships";
 
 pub(super) fn parse_slide_xml(xml_data: &[u8]) -> Result<Vec<SlideElement>> {
     let xml_str = utf8_validation::from_utf8(xml_data)
         .map_err(|_| KreuzbergError::parsing("Invalid UTF-8 in slide XML".to_string()))?;
 
-    let doc =
-        Document::parse(xml_str).map_err(|e| KreuzbergError::parsing(format!("Failed to parse slide XML: {}", e)))?;
+    let doc = xml_parser::parse_document(xml_str, None, |e| {
+        KreuzbergError::parsing(format!("Failed to parse slide XML: {}", e))
+    })?;
 
     let root = doc.root_element();
     let ns = root.tag_name().namespace();
@@ -71,6 +74,8 @@ fn parse_group(node: &Node) -> Result<Vec<SlideElement>> {
         "graphicFrame" => {
             if let Some(graphic_element) = parse_graphic_frame(node)? {
                 elements.push(SlideElement::Table(graphic_element, position));
+            } else if let Some(chart_id) = parse_chart_frame(node) {
+                elements.push(SlideElement::Chart(chart_id, position));
             }
         }
         "pic" => {
@@ -152,6 +157,14 @@ fn parse_graphic_frame(node: &Node) -> Result<Option<TableElement>> {
     Ok(None)
 }
 
+/// Relationship id of the chart part shown by a chart graphic frame.
+fn parse_chart_frame(node: &Node) -> Option<String> {
+    node.descendants()
+        .find(|n| n.is_element() && n.tag_name().name() == "chart" && n.tag_name().namespace() == Some(C_NAMESPACE))
+        .and_then(|chart| chart.attribute((RELS_NAMESPACE, "id")))
+        .map(str::to_owned)
+}
+
 fn parse_table(tbl_node: &Node) -> Result<TableElement> {
     let mut rows = Vec::new();
 
@@ -337,8 +350,9 @@ pub(super) fn parse_slide_rels(rels_data: &[u8]) -> Result<Vec<ImageReference>>
     let xml_str = utf8_validation::from_utf8(rels_data)
         .map_err(|e| KreuzbergError::parsing(format!("Invalid UTF-8 in rels XML: {}", e)))?;
 
-    let doc =
-        Document::parse(xml_str).map_err(|e| KreuzbergError::parsing(format!("Failed to parse rels XML: {}", e)))?;
+    let doc = xml_parser::parse_document(xml_str, None, |e| {
+        KreuzbergError::parsing(format!("Failed to parse rels XML: {}", e))
+    })?;
 
     let mut images = Vec::new();
 
@@ -362,8 +376,9 @@ pub(super) fn parse_presentation_rels(rels_data: &[u8]) -> Result<Vec<String>> {
     let xml_str = utf8_validation::from_utf8(rels_data)
         .map_err(|e| KreuzbergError::parsing(format!("Invalid UTF-8 in presentation rels: {}", e)))?;
 
-    let doc = Document::parse(xml_str)
-        .map_err(|e| KreuzbergError::parsing(format!("Failed to parse presentation rels: {}", e)))?;
+    let doc = xml_parser::parse_document(xml_str, None, |e| {
+        KreuzbergError::parsing(format!("Failed to parse presentation rels: {}", e))
+    })?;
 
     let mut slide_paths = Vec::new();
 
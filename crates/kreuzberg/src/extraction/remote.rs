@@ -0,0 +1,269 @@
+//! Discovery of the remote resources markup documents reference.
+//!
+//! The references found here are handed to
+//! [`record_references`](crate::core::network::record_references), which applies the
+//! network policy; nothing is fetched while extracting.
+
+use crate::core::config::XmlConfig;
+use crate::extraction::xml_parser;
+use crate::types::RemoteResourceKind;
+
+/// Remote references of an HTML document: images, stylesheets, scripts, frames,
+/// embedded objects and media.
+#[cfg(feature = "html")]
+pub(crate) fn html_references(html: &str) -> Vec<(RemoteResourceKind, String)> {
+    let Ok(dom) = tl::parse(html, tl::ParserOptions::default()) else {
+        return Vec::new();
+    };
+
+    let mut references = Vec::new();
+    for tag in dom.nodes().iter().filter_map(tl::Node::as_tag) {
+        let attribute = |name: &str| {
+            tag.attributes().get(name).flatten().map(|value| {
+                html_escape::decode_html_entities(&value.as_utf8_str())
+                    .trim()
+                    .to_string()
+            })
+        };
+        let name = tag.name().as_utf8_str().to_ascii_lowercase();
+        let reference = match name.as_str() {
+            "img" | "input" => attribute("src").map(|url| (RemoteResourceKind::Image, url)),
+            "link" => {
+                let rel = attribute("rel").unwrap_or_default().to_ascii_lowercase();
+                let kind = if rel.split_whitespace().any(|rel| rel == "stylesheet") {
+                    Some(RemoteResourceKind::Stylesheet)
+                } else if rel.split_whitespace().any(|rel| rel == "icon") {
+                    Some(RemoteResourceKind::Image)
+                } else {
+                    None
+                };
+                kind.and_then(|kind| attribute("href").map(|url| (kind, url)))
+            }
+            "script" => attribute("src").map(|url| (RemoteResourceKind::Script, url)),
+            "iframe" | "frame" | "embed" => attribute("src").map(|url| (RemoteResourceKind::Embed, url)),
+            "object" => attribute("data").map(|url| (RemoteResourceKind::Embed, url)),
+            "video" => attribute("poster").map(|url| (RemoteResourceKind::Image, url)),
+            "audio" | "source" | "track" => attribute("src").map(|url| (RemoteResourceKind::Other, url)),
+            _ => None,
+        };
+        references.extend(reference);
+    }
+    references
+}
+
+/// Remote references in the prolog of an XML document: external DTDs and entities
+/// declared in the `DOCTYPE`, and `xml-stylesheet` processing instructions.
+pub(crate) fn xml_prolog_references(xml: &str) -> Vec<(RemoteResourceKind, String)> {
+    let mut references = Vec::new();
+    let mut rest = xml.trim_start_matches('\u{feff}');
+    loop {
+        rest = rest.trim_start();
+        if let Some(instruction) = rest.strip_prefix("<?") {
+            let Some(end) = instruction.find("?>") else {
+                break;
+            };
+            if let Some(stylesheet) = instruction[..end].strip_prefix("xml-stylesheet")
+                && let Some(href) = pseudo_attribute(stylesheet, "href")
+            {
+                references.push((RemoteResourceKind::Stylesheet, href));
+            }
+            rest = &instruction[end + 2..];
+        } else if let Some(comment) = rest.strip_prefix("<!--") {
+            let Some(end) = comment.find("-->") else {
+                break;
+            };
+            rest = &comment[end + 3..];
+        } else if rest.starts_with("<!DOCTYPE") {
+            let end = doctype_end(rest);
+            references.extend(
+                external_ids(&rest[..end])
+                    .into_iter()
+                    .map(|url| (RemoteResourceKind::Entity, url)),
+            );
+            rest = &rest[end..];
+        } else {
+            break;
+        }
+    }
+    references
+}
+
+/// Remote references of an SVG drawing: its prolog, and the images, `<use>` targets
+/// and scripts it links with `href` or `xlink:href`.
+pub(crate) fn svg_references(svg: &str, limits: Option<&XmlConfig>) -> Vec<(RemoteResourceKind, String)> {
+    let mut references = xml_prolog_references(svg);
+    let Some(doc) = xml_parser::try_parse_document(svg, limits) else {
+        return references;
+    };
+
+    for node in doc.descendants().filter(roxmltree::Node::is_element) {
+        let kind = match node.tag_name().name() {
+            "image" | "feImage" => RemoteResourceKind::Image,
+            "use" => RemoteResourceKind::Embed,
+            "script" => RemoteResourceKind::Script,
+            _ => continue,
+        };
+        let href = node
+            .attributes()
+            .find(|attribute| attribute.name() == "href")
+            .map(|attribute| attribute.value().trim().to_string());
+        references.extend(href.map(|url| (kind, url)));
+    }
+    references
+}
+
+/// Length of the `DOCTYPE` declaration at the start of `xml`, including any internal subset.
+fn doctype_end(xml: &str) -> usize {
+    let mut in_subset = false;
+    let mut quote = None;
+    for (index, c) in xml.char_indices() {
+        match (quote, c) {
+            (Some(open), c) if c == open => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[') => in_subset = true,
+            (None, ']') => in_subset = false,
+            (None, '>') if !in_subset => return index + 1,
+            _ => {}
+        }
+    }
+    xml.len()
+}
+
+/// System identifiers of the `SYSTEM` and `PUBLIC` external ids in a `DOCTYPE` declaration.
+fn external_ids(doctype: &str) -> Vec<String> {
+    let mut ids = Vec::new();
+    let mut rest = doctype;
+    while let Some(position) = rest.find(['S', 'P']) {
+        let keyword = &rest[position..];
+        let (after, literals) = if let Some(after) = keyword.strip_prefix("SYSTEM") {
+            (after, 1)
+        } else if let Some(after) = keyword.strip_prefix("PUBLIC") {
+            (after, 2)
+        } else {
+            rest = &keyword[1..];
+            continue;
+        };
+
+        rest = after;
+        let mut last = None;
+        for _ in 0..literals {
+            match quoted_literal(rest) {
+                Some((literal, after)) => {
+                    last = Some(literal);
+                    rest = after;
+                }
+                None => {
+                    last = None;
+                    break;
+                }
+            }
+        }
+        ids.extend(last.map(str::to_string));
+    }
+    ids
+}
+
+/// The quoted literal after optional whitespace at the start of `text`, and the text after it.
+fn quoted_literal(text: &str) -> Option<(&str, &str)> {
+    let text = text.trim_start();
+    let quote = text.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    let end = text[1..].find(quote)? + 1;
+    Some((&text[1..end], &text[end + 1..]))
+}
+
+/// Value of a pseudo-attribute of a processing instruction.
+fn pseudo_attribute(instruction: &str, name: &str) -> Option<String> {
+    let mut rest = instruction;
+    while let Some(position) = rest.find(name) {
+        let after = rest[position + name.len()..].trim_start();
+        let preceded_by_space = rest[..position].chars().last().is_none_or(char::is_whitespace);
+        if preceded_by_space && let Some(value) = after.strip_prefix('=') {
+            return quoted_literal(value).map(|(value, _)| value.trim().to_string());
+        }
+        rest = &rest[position + name.len()..];
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "html")]
+    #[test]
+    fn test_html_references() {
+        let html = r#"<html><head>
+            <link rel="stylesheet" href="https://cdn.example.com/site.css">
+            <link rel="canonical" href="https://example.com/page">
+            <script src="//cdn.example.com/app.js"></script>
+            </head><body>
+            <img src="https://example.com/a.png?x=1&amp;y=2"><img src="local.png">
+            <iframe src="http://169.254.169.254/"></iframe>
+            <a href="https://example.com/">link</a>
+            </body></html>"#;
+
+        assert_eq!(
+            html_references(html),
+            vec![
+                (
+                    RemoteResourceKind::Stylesheet,
+                    "https://cdn.example.com/site.css".to_string()
+                ),
+                (RemoteResourceKind::Script, "//cdn.example.com/app.js".to_string()),
+                (
+                    RemoteResourceKind::Image,
+                    "https://example.com/a.png?x=1&y=2".to_string()
+                ),
+                (RemoteResourceKind::Image, "local.png".to_string()),
+                (RemoteResourceKind::Embed, "http://169.254.169.254/".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_xml_prolog_references() {
+        let xml = r#"<?xml version="1.0"?>
+            <?xml-stylesheet type="text/xsl" href="https://example.com/style.xsl"?>
+            <!-- comment -->
+            <!DOCTYPE note PUBLIC "-//Example//DTD Note//EN" "http://example.com/note.dtd" [
+                <!ENTITY secret SYSTEM "file:///etc/passwd">
+                <!ENTITY name "[PUBLIC]">
+            ]>
+            <note>&secret;</note>"#;
+
+        assert_eq!(
+            xml_prolog_references(xml),
+            vec![
+                (
+                    RemoteResourceKind::Stylesheet,
+                    "https://example.com/style.xsl".to_string()
+                ),
+                (RemoteResourceKind::Entity, "http://example.com/note.dtd".to_string()),
+                (RemoteResourceKind::Entity, "file:///etc/passwd".to_string()),
+            ]
+        );
+        assert!(xml_prolog_references("<note>SYSTEM \"http://example.com\"</note>").is_empty());
+    }
+
+    #[test]
+    fn test_svg_references() {
+        let svg = r##"<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+            <image xlink:href="https://example.com/photo.jpg"/>
+            <use href="https://example.com/icons.svg#star"/>
+            <use href="#local"/>
+        </svg>"##;
+
+        assert_eq!(
+            svg_references(svg, None),
+            vec![
+                (RemoteResourceKind::Image, "https://example.com/photo.jpg".to_string()),
+                (
+                    RemoteResourceKind::Embed,
+                    "https://example.com/icons.svg#star".to_string()
+                ),
+                (RemoteResourceKind::Embed, "#local".to_string()),
+            ]
+        );
+    }
+}
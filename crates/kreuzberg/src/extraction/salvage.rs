@@ -0,0 +1,608 @@
+//! Recovery of damaged inputs.
+//!
+//! With [`ExtractionConfig::salvage`](crate::core::config::ExtractionConfig) set, an input
+//! whose extraction fails is repaired with [`repair`] and extracted again:
+//!
+//! - PDFs get a new cross-reference table listing the complete objects, truncated objects
+//!   are dropped, and the page tree is rebuilt from the page objects when the catalog
+//!   or the page tree root is lost
+//! - ZIP archives, and the OOXML, OpenDocument and EPUB packages built on them, are
+//!   rebuilt from their local file headers without the central directory, keeping what
+//!   can be decompressed of truncated and corrupted entries
+//! - XML parts of packages that end early or are malformed are cut at the last complete
+//!   element, and the open elements are closed
+//!
+//! The damage found is reported in `metadata.additional["salvage_warnings"]`.
+
+/// Key of the damage reports in `metadata.additional`.
+pub(crate) const SALVAGE_WARNINGS_KEY: &str = "salvage_warnings";
+
+/// A repaired input.
+#[derive(Debug)]
+pub(crate) struct Salvage {
+    pub(crate) content: Vec<u8>,
+    /// Damage found and repaired
+    pub(crate) warnings: Vec<String>,
+}
+
+/// Repair the damaged `content` of `mime_type`, or `None` when it is not of a format
+/// that can be repaired or no damage was found.
+pub(crate) fn repair(content: &[u8], mime_type: &str) -> Option<Salvage> {
+    #[cfg(feature = "pdf")]
+    if mime_type == "application/pdf" {
+        return repair_pdf(content);
+    }
+
+    #[cfg(any(feature = "office", feature = "archives"))]
+    if content.starts_with(ZIP_LOCAL_HEADER) {
+        return repair_zip(content, mime_type != "application/zip");
+    }
+
+    let _ = (content, mime_type);
+    None
+}
+
+#[cfg(feature = "pdf")]
+mod pdf_patterns {
+    use once_cell::sync::Lazy;
+    use regex::bytes::Regex;
+
+    pub(super) static OBJECT_HEADER: Lazy<Regex> =
+        Lazy::new(|| Regex::new(r"(?-u)(?:^|[\s>])(\d{1,10})[ \t\r\n]+(\d{1,5})[ \t\r\n]+obj\b").unwrap());
+    pub(super) static ROOT: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?-u)/Root\s+(\d+)\s+\d+\s+R").unwrap());
+    pub(super) static INFO: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?-u)/Info\s+(\d+)\s+\d+\s+R").unwrap());
+    pub(super) static PAGES: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?-u)/Pages\s+(\d+)\s+\d+\s+R").unwrap());
+    pub(super) static CATALOG: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?-u)/Type\s*/Catalog\b").unwrap());
+    pub(super) static PAGE: Lazy<Regex> = Lazy::new(|| Regex::new(r"(?-u)/Type\s*/Page\b").unwrap());
+}
+
+/// Rebuild the cross-reference table of a PDF from the objects found in its body.
+///
+/// Encrypted documents are not repaired, as the rebuilt trailer would lose the keys.
+#[cfg(feature = "pdf")]
+fn repair_pdf(content: &[u8]) -> Option<Salvage> {
+    use pdf_patterns::*;
+    use regex::bytes::Regex;
+    use std::collections::BTreeMap;
+    use std::ops::Range;
+
+    let header = memchr::memmem::find(&content[..content.len().min(1024)], b"%PDF-")?;
+    if memchr::memmem::find(content, b"/Encrypt").is_some() {
+        return None;
+    }
+    let version: String = content[header + 5..]
+        .iter()
+        .take_while(|byte| byte.is_ascii_digit() || **byte == b'.')
+        .map(|byte| *byte as char)
+        .collect();
+
+    fn parse<T: std::str::FromStr>(bytes: &[u8]) -> Option<T> {
+        std::str::from_utf8(bytes).ok()?.parse().ok()
+    }
+
+    let headers: Vec<(u32, u16, Range<usize>)> = OBJECT_HEADER
+        .captures_iter(content)
+        .filter_map(|captures| {
+            let number = parse(&captures[1])?;
+            let generation = parse(&captures[2])?;
+            let whole = captures.get(0)?;
+            Some((number, generation, captures.get(1)?.start()..whole.end()))
+        })
+        .collect();
+
+    // Later definitions of an object replace earlier ones, as in incremental updates.
+    let mut objects: BTreeMap<u32, (u16, Range<usize>)> = BTreeMap::new();
+    let mut truncated = Vec::new();
+    for (index, (number, generation, header)) in headers.iter().enumerate() {
+        let window_end = headers.get(index + 1).map_or(content.len(), |next| next.2.start);
+        match memchr::memmem::rfind(&content[header.end..window_end], b"endobj") {
+            Some(end) => {
+                objects.insert(*number, (*generation, header.start..header.end + end + b"endobj".len()));
+            }
+            None => truncated.push(*number),
+        }
+    }
+    let body = |number: u32| objects.get(&number).map(|(_, range)| &content[range.clone()]);
+    let reference = |regex: &Regex, bytes: &[u8]| -> Option<u32> {
+        regex
+            .captures_iter(bytes)
+            .last()
+            .and_then(|captures| parse(&captures[1]))
+    };
+    let page_tree_of = |catalog: u32| {
+        body(catalog)
+            .and_then(|bytes| reference(&PAGES, bytes))
+            .filter(|pages| body(*pages).is_some())
+    };
+
+    let mut warnings = vec![format!(
+        "Rebuilt the cross-reference table of the PDF from {} objects",
+        objects.len()
+    )];
+    if !truncated.is_empty() {
+        warnings.push(format!(
+            "Dropped {} truncated PDF objects ({})",
+            truncated.len(),
+            truncated.iter().map(u32::to_string).collect::<Vec<_>>().join(", ")
+        ));
+    }
+
+    let root = reference(&ROOT, content)
+        .filter(|root| page_tree_of(*root).is_some())
+        .or_else(|| {
+            objects.keys().copied().find(|number| {
+                body(*number).is_some_and(|bytes| CATALOG.is_match(bytes)) && page_tree_of(*number).is_some()
+            })
+        });
+    let info = reference(&INFO, content).filter(|info| body(*info).is_some());
+
+    let mut extra = Vec::new();
+    let root = match root {
+        Some(root) => root,
+        None => {
+            let pages: Vec<u32> = objects
+                .keys()
+                .copied()
+                .filter(|number| body(*number).is_some_and(|bytes| PAGE.is_match(bytes)))
+                .collect();
+            if pages.is_empty() {
+                return None;
+            }
+            warnings.push(format!("Rebuilt the PDF page tree from {} page objects", pages.len()));
+            let next = objects.keys().next_back().map_or(1, |last| last + 1);
+            let kids: Vec<String> = pages
+                .iter()
+                .map(|page| format!("{} {} R", page, objects[page].0))
+                .collect();
+            extra.push((
+                next,
+                format!(
+                    "<< /Type /Pages /Kids [{}] /Count {} /MediaBox [0 0 612 792] >>",
+                    kids.join(" "),
+                    pages.len()
+                ),
+            ));
+            extra.push((next + 1, format!("<< /Type /Catalog /Pages {} 0 R >>", next)));
+            next + 1
+        }
+    };
+
+    let mut pdf = format!(
+        "%PDF-{}\n%\u{e2}\u{e3}\u{cf}\u{d3}\n",
+        if version.is_empty() { "1.7" } else { &version }
+    )
+    .into_bytes();
+    let mut offsets: BTreeMap<u32, (u16, usize)> = BTreeMap::new();
+    for (number, (generation, range)) in &objects {
+        offsets.insert(*number, (*generation, pdf.len()));
+        pdf.extend_from_slice(&content[range.clone()]);
+        pdf.push(b'\n');
+    }
+    for (number, object) in &extra {
+        offsets.insert(*number, (0, pdf.len()));
+        pdf.extend_from_slice(format!("{number} 0 obj\n{object}\nendobj\n").as_bytes());
+    }
+
+    let size = offsets.keys().next_back().map_or(1, |last| last + 1);
+    let xref = pdf.len();
+    pdf.extend_from_slice(format!("xref\n0 {size}\n0000000000 65535 f \n").as_bytes());
+    for number in 1..size {
+        match offsets.get(&number) {
+            Some((generation, offset)) => {
+                pdf.extend_from_slice(format!("{offset:010} {generation:05} n \n").as_bytes())
+            }
+            None => pdf.extend_from_slice(b"0000000000 65535 f \n"),
+        }
+    }
+    let generation = |number: u32| offsets.get(&number).map_or(0, |(generation, _)| *generation);
+    let info = info.map_or(String::new(), |info| format!(" /Info {} {} R", info, generation(info)));
+    pdf.extend_from_slice(
+        format!(
+            "trailer\n<< /Size {size} /Root {root} {} R{info} >>\nstartxref\n{xref}\n%%EOF\n",
+            generation(root)
+        )
+        .as_bytes(),
+    );
+
+    Some(Salvage { content: pdf, warnings })
+}
+
+/// Signature of ZIP local file headers.
+#[cfg(any(feature = "office", feature = "archives"))]
+const ZIP_LOCAL_HEADER: &[u8] = b"PK\x03\x04";
+
+/// Rebuild a ZIP archive from its local file headers, closing the truncated XML parts
+/// when `repair_xml` is set.
+#[cfg(any(feature = "office", feature = "archives"))]
+fn repair_zip(content: &[u8], repair_xml: bool) -> Option<Salvage> {
+    use std::io::{Read, Write};
+    use zip::write::SimpleFileOptions;
+
+    const DATA_DESCRIPTOR: &[u8] = b"PK\x07\x08";
+    const CENTRAL_HEADER: &[u8] = b"PK\x01\x02";
+    const FLAG_ENCRYPTED: u16 = 1;
+    const FLAG_DATA_DESCRIPTOR: u16 = 1 << 3;
+
+    let u16_at = |offset: usize| u16::from_le_bytes([content[offset], content[offset + 1]]);
+    let u32_at = |offset: usize| {
+        u32::from_le_bytes([
+            content[offset],
+            content[offset + 1],
+            content[offset + 2],
+            content[offset + 3],
+        ])
+    };
+    let find = |needle: &[u8], from: usize| memchr::memmem::find(&content[from..], needle).map(|at| from + at);
+
+    let mut warnings = Vec::new();
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    let mut recovered = 0;
+    let mut next = find(ZIP_LOCAL_HEADER, 0);
+
+    while let Some(start) = next {
+        if start + 30 > content.len() {
+            break;
+        }
+        let flags = u16_at(start + 6);
+        let name_end = start + 30 + u16_at(start + 26) as usize;
+        let data_start = name_end + u16_at(start + 28) as usize;
+        if data_start > content.len() {
+            warnings.push(format!("Dropped the truncated ZIP entry header at byte {start}"));
+            break;
+        }
+        let name = String::from_utf8_lossy(&content[start + 30..name_end]).into_owned();
+
+        let compressed_size = u32_at(start + 18);
+        let (mut crc, mut size) = (u32_at(start + 14), u32_at(start + 22));
+        let mut data_end = None;
+        if flags & FLAG_DATA_DESCRIPTOR != 0 || compressed_size == u32::MAX {
+            // Sizes follow the data, in a descriptor ending before the next header.
+            if let Some(descriptor) = find(DATA_DESCRIPTOR, data_start).filter(|at| at + 16 <= content.len()) {
+                (crc, size) = (u32_at(descriptor + 4), u32_at(descriptor + 12));
+                data_end = Some(descriptor);
+            } else if let Some(header) = [ZIP_LOCAL_HEADER, CENTRAL_HEADER]
+                .iter()
+                .filter_map(|signature| find(signature, data_start))
+                .min()
+                .filter(|header| *header >= data_start + 12)
+            {
+                (crc, size) = (u32_at(header - 12), u32_at(header - 4));
+                data_end = Some(header - 12);
+            }
+        } else if data_start + compressed_size as usize <= content.len() {
+            data_end = Some(data_start + compressed_size as usize);
+        }
+        let truncated = data_end.is_none();
+        let data_end = data_end.unwrap_or(content.len());
+        next = find(ZIP_LOCAL_HEADER, data_end.max(start + 1));
+
+        if flags & FLAG_ENCRYPTED != 0 {
+            warnings.push(format!("Skipped the encrypted ZIP entry '{name}'"));
+            continue;
+        }
+
+        // A local header with the sizes filled in and no extra field, so that the
+        // streaming reader can decompress the entry.
+        let mut header = content[start..start + 30].to_vec();
+        header[6..8].copy_from_slice(&(flags & !FLAG_DATA_DESCRIPTOR).to_le_bytes());
+        header[14..18].copy_from_slice(&crc.to_le_bytes());
+        header[18..22].copy_from_slice(&((data_end - data_start) as u32).to_le_bytes());
+        header[22..26].copy_from_slice(&size.to_le_bytes());
+        header[28..30].copy_from_slice(&0u16.to_le_bytes());
+        let mut stream = header
+            .as_slice()
+            .chain(&content[start + 30..name_end])
+            .chain(&content[data_start..data_end]);
+
+        let mut data = Vec::new();
+        let outcome = match zip::read::read_zipfile_from_stream(&mut stream) {
+            Ok(Some(mut file)) => file.read_to_end(&mut data).map(|_| ()).map_err(|e| e.to_string()),
+            Ok(None) => continue,
+            Err(e) => Err(e.to_string()),
+        };
+        match outcome {
+            Ok(()) if !truncated => {}
+            Ok(()) => warnings.push(format!(
+                "ZIP entry '{name}' is truncated: recovered {} bytes",
+                data.len()
+            )),
+            Err(e) if data.is_empty() => {
+                warnings.push(format!("Dropped the unreadable ZIP entry '{name}': {e}"));
+                continue;
+            }
+            Err(e) => warnings.push(format!(
+                "ZIP entry '{name}' is damaged ({e}): recovered {} bytes",
+                data.len()
+            )),
+        }
+
+        #[cfg(feature = "office")]
+        if repair_xml
+            && (name.ends_with(".xml") || name.ends_with(".rels"))
+            && let Some((repaired, warning)) = repair_xml_part(&data)
+        {
+            warnings.push(format!("XML part '{name}' {warning}"));
+            data = repaired;
+        }
+
+        let written = if name.ends_with('/') && data.is_empty() {
+            writer.add_directory(name.as_str(), options)
+        } else {
+            writer.start_file(name.as_str(), options)
+        };
+        match written.and_then(|_| writer.write_all(&data).map_err(Into::into)) {
+            Ok(()) => recovered += 1,
+            Err(e) => warnings.push(format!("Dropped the ZIP entry '{name}': {e}")),
+        }
+    }
+    #[cfg(not(feature = "office"))]
+    let _ = repair_xml;
+
+    if recovered == 0 {
+        return None;
+    }
+    if zip::ZipArchive::new(std::io::Cursor::new(content)).is_err() {
+        warnings.insert(
+            0,
+            format!("The ZIP central directory is missing or damaged: rebuilt the archive from {recovered} entries"),
+        );
+    }
+    if warnings.is_empty() {
+        return None;
+    }
+    let content = writer.finish().ok()?.into_inner();
+    Some(Salvage { content, warnings })
+}
+
+/// Cut a malformed XML part after its last complete node and close the elements left
+/// open, or `None` when it is well-formed. Returns the part with what was repaired.
+#[cfg(feature = "office")]
+fn repair_xml_part(xml: &[u8]) -> Option<(Vec<u8>, String)> {
+    use quick_xml::events::Event;
+
+    let mut reader = quick_xml::Reader::from_reader(xml);
+    let mut open: Vec<Vec<u8>> = Vec::new();
+    let mut complete = 0;
+    let error = loop {
+        match reader.read_event() {
+            Ok(Event::Eof) if open.is_empty() => return None,
+            Ok(Event::Eof) => break None,
+            Ok(Event::Start(element)) => open.push(element.name().as_ref().to_vec()),
+            Ok(Event::End(_)) => {
+                open.pop();
+            }
+            Ok(_) => {}
+            Err(e) => break Some(e),
+        }
+        complete = reader.buffer_position() as usize;
+    };
+
+    let mut repaired = xml[..complete].to_vec();
+    let closed = open.len();
+    for name in open.iter().rev() {
+        repaired.extend_from_slice(b"</");
+        repaired.extend_from_slice(name);
+        repaired.push(b'>');
+    }
+    let warning = match error {
+        Some(e) => format!("is malformed ({e}): cut at byte {complete} and closed {closed} elements"),
+        None => format!("is truncated: closed {closed} elements"),
+    };
+    Some((repaired, warning))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_repair_unknown_format() {
+        assert!(repair(b"plain text", "text/plain").is_none());
+    }
+
+    /// A ZIP archive of `entries`, stored uncompressed when `stored` is set.
+    #[cfg(any(feature = "office", feature = "archives"))]
+    fn zip(entries: &[(&str, &str)], stored: bool) -> Vec<u8> {
+        use std::io::Write;
+
+        let method = if stored {
+            zip::CompressionMethod::Stored
+        } else {
+            zip::CompressionMethod::Deflated
+        };
+        let options = zip::write::SimpleFileOptions::default().compression_method(method);
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (name, data) in entries {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(data.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    #[cfg(any(feature = "office", feature = "archives"))]
+    fn read_entry(content: &[u8], name: &str) -> String {
+        use std::io::Read;
+
+        let mut archive = zip::ZipArchive::new(std::io::Cursor::new(content)).unwrap();
+        let mut data = String::new();
+        archive.by_name(name).unwrap().read_to_string(&mut data).unwrap();
+        data
+    }
+
+    #[cfg(any(feature = "office", feature = "archives"))]
+    #[test]
+    fn test_repair_zip_without_central_directory() {
+        let body = "kreuzberg ".repeat(200);
+        let content = zip(&[("first.txt", "first entry"), ("second.txt", &body)], false);
+        let central_directory = memchr::memmem::find(&content, b"PK\x01\x02").unwrap();
+
+        let salvage = repair(&content[..central_directory], "application/zip").unwrap();
+        assert_eq!(salvage.warnings.len(), 1, "{:?}", salvage.warnings);
+        assert!(salvage.warnings[0].contains("central directory"));
+        assert_eq!(read_entry(&salvage.content, "first.txt"), "first entry");
+        assert_eq!(read_entry(&salvage.content, "second.txt"), body);
+    }
+
+    #[cfg(any(feature = "office", feature = "archives"))]
+    #[test]
+    fn test_repair_zip_truncated_entry() {
+        let content = zip(&[("first.txt", "first entry"), ("second.txt", "second entry")], true);
+        let cut = memchr::memmem::find(&content, b"second entry").unwrap() + "second".len();
+
+        let salvage = repair(&content[..cut], "application/zip").unwrap();
+        assert!(
+            salvage.warnings.iter().any(|warning| warning.contains("'second.txt'")),
+            "{:?}",
+            salvage.warnings
+        );
+        assert_eq!(read_entry(&salvage.content, "first.txt"), "first entry");
+        assert_eq!(read_entry(&salvage.content, "second.txt"), "second");
+    }
+
+    #[cfg(any(feature = "office", feature = "archives"))]
+    #[test]
+    fn test_repair_intact_zip() {
+        let content = zip(&[("first.txt", "first entry")], false);
+        assert!(repair(&content, "application/zip").is_none());
+    }
+
+    #[cfg(feature = "office")]
+    #[test]
+    fn test_repair_xml_part() {
+        let (repaired, warning) = repair_xml_part(b"<a><b>text</b><c>more").unwrap();
+        assert_eq!(repaired, b"<a><b>text</b><c>more</c></a>");
+        assert!(warning.contains("closed 2 elements"), "{warning}");
+
+        let (repaired, _) = repair_xml_part(b"<a><b>text</b><c att").unwrap();
+        assert_eq!(repaired, b"<a><b>text</b></a>");
+
+        assert!(repair_xml_part(b"<?xml version=\"1.0\"?><a><b/></a>").is_none());
+    }
+
+    #[cfg(feature = "office")]
+    #[test]
+    fn test_repair_package_with_truncated_part() {
+        let document =
+            "<w:document><w:body><w:p><w:t>Recovered</w:t></w:p><w:p><w:t>Lost</w:t></w:p></w:body></w:document>";
+        let content = zip(
+            &[("[Content_Types].xml", "<Types/>"), ("word/document.xml", document)],
+            true,
+        );
+        let cut = memchr::memmem::find(&content, b"<w:t>Lost").unwrap();
+
+        let salvage = repair(
+            &content[..cut],
+            "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        )
+        .unwrap();
+        assert_eq!(
+            read_entry(&salvage.content, "word/document.xml"),
+            "<w:document><w:body><w:p><w:t>Recovered</w:t></w:p><w:p></w:p></w:body></w:document>"
+        );
+        assert!(
+            salvage
+                .warnings
+                .iter()
+                .any(|warning| warning.starts_with("XML part 'word/document.xml'"))
+        );
+    }
+
+    #[cfg(feature = "pdf")]
+    fn pdf(pages: &[&str]) -> Vec<u8> {
+        use lopdf::content::{Content, Operation};
+        use lopdf::{Document, Object, Stream, dictionary};
+
+        let mut document = Document::with_version("1.5");
+        let pages_id = document.new_object_id();
+        let font = document.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+        });
+        let kids: Vec<Object> = pages
+            .iter()
+            .map(|text| {
+                let content = Content {
+                    operations: vec![
+                        Operation::new("BT", vec![]),
+                        Operation::new("Tf", vec!["F1".into(), 12.into()]),
+                        Operation::new("Td", vec![72.into(), 720.into()]),
+                        Operation::new("Tj", vec![Object::string_literal(*text)]),
+                        Operation::new("ET", vec![]),
+                    ],
+                };
+                let stream = document.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+                document
+                    .add_object(dictionary! {
+                        "Type" => "Page",
+                        "Parent" => pages_id,
+                        "Contents" => stream,
+                        "Resources" => dictionary! { "Font" => dictionary! { "F1" => font } },
+                    })
+                    .into()
+            })
+            .collect();
+        document.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Count" => kids.len() as i64,
+                "Kids" => kids,
+                "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+            }),
+        );
+        let catalog = document.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        document.trailer.set("Root", catalog);
+
+        let mut bytes = Vec::new();
+        document.save_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn test_repair_pdf_without_xref() {
+        let content = pdf(&["First page", "Second page"]);
+        let startxref = memchr::memmem::rfind(&content, b"startxref").unwrap();
+        let xref: usize = std::str::from_utf8(&content[startxref + 9..])
+            .unwrap()
+            .split_whitespace()
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(lopdf::Document::load_mem(&content[..xref]).is_err());
+
+        let salvage = repair(&content[..xref], "application/pdf").unwrap();
+        assert_eq!(salvage.warnings.len(), 1, "{:?}", salvage.warnings);
+        let document = lopdf::Document::load_mem(&salvage.content).unwrap();
+        assert_eq!(document.get_pages().len(), 2);
+        assert_eq!(document.extract_text(&[2]).unwrap().trim(), "Second page");
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn test_repair_pdf_without_catalog() {
+        let content = pdf(&["First page", "Second page"]);
+        let catalog = memchr::memmem::rfind(&content, b"/Catalog").unwrap();
+        let cut = memchr::memmem::rfind(&content[..catalog], b" obj").unwrap() + 4;
+
+        let salvage = repair(&content[..cut], "application/pdf").unwrap();
+        assert!(
+            salvage
+                .warnings
+                .iter()
+                .any(|warning| warning.contains("truncated PDF objects"))
+        );
+        assert!(salvage.warnings.iter().any(|warning| warning.contains("page tree")));
+        let document = lopdf::Document::load_mem(&salvage.content).unwrap();
+        assert_eq!(document.extract_text(&[1]).unwrap().trim(), "First page");
+    }
+
+    #[cfg(feature = "pdf")]
+    #[test]
+    fn test_repair_encrypted_pdf() {
+        assert!(repair(b"%PDF-1.7\n1 0 obj\n<< /Encrypt 2 0 R >>\nendobj\n", "application/pdf").is_none());
+    }
+}
@@ -0,0 +1,457 @@
+//! Signature and stamp detection on scanned pages.
+//!
+//! Pages are scaled to a fixed working size (about 150 DPI for Letter and A4
+//! pages), ink is separated from the paper, and nearby ink is grouped into marks.
+//! Each mark is classified with shape heuristics:
+//!
+//! - a **signature** is a sparse group of ink whose largest stroke spans much of
+//!   the mark, unlike printed text, which is made of many small glyphs;
+//! - a **stamp** is a roughly square group of colored ink enclosed by its own
+//!   border, such as the ring of a round seal.
+//!
+//! Tables, frames and rules are rejected because their ink lies on the border of
+//! their box.
+
+use crate::core::config::SignatureConfig;
+use crate::error::{KreuzbergError, Result};
+use crate::extraction::image::{TiffPages, decode_image};
+use crate::types::{BoundingBox, Signature, SignatureKind};
+use image::{DynamicImage, RgbImage};
+
+/// Length of the longer side of the working image, in pixels.
+const WORKING_SIDE: u32 = 1650;
+/// Resolution PDF pages are rendered at; Letter and A4 pages end up near the working size.
+#[cfg(feature = "pdf")]
+const PDF_DPI: i32 = 150;
+/// Distance at which ink pixels are grouped into the same mark.
+const GROUP_RADIUS: usize = 6;
+
+/// Ink pixels darker than this count as ink regardless of color.
+const DARK_LUMA: u8 = 150;
+/// Pixels whose channels differ by more than this count as colored ink.
+const COLOR_CHROMA: u8 = 60;
+
+/// Detect signatures and stamps in an image.
+///
+/// Boxes are in pixels of `image`. Returns an empty list when nothing is found.
+pub fn detect_signatures(image: &DynamicImage, config: &SignatureConfig) -> Vec<Signature> {
+    if !config.signatures && !config.stamps {
+        return Vec::new();
+    }
+    let (width, height) = (image.width(), image.height());
+    if width == 0 || height == 0 {
+        return Vec::new();
+    }
+
+    let scale = f64::from(WORKING_SIDE) / f64::from(width.max(height));
+    let working_width = ((f64::from(width) * scale).round() as u32).max(1);
+    let working_height = ((f64::from(height) * scale).round() as u32).max(1);
+    let working = image
+        .resize_exact(working_width, working_height, image::imageops::FilterType::Triangle)
+        .to_rgb8();
+
+    let ink = InkMask::new(&working);
+    ink.marks()
+        .into_iter()
+        .filter_map(|mark| {
+            let kind = mark.classify(ink.width, ink.height)?;
+            let wanted = match kind {
+                SignatureKind::Signature => config.signatures,
+                SignatureKind::Stamp => config.stamps,
+            };
+            wanted.then(|| Signature {
+                kind,
+                page_number: None,
+                bbox: BoundingBox {
+                    x0: mark.left as f64 / scale,
+                    y0: mark.top as f64 / scale,
+                    x1: (mark.right + 1) as f64 / scale,
+                    y1: (mark.bottom + 1) as f64 / scale,
+                },
+            })
+        })
+        .collect()
+}
+
+/// Detect signatures and stamps in image bytes; TIFF pages are scanned one by one.
+///
+/// Marks found in a TIFF carry their page number.
+pub fn detect_image_signatures(content: &[u8], mime_type: &str, config: &SignatureConfig) -> Result<Vec<Signature>> {
+    if !mime_type.to_lowercase().contains("tiff") {
+        let image =
+            decode_image(content).map_err(|e| KreuzbergError::parsing(format!("Failed to decode image: {}", e)))?;
+        return Ok(detect_signatures(&image, config));
+    }
+
+    let mut signatures = Vec::new();
+    for (page_index, page) in TiffPages::new(content)?.enumerate() {
+        for mut signature in detect_signatures(&page?, config) {
+            signature.page_number = Some(page_index + 1);
+            signatures.push(signature);
+        }
+    }
+    Ok(signatures)
+}
+
+/// Render every page of a PDF and detect its signatures and stamps.
+///
+/// Boxes are converted to PDF points from the top-left corner of the page.
+#[cfg(feature = "pdf")]
+pub fn detect_pdf_signatures(content: &[u8], config: &SignatureConfig) -> Result<Vec<Signature>> {
+    use crate::pdf::rendering::{PageRenderOptions, PdfRenderer};
+
+    let render_error = |e: crate::pdf::error::PdfError| KreuzbergError::Parsing {
+        message: format!("Failed to render PDF pages: {}", e),
+        source: None,
+    };
+    let render_options = PageRenderOptions {
+        target_dpi: PDF_DPI,
+        ..PageRenderOptions::default()
+    };
+
+    let renderer = PdfRenderer::new().map_err(render_error)?;
+    let page_sizes = renderer.page_sizes(content).map_err(render_error)?;
+
+    let mut signatures = Vec::new();
+    for (page_index, (width_points, _)) in page_sizes.into_iter().enumerate() {
+        let image = renderer
+            .render_page_to_image(content, page_index, &render_options)
+            .map_err(render_error)?;
+        let scale = f64::from(width_points) / f64::from(image.width().max(1));
+        for mut signature in detect_signatures(&image, config) {
+            signature.page_number = Some(page_index + 1);
+            let bbox = &mut signature.bbox;
+            bbox.x0 *= scale;
+            bbox.y0 *= scale;
+            bbox.x1 *= scale;
+            bbox.y1 *= scale;
+            signatures.push(signature);
+        }
+    }
+    Ok(signatures)
+}
+
+/// Ink pixels of the working image.
+struct InkMask {
+    width: usize,
+    height: usize,
+    ink: Vec<bool>,
+    colored: Vec<bool>,
+}
+
+/// Connected run of ink pixels.
+#[derive(Debug, Clone, Copy)]
+struct Stroke {
+    left: usize,
+    top: usize,
+    right: usize,
+    bottom: usize,
+    pixels: usize,
+    colored: usize,
+    /// Pixels within two pixels of the stroke's box
+    border_pixels: usize,
+}
+
+impl Stroke {
+    fn width(&self) -> usize {
+        self.right - self.left + 1
+    }
+
+    fn height(&self) -> usize {
+        self.bottom - self.top + 1
+    }
+
+    fn area(&self) -> usize {
+        self.width() * self.height()
+    }
+}
+
+/// Strokes grouped because they lie within [`GROUP_RADIUS`] of each other.
+#[derive(Debug)]
+struct Mark {
+    left: usize,
+    top: usize,
+    right: usize,
+    bottom: usize,
+    pixels: usize,
+    colored: usize,
+    /// Stroke with the largest box
+    largest: Stroke,
+}
+
+impl InkMask {
+    fn new(image: &RgbImage) -> Self {
+        let (width, height) = (image.width() as usize, image.height() as usize);
+        let mut ink = vec![false; width * height];
+        let mut colored = vec![false; width * height];
+
+        for (i, pixel) in image.pixels().enumerate() {
+            let [r, g, b] = pixel.0;
+            let luma = ((u32::from(r) * 299 + u32::from(g) * 587 + u32::from(b) * 114) / 1000) as u8;
+            let chroma = r.max(g).max(b) - r.min(g).min(b);
+            let is_colored = chroma > COLOR_CHROMA && luma < 230;
+            ink[i] = luma < DARK_LUMA || is_colored;
+            colored[i] = is_colored;
+        }
+
+        Self {
+            width,
+            height,
+            ink,
+            colored,
+        }
+    }
+
+    /// Label the strokes, then group strokes whose dilated pixels touch.
+    fn marks(&self) -> Vec<Mark> {
+        let (stroke_labels, strokes) = self.strokes();
+        let grown = dilate(&self.ink, self.width, self.height, GROUP_RADIUS);
+        let (group_labels, group_count) = label(&grown, self.width, self.height);
+
+        let mut marks: Vec<Option<Mark>> = (0..group_count).map(|_| None).collect();
+        let mut assigned = vec![false; strokes.len()];
+        for (i, &stroke_label) in stroke_labels.iter().enumerate() {
+            let Some(stroke_index) = stroke_label else { continue };
+            if std::mem::replace(&mut assigned[stroke_index], true) {
+                continue;
+            }
+            let Some(group) = group_labels[i] else { continue };
+            let stroke = strokes[stroke_index];
+
+            let mark = marks[group].get_or_insert(Mark {
+                left: stroke.left,
+                top: stroke.top,
+                right: stroke.right,
+                bottom: stroke.bottom,
+                pixels: 0,
+                colored: 0,
+                largest: stroke,
+            });
+            mark.left = mark.left.min(stroke.left);
+            mark.top = mark.top.min(stroke.top);
+            mark.right = mark.right.max(stroke.right);
+            mark.bottom = mark.bottom.max(stroke.bottom);
+            mark.pixels += stroke.pixels;
+            mark.colored += stroke.colored;
+            if stroke.area() > mark.largest.area() {
+                mark.largest = stroke;
+            }
+        }
+
+        marks.into_iter().flatten().collect()
+    }
+
+    fn strokes(&self) -> (Vec<Option<usize>>, Vec<Stroke>) {
+        let (labels, count) = label(&self.ink, self.width, self.height);
+
+        let mut strokes: Vec<Option<Stroke>> = vec![None; count];
+        for (i, stroke_label) in labels.iter().enumerate() {
+            let Some(index) = *stroke_label else { continue };
+            let (x, y) = (i % self.width, i / self.width);
+            let stroke = strokes[index].get_or_insert(Stroke {
+                left: x,
+                top: y,
+                right: x,
+                bottom: y,
+                pixels: 0,
+                colored: 0,
+                border_pixels: 0,
+            });
+            stroke.left = stroke.left.min(x);
+            stroke.top = stroke.top.min(y);
+            stroke.right = stroke.right.max(x);
+            stroke.bottom = stroke.bottom.max(y);
+            stroke.pixels += 1;
+            stroke.colored += usize::from(self.colored[i]);
+        }
+        let mut strokes: Vec<Stroke> = strokes.into_iter().flatten().collect();
+
+        // The box is only known after the first pass.
+        for (i, stroke_label) in labels.iter().enumerate() {
+            let Some(index) = *stroke_label else { continue };
+            let (x, y) = (i % self.width, i / self.width);
+            let stroke = &mut strokes[index];
+            let near_border =
+                x <= stroke.left + 2 || x + 2 >= stroke.right || y <= stroke.top + 2 || y + 2 >= stroke.bottom;
+            stroke.border_pixels += usize::from(near_border);
+        }
+
+        (labels, strokes)
+    }
+}
+
+impl Mark {
+    fn width(&self) -> usize {
+        self.right - self.left + 1
+    }
+
+    fn height(&self) -> usize {
+        self.bottom - self.top + 1
+    }
+
+    fn classify(&self, page_width: usize, page_height: usize) -> Option<SignatureKind> {
+        let (width, height) = (self.width(), self.height());
+        if width > page_width / 2 || height > page_height / 4 {
+            return None;
+        }
+
+        let largest = &self.largest;
+        let fill = self.pixels as f64 / (width * height) as f64;
+        let aspect = width as f64 / height as f64;
+        let colored_ratio = self.colored as f64 / self.pixels.max(1) as f64;
+        let largest_fill = largest.pixels as f64 / largest.area() as f64;
+        let is_frame = largest.border_pixels as f64 > 0.6 * largest.pixels as f64;
+
+        let encloses = largest.width() * 10 >= width * 8 && largest.height() * 10 >= height * 8;
+        if width.min(height) >= 60
+            && colored_ratio >= 0.6
+            && (0.5..=2.0).contains(&aspect)
+            && encloses
+            && (0.03..=0.5).contains(&fill)
+        {
+            return Some(SignatureKind::Stamp);
+        }
+
+        let spans = largest.width() * 10 >= width * 3 && largest.height() * 2 >= height;
+        if (35..=250).contains(&height)
+            && width >= 60
+            && (1.2..=10.0).contains(&aspect)
+            && fill <= 0.25
+            && spans
+            && largest.height() >= 30
+            && largest_fill <= 0.2
+            && !is_frame
+        {
+            return Some(SignatureKind::Signature);
+        }
+
+        None
+    }
+}
+
+/// Label 8-connected regions of `mask`; returns the label of every pixel and the label count.
+fn label(mask: &[bool], width: usize, height: usize) -> (Vec<Option<usize>>, usize) {
+    let mut labels = vec![None; mask.len()];
+    let mut count = 0;
+    let mut stack = Vec::new();
+
+    for start in 0..mask.len() {
+        if !mask[start] || labels[start].is_some() {
+            continue;
+        }
+        labels[start] = Some(count);
+        stack.push(start);
+
+        while let Some(i) = stack.pop() {
+            let (x, y) = (i % width, i / width);
+            for ny in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+                for nx in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                    let n = ny * width + nx;
+                    if mask[n] && labels[n].is_none() {
+                        labels[n] = Some(count);
+                        stack.push(n);
+                    }
+                }
+            }
+        }
+        count += 1;
+    }
+
+    (labels, count)
+}
+
+/// Grow `mask` by a square of the given radius.
+fn dilate(mask: &[bool], width: usize, height: usize, radius: usize) -> Vec<bool> {
+    let grow = |line: &[bool]| -> Vec<bool> {
+        let mut prefix = vec![0usize; line.len() + 1];
+        for (i, &set) in line.iter().enumerate() {
+            prefix[i + 1] = prefix[i] + usize::from(set);
+        }
+        (0..line.len())
+            .map(|i| prefix[(i + radius + 1).min(line.len())] > prefix[i.saturating_sub(radius)])
+            .collect()
+    };
+
+    let mut rows = Vec::with_capacity(mask.len());
+    for row in mask.chunks(width) {
+        rows.extend(grow(row));
+    }
+
+    let mut grown = vec![false; mask.len()];
+    let mut column = vec![false; height];
+    for x in 0..width {
+        for y in 0..height {
+            column[y] = rows[y * width + x];
+        }
+        for (y, set) in grow(&column).into_iter().enumerate() {
+            grown[y * width + x] = set;
+        }
+    }
+    grown
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> Vec<u8> {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../test_documents/images")
+            .join(name);
+        std::fs::read(path).unwrap()
+    }
+
+    #[test]
+    fn test_detect_signature_and_stamp() {
+        let signatures =
+            detect_image_signatures(&fixture("signed_form.png"), "image/png", &SignatureConfig::default()).unwrap();
+        assert_eq!(signatures.len(), 2, "{:?}", signatures);
+
+        let signature = signatures
+            .iter()
+            .find(|mark| mark.kind == SignatureKind::Signature)
+            .unwrap();
+        // Drawn above the signature line between x = 220 and 620, y = 1030 and 1140.
+        assert!(
+            signature.bbox.x0 > 200.0 && signature.bbox.x1 < 640.0,
+            "{:?}",
+            signature.bbox
+        );
+        assert!(
+            signature.bbox.y0 > 1010.0 && signature.bbox.y1 < 1160.0,
+            "{:?}",
+            signature.bbox
+        );
+
+        let stamp = signatures
+            .iter()
+            .find(|mark| mark.kind == SignatureKind::Stamp)
+            .unwrap();
+        // Round stamp centered at (930, 1150) with a radius of 110.
+        assert!((stamp.bbox.x0 - 820.0).abs() < 15.0 && (stamp.bbox.x1 - 1040.0).abs() < 15.0);
+        assert!((stamp.bbox.y0 - 1040.0).abs() < 15.0 && (stamp.bbox.y1 - 1260.0).abs() < 15.0);
+        assert!(signatures.iter().all(|mark| mark.page_number.is_none()));
+    }
+
+    #[test]
+    fn test_config_selects_kinds() {
+        let config = SignatureConfig {
+            signatures: false,
+            ..Default::default()
+        };
+        let signatures = detect_image_signatures(&fixture("signed_form.png"), "image/png", &config).unwrap();
+        assert_eq!(signatures.len(), 1);
+        assert_eq!(signatures[0].kind, SignatureKind::Stamp);
+    }
+
+    #[test]
+    fn test_printed_pages_have_no_marks() {
+        for (name, mime_type) in [
+            ("layout_parser_paper_with_table.jpg", "image/jpeg"),
+            ("invoice_image.png", "image/png"),
+        ] {
+            let signatures = detect_image_signatures(&fixture(name), mime_type, &SignatureConfig::default()).unwrap();
+            assert!(signatures.is_empty(), "{}: {:?}", name, signatures);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! SVG text extraction.
+//!
+//! Reads the `<text>` elements of an SVG drawing in document order, with the
+//! position given by their `x`/`y` attributes. A `<tspan>` with its own `x`, `y`
+//! or `dy` is placed separately by renderers and starts a new line; other
+//! `<tspan>`s, `<textPath>`s and links continue the current line. Text inside
+//! `<defs>`, `<style>`, `<script>` and `<metadata>` is not rendered and is skipped.
+//!
+//! # Example
+//!
+//! ```rust
+//! use kreuzberg::extraction::svg::parse_svg;
+//!
+//! # fn example() -> kreuzberg::Result<()> {
+//! let svg = br#"<svg xmlns="http://www.w3.org/2000/svg">
+//!     <title>Flow</title>
+//!     <text x="10" y="20">Start <tspan font-weight="bold">here</tspan></text>
+//!     <text x="10" y="40">End</text>
+//! </svg>"#;
+//! let result = parse_svg(svg)?;
+//!
+//! assert_eq!(result.content, "Start here\nEnd");
+//! assert_eq!(result.title.as_deref(), Some("Flow"));
+//! assert_eq!(result.texts[1].y, Some(40.0));
+//! # Ok(())
+//! # }
+//! ```
+use crate::core::config::XmlConfig;
+use crate::error::{KreuzbergError, Result};
+use crate::extraction::xml_parser;
+use crate::types::{SvgExtractionResult, SvgText};
+use roxmltree::Node;
+
+/// Elements whose content is never rendered as text.
+const SKIPPED_ELEMENTS: &[&str] = &["defs", "style", "script", "metadata", "title", "desc"];
+
+pub fn parse_svg(svg_bytes: &[u8]) -> Result<SvgExtractionResult> {
+    parse_svg_with_limits(svg_bytes, None)
+}
+
+/// Like [`parse_svg`], under the given XML limits (None = default limits).
+pub(crate) fn parse_svg_with_limits(svg_bytes: &[u8], limits: Option<&XmlConfig>) -> Result<SvgExtractionResult> {
+    let svg =
+        std::str::from_utf8(svg_bytes).map_err(|e| KreuzbergError::parsing(format!("Invalid UTF-8 in SVG: {}", e)))?;
+    let doc = xml_parser::parse_document(svg, limits, |e| {
+        KreuzbergError::parsing(format!("Failed to parse SVG: {}", e))
+    })?;
+
+    let root = doc.root_element();
+    if root.tag_name().name() != "svg" {
+        return Err(KreuzbergError::parsing(format!(
+            "Not an SVG document: root element is <{}>",
+            root.tag_name().name()
+        )));
+    }
+
+    let mut texts = Vec::new();
+    collect_texts(&root, &mut texts);
+
+    Ok(SvgExtractionResult {
+        content: texts
+            .iter()
+            .map(|text| text.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n"),
+        title: child_text(&root, "title"),
+        description: child_text(&root, "desc"),
+        texts,
+    })
+}
+
+fn collect_texts(node: &Node<'_, '_>, texts: &mut Vec<SvgText>) {
+    for child in node.children().filter(Node::is_element) {
+        let name = child.tag_name().name();
+        if SKIPPED_ELEMENTS.contains(&name) {
+            continue;
+        }
+        if name == "text" {
+            let mut line = SvgText {
+                text: String::new(),
+                x: coordinate(&child, "x"),
+                y: coordinate(&child, "y"),
+            };
+            collect_runs(&child, &mut line, texts);
+            push_line(line, texts);
+        } else {
+            collect_texts(&child, texts);
+        }
+    }
+}
+
+/// Append the text runs of a `<text>` element to `line`, starting a new line at positioned `<tspan>`s.
+fn collect_runs(node: &Node<'_, '_>, line: &mut SvgText, texts: &mut Vec<SvgText>) {
+    for child in node.children() {
+        if child.is_text() {
+            line.text.push_str(child.text().unwrap_or_default());
+            continue;
+        }
+        if !child.is_element() || SKIPPED_ELEMENTS.contains(&child.tag_name().name()) {
+            continue;
+        }
+        if child.tag_name().name() == "tspan"
+            && (child.has_attribute("x") || child.has_attribute("y") || child.has_attribute("dy"))
+        {
+            let y = coordinate(&child, "y").or_else(|| Some(line.y? + coordinate(&child, "dy")?));
+            let next = SvgText {
+                text: String::new(),
+                x: coordinate(&child, "x").or(line.x),
+                y,
+            };
+            push_line(std::mem::replace(line, next), texts);
+        }
+        collect_runs(&child, line, texts);
+    }
+}
+
+/// Collapse whitespace as SVG renderers do and keep the line if any text remains.
+fn push_line(mut line: SvgText, texts: &mut Vec<SvgText>) {
+    line.text = line.text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if !line.text.is_empty() {
+        texts.push(line);
+    }
+}
+
+/// First value of a coordinate attribute, which may list one value per character.
+fn coordinate(node: &Node<'_, '_>, name: &str) -> Option<f64> {
+    let value = node
+        .attribute(name)?
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .find(|value| !value.is_empty())?;
+    value.trim_end_matches("px").parse().ok()
+}
+
+fn child_text(node: &Node<'_, '_>, name: &str) -> Option<String> {
+    let child = node.children().find(|child| child.has_tag_name(name))?;
+    let text: String = child
+        .descendants()
+        .filter(Node::is_text)
+        .filter_map(|n| n.text())
+        .collect();
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    (!text.is_empty()).then_some(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positioned_tspans_start_lines() {
+        let svg = br##"<?xml version="1.0"?>
+<!DOCTYPE svg PUBLIC "-//W3C//DTD SVG 1.1//EN" "http://www.w3.org/Graphics/SVG/1.1/DTD/svg11.dtd">
+<svg xmlns="http://www.w3.org/2000/svg" xmlns:xlink="http://www.w3.org/1999/xlink">
+    <defs><text id="unused">Template</text></defs>
+    <style>text { font: 12px sans-serif; }</style>
+    <g transform="translate(5 5)">
+        <text x="10 18 26" y="20px">
+            <title>tooltip</title>
+            Line one
+            <tspan x="10" dy="14">line <a xlink:href="#b">two</a></tspan>
+            <tspan dy="14">line three</tspan>
+        </text>
+    </g>
+    <text><textPath xlink:href="#curve">On a path</textPath></text>
+</svg>"##;
+
+        let result = parse_svg(svg).unwrap();
+        assert_eq!(result.content, "Line one\nline two\nline three\nOn a path");
+        let positions: Vec<_> = result.texts.iter().map(|text| (text.x, text.y)).collect();
+        assert_eq!(
+            positions,
+            vec![
+                (Some(10.0), Some(20.0)),
+                (Some(10.0), Some(34.0)),
+                (Some(10.0), Some(48.0)),
+                (None, None)
+            ]
+        );
+        assert_eq!(result.title, None);
+    }
+
+    #[test]
+    fn test_rejects_non_svg_documents() {
+        assert!(parse_svg(b"<html><body/></html>").is_err());
+        assert!(parse_svg(b"<svg><text>unclosed</svg>").is_err());
+    }
+}
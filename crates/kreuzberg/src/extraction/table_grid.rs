@@ -0,0 +1,118 @@
+//! Span resolution for tables of markup formats.
+//!
+//! HTML, DOCX and ODT describe merged cells by their row and column span. This
+//! module places such cells on a regular grid, repeating a spanning cell's text
+//! in every position it covers, so every row of a [`Table`](crate::types::Table)
+//! has the same number of columns.
+
+/// Largest column span honored, as in the HTML specification.
+pub(crate) const MAX_COLSPAN: usize = 1000;
+
+/// Largest row span honored, as in the HTML specification.
+pub(crate) const MAX_ROWSPAN: usize = 65534;
+
+/// A cell as written in the source, before span resolution.
+#[derive(Debug, Clone)]
+pub(crate) struct GridCell {
+    pub text: String,
+    /// Rows covered; 0 covers all remaining rows
+    pub row_span: usize,
+    /// Columns covered (at least 1)
+    pub col_span: usize,
+}
+
+impl GridCell {
+    #[cfg(feature = "office")]
+    pub fn new(text: String) -> Self {
+        Self {
+            text,
+            row_span: 1,
+            col_span: 1,
+        }
+    }
+}
+
+/// Place cells on a grid, resolving spans.
+///
+/// Cells fill the first free position of their row from left to right. Spans are
+/// clipped to the rows of the table; rows without cells are dropped and short rows
+/// are padded with empty cells. A cell's text is moved to its first position and
+/// copied only to the other positions it spans.
+pub(crate) fn build_grid(rows: Vec<Vec<GridCell>>) -> Vec<Vec<String>> {
+    let row_count = rows.len();
+    let mut grid: Vec<Vec<Option<String>>> = vec![Vec::new(); row_count];
+
+    for (row_index, row) in rows.into_iter().enumerate() {
+        let mut column = 0;
+        for cell in row {
+            while grid[row_index].get(column).is_some_and(Option::is_some) {
+                column += 1;
+            }
+            let col_span = cell.col_span.clamp(1, MAX_COLSPAN);
+            let last_row = match cell.row_span {
+                0 => row_count,
+                span => (row_index + span.min(MAX_ROWSPAN)).min(row_count),
+            };
+            for (offset, grid_row) in grid[row_index..last_row].iter_mut().enumerate() {
+                if grid_row.len() < column + col_span {
+                    grid_row.resize(column + col_span, None);
+                }
+                let first = if offset == 0 { column + 1 } else { column };
+                for slot in &mut grid_row[first..column + col_span] {
+                    *slot = Some(cell.text.clone());
+                }
+            }
+            grid[row_index][column] = Some(cell.text);
+            column += col_span;
+        }
+    }
+
+    let width = grid.iter().map(Vec::len).max().unwrap_or(0);
+    grid.into_iter()
+        .filter(|row| !row.is_empty())
+        .map(|row| {
+            let mut row: Vec<String> = row.into_iter().map(Option::unwrap_or_default).collect();
+            row.resize(width, String::new());
+            row
+        })
+        .collect()
+}
+
+/// Render cells as plain text: one line per row, cells separated by tabs.
+#[cfg(feature = "office")]
+pub(crate) fn cells_to_text(cells: &[Vec<String>]) -> String {
+    cells.iter().map(|row| row.join("\t")).collect::<Vec<_>>().join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(text: &str, row_span: usize, col_span: usize) -> GridCell {
+        GridCell {
+            text: text.to_string(),
+            row_span,
+            col_span,
+        }
+    }
+
+    #[test]
+    fn test_build_grid_resolves_spans() {
+        let rows = vec![
+            vec![cell("A", 2, 1), cell("B", 1, 1), cell("C", 1, 1)],
+            vec![cell("D", 1, 2)],
+            vec![cell("E", 1, 1), cell("F", 0, 1), cell("G", 1, 1)],
+            vec![cell("H", 1, 1), cell("I", 1, 1)],
+        ];
+        assert_eq!(
+            build_grid(rows.clone()),
+            vec![
+                vec!["A", "B", "C"],
+                vec!["A", "D", "D"],
+                vec!["E", "F", "G"],
+                vec!["H", "F", "I"],
+            ]
+        );
+        assert_eq!(cells_to_text(&build_grid(rows[3..4].to_vec())), "H\tI");
+    }
+}
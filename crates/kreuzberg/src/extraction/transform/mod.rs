@@ -248,6 +248,9 @@ mod tests {
             modified_at: None,
             created_by: None,
             modified_by: None,
+            producer: None,
+            page_count: None,
+            word_count: None,
             pages: None,
             format: None,
             image_preprocessing: None,
@@ -272,6 +275,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: Some(vec![
                 PageContent {
                     page_number: 1,
@@ -383,6 +387,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: Some(vec![PageContent {
                 page_number: 1,
                 content: "Some text".to_string(),
@@ -431,6 +436,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -463,6 +469,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
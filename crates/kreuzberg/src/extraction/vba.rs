@@ -0,0 +1,280 @@
+//! VBA project reading.
+//!
+//! Office documents store macros in a VBA project: an OLE compound file
+//! (`vbaProject.bin` in OOXML packages, a `Macros` or `_VBA_PROJECT_CUR` storage in
+//! legacy `.doc` and `.xls` files) whose `VBA/dir` stream lists the modules and whose
+//! module streams hold the source code, both compressed as described in [MS-OVBA].
+//!
+//! [MS-OVBA]: https://learn.microsoft.com/en-us/openspecs/office_file_formats/ms-ovba
+
+use crate::{KreuzbergError, Result};
+use std::io::{Cursor, Read};
+use std::path::{Path, PathBuf};
+
+/// Record ids of the `dir` stream used to locate module source.
+const PROJECTCODEPAGE: u16 = 0x0003;
+const PROJECTVERSION: u16 = 0x0009;
+const MODULENAME: u16 = 0x0019;
+const MODULESTREAMNAME: u16 = 0x001A;
+const MODULEOFFSET: u16 = 0x0031;
+const MODULE_TERMINATOR: u16 = 0x002B;
+
+/// Module name and source code of each module of the VBA projects in an OLE file.
+pub(crate) fn vba_modules(ole: &[u8]) -> Result<Vec<(String, String)>> {
+    let mut file = cfb::CompoundFile::open(Cursor::new(ole))
+        .map_err(|e| KreuzbergError::parsing(format!("Failed to read OLE file: {}", e)))?;
+
+    let dir_streams: Vec<PathBuf> = file
+        .walk()
+        .filter(|entry| entry.is_stream() && entry.name().eq_ignore_ascii_case("dir"))
+        .map(|entry| entry.path().to_path_buf())
+        .filter(|path| {
+            path.parent()
+                .and_then(Path::file_name)
+                .is_some_and(|name| name.eq_ignore_ascii_case("VBA"))
+        })
+        .collect();
+
+    let mut modules = Vec::new();
+    for dir_path in dir_streams {
+        let storage = dir_path.parent().unwrap_or(Path::new("/"));
+        let dir = decompress(&read_stream(&mut file, &dir_path)?)?;
+        let (codepage, entries) = parse_dir(&dir)?;
+        for entry in entries {
+            let Ok(stream) = read_stream(&mut file, &storage.join(&entry.stream_name)) else {
+                continue;
+            };
+            let Some(compressed) = stream.get(entry.offset..) else {
+                continue;
+            };
+            let source = decompress(compressed)?;
+            modules.push((entry.name, decode(&source, codepage)));
+        }
+    }
+    Ok(modules)
+}
+
+fn read_stream(file: &mut cfb::CompoundFile<Cursor<&[u8]>>, path: &Path) -> Result<Vec<u8>> {
+    let mut stream = file
+        .open_stream(path)
+        .map_err(|e| KreuzbergError::parsing(format!("Failed to open OLE stream {}: {}", path.display(), e)))?;
+    let mut data = Vec::new();
+    stream
+        .read_to_end(&mut data)
+        .map_err(|e| KreuzbergError::parsing(format!("Failed to read OLE stream {}: {}", path.display(), e)))?;
+    Ok(data)
+}
+
+struct ModuleEntry {
+    name: String,
+    stream_name: String,
+    offset: usize,
+}
+
+/// Code page and modules of a decompressed `dir` stream.
+fn parse_dir(dir: &[u8]) -> Result<(u16, Vec<ModuleEntry>)> {
+    let mut codepage = 1252;
+    let mut modules = Vec::new();
+    let mut name = None;
+    let mut stream_name = None;
+    let mut offset = 0;
+
+    let mut position = 0;
+    while position + 6 <= dir.len() {
+        let id = u16::from_le_bytes([dir[position], dir[position + 1]]);
+        let size = u32::from_le_bytes([
+            dir[position + 2],
+            dir[position + 3],
+            dir[position + 4],
+            dir[position + 5],
+        ]) as usize;
+        // PROJECTVERSION declares a size of 4 but is followed by 6 bytes.
+        let size = if id == PROJECTVERSION { 6 } else { size };
+        let data = dir
+            .get(position + 6..position + 6 + size)
+            .ok_or_else(|| KreuzbergError::parsing("Truncated VBA dir stream record"))?;
+        position += 6 + size;
+
+        match id {
+            PROJECTCODEPAGE if size >= 2 => codepage = u16::from_le_bytes([data[0], data[1]]),
+            MODULENAME => name = Some(decode(data, codepage)),
+            MODULESTREAMNAME => stream_name = Some(decode(data, codepage)),
+            MODULEOFFSET if size >= 4 => offset = u32::from_le_bytes([data[0], data[1], data[2], data[3]]) as usize,
+            MODULE_TERMINATOR => {
+                if let Some(name) = name.take() {
+                    let stream_name = stream_name.take().unwrap_or_else(|| name.clone());
+                    modules.push(ModuleEntry {
+                        name,
+                        stream_name,
+                        offset,
+                    });
+                }
+                offset = 0;
+            }
+            _ => {}
+        }
+    }
+    Ok((codepage, modules))
+}
+
+/// Decompress an MS-OVBA compressed container.
+pub(crate) fn decompress(container: &[u8]) -> Result<Vec<u8>> {
+    if container.first() != Some(&0x01) {
+        return Err(KreuzbergError::parsing("Invalid VBA compressed container signature"));
+    }
+
+    let mut output = Vec::with_capacity(container.len() * 2);
+    let mut position = 1;
+    while position + 2 <= container.len() {
+        let header = u16::from_le_bytes([container[position], container[position + 1]]);
+        let chunk_end = (position + (header & 0x0FFF) as usize + 3).min(container.len());
+        let compressed = header & 0x8000 != 0;
+        position += 2;
+
+        if !compressed {
+            let end = (position + 4096).min(container.len());
+            output.extend_from_slice(&container[position..end]);
+            position = end;
+            continue;
+        }
+
+        let chunk_start = output.len();
+        while position < chunk_end {
+            let flags = container[position];
+            position += 1;
+            for bit in 0..8 {
+                if position >= chunk_end {
+                    break;
+                }
+                if flags & (1 << bit) == 0 {
+                    output.push(container[position]);
+                    position += 1;
+                    continue;
+                }
+
+                if position + 2 > chunk_end {
+                    return Err(KreuzbergError::parsing("Truncated VBA copy token"));
+                }
+                let token = u16::from_le_bytes([container[position], container[position + 1]]);
+                position += 2;
+
+                let difference = output.len() - chunk_start;
+                let mut bit_count = 4;
+                while (1usize << bit_count) < difference {
+                    bit_count += 1;
+                }
+                let length_mask = 0xFFFFu16 >> bit_count;
+                let length = (token & length_mask) as usize + 3;
+                let offset = (token >> (16 - bit_count)) as usize + 1;
+                if offset > difference {
+                    return Err(KreuzbergError::parsing("Invalid VBA copy token offset"));
+                }
+                let start = output.len() - offset;
+                for i in 0..length {
+                    output.push(output[start + i]);
+                }
+            }
+        }
+        position = chunk_end;
+    }
+    Ok(output)
+}
+
+/// Decode text in the project's code page.
+fn decode(bytes: &[u8], codepage: u16) -> String {
+    let encoding = match codepage {
+        65001 => encoding_rs::UTF_8,
+        874 => encoding_rs::WINDOWS_874,
+        932 => encoding_rs::SHIFT_JIS,
+        936 => encoding_rs::GBK,
+        949 => encoding_rs::EUC_KR,
+        950 => encoding_rs::BIG5,
+        1250 => encoding_rs::WINDOWS_1250,
+        1251 => encoding_rs::WINDOWS_1251,
+        1253 => encoding_rs::WINDOWS_1253,
+        1254 => encoding_rs::WINDOWS_1254,
+        1255 => encoding_rs::WINDOWS_1255,
+        1256 => encoding_rs::WINDOWS_1256,
+        1257 => encoding_rs::WINDOWS_1257,
+        1258 => encoding_rs::WINDOWS_1258,
+        _ => encoding_rs::WINDOWS_1252,
+    };
+    encoding.decode_without_bom_handling(bytes).0.into_owned()
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// Compress `data` (at most 4096 bytes) as a single chunk of literal tokens.
+    fn compress_literals(data: &[u8]) -> Vec<u8> {
+        let mut chunk = Vec::new();
+        for tokens in data.chunks(8) {
+            chunk.push(0x00);
+            chunk.extend_from_slice(tokens);
+        }
+        let header = 0xB000 | (chunk.len() as u16 + 2 - 3);
+        let mut container = vec![0x01];
+        container.extend_from_slice(&header.to_le_bytes());
+        container.extend_from_slice(&chunk);
+        container
+    }
+
+    fn record(id: u16, data: &[u8]) -> Vec<u8> {
+        let mut record = id.to_le_bytes().to_vec();
+        record.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        record.extend_from_slice(data);
+        record
+    }
+
+    /// A `vbaProject.bin` with one module holding `source`.
+    pub(crate) fn vba_project(module: &str, source: &str) -> Vec<u8> {
+        let mut dir = record(PROJECTCODEPAGE, &1252u16.to_le_bytes());
+        dir.extend_from_slice(&PROJECTVERSION.to_le_bytes());
+        dir.extend_from_slice(&4u32.to_le_bytes());
+        dir.extend_from_slice(&[0; 6]);
+        dir.extend(record(MODULENAME, module.as_bytes()));
+        dir.extend(record(MODULESTREAMNAME, module.as_bytes()));
+        dir.extend(record(0x0032, &[]));
+        dir.extend(record(MODULEOFFSET, &4u32.to_le_bytes()));
+        dir.extend(record(MODULE_TERMINATOR, &[]));
+
+        let mut stream = vec![0xAA; 4];
+        stream.extend(compress_literals(source.as_bytes()));
+
+        let mut file = cfb::CompoundFile::create(Cursor::new(Vec::new())).unwrap();
+        file.create_storage("/VBA").unwrap();
+        file.create_stream("/VBA/dir")
+            .unwrap()
+            .write_all(&compress_literals(&dir))
+            .unwrap();
+        file.create_stream(format!("/VBA/{module}"))
+            .unwrap()
+            .write_all(&stream)
+            .unwrap();
+        file.flush().unwrap();
+        file.into_inner().into_inner()
+    }
+
+    #[test]
+    fn test_decompress_copy_tokens() {
+        // "abcabcabc": three literals, then a copy token of length 6 at offset 3.
+        // With 3 bytes decompressed, the token uses 4 offset bits and 12 length bits.
+        let token: u16 = (2 << 12) | (6 - 3);
+        let mut container = vec![0x01];
+        let chunk = [&[0b0000_1000u8, b'a', b'b', b'c'][..], &token.to_le_bytes()].concat();
+        container.extend_from_slice(&(0xB000 | (chunk.len() as u16 + 2 - 3)).to_le_bytes());
+        container.extend_from_slice(&chunk);
+
+        assert_eq!(decompress(&container).unwrap(), b"abcabcabc");
+        assert!(decompress(b"\x02").is_err());
+    }
+
+    #[test]
+    fn test_vba_modules() {
+        let source = "Attribute VB_Name = \"Module1\"\r\nSub AutoOpen()\r\n    Shell \"calc.exe\"\r\nEnd Sub\r\n";
+        let modules = vba_modules(&vba_project("Module1", source)).unwrap();
+        assert_eq!(modules, vec![("Module1".to_string(), source.to_string())]);
+    }
+}
@@ -0,0 +1,443 @@
+//! XML parsing under security limits.
+//!
+//! Every XML-based extractor goes through this module, whether it builds a DOM with
+//! [`parse_document`] or streams the document with `quick-xml` after
+//! [`check_document`]. The check enforces the [`XmlConfig`] limits before the parser
+//! sees the document:
+//!
+//! - documents with a DTD are rejected unless `allow_dtd` is set
+//! - documents declaring external entities are rejected unless
+//!   `allow_external_entities` is set; external entities are never resolved
+//! - internal entity expansion is counted without expanding anything, so "billion
+//!   laughs" and quadratic blowup documents fail before they are expanded
+//! - element nesting deeper than `max_depth` is rejected
+//!
+//! Violations are [`KreuzbergError::Security`] errors.
+
+use crate::core::config::XmlConfig;
+use crate::{KreuzbergError, Result};
+use ahash::AHashMap;
+use memchr::memmem;
+
+/// Entities predefined by XML, which are not declared and expand to a single character.
+const PREDEFINED_ENTITIES: &[&[u8]] = &[b"lt", b"gt", b"amp", b"apos", b"quot"];
+
+/// Parse `xml` into a DOM under the limits of `config` (None = default limits).
+///
+/// Syntax errors are turned into an error by `on_error`, so callers keep describing
+/// the part that failed to parse.
+pub(crate) fn parse_document<'a>(
+    xml: &'a str,
+    config: Option<&XmlConfig>,
+    on_error: impl FnOnce(roxmltree::Error) -> KreuzbergError,
+) -> Result<roxmltree::Document<'a>> {
+    check_document(xml.as_bytes(), config)?;
+    let options = roxmltree::ParsingOptions {
+        allow_dtd: true,
+        ..Default::default()
+    };
+    roxmltree::Document::parse_with_options(xml, options).map_err(on_error)
+}
+
+/// Like [`parse_document`], for parts whose parse failures are tolerated.
+pub(crate) fn try_parse_document<'a>(xml: &'a str, config: Option<&XmlConfig>) -> Option<roxmltree::Document<'a>> {
+    parse_document(xml, config, |e| KreuzbergError::parsing(e.to_string())).ok()
+}
+
+/// Check `xml` against the limits of `config` (None = default limits).
+pub(crate) fn check_document(xml: &[u8], config: Option<&XmlConfig>) -> Result<()> {
+    let default_config = XmlConfig::default();
+    let config = config.unwrap_or(&default_config);
+    let mut checker = Checker {
+        config,
+        entities: AHashMap::new(),
+        expansions: AHashMap::new(),
+        expanded: Expansion::default(),
+    };
+
+    let mut depth = 0usize;
+    let mut position = 0;
+    while let Some(offset) = memchr::memchr(b'<', &xml[position..]) {
+        let start = position + offset;
+        checker.count_references(&xml[position..start])?;
+
+        let rest = &xml[start..];
+        let length = if rest.starts_with(b"<!--") {
+            skip_past(rest, b"-->")
+        } else if rest.starts_with(b"<![CDATA[") {
+            skip_past(rest, b"]]>")
+        } else if rest.starts_with(b"<?") {
+            skip_past(rest, b"?>")
+        } else if rest.starts_with(b"<!DOCTYPE") {
+            if !config.allow_dtd {
+                return Err(KreuzbergError::security(
+                    "XML document declares a DTD, which xml.allow_dtd does not allow",
+                ));
+            }
+            let length = doctype_length(rest);
+            checker.declare_entities(&rest[..length])?;
+            length
+        } else if rest.starts_with(b"</") {
+            depth = depth.saturating_sub(1);
+            markup_length(rest)
+        } else {
+            let length = markup_length(rest);
+            let tag = &rest[..length];
+            checker.count_references(tag)?;
+            if !tag.ends_with(b"/>") {
+                depth += 1;
+                if depth > config.max_depth {
+                    return Err(KreuzbergError::security(format!(
+                        "XML document nests elements deeper than xml.max_depth ({})",
+                        config.max_depth
+                    )));
+                }
+            }
+            length
+        };
+        position = start + length;
+    }
+    checker.count_references(&xml[position..])
+}
+
+/// Entity references expanded and bytes produced by expanding an entity.
+#[derive(Debug, Default, Clone, Copy)]
+struct Expansion {
+    references: u64,
+    bytes: u64,
+}
+
+struct Checker<'a> {
+    config: &'a XmlConfig,
+    /// Replacement text of the declared internal general entities.
+    entities: AHashMap<&'a [u8], Vec<u8>>,
+    /// Expansion of each entity, once computed.
+    expansions: AHashMap<&'a [u8], Expansion>,
+    /// Expansion of the document so far.
+    expanded: Expansion,
+}
+
+impl<'a> Checker<'a> {
+    /// Record the entity declarations of a `DOCTYPE` declaration.
+    fn declare_entities(&mut self, doctype: &'a [u8]) -> Result<()> {
+        let Some(subset_start) = doctype.iter().position(|&b| b == b'[') else {
+            return Ok(());
+        };
+
+        let mut position = subset_start + 1;
+        while let Some(offset) = memchr::memchr(b'<', &doctype[position..]) {
+            let start = position + offset;
+            let rest = &doctype[start..];
+            if rest.starts_with(b"<!--") {
+                position = start + skip_past(rest, b"-->");
+                continue;
+            }
+            let length = markup_length(rest);
+            if let Some(declaration) = rest[..length].strip_prefix(b"<!ENTITY") {
+                self.declare_entity(declaration)?;
+            }
+            position = start + length;
+        }
+        Ok(())
+    }
+
+    /// Record an entity declaration, without its `<!ENTITY` keyword.
+    fn declare_entity(&mut self, declaration: &'a [u8]) -> Result<()> {
+        let declaration = trim_start(declaration);
+        let (parameter, declaration) = match declaration.strip_prefix(b"%") {
+            Some(rest) => (true, trim_start(rest)),
+            None => (false, declaration),
+        };
+        let name_length = declaration
+            .iter()
+            .position(|b| b.is_ascii_whitespace())
+            .unwrap_or(declaration.len());
+        let (name, definition) = declaration.split_at(name_length);
+        let definition = trim_start(definition);
+
+        if definition.starts_with(b"SYSTEM") || definition.starts_with(b"PUBLIC") {
+            if !self.config.allow_external_entities {
+                return Err(KreuzbergError::security(format!(
+                    "XML document declares the external entity '{}', which xml.allow_external_entities does not allow",
+                    String::from_utf8_lossy(name)
+                )));
+            }
+            return Ok(());
+        }
+
+        // Parameter entities are not expanded by the parsers; the first declaration
+        // of a general entity is binding.
+        if !parameter
+            && let Some(&quote) = definition.first()
+            && (quote == b'"' || quote == b'\'')
+            && let Some(end) = memchr::memchr(quote, &definition[1..])
+        {
+            self.entities
+                .entry(name)
+                .or_insert_with(|| decode_ampersands(&definition[1..end + 1]));
+        }
+        Ok(())
+    }
+
+    /// Add the expansion of the entity references in `text` to the document's.
+    fn count_references(&mut self, text: &[u8]) -> Result<()> {
+        if self.entities.is_empty() {
+            return Ok(());
+        }
+        for name in references(text) {
+            let Some(expansion) = self.expansion(name, &mut Vec::new())? else {
+                continue;
+            };
+            self.expanded.references = self.expanded.references.saturating_add(expansion.references + 1);
+            self.expanded.bytes = self.expanded.bytes.saturating_add(expansion.bytes);
+            self.check(self.expanded)?;
+        }
+        Ok(())
+    }
+
+    /// Expansion of the entity `name`, or None when it is not a declared internal entity.
+    fn expansion(&mut self, name: &[u8], stack: &mut Vec<&'a [u8]>) -> Result<Option<Expansion>> {
+        let Some((&key, value)) = self.entities.get_key_value(name) else {
+            return Ok(None);
+        };
+        if let Some(&expansion) = self.expansions.get(key) {
+            return Ok(Some(expansion));
+        }
+        if stack.contains(&key) {
+            return Err(KreuzbergError::security(format!(
+                "XML entity '{}' refers to itself",
+                String::from_utf8_lossy(key)
+            )));
+        }
+
+        let value = value.clone();
+        stack.push(key);
+        let mut expansion = Expansion {
+            references: 0,
+            bytes: value.len() as u64,
+        };
+        for reference in references(&value) {
+            if let Some(inner) = self.expansion(reference, stack)? {
+                expansion.references = expansion.references.saturating_add(inner.references + 1);
+                expansion.bytes = expansion.bytes.saturating_add(inner.bytes);
+                self.check(expansion)?;
+            }
+        }
+        stack.pop();
+
+        self.expansions.insert(key, expansion);
+        Ok(Some(expansion))
+    }
+
+    fn check(&self, expansion: Expansion) -> Result<()> {
+        if expansion.references > self.config.max_entity_expansions {
+            return Err(KreuzbergError::security(format!(
+                "XML document expands more entity references than xml.max_entity_expansions ({})",
+                self.config.max_entity_expansions
+            )));
+        }
+        if expansion.bytes > self.config.max_entity_expansion_bytes {
+            return Err(KreuzbergError::security(format!(
+                "XML entity expansion exceeds xml.max_entity_expansion_bytes ({} bytes)",
+                self.config.max_entity_expansion_bytes
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Names of the general entity references in `text`, skipping character references
+/// and predefined entities.
+fn references(text: &[u8]) -> impl Iterator<Item = &[u8]> {
+    memchr::memchr_iter(b'&', text).filter_map(move |start| {
+        let rest = &text[start + 1..];
+        let end = rest.iter().take(256).position(|&b| b == b';')?;
+        let name = &rest[..end];
+        (!name.is_empty() && name[0] != b'#' && !PREDEFINED_ENTITIES.contains(&name)).then_some(name)
+    })
+}
+
+/// Replace the character references to `&` in an entity value, which become entity
+/// references when the entity is expanded.
+fn decode_ampersands(value: &[u8]) -> Vec<u8> {
+    let mut decoded = Vec::with_capacity(value.len());
+    let mut position = 0;
+    while position < value.len() {
+        let rest = &value[position..];
+        let reference = [&b"&#38;"[..], b"&#x26;", b"&#X26;"]
+            .into_iter()
+            .find(|reference| rest.starts_with(reference));
+        match reference {
+            Some(reference) => {
+                decoded.push(b'&');
+                position += reference.len();
+            }
+            None => {
+                decoded.push(value[position]);
+                position += 1;
+            }
+        }
+    }
+    decoded
+}
+
+/// Length of `markup` up to the end of `terminator`, or all of it when unterminated.
+fn skip_past(markup: &[u8], terminator: &[u8]) -> usize {
+    memmem::find(markup, terminator).map_or(markup.len(), |end| end + terminator.len())
+}
+
+/// Length of the tag or declaration at the start of `markup`, up to the first `>`
+/// outside quotes.
+fn markup_length(markup: &[u8]) -> usize {
+    let mut quote = None;
+    for (index, &byte) in markup.iter().enumerate() {
+        match (quote, byte) {
+            (Some(open), byte) if byte == open => quote = None,
+            (Some(_), _) => {}
+            (None, b'"' | b'\'') => quote = Some(byte),
+            (None, b'>') => return index + 1,
+            _ => {}
+        }
+    }
+    markup.len()
+}
+
+/// Length of the `DOCTYPE` declaration at the start of `xml`, including its internal subset.
+fn doctype_length(xml: &[u8]) -> usize {
+    let mut in_subset = false;
+    let mut quote = None;
+    let mut index = 0;
+    while index < xml.len() {
+        let byte = xml[index];
+        match (quote, byte) {
+            (Some(open), byte) if byte == open => quote = None,
+            (Some(_), _) => {}
+            (None, b'<') if in_subset && xml[index..].starts_with(b"<!--") => {
+                index += skip_past(&xml[index..], b"-->");
+                continue;
+            }
+            (None, b'"' | b'\'') => quote = Some(byte),
+            (None, b'[') => in_subset = true,
+            (None, b']') => in_subset = false,
+            (None, b'>') if !in_subset => return index + 1,
+            _ => {}
+        }
+        index += 1;
+    }
+    xml.len()
+}
+
+fn trim_start(bytes: &[u8]) -> &[u8] {
+    let start = bytes
+        .iter()
+        .position(|b| !b.is_ascii_whitespace())
+        .unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn is_security_error(result: Result<()>) -> bool {
+        matches!(result, Err(KreuzbergError::Security { .. }))
+    }
+
+    #[test]
+    fn test_check_document_accepts_ordinary_documents() {
+        let xml = br#"<?xml version="1.0"?>
+            <!DOCTYPE note [
+                <!ENTITY author "Jane &amp; John">
+                <!-- <!ENTITY commented SYSTEM "file:///etc/passwd"> -->
+            ]>
+            <note by="&author;"><![CDATA[<not a tag>]]><to>&author; &#169; &lt;</to><br/></note>"#;
+        assert!(check_document(xml, None).is_ok());
+        assert!(check_document(b"<a><b>text</b></a>", None).is_ok());
+    }
+
+    #[test]
+    fn test_check_document_rejects_billion_laughs() {
+        let xml = br#"<?xml version="1.0"?>
+            <!DOCTYPE lolz [
+              <!ENTITY lol "lol">
+              <!ENTITY lol1 "&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;&lol;">
+              <!ENTITY lol2 "&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;&lol1;">
+              <!ENTITY lol3 "&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;&lol2;">
+              <!ENTITY lol4 "&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;&lol3;">
+              <!ENTITY lol5 "&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;&lol4;">
+            ]>
+            <lolz>&lol5;</lolz>"#;
+        assert!(is_security_error(check_document(xml, None)));
+
+        let escaped = br#"<!DOCTYPE a [
+              <!ENTITY a "aaaaaaaaaa">
+              <!ENTITY b "&#38;a;&#38;a;&#38;a;&#38;a;&#38;a;&#38;a;&#38;a;&#38;a;&#38;a;&#38;a;">
+            ]><a>&b;</a>"#;
+        let config = XmlConfig {
+            max_entity_expansions: 5,
+            ..Default::default()
+        };
+        assert!(is_security_error(check_document(escaped, Some(&config))));
+    }
+
+    #[test]
+    fn test_check_document_rejects_quadratic_blowup() {
+        let value = "a".repeat(50_000);
+        let references = "&a;".repeat(100);
+        let xml = format!("<!DOCTYPE x [<!ENTITY a \"{value}\">]><x>{references}</x>");
+        assert!(is_security_error(check_document(xml.as_bytes(), None)));
+    }
+
+    #[test]
+    fn test_check_document_rejects_recursive_entities() {
+        let xml = br#"<!DOCTYPE x [<!ENTITY a "&b;"><!ENTITY b "&a;">]><x>&a;</x>"#;
+        assert!(is_security_error(check_document(xml, None)));
+    }
+
+    #[test]
+    fn test_check_document_external_entities_and_dtds() {
+        let xxe = br#"<!DOCTYPE x [<!ENTITY xxe SYSTEM "file:///etc/passwd">]><x>&xxe;</x>"#;
+        assert!(is_security_error(check_document(xxe, None)));
+        let config = XmlConfig {
+            allow_external_entities: true,
+            ..Default::default()
+        };
+        assert!(check_document(xxe, Some(&config)).is_ok());
+
+        let external_dtd = br#"<!DOCTYPE book PUBLIC "-//OASIS//DTD DocBook XML V4.5//EN" "docbookx.dtd"><book/>"#;
+        assert!(check_document(external_dtd, None).is_ok());
+        let config = XmlConfig {
+            allow_dtd: false,
+            ..Default::default()
+        };
+        assert!(is_security_error(check_document(external_dtd, Some(&config))));
+    }
+
+    #[test]
+    fn test_check_document_max_depth() {
+        let xml = format!("{}{}", "<a>".repeat(20), "</a>".repeat(20));
+        let config = XmlConfig {
+            max_depth: 10,
+            ..Default::default()
+        };
+        assert!(is_security_error(check_document(xml.as_bytes(), Some(&config))));
+        assert!(check_document(xml.as_bytes(), None).is_ok());
+
+        let siblings = "<a></a><b/>".repeat(20);
+        assert!(check_document(format!("<r>{siblings}</r>").as_bytes(), Some(&config)).is_ok());
+    }
+
+    #[test]
+    fn test_parse_document() {
+        let doc = parse_document(
+            r#"<!DOCTYPE x [<!ENTITY name "World">]><x>Hello &name;</x>"#,
+            None,
+            |e| KreuzbergError::parsing(e.to_string()),
+        )
+        .unwrap();
+        assert_eq!(doc.root_element().text(), Some("Hello World"));
+
+        let result = parse_document("<x>", None, |e| KreuzbergError::parsing(format!("Bad part: {e}")));
+        assert!(matches!(result, Err(KreuzbergError::Parsing { message, .. }) if message.starts_with("Bad part")));
+    }
+}
@@ -2,24 +2,24 @@
 
 use crate::Result;
 use crate::core::config::ExtractionConfig;
+use crate::core::io::Spill;
 use crate::extraction::archive::{
-    ArchiveMetadata as ExtractedMetadata, extract_7z_metadata, extract_7z_text_content, extract_tar_metadata,
-    extract_tar_text_content, extract_zip_metadata, extract_zip_text_content,
+    ArchiveMetadata as ExtractedMetadata, extract_7z_metadata, extract_tar_metadata, extract_zip_metadata,
 };
+use crate::extraction::nested::{ContainerKind, Nesting, archive_text};
 use crate::plugins::{DocumentExtractor, Plugin};
 use crate::types::{ArchiveMetadata, ExtractionResult, Metadata};
 use ahash::AHashMap;
 use async_trait::async_trait;
 use std::borrow::Cow;
-use std::collections::HashMap;
 
-/// Build an ExtractionResult from archive metadata and text contents.
+/// Build an ExtractionResult from archive metadata and text output.
 ///
 /// This helper function eliminates duplication across ZIP/TAR/7Z extractors by centralizing
 /// the logic for transforming extracted metadata into the final result structure.
 fn build_archive_result(
     extraction_metadata: ExtractedMetadata,
-    text_contents: HashMap<String, String>,
+    content: String,
     format_name: &'static str,
     mime_type: &str,
 ) -> ExtractionResult {
@@ -51,24 +51,8 @@ fn build_archive_result(
         .collect();
     additional.insert(Cow::Borrowed("files"), serde_json::json!(file_details));
 
-    let mut output = format!(
-        "{} Archive ({} files, {} bytes)\n\n",
-        format_name, extraction_metadata.file_count, extraction_metadata.total_size
-    );
-    output.push_str("Files:\n");
-    for entry in &extraction_metadata.file_list {
-        output.push_str(&format!("- {} ({} bytes)\n", entry.path, entry.size));
-    }
-
-    if !text_contents.is_empty() {
-        output.push_str("\n\nText File Contents:\n\n");
-        for (path, content) in text_contents {
-            output.push_str(&format!("=== {} ===\n{}\n\n", path, content));
-        }
-    }
-
     ExtractionResult {
-        content: output,
+        content,
         mime_type: mime_type.to_string().into(),
         metadata: Metadata {
             format: Some(crate::types::FormatMetadata::Archive(archive_metadata)),
@@ -81,6 +65,7 @@ fn build_archive_result(
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     }
 }
@@ -132,7 +117,7 @@ impl Plugin for ZipExtractor {
 #[async_trait]
 impl DocumentExtractor for ZipExtractor {
     #[cfg_attr(feature = "otel", tracing::instrument(
-        skip(self, content, _config),
+        skip(self, content, config),
         fields(
             extractor.name = self.name(),
             content.size_bytes = content.len(),
@@ -142,16 +127,26 @@ impl DocumentExtractor for ZipExtractor {
         &self,
         content: &[u8],
         mime_type: &str,
-        _config: &ExtractionConfig,
+        config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
         let extraction_metadata = extract_zip_metadata(content)?;
-        let text_contents = extract_zip_text_content(content)?;
-        Ok(build_archive_result(
-            extraction_metadata,
-            text_contents,
-            "ZIP",
-            mime_type,
-        ))
+        let spill = Spill::new(config.io.as_ref());
+        let mut nesting = config
+            .containers
+            .as_ref()
+            .map(|containers| Nesting::new(containers, content).with_spill(spill.clone()));
+        let text = archive_text(
+            ContainerKind::Zip,
+            content,
+            &extraction_metadata,
+            nesting.as_mut(),
+            &spill,
+        )?;
+        let mut result = build_archive_result(extraction_metadata, text, "ZIP", mime_type);
+        if let Some(nesting) = nesting {
+            nesting.record(&mut result.metadata);
+        }
+        Ok(result)
     }
 
     fn supported_mime_types(&self) -> &[&str] {
@@ -161,6 +156,10 @@ impl DocumentExtractor for ZipExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["archives"]
+    }
 }
 
 /// TAR archive extractor.
@@ -210,7 +209,7 @@ impl Plugin for TarExtractor {
 #[async_trait]
 impl DocumentExtractor for TarExtractor {
     #[cfg_attr(feature = "otel", tracing::instrument(
-        skip(self, content, _config),
+        skip(self, content, config),
         fields(
             extractor.name = self.name(),
             content.size_bytes = content.len(),
@@ -220,16 +219,26 @@ impl DocumentExtractor for TarExtractor {
         &self,
         content: &[u8],
         mime_type: &str,
-        _config: &ExtractionConfig,
+        config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
         let extraction_metadata = extract_tar_metadata(content)?;
-        let text_contents = extract_tar_text_content(content)?;
-        Ok(build_archive_result(
-            extraction_metadata,
-            text_contents,
-            "TAR",
-            mime_type,
-        ))
+        let spill = Spill::new(config.io.as_ref());
+        let mut nesting = config
+            .containers
+            .as_ref()
+            .map(|containers| Nesting::new(containers, content).with_spill(spill.clone()));
+        let text = archive_text(
+            ContainerKind::Tar,
+            content,
+            &extraction_metadata,
+            nesting.as_mut(),
+            &spill,
+        )?;
+        let mut result = build_archive_result(extraction_metadata, text, "TAR", mime_type);
+        if let Some(nesting) = nesting {
+            nesting.record(&mut result.metadata);
+        }
+        Ok(result)
     }
 
     fn supported_mime_types(&self) -> &[&str] {
@@ -244,6 +253,10 @@ impl DocumentExtractor for TarExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["archives"]
+    }
 }
 
 /// 7z archive extractor.
@@ -293,7 +306,7 @@ impl Plugin for SevenZExtractor {
 #[async_trait]
 impl DocumentExtractor for SevenZExtractor {
     #[cfg_attr(feature = "otel", tracing::instrument(
-        skip(self, content, _config),
+        skip(self, content, config),
         fields(
             extractor.name = self.name(),
             content.size_bytes = content.len(),
@@ -303,16 +316,26 @@ impl DocumentExtractor for SevenZExtractor {
         &self,
         content: &[u8],
         mime_type: &str,
-        _config: &ExtractionConfig,
+        config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
         let extraction_metadata = extract_7z_metadata(content)?;
-        let text_contents = extract_7z_text_content(content)?;
-        Ok(build_archive_result(
-            extraction_metadata,
-            text_contents,
-            "7Z",
-            mime_type,
-        ))
+        let spill = Spill::new(config.io.as_ref());
+        let mut nesting = config
+            .containers
+            .as_ref()
+            .map(|containers| Nesting::new(containers, content).with_spill(spill.clone()));
+        let text = archive_text(
+            ContainerKind::SevenZ,
+            content,
+            &extraction_metadata,
+            nesting.as_mut(),
+            &spill,
+        )?;
+        let mut result = build_archive_result(extraction_metadata, text, "7Z", mime_type);
+        if let Some(nesting) = nesting {
+            nesting.record(&mut result.metadata);
+        }
+        Ok(result)
     }
 
     fn supported_mime_types(&self) -> &[&str] {
@@ -322,6 +345,10 @@ impl DocumentExtractor for SevenZExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["archives"]
+    }
 }
 
 #[cfg(test)]
@@ -430,6 +457,64 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_zip_extractor_spills_large_text() {
+        let large = "line of a large text dump\n".repeat(2_000);
+        let mut cursor = Cursor::new(Vec::new());
+        {
+            let mut zip = ZipWriter::new(&mut cursor);
+            let options = FileOptions::<'_, ()>::default();
+            for (name, data) in [
+                ("a.txt", b"first".as_slice()),
+                ("dump.log", large.as_bytes()),
+                ("binary.txt", &[0xff, 0xfe]),
+                ("z.md", b"# last"),
+            ] {
+                zip.start_file(name, options).unwrap();
+                zip.write_all(data).unwrap();
+            }
+            zip.finish().unwrap();
+        }
+        let bytes = cursor.into_inner();
+
+        let in_memory = ZipExtractor::new()
+            .extract_bytes(&bytes, "application/zip", &ExtractionConfig::default())
+            .await
+            .unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let mut spilled_config = ExtractionConfig {
+            io: Some(crate::core::config::IoConfig {
+                spill_threshold_bytes: 1024,
+                temp_dir: Some(temp_dir.path().to_path_buf()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let spilled = ZipExtractor::new()
+            .extract_bytes(&bytes, "application/zip", &spilled_config)
+            .await
+            .unwrap();
+        // The workspace of the spilled text is removed after extraction.
+        assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+
+        spilled_config.io.as_mut().unwrap().temp_quota_bytes = Some(4096);
+        assert!(
+            ZipExtractor::new()
+                .extract_bytes(&bytes, "application/zip", &spilled_config)
+                .await
+                .is_err()
+        );
+
+        assert_eq!(spilled.content, in_memory.content);
+        assert!(
+            spilled
+                .content
+                .contains(&format!("=== dump.log ===\n{}\n\n=== z.md ===\n# last", large))
+        );
+        assert!(spilled.content.contains("=== a.txt ===\nfirst\n\n=== dump.log"));
+        assert!(!spilled.content.contains("=== binary.txt ==="));
+    }
+
     #[test]
     fn test_zip_plugin_interface() {
         let extractor = ZipExtractor::new();
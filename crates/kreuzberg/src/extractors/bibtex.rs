@@ -175,6 +175,7 @@ impl DocumentExtractor for BibtexExtractor {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -186,6 +187,10 @@ impl DocumentExtractor for BibtexExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["office"]
+    }
 }
 
 #[cfg(all(test, feature = "office"))]
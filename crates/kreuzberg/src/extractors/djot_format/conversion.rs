@@ -159,6 +159,7 @@ mod tests {
             chunks: None,
             images: None,
             pages: None,
+            scripts: None,
             djot_content: Some(DjotContent {
                 plain_text: "Test content".to_string(),
                 blocks: vec![FormattedBlock {
@@ -201,6 +202,7 @@ mod tests {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
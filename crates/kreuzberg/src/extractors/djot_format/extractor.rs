@@ -78,7 +78,7 @@ impl DocumentExtractor for DjotExtractor {
         mime_type: &str,
         _config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
-        let text = String::from_utf8_lossy(content).into_owned();
+        let text = crate::text::utf8_validation::decode_lossy(content);
 
         let (yaml, remaining_content) = crate::extractors::frontmatter_utils::extract_frontmatter(&text);
 
@@ -114,6 +114,7 @@ impl DocumentExtractor for DjotExtractor {
             images: None,
             pages: None,
             djot_content: Some(djot_content),
+            scripts: None,
             elements: None,
         })
     }
@@ -371,10 +371,8 @@ impl DocumentExtractor for DocbookExtractor {
         mime_type: &str,
         config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
-        let _ = config;
-        let docbook_content = std::str::from_utf8(content)
-            .map(|s| s.to_string())
-            .unwrap_or_else(|_| String::from_utf8_lossy(content).to_string());
+        crate::extraction::xml_parser::check_document(content, config.xml.as_ref())?;
+        let docbook_content = crate::text::utf8_validation::decode_lossy(content);
 
         let (extracted_content, title, author, date, tables) = parse_docbook_single_pass(&docbook_content)?;
 
@@ -406,6 +404,7 @@ impl DocumentExtractor for DocbookExtractor {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -421,7 +420,7 @@ impl DocumentExtractor for DocbookExtractor {
         )
     )]
     async fn extract_file(&self, path: &Path, mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
-        let bytes = tokio::fs::read(path).await?;
+        let bytes = crate::core::io::open_file_async(path, config.io.as_ref()).await?;
         self.extract_bytes(&bytes, mime_type, config).await
     }
 
@@ -432,6 +431,10 @@ impl DocumentExtractor for DocbookExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["xml"]
+    }
 }
 
 #[cfg(test)]
@@ -1,23 +1,27 @@
 #![cfg(all(feature = "tokio-runtime", feature = "office"))]
 
-//! DOCX extractor using docx-lite for high-performance text extraction.
+//! DOCX extractor with document-order text, list numbering and table extraction.
 //!
 //! Supports: Microsoft Word (.docx)
 
 use crate::Result;
-use crate::core::config::ExtractionConfig;
-use crate::extraction::{cells_to_markdown, office_metadata};
+use crate::core::config::{ExtractionConfig, MetadataMode};
+use crate::extraction::charts::chart_summary;
+use crate::extraction::docx_body::{DocxBody, extract_docx_body};
+use crate::extraction::office_metadata;
 use crate::plugins::{DocumentExtractor, Plugin};
-use crate::types::{ExtractionResult, Metadata, PageBoundary, PageInfo, PageStructure, PageUnitType, Table};
+use crate::types::{ExtractionResult, Metadata, PageInfo, PageStructure, PageUnitType};
 use ahash::AHashMap;
 use async_trait::async_trait;
 use std::borrow::Cow;
-use std::io::Cursor;
+use std::io::{Cursor, Read, Seek};
 
-/// High-performance DOCX extractor using docx-lite.
+/// High-performance DOCX extractor.
 ///
 /// This extractor provides:
-/// - Fast text extraction via streaming XML parsing (~160 MB/s average)
+/// - Text extraction in document order with rendered list numbers (`1.`, `a.`, `i.`)
+/// - Table extraction with merged cells resolved, rendered per `DocxConfig::tables`
+/// - Chart series data as tables, summarized in the `charts` metadata entry
 /// - Comprehensive metadata extraction (core.xml, app.xml, custom.xml)
 pub struct DocxExtractor;
 
@@ -52,7 +56,7 @@ impl Plugin for DocxExtractor {
     }
 
     fn description(&self) -> &str {
-        "High-performance DOCX text extraction using docx-lite with metadata support"
+        "High-performance DOCX text and table extraction with metadata support"
     }
 
     fn author(&self) -> &str {
@@ -60,55 +64,10 @@ impl Plugin for DocxExtractor {
     }
 }
 
-/// Convert docx-lite table to Kreuzberg Table struct with markdown representation.
-///
-/// # Arguments
-/// * `docx_table` - The table from docx-lite library
-/// * `table_index` - Index of the table in the document (used as page_number)
-///
-/// # Returns
-/// * `Table` - Converted table with cells and markdown representation
-fn convert_docx_table_to_table(docx_table: &docx_lite::Table, table_index: usize) -> Table {
-    let cells: Vec<Vec<String>> = docx_table
-        .rows
-        .iter()
-        .map(|row| {
-            row.cells
-                .iter()
-                .map(|cell| {
-                    cell.paragraphs
-                        .iter()
-                        .map(|para| para.to_text())
-                        .collect::<Vec<_>>()
-                        .join(" ")
-                        .trim()
-                        .to_string()
-                })
-                .collect()
-        })
-        .collect();
-
-    let markdown = cells_to_markdown(&cells);
-
-    Table {
-        cells,
-        markdown,
-        page_number: table_index + 1,
-    }
-}
-
-/// Convert 2D cell data to markdown table format.
-///
-/// # Arguments
-/// * `cells` - 2D vector of cell strings (rows × columns)
-///
-/// # Returns
-/// * `String` - Markdown formatted table
-
 #[async_trait]
 impl DocumentExtractor for DocxExtractor {
     #[cfg_attr(feature = "otel", tracing::instrument(
-        skip(self, content, _config),
+        skip(self, content, config),
         fields(
             extractor.name = self.name(),
             content.size_bytes = content.len(),
@@ -118,170 +77,56 @@ impl DocumentExtractor for DocxExtractor {
         &self,
         content: &[u8],
         mime_type: &str,
-        _config: &ExtractionConfig,
+        config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
-        let (text, tables, page_boundaries) = if crate::core::batch_mode::is_batch_mode() {
+        let table_mode = config.docx.as_ref().map(|docx| docx.tables).unwrap_or_default();
+        let body = if crate::core::batch_mode::is_batch_mode() {
             let content_owned = content.to_vec();
             let span = tracing::Span::current();
-            tokio::task::spawn_blocking(
-                move || -> crate::error::Result<(String, Vec<Table>, Option<Vec<PageBoundary>>)> {
-                    let _guard = span.entered();
-                    let cursor = Cursor::new(&content_owned);
-                    let doc = docx_lite::parse_document(cursor)
-                        .map_err(|e| crate::error::KreuzbergError::parsing(format!("DOCX parsing failed: {}", e)))?;
-
-                    let text = doc.extract_text();
-
-                    let tables: Vec<Table> = doc
-                        .tables
-                        .iter()
-                        .enumerate()
-                        .map(|(idx, table)| convert_docx_table_to_table(table, idx))
-                        .collect();
-
-                    let page_boundaries = crate::extraction::docx::detect_page_breaks_from_docx(&content_owned)?;
-
-                    Ok((text, tables, page_boundaries))
-                },
-            )
+            tokio::task::spawn_blocking(move || {
+                let _guard = span.entered();
+                extract_docx_body(&content_owned, table_mode)
+            })
             .await
             .map_err(|e| crate::error::KreuzbergError::parsing(format!("DOCX extraction task failed: {}", e)))??
         } else {
-            let cursor = Cursor::new(content);
-            let doc = docx_lite::parse_document(cursor)
-                .map_err(|e| crate::error::KreuzbergError::parsing(format!("DOCX parsing failed: {}", e)))?;
-
-            let text = doc.extract_text();
-
-            let tables: Vec<Table> = doc
-                .tables
-                .iter()
-                .enumerate()
-                .map(|(idx, table)| convert_docx_table_to_table(table, idx))
-                .collect();
-
-            let page_boundaries = crate::extraction::docx::detect_page_breaks_from_docx(content)?;
-
-            (text, tables, page_boundaries)
+            extract_docx_body(content, table_mode)?
         };
-
-        let mut archive = if crate::core::batch_mode::is_batch_mode() {
-            let content_owned = content.to_vec();
-            let span = tracing::Span::current();
-            tokio::task::spawn_blocking(move || -> crate::error::Result<_> {
-                let _guard = span.entered();
+        let page_boundaries = body.page_boundaries();
+        let DocxBody {
+            text, tables, charts, ..
+        } = body;
+
+        let (mut metadata_map, parsed_keywords) = if config.metadata.includes_basic() {
+            let mut archive = if crate::core::batch_mode::is_batch_mode() {
+                let content_owned = content.to_vec();
+                let span = tracing::Span::current();
+                tokio::task::spawn_blocking(move || -> crate::error::Result<_> {
+                    let _guard = span.entered();
+                    let cursor = Cursor::new(content_owned);
+                    zip::ZipArchive::new(cursor).map_err(|e| {
+                        crate::error::KreuzbergError::parsing(format!("Failed to open ZIP archive: {}", e))
+                    })
+                })
+                .await
+                .map_err(|e| crate::error::KreuzbergError::parsing(format!("Task join error: {}", e)))??
+            } else {
+                let content_owned = content.to_vec();
                 let cursor = Cursor::new(content_owned);
                 zip::ZipArchive::new(cursor)
-                    .map_err(|e| crate::error::KreuzbergError::parsing(format!("Failed to open ZIP archive: {}", e)))
-            })
-            .await
-            .map_err(|e| crate::error::KreuzbergError::parsing(format!("Task join error: {}", e)))??
+                    .map_err(|e| crate::error::KreuzbergError::parsing(format!("Failed to open ZIP archive: {}", e)))?
+            };
+            read_properties(&mut archive, config.metadata)
         } else {
-            let content_owned = content.to_vec();
-            let cursor = Cursor::new(content_owned);
-            zip::ZipArchive::new(cursor)
-                .map_err(|e| crate::error::KreuzbergError::parsing(format!("Failed to open ZIP archive: {}", e)))?
+            (AHashMap::new(), None)
         };
 
-        let mut metadata_map = AHashMap::new();
-        let mut parsed_keywords: Option<Vec<String>> = None;
-
-        if let Ok(core) = office_metadata::extract_core_properties(&mut archive) {
-            if let Some(title) = core.title {
-                metadata_map.insert(Cow::Borrowed("title"), serde_json::Value::String(title));
-            }
-            if let Some(creator) = core.creator {
-                metadata_map.insert(
-                    Cow::Borrowed("authors"),
-                    serde_json::Value::Array(vec![serde_json::Value::String(creator.clone())]),
-                );
-                metadata_map.insert(Cow::Borrowed("created_by"), serde_json::Value::String(creator));
-            }
-            if let Some(subject) = core.subject {
-                metadata_map.insert(Cow::Borrowed("subject"), serde_json::Value::String(subject));
-            }
-            if let Some(keywords) = core.keywords {
-                // Parse comma-separated keywords into Vec<String>
-                parsed_keywords = Some(
-                    keywords
-                        .split(',')
-                        .map(|s| s.trim().to_string())
-                        .filter(|s| !s.is_empty())
-                        .collect(),
-                );
-            }
-            if let Some(description) = core.description {
-                metadata_map.insert(Cow::Borrowed("description"), serde_json::Value::String(description));
-            }
-            if let Some(modified_by) = core.last_modified_by {
-                metadata_map.insert(Cow::Borrowed("modified_by"), serde_json::Value::String(modified_by));
-            }
-            if let Some(created) = core.created {
-                metadata_map.insert(Cow::Borrowed("created_at"), serde_json::Value::String(created));
-            }
-            if let Some(modified) = core.modified {
-                metadata_map.insert(Cow::Borrowed("modified_at"), serde_json::Value::String(modified));
-            }
-            if let Some(revision) = core.revision {
-                metadata_map.insert(Cow::Borrowed("revision"), serde_json::Value::String(revision));
-            }
-            if let Some(category) = core.category {
-                metadata_map.insert(Cow::Borrowed("category"), serde_json::Value::String(category));
-            }
-            if let Some(content_status) = core.content_status {
-                metadata_map.insert(
-                    Cow::Borrowed("content_status"),
-                    serde_json::Value::String(content_status),
-                );
-            }
-            if let Some(language) = core.language {
-                metadata_map.insert(Cow::Borrowed("language"), serde_json::Value::String(language));
-            }
-        }
-
-        if let Ok(app) = office_metadata::extract_docx_app_properties(&mut archive) {
-            if let Some(pages) = app.pages {
-                metadata_map.insert(Cow::Borrowed("page_count"), serde_json::Value::Number(pages.into()));
-            }
-            if let Some(words) = app.words {
-                metadata_map.insert(Cow::Borrowed("word_count"), serde_json::Value::Number(words.into()));
-            }
-            if let Some(chars) = app.characters {
-                metadata_map.insert(
-                    Cow::Borrowed("character_count"),
-                    serde_json::Value::Number(chars.into()),
-                );
-            }
-            if let Some(lines) = app.lines {
-                metadata_map.insert(Cow::Borrowed("line_count"), serde_json::Value::Number(lines.into()));
-            }
-            if let Some(paragraphs) = app.paragraphs {
-                metadata_map.insert(
-                    Cow::Borrowed("paragraph_count"),
-                    serde_json::Value::Number(paragraphs.into()),
-                );
-            }
-            if let Some(template) = app.template {
-                metadata_map.insert(Cow::Borrowed("template"), serde_json::Value::String(template));
-            }
-            if let Some(company) = app.company {
-                metadata_map.insert(Cow::Borrowed("company"), serde_json::Value::String(company));
-            }
-            if let Some(time) = app.total_time {
-                metadata_map.insert(
-                    Cow::Borrowed("total_editing_time_minutes"),
-                    serde_json::Value::Number(time.into()),
-                );
-            }
-            if let Some(application) = app.application {
-                metadata_map.insert(Cow::Borrowed("application"), serde_json::Value::String(application));
-            }
-        }
-
-        if let Ok(custom) = office_metadata::extract_custom_properties(&mut archive) {
-            for (key, value) in custom {
-                metadata_map.insert(Cow::Owned(format!("custom_{}", key)), value);
-            }
+        if config.metadata.includes_full() && !charts.is_empty() {
+            let summaries = charts
+                .iter()
+                .map(|(index, chart)| chart_summary(chart, *index))
+                .collect();
+            metadata_map.insert(Cow::Borrowed("charts"), serde_json::Value::Array(summaries));
         }
 
         let page_structure = if let Some(boundaries) = page_boundaries {
@@ -299,6 +144,8 @@ impl DocumentExtractor for DocxExtractor {
                             image_count: None,
                             table_count: None,
                             hidden: None,
+                            rotation: None,
+                            blank: None,
                         })
                         .collect(),
                 ),
@@ -322,10 +169,27 @@ impl DocumentExtractor for DocxExtractor {
             chunks: None,
             images: Some(vec![]),
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
 
+    async fn extract_metadata(
+        &self,
+        content: &[u8],
+        _mime_type: &str,
+        config: &ExtractionConfig,
+    ) -> Result<Option<Metadata>> {
+        let mut archive = zip::ZipArchive::new(Cursor::new(content))
+            .map_err(|e| crate::error::KreuzbergError::parsing(format!("Failed to open ZIP archive: {}", e)))?;
+        let (additional, keywords) = read_properties(&mut archive, config.metadata);
+        Ok(Some(Metadata {
+            keywords,
+            additional,
+            ..Default::default()
+        }))
+    }
+
     fn supported_mime_types(&self) -> &[&str] {
         &["application/vnd.openxmlformats-officedocument.wordprocessingml.document"]
     }
@@ -333,6 +197,105 @@ impl DocumentExtractor for DocxExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["office", "tokio-runtime"]
+    }
+}
+
+/// Read the document properties of a DOCX archive into metadata entries and keywords.
+///
+/// Custom properties are only read with [`MetadataMode::Full`].
+fn read_properties<R: Read + Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    mode: MetadataMode,
+) -> (AHashMap<Cow<'static, str>, serde_json::Value>, Option<Vec<String>>) {
+    let mut metadata_map = AHashMap::new();
+    let mut parsed_keywords: Option<Vec<String>> = None;
+
+    if let Ok(core) = office_metadata::extract_core_properties(archive) {
+        for (key, value) in core.document_property_entries() {
+            metadata_map.insert(Cow::Borrowed(key), serde_json::Value::String(value));
+        }
+        if let Some(title) = core.title {
+            metadata_map.insert(Cow::Borrowed("title"), serde_json::Value::String(title));
+        }
+        if let Some(creator) = core.creator {
+            metadata_map.insert(
+                Cow::Borrowed("authors"),
+                serde_json::Value::Array(vec![serde_json::Value::String(creator.clone())]),
+            );
+            metadata_map.insert(Cow::Borrowed("created_by"), serde_json::Value::String(creator));
+        }
+        if let Some(subject) = core.subject {
+            metadata_map.insert(Cow::Borrowed("subject"), serde_json::Value::String(subject));
+        }
+        if let Some(keywords) = core.keywords {
+            // Parse comma-separated keywords into Vec<String>
+            parsed_keywords = Some(
+                keywords
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+            );
+        }
+    }
+
+    if let Ok(app) = office_metadata::extract_docx_app_properties(archive) {
+        if let Some(pages) = app.pages {
+            metadata_map.insert(Cow::Borrowed("page_count"), serde_json::Value::Number(pages.into()));
+        }
+        if let Some(words) = app.words {
+            metadata_map.insert(Cow::Borrowed("word_count"), serde_json::Value::Number(words.into()));
+        }
+        if let Some(chars) = app.characters {
+            metadata_map.insert(
+                Cow::Borrowed("character_count"),
+                serde_json::Value::Number(chars.into()),
+            );
+        }
+        if let Some(lines) = app.lines {
+            metadata_map.insert(Cow::Borrowed("line_count"), serde_json::Value::Number(lines.into()));
+        }
+        if let Some(paragraphs) = app.paragraphs {
+            metadata_map.insert(
+                Cow::Borrowed("paragraph_count"),
+                serde_json::Value::Number(paragraphs.into()),
+            );
+        }
+        if let Some(template) = app.template {
+            metadata_map.insert(Cow::Borrowed("template"), serde_json::Value::String(template));
+        }
+        if let Some(company) = app.company {
+            metadata_map.insert(Cow::Borrowed("company"), serde_json::Value::String(company));
+        }
+        if let Some(time) = app.total_time {
+            metadata_map.insert(
+                Cow::Borrowed("total_editing_time_minutes"),
+                serde_json::Value::Number(time.into()),
+            );
+        }
+        if let Some(application) = app.application {
+            metadata_map.insert(Cow::Borrowed("application"), serde_json::Value::String(application));
+        }
+        if let Some(app_version) = app.app_version {
+            metadata_map.insert(
+                Cow::Borrowed("application_version"),
+                serde_json::Value::String(app_version),
+            );
+        }
+    }
+
+    if mode.includes_full()
+        && let Ok(custom) = office_metadata::extract_custom_properties(archive)
+    {
+        for (key, value) in custom {
+            metadata_map.insert(Cow::Owned(format!("custom_{}", key)), value);
+        }
+    }
+
+    (metadata_map, parsed_keywords)
 }
 
 #[cfg(test)]
@@ -371,49 +334,82 @@ mod tests {
         assert!(extractor.shutdown().is_ok());
     }
 
-    #[test]
-    fn test_convert_docx_table_to_table() {
-        use docx_lite::{Paragraph, Run, Table as DocxTable, TableCell, TableRow};
-
-        let mut table = DocxTable::new();
-
-        let mut header_row = TableRow::default();
-        let mut cell1 = TableCell::default();
-        let mut para1 = Paragraph::new();
-        para1.add_run(Run::new("Name".to_string()));
-        cell1.paragraphs.push(para1);
-        header_row.cells.push(cell1);
-
-        let mut cell2 = TableCell::default();
-        let mut para2 = Paragraph::new();
-        para2.add_run(Run::new("Age".to_string()));
-        cell2.paragraphs.push(para2);
-        header_row.cells.push(cell2);
-
-        table.rows.push(header_row);
-
-        let mut data_row = TableRow::default();
-        let mut cell3 = TableCell::default();
-        let mut para3 = Paragraph::new();
-        para3.add_run(Run::new("Alice".to_string()));
-        cell3.paragraphs.push(para3);
-        data_row.cells.push(cell3);
-
-        let mut cell4 = TableCell::default();
-        let mut para4 = Paragraph::new();
-        para4.add_run(Run::new("30".to_string()));
-        cell4.paragraphs.push(para4);
-        data_row.cells.push(cell4);
-
-        table.rows.push(data_row);
-
-        let result = convert_docx_table_to_table(&table, 0);
-
-        assert_eq!(result.page_number, 1);
-        assert_eq!(result.cells.len(), 2);
-        assert_eq!(result.cells[0], vec!["Name", "Age"]);
-        assert_eq!(result.cells[1], vec!["Alice", "30"]);
-        assert!(result.markdown.contains("| Name | Age |"));
-        assert!(result.markdown.contains("| Alice | 30 |"));
+    #[tokio::test]
+    async fn test_docx_extractor_metadata_modes() {
+        use crate::core::config::MetadataMode;
+
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../test_documents/documents/word_sample.docx");
+        let Ok(bytes) = std::fs::read(path) else {
+            return;
+        };
+        let extractor = DocxExtractor::new();
+        let mime_type = "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+
+        let metadata = extractor
+            .extract_metadata(&bytes, mime_type, &ExtractionConfig::default())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.additional["created_by"], "Christoph Auer");
+        assert_eq!(metadata.additional["custom_AssetID"], "TF10002040");
+
+        let config = ExtractionConfig {
+            metadata: MetadataMode::Basic,
+            ..Default::default()
+        };
+        let metadata = extractor
+            .extract_metadata(&bytes, mime_type, &config)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(!metadata.additional.contains_key("custom_AssetID"));
+
+        let config = ExtractionConfig {
+            metadata: MetadataMode::None,
+            ..Default::default()
+        };
+        let result = extractor.extract_bytes(&bytes, mime_type, &config).await.unwrap();
+        assert!(result.metadata.additional.is_empty());
+        assert!(!result.content.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_docx_extractor_table_modes() {
+        use crate::core::config::{DocxConfig, DocxTableMode};
+
+        let path =
+            std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../test_documents/documents/word_tables.docx");
+        let Ok(bytes) = std::fs::read(path) else {
+            return;
+        };
+        let extractor = DocxExtractor::new();
+        let mime_type = "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+
+        let result = extractor
+            .extract_bytes(&bytes, mime_type, &ExtractionConfig::default())
+            .await
+            .unwrap();
+        assert!(
+            result
+                .content
+                .contains("A uniform table\n\n| Header 0.0 | Header 0.1 | Header 0.2 |")
+        );
+        assert_eq!(result.tables.len(), 5);
+        assert_eq!(result.tables[0].page_number, 1);
+        assert_eq!(
+            result.tables[3].cells[2],
+            vec!["Cell 2.0", "Merged Cell 1.1 2.1", "Cell 2.2"]
+        );
+
+        let config = ExtractionConfig {
+            docx: Some(DocxConfig {
+                tables: DocxTableMode::Structured,
+            }),
+            ..Default::default()
+        };
+        let result = extractor.extract_bytes(&bytes, mime_type, &config).await.unwrap();
+        assert!(!result.content.contains("Header 0.0"));
+        assert_eq!(result.tables.len(), 5);
     }
 }
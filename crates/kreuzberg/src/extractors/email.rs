@@ -2,6 +2,8 @@
 
 use crate::Result;
 use crate::core::config::ExtractionConfig;
+use crate::core::io::Spill;
+use crate::extraction::nested::{Nesting, email_text};
 use crate::extractors::SyncExtractor;
 use crate::plugins::{DocumentExtractor, Plugin};
 use crate::types::{EmailMetadata, ExtractionResult, Metadata};
@@ -47,10 +49,14 @@ impl Plugin for EmailExtractor {
 }
 
 impl SyncExtractor for EmailExtractor {
-    fn extract_sync(&self, content: &[u8], mime_type: &str, _config: &ExtractionConfig) -> Result<ExtractionResult> {
+    fn extract_sync(&self, content: &[u8], mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
         let email_result = crate::extraction::email::extract_email_content(content, mime_type)?;
 
-        let text = crate::extraction::email::build_email_text_output(&email_result);
+        let mut nesting = config
+            .containers
+            .as_ref()
+            .map(|containers| Nesting::new(containers, content).with_spill(Spill::new(config.io.as_ref())));
+        let text = email_text(&email_result, nesting.as_mut());
 
         let attachment_names: Vec<String> = email_result
             .attachments
@@ -73,7 +79,7 @@ impl SyncExtractor for EmailExtractor {
             additional.insert(Cow::Owned(key.clone()), serde_json::json!(value));
         }
 
-        Ok(ExtractionResult {
+        let mut result = ExtractionResult {
             content: text,
             mime_type: mime_type.to_string().into(),
             metadata: Metadata {
@@ -89,8 +95,13 @@ impl SyncExtractor for EmailExtractor {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
-        })
+        };
+        if let Some(nesting) = nesting {
+            nesting.record(&mut result.metadata);
+        }
+        Ok(result)
     }
 }
 
@@ -121,7 +132,7 @@ impl DocumentExtractor for EmailExtractor {
     ))]
     #[cfg(feature = "tokio-runtime")]
     async fn extract_file(&self, path: &Path, mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
-        let bytes = tokio::fs::read(path).await?;
+        let bytes = crate::core::io::open_file_async(path, config.io.as_ref()).await?;
         self.extract_bytes(&bytes, mime_type, config).await
     }
 
@@ -136,6 +147,10 @@ impl DocumentExtractor for EmailExtractor {
     fn as_sync_extractor(&self) -> Option<&dyn crate::extractors::SyncExtractor> {
         Some(self)
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["email"]
+    }
 }
 
 #[cfg(test)]
@@ -4,28 +4,35 @@
 //! with markdown conversion and HTML cleaning utilities.
 
 use crate::Result;
+use crate::core::config::XmlConfig;
+use crate::extraction::remote::html_references;
+use crate::types::RemoteResourceKind;
 use std::io::Cursor;
 use zip::ZipArchive;
 
 use super::metadata::parse_opf;
 use super::parsing::{read_file_from_zip, resolve_path};
 
-/// Extract text content from an EPUB document by reading in spine order
+/// Extract text content from an EPUB document by reading in spine order, along with the
+/// remote resources the chapters reference
 pub(super) fn extract_content(
     archive: &mut ZipArchive<Cursor<Vec<u8>>>,
     opf_path: &str,
     manifest_dir: &str,
-) -> Result<String> {
+    limits: Option<&XmlConfig>,
+) -> Result<(String, Vec<(RemoteResourceKind, String)>)> {
     let opf_xml = read_file_from_zip(archive, opf_path)?;
-    let (_, spine_hrefs) = parse_opf(&opf_xml)?;
+    let (_, spine_hrefs) = parse_opf(&opf_xml, limits)?;
 
     let mut content = String::new();
+    let mut references = Vec::new();
 
     for (index, href) in spine_hrefs.iter().enumerate() {
         let file_path = resolve_path(manifest_dir, href);
 
         match read_file_from_zip(archive, &file_path) {
             Ok(xhtml_content) => {
+                references.extend(html_references(&xhtml_content));
                 let text = extract_text_from_xhtml(&xhtml_content);
                 if !text.is_empty() {
                     if index > 0 && !content.ends_with('\n') {
@@ -41,7 +48,7 @@ pub(super) fn extract_content(
         }
     }
 
-    Ok(content.trim().to_string())
+    Ok((content.trim().to_string(), references))
 }
 
 /// Extract text from XHTML content using html-to-markdown-rs
@@ -4,7 +4,9 @@
 //! Dublin Core metadata following EPUB2 and EPUB3 standards.
 
 use crate::Result;
-use roxmltree;
+use crate::core::config::XmlConfig;
+use crate::extraction::xml_parser;
+use crate::types::RemoteResourceKind;
 use std::collections::BTreeMap;
 
 /// Metadata extracted from OPF (Open Packaging Format) file
@@ -22,10 +24,13 @@ pub(super) struct OepbMetadata {
 }
 
 /// Extract metadata from EPUB OPF file
-pub(super) fn extract_metadata(opf_xml: &str) -> Result<(OepbMetadata, BTreeMap<String, serde_json::Value>)> {
+pub(super) fn extract_metadata(
+    opf_xml: &str,
+    limits: Option<&XmlConfig>,
+) -> Result<(OepbMetadata, BTreeMap<String, serde_json::Value>)> {
     let mut additional_metadata = BTreeMap::new();
 
-    let (epub_metadata, _) = parse_opf(opf_xml)?;
+    let (epub_metadata, _) = parse_opf(opf_xml, limits)?;
 
     if let Some(ref identifier) = epub_metadata.identifier {
         additional_metadata.insert("identifier".to_string(), serde_json::json!(identifier.clone()));
@@ -50,88 +55,108 @@ pub(super) fn extract_metadata(opf_xml: &str) -> Result<(OepbMetadata, BTreeMap<
     Ok((epub_metadata, additional_metadata))
 }
 
+/// Manifest items of an OPF file, by the kind of resource their media type describes.
+///
+/// Manifest items may live outside the container (EPUB 3 allows remote audio, video
+/// and fonts), so these are reported as remote references when their href is a URL.
+pub(super) fn manifest_references(xml: &str, limits: Option<&XmlConfig>) -> Vec<(RemoteResourceKind, String)> {
+    let Ok(doc) = xml_parser::parse_document(xml, limits, |e| crate::KreuzbergError::parsing(e.to_string())) else {
+        return Vec::new();
+    };
+    doc.descendants()
+        .filter(|node| node.tag_name().name() == "item")
+        .filter_map(|node| {
+            let href = node.attribute("href")?;
+            let media_type = node.attribute("media-type").unwrap_or_default();
+            let kind = match media_type {
+                media_type if media_type.starts_with("image/") => RemoteResourceKind::Image,
+                "text/css" => RemoteResourceKind::Stylesheet,
+                "application/javascript" | "text/javascript" | "application/ecmascript" => RemoteResourceKind::Script,
+                _ => RemoteResourceKind::Other,
+            };
+            Some((kind, href.to_string()))
+        })
+        .collect()
+}
+
 /// Parse OPF file and extract metadata and spine order
-pub(super) fn parse_opf(xml: &str) -> Result<(OepbMetadata, Vec<String>)> {
-    match roxmltree::Document::parse(xml) {
-        Ok(doc) => {
-            let root = doc.root();
-
-            let mut metadata = OepbMetadata::default();
-            let mut manifest: BTreeMap<String, String> = BTreeMap::new();
-            let mut spine_order: Vec<String> = Vec::new();
-
-            for node in root.descendants() {
-                match node.tag_name().name() {
-                    "title" => {
-                        if let Some(text) = node.text() {
-                            metadata.title = Some(text.trim().to_string());
-                        }
-                    }
-                    "creator" => {
-                        if let Some(text) = node.text() {
-                            metadata.creator = Some(text.trim().to_string());
-                        }
-                    }
-                    "date" => {
-                        if let Some(text) = node.text() {
-                            metadata.date = Some(text.trim().to_string());
-                        }
-                    }
-                    "language" => {
-                        if let Some(text) = node.text() {
-                            metadata.language = Some(text.trim().to_string());
-                        }
-                    }
-                    "identifier" => {
-                        if let Some(text) = node.text() {
-                            metadata.identifier = Some(text.trim().to_string());
-                        }
-                    }
-                    "publisher" => {
-                        if let Some(text) = node.text() {
-                            metadata.publisher = Some(text.trim().to_string());
-                        }
-                    }
-                    "subject" => {
-                        if let Some(text) = node.text() {
-                            metadata.subject = Some(text.trim().to_string());
-                        }
-                    }
-                    "description" => {
-                        if let Some(text) = node.text() {
-                            metadata.description = Some(text.trim().to_string());
-                        }
-                    }
-                    "rights" => {
-                        if let Some(text) = node.text() {
-                            metadata.rights = Some(text.trim().to_string());
-                        }
-                    }
-                    "item" => {
-                        if let Some(id) = node.attribute("id")
-                            && let Some(href) = node.attribute("href")
-                        {
-                            manifest.insert(id.to_string(), href.to_string());
-                        }
-                    }
-                    _ => {}
+pub(super) fn parse_opf(xml: &str, limits: Option<&XmlConfig>) -> Result<(OepbMetadata, Vec<String>)> {
+    let doc = xml_parser::parse_document(xml, limits, |e| crate::KreuzbergError::Parsing {
+        message: format!("Failed to parse OPF file: {}", e),
+        source: None,
+    })?;
+    let root = doc.root();
+
+    let mut metadata = OepbMetadata::default();
+    let mut manifest: BTreeMap<String, String> = BTreeMap::new();
+    let mut spine_order: Vec<String> = Vec::new();
+
+    for node in root.descendants() {
+        match node.tag_name().name() {
+            "title" => {
+                if let Some(text) = node.text() {
+                    metadata.title = Some(text.trim().to_string());
                 }
             }
-
-            for node in root.descendants() {
-                if node.tag_name().name() == "itemref"
-                    && let Some(idref) = node.attribute("idref")
-                    && let Some(href) = manifest.get(idref)
+            "creator" => {
+                if let Some(text) = node.text() {
+                    metadata.creator = Some(text.trim().to_string());
+                }
+            }
+            "date" => {
+                if let Some(text) = node.text() {
+                    metadata.date = Some(text.trim().to_string());
+                }
+            }
+            "language" => {
+                if let Some(text) = node.text() {
+                    metadata.language = Some(text.trim().to_string());
+                }
+            }
+            "identifier" => {
+                if let Some(text) = node.text() {
+                    metadata.identifier = Some(text.trim().to_string());
+                }
+            }
+            "publisher" => {
+                if let Some(text) = node.text() {
+                    metadata.publisher = Some(text.trim().to_string());
+                }
+            }
+            "subject" => {
+                if let Some(text) = node.text() {
+                    metadata.subject = Some(text.trim().to_string());
+                }
+            }
+            "description" => {
+                if let Some(text) = node.text() {
+                    metadata.description = Some(text.trim().to_string());
+                }
+            }
+            "rights" => {
+                if let Some(text) = node.text() {
+                    metadata.rights = Some(text.trim().to_string());
+                }
+            }
+            "item" => {
+                if let Some(id) = node.attribute("id")
+                    && let Some(href) = node.attribute("href")
                 {
-                    spine_order.push(href.clone());
+                    manifest.insert(id.to_string(), href.to_string());
                 }
             }
+            _ => {}
+        }
+    }
 
-            Ok((metadata, spine_order))
+    for node in root.descendants() {
+        if node.tag_name().name() == "itemref"
+            && let Some(idref) = node.attribute("idref")
+            && let Some(href) = manifest.get(idref)
+        {
+            spine_order.push(href.clone());
         }
-        Err(e) => Err(crate::KreuzbergError::Parsing {
-            message: format!("Failed to parse OPF file: {}", e),
-            source: None,
-        }),
     }
+
+    Ok((metadata, spine_order))
 }
@@ -26,7 +26,7 @@ use std::io::Cursor;
 use zip::ZipArchive;
 
 use content::extract_content;
-use metadata::extract_metadata;
+use metadata::{extract_metadata, manifest_references};
 use parsing::{parse_container_xml, read_file_from_zip};
 
 /// EPUB format extractor using permissive-licensed dependencies.
@@ -80,7 +80,7 @@ impl DocumentExtractor for EpubExtractor {
     #[cfg_attr(
         feature = "otel",
         tracing::instrument(
-            skip(self, content, _config),
+            skip(self, content, config),
             fields(
                 extractor.name = self.name(),
                 content.size_bytes = content.len(),
@@ -91,7 +91,7 @@ impl DocumentExtractor for EpubExtractor {
         &self,
         content: &[u8],
         mime_type: &str,
-        _config: &ExtractionConfig,
+        config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
         let cursor = Cursor::new(content.to_vec());
 
@@ -101,7 +101,7 @@ impl DocumentExtractor for EpubExtractor {
         })?;
 
         let container_xml = read_file_from_zip(&mut archive, "META-INF/container.xml")?;
-        let opf_path = parse_container_xml(&container_xml)?;
+        let opf_path = parse_container_xml(&container_xml, config.xml.as_ref())?;
 
         let manifest_dir = if let Some(last_slash) = opf_path.rfind('/') {
             opf_path[..last_slash].to_string()
@@ -111,31 +111,37 @@ impl DocumentExtractor for EpubExtractor {
 
         let opf_xml = read_file_from_zip(&mut archive, &opf_path)?;
 
-        let extracted_content = extract_content(&mut archive, &opf_path, &manifest_dir)?;
+        let (extracted_content, mut remote_references) =
+            extract_content(&mut archive, &opf_path, &manifest_dir, config.xml.as_ref())?;
+        remote_references.extend(manifest_references(&opf_xml, config.xml.as_ref()));
 
-        let (epub_metadata, additional_metadata) = extract_metadata(&opf_xml)?;
+        let (epub_metadata, additional_metadata) = extract_metadata(&opf_xml, config.xml.as_ref())?;
         let metadata_map: AHashMap<Cow<'static, str>, serde_json::Value> = additional_metadata
             .into_iter()
             .map(|(k, v)| (Cow::Owned(k), v))
             .collect();
 
+        let mut metadata = Metadata {
+            title: epub_metadata.title,
+            authors: epub_metadata.creator.map(|c| vec![c]),
+            language: epub_metadata.language,
+            created_at: epub_metadata.date,
+            additional: metadata_map,
+            ..Default::default()
+        };
+        crate::core::network::record_references(&mut metadata, remote_references, config.network.as_ref());
+
         Ok(ExtractionResult {
             content: extracted_content,
             mime_type: mime_type.to_string().into(),
-            metadata: Metadata {
-                title: epub_metadata.title,
-                authors: epub_metadata.creator.map(|c| vec![c]),
-                language: epub_metadata.language,
-                created_at: epub_metadata.date,
-                additional: metadata_map,
-                ..Default::default()
-            },
+            metadata,
             pages: None,
             tables: vec![],
             detected_languages: None,
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -151,6 +157,10 @@ impl DocumentExtractor for EpubExtractor {
     fn priority(&self) -> i32 {
         60
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["office"]
+    }
 }
 
 #[cfg(all(test, feature = "office"))]
@@ -4,31 +4,28 @@
 //! including ZIP archive operations and container.xml parsing.
 
 use crate::Result;
-use roxmltree;
+use crate::core::config::XmlConfig;
+use crate::extraction::xml_parser;
 use std::io::Cursor;
 use zip::ZipArchive;
 
 /// Parse container.xml to find the OPF file path
-pub(super) fn parse_container_xml(xml: &str) -> Result<String> {
-    match roxmltree::Document::parse(xml) {
-        Ok(doc) => {
-            for node in doc.descendants() {
-                if node.tag_name().name() == "rootfile"
-                    && let Some(full_path) = node.attribute("full-path")
-                {
-                    return Ok(full_path.to_string());
-                }
-            }
-            Err(crate::KreuzbergError::Parsing {
-                message: "No rootfile found in container.xml".to_string(),
-                source: None,
-            })
+pub(super) fn parse_container_xml(xml: &str, limits: Option<&XmlConfig>) -> Result<String> {
+    let doc = xml_parser::parse_document(xml, limits, |e| crate::KreuzbergError::Parsing {
+        message: format!("Failed to parse container.xml: {}", e),
+        source: None,
+    })?;
+    for node in doc.descendants() {
+        if node.tag_name().name() == "rootfile"
+            && let Some(full_path) = node.attribute("full-path")
+        {
+            return Ok(full_path.to_string());
         }
-        Err(e) => Err(crate::KreuzbergError::Parsing {
-            message: format!("Failed to parse container.xml: {}", e),
-            source: None,
-        }),
     }
+    Err(crate::KreuzbergError::Parsing {
+        message: "No rootfile found in container.xml".to_string(),
+        source: None,
+    })
 }
 
 /// Read a file from the ZIP archive
@@ -52,6 +52,26 @@ impl ExcelExtractor {
 
         tables
     }
+
+    /// Append the tables of the sheets' charts, recording their summaries in the `charts` metadata entry.
+    #[cfg(feature = "office")]
+    fn with_chart_tables(
+        workbook: &crate::types::ExcelWorkbook,
+        mut tables: Vec<Table>,
+        additional: &mut AHashMap<Cow<'static, str>, serde_json::Value>,
+    ) -> Vec<Table> {
+        use crate::extraction::charts::{chart_summary, chart_table};
+
+        let mut summaries = Vec::new();
+        for chart in workbook.sheets.iter().flat_map(|sheet| &sheet.charts) {
+            summaries.push(chart_summary(chart, tables.len()));
+            tables.push(chart_table(chart));
+        }
+        if !summaries.is_empty() {
+            additional.insert(Cow::Borrowed("charts"), serde_json::Value::Array(summaries));
+        }
+        tables
+    }
 }
 
 impl Plugin for ExcelExtractor {
@@ -75,7 +95,7 @@ impl Plugin for ExcelExtractor {
 #[async_trait]
 impl DocumentExtractor for ExcelExtractor {
     #[cfg_attr(feature = "otel", tracing::instrument(
-        skip(self, content, _config),
+        skip(self, content, config),
         fields(
             extractor.name = self.name(),
             content.size_bytes = content.len(),
@@ -85,7 +105,7 @@ impl DocumentExtractor for ExcelExtractor {
         &self,
         content: &[u8],
         mime_type: &str,
-        _config: &ExtractionConfig,
+        config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
         let extension = match mime_type {
             "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet" => ".xlsx",
@@ -99,18 +119,19 @@ impl DocumentExtractor for ExcelExtractor {
             _ => ".xlsx",
         };
 
+        let spreadsheet = config.spreadsheet.clone().unwrap_or_default();
         let workbook = if crate::core::batch_mode::is_batch_mode() {
             let content_owned = content.to_vec();
             let extension_owned = extension.to_string();
             let span = tracing::Span::current();
             tokio::task::spawn_blocking(move || {
                 let _guard = span.entered();
-                crate::extraction::excel::read_excel_bytes(&content_owned, &extension_owned)
+                crate::extraction::excel::read_excel_bytes_with_config(&content_owned, &extension_owned, &spreadsheet)
             })
             .await
             .map_err(|e| crate::error::KreuzbergError::parsing(format!("Excel extraction task failed: {}", e)))??
         } else {
-            crate::extraction::excel::read_excel_bytes(content, extension)?
+            crate::extraction::excel::read_excel_bytes_with_config(content, extension, &spreadsheet)?
         };
 
         let markdown = crate::extraction::excel::excel_to_markdown(&workbook);
@@ -120,6 +141,9 @@ impl DocumentExtractor for ExcelExtractor {
         let excel_metadata = ExcelMetadata {
             sheet_count: workbook.sheets.len(),
             sheet_names,
+            sheets: workbook.sheet_metadata.clone(),
+            defined_names: workbook.defined_names.clone(),
+            comments: workbook.comments.clone(),
         };
 
         let mut additional = AHashMap::new();
@@ -128,6 +152,8 @@ impl DocumentExtractor for ExcelExtractor {
                 additional.insert(Cow::Owned(key.clone()), serde_json::json!(value));
             }
         }
+        #[cfg(feature = "office")]
+        let tables = Self::with_chart_tables(&workbook, tables, &mut additional);
 
         Ok(ExtractionResult {
             content: markdown,
@@ -143,22 +169,20 @@ impl DocumentExtractor for ExcelExtractor {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
 
     #[cfg_attr(feature = "otel", tracing::instrument(
-        skip(self, path, _config),
+        skip(self, path, config),
         fields(
             extractor.name = self.name(),
         )
     ))]
-    async fn extract_file(&self, path: &Path, mime_type: &str, _config: &ExtractionConfig) -> Result<ExtractionResult> {
-        let path_str = path
-            .to_str()
-            .ok_or_else(|| crate::KreuzbergError::validation("Invalid file path".to_string()))?;
-
-        let workbook = crate::extraction::excel::read_excel_file(path_str)?;
+    async fn extract_file(&self, path: &Path, mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
+        let spreadsheet = config.spreadsheet.clone().unwrap_or_default();
+        let workbook = crate::extraction::excel::read_excel_file_with_config(path, &spreadsheet)?;
         let markdown = crate::extraction::excel::excel_to_markdown(&workbook);
         let tables = Self::sheets_to_tables(&workbook);
 
@@ -166,6 +190,9 @@ impl DocumentExtractor for ExcelExtractor {
         let excel_metadata = ExcelMetadata {
             sheet_count: workbook.sheets.len(),
             sheet_names,
+            sheets: workbook.sheet_metadata.clone(),
+            defined_names: workbook.defined_names.clone(),
+            comments: workbook.comments.clone(),
         };
 
         let mut additional = AHashMap::new();
@@ -174,6 +201,8 @@ impl DocumentExtractor for ExcelExtractor {
                 additional.insert(Cow::Owned(key.clone()), serde_json::json!(value));
             }
         }
+        #[cfg(feature = "office")]
+        let tables = Self::with_chart_tables(&workbook, tables, &mut additional);
 
         Ok(ExtractionResult {
             content: markdown,
@@ -189,6 +218,7 @@ impl DocumentExtractor for ExcelExtractor {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -209,6 +239,10 @@ impl DocumentExtractor for ExcelExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["excel"]
+    }
 }
 
 #[cfg(test)]
@@ -255,11 +289,15 @@ mod tests {
                 vec!["Alice".to_string(), "30".to_string(), "NYC".to_string()],
                 vec!["Bob".to_string(), "25".to_string(), "LA".to_string()],
             ]),
+            charts: Vec::new(),
         };
 
         let workbook = crate::types::ExcelWorkbook {
             sheets: vec![sheet],
             metadata: HashMap::new(),
+            sheet_metadata: Vec::new(),
+            defined_names: Vec::new(),
+            comments: Vec::new(),
         };
 
         let tables = ExcelExtractor::sheets_to_tables(&workbook);
@@ -284,11 +322,15 @@ mod tests {
             col_count: 0,
             cell_count: 0,
             table_cells: None,
+            charts: Vec::new(),
         };
 
         let workbook = crate::types::ExcelWorkbook {
             sheets: vec![sheet],
             metadata: HashMap::new(),
+            sheet_metadata: Vec::new(),
+            defined_names: Vec::new(),
+            comments: Vec::new(),
         };
 
         let tables = ExcelExtractor::sheets_to_tables(&workbook);
@@ -316,6 +358,7 @@ mod tests {
                 vec!["Col1".to_string(), "Col2".to_string()],
                 vec!["A".to_string(), "B".to_string()],
             ]),
+            charts: Vec::new(),
         };
 
         let sheet2 = ExcelSheet {
@@ -334,11 +377,15 @@ mod tests {
                 vec!["X".to_string(), "Y".to_string()],
                 vec!["1".to_string(), "2".to_string()],
             ]),
+            charts: Vec::new(),
         };
 
         let workbook = crate::types::ExcelWorkbook {
             sheets: vec![sheet1, sheet2],
             metadata: HashMap::new(),
+            sheet_metadata: Vec::new(),
+            defined_names: Vec::new(),
+            comments: Vec::new(),
         };
 
         let tables = ExcelExtractor::sheets_to_tables(&workbook);
@@ -371,11 +418,15 @@ mod tests {
                 vec!["Item|A".to_string(), "100".to_string(), "$1,000".to_string()],
                 vec!["Item B".to_string(), "200".to_string(), "$2,000".to_string()],
             ]),
+            charts: Vec::new(),
         };
 
         let workbook = crate::types::ExcelWorkbook {
             sheets: vec![sheet],
             metadata: HashMap::new(),
+            sheet_metadata: Vec::new(),
+            defined_names: Vec::new(),
+            comments: Vec::new(),
         };
 
         let tables = ExcelExtractor::sheets_to_tables(&workbook);
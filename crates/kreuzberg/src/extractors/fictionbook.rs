@@ -426,8 +426,9 @@ impl DocumentExtractor for FictionBookExtractor {
         &self,
         content: &[u8],
         mime_type: &str,
-        _config: &ExtractionConfig,
+        config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
+        crate::extraction::xml_parser::check_document(content, config.xml.as_ref())?;
         let metadata = Self::extract_metadata(content)?;
 
         let extracted_content = Self::extract_body_content(content)?;
@@ -441,6 +442,7 @@ impl DocumentExtractor for FictionBookExtractor {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         })
@@ -457,6 +459,10 @@ impl DocumentExtractor for FictionBookExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["office"]
+    }
 }
 
 #[cfg(all(test, feature = "office"))]
@@ -5,7 +5,7 @@ use crate::core::config::{ExtractionConfig, OutputFormat};
 use crate::extractors::SyncExtractor;
 use crate::plugins::{DocumentExtractor, Plugin};
 use crate::text::utf8_validation;
-use crate::types::{ExtractionResult, Metadata, Table};
+use crate::types::{ExtractionResult, Metadata};
 use async_trait::async_trait;
 #[cfg(feature = "tokio-runtime")]
 use std::path::Path;
@@ -27,157 +27,6 @@ impl HtmlExtractor {
     }
 }
 
-/// Extract all tables from pre-converted markdown content.
-///
-/// Parses markdown pipe-delimited format to extract table structures.
-/// This function accepts already-converted markdown to enable reuse of
-/// a single HTML-to-markdown conversion pass, improving performance by
-/// eliminating duplicate conversions.
-///
-/// # Optimization
-/// By accepting markdown instead of HTML, callers can convert HTML once
-/// and reuse the result for both table extraction and metadata parsing,
-/// reducing computational overhead by ~50% on table extraction flows.
-fn extract_html_tables(markdown: &str) -> Result<Vec<Table>> {
-    let tables = parse_markdown_tables(markdown);
-    Ok(tables)
-}
-
-/// Parse markdown tables from HTML-converted markdown.
-///
-/// Extracts table data from markdown pipe-delimited format.
-/// This maintains the existing Table structure API.
-fn parse_markdown_tables(markdown: &str) -> Vec<Table> {
-    let mut tables = Vec::new();
-    let mut table_index = 0;
-    let lines: Vec<&str> = markdown.lines().collect();
-    let mut i = 0;
-
-    while i < lines.len() {
-        if lines[i].trim_start().starts_with('|')
-            && let Some((cells, end_idx)) = extract_markdown_table(&lines, i)
-            && !cells.is_empty()
-        {
-            let markdown_table = reconstruct_markdown_table(&cells);
-            tables.push(Table {
-                cells,
-                markdown: markdown_table,
-                page_number: table_index + 1,
-            });
-            table_index += 1;
-            i = end_idx;
-            continue;
-        }
-        i += 1;
-    }
-
-    tables
-}
-
-/// Extract a single markdown table from lines.
-///
-/// Returns the parsed table cells and the index after the table ends.
-fn extract_markdown_table(lines: &[&str], start_idx: usize) -> Option<(Vec<Vec<String>>, usize)> {
-    let header_line = lines.get(start_idx)?;
-
-    if !header_line.trim_start().starts_with('|') {
-        return None;
-    }
-
-    let mut cells = Vec::new();
-    let mut i = start_idx;
-
-    if let Some(header_cells) = parse_markdown_table_row(header_line) {
-        cells.push(header_cells);
-        i += 1;
-    } else {
-        return None;
-    }
-
-    if i < lines.len() {
-        let sep_line = lines[i];
-        if is_markdown_table_separator(sep_line) {
-            i += 1;
-        }
-    }
-
-    while i < lines.len() {
-        let line = lines[i];
-        if let Some(row_cells) = parse_markdown_table_row(line) {
-            cells.push(row_cells);
-            i += 1;
-        } else if !line.trim_start().starts_with('|') {
-            break;
-        } else {
-            i += 1;
-        }
-    }
-
-    if cells.len() > 1 { Some((cells, i)) } else { None }
-}
-
-/// Parse a single markdown table row into cell contents.
-fn parse_markdown_table_row(line: &str) -> Option<Vec<String>> {
-    let trimmed = line.trim_start();
-
-    if !trimmed.starts_with('|') || !trimmed.contains('|') {
-        return None;
-    }
-
-    let cells: Vec<String> = trimmed
-        .split('|')
-        .skip(1)
-        .map(|cell| cell.trim().to_string())
-        .filter(|cell| !cell.is_empty())
-        .collect();
-
-    if cells.is_empty() { None } else { Some(cells) }
-}
-
-/// Check if a line is a markdown table separator.
-fn is_markdown_table_separator(line: &str) -> bool {
-    let trimmed = line.trim_start();
-    if !trimmed.starts_with('|') {
-        return false;
-    }
-
-    trimmed
-        .split('|')
-        .all(|cell| cell.trim().chars().all(|c| c == '-' || c == ':' || c.is_whitespace()))
-}
-
-/// Reconstruct markdown table from cells.
-///
-/// Takes parsed table cells and creates a properly formatted markdown table string.
-fn reconstruct_markdown_table(cells: &[Vec<String>]) -> String {
-    if cells.is_empty() {
-        return String::new();
-    }
-
-    let mut markdown = String::new();
-
-    for (row_idx, row) in cells.iter().enumerate() {
-        markdown.push('|');
-        for cell in row {
-            markdown.push(' ');
-            markdown.push_str(cell);
-            markdown.push(' ');
-            markdown.push('|');
-        }
-        markdown.push('\n');
-
-        if row_idx == 0 {
-            markdown.push('|');
-            for _ in row {
-                markdown.push_str("------|");
-            }
-            markdown.push('\n');
-        }
-    }
-
-    markdown
-}
-
 impl Plugin for HtmlExtractor {
     fn name(&self) -> &str {
         "html-extractor"
@@ -198,9 +47,7 @@ impl Plugin for HtmlExtractor {
 
 impl SyncExtractor for HtmlExtractor {
     fn extract_sync(&self, content: &[u8], mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
-        let html = utf8_validation::from_utf8(content)
-            .map(|s| s.to_string())
-            .unwrap_or_else(|_| String::from_utf8_lossy(content).to_string());
+        let html = utf8_validation::decode_lossy(content);
 
         let (content_text, html_metadata) = crate::extraction::html::convert_html_to_markdown_with_metadata(
             &html,
@@ -208,7 +55,7 @@ impl SyncExtractor for HtmlExtractor {
             Some(config.output_format),
         )?;
 
-        let tables = extract_html_tables(&content_text)?;
+        let tables = crate::extraction::html::extract_html_tables(&html);
 
         // Set mime_type based on actual output format
         let result_mime_type = match config.output_format {
@@ -217,19 +64,27 @@ impl SyncExtractor for HtmlExtractor {
             _ => mime_type, // Preserve original mime_type for other formats
         };
 
+        let mut metadata = Metadata {
+            format: html_metadata.map(|m| crate::types::FormatMetadata::Html(Box::new(m))),
+            ..Default::default()
+        };
+        crate::core::network::record_references(
+            &mut metadata,
+            crate::extraction::remote::html_references(&html),
+            config.network.as_ref(),
+        );
+
         Ok(ExtractionResult {
             content: content_text,
             mime_type: result_mime_type.to_string().into(),
-            metadata: Metadata {
-                format: html_metadata.map(|m| crate::types::FormatMetadata::Html(Box::new(m))),
-                ..Default::default()
-            },
+            metadata,
             pages: None,
             tables,
             detected_languages: None,
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -262,7 +117,7 @@ impl DocumentExtractor for HtmlExtractor {
     ))]
     #[cfg(feature = "tokio-runtime")]
     async fn extract_file(&self, path: &Path, mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
-        let bytes = tokio::fs::read(path).await?;
+        let bytes = crate::core::io::open_file_async(path, config.io.as_ref()).await?;
         self.extract_bytes(&bytes, mime_type, config).await
     }
 
@@ -277,16 +132,16 @@ impl DocumentExtractor for HtmlExtractor {
     fn as_sync_extractor(&self) -> Option<&dyn crate::extractors::SyncExtractor> {
         Some(self)
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["html"]
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    /// Helper function to convert HTML to markdown for testing
-    fn html_to_markdown_for_test(html: &str) -> String {
-        crate::extraction::html::convert_html_to_markdown(html, None, None).unwrap()
-    }
+    use crate::extraction::html::extract_html_tables;
 
     #[test]
     fn test_html_extractor_plugin_interface() {
@@ -315,8 +170,7 @@ mod tests {
             </table>
         "#;
 
-        let markdown = html_to_markdown_for_test(html);
-        let tables = extract_html_tables(&markdown).unwrap();
+        let tables = extract_html_tables(html);
         assert_eq!(tables.len(), 1);
 
         let table = &tables[0];
@@ -345,8 +199,7 @@ mod tests {
             </table>
         "#;
 
-        let markdown = html_to_markdown_for_test(html);
-        let tables = extract_html_tables(&markdown).unwrap();
+        let tables = extract_html_tables(html);
         assert_eq!(tables.len(), 2);
         assert_eq!(tables[0].page_number, 1);
         assert_eq!(tables[1].page_number, 2);
@@ -361,8 +214,7 @@ mod tests {
             </table>
         "#;
 
-        let markdown = html_to_markdown_for_test(html);
-        let tables = extract_html_tables(&markdown).unwrap();
+        let tables = extract_html_tables(html);
         assert_eq!(tables.len(), 1);
 
         let table = &tables[0];
@@ -374,8 +226,7 @@ mod tests {
     #[test]
     fn test_extract_html_tables_empty() {
         let html = "<p>No tables here</p>";
-        let markdown = html_to_markdown_for_test(html);
-        let tables = extract_html_tables(&markdown).unwrap();
+        let tables = extract_html_tables(html);
         assert_eq!(tables.len(), 0);
     }
 
@@ -388,8 +239,7 @@ mod tests {
             </table>
         "#;
 
-        let markdown = html_to_markdown_for_test(html);
-        let tables = extract_html_tables(&markdown).unwrap();
+        let tables = extract_html_tables(html);
         assert_eq!(tables.len(), 1);
 
         let table = &tables[0];
@@ -427,6 +277,41 @@ mod tests {
         assert_eq!(table.cells[2], vec!["Bob", "25"]);
     }
 
+    #[tokio::test]
+    async fn test_html_extractor_records_remote_resources() {
+        let html = r#"<html><body>
+            <img src="https://example.com/chart.png">
+            <img src="http://169.254.169.254/latest/meta-data/">
+        </body></html>"#;
+
+        let extractor = HtmlExtractor::new();
+        let config = ExtractionConfig {
+            network: Some(crate::core::config::NetworkConfig {
+                allow_remote: true,
+                allowed_hosts: vec!["example.com".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let result = extractor
+            .extract_bytes(html.as_bytes(), "text/html", &config)
+            .await
+            .unwrap();
+
+        let resources = &result.metadata.additional["remote_resources"];
+        assert_eq!(resources[0]["url"], "https://example.com/chart.png");
+        assert_eq!(resources[0]["kind"], "image");
+        assert_eq!(resources[0]["status"], "allowed");
+        assert_eq!(resources[1]["status"], "blocked");
+
+        let result = extractor
+            .extract_bytes(html.as_bytes(), "text/html", &ExtractionConfig::default())
+            .await
+            .unwrap();
+        assert_eq!(result.metadata.additional["remote_resources"][0]["status"], "blocked");
+    }
+
     #[tokio::test]
     async fn test_html_extractor_with_djot_output() {
         let html = r#"
@@ -2,16 +2,32 @@
 
 use crate::Result;
 use crate::core::config::ExtractionConfig;
+#[cfg(feature = "ocr")]
+use crate::core::config::{OcrConfig, OcrPageContext, OutputFormat, PageConfig};
+#[cfg(feature = "ocr")]
+use crate::extraction::image::TiffPages;
 use crate::extraction::image::extract_image_metadata;
+#[cfg(feature = "ocr")]
+use crate::ocr::scheduler::{OcrPageJob, PagePreparation, PagePreparer, blank_page_result, duplicate_pages};
+#[cfg(feature = "ocr")]
+use crate::plugins::OcrBackend;
 use crate::plugins::{DocumentExtractor, Plugin};
 use crate::types::{ExtractionResult, Metadata};
+#[cfg(feature = "ocr")]
+use crate::types::{PageBoundary, PageContent, PageInfo, PageStructure, PageUnitType, Table};
 use async_trait::async_trait;
+#[cfg(feature = "ocr")]
+use std::io::Cursor;
+#[cfg(feature = "ocr")]
+use std::sync::Arc;
 
 /// Image extractor for various image formats.
 ///
-/// Supports: PNG, JPEG, WebP, BMP, TIFF, GIF.
+/// Supports: PNG, JPEG, WebP, BMP, TIFF, GIF, and with the optional codec features
+/// HEIC/HEIF and AVIF (`heif`) and JPEG XL (`jxl`).
 /// Extracts dimensions, format, and EXIF metadata.
-/// Optionally runs OCR when configured.
+/// Optionally runs OCR when configured; multi-page TIFFs, including CCITT fax
+/// pages, are recognized page by page.
 pub struct ImageExtractor;
 
 impl ImageExtractor {
@@ -20,7 +36,7 @@ impl ImageExtractor {
         Self
     }
 
-    /// Extract text from image using OCR with optional page tracking for multi-frame TIFFs.
+    /// Extract text from image using OCR; TIFF files are recognized page by page.
     #[cfg(feature = "ocr")]
     async fn extract_with_ocr(
         &self,
@@ -28,42 +44,324 @@ impl ImageExtractor {
         mime_type: &str,
         config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
-        use crate::plugins::registry::get_ocr_backend_registry;
-
         let ocr_config = config.ocr.as_ref().ok_or_else(|| crate::KreuzbergError::Parsing {
             message: "OCR config required for image OCR".to_string(),
             source: None,
         })?;
 
-        let backend = {
-            let registry = get_ocr_backend_registry();
-            let registry = registry.read().map_err(|e| crate::KreuzbergError::Plugin {
-                message: format!("Failed to acquire read lock on OCR backend registry: {}", e),
-                plugin_name: "ocr-registry".to_string(),
-            })?;
-            registry.get(&ocr_config.backend)?
+        let backend = crate::plugins::resolve_ocr_backend(ocr_config)?;
+        let backend = crate::plugins::guard::guard_ocr_backend(backend, config);
+
+        let mut result = if mime_type.to_lowercase().contains("tiff") {
+            extract_tiff_with_ocr(content, config, ocr_config, backend).await?
+        } else {
+            // Thread output_format from ExtractionConfig to OcrConfig
+            let mut ocr_config_with_format = ocr_config.for_page(&OcrPageContext {
+                page_number: 1,
+                page_count: 1,
+                width_points: 0.0,
+                height_points: 0.0,
+            });
+            ocr_config_with_format.output_format = Some(config.output_format);
+            crate::ocr::scheduler::ensure_handwriting_supported(backend.as_ref(), &ocr_config_with_format)?;
+
+            if ocr_config_with_format.auto_rotate || ocr_config_with_format.skip_blank_pages.is_some() {
+                let page = crate::extraction::image::decode_image(content)
+                    .map_err(|e| crate::KreuzbergError::parsing(format!("Failed to decode image: {}", e)))?;
+                let dimensions = (f64::from(page.width()), f64::from(page.height()));
+                let (page, preparation) =
+                    PagePreparer::new(&ocr_config_with_format).prepare(1, page, &ocr_config_with_format);
+
+                let mut result = if preparation.is_skipped() {
+                    blank_page_result()
+                } else if preparation.rotation.unwrap_or(0) == 0 {
+                    backend.process_image(content, &ocr_config_with_format).await?
+                } else {
+                    backend
+                        .process_image(&encode_png(&page)?, &ocr_config_with_format)
+                        .await?
+                };
+                result.pages = None;
+                result.metadata.pages = Some(PageStructure {
+                    total_count: 1,
+                    unit_type: PageUnitType::Page,
+                    boundaries: None,
+                    pages: Some(vec![preparation.page_info(1, Some(dimensions))]),
+                });
+                result
+            } else {
+                let mut result = backend.process_image(content, &ocr_config_with_format).await?;
+                result.pages = None;
+                result
+            }
         };
+        crate::text::bidi::annotate_text_direction(&result.content, &mut result.metadata);
+
+        Ok(result)
+    }
+}
+
+/// Record the barcodes of an image in `metadata.additional["barcodes"]` when detection is configured.
+#[cfg(feature = "barcodes")]
+fn add_barcodes(metadata: &mut Metadata, content: &[u8], mime_type: &str, config: &ExtractionConfig) -> Result<()> {
+    let Some(barcode_config) = config.barcodes.as_ref() else {
+        return Ok(());
+    };
+    let barcodes = crate::extraction::barcode::detect_image_barcodes(content, mime_type, barcode_config)?;
+    if !barcodes.is_empty() {
+        metadata
+            .additional
+            .insert(std::borrow::Cow::Borrowed("barcodes"), serde_json::json!(barcodes));
+    }
+    Ok(())
+}
 
-        // Thread output_format from ExtractionConfig to OcrConfig
-        let mut ocr_config_with_format = ocr_config.clone();
-        ocr_config_with_format.output_format = Some(config.output_format);
+/// Record the signatures and stamps of an image in `metadata.additional["signatures"]`
+/// when detection is configured.
+#[cfg(feature = "ocr")]
+fn add_signatures(metadata: &mut Metadata, content: &[u8], mime_type: &str, config: &ExtractionConfig) -> Result<()> {
+    let Some(signature_config) = config.signatures.as_ref() else {
+        return Ok(());
+    };
+    let signatures = crate::extraction::signature::detect_image_signatures(content, mime_type, signature_config)?;
+    if !signatures.is_empty() {
+        metadata
+            .additional
+            .insert(std::borrow::Cow::Borrowed("signatures"), serde_json::json!(signatures));
+    }
+    Ok(())
+}
 
-        let ocr_result = backend.process_image(content, &ocr_config_with_format).await?;
+/// OCR the pages of a TIFF in page order.
+///
+/// Pages are decoded on a blocking thread and recognized by the OCR page scheduler,
+/// like PDF pages. Pages are separated as in PDF text, with page markers when
+/// configured, and the page boundaries, pixel dimensions, applied rotations and blank
+/// pages are recorded in `metadata.pages`. Duplicate pages are reported in
+/// `metadata.additional` and, when dropped, left out of the content.
+#[cfg(feature = "ocr")]
+async fn extract_tiff_with_ocr(
+    content: &[u8],
+    config: &ExtractionConfig,
+    ocr_config: &OcrConfig,
+    backend: Arc<dyn OcrBackend>,
+) -> Result<ExtractionResult> {
+    use crate::ocr::scheduler::{page_job_channel, run_page_jobs};
+
+    let concurrency = ocr_config.effective_concurrency();
+    let (sender, receiver) = page_job_channel(concurrency);
+
+    let tiff_bytes = content.to_vec();
+    let decode_config = ocr_config.clone();
+    let output_format = config.output_format;
+    let page_config = config.pages.clone();
+    let span = tracing::Span::current();
+    let producer = tokio::task::spawn_blocking(move || {
+        let _guard = span.entered();
+        let mut queued_pages = Vec::new();
+        if let Err(e) = decode_page_jobs(
+            &tiff_bytes,
+            &decode_config,
+            output_format,
+            page_config.as_ref(),
+            &sender,
+            &mut queued_pages,
+        ) {
+            // The receiver may already be gone if OCR failed; the error is reported either way.
+            let _ = sender.blocking_send(Err(e));
+        }
+        queued_pages
+    });
 
-        let ocr_text = ocr_result.content.clone();
-        let ocr_extraction_result = crate::extraction::image::extract_text_from_image_with_ocr(
-            content,
-            mime_type,
-            ocr_text,
-            config.pages.as_ref(),
-        )?;
+    let results = run_page_jobs(backend, receiver, concurrency).await;
+    let queued_pages = producer.await.map_err(|e| crate::KreuzbergError::Ocr {
+        message: format!("TIFF page decoding task failed: {}", e),
+        source: None,
+    })?;
 
-        let mut result = ocr_result;
-        result.content = ocr_extraction_result.content;
-        result.pages = ocr_extraction_result.page_contents;
+    Ok(assemble_pages(results?, queued_pages, config.pages.as_ref()))
+}
 
-        Ok(result)
+/// Decode every selected page of a TIFF and queue it for OCR.
+///
+/// Blocks when the queue is full, so decoding never runs more than the OCR
+/// concurrency ahead of recognition, and records the pixel dimensions of each queued
+/// page and how it was prepared: rotated with `auto_rotate`, detected as blank with
+/// `skip_blank_pages` or as a duplicate with `duplicate_pages`. Pages outside the page
+/// selection are queued as skipped without being decoded. Returns early without
+/// error when the receiver has been dropped.
+#[cfg(feature = "ocr")]
+fn decode_page_jobs(
+    content: &[u8],
+    ocr_config: &OcrConfig,
+    output_format: OutputFormat,
+    pages: Option<&PageConfig>,
+    sender: &tokio::sync::mpsc::Sender<Result<OcrPageJob>>,
+    queued_pages: &mut Vec<(PageInfo, PagePreparation)>,
+) -> Result<()> {
+    let page_count = TiffPages::count(content)?;
+    let mut preparer = PagePreparer::new(ocr_config);
+    let mut tiff_pages = TiffPages::new(content)?;
+
+    for page_index in 0..page_count {
+        if pages.is_some_and(|pages| !pages.includes_page(page_index + 1)) {
+            if let Some(skipped) = tiff_pages.skip_page() {
+                skipped?;
+            }
+            let preparation = PagePreparation::excluded();
+            queued_pages.push((preparation.page_info(page_index + 1, None), preparation));
+            let job = OcrPageJob {
+                index: page_index,
+                image: Vec::new(),
+                config: ocr_config.clone(),
+                skip: true,
+            };
+            if sender.blocking_send(Ok(job)).is_err() {
+                return Ok(());
+            }
+            continue;
+        }
+
+        let Some(page) = tiff_pages.next() else {
+            break;
+        };
+        let page = page?;
+        let mut page_config = ocr_config.for_page(&OcrPageContext {
+            page_number: page_index + 1,
+            page_count,
+            width_points: 0.0,
+            height_points: 0.0,
+        });
+        page_config.output_format = Some(output_format);
+
+        let dimensions = (f64::from(page.width()), f64::from(page.height()));
+        let (page, preparation) = preparer.prepare(page_index + 1, page, &page_config);
+        let image = if preparation.is_skipped() {
+            Vec::new()
+        } else {
+            encode_png(&page)?
+        };
+        queued_pages.push((preparation.page_info(page_index + 1, Some(dimensions)), preparation));
+
+        let job = OcrPageJob {
+            index: page_index,
+            image,
+            config: page_config,
+            skip: preparation.is_skipped(),
+        };
+        if sender.blocking_send(Ok(job)).is_err() {
+            return Ok(());
+        }
+    }
+
+    Ok(())
+}
+
+/// Join per-page OCR results into one result with page boundaries and page metadata.
+///
+/// The metadata of the first page's result is kept; tables are numbered by their page.
+/// Dropped duplicate pages only keep their page metadata, and pages outside the page
+/// selection are left out entirely.
+#[cfg(feature = "ocr")]
+fn assemble_pages(
+    page_results: Vec<ExtractionResult>,
+    queued_pages: Vec<(PageInfo, PagePreparation)>,
+    page_config: Option<&PageConfig>,
+) -> ExtractionResult {
+    let mut content = String::new();
+    let mut boundaries = Vec::with_capacity(page_results.len());
+    let mut page_infos = Vec::with_capacity(page_results.len());
+    let mut page_contents = page_config.filter(|config| config.extract_pages).map(|_| Vec::new());
+    let mut tables = Vec::new();
+    let mut metadata = None;
+    let mut preparations = Vec::with_capacity(page_results.len());
+    let mut queued_pages = queued_pages.into_iter();
+    let page_count = page_results.len();
+
+    for (page_index, page) in page_results.into_iter().enumerate() {
+        let page_number = page_index + 1;
+        let (mut page_info, preparation) = queued_pages.next().unwrap_or_else(|| {
+            (
+                PagePreparation::default().page_info(page_number, None),
+                PagePreparation::default(),
+            )
+        });
+        preparations.push(preparation);
+        if preparation.excluded {
+            continue;
+        }
+        if preparation.dropped {
+            page_infos.push(page_info);
+            continue;
+        }
+
+        if let Some(config) = page_config.filter(|config| config.insert_page_markers) {
+            config.push_marker(&mut content, page_number);
+        } else if !boundaries.is_empty() {
+            content.push_str("\n\n");
+        }
+
+        let byte_start = content.len();
+        content.push_str(&page.content);
+        boundaries.push(PageBoundary {
+            byte_start,
+            byte_end: content.len(),
+            page_number,
+        });
+
+        let page_tables: Vec<Table> = page
+            .tables
+            .into_iter()
+            .map(|table| Table { page_number, ..table })
+            .collect();
+        page_info.table_count = Some(page_tables.len());
+        page_infos.push(page_info);
+        if let Some(pages) = page_contents.as_mut() {
+            pages.push(PageContent {
+                page_number,
+                content: page.content,
+                tables: page_tables.iter().cloned().map(Arc::new).collect(),
+                images: vec![],
+                hierarchy: None,
+            });
+        }
+        tables.extend(page_tables);
+        metadata.get_or_insert(page.metadata);
     }
+
+    let mut metadata = metadata.unwrap_or_default();
+    crate::ocr::duplicate_page::record_duplicate_pages(&mut metadata, &duplicate_pages(&preparations));
+    metadata.pages = Some(PageStructure {
+        total_count: page_count,
+        unit_type: PageUnitType::Page,
+        boundaries: Some(boundaries),
+        pages: Some(page_infos),
+    });
+
+    ExtractionResult {
+        content,
+        mime_type: "image/tiff".into(),
+        metadata,
+        pages: page_contents,
+        tables,
+        detected_languages: None,
+        chunks: None,
+        images: None,
+        djot_content: None,
+        scripts: None,
+        elements: None,
+    }
+}
+
+#[cfg(feature = "ocr")]
+fn encode_png(page: &image::DynamicImage) -> Result<Vec<u8>> {
+    let mut image_bytes = Cursor::new(Vec::new());
+    page.write_to(&mut image_bytes, image::ImageFormat::Png)
+        .map_err(|e| crate::KreuzbergError::Parsing {
+            message: format!("Failed to encode image: {}", e),
+            source: None,
+        })?;
+    Ok(image_bytes.into_inner())
 }
 
 impl Default for ImageExtractor {
@@ -90,7 +388,7 @@ impl Plugin for ImageExtractor {
     }
 
     fn description(&self) -> &str {
-        "Extracts dimensions, format, and EXIF data from images (PNG, JPEG, WebP, BMP, TIFF, GIF)"
+        "Extracts dimensions, format, and EXIF data from images (PNG, JPEG, WebP, BMP, TIFF, GIF, HEIC, AVIF, JPEG XL)"
     }
 
     fn author(&self) -> &str {
@@ -129,6 +427,9 @@ impl DocumentExtractor for ImageExtractor {
 
                 ocr_result.metadata.format = Some(crate::types::FormatMetadata::Image(image_metadata));
                 ocr_result.mime_type = mime_type.to_string().into();
+                #[cfg(feature = "barcodes")]
+                add_barcodes(&mut ocr_result.metadata, content, mime_type, config)?;
+                add_signatures(&mut ocr_result.metadata, content, mime_type, config)?;
 
                 return Ok(ocr_result);
             }
@@ -152,26 +453,36 @@ impl DocumentExtractor for ImageExtractor {
                     chunks: None,
                     images: None,
                     djot_content: None,
+                    scripts: None,
+                    elements: None,
                 });
             }
         }
 
+        #[cfg_attr(not(feature = "ocr"), allow(unused_mut))]
+        let mut metadata = Metadata {
+            format: Some(crate::types::FormatMetadata::Image(image_metadata)),
+            ..Default::default()
+        };
+        #[cfg(feature = "barcodes")]
+        add_barcodes(&mut metadata, content, mime_type, config)?;
+        #[cfg(feature = "ocr")]
+        add_signatures(&mut metadata, content, mime_type, config)?;
+
         Ok(ExtractionResult {
             content: format!(
                 "Image: {} {}x{}",
                 extraction_metadata.format, extraction_metadata.width, extraction_metadata.height
             ),
             mime_type: mime_type.to_string().into(),
-            metadata: Metadata {
-                format: Some(crate::types::FormatMetadata::Image(image_metadata)),
-                ..Default::default()
-            },
+            metadata,
             pages: None,
             tables: vec![],
             detected_languages: None,
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -185,12 +496,20 @@ impl DocumentExtractor for ImageExtractor {
             "image/bmp",
             "image/tiff",
             "image/gif",
+            "image/heic",
+            "image/heif",
+            "image/avif",
+            "image/jxl",
         ]
     }
 
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["ocr"]
+    }
 }
 
 #[cfg(test)]
@@ -218,6 +537,144 @@ mod tests {
         assert_eq!(extractor.priority(), 50);
     }
 
+    #[cfg(feature = "ocr")]
+    #[test]
+    fn test_assemble_pages_in_order_with_markers() {
+        let page = |content: &str| ExtractionResult {
+            content: content.to_string(),
+            mime_type: "text/plain".into(),
+            metadata: Metadata::default(),
+            pages: None,
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            scripts: None,
+            elements: None,
+        };
+        let page_config = PageConfig {
+            extract_pages: true,
+            insert_page_markers: true,
+            ..Default::default()
+        };
+
+        let result = assemble_pages(
+            vec![page("First fax page"), page("Second fax page")],
+            vec![
+                (
+                    PagePreparation::default().page_info(1, Some((1728.0, 2200.0))),
+                    PagePreparation::default(),
+                ),
+                (
+                    PagePreparation {
+                        rotation: Some(180),
+                        blank: Some(false),
+                        ..Default::default()
+                    }
+                    .page_info(2, Some((1728.0, 2400.0))),
+                    PagePreparation::default(),
+                ),
+            ],
+            Some(&page_config),
+        );
+
+        let marker = |page_num: &str| page_config.marker_format.replace("{page_num}", page_num);
+        assert_eq!(
+            result.content,
+            format!("{}First fax page{}Second fax page", marker("1"), marker("2"))
+        );
+        let structure = result.metadata.pages.unwrap();
+        assert_eq!(structure.total_count, 2);
+        let boundaries = structure.boundaries.unwrap();
+        assert_eq!(
+            &result.content[boundaries[1].byte_start..boundaries[1].byte_end],
+            "Second fax page"
+        );
+        let page_infos = structure.pages.unwrap();
+        assert_eq!(page_infos[1].dimensions, Some((1728.0, 2400.0)));
+        assert_eq!(page_infos[1].table_count, Some(0));
+        assert_eq!(page_infos[1].rotation, Some(180));
+        assert_eq!(page_infos[1].blank, Some(false));
+        assert_eq!(page_infos[0].rotation, None);
+        let pages = result.pages.unwrap();
+        assert_eq!(pages[0].page_number, 1);
+        assert_eq!(pages[0].content, "First fax page");
+
+        let result = assemble_pages(vec![page("One"), page("Two")], vec![], None);
+        assert_eq!(result.content, "One\n\nTwo");
+        assert!(result.pages.is_none());
+    }
+
+    #[cfg(feature = "ocr")]
+    #[test]
+    fn test_assemble_pages_leaves_out_dropped_duplicates() {
+        let page = |content: &str| ExtractionResult {
+            content: content.to_string(),
+            ..crate::ocr::scheduler::blank_page_result()
+        };
+        let queued = |number, duplicate_of: Option<usize>| {
+            let preparation = PagePreparation {
+                duplicate_of,
+                dropped: duplicate_of.is_some(),
+                ..Default::default()
+            };
+            (preparation.page_info(number, Some((1728.0, 2200.0))), preparation)
+        };
+
+        let result = assemble_pages(
+            vec![page("One"), page(""), page("Three")],
+            vec![queued(1, None), queued(2, Some(1)), queued(3, None)],
+            Some(&PageConfig {
+                extract_pages: true,
+                ..Default::default()
+            }),
+        );
+
+        assert_eq!(result.content, "One\n\nThree");
+        let pages = result.pages.unwrap();
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[1].page_number, 3);
+        let structure = result.metadata.pages.unwrap();
+        assert_eq!(structure.total_count, 3);
+        assert_eq!(structure.boundaries.unwrap()[1].page_number, 3);
+        assert_eq!(
+            result.metadata.additional["duplicate_page_warnings"],
+            serde_json::json!(["Dropped page 2, a duplicate of page 1"])
+        );
+    }
+
+    #[cfg(feature = "ocr")]
+    #[test]
+    fn test_assemble_pages_leaves_out_excluded_pages() {
+        let page = |content: &str| ExtractionResult {
+            content: content.to_string(),
+            ..crate::ocr::scheduler::blank_page_result()
+        };
+        let included = |number| {
+            let preparation = PagePreparation::default();
+            (preparation.page_info(number, Some((1728.0, 2200.0))), preparation)
+        };
+        let excluded = |number| {
+            (
+                PagePreparation::excluded().page_info(number, None),
+                PagePreparation::excluded(),
+            )
+        };
+
+        let result = assemble_pages(
+            vec![page(""), page("Two"), page("")],
+            vec![excluded(1), included(2), excluded(3)],
+            None,
+        );
+
+        assert_eq!(result.content, "Two");
+        let structure = result.metadata.pages.unwrap();
+        assert_eq!(structure.total_count, 3);
+        assert_eq!(structure.pages.unwrap().len(), 1);
+        assert_eq!(structure.boundaries.unwrap()[0].page_number, 2);
+    }
+
     #[test]
     fn test_image_extractor_default() {
         let extractor = ImageExtractor;
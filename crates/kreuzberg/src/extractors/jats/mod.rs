@@ -81,10 +81,8 @@ impl DocumentExtractor for JatsExtractor {
         mime_type: &str,
         config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
-        let _ = config;
-        let jats_content = std::str::from_utf8(content)
-            .map(|s| s.to_string())
-            .unwrap_or_else(|_| String::from_utf8_lossy(content).to_string());
+        crate::extraction::xml_parser::check_document(content, config.xml.as_ref())?;
+        let jats_content = crate::text::utf8_validation::decode_lossy(content);
 
         let (jats_metadata, extracted_content, _title, tables) = extract_jats_all_in_one(&jats_content)?;
 
@@ -167,6 +165,7 @@ impl DocumentExtractor for JatsExtractor {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -183,7 +182,7 @@ impl DocumentExtractor for JatsExtractor {
     )]
     #[cfg(feature = "tokio-runtime")]
     async fn extract_file(&self, path: &Path, mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
-        let bytes = tokio::fs::read(path).await?;
+        let bytes = crate::core::io::open_file_async(path, config.io.as_ref()).await?;
         self.extract_bytes(&bytes, mime_type, config).await
     }
 
@@ -194,6 +193,10 @@ impl DocumentExtractor for JatsExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["xml"]
+    }
 }
 
 #[cfg(test)]
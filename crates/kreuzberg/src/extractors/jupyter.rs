@@ -344,6 +344,7 @@ impl DocumentExtractor for JupyterExtractor {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -355,6 +356,10 @@ impl DocumentExtractor for JupyterExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["office"]
+    }
 }
 
 #[cfg(test)]
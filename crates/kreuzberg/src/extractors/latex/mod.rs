@@ -90,7 +90,7 @@ impl DocumentExtractor for LatexExtractor {
         mime_type: &str,
         _config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
-        let latex_str = String::from_utf8_lossy(content).to_string();
+        let latex_str = crate::text::utf8_validation::decode_lossy(content);
         let (text, metadata, tables) = Self::extract_from_latex(&latex_str);
 
         Ok(ExtractionResult {
@@ -102,6 +102,7 @@ impl DocumentExtractor for LatexExtractor {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         })
@@ -114,6 +115,10 @@ impl DocumentExtractor for LatexExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["office"]
+    }
 }
 
 #[cfg(test)]
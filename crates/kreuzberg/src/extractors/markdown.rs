@@ -200,7 +200,7 @@ impl DocumentExtractor for MarkdownExtractor {
         mime_type: &str,
         _config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
-        let text = String::from_utf8_lossy(content).into_owned();
+        let text = crate::text::utf8_validation::decode_lossy(content);
 
         let (yaml, remaining_content) = extract_frontmatter(&text);
 
@@ -232,6 +232,7 @@ impl DocumentExtractor for MarkdownExtractor {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         })
@@ -244,6 +245,10 @@ impl DocumentExtractor for MarkdownExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["office"]
+    }
 }
 
 #[cfg(all(test, feature = "office"))]
@@ -133,6 +133,9 @@ pub mod pptx;
 #[cfg(feature = "office")]
 pub mod rtf;
 
+#[cfg(any(feature = "xml", feature = "html"))]
+pub mod svg;
+
 #[cfg(feature = "xml")]
 pub mod xml;
 
@@ -199,7 +202,7 @@ pub use opml::OpmlExtractor;
 pub use typst::TypstExtractor;
 
 #[cfg(feature = "pdf")]
-pub use pdf::PdfExtractor;
+pub use pdf::{LopdfBackend, PdfBackend, PdfExtraction, PdfExtractor, PdfiumBackend, pdf_backend};
 
 #[cfg(all(feature = "tokio-runtime", feature = "office"))]
 pub use pptx::PptxExtractor;
@@ -207,6 +210,9 @@ pub use pptx::PptxExtractor;
 #[cfg(feature = "office")]
 pub use rtf::RtfExtractor;
 
+#[cfg(any(feature = "xml", feature = "html"))]
+pub use svg::SvgExtractor;
+
 #[cfg(feature = "xml")]
 pub use xml::XmlExtractor;
 
@@ -280,8 +286,15 @@ pub fn register_default_extractors() -> Result<()> {
     #[cfg(feature = "xml")]
     registry.register(Arc::new(XmlExtractor::new()))?;
 
+    #[cfg(any(feature = "xml", feature = "html"))]
+    registry.register(Arc::new(SvgExtractor::new()))?;
+
     #[cfg(feature = "pdf")]
     registry.register(Arc::new(PdfExtractor::new()))?;
+    #[cfg(feature = "pdf")]
+    registry.register(Arc::new(PdfExtractor::with_backend(
+        crate::core::config::PdfBackendKind::Lopdf,
+    )))?;
 
     #[cfg(feature = "excel")]
     registry.register(Arc::new(ExcelExtractor::new()))?;
@@ -366,10 +379,17 @@ mod tests {
             assert!(extractor_names.contains(&"xml-extractor".to_string()));
         }
 
-        #[cfg(feature = "pdf")]
+        #[cfg(any(feature = "xml", feature = "html"))]
         {
             expected_count += 1;
+            assert!(extractor_names.contains(&"svg-extractor".to_string()));
+        }
+
+        #[cfg(feature = "pdf")]
+        {
+            expected_count += 2;
             assert!(extractor_names.contains(&"pdf-extractor".to_string()));
+            assert!(extractor_names.contains(&"pdf-lopdf-extractor".to_string()));
         }
 
         #[cfg(feature = "excel")]
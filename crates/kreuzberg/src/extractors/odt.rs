@@ -5,13 +5,15 @@
 //! Supports: OpenDocument Text (.odt)
 
 use crate::Result;
-use crate::core::config::ExtractionConfig;
+use crate::core::config::{DocxTableMode, ExtractionConfig};
+use crate::extraction::list_numbering::{ListCounters, NumberFormat};
+use crate::extraction::table_grid::{GridCell, build_grid, cells_to_text};
+use crate::extraction::xml_parser;
 use crate::extraction::{cells_to_markdown, office_metadata};
 use crate::plugins::{DocumentExtractor, Plugin};
 use crate::types::{ExtractionResult, Metadata, Table};
 use ahash::AHashMap;
 use async_trait::async_trait;
-use roxmltree::Document;
 use std::borrow::Cow;
 use std::io::Cursor;
 
@@ -20,7 +22,8 @@ use std::io::Cursor;
 /// This extractor provides:
 /// - Fast text extraction via roxmltree XML parsing
 /// - Comprehensive metadata extraction from meta.xml
-/// - Table extraction with row and cell support
+/// - Table extraction with merged cells resolved, rendered per `DocxConfig::tables`
+/// - List extraction with rendered numbers (`1.`, `a.`, `i.`) and nesting
 /// - Formatting preservation (bold, italic, strikeout)
 /// - Support for headings, paragraphs, and special elements
 pub struct OdtExtractor;
@@ -121,7 +124,7 @@ fn extract_embedded_formulas(archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>) ->
         {
             let mut xml_content = String::new();
             if file.read_to_string(&mut xml_content).is_ok()
-                && let Ok(doc) = Document::parse(&xml_content)
+                && let Some(doc) = xml_parser::try_parse_document(&xml_content, None)
             {
                 let root = doc.root_element();
 
@@ -145,79 +148,258 @@ fn extract_embedded_formulas(archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>) ->
     Ok(formula_parts.join("\n"))
 }
 
+/// Read an XML part of the ODT package, if present.
+fn read_xml_part(archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>, name: &str) -> crate::error::Result<Option<String>> {
+    use std::io::Read;
+    let Ok(mut file) = archive.by_name(name) else {
+        return Ok(None);
+    };
+    let mut xml_content = String::new();
+    file.read_to_string(&mut xml_content)
+        .map_err(|e| crate::error::KreuzbergError::parsing(format!("Failed to read {}: {}", name, e)))?;
+    Ok(Some(xml_content))
+}
+
 /// Extract text content from ODT content.xml
 ///
 /// # Arguments
 /// * `archive` - ZIP archive containing the ODT document
+/// * `table_mode` - How tables are rendered in the text
 ///
 /// # Returns
 /// * `String` - Extracted text content
-fn extract_content_text(archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>) -> crate::error::Result<String> {
-    let mut xml_content = String::new();
-
-    match archive.by_name("content.xml") {
-        Ok(mut file) => {
-            use std::io::Read;
-            file.read_to_string(&mut xml_content)
-                .map_err(|e| crate::error::KreuzbergError::parsing(format!("Failed to read content.xml: {}", e)))?;
-        }
-        Err(_) => {
-            return Ok(String::new());
-        }
-    }
-
-    let doc = Document::parse(&xml_content)
-        .map_err(|e| crate::error::KreuzbergError::parsing(format!("Failed to parse content.xml: {}", e)))?;
+fn extract_content_text(
+    archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>,
+    table_mode: DocxTableMode,
+) -> crate::error::Result<String> {
+    let Some(xml_content) = read_xml_part(archive, "content.xml")? else {
+        return Ok(String::new());
+    };
+
+    let doc = xml_parser::parse_document(&xml_content, None, |e| {
+        crate::error::KreuzbergError::parsing(format!("Failed to parse content.xml: {}", e))
+    })?;
 
     let root = doc.root_element();
 
-    let mut text_parts: Vec<String> = Vec::new();
+    // List styles live in the automatic styles of content.xml and the common styles of styles.xml.
+    let mut list_styles = ListStyles::new();
+    if let Some(styles_xml) = read_xml_part(archive, "styles.xml")?
+        && let Some(styles_doc) = xml_parser::try_parse_document(&styles_xml, None)
+    {
+        parse_list_styles(styles_doc.root_element(), &mut list_styles);
+    }
+    parse_list_styles(root, &mut list_styles);
+
+    let mut writer = OdtTextWriter {
+        list_styles: &list_styles,
+        table_mode,
+        text_parts: Vec::new(),
+        last_list: None,
+        lists_by_id: AHashMap::new(),
+    };
 
     for body_child in root.children() {
         if body_child.tag_name().name() == "body" {
             for text_elem in body_child.children() {
                 if text_elem.tag_name().name() == "text" {
-                    process_document_elements(text_elem, &mut text_parts);
+                    writer.process_document_elements(text_elem);
                 }
             }
         }
     }
 
-    Ok(text_parts.join("\n").trim().to_string())
+    Ok(writer.text_parts.join("\n").trim().to_string())
 }
 
-/// Helper function to process document elements (paragraphs, headings, tables)
-/// Only processes direct children, avoiding nested content like table cells
-fn process_document_elements(parent: roxmltree::Node, text_parts: &mut Vec<String>) {
-    for node in parent.children() {
-        match node.tag_name().name() {
-            "h" => {
-                if let Some(text) = extract_node_text(node)
-                    && !text.trim().is_empty()
-                {
-                    text_parts.push(format!("# {}", text.trim()));
-                    text_parts.push(String::new());
+fn attribute<'a>(node: roxmltree::Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.attributes().find(|a| a.name() == name).map(|a| a.value())
+}
+
+/// Rendering of one level of a `text:list-style`.
+#[derive(Debug, Clone)]
+enum ListLevelStyle {
+    Number {
+        format: NumberFormat,
+        prefix: String,
+        suffix: String,
+        /// Number of levels shown in the marker, e.g. 2 for `1.3.`
+        display_levels: usize,
+        start: usize,
+    },
+    Bullet,
+}
+
+/// List styles by name, with their levels by 0-based level.
+type ListStyles = AHashMap<String, AHashMap<usize, ListLevelStyle>>;
+
+fn parse_list_styles(root: roxmltree::Node, styles: &mut ListStyles) {
+    for style in root.descendants().filter(|n| n.tag_name().name() == "list-style") {
+        let Some(name) = attribute(style, "name") else {
+            continue;
+        };
+        let mut levels = AHashMap::new();
+        for level_style in style.children().filter(roxmltree::Node::is_element) {
+            let Some(level) = attribute(level_style, "level").and_then(|l| l.parse::<usize>().ok()) else {
+                continue;
+            };
+            let parsed = match level_style.tag_name().name() {
+                "list-level-style-number" => ListLevelStyle::Number {
+                    format: NumberFormat::from_odf(attribute(level_style, "num-format").unwrap_or_default()),
+                    prefix: attribute(level_style, "num-prefix").unwrap_or_default().to_string(),
+                    suffix: attribute(level_style, "num-suffix").unwrap_or_default().to_string(),
+                    display_levels: attribute(level_style, "display-levels")
+                        .and_then(|d| d.parse().ok())
+                        .unwrap_or(1),
+                    start: attribute(level_style, "start-value")
+                        .and_then(|s| s.parse().ok())
+                        .unwrap_or(1),
+                },
+                "list-level-style-bullet" | "list-level-style-image" => ListLevelStyle::Bullet,
+                _ => continue,
+            };
+            levels.insert(level.saturating_sub(1), parsed);
+        }
+        styles.insert(name.to_string(), levels);
+    }
+}
+
+/// Builds the text of the document body in document order.
+struct OdtTextWriter<'s> {
+    list_styles: &'s ListStyles,
+    table_mode: DocxTableMode,
+    text_parts: Vec<String>,
+    /// Counters of the most recent list, for `text:continue-numbering`
+    last_list: Option<ListCounters>,
+    /// Counters of lists with an `xml:id`, for `text:continue-list`
+    lists_by_id: AHashMap<String, ListCounters>,
+}
+
+impl OdtTextWriter<'_> {
+    /// Helper function to process document elements (paragraphs, headings, lists, tables)
+    /// Only processes direct children, avoiding nested content like table cells
+    fn process_document_elements(&mut self, parent: roxmltree::Node) {
+        for node in parent.children() {
+            match node.tag_name().name() {
+                "h" => {
+                    if let Some(text) = extract_node_text(node)
+                        && !text.trim().is_empty()
+                    {
+                        self.text_parts.push(format!("# {}", text.trim()));
+                        self.text_parts.push(String::new());
+                    }
                 }
-            }
-            "p" => {
-                if let Some(text) = extract_node_text(node)
-                    && !text.trim().is_empty()
-                {
-                    text_parts.push(text.trim().to_string());
-                    text_parts.push(String::new());
+                "p" => {
+                    if let Some(text) = extract_node_text(node)
+                        && !text.trim().is_empty()
+                    {
+                        self.text_parts.push(text.trim().to_string());
+                        self.text_parts.push(String::new());
+                    }
+                }
+                "list" => {
+                    let mut counters = match (attribute(node, "continue-list"), attribute(node, "continue-numbering")) {
+                        (Some(id), _) => self.lists_by_id.get(id).cloned().unwrap_or_default(),
+                        (None, Some("true")) => self.last_list.clone().unwrap_or_default(),
+                        _ => ListCounters::default(),
+                    };
+                    let parts_before = self.text_parts.len();
+                    self.process_list(node, 0, attribute(node, "style-name"), &mut counters);
+                    if self.text_parts.len() > parts_before {
+                        self.text_parts.push(String::new());
+                    }
+                    if let Some(id) = attribute(node, "id") {
+                        self.lists_by_id.insert(id.to_string(), counters.clone());
+                    }
+                    self.last_list = Some(counters);
+                }
+                "table" => {
+                    if let Some(table_text) = extract_table_text(node, self.table_mode) {
+                        self.text_parts.push(table_text);
+                        self.text_parts.push(String::new());
+                    }
                 }
+                "section" => self.process_document_elements(node),
+                _ => {}
             }
-            "table" => {
-                if let Some(table_text) = extract_table_text(node) {
-                    text_parts.push(table_text);
-                    text_parts.push(String::new());
+        }
+    }
+
+    /// Render the items of a list, one line per paragraph, indented by nesting level.
+    fn process_list(&mut self, list: roxmltree::Node, level: usize, style: Option<&str>, counters: &mut ListCounters) {
+        for item in list.children() {
+            let is_header = match item.tag_name().name() {
+                "list-item" => false,
+                "list-header" => true,
+                _ => continue,
+            };
+            if !is_header && let Some(start) = attribute(item, "start-value").and_then(|s| s.parse().ok()) {
+                counters.restart(level, start);
+            }
+
+            let mut numbered = is_header;
+            for part in item.children() {
+                match part.tag_name().name() {
+                    "p" | "h" => {
+                        let Some(text) = extract_node_text(part).filter(|t| !t.trim().is_empty()) else {
+                            continue;
+                        };
+                        let mut line = "  ".repeat(level);
+                        if !numbered {
+                            numbered = true;
+                            let marker = self.list_marker(style, level, counters);
+                            if !marker.is_empty() {
+                                line.push_str(&marker);
+                                line.push(' ');
+                            }
+                        }
+                        line.push_str(text.trim());
+                        self.text_parts.push(line);
+                    }
+                    "list" => {
+                        let nested_style = attribute(part, "style-name").or(style);
+                        self.process_list(part, level + 1, nested_style, counters);
+                    }
+                    _ => {}
                 }
             }
-            _ => {}
         }
     }
+
+    /// Advance the counters for a list item and render its marker (`1.`, `a)`, `1.2.`, `•`).
+    fn list_marker(&self, style: Option<&str>, level: usize, counters: &mut ListCounters) -> String {
+        let levels = style.and_then(|name| self.list_styles.get(name));
+        let Some(ListLevelStyle::Number {
+            prefix,
+            suffix,
+            display_levels,
+            ..
+        }) = levels.and_then(|levels| levels.get(&level))
+        else {
+            return BULLET.to_string();
+        };
+
+        let level_style = |l: usize| levels.and_then(|levels| levels.get(&l));
+        let values = counters.next(level, |l| match level_style(l) {
+            Some(ListLevelStyle::Number { start, .. }) => *start,
+            _ => 1,
+        });
+        let first = (level + 1).saturating_sub((*display_levels).max(1));
+        let numbers: Vec<String> = (first..=level)
+            .filter_map(|l| match level_style(l) {
+                Some(ListLevelStyle::Number { format, .. }) => Some(format.format(values[l])),
+                _ => None,
+            })
+            .filter(|number| !number.is_empty())
+            .collect();
+
+        format!("{}{}{}", prefix, numbers.join("."), suffix)
+    }
 }
 
+/// Marker of bulleted list items.
+const BULLET: &str = "•";
+
 /// Extract text from a single XML node, handling spans and formatting
 ///
 /// # Arguments
@@ -226,104 +408,125 @@ fn process_document_elements(parent: roxmltree::Node, text_parts: &mut Vec<Strin
 /// # Returns
 /// * `Option<String>` - The extracted text with formatting preserved
 fn extract_node_text(node: roxmltree::Node) -> Option<String> {
-    let mut text_parts = Vec::new();
+    let mut text = String::new();
+    collect_node_text(node, &mut text);
+    if text.is_empty() { None } else { Some(text) }
+}
 
+fn collect_node_text(node: roxmltree::Node, text: &mut String) {
     for child in node.children() {
+        if child.is_text() {
+            text.push_str(child.text().unwrap_or_default());
+            continue;
+        }
         match child.tag_name().name() {
-            "span" => {
-                if let Some(text) = child.text() {
-                    text_parts.push(text.to_string());
-                }
-            }
-            "tab" => {
-                text_parts.push("\t".to_string());
-            }
-            "line-break" => {
-                text_parts.push("\n".to_string());
-            }
-            _ => {
-                if let Some(text) = child.text() {
-                    text_parts.push(text.to_string());
-                }
-            }
+            "tab" => text.push('\t'),
+            "line-break" => text.push('\n'),
+            "s" => {
+                let count = attribute(child, "c").and_then(|c| c.parse().ok()).unwrap_or(1);
+                text.extend(std::iter::repeat_n(' ', count));
+            }
+            // Footnotes and comments are not part of the running text.
+            "note" | "annotation" => {}
+            _ => collect_node_text(child, text),
         }
     }
-
-    if text_parts.is_empty() {
-        node.text().map(|s| s.to_string())
-    } else {
-        Some(text_parts.join(""))
-    }
 }
 
-/// Extract table content as text with markdown formatting
+/// Largest `number-rows-repeated` / `number-columns-repeated` expanded.
+const MAX_REPEAT: usize = 1000;
+
+/// Extract table content as text in the configured table mode
 ///
 /// # Arguments
 /// * `table_node` - The table XML node
+/// * `table_mode` - Markdown table, tab-separated text, or nothing for structured-only tables
 ///
 /// # Returns
-/// * `Option<String>` - Markdown formatted table
-fn extract_table_text(table_node: roxmltree::Node) -> Option<String> {
-    let mut rows = Vec::new();
-    let mut max_cols = 0;
-
-    for row_node in table_node.children() {
-        if row_node.tag_name().name() == "table-row" {
-            let mut row_cells = Vec::new();
-
-            for cell_node in row_node.children() {
-                if cell_node.tag_name().name() == "table-cell" {
-                    let cell_text = extract_node_text(cell_node).unwrap_or_default();
-                    row_cells.push(cell_text.trim().to_string());
-                }
-            }
-
-            if !row_cells.is_empty() {
-                max_cols = max_cols.max(row_cells.len());
-                rows.push(row_cells);
-            }
-        }
-    }
-
-    if rows.is_empty() {
+/// * `Option<String>` - Rendered table
+fn extract_table_text(table_node: roxmltree::Node, table_mode: DocxTableMode) -> Option<String> {
+    let cells = table_cells(table_node);
+    if cells.is_empty() {
         return None;
     }
 
-    for row in &mut rows {
-        while row.len() < max_cols {
-            row.push(String::new());
-        }
+    match table_mode {
+        DocxTableMode::Markdown => Some(cells_to_markdown(&cells)),
+        DocxTableMode::Text => Some(cells_to_text(&cells)),
+        DocxTableMode::Structured => None,
     }
+}
 
-    let mut markdown = String::new();
+/// Cells of a table with spanned cells resolved and trailing empty rows and columns removed.
+fn table_cells(table_node: roxmltree::Node) -> Vec<Vec<String>> {
+    let mut rows = Vec::new();
+    collect_table_rows(table_node, &mut rows);
 
-    if !rows.is_empty() {
-        markdown.push('|');
-        for cell in &rows[0] {
-            markdown.push(' ');
-            markdown.push_str(cell);
-            markdown.push_str(" |");
-        }
-        markdown.push('\n');
+    let mut cells = build_grid(rows);
+    while cells.last().is_some_and(|row| row.iter().all(String::is_empty)) {
+        cells.pop();
+    }
+    let width = (0..cells.first().map_or(0, Vec::len))
+        .rev()
+        .find(|&column| cells.iter().any(|row| !row[column].is_empty()))
+        .map_or(0, |column| column + 1);
+    if width == 0 {
+        return Vec::new();
+    }
+    for row in &mut cells {
+        row.truncate(width);
+    }
+    cells
+}
 
-        markdown.push('|');
-        for _ in 0..rows[0].len() {
-            markdown.push_str(" --- |");
-        }
-        markdown.push('\n');
-
-        for row in rows.iter().skip(1) {
-            markdown.push('|');
-            for cell in row {
-                markdown.push(' ');
-                markdown.push_str(cell);
-                markdown.push_str(" |");
+fn collect_table_rows(parent: roxmltree::Node, rows: &mut Vec<Vec<GridCell>>) {
+    for node in parent.children() {
+        match node.tag_name().name() {
+            "table-row" => {
+                let mut row = Vec::new();
+                for cell_node in node.children() {
+                    // Covered cells are the positions hidden by a spanning cell.
+                    if cell_node.tag_name().name() != "table-cell" {
+                        continue;
+                    }
+                    let number = |name: &str| attribute(cell_node, name).and_then(|n| n.parse::<usize>().ok());
+                    let cell = GridCell {
+                        row_span: number("number-rows-spanned").unwrap_or(1).max(1),
+                        col_span: number("number-columns-spanned").unwrap_or(1).max(1),
+                        ..GridCell::new(cell_text(cell_node))
+                    };
+                    let repeat = number("number-columns-repeated").unwrap_or(1).clamp(1, MAX_REPEAT);
+                    row.extend(std::iter::repeat_n(cell, repeat));
+                }
+                let repeat = attribute(node, "number-rows-repeated")
+                    .and_then(|n| n.parse::<usize>().ok())
+                    .unwrap_or(1)
+                    .clamp(1, MAX_REPEAT);
+                rows.extend(std::iter::repeat_n(row, repeat));
             }
-            markdown.push('\n');
+            "table-header-rows" | "table-rows" | "table-row-group" => collect_table_rows(node, rows),
+            _ => {}
         }
     }
+}
 
-    Some(markdown)
+/// Text of a table cell: its paragraphs, list items and nested tables joined by spaces.
+fn cell_text(cell_node: roxmltree::Node) -> String {
+    let mut parts = Vec::new();
+    for node in cell_node.descendants().skip(1) {
+        if matches!(node.tag_name().name(), "p" | "h")
+            && !node
+                .ancestors()
+                .skip(1)
+                .take_while(|a| *a != cell_node)
+                .any(|a| matches!(a.tag_name().name(), "p" | "h"))
+            && let Some(text) = extract_node_text(node)
+            && !text.trim().is_empty()
+        {
+            parts.push(text.trim().to_string());
+        }
+    }
+    parts.join(" ")
 }
 
 /// Extract tables from ODT content.xml
@@ -334,21 +537,13 @@ fn extract_table_text(table_node: roxmltree::Node) -> Option<String> {
 /// # Returns
 /// * `Result<Vec<Table>>` - Extracted tables
 fn extract_tables(archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>) -> crate::error::Result<Vec<Table>> {
-    let mut xml_content = String::new();
+    let Some(xml_content) = read_xml_part(archive, "content.xml")? else {
+        return Ok(Vec::new());
+    };
 
-    match archive.by_name("content.xml") {
-        Ok(mut file) => {
-            use std::io::Read;
-            file.read_to_string(&mut xml_content)
-                .map_err(|e| crate::error::KreuzbergError::parsing(format!("Failed to read content.xml: {}", e)))?;
-        }
-        Err(_) => {
-            return Ok(Vec::new());
-        }
-    }
-
-    let doc = Document::parse(&xml_content)
-        .map_err(|e| crate::error::KreuzbergError::parsing(format!("Failed to parse content.xml: {}", e)))?;
+    let doc = xml_parser::parse_document(&xml_content, None, |e| {
+        crate::error::KreuzbergError::parsing(format!("Failed to parse content.xml: {}", e))
+    })?;
 
     let root = doc.root_element();
     let mut tables = Vec::new();
@@ -375,24 +570,7 @@ fn extract_tables(archive: &mut zip::ZipArchive<Cursor<Vec<u8>>>) -> crate::erro
 /// # Returns
 /// * `Option<Table>` - Parsed table
 fn parse_odt_table(table_node: roxmltree::Node, table_index: usize) -> Option<Table> {
-    let mut cells: Vec<Vec<String>> = Vec::new();
-
-    for row_node in table_node.children() {
-        if row_node.tag_name().name() == "table-row" {
-            let mut row_cells = Vec::new();
-
-            for cell_node in row_node.children() {
-                if cell_node.tag_name().name() == "table-cell" {
-                    let cell_text = extract_node_text(cell_node).unwrap_or_default();
-                    row_cells.push(cell_text.trim().to_string());
-                }
-            }
-
-            if !row_cells.is_empty() {
-                cells.push(row_cells);
-            }
-        }
-    }
+    let cells = table_cells(table_node);
 
     if cells.is_empty() {
         return None;
@@ -412,7 +590,7 @@ impl DocumentExtractor for OdtExtractor {
     #[cfg_attr(
         feature = "otel",
         tracing::instrument(
-            skip(self, content, _config),
+            skip(self, content, config),
             fields(
                 extractor.name = self.name(),
                 content.size_bytes = content.len(),
@@ -423,9 +601,10 @@ impl DocumentExtractor for OdtExtractor {
         &self,
         content: &[u8],
         mime_type: &str,
-        _config: &ExtractionConfig,
+        config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
         let content_owned = content.to_vec();
+        let table_mode = config.docx.as_ref().map(|docx| docx.tables).unwrap_or_default();
 
         let (text, tables) = if crate::core::batch_mode::is_batch_mode() {
             let content_for_task = content_owned.clone();
@@ -437,7 +616,7 @@ impl DocumentExtractor for OdtExtractor {
                 let mut archive = zip::ZipArchive::new(cursor)
                     .map_err(|e| crate::error::KreuzbergError::parsing(format!("Failed to open ZIP archive: {}", e)))?;
 
-                let text = extract_content_text(&mut archive)?;
+                let text = extract_content_text(&mut archive, table_mode)?;
                 let tables = extract_tables(&mut archive)?;
                 let embedded_formulas = extract_embedded_formulas(&mut archive)?;
 
@@ -460,7 +639,7 @@ impl DocumentExtractor for OdtExtractor {
             let mut archive = zip::ZipArchive::new(cursor)
                 .map_err(|e| crate::error::KreuzbergError::parsing(format!("Failed to open ZIP archive: {}", e)))?;
 
-            let text = extract_content_text(&mut archive)?;
+            let text = extract_content_text(&mut archive, table_mode)?;
             let tables = extract_tables(&mut archive)?;
             let embedded_formulas = extract_embedded_formulas(&mut archive)?;
 
@@ -488,12 +667,16 @@ impl DocumentExtractor for OdtExtractor {
             if let Some(title) = odt_props.title {
                 metadata_map.insert(Cow::Borrowed("title"), serde_json::Value::String(title));
             }
-            if let Some(creator) = odt_props.creator {
+            // dc:creator names whoever saved the document last, meta:initial-creator its author.
+            if let Some(author) = odt_props.initial_creator.clone().or_else(|| odt_props.creator.clone()) {
                 metadata_map.insert(
                     Cow::Borrowed("authors"),
-                    serde_json::Value::Array(vec![serde_json::Value::String(creator.clone())]),
+                    serde_json::Value::Array(vec![serde_json::Value::String(author.clone())]),
                 );
-                metadata_map.insert(Cow::Borrowed("created_by"), serde_json::Value::String(creator));
+                metadata_map.insert(Cow::Borrowed("created_by"), serde_json::Value::String(author));
+            }
+            if let Some(creator) = odt_props.creator {
+                metadata_map.insert(Cow::Borrowed("modified_by"), serde_json::Value::String(creator));
             }
             if let Some(initial_creator) = odt_props.initial_creator {
                 metadata_map.insert(
@@ -529,11 +712,18 @@ impl DocumentExtractor for OdtExtractor {
                 );
             }
             if let Some(editing_cycles) = odt_props.editing_cycles {
+                metadata_map.insert(
+                    Cow::Borrowed("revision"),
+                    serde_json::Value::String(editing_cycles.clone()),
+                );
                 metadata_map.insert(
                     Cow::Borrowed("editing_cycles"),
                     serde_json::Value::String(editing_cycles),
                 );
             }
+            if let Some(print_date) = odt_props.print_date {
+                metadata_map.insert(Cow::Borrowed("last_printed"), serde_json::Value::String(print_date));
+            }
             if let Some(page_count) = odt_props.page_count {
                 metadata_map.insert(
                     Cow::Borrowed("page_count"),
@@ -570,6 +760,9 @@ impl DocumentExtractor for OdtExtractor {
                     serde_json::Value::Number(image_count.into()),
                 );
             }
+            for (key, value) in odt_props.user_defined {
+                metadata_map.insert(Cow::Owned(format!("custom_{}", key)), value);
+            }
         }
 
         Ok(ExtractionResult {
@@ -585,6 +778,7 @@ impl DocumentExtractor for OdtExtractor {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -596,6 +790,10 @@ impl DocumentExtractor for OdtExtractor {
     fn priority(&self) -> i32 {
         60
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["office", "tokio-runtime"]
+    }
 }
 
 #[cfg(test)]
@@ -62,7 +62,7 @@ impl DocumentExtractor for OpmlExtractor {
     #[cfg_attr(
         feature = "otel",
         tracing::instrument(
-            skip(self, content, _config),
+            skip(self, content, config),
             fields(
                 extractor.name = self.name(),
                 content.size_bytes = content.len(),
@@ -73,9 +73,9 @@ impl DocumentExtractor for OpmlExtractor {
         &self,
         content: &[u8],
         mime_type: &str,
-        _config: &ExtractionConfig,
+        config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
-        let (extracted_content, metadata_map) = parser::extract_content_and_metadata(content)?;
+        let (extracted_content, metadata_map) = parser::extract_content_and_metadata(content, config.xml.as_ref())?;
 
         Ok(ExtractionResult {
             content: extracted_content,
@@ -90,6 +90,7 @@ impl DocumentExtractor for OpmlExtractor {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -101,6 +102,10 @@ impl DocumentExtractor for OpmlExtractor {
     fn priority(&self) -> i32 {
         55
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["office"]
+    }
 }
 
 #[cfg(all(test, feature = "office"))]
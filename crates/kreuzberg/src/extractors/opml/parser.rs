@@ -24,12 +24,14 @@ use serde_json;
 #[cfg(feature = "office")]
 pub(crate) fn extract_content_and_metadata(
     content: &[u8],
+    limits: Option<&crate::core::config::XmlConfig>,
 ) -> Result<(String, AHashMap<Cow<'static, str>, serde_json::Value>)> {
-    let doc = roxmltree::Document::parse(
+    let doc = crate::extraction::xml_parser::parse_document(
         std::str::from_utf8(content)
             .map_err(|e| crate::KreuzbergError::Other(format!("Invalid UTF-8 in OPML: {}", e)))?,
-    )
-    .map_err(|e| crate::KreuzbergError::Other(format!("Failed to parse OPML: {}", e)))?;
+        limits,
+        |e| crate::KreuzbergError::Other(format!("Failed to parse OPML: {}", e)),
+    )?;
 
     let mut extracted_content = String::new();
     let mut metadata = AHashMap::new();
@@ -137,7 +139,7 @@ mod tests {
   </body>
 </opml>"#;
 
-        let (content, metadata) = extract_content_and_metadata(opml).expect("Should parse simple OPML");
+        let (content, metadata) = extract_content_and_metadata(opml, None).expect("Should parse simple OPML");
 
         assert!(content.contains("Item 1"), "Should extract first item");
         assert!(content.contains("Item 2"), "Should extract second item");
@@ -164,7 +166,7 @@ mod tests {
   </body>
 </opml>"#;
 
-        let (content, _) = extract_content_and_metadata(opml).expect("Should parse nested OPML");
+        let (content, _) = extract_content_and_metadata(opml, None).expect("Should parse nested OPML");
 
         assert!(content.contains("Category"), "Should contain top level");
         assert!(content.contains("Subcategory"), "Should contain nested level");
@@ -187,7 +189,7 @@ mod tests {
   </body>
 </opml>"#;
 
-        let (content, _) = extract_content_and_metadata(opml).expect("Should parse RSS OPML");
+        let (content, _) = extract_content_and_metadata(opml, None).expect("Should parse RSS OPML");
 
         assert!(content.contains("Hacker News"), "Should extract feed title");
         assert!(
@@ -213,7 +215,7 @@ mod tests {
   </body>
 </opml>"#;
 
-        let (_content, metadata) = extract_content_and_metadata(opml).expect("Should extract metadata");
+        let (_content, metadata) = extract_content_and_metadata(opml, None).expect("Should extract metadata");
 
         assert_eq!(metadata.get("title").and_then(|v| v.as_str()), Some("My Feeds"));
         assert_eq!(metadata.get("ownerName").and_then(|v| v.as_str()), Some("John Doe"));
@@ -238,7 +240,7 @@ mod tests {
   </body>
 </opml>"#;
 
-        let (content, metadata) = extract_content_and_metadata(opml).expect("Should handle special characters");
+        let (content, metadata) = extract_content_and_metadata(opml, None).expect("Should handle special characters");
 
         assert!(
             content.contains("Business") && content.contains("Startups"),
@@ -259,7 +261,7 @@ mod tests {
   </body>
 </opml>"#;
 
-        let (_content, metadata) = extract_content_and_metadata(opml).expect("Should handle empty body");
+        let (_content, metadata) = extract_content_and_metadata(opml, None).expect("Should handle empty body");
 
         assert_eq!(metadata.get("title").and_then(|v| v.as_str()), Some("Empty"));
     }
@@ -276,7 +278,7 @@ mod tests {
   </body>
 </opml>"#;
 
-        let result = extract_content_and_metadata(opml);
+        let result = extract_content_and_metadata(opml, None);
         assert!(result.is_err(), "Should fail to parse OPML with missing closing tags");
     }
 
@@ -291,7 +293,7 @@ mod tests {
   </body>
 </opml>"#;
 
-        let result = extract_content_and_metadata(opml);
+        let result = extract_content_and_metadata(opml, None);
         assert!(result.is_err(), "Should fail to parse OPML with invalid nesting");
     }
 
@@ -312,7 +314,8 @@ mod tests {
   </body>
 </opml>"#;
 
-        let (content, metadata) = extract_content_and_metadata(opml).expect("Should handle empty outline elements");
+        let (content, metadata) =
+            extract_content_and_metadata(opml, None).expect("Should handle empty outline elements");
 
         assert!(content.contains("Valid Item"), "Should extract valid items");
         assert!(content.contains("Another Valid"), "Should extract nested valid items");
@@ -343,7 +346,7 @@ mod tests {
   </body>
 </opml>"#;
 
-        let (content, _) = extract_content_and_metadata(opml).expect("Should handle deeply nested structures");
+        let (content, _) = extract_content_and_metadata(opml, None).expect("Should handle deeply nested structures");
 
         assert!(content.contains("Level 1"), "Should extract top-level item");
         assert!(content.contains("Deep Item"), "Should extract deeply nested item");
@@ -365,7 +368,7 @@ mod tests {
 </opml>"#;
 
         let (content, metadata) =
-            extract_content_and_metadata(opml).expect("Should handle outline with missing text attribute");
+            extract_content_and_metadata(opml, None).expect("Should handle outline with missing text attribute");
 
         assert!(content.contains("Valid Item"), "Should extract item with text");
         assert!(!content.contains("https://"), "Should not extract URLs");
@@ -391,7 +394,7 @@ mod tests {
   </body>
 </opml>"#;
 
-        let (content, _) = extract_content_and_metadata(opml).expect("Should handle whitespace-only text");
+        let (content, _) = extract_content_and_metadata(opml, None).expect("Should handle whitespace-only text");
 
         assert!(
             content.contains("Real Content"),
@@ -417,7 +420,7 @@ mod tests {
   </body>
 </opml>"#;
 
-        let (content, metadata) = extract_content_and_metadata(opml).expect("Should handle HTML entities");
+        let (content, metadata) = extract_content_and_metadata(opml, None).expect("Should handle HTML entities");
 
         assert!(
             content.contains("News") && content.contains("Updates"),
@@ -445,7 +448,7 @@ mod tests {
   </body>
 </opml>"#;
 
-        let (content, metadata) = extract_content_and_metadata(opml).expect("Should handle single outline");
+        let (content, metadata) = extract_content_and_metadata(opml, None).expect("Should handle single outline");
 
         assert!(content.contains("Only Item"), "Should extract single item");
         assert_eq!(metadata.get("title").and_then(|v| v.as_str()), Some("Single"));
@@ -460,7 +463,7 @@ mod tests {
   </head>
 </opml>"#;
 
-        let (content, metadata) = extract_content_and_metadata(opml).expect("Should handle OPML without body");
+        let (content, metadata) = extract_content_and_metadata(opml, None).expect("Should handle OPML without body");
 
         assert_eq!(metadata.get("title").and_then(|v| v.as_str()), Some("No Body"));
         assert!(content.is_empty() || content.trim() == "No Body");
@@ -475,7 +478,7 @@ mod tests {
   </body>
 </opml>"#;
 
-        let (content, metadata) = extract_content_and_metadata(opml).expect("Should handle OPML without head");
+        let (content, metadata) = extract_content_and_metadata(opml, None).expect("Should handle OPML without head");
 
         assert!(content.contains("Item"), "Should extract body content");
         assert!(metadata.is_empty(), "Should have no metadata without head");
@@ -289,7 +289,7 @@ impl DocumentExtractor for OrgModeExtractor {
         mime_type: &str,
         _config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
-        let org_text = String::from_utf8_lossy(content).into_owned();
+        let org_text = crate::text::utf8_validation::decode_lossy(content);
 
         let lines: Vec<String> = org_text.lines().map(|s| s.to_string()).collect();
         let org = Org::from_vec(&lines)?;
@@ -307,6 +307,7 @@ impl DocumentExtractor for OrgModeExtractor {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         })
@@ -319,6 +320,10 @@ impl DocumentExtractor for OrgModeExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["office"]
+    }
 }
 
 #[cfg(all(test, feature = "office"))]
@@ -0,0 +1,282 @@
+//! PDF engines.
+//!
+//! The PDF extractor parses documents with a [`PdfBackend`], chosen by
+//! `pdf_options.backend`. Engines fail on different malformed documents, so a corpus
+//! that crashes one can often be extracted with the other. Whatever the engine, pages
+//! are rendered for OCR with Pdfium.
+
+use super::extraction::extract_all_from_document;
+use crate::Result;
+use crate::core::config::{ExtractionConfig, PdfBackendKind};
+use crate::pdf::error::PdfError;
+use crate::pdf::metadata::{PdfExtractionMetadata, extract_metadata_from_lopdf};
+use crate::types::{PageBoundary, PageContent, Table};
+
+/// Text, metadata and tables of a PDF document, as read by a [`PdfBackend`].
+#[derive(Debug, Clone)]
+pub struct PdfExtraction {
+    /// Metadata, with the page structure when pages are tracked
+    pub metadata: PdfExtractionMetadata,
+    /// Text of the selected pages
+    pub text: String,
+    /// Tables found from text positions
+    pub tables: Vec<Table>,
+    /// Content of each selected page, when `pages.extract_pages` is set
+    pub pages: Option<Vec<PageContent>>,
+    /// Byte ranges of the pages in `text`, when `pages` is configured
+    pub boundaries: Option<Vec<PageBoundary>>,
+}
+
+/// An engine that parses PDF documents.
+pub trait PdfBackend: Send + Sync {
+    /// Engine name, as in `pdf_options.backend`
+    fn name(&self) -> &'static str;
+
+    /// Read the text, metadata and tables of the PDF `content`, honoring the page,
+    /// cleanup and PDF options of `config` that the engine supports.
+    fn extract(&self, content: &[u8], config: &ExtractionConfig) -> Result<PdfExtraction>;
+}
+
+/// The engine of `kind`.
+pub fn pdf_backend(kind: PdfBackendKind) -> &'static dyn PdfBackend {
+    match kind {
+        PdfBackendKind::Pdfium => &PdfiumBackend,
+        PdfBackendKind::Lopdf => &LopdfBackend,
+    }
+}
+
+/// Pdfium, with text layout, tables, hierarchy, watermark and redaction detection.
+pub struct PdfiumBackend;
+
+impl PdfBackend for PdfiumBackend {
+    fn name(&self) -> &'static str {
+        "pdfium"
+    }
+
+    fn extract(&self, content: &[u8], config: &ExtractionConfig) -> Result<PdfExtraction> {
+        let pdfium = crate::pdf::bindings::bind_pdfium(PdfError::MetadataExtractionFailed, "initialize Pdfium");
+        #[cfg(target_arch = "wasm32")]
+        let pdfium = pdfium.map_err(|pdf_err| {
+            if pdf_err.to_string().contains("WASM") || pdf_err.to_string().contains("Module") {
+                crate::error::KreuzbergError::Parsing {
+                    message: "PDF extraction requires proper WASM module initialization. \
+                         Ensure your WASM environment is set up with PDFium support. \
+                         See: https://docs.kreuzberg.dev/wasm/pdf"
+                        .to_string(),
+                    source: None,
+                }
+            } else {
+                pdf_err.into()
+            }
+        });
+        let pdfium = pdfium?;
+
+        let document = pdfium.load_pdf_from_byte_slice(content, None).map_err(|e| {
+            let err_msg = crate::pdf::error::format_pdfium_error(e);
+            if err_msg.contains("password") || err_msg.contains("Password") {
+                PdfError::PasswordRequired
+            } else {
+                PdfError::InvalidPdf(err_msg)
+            }
+        })?;
+
+        let (metadata, text, tables, pages, boundaries) = extract_all_from_document(&document, config)?;
+        Ok(PdfExtraction {
+            metadata,
+            text,
+            tables,
+            pages,
+            boundaries,
+        })
+    }
+}
+
+/// lopdf, in pure Rust: text and metadata of each page, without tables, hierarchy,
+/// watermark and redaction detection, conformance claims and layout reconstruction.
+pub struct LopdfBackend;
+
+impl PdfBackend for LopdfBackend {
+    fn name(&self) -> &'static str {
+        "lopdf"
+    }
+
+    fn extract(&self, content: &[u8], config: &ExtractionConfig) -> Result<PdfExtraction> {
+        let document = lopdf::Document::load_mem(content).map_err(|e| match e {
+            lopdf::Error::InvalidPassword | lopdf::Error::Decryption(_) => PdfError::PasswordRequired,
+            e => PdfError::from(e),
+        })?;
+        if document.is_encrypted() {
+            return Err(PdfError::PasswordRequired.into());
+        }
+
+        let page_config = config.pages.as_ref();
+        let mut text = String::new();
+        let mut boundaries = Vec::new();
+        let mut pages = page_config
+            .filter(|page_config| page_config.extract_pages)
+            .map(|_| Vec::new());
+
+        for page_number in document.get_pages().into_keys() {
+            let page_number = page_number as usize;
+            if page_config.is_some_and(|page_config| !page_config.includes_page(page_number)) {
+                continue;
+            }
+            let page_text = document.extract_text(&[page_number as u32]).map_err(|e| {
+                PdfError::TextExtractionFailed(format!("Page {} text extraction failed: {}", page_number, e))
+            })?;
+            let page_text = page_text.trim_end();
+
+            match page_config {
+                Some(page_config) if page_config.insert_page_markers => page_config.push_marker(&mut text, page_number),
+                _ if !boundaries.is_empty() => text.push_str("\n\n"),
+                _ => {}
+            }
+            let byte_start = text.len();
+            text.push_str(page_text);
+            boundaries.push(PageBoundary {
+                byte_start,
+                byte_end: text.len(),
+                page_number,
+            });
+
+            if let Some(pages) = &mut pages {
+                pages.push(PageContent {
+                    page_number,
+                    content: page_text.to_string(),
+                    tables: Vec::new(),
+                    images: Vec::new(),
+                    hierarchy: None,
+                });
+            }
+        }
+
+        let boundaries = page_config.map(|_| boundaries);
+        let metadata = extract_metadata_from_lopdf(&document, boundaries.as_deref())?;
+        Ok(PdfExtraction {
+            metadata,
+            text,
+            tables: Vec::new(),
+            pages,
+            boundaries,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::PageConfig;
+    use lopdf::content::{Content, Operation};
+    use lopdf::{Document, Object, Stream, dictionary};
+
+    /// A PDF with one page per text, titled "Backends".
+    fn pdf(pages: &[&str]) -> Vec<u8> {
+        let mut document = Document::with_version("1.5");
+        let pages_id = document.new_object_id();
+        let font = document.add_object(dictionary! {
+            "Type" => "Font",
+            "Subtype" => "Type1",
+            "BaseFont" => "Helvetica",
+        });
+        let kids: Vec<Object> = pages
+            .iter()
+            .map(|text| {
+                let content = Content {
+                    operations: vec![
+                        Operation::new("BT", vec![]),
+                        Operation::new("Tf", vec!["F1".into(), 12.into()]),
+                        Operation::new("Td", vec![72.into(), 720.into()]),
+                        Operation::new("Tj", vec![Object::string_literal(*text)]),
+                        Operation::new("ET", vec![]),
+                    ],
+                };
+                let stream = document.add_object(Stream::new(dictionary! {}, content.encode().unwrap()));
+                document
+                    .add_object(dictionary! {
+                        "Type" => "Page",
+                        "Parent" => pages_id,
+                        "Contents" => stream,
+                        "Resources" => dictionary! { "Font" => dictionary! { "F1" => font } },
+                    })
+                    .into()
+            })
+            .collect();
+        document.objects.insert(
+            pages_id,
+            Object::Dictionary(dictionary! {
+                "Type" => "Pages",
+                "Count" => kids.len() as i64,
+                "Kids" => kids,
+                "MediaBox" => vec![0.into(), 0.into(), 612.into(), 792.into()],
+            }),
+        );
+        let catalog = document.add_object(dictionary! { "Type" => "Catalog", "Pages" => pages_id });
+        let info = document.add_object(dictionary! {
+            "Title" => Object::string_literal("Backends"),
+            "Author" => Object::string_literal("Ada Lovelace; Charles Babbage"),
+            "CreationDate" => Object::string_literal("D:20240102030405Z"),
+        });
+        document.trailer.set("Root", catalog);
+        document.trailer.set("Info", info);
+
+        let mut bytes = Vec::new();
+        document.save_to(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn test_lopdf_backend() {
+        let content = pdf(&["First page", "Second page"]);
+        let extraction = LopdfBackend.extract(&content, &ExtractionConfig::default()).unwrap();
+
+        assert_eq!(extraction.text, "First page\n\nSecond page");
+        assert!(extraction.boundaries.is_none() && extraction.pages.is_none());
+        let metadata = extraction.metadata;
+        assert_eq!(metadata.title.as_deref(), Some("Backends"));
+        assert_eq!(
+            metadata.authors,
+            Some(vec!["Ada Lovelace".to_string(), "Charles Babbage".to_string()])
+        );
+        assert_eq!(metadata.created_at.as_deref(), Some("2024-01-02T03:04:05Z"));
+        assert_eq!(metadata.pdf_specific.page_count, Some(2));
+        assert_eq!(metadata.pdf_specific.width, Some(612));
+        assert_eq!(metadata.pdf_specific.height, Some(792));
+    }
+
+    #[test]
+    fn test_lopdf_backend_pages() {
+        let content = pdf(&["First page", "Second page", "Third page"]);
+        let config = ExtractionConfig {
+            pages: Some(PageConfig {
+                extract_pages: true,
+                insert_page_markers: true,
+                marker_format: "[{page_num}]".to_string(),
+                selection: Some("2-".parse().unwrap()),
+            }),
+            ..Default::default()
+        };
+        let extraction = LopdfBackend.extract(&content, &config).unwrap();
+
+        assert_eq!(extraction.text, "[2]Second page[3]Third page");
+        let boundaries = extraction.boundaries.unwrap();
+        assert_eq!(boundaries.len(), 2);
+        assert_eq!(
+            &extraction.text[boundaries[1].byte_start..boundaries[1].byte_end],
+            "Third page"
+        );
+        assert_eq!(boundaries[1].page_number, 3);
+        let pages = extraction.pages.unwrap();
+        assert_eq!(pages[0].content, "Second page");
+        let page_structure = extraction.metadata.page_structure.unwrap();
+        assert_eq!(page_structure.total_count, 3);
+        assert_eq!(page_structure.pages.unwrap()[0].dimensions, Some((612.0, 792.0)));
+    }
+
+    #[test]
+    fn test_lopdf_backend_invalid_pdf() {
+        let error = LopdfBackend
+            .extract(b"not a pdf", &ExtractionConfig::default())
+            .unwrap_err();
+        assert!(error.to_string().contains("Invalid PDF"), "{error}");
+    }
+}
@@ -51,7 +51,7 @@ pub(crate) fn extract_all_from_document(
     let (native_text, boundaries, page_contents, pdf_metadata) =
         crate::pdf::text::extract_text_and_metadata_from_pdf_document(document, Some(config))?;
 
-    let tables = extract_tables_from_document(document, &pdf_metadata)?;
+    let tables = extract_tables_from_document(document, &pdf_metadata, config.pages.as_ref())?;
 
     Ok((pdf_metadata, native_text, tables, page_contents, boundaries))
 }
@@ -62,17 +62,26 @@ pub(crate) fn extract_all_from_document(
 /// then uses the existing table reconstruction logic to detect tables.
 ///
 /// Uses the shared PdfDocument reference (wrapped in Arc<RwLock<>> for thread-safety).
+/// Pages outside the page selection are skipped.
 #[cfg(all(feature = "pdf", feature = "ocr"))]
 fn extract_tables_from_document(
     document: &PdfDocument,
     _metadata: &crate::pdf::metadata::PdfExtractionMetadata,
+    page_config: Option<&crate::core::config::PageConfig>,
 ) -> Result<Vec<Table>> {
     use crate::ocr::table::{reconstruct_table, table_to_markdown};
     use crate::pdf::table::extract_words_from_page;
 
     let mut all_tables = Vec::new();
 
-    for (page_index, page) in document.pages().iter().enumerate() {
+    for page_index in 0..document.pages().len() as usize {
+        if page_config.is_some_and(|config| !config.includes_page(page_index + 1)) {
+            continue;
+        }
+        let page = document
+            .pages()
+            .get(page_index as i32)
+            .map_err(|_| crate::pdf::error::PdfError::PageNotFound(page_index + 1))?;
         let words = extract_words_from_page(&page, 0.0)?;
 
         if words.is_empty() {
@@ -103,6 +112,7 @@ fn extract_tables_from_document(
 fn extract_tables_from_document(
     _document: &PdfDocument,
     _metadata: &crate::pdf::metadata::PdfExtractionMetadata,
+    _page_config: Option<&crate::core::config::PageConfig>,
 ) -> Result<Vec<crate::types::Table>> {
     Ok(vec![])
 }
@@ -3,6 +3,7 @@
 //! Provides extraction of text, metadata, tables, and images from PDF documents
 //! using pypdfium2 and playa-pdf. Supports both native text extraction and OCR fallback.
 
+mod backend;
 mod extraction;
 mod ocr;
 mod pages;
@@ -10,27 +11,31 @@ mod pages;
 use bytes::Bytes;
 
 use crate::Result;
-use crate::core::config::ExtractionConfig;
+use crate::core::config::{ExtractionConfig, PdfBackendKind};
 use crate::plugins::{DocumentExtractor, Plugin};
 use crate::types::{ExtractionResult, Metadata};
 use async_trait::async_trait;
+use std::borrow::Cow;
 #[cfg(feature = "tokio-runtime")]
 use std::path::Path;
 
-#[cfg(feature = "pdf")]
-use crate::pdf::error::PdfError;
-
 // Re-export for backward compatibility
 #[cfg(feature = "ocr")]
 pub use ocr::{NativeTextStats, OcrFallbackDecision, evaluate_native_text_for_ocr, evaluate_per_page_ocr};
 
-use extraction::extract_all_from_document;
+pub use backend::{LopdfBackend, PdfBackend, PdfExtraction, PdfiumBackend, pdf_backend};
 #[cfg(feature = "ocr")]
 use ocr::extract_with_ocr;
 use pages::assign_tables_and_images_to_pages;
 
 /// PDF document extractor using pypdfium2 and playa-pdf.
-pub struct PdfExtractor;
+///
+/// The default extractor parses documents with the engine of `pdf_options.backend`;
+/// [`PdfExtractor::with_backend`] builds one that always uses the same engine, to be
+/// named in fallback chains.
+pub struct PdfExtractor {
+    backend: Option<PdfBackendKind>,
+}
 
 impl Default for PdfExtractor {
     fn default() -> Self {
@@ -40,13 +45,31 @@ impl Default for PdfExtractor {
 
 impl PdfExtractor {
     pub fn new() -> Self {
-        Self
+        Self { backend: None }
+    }
+
+    /// An extractor always parsing documents with the engine of `backend`, named
+    /// `pdf-<backend>-extractor`, with a lower priority than the default extractor.
+    pub fn with_backend(backend: PdfBackendKind) -> Self {
+        Self { backend: Some(backend) }
+    }
+
+    fn backend(&self, config: &ExtractionConfig) -> &'static dyn PdfBackend {
+        pdf_backend(
+            self.backend
+                .or_else(|| config.pdf_options.as_ref().map(|pdf| pdf.backend))
+                .unwrap_or_default(),
+        )
     }
 }
 
 impl Plugin for PdfExtractor {
     fn name(&self) -> &str {
-        "pdf-extractor"
+        match self.backend {
+            None => "pdf-extractor",
+            Some(PdfBackendKind::Pdfium) => "pdf-pdfium-extractor",
+            Some(PdfBackendKind::Lopdf) => "pdf-lopdf-extractor",
+        }
     }
 
     fn version(&self) -> String {
@@ -78,35 +101,14 @@ impl DocumentExtractor for PdfExtractor {
         config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
         #[cfg(feature = "pdf")]
-        let (pdf_metadata, native_text, tables, page_contents, _boundaries) = {
-            #[cfg(target_arch = "wasm32")]
-            {
-                let pdfium = crate::pdf::bindings::bind_pdfium(PdfError::MetadataExtractionFailed, "initialize Pdfium")
-                    .map_err(|pdf_err| {
-                        if pdf_err.to_string().contains("WASM") || pdf_err.to_string().contains("Module") {
-                            crate::error::KreuzbergError::Parsing {
-                                message: "PDF extraction requires proper WASM module initialization. \
-                                     Ensure your WASM environment is set up with PDFium support. \
-                                     See: https://docs.kreuzberg.dev/wasm/pdf"
-                                    .to_string(),
-                                source: None,
-                            }
-                        } else {
-                            pdf_err.into()
-                        }
-                    })?;
-
-                let document = pdfium.load_pdf_from_byte_slice(content, None).map_err(|e| {
-                    let err_msg = crate::pdf::error::format_pdfium_error(e);
-                    if err_msg.contains("password") || err_msg.contains("Password") {
-                        PdfError::PasswordRequired
-                    } else {
-                        PdfError::InvalidPdf(err_msg)
-                    }
-                })?;
-
-                extract_all_from_document(&document, config)?
-            }
+        let PdfExtraction {
+            metadata: mut pdf_metadata,
+            text: native_text,
+            tables,
+            pages: page_contents,
+            boundaries: _boundaries,
+        } = {
+            let backend = self.backend(config);
             #[cfg(all(not(target_arch = "wasm32"), feature = "tokio-runtime"))]
             {
                 if crate::core::batch_mode::is_batch_mode() {
@@ -116,82 +118,39 @@ impl DocumentExtractor for PdfExtractor {
                     tokio::task::spawn_blocking(move || {
                         let _guard = span.entered();
 
-                        let pdfium =
-                            crate::pdf::bindings::bind_pdfium(PdfError::MetadataExtractionFailed, "initialize Pdfium")?;
-
-                        let document = pdfium.load_pdf_from_byte_slice(&content_owned, None).map_err(|e| {
-                            let err_msg = crate::pdf::error::format_pdfium_error(e);
-                            if err_msg.contains("password") || err_msg.contains("Password") {
-                                PdfError::PasswordRequired
-                            } else {
-                                PdfError::InvalidPdf(err_msg)
-                            }
-                        })?;
-
-                        let (pdf_metadata, native_text, tables, page_contents, _boundaries) =
-                            extract_all_from_document(&document, &config_owned)?;
+                        let extraction = backend.extract(&content_owned, &config_owned)?;
 
                         if let Some(page_cfg) = config_owned.pages.as_ref()
                             && page_cfg.extract_pages
-                            && page_contents.is_none()
+                            && extraction.pages.is_none()
                         {
-                            return Err(PdfError::ExtractionFailed(
+                            return Err(crate::pdf::error::PdfError::ExtractionFailed(
                                 "Page extraction was configured but no page data was extracted in batch mode"
                                     .to_string(),
                             )
                             .into());
                         }
 
-                        Ok::<_, crate::error::KreuzbergError>((
-                            pdf_metadata,
-                            native_text,
-                            tables,
-                            page_contents,
-                            _boundaries,
-                        ))
+                        Ok::<_, crate::error::KreuzbergError>(extraction)
                     })
                     .await
                     .map_err(|e| crate::error::KreuzbergError::Other(format!("PDF extraction task failed: {}", e)))??
                 } else {
-                    let pdfium =
-                        crate::pdf::bindings::bind_pdfium(PdfError::MetadataExtractionFailed, "initialize Pdfium")?;
-
-                    let document = pdfium.load_pdf_from_byte_slice(content, None).map_err(|e| {
-                        let err_msg = crate::pdf::error::format_pdfium_error(e);
-                        if err_msg.contains("password") || err_msg.contains("Password") {
-                            PdfError::PasswordRequired
-                        } else {
-                            PdfError::InvalidPdf(err_msg)
-                        }
-                    })?;
-
-                    extract_all_from_document(&document, config)?
+                    backend.extract(content, config)?
                 }
             }
-            #[cfg(all(not(target_arch = "wasm32"), not(feature = "tokio-runtime")))]
+            #[cfg(any(target_arch = "wasm32", not(feature = "tokio-runtime")))]
             {
-                let pdfium =
-                    crate::pdf::bindings::bind_pdfium(PdfError::MetadataExtractionFailed, "initialize Pdfium")?;
-
-                let document = pdfium.load_pdf_from_byte_slice(content, None).map_err(|e| {
-                    let err_msg = crate::pdf::error::format_pdfium_error(e);
-                    if err_msg.contains("password") || err_msg.contains("Password") {
-                        PdfError::PasswordRequired
-                    } else {
-                        PdfError::InvalidPdf(err_msg)
-                    }
-                })?;
-
-                extract_all_from_document(&document, config)?
+                backend.extract(content, config)?
             }
         };
 
         #[cfg(feature = "ocr")]
-        let text = if config.force_ocr {
+        let (text, ocr_pages) = if config.force_ocr {
             if config.ocr.is_some() {
                 extract_with_ocr(content, config).await?
             } else {
-                native_text
+                (native_text, Vec::new())
             }
         } else if config.ocr.is_some() {
             let decision = ocr::evaluate_per_page_ocr(
@@ -217,10 +176,10 @@ impl DocumentExtractor for PdfExtractor {
             if decision.fallback {
                 extract_with_ocr(content, config).await?
             } else {
-                native_text
+                (native_text, Vec::new())
             }
         } else {
-            native_text
+            (native_text, Vec::new())
         };
 
         #[cfg(not(feature = "ocr"))]
@@ -280,43 +239,121 @@ impl DocumentExtractor for PdfExtractor {
 
         let final_pages = assign_tables_and_images_to_pages(page_contents, &tables, images.as_deref().unwrap_or(&[]));
 
+        let report_watermarks = config.cleanup.as_ref().is_some_and(|c| c.report_watermarks);
+        let watermarks = std::mem::take(&mut pdf_metadata.watermarks);
+        let redactions = std::mem::take(&mut pdf_metadata.redactions);
+        pdf_metadata.pdf_specific.set_conformance_claims(content);
+        #[cfg(feature = "ocr")]
+        ocr::record_page_preparations(&mut pdf_metadata.page_structure, &ocr_pages);
+
+        let mut metadata = Metadata {
+            #[cfg(feature = "pdf")]
+            title: pdf_metadata.title.clone(),
+            #[cfg(feature = "pdf")]
+            subject: pdf_metadata.subject.clone(),
+            #[cfg(feature = "pdf")]
+            authors: pdf_metadata.authors.clone(),
+            #[cfg(feature = "pdf")]
+            keywords: pdf_metadata.keywords.clone(),
+            #[cfg(feature = "pdf")]
+            created_at: pdf_metadata.created_at.clone(),
+            #[cfg(feature = "pdf")]
+            modified_at: pdf_metadata.modified_at.clone(),
+            #[cfg(feature = "pdf")]
+            created_by: pdf_metadata.created_by.clone(),
+            #[cfg(feature = "pdf")]
+            producer: pdf_metadata.pdf_specific.producer.take(),
+            #[cfg(feature = "pdf")]
+            page_count: pdf_metadata.pdf_specific.page_count.take(),
+            #[cfg(feature = "pdf")]
+            pages: pdf_metadata.page_structure.clone(),
+            #[cfg(feature = "pdf")]
+            format: Some(crate::types::FormatMetadata::Pdf(pdf_metadata.pdf_specific)),
+            ..Default::default()
+        };
+
+        if report_watermarks && !watermarks.is_empty() {
+            metadata
+                .additional
+                .insert(Cow::Borrowed("watermarks"), serde_json::json!(watermarks));
+        }
+
+        #[cfg(feature = "ocr")]
+        crate::ocr::duplicate_page::record_duplicate_pages(
+            &mut metadata,
+            &crate::ocr::scheduler::duplicate_pages(&ocr_pages),
+        );
+
+        if let Some(redaction) = config.pdf_options.as_ref().and_then(|pdf| pdf.redaction.as_ref())
+            && !redactions.is_empty()
+        {
+            let warnings = crate::pdf::redaction::redaction_warnings(&redactions, redaction.suppress_covered_text);
+            #[cfg(feature = "otel")]
+            for warning in &warnings {
+                tracing::warn!("Redacted text is still extractable: {}", warning);
+            }
+            if !warnings.is_empty() {
+                metadata
+                    .additional
+                    .insert(Cow::Borrowed("redaction_warnings"), serde_json::json!(warnings));
+            }
+            metadata
+                .additional
+                .insert(Cow::Borrowed("redactions"), serde_json::json!(redactions));
+        }
+
+        #[cfg(feature = "barcodes")]
+        if let Some(barcode_config) = config.barcodes.clone() {
+            let pdf_bytes = content.to_vec();
+            let barcodes = tokio::task::spawn_blocking(move || {
+                crate::extraction::barcode::detect_pdf_barcodes(&pdf_bytes, &barcode_config)
+            })
+            .await
+            .map_err(|e| crate::error::KreuzbergError::Other(format!("PDF barcode detection task failed: {}", e)))??;
+            if !barcodes.is_empty() {
+                metadata
+                    .additional
+                    .insert(Cow::Borrowed("barcodes"), serde_json::json!(barcodes));
+            }
+        }
+
+        #[cfg(feature = "ocr")]
+        if let Some(signature_config) = config.signatures.clone() {
+            let pdf_bytes = content.to_vec();
+            let signatures = tokio::task::spawn_blocking(move || {
+                crate::extraction::signature::detect_pdf_signatures(&pdf_bytes, &signature_config)
+            })
+            .await
+            .map_err(|e| {
+                crate::error::KreuzbergError::Other(format!("PDF signature detection task failed: {}", e))
+            })??;
+            if !signatures.is_empty() {
+                metadata
+                    .additional
+                    .insert(Cow::Borrowed("signatures"), serde_json::json!(signatures));
+            }
+        }
+
+        crate::text::bidi::annotate_text_direction(&text, &mut metadata);
+
         Ok(ExtractionResult {
             content: text,
             mime_type: mime_type.to_string().into(),
-            metadata: Metadata {
-                #[cfg(feature = "pdf")]
-                title: pdf_metadata.title.clone(),
-                #[cfg(feature = "pdf")]
-                subject: pdf_metadata.subject.clone(),
-                #[cfg(feature = "pdf")]
-                authors: pdf_metadata.authors.clone(),
-                #[cfg(feature = "pdf")]
-                keywords: pdf_metadata.keywords.clone(),
-                #[cfg(feature = "pdf")]
-                created_at: pdf_metadata.created_at.clone(),
-                #[cfg(feature = "pdf")]
-                modified_at: pdf_metadata.modified_at.clone(),
-                #[cfg(feature = "pdf")]
-                created_by: pdf_metadata.created_by.clone(),
-                #[cfg(feature = "pdf")]
-                pages: pdf_metadata.page_structure.clone(),
-                #[cfg(feature = "pdf")]
-                format: Some(crate::types::FormatMetadata::Pdf(pdf_metadata.pdf_specific)),
-                ..Default::default()
-            },
+            metadata,
             pages: final_pages,
             tables,
             detected_languages: None,
             chunks: None,
             images,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
 
     #[cfg(feature = "tokio-runtime")]
     async fn extract_file(&self, path: &Path, mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
-        let bytes = tokio::fs::read(path).await?;
+        let bytes = crate::core::io::open_file_async(path, config.io.as_ref()).await?;
         self.extract_bytes(&bytes, mime_type, config).await
     }
 
@@ -325,7 +362,11 @@ impl DocumentExtractor for PdfExtractor {
     }
 
     fn priority(&self) -> i32 {
-        50
+        if self.backend.is_some() { 40 } else { 50 }
+    }
+
+    fn required_features(&self) -> &[&str] {
+        &["pdf"]
     }
 }
 
@@ -540,6 +581,7 @@ mod tests {
                 extract_pages: true,
                 insert_page_markers: false,
                 marker_format: "<!-- PAGE {page_num} -->".to_string(),
+                selection: None,
             }),
             ..Default::default()
         };
@@ -598,6 +640,7 @@ mod tests {
                 extract_pages: true,
                 insert_page_markers: true,
                 marker_format: "\n\n<!-- PAGE {page_num} -->\n\n".to_string(),
+                selection: None,
             }),
             ..Default::default()
         };
@@ -623,6 +666,45 @@ mod tests {
         }
     }
 
+    #[cfg(feature = "ocr")]
+    #[test]
+    fn test_record_page_preparations() {
+        use crate::ocr::scheduler::PagePreparation;
+        use crate::types::{PageStructure, PageUnitType};
+
+        let mut page_structure = None;
+        ocr::record_page_preparations(&mut page_structure, &[PagePreparation::default(); 2]);
+        assert!(page_structure.is_none());
+
+        let rotated = |rotation| PagePreparation {
+            rotation: Some(rotation),
+            blank: Some(false),
+            ..Default::default()
+        };
+        let blank = PagePreparation {
+            blank: Some(true),
+            ..Default::default()
+        };
+        ocr::record_page_preparations(&mut page_structure, &[rotated(0), rotated(90), blank]);
+        let pages = page_structure.unwrap().pages.unwrap();
+        assert_eq!(pages.len(), 3);
+        assert_eq!((pages[1].number, pages[1].rotation), (2, Some(90)));
+        assert_eq!((pages[2].rotation, pages[2].blank), (None, Some(true)));
+
+        let page = |number| PagePreparation::default().page_info(number, Some((612.0, 792.0)));
+        let mut page_structure = Some(PageStructure {
+            total_count: 2,
+            unit_type: PageUnitType::Page,
+            boundaries: None,
+            pages: Some(vec![page(1), page(2)]),
+        });
+        ocr::record_page_preparations(&mut page_structure, &[rotated(270), blank]);
+        let pages = page_structure.unwrap().pages.unwrap();
+        assert_eq!(pages[0].rotation, Some(270));
+        assert_eq!(pages[0].dimensions, Some((612.0, 792.0)));
+        assert_eq!(pages[1].blank, Some(true));
+    }
+
     #[test]
     #[cfg(feature = "pdf")]
     fn test_pdf_extractor_without_feature_pdf() {
@@ -150,7 +150,8 @@ pub fn evaluate_per_page_ocr(
         _ => return evaluate_native_text_for_ocr(native_text, page_count),
     };
 
-    let mut document_decision = evaluate_native_text_for_ocr(native_text, page_count);
+    // With a page selection, only the selected pages have text and boundaries.
+    let mut document_decision = evaluate_native_text_for_ocr(native_text, Some(boundaries.len()));
 
     for boundary in boundaries {
         if boundary.byte_end > native_text.len() || boundary.byte_start > boundary.byte_end {
@@ -168,7 +169,8 @@ pub fn evaluate_per_page_ocr(
 
 /// Extract text from PDF using OCR.
 ///
-/// Renders all pages to images and processes them with OCR backend.
+/// Renders pages to images on a blocking thread while up to `ocr.concurrency` pages
+/// are processed in parallel by the OCR backend.
 ///
 /// # Arguments
 ///
@@ -177,47 +179,193 @@ pub fn evaluate_per_page_ocr(
 ///
 /// # Returns
 ///
-/// Concatenated text from all pages, separated by double newlines
+/// Concatenated text from all selected pages but dropped duplicates, separated by
+/// double newlines, and how each page was prepared for OCR (rotation with `auto_rotate`,
+/// blank with `skip_blank_pages`, duplicates with `duplicate_pages`, excluded by the
+/// page selection)
 #[cfg(feature = "ocr")]
-pub(crate) async fn extract_with_ocr(content: &[u8], config: &ExtractionConfig) -> crate::Result<String> {
+pub(crate) async fn extract_with_ocr(
+    content: &[u8],
+    config: &ExtractionConfig,
+) -> crate::Result<(String, Vec<crate::ocr::scheduler::PagePreparation>)> {
+    use crate::ocr::scheduler::{page_job_channel, run_page_jobs};
+    let ocr_config = config.ocr.as_ref().ok_or_else(|| crate::KreuzbergError::Parsing {
+        message: "OCR config required for force_ocr".to_string(),
+        source: None,
+    })?;
+
+    let backend = crate::plugins::resolve_ocr_backend(ocr_config)?;
+    let backend = crate::plugins::guard::guard_ocr_backend(backend, config);
+
+    let concurrency = ocr_config.effective_concurrency();
+    let (sender, receiver) = page_job_channel(concurrency);
+
+    let pdf_bytes = content.to_vec();
+    let render_config = ocr_config.clone();
+    let page_config = config.pages.clone();
+    let span = tracing::Span::current();
+    let producer = tokio::task::spawn_blocking(move || {
+        let _guard = span.entered();
+        let mut preparations = Vec::new();
+        if let Err(e) = render_page_jobs(
+            &pdf_bytes,
+            &render_config,
+            page_config.as_ref(),
+            &sender,
+            &mut preparations,
+        ) {
+            // The receiver may already be gone if OCR failed; the error is reported either way.
+            let _ = sender.blocking_send(Err(e));
+        }
+        preparations
+    });
+
+    let results = run_page_jobs(backend, receiver, concurrency).await;
+    let preparations = producer.await.map_err(|e| crate::KreuzbergError::Ocr {
+        message: format!("PDF page rendering task failed: {}", e),
+        source: None,
+    })?;
+
+    let page_texts: Vec<String> = results?
+        .into_iter()
+        .zip(&preparations)
+        .filter(|(_, preparation)| !preparation.dropped && !preparation.excluded)
+        .map(|(result, _)| result.content)
+        .collect();
+
+    Ok((page_texts.join("\n\n"), preparations))
+}
+
+/// Record the rotations and blank pages of OCRed pages in the page metadata.
+///
+/// Pages missing from the page structure are added. Nothing is recorded when neither
+/// `auto_rotate` nor `skip_blank_pages` was enabled for any page.
+#[cfg(feature = "ocr")]
+pub(crate) fn record_page_preparations(
+    page_structure: &mut Option<crate::types::PageStructure>,
+    preparations: &[crate::ocr::scheduler::PagePreparation],
+) {
+    use crate::types::{PageStructure, PageUnitType};
+
+    if !preparations.iter().any(|preparation| preparation.has_page_info()) {
+        return;
+    }
+
+    let structure = page_structure.get_or_insert_with(|| PageStructure {
+        total_count: preparations.len(),
+        unit_type: PageUnitType::Page,
+        boundaries: None,
+        pages: None,
+    });
+    let pages = structure.pages.get_or_insert_with(Vec::new);
+
+    for (page_index, preparation) in preparations.iter().enumerate() {
+        if !preparation.has_page_info() {
+            continue;
+        }
+        let page_number = page_index + 1;
+        match pages.iter_mut().find(|page| page.number == page_number) {
+            Some(page) => {
+                page.rotation = preparation.rotation;
+                page.blank = preparation.blank;
+            }
+            None => pages.push(preparation.page_info(page_number, None)),
+        }
+    }
+}
+
+/// Render every selected page of a PDF and queue it for OCR.
+///
+/// Blocks when the queue is full, so rendering never runs more than the OCR
+/// concurrency ahead of recognition, and records how each queued page was prepared.
+/// Blank pages, dropped duplicates and pages outside the page selection are queued
+/// without an image. Returns early without error when the receiver has been dropped.
+#[cfg(feature = "ocr")]
+fn render_page_jobs(
+    content: &[u8],
+    ocr_config: &crate::core::config::OcrConfig,
+    pages: Option<&crate::core::config::PageConfig>,
+    sender: &tokio::sync::mpsc::Sender<crate::Result<crate::ocr::scheduler::OcrPageJob>>,
+    preparations: &mut Vec<crate::ocr::scheduler::PagePreparation>,
+) -> crate::Result<()> {
+    use crate::core::config::OcrPageContext;
+    use crate::ocr::scheduler::{OcrPageJob, PagePreparation, PagePreparer};
     use crate::pdf::rendering::{PageRenderOptions, PdfRenderer};
-    use crate::plugins::registry::get_ocr_backend_registry;
     use image::ImageEncoder;
     use image::codecs::png::PngEncoder;
     use std::io::Cursor;
 
-    let ocr_config = config.ocr.as_ref().ok_or_else(|| crate::KreuzbergError::Parsing {
-        message: "OCR config required for force_ocr".to_string(),
+    let renderer = PdfRenderer::new().map_err(|e| crate::KreuzbergError::Parsing {
+        message: format!("Failed to initialize PDF renderer: {}", e),
         source: None,
     })?;
 
-    let backend = {
-        let registry = get_ocr_backend_registry();
-        let registry = registry.read().map_err(|e| crate::KreuzbergError::Plugin {
-            message: format!("Failed to acquire read lock on OCR backend registry: {}", e),
-            plugin_name: "ocr-registry".to_string(),
-        })?;
-        registry.get(&ocr_config.backend)?
-    };
-
-    let images = {
-        let render_options = PageRenderOptions::default();
-        let renderer = PdfRenderer::new().map_err(|e| crate::KreuzbergError::Parsing {
-            message: format!("Failed to initialize PDF renderer: {}", e),
+    let page_sizes = renderer
+        .page_sizes(content)
+        .map_err(|e| crate::KreuzbergError::Parsing {
+            message: format!("Failed to render PDF pages: {}", e),
             source: None,
         })?;
+    let page_count = page_sizes.len();
+    let mut preparer = PagePreparer::new(ocr_config);
+
+    for (page_index, (width_points, height_points)) in page_sizes.into_iter().enumerate() {
+        if pages.is_some_and(|pages| !pages.includes_page(page_index + 1)) {
+            preparations.push(PagePreparation::excluded());
+            let job = OcrPageJob {
+                index: page_index,
+                image: Vec::new(),
+                config: ocr_config.clone(),
+                skip: true,
+            };
+            if sender.blocking_send(Ok(job)).is_err() {
+                return Ok(());
+            }
+            continue;
+        }
+
+        let page_config = ocr_config.for_page(&OcrPageContext {
+            page_number: page_index + 1,
+            page_count,
+            width_points,
+            height_points,
+        });
 
-        renderer
-            .render_all_pages(content, &render_options)
+        let defaults = PageRenderOptions::default();
+        let render_options = match page_config.dpi {
+            Some(dpi) => {
+                crate::core::config_validation::validate_dpi(dpi)?;
+                PageRenderOptions {
+                    target_dpi: dpi,
+                    max_dpi: defaults.max_dpi.max(dpi),
+                    ..defaults
+                }
+            }
+            None => defaults,
+        };
+
+        let image = renderer
+            .render_page_to_image(content, page_index, &render_options)
             .map_err(|e| crate::KreuzbergError::Parsing {
                 message: format!("Failed to render PDF pages: {}", e),
                 source: None,
-            })?
-    };
+            })?;
 
-    let mut page_texts = Vec::with_capacity(images.len());
+        let (image, preparation) = preparer.prepare(page_index + 1, image, &page_config);
+        preparations.push(preparation);
+        if preparation.is_skipped() {
+            let job = OcrPageJob {
+                index: page_index,
+                image: Vec::new(),
+                config: page_config,
+                skip: true,
+            };
+            if sender.blocking_send(Ok(job)).is_err() {
+                return Ok(());
+            }
+            continue;
+        }
 
-    for image in images {
         let rgb_image = image.to_rgb8();
         let (width, height) = rgb_image.dimensions();
 
@@ -230,12 +378,16 @@ pub(crate) async fn extract_with_ocr(content: &[u8], config: &ExtractionConfig)
                 source: None,
             })?;
 
-        let image_data = image_bytes.into_inner();
-
-        let ocr_result = backend.process_image(&image_data, ocr_config).await?;
-
-        page_texts.push(ocr_result.content);
+        let job = OcrPageJob {
+            index: page_index,
+            image: image_bytes.into_inner(),
+            config: page_config,
+            skip: false,
+        };
+        if sender.blocking_send(Ok(job)).is_err() {
+            return Ok(());
+        }
     }
 
-    Ok(page_texts.join("\n\n"))
+    Ok(())
 }
@@ -4,8 +4,9 @@
 
 use crate::Result;
 use crate::core::config::ExtractionConfig;
+use crate::extraction::charts::{chart_summary, chart_table};
 use crate::plugins::{DocumentExtractor, Plugin};
-use crate::types::{ExtractionResult, Metadata};
+use crate::types::{Chart, ExtractionResult, Metadata, Table};
 use ahash::AHashMap;
 use async_trait::async_trait;
 use std::borrow::Cow;
@@ -75,6 +76,7 @@ impl PptxExtractor {
                         chunks: None,
                         images: None,
                         djot_content: None,
+                        scripts: None,
                         pages: None,
                         elements: None,
                     };
@@ -90,6 +92,19 @@ impl PptxExtractor {
     }
 }
 
+/// Chart tables for the result, with their summaries recorded in the `charts` metadata entry.
+fn chart_tables(charts: &[Chart], additional: &mut AHashMap<Cow<'static, str>, serde_json::Value>) -> Vec<Table> {
+    if !charts.is_empty() {
+        let summaries = charts
+            .iter()
+            .enumerate()
+            .map(|(index, chart)| chart_summary(chart, index))
+            .collect();
+        additional.insert(Cow::Borrowed("charts"), serde_json::Value::Array(summaries));
+    }
+    charts.iter().map(chart_table).collect()
+}
+
 impl Plugin for PptxExtractor {
     fn name(&self) -> &str {
         "pptx-extractor"
@@ -139,10 +154,15 @@ impl DocumentExtractor for PptxExtractor {
             crate::extraction::pptx::extract_pptx_from_bytes(content, extract_images, config.pages.as_ref())?
         };
 
-        let mut additional: AHashMap<Cow<'static, str>, serde_json::Value> = AHashMap::new();
+        let mut additional: AHashMap<Cow<'static, str>, serde_json::Value> = pptx_result
+            .properties
+            .into_iter()
+            .map(|(key, value)| (Cow::Owned(key), value))
+            .collect();
         additional.insert(Cow::Borrowed("slide_count"), serde_json::json!(pptx_result.slide_count));
         additional.insert(Cow::Borrowed("image_count"), serde_json::json!(pptx_result.image_count));
         additional.insert(Cow::Borrowed("table_count"), serde_json::json!(pptx_result.table_count));
+        let tables = chart_tables(&pptx_result.charts, &mut additional);
 
         let images = if extract_images {
             // Image extraction is enabled, return images or empty vector
@@ -179,11 +199,12 @@ impl DocumentExtractor for PptxExtractor {
             mime_type: mime_type.to_string().into(),
             metadata,
             pages: pptx_result.page_contents,
-            tables: vec![],
+            tables,
             detected_languages: None,
             chunks: None,
             images,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -195,19 +216,19 @@ impl DocumentExtractor for PptxExtractor {
         )
     ))]
     async fn extract_file(&self, path: &Path, mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
-        let path_str = path
-            .to_str()
-            .ok_or_else(|| crate::KreuzbergError::validation("Invalid file path".to_string()))?;
-
         let extract_images = config.images.as_ref().is_some_and(|img| img.extract_images);
 
-        let pptx_result =
-            crate::extraction::pptx::extract_pptx_from_path(path_str, extract_images, config.pages.as_ref())?;
+        let pptx_result = crate::extraction::pptx::extract_pptx_from_path(path, extract_images, config.pages.as_ref())?;
 
-        let mut additional: AHashMap<Cow<'static, str>, serde_json::Value> = AHashMap::new();
+        let mut additional: AHashMap<Cow<'static, str>, serde_json::Value> = pptx_result
+            .properties
+            .into_iter()
+            .map(|(key, value)| (Cow::Owned(key), value))
+            .collect();
         additional.insert(Cow::Borrowed("slide_count"), serde_json::json!(pptx_result.slide_count));
         additional.insert(Cow::Borrowed("image_count"), serde_json::json!(pptx_result.image_count));
         additional.insert(Cow::Borrowed("table_count"), serde_json::json!(pptx_result.table_count));
+        let tables = chart_tables(&pptx_result.charts, &mut additional);
 
         let images = if extract_images {
             // Image extraction is enabled, return images or empty vector
@@ -244,11 +265,12 @@ impl DocumentExtractor for PptxExtractor {
             mime_type: mime_type.to_string().into(),
             metadata,
             pages: pptx_result.page_contents,
-            tables: vec![],
+            tables,
             detected_languages: None,
             chunks: None,
             images,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -264,6 +286,10 @@ impl DocumentExtractor for PptxExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["office", "tokio-runtime"]
+    }
 }
 
 #[cfg(test)]
@@ -441,7 +441,7 @@ impl DocumentExtractor for RstExtractor {
         mime_type: &str,
         _config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
-        let text = String::from_utf8_lossy(content).into_owned();
+        let text = crate::text::utf8_validation::decode_lossy(content);
 
         let (extracted_text, metadata) = Self::extract_text_and_metadata(&text);
 
@@ -456,6 +456,7 @@ impl DocumentExtractor for RstExtractor {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         })
@@ -468,6 +469,10 @@ impl DocumentExtractor for RstExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["office"]
+    }
 }
 
 #[cfg(all(test, feature = "office"))]
@@ -106,6 +106,7 @@ impl DocumentExtractor for RtfExtractor {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -117,6 +118,10 @@ impl DocumentExtractor for RtfExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["office"]
+    }
 }
 
 #[cfg(test)]
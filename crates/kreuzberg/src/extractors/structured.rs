@@ -93,6 +93,7 @@ impl DocumentExtractor for StructuredExtractor {
             images: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         })
     }
 
@@ -104,7 +105,7 @@ impl DocumentExtractor for StructuredExtractor {
         )
     ))]
     async fn extract_file(&self, path: &Path, mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
-        let bytes = tokio::fs::read(path).await?;
+        let bytes = crate::core::io::open_file_async(path, config.io.as_ref()).await?;
         self.extract_bytes(&bytes, mime_type, config).await
     }
 
@@ -0,0 +1,167 @@
+//! SVG extractor.
+
+use crate::Result;
+use crate::core::config::ExtractionConfig;
+use crate::extraction::svg::parse_svg_with_limits;
+use crate::extractors::SyncExtractor;
+use crate::plugins::{DocumentExtractor, Plugin};
+use crate::types::{ExtractionResult, Metadata};
+use ahash::AHashMap;
+use async_trait::async_trait;
+use std::borrow::Cow;
+
+/// SVG extractor.
+///
+/// Extracts the text of SVG drawings line by line. The positions of the lines are
+/// listed in the `texts` metadata entry; the drawing's `<title>` becomes the document
+/// title and its `<desc>` the `description` entry.
+pub struct SvgExtractor;
+
+impl SvgExtractor {
+    /// Create a new SVG extractor.
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for SvgExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Plugin for SvgExtractor {
+    fn name(&self) -> &str {
+        "svg-extractor"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn description(&self) -> &str {
+        "Extracts text and text positions from SVG drawings"
+    }
+
+    fn author(&self) -> &str {
+        "Kreuzberg Team"
+    }
+}
+
+impl SyncExtractor for SvgExtractor {
+    fn extract_sync(&self, content: &[u8], mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
+        let svg_result = parse_svg_with_limits(content, config.xml.as_ref())?;
+
+        let mut additional = AHashMap::new();
+        if let Some(description) = svg_result.description {
+            additional.insert(Cow::Borrowed("description"), serde_json::Value::String(description));
+        }
+        if !svg_result.texts.is_empty() {
+            additional.insert(Cow::Borrowed("texts"), serde_json::json!(svg_result.texts));
+        }
+
+        let mut metadata = Metadata {
+            title: svg_result.title,
+            additional,
+            ..Default::default()
+        };
+        // `parse_svg` has already rejected invalid UTF-8.
+        if let Ok(svg) = std::str::from_utf8(content) {
+            crate::core::network::record_references(
+                &mut metadata,
+                crate::extraction::remote::svg_references(svg, config.xml.as_ref()),
+                config.network.as_ref(),
+            );
+        }
+
+        Ok(ExtractionResult {
+            content: svg_result.content,
+            mime_type: mime_type.to_string().into(),
+            metadata,
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            pages: None,
+            djot_content: None,
+            scripts: None,
+            elements: None,
+        })
+    }
+}
+
+#[async_trait]
+impl DocumentExtractor for SvgExtractor {
+    #[cfg_attr(feature = "otel", tracing::instrument(
+        skip(self, content, config),
+        fields(
+            extractor.name = self.name(),
+            content.size_bytes = content.len(),
+        )
+    ))]
+    async fn extract_bytes(
+        &self,
+        content: &[u8],
+        mime_type: &str,
+        config: &ExtractionConfig,
+    ) -> Result<ExtractionResult> {
+        self.extract_sync(content, mime_type, config)
+    }
+
+    fn supported_mime_types(&self) -> &[&str] {
+        &["image/svg+xml"]
+    }
+
+    fn priority(&self) -> i32 {
+        50
+    }
+
+    fn as_sync_extractor(&self) -> Option<&dyn crate::extractors::SyncExtractor> {
+        Some(self)
+    }
+
+    fn required_features(&self) -> &[&str] {
+        if cfg!(feature = "xml") { &["xml"] } else { &["html"] }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_svg_extractor() {
+        let extractor = SvgExtractor::new();
+        let content = br#"<svg xmlns="http://www.w3.org/2000/svg"><title>Org chart</title><desc>Reporting lines</desc>
+            <rect width="80" height="20"/><text x="5" y="15">CEO</text><text x="5" y="45">CTO</text></svg>"#;
+
+        let result = extractor
+            .extract_bytes(content, "image/svg+xml", &ExtractionConfig::default())
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "CEO\nCTO");
+        assert_eq!(result.metadata.title.as_deref(), Some("Org chart"));
+        assert_eq!(result.metadata.additional["description"], "Reporting lines");
+        assert_eq!(
+            result.metadata.additional["texts"][1],
+            serde_json::json!({"text": "CTO", "x": 5.0, "y": 45.0})
+        );
+    }
+
+    #[test]
+    fn test_svg_plugin_interface() {
+        let extractor = SvgExtractor::new();
+        assert_eq!(extractor.name(), "svg-extractor");
+        assert_eq!(extractor.supported_mime_types(), &["image/svg+xml"]);
+        assert_eq!(extractor.priority(), 50);
+    }
+}
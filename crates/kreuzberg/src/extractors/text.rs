@@ -66,19 +66,19 @@ impl DocumentExtractor for PlainTextExtractor {
         mime_type: &str,
         _config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
-        let text = String::from_utf8_lossy(content).into_owned();
-        let text = text.trim_end_matches('\n').trim_end_matches('\r').to_string();
+        let decoded = crate::text::utf8_validation::decode_lossy(content);
+        let text = decoded.trim_end_matches('\n').trim_end_matches('\r');
         let line_count = text.lines().count();
         let word_count = text.split_whitespace().count();
         let character_count = text.len();
 
         Ok(ExtractionResult {
-            content: text,
+            content: text.to_owned(),
             mime_type: mime_type.to_string().into(),
             metadata: crate::types::Metadata {
+                word_count: Some(word_count),
                 format: Some(crate::types::FormatMetadata::Text(crate::types::TextMetadata {
                     line_count,
-                    word_count,
                     character_count,
                     headers: None,
                     links: None,
@@ -93,6 +93,7 @@ impl DocumentExtractor for PlainTextExtractor {
             images: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         })
     }
 
@@ -171,9 +172,9 @@ impl DocumentExtractor for MarkdownExtractor {
             content: text_result.content,
             mime_type: mime_type.to_string().into(),
             metadata: crate::types::Metadata {
+                word_count: Some(text_result.word_count),
                 format: Some(crate::types::FormatMetadata::Text(crate::types::TextMetadata {
                     line_count: text_result.line_count,
-                    word_count: text_result.word_count,
                     character_count: text_result.character_count,
                     headers: text_result.headers,
                     links: text_result.links,
@@ -188,6 +189,7 @@ impl DocumentExtractor for MarkdownExtractor {
             images: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         })
     }
 
@@ -220,7 +222,7 @@ mod tests {
             _ => panic!("Expected Text metadata"),
         };
         assert_eq!(text_meta.line_count, 2);
-        assert_eq!(text_meta.word_count, 6);
+        assert_eq!(result.metadata.word_count, Some(6));
     }
 
     #[tokio::test]
@@ -103,7 +103,7 @@ impl DocumentExtractor for TypstExtractor {
         mime_type: &str,
         _config: &ExtractionConfig,
     ) -> Result<ExtractionResult> {
-        let typst_str = String::from_utf8_lossy(content).to_string();
+        let typst_str = crate::text::utf8_validation::decode_lossy(content);
         let (text, metadata) = Self::extract_from_typst(&typst_str);
 
         Ok(ExtractionResult {
@@ -115,6 +115,7 @@ impl DocumentExtractor for TypstExtractor {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         })
@@ -127,6 +128,10 @@ impl DocumentExtractor for TypstExtractor {
     fn priority(&self) -> i32 {
         50
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["office"]
+    }
 }
 
 /// Internal Typst parser
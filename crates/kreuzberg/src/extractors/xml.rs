@@ -53,25 +53,34 @@ impl Plugin for XmlExtractor {
 }
 
 impl SyncExtractor for XmlExtractor {
-    fn extract_sync(&self, content: &[u8], mime_type: &str, _config: &ExtractionConfig) -> Result<ExtractionResult> {
+    fn extract_sync(&self, content: &[u8], mime_type: &str, config: &ExtractionConfig) -> Result<ExtractionResult> {
+        crate::extraction::xml_parser::check_document(content, config.xml.as_ref())?;
         let xml_result = parse_xml(content, false)?;
 
+        let mut metadata = crate::types::Metadata {
+            format: Some(crate::types::FormatMetadata::Xml(crate::types::XmlMetadata {
+                element_count: xml_result.element_count,
+                unique_elements: xml_result.unique_elements,
+            })),
+            ..Default::default()
+        };
+        crate::core::network::record_references(
+            &mut metadata,
+            crate::extraction::remote::xml_prolog_references(&crate::text::utf8_validation::decode_lossy(content)),
+            config.network.as_ref(),
+        );
+
         Ok(ExtractionResult {
             content: xml_result.content,
             mime_type: mime_type.to_string().into(),
-            metadata: crate::types::Metadata {
-                format: Some(crate::types::FormatMetadata::Xml(crate::types::XmlMetadata {
-                    element_count: xml_result.element_count,
-                    unique_elements: xml_result.unique_elements,
-                })),
-                ..Default::default()
-            },
+            metadata,
             tables: vec![],
             detected_languages: None,
             chunks: None,
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -96,7 +105,7 @@ impl DocumentExtractor for XmlExtractor {
     }
 
     fn supported_mime_types(&self) -> &[&str] {
-        &["application/xml", "text/xml", "image/svg+xml"]
+        &["application/xml", "text/xml"]
     }
 
     fn priority(&self) -> i32 {
@@ -106,6 +115,10 @@ impl DocumentExtractor for XmlExtractor {
     fn as_sync_extractor(&self) -> Option<&dyn crate::extractors::SyncExtractor> {
         Some(self)
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["xml"]
+    }
 }
 
 #[cfg(test)]
@@ -140,10 +153,7 @@ mod tests {
         let extractor = XmlExtractor::new();
         assert_eq!(extractor.name(), "xml-extractor");
         assert_eq!(extractor.version(), env!("CARGO_PKG_VERSION"));
-        assert_eq!(
-            extractor.supported_mime_types(),
-            &["application/xml", "text/xml", "image/svg+xml"]
-        );
+        assert_eq!(extractor.supported_mime_types(), &["application/xml", "text/xml"]);
         assert_eq!(extractor.priority(), 50);
     }
 }
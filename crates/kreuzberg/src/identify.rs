@@ -0,0 +1,346 @@
+//! File identification without extraction.
+//!
+//! [`identify`] and [`identify_bytes`] report what a file is (MIME type, extension,
+//! container format) and a few signals useful to route it (page count, encryption,
+//! text layer), read from signatures and package parts without parsing the document,
+//! so large collections can be triaged before anything is extracted.
+//!
+//! The signals are best-effort: a field is `None` when the file does not declare it
+//! or it cannot be told without extracting the file. PDF page counts and text layers
+//! are read from the uncompressed objects, so they are unknown for PDFs that keep
+//! their page tree and resources in compressed object streams.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use kreuzberg::identify;
+//!
+//! # fn example() -> kreuzberg::Result<()> {
+//! let identification = identify("scan.pdf")?;
+//! if identification.has_text_layer == Some(false) {
+//!     println!("{} needs OCR", identification.mime_type);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::Result;
+use crate::core::mime;
+use memchr::memmem;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Signature of zip archives, including OOXML, OpenDocument and EPUB packages.
+const ZIP_MAGIC: &[u8] = b"PK\x03\x04";
+
+/// Signature of empty zip archives.
+const EMPTY_ZIP_MAGIC: &[u8] = b"PK\x05\x06";
+
+/// Signature of OLE compound files (legacy `.doc`, `.xls`, `.ppt`, `.msg`).
+const OLE_MAGIC: &[u8] = b"\xD0\xCF\x11\xE0\xA1\xB1\x1A\xE1";
+
+/// Signature of gzip streams.
+const GZIP_MAGIC: &[u8] = b"\x1F\x8B";
+
+/// Signature of 7-Zip archives.
+const SEVEN_ZIP_MAGIC: &[u8] = b"7z\xBC\xAF\x27\x1C";
+
+/// MIME type reported for files of unknown type.
+const UNKNOWN_MIME_TYPE: &str = "application/octet-stream";
+
+/// Container format a file is stored in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContainerFormat {
+    /// Zip archive: OOXML, OpenDocument and EPUB packages, and plain zip archives
+    Zip,
+    /// OLE compound file: legacy Office documents, Outlook messages, and encrypted OOXML
+    Ole,
+    /// Tar archive
+    Tar,
+    /// 7-Zip archive
+    SevenZip,
+    /// Gzip stream
+    Gzip,
+}
+
+/// What a file is, as told by [`identify`] and [`identify_bytes`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Identification {
+    /// Detected MIME type (`application/octet-stream` when unknown)
+    pub mime_type: String,
+    /// File extension of the MIME type, without the leading dot
+    pub extension: Option<String>,
+    /// Container format the file is stored in (None = not a container)
+    pub container: Option<ContainerFormat>,
+    /// Number of pages or slides, when the file declares it
+    pub page_count: Option<usize>,
+    /// Whether the file is encrypted or password protected
+    pub encrypted: bool,
+    /// Whether the file has a text layer to extract (None = unknown): `false` for
+    /// images and for PDFs without fonts, which need OCR
+    pub has_text_layer: Option<bool>,
+}
+
+/// Identify the file at `path`.
+///
+/// The MIME type is detected from the extension, or from the content when the
+/// extension is unknown.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Io` if the file doesn't exist or cannot be read. Files of
+/// unknown type are not an error.
+pub fn identify(path: impl AsRef<Path>) -> Result<Identification> {
+    let path = path.as_ref();
+    crate::core::io::validate_file_exists(path)?;
+    let content = crate::core::io::open_file_sync(path, None)?;
+
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase());
+    let mime_type = match mime::detect_mime_type(path, false) {
+        Ok(mime_type) => mime_type,
+        Err(_) => detect_from_bytes(&content),
+    };
+    // Keep the file's own extension when it is one of the MIME type's, e.g. `jpg` over `jpeg`.
+    let extension = match extension {
+        Some(ext) if mime::get_extensions_for_mime(&mime_type).is_ok_and(|extensions| extensions.contains(&ext)) => {
+            Some(ext)
+        }
+        _ => extension_for(&mime_type, &content),
+    };
+    Ok(identification(&content, mime_type, extension))
+}
+
+/// Identify a file from its content.
+pub fn identify_bytes(content: &[u8]) -> Identification {
+    let mime_type = detect_from_bytes(content);
+    let extension = extension_for(&mime_type, content);
+    identification(content, mime_type, extension)
+}
+
+fn identification(content: &[u8], mime_type: String, extension: Option<String>) -> Identification {
+    let container = container_format(content);
+    let mut identification = Identification {
+        mime_type,
+        extension,
+        container,
+        page_count: None,
+        encrypted: false,
+        has_text_layer: None,
+    };
+
+    if content.starts_with(b"%PDF") || identification.mime_type == mime::PDF_MIME_TYPE {
+        identify_pdf(content, &mut identification);
+    } else if identification.mime_type.starts_with("image/") && identification.mime_type != mime::SVG_MIME_TYPE {
+        identification.has_text_layer = Some(false);
+    } else if identification.mime_type.starts_with("text/") {
+        identification.has_text_layer = Some(true);
+    }
+
+    match container {
+        Some(ContainerFormat::Zip) => identify_zip(content, &mut identification),
+        Some(ContainerFormat::Ole) => {
+            // Encrypted OOXML documents are OLE files with an `EncryptedPackage` stream;
+            // directory entry names are UTF-16LE.
+            let name: Vec<u8> = "EncryptedPackage".encode_utf16().flat_map(u16::to_le_bytes).collect();
+            identification.encrypted = memmem::find(content, &name).is_some();
+        }
+        _ => {}
+    }
+
+    identification
+}
+
+/// MIME type of `content`, also for types that are not supported for extraction.
+fn detect_from_bytes(content: &[u8]) -> String {
+    mime::detect_mime_type_from_bytes(content)
+        .ok()
+        .or_else(|| infer::get(content).map(|kind| kind.mime_type().to_string()))
+        .unwrap_or_else(|| UNKNOWN_MIME_TYPE.to_string())
+}
+
+/// Extension of `mime_type`, preferring the one matched by the content signature.
+fn extension_for(mime_type: &str, content: &[u8]) -> Option<String> {
+    if let Some(kind) = infer::get(content)
+        && kind.mime_type() == mime_type
+    {
+        return Some(kind.extension().to_string());
+    }
+    if mime_type == UNKNOWN_MIME_TYPE {
+        return None;
+    }
+    let mut extensions = mime::get_extensions_for_mime(mime_type).ok()?;
+    extensions.sort();
+    extensions.into_iter().next()
+}
+
+fn container_format(content: &[u8]) -> Option<ContainerFormat> {
+    if content.starts_with(ZIP_MAGIC) || content.starts_with(EMPTY_ZIP_MAGIC) {
+        Some(ContainerFormat::Zip)
+    } else if content.starts_with(OLE_MAGIC) {
+        Some(ContainerFormat::Ole)
+    } else if content.starts_with(SEVEN_ZIP_MAGIC) {
+        Some(ContainerFormat::SevenZip)
+    } else if content.starts_with(GZIP_MAGIC) {
+        Some(ContainerFormat::Gzip)
+    } else if content.get(257..262) == Some(b"ustar") {
+        Some(ContainerFormat::Tar)
+    } else {
+        None
+    }
+}
+
+/// Read the encryption, page count and text layer of a PDF from its uncompressed objects.
+fn identify_pdf(content: &[u8], identification: &mut Identification) {
+    identification.encrypted = memmem::find(content, b"/Encrypt").is_some();
+
+    // The root of the page tree has the largest `/Count` of the `/Pages` nodes.
+    identification.page_count = page_tree_nodes(content)
+        .filter_map(|node| integer_after(node, b"/Count"))
+        .max();
+
+    let compressed_objects = memmem::find(content, b"/ObjStm").is_some();
+    identification.has_text_layer = if memmem::find(content, b"/Font").is_some() {
+        Some(true)
+    } else if compressed_objects {
+        None
+    } else {
+        Some(false)
+    };
+}
+
+/// Dictionaries of the `/Type /Pages` nodes of a PDF, each up to the end of its object.
+fn page_tree_nodes(content: &[u8]) -> impl Iterator<Item = &[u8]> {
+    memmem::find_iter(content, b"/Type").filter_map(move |start| {
+        let rest = &content[start + b"/Type".len()..];
+        let rest = rest.trim_ascii_start();
+        let after = rest.strip_prefix(b"/Pages")?;
+        if after.first().is_some_and(|byte| byte.is_ascii_alphanumeric()) {
+            return None;
+        }
+        let object_start = memmem::rfind(&content[..start], b"obj").unwrap_or(0);
+        let object_end = memmem::find(&content[start..], b"endobj").map_or(content.len(), |end| start + end);
+        Some(&content[object_start..object_end])
+    })
+}
+
+/// The non-negative integer following the first `key` in `dictionary`.
+fn integer_after(dictionary: &[u8], key: &[u8]) -> Option<usize> {
+    let start = memmem::find(dictionary, key)? + key.len();
+    let digits = dictionary[start..].trim_ascii_start();
+    let len = digits.iter().take_while(|byte| byte.is_ascii_digit()).count();
+    std::str::from_utf8(&digits[..len]).ok()?.parse().ok()
+}
+
+/// Read the encryption and page count of a zip package.
+#[cfg_attr(not(any(feature = "office", feature = "archives")), allow(unused_variables))]
+fn identify_zip(content: &[u8], identification: &mut Identification) {
+    #[cfg(any(feature = "office", feature = "archives"))]
+    {
+        use std::io::Read;
+
+        let Ok(mut archive) = zip::ZipArchive::new(std::io::Cursor::new(content)) else {
+            return;
+        };
+        identification.encrypted =
+            (0..archive.len()).any(|index| archive.by_index_raw(index).is_ok_and(|file| file.encrypted()));
+
+        let mut read_part = |name: &str| -> Option<String> {
+            let mut part = archive.by_name(name).ok()?;
+            let mut xml = String::new();
+            part.read_to_string(&mut xml).ok()?;
+            Some(xml)
+        };
+
+        // OOXML declares pages and slides in `docProps/app.xml`.
+        if let Some(app) = read_part("docProps/app.xml") {
+            identification.page_count =
+                xml_element_integer(&app, "Pages").or_else(|| xml_element_integer(&app, "Slides"));
+        }
+        // OpenDocument declares pages in `meta.xml` and encryption in its manifest.
+        else if let Some(meta) = read_part("meta.xml") {
+            identification.page_count = integer_after(meta.as_bytes(), b"meta:page-count=\"");
+        }
+        if let Some(manifest) = read_part("META-INF/manifest.xml")
+            && manifest.contains("manifest:encryption-data")
+        {
+            identification.encrypted = true;
+        }
+    }
+}
+
+/// The integer content of the first `<name>` element of `xml`.
+#[cfg(any(feature = "office", feature = "archives"))]
+fn xml_element_integer(xml: &str, name: &str) -> Option<usize> {
+    let start = xml.find(&format!("<{name}>"))? + name.len() + 2;
+    let end = start + xml[start..].find('<')?;
+    xml[start..end].trim().parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_document(path: &str) -> Option<std::path::PathBuf> {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../test_documents")
+            .join(path);
+        path.exists().then_some(path)
+    }
+
+    #[test]
+    fn test_identify_pdf_signals() {
+        let Some(path) = test_document("pdfs/multi_page.pdf") else {
+            return;
+        };
+        let identification = identify(&path).unwrap();
+        assert_eq!(identification.mime_type, "application/pdf");
+        assert_eq!(identification.extension.as_deref(), Some("pdf"));
+        assert_eq!(identification.container, None);
+        assert_eq!(identification.page_count, Some(5));
+        assert!(!identification.encrypted);
+        assert_eq!(identification.has_text_layer, Some(true));
+
+        if let Some(path) = test_document("pdfs/image_only_german_pdf.pdf") {
+            let identification = identify_bytes(&std::fs::read(path).unwrap());
+            assert_eq!(identification.mime_type, "application/pdf");
+            assert_eq!(identification.has_text_layer, Some(false));
+        }
+        if let Some(path) = test_document("pdfs/copy_protected.pdf") {
+            assert!(identify(path).unwrap().encrypted);
+        }
+    }
+
+    #[cfg(feature = "office")]
+    #[test]
+    fn test_identify_docx_package() {
+        let Some(path) = test_document("documents/word_sample.docx") else {
+            return;
+        };
+        let identification = identify(&path).unwrap();
+        assert_eq!(identification.mime_type, mime::DOCX_MIME_TYPE);
+        assert_eq!(identification.extension.as_deref(), Some("docx"));
+        assert_eq!(identification.container, Some(ContainerFormat::Zip));
+        assert!(identification.page_count.is_some());
+        assert!(!identification.encrypted);
+    }
+
+    #[test]
+    fn test_identify_bytes_of_unknown_and_text_content() {
+        let identification = identify_bytes(b"\x00\x01\x02\xFF\xFE");
+        assert_eq!(identification.mime_type, "application/octet-stream");
+        assert_eq!(identification.extension, None);
+        assert_eq!(identification.has_text_layer, None);
+
+        let identification = identify_bytes(b"Plain notes");
+        assert_eq!(identification.mime_type, "text/plain");
+        assert_eq!(identification.has_text_layer, Some(true));
+
+        let mut tar = vec![0u8; 512];
+        tar[257..262].copy_from_slice(b"ustar");
+        assert_eq!(identify_bytes(&tar).container, Some(ContainerFormat::Tar));
+    }
+}
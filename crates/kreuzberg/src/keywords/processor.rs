@@ -117,6 +117,7 @@ machine learning that uses neural networks with multiple layers.
             pages: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         };
 
         processor.process(&mut result, &config).await.unwrap();
@@ -148,6 +149,7 @@ machine learning that uses neural networks with multiple layers.
             pages: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         };
 
         processor.process(&mut result, &config).await.unwrap();
@@ -175,6 +177,7 @@ machine learning that uses neural networks with multiple layers.
             pages: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         };
 
         processor.process(&mut result, &config).await.unwrap();
@@ -202,6 +205,7 @@ machine learning that uses neural networks with multiple layers.
             pages: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         };
 
         processor.process(&mut result, &config).await.unwrap();
@@ -240,6 +244,7 @@ machine learning that uses neural networks with multiple layers.
             pages: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         };
 
         let config_with_keywords = ExtractionConfig {
@@ -267,6 +272,7 @@ machine learning that uses neural networks with multiple layers.
             pages: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         };
 
         let long_result = ExtractionResult {
@@ -280,6 +286,7 @@ machine learning that uses neural networks with multiple layers.
             pages: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         };
 
         let short_duration = processor.estimated_duration_ms(&short_result);
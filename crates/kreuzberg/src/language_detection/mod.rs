@@ -1,6 +1,8 @@
 //! Language detection using the whatlang Rust crate.
 //!
-//! Provides fast language detection for extracted text content.
+//! Provides fast language detection for extracted text content. [`WhatlangDetector`]
+//! is the default [`LanguageDetector`](crate::plugins::LanguageDetector) plugin;
+//! register another detector to replace it.
 
 use crate::Result;
 use crate::core::config::LanguageDetectionConfig;
@@ -99,7 +101,7 @@ fn detect_multiple_languages(text: &str, config: &LanguageDetectionConfig) -> Re
     }
 
     let mut lang_vec: Vec<(Lang, usize)> = lang_counts.into_iter().collect();
-    lang_vec.sort_by(|a, b| b.1.cmp(&a.1));
+    lang_vec.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.code().cmp(b.0.code())));
 
     let languages: Vec<String> = lang_vec.iter().map(|(lang, _)| lang_to_iso639_3(*lang)).collect();
 
@@ -185,6 +187,45 @@ fn lang_to_iso639_3(lang: Lang) -> String {
     .to_string()
 }
 
+/// The built-in language detector, based on whatlang.
+///
+/// Registered as `whatlang` with priority 0, so registered detectors with the
+/// default priority take precedence.
+#[derive(Debug, Clone, Copy)]
+pub struct WhatlangDetector;
+
+impl crate::plugins::Plugin for WhatlangDetector {
+    fn name(&self) -> &str {
+        "whatlang"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn description(&self) -> &str {
+        "Trigram-based language detection with whatlang"
+    }
+}
+
+impl crate::plugins::LanguageDetector for WhatlangDetector {
+    fn detect(&self, text: &str, config: &LanguageDetectionConfig) -> Result<Option<Vec<String>>> {
+        detect_languages(text, config)
+    }
+
+    fn priority(&self) -> i32 {
+        0
+    }
+}
+
 /// Register the language detection processor with the global registry.
 ///
 /// This function should be called once at application startup to register
@@ -13,7 +13,8 @@ use async_trait::async_trait;
 /// - Runs in the Early processing stage
 /// - Only processes when `config.language_detection` is configured
 /// - Stores detected languages in `result.detected_languages`
-/// - Uses the whatlang library for detection
+/// - Uses the registered [`LanguageDetector`](crate::plugins::LanguageDetector) plugin
+///   of the highest priority, whatlang by default
 ///
 /// # Example
 ///
@@ -54,7 +55,7 @@ impl PostProcessor for LanguageDetector {
             None => return Ok(()),
         };
 
-        match super::detect_languages(&result.content, lang_config)
+        match crate::plugins::detect_languages_with_registered(&result.content, lang_config)
             .map_err(|e| KreuzbergError::Other(format!("Language detection failed: {}", e)))?
         {
             Some(languages) => {
@@ -110,6 +111,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -136,6 +138,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -173,6 +176,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -204,6 +208,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -217,6 +222,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
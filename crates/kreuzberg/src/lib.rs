@@ -34,13 +34,21 @@
 
 #![deny(unsafe_code)]
 
+pub mod audit;
 pub mod cache;
+pub mod capabilities;
+pub mod compression;
 pub mod core;
+pub mod diff;
 pub mod error;
 pub mod extraction;
 pub mod extractors;
+pub mod identify;
 pub mod panic_context;
 pub mod plugins;
+pub mod provenance;
+pub mod self_test;
+pub mod store;
 pub mod text;
 pub mod types;
 pub mod utils;
@@ -51,6 +59,24 @@ pub mod api;
 #[cfg(feature = "mcp")]
 pub mod mcp;
 
+#[cfg(feature = "queue")]
+pub mod queue;
+
+#[cfg(feature = "watch")]
+pub mod watch;
+
+#[cfg(feature = "catalog")]
+pub mod catalog;
+
+#[cfg(feature = "models")]
+pub mod models;
+
+#[cfg(feature = "bench")]
+pub mod bench;
+
+#[cfg(feature = "otel")]
+pub mod telemetry;
+
 #[cfg(feature = "chunking")]
 pub mod chunking;
 
@@ -75,28 +101,39 @@ pub mod ocr;
 #[cfg(feature = "pdf")]
 pub mod pdf;
 
-pub use error::{KreuzbergError, Result};
+pub use error::{ErrorClass, KreuzbergError, Result};
 pub use types::*;
 
 #[cfg(feature = "tokio-runtime")]
 pub use core::extractor::{batch_extract_bytes, batch_extract_file};
-pub use core::extractor::{extract_bytes, extract_file};
+pub use core::extractor::{extract_bytes, extract_file, extract_metadata_only};
 
 pub use core::extractor::{batch_extract_bytes_sync, extract_bytes_sync};
 
 #[cfg(feature = "tokio-runtime")]
-pub use core::extractor::{batch_extract_file_sync, extract_file_sync};
+pub use core::extractor::{batch_extract_file_sync, extract_file_sync, extract_metadata_only_sync};
 
 pub use core::config::{
-    ChunkerType, ChunkingConfig, EmbeddingConfig, EmbeddingModelType, ExtractionConfig, ImageExtractionConfig,
-    LanguageDetectionConfig, OcrConfig, OutputFormat, PageConfig, PostProcessorConfig, TokenReductionConfig,
+    BarcodeConfig, BlankPageConfig, BoilerplateConfig, CacheConfig, ChunkerType, ChunkingConfig, CleanupConfig,
+    Compression, ConfigOverrides, ContainerConfig, ContentFilterConfig, DocxConfig, DocxTableMode, DuplicatePageConfig,
+    EmbeddingConfig, EmbeddingModelType, ExtractRule, ExtractionConfig, GlossaryConfig, GlossaryTerm, HandwritingMode,
+    ImageExtractionConfig, IoConfig, IsolationMode, LanguageDetectionConfig, MetadataMode, ModelAsset, ModelKind,
+    ModelsConfig, NetworkConfig, NormalizationProfile, OcrConfig, OcrPageContext, OcrPageOverrideCallback,
+    OcrPageOverrides, OutputConfig, OutputFormat, OverlapStrategy, PageConfig, PageSelection, PluginGuardConfig,
+    PostProcessorConfig, ProvenanceConfig, RetryConfig, RulesConfig, SecurityConfig, SignatureConfig,
+    SpecialFilePolicy, SpellcheckConfig, SpreadsheetConfig, SymlinkPolicy, TableChunkFormat, TableChunkingConfig,
+    ThreadPoolConfig, TokenCountConfig, TokenCounterCallback, TokenPricing, TokenReductionConfig, TokenizerKind,
+    TranslationConfig, TranslationScope, TransliterationConfig, TransliterationScheme, UnitSystem, ValueAnnotationMode,
+    ValueKind, ValueNormalizationConfig, WalkConfig, WorkerConfig, XmlConfig,
 };
 
 #[cfg(feature = "api")]
-pub use core::server_config::ServerConfig;
+pub use core::server_config::{ApiQuota, ApiTenant, JobsConfig, ServerConfig};
 
 #[cfg(feature = "pdf")]
-pub use core::config::{HierarchyConfig, PdfConfig};
+pub use core::config::{
+    ConformanceLevel, HierarchyConfig, PdfBackendKind, PdfConfig, PdfStandard, RedactionConfig, RubyMode,
+};
 
 pub use core::mime::{
     DOCX_MIME_TYPE, EXCEL_MIME_TYPE, HTML_MIME_TYPE, JSON_MIME_TYPE, MARKDOWN_MIME_TYPE, PDF_MIME_TYPE,
@@ -106,8 +143,15 @@ pub use core::mime::{
 
 pub use core::formats::{KNOWN_FORMATS, is_valid_format_field};
 
+pub use capabilities::{Capabilities, RuntimeDependency, capabilities};
+pub use identify::{ContainerFormat, Identification, identify, identify_bytes};
+pub use self_test::{ComponentCheck, SelfTestReport, self_test};
+
+pub use core::pipeline::{ExtractionPlan, OcrTrigger, PlannedOcr, PlannedPlugin, PlannedPostProcessor};
+
 pub use plugins::registry::{
-    get_document_extractor_registry, get_ocr_backend_registry, get_post_processor_registry, get_validator_registry,
+    get_content_filter_registry, get_document_extractor_registry, get_ocr_backend_registry, get_pipeline_hook_registry,
+    get_post_processor_registry, get_validator_registry,
 };
 
 #[cfg(feature = "embeddings")]
@@ -10,6 +10,7 @@ use std::fmt::Write;
 ///
 /// This function ensures different error types are properly differentiated in MCP responses:
 /// - `Validation` errors → `INVALID_PARAMS` (-32602)
+/// - `Security` errors → `INVALID_PARAMS` (-32602)
 /// - `UnsupportedFormat` errors → `INVALID_PARAMS` (-32602)
 /// - `Parsing` errors → `PARSE_ERROR` (-32700)
 /// - `Io` errors → `INTERNAL_ERROR` (-32603) with context preserved
@@ -27,6 +28,14 @@ pub fn map_kreuzberg_error_to_mcp(error: KreuzbergError) -> McpError {
             McpError::invalid_params(error_message, None)
         }
 
+        KreuzbergError::Security { message, source } => {
+            let mut error_message = format!("Security error: {}", message);
+            if let Some(src) = source {
+                let _ = write!(error_message, " (caused by: {})", src);
+            }
+            McpError::invalid_params(error_message, None)
+        }
+
         KreuzbergError::UnsupportedFormat(mime_type) => {
             McpError::invalid_params(format!("Unsupported format: {}", mime_type), None)
         }
@@ -86,6 +95,10 @@ pub fn map_kreuzberg_error_to_mcp(error: KreuzbergError) -> McpError {
             McpError::internal_error(format!("Plugin '{}' error: {}", plugin_name, message), None)
         }
 
+        KreuzbergError::ExtractorPanicked { name, payload } => {
+            McpError::internal_error(format!("Extractor '{}' panicked: {}", name, payload), None)
+        }
+
         KreuzbergError::LockPoisoned(msg) => McpError::internal_error(format!("Internal lock poisoned: {}", msg), None),
 
         KreuzbergError::Other(msg) => McpError::internal_error(msg, None),
@@ -298,6 +311,10 @@ mod tests {
                 message: "test".to_string(),
                 plugin_name: "test-plugin".to_string(),
             },
+            KreuzbergError::ExtractorPanicked {
+                name: "test-extractor".to_string(),
+                payload: "test".to_string(),
+            },
             KreuzbergError::LockPoisoned("test".to_string()),
             KreuzbergError::Other("test".to_string()),
         ];
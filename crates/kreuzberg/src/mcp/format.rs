@@ -300,6 +300,7 @@ mod tests {
             pages: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         };
 
         let formatted = format_extraction_result(&result);
@@ -330,6 +331,7 @@ mod tests {
             pages: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         };
 
         let formatted = format_extraction_result(&result);
@@ -358,12 +360,15 @@ mod tests {
                     total_chunks: 1,
                     first_page: None,
                     last_page: None,
+                    chunk_type: crate::ChunkType::Text,
+                    additional: Default::default(),
                 },
             }]),
             images: None,
             pages: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         };
 
         let formatted = format_extraction_result(&result);
@@ -386,6 +391,7 @@ mod tests {
             pages: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         };
 
         let formatted = format_extraction_result(&result);
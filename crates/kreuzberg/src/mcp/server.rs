@@ -651,6 +651,7 @@ mod tests {
                 language: "spa".to_string(),
                 tesseract_config: None,
                 output_format: None,
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -0,0 +1,573 @@
+//! Model asset manager.
+//!
+//! A [`ModelManager`] installs the model assets of a [`ModelsConfig`] (Tesseract
+//! trained data, ONNX models, tokenizer files) into a versioned directory layout,
+//! downloading them on first use and verifying their SHA-256:
+//!
+//! ```text
+//! <dir>/
+//!   tessdata/
+//!     tessdata_fast/4.1.0/eng.traineddata
+//!     tessdata_fast/4.1.0/eng.traineddata.sha256
+//!   onnx/
+//!     ppocr-v4/1/det.onnx
+//!     ...
+//! ```
+//!
+//! Every version of a model is a directory of its own, which can be used directly as
+//! `TESSDATA_PREFIX` or as the model directory of the ONNX OCR backend. The digest of
+//! each downloaded file is recorded next to it, so installed assets are not hashed
+//! again on every start. Downloads go to a temporary file that is only renamed into
+//! place once verified, so an interrupted download never leaves a partial asset.
+//!
+//! In offline mode nothing is downloaded, and assets that are not installed are an
+//! error; assets can be pre-populated from a mirror with `file://` URLs. When network
+//! access is denied (`KREUZBERG_NETWORK=deny`), only `file://` assets are installed.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use kreuzberg::core::config::{ModelAsset, ModelsConfig};
+//! use kreuzberg::models::ModelManager;
+//!
+//! # async fn example() -> kreuzberg::Result<()> {
+//! let config = ModelsConfig {
+//!     dir: Some("/var/lib/kreuzberg/models".into()),
+//!     assets: vec![ModelAsset::tessdata("tessdata_fast", "4.1.0", "eng")],
+//!     ..Default::default()
+//! };
+//! let models = ModelManager::new(&config);
+//! models.ensure_all().await?;
+//! let tessdata = models.model_dir(&config.assets[0]);
+//! println!("TESSDATA_PREFIX={}", tessdata.display());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::config::{ModelAsset, ModelKind, ModelsConfig, NetworkConfig};
+use crate::{KreuzbergError, Result};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Extension of the files recording the digest of an installed asset.
+const CHECKSUM_EXTENSION: &str = "sha256";
+
+/// Environment variable overriding the default model directory.
+const MODELS_DIR_ENV: &str = "KREUZBERG_MODELS_DIR";
+
+/// Environment variable enabling offline mode.
+const MODELS_OFFLINE_ENV: &str = "KREUZBERG_MODELS_OFFLINE";
+
+/// An installed model file.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct InstalledModel {
+    /// Kind of the model
+    pub kind: ModelKind,
+    /// Name of the model
+    pub name: String,
+    /// Version of the model
+    pub version: String,
+    /// File name
+    pub file: String,
+    /// Path of the file
+    pub path: PathBuf,
+    /// Size in bytes
+    pub size: u64,
+    /// Recorded SHA-256 of the file, for files installed by the manager
+    pub sha256: Option<String>,
+}
+
+/// Installs, verifies and lists model assets in a directory.
+#[derive(Debug, Clone)]
+pub struct ModelManager {
+    root: PathBuf,
+    offline: bool,
+    deny_network: bool,
+    timeout_secs: u64,
+    assets: Vec<ModelAsset>,
+}
+
+impl ModelManager {
+    /// A manager of the assets of `config`.
+    ///
+    /// The directory is created when the first asset is installed.
+    pub fn new(config: &ModelsConfig) -> Self {
+        let root = config
+            .dir
+            .clone()
+            .unwrap_or_else(|| match std::env::var_os(MODELS_DIR_ENV) {
+                Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+                _ => std::env::current_dir()
+                    .unwrap_or_else(|_| PathBuf::from("."))
+                    .join(".kreuzberg")
+                    .join("models"),
+            });
+        let offline = config.offline
+            || std::env::var(MODELS_OFFLINE_ENV)
+                .is_ok_and(|value| matches!(value.to_ascii_lowercase().as_str(), "1" | "true" | "yes"));
+        Self {
+            root,
+            offline,
+            deny_network: NetworkConfig::denied_by_environment(),
+            timeout_secs: config.timeout_secs,
+            assets: config.assets.clone(),
+        }
+    }
+
+    /// Deny network access: only `file://` assets are installed, others are a security
+    /// error. Set by `KREUZBERG_NETWORK=deny`.
+    pub fn deny_network(mut self) -> Self {
+        self.deny_network = true;
+        self
+    }
+
+    /// Directory of the assets.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Whether downloads are disabled.
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Directory of the version of the model of `asset`, shared by all its files.
+    pub fn model_dir(&self, asset: &ModelAsset) -> PathBuf {
+        self.root
+            .join(asset.kind.as_str())
+            .join(&asset.name)
+            .join(&asset.version)
+    }
+
+    /// Path of the file of `asset`.
+    pub fn path(&self, asset: &ModelAsset) -> PathBuf {
+        self.model_dir(asset).join(asset.file_name())
+    }
+
+    /// Whether `asset` is installed, with the pinned checksum if it has one.
+    ///
+    /// Only the recorded digest is compared; [`verify`](Self::verify) hashes the file.
+    pub fn is_installed(&self, asset: &ModelAsset) -> bool {
+        let path = self.path(asset);
+        path.is_file()
+            && asset.sha256.as_ref().is_none_or(|expected| {
+                recorded_digest(&path).is_some_and(|digest| digest.eq_ignore_ascii_case(expected))
+            })
+    }
+
+    /// Hash the installed file of `asset` and compare it with the pinned checksum, or
+    /// with the digest recorded when it was installed.
+    ///
+    /// Returns `false` for a file that is not installed or has neither.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the file cannot be read.
+    pub fn verify(&self, asset: &ModelAsset) -> Result<bool> {
+        let path = self.path(asset);
+        if !path.is_file() {
+            return Ok(false);
+        }
+        let Some(expected) = asset.sha256.clone().or_else(|| recorded_digest(&path)) else {
+            return Ok(false);
+        };
+        Ok(file_digest(&path)?.eq_ignore_ascii_case(&expected))
+    }
+
+    /// Install `asset` unless it is installed, returning the path of its file.
+    ///
+    /// A file found without a recorded digest, e.g. copied in by hand, is hashed once
+    /// and kept if it matches the pinned checksum; otherwise it is downloaded again.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if the asset is invalid or the download
+    /// does not match its checksum, `KreuzbergError::MissingDependency` if it is not
+    /// installed in offline mode, `KreuzbergError::Security` if it would be downloaded
+    /// while network access is denied, and `KreuzbergError::Other` or an I/O error if the
+    /// download fails.
+    pub async fn ensure(&self, asset: &ModelAsset) -> Result<PathBuf> {
+        asset.validate()?;
+        let path = self.path(asset);
+
+        if path.is_file() {
+            let Some(expected) = &asset.sha256 else {
+                return Ok(path);
+            };
+            if self.is_installed(asset) {
+                return Ok(path);
+            }
+            let digest = file_digest(&path)?;
+            if digest.eq_ignore_ascii_case(expected) {
+                record_digest(&path, &digest)?;
+                return Ok(path);
+            }
+            tracing::warn!(
+                "Model asset {} does not match its sha256, installing it again",
+                path.display()
+            );
+        }
+
+        if self.offline {
+            return Err(KreuzbergError::MissingDependency(format!(
+                "Model asset '{}' {} is not installed at {} and downloads are disabled (offline mode)",
+                asset.name,
+                asset.version,
+                path.display()
+            )));
+        }
+        if self.deny_network && !asset.url.starts_with("file:") {
+            return Err(KreuzbergError::Security {
+                message: format!(
+                    "Model asset '{}' {} is not installed at {} and network access is denied",
+                    asset.name,
+                    asset.version,
+                    path.display()
+                ),
+                source: None,
+            });
+        }
+
+        let dir = path.parent().expect("asset paths have a parent directory");
+        std::fs::create_dir_all(dir)?;
+        // Download next to the target and rename, so readers never see a partial asset.
+        let partial = dir.join(format!(".{}.{}.partial", asset.file_name(), uuid::Uuid::new_v4()));
+        let digest = match self.download(&asset.url, &partial).await {
+            Ok(digest) => digest,
+            Err(e) => {
+                let _ = std::fs::remove_file(&partial);
+                return Err(e);
+            }
+        };
+        if let Some(expected) = &asset.sha256
+            && !digest.eq_ignore_ascii_case(expected)
+        {
+            let _ = std::fs::remove_file(&partial);
+            return Err(KreuzbergError::validation(format!(
+                "Model asset '{}' from {} has sha256 {}, expected {}",
+                asset.name, asset.url, digest, expected
+            )));
+        }
+        std::fs::rename(&partial, &path)?;
+        record_digest(&path, &digest)?;
+        tracing::info!("Installed model asset {}", path.display());
+        Ok(path)
+    }
+
+    /// Install all assets of the configuration, returning the paths of their files.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of the first asset that cannot be installed.
+    pub async fn ensure_all(&self) -> Result<Vec<PathBuf>> {
+        let mut paths = Vec::with_capacity(self.assets.len());
+        for asset in &self.assets {
+            paths.push(self.ensure(asset).await?);
+        }
+        Ok(paths)
+    }
+
+    /// All installed model files, ordered by kind, name, version and file name.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the directory cannot be read.
+    pub fn installed(&self) -> Result<Vec<InstalledModel>> {
+        let mut models = Vec::new();
+        for kind in [ModelKind::Tessdata, ModelKind::Onnx, ModelKind::Tokenizer] {
+            for (name, name_dir) in subdirectories(&self.root.join(kind.as_str()))? {
+                for (version, version_dir) in subdirectories(&name_dir)? {
+                    for entry in std::fs::read_dir(&version_dir)? {
+                        let entry = entry?;
+                        let file = entry.file_name().to_string_lossy().into_owned();
+                        let path = entry.path();
+                        if file.starts_with('.')
+                            || path
+                                .extension()
+                                .is_some_and(|extension| extension == CHECKSUM_EXTENSION)
+                            || !entry.file_type()?.is_file()
+                        {
+                            continue;
+                        }
+                        models.push(InstalledModel {
+                            kind,
+                            name: name.clone(),
+                            version: version.clone(),
+                            file,
+                            size: entry.metadata()?.len(),
+                            sha256: recorded_digest(&path),
+                            path,
+                        });
+                    }
+                }
+            }
+        }
+        models.sort_by(|a, b| {
+            (a.kind.as_str(), &a.name, &a.version, &a.file).cmp(&(b.kind.as_str(), &b.name, &b.version, &b.file))
+        });
+        Ok(models)
+    }
+
+    /// Remove the installed versions of the configured models that the configuration
+    /// no longer lists, returning the removed version directories.
+    ///
+    /// Models the configuration does not mention at all are kept.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if a directory cannot be read or removed.
+    pub fn prune(&self) -> Result<Vec<PathBuf>> {
+        let configured: HashSet<(ModelKind, &str, &str)> = self
+            .assets
+            .iter()
+            .map(|asset| (asset.kind, asset.name.as_str(), asset.version.as_str()))
+            .collect();
+        let models: HashSet<(ModelKind, &str)> = configured.iter().map(|&(kind, name, _)| (kind, name)).collect();
+
+        let mut removed = Vec::new();
+        for &(kind, name) in &models {
+            for (version, version_dir) in subdirectories(&self.root.join(kind.as_str()).join(name))? {
+                if !configured.contains(&(kind, name, version.as_str())) {
+                    std::fs::remove_dir_all(&version_dir)?;
+                    removed.push(version_dir);
+                }
+            }
+        }
+        removed.sort();
+        Ok(removed)
+    }
+
+    /// Copy or download `url` to `path`, returning the SHA-256 of the data.
+    async fn download(&self, url: &str, path: &Path) -> Result<String> {
+        let parsed = reqwest::Url::parse(url)
+            .map_err(|e| KreuzbergError::validation(format!("Invalid model asset URL '{}': {}", url, e)))?;
+        let mut file = HashingWriter::new(std::fs::File::create(path)?);
+
+        match parsed.scheme() {
+            "file" => {
+                let source = parsed.to_file_path().map_err(|_| {
+                    KreuzbergError::validation(format!("Model asset URL '{}' is not a local file path", url))
+                })?;
+                std::io::copy(&mut std::fs::File::open(&source)?, &mut file)?;
+            }
+            "http" | "https" => {
+                let client = reqwest::Client::builder()
+                    .timeout(std::time::Duration::from_secs(self.timeout_secs))
+                    .build()
+                    .map_err(|e| KreuzbergError::Other(format!("Failed to create HTTP client: {}", e)))?;
+                let failed = |e: reqwest::Error| {
+                    KreuzbergError::Other(format!("Failed to download model asset from {}: {}", url, e))
+                };
+                let mut response = client
+                    .get(parsed)
+                    .send()
+                    .await
+                    .and_then(reqwest::Response::error_for_status)
+                    .map_err(failed)?;
+                while let Some(chunk) = response.chunk().await.map_err(failed)? {
+                    file.write_all(&chunk)?;
+                }
+            }
+            scheme => {
+                return Err(KreuzbergError::validation(format!(
+                    "Unsupported model asset URL scheme '{}' in '{}'",
+                    scheme, url
+                )));
+            }
+        }
+
+        file.inner.sync_all()?;
+        Ok(hex::encode(file.hasher.finalize()))
+    }
+}
+
+/// Writer computing the SHA-256 of the data written through it.
+struct HashingWriter<W> {
+    inner: W,
+    hasher: Sha256,
+}
+
+impl<W> HashingWriter<W> {
+    fn new(inner: W) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+        }
+    }
+}
+
+impl<W: Write> Write for HashingWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        let written = self.inner.write(buf)?;
+        self.hasher.update(&buf[..written]);
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        self.inner.flush()
+    }
+}
+
+/// SHA-256 of the file at `path`.
+fn file_digest(path: &Path) -> Result<String> {
+    let mut hasher = HashingWriter::new(std::io::sink());
+    std::io::copy(&mut std::fs::File::open(path)?, &mut hasher)?;
+    Ok(hex::encode(hasher.hasher.finalize()))
+}
+
+fn checksum_path(path: &Path) -> PathBuf {
+    let mut checksum = path.as_os_str().to_owned();
+    checksum.push(".");
+    checksum.push(CHECKSUM_EXTENSION);
+    PathBuf::from(checksum)
+}
+
+/// Digest recorded when the file at `path` was installed.
+fn recorded_digest(path: &Path) -> Option<String> {
+    let digest = std::fs::read_to_string(checksum_path(path)).ok()?;
+    Some(digest.trim().to_string())
+}
+
+fn record_digest(path: &Path, digest: &str) -> Result<()> {
+    std::fs::write(checksum_path(path), format!("{}\n", digest))?;
+    Ok(())
+}
+
+/// Names and paths of the subdirectories of `dir`, none if it does not exist.
+fn subdirectories(dir: &Path) -> Result<Vec<(String, PathBuf)>> {
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut dirs = Vec::new();
+    for entry in entries {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            dirs.push((entry.file_name().to_string_lossy().into_owned(), entry.path()));
+        }
+    }
+    Ok(dirs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn asset(source: &Path, version: &str, sha256: Option<&str>) -> ModelAsset {
+        ModelAsset {
+            name: "ppocr-v4".to_string(),
+            kind: ModelKind::Onnx,
+            version: version.to_string(),
+            url: reqwest::Url::from_file_path(source).unwrap().to_string(),
+            sha256: sha256.map(str::to_string),
+            file: Some("det.onnx".to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_ensure_installs_and_verifies_assets() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("mirror-det.onnx");
+        std::fs::write(&source, b"onnx model").unwrap();
+        let digest = hex::encode(Sha256::digest(b"onnx model"));
+
+        let config = ModelsConfig {
+            dir: Some(dir.path().join("models")),
+            assets: vec![asset(&source, "1", Some(&digest))],
+            ..Default::default()
+        };
+        let models = ModelManager::new(&config);
+        let paths = models.ensure_all().await.unwrap();
+        assert_eq!(paths, vec![dir.path().join("models/onnx/ppocr-v4/1/det.onnx")]);
+        assert_eq!(std::fs::read(&paths[0]).unwrap(), b"onnx model");
+        assert!(models.is_installed(&config.assets[0]));
+        assert!(models.verify(&config.assets[0]).unwrap());
+
+        let installed = models.installed().unwrap();
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].version, "1");
+        assert_eq!(installed[0].sha256.as_deref(), Some(digest.as_str()));
+
+        // Installed assets are found without the source, also offline.
+        std::fs::remove_file(&source).unwrap();
+        let offline = ModelManager::new(&ModelsConfig {
+            offline: true,
+            ..config.clone()
+        });
+        assert_eq!(offline.ensure(&config.assets[0]).await.unwrap(), paths[0]);
+        assert!(matches!(
+            offline.ensure(&asset(&source, "2", None)).await,
+            Err(KreuzbergError::MissingDependency(_))
+        ));
+
+        std::fs::write(&source, b"tampered model").unwrap();
+        let denied = ModelManager::new(&config).deny_network();
+        assert!(denied.ensure(&asset(&source, "2", None)).await.is_ok());
+        let remote = ModelAsset {
+            url: "https://models.example.com/det.onnx".to_string(),
+            ..asset(&source, "4", None)
+        };
+        assert!(matches!(
+            denied.ensure(&remote).await,
+            Err(KreuzbergError::Security { .. })
+        ));
+        std::fs::remove_dir_all(dir.path().join("models/onnx/ppocr-v4/2")).unwrap();
+
+        assert!(matches!(
+            models.ensure(&asset(&source, "3", Some(&digest))).await,
+            Err(KreuzbergError::Validation { .. })
+        ));
+        assert!(!dir.path().join("models/onnx/ppocr-v4/3/det.onnx").exists());
+        assert_eq!(models.installed().unwrap().len(), 1);
+
+        std::fs::write(&paths[0], b"corrupted").unwrap();
+        assert!(!models.verify(&config.assets[0]).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_prune_removes_unlisted_versions() {
+        let dir = tempdir().unwrap();
+        let source = dir.path().join("det.onnx");
+        std::fs::write(&source, b"onnx model").unwrap();
+        let root = dir.path().join("models");
+
+        let old = ModelManager::new(&ModelsConfig {
+            dir: Some(root.clone()),
+            assets: vec![asset(&source, "1", None)],
+            ..Default::default()
+        });
+        old.ensure_all().await.unwrap();
+        let other = ModelAsset {
+            name: "other".to_string(),
+            ..asset(&source, "1", None)
+        };
+        old.ensure(&other).await.unwrap();
+
+        let new = ModelManager::new(&ModelsConfig {
+            dir: Some(root.clone()),
+            assets: vec![asset(&source, "2", None)],
+            ..Default::default()
+        });
+        new.ensure_all().await.unwrap();
+        assert_eq!(new.prune().unwrap(), vec![root.join("onnx/ppocr-v4/1")]);
+
+        let versions: Vec<_> = new
+            .installed()
+            .unwrap()
+            .into_iter()
+            .map(|model| (model.name, model.version))
+            .collect();
+        assert_eq!(
+            versions,
+            vec![
+                ("other".to_string(), "1".to_string()),
+                ("ppocr-v4".to_string(), "2".to_string())
+            ]
+        );
+    }
+}
@@ -25,7 +25,9 @@ pub(in crate::ocr) fn languages() -> Vec<String> {
         "ceb".to_string(),
         "ces".to_string(),
         "chi_sim".to_string(),
+        "chi_sim_vert".to_string(),
         "chi_tra".to_string(),
+        "chi_tra_vert".to_string(),
         "chr".to_string(),
         "cos".to_string(),
         "cym".to_string(),
@@ -66,6 +68,7 @@ pub(in crate::ocr) fn languages() -> Vec<String> {
         "ita_old".to_string(),
         "jav".to_string(),
         "jpn".to_string(),
+        "jpn_vert".to_string(),
         "kan".to_string(),
         "kat".to_string(),
         "kat_old".to_string(),
@@ -74,6 +77,7 @@ pub(in crate::ocr) fn languages() -> Vec<String> {
         "kir".to_string(),
         "kmr".to_string(),
         "kor".to_string(),
+        "kor_vert".to_string(),
         "lao".to_string(),
         "lat".to_string(),
         "lav".to_string(),
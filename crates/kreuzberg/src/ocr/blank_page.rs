@@ -0,0 +1,150 @@
+//! Blank page detection.
+//!
+//! Scanned documents often contain empty pages: separator sheets, empty backs of
+//! duplex scans, pages with only scanner artifacts. Their ink density is measured on a
+//! downscaled copy of the page, ignoring the margins, where scanners leave shadows and
+//! punch holes, and isolated specks of dust, so they can be skipped by OCR.
+
+use crate::core::config::BlankPageConfig;
+use image::imageops::FilterType;
+use image::{DynamicImage, GrayImage};
+
+/// Longer side, in pixels, of the image the ink density is measured on.
+const ANALYSIS_SIZE: u32 = 1000;
+/// Share of the width and height ignored on every side of the page.
+const MARGIN: f64 = 0.05;
+/// Minimum difference in brightness from the paper for a pixel to count as ink.
+const INK_CONTRAST: u8 = 80;
+
+/// Share of the page covered by ink, between 0.0 and 1.0.
+///
+/// Pixels count as ink when they differ clearly from the paper, the median brightness
+/// of the page, so light text on dark paper is measured as well. Ink pixels without an
+/// inked neighbour are treated as specks and not counted.
+pub fn ink_density(image: &DynamicImage) -> f64 {
+    if image.width() == 0 || image.height() == 0 {
+        return 0.0;
+    }
+
+    let gray = if image.width().max(image.height()) > ANALYSIS_SIZE {
+        image
+            .resize(ANALYSIS_SIZE, ANALYSIS_SIZE, FilterType::Triangle)
+            .to_luma8()
+    } else {
+        image.to_luma8()
+    };
+
+    let (width, height) = gray.dimensions();
+    let margin_x = (f64::from(width) * MARGIN) as u32;
+    let margin_y = (f64::from(height) * MARGIN) as u32;
+    let (x0, x1) = (margin_x, width - margin_x);
+    let (y0, y1) = (margin_y, height - margin_y);
+    if x1 <= x0 || y1 <= y0 {
+        return 0.0;
+    }
+
+    let paper = median_brightness(&gray, x0..x1, y0..y1);
+    let is_ink = |x: u32, y: u32| gray.get_pixel(x, y)[0].abs_diff(paper) >= INK_CONTRAST;
+
+    let mut ink = 0u64;
+    for y in y0..y1 {
+        for x in x0..x1 {
+            if !is_ink(x, y) {
+                continue;
+            }
+            let has_inked_neighbour = (y.saturating_sub(1)..=(y + 1).min(y1 - 1))
+                .flat_map(|ny| (x.saturating_sub(1)..=(x + 1).min(x1 - 1)).map(move |nx| (nx, ny)))
+                .any(|(nx, ny)| (nx, ny) != (x, y) && is_ink(nx, ny));
+            if has_inked_neighbour {
+                ink += 1;
+            }
+        }
+    }
+
+    ink as f64 / (f64::from(x1 - x0) * f64::from(y1 - y0))
+}
+
+/// Whether a page holds too little ink to be worth OCRing.
+pub fn is_blank(image: &DynamicImage, config: &BlankPageConfig) -> bool {
+    ink_density(image) <= config.ink_threshold
+}
+
+fn median_brightness(gray: &GrayImage, columns: std::ops::Range<u32>, rows: std::ops::Range<u32>) -> u8 {
+    let mut histogram = [0u64; 256];
+    for y in rows.clone() {
+        for x in columns.clone() {
+            histogram[gray.get_pixel(x, y)[0] as usize] += 1;
+        }
+    }
+
+    let half = (u64::from(columns.end - columns.start) * u64::from(rows.end - rows.start)).div_ceil(2);
+    let mut seen = 0;
+    for (value, &count) in histogram.iter().enumerate() {
+        seen += count;
+        if seen >= half {
+            return value as u8;
+        }
+    }
+    u8::MAX
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{Luma, RgbImage};
+
+    fn fixture(name: &str) -> DynamicImage {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../test_documents/images")
+            .join(name);
+        image::open(path).unwrap()
+    }
+
+    #[test]
+    fn test_scanner_artifacts_count_as_blank() {
+        let mut page = GrayImage::from_pixel(850, 1100, Luma([238]));
+        // Dark scanner edge, a punch hole in the margin and specks of dust.
+        for y in 0..1100 {
+            for x in 0..20 {
+                page.put_pixel(x, y, Luma([30]));
+            }
+        }
+        for y in 500..530 {
+            for x in 25..40 {
+                page.put_pixel(x, y, Luma([10]));
+            }
+        }
+        for i in 0..200 {
+            page.put_pixel(60 + (i * 37) % 700, 80 + (i * 53) % 900, Luma([0]));
+        }
+        let page = DynamicImage::ImageLuma8(page);
+
+        assert!(ink_density(&page) < 0.0001);
+        assert!(is_blank(&page, &BlankPageConfig::default()));
+        assert!(is_blank(
+            &DynamicImage::ImageRgb8(RgbImage::new(10, 10)),
+            &BlankPageConfig::default()
+        ));
+    }
+
+    #[test]
+    fn test_pages_with_text_are_not_blank() {
+        for name in ["invoice_image.png", "ocr_image.jpg", "test_hello_world.png"] {
+            let page = fixture(name);
+            assert!(!is_blank(&page, &BlankPageConfig::default()), "{name}");
+        }
+    }
+
+    #[test]
+    fn test_ink_threshold_is_configurable() {
+        let page = fixture("invoice_image.png");
+        let density = ink_density(&page);
+        assert!(is_blank(&page, &BlankPageConfig { ink_threshold: density }));
+        assert!(!is_blank(
+            &page,
+            &BlankPageConfig {
+                ink_threshold: density / 2.0
+            }
+        ));
+    }
+}
@@ -0,0 +1,250 @@
+//! Duplicate page detection.
+//!
+//! Sheets fed twice through a scanner produce pages that differ only by noise, a small
+//! shift and compression artifacts. Each page is reduced to a 255-bit perceptual hash,
+//! the signs of the low-frequency DCT coefficients of a 64x64 thumbnail relative to
+//! their median, which such differences barely change. A page whose hash is within
+//! [`DuplicatePageConfig::max_distance`] bits of an earlier page's hash repeats that page.
+
+use crate::core::config::DuplicatePageConfig;
+use crate::types::{DuplicatePage, Metadata};
+use image::DynamicImage;
+use image::imageops::FilterType;
+use std::borrow::Cow;
+use std::f64::consts::PI;
+use std::sync::OnceLock;
+
+/// Side of the square thumbnail the hash is computed from.
+const THUMBNAIL_SIZE: usize = 64;
+/// Number of DCT frequencies per axis kept in the hash.
+const FREQUENCIES: usize = 16;
+
+/// Perceptual hash of a page.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PageHash([u64; 4]);
+
+impl PageHash {
+    /// Hash a page image.
+    pub fn of(image: &DynamicImage) -> Self {
+        let thumbnail = image
+            .resize_exact(THUMBNAIL_SIZE as u32, THUMBNAIL_SIZE as u32, FilterType::Triangle)
+            .to_luma8();
+        let pixels: Vec<f64> = thumbnail.pixels().map(|pixel| f64::from(pixel[0])).collect();
+        let cosines = dct_cosines();
+
+        // Separable 2D DCT restricted to the low frequencies: rows first, then columns.
+        let mut rows = vec![0.0; THUMBNAIL_SIZE * FREQUENCIES];
+        for y in 0..THUMBNAIL_SIZE {
+            for v in 0..FREQUENCIES {
+                rows[y * FREQUENCIES + v] = (0..THUMBNAIL_SIZE)
+                    .map(|x| pixels[y * THUMBNAIL_SIZE + x] * cosines[v * THUMBNAIL_SIZE + x])
+                    .sum();
+            }
+        }
+        let mut coefficients = Vec::with_capacity(FREQUENCIES * FREQUENCIES);
+        for u in 0..FREQUENCIES {
+            for v in 0..FREQUENCIES {
+                coefficients.push(
+                    (0..THUMBNAIL_SIZE)
+                        .map(|y| rows[y * FREQUENCIES + v] * cosines[u * THUMBNAIL_SIZE + y])
+                        .sum::<f64>(),
+                );
+            }
+        }
+
+        // The DC coefficient only reflects the overall brightness.
+        let coefficients = &coefficients[1..];
+        let mut sorted = coefficients.to_vec();
+        sorted.sort_by(f64::total_cmp);
+        let median = sorted[sorted.len() / 2];
+
+        let mut bits = [0u64; 4];
+        for (i, &coefficient) in coefficients.iter().enumerate() {
+            if coefficient > median {
+                bits[i / 64] |= 1 << (i % 64);
+            }
+        }
+        Self(bits)
+    }
+
+    /// Number of differing bits between two hashes.
+    pub fn distance(&self, other: &PageHash) -> u32 {
+        self.0.iter().zip(other.0).map(|(a, b)| (a ^ b).count_ones()).sum()
+    }
+}
+
+fn dct_cosines() -> &'static [f64] {
+    static COSINES: OnceLock<Vec<f64>> = OnceLock::new();
+    COSINES.get_or_init(|| {
+        (0..FREQUENCIES)
+            .flat_map(|frequency| {
+                (0..THUMBNAIL_SIZE)
+                    .map(move |x| (PI * (2 * x + 1) as f64 * frequency as f64 / (2 * THUMBNAIL_SIZE) as f64).cos())
+            })
+            .collect()
+    })
+}
+
+/// Finds the pages of a document that repeat an earlier page.
+///
+/// Pages must be checked in page order.
+pub struct DuplicatePageDetector {
+    max_distance: u32,
+    pages: Vec<(usize, PageHash)>,
+}
+
+impl DuplicatePageDetector {
+    pub fn new(config: &DuplicatePageConfig) -> Self {
+        Self {
+            max_distance: config.max_distance,
+            pages: Vec::new(),
+        }
+    }
+
+    /// Return the number of the earlier page that page `page_number` repeats.
+    ///
+    /// Pages that repeat no earlier page are remembered for the following pages.
+    pub fn check(&mut self, page_number: usize, page: &DynamicImage) -> Option<usize> {
+        let hash = PageHash::of(page);
+        let original = self
+            .pages
+            .iter()
+            .filter(|(_, earlier)| earlier.distance(&hash) <= self.max_distance)
+            .min_by_key(|(_, earlier)| earlier.distance(&hash))
+            .map(|&(number, _)| number);
+
+        if original.is_none() {
+            self.pages.push((page_number, hash));
+        }
+        original
+    }
+}
+
+/// Record duplicate pages in `metadata.additional["duplicate_pages"]`, and dropped ones
+/// in `metadata.additional["duplicate_page_warnings"]`.
+pub(crate) fn record_duplicate_pages(metadata: &mut Metadata, duplicates: &[DuplicatePage]) {
+    if duplicates.is_empty() {
+        return;
+    }
+
+    let warnings: Vec<String> = duplicates
+        .iter()
+        .filter(|duplicate| duplicate.dropped)
+        .map(|duplicate| {
+            format!(
+                "Dropped page {}, a duplicate of page {}",
+                duplicate.page_number, duplicate.duplicate_of
+            )
+        })
+        .collect();
+
+    metadata
+        .additional
+        .insert(Cow::Borrowed("duplicate_pages"), serde_json::json!(duplicates));
+    if !warnings.is_empty() {
+        metadata
+            .additional
+            .insert(Cow::Borrowed("duplicate_page_warnings"), serde_json::json!(warnings));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::imageops;
+    use image::{GrayImage, Luma};
+
+    fn fixture(name: &str) -> DynamicImage {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../test_documents/images")
+            .join(name);
+        image::open(path).unwrap()
+    }
+
+    /// Simulate scanning a page again: shifted, darker, noisy and JPEG compressed.
+    fn rescan(page: &DynamicImage) -> DynamicImage {
+        let page = page.to_luma8();
+        let mut state = 0x2545_f491_u32;
+        let mut rescanned = GrayImage::from_pixel(page.width(), page.height(), Luma([255]));
+        imageops::overlay(&mut rescanned, &page, 5, -4);
+        for pixel in rescanned.pixels_mut() {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            let noise = (state % 31) as i32 - 15;
+            pixel[0] = (i32::from(pixel[0]) * 9 / 10 + 12 + noise).clamp(0, 255) as u8;
+        }
+
+        let mut jpeg = std::io::Cursor::new(Vec::new());
+        DynamicImage::ImageLuma8(rescanned)
+            .write_to(&mut jpeg, image::ImageFormat::Jpeg)
+            .unwrap();
+        image::load_from_memory(&jpeg.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn test_rescanned_page_is_a_duplicate() {
+        let config = DuplicatePageConfig::default();
+        for name in ["2305_03393v1_pg9_img.png", "signed_form.png", "ocr_image.jpg"] {
+            let page = fixture(name);
+            let distance = PageHash::of(&page).distance(&PageHash::of(&rescan(&page)));
+            assert!(distance <= config.max_distance, "{name}: distance {distance}");
+        }
+    }
+
+    #[test]
+    fn test_detector_reports_the_original_page() {
+        let first = fixture("2305_03393v1_pg9_img.png");
+        let second = fixture("signed_form.png");
+
+        let mut detector = DuplicatePageDetector::new(&DuplicatePageConfig::default());
+        assert_eq!(detector.check(1, &first), None);
+        assert_eq!(detector.check(2, &second), None);
+        assert_eq!(detector.check(3, &rescan(&first)), Some(1));
+        assert_eq!(detector.check(4, &second), Some(2));
+    }
+
+    #[test]
+    fn test_different_pages_of_the_same_layout_are_not_duplicates() {
+        // The top and bottom halves of a paper page stand in for consecutive pages.
+        let page = fixture("2305_03393v1_pg9_img.png");
+        let (width, height) = (page.width(), page.height());
+        let top = page.crop_imm(0, 0, width, height / 2);
+        let bottom = page.crop_imm(0, height / 2, width, height / 2);
+
+        let distance = PageHash::of(&top).distance(&PageHash::of(&bottom));
+        assert!(
+            distance > 2 * DuplicatePageConfig::default().max_distance,
+            "distance {distance}"
+        );
+    }
+
+    #[test]
+    fn test_record_duplicate_pages_warns_about_dropped_pages() {
+        let mut metadata = Metadata::default();
+        record_duplicate_pages(
+            &mut metadata,
+            &[
+                DuplicatePage {
+                    page_number: 3,
+                    duplicate_of: 2,
+                    dropped: true,
+                },
+                DuplicatePage {
+                    page_number: 5,
+                    duplicate_of: 1,
+                    dropped: false,
+                },
+            ],
+        );
+        assert_eq!(metadata.additional["duplicate_pages"][0]["duplicate_of"], 2);
+        assert_eq!(
+            metadata.additional["duplicate_page_warnings"],
+            serde_json::json!(["Dropped page 3, a duplicate of page 2"])
+        );
+
+        let mut metadata = Metadata::default();
+        record_duplicate_pages(&mut metadata, &[]);
+        assert!(metadata.additional.is_empty());
+    }
+}
@@ -0,0 +1,164 @@
+//! Pool of initialized OCR engines.
+//!
+//! Initializing a Tesseract engine loads the trained data of its languages, which takes
+//! longer than recognizing a typical page. Engines are therefore checked out of a pool
+//! keyed by their initialization parameters and returned to it after use, so only the
+//! first pages recognized with a language pay for the initialization. Engines are reset
+//! before they are returned; an engine that cannot be reset is dropped.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::ops::Deref;
+
+/// Idle engines by initialization key.
+pub(crate) struct EnginePool<E> {
+    idle: Mutex<HashMap<String, Vec<E>>>,
+    /// Maximum number of idle engines kept per key
+    max_idle: usize,
+    /// Prepare an engine for its next use; `false` drops it
+    reset: fn(&E) -> bool,
+}
+
+impl<E> EnginePool<E> {
+    /// An empty pool keeping up to `max_idle` idle engines per key.
+    pub(crate) fn new(max_idle: usize, reset: fn(&E) -> bool) -> Self {
+        Self {
+            idle: Mutex::new(HashMap::new()),
+            max_idle,
+            reset,
+        }
+    }
+
+    /// An idle engine initialized for `key`, or a new one from `create`.
+    pub(crate) fn checkout<Err>(
+        &self,
+        key: &str,
+        create: impl FnOnce() -> Result<E, Err>,
+    ) -> Result<PooledEngine<'_, E>, Err> {
+        let idle = self.idle.lock().get_mut(key).and_then(Vec::pop);
+        let engine = match idle {
+            Some(engine) => engine,
+            None => create()?,
+        };
+        Ok(PooledEngine {
+            pool: self,
+            key: key.to_string(),
+            engine: Some(engine),
+        })
+    }
+
+    /// Create engines for `key` until `count` of them are idle, up to the pool limit.
+    pub(crate) fn warm<Err>(
+        &self,
+        key: &str,
+        count: usize,
+        mut create: impl FnMut() -> Result<E, Err>,
+    ) -> Result<(), Err> {
+        let count = count.min(self.max_idle);
+        while self.idle_count(key) < count {
+            let engine = create()?;
+            self.checkin(key, engine);
+        }
+        Ok(())
+    }
+
+    /// Number of idle engines for `key`.
+    pub(crate) fn idle_count(&self, key: &str) -> usize {
+        self.idle.lock().get(key).map_or(0, Vec::len)
+    }
+
+    /// Drop all idle engines.
+    pub(crate) fn clear(&self) {
+        self.idle.lock().clear();
+    }
+
+    fn checkin(&self, key: &str, engine: E) {
+        if !(self.reset)(&engine) {
+            return;
+        }
+        let mut idle = self.idle.lock();
+        let engines = idle.entry(key.to_string()).or_default();
+        if engines.len() < self.max_idle {
+            engines.push(engine);
+        }
+    }
+}
+
+/// An engine checked out of an [`EnginePool`], returned to it when dropped.
+pub(crate) struct PooledEngine<'a, E> {
+    pool: &'a EnginePool<E>,
+    key: String,
+    engine: Option<E>,
+}
+
+impl<E> Deref for PooledEngine<'_, E> {
+    type Target = E;
+
+    fn deref(&self) -> &E {
+        // The engine is only taken out in `drop`.
+        self.engine.as_ref().expect("pooled engine is present until dropped")
+    }
+}
+
+impl<E> Drop for PooledEngine<'_, E> {
+    fn drop(&mut self) {
+        if let Some(engine) = self.engine.take() {
+            self.pool.checkin(&self.key, engine);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct Engine {
+        id: usize,
+        healthy: bool,
+    }
+
+    fn pool(max_idle: usize) -> EnginePool<Engine> {
+        EnginePool::new(max_idle, |engine| engine.healthy)
+    }
+
+    #[test]
+    fn test_engines_are_reused_per_key() {
+        let pool = pool(2);
+        let created = AtomicUsize::new(0);
+        let create = || {
+            Ok::<_, ()>(Engine {
+                id: created.fetch_add(1, Ordering::SeqCst),
+                healthy: true,
+            })
+        };
+
+        let first = pool.checkout("eng", create).unwrap();
+        let second = pool.checkout("eng", create).unwrap();
+        assert_eq!((first.id, second.id), (0, 1));
+        drop(first);
+        assert_eq!(pool.checkout("eng", create).unwrap().id, 0);
+        assert_eq!(pool.checkout("deu", create).unwrap().id, 2);
+        drop(second);
+        assert_eq!(pool.idle_count("eng"), 2);
+        assert_eq!(pool.idle_count("deu"), 1);
+
+        pool.warm("fra", 5, create).unwrap();
+        assert_eq!(pool.idle_count("fra"), 2);
+        assert_eq!(created.load(Ordering::SeqCst), 5);
+
+        pool.clear();
+        assert_eq!(pool.idle_count("eng"), 0);
+        assert!(pool.checkout("eng", || Err::<Engine, _>("failed")).is_err());
+    }
+
+    #[test]
+    fn test_engines_failing_reset_are_dropped() {
+        let pool = pool(4);
+        drop(
+            pool.checkout("eng", || Ok::<_, ()>(Engine { id: 0, healthy: false }))
+                .unwrap(),
+        );
+        assert_eq!(pool.idle_count("eng"), 0);
+    }
+}
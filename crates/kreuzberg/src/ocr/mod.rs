@@ -7,6 +7,7 @@
 //!
 //! - **Tesseract integration**: Native Tesseract backend via `kreuzberg-tesseract`
 //! - **Result caching**: Persistent cache for OCR results using file hashing
+//! - **Engine reuse**: Initialized Tesseract engines are pooled and reused across documents
 //! - **Table reconstruction**: Extract and reconstruct tables from hOCR/TSV output
 //! - **hOCR to Markdown**: Convert hOCR format to clean Markdown
 //! - **Batch processing**: Process multiple images efficiently
@@ -38,11 +39,18 @@
 //! kreuzberg = { version = "4.0", features = ["ocr"] }
 //! ```
 mod backends;
+pub mod blank_page;
 pub mod cache;
+pub mod duplicate_page;
+pub(crate) mod engine_pool;
 pub mod error;
 pub mod hocr;
 pub mod language_registry;
+#[cfg(feature = "onnx-ocr")]
+pub mod onnx;
+pub mod orientation;
 pub mod processor;
+pub(crate) mod scheduler;
 pub mod table;
 pub mod tesseract_backend;
 pub mod types;
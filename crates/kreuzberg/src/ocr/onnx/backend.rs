@@ -0,0 +1,477 @@
+//! `OcrBackend` implementation on top of ONNX Runtime sessions.
+
+use super::config::{HandwritingModelConfig, OnnxExecutionProvider, OnnxOcrConfig};
+use super::detection::{self, BoxParams, TextBox};
+use super::handwriting::{self, Vocabulary};
+use super::recognition;
+use crate::core::config::{HandwritingMode, OcrConfig};
+use crate::core::thread_pool::run_blocking;
+use crate::plugins::{OcrBackend, OcrBackendType, Plugin};
+use crate::types::{ExtractionResult, Metadata};
+use crate::{KreuzbergError, Result};
+use ahash::AHashMap;
+use async_trait::async_trait;
+use image::RgbImage;
+use ndarray::{Array2, Axis, Ix3};
+use ort::execution_providers::{CPU, CUDA, DirectML, ExecutionProviderDispatch};
+use ort::session::Session;
+use ort::value::Tensor;
+use std::borrow::Cow;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+const BACKEND_NAME: &str = "onnx-ocr";
+
+/// GPU-capable OCR backend running detection and recognition models with ONNX Runtime.
+///
+/// Sessions are created once and shared; inference runs on the blocking thread pool.
+///
+/// # Thread Safety
+///
+/// Each session is guarded by a mutex, so concurrent requests are serialized per
+/// model. Register several backends under different names to run models in parallel.
+pub struct OnnxOcrBackend {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    config: OnnxOcrConfig,
+    detector: Mutex<Session>,
+    recognizer: Mutex<Session>,
+    dictionary: Vec<String>,
+    handwriting: Option<HandwritingModel>,
+}
+
+/// Sessions and vocabulary of the handwriting recognition model.
+struct HandwritingModel {
+    config: HandwritingModelConfig,
+    encoder: Mutex<Session>,
+    decoder: Mutex<Session>,
+    vocabulary: Vocabulary,
+}
+
+/// Text recognized in a single detected region.
+struct RecognizedRegion {
+    text: String,
+    confidence: f32,
+    handwritten: bool,
+}
+
+impl OnnxOcrBackend {
+    /// Load the models and create the backend.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Ocr` if ONNX Runtime cannot be loaded, a model, the
+    /// dictionary or the handwriting vocabulary cannot be read, or an explicitly
+    /// requested execution provider is unavailable.
+    pub fn new(config: OnnxOcrConfig) -> Result<Self> {
+        let dictionary = recognition::load_dictionary(&config.dictionary)?;
+        let detector = create_session(&config, &config.detection_model)?;
+        let recognizer = create_session(&config, &config.recognition_model)?;
+        let handwriting = match &config.handwriting {
+            Some(handwriting) => Some(HandwritingModel {
+                vocabulary: Vocabulary::load(&handwriting.vocabulary)?,
+                encoder: Mutex::new(create_session(&config, &handwriting.encoder_model)?),
+                decoder: Mutex::new(create_session(&config, &handwriting.decoder_model)?),
+                config: handwriting.clone(),
+            }),
+            None => None,
+        };
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                config,
+                detector: Mutex::new(detector),
+                recognizer: Mutex::new(recognizer),
+                dictionary,
+                handwriting,
+            }),
+        })
+    }
+
+    /// The configuration the backend was created with.
+    pub fn config(&self) -> &OnnxOcrConfig {
+        &self.inner.config
+    }
+}
+
+fn ort_error(context: &str, error: ort::Error) -> KreuzbergError {
+    KreuzbergError::Ocr {
+        message: format!("{}: {}", context, error),
+        source: Some(Box::new(error)),
+    }
+}
+
+fn execution_providers(config: &OnnxOcrConfig) -> Vec<ExecutionProviderDispatch> {
+    match config.execution_provider {
+        OnnxExecutionProvider::Auto => vec![
+            CUDA::default().with_device_id(config.device_id).build(),
+            DirectML::default().with_device_id(config.device_id).build(),
+            CPU::default().build(),
+        ],
+        OnnxExecutionProvider::Cpu => vec![CPU::default().build()],
+        OnnxExecutionProvider::Cuda => vec![
+            CUDA::default()
+                .with_device_id(config.device_id)
+                .build()
+                .error_on_failure(),
+        ],
+        OnnxExecutionProvider::DirectMl => vec![
+            DirectML::default()
+                .with_device_id(config.device_id)
+                .build()
+                .error_on_failure(),
+        ],
+    }
+}
+
+fn create_session(config: &OnnxOcrConfig, model: &Path) -> Result<Session> {
+    if !model.is_file() {
+        return Err(KreuzbergError::Ocr {
+            message: format!("ONNX OCR model not found: {}", model.display()),
+            source: None,
+        });
+    }
+
+    let mut builder = Session::builder()
+        .map_err(|e| ort_error("Failed to initialize ONNX Runtime", e))?
+        .with_execution_providers(execution_providers(config))
+        .map_err(|e| {
+            ort_error(
+                &format!(
+                    "Execution provider '{}' unavailable",
+                    config.execution_provider.as_str()
+                ),
+                e,
+            )
+        })?;
+
+    if let Some(threads) = config.intra_threads {
+        builder = builder
+            .with_intra_threads(threads)
+            .map_err(|e| ort_error("Failed to configure ONNX Runtime threads", e))?;
+    }
+
+    builder
+        .commit_from_file(model)
+        .map_err(|e| ort_error(&format!("Failed to load ONNX model '{}'", model.display()), e))
+}
+
+impl Inner {
+    fn recognize_image(&self, image_bytes: &[u8], mode: HandwritingMode) -> Result<Vec<Vec<RecognizedRegion>>> {
+        let image = crate::extraction::image::decode_image(image_bytes)
+            .map_err(|e| KreuzbergError::Ocr {
+                message: format!("Failed to decode image for OCR: {}", e),
+                source: Some(Box::new(e)),
+            })?
+            .to_rgb8();
+
+        let lines = detection::group_into_lines(self.detect(&image)?);
+
+        let mut recognized = Vec::with_capacity(lines.len());
+        for line in lines {
+            let mut regions = Vec::with_capacity(line.len());
+            for text_box in &line {
+                if let Some(region) = self.read_region(&image, text_box, mode)? {
+                    regions.push(region);
+                }
+            }
+            if !regions.is_empty() {
+                recognized.push(regions);
+            }
+        }
+
+        Ok(recognized)
+    }
+
+    /// Run the detection model; returned boxes are in original image coordinates.
+    fn detect(&self, image: &RgbImage) -> Result<Vec<TextBox>> {
+        let (width, height) = image.dimensions();
+        let (input_width, input_height) = detection::detection_input_size(width, height, self.config.max_side_len);
+        let input = Tensor::from_array(detection::detection_input(image, input_width, input_height))
+            .map_err(|e| ort_error("Failed to create detection input", e))?;
+
+        let mut session = self.detector.lock().expect("ONNX detection session lock poisoned");
+        let outputs = session
+            .run(ort::inputs![input])
+            .map_err(|e| ort_error("Text detection failed", e))?;
+        let probabilities = outputs[0]
+            .try_extract_array::<f32>()
+            .map_err(|e| ort_error("Unexpected detection output", e))?;
+
+        let shape = probabilities.shape();
+        let (map_height, map_width) = (shape[shape.len() - 2], shape[shape.len() - 1]);
+        let flat: Vec<f32> = probabilities.iter().take(map_width * map_height).copied().collect();
+
+        let params = BoxParams {
+            detection_threshold: self.config.detection_threshold,
+            box_threshold: self.config.box_threshold,
+            unclip_ratio: self.config.unclip_ratio,
+        };
+        let scale_x = width as f32 / map_width as f32;
+        let scale_y = height as f32 / map_height as f32;
+
+        Ok(detection::extract_boxes(&flat, map_width, map_height, &params)
+            .into_iter()
+            .map(|text_box| text_box.scaled(scale_x, scale_y))
+            .collect())
+    }
+
+    /// Read a region with the printed-text model, the handwriting model, or both.
+    ///
+    /// In `auto` mode the handwriting model reads regions the printed-text model is
+    /// unsure about, and the more confident reading is kept.
+    fn read_region(
+        &self,
+        image: &RgbImage,
+        text_box: &TextBox,
+        mode: HandwritingMode,
+    ) -> Result<Option<RecognizedRegion>> {
+        let crop = crop_region(image, text_box);
+        let Some(model) = self.handwriting.as_ref().filter(|_| mode != HandwritingMode::Off) else {
+            return self.recognize_region(&crop);
+        };
+
+        if mode == HandwritingMode::On {
+            return model.recognize(&crop);
+        }
+
+        let printed = self.recognize_region(&crop)?;
+        if printed
+            .as_ref()
+            .is_some_and(|region| region.confidence >= model.config.auto_threshold)
+        {
+            return Ok(printed);
+        }
+
+        let handwritten = model.recognize(&crop)?;
+        Ok(match (printed, handwritten) {
+            (Some(printed), Some(handwritten)) if printed.confidence >= handwritten.confidence => Some(printed),
+            (printed, handwritten) => handwritten.or(printed),
+        })
+    }
+
+    fn recognize_region(&self, crop: &RgbImage) -> Result<Option<RecognizedRegion>> {
+        let input = Tensor::from_array(recognition::recognition_input(crop, self.config.recognition_height))
+            .map_err(|e| ort_error("Failed to create recognition input", e))?;
+
+        let mut session = self.recognizer.lock().expect("ONNX recognition session lock poisoned");
+        let outputs = session
+            .run(ort::inputs![input])
+            .map_err(|e| ort_error("Text recognition failed", e))?;
+        let probabilities = outputs[0]
+            .try_extract_array::<f32>()
+            .map_err(|e| ort_error("Unexpected recognition output", e))?
+            .into_dimensionality::<Ix3>()
+            .map_err(|e| KreuzbergError::Ocr {
+                message: format!("Unexpected recognition output shape: {}", e),
+                source: None,
+            })?;
+
+        let (text, confidence) = recognition::ctc_greedy_decode(probabilities.index_axis(Axis(0), 0), &self.dictionary);
+
+        Ok((!text.is_empty()).then_some(RecognizedRegion {
+            text,
+            confidence,
+            handwritten: false,
+        }))
+    }
+}
+
+impl HandwritingModel {
+    /// Encode the region once, then generate its tokens greedily until the end token.
+    fn recognize(&self, crop: &RgbImage) -> Result<Option<RecognizedRegion>> {
+        let input = Tensor::from_array(handwriting::handwriting_input(crop, self.config.image_size))
+            .map_err(|e| ort_error("Failed to create handwriting encoder input", e))?;
+
+        let hidden_states = {
+            let mut encoder = self.encoder.lock().expect("ONNX handwriting encoder lock poisoned");
+            let outputs = encoder
+                .run(ort::inputs![input])
+                .map_err(|e| ort_error("Handwriting encoding failed", e))?;
+            outputs[0]
+                .try_extract_array::<f32>()
+                .map_err(|e| ort_error("Unexpected handwriting encoder output", e))?
+                .to_owned()
+        };
+
+        let mut decoder = self.decoder.lock().expect("ONNX handwriting decoder lock poisoned");
+        let mut tokens = vec![self.config.decoder_start_token_id];
+        let mut probability_sum = 0.0f32;
+
+        for _ in 0..self.config.max_tokens {
+            let input_ids =
+                Array2::from_shape_vec((1, tokens.len()), tokens.clone()).map_err(|e| KreuzbergError::Ocr {
+                    message: format!("Failed to create handwriting decoder input: {}", e),
+                    source: None,
+                })?;
+            let inputs = ort::inputs! {
+                "input_ids" => Tensor::from_array(input_ids)
+                    .map_err(|e| ort_error("Failed to create handwriting decoder input", e))?,
+                "encoder_hidden_states" => Tensor::from_array(hidden_states.clone())
+                    .map_err(|e| ort_error("Failed to create handwriting decoder input", e))?,
+            };
+            let outputs = decoder
+                .run(inputs)
+                .map_err(|e| ort_error("Handwriting decoding failed", e))?;
+            let logits = outputs[0]
+                .try_extract_array::<f32>()
+                .map_err(|e| ort_error("Unexpected handwriting decoder output", e))?
+                .into_dimensionality::<Ix3>()
+                .map_err(|e| KreuzbergError::Ocr {
+                    message: format!("Unexpected handwriting decoder output shape: {}", e),
+                    source: None,
+                })?;
+
+            let last = logits.index_axis(Axis(0), 0);
+            let (token, probability) = handwriting::next_token(last.index_axis(Axis(0), last.nrows() - 1));
+            if token == self.config.eos_token_id {
+                break;
+            }
+            tokens.push(token);
+            probability_sum += probability;
+        }
+
+        let generated = &tokens[1..];
+        let text = self.vocabulary.decode(generated);
+        let confidence = if generated.is_empty() {
+            0.0
+        } else {
+            probability_sum / generated.len() as f32
+        };
+
+        Ok((!text.is_empty()).then_some(RecognizedRegion {
+            text,
+            confidence,
+            handwritten: true,
+        }))
+    }
+}
+
+fn crop_region(image: &RgbImage, text_box: &TextBox) -> RgbImage {
+    let (width, height) = image.dimensions();
+    let left = (text_box.left.floor().max(0.0) as u32).min(width.saturating_sub(1));
+    let top = (text_box.top.floor().max(0.0) as u32).min(height.saturating_sub(1));
+    let right = (text_box.right.ceil() as u32).clamp(left + 1, width);
+    let bottom = (text_box.bottom.ceil() as u32).clamp(top + 1, height);
+
+    image::imageops::crop_imm(image, left, top, right - left, bottom - top).to_image()
+}
+
+impl Plugin for OnnxOcrBackend {
+    fn name(&self) -> &str {
+        BACKEND_NAME
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OcrBackend for OnnxOcrBackend {
+    async fn process_image(&self, image_bytes: &[u8], config: &OcrConfig) -> Result<ExtractionResult> {
+        let inner = Arc::clone(&self.inner);
+        let image_bytes = image_bytes.to_vec();
+        let mode = config.handwriting;
+        if mode == HandwritingMode::On && inner.handwriting.is_none() {
+            return Err(KreuzbergError::validation(
+                "Handwriting recognition requires a handwriting model in the ONNX OCR configuration",
+            ));
+        }
+
+        let lines = run_blocking(config.thread_pool.as_ref(), move || {
+            inner.recognize_image(&image_bytes, mode)
+        })
+        .await??;
+
+        let region_count: usize = lines.iter().map(Vec::len).sum();
+        let mean_confidence = if region_count == 0 {
+            0.0
+        } else {
+            lines.iter().flatten().map(|region| region.confidence).sum::<f32>() / region_count as f32
+        };
+
+        let content = lines
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|region| region.text.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        let mut additional = AHashMap::new();
+        additional.insert(Cow::Borrowed("ocr_backend"), serde_json::json!(BACKEND_NAME));
+        additional.insert(
+            Cow::Borrowed("execution_provider"),
+            serde_json::json!(self.inner.config.execution_provider.as_str()),
+        );
+        additional.insert(Cow::Borrowed("text_regions"), serde_json::json!(region_count));
+        if mode != HandwritingMode::Off {
+            let handwritten = lines.iter().flatten().filter(|region| region.handwritten).count();
+            additional.insert(Cow::Borrowed("handwritten_regions"), serde_json::json!(handwritten));
+        }
+        additional.insert(Cow::Borrowed("mean_confidence"), serde_json::json!(mean_confidence));
+
+        let metadata = Metadata {
+            format: Some(crate::types::FormatMetadata::Ocr(crate::types::OcrMetadata {
+                language: config.language.clone(),
+                psm: 0,
+                output_format: "text".to_string(),
+                table_count: 0,
+                table_rows: None,
+                table_cols: None,
+            })),
+            additional,
+            ..Default::default()
+        };
+
+        Ok(ExtractionResult {
+            content,
+            mime_type: "text/plain".into(),
+            metadata,
+            pages: None,
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            elements: None,
+            djot_content: None,
+            scripts: None,
+        })
+    }
+
+    async fn process_file(&self, path: &Path, config: &OcrConfig) -> Result<ExtractionResult> {
+        let bytes = tokio::fs::read(path).await?;
+        self.process_image(&bytes, config).await
+    }
+
+    /// Language support is determined by the loaded recognition model and dictionary.
+    fn supports_language(&self, _lang: &str) -> bool {
+        true
+    }
+
+    fn backend_type(&self) -> OcrBackendType {
+        OcrBackendType::Onnx
+    }
+
+    fn supports_handwriting(&self) -> bool {
+        self.inner.handwriting.is_some()
+    }
+
+    fn required_features(&self) -> &[&str] {
+        &["onnx-ocr"]
+    }
+}
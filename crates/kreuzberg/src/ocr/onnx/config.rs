@@ -0,0 +1,372 @@
+//! Configuration for the ONNX Runtime OCR backend.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// File name of the text detection model inside a model directory.
+pub const DETECTION_MODEL_FILE: &str = "det.onnx";
+/// File name of the text recognition model inside a model directory.
+pub const RECOGNITION_MODEL_FILE: &str = "rec.onnx";
+/// File name of the recognition character dictionary inside a model directory.
+pub const DICTIONARY_FILE: &str = "dict.txt";
+
+/// File name of the handwriting encoder model inside a handwriting model directory.
+pub const HANDWRITING_ENCODER_FILE: &str = "encoder_model.onnx";
+/// File name of the handwriting decoder model inside a handwriting model directory.
+pub const HANDWRITING_DECODER_FILE: &str = "decoder_model.onnx";
+/// File name of the handwriting tokenizer vocabulary inside a handwriting model directory.
+pub const HANDWRITING_VOCABULARY_FILE: &str = "vocab.json";
+
+/// Environment variable pointing to a model directory; enables the backend on startup.
+pub const MODEL_DIR_ENV: &str = "KREUZBERG_ONNX_OCR_MODEL_DIR";
+/// Environment variable selecting the execution provider (`auto`, `cpu`, `cuda`, `directml`).
+pub const EXECUTION_PROVIDER_ENV: &str = "KREUZBERG_ONNX_OCR_PROVIDER";
+/// Environment variable pointing to a handwriting model directory.
+pub const HANDWRITING_MODEL_DIR_ENV: &str = "KREUZBERG_ONNX_OCR_HANDWRITING_MODEL_DIR";
+
+/// Hardware the ONNX Runtime sessions run on.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OnnxExecutionProvider {
+    /// Use CUDA or DirectML when available, otherwise fall back to the CPU
+    #[default]
+    Auto,
+    /// CPU only
+    Cpu,
+    /// NVIDIA GPUs via CUDA (fails if CUDA is unavailable)
+    Cuda,
+    /// DirectX 12 GPUs on Windows via DirectML (fails if DirectML is unavailable)
+    #[serde(rename = "directml")]
+    DirectMl,
+}
+
+impl OnnxExecutionProvider {
+    /// Parse a provider name as used in configuration files and environment variables.
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.trim().to_lowercase().as_str() {
+            "auto" => Some(Self::Auto),
+            "cpu" => Some(Self::Cpu),
+            "cuda" | "gpu" => Some(Self::Cuda),
+            "directml" | "dml" => Some(Self::DirectMl),
+            _ => None,
+        }
+    }
+
+    /// Lowercase name of the provider.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Cpu => "cpu",
+            Self::Cuda => "cuda",
+            Self::DirectMl => "directml",
+        }
+    }
+}
+
+/// Configuration for [`OnnxOcrBackend`](super::OnnxOcrBackend).
+///
+/// The backend runs a PaddleOCR-style pipeline: a DB text detection model finds
+/// text regions, and a CTC recognition model (CRNN/SVTR) reads each region. Models
+/// exported from PaddleOCR (PP-OCRv3/v4) to ONNX work out of the box.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::ocr::onnx::{OnnxExecutionProvider, OnnxOcrConfig};
+///
+/// let config = OnnxOcrConfig::from_model_dir("/models/ppocr-v4")
+///     .with_execution_provider(OnnxExecutionProvider::Cuda);
+/// assert!(config.detection_model.ends_with("det.onnx"));
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OnnxOcrConfig {
+    /// Path to the text detection model
+    pub detection_model: PathBuf,
+
+    /// Path to the text recognition model
+    pub recognition_model: PathBuf,
+
+    /// Path to the recognition dictionary (one character per line)
+    pub dictionary: PathBuf,
+
+    /// Execution provider used for both models
+    #[serde(default)]
+    pub execution_provider: OnnxExecutionProvider,
+
+    /// GPU device index for CUDA and DirectML
+    #[serde(default)]
+    pub device_id: i32,
+
+    /// Images are scaled down so that their longer side does not exceed this before detection
+    #[serde(default = "default_max_side_len")]
+    pub max_side_len: u32,
+
+    /// Probability above which a pixel of the detection map counts as text
+    #[serde(default = "default_detection_threshold")]
+    pub detection_threshold: f32,
+
+    /// Minimum mean probability for a detected region to be kept
+    #[serde(default = "default_box_threshold")]
+    pub box_threshold: f32,
+
+    /// How far detected regions are expanded before recognition
+    #[serde(default = "default_unclip_ratio")]
+    pub unclip_ratio: f32,
+
+    /// Input height of the recognition model
+    #[serde(default = "default_recognition_height")]
+    pub recognition_height: u32,
+
+    /// Number of threads used by each session (None = ONNX Runtime default)
+    #[serde(default)]
+    pub intra_threads: Option<usize>,
+
+    /// Handwriting recognition model (None = no handwriting support)
+    #[serde(default)]
+    pub handwriting: Option<HandwritingModelConfig>,
+}
+
+/// A TrOCR-style handwriting recognition model exported to ONNX.
+///
+/// The encoder reads a text line image and the decoder generates its tokens one at
+/// a time. Exports of `microsoft/trocr-*-handwritten` made with Hugging Face Optimum
+/// (`encoder_model.onnx`, `decoder_model.onnx` without past key values, and the
+/// tokenizer's `vocab.json`) work out of the box.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HandwritingModelConfig {
+    /// Path to the image encoder model
+    pub encoder_model: PathBuf,
+
+    /// Path to the text decoder model
+    pub decoder_model: PathBuf,
+
+    /// Path to the tokenizer vocabulary (`vocab.json` mapping tokens to ids)
+    pub vocabulary: PathBuf,
+
+    /// Side length of the square encoder input
+    #[serde(default = "default_handwriting_image_size")]
+    pub image_size: u32,
+
+    /// Maximum number of tokens generated per text region
+    #[serde(default = "default_handwriting_max_tokens")]
+    pub max_tokens: usize,
+
+    /// Token the decoder starts generating from
+    #[serde(default = "default_decoder_start_token_id")]
+    pub decoder_start_token_id: i64,
+
+    /// Token ending the generated text
+    #[serde(default = "default_eos_token_id")]
+    pub eos_token_id: i64,
+
+    /// In `auto` mode, regions read with a lower confidence by the printed-text model
+    /// are read again with the handwriting model
+    #[serde(default = "default_auto_threshold")]
+    pub auto_threshold: f32,
+}
+
+fn default_handwriting_image_size() -> u32 {
+    384
+}
+
+fn default_handwriting_max_tokens() -> usize {
+    64
+}
+
+fn default_decoder_start_token_id() -> i64 {
+    2
+}
+
+fn default_eos_token_id() -> i64 {
+    2
+}
+
+fn default_auto_threshold() -> f32 {
+    0.8
+}
+
+impl HandwritingModelConfig {
+    /// Create a configuration for the given model files with default settings.
+    pub fn new(
+        encoder_model: impl Into<PathBuf>,
+        decoder_model: impl Into<PathBuf>,
+        vocabulary: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            encoder_model: encoder_model.into(),
+            decoder_model: decoder_model.into(),
+            vocabulary: vocabulary.into(),
+            image_size: default_handwriting_image_size(),
+            max_tokens: default_handwriting_max_tokens(),
+            decoder_start_token_id: default_decoder_start_token_id(),
+            eos_token_id: default_eos_token_id(),
+            auto_threshold: default_auto_threshold(),
+        }
+    }
+
+    /// Create a configuration for a directory containing `encoder_model.onnx`,
+    /// `decoder_model.onnx` and `vocab.json`.
+    pub fn from_model_dir(dir: impl AsRef<Path>) -> Self {
+        let dir = dir.as_ref();
+        Self::new(
+            dir.join(HANDWRITING_ENCODER_FILE),
+            dir.join(HANDWRITING_DECODER_FILE),
+            dir.join(HANDWRITING_VOCABULARY_FILE),
+        )
+    }
+}
+
+fn default_max_side_len() -> u32 {
+    960
+}
+
+fn default_detection_threshold() -> f32 {
+    0.3
+}
+
+fn default_box_threshold() -> f32 {
+    0.6
+}
+
+fn default_unclip_ratio() -> f32 {
+    1.5
+}
+
+fn default_recognition_height() -> u32 {
+    48
+}
+
+impl OnnxOcrConfig {
+    /// Create a configuration for the given model files with default settings.
+    pub fn new(
+        detection_model: impl Into<PathBuf>,
+        recognition_model: impl Into<PathBuf>,
+        dictionary: impl Into<PathBuf>,
+    ) -> Self {
+        Self {
+            detection_model: detection_model.into(),
+            recognition_model: recognition_model.into(),
+            dictionary: dictionary.into(),
+            execution_provider: OnnxExecutionProvider::default(),
+            device_id: 0,
+            max_side_len: default_max_side_len(),
+            detection_threshold: default_detection_threshold(),
+            box_threshold: default_box_threshold(),
+            unclip_ratio: default_unclip_ratio(),
+            recognition_height: default_recognition_height(),
+            intra_threads: None,
+            handwriting: None,
+        }
+    }
+
+    /// Create a configuration for a directory containing `det.onnx`, `rec.onnx` and `dict.txt`.
+    pub fn from_model_dir(dir: impl AsRef<Path>) -> Self {
+        let dir = dir.as_ref();
+        Self::new(
+            dir.join(DETECTION_MODEL_FILE),
+            dir.join(RECOGNITION_MODEL_FILE),
+            dir.join(DICTIONARY_FILE),
+        )
+    }
+
+    /// Build a configuration from `KREUZBERG_ONNX_OCR_MODEL_DIR`, `KREUZBERG_ONNX_OCR_PROVIDER`
+    /// and `KREUZBERG_ONNX_OCR_HANDWRITING_MODEL_DIR`.
+    ///
+    /// Returns `None` when no model directory is configured. Unknown provider names
+    /// fall back to [`OnnxExecutionProvider::Auto`].
+    pub fn from_env() -> Option<Self> {
+        let dir = std::env::var_os(MODEL_DIR_ENV)?;
+        let mut config = Self::from_model_dir(dir);
+
+        if let Ok(provider) = std::env::var(EXECUTION_PROVIDER_ENV) {
+            match OnnxExecutionProvider::parse(&provider) {
+                Some(provider) => config.execution_provider = provider,
+                None => tracing::warn!("Unknown {} value '{}', using 'auto'", EXECUTION_PROVIDER_ENV, provider),
+            }
+        }
+
+        if let Some(handwriting_dir) = std::env::var_os(HANDWRITING_MODEL_DIR_ENV) {
+            config.handwriting = Some(HandwritingModelConfig::from_model_dir(handwriting_dir));
+        }
+
+        Some(config)
+    }
+
+    /// Set the execution provider.
+    pub fn with_execution_provider(mut self, execution_provider: OnnxExecutionProvider) -> Self {
+        self.execution_provider = execution_provider;
+        self
+    }
+
+    /// Set the GPU device index.
+    pub fn with_device_id(mut self, device_id: i32) -> Self {
+        self.device_id = device_id;
+        self
+    }
+
+    /// Set the handwriting recognition model.
+    pub fn with_handwriting(mut self, handwriting: HandwritingModelConfig) -> Self {
+        self.handwriting = Some(handwriting);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_model_dir_uses_default_file_names() {
+        let config = OnnxOcrConfig::from_model_dir("/models");
+        assert_eq!(config.detection_model, PathBuf::from("/models/det.onnx"));
+        assert_eq!(config.recognition_model, PathBuf::from("/models/rec.onnx"));
+        assert_eq!(config.dictionary, PathBuf::from("/models/dict.txt"));
+        assert_eq!(config.execution_provider, OnnxExecutionProvider::Auto);
+    }
+
+    #[test]
+    fn test_execution_provider_parse() {
+        assert_eq!(OnnxExecutionProvider::parse("CUDA"), Some(OnnxExecutionProvider::Cuda));
+        assert_eq!(
+            OnnxExecutionProvider::parse("directml"),
+            Some(OnnxExecutionProvider::DirectMl)
+        );
+        assert_eq!(OnnxExecutionProvider::parse("tpu"), None);
+    }
+
+    #[test]
+    fn test_deserialize_applies_defaults() {
+        let config: OnnxOcrConfig = serde_json::from_str(
+            r#"{"detection_model": "d.onnx", "recognition_model": "r.onnx", "dictionary": "k.txt", "execution_provider": "directml"}"#,
+        )
+        .unwrap();
+        assert_eq!(config.execution_provider, OnnxExecutionProvider::DirectMl);
+        assert_eq!(config.max_side_len, 960);
+        assert_eq!(config.recognition_height, 48);
+        assert!(config.handwriting.is_none());
+    }
+
+    #[test]
+    fn test_handwriting_model_dir_and_defaults() {
+        let config = OnnxOcrConfig::from_model_dir("/models")
+            .with_handwriting(HandwritingModelConfig::from_model_dir("/models/trocr"));
+        let handwriting = config.handwriting.unwrap();
+        assert_eq!(
+            handwriting.encoder_model,
+            PathBuf::from("/models/trocr/encoder_model.onnx")
+        );
+        assert_eq!(
+            handwriting.decoder_model,
+            PathBuf::from("/models/trocr/decoder_model.onnx")
+        );
+        assert_eq!(handwriting.vocabulary, PathBuf::from("/models/trocr/vocab.json"));
+
+        let handwriting: HandwritingModelConfig = serde_json::from_str(
+            r#"{"encoder_model": "e.onnx", "decoder_model": "d.onnx", "vocabulary": "v.json", "max_tokens": 32}"#,
+        )
+        .unwrap();
+        assert_eq!(handwriting.max_tokens, 32);
+        assert_eq!(handwriting.image_size, 384);
+        assert_eq!(handwriting.eos_token_id, 2);
+    }
+}
@@ -0,0 +1,247 @@
+//! Text detection: preprocessing and DB (differentiable binarization) post-processing.
+
+use image::RgbImage;
+use image::imageops::{self, FilterType};
+use ndarray::Array4;
+
+/// ImageNet normalization used by PaddleOCR detection models.
+const MEAN: [f32; 3] = [0.485, 0.456, 0.406];
+const STD: [f32; 3] = [0.229, 0.224, 0.225];
+
+/// Detection input sides must be multiples of this.
+const SIZE_MULTIPLE: u32 = 32;
+
+/// Regions whose shorter side is below this (in detection map pixels) are dropped.
+const MIN_REGION_SIDE: usize = 3;
+
+/// An axis-aligned text region.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(super) struct TextBox {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+    pub score: f32,
+}
+
+impl TextBox {
+    fn center_y(&self) -> f32 {
+        (self.top + self.bottom) / 2.0
+    }
+
+    /// Scale the box by independent horizontal and vertical factors.
+    pub fn scaled(&self, scale_x: f32, scale_y: f32) -> Self {
+        Self {
+            left: self.left * scale_x,
+            top: self.top * scale_y,
+            right: self.right * scale_x,
+            bottom: self.bottom * scale_y,
+            score: self.score,
+        }
+    }
+}
+
+/// Thresholds for turning a probability map into text regions.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct BoxParams {
+    pub detection_threshold: f32,
+    pub box_threshold: f32,
+    pub unclip_ratio: f32,
+}
+
+/// Compute the detection input size for an image.
+///
+/// The longer side is limited to `max_side_len` and both sides are rounded to a
+/// multiple of 32, as required by the DB network.
+pub(super) fn detection_input_size(width: u32, height: u32, max_side_len: u32) -> (u32, u32) {
+    let longer = width.max(height).max(1) as f32;
+    let ratio = if longer > max_side_len as f32 {
+        max_side_len as f32 / longer
+    } else {
+        1.0
+    };
+
+    let round = |side: u32| {
+        let scaled = (side as f32 * ratio).round() as u32;
+        (scaled.div_ceil(SIZE_MULTIPLE) * SIZE_MULTIPLE).max(SIZE_MULTIPLE)
+    };
+
+    (round(width), round(height))
+}
+
+/// Resize and normalize an image into an NCHW detection tensor.
+pub(super) fn detection_input(image: &RgbImage, width: u32, height: u32) -> Array4<f32> {
+    let resized = imageops::resize(image, width, height, FilterType::Triangle);
+    let mut input = Array4::<f32>::zeros((1, 3, height as usize, width as usize));
+
+    for (x, y, pixel) in resized.enumerate_pixels() {
+        for channel in 0..3 {
+            let value = pixel[channel] as f32 / 255.0;
+            input[[0, channel, y as usize, x as usize]] = (value - MEAN[channel]) / STD[channel];
+        }
+    }
+
+    input
+}
+
+/// Extract text regions from a DB probability map.
+///
+/// Pixels above `detection_threshold` are grouped into 4-connected components. Each
+/// component's bounding box is scored by the mean probability inside it, filtered by
+/// `box_threshold`, and expanded by `unclip_ratio` (the DB network predicts shrunk
+/// regions). Coordinates are in probability map pixels.
+pub(super) fn extract_boxes(probabilities: &[f32], width: usize, height: usize, params: &BoxParams) -> Vec<TextBox> {
+    if probabilities.len() < width * height {
+        return Vec::new();
+    }
+
+    let mut visited = vec![false; width * height];
+    let mut boxes = Vec::new();
+    let mut stack = Vec::new();
+
+    for start in 0..width * height {
+        if visited[start] || probabilities[start] <= params.detection_threshold {
+            continue;
+        }
+
+        let (mut min_x, mut min_y, mut max_x, mut max_y) = (usize::MAX, usize::MAX, 0, 0);
+        visited[start] = true;
+        stack.push(start);
+
+        while let Some(index) = stack.pop() {
+            let (x, y) = (index % width, index / width);
+            min_x = min_x.min(x);
+            min_y = min_y.min(y);
+            max_x = max_x.max(x);
+            max_y = max_y.max(y);
+
+            let neighbours = [
+                (x > 0).then(|| index - 1),
+                (x + 1 < width).then(|| index + 1),
+                (y > 0).then(|| index - width),
+                (y + 1 < height).then(|| index + width),
+            ];
+            for neighbour in neighbours.into_iter().flatten() {
+                if !visited[neighbour] && probabilities[neighbour] > params.detection_threshold {
+                    visited[neighbour] = true;
+                    stack.push(neighbour);
+                }
+            }
+        }
+
+        let box_width = max_x - min_x + 1;
+        let box_height = max_y - min_y + 1;
+        if box_width.min(box_height) < MIN_REGION_SIDE {
+            continue;
+        }
+
+        let mut sum = 0.0f32;
+        for y in min_y..=max_y {
+            sum += probabilities[y * width + min_x..=y * width + max_x].iter().sum::<f32>();
+        }
+        let score = sum / (box_width * box_height) as f32;
+        if score < params.box_threshold {
+            continue;
+        }
+
+        let (w, h) = (box_width as f32, box_height as f32);
+        let distance = w * h * params.unclip_ratio / (2.0 * (w + h));
+
+        boxes.push(TextBox {
+            left: (min_x as f32 - distance).max(0.0),
+            top: (min_y as f32 - distance).max(0.0),
+            right: (max_x as f32 + 1.0 + distance).min(width as f32),
+            bottom: (max_y as f32 + 1.0 + distance).min(height as f32),
+            score,
+        });
+    }
+
+    boxes
+}
+
+/// Group boxes into lines (top to bottom) with boxes ordered left to right.
+///
+/// A box joins the current line when its vertical center falls within the first
+/// box of that line.
+pub(super) fn group_into_lines(mut boxes: Vec<TextBox>) -> Vec<Vec<TextBox>> {
+    boxes.sort_by(|a, b| a.center_y().total_cmp(&b.center_y()));
+
+    let mut lines: Vec<Vec<TextBox>> = Vec::new();
+    for text_box in boxes {
+        match lines.last_mut() {
+            Some(line) if text_box.center_y() >= line[0].top && text_box.center_y() <= line[0].bottom => {
+                line.push(text_box);
+            }
+            _ => lines.push(vec![text_box]),
+        }
+    }
+
+    for line in &mut lines {
+        line.sort_by(|a, b| a.left.total_cmp(&b.left));
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PARAMS: BoxParams = BoxParams {
+        detection_threshold: 0.3,
+        box_threshold: 0.6,
+        unclip_ratio: 1.5,
+    };
+
+    fn map_with_rects(width: usize, height: usize, rects: &[(usize, usize, usize, usize)]) -> Vec<f32> {
+        let mut map = vec![0.0; width * height];
+        for &(left, top, right, bottom) in rects {
+            for y in top..bottom {
+                for x in left..right {
+                    map[y * width + x] = 0.9;
+                }
+            }
+        }
+        map
+    }
+
+    #[test]
+    fn test_detection_input_size_limits_and_rounds() {
+        assert_eq!(detection_input_size(1920, 1080, 960), (960, 544));
+        assert_eq!(detection_input_size(100, 50, 960), (128, 64));
+        assert_eq!(detection_input_size(10, 10, 960), (32, 32));
+    }
+
+    #[test]
+    fn test_extract_boxes_finds_separate_regions() {
+        let map = map_with_rects(64, 32, &[(4, 4, 20, 10), (30, 4, 60, 10), (4, 20, 40, 28)]);
+        let boxes = extract_boxes(&map, 64, 32, &PARAMS);
+        assert_eq!(boxes.len(), 3);
+        assert!(boxes.iter().all(|b| b.score > 0.8));
+
+        // Regions are expanded beyond the thresholded pixels
+        assert!(boxes[0].left < 4.0 && boxes[0].right > 20.0);
+    }
+
+    #[test]
+    fn test_extract_boxes_drops_specks() {
+        let map = map_with_rects(32, 32, &[(5, 5, 7, 7)]);
+        assert!(extract_boxes(&map, 32, 32, &PARAMS).is_empty());
+    }
+
+    #[test]
+    fn test_group_into_lines_orders_reading_order() {
+        let make = |left, top| TextBox {
+            left,
+            top,
+            right: left + 10.0,
+            bottom: top + 10.0,
+            score: 1.0,
+        };
+        let lines = group_into_lines(vec![make(50.0, 31.0), make(40.0, 0.0), make(0.0, 2.0), make(0.0, 30.0)]);
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0][0].left, 0.0);
+        assert_eq!(lines[0][1].left, 40.0);
+        assert_eq!(lines[1][1].left, 50.0);
+    }
+}
@@ -0,0 +1,171 @@
+//! Handwriting recognition: TrOCR preprocessing, greedy token selection and detokenization.
+
+use crate::{KreuzbergError, Result};
+use image::RgbImage;
+use image::imageops::{self, FilterType};
+use ndarray::{Array4, ArrayView1};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Tokens that mark sequence structure rather than text.
+const SPECIAL_TOKENS: &[&str] = &["<s>", "</s>", "<pad>", "<unk>", "<mask>"];
+
+/// SentencePiece word boundary marker.
+const WORD_BOUNDARY: char = '\u{2581}';
+
+/// Tokenizer vocabulary of a handwriting model, indexed by token id.
+///
+/// Both byte-level BPE vocabularies (RoBERTa, used by the base and large TrOCR
+/// models) and SentencePiece vocabularies (used by the small models) are supported.
+pub(super) struct Vocabulary {
+    tokens: Vec<Option<String>>,
+    byte_level: bool,
+}
+
+impl Vocabulary {
+    /// Load a `vocab.json` mapping tokens to ids.
+    pub(super) fn load(path: &Path) -> Result<Self> {
+        let contents = std::fs::read_to_string(path).map_err(|e| KreuzbergError::Ocr {
+            message: format!("Failed to read handwriting vocabulary '{}': {}", path.display(), e),
+            source: Some(Box::new(e)),
+        })?;
+
+        Self::parse(&contents).map_err(|e| KreuzbergError::Ocr {
+            message: format!("Invalid handwriting vocabulary '{}': {}", path.display(), e),
+            source: Some(Box::new(e)),
+        })
+    }
+
+    fn parse(contents: &str) -> std::result::Result<Self, serde_json::Error> {
+        let ids: HashMap<String, usize> = serde_json::from_str(contents)?;
+
+        let mut tokens = vec![None; ids.values().max().map_or(0, |max| max + 1)];
+        let mut word_boundaries = false;
+        for (token, id) in ids {
+            word_boundaries |= token.starts_with(WORD_BOUNDARY);
+            tokens[id] = Some(token);
+        }
+
+        Ok(Self {
+            tokens,
+            byte_level: !word_boundaries,
+        })
+    }
+
+    /// Turn generated token ids into text, skipping special and unknown tokens.
+    pub(super) fn decode(&self, ids: &[i64]) -> String {
+        let pieces = ids
+            .iter()
+            .filter_map(|&id| usize::try_from(id).ok())
+            .filter_map(|id| self.tokens.get(id).and_then(Option::as_deref))
+            .filter(|token| !SPECIAL_TOKENS.contains(token));
+
+        let text = if self.byte_level {
+            let bytes: Vec<u8> = pieces.flat_map(str::chars).filter_map(byte_for_char).collect();
+            String::from_utf8_lossy(&bytes).into_owned()
+        } else {
+            pieces.collect::<String>().replace(WORD_BOUNDARY, " ")
+        };
+
+        text.trim().to_string()
+    }
+}
+
+/// Inverse of the GPT-2 byte-to-character table used by byte-level BPE tokenizers.
+///
+/// Printable Latin-1 bytes map to themselves; the remaining bytes were shifted to the
+/// characters from U+0100 on, in byte order.
+fn byte_for_char(c: char) -> Option<u8> {
+    let printable = |byte: u32| matches!(byte, 0x21..=0x7E | 0xA1..=0xAC | 0xAE..=0xFF);
+
+    let code = u32::from(c);
+    if printable(code) {
+        return u8::try_from(code).ok();
+    }
+
+    let shifted = code.checked_sub(0x100)?;
+    (0u32..=0xFF)
+        .filter(|&byte| !printable(byte))
+        .nth(shifted as usize)
+        .and_then(|byte| u8::try_from(byte).ok())
+}
+
+/// Resize and normalize a text region crop into the square NCHW encoder tensor.
+pub(super) fn handwriting_input(crop: &RgbImage, size: u32) -> Array4<f32> {
+    let resized = imageops::resize(crop, size, size, FilterType::Triangle);
+    let mut input = Array4::<f32>::zeros((1, 3, size as usize, size as usize));
+
+    for (x, y, pixel) in resized.enumerate_pixels() {
+        for channel in 0..3 {
+            let value = pixel[channel] as f32 / 255.0;
+            input[[0, channel, y as usize, x as usize]] = (value - 0.5) / 0.5;
+        }
+    }
+
+    input
+}
+
+/// Pick the most likely next token from the decoder logits of the last position.
+///
+/// Returns the token id and its softmax probability.
+pub(super) fn next_token(logits: ArrayView1<'_, f32>) -> (i64, f32) {
+    let (best, best_logit) =
+        logits.iter().copied().enumerate().fold(
+            (0, f32::MIN),
+            |best, (i, logit)| if logit > best.1 { (i, logit) } else { best },
+        );
+
+    let normalizer: f32 = logits.iter().map(|&logit| (logit - best_logit).exp()).sum();
+    let probability = if normalizer > 0.0 { 1.0 / normalizer } else { 0.0 };
+
+    (best as i64, probability)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array1;
+
+    #[test]
+    fn test_decode_byte_level_vocabulary() {
+        let vocabulary =
+            Vocabulary::parse(r#"{"<s>": 0, "<pad>": 1, "</s>": 2, "Dear": 3, "ĠSir": 4, ",": 5, "ĠcafÃ©": 6}"#)
+                .unwrap();
+        assert!(vocabulary.byte_level);
+        assert_eq!(vocabulary.decode(&[2, 3, 4, 5, 6, 2, 1]), "Dear Sir, café");
+        assert_eq!(vocabulary.decode(&[0, 42, -1]), "");
+    }
+
+    #[test]
+    fn test_decode_sentencepiece_vocabulary() {
+        let vocabulary = Vocabulary::parse(r#"{"<s>": 0, "</s>": 2, "▁Kind": 7, "▁re": 8, "gards": 9}"#).unwrap();
+        assert!(!vocabulary.byte_level);
+        assert_eq!(vocabulary.decode(&[2, 7, 8, 9, 2]), "Kind regards");
+    }
+
+    #[test]
+    fn test_byte_for_char_matches_gpt2_table() {
+        assert_eq!(byte_for_char('a'), Some(b'a'));
+        assert_eq!(byte_for_char('\u{0120}'), Some(b' '));
+        assert_eq!(byte_for_char('\u{010A}'), Some(b'\n'));
+        assert_eq!(byte_for_char('\u{0100}'), Some(0));
+        assert_eq!(byte_for_char('\u{0143}'), Some(0xAD));
+        assert_eq!(byte_for_char('\u{0144}'), None);
+    }
+
+    #[test]
+    fn test_next_token_returns_argmax_probability() {
+        let logits = Array1::from(vec![0.0, 2.0_f32.ln(), 0.0, 0.0]);
+        let (token, probability) = next_token(logits.view());
+        assert_eq!(token, 1);
+        assert!((probability - 0.4).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_handwriting_input_is_square_and_normalized() {
+        let crop = RgbImage::from_pixel(200, 30, image::Rgb([255, 255, 255]));
+        let input = handwriting_input(&crop, 384);
+        assert_eq!(input.shape(), &[1, 3, 384, 384]);
+        assert!((input[[0, 2, 383, 383]] - 1.0).abs() < 1e-6);
+    }
+}
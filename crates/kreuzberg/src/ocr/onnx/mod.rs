@@ -0,0 +1,49 @@
+//! ONNX Runtime OCR backend.
+//!
+//! Runs PaddleOCR-style text detection and recognition models through ONNX Runtime,
+//! optionally on the GPU (CUDA or DirectML). This is considerably faster than
+//! Tesseract on large scanned archives.
+//!
+//! The backend is registered as `"onnx-ocr"`. It is registered automatically when
+//! `KREUZBERG_ONNX_OCR_MODEL_DIR` points to a directory containing `det.onnx`,
+//! `rec.onnx` and `dict.txt`; otherwise register it manually:
+//!
+//! ```rust,no_run
+//! use kreuzberg::ocr::onnx::{OnnxExecutionProvider, OnnxOcrBackend, OnnxOcrConfig};
+//! use kreuzberg::plugins::register_ocr_backend;
+//! use std::sync::Arc;
+//!
+//! # fn example() -> kreuzberg::Result<()> {
+//! let config = OnnxOcrConfig::from_model_dir("/models/ppocr-v4")
+//!     .with_execution_provider(OnnxExecutionProvider::Cuda);
+//! register_ocr_backend(Arc::new(OnnxOcrBackend::new(config)?))?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Handwriting
+//!
+//! With a TrOCR-style handwriting model configured (see [`HandwritingModelConfig`],
+//! or set `KREUZBERG_ONNX_OCR_HANDWRITING_MODEL_DIR`), the backend honors
+//! `OcrConfig::handwriting`: `on` reads every detected region with the handwriting
+//! model, `auto` only the regions the printed-text model reads with low confidence.
+//!
+//! # Runtime
+//!
+//! The ONNX Runtime shared library is loaded at runtime. Set `ORT_DYLIB_PATH` if it
+//! is not on the library search path. GPU providers additionally require a GPU
+//! build of ONNX Runtime.
+//!
+//! # Optional Feature
+//!
+//! This module requires the `onnx-ocr` feature (`onnx-ocr-cuda` / `onnx-ocr-directml`
+//! for GPU support).
+
+mod backend;
+mod config;
+mod detection;
+mod handwriting;
+mod recognition;
+
+pub use backend::OnnxOcrBackend;
+pub use config::{HandwritingModelConfig, OnnxExecutionProvider, OnnxOcrConfig};
@@ -0,0 +1,140 @@
+//! Text recognition: preprocessing and CTC decoding.
+
+use crate::{KreuzbergError, Result};
+use image::RgbImage;
+use image::imageops::{self, FilterType};
+use ndarray::{Array4, ArrayView2};
+use std::path::Path;
+
+/// Minimum width-to-height ratio of the recognition input (PaddleOCR pads to 320x48).
+const MIN_ASPECT_RATIO: f32 = 320.0 / 48.0;
+
+/// Load a recognition dictionary with one character per line.
+///
+/// A trailing space entry is appended, matching PaddleOCR's `use_space_char`.
+pub(super) fn load_dictionary(path: &Path) -> Result<Vec<String>> {
+    let contents = std::fs::read_to_string(path).map_err(|e| KreuzbergError::Ocr {
+        message: format!("Failed to read OCR dictionary '{}': {}", path.display(), e),
+        source: Some(Box::new(e)),
+    })?;
+
+    Ok(parse_dictionary(&contents))
+}
+
+fn parse_dictionary(contents: &str) -> Vec<String> {
+    let mut dictionary: Vec<String> = contents
+        .lines()
+        .map(|line| line.trim_end_matches('\r').to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+    dictionary.push(" ".to_string());
+    dictionary
+}
+
+/// Resize and normalize a text line crop into an NCHW recognition tensor.
+///
+/// The crop is scaled to `height` keeping its aspect ratio and right-padded to a
+/// minimum width.
+pub(super) fn recognition_input(crop: &RgbImage, height: u32) -> Array4<f32> {
+    let (crop_width, crop_height) = crop.dimensions();
+    let ratio = crop_width as f32 / crop_height.max(1) as f32;
+
+    let resized_width = ((height as f32 * ratio).ceil() as u32).max(1);
+    let input_width = resized_width.max((height as f32 * MIN_ASPECT_RATIO) as u32);
+
+    let resized = imageops::resize(crop, resized_width, height, FilterType::Triangle);
+    let mut input = Array4::<f32>::zeros((1, 3, height as usize, input_width as usize));
+
+    for (x, y, pixel) in resized.enumerate_pixels() {
+        for channel in 0..3 {
+            let value = pixel[channel] as f32 / 255.0;
+            input[[0, channel, y as usize, x as usize]] = (value - 0.5) / 0.5;
+        }
+    }
+
+    input
+}
+
+/// Greedy CTC decoding of a `[time, classes]` probability matrix.
+///
+/// Class 0 is the CTC blank and class `i` maps to `dictionary[i - 1]`. Repeated
+/// classes are collapsed. Returns the text and the mean probability of the emitted
+/// characters.
+pub(super) fn ctc_greedy_decode(probabilities: ArrayView2<'_, f32>, dictionary: &[String]) -> (String, f32) {
+    let mut text = String::new();
+    let mut confidence_sum = 0.0f32;
+    let mut emitted = 0usize;
+    let mut previous = 0usize;
+
+    for step in probabilities.rows() {
+        let (class, probability) = step
+            .iter()
+            .copied()
+            .enumerate()
+            .fold((0, f32::MIN), |best, (i, p)| if p > best.1 { (i, p) } else { best });
+
+        if class != 0
+            && class != previous
+            && let Some(symbol) = dictionary.get(class - 1)
+        {
+            text.push_str(symbol);
+            confidence_sum += probability;
+            emitted += 1;
+        }
+        previous = class;
+    }
+
+    let confidence = if emitted == 0 {
+        0.0
+    } else {
+        confidence_sum / emitted as f32
+    };
+    (text.trim().to_string(), confidence)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ndarray::Array2;
+
+    fn one_hot(classes: &[usize], class_count: usize) -> Array2<f32> {
+        let mut matrix = Array2::<f32>::from_elem((classes.len(), class_count), 0.01);
+        for (step, &class) in classes.iter().enumerate() {
+            matrix[[step, class]] = 0.9;
+        }
+        matrix
+    }
+
+    #[test]
+    fn test_parse_dictionary_appends_space() {
+        assert_eq!(parse_dictionary("a\r\nb\n\nc\n"), vec!["a", "b", "c", " "]);
+    }
+
+    #[test]
+    fn test_ctc_greedy_decode_collapses_repeats_and_blanks() {
+        let dictionary = parse_dictionary("h\ne\nl\no");
+        // h h _ e l l _ l o
+        let probabilities = one_hot(&[1, 1, 0, 2, 3, 3, 0, 3, 4], 6);
+        let (text, confidence) = ctc_greedy_decode(probabilities.view(), &dictionary);
+        assert_eq!(text, "hello");
+        assert!((confidence - 0.9).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_ctc_greedy_decode_handles_space_and_unknown_classes() {
+        let dictionary = parse_dictionary("a\nb");
+        // a <space> b <out of range>
+        let probabilities = one_hot(&[1, 3, 2, 7], 8);
+        let (text, _) = ctc_greedy_decode(probabilities.view(), &dictionary);
+        assert_eq!(text, "a b");
+    }
+
+    #[test]
+    fn test_recognition_input_pads_short_crops() {
+        let crop = RgbImage::from_pixel(20, 10, image::Rgb([255, 255, 255]));
+        let input = recognition_input(&crop, 48);
+        assert_eq!(input.shape(), &[1, 3, 48, 320]);
+        assert_eq!(input[[0, 0, 0, 0]], 1.0);
+        assert_eq!(input[[0, 0, 0, 319]], 0.0);
+    }
+}
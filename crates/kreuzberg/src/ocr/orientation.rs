@@ -0,0 +1,327 @@
+//! Page orientation detection.
+//!
+//! Sideways and upside-down scans are recognized from the ink distribution of the
+//! page, independently of the OCR backend. Text lines make the ink profile across the
+//! lines far more uneven than the profile along them, which tells horizontal from
+//! vertical lines. Latin script carries more ink above the x-height band (ascenders
+//! and capitals) than below it (descenders), which tells upright from upside-down lines.
+//! Pages without clear evidence are left as they are.
+//!
+//! The up/down evidence is tuned for Latin script; pages of vertically written CJK text
+//! are taken for sideways pages.
+
+use image::imageops::{self, FilterType};
+use image::{DynamicImage, GrayImage, Luma};
+use std::ops::Range;
+
+/// Longer side, in pixels, of the image the analysis runs on.
+const ANALYSIS_SIZE: u32 = 1200;
+/// Share of ink pixels outside of which a page is not treated as text.
+const MIN_INK_RATIO: f64 = 0.001;
+const MAX_INK_RATIO: f64 = 0.35;
+/// Factor by which one projection profile must be more uneven than the other.
+const LINE_MARGIN: f64 = 1.3;
+/// Factor by which the ink on one side of the x-height band must exceed the other.
+const FLIP_MARGIN: f64 = 1.25;
+/// Minimum height of a text line, in pixels of the analysis image.
+const MIN_LINE_HEIGHT: usize = 5;
+/// Minimum number of text lines for up/down evidence.
+const MIN_LINES: usize = 3;
+/// Minimum ratio of length to height of a text line.
+const MIN_LINE_ASPECT: u32 = 3;
+/// Minimum share of the line ink above and below the x-height band for it to count.
+const MIN_EXTENDER_SHARE: f64 = 0.05;
+
+const INK: Luma<u8> = Luma([255]);
+
+/// Detect the clockwise rotation in degrees (0, 90, 180 or 270) that turns a page upright.
+///
+/// Returns 0 for upright pages and pages without enough text to decide.
+pub fn detect_rotation(image: &DynamicImage) -> u16 {
+    let Some(mask) = ink_mask(image) else {
+        return 0;
+    };
+
+    let across = profile_contrast(&row_profile(&mask));
+    let along = profile_contrast(&column_profile(&mask));
+
+    if across >= along * LINE_MARGIN {
+        match upright(&mask) {
+            Some(false) => 180,
+            _ => 0,
+        }
+    } else if along >= across * LINE_MARGIN {
+        // Letters of a single line also look like vertical lines; real lines are long.
+        let rotated = imageops::rotate90(&mask);
+        if !has_long_lines(&rotated) {
+            return 0;
+        }
+        match upright(&rotated) {
+            Some(true) => 90,
+            Some(false) => 270,
+            None => 0,
+        }
+    } else {
+        0
+    }
+}
+
+/// Rotate a page upright, returning the page and the applied clockwise rotation in degrees.
+pub fn rotate_upright(image: DynamicImage) -> (DynamicImage, u16) {
+    let rotation = detect_rotation(&image);
+    let image = match rotation {
+        90 => image.rotate90(),
+        180 => image.rotate180(),
+        270 => image.rotate270(),
+        _ => image,
+    };
+    (image, rotation)
+}
+
+/// Binarize a downscaled copy of the page with an Otsu threshold; ink is white.
+///
+/// Returns `None` for blank pages and pages with too much ink to be text, such as photos.
+fn ink_mask(image: &DynamicImage) -> Option<GrayImage> {
+    if image.width() == 0 || image.height() == 0 {
+        return None;
+    }
+
+    let gray = if image.width().max(image.height()) > ANALYSIS_SIZE {
+        image
+            .resize(ANALYSIS_SIZE, ANALYSIS_SIZE, FilterType::Triangle)
+            .to_luma8()
+    } else {
+        image.to_luma8()
+    };
+
+    let threshold = otsu_threshold(&gray);
+    let mut ink_pixels = 0usize;
+    let mask = GrayImage::from_fn(gray.width(), gray.height(), |x, y| {
+        if gray.get_pixel(x, y)[0] <= threshold {
+            ink_pixels += 1;
+            INK
+        } else {
+            Luma([0])
+        }
+    });
+
+    let ink_ratio = ink_pixels as f64 / (mask.width() as f64 * mask.height() as f64);
+    (MIN_INK_RATIO..=MAX_INK_RATIO).contains(&ink_ratio).then_some(mask)
+}
+
+fn otsu_threshold(gray: &GrayImage) -> u8 {
+    let mut histogram = [0u64; 256];
+    for pixel in gray.pixels() {
+        histogram[pixel[0] as usize] += 1;
+    }
+
+    let total = (gray.width() as f64) * (gray.height() as f64);
+    let total_sum: f64 = histogram
+        .iter()
+        .enumerate()
+        .map(|(value, &count)| value as f64 * count as f64)
+        .sum();
+
+    let (mut background_weight, mut background_sum) = (0.0, 0.0);
+    let (mut threshold, mut best_variance) = (0u8, 0.0);
+    for (value, &count) in histogram.iter().enumerate() {
+        background_weight += count as f64;
+        let foreground_weight = total - background_weight;
+        if background_weight == 0.0 {
+            continue;
+        }
+        if foreground_weight == 0.0 {
+            break;
+        }
+
+        background_sum += value as f64 * count as f64;
+        let background_mean = background_sum / background_weight;
+        let foreground_mean = (total_sum - background_sum) / foreground_weight;
+        let variance = background_weight * foreground_weight * (background_mean - foreground_mean).powi(2);
+        if variance > best_variance {
+            best_variance = variance;
+            threshold = value as u8;
+        }
+    }
+    threshold
+}
+
+fn row_profile(mask: &GrayImage) -> Vec<u32> {
+    mask.rows()
+        .map(|row| row.filter(|&&pixel| pixel == INK).count() as u32)
+        .collect()
+}
+
+fn column_profile(mask: &GrayImage) -> Vec<u32> {
+    let mut profile = vec![0u32; mask.width() as usize];
+    for (x, _, pixel) in mask.enumerate_pixels() {
+        if *pixel == INK {
+            profile[x as usize] += 1;
+        }
+    }
+    profile
+}
+
+/// Squared coefficient of variation of a profile, ignoring the blank margins.
+fn profile_contrast(profile: &[u32]) -> f64 {
+    let Some(first) = profile.iter().position(|&ink| ink > 0) else {
+        return 0.0;
+    };
+    let last = profile.iter().rposition(|&ink| ink > 0).unwrap_or(first);
+    let profile = &profile[first..=last];
+
+    let mean = profile.iter().map(|&ink| f64::from(ink)).sum::<f64>() / profile.len() as f64;
+    let variance = profile.iter().map(|&ink| (f64::from(ink) - mean).powi(2)).sum::<f64>() / profile.len() as f64;
+    variance / (mean * mean)
+}
+
+/// Whether the horizontal text lines of a mask are upright.
+///
+/// Decided by the ink above and below the x-height band of the lines, or, for text
+/// without ascenders and descenders such as capitals and digits, by whether the lines
+/// share their left or their right edge. Returns `None` without a clear majority.
+fn upright(mask: &GrayImage) -> Option<bool> {
+    let profile = row_profile(mask);
+    let lines = text_lines(&profile);
+    if lines.len() < MIN_LINES {
+        return None;
+    }
+
+    let (mut above, mut below, mut ink) = (0.0, 0.0, 0.0);
+    for line in &lines {
+        let rows = &profile[line.clone()];
+        let peak = rows.iter().copied().max().unwrap_or(0);
+        // The x-height band spans the rows holding at least half of the densest row.
+        let band_start = rows.iter().position(|&ink| 2 * ink >= peak).unwrap_or(0);
+        let band_end = rows.iter().rposition(|&ink| 2 * ink >= peak).unwrap_or(rows.len() - 1);
+
+        above += rows[..band_start].iter().map(|&ink| f64::from(ink)).sum::<f64>();
+        below += rows[band_end + 1..].iter().map(|&ink| f64::from(ink)).sum::<f64>();
+        ink += rows.iter().map(|&ink| f64::from(ink)).sum::<f64>();
+    }
+
+    if above + below >= ink * MIN_EXTENDER_SHARE
+        && let Some(upright) = majority(above, below)
+    {
+        return Some(upright);
+    }
+
+    let (left, right) = aligned_edges(mask, &lines);
+    if left.max(right) < MIN_LINES {
+        return None;
+    }
+    majority(left as f64, right as f64)
+}
+
+/// `Some(true)` when `upright` clearly outweighs `flipped`, `Some(false)` for the reverse.
+fn majority(upright: f64, flipped: f64) -> Option<bool> {
+    if upright > flipped * FLIP_MARGIN {
+        Some(true)
+    } else if flipped > upright * FLIP_MARGIN {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+/// Row ranges of the text lines in a row profile.
+///
+/// Rows with less than a twentieth of the average ink separate lines, so that ruling
+/// lines crossing the text do not merge the lines around them.
+fn text_lines(profile: &[u32]) -> Vec<Range<usize>> {
+    let inked: Vec<u32> = profile.iter().copied().filter(|&ink| ink > 0).collect();
+    let noise = (inked.iter().sum::<u32>() / (20 * inked.len().max(1) as u32)).max(1);
+
+    let mut lines = Vec::new();
+    let mut line_start = None;
+    for (row, &ink) in profile.iter().chain(std::iter::once(&0)).enumerate() {
+        match (line_start, ink > noise) {
+            (None, true) => line_start = Some(row),
+            (Some(start), false) => {
+                line_start = None;
+                if row - start >= MIN_LINE_HEIGHT {
+                    lines.push(start..row);
+                }
+            }
+            _ => {}
+        }
+    }
+    lines
+}
+
+/// First and last inked column of every line.
+fn line_extents(mask: &GrayImage, lines: &[Range<usize>]) -> Vec<(u32, u32)> {
+    lines
+        .iter()
+        .filter_map(|line| {
+            let mut columns = (0..mask.width()).filter(|&x| line.clone().any(|y| mask.get_pixel(x, y as u32) == &INK));
+            let first = columns.next()?;
+            Some((first, columns.next_back().unwrap_or(first)))
+        })
+        .collect()
+}
+
+/// Whether most text lines of a mask are much longer than they are high.
+fn has_long_lines(mask: &GrayImage) -> bool {
+    let lines = text_lines(&row_profile(mask));
+    let long = line_extents(mask, &lines)
+        .iter()
+        .zip(&lines)
+        .filter(|((first, last), line)| last - first + 1 >= MIN_LINE_ASPECT * line.len() as u32)
+        .count();
+    long > 0 && 2 * long >= lines.len()
+}
+
+/// Number of lines sharing the most common left edge and the most common right edge.
+fn aligned_edges(mask: &GrayImage, lines: &[Range<usize>]) -> (usize, usize) {
+    let (lefts, rights): (Vec<u32>, Vec<u32>) = line_extents(mask, lines).into_iter().unzip();
+
+    let tolerance = (mask.width() / 100).max(2);
+    let most_aligned = |edges: &[u32]| {
+        edges
+            .iter()
+            .map(|&edge| edges.iter().filter(|&&other| other.abs_diff(edge) <= tolerance).count())
+            .max()
+            .unwrap_or(0)
+    };
+    (most_aligned(&lefts), most_aligned(&rights))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(name: &str) -> DynamicImage {
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+            .join("../../test_documents/images")
+            .join(name);
+        image::open(path).unwrap()
+    }
+
+    #[test]
+    fn test_detect_rotation_of_rotated_scans() {
+        for name in ["ocr_image.jpg", "layout_parser_ocr.jpg", "invoice_image.png"] {
+            let page = fixture(name);
+            assert_eq!(detect_rotation(&page), 0, "{name}");
+            assert_eq!(detect_rotation(&page.rotate90()), 270, "{name}");
+            assert_eq!(detect_rotation(&page.rotate180()), 180, "{name}");
+            assert_eq!(detect_rotation(&page.rotate270()), 90, "{name}");
+        }
+    }
+
+    #[test]
+    fn test_rotate_upright_restores_page() {
+        let page = fixture("ocr_image.jpg").to_luma8();
+        let (upright, rotation) = rotate_upright(DynamicImage::ImageLuma8(imageops::rotate270(&page)));
+        assert_eq!(rotation, 90);
+        assert_eq!(upright.to_luma8(), page);
+    }
+
+    #[test]
+    fn test_pages_without_text_are_left_alone() {
+        assert_eq!(detect_rotation(&fixture("flower_no_text.jpg")), 0);
+        assert_eq!(detect_rotation(&DynamicImage::new_luma8(300, 200)), 0);
+        // A single line gives no reliable evidence either way.
+        assert_eq!(detect_rotation(&fixture("test_hello_world.png").rotate90()), 0);
+    }
+}
@@ -84,10 +84,9 @@ pub(super) fn apply_tesseract_variables(api: &TesseractAPI, config: &TesseractCo
     )
     .map_err(|e| OcrError::InvalidConfiguration(format!("Failed to set tessedit_enable_dict_correction: {}", e)))?;
 
-    if !config.tessedit_char_whitelist.is_empty() {
-        api.set_variable("tessedit_char_whitelist", &config.tessedit_char_whitelist)
-            .map_err(|e| OcrError::InvalidConfiguration(format!("Failed to set tessedit_char_whitelist: {}", e)))?;
-    }
+    // Set even when empty, so a whitelist does not carry over to the next use of a pooled engine.
+    api.set_variable("tessedit_char_whitelist", &config.tessedit_char_whitelist)
+        .map_err(|e| OcrError::InvalidConfiguration(format!("Failed to set tessedit_char_whitelist: {}", e)))?;
 
     api.set_variable(
         "tessedit_use_primary_params_model",
@@ -9,12 +9,14 @@ use super::validation::{
 };
 use crate::core::config::ExtractionConfig;
 use crate::ocr::cache::OcrCache;
+use crate::ocr::engine_pool::EnginePool;
 use crate::ocr::error::OcrError;
 use crate::ocr::hocr::convert_hocr_to_markdown;
 use crate::ocr::table::{extract_words_from_tsv, reconstruct_table, table_to_markdown};
 use crate::ocr::types::{BatchItemResult, TesseractConfig};
 use crate::types::{OcrExtractionResult, OcrTable};
 use kreuzberg_tesseract::{TessPageSegMode, TesseractAPI};
+use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::env;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -38,11 +40,48 @@ where
     tracing::debug!("[ci-debug][ocr::processor::{stage}] {timestamp:.3}s {}", details());
 }
 
+/// Initialized Tesseract engines, by tessdata path and language.
+static TESSERACT_ENGINES: Lazy<EnginePool<TesseractAPI>> = Lazy::new(|| {
+    let max_idle = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+    EnginePool::new(max_idle, |api: &TesseractAPI| {
+        api.clear().is_ok() && api.clear_adaptive_classifier().is_ok()
+    })
+});
+
+fn engine_key(tessdata_path: &str, language: &str) -> String {
+    format!("{}\n{}", tessdata_path, language)
+}
+
+/// Create a Tesseract engine initialized for `language`.
+fn init_engine(tessdata_path: &str, language: &str) -> Result<TesseractAPI, OcrError> {
+    let api = TesseractAPI::new();
+    api.init(tessdata_path, language).map_err(|e| {
+        OcrError::TesseractInitializationFailed(format!("Failed to initialize language '{}': {}", language, e))
+    })?;
+    Ok(api)
+}
+
+/// Initialize Tesseract engines for `language` until `count` of them are idle.
+///
+/// The number of idle engines per language is limited to the number of CPU cores.
+pub(super) fn warm_engines(language: &str, count: usize) -> Result<(), OcrError> {
+    let tessdata_path = resolve_tessdata_path();
+    validate_language_and_traineddata(language, &tessdata_path)?;
+    TESSERACT_ENGINES.warm(&engine_key(&tessdata_path, language), count, || {
+        init_engine(&tessdata_path, language)
+    })
+}
+
+/// Drop all idle Tesseract engines.
+pub(super) fn clear_engines() {
+    TESSERACT_ENGINES.clear();
+}
+
 /// Perform OCR on an image using Tesseract.
 ///
 /// This function handles the complete OCR pipeline:
 /// 1. Image loading and preprocessing
-/// 2. Tesseract initialization and configuration
+/// 2. Tesseract engine checkout from the engine pool and configuration
 /// 3. Text recognition
 /// 4. Output formatting (text, markdown, hOCR, or TSV)
 /// 5. Optional table detection
@@ -72,7 +111,7 @@ pub(super) fn perform_ocr(
         )
     });
 
-    let img = image::load_from_memory(image_bytes)
+    let img = crate::extraction::image::decode_image(image_bytes)
         .map_err(|e| OcrError::ImageProcessingFailed(format!("Failed to decode image: {}", e)))?;
 
     let rgb_image = img.to_rgb8();
@@ -87,7 +126,6 @@ pub(super) fn perform_ocr(
         )
     });
 
-    let api = TesseractAPI::new();
     let tessdata_path = resolve_tessdata_path();
 
     log_ci_debug(ci_debug_enabled, "tessdata", || {
@@ -120,18 +158,22 @@ pub(super) fn perform_ocr(
     // Validate language and traineddata files
     validate_language_and_traineddata(&config.language, &tessdata_path)?;
 
-    let init_result = api.init(&tessdata_path, &config.language);
+    let mut initialized = false;
+    let init_result = TESSERACT_ENGINES.checkout(&engine_key(&tessdata_path, &config.language), || {
+        initialized = true;
+        init_engine(&tessdata_path, &config.language)
+    });
     log_ci_debug(ci_debug_enabled, "init", || match &init_result {
-        Ok(_) => format!("language={} datapath='{}'", config.language, tessdata_path),
+        Ok(_) => format!(
+            "language={} datapath='{}' reused={}",
+            config.language, tessdata_path, !initialized
+        ),
         Err(err) => format!(
             "language={} datapath='{}' error={:?}",
             config.language, tessdata_path, err
         ),
     });
-
-    init_result.map_err(|e| {
-        OcrError::TesseractInitializationFailed(format!("Failed to initialize language '{}': {}", config.language, e))
-    })?;
+    let api = init_result?;
 
     if ci_debug_enabled {
         match api.get_available_languages() {
@@ -398,10 +440,14 @@ fn process_image_resolved(
     let config_str = hash_config(config);
 
     if config.use_cache
-        && let Some(cached_result) = cache.get_cached_result(&image_hash, "tesseract", &config_str)?
+        && let Some(mut cached_result) = cache.get_cached_result(&image_hash, "tesseract", &config_str)?
     {
         #[cfg(feature = "otel")]
         tracing::Span::current().record("cache.hit", true);
+        cached_result.metadata.insert(
+            crate::core::pipeline::OCR_CACHE_HIT_KEY.to_string(),
+            serde_json::Value::Bool(true),
+        );
         return Ok(cached_result);
     }
 
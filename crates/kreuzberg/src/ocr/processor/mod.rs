@@ -57,6 +57,21 @@ impl OcrProcessor {
         execution::process_image_with_cache(image_bytes, config, &self.cache, Some(output_format))
     }
 
+    /// Initialize Tesseract engines for `language` ahead of the first OCR calls.
+    ///
+    /// Initialized engines are shared by all processors and reused across calls, so
+    /// only the first pages recognized with a language pay for loading its trained
+    /// data. Warming up moves that cost out of the first requests, e.g. at server
+    /// startup. At most one idle engine per CPU core is kept per language.
+    pub fn warm_up(&self, language: &str, engines: usize) -> Result<(), OcrError> {
+        execution::warm_engines(language, engines)
+    }
+
+    /// Drop the idle Tesseract engines shared by all processors.
+    pub fn clear_engines(&self) {
+        execution::clear_engines();
+    }
+
     pub fn clear_cache(&self) -> Result<(), OcrError> {
         self.cache.clear()
     }
@@ -5,6 +5,7 @@
 
 use crate::ocr::error::OcrError;
 use crate::ocr::validation::TESSERACT_SUPPORTED_LANGUAGE_CODES;
+use crate::text::simd_scan;
 use std::env;
 use std::path::Path;
 
@@ -161,16 +162,15 @@ pub(super) fn resolve_all_installed_languages(tessdata_path: &str) -> Result<Str
 ///
 /// Cleaned text with control characters removed
 pub(super) fn strip_control_characters(text: &str) -> String {
-    if text
-        .chars()
-        .any(|c| matches!(c, '\u{0000}'..='\u{001F}' | '\u{007F}') && c != '\n' && c != '\r' && c != '\t')
-    {
-        text.chars()
-            .filter(|c| !matches!(c, '\u{0000}'..='\u{001F}' | '\u{007F}') || matches!(c, '\n' | '\r' | '\t'))
-            .collect()
-    } else {
-        text.to_string()
+    let mut cleaned = String::with_capacity(text.len());
+    let mut rest = text;
+    // Control characters are single bytes, so the text around them stays valid UTF-8.
+    while let Some(index) = simd_scan::find_control(rest.as_bytes()) {
+        cleaned.push_str(&rest[..index]);
+        rest = &rest[index + 1..];
     }
+    cleaned.push_str(rest);
+    cleaned
 }
 
 #[cfg(test)]
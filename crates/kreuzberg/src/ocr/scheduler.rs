@@ -0,0 +1,462 @@
+//! Page-level parallel OCR scheduling.
+//!
+//! Multi-page documents are OCRed by dispatching pages onto a bounded set of tokio
+//! tasks. Pages arrive through a bounded channel from the producer (e.g. the PDF
+//! renderer), so the producer blocks once `concurrency` pages are queued and at most
+//! `concurrency` pages are being recognized at any time. Results are reassembled in
+//! page order regardless of completion order.
+
+use crate::core::config::{HandwritingMode, OcrConfig};
+use crate::ocr::duplicate_page::DuplicatePageDetector;
+use crate::plugins::OcrBackend;
+use crate::types::{DuplicatePage, ExtractionResult, PageInfo};
+use crate::{KreuzbergError, Result};
+use image::DynamicImage;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+
+/// A single page waiting to be OCRed.
+pub(crate) struct OcrPageJob {
+    /// Zero-based position of the page in the document
+    pub index: usize,
+    /// Encoded page image
+    pub image: Vec<u8>,
+    /// Effective OCR configuration for the page
+    pub config: OcrConfig,
+    /// Whether the page is skipped, being blank or a dropped duplicate; skipped pages
+    /// are not recognized and yield no text
+    pub skip: bool,
+}
+
+/// Blank page, duplicate and orientation handling applied to a decoded page before OCR.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub(crate) struct PagePreparation {
+    /// Clockwise rotation applied to the page, when `auto_rotate` is enabled
+    pub rotation: Option<u16>,
+    /// Whether the page is blank, when `skip_blank_pages` is enabled
+    pub blank: Option<bool>,
+    /// Number of the earlier page this page repeats, when `duplicate_pages` is enabled
+    pub duplicate_of: Option<usize>,
+    /// Whether the page is a duplicate left out of the output
+    pub dropped: bool,
+    /// Whether the page is outside the page selection; excluded pages are neither
+    /// decoded for OCR nor reported
+    pub excluded: bool,
+}
+
+impl PagePreparation {
+    /// Whether the page can be skipped by OCR.
+    pub fn is_skipped(&self) -> bool {
+        self.blank == Some(true) || self.dropped || self.excluded
+    }
+
+    /// Preparation of a page outside the page selection.
+    pub fn excluded() -> Self {
+        Self {
+            excluded: true,
+            ..Self::default()
+        }
+    }
+
+    /// Whether the preparation is recorded in the page metadata.
+    pub fn has_page_info(&self) -> bool {
+        self.rotation.is_some() || self.blank.is_some()
+    }
+
+    /// Page metadata recording the preparation of page `number`.
+    pub fn page_info(&self, number: usize, dimensions: Option<(f64, f64)>) -> PageInfo {
+        PageInfo {
+            number,
+            title: None,
+            dimensions,
+            image_count: None,
+            table_count: None,
+            hidden: None,
+            rotation: self.rotation,
+            blank: self.blank,
+        }
+    }
+}
+
+/// Prepares the pages of one document for OCR.
+///
+/// Pages must be prepared in page order, so that duplicates are reported against the
+/// first occurrence of a page.
+pub(crate) struct PagePreparer {
+    duplicates: Option<(DuplicatePageDetector, bool)>,
+}
+
+impl PagePreparer {
+    /// Create a preparer for a document, with duplicate detection as configured in
+    /// the document-level `config`.
+    pub fn new(config: &OcrConfig) -> Self {
+        Self {
+            duplicates: config
+                .duplicate_pages
+                .as_ref()
+                .map(|duplicates| (DuplicatePageDetector::new(duplicates), duplicates.drop_duplicates)),
+        }
+    }
+
+    /// Detect whether page `page_number` is blank or a duplicate and, unless it is left
+    /// out, rotate it upright as configured by the page's `config`.
+    pub fn prepare(
+        &mut self,
+        page_number: usize,
+        page: DynamicImage,
+        config: &OcrConfig,
+    ) -> (DynamicImage, PagePreparation) {
+        let mut preparation = PagePreparation::default();
+
+        if let Some(blank_pages) = &config.skip_blank_pages {
+            let blank = crate::ocr::blank_page::is_blank(&page, blank_pages);
+            preparation.blank = Some(blank);
+            if blank {
+                return (page, preparation);
+            }
+        }
+
+        if let Some((detector, drop_duplicates)) = &mut self.duplicates {
+            preparation.duplicate_of = detector.check(page_number, &page);
+            preparation.dropped = *drop_duplicates && preparation.duplicate_of.is_some();
+            if preparation.dropped {
+                return (page, preparation);
+            }
+        }
+
+        if config.auto_rotate {
+            let (page, rotation) = crate::ocr::orientation::rotate_upright(page);
+            preparation.rotation = Some(rotation);
+            return (page, preparation);
+        }
+
+        (page, preparation)
+    }
+}
+
+/// Duplicate pages among the prepared pages of a document, in page order.
+pub(crate) fn duplicate_pages(preparations: &[PagePreparation]) -> Vec<DuplicatePage> {
+    preparations
+        .iter()
+        .enumerate()
+        .filter_map(|(page_index, preparation)| {
+            preparation.duplicate_of.map(|duplicate_of| DuplicatePage {
+                page_number: page_index + 1,
+                duplicate_of,
+                dropped: preparation.dropped,
+            })
+        })
+        .collect()
+}
+
+/// Fail when handwriting recognition is required but `backend` cannot read handwriting.
+///
+/// `HandwritingMode::Auto` is accepted by every backend and falls back to printed-text
+/// recognition.
+pub(crate) fn ensure_handwriting_supported(backend: &dyn OcrBackend, config: &OcrConfig) -> Result<()> {
+    if config.handwriting == HandwritingMode::On && !backend.supports_handwriting() {
+        return Err(KreuzbergError::validation(format!(
+            "OCR backend '{}' does not support handwriting recognition; use a backend with a handwriting model or set handwriting to 'auto' or 'off'",
+            backend.name()
+        )));
+    }
+    Ok(())
+}
+
+/// Create the bounded channel used to feed [`run_page_jobs`].
+pub(crate) fn page_job_channel(
+    concurrency: usize,
+) -> (mpsc::Sender<Result<OcrPageJob>>, mpsc::Receiver<Result<OcrPageJob>>) {
+    mpsc::channel(concurrency.max(1))
+}
+
+/// OCR all pages received on `jobs` with at most `concurrency` pages in flight.
+///
+/// Returns one result per page, ordered by [`OcrPageJob::index`], with empty results
+/// for skipped pages. The first error,
+/// either sent by the producer or returned by the backend, aborts the remaining
+/// pages and is returned.
+pub(crate) async fn run_page_jobs(
+    backend: Arc<dyn OcrBackend>,
+    mut jobs: mpsc::Receiver<Result<OcrPageJob>>,
+    concurrency: usize,
+) -> Result<Vec<ExtractionResult>> {
+    let concurrency = concurrency.max(1);
+    let mut in_flight: JoinSet<(usize, Result<ExtractionResult>)> = JoinSet::new();
+    let mut results: Vec<Option<ExtractionResult>> = Vec::new();
+    let mut accepting = true;
+
+    while accepting || !in_flight.is_empty() {
+        let finished = if accepting && in_flight.len() < concurrency {
+            tokio::select! {
+                job = jobs.recv() => {
+                    match job {
+                        Some(Ok(job)) => {
+                            if let Err(e) = ensure_handwriting_supported(backend.as_ref(), &job.config) {
+                                in_flight.abort_all();
+                                return Err(e);
+                            }
+                            if job.skip {
+                                store_result(&mut results, job.index, blank_page_result());
+                                continue;
+                            }
+                            let backend = Arc::clone(&backend);
+                            in_flight.spawn(async move {
+                                let result = backend.process_image(&job.image, &job.config).await;
+                                (job.index, result)
+                            });
+                        }
+                        Some(Err(e)) => {
+                            in_flight.abort_all();
+                            return Err(e);
+                        }
+                        None => accepting = false,
+                    }
+                    None
+                }
+                Some(joined) = in_flight.join_next(), if !in_flight.is_empty() => Some(joined),
+            }
+        } else {
+            in_flight.join_next().await
+        };
+
+        if let Some(joined) = finished {
+            let (index, result) = joined.map_err(|e| KreuzbergError::Ocr {
+                message: format!("OCR page task failed: {}", e),
+                source: None,
+            })?;
+
+            match result {
+                Ok(result) => {
+                    crate::core::pipeline::record_ocr_page(index + 1, &result);
+                    store_result(&mut results, index, result)
+                }
+                Err(e) => {
+                    in_flight.abort_all();
+                    return Err(e);
+                }
+            }
+        }
+    }
+
+    results
+        .into_iter()
+        .enumerate()
+        .map(|(index, result)| {
+            result.ok_or_else(|| KreuzbergError::Ocr {
+                message: format!("No OCR result for page {}", index + 1),
+                source: None,
+            })
+        })
+        .collect()
+}
+
+fn store_result(results: &mut Vec<Option<ExtractionResult>>, index: usize, result: ExtractionResult) {
+    if results.len() <= index {
+        results.resize_with(index + 1, || None);
+    }
+    results[index] = Some(result);
+}
+
+/// The OCR result of a skipped page: no text.
+pub(crate) fn blank_page_result() -> ExtractionResult {
+    ExtractionResult {
+        content: String::new(),
+        mime_type: "text/plain".into(),
+        metadata: Default::default(),
+        pages: None,
+        tables: vec![],
+        detected_languages: None,
+        chunks: None,
+        images: None,
+        djot_content: None,
+        scripts: None,
+        elements: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::{OcrBackendType, Plugin};
+    use crate::types::Metadata;
+    use async_trait::async_trait;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Echoes the image bytes as text after a delay that decreases with the page index.
+    struct EchoBackend {
+        active: AtomicUsize,
+        max_active: AtomicUsize,
+    }
+
+    impl Plugin for EchoBackend {
+        fn name(&self) -> &str {
+            "echo"
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl OcrBackend for EchoBackend {
+        async fn process_image(&self, image_bytes: &[u8], _config: &OcrConfig) -> Result<ExtractionResult> {
+            let active = self.active.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_active.fetch_max(active, Ordering::SeqCst);
+
+            let text = String::from_utf8_lossy(image_bytes).to_string();
+            if text == "fail" {
+                self.active.fetch_sub(1, Ordering::SeqCst);
+                return Err(KreuzbergError::Ocr {
+                    message: "boom".to_string(),
+                    source: None,
+                });
+            }
+            tokio::time::sleep(Duration::from_millis(40 - 4 * text.len().min(9) as u64)).await;
+            self.active.fetch_sub(1, Ordering::SeqCst);
+
+            Ok(ExtractionResult {
+                content: text,
+                mime_type: "text/plain".into(),
+                metadata: Metadata::default(),
+                pages: None,
+                tables: vec![],
+                detected_languages: None,
+                chunks: None,
+                images: None,
+                elements: None,
+                djot_content: None,
+                scripts: None,
+            })
+        }
+
+        async fn process_file(&self, _path: &Path, _config: &OcrConfig) -> Result<ExtractionResult> {
+            unreachable!()
+        }
+
+        fn supports_language(&self, _lang: &str) -> bool {
+            true
+        }
+
+        fn backend_type(&self) -> OcrBackendType {
+            OcrBackendType::Custom
+        }
+    }
+
+    fn backend() -> Arc<EchoBackend> {
+        Arc::new(EchoBackend {
+            active: AtomicUsize::new(0),
+            max_active: AtomicUsize::new(0),
+        })
+    }
+
+    async fn run(backend: Arc<EchoBackend>, pages: Vec<&'static str>, concurrency: usize) -> Result<Vec<String>> {
+        let (sender, receiver) = page_job_channel(concurrency);
+        let producer = tokio::spawn(async move {
+            for (index, page) in pages.into_iter().enumerate() {
+                let job = OcrPageJob {
+                    index,
+                    image: page.as_bytes().to_vec(),
+                    config: OcrConfig::default(),
+                    skip: page.starts_with('-'),
+                };
+                if sender.send(Ok(job)).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let results = run_page_jobs(backend, receiver, concurrency).await;
+        producer.await.unwrap();
+        results.map(|results| results.into_iter().map(|r| r.content).collect())
+    }
+
+    #[tokio::test]
+    async fn test_results_are_in_page_order() {
+        let pages = vec!["a", "bb", "ccc", "dddd", "eeeee", "ffffff"];
+        let texts = run(backend(), pages.clone(), 3).await.unwrap();
+        assert_eq!(texts, pages);
+    }
+
+    #[tokio::test]
+    async fn test_concurrency_is_bounded() {
+        let backend = backend();
+        let pages = vec!["a", "b", "c", "d", "e", "f", "g", "h"];
+        run(Arc::clone(&backend), pages, 2).await.unwrap();
+        let max_active = backend.max_active.load(Ordering::SeqCst);
+        assert!((1..=2).contains(&max_active), "max active was {}", max_active);
+    }
+
+    #[tokio::test]
+    async fn test_backend_error_is_returned() {
+        let result = run(backend(), vec!["a", "fail", "c"], 2).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_skipped_pages_are_not_recognized() {
+        let texts = run(backend(), vec!["a", "-fail", "c"], 2).await.unwrap();
+        assert_eq!(texts, vec!["a", "", "c"]);
+    }
+
+    #[test]
+    fn test_duplicate_pages_are_reported_and_dropped() {
+        let page = |shade| {
+            DynamicImage::ImageLuma8(image::GrayImage::from_fn(200, 260, |x, y| {
+                image::Luma([if (x / 20 + y / shade) % 2 == 0 { 0 } else { 255 }])
+            }))
+        };
+        let config = |drop_duplicates| OcrConfig {
+            duplicate_pages: Some(crate::core::config::DuplicatePageConfig {
+                drop_duplicates,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        for drop_duplicates in [false, true] {
+            let config = config(drop_duplicates);
+            let mut preparer = PagePreparer::new(&config);
+            let preparations: Vec<PagePreparation> = [page(20), page(45), page(20)]
+                .into_iter()
+                .enumerate()
+                .map(|(index, page)| preparer.prepare(index + 1, page, &config).1)
+                .collect();
+
+            assert!(!preparations[1].is_skipped());
+            assert_eq!(preparations[2].is_skipped(), drop_duplicates);
+            assert_eq!(
+                duplicate_pages(&preparations),
+                vec![DuplicatePage {
+                    page_number: 3,
+                    duplicate_of: 1,
+                    dropped: drop_duplicates,
+                }]
+            );
+        }
+    }
+
+    #[test]
+    fn test_handwriting_requires_backend_support() {
+        let backend = backend();
+        let config = |handwriting| OcrConfig {
+            handwriting,
+            ..Default::default()
+        };
+        assert!(ensure_handwriting_supported(backend.as_ref(), &config(HandwritingMode::Off)).is_ok());
+        assert!(ensure_handwriting_supported(backend.as_ref(), &config(HandwritingMode::Auto)).is_ok());
+        assert!(matches!(
+            ensure_handwriting_supported(backend.as_ref(), &config(HandwritingMode::On)),
+            Err(KreuzbergError::Validation { .. })
+        ));
+    }
+}
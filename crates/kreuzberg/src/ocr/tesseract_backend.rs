@@ -5,6 +5,7 @@
 
 use crate::Result;
 use crate::core::config::OcrConfig;
+use crate::core::thread_pool::run_blocking;
 use crate::ocr::processor::OcrProcessor;
 use crate::plugins::{OcrBackend, OcrBackendType, Plugin};
 use crate::types::ExtractionResult;
@@ -54,6 +55,25 @@ impl TesseractBackend {
         })
     }
 
+    /// Initialize `engines` Tesseract engines for `language` ahead of the first OCR calls.
+    ///
+    /// Engines are kept in a pool and reused across documents, so only the first pages
+    /// recognized with a language pay for its initialization. Servers can call this at
+    /// startup to take that cost out of the first requests.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Ocr` if the trained data of `language` is missing or an
+    /// engine cannot be initialized.
+    pub fn warm_up(&self, language: &str, engines: usize) -> Result<()> {
+        self.processor
+            .warm_up(language, engines)
+            .map_err(|e| crate::KreuzbergError::Ocr {
+                message: format!("Failed to warm up Tesseract engines: {}", e),
+                source: Some(Box::new(e)),
+            })
+    }
+
     /// Convert public API TesseractConfig to internal TesseractConfig.
     ///
     /// The public API types (crate::types) use i32 for compatibility with PyO3,
@@ -167,6 +187,7 @@ impl Plugin for TesseractBackend {
     }
 
     fn shutdown(&self) -> Result<()> {
+        self.processor.clear_engines();
         self.processor.clear_cache().map_err(|e| crate::KreuzbergError::Plugin {
             message: format!("Failed to clear Tesseract cache: {}", e),
             plugin_name: "tesseract".to_string(),
@@ -184,15 +205,11 @@ impl OcrBackend for TesseractBackend {
         let processor = Arc::clone(&self.processor);
         let image_bytes = image_bytes.to_vec();
 
-        let ocr_result = tokio::task::spawn_blocking(move || match output_format {
+        let ocr_result = run_blocking(config.thread_pool.as_ref(), move || match output_format {
             Some(fmt) => processor.process_image_with_format(&image_bytes, &tess_config_clone, fmt),
             None => processor.process_image(&image_bytes, &tess_config_clone),
         })
-        .await
-        .map_err(|e| crate::KreuzbergError::Plugin {
-            message: format!("Tesseract task panicked: {}", e),
-            plugin_name: "tesseract".to_string(),
-        })?
+        .await?
         .map_err(|e| crate::KreuzbergError::Ocr {
             message: format!("Tesseract OCR failed: {}", e),
             source: Some(Box::new(e)),
@@ -247,6 +264,7 @@ impl OcrBackend for TesseractBackend {
             images: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         })
     }
 
@@ -258,15 +276,11 @@ impl OcrBackend for TesseractBackend {
         let processor = Arc::clone(&self.processor);
         let path_str = path.to_string_lossy().to_string();
 
-        let ocr_result = tokio::task::spawn_blocking(move || match output_format {
+        let ocr_result = run_blocking(config.thread_pool.as_ref(), move || match output_format {
             Some(fmt) => processor.process_file_with_format(&path_str, &tess_config_clone, fmt),
             None => processor.process_file(&path_str, &tess_config_clone),
         })
-        .await
-        .map_err(|e| crate::KreuzbergError::Plugin {
-            message: format!("Tesseract task panicked: {}", e),
-            plugin_name: "tesseract".to_string(),
-        })?
+        .await?
         .map_err(|e| crate::KreuzbergError::Ocr {
             message: format!("Tesseract OCR failed: {}", e),
             source: Some(Box::new(e)),
@@ -321,6 +335,7 @@ impl OcrBackend for TesseractBackend {
             images: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         })
     }
 
@@ -339,6 +354,10 @@ impl OcrBackend for TesseractBackend {
     fn supports_table_detection(&self) -> bool {
         true
     }
+
+    fn required_features(&self) -> &[&str] {
+        &["ocr"]
+    }
 }
 
 #[cfg(test)]
@@ -399,6 +418,7 @@ mod tests {
             language: "deu".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         };
 
         let tess_config = backend.config_to_tesseract(&ocr_config);
@@ -421,6 +441,7 @@ mod tests {
             language: "eng".to_string(),
             tesseract_config: Some(custom_tess_config),
             output_format: None,
+            ..Default::default()
         };
 
         let tess_config = backend.config_to_tesseract(&ocr_config);
@@ -465,6 +486,7 @@ mod tests {
             language: "eng".to_string(),
             tesseract_config: Some(custom_tess_config),
             output_format: None,
+            ..Default::default()
         };
 
         let tess_config = backend.config_to_tesseract(&ocr_config);
@@ -21,7 +21,9 @@ lazy_static::lazy_static! {
         set.insert("ceb");
         set.insert("ces");
         set.insert("chi_sim");
+        set.insert("chi_sim_vert");
         set.insert("chi_tra");
+        set.insert("chi_tra_vert");
         set.insert("chr");
         set.insert("cos");
         set.insert("cym");
@@ -62,6 +64,7 @@ lazy_static::lazy_static! {
         set.insert("ita_old");
         set.insert("jav");
         set.insert("jpn");
+        set.insert("jpn_vert");
         set.insert("kan");
         set.insert("kat");
         set.insert("kat_old");
@@ -70,6 +73,7 @@ lazy_static::lazy_static! {
         set.insert("kir");
         set.insert("kmr");
         set.insert("kor");
+        set.insert("kor_vert");
         set.insert("lao");
         set.insert("lat");
         set.insert("lav");
@@ -178,6 +182,12 @@ mod tests {
         assert!(validate_language_code("chi_sim").is_ok());
     }
 
+    #[test]
+    fn test_validate_language_code_vertical_models() {
+        assert!(validate_language_code("jpn_vert").is_ok());
+        assert!(validate_language_code("chi_tra_vert+jpn").is_ok());
+    }
+
     #[test]
     fn test_validate_language_code_multiple() {
         assert!(validate_language_code("eng+fra").is_ok());
@@ -0,0 +1,74 @@
+//! Right-to-left text ordering for PDF pages.
+//!
+//! Decides whether the RTL text of a page is stored in visual order by looking at
+//! glyph positions, and fixes up the page text accordingly. The text-level
+//! algorithms live in [`crate::text::bidi`].
+
+use crate::text::bidi::{contains_rtl, is_rtl_char, normalize_arabic_presentation_forms, reorder_visual_lines};
+use pdfium_render::prelude::*;
+use std::borrow::Cow;
+
+/// Minimum number of consecutive RTL glyph pairs needed to decide the page order.
+const MIN_RTL_PAIRS: usize = 3;
+
+/// Convert the RTL text of a page to logical order and normalized letters.
+///
+/// Text without RTL characters is returned unchanged (borrowed).
+pub fn fix_rtl_page_text<'a>(text: &PdfPageText<'_>, page_text: Cow<'a, str>) -> Cow<'a, str> {
+    if !contains_rtl(&page_text) {
+        return page_text;
+    }
+
+    let ordered = if is_visual_rtl_order(text) {
+        Cow::Owned(reorder_visual_lines(&page_text).into_owned())
+    } else {
+        page_text
+    };
+
+    match normalize_arabic_presentation_forms(&ordered) {
+        Cow::Borrowed(_) => ordered,
+        Cow::Owned(normalized) => Cow::Owned(normalized),
+    }
+}
+
+/// Whether the RTL glyphs of a page appear in left-to-right (visual) order.
+///
+/// In logical order, consecutive RTL letters on the same line move leftwards; in
+/// visual order they move rightwards.
+fn is_visual_rtl_order(text: &PdfPageText<'_>) -> bool {
+    let mut rightwards = 0usize;
+    let mut leftwards = 0usize;
+    let mut previous: Option<(f32, f32, f32)> = None;
+
+    for pdf_char in text.chars().iter() {
+        let rtl_letter = pdf_char
+            .unicode_char()
+            .is_some_and(|c| c.is_alphabetic() && is_rtl_char(c));
+        if !rtl_letter {
+            previous = None;
+            continue;
+        }
+
+        let Ok(bounds) = pdf_char.loose_bounds() else {
+            previous = None;
+            continue;
+        };
+        let center_x = (bounds.left().value + bounds.right().value) / 2.0;
+        let bottom = bounds.bottom().value;
+        let height = bounds.height().value.abs().max(1.0);
+
+        if let Some((prev_x, prev_bottom, prev_height)) = previous
+            && (bottom - prev_bottom).abs() < prev_height.max(height) / 2.0
+        {
+            if center_x > prev_x {
+                rightwards += 1;
+            } else if center_x < prev_x {
+                leftwards += 1;
+            }
+        }
+
+        previous = Some((center_x, bottom, height));
+    }
+
+    rightwards >= MIN_RTL_PAIRS && rightwards > leftwards
+}
@@ -0,0 +1,339 @@
+//! CJK layout handling for PDF pages.
+//!
+//! Pdfium orders the characters of a page as horizontal lines, which breaks
+//! vertically set (tategaki) Japanese and Chinese text: characters of adjacent
+//! columns are interleaved. Ruby (furigana) set in a smaller font next to the base
+//! text is interleaved as well.
+//!
+//! This module rebuilds the text of such pages from glyph positions: columns are
+//! read top-to-bottom and right-to-left, and ruby is stripped, inlined or kept
+//! according to [`RubyMode`].
+
+use crate::core::config::RubyMode;
+use pdfium_render::prelude::*;
+use std::borrow::Cow;
+
+/// Ruby glyphs are at most this fraction of the median CJK font size.
+const RUBY_FONT_RATIO: f32 = 0.7;
+
+/// Minimum number of consecutive glyph moves needed to decide the writing direction.
+const MIN_DIRECTION_MOVES: usize = 3;
+
+/// A glyph with its position, in PDF coordinates (y grows upwards).
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct LayoutChar {
+    pub ch: char,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+    pub font_size: f32,
+}
+
+/// Whether a character belongs to a CJK script or CJK punctuation block.
+pub(crate) fn is_cjk(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x3000..=0x30FF | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF | 0xFF00..=0xFFEF | 0xAC00..=0xD7AF
+    )
+}
+
+fn is_kana(c: char) -> bool {
+    matches!(c as u32, 0x3041..=0x30FF | 0xFF66..=0xFF9F)
+}
+
+/// Fix reading order and ruby handling of a page with CJK text.
+///
+/// Pages without CJK text, and horizontal pages without ruby (or with
+/// [`RubyMode::Keep`]), are returned unchanged.
+pub fn fix_cjk_page_text<'a>(text: &PdfPageText<'_>, page_text: Cow<'a, str>, ruby: RubyMode) -> Cow<'a, str> {
+    if !page_text.chars().any(is_cjk) {
+        return page_text;
+    }
+
+    let chars = collect_layout_chars(text);
+    match layout_cjk_text(&chars, ruby) {
+        Some(rebuilt) => Cow::Owned(rebuilt),
+        None => page_text,
+    }
+}
+
+fn collect_layout_chars(text: &PdfPageText<'_>) -> Vec<LayoutChar> {
+    text.chars()
+        .iter()
+        .filter_map(|pdf_char| {
+            let ch = pdf_char.unicode_char()?;
+            if ch.is_whitespace() || ch.is_control() {
+                return None;
+            }
+            let bounds = pdf_char.loose_bounds().ok()?;
+            Some(LayoutChar {
+                ch,
+                x: (bounds.left().value + bounds.right().value) / 2.0,
+                y: (bounds.bottom().value + bounds.top().value) / 2.0,
+                width: bounds.width().value.abs(),
+                height: bounds.height().value.abs(),
+                font_size: pdf_char.scaled_font_size().value,
+            })
+        })
+        .collect()
+}
+
+/// Rebuild page text from glyphs, or return `None` when the original order is fine.
+pub(crate) fn layout_cjk_text(chars: &[LayoutChar], ruby: RubyMode) -> Option<String> {
+    let vertical = is_vertical_layout(chars);
+
+    let ruby_threshold = if ruby == RubyMode::Keep {
+        None
+    } else {
+        median(chars.iter().filter(|c| is_cjk(c.ch)).map(|c| c.font_size)).map(|m| m * RUBY_FONT_RATIO)
+    };
+    let is_ruby = |c: &LayoutChar| ruby_threshold.is_some_and(|t| is_kana(c.ch) && c.font_size < t);
+
+    let (ruby_chars, base_chars): (Vec<LayoutChar>, Vec<LayoutChar>) = chars.iter().partition(|c| is_ruby(c));
+
+    if !vertical && ruby_chars.is_empty() {
+        return None;
+    }
+
+    let base_lines = group_lines(&base_chars, vertical);
+
+    // Ruby annotations inserted after a base character: (line, index) -> ruby text
+    let mut annotations: Vec<((usize, usize), String)> = Vec::new();
+    if ruby == RubyMode::Inline {
+        for run in group_lines(&ruby_chars, vertical)
+            .iter()
+            .flat_map(|line| split_runs(line, vertical))
+        {
+            if let Some(anchor) = find_ruby_anchor(&run, &base_lines, vertical) {
+                annotations.push((anchor, run.iter().map(|c| c.ch).collect()));
+            }
+        }
+    }
+
+    let mut output = String::new();
+    for (line_idx, line) in base_lines.iter().enumerate() {
+        if line_idx > 0 {
+            output.push('\n');
+        }
+        for (char_idx, c) in line.iter().enumerate() {
+            if char_idx > 0 && needs_space(&line[char_idx - 1], c, vertical) {
+                output.push(' ');
+            }
+            output.push(c.ch);
+            for (_, ruby_text) in annotations.iter().filter(|(anchor, _)| *anchor == (line_idx, char_idx)) {
+                output.push('(');
+                output.push_str(ruby_text);
+                output.push(')');
+            }
+        }
+    }
+
+    Some(output)
+}
+
+/// Whether consecutive CJK glyphs mostly move downwards (vertical writing).
+fn is_vertical_layout(chars: &[LayoutChar]) -> bool {
+    let mut down = 0usize;
+    let mut across = 0usize;
+
+    for pair in chars.windows(2) {
+        let (a, b) = (&pair[0], &pair[1]);
+        if !is_cjk(a.ch) || !is_cjk(b.ch) {
+            continue;
+        }
+        let size = a.font_size.max(b.font_size).max(1.0);
+        let dx = b.x - a.x;
+        let dy = b.y - a.y;
+        if dx.abs() < size / 2.0 && dy < 0.0 {
+            down += 1;
+        } else if dy.abs() < size / 2.0 && dx > 0.0 {
+            across += 1;
+        }
+    }
+
+    down >= MIN_DIRECTION_MOVES && down > across
+}
+
+/// Group glyphs into lines (horizontal) or columns (vertical) in reading order.
+fn group_lines(chars: &[LayoutChar], vertical: bool) -> Vec<Vec<LayoutChar>> {
+    let mut sorted: Vec<LayoutChar> = chars.to_vec();
+    // Lines top-to-bottom, columns right-to-left
+    if vertical {
+        sorted.sort_by(|a, b| b.x.total_cmp(&a.x));
+    } else {
+        sorted.sort_by(|a, b| b.y.total_cmp(&a.y));
+    }
+
+    let mut lines: Vec<Vec<LayoutChar>> = Vec::new();
+    for c in sorted {
+        let cross = if vertical { c.x } else { c.y };
+        let tolerance = if vertical { c.width } else { c.height }.max(1.0) / 2.0;
+        match lines.last_mut() {
+            Some(line) if (line_cross(line, vertical) - cross).abs() <= tolerance => line.push(c),
+            _ => lines.push(vec![c]),
+        }
+    }
+
+    for line in &mut lines {
+        if vertical {
+            line.sort_by(|a, b| b.y.total_cmp(&a.y));
+        } else {
+            line.sort_by(|a, b| a.x.total_cmp(&b.x));
+        }
+    }
+    lines
+}
+
+/// Whether a word space separates two glyphs of a line (only outside CJK text).
+fn needs_space(prev: &LayoutChar, next: &LayoutChar, vertical: bool) -> bool {
+    if is_cjk(prev.ch) && is_cjk(next.ch) {
+        return false;
+    }
+    let extent = |c: &LayoutChar| if vertical { c.height } else { c.width } / 2.0;
+    let gap = flow(next, vertical) - flow(prev, vertical) - extent(prev) - extent(next);
+    gap > prev.font_size.max(next.font_size) * 0.25
+}
+
+fn line_cross(line: &[LayoutChar], vertical: bool) -> f32 {
+    let sum: f32 = line.iter().map(|c| if vertical { c.x } else { c.y }).sum();
+    sum / line.len() as f32
+}
+
+/// Position along the reading direction (increasing in reading order).
+fn flow(c: &LayoutChar, vertical: bool) -> f32 {
+    if vertical { -c.y } else { c.x }
+}
+
+/// Split a ruby line into runs belonging to separate base words.
+fn split_runs(line: &[LayoutChar], vertical: bool) -> Vec<Vec<LayoutChar>> {
+    let mut runs: Vec<Vec<LayoutChar>> = Vec::new();
+    for c in line {
+        match runs.last_mut() {
+            Some(run)
+                if run
+                    .last()
+                    .is_some_and(|prev| flow(c, vertical) - flow(prev, vertical) <= prev.font_size.max(1.0) * 1.5) =>
+            {
+                run.push(*c)
+            }
+            _ => runs.push(vec![*c]),
+        }
+    }
+    runs
+}
+
+/// Find the base glyph a ruby run annotates: the last glyph of the nearest line
+/// that overlaps the run along the reading direction.
+fn find_ruby_anchor(run: &[LayoutChar], base_lines: &[Vec<LayoutChar>], vertical: bool) -> Option<(usize, usize)> {
+    let start = run.iter().map(|c| flow(c, vertical)).fold(f32::INFINITY, f32::min);
+    let end = run.iter().map(|c| flow(c, vertical)).fold(f32::NEG_INFINITY, f32::max);
+    let cross = line_cross(run, vertical);
+    let slack = run[0].font_size.max(1.0);
+
+    base_lines
+        .iter()
+        .enumerate()
+        .filter_map(|(line_idx, line)| {
+            let last_overlap = line.iter().rposition(|c| {
+                let half = c.font_size.max(1.0) / 2.0;
+                let pos = flow(c, vertical);
+                pos + half >= start - slack / 2.0 && pos - half <= end + slack / 2.0
+            })?;
+            Some(((line_idx, last_overlap), (line_cross(line, vertical) - cross).abs()))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(anchor, _)| anchor)
+}
+
+fn median(values: impl Iterator<Item = f32>) -> Option<f32> {
+    let mut values: Vec<f32> = values.collect();
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(|a, b| a.total_cmp(b));
+    Some(values[values.len() / 2])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn glyph(ch: char, x: f32, y: f32, size: f32) -> LayoutChar {
+        LayoutChar {
+            ch,
+            x,
+            y,
+            width: size,
+            height: size,
+            font_size: size,
+        }
+    }
+
+    /// Two vertical columns, with the left column first in content order.
+    fn vertical_page() -> Vec<LayoutChar> {
+        vec![
+            glyph('す', 80.0, 700.0, 10.0),
+            glyph('ね', 80.0, 690.0, 10.0),
+            glyph('日', 100.0, 700.0, 10.0),
+            glyph('本', 100.0, 690.0, 10.0),
+            glyph('語', 100.0, 680.0, 10.0),
+            glyph('で', 100.0, 670.0, 10.0),
+        ]
+    }
+
+    #[test]
+    fn test_vertical_columns_read_right_to_left() {
+        let text = layout_cjk_text(&vertical_page(), RubyMode::Strip).unwrap();
+        assert_eq!(text, "日本語で\nすね");
+    }
+
+    #[test]
+    fn test_horizontal_without_ruby_untouched() {
+        let chars = vec![
+            glyph('東', 10.0, 700.0, 10.0),
+            glyph('京', 20.0, 700.0, 10.0),
+            glyph('都', 30.0, 700.0, 10.0),
+        ];
+        assert!(layout_cjk_text(&chars, RubyMode::Strip).is_none());
+    }
+
+    fn horizontal_with_ruby() -> Vec<LayoutChar> {
+        vec![
+            glyph('漢', 10.0, 700.0, 10.0),
+            glyph('か', 8.0, 708.0, 5.0),
+            glyph('ん', 13.0, 708.0, 5.0),
+            glyph('字', 20.0, 700.0, 10.0),
+            glyph('じ', 20.0, 708.0, 5.0),
+            glyph('を', 30.0, 700.0, 10.0),
+            glyph('書', 40.0, 700.0, 10.0),
+        ]
+    }
+
+    #[test]
+    fn test_ruby_strip() {
+        let text = layout_cjk_text(&horizontal_with_ruby(), RubyMode::Strip).unwrap();
+        assert_eq!(text, "漢字を書");
+    }
+
+    #[test]
+    fn test_ruby_inline() {
+        let text = layout_cjk_text(&horizontal_with_ruby(), RubyMode::Inline).unwrap();
+        assert_eq!(text, "漢字(かんじ)を書");
+    }
+
+    #[test]
+    fn test_latin_words_keep_spaces() {
+        let mut chars = horizontal_with_ruby();
+        chars.push(glyph('P', 60.0, 700.0, 10.0));
+        chars.push(glyph('D', 68.0, 700.0, 10.0));
+        chars.push(glyph('F', 76.0, 700.0, 10.0));
+        let text = layout_cjk_text(&chars, RubyMode::Strip).unwrap();
+        assert_eq!(text, "漢字を書 PDF");
+    }
+
+    #[test]
+    fn test_ruby_keep_leaves_horizontal_text() {
+        assert!(layout_cjk_text(&horizontal_with_ruby(), RubyMode::Keep).is_none());
+    }
+}
@@ -0,0 +1,114 @@
+//! PDF/A and PDF/UA conformance claims.
+//!
+//! Documents declare the standards they conform to in their XMP metadata, with the
+//! `pdfaid:part`, `pdfaid:conformance` and `pdfuaid:part` properties. PDF/A does not
+//! allow the metadata stream to be compressed, so the packets are found in the file
+//! bytes without decoding streams. A claim is what the writer declares; the document
+//! is not checked against the standard.
+
+use crate::core::config::{ConformanceLevel, PdfStandard};
+use memchr::memmem;
+
+/// Conformance levels declared in a document's XMP metadata.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConformanceClaims {
+    /// Declared PDF/A level
+    pub pdfa: Option<ConformanceLevel>,
+    /// Declared PDF/UA level
+    pub pdfua: Option<ConformanceLevel>,
+}
+
+/// Read the conformance claims of a PDF from its XMP packets.
+///
+/// Incremental updates append new metadata, so later packets override earlier ones.
+pub fn conformance_claims(pdf_bytes: &[u8]) -> ConformanceClaims {
+    let mut claims = ConformanceClaims::default();
+
+    let mut rest = pdf_bytes;
+    while let Some(start) = memmem::find(rest, b"<x:xmpmeta") {
+        let packet = &rest[start..];
+        let end = memmem::find(packet, b"</x:xmpmeta>").unwrap_or(packet.len());
+        let packet = &packet[..end];
+
+        if let Some(part) = property(packet, b"pdfaid:part").and_then(|part| part.parse().ok()) {
+            claims.pdfa = Some(ConformanceLevel {
+                standard: PdfStandard::PdfA,
+                part: Some(part),
+                level: property(packet, b"pdfaid:conformance")
+                    .filter(|level| level.len() == 1)
+                    .and_then(|level| level.to_ascii_lowercase().chars().next()),
+            });
+        }
+        if let Some(part) = property(packet, b"pdfuaid:part").and_then(|part| part.parse().ok()) {
+            claims.pdfua = Some(ConformanceLevel {
+                standard: PdfStandard::PdfUa,
+                part: Some(part),
+                level: None,
+            });
+        }
+
+        rest = &rest[start + end..];
+    }
+
+    claims
+}
+
+/// Value of an XMP simple property, written either as an attribute (`name="1"`) or as
+/// an element (`<name>1</name>`).
+fn property<'a>(packet: &'a [u8], name: &[u8]) -> Option<&'a str> {
+    for position in memmem::find_iter(packet, name) {
+        let after = &packet[position + name.len()..];
+        let trimmed = after.trim_ascii_start();
+        let value = if let Some(attribute) = trimmed.strip_prefix(b"=") {
+            let attribute = attribute.trim_ascii_start();
+            let quote = *attribute.first()?;
+            if quote != b'"' && quote != b'\'' {
+                continue;
+            }
+            let value = &attribute[1..];
+            &value[..memchr::memchr(quote, value)?]
+        } else if let Some(element) = after.strip_prefix(b">") {
+            &element[..memchr::memchr(b'<', element)?]
+        } else {
+            continue;
+        };
+
+        if let Ok(value) = std::str::from_utf8(value.trim_ascii())
+            && !value.is_empty()
+        {
+            return Some(value);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conformance_claims_from_attributes_and_elements() {
+        let pdf = br#"%PDF-1.7
+<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:Description rdf:about=""
+    pdfaid:part="2" pdfaid:conformance="B"/></x:xmpmeta>
+<x:xmpmeta xmlns:x="adobe:ns:meta/"><rdf:Description rdf:about="">
+    <pdfaid:part>3</pdfaid:part><pdfaid:conformance>A</pdfaid:conformance>
+    <pdfuaid:part>1</pdfuaid:part></rdf:Description></x:xmpmeta>"#;
+
+        let claims = conformance_claims(pdf);
+        assert_eq!(claims.pdfa.unwrap().to_string(), "PDF/A-3a");
+        assert_eq!(claims.pdfua.unwrap().to_string(), "PDF/UA-1");
+    }
+
+    #[test]
+    fn test_extension_schema_is_not_a_claim() {
+        let pdf = br#"<x:xmpmeta><pdfaExtension:schemas><rdf:li>
+    <pdfaSchema:prefix>pdfaid</pdfaSchema:prefix>
+    <pdfaProperty:name>part</pdfaProperty:name></rdf:li></pdfaExtension:schemas></x:xmpmeta>"#;
+        assert_eq!(conformance_claims(pdf), ConformanceClaims::default());
+        assert_eq!(
+            conformance_claims(b"%PDF-1.4 no metadata"),
+            ConformanceClaims::default()
+        );
+    }
+}
@@ -8,7 +8,9 @@ use serde::{Deserialize, Serialize};
 ///
 /// Contains metadata fields specific to PDF documents that are not in the common
 /// `Metadata` structure. Common fields like title, authors, keywords, and dates
-/// are now at the `Metadata` level.
+/// are now at the `Metadata` level. `producer` and `page_count` are only set here by
+/// [`extract_metadata`]; in extraction results they are reported as
+/// `Metadata::producer` and `Metadata::page_count`.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
 pub struct PdfMetadata {
@@ -24,6 +26,14 @@ pub struct PdfMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub is_encrypted: Option<bool>,
 
+    /// PDF/A conformance declared in the XMP metadata (e.g., "PDF/A-2b")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pdfa_conformance: Option<String>,
+
+    /// PDF/UA conformance declared in the XMP metadata (e.g., "PDF/UA-1")
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pdfua_conformance: Option<String>,
+
     /// First page width in points (1/72 inch)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub width: Option<i64>,
@@ -37,6 +47,15 @@ pub struct PdfMetadata {
     pub page_count: Option<usize>,
 }
 
+impl PdfMetadata {
+    /// Record the conformance claims in the XMP metadata of `pdf_bytes`.
+    pub(crate) fn set_conformance_claims(&mut self, pdf_bytes: &[u8]) {
+        let claims = super::conformance::conformance_claims(pdf_bytes);
+        self.pdfa_conformance = claims.pdfa.map(|level| level.to_string());
+        self.pdfua_conformance = claims.pdfua.map(|level| level.to_string());
+    }
+}
+
 /// Complete PDF extraction metadata including common and PDF-specific fields.
 ///
 /// This struct combines common document fields (title, authors, dates) with
@@ -78,18 +97,28 @@ pub struct PdfExtractionMetadata {
     /// Page structure with boundaries and optional per-page metadata
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page_structure: Option<PageStructure>,
+
+    /// Watermark text detected across pages (populated when `cleanup` is configured)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub watermarks: Vec<String>,
+
+    /// Redactions detected on the pages (populated when `pdf_options.redaction` is configured)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redactions: Vec<super::redaction::PdfRedaction>,
 }
 
 /// Extract PDF-specific metadata from raw bytes.
 ///
-/// Returns only PDF-specific metadata (version, producer, encryption status, dimensions).
+/// Returns only PDF-specific metadata (version, producer, encryption status, conformance
+/// claims, dimensions).
 pub fn extract_metadata(pdf_bytes: &[u8]) -> Result<PdfMetadata> {
     extract_metadata_with_password(pdf_bytes, None)
 }
 
 /// Extract PDF-specific metadata from raw bytes with optional password.
 ///
-/// Returns only PDF-specific metadata (version, producer, encryption status, dimensions).
+/// Returns only PDF-specific metadata (version, producer, encryption status, conformance
+/// claims, dimensions).
 pub fn extract_metadata_with_password(pdf_bytes: &[u8], password: Option<&str>) -> Result<PdfMetadata> {
     let pdfium = bind_pdfium(PdfError::MetadataExtractionFailed, "metadata extraction")?;
 
@@ -104,7 +133,9 @@ pub fn extract_metadata_with_password(pdf_bytes: &[u8], password: Option<&str>)
         }
     })?;
 
-    extract_pdf_specific_metadata(&document)
+    let mut metadata = extract_pdf_specific_metadata(&document)?;
+    metadata.set_conformance_claims(pdf_bytes);
+    Ok(metadata)
 }
 
 pub fn extract_metadata_with_passwords(pdf_bytes: &[u8], passwords: &[&str]) -> Result<PdfMetadata> {
@@ -174,6 +205,8 @@ pub(crate) fn extract_metadata_from_document_impl(
         created_by: common.created_by,
         pdf_specific,
         page_structure,
+        watermarks: Vec::new(),
+        redactions: Vec::new(),
     })
 }
 
@@ -221,42 +254,48 @@ fn extract_pdf_specific_metadata(document: &PdfDocument<'_>) -> Result<PdfMetada
 ///
 /// # Validation
 ///
-/// - Boundaries must not be empty
-/// - Boundary count must match the document's page count
+/// - Every boundary must refer to a page of the document. With a page selection, only
+///   the selected pages have boundaries, so there may be fewer boundaries than pages.
 fn build_page_structure(document: &PdfDocument<'_>, boundaries: &[PageBoundary]) -> Result<PageStructure> {
-    let total_count = document.pages().len() as usize;
-
-    if boundaries.is_empty() {
-        return Err(PdfError::MetadataExtractionFailed(
-            "No page boundaries provided for PageStructure".to_string(),
-        ));
-    }
+    page_structure(document.pages().len() as usize, boundaries, |page_number| {
+        document
+            .pages()
+            .page_size((page_number - 1) as i32)
+            .ok()
+            .map(|page_rect| (page_rect.width().value as f64, page_rect.height().value as f64))
+    })
+}
 
-    if boundaries.len() != total_count {
+/// Build a PageStructure of a document of `total_count` pages from its page boundaries
+/// and the `dimensions` of its pages.
+fn page_structure(
+    total_count: usize,
+    boundaries: &[PageBoundary],
+    dimensions: impl Fn(usize) -> Option<(f64, f64)>,
+) -> Result<PageStructure> {
+    if let Some(boundary) = boundaries
+        .iter()
+        .find(|boundary| boundary.page_number == 0 || boundary.page_number > total_count)
+    {
         return Err(PdfError::MetadataExtractionFailed(format!(
-            "Boundary count {} doesn't match page count {}",
-            boundaries.len(),
-            total_count
+            "Boundary for page {} doesn't match page count {}",
+            boundary.page_number, total_count
         )));
     }
 
     let mut pages = Vec::new();
-    for (index, boundary) in boundaries.iter().enumerate() {
+    for boundary in boundaries {
         let page_number = boundary.page_number;
 
-        let dimensions = if let Ok(page_rect) = document.pages().page_size(index as i32) {
-            Some((page_rect.width().value as f64, page_rect.height().value as f64))
-        } else {
-            None
-        };
-
         pages.push(PageInfo {
             number: page_number,
             title: None,
-            dimensions,
+            dimensions: dimensions(page_number),
             image_count: None,
             table_count: None,
             hidden: None,
+            rotation: None,
+            blank: None,
         });
     }
 
@@ -268,6 +307,91 @@ fn build_page_structure(document: &PdfDocument<'_>, boundaries: &[PageBoundary])
     })
 }
 
+/// Extract the metadata of a document parsed with lopdf, with the page structure of
+/// `page_boundaries` when given.
+///
+/// Conformance claims, watermarks and redactions are not read.
+pub(crate) fn extract_metadata_from_lopdf(
+    document: &lopdf::Document,
+    page_boundaries: Option<&[PageBoundary]>,
+) -> Result<PdfExtractionMetadata> {
+    let info = document
+        .trailer
+        .get_deref(b"Info", document)
+        .and_then(lopdf::Object::as_dict)
+        .ok();
+    let tag = |key: &[u8]| {
+        info?
+            .get_deref(key, document)
+            .ok()
+            .and_then(|value| lopdf::decode_text_string(value).ok())
+            .filter(|value| !value.trim().is_empty())
+    };
+    let pages = document.get_pages();
+    let dimensions = |page_number: usize| {
+        pages
+            .get(&(page_number as u32))
+            .and_then(|page_id| lopdf_page_size(document, *page_id))
+    };
+
+    let first_page = dimensions(1);
+    let pdf_specific = PdfMetadata {
+        pdf_version: Some(document.version.clone()),
+        producer: tag(b"Producer"),
+        is_encrypted: Some(document.was_encrypted()),
+        width: first_page.map(|(width, _)| width.round() as i64),
+        height: first_page.map(|(_, height)| height.round() as i64),
+        page_count: Some(pages.len()),
+        ..Default::default()
+    };
+    let page_structure = match page_boundaries {
+        Some(boundaries) => Some(page_structure(pages.len(), boundaries, dimensions)?),
+        None => None,
+    };
+
+    Ok(PdfExtractionMetadata {
+        title: tag(b"Title"),
+        subject: tag(b"Subject"),
+        authors: tag(b"Author")
+            .map(|authors| parse_authors(&authors))
+            .filter(|authors| !authors.is_empty()),
+        keywords: tag(b"Keywords")
+            .map(|keywords| parse_keywords(&keywords))
+            .filter(|keywords| !keywords.is_empty()),
+        created_at: tag(b"CreationDate").map(|date| parse_pdf_date(&date)),
+        modified_at: tag(b"ModDate").map(|date| parse_pdf_date(&date)),
+        created_by: tag(b"Creator"),
+        pdf_specific,
+        page_structure,
+        watermarks: Vec::new(),
+        redactions: Vec::new(),
+    })
+}
+
+/// Width and height of a page in points, from its media box or the one it inherits.
+fn lopdf_page_size(document: &lopdf::Document, page_id: lopdf::ObjectId) -> Option<(f64, f64)> {
+    let mut node = document.get_dictionary(page_id).ok()?;
+    // Bounded, as a malformed page tree may have a cycle of parents.
+    for _ in 0..32 {
+        if let Ok(media_box) = node.get_deref(b"MediaBox", document).and_then(lopdf::Object::as_array) {
+            let coordinates: Vec<f64> = media_box
+                .iter()
+                .filter_map(|value| document.dereference(value).ok()?.1.as_float().ok())
+                .map(f64::from)
+                .collect();
+            return match coordinates[..] {
+                [x0, y0, x1, y1] => Some(((x1 - x0).abs(), (y1 - y0).abs())),
+                _ => None,
+            };
+        }
+        node = node
+            .get_deref(b"Parent", document)
+            .and_then(lopdf::Object::as_dict)
+            .ok()?;
+    }
+    None
+}
+
 /// Extract common metadata from a PDF document.
 ///
 /// Returns common fields (title, authors, keywords, dates) that are now stored
@@ -491,20 +615,14 @@ mod tests {
         assert!(result.is_err());
     }
 
-    #[test]
-    fn test_build_page_structure_empty_boundaries() {
-        let result_msg = "No page boundaries provided for PageStructure".to_string();
-        assert!(!result_msg.is_empty());
-    }
-
     #[test]
     fn test_build_page_structure_boundary_mismatch_message() {
-        let boundaries_count = 3;
+        let page_number = 6;
         let page_count = 5;
         let error_msg = format!(
-            "Boundary count {} doesn't match page count {}",
-            boundaries_count, page_count
+            "Boundary for page {} doesn't match page count {}",
+            page_number, page_count
         );
-        assert_eq!(error_msg, "Boundary count 3 doesn't match page count 5");
+        assert_eq!(error_msg, "Boundary for page 6 doesn't match page count 5");
     }
 }
@@ -10,6 +10,7 @@
 //! - **Metadata extraction**: Parse PDF metadata (title, author, creation date, etc.)
 //! - **Image extraction**: Extract embedded images from PDF pages
 //! - **Page rendering**: Render PDF pages to images for OCR processing
+//! - **Text layer probe**: Tell which pages have text before deciding on OCR
 //! - **Error handling**: Comprehensive PDF-specific error types
 //!
 //! # Example
@@ -36,10 +37,16 @@
 //! This module requires the `pdf` feature. The `ocr` feature enables additional
 //! functionality in the PDF extractor for rendering pages to images.
 #[cfg(feature = "pdf")]
+pub mod bidi;
+#[cfg(feature = "pdf")]
 pub(crate) mod bindings;
 #[cfg(all(feature = "pdf", feature = "bundled-pdfium"))]
 pub mod bundled;
 #[cfg(feature = "pdf")]
+pub mod cjk;
+#[cfg(feature = "pdf")]
+pub mod conformance;
+#[cfg(feature = "pdf")]
 pub mod error;
 #[cfg(feature = "pdf")]
 pub mod fonts;
@@ -50,11 +57,17 @@ pub mod images;
 #[cfg(feature = "pdf")]
 pub mod metadata;
 #[cfg(feature = "pdf")]
+pub mod redaction;
+#[cfg(feature = "pdf")]
 pub mod rendering;
 #[cfg(feature = "pdf")]
 pub mod table;
 #[cfg(feature = "pdf")]
 pub mod text;
+#[cfg(feature = "pdf")]
+pub mod text_layer;
+#[cfg(feature = "pdf")]
+pub mod watermark;
 
 #[cfg(feature = "pdf")]
 pub use crate::core::config::HierarchyConfig;
@@ -74,8 +87,12 @@ pub use images::{PdfImage, PdfImageExtractor, extract_images_from_pdf};
 #[cfg(feature = "pdf")]
 pub use metadata::extract_metadata;
 #[cfg(feature = "pdf")]
+pub use redaction::{PdfRedaction, RedactionKind};
+#[cfg(feature = "pdf")]
 pub use rendering::{PageRenderOptions, render_page_to_image};
 #[cfg(feature = "pdf")]
 pub use table::extract_words_from_page;
 #[cfg(feature = "pdf")]
 pub use text::extract_text_from_pdf;
+#[cfg(feature = "pdf")]
+pub use text_layer::{TextLayerReport, has_text_layer, has_text_layer_from_bytes};
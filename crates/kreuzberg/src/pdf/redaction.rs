@@ -0,0 +1,343 @@
+//! PDF redaction detection.
+//!
+//! Finds redaction annotations and filled black rectangles painted over text, and
+//! the characters underneath them that are still present in the content stream.
+//! A correctly applied redaction removes the text; a box drawn on top of it only
+//! hides it visually, so the "redacted" text leaks into any text extraction.
+
+use super::error::{PdfError, Result};
+use crate::core::config::RedactionConfig;
+use pdfium_render::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Channel value (0-255) at or below which a fill color counts as black.
+const MAX_DARK_CHANNEL: u8 = 48;
+
+/// Minimum alpha (0-255) of a fill that hides the text underneath.
+const MIN_OPAQUE_ALPHA: u8 = 200;
+
+/// Minimum width and height of a redaction box in points.
+const MIN_BOX_SIZE: f32 = 2.0;
+
+/// Boxes covering more than this fraction of the page are backgrounds, not redactions.
+const MAX_PAGE_COVERAGE: f32 = 0.5;
+
+/// How a redaction was applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RedactionKind {
+    /// A redaction annotation (`/Subtype /Redact`) that has not been applied
+    Annotation,
+    /// A filled black rectangle drawn over text
+    FilledRectangle,
+}
+
+/// A redacted region of a page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PdfRedaction {
+    /// Page number (1-indexed)
+    pub page_number: usize,
+    /// How the redaction was applied
+    pub kind: RedactionKind,
+    /// Region in PDF points as `[left, bottom, right, top]`
+    pub bounds: [f32; 4],
+    /// Number of characters under the redaction that are still extractable
+    pub covered_chars: usize,
+}
+
+/// Redactions of a document, used to suppress covered text during extraction.
+#[derive(Debug, Default)]
+pub struct DocumentRedactions {
+    /// Detected redactions in page order
+    pub redactions: Vec<PdfRedaction>,
+    /// Indices of covered characters per page (empty unless suppression is enabled)
+    covered_chars: Vec<Vec<usize>>,
+    marker: String,
+}
+
+impl DocumentRedactions {
+    /// Page text with covered characters replaced by the configured marker.
+    ///
+    /// Returns `None` when nothing on the page is suppressed.
+    pub(crate) fn suppressed_page_text(&self, page_index: usize, text: &PdfPageText<'_>) -> Option<String> {
+        let covered = self.covered_chars.get(page_index).filter(|c| !c.is_empty())?;
+        let chars = text
+            .chars()
+            .iter()
+            .filter_map(|pdf_char| {
+                let ch = pdf_char.unicode_char()?;
+                let index = pdf_char.index();
+                Some((ch, covered.binary_search(&index).is_ok()))
+            })
+            .collect::<Vec<_>>();
+        Some(replace_covered(chars, &self.marker))
+    }
+}
+
+/// Detect redactions on every page of a document.
+///
+/// Covered characters are tracked for suppression only when
+/// `config.suppress_covered_text` is set.
+pub fn detect_document_redactions(document: &PdfDocument<'_>, config: &RedactionConfig) -> Result<DocumentRedactions> {
+    let mut result = DocumentRedactions {
+        marker: config.marker.clone(),
+        ..Default::default()
+    };
+
+    for (page_index, page) in document.pages().iter().enumerate() {
+        let mut regions: Vec<(RedactionKind, PdfRect)> = page
+            .annotations()
+            .iter()
+            .filter(|annotation| annotation.annotation_type() == PdfPageAnnotationType::Redacted)
+            .filter_map(|annotation| annotation.bounds().ok())
+            .map(|bounds| (RedactionKind::Annotation, bounds))
+            .collect();
+
+        if config.detect_filled_rectangles {
+            let page_area = page.width().value * page.height().value;
+            regions.extend(
+                filled_boxes_over_text(&page, page_area)
+                    .into_iter()
+                    .map(|bounds| (RedactionKind::FilledRectangle, bounds)),
+            );
+        }
+
+        if regions.is_empty() {
+            if config.suppress_covered_text {
+                result.covered_chars.push(Vec::new());
+            }
+            continue;
+        }
+
+        let text = page
+            .text()
+            .map_err(|e| PdfError::TextExtractionFailed(format!("Page text extraction failed: {}", e)))?;
+
+        let mut counts = vec![0usize; regions.len()];
+        let mut covered = Vec::new();
+        for pdf_char in text.chars().iter() {
+            if pdf_char.unicode_char().is_none_or(|c| c.is_whitespace()) {
+                continue;
+            }
+            let Ok(bounds) = pdf_char.loose_bounds() else {
+                continue;
+            };
+            // Light text on a black box is a visible label, not hidden text.
+            if pdf_char.fill_color().is_ok_and(|color| !is_dark(&color)) {
+                continue;
+            }
+
+            let center = (
+                (bounds.left().value + bounds.right().value) / 2.0,
+                (bounds.bottom().value + bounds.top().value) / 2.0,
+            );
+            let mut is_covered = false;
+            for (count, (_, region)) in counts.iter_mut().zip(&regions) {
+                if contains(&to_array(region), center) {
+                    *count += 1;
+                    is_covered = true;
+                }
+            }
+            if is_covered {
+                covered.push(pdf_char.index());
+            }
+        }
+
+        for ((kind, region), covered_chars) in regions.iter().zip(counts) {
+            result.redactions.push(PdfRedaction {
+                page_number: page_index + 1,
+                kind: *kind,
+                bounds: to_array(region),
+                covered_chars,
+            });
+        }
+        if config.suppress_covered_text {
+            result.covered_chars.push(covered);
+        }
+    }
+
+    Ok(result)
+}
+
+/// Human-readable warnings for pages where redacted text is still extractable.
+pub fn redaction_warnings(redactions: &[PdfRedaction], suppressed: bool) -> Vec<String> {
+    let mut warnings = Vec::new();
+    let mut index = 0;
+    while index < redactions.len() {
+        let page_number = redactions[index].page_number;
+        let page: Vec<&PdfRedaction> = redactions[index..]
+            .iter()
+            .take_while(|r| r.page_number == page_number)
+            .collect();
+        index += page.len();
+
+        let leaking: Vec<&&PdfRedaction> = page.iter().filter(|r| r.covered_chars > 0).collect();
+        if leaking.is_empty() {
+            continue;
+        }
+        let chars: usize = leaking.iter().map(|r| r.covered_chars).sum();
+        let action = if suppressed {
+            "were removed from the content"
+        } else {
+            "are included in the content"
+        };
+        warnings.push(format!(
+            "page {}: {} character(s) under {} redaction(s) are still extractable and {}",
+            page_number,
+            chars,
+            leaking.len(),
+            action
+        ));
+    }
+    warnings
+}
+
+/// Filled black path objects painted after (on top of) text they overlap.
+fn filled_boxes_over_text(page: &PdfPage<'_>, page_area: f32) -> Vec<PdfRect> {
+    let mut text_bounds: Vec<[f32; 4]> = Vec::new();
+    let mut boxes = Vec::new();
+
+    for object in page.objects().iter() {
+        let Ok(quad) = object.bounds() else {
+            continue;
+        };
+        let bounds = [
+            quad.left().value,
+            quad.bottom().value,
+            quad.right().value,
+            quad.top().value,
+        ];
+
+        if object.as_text_object().is_some() {
+            text_bounds.push(bounds);
+            continue;
+        }
+
+        let Some(path) = object.as_path_object() else {
+            continue;
+        };
+        let filled = path.fill_mode().is_ok_and(|mode| mode != PdfPathFillMode::None);
+        let dark = path
+            .fill_color()
+            .is_ok_and(|color| is_dark(&color) && color.alpha() >= MIN_OPAQUE_ALPHA);
+        if filled && dark && is_redaction_box(&bounds, page_area) && text_bounds.iter().any(|t| overlaps(t, &bounds)) {
+            boxes.push(PdfRect::new_from_values(bounds[1], bounds[0], bounds[3], bounds[2]));
+        }
+    }
+
+    boxes
+}
+
+fn is_dark(color: &PdfColor) -> bool {
+    color.red() <= MAX_DARK_CHANNEL && color.green() <= MAX_DARK_CHANNEL && color.blue() <= MAX_DARK_CHANNEL
+}
+
+fn is_redaction_box(bounds: &[f32; 4], page_area: f32) -> bool {
+    let width = bounds[2] - bounds[0];
+    let height = bounds[3] - bounds[1];
+    width >= MIN_BOX_SIZE
+        && height >= MIN_BOX_SIZE
+        && (page_area <= 0.0 || width * height <= page_area * MAX_PAGE_COVERAGE)
+}
+
+fn to_array(rect: &PdfRect) -> [f32; 4] {
+    [
+        rect.left().value,
+        rect.bottom().value,
+        rect.right().value,
+        rect.top().value,
+    ]
+}
+
+fn contains(bounds: &[f32; 4], (x, y): (f32, f32)) -> bool {
+    x >= bounds[0] && x <= bounds[2] && y >= bounds[1] && y <= bounds[3]
+}
+
+fn overlaps(a: &[f32; 4], b: &[f32; 4]) -> bool {
+    a[0] < b[2] && b[0] < a[2] && a[1] < b[3] && b[1] < a[3]
+}
+
+/// Rebuild text, replacing each run of covered characters with `marker`.
+///
+/// Whitespace between covered characters belongs to the run, so a redacted
+/// phrase yields a single marker.
+fn replace_covered(chars: Vec<(char, bool)>, marker: &str) -> String {
+    let mut output = String::with_capacity(chars.len());
+    let mut in_run = false;
+    let mut pending_whitespace = String::new();
+
+    for (ch, covered) in chars {
+        if covered {
+            if !in_run {
+                output.push_str(&pending_whitespace);
+                output.push_str(marker);
+                in_run = true;
+            }
+            pending_whitespace.clear();
+        } else if ch.is_whitespace() && ch != '\n' && ch != '\r' {
+            pending_whitespace.push(ch);
+        } else {
+            output.push_str(&pending_whitespace);
+            pending_whitespace.clear();
+            output.push(ch);
+            in_run = false;
+        }
+    }
+
+    output.push_str(&pending_whitespace);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chars(text: &str, covered: &[usize]) -> Vec<(char, bool)> {
+        text.chars()
+            .enumerate()
+            .map(|(i, c)| (c, covered.contains(&i)))
+            .collect()
+    }
+
+    #[test]
+    fn test_replace_covered_merges_runs() {
+        let text = "SSN: 123 45 6789 on file";
+        let covered: Vec<usize> = (5..16).filter(|&i| text.as_bytes()[i] != b' ').collect();
+        assert_eq!(
+            replace_covered(chars(text, &covered), "[REDACTED]"),
+            "SSN: [REDACTED] on file"
+        );
+        assert_eq!(replace_covered(chars(text, &covered), ""), "SSN:  on file");
+    }
+
+    #[test]
+    fn test_replace_covered_keeps_line_breaks() {
+        let text = "ab\r\ncd";
+        assert_eq!(replace_covered(chars(text, &[1, 4]), "#"), "a#\r\n#d");
+    }
+
+    #[test]
+    fn test_redaction_box_geometry() {
+        assert!(is_redaction_box(&[10.0, 10.0, 120.0, 22.0], 612.0 * 792.0));
+        assert!(!is_redaction_box(&[10.0, 10.0, 120.0, 11.0], 612.0 * 792.0));
+        assert!(!is_redaction_box(&[0.0, 0.0, 612.0, 792.0], 612.0 * 792.0));
+        assert!(overlaps(&[0.0, 0.0, 10.0, 10.0], &[5.0, 5.0, 15.0, 15.0]));
+        assert!(!overlaps(&[0.0, 0.0, 10.0, 10.0], &[10.0, 0.0, 15.0, 10.0]));
+    }
+
+    #[test]
+    fn test_redaction_warnings_per_page() {
+        let redaction = |page_number, covered_chars| PdfRedaction {
+            page_number,
+            kind: RedactionKind::FilledRectangle,
+            bounds: [0.0, 0.0, 10.0, 10.0],
+            covered_chars,
+        };
+        let redactions = vec![redaction(1, 0), redaction(2, 4), redaction(2, 3), redaction(3, 0)];
+
+        let warnings = redaction_warnings(&redactions, false);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].starts_with("page 2: 7 character(s) under 2 redaction(s)"));
+        assert!(redaction_warnings(&redactions, true)[0].ends_with("removed from the content"));
+    }
+}
@@ -103,6 +103,24 @@ impl PdfRenderer<'_> {
         Ok(DynamicImage::ImageRgb8(image))
     }
 
+    /// Get the width and height of every page in PDF points.
+    pub fn page_sizes(&self, pdf_bytes: &[u8]) -> Result<Vec<(f32, f32)>> {
+        let document = self.pdfium.load_pdf_from_byte_slice(pdf_bytes, None).map_err(|e| {
+            let err_msg = super::error::format_pdfium_error(e);
+            if err_msg.contains("password") || err_msg.contains("Password") {
+                PdfError::PasswordRequired
+            } else {
+                PdfError::InvalidPdf(err_msg)
+            }
+        })?;
+
+        Ok(document
+            .pages()
+            .iter()
+            .map(|page| (page.width().value, page.height().value))
+            .collect())
+    }
+
     pub fn render_all_pages(&self, pdf_bytes: &[u8], options: &PageRenderOptions) -> Result<Vec<DynamicImage>> {
         self.render_all_pages_with_password(pdf_bytes, options, None)
     }
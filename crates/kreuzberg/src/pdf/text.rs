@@ -2,10 +2,14 @@
 //!
 //! This module provides functions to extract text content from PDF files using the pdfium-render library.
 
+use super::bidi::fix_rtl_page_text;
 use super::bindings::{PdfiumHandle, bind_pdfium};
+use super::cjk::fix_cjk_page_text;
 use super::error::{PdfError, Result};
-use crate::core::config::PageConfig;
+use crate::core::config::{PageConfig, RubyMode};
 use crate::pdf::metadata::PdfExtractionMetadata;
+use crate::pdf::redaction::DocumentRedactions;
+use crate::text::watermark::strip_watermark_lines;
 use crate::types::{PageBoundary, PageContent};
 use pdfium_render::prelude::*;
 
@@ -139,9 +143,15 @@ pub fn extract_text_and_metadata_from_pdf_document(
     extraction_config: Option<&crate::core::config::ExtractionConfig>,
 ) -> Result<PdfUnifiedExtractionResult> {
     let page_config = extraction_config.and_then(|c| c.pages.as_ref());
-    let (text, boundaries, page_contents) = extract_text_from_pdf_document(document, page_config, extraction_config)?;
+    let watermarks = detect_configured_watermarks(document, extraction_config)?;
+    let strip = strip_list(extraction_config, &watermarks);
+    let redactions = detect_configured_redactions(document, extraction_config)?;
+    let (text, boundaries, page_contents) =
+        extract_text_impl(document, page_config, extraction_config, strip, &redactions)?;
 
-    let metadata = crate::pdf::metadata::extract_metadata_from_document_impl(document, boundaries.as_deref())?;
+    let mut metadata = crate::pdf::metadata::extract_metadata_from_document_impl(document, boundaries.as_deref())?;
+    metadata.watermarks = watermarks;
+    metadata.redactions = redactions.redactions;
 
     Ok((text, boundaries, page_contents, metadata))
 }
@@ -170,18 +180,85 @@ pub fn extract_text_and_metadata_from_pdf_document(
 ///
 /// When page_config is None, uses fast path with minimal overhead.
 /// When page_config is Some, tracks byte offsets using .len() for O(1) performance (UTF-8 valid boundaries).
+///
+/// When `cleanup.strip_watermarks` is enabled, detected watermark lines are removed
+/// from each page before boundaries are recorded. Likewise, text covered by a
+/// redaction is replaced when `pdf_options.redaction.suppress_covered_text` is enabled.
 pub fn extract_text_from_pdf_document(
     document: &PdfDocument<'_>,
     page_config: Option<&PageConfig>,
     extraction_config: Option<&crate::core::config::ExtractionConfig>,
+) -> Result<PdfTextExtractionResult> {
+    let watermarks = detect_configured_watermarks(document, extraction_config)?;
+    let strip = strip_list(extraction_config, &watermarks);
+    let redactions = detect_configured_redactions(document, extraction_config)?;
+    extract_text_impl(document, page_config, extraction_config, strip, &redactions)
+}
+
+fn extract_text_impl(
+    document: &PdfDocument<'_>,
+    page_config: Option<&PageConfig>,
+    extraction_config: Option<&crate::core::config::ExtractionConfig>,
+    strip_watermarks: &[String],
+    redactions: &DocumentRedactions,
 ) -> Result<PdfTextExtractionResult> {
     if page_config.is_none() {
-        return extract_text_lazy_fast_path(document);
+        let ruby = extraction_config
+            .and_then(|cfg| cfg.pdf_options.as_ref())
+            .map(|pdf_cfg| pdf_cfg.ruby)
+            .unwrap_or_default();
+        return extract_text_lazy_fast_path(document, strip_watermarks, redactions, ruby);
     }
 
     let config = page_config.unwrap();
 
-    extract_text_lazy_with_tracking(document, config, extraction_config)
+    extract_text_lazy_with_tracking(document, config, extraction_config, strip_watermarks, redactions)
+}
+
+/// Run the watermark pre-pass when the cleanup configuration asks for it.
+fn detect_configured_watermarks(
+    document: &PdfDocument<'_>,
+    extraction_config: Option<&crate::core::config::ExtractionConfig>,
+) -> Result<Vec<String>> {
+    match extraction_config.and_then(|c| c.cleanup.as_ref()) {
+        Some(cleanup) if cleanup.detects_watermarks() => {
+            super::watermark::detect_document_watermarks(document, cleanup.watermark_min_page_ratio)
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+/// Run the redaction pre-pass when `pdf_options.redaction` is configured.
+fn detect_configured_redactions(
+    document: &PdfDocument<'_>,
+    extraction_config: Option<&crate::core::config::ExtractionConfig>,
+) -> Result<DocumentRedactions> {
+    match extraction_config
+        .and_then(|c| c.pdf_options.as_ref())
+        .and_then(|pdf| pdf.redaction.as_ref())
+    {
+        Some(redaction) => super::redaction::detect_document_redactions(document, redaction),
+        None => Ok(DocumentRedactions::default()),
+    }
+}
+
+/// Raw page text with redacted characters suppressed, and whether suppression applied.
+fn raw_page_text(text: &PdfPageText<'_>, page_idx: usize, redactions: &DocumentRedactions) -> (String, bool) {
+    match redactions.suppressed_page_text(page_idx, text) {
+        Some(suppressed) => (suppressed, true),
+        None => (text.all(), false),
+    }
+}
+
+/// Watermarks to remove from page text (empty unless `strip_watermarks` is set).
+fn strip_list<'a>(
+    extraction_config: Option<&crate::core::config::ExtractionConfig>,
+    watermarks: &'a [String],
+) -> &'a [String] {
+    let strip = extraction_config
+        .and_then(|c| c.cleanup.as_ref())
+        .is_some_and(|cleanup| cleanup.strip_watermarks);
+    if strip { watermarks } else { &[] }
 }
 
 /// Fast path for text extraction without page tracking.
@@ -197,7 +274,12 @@ pub fn extract_text_from_pdf_document(
 /// and extrapolating for the full document. This reduces String reallocation
 /// calls from O(n) to O(log n) while maintaining low peak memory usage.
 /// For large documents, this can reduce allocation overhead by 40-50%.
-fn extract_text_lazy_fast_path(document: &PdfDocument<'_>) -> Result<PdfTextExtractionResult> {
+fn extract_text_lazy_fast_path(
+    document: &PdfDocument<'_>,
+    strip_watermarks: &[String],
+    redactions: &DocumentRedactions,
+    ruby: RubyMode,
+) -> Result<PdfTextExtractionResult> {
     let page_count = document.pages().len() as usize;
     let mut content = String::new();
     let mut total_sample_size = 0usize;
@@ -208,7 +290,15 @@ fn extract_text_lazy_fast_path(document: &PdfDocument<'_>) -> Result<PdfTextExtr
             .text()
             .map_err(|e| PdfError::TextExtractionFailed(format!("Page text extraction failed: {}", e)))?;
 
-        let page_text = text.all();
+        let (raw_text, suppressed) = raw_page_text(&text, page_idx, redactions);
+        let page_text = strip_watermark_lines(&raw_text, strip_watermarks);
+        let page_text = fix_rtl_page_text(&text, page_text);
+        // The CJK layout is rebuilt from glyphs, which would restore suppressed text.
+        let page_text = if suppressed {
+            page_text
+        } else {
+            fix_cjk_page_text(&text, page_text, ruby)
+        };
         let page_size = page_text.len();
 
         if page_idx > 0 {
@@ -241,6 +331,7 @@ fn extract_text_lazy_fast_path(document: &PdfDocument<'_>) -> Result<PdfTextExtr
 ///
 /// When hierarchy extraction is enabled, extracts text hierarchy (H1-H6 levels)
 /// from font size clustering and assigns semantic heading levels to text blocks.
+/// Only the pages in `config.selection` are extracted, under their original numbers.
 ///
 /// # Performance Optimization
 ///
@@ -251,12 +342,18 @@ fn extract_text_lazy_with_tracking(
     document: &PdfDocument<'_>,
     config: &PageConfig,
     extraction_config: Option<&crate::core::config::ExtractionConfig>,
+    strip_watermarks: &[String],
+    redactions: &DocumentRedactions,
 ) -> Result<PdfTextExtractionResult> {
     let mut content = String::new();
     let page_count = document.pages().len() as usize;
-    let mut boundaries = Vec::with_capacity(page_count);
+    let selected_count = config
+        .selection
+        .as_ref()
+        .map_or(page_count, |selection| selection.pages(page_count).len());
+    let mut boundaries = Vec::with_capacity(selected_count);
     let mut page_contents = if config.extract_pages {
-        Some(Vec::with_capacity(page_count))
+        Some(Vec::with_capacity(selected_count))
     } else {
         None
     };
@@ -273,29 +370,50 @@ fn extract_text_lazy_with_tracking(
         .and_then(|pdf_cfg| pdf_cfg.hierarchy.as_ref())
         .cloned();
 
+    let ruby = extraction_config
+        .and_then(|cfg| cfg.pdf_options.as_ref())
+        .map(|pdf_cfg| pdf_cfg.ruby)
+        .unwrap_or_default();
+
     let mut total_sample_size = 0usize;
     let mut sample_count = 0;
 
-    for (page_idx, page) in document.pages().iter().enumerate() {
+    for page_idx in 0..page_count {
         let page_number = page_idx + 1;
+        // Pages outside the selection are never loaded.
+        if !config.includes_page(page_number) {
+            continue;
+        }
+        let page = document
+            .pages()
+            .get(page_idx as i32)
+            .map_err(|_| PdfError::PageNotFound(page_number))?;
+        let position = boundaries.len();
 
         let text = page
             .text()
             .map_err(|e| PdfError::TextExtractionFailed(format!("Page text extraction failed: {}", e)))?;
 
-        let page_text_ref = text.all();
+        let (raw_text, suppressed) = raw_page_text(&text, page_idx, redactions);
+        let page_text_ref = strip_watermark_lines(&raw_text, strip_watermarks);
+        let page_text_ref = fix_rtl_page_text(&text, page_text_ref);
+        // The CJK layout is rebuilt from glyphs, which would restore suppressed text.
+        let page_text_ref = if suppressed {
+            page_text_ref
+        } else {
+            fix_cjk_page_text(&text, page_text_ref, ruby)
+        };
         let page_size = page_text_ref.len();
 
-        if page_idx < 5 {
+        if position < 5 {
             total_sample_size += page_size;
             sample_count += 1;
         }
 
         // Insert page marker before the page content (for ALL pages including page 1)
         if config.insert_page_markers {
-            let marker = config.marker_format.replace("{page_num}", &page_number.to_string());
-            content.push_str(&marker);
-        } else if page_idx > 0 {
+            config.push_marker(&mut content, page_number);
+        } else if position > 0 {
             // Only add separator between pages when markers are disabled
             content.push_str("\n\n");
         }
@@ -320,17 +438,17 @@ fn extract_text_lazy_with_tracking(
 
             pages.push(PageContent {
                 page_number,
-                content: page_text_ref.to_owned(),
+                content: page_text_ref.into_owned(),
                 tables: Vec::new(),
                 images: Vec::new(),
                 hierarchy,
             });
         }
 
-        if page_idx == 4 && page_count > 5 && sample_count > 0 {
+        if position == 4 && selected_count > 5 && sample_count > 0 {
             let avg_page_size = total_sample_size / sample_count;
-            let estimated_remaining = avg_page_size * (page_count - 5);
-            let separator_overhead = (page_count - 5) * 3;
+            let estimated_remaining = avg_page_size * (selected_count - 5);
+            let separator_overhead = (selected_count - 5) * 3;
             content.reserve(estimated_remaining + separator_overhead + (estimated_remaining / 10));
         }
     }
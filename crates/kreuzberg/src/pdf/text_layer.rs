@@ -0,0 +1,122 @@
+//! PDF text layer probe.
+//!
+//! Tells which pages of a PDF carry extractable text, without extracting it, so a
+//! pipeline can decide whether OCR is needed before committing to a full extraction.
+//! A page has a text layer when its text objects hold at least one non-whitespace
+//! character; scanned pages with an invisible OCR layer count as having one.
+
+use super::bindings::bind_pdfium;
+use super::error::{PdfError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Which pages of a PDF have a text layer.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextLayerReport {
+    /// Whether each page has a text layer, in page order
+    pub pages: Vec<bool>,
+    /// Percentage of pages with a text layer, from 0 to 100 (0 for documents without pages)
+    pub coverage_percent: f64,
+}
+
+impl TextLayerReport {
+    /// Build the report from the per-page results.
+    pub fn from_pages(pages: Vec<bool>) -> Self {
+        let with_text = pages.iter().filter(|has_text| **has_text).count();
+        let coverage_percent = if pages.is_empty() {
+            0.0
+        } else {
+            with_text as f64 * 100.0 / pages.len() as f64
+        };
+        Self {
+            pages,
+            coverage_percent,
+        }
+    }
+
+    /// Whether every page has a text layer.
+    pub fn is_complete(&self) -> bool {
+        self.pages.iter().all(|has_text| *has_text)
+    }
+
+    /// 1-indexed numbers of the pages without a text layer, the candidates for OCR.
+    pub fn pages_without_text(&self) -> Vec<usize> {
+        self.pages
+            .iter()
+            .enumerate()
+            .filter(|(_, has_text)| !**has_text)
+            .map(|(index, _)| index + 1)
+            .collect()
+    }
+}
+
+/// Probe the text layer of the PDF at `path`.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Io` if the file cannot be read, and a parsing error if it is
+/// not a valid PDF or is password-protected.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use kreuzberg::pdf::has_text_layer;
+///
+/// # fn example() -> kreuzberg::Result<()> {
+/// let report = has_text_layer("scan.pdf")?;
+/// if !report.is_complete() {
+///     println!("OCR pages {:?} ({:.0}% have text)", report.pages_without_text(), report.coverage_percent);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub fn has_text_layer(path: impl AsRef<Path>) -> crate::Result<TextLayerReport> {
+    let content = crate::core::io::open_file_sync(path, None)?;
+    Ok(has_text_layer_from_bytes(&content, None)?)
+}
+
+/// Probe the text layer of the PDF `pdf_bytes`, opened with `password` if given.
+pub fn has_text_layer_from_bytes(pdf_bytes: &[u8], password: Option<&str>) -> Result<TextLayerReport> {
+    let pdfium = bind_pdfium(PdfError::TextExtractionFailed, "text layer probe")?;
+    let document = pdfium.load_pdf_from_byte_slice(pdf_bytes, password).map_err(|e| {
+        let err_msg = super::error::format_pdfium_error(e);
+        if (err_msg.contains("password") || err_msg.contains("Password")) && password.is_some() {
+            PdfError::InvalidPassword
+        } else if err_msg.contains("password") || err_msg.contains("Password") {
+            PdfError::PasswordRequired
+        } else {
+            PdfError::InvalidPdf(err_msg)
+        }
+    })?;
+
+    let mut pages = Vec::with_capacity(document.pages().len() as usize);
+    for page in document.pages().iter() {
+        let text = page
+            .text()
+            .map_err(|e| PdfError::TextExtractionFailed(format!("Page text extraction failed: {}", e)))?;
+        let has_text = text
+            .chars()
+            .iter()
+            .any(|pdf_char| pdf_char.unicode_char().is_some_and(|ch| !ch.is_whitespace()));
+        pages.push(has_text);
+    }
+
+    Ok(TextLayerReport::from_pages(pages))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_layer_report_from_pages() {
+        let report = TextLayerReport::from_pages(vec![true, false, true, true]);
+        assert_eq!(report.coverage_percent, 75.0);
+        assert!(!report.is_complete());
+        assert_eq!(report.pages_without_text(), vec![2]);
+
+        let report = TextLayerReport::from_pages(Vec::new());
+        assert_eq!(report.coverage_percent, 0.0);
+        assert!(report.pages_without_text().is_empty());
+    }
+}
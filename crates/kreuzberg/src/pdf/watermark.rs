@@ -0,0 +1,94 @@
+//! PDF watermark detection.
+//!
+//! Collects rotated (diagonal) text runs from each page and combines them with the
+//! per-page text to find watermark overlays. The actual heuristics live in
+//! [`crate::text::watermark`].
+
+use super::error::{PdfError, Result};
+use crate::text::watermark::detect_watermarks;
+use pdfium_render::prelude::*;
+
+/// Angles within this many degrees of a multiple of 90 are treated as upright text.
+const UPRIGHT_TOLERANCE_DEGREES: f32 = 5.0;
+
+/// Detect watermark text in a PDF document.
+///
+/// Performs a pre-pass over all pages, gathering page text and rotated text runs,
+/// and returns the normalized watermark strings found across the document.
+///
+/// # Arguments
+///
+/// * `document` - The PDF document to scan
+/// * `min_page_ratio` - Minimum fraction of pages a candidate has to appear on
+pub fn detect_document_watermarks(document: &PdfDocument<'_>, min_page_ratio: f64) -> Result<Vec<String>> {
+    let mut page_texts = Vec::with_capacity(document.pages().len() as usize);
+    let mut rotated_runs = Vec::with_capacity(document.pages().len() as usize);
+
+    for page in document.pages().iter() {
+        let text = page
+            .text()
+            .map_err(|e| PdfError::TextExtractionFailed(format!("Page text extraction failed: {}", e)))?;
+
+        rotated_runs.push(collect_rotated_runs(&text));
+        page_texts.push(text.all());
+    }
+
+    let pages: Vec<&str> = page_texts.iter().map(String::as_str).collect();
+    Ok(detect_watermarks(&pages, &rotated_runs, min_page_ratio))
+}
+
+/// Group consecutive rotated characters of a page into text runs.
+fn collect_rotated_runs(text: &PdfPageText<'_>) -> Vec<String> {
+    let mut runs = Vec::new();
+    let mut current = String::new();
+
+    for pdf_char in text.chars().iter() {
+        let Some(ch) = pdf_char.unicode_char() else {
+            continue;
+        };
+
+        let rotated = pdf_char.angle_degrees().map(is_diagonal).unwrap_or(false);
+
+        if ch == '\n' || ch == '\r' || (!rotated && !ch.is_whitespace()) {
+            flush_run(&mut current, &mut runs);
+            continue;
+        }
+
+        if rotated || !current.is_empty() {
+            current.push(ch);
+        }
+    }
+
+    flush_run(&mut current, &mut runs);
+    runs
+}
+
+fn flush_run(current: &mut String, runs: &mut Vec<String>) {
+    let trimmed = current.trim();
+    if !trimmed.is_empty() {
+        runs.push(trimmed.to_string());
+    }
+    current.clear();
+}
+
+/// Whether an angle (in degrees) is neither horizontal nor vertical.
+fn is_diagonal(angle_degrees: f32) -> bool {
+    let remainder = angle_degrees.rem_euclid(90.0);
+    remainder > UPRIGHT_TOLERANCE_DEGREES && remainder < 90.0 - UPRIGHT_TOLERANCE_DEGREES
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_diagonal() {
+        assert!(is_diagonal(45.0));
+        assert!(is_diagonal(-30.0));
+        assert!(is_diagonal(315.0));
+        assert!(!is_diagonal(0.0));
+        assert!(!is_diagonal(90.0));
+        assert!(!is_diagonal(179.0));
+        assert!(!is_diagonal(-270.0));
+    }
+}
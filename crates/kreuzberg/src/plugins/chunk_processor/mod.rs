@@ -0,0 +1,142 @@
+//! Chunk processor plugin system.
+//!
+//! This module provides the trait and registry for plugins that enrich individual
+//! chunks after chunking.
+
+mod registry;
+mod r#trait;
+
+pub use r#trait::ChunkProcessor;
+
+pub use registry::{
+    clear_chunk_processors, list_chunk_processors, register_chunk_processor, unregister_chunk_processor,
+};
+
+pub(crate) use registry::registered_chunk_processors;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::{ExtractionConfig, PostProcessorConfig};
+    use crate::core::pipeline::run_pipeline;
+    use crate::plugins::Plugin;
+    use crate::types::{Chunk, ExtractionResult, Metadata};
+    use crate::{KreuzbergError, Result};
+    use async_trait::async_trait;
+    use std::borrow::Cow;
+    use std::sync::Arc;
+
+    /// Records the position of every chunk in the document.
+    struct PositionTagger;
+
+    impl Plugin for PositionTagger {
+        fn name(&self) -> &str {
+            "position-tagger"
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl ChunkProcessor for PositionTagger {
+        async fn process_chunk(
+            &self,
+            chunk: &mut Chunk,
+            result: &ExtractionResult,
+            _config: &ExtractionConfig,
+        ) -> Result<()> {
+            let position = chunk.metadata.byte_start as f64 / result.content.len() as f64;
+            chunk.metadata.additional.insert(
+                "position".to_string(),
+                serde_json::json!((position * 100.0).round() / 100.0),
+            );
+            Ok(())
+        }
+    }
+
+    /// Fails on every chunk.
+    struct Failing;
+
+    impl Plugin for Failing {
+        fn name(&self) -> &str {
+            "failing-chunks"
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl ChunkProcessor for Failing {
+        async fn process_chunk(&self, _: &mut Chunk, _: &ExtractionResult, _: &ExtractionConfig) -> Result<()> {
+            Err(KreuzbergError::parsing("no entities found"))
+        }
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_chunk_processors_enrich_chunks() {
+        let result = ExtractionResult {
+            content: "First part of the text. ".repeat(10),
+            mime_type: Cow::Borrowed("text/plain"),
+            metadata: Metadata::default(),
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            scripts: None,
+            pages: None,
+            elements: None,
+        };
+        let mut config = ExtractionConfig {
+            chunking: Some(crate::ChunkingConfig {
+                max_characters: 60,
+                overlap: 0,
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        register_chunk_processor(Arc::new(PositionTagger)).unwrap();
+        register_chunk_processor(Arc::new(Failing)).unwrap();
+        let listed = list_chunk_processors().unwrap();
+        let enriched = run_pipeline(result.clone(), &config).await;
+        config.postprocessor = Some(PostProcessorConfig {
+            disabled_processors: Some(vec!["position-tagger".to_string()]),
+            ..Default::default()
+        });
+        let disabled = run_pipeline(result, &config).await;
+        unregister_chunk_processor("position-tagger").unwrap();
+        unregister_chunk_processor("failing-chunks").unwrap();
+
+        assert_eq!(listed, vec!["failing-chunks", "position-tagger"]);
+        let enriched = enriched.unwrap();
+        let chunks = enriched.chunks.unwrap();
+        assert!(chunks.len() > 1);
+        assert_eq!(chunks[0].metadata.additional["position"], 0.0);
+        assert!(chunks.last().unwrap().metadata.additional["position"].as_f64().unwrap() > 0.5);
+        assert!(
+            enriched.metadata.additional["processing_error_failing-chunks"]
+                .as_str()
+                .unwrap()
+                .contains("no entities found")
+        );
+
+        let disabled = disabled.unwrap();
+        assert!(disabled.chunks.unwrap()[0].metadata.additional.is_empty());
+    }
+}
@@ -0,0 +1,105 @@
+//! Chunk processor registry management.
+//!
+//! This module provides functions for managing the global chunk processor registry.
+
+use super::r#trait::ChunkProcessor;
+use crate::{KreuzbergError, Result};
+use std::sync::Arc;
+
+/// Register a chunk processor with the global registry.
+///
+/// The processor's `name()` method is used as the registration name; a processor with
+/// the same name is replaced.
+///
+/// # Errors
+///
+/// - `KreuzbergError::Validation` - Invalid processor name (empty or contains whitespace)
+/// - Any error from the processor's `initialize()` method
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::plugins::{ChunkProcessor, Plugin, register_chunk_processor};
+/// use kreuzberg::types::{Chunk, ExtractionResult};
+/// use kreuzberg::{ExtractionConfig, KreuzbergError, Result};
+/// use async_trait::async_trait;
+/// use std::sync::Arc;
+///
+/// struct Noop;
+///
+/// impl Plugin for Noop {
+///     fn name(&self) -> &str { "noop-chunks" }
+///     fn version(&self) -> String { "1.0.0".to_string() }
+///     fn initialize(&self) -> Result<()> { Ok(()) }
+///     fn shutdown(&self) -> Result<()> { Ok(()) }
+/// }
+///
+/// #[async_trait]
+/// impl ChunkProcessor for Noop {
+///     async fn process_chunk(&self, _: &mut Chunk, _: &ExtractionResult, _: &ExtractionConfig) -> Result<()> {
+///         Ok(())
+///     }
+/// }
+///
+/// register_chunk_processor(Arc::new(Noop))?;
+/// # kreuzberg::plugins::unregister_chunk_processor("noop-chunks")?;
+/// # Ok::<(), KreuzbergError>(())
+/// ```
+pub fn register_chunk_processor(processor: Arc<dyn ChunkProcessor>) -> Result<()> {
+    use crate::plugins::registry::get_chunk_processor_registry;
+
+    let registry = get_chunk_processor_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on chunk processor registry"); // ~keep
+
+    registry.register(processor)
+}
+
+/// Unregister a chunk processor by name.
+///
+/// Removes the processor from the global registry and calls its `shutdown()` method.
+/// Unknown names are ignored.
+pub fn unregister_chunk_processor(name: &str) -> Result<()> {
+    use crate::plugins::registry::get_chunk_processor_registry;
+
+    let registry = get_chunk_processor_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on chunk processor registry"); // ~keep
+
+    registry.remove(name)
+}
+
+/// List the names of all registered chunk processors in execution order.
+pub fn list_chunk_processors() -> Result<Vec<String>> {
+    use crate::plugins::registry::get_chunk_processor_registry;
+
+    let registry = get_chunk_processor_registry();
+    let registry = registry
+        .read()
+        .expect("~keep Failed to acquire read lock on chunk processor registry"); // ~keep
+
+    Ok(registry.list())
+}
+
+/// Clear all chunk processors from the global registry, calling their `shutdown()` methods.
+pub fn clear_chunk_processors() -> Result<()> {
+    use crate::plugins::registry::get_chunk_processor_registry;
+
+    let registry = get_chunk_processor_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on chunk processor registry"); // ~keep
+
+    registry.shutdown_all()
+}
+
+/// Registered chunk processors in execution order (highest priority first).
+pub(crate) fn registered_chunk_processors() -> Result<Vec<Arc<dyn ChunkProcessor>>> {
+    let registry = crate::plugins::registry::get_chunk_processor_registry();
+    let registry = registry
+        .read()
+        .map_err(|e| KreuzbergError::Other(format!("Chunk processor registry lock poisoned: {}", e)))?;
+    Ok(registry.get_all())
+}
@@ -0,0 +1,98 @@
+//! Chunk processor trait.
+//!
+//! This module defines the trait for plugins that enrich individual chunks.
+
+use crate::Result;
+use crate::core::config::ExtractionConfig;
+use crate::plugins::Plugin;
+use crate::types::{Chunk, ExtractionResult};
+use async_trait::async_trait;
+
+/// Trait for plugins that enrich chunks after chunking.
+///
+/// Post-processors see the whole document; chunk processors see one chunk at a time,
+/// with the document as context, and attach what they find to the chunk: entities or
+/// keywords in `chunk.metadata.additional`, an embedding in `chunk.embedding`, the
+/// section path of the chunk. They run after chunking and chunk filters, in priority
+/// order (highest first), on every extraction with chunking enabled.
+///
+/// `PostProcessorConfig::enabled_processors` and `disabled_processors` apply to chunk
+/// processors by name, and calls are guarded by `ExtractionConfig::plugin_guard`.
+///
+/// # Error Handling
+///
+/// Like post-processors, errors are recorded in
+/// `metadata.additional["processing_error_<name>"]`, except for I/O, lock and plugin
+/// errors, which fail the extraction.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::plugins::{ChunkProcessor, Plugin};
+/// use kreuzberg::types::{Chunk, ExtractionResult};
+/// use kreuzberg::{ExtractionConfig, Result};
+/// use async_trait::async_trait;
+///
+/// struct WordCounter;
+///
+/// impl Plugin for WordCounter {
+///     fn name(&self) -> &str { "word-counter" }
+///     fn version(&self) -> String { "1.0.0".to_string() }
+///     fn initialize(&self) -> Result<()> { Ok(()) }
+///     fn shutdown(&self) -> Result<()> { Ok(()) }
+/// }
+///
+/// #[async_trait]
+/// impl ChunkProcessor for WordCounter {
+///     async fn process_chunk(
+///         &self,
+///         chunk: &mut Chunk,
+///         _result: &ExtractionResult,
+///         _config: &ExtractionConfig,
+///     ) -> Result<()> {
+///         let words = chunk.content.split_whitespace().count();
+///         chunk.metadata.additional.insert("word_count".to_string(), words.into());
+///         Ok(())
+///     }
+/// }
+/// ```
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ChunkProcessor: Plugin {
+    /// Enrich one chunk.
+    ///
+    /// `result` is the document the chunk belongs to; its `chunks` are `None` while the
+    /// chunks are being processed.
+    async fn process_chunk(
+        &self,
+        chunk: &mut Chunk,
+        result: &ExtractionResult,
+        config: &ExtractionConfig,
+    ) -> Result<()>;
+
+    /// Enrich all chunks of a document.
+    ///
+    /// Calls [`process_chunk`](Self::process_chunk) for every chunk in order. Override
+    /// it to process chunks in batches, for example to embed them in one model call.
+    async fn process_chunks(
+        &self,
+        chunks: &mut [Chunk],
+        result: &ExtractionResult,
+        config: &ExtractionConfig,
+    ) -> Result<()> {
+        for chunk in chunks {
+            self.process_chunk(chunk, result, config).await?;
+        }
+        Ok(())
+    }
+
+    /// Whether to process the chunks of `result`. Defaults to true.
+    fn should_process(&self, _result: &ExtractionResult, _config: &ExtractionConfig) -> bool {
+        true
+    }
+
+    /// Execution priority (higher runs first). Defaults to 50.
+    fn priority(&self) -> i32 {
+        50
+    }
+}
@@ -0,0 +1,87 @@
+//! Structured descriptions of registered plugins.
+//!
+//! [`list_extractors`](crate::plugins::list_extractors) and
+//! [`list_ocr_backends`](crate::plugins::list_ocr_backends) return these descriptors so
+//! management tools and extraction plans can show what each plugin handles.
+
+use super::{DocumentExtractor, OcrBackend, OcrBackendType};
+use serde::{Deserialize, Serialize};
+
+/// Description of a registered document extractor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExtractorDescriptor {
+    /// Extractor name
+    pub name: String,
+    /// Extractor version
+    pub version: String,
+    /// Human-readable description, empty when the extractor has none
+    pub description: String,
+    /// MIME types the extractor handles, including prefix patterns such as `image/*`
+    pub mime_types: Vec<String>,
+    /// Priority among extractors of the same MIME type
+    pub priority: i32,
+    /// Crate features the extractor is built with
+    pub required_features: Vec<String>,
+    /// Whether the extractor also runs synchronously, as in WASM builds
+    pub supports_sync: bool,
+    /// Whether the extractor performs network I/O
+    #[serde(default)]
+    pub requires_network: bool,
+}
+
+impl ExtractorDescriptor {
+    /// Describe `extractor`.
+    pub fn new(extractor: &dyn DocumentExtractor) -> Self {
+        Self {
+            name: extractor.name().to_string(),
+            version: extractor.version(),
+            description: extractor.description().to_string(),
+            mime_types: extractor.supported_mime_types().iter().map(|s| s.to_string()).collect(),
+            priority: extractor.priority(),
+            required_features: extractor.required_features().iter().map(|s| s.to_string()).collect(),
+            supports_sync: extractor.as_sync_extractor().is_some(),
+            requires_network: extractor.requires_network(),
+        }
+    }
+}
+
+/// Description of a registered OCR backend.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OcrBackendDescriptor {
+    /// Backend name
+    pub name: String,
+    /// Backend version
+    pub version: String,
+    /// Human-readable description, empty when the backend has none
+    pub description: String,
+    /// Kind of backend
+    pub backend_type: OcrBackendType,
+    /// Languages the backend reports, empty when it does not list them
+    pub languages: Vec<String>,
+    /// Whether the backend detects tables
+    pub supports_table_detection: bool,
+    /// Whether the backend reads handwriting
+    pub supports_handwriting: bool,
+    /// Crate features the backend is built with
+    pub required_features: Vec<String>,
+    /// Whether the backend performs network I/O, e.g. a cloud OCR service
+    #[serde(default)]
+    pub requires_network: bool,
+}
+
+impl OcrBackendDescriptor {
+    /// Describe `backend`.
+    pub fn new(backend: &dyn OcrBackend) -> Self {
+        Self {
+            name: backend.name().to_string(),
+            version: backend.version(),
+            description: backend.description().to_string(),
+            backend_type: backend.backend_type(),
+            languages: backend.supported_languages(),
+            supports_table_detection: backend.supports_table_detection(),
+            supports_handwriting: backend.supports_handwriting(),
+            required_features: backend.required_features().iter().map(|s| s.to_string()).collect(),
+            requires_network: backend.requires_network(),
+        }
+    }
+}
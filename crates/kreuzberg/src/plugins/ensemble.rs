@@ -0,0 +1,489 @@
+//! Ensemble OCR: several backends voting on every word.
+//!
+//! OCR backends fail on different things: one misreads thin serifs, another drops
+//! words on stained paper. [`EnsembleOcrBackend`] recognizes each image with all of
+//! its backends, aligns their outputs word by word against the most confident output
+//! and lets the backends vote on every word, weighted by their confidence. With three
+//! or more backends, isolated misreadings are outvoted; with two, each disputed word
+//! comes from the more confident backend.
+//!
+//! `OcrConfig::ensemble` selects the backends of an extraction.
+
+use crate::core::config::OcrConfig;
+use crate::diff::lines::{Edit, diff_lines};
+use crate::plugins::registry::get_ocr_backend_registry;
+use crate::plugins::{OcrBackend, OcrBackendType, Plugin};
+use crate::types::ExtractionResult;
+use crate::{KreuzbergError, Result};
+use async_trait::async_trait;
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// Metadata key of the voting summary, in `metadata.additional`.
+pub const ENSEMBLE_KEY: &str = "ocr_ensemble";
+
+/// Vote weight of a backend that does not report `mean_confidence`.
+const DEFAULT_CONFIDENCE: f64 = 0.5;
+
+/// An OCR backend merging the outputs of other backends by confidence-weighted voting.
+///
+/// Backends run one after the other on each image. A backend that fails is left out of
+/// the vote; the image fails only if every backend fails. The confidence of a backend is
+/// the `mean_confidence` it reports in `metadata.additional` (0-1, or 0-100), or 0.5.
+///
+/// The merged result keeps the layout, tables and metadata of the most confident output
+/// and records the vote in `metadata.additional["ocr_ensemble"]`.
+pub struct EnsembleOcrBackend {
+    backends: Vec<Arc<dyn OcrBackend>>,
+}
+
+impl EnsembleOcrBackend {
+    /// Ensemble of `backends`, in order of precedence for ties.
+    pub fn new(backends: Vec<Arc<dyn OcrBackend>>) -> Self {
+        Self { backends }
+    }
+}
+
+impl Plugin for EnsembleOcrBackend {
+    fn name(&self) -> &str {
+        "ensemble"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn description(&self) -> &str {
+        "Merges the outputs of several OCR backends by confidence-weighted voting"
+    }
+
+    fn requires_network(&self) -> bool {
+        self.backends.iter().any(|backend| backend.requires_network())
+    }
+}
+
+#[async_trait]
+impl OcrBackend for EnsembleOcrBackend {
+    async fn process_image(&self, image_bytes: &[u8], config: &OcrConfig) -> Result<ExtractionResult> {
+        let mut outputs = Vec::with_capacity(self.backends.len());
+        let mut failed = Vec::new();
+        let mut first_error = None;
+        for backend in &self.backends {
+            match backend.process_image(image_bytes, config).await {
+                Ok(result) => outputs.push((backend.name().to_string(), result)),
+                Err(e) => {
+                    tracing::warn!("OCR backend '{}' failed in ensemble: {}", backend.name(), e);
+                    failed.push(backend.name().to_string());
+                    first_error.get_or_insert(e);
+                }
+            }
+        }
+        if outputs.is_empty() {
+            return Err(
+                first_error.unwrap_or_else(|| KreuzbergError::validation("OCR ensemble has no backends".to_string()))
+            );
+        }
+
+        Ok(vote(outputs, failed))
+    }
+
+    fn supports_language(&self, lang: &str) -> bool {
+        self.backends.iter().all(|backend| backend.supports_language(lang))
+    }
+
+    fn backend_type(&self) -> OcrBackendType {
+        OcrBackendType::Custom
+    }
+
+    fn supported_languages(&self) -> Vec<String> {
+        let mut languages = self
+            .backends
+            .first()
+            .map(|backend| backend.supported_languages())
+            .unwrap_or_default();
+        languages.retain(|language| self.supports_language(language));
+        languages
+    }
+
+    fn supports_handwriting(&self) -> bool {
+        !self.backends.is_empty() && self.backends.iter().all(|backend| backend.supports_handwriting())
+    }
+}
+
+/// The backend selected by `config`: an [`EnsembleOcrBackend`] of the `ensemble`
+/// backends, or else the registered `backend`.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Plugin` if a backend is not registered.
+pub(crate) fn resolve_ocr_backend(config: &OcrConfig) -> Result<Arc<dyn OcrBackend>> {
+    let registry = get_ocr_backend_registry();
+    let registry = registry.read().map_err(|e| KreuzbergError::Plugin {
+        message: format!("Failed to acquire read lock on OCR backend registry: {}", e),
+        plugin_name: "ocr-registry".to_string(),
+    })?;
+
+    if config.ensemble.is_empty() {
+        return registry.get(&config.backend);
+    }
+    let backends = config
+        .ensemble
+        .iter()
+        .map(|name| registry.get(name))
+        .collect::<Result<Vec<_>>>()?;
+    Ok(Arc::new(EnsembleOcrBackend::new(backends)))
+}
+
+fn confidence(result: &ExtractionResult) -> f64 {
+    match result
+        .metadata
+        .additional
+        .get("mean_confidence")
+        .and_then(|value| value.as_f64())
+    {
+        Some(confidence) if confidence > 1.0 => confidence / 100.0,
+        Some(confidence) => confidence.max(0.0),
+        None => DEFAULT_CONFIDENCE,
+    }
+}
+
+/// Whitespace-separated words of `text` with their byte spans.
+fn words(text: &str) -> Vec<(usize, usize)> {
+    text.split(char::is_whitespace)
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let start = word.as_ptr() as usize - text.as_ptr() as usize;
+            (start, start + word.len())
+        })
+        .collect()
+}
+
+/// What one backend says about the words of the primary output.
+struct Reading {
+    /// Text for each primary word, empty if the backend has nothing there
+    slots: Vec<String>,
+    /// Text before each primary word and after the last one, where the primary has none
+    gaps: Vec<String>,
+}
+
+fn read_against(primary: &[&str], other: &[&str]) -> Reading {
+    let mut reading = Reading {
+        slots: vec![String::new(); primary.len()],
+        gaps: vec![String::new(); primary.len() + 1],
+    };
+    let mut deleted: Vec<usize> = Vec::new();
+    let mut inserted: Vec<usize> = Vec::new();
+
+    for edit in diff_lines(primary, other) {
+        match edit {
+            Edit::Equal(a, b) => {
+                assign(&mut reading, other, a, &mut deleted, &mut inserted);
+                reading.slots[a] = other[b].to_string();
+            }
+            Edit::Delete(a) => deleted.push(a),
+            Edit::Insert(b) => inserted.push(b),
+        }
+    }
+    assign(&mut reading, other, primary.len(), &mut deleted, &mut inserted);
+    reading
+}
+
+/// Assign the words of `other` that replace the `deleted` primary words, or that are
+/// inserted before primary word `next`.
+///
+/// Replacements of as many words as were deleted are assigned one to one; otherwise the
+/// whole replacement goes to the first deleted word.
+fn assign(reading: &mut Reading, other: &[&str], next: usize, deleted: &mut Vec<usize>, inserted: &mut Vec<usize>) {
+    let text = || inserted.iter().map(|&i| other[i]).collect::<Vec<_>>().join(" ");
+    match deleted.first() {
+        None if !inserted.is_empty() => reading.gaps[next] = text(),
+        None => {}
+        Some(_) if deleted.len() == inserted.len() => {
+            for (&slot, &word) in deleted.iter().zip(inserted.iter()) {
+                reading.slots[slot] = other[word].to_string();
+            }
+        }
+        Some(&first) => reading.slots[first] = text(),
+    }
+    deleted.clear();
+    inserted.clear();
+}
+
+/// The candidate with the highest total weight; ties go to the earliest candidate.
+fn elect<'a>(candidates: impl Iterator<Item = (&'a str, f64)>) -> &'a str {
+    let mut tally: Vec<(&str, f64)> = Vec::new();
+    for (text, weight) in candidates {
+        match tally.iter_mut().find(|(candidate, _)| *candidate == text) {
+            Some((_, total)) => *total += weight,
+            None => tally.push((text, weight)),
+        }
+    }
+    tally
+        .iter()
+        .fold(None, |best: Option<(&str, f64)>, &(text, weight)| match best {
+            Some((_, best_weight)) if best_weight >= weight => best,
+            _ => Some((text, weight)),
+        })
+        .map_or("", |(text, _)| text)
+}
+
+/// Merge the outputs of the backends named in `outputs` into one result.
+fn vote(mut outputs: Vec<(String, ExtractionResult)>, failed: Vec<String>) -> ExtractionResult {
+    let weights: Vec<f64> = outputs.iter().map(|(_, result)| confidence(result)).collect();
+    let primary_index = weights
+        .iter()
+        .enumerate()
+        .fold(0, |best, (i, &weight)| if weight > weights[best] { i } else { best });
+    let backends: Vec<String> = outputs.iter().map(|(name, _)| name.clone()).collect();
+    let (primary_name, mut merged) = outputs.remove(primary_index);
+    let primary_weight = weights[primary_index];
+    let other_weights: Vec<f64> = weights
+        .iter()
+        .enumerate()
+        .filter(|&(i, _)| i != primary_index)
+        .map(|(_, &weight)| weight)
+        .collect();
+
+    let text = merged.content.clone();
+    let spans = words(&text);
+    let primary: Vec<&str> = spans.iter().map(|&(start, end)| &text[start..end]).collect();
+    let readings: Vec<Reading> = outputs
+        .iter()
+        .map(|(_, result)| {
+            let other: Vec<&str> = words(&result.content)
+                .into_iter()
+                .map(|(start, end)| &result.content[start..end])
+                .collect();
+            read_against(&primary, &other)
+        })
+        .collect();
+
+    let gap = |index: usize| {
+        elect(
+            std::iter::once(("", primary_weight)).chain(
+                readings
+                    .iter()
+                    .zip(&other_weights)
+                    .map(|(reading, &weight)| (reading.gaps[index].as_str(), weight)),
+            ),
+        )
+    };
+    let slot = |index: usize| {
+        elect(
+            std::iter::once((primary[index], primary_weight)).chain(
+                readings
+                    .iter()
+                    .zip(&other_weights)
+                    .map(|(reading, &weight)| (reading.slots[index].as_str(), weight)),
+            ),
+        )
+    };
+
+    let mut content = String::with_capacity(text.len());
+    let mut changed_words = 0;
+    let mut inserted_words = 0;
+    content.push_str(&text[..spans.first().map_or(text.len(), |&(start, _)| start)]);
+    for (index, &(_, end)) in spans.iter().enumerate() {
+        let inserted = gap(index);
+        if !inserted.is_empty() {
+            content.push_str(inserted);
+            content.push(' ');
+            inserted_words += inserted.split_whitespace().count();
+        }
+
+        let word = slot(index);
+        if word != primary[index] {
+            changed_words += 1;
+        }
+        let separator = &text[end..spans.get(index + 1).map_or(text.len(), |&(start, _)| start)];
+        if !word.is_empty() {
+            content.push_str(word);
+            content.push_str(separator);
+        } else if separator.contains('\n') {
+            content.truncate(content.trim_end_matches([' ', '\t']).len());
+            content.push_str(separator);
+        }
+    }
+    let trailing = gap(spans.len());
+    if !trailing.is_empty() {
+        let body = content.trim_end().len();
+        let tail = content.split_off(body);
+        if body > 0 {
+            content.push(' ');
+        }
+        content.push_str(trailing);
+        content.push_str(&tail);
+        inserted_words += trailing.split_whitespace().count();
+    }
+
+    merged.content = content;
+    merged.metadata.additional.insert(
+        Cow::Borrowed(ENSEMBLE_KEY),
+        serde_json::json!({
+            "backends": backends,
+            "failed": failed,
+            "primary": primary_name,
+            "changed_words": changed_words,
+            "inserted_words": inserted_words,
+        }),
+    );
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::{register_ocr_backend, unregister_ocr_backend};
+    use crate::types::Metadata;
+    use serial_test::serial;
+
+    struct FixedBackend {
+        name: &'static str,
+        text: &'static str,
+        confidence: Option<f64>,
+    }
+
+    impl Plugin for FixedBackend {
+        fn name(&self) -> &str {
+            self.name
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl OcrBackend for FixedBackend {
+        async fn process_image(&self, _image_bytes: &[u8], _config: &OcrConfig) -> Result<ExtractionResult> {
+            if self.text.is_empty() {
+                return Err(KreuzbergError::ocr("no text"));
+            }
+            let mut metadata = Metadata::default();
+            if let Some(confidence) = self.confidence {
+                metadata
+                    .additional
+                    .insert(Cow::Borrowed("mean_confidence"), serde_json::json!(confidence));
+            }
+            Ok(ExtractionResult {
+                content: self.text.to_string(),
+                mime_type: Cow::Borrowed("text/plain"),
+                metadata,
+                tables: vec![],
+                detected_languages: None,
+                chunks: None,
+                images: None,
+                djot_content: None,
+                scripts: None,
+                pages: None,
+                elements: None,
+            })
+        }
+
+        fn supports_language(&self, lang: &str) -> bool {
+            lang == "eng" || self.name != "fixed-c"
+        }
+
+        fn backend_type(&self) -> OcrBackendType {
+            OcrBackendType::Custom
+        }
+    }
+
+    fn backend(name: &'static str, text: &'static str, confidence: Option<f64>) -> Arc<dyn OcrBackend> {
+        Arc::new(FixedBackend { name, text, confidence })
+    }
+
+    #[tokio::test]
+    async fn test_ensemble_outvotes_misreadings() {
+        let ensemble = EnsembleOcrBackend::new(vec![
+            backend("fixed-a", "Invoice tota1: 120 EUR\npayable in thirty days", Some(0.9)),
+            backend("fixed-b", "lnvoice total: 120 EUR\npayable in thirty days", Some(0.8)),
+            backend("fixed-c", "Invoice total: 12O EUR\npayable in thirty days", Some(80.0)),
+        ]);
+        let result = ensemble.process_image(b"", &OcrConfig::default()).await.unwrap();
+
+        assert_eq!(result.content, "Invoice total: 120 EUR\npayable in thirty days");
+        let summary = &result.metadata.additional[ENSEMBLE_KEY];
+        assert_eq!(summary["primary"], "fixed-a");
+        assert_eq!(summary["changed_words"], 1);
+        assert_eq!(summary["inserted_words"], 0);
+        assert_eq!(
+            summary["backends"],
+            serde_json::json!(["fixed-a", "fixed-b", "fixed-c"])
+        );
+
+        assert!(ensemble.supports_language("eng"));
+        assert!(!ensemble.supports_language("deu"));
+    }
+
+    #[tokio::test]
+    async fn test_ensemble_restores_dropped_and_removes_spurious_words() {
+        let ensemble = EnsembleOcrBackend::new(vec![
+            backend("fixed-a", "Total amount due", None),
+            backend("fixed-b", "Total amount . due now", None),
+            backend("fixed-c", "Total amount due now", None),
+            backend("fixed-d", "", None),
+        ]);
+        let result = ensemble.process_image(b"", &OcrConfig::default()).await.unwrap();
+
+        assert_eq!(result.content, "Total amount due now");
+        let summary = &result.metadata.additional[ENSEMBLE_KEY];
+        assert_eq!(summary["inserted_words"], 1);
+        assert_eq!(summary["failed"], serde_json::json!(["fixed-d"]));
+
+        let all_failing = EnsembleOcrBackend::new(vec![backend("fixed-d", "", None)]);
+        assert!(all_failing.process_image(b"", &OcrConfig::default()).await.is_err());
+    }
+
+    #[test]
+    fn test_read_against_aligns_changed_regions() {
+        let reading = read_against(&["a", "b", "c", "d"], &["a", "x", "y", "z", "d", "e"]);
+        assert_eq!(reading.slots, vec!["a", "x y z", "", "d"]);
+        assert_eq!(reading.gaps[4], "e");
+
+        let reading = read_against(&["a", "b", "c"], &["a", "B", "c"]);
+        assert_eq!(reading.slots, vec!["a", "B", "c"]);
+        assert!(reading.gaps.iter().all(String::is_empty));
+    }
+
+    #[test]
+    #[serial]
+    fn test_resolve_ocr_backend_builds_ensemble() {
+        register_ocr_backend(backend("fixed-a", "a", None)).unwrap();
+        register_ocr_backend(backend("fixed-b", "b", None)).unwrap();
+
+        let config = OcrConfig {
+            backend: "fixed-a".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(resolve_ocr_backend(&config).unwrap().name(), "fixed-a");
+
+        let config = OcrConfig {
+            ensemble: vec!["fixed-a".to_string(), "fixed-b".to_string()],
+            ..config
+        };
+        assert_eq!(resolve_ocr_backend(&config).unwrap().name(), "ensemble");
+
+        let config = OcrConfig {
+            ensemble: vec!["fixed-a".to_string(), "fixed-missing".to_string()],
+            ..config
+        };
+        assert!(resolve_ocr_backend(&config).is_err());
+
+        unregister_ocr_backend("fixed-a").unwrap();
+        unregister_ocr_backend("fixed-b").unwrap();
+    }
+}
@@ -61,6 +61,7 @@ mod tests {
                 chunks: None,
                 images: None,
                 djot_content: None,
+                scripts: None,
                 pages: None,
                 elements: None,
             })
@@ -236,6 +237,7 @@ mod tests {
                     chunks: None,
                     images: None,
                     djot_content: None,
+                    scripts: None,
                     pages: None,
                     elements: None,
                 })
@@ -3,6 +3,7 @@
 //! This module provides functions for managing the global extractor registry.
 
 use super::r#trait::DocumentExtractor;
+use crate::plugins::ExtractorDescriptor;
 use std::sync::Arc;
 
 /// Register a document extractor with the global registry.
@@ -120,11 +121,13 @@ pub fn unregister_extractor(name: &str) -> crate::Result<()> {
 
 /// List all registered extractors.
 ///
-/// Returns the names of all extractors currently registered in the global registry.
+/// Returns a descriptor of each extractor currently registered in the global registry,
+/// sorted by name.
 ///
 /// # Returns
 ///
-/// A vector of extractor names.
+/// A vector of extractor descriptors with name, version, MIME types, priority and
+/// required crate features.
 ///
 /// # Example
 ///
@@ -133,13 +136,13 @@ pub fn unregister_extractor(name: &str) -> crate::Result<()> {
 ///
 /// # tokio_test::block_on(async {
 /// let extractors = list_extractors()?;
-/// for name in extractors {
-///     println!("Registered extractor: {}", name);
+/// for extractor in extractors {
+///     println!("{} {}: {:?}", extractor.name, extractor.version, extractor.mime_types);
 /// }
 /// # Ok::<(), kreuzberg::KreuzbergError>(())
 /// # });
 /// ```
-pub fn list_extractors() -> crate::Result<Vec<String>> {
+pub fn list_extractors() -> crate::Result<Vec<ExtractorDescriptor>> {
     use crate::plugins::registry::get_document_extractor_registry;
 
     let registry = get_document_extractor_registry();
@@ -147,7 +150,7 @@ pub fn list_extractors() -> crate::Result<Vec<String>> {
         .read()
         .expect("~keep Failed to acquire read lock on extractor registry"); // ~keep
 
-    Ok(registry.list())
+    Ok(registry.describe())
 }
 
 /// Clear all extractors from the global registry.
@@ -231,6 +234,7 @@ mod tests {
                 chunks: None,
                 images: None,
                 djot_content: None,
+                scripts: None,
                 pages: None,
                 elements: None,
             })
@@ -306,7 +310,11 @@ mod tests {
 
         let list = super::list_extractors().unwrap();
         assert_eq!(list.len(), 1);
-        assert!(list.contains(&"mock-extractor".to_string()));
+        assert_eq!(list[0].name, "mock-extractor");
+        assert_eq!(list[0].version, "1.0.0");
+        assert_eq!(list[0].mime_types, vec!["text/test-list-2".to_string()]);
+        assert_eq!(list[0].priority, 51);
+        assert!(list[0].required_features.is_empty());
 
         super::unregister_extractor("mock-extractor").unwrap();
     }
@@ -370,6 +378,7 @@ mod tests {
                     chunks: None,
                     images: None,
                     djot_content: None,
+                    scripts: None,
                     pages: None,
                     elements: None,
                 })
@@ -418,6 +427,7 @@ mod tests {
                     chunks: None,
                     images: None,
                     djot_content: None,
+                    scripts: None,
                     pages: None,
                     elements: None,
                 })
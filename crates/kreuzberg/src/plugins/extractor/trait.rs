@@ -5,7 +5,7 @@
 use crate::Result;
 use crate::core::config::ExtractionConfig;
 use crate::plugins::Plugin;
-use crate::types::ExtractionResult;
+use crate::types::{ExtractionResult, Metadata};
 use async_trait::async_trait;
 use std::path::Path;
 
@@ -228,7 +228,7 @@ pub trait DocumentExtractor: Plugin {
         #[cfg(feature = "tokio-runtime")]
         {
             use crate::core::io;
-            let bytes = io::read_file_async(path).await?;
+            let bytes = io::open_file_async(path, config.io.as_ref()).await?;
             self.extract_bytes(&bytes, mime_type, config).await
         }
         #[cfg(not(feature = "tokio-runtime"))]
@@ -240,6 +240,29 @@ pub trait DocumentExtractor: Plugin {
         }
     }
 
+    /// Extract only the metadata of a document, without its text.
+    ///
+    /// Used by [`extract_metadata_only`](crate::extract_metadata_only). Override when
+    /// the format stores its metadata apart from its content, so it can be read without
+    /// parsing the document body. The metadata should be what `extract_bytes` would
+    /// report with the same `config`, without fields computed from the content.
+    ///
+    /// The default implementation returns `Ok(None)`, and the caller falls back to a
+    /// full extraction.
+    ///
+    /// # Errors
+    ///
+    /// Same as `extract_bytes`.
+    async fn extract_metadata(
+        &self,
+        content: &[u8],
+        mime_type: &str,
+        config: &ExtractionConfig,
+    ) -> Result<Option<Metadata>> {
+        let _ = (content, mime_type, config);
+        Ok(None)
+    }
+
     /// Get the list of MIME types supported by this extractor.
     ///
     /// Can include exact MIME types and prefix patterns:
@@ -381,6 +404,15 @@ pub trait DocumentExtractor: Plugin {
         true
     }
 
+    /// Optional: Crate features the extractor is built with.
+    ///
+    /// Reported by [`list_extractors`](crate::plugins::list_extractors) so management
+    /// tools can tell which formats depend on optional features. Defaults to none;
+    /// built-in extractors list the Cargo features they need, such as `pdf` or `office`.
+    fn required_features(&self) -> &[&str] {
+        &[]
+    }
+
     /// Attempt to get a reference to this extractor as a SyncExtractor.
     ///
     /// Returns None if the extractor doesn't support synchronous extraction.
@@ -0,0 +1,197 @@
+//! Built-in content filters.
+//!
+//! These filters are configured through [`ContentFilterConfig`] and run before
+//! registered filters; they are not added to the global registry.
+
+use super::r#trait::ContentFilter;
+use crate::core::config::{ContentFilterConfig, ExtractionConfig};
+use crate::plugins::Plugin;
+use crate::{KreuzbergError, Result};
+use once_cell::sync::Lazy;
+use regex::{Regex, RegexSet};
+use std::borrow::Cow;
+use std::sync::Arc;
+
+/// Common English profanity, matched as whole words with regular suffixes.
+const PROFANITY: &[&str] = &[
+    "arse",
+    "arsehole",
+    "asshole",
+    "bastard",
+    "bitch",
+    "bollocks",
+    "bullshit",
+    "cock",
+    "crap",
+    "cunt",
+    "damn",
+    "dick",
+    "dickhead",
+    "fuck",
+    "fucker",
+    "motherfucker",
+    "piss",
+    "prick",
+    "shit",
+    "slut",
+    "twat",
+    "wanker",
+    "whore",
+];
+
+static PROFANITY_REGEX: Lazy<Regex> = Lazy::new(|| {
+    let words = PROFANITY.join("|");
+    Regex::new(&format!(r"(?i)\b(?:{})(?:s|es|ed|ing|er|ers)?\b", words)).expect("~keep valid profanity pattern") // ~keep
+});
+
+/// Replaces profane words with asterisks of the same length.
+pub struct ProfanityFilter;
+
+impl Plugin for ProfanityFilter {
+    fn name(&self) -> &str {
+        "profanity"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ContentFilter for ProfanityFilter {
+    fn filter<'a>(&self, text: &'a str, _config: &ExtractionConfig) -> Result<Cow<'a, str>> {
+        Ok(PROFANITY_REGEX.replace_all(text, |captures: &regex::Captures<'_>| {
+            "*".repeat(captures[0].chars().count())
+        }))
+    }
+}
+
+/// Replaces matches of configured regular expressions.
+pub struct DenylistFilter {
+    patterns: Vec<Regex>,
+    quick_check: RegexSet,
+    replacement: String,
+}
+
+impl DenylistFilter {
+    /// Compile a denylist.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if a pattern is not a valid regular expression.
+    pub fn new(patterns: &[String], replacement: impl Into<String>) -> Result<Self> {
+        let compiled = patterns
+            .iter()
+            .map(|pattern| {
+                Regex::new(pattern).map_err(|e| {
+                    KreuzbergError::validation(format!("Invalid content filter denylist pattern '{}': {}", pattern, e))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let quick_check = RegexSet::new(patterns)
+            .map_err(|e| KreuzbergError::validation(format!("Invalid content filter denylist: {}", e)))?;
+
+        Ok(Self {
+            patterns: compiled,
+            quick_check,
+            replacement: replacement.into(),
+        })
+    }
+}
+
+impl Plugin for DenylistFilter {
+    fn name(&self) -> &str {
+        "denylist"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+impl ContentFilter for DenylistFilter {
+    fn filter<'a>(&self, text: &'a str, _config: &ExtractionConfig) -> Result<Cow<'a, str>> {
+        let matched = self.quick_check.matches(text);
+        if !matched.matched_any() {
+            return Ok(Cow::Borrowed(text));
+        }
+
+        let mut filtered = text.to_string();
+        for index in matched.iter() {
+            let replacement = regex::NoExpand(&self.replacement);
+            if let Cow::Owned(replaced) = self.patterns[index].replace_all(&filtered, replacement) {
+                filtered = replaced;
+            }
+        }
+        Ok(Cow::Owned(filtered))
+    }
+}
+
+/// Built-in filters enabled by the configuration, in execution order.
+pub(crate) fn configured_filters(config: &ContentFilterConfig) -> Result<Vec<Arc<dyn ContentFilter>>> {
+    let mut filters: Vec<Arc<dyn ContentFilter>> = Vec::new();
+    if !config.denylist.is_empty() {
+        filters.push(Arc::new(DenylistFilter::new(
+            &config.denylist,
+            config.denylist_replacement.clone(),
+        )?));
+    }
+    if config.mask_profanity {
+        filters.push(Arc::new(ProfanityFilter));
+    }
+    Ok(filters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profanity_filter_masks_whole_words() {
+        let config = ExtractionConfig::default();
+        let filtered = ProfanityFilter
+            .filter("What the Shit, scunthorpe shits", &config)
+            .unwrap();
+        assert_eq!(filtered, "What the ****, scunthorpe *****");
+
+        assert!(matches!(
+            ProfanityFilter.filter("clean text", &config).unwrap(),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_denylist_filter() {
+        let config = ExtractionConfig::default();
+        let filter = DenylistFilter::new(
+            &[r"(?i)project\s+falcon".to_string(), r"\d{3}-\d{4}".to_string()],
+            "[x]",
+        )
+        .unwrap();
+        assert_eq!(
+            filter.filter("Project  Falcon: call 555-1234 ($1)", &config).unwrap(),
+            "[x]: call [x] ($1)"
+        );
+        assert!(matches!(
+            filter.filter("nothing here", &config).unwrap(),
+            Cow::Borrowed(_)
+        ));
+
+        assert!(DenylistFilter::new(&["(unclosed".to_string()], "").is_err());
+    }
+}
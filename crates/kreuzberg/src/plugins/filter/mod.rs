@@ -0,0 +1,121 @@
+//! Content filter plugin system.
+//!
+//! This module provides the trait, registry and built-in implementations for
+//! filters applied to extracted text before results are returned.
+
+mod builtin;
+mod registry;
+mod r#trait;
+
+pub use builtin::{DenylistFilter, ProfanityFilter};
+pub use r#trait::ContentFilter;
+
+pub use registry::{clear_content_filters, list_content_filters, register_content_filter, unregister_content_filter};
+
+pub(crate) use builtin::configured_filters;
+pub(crate) use registry::registered_content_filters;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Result;
+    use crate::core::config::{ChunkingConfig, ContentFilterConfig, ExtractionConfig};
+    use crate::plugins::Plugin;
+    use crate::types::Chunk;
+    use std::borrow::Cow;
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// Replaces a marker token that only appears in this test's input.
+    struct MarkerFilter {
+        chunks_seen: AtomicUsize,
+    }
+
+    impl Plugin for MarkerFilter {
+        fn name(&self) -> &str {
+            "marker-filter"
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl ContentFilter for MarkerFilter {
+        fn filter<'a>(&self, text: &'a str, _config: &ExtractionConfig) -> Result<Cow<'a, str>> {
+            if text.contains("kz-filter-marker") {
+                Ok(Cow::Owned(text.replace("kz-filter-marker", "[marker]")))
+            } else {
+                Ok(Cow::Borrowed(text))
+            }
+        }
+
+        fn filter_chunk(&self, chunk: &mut Chunk, _config: &ExtractionConfig) -> Result<()> {
+            if chunk.content.contains("[marker]") {
+                self.chunks_seen.fetch_add(1, Ordering::SeqCst);
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_builtin_filters_apply_before_chunking() {
+        let config = ExtractionConfig {
+            content_filter: Some(ContentFilterConfig {
+                mask_profanity: true,
+                denylist: vec![r"ACME-\d+".to_string()],
+                ..Default::default()
+            }),
+            chunking: Some(ChunkingConfig::default()),
+            ..Default::default()
+        };
+
+        let result = crate::extract_bytes(b"Ticket ACME-42 is damn late.", "text/plain", &config)
+            .await
+            .unwrap();
+
+        assert_eq!(result.content, "Ticket [FILTERED] is **** late.");
+        assert_eq!(result.chunks.unwrap()[0].content, result.content);
+        assert_eq!(
+            result.metadata.additional.get("content_filters"),
+            Some(&serde_json::json!(["denylist", "profanity"]))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_denylist_fails_extraction() {
+        let config = ExtractionConfig {
+            content_filter: Some(ContentFilterConfig {
+                denylist: vec!["(".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        assert!(crate::extract_bytes(b"text", "text/plain", &config).await.is_err());
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_registered_filter_runs_on_content_and_chunks() {
+        let filter = Arc::new(MarkerFilter {
+            chunks_seen: AtomicUsize::new(0),
+        });
+        register_content_filter(filter.clone()).unwrap();
+
+        let config = ExtractionConfig {
+            chunking: Some(ChunkingConfig::default()),
+            ..Default::default()
+        };
+        let result = crate::extract_bytes(b"before kz-filter-marker after", "text/plain", &config).await;
+
+        unregister_content_filter("marker-filter").unwrap();
+
+        assert_eq!(result.unwrap().content, "before [marker] after");
+        assert_eq!(filter.chunks_seen.load(Ordering::SeqCst), 1);
+    }
+}
@@ -0,0 +1,101 @@
+//! Content filter registry management.
+//!
+//! This module provides functions for managing the global content filter registry.
+
+use super::r#trait::ContentFilter;
+use std::sync::Arc;
+
+/// Register a content filter with the global registry.
+///
+/// The filter's `name()` method is used as the registration name.
+///
+/// # Errors
+///
+/// - `KreuzbergError::Validation` - Invalid filter name (empty or contains whitespace)
+/// - Any error from the filter's `initialize()` method
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::plugins::{ContentFilter, Plugin, register_content_filter};
+/// use kreuzberg::{ExtractionConfig, KreuzbergError, Result};
+/// use std::borrow::Cow;
+/// use std::sync::Arc;
+///
+/// struct NoopFilter;
+///
+/// impl Plugin for NoopFilter {
+///     fn name(&self) -> &str { "noop" }
+///     fn version(&self) -> String { "1.0.0".to_string() }
+///     fn initialize(&self) -> Result<()> { Ok(()) }
+///     fn shutdown(&self) -> Result<()> { Ok(()) }
+/// }
+///
+/// impl ContentFilter for NoopFilter {
+///     fn filter<'a>(&self, text: &'a str, _config: &ExtractionConfig) -> Result<Cow<'a, str>> {
+///         Ok(Cow::Borrowed(text))
+///     }
+/// }
+///
+/// register_content_filter(Arc::new(NoopFilter))?;
+/// # kreuzberg::plugins::unregister_content_filter("noop")?;
+/// # Ok::<(), KreuzbergError>(())
+/// ```
+pub fn register_content_filter(filter: Arc<dyn ContentFilter>) -> crate::Result<()> {
+    use crate::plugins::registry::get_content_filter_registry;
+
+    let registry = get_content_filter_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on content filter registry"); // ~keep
+
+    registry.register(filter)
+}
+
+/// Unregister a content filter by name.
+///
+/// Removes the filter from the global registry and calls its `shutdown()` method.
+/// Unknown names are ignored.
+pub fn unregister_content_filter(name: &str) -> crate::Result<()> {
+    use crate::plugins::registry::get_content_filter_registry;
+
+    let registry = get_content_filter_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on content filter registry"); // ~keep
+
+    registry.remove(name)
+}
+
+/// List the names of all registered content filters.
+pub fn list_content_filters() -> crate::Result<Vec<String>> {
+    use crate::plugins::registry::get_content_filter_registry;
+
+    let registry = get_content_filter_registry();
+    let registry = registry
+        .read()
+        .expect("~keep Failed to acquire read lock on content filter registry"); // ~keep
+
+    Ok(registry.list())
+}
+
+/// Clear all content filters from the global registry, calling their `shutdown()` methods.
+pub fn clear_content_filters() -> crate::Result<()> {
+    use crate::plugins::registry::get_content_filter_registry;
+
+    let registry = get_content_filter_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on content filter registry"); // ~keep
+
+    registry.shutdown_all()
+}
+
+/// Registered filters in priority order (highest first).
+pub(crate) fn registered_content_filters() -> crate::Result<Vec<Arc<dyn ContentFilter>>> {
+    let registry = crate::plugins::registry::get_content_filter_registry();
+    let registry = registry
+        .read()
+        .map_err(|e| crate::KreuzbergError::Other(format!("Content filter registry lock poisoned: {}", e)))?;
+    Ok(registry.get_all())
+}
@@ -0,0 +1,73 @@
+//! Content filter trait.
+//!
+//! This module defines the trait for filtering extracted text before results are returned.
+
+use crate::Result;
+use crate::core::config::ExtractionConfig;
+use crate::plugins::Plugin;
+use crate::types::Chunk;
+use std::borrow::Cow;
+
+/// Trait for content filter plugins.
+///
+/// Filters run after post-processing and before chunking, in priority order
+/// (highest first):
+///
+/// 1. [`filter`](Self::filter) - on the document content, per-page content and table cells
+/// 2. [`filter_chunk`](Self::filter_chunk) - on every chunk, after chunking (only when chunking is enabled)
+///
+/// Chunks are created from the filtered content, so text filtered in step 1 never
+/// reaches them.
+///
+/// # Use Cases
+///
+/// - **Compliance**: Mask profanity or internal code names before indexing
+/// - **Privacy**: Remove identifiers matched by patterns
+/// - **Policy Enforcement**: Reject documents containing forbidden terms
+///
+/// # Error Handling
+///
+/// Errors fail the extraction, so a filter can reject a document.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::plugins::{ContentFilter, Plugin};
+/// use kreuzberg::{ExtractionConfig, Result};
+/// use std::borrow::Cow;
+///
+/// struct CodeNameFilter;
+///
+/// impl Plugin for CodeNameFilter {
+///     fn name(&self) -> &str { "code-names" }
+///     fn version(&self) -> String { "1.0.0".to_string() }
+///     fn initialize(&self) -> Result<()> { Ok(()) }
+///     fn shutdown(&self) -> Result<()> { Ok(()) }
+/// }
+///
+/// impl ContentFilter for CodeNameFilter {
+///     fn filter<'a>(&self, text: &'a str, _config: &ExtractionConfig) -> Result<Cow<'a, str>> {
+///         if text.contains("Falcon") {
+///             Ok(Cow::Owned(text.replace("Falcon", "[project]")))
+///         } else {
+///             Ok(Cow::Borrowed(text))
+///         }
+///     }
+/// }
+/// ```
+pub trait ContentFilter: Plugin {
+    /// Filter a piece of extracted text.
+    ///
+    /// Return `Cow::Borrowed` when the text is unchanged.
+    fn filter<'a>(&self, text: &'a str, config: &ExtractionConfig) -> Result<Cow<'a, str>>;
+
+    /// Inspect or modify a chunk after chunking.
+    fn filter_chunk(&self, _chunk: &mut Chunk, _config: &ExtractionConfig) -> Result<()> {
+        Ok(())
+    }
+
+    /// Execution priority (higher runs first). Defaults to 50.
+    fn priority(&self) -> i32 {
+        50
+    }
+}
@@ -0,0 +1,403 @@
+//! Timeouts and circuit breaking for plugin calls.
+//!
+//! When `ExtractionConfig::plugin_guard` is set, calls into extractors, OCR backends,
+//! post-processors, chunk processors, validators and translators go through [`guarded`]. A call that
+//! takes longer than its timeout fails with a plugin error. A plugin whose calls fail
+//! `failure_threshold` times in a row is disabled for `cooldown_ms`; after the cooldown
+//! the next call is let through, and one more failure disables the plugin again.
+//!
+//! Only failures that point at the plugin itself count: timeouts and errors of class
+//! `plugin`, `extractor_panicked`, `ocr`, `missing_dependency` or `other`. Errors caused
+//! by the input, such as parsing or validation errors, neither count as failures nor
+//! reset the count.
+//!
+//! Whatever the configuration, extractor calls also go through [`catch_extractor_panic`],
+//! which turns a panic of the extractor into a [`KreuzbergError::ExtractorPanicked`]
+//! error, so that one malformed document cannot bring down a batch or a server, and
+//! fallback chains can move on to the next extractor.
+
+use crate::core::config::PluginGuardConfig;
+use crate::error::ErrorClass;
+use crate::{KreuzbergError, Result};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::panic::AssertUnwindSafe;
+use std::task::Poll;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "ocr")]
+use crate::OcrConfig;
+#[cfg(feature = "ocr")]
+use crate::core::config::ExtractionConfig;
+#[cfg(feature = "ocr")]
+use crate::plugins::{OcrBackend, OcrBackendType, Plugin};
+#[cfg(feature = "ocr")]
+use crate::types::ExtractionResult;
+#[cfg(feature = "ocr")]
+use async_trait::async_trait;
+#[cfg(feature = "ocr")]
+use std::path::Path;
+#[cfg(feature = "ocr")]
+use std::sync::Arc;
+
+/// The kinds of plugins whose calls are guarded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub(crate) enum PluginKind {
+    Extractor,
+    #[cfg(feature = "ocr")]
+    OcrBackend,
+    PostProcessor,
+    ChunkProcessor,
+    Validator,
+    Translator,
+}
+
+impl PluginKind {
+    fn label(self) -> &'static str {
+        match self {
+            PluginKind::Extractor => "Extractor",
+            #[cfg(feature = "ocr")]
+            PluginKind::OcrBackend => "OCR backend",
+            PluginKind::PostProcessor => "Post-processor",
+            PluginKind::ChunkProcessor => "Chunk processor",
+            PluginKind::Validator => "Validator",
+            PluginKind::Translator => "Translator",
+        }
+    }
+}
+
+/// Failure count and cooldown of one plugin.
+#[derive(Debug, Default)]
+struct Breaker {
+    consecutive_failures: u32,
+    disabled_until: Option<Instant>,
+}
+
+static BREAKERS: Lazy<Mutex<HashMap<(PluginKind, String), Breaker>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Remaining cooldown of the plugin `name`, when it is disabled.
+pub(crate) fn disabled_for(kind: PluginKind, name: &str) -> Option<Duration> {
+    let breakers = BREAKERS.lock();
+    let disabled_until = breakers.get(&(kind, name.to_string()))?.disabled_until?;
+    disabled_until.checked_duration_since(Instant::now())
+}
+
+/// Warning recorded when the plugin `name` is skipped because it is disabled.
+pub(crate) fn skipped_warning(kind: PluginKind, name: &str, remaining: Duration) -> String {
+    format!(
+        "{} '{}' skipped: disabled for another {} ms after repeated failures",
+        kind.label(),
+        name,
+        remaining.as_millis()
+    )
+}
+
+/// Run `call` into the plugin `name` with the timeout and circuit breaker of `config`.
+///
+/// Without a `config`, `call` is awaited as is. A disabled plugin is not called and
+/// fails with a plugin error.
+pub(crate) async fn guarded<T>(
+    kind: PluginKind,
+    name: &str,
+    config: Option<&PluginGuardConfig>,
+    call: impl Future<Output = Result<T>>,
+) -> Result<T> {
+    let Some(config) = config else {
+        return call.await;
+    };
+
+    if let Some(remaining) = disabled_for(kind, name) {
+        return Err(KreuzbergError::Plugin {
+            message: format!(
+                "{} '{}' is disabled for another {} ms after repeated failures",
+                kind.label(),
+                name,
+                remaining.as_millis()
+            ),
+            plugin_name: name.to_string(),
+        });
+    }
+
+    let outcome = match config.timeout_for(name) {
+        #[cfg(feature = "tokio-runtime")]
+        Some(timeout) => tokio::time::timeout(timeout, call).await.unwrap_or_else(|_| {
+            Err(KreuzbergError::Plugin {
+                message: format!("{} '{}' timed out after {} ms", kind.label(), name, timeout.as_millis()),
+                plugin_name: name.to_string(),
+            })
+        }),
+        _ => call.await,
+    };
+
+    record(kind, name, config, &outcome);
+    outcome
+}
+
+/// Update the breaker of the plugin `name` with the outcome of a call.
+fn record<T>(kind: PluginKind, name: &str, config: &PluginGuardConfig, outcome: &Result<T>) {
+    let failed = match outcome {
+        Ok(_) => false,
+        Err(e) => matches!(
+            e.class(),
+            ErrorClass::Plugin
+                | ErrorClass::ExtractorPanicked
+                | ErrorClass::Ocr
+                | ErrorClass::MissingDependency
+                | ErrorClass::Other
+        ),
+    };
+    if outcome.is_err() && !failed {
+        return;
+    }
+
+    let mut breakers = BREAKERS.lock();
+    if !failed {
+        breakers.remove(&(kind, name.to_string()));
+        return;
+    }
+
+    let breaker = breakers.entry((kind, name.to_string())).or_default();
+    breaker.consecutive_failures += 1;
+    if config.failure_threshold > 0 && breaker.consecutive_failures >= config.failure_threshold {
+        breaker.disabled_until = Some(Instant::now() + Duration::from_millis(config.cooldown_ms));
+        tracing::warn!(
+            "{} '{}' failed {} times in a row and is disabled for {} ms",
+            kind.label(),
+            name,
+            breaker.consecutive_failures,
+            config.cooldown_ms
+        );
+    }
+}
+
+/// Run `call` into the extractor `name`, turning a panic into an
+/// [`KreuzbergError::ExtractorPanicked`] error.
+pub(crate) async fn catch_extractor_panic<T>(name: &str, call: impl Future<Output = Result<T>>) -> Result<T> {
+    let mut call = std::pin::pin!(call);
+    std::future::poll_fn(|cx| {
+        std::panic::catch_unwind(AssertUnwindSafe(|| call.as_mut().poll(cx)))
+            .unwrap_or_else(|payload| Poll::Ready(Err(extractor_panicked(name, payload.as_ref()))))
+    })
+    .await
+}
+
+/// Run the synchronous `call` into the extractor `name`, turning a panic into an
+/// [`KreuzbergError::ExtractorPanicked`] error.
+#[cfg(any(not(feature = "tokio-runtime"), test))]
+pub(crate) fn catch_extractor_panic_sync<T>(name: &str, call: impl FnOnce() -> Result<T>) -> Result<T> {
+    std::panic::catch_unwind(AssertUnwindSafe(call))
+        .unwrap_or_else(|payload| Err(extractor_panicked(name, payload.as_ref())))
+}
+
+fn extractor_panicked(name: &str, payload: &(dyn Any + Send)) -> KreuzbergError {
+    let payload = crate::panic_context::extract_panic_message(payload);
+    tracing::error!("Extractor '{}' panicked: {}", name, payload);
+    KreuzbergError::ExtractorPanicked {
+        name: name.to_string(),
+        payload,
+    }
+}
+
+/// Wrap `backend` so its calls are guarded by `config.plugin_guard`.
+///
+/// Returns `backend` unchanged when `config.plugin_guard` is not set.
+#[cfg(feature = "ocr")]
+pub(crate) fn guard_ocr_backend(backend: Arc<dyn OcrBackend>, config: &ExtractionConfig) -> Arc<dyn OcrBackend> {
+    match &config.plugin_guard {
+        Some(guard) => Arc::new(GuardedOcrBackend {
+            inner: backend,
+            config: guard.clone(),
+        }),
+        None => backend,
+    }
+}
+
+/// An OCR backend whose recognition calls are guarded.
+#[cfg(feature = "ocr")]
+struct GuardedOcrBackend {
+    inner: Arc<dyn OcrBackend>,
+    config: PluginGuardConfig,
+}
+
+#[cfg(feature = "ocr")]
+impl Plugin for GuardedOcrBackend {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn version(&self) -> String {
+        self.inner.version()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        self.inner.initialize()
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        self.inner.shutdown()
+    }
+
+    fn description(&self) -> &str {
+        self.inner.description()
+    }
+
+    fn requires_network(&self) -> bool {
+        self.inner.requires_network()
+    }
+}
+
+#[cfg(feature = "ocr")]
+#[async_trait]
+impl OcrBackend for GuardedOcrBackend {
+    async fn process_image(&self, image_bytes: &[u8], config: &OcrConfig) -> Result<ExtractionResult> {
+        guarded(
+            PluginKind::OcrBackend,
+            self.inner.name(),
+            Some(&self.config),
+            self.inner.process_image(image_bytes, config),
+        )
+        .await
+    }
+
+    async fn process_file(&self, path: &Path, config: &OcrConfig) -> Result<ExtractionResult> {
+        guarded(
+            PluginKind::OcrBackend,
+            self.inner.name(),
+            Some(&self.config),
+            self.inner.process_file(path, config),
+        )
+        .await
+    }
+
+    fn supports_language(&self, lang: &str) -> bool {
+        self.inner.supports_language(lang)
+    }
+
+    fn backend_type(&self) -> OcrBackendType {
+        self.inner.backend_type()
+    }
+
+    fn supported_languages(&self) -> Vec<String> {
+        self.inner.supported_languages()
+    }
+
+    fn supports_table_detection(&self) -> bool {
+        self.inner.supports_table_detection()
+    }
+
+    fn supports_handwriting(&self) -> bool {
+        self.inner.supports_handwriting()
+    }
+
+    fn required_features(&self) -> &[&str] {
+        self.inner.required_features()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(timeout_ms: u64) -> PluginGuardConfig {
+        PluginGuardConfig {
+            timeout_ms,
+            failure_threshold: 2,
+            cooldown_ms: 60_000,
+            ..Default::default()
+        }
+    }
+
+    fn plugin_error() -> Result<()> {
+        Err(KreuzbergError::Plugin {
+            message: "boom".to_string(),
+            plugin_name: "flaky".to_string(),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_guarded_disables_plugin_after_repeated_failures() {
+        let config = config(0);
+        let name = "guard-test-flaky";
+
+        guarded(PluginKind::Validator, name, Some(&config), async { plugin_error() })
+            .await
+            .unwrap_err();
+        guarded(PluginKind::Validator, name, Some(&config), async {
+            Err::<(), _>(KreuzbergError::validation("bad input"))
+        })
+        .await
+        .unwrap_err();
+        assert!(disabled_for(PluginKind::Validator, name).is_none());
+
+        guarded(PluginKind::Validator, name, Some(&config), async { plugin_error() })
+            .await
+            .unwrap_err();
+        assert!(disabled_for(PluginKind::Validator, name).is_some());
+        assert!(disabled_for(PluginKind::PostProcessor, name).is_none());
+
+        let err = guarded(PluginKind::Validator, name, Some(&config), async { Ok(()) })
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("is disabled"));
+
+        guarded(PluginKind::Validator, name, None, async { Ok(()) })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_guarded_times_out_slow_calls() {
+        let config = config(20);
+        let name = "guard-test-slow";
+
+        let err = guarded(PluginKind::PostProcessor, name, Some(&config), async {
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            Ok(())
+        })
+        .await
+        .unwrap_err();
+        assert!(err.to_string().contains("timed out after 20 ms"));
+
+        guarded(PluginKind::PostProcessor, name, Some(&config), async { Ok(()) })
+            .await
+            .unwrap();
+        assert!(
+            !BREAKERS
+                .lock()
+                .contains_key(&(PluginKind::PostProcessor, name.to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_catch_extractor_panic() {
+        let err = catch_extractor_panic("panicky-extractor", async {
+            tokio::task::yield_now().await;
+            let parts: Vec<u8> = Vec::new();
+            Ok(parts[3])
+        })
+        .await
+        .unwrap_err();
+        assert!(
+            matches!(&err, KreuzbergError::ExtractorPanicked { name, payload }
+                if name == "panicky-extractor" && payload.contains("index out of bounds")),
+            "{err:?}"
+        );
+
+        let value = catch_extractor_panic("panicky-extractor", async { Ok(7) })
+            .await
+            .unwrap();
+        assert_eq!(value, 7);
+    }
+
+    #[test]
+    fn test_catch_extractor_panic_sync() {
+        let err = catch_extractor_panic_sync::<()>("panicky-extractor", || panic!("malformed document")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Extractor 'panicky-extractor' panicked: malformed document"
+        );
+    }
+}
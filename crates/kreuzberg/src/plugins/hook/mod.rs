@@ -0,0 +1,120 @@
+//! Pipeline hook plugin system.
+//!
+//! This module provides the trait and registry for hooks that run around every
+//! extraction.
+
+mod registry;
+mod r#trait;
+
+pub use r#trait::{ExtractionRequest, PipelineHook};
+
+pub use registry::{clear_pipeline_hooks, list_pipeline_hooks, register_pipeline_hook, unregister_pipeline_hook};
+
+pub(crate) use registry::registered_pipeline_hooks;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ExtractionConfig;
+    use crate::plugins::Plugin;
+    use crate::types::{ExtractionResult, SecurityFinding};
+    use crate::{KreuzbergError, Result};
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    const ACCEPTED: &[u8] = b"# pipeline hook test: accepted";
+    const REJECTED: &[u8] = b"# pipeline hook test: rejected input";
+
+    /// Records calls for the test payloads and rejects [`REJECTED`].
+    ///
+    /// The registry is global, so inputs from other tests are left untouched.
+    struct RecordingHook {
+        calls: Mutex<Vec<String>>,
+    }
+
+    impl RecordingHook {
+        fn is_test_request(request: &ExtractionRequest) -> bool {
+            matches!(request.size_bytes, Some(n) if n == ACCEPTED.len() as u64 || n == REJECTED.len() as u64)
+        }
+    }
+
+    impl Plugin for RecordingHook {
+        fn name(&self) -> &str {
+            "recording-hook"
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl PipelineHook for RecordingHook {
+        async fn before_extract(&self, request: &mut ExtractionRequest) -> Result<()> {
+            if !Self::is_test_request(request) {
+                return Ok(());
+            }
+            self.calls.lock().unwrap().push(format!("before:{}", request.mime_type));
+            if request.size_bytes == Some(REJECTED.len() as u64) {
+                return Err(KreuzbergError::validation("Input rejected by hook"));
+            }
+            request.mime_type = "text/plain".to_string();
+            Ok(())
+        }
+
+        fn should_inspect(&self, request: &ExtractionRequest) -> bool {
+            Self::is_test_request(request)
+        }
+
+        async fn inspect_input(&self, _request: &ExtractionRequest, content: &[u8]) -> Result<Vec<SecurityFinding>> {
+            self.calls.lock().unwrap().push(format!("inspect:{}", content.len()));
+            Ok(Vec::new())
+        }
+
+        async fn after_extract(&self, request: &ExtractionRequest, result: &mut ExtractionResult) -> Result<()> {
+            if Self::is_test_request(request) {
+                self.calls.lock().unwrap().push(format!("after:{}", request.mime_type));
+                result.content.push_str(" [audited]");
+            }
+            Ok(())
+        }
+
+        async fn on_error(&self, request: &ExtractionRequest, error: &KreuzbergError) {
+            if Self::is_test_request(request) {
+                self.calls.lock().unwrap().push(format!("error:{}", error));
+            }
+        }
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_hooks_wrap_extraction() {
+        let hook = Arc::new(RecordingHook {
+            calls: Mutex::new(Vec::new()),
+        });
+        register_pipeline_hook(hook.clone()).unwrap();
+
+        let config = ExtractionConfig::default();
+        let result = crate::extract_bytes(ACCEPTED, "text/markdown", &config).await;
+        let rejected = crate::extract_bytes(REJECTED, "text/markdown", &config).await;
+
+        unregister_pipeline_hook("recording-hook").unwrap();
+
+        let result = result.unwrap();
+        assert_eq!(result.content, "# pipeline hook test: accepted [audited]");
+        assert_eq!(result.mime_type, "text/plain");
+        assert!(matches!(rejected, Err(KreuzbergError::Validation { .. })));
+
+        let calls = hook.calls.lock().unwrap();
+        assert_eq!(calls[0], "before:text/markdown");
+        assert_eq!(calls[1], format!("inspect:{}", ACCEPTED.len()));
+        assert_eq!(calls[2], "after:text/plain");
+        assert_eq!(calls[3], "before:text/markdown");
+        assert!(calls[4].starts_with("error:"));
+    }
+}
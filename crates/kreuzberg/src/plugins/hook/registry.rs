@@ -0,0 +1,98 @@
+//! Pipeline hook registry management.
+//!
+//! This module provides functions for managing the global pipeline hook registry.
+
+use super::r#trait::PipelineHook;
+use std::sync::Arc;
+
+/// Register a pipeline hook with the global registry.
+///
+/// The hook's `name()` method is used as the registration name.
+///
+/// # Errors
+///
+/// - `KreuzbergError::Validation` - Invalid hook name (empty or contains whitespace)
+/// - Any error from the hook's `initialize()` method
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::plugins::{PipelineHook, Plugin, register_pipeline_hook};
+/// use kreuzberg::{KreuzbergError, Result};
+/// use async_trait::async_trait;
+/// use std::sync::Arc;
+///
+/// struct NoopHook;
+///
+/// impl Plugin for NoopHook {
+///     fn name(&self) -> &str { "noop" }
+///     fn version(&self) -> String { "1.0.0".to_string() }
+///     fn initialize(&self) -> Result<()> { Ok(()) }
+///     fn shutdown(&self) -> Result<()> { Ok(()) }
+/// }
+///
+/// #[async_trait]
+/// impl PipelineHook for NoopHook {}
+///
+/// register_pipeline_hook(Arc::new(NoopHook))?;
+/// # kreuzberg::plugins::unregister_pipeline_hook("noop")?;
+/// # Ok::<(), KreuzbergError>(())
+/// ```
+pub fn register_pipeline_hook(hook: Arc<dyn PipelineHook>) -> crate::Result<()> {
+    use crate::plugins::registry::get_pipeline_hook_registry;
+
+    let registry = get_pipeline_hook_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on pipeline hook registry"); // ~keep
+
+    registry.register(hook)
+}
+
+/// Unregister a pipeline hook by name.
+///
+/// Removes the hook from the global registry and calls its `shutdown()` method.
+/// Unknown names are ignored.
+pub fn unregister_pipeline_hook(name: &str) -> crate::Result<()> {
+    use crate::plugins::registry::get_pipeline_hook_registry;
+
+    let registry = get_pipeline_hook_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on pipeline hook registry"); // ~keep
+
+    registry.remove(name)
+}
+
+/// List the names of all registered pipeline hooks.
+pub fn list_pipeline_hooks() -> crate::Result<Vec<String>> {
+    use crate::plugins::registry::get_pipeline_hook_registry;
+
+    let registry = get_pipeline_hook_registry();
+    let registry = registry
+        .read()
+        .expect("~keep Failed to acquire read lock on pipeline hook registry"); // ~keep
+
+    Ok(registry.list())
+}
+
+/// Clear all pipeline hooks from the global registry, calling their `shutdown()` methods.
+pub fn clear_pipeline_hooks() -> crate::Result<()> {
+    use crate::plugins::registry::get_pipeline_hook_registry;
+
+    let registry = get_pipeline_hook_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on pipeline hook registry"); // ~keep
+
+    registry.shutdown_all()
+}
+
+/// Registered hooks in priority order (highest first).
+pub(crate) fn registered_pipeline_hooks() -> crate::Result<Vec<Arc<dyn PipelineHook>>> {
+    let registry = crate::plugins::registry::get_pipeline_hook_registry();
+    let registry = registry
+        .read()
+        .map_err(|e| crate::KreuzbergError::Other(format!("Pipeline hook registry lock poisoned: {}", e)))?;
+    Ok(registry.get_all())
+}
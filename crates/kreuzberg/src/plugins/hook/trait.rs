@@ -0,0 +1,117 @@
+//! Pipeline hook trait.
+//!
+//! This module defines the trait for observing and shaping extraction requests.
+
+use crate::core::config::ExtractionConfig;
+use crate::plugins::Plugin;
+use crate::types::{ExtractionResult, SecurityFinding};
+use crate::{KreuzbergError, Result};
+use async_trait::async_trait;
+use std::path::PathBuf;
+
+/// An extraction about to be run, as seen by pipeline hooks.
+///
+/// Hooks may modify `mime_type` and `config` in [`PipelineHook::before_extract`];
+/// the extraction then runs with the modified values.
+#[derive(Debug, Clone)]
+pub struct ExtractionRequest {
+    /// Source file, or `None` when extracting from bytes
+    pub path: Option<PathBuf>,
+    /// Size of the input in bytes, when known
+    pub size_bytes: Option<u64>,
+    /// MIME type used to select the extractor
+    pub mime_type: String,
+    /// Effective configuration for this extraction
+    pub config: ExtractionConfig,
+}
+
+/// Trait for pipeline hook plugins.
+///
+/// Hooks are called around every extraction run through `extract_file`,
+/// `extract_bytes` and the batch functions, in priority order (highest first):
+///
+/// 1. [`before_extract`](Self::before_extract) - before the extractor is selected
+/// 2. [`inspect_input`](Self::inspect_input) - with the raw input, before the extractor runs (only when [`should_inspect`](Self::should_inspect) returns `true`)
+/// 3. [`before_chunk`](Self::before_chunk) - after post-processing, before chunking (only when chunking is enabled)
+/// 4. [`after_extract`](Self::after_extract) - after the full pipeline, on the final result
+/// 5. [`on_error`](Self::on_error) - if any of the above, the extractor, or the pipeline failed
+///
+/// # Use Cases
+///
+/// - **Auditing**: Record who extracted which document and what came out
+/// - **Request Shaping**: Force OCR for certain paths, override MIME types, reject inputs
+/// - **Quarantine**: Scan inputs for malware and report or reject suspicious ones
+/// - **Telemetry**: Emit custom metrics and traces per extraction
+///
+/// # Error Handling
+///
+/// Errors returned from `before_extract`, `inspect_input`, `before_chunk` and `after_extract`
+/// fail the extraction, so a hook can reject a request. `on_error` cannot fail.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::plugins::{ExtractionRequest, PipelineHook, Plugin};
+/// use kreuzberg::{ExtractionResult, Result};
+/// use async_trait::async_trait;
+///
+/// struct AuditHook;
+///
+/// impl Plugin for AuditHook {
+///     fn name(&self) -> &str { "audit" }
+///     fn version(&self) -> String { "1.0.0".to_string() }
+///     fn initialize(&self) -> Result<()> { Ok(()) }
+///     fn shutdown(&self) -> Result<()> { Ok(()) }
+/// }
+///
+/// #[async_trait]
+/// impl PipelineHook for AuditHook {
+///     async fn after_extract(&self, request: &ExtractionRequest, result: &mut ExtractionResult) -> Result<()> {
+///         println!("{:?} -> {} characters", request.path, result.content.len());
+///         Ok(())
+///     }
+/// }
+/// ```
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait PipelineHook: Plugin {
+    /// Called before extraction; may modify the request or reject it with an error.
+    async fn before_extract(&self, _request: &mut ExtractionRequest) -> Result<()> {
+        Ok(())
+    }
+
+    /// Whether [`inspect_input`](Self::inspect_input) is called for this request. Defaults to `false`.
+    ///
+    /// Files are read into memory an extra time for inspection, so hooks should only opt
+    /// in for the requests they inspect.
+    fn should_inspect(&self, _request: &ExtractionRequest) -> bool {
+        false
+    }
+
+    /// Called with the raw input before the extractor runs.
+    ///
+    /// The returned findings are reported in `metadata.additional["security_findings"]`
+    /// together with those of the built-in inspection, and inputs with findings blocked by
+    /// [`SecurityConfig`](crate::core::config::SecurityConfig) are rejected.
+    async fn inspect_input(&self, _request: &ExtractionRequest, _content: &[u8]) -> Result<Vec<SecurityFinding>> {
+        Ok(Vec::new())
+    }
+
+    /// Called with the final result after the pipeline has run.
+    async fn after_extract(&self, _request: &ExtractionRequest, _result: &mut ExtractionResult) -> Result<()> {
+        Ok(())
+    }
+
+    /// Called when the extraction fails, including failures raised by other hooks.
+    async fn on_error(&self, _request: &ExtractionRequest, _error: &KreuzbergError) {}
+
+    /// Called after post-processing and before the content is chunked.
+    async fn before_chunk(&self, _result: &mut ExtractionResult, _config: &ExtractionConfig) -> Result<()> {
+        Ok(())
+    }
+
+    /// Execution priority (higher runs first). Defaults to 50.
+    fn priority(&self) -> i32 {
+        50
+    }
+}
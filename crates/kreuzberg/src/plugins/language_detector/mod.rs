@@ -0,0 +1,76 @@
+//! Language detector plugin system.
+//!
+//! This module provides the trait and registry for backends that detect the
+//! languages of extracted text.
+
+mod registry;
+mod r#trait;
+
+pub use r#trait::LanguageDetector;
+
+pub use registry::{
+    clear_language_detectors, list_language_detectors, register_language_detector, unregister_language_detector,
+};
+
+pub(crate) use registry::detect_languages_with_registered;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Result;
+    use crate::core::config::{ExtractionConfig, LanguageDetectionConfig};
+    use crate::plugins::Plugin;
+    use std::sync::Arc;
+
+    /// Reports Latin for every text.
+    struct LatinDetector;
+
+    impl Plugin for LatinDetector {
+        fn name(&self) -> &str {
+            "latin-detector"
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl LanguageDetector for LatinDetector {
+        fn detect(&self, _text: &str, _config: &LanguageDetectionConfig) -> Result<Option<Vec<String>>> {
+            Ok(Some(vec!["lat".to_string()]))
+        }
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_registered_detector_replaces_default() {
+        let config = ExtractionConfig {
+            language_detection: Some(LanguageDetectionConfig {
+                enabled: true,
+                min_confidence: 0.5,
+                detect_multiple: false,
+            }),
+            ..Default::default()
+        };
+        let text = b"Hello world! This is a short English sentence for the detector.";
+
+        register_language_detector(Arc::new(LatinDetector)).unwrap();
+        let listed = list_language_detectors().unwrap();
+        let result = crate::extract_bytes(text, "text/plain", &config).await;
+        unregister_language_detector("latin-detector").unwrap();
+
+        assert_eq!(listed[0], "latin-detector");
+        assert_eq!(result.unwrap().detected_languages, Some(vec!["lat".to_string()]));
+
+        #[cfg(feature = "language-detection")]
+        {
+            let result = crate::extract_bytes(text, "text/plain", &config).await.unwrap();
+            assert_eq!(result.detected_languages, Some(vec!["eng".to_string()]));
+        }
+    }
+}
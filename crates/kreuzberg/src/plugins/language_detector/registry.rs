@@ -0,0 +1,130 @@
+//! Language detector registry management.
+//!
+//! This module provides functions for managing the global language detector registry.
+
+use super::r#trait::LanguageDetector;
+use crate::core::config::LanguageDetectionConfig;
+use crate::{KreuzbergError, Result};
+use std::sync::Arc;
+
+/// Register a language detector with the global registry.
+///
+/// The detector's `name()` method is used as the registration name; a detector with
+/// the same name is replaced.
+///
+/// # Errors
+///
+/// - `KreuzbergError::Validation` - Invalid detector name (empty or contains whitespace)
+/// - Any error from the detector's `initialize()` method
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::core::config::LanguageDetectionConfig;
+/// use kreuzberg::plugins::{LanguageDetector, Plugin, register_language_detector};
+/// use kreuzberg::{KreuzbergError, Result};
+/// use std::sync::Arc;
+///
+/// struct EnglishOnly;
+///
+/// impl Plugin for EnglishOnly {
+///     fn name(&self) -> &str { "english-only" }
+///     fn version(&self) -> String { "1.0.0".to_string() }
+///     fn initialize(&self) -> Result<()> { Ok(()) }
+///     fn shutdown(&self) -> Result<()> { Ok(()) }
+/// }
+///
+/// impl LanguageDetector for EnglishOnly {
+///     fn detect(&self, _text: &str, _config: &LanguageDetectionConfig) -> Result<Option<Vec<String>>> {
+///         Ok(Some(vec!["eng".to_string()]))
+///     }
+/// }
+///
+/// register_language_detector(Arc::new(EnglishOnly))?;
+/// # kreuzberg::plugins::unregister_language_detector("english-only")?;
+/// # Ok::<(), KreuzbergError>(())
+/// ```
+pub fn register_language_detector(detector: Arc<dyn LanguageDetector>) -> Result<()> {
+    use crate::plugins::registry::get_language_detector_registry;
+
+    let registry = get_language_detector_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on language detector registry"); // ~keep
+
+    registry.register(detector)
+}
+
+/// Unregister a language detector by name.
+///
+/// Removes the detector from the global registry and calls its `shutdown()` method.
+/// Unknown names are ignored.
+pub fn unregister_language_detector(name: &str) -> Result<()> {
+    use crate::plugins::registry::get_language_detector_registry;
+
+    let registry = get_language_detector_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on language detector registry"); // ~keep
+
+    registry.remove(name)
+}
+
+/// List the names of all registered language detectors, the one in use first.
+pub fn list_language_detectors() -> Result<Vec<String>> {
+    use crate::plugins::registry::get_language_detector_registry;
+
+    let registry = get_language_detector_registry();
+    let registry = registry
+        .read()
+        .expect("~keep Failed to acquire read lock on language detector registry"); // ~keep
+
+    Ok(registry.list())
+}
+
+/// Clear all language detectors from the global registry, calling their `shutdown()`
+/// methods. This includes the built-in `whatlang` detector.
+pub fn clear_language_detectors() -> Result<()> {
+    use crate::plugins::registry::get_language_detector_registry;
+
+    let registry = get_language_detector_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on language detector registry"); // ~keep
+
+    registry.shutdown_all()
+}
+
+/// Detect the languages of `text` with the registered detector of the highest priority.
+///
+/// Returns `None` without calling a detector when detection is disabled or `text` is
+/// blank.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::MissingDependency` if no detector is registered, and any
+/// error of the detector.
+pub(crate) fn detect_languages_with_registered(
+    text: &str,
+    config: &LanguageDetectionConfig,
+) -> Result<Option<Vec<String>>> {
+    if !config.enabled || text.trim().is_empty() {
+        return Ok(None);
+    }
+
+    let registry = crate::plugins::registry::get_language_detector_registry();
+    let detector = registry
+        .read()
+        .map_err(|e| KreuzbergError::Other(format!("Language detector registry lock poisoned: {}", e)))?
+        .get_default();
+    let Some(detector) = detector else {
+        let message = if cfg!(feature = "language-detection") {
+            "No language detector registered"
+        } else {
+            "Language detection feature not enabled and no language detector registered"
+        };
+        return Err(KreuzbergError::MissingDependency(message.to_string()));
+    };
+
+    detector.detect(text, config)
+}
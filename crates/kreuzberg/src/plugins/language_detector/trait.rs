@@ -0,0 +1,59 @@
+//! Language detector trait.
+//!
+//! This module defines the trait for backends that detect the languages of extracted text.
+
+use crate::Result;
+use crate::core::config::LanguageDetectionConfig;
+use crate::plugins::Plugin;
+
+/// Trait for language detection backends.
+///
+/// When `ExtractionConfig::language_detection` is set, the registered detector with the
+/// highest [`priority`](Self::priority) detects the languages of the document content.
+/// The built-in `whatlang` detector (feature `language-detection`) has priority 0, so
+/// any detector registered with the default priority replaces it. Register detectors
+/// based on fastText, CLD3 or lingua for better accuracy on short texts.
+///
+/// Detectors are only called when detection is enabled and the content is not blank.
+///
+/// # Error Handling
+///
+/// Errors do not fail the extraction; they are recorded in
+/// `metadata.additional["language_detection_error"]`.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::core::config::LanguageDetectionConfig;
+/// use kreuzberg::plugins::{LanguageDetector, Plugin};
+/// use kreuzberg::Result;
+///
+/// struct GermanOnly;
+///
+/// impl Plugin for GermanOnly {
+///     fn name(&self) -> &str { "german-only" }
+///     fn version(&self) -> String { "1.0.0".to_string() }
+///     fn initialize(&self) -> Result<()> { Ok(()) }
+///     fn shutdown(&self) -> Result<()> { Ok(()) }
+/// }
+///
+/// impl LanguageDetector for GermanOnly {
+///     fn detect(&self, text: &str, _config: &LanguageDetectionConfig) -> Result<Option<Vec<String>>> {
+///         let german = ["der", "die", "und"].iter().any(|word| text.split_whitespace().any(|w| w == *word));
+///         Ok(german.then(|| vec!["deu".to_string()]))
+///     }
+/// }
+/// ```
+pub trait LanguageDetector: Plugin {
+    /// Detect the languages of `text`.
+    ///
+    /// Returns ISO 639-3 codes, most prominent first, or `None` when no language is
+    /// detected with at least `config.min_confidence`. With `config.detect_multiple`
+    /// unset, at most one language should be returned.
+    fn detect(&self, text: &str, config: &LanguageDetectionConfig) -> Result<Option<Vec<String>>>;
+
+    /// Priority among registered detectors (the highest is used). Defaults to 50.
+    fn priority(&self) -> i32 {
+        50
+    }
+}
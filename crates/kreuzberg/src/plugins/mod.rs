@@ -9,7 +9,13 @@
 //! - [`OcrBackend`] - OCR processing plugins
 //! - [`DocumentExtractor`] - Document format extraction plugins
 //! - [`PostProcessor`] - Content post-processing plugins
+//! - [`ChunkProcessor`] - Per-chunk enrichment plugins
 //! - [`Validator`] - Validation plugins
+//! - [`PipelineHook`] - Hooks called around every extraction
+//! - [`ContentFilter`] - Filters applied to extracted text before results are returned
+//! - [`LanguageDetector`] - Language detection backends
+//! - [`Translator`] - Machine translation backends
+//! - [`SentenceSegmenter`] - Sentence segmentation backends
 //!
 //! # Language Support
 //!
@@ -202,25 +208,72 @@
 //! }
 //! ```
 
+mod chunk_processor;
+mod descriptor;
+mod ensemble;
 mod extractor;
+mod filter;
+pub(crate) mod guard;
+mod hook;
+mod language_detector;
 mod ocr;
 mod processor;
 pub mod registry;
+mod sentence_segmenter;
 pub mod startup_validation;
 mod traits;
+mod translator;
 mod validator;
 
+pub(crate) use chunk_processor::registered_chunk_processors;
+pub use chunk_processor::{
+    ChunkProcessor, clear_chunk_processors, list_chunk_processors, register_chunk_processor, unregister_chunk_processor,
+};
+pub use descriptor::{ExtractorDescriptor, OcrBackendDescriptor};
+pub(crate) use ensemble::resolve_ocr_backend;
+pub use ensemble::{ENSEMBLE_KEY, EnsembleOcrBackend};
 pub use extractor::{DocumentExtractor, clear_extractors, list_extractors, register_extractor, unregister_extractor};
+pub use filter::{
+    ContentFilter, DenylistFilter, ProfanityFilter, clear_content_filters, list_content_filters,
+    register_content_filter, unregister_content_filter,
+};
+pub(crate) use filter::{configured_filters, registered_content_filters};
+pub(crate) use hook::registered_pipeline_hooks;
+pub use hook::{
+    ExtractionRequest, PipelineHook, clear_pipeline_hooks, list_pipeline_hooks, register_pipeline_hook,
+    unregister_pipeline_hook,
+};
+pub(crate) use language_detector::detect_languages_with_registered;
+pub use language_detector::{
+    LanguageDetector, clear_language_detectors, list_language_detectors, register_language_detector,
+    unregister_language_detector,
+};
 pub use ocr::{
     OcrBackend, OcrBackendType, clear_ocr_backends, list_ocr_backends, register_ocr_backend, unregister_ocr_backend,
 };
 pub use processor::{PostProcessor, ProcessingStage, list_post_processors};
+#[cfg(any(feature = "chunking", feature = "quality"))]
+pub(crate) use sentence_segmenter::resolve_sentence_segmenter;
+pub use sentence_segmenter::{
+    SentenceSegmenter, clear_sentence_segmenters, list_sentence_segmenters, register_sentence_segmenter,
+    unregister_sentence_segmenter,
+};
 pub use traits::Plugin;
+pub(crate) use translator::resolve_translator;
+pub use translator::{Translator, clear_translators, list_translators, register_translator, unregister_translator};
+#[cfg(feature = "pdf")]
+pub use validator::PdfConformanceValidator;
+pub(crate) use validator::configured_validators;
 pub use validator::{Validator, clear_validators, list_validators, register_validator, unregister_validator};
 
 // Re-export registry items for backward compatibility
 pub use registry::{
-    DOCUMENT_EXTRACTOR_REGISTRY, DocumentExtractorRegistry, OCR_BACKEND_REGISTRY, OcrBackendRegistry,
-    POST_PROCESSOR_REGISTRY, PostProcessorRegistry, VALIDATOR_REGISTRY, ValidatorRegistry,
-    get_document_extractor_registry, get_ocr_backend_registry, get_post_processor_registry, get_validator_registry,
+    CHUNK_PROCESSOR_REGISTRY, CONTENT_FILTER_REGISTRY, ChunkProcessorRegistry, ContentFilterRegistry,
+    DOCUMENT_EXTRACTOR_REGISTRY, DocumentExtractorRegistry, LANGUAGE_DETECTOR_REGISTRY, LanguageDetectorRegistry,
+    OCR_BACKEND_REGISTRY, OcrBackendRegistry, PIPELINE_HOOK_REGISTRY, POST_PROCESSOR_REGISTRY, PipelineHookRegistry,
+    PostProcessorRegistry, SENTENCE_SEGMENTER_REGISTRY, SentenceSegmenterRegistry, TRANSLATOR_REGISTRY,
+    TranslatorRegistry, VALIDATOR_REGISTRY, ValidatorRegistry, get_chunk_processor_registry,
+    get_content_filter_registry, get_document_extractor_registry, get_language_detector_registry,
+    get_ocr_backend_registry, get_pipeline_hook_registry, get_post_processor_registry, get_sentence_segmenter_registry,
+    get_translator_registry, get_validator_registry,
 };
@@ -4,9 +4,10 @@
 
 use crate::Result;
 use crate::core::config::OcrConfig;
-use crate::plugins::Plugin;
+use crate::plugins::{OcrBackendDescriptor, Plugin};
 use crate::types::ExtractionResult;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::sync::Arc;
 
@@ -14,7 +15,8 @@ use std::sync::Arc;
 use crate::KreuzbergError;
 
 /// OCR backend types.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum OcrBackendType {
     /// Tesseract OCR (native Rust binding)
     Tesseract,
@@ -22,6 +24,8 @@ pub enum OcrBackendType {
     EasyOCR,
     /// PaddleOCR (Python-based, via FFI)
     PaddleOCR,
+    /// ONNX Runtime detection + recognition models (CPU or GPU)
+    Onnx,
     /// Custom/third-party OCR backend
     Custom,
 }
@@ -270,6 +274,21 @@ pub trait OcrBackend: Plugin {
     fn supports_table_detection(&self) -> bool {
         false
     }
+
+    /// Optional: Check if the backend can read handwritten text.
+    ///
+    /// Defaults to `false`. Override if your backend honors [`OcrConfig::handwriting`].
+    fn supports_handwriting(&self) -> bool {
+        false
+    }
+
+    /// Optional: Crate features the backend is built with.
+    ///
+    /// Reported by [`list_ocr_backends`]. Defaults to none; built-in backends list the
+    /// Cargo features they need, such as `ocr`.
+    fn required_features(&self) -> &[&str] {
+        &[]
+    }
 }
 
 /// Register an OCR backend with the global registry.
@@ -385,11 +404,13 @@ pub fn unregister_ocr_backend(name: &str) -> crate::Result<()> {
 
 /// List all registered OCR backends.
 ///
-/// Returns the names of all OCR backends currently registered in the global registry.
+/// Returns a descriptor of each OCR backend currently registered in the global
+/// registry, sorted by name.
 ///
 /// # Returns
 ///
-/// A vector of OCR backend names.
+/// A vector of OCR backend descriptors with name, version, backend type, languages,
+/// capabilities and required crate features.
 ///
 /// # Example
 ///
@@ -398,13 +419,13 @@ pub fn unregister_ocr_backend(name: &str) -> crate::Result<()> {
 ///
 /// # tokio_test::block_on(async {
 /// let backends = list_ocr_backends()?;
-/// for name in backends {
-///     println!("Registered OCR backend: {}", name);
+/// for backend in backends {
+///     println!("{} ({:?}): {:?}", backend.name, backend.backend_type, backend.languages);
 /// }
 /// # Ok::<(), kreuzberg::KreuzbergError>(())
 /// # });
 /// ```
-pub fn list_ocr_backends() -> crate::Result<Vec<String>> {
+pub fn list_ocr_backends() -> crate::Result<Vec<OcrBackendDescriptor>> {
     use crate::plugins::registry::get_ocr_backend_registry;
 
     let registry = get_ocr_backend_registry();
@@ -413,7 +434,7 @@ pub fn list_ocr_backends() -> crate::Result<Vec<String>> {
         .read()
         .expect("OCR backend registry lock poisoned - critical runtime error");
 
-    Ok(registry.list())
+    Ok(registry.describe())
 }
 
 /// Clear all OCR backends from the global registry.
@@ -486,6 +507,7 @@ mod tests {
                 chunks: None,
                 images: None,
                 djot_content: None,
+                scripts: None,
                 pages: None,
                 elements: None,
             })
@@ -515,6 +537,7 @@ mod tests {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         };
 
         let result = backend.process_image(b"fake image data", &config).await.unwrap();
@@ -603,6 +626,7 @@ mod tests {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         };
 
         let result = backend.process_file(path, &config).await.unwrap();
@@ -641,6 +665,7 @@ mod tests {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         };
 
         let result = backend.process_image(b"", &config).await;
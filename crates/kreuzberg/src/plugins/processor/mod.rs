@@ -74,6 +74,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -126,6 +127,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -195,6 +197,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -228,6 +231,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
@@ -256,6 +260,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -309,6 +314,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -322,6 +328,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -353,6 +360,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
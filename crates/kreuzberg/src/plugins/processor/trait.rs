@@ -7,12 +7,14 @@ use crate::core::config::ExtractionConfig;
 use crate::plugins::Plugin;
 use crate::types::ExtractionResult;
 use async_trait::async_trait;
+use serde::Serialize;
 
 /// Processing stages for post-processors.
 ///
 /// Post-processors are executed in stage order (Early → Middle → Late).
 /// Use stages to control the order of post-processing operations.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum ProcessingStage {
     /// Early stage - foundational processing.
     ///
@@ -59,7 +61,11 @@ pub enum ProcessingStage {
 /// 2. **Middle** - Keyword extraction, token reduction
 /// 3. **Late** - Custom hooks, final validation
 ///
-/// Within each stage, processors are executed in registration order.
+/// Within each stage, processors are executed by priority (highest first) and then in
+/// registration order. A processor can also name processors it must run before or after
+/// with [`runs_before`](PostProcessor::runs_before) and
+/// [`runs_after`](PostProcessor::runs_after); these constraints take precedence over
+/// priority.
 ///
 /// # Error Handling
 ///
@@ -281,4 +287,46 @@ pub trait PostProcessor: Plugin {
     fn estimated_duration_ms(&self, _result: &ExtractionResult) -> u64 {
         0
     }
+
+    /// Optional: Names of post-processors this processor must run before.
+    ///
+    /// Only processors in the same stage can be reordered: naming a processor from an
+    /// earlier stage makes registration fail, and one from a later stage already runs
+    /// after this one. Names of processors that are not registered are ignored.
+    /// Defaults to none.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use kreuzberg::plugins::{Plugin, PostProcessor, ProcessingStage};
+    /// # use kreuzberg::{Result, ExtractionResult, ExtractionConfig};
+    /// # use async_trait::async_trait;
+    /// # struct Normalizer;
+    /// # impl Plugin for Normalizer {
+    /// #     fn name(&self) -> &str { "normalizer" }
+    /// #     fn version(&self) -> String { "1.0.0".to_string() }
+    /// #     fn initialize(&self) -> Result<()> { Ok(()) }
+    /// #     fn shutdown(&self) -> Result<()> { Ok(()) }
+    /// # }
+    /// # #[async_trait]
+    /// # impl PostProcessor for Normalizer {
+    /// #     fn processing_stage(&self) -> ProcessingStage { ProcessingStage::Middle }
+    /// #     async fn process(&self, result: &mut ExtractionResult, _: &ExtractionConfig) -> Result<()> { Ok(()) }
+    /// /// Normalize text before keywords are extracted from it
+    /// fn runs_before(&self) -> Vec<String> {
+    ///     vec!["keyword-extraction".to_string()]
+    /// }
+    /// # }
+    /// ```
+    fn runs_before(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Optional: Names of post-processors this processor must run after.
+    ///
+    /// The counterpart of [`runs_before`](PostProcessor::runs_before), with the same
+    /// rules. Defaults to none.
+    fn runs_after(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
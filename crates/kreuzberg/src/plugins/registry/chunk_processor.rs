@@ -0,0 +1,173 @@
+//! Chunk processor registry implementation.
+
+use crate::plugins::ChunkProcessor;
+use crate::{Result, audit};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Registry for chunk processor plugins.
+///
+/// Processors run by priority, highest first; processors of equal priority are
+/// ordered by name.
+pub struct ChunkProcessorRegistry {
+    processors: HashMap<String, Arc<dyn ChunkProcessor>>,
+}
+
+impl ChunkProcessorRegistry {
+    /// Create a new empty chunk processor registry.
+    pub fn new() -> Self {
+        Self {
+            processors: HashMap::new(),
+        }
+    }
+
+    /// Register a chunk processor, replacing a processor of the same name.
+    ///
+    /// # Arguments
+    ///
+    /// * `processor` - The processor to register
+    pub fn register(&mut self, processor: Arc<dyn ChunkProcessor>) -> Result<()> {
+        let name = processor.name().to_string();
+        let version = processor.version();
+        let outcome = self.insert(processor);
+        audit::record_registration("chunk_processor", &name, version, &outcome);
+        outcome
+    }
+
+    fn insert(&mut self, processor: Arc<dyn ChunkProcessor>) -> Result<()> {
+        let name = processor.name().to_string();
+
+        super::validate_plugin_name(&name)?;
+
+        processor.initialize()?;
+
+        self.processors.insert(name, processor);
+        Ok(())
+    }
+
+    /// Get a chunk processor by name.
+    pub fn get(&self, name: &str) -> Option<Arc<dyn ChunkProcessor>> {
+        self.processors.get(name).cloned()
+    }
+
+    /// All chunk processors in execution order.
+    pub fn get_all(&self) -> Vec<Arc<dyn ChunkProcessor>> {
+        let mut processors: Vec<_> = self.processors.values().cloned().collect();
+        processors.sort_by(|a, b| b.priority().cmp(&a.priority()).then_with(|| a.name().cmp(b.name())));
+        processors
+    }
+
+    /// List all registered chunk processor names in execution order.
+    pub fn list(&self) -> Vec<String> {
+        self.get_all()
+            .iter()
+            .map(|processor| processor.name().to_string())
+            .collect()
+    }
+
+    /// Remove a chunk processor from the registry.
+    ///
+    /// Calls `shutdown()` on the processor before removing.
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        let registered = self.list().iter().any(|registered| registered == name);
+        let outcome = self.evict(name);
+        if registered {
+            audit::record_unregistration("chunk_processor", name, &outcome);
+        }
+        outcome
+    }
+
+    fn evict(&mut self, name: &str) -> Result<()> {
+        if let Some(processor) = self.processors.remove(name) {
+            processor.shutdown()?;
+        }
+        Ok(())
+    }
+
+    /// Shutdown all chunk processors and clear the registry.
+    pub fn shutdown_all(&mut self) -> Result<()> {
+        let names = self.list();
+        let outcome = self.evict_all();
+        if !names.is_empty() {
+            audit::record_clear("chunk_processor", names, &outcome);
+        }
+        outcome
+    }
+
+    fn evict_all(&mut self) -> Result<()> {
+        for (_, processor) in self.processors.drain() {
+            processor.shutdown()?;
+        }
+        Ok(())
+    }
+}
+
+impl Default for ChunkProcessorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ExtractionConfig;
+    use crate::plugins::Plugin;
+    use crate::types::{Chunk, ExtractionResult};
+    use async_trait::async_trait;
+
+    struct MockProcessor {
+        name: String,
+        priority: i32,
+    }
+
+    impl Plugin for MockProcessor {
+        fn name(&self) -> &str {
+            &self.name
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl ChunkProcessor for MockProcessor {
+        async fn process_chunk(&self, _: &mut Chunk, _: &ExtractionResult, _: &ExtractionConfig) -> Result<()> {
+            Ok(())
+        }
+
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+    }
+
+    fn processor(name: &str, priority: i32) -> Arc<dyn ChunkProcessor> {
+        Arc::new(MockProcessor {
+            name: name.to_string(),
+            priority,
+        })
+    }
+
+    #[test]
+    fn test_chunk_processor_registry_order() {
+        let mut registry = ChunkProcessorRegistry::new();
+        registry.register(processor("keywords", 10)).unwrap();
+        registry.register(processor("section-path", 90)).unwrap();
+        registry.register(processor("entities", 10)).unwrap();
+        assert_eq!(registry.list(), vec!["section-path", "entities", "keywords"]);
+        assert!(registry.get("entities").is_some());
+
+        registry.remove("section-path").unwrap();
+        assert_eq!(registry.list(), vec!["entities", "keywords"]);
+        assert!(registry.register(processor("", 50)).is_err());
+
+        registry.shutdown_all().unwrap();
+        assert!(registry.get_all().is_empty());
+    }
+}
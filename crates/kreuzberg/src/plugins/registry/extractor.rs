@@ -1,7 +1,7 @@
 //! Document extractor registry implementation.
 
-use crate::plugins::DocumentExtractor;
-use crate::{KreuzbergError, Result};
+use crate::plugins::{DocumentExtractor, ExtractorDescriptor};
+use crate::{KreuzbergError, Result, audit};
 use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
@@ -39,6 +39,14 @@ impl DocumentExtractorRegistry {
     /// - `Ok(())` if registration succeeded
     /// - `Err(...)` if initialization failed
     pub fn register(&mut self, extractor: Arc<dyn DocumentExtractor>) -> Result<()> {
+        let name = extractor.name().to_string();
+        let version = extractor.version();
+        let outcome = self.insert(extractor);
+        audit::record_registration("extractor", &name, version, &outcome);
+        outcome
+    }
+
+    fn insert(&mut self, extractor: Arc<dyn DocumentExtractor>) -> Result<()> {
         let name = extractor.name().to_string();
         let priority = extractor.priority();
         let mime_types: Vec<String> = extractor.supported_mime_types().iter().map(|s| s.to_string()).collect();
@@ -142,13 +150,52 @@ impl DocumentExtractorRegistry {
         Err(KreuzbergError::UnsupportedFormat(mime_type.to_string()))
     }
 
+    /// Get a registered extractor by name, or None if no extractor has that name.
+    pub fn get_by_name(&self, name: &str) -> Option<Arc<dyn DocumentExtractor>> {
+        self.name_index.get(name)?.iter().find_map(|(mime_type, priority)| {
+            self.extractors
+                .get(mime_type)?
+                .get(priority)
+                .filter(|extractor| extractor.name() == name)
+                .map(Arc::clone)
+        })
+    }
+
     /// List all registered extractors.
     pub fn list(&self) -> Vec<String> {
         self.name_index.keys().cloned().collect()
     }
 
+    /// Describe all registered extractors, sorted by name.
+    pub fn describe(&self) -> Vec<ExtractorDescriptor> {
+        let mut descriptors: Vec<ExtractorDescriptor> = self
+            .name_index
+            .iter()
+            .filter_map(|(name, entries)| {
+                entries.iter().find_map(|(mime_type, priority)| {
+                    self.extractors
+                        .get(mime_type)?
+                        .get(priority)
+                        .filter(|extractor| extractor.name() == name)
+                })
+            })
+            .map(|extractor| ExtractorDescriptor::new(extractor.as_ref()))
+            .collect();
+        descriptors.sort_by(|a, b| a.name.cmp(&b.name));
+        descriptors
+    }
+
     /// Remove an extractor from the registry.
     pub fn remove(&mut self, name: &str) -> Result<()> {
+        let registered = self.list().iter().any(|registered| registered == name);
+        let outcome = self.evict(name);
+        if registered {
+            audit::record_unregistration("extractor", name, &outcome);
+        }
+        outcome
+    }
+
+    fn evict(&mut self, name: &str) -> Result<()> {
         let index_entries = match self.name_index.remove(name) {
             Some(entries) => entries,
             None => {
@@ -194,6 +241,15 @@ impl DocumentExtractorRegistry {
 
     /// Shutdown all extractors and clear the registry.
     pub fn shutdown_all(&mut self) -> Result<()> {
+        let names = self.list();
+        let outcome = self.evict_all();
+        if !names.is_empty() {
+            audit::record_clear("extractor", names, &outcome);
+        }
+        outcome
+    }
+
+    fn evict_all(&mut self) -> Result<()> {
         let names = self.list();
         let count = names.len();
 
@@ -202,7 +258,7 @@ impl DocumentExtractorRegistry {
         }
 
         for name in names {
-            self.remove(&name)?;
+            self.evict(&name)?;
         }
 
         if count > 0 {
@@ -260,6 +316,7 @@ mod tests {
                 chunks: None,
                 images: None,
                 djot_content: None,
+                scripts: None,
                 pages: None,
                 elements: None,
             })
@@ -336,6 +393,30 @@ mod tests {
         assert_eq!(retrieved.name(), "high-priority-pdf");
     }
 
+    #[test]
+    fn test_document_extractor_registry_get_by_name() {
+        let mut registry = DocumentExtractorRegistry::new();
+
+        registry
+            .register(Arc::new(MockExtractor {
+                name: "low-priority-pdf".to_string(),
+                mime_types: &["application/pdf"],
+                priority: 10,
+            }))
+            .unwrap();
+        registry
+            .register(Arc::new(MockExtractor {
+                name: "high-priority-pdf".to_string(),
+                mime_types: &["application/pdf"],
+                priority: 100,
+            }))
+            .unwrap();
+
+        let retrieved = registry.get_by_name("low-priority-pdf").unwrap();
+        assert_eq!(retrieved.name(), "low-priority-pdf");
+        assert!(registry.get_by_name("poppler").is_none());
+    }
+
     #[test]
     fn test_document_extractor_registry_not_found() {
         let registry = DocumentExtractorRegistry::new();
@@ -502,6 +583,7 @@ mod tests {
                 chunks: None,
                 images: None,
                 djot_content: None,
+                scripts: None,
                 pages: None,
                 elements: None,
             })
@@ -0,0 +1,242 @@
+//! Content filter registry implementation.
+
+use crate::plugins::ContentFilter;
+use crate::{Result, audit};
+use indexmap::IndexMap;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Registry for content filter plugins.
+///
+/// Manages filters with priority-based execution order.
+pub struct ContentFilterRegistry {
+    filters: BTreeMap<i32, IndexMap<String, Arc<dyn ContentFilter>>>,
+}
+
+impl ContentFilterRegistry {
+    /// Create a new empty filter registry.
+    pub fn new() -> Self {
+        Self {
+            filters: BTreeMap::new(),
+        }
+    }
+
+    /// Register a filter.
+    ///
+    /// # Arguments
+    ///
+    /// * `filter` - The filter to register
+    pub fn register(&mut self, filter: Arc<dyn ContentFilter>) -> Result<()> {
+        let name = filter.name().to_string();
+        let version = filter.version();
+        let outcome = self.insert(filter);
+        audit::record_registration("content_filter", &name, version, &outcome);
+        outcome
+    }
+
+    fn insert(&mut self, filter: Arc<dyn ContentFilter>) -> Result<()> {
+        let name = filter.name().to_string();
+        let priority = filter.priority();
+
+        if let Err(e) = super::validate_plugin_name(&name) {
+            tracing::warn!(
+                "Failed to validate filter name '{}': {}. \
+                 Registration aborted. Plugin names must be non-empty and contain only alphanumeric characters, hyphens, and underscores.",
+                name,
+                e
+            );
+            return Err(e);
+        }
+
+        if let Err(e) = filter.initialize() {
+            tracing::error!(
+                "Failed to initialize filter '{}' with priority {}: {}. \
+                 Filter will not be called.",
+                name,
+                priority,
+                e
+            );
+            return Err(e);
+        }
+
+        self.filters.entry(priority).or_default().insert(name.clone(), filter);
+        tracing::debug!("Registered filter '{}' with priority {}", name, priority);
+
+        Ok(())
+    }
+
+    /// Get all filters in priority order.
+    ///
+    /// # Returns
+    ///
+    /// Vector of filters in priority order (highest first).
+    pub fn get_all(&self) -> Vec<Arc<dyn ContentFilter>> {
+        let mut result = Vec::new();
+
+        for (_priority, filters) in self.filters.iter().rev() {
+            for filter in filters.values() {
+                result.push(Arc::clone(filter));
+            }
+        }
+
+        result
+    }
+
+    /// List all registered filter names.
+    pub fn list(&self) -> Vec<String> {
+        let mut names = std::collections::HashSet::new();
+        for filters in self.filters.values() {
+            names.extend(filters.keys().cloned());
+        }
+        names.into_iter().collect()
+    }
+
+    /// Remove a filter from the registry.
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        let registered = self.list().iter().any(|registered| registered == name);
+        let outcome = self.evict(name);
+        if registered {
+            audit::record_unregistration("content_filter", name, &outcome);
+        }
+        outcome
+    }
+
+    fn evict(&mut self, name: &str) -> Result<()> {
+        let mut filter_to_shutdown: Option<Arc<dyn ContentFilter>> = None;
+        let mut found = false;
+
+        for filters in self.filters.values_mut() {
+            if let Some(filter) = filters.shift_remove(name)
+                && filter_to_shutdown.is_none()
+            {
+                filter_to_shutdown = Some(filter);
+                found = true;
+            }
+        }
+
+        if !found {
+            tracing::debug!(
+                "Content filter '{}' not found in registry (already removed or never registered)",
+                name
+            );
+        }
+
+        if let Some(filter) = filter_to_shutdown {
+            if let Err(e) = filter.shutdown() {
+                tracing::warn!(
+                    "Failed to shutdown filter '{}': {}. \
+                     Resources may not have been properly released.",
+                    name,
+                    e
+                );
+                return Err(e);
+            }
+            tracing::debug!("Successfully removed and shut down filter '{}'", name);
+        }
+
+        self.filters.retain(|_, filters| !filters.is_empty());
+
+        Ok(())
+    }
+
+    /// Shutdown all filters and clear the registry.
+    pub fn shutdown_all(&mut self) -> Result<()> {
+        let names = self.list();
+        let outcome = self.evict_all();
+        if !names.is_empty() {
+            audit::record_clear("content_filter", names, &outcome);
+        }
+        outcome
+    }
+
+    fn evict_all(&mut self) -> Result<()> {
+        let names = self.list();
+        let count = names.len();
+
+        if count > 0 {
+            tracing::debug!("Shutting down {} filters", count);
+        }
+
+        for name in names {
+            self.evict(&name)?;
+        }
+
+        if count > 0 {
+            tracing::debug!("Successfully shut down all {} filters", count);
+        }
+        Ok(())
+    }
+}
+
+impl Default for ContentFilterRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ExtractionConfig;
+    use crate::plugins::Plugin;
+    use std::borrow::Cow;
+
+    struct MockFilter {
+        name: String,
+        priority: i32,
+    }
+
+    impl Plugin for MockFilter {
+        fn name(&self) -> &str {
+            &self.name
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl ContentFilter for MockFilter {
+        fn filter<'a>(&self, text: &'a str, _config: &ExtractionConfig) -> Result<Cow<'a, str>> {
+            Ok(Cow::Borrowed(text))
+        }
+
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+    }
+
+    fn mock(name: &str, priority: i32) -> Arc<dyn ContentFilter> {
+        Arc::new(MockFilter {
+            name: name.to_string(),
+            priority,
+        })
+    }
+
+    #[test]
+    fn test_content_filter_registry_priority_order() {
+        let mut registry = ContentFilterRegistry::new();
+        registry.register(mock("low", 10)).unwrap();
+        registry.register(mock("high", 100)).unwrap();
+        registry.register(mock("default", 50)).unwrap();
+
+        let names: Vec<String> = registry.get_all().iter().map(|f| f.name().to_string()).collect();
+        assert_eq!(names, vec!["high", "default", "low"]);
+    }
+
+    #[test]
+    fn test_content_filter_registry_remove_and_invalid_name() {
+        let mut registry = ContentFilterRegistry::new();
+        registry.register(mock("audit", 50)).unwrap();
+        registry.remove("audit").unwrap();
+        assert!(registry.list().is_empty());
+
+        assert!(registry.register(mock("bad name", 50)).is_err());
+        assert!(registry.list().is_empty());
+    }
+}
@@ -0,0 +1,236 @@
+//! Pipeline hook registry implementation.
+
+use crate::plugins::PipelineHook;
+use crate::{Result, audit};
+use indexmap::IndexMap;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+/// Registry for pipeline hook plugins.
+///
+/// Manages hooks with priority-based execution order.
+pub struct PipelineHookRegistry {
+    hooks: BTreeMap<i32, IndexMap<String, Arc<dyn PipelineHook>>>,
+}
+
+impl PipelineHookRegistry {
+    /// Create a new empty hook registry.
+    pub fn new() -> Self {
+        Self { hooks: BTreeMap::new() }
+    }
+
+    /// Register a hook.
+    ///
+    /// # Arguments
+    ///
+    /// * `hook` - The hook to register
+    pub fn register(&mut self, hook: Arc<dyn PipelineHook>) -> Result<()> {
+        let name = hook.name().to_string();
+        let version = hook.version();
+        let outcome = self.insert(hook);
+        audit::record_registration("pipeline_hook", &name, version, &outcome);
+        outcome
+    }
+
+    fn insert(&mut self, hook: Arc<dyn PipelineHook>) -> Result<()> {
+        let name = hook.name().to_string();
+        let priority = hook.priority();
+
+        if let Err(e) = super::validate_plugin_name(&name) {
+            tracing::warn!(
+                "Failed to validate hook name '{}': {}. \
+                 Registration aborted. Plugin names must be non-empty and contain only alphanumeric characters, hyphens, and underscores.",
+                name,
+                e
+            );
+            return Err(e);
+        }
+
+        if let Err(e) = hook.initialize() {
+            tracing::error!(
+                "Failed to initialize hook '{}' with priority {}: {}. \
+                 Hook will not be called.",
+                name,
+                priority,
+                e
+            );
+            return Err(e);
+        }
+
+        self.hooks.entry(priority).or_default().insert(name.clone(), hook);
+        tracing::debug!("Registered hook '{}' with priority {}", name, priority);
+
+        Ok(())
+    }
+
+    /// Get all hooks in priority order.
+    ///
+    /// # Returns
+    ///
+    /// Vector of hooks in priority order (highest first).
+    pub fn get_all(&self) -> Vec<Arc<dyn PipelineHook>> {
+        let mut result = Vec::new();
+
+        for (_priority, hooks) in self.hooks.iter().rev() {
+            for hook in hooks.values() {
+                result.push(Arc::clone(hook));
+            }
+        }
+
+        result
+    }
+
+    /// List all registered hook names.
+    pub fn list(&self) -> Vec<String> {
+        let mut names = std::collections::HashSet::new();
+        for hooks in self.hooks.values() {
+            names.extend(hooks.keys().cloned());
+        }
+        names.into_iter().collect()
+    }
+
+    /// Remove a hook from the registry.
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        let registered = self.list().iter().any(|registered| registered == name);
+        let outcome = self.evict(name);
+        if registered {
+            audit::record_unregistration("pipeline_hook", name, &outcome);
+        }
+        outcome
+    }
+
+    fn evict(&mut self, name: &str) -> Result<()> {
+        let mut hook_to_shutdown: Option<Arc<dyn PipelineHook>> = None;
+        let mut found = false;
+
+        for hooks in self.hooks.values_mut() {
+            if let Some(hook) = hooks.shift_remove(name)
+                && hook_to_shutdown.is_none()
+            {
+                hook_to_shutdown = Some(hook);
+                found = true;
+            }
+        }
+
+        if !found {
+            tracing::debug!(
+                "Pipeline hook '{}' not found in registry (already removed or never registered)",
+                name
+            );
+        }
+
+        if let Some(hook) = hook_to_shutdown {
+            if let Err(e) = hook.shutdown() {
+                tracing::warn!(
+                    "Failed to shutdown hook '{}': {}. \
+                     Resources may not have been properly released.",
+                    name,
+                    e
+                );
+                return Err(e);
+            }
+            tracing::debug!("Successfully removed and shut down hook '{}'", name);
+        }
+
+        self.hooks.retain(|_, hooks| !hooks.is_empty());
+
+        Ok(())
+    }
+
+    /// Shutdown all hooks and clear the registry.
+    pub fn shutdown_all(&mut self) -> Result<()> {
+        let names = self.list();
+        let outcome = self.evict_all();
+        if !names.is_empty() {
+            audit::record_clear("pipeline_hook", names, &outcome);
+        }
+        outcome
+    }
+
+    fn evict_all(&mut self) -> Result<()> {
+        let names = self.list();
+        let count = names.len();
+
+        if count > 0 {
+            tracing::debug!("Shutting down {} hooks", count);
+        }
+
+        for name in names {
+            self.evict(&name)?;
+        }
+
+        if count > 0 {
+            tracing::debug!("Successfully shut down all {} hooks", count);
+        }
+        Ok(())
+    }
+}
+
+impl Default for PipelineHookRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::Plugin;
+    use async_trait::async_trait;
+
+    struct MockHook {
+        name: String,
+        priority: i32,
+    }
+
+    impl Plugin for MockHook {
+        fn name(&self) -> &str {
+            &self.name
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl PipelineHook for MockHook {
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+    }
+
+    fn hook(name: &str, priority: i32) -> Arc<dyn PipelineHook> {
+        Arc::new(MockHook {
+            name: name.to_string(),
+            priority,
+        })
+    }
+
+    #[test]
+    fn test_pipeline_hook_registry_priority_order() {
+        let mut registry = PipelineHookRegistry::new();
+        registry.register(hook("low", 10)).unwrap();
+        registry.register(hook("high", 100)).unwrap();
+        registry.register(hook("default", 50)).unwrap();
+
+        let names: Vec<String> = registry.get_all().iter().map(|h| h.name().to_string()).collect();
+        assert_eq!(names, vec!["high", "default", "low"]);
+    }
+
+    #[test]
+    fn test_pipeline_hook_registry_remove_and_invalid_name() {
+        let mut registry = PipelineHookRegistry::new();
+        registry.register(hook("audit", 50)).unwrap();
+        registry.remove("audit").unwrap();
+        assert!(registry.list().is_empty());
+
+        assert!(registry.register(hook("bad name", 50)).is_err());
+        assert!(registry.list().is_empty());
+    }
+}
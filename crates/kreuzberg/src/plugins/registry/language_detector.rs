@@ -0,0 +1,199 @@
+//! Language detector registry implementation.
+
+use crate::plugins::LanguageDetector;
+use crate::{Result, audit};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Registry for language detector plugins.
+///
+/// The detector with the highest priority is used; detectors of equal priority are
+/// ordered by name.
+pub struct LanguageDetectorRegistry {
+    detectors: HashMap<String, Arc<dyn LanguageDetector>>,
+}
+
+impl LanguageDetectorRegistry {
+    /// Create a new language detector registry with the default detector.
+    ///
+    /// Registers the built-in `whatlang` detector if the "language-detection" feature is
+    /// enabled.
+    pub fn new() -> Self {
+        #[cfg(feature = "language-detection")]
+        let mut registry = Self::new_empty();
+
+        #[cfg(not(feature = "language-detection"))]
+        let registry = Self::new_empty();
+
+        #[cfg(feature = "language-detection")]
+        if let Err(e) = registry.register(Arc::new(crate::language_detection::WhatlangDetector)) {
+            tracing::error!("Failed to register the whatlang language detector: {}", e);
+        }
+
+        registry
+    }
+
+    /// Create a new empty registry without the default detector.
+    pub fn new_empty() -> Self {
+        Self {
+            detectors: HashMap::new(),
+        }
+    }
+
+    /// Register a detector, replacing a detector of the same name.
+    ///
+    /// # Arguments
+    ///
+    /// * `detector` - The detector to register
+    pub fn register(&mut self, detector: Arc<dyn LanguageDetector>) -> Result<()> {
+        let name = detector.name().to_string();
+        let version = detector.version();
+        let outcome = self.insert(detector);
+        audit::record_registration("language_detector", &name, version, &outcome);
+        outcome
+    }
+
+    fn insert(&mut self, detector: Arc<dyn LanguageDetector>) -> Result<()> {
+        let name = detector.name().to_string();
+
+        super::validate_plugin_name(&name)?;
+
+        detector.initialize()?;
+
+        self.detectors.insert(name, detector);
+        Ok(())
+    }
+
+    /// Get a detector by name.
+    pub fn get(&self, name: &str) -> Option<Arc<dyn LanguageDetector>> {
+        self.detectors.get(name).cloned()
+    }
+
+    /// The detector in use: the one with the highest priority.
+    pub fn get_default(&self) -> Option<Arc<dyn LanguageDetector>> {
+        self.sorted().into_iter().next()
+    }
+
+    /// List all registered detector names, the one in use first.
+    pub fn list(&self) -> Vec<String> {
+        self.sorted()
+            .iter()
+            .map(|detector| detector.name().to_string())
+            .collect()
+    }
+
+    /// Remove a detector from the registry.
+    ///
+    /// Calls `shutdown()` on the detector before removing.
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        let registered = self.list().iter().any(|registered| registered == name);
+        let outcome = self.evict(name);
+        if registered {
+            audit::record_unregistration("language_detector", name, &outcome);
+        }
+        outcome
+    }
+
+    fn evict(&mut self, name: &str) -> Result<()> {
+        if let Some(detector) = self.detectors.remove(name) {
+            detector.shutdown()?;
+        }
+        Ok(())
+    }
+
+    /// Shutdown all detectors and clear the registry.
+    pub fn shutdown_all(&mut self) -> Result<()> {
+        let names = self.list();
+        let outcome = self.evict_all();
+        if !names.is_empty() {
+            audit::record_clear("language_detector", names, &outcome);
+        }
+        outcome
+    }
+
+    fn evict_all(&mut self) -> Result<()> {
+        for (_, detector) in self.detectors.drain() {
+            detector.shutdown()?;
+        }
+        Ok(())
+    }
+
+    fn sorted(&self) -> Vec<Arc<dyn LanguageDetector>> {
+        let mut detectors: Vec<_> = self.detectors.values().cloned().collect();
+        detectors.sort_by(|a, b| b.priority().cmp(&a.priority()).then_with(|| a.name().cmp(b.name())));
+        detectors
+    }
+}
+
+impl Default for LanguageDetectorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::LanguageDetectionConfig;
+    use crate::plugins::Plugin;
+
+    struct MockDetector {
+        name: String,
+        priority: i32,
+    }
+
+    impl Plugin for MockDetector {
+        fn name(&self) -> &str {
+            &self.name
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl LanguageDetector for MockDetector {
+        fn detect(&self, _text: &str, _config: &LanguageDetectionConfig) -> Result<Option<Vec<String>>> {
+            Ok(None)
+        }
+
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+    }
+
+    fn detector(name: &str, priority: i32) -> Arc<dyn LanguageDetector> {
+        Arc::new(MockDetector {
+            name: name.to_string(),
+            priority,
+        })
+    }
+
+    #[test]
+    fn test_language_detector_registry_priority() {
+        let mut registry = LanguageDetectorRegistry::new_empty();
+        assert!(registry.get_default().is_none());
+
+        registry.register(detector("short-text", 50)).unwrap();
+        registry.register(detector("fallback", 0)).unwrap();
+        registry.register(detector("accurate", 50)).unwrap();
+        assert_eq!(registry.list(), vec!["accurate", "short-text", "fallback"]);
+        assert_eq!(registry.get_default().unwrap().name(), "accurate");
+
+        registry.remove("accurate").unwrap();
+        assert_eq!(registry.get_default().unwrap().name(), "short-text");
+        assert!(registry.register(detector("bad name", 50)).is_err());
+    }
+
+    #[cfg(feature = "language-detection")]
+    #[test]
+    fn test_language_detector_registry_default() {
+        let registry = LanguageDetectorRegistry::new();
+        assert_eq!(registry.list(), vec!["whatlang"]);
+    }
+}
@@ -3,15 +3,30 @@
 //! This module provides registries for managing plugins of different types.
 //! Each plugin type (OcrBackend, DocumentExtractor, etc.) has its own registry
 //! with type-safe registration and lookup.
+//!
+//! Registrations, unregistrations and clears are recorded in the [`audit`](crate::audit) log.
 
+mod chunk_processor;
 mod extractor;
+mod filter;
+mod hook;
+mod language_detector;
 mod ocr;
+mod ordering;
 mod processor;
+mod sentence_segmenter;
+mod translator;
 mod validator;
 
+pub use chunk_processor::ChunkProcessorRegistry;
 pub use extractor::DocumentExtractorRegistry;
+pub use filter::ContentFilterRegistry;
+pub use hook::PipelineHookRegistry;
+pub use language_detector::LanguageDetectorRegistry;
 pub use ocr::OcrBackendRegistry;
 pub use processor::PostProcessorRegistry;
+pub use sentence_segmenter::SentenceSegmenterRegistry;
+pub use translator::TranslatorRegistry;
 pub use validator::ValidatorRegistry;
 
 use crate::{KreuzbergError, Result};
@@ -63,6 +78,30 @@ pub static POST_PROCESSOR_REGISTRY: Lazy<Arc<RwLock<PostProcessorRegistry>>> =
 pub static VALIDATOR_REGISTRY: Lazy<Arc<RwLock<ValidatorRegistry>>> =
     Lazy::new(|| Arc::new(RwLock::new(ValidatorRegistry::new())));
 
+/// Global pipeline hook registry singleton.
+pub static PIPELINE_HOOK_REGISTRY: Lazy<Arc<RwLock<PipelineHookRegistry>>> =
+    Lazy::new(|| Arc::new(RwLock::new(PipelineHookRegistry::new())));
+
+/// Global content filter registry singleton.
+pub static CONTENT_FILTER_REGISTRY: Lazy<Arc<RwLock<ContentFilterRegistry>>> =
+    Lazy::new(|| Arc::new(RwLock::new(ContentFilterRegistry::new())));
+
+/// Global language detector registry singleton.
+pub static LANGUAGE_DETECTOR_REGISTRY: Lazy<Arc<RwLock<LanguageDetectorRegistry>>> =
+    Lazy::new(|| Arc::new(RwLock::new(LanguageDetectorRegistry::new())));
+
+/// Global translator registry singleton.
+pub static TRANSLATOR_REGISTRY: Lazy<Arc<RwLock<TranslatorRegistry>>> =
+    Lazy::new(|| Arc::new(RwLock::new(TranslatorRegistry::new())));
+
+/// Global chunk processor registry singleton.
+pub static CHUNK_PROCESSOR_REGISTRY: Lazy<Arc<RwLock<ChunkProcessorRegistry>>> =
+    Lazy::new(|| Arc::new(RwLock::new(ChunkProcessorRegistry::new())));
+
+/// Global sentence segmenter registry singleton.
+pub static SENTENCE_SEGMENTER_REGISTRY: Lazy<Arc<RwLock<SentenceSegmenterRegistry>>> =
+    Lazy::new(|| Arc::new(RwLock::new(SentenceSegmenterRegistry::new())));
+
 /// Get the global OCR backend registry.
 pub fn get_ocr_backend_registry() -> Arc<RwLock<OcrBackendRegistry>> {
     OCR_BACKEND_REGISTRY.clone()
@@ -83,6 +122,36 @@ pub fn get_validator_registry() -> Arc<RwLock<ValidatorRegistry>> {
     VALIDATOR_REGISTRY.clone()
 }
 
+/// Get the global pipeline hook registry.
+pub fn get_pipeline_hook_registry() -> Arc<RwLock<PipelineHookRegistry>> {
+    PIPELINE_HOOK_REGISTRY.clone()
+}
+
+/// Get the global content filter registry.
+pub fn get_content_filter_registry() -> Arc<RwLock<ContentFilterRegistry>> {
+    CONTENT_FILTER_REGISTRY.clone()
+}
+
+/// Get the global language detector registry.
+pub fn get_language_detector_registry() -> Arc<RwLock<LanguageDetectorRegistry>> {
+    LANGUAGE_DETECTOR_REGISTRY.clone()
+}
+
+/// Get the global translator registry.
+pub fn get_translator_registry() -> Arc<RwLock<TranslatorRegistry>> {
+    TRANSLATOR_REGISTRY.clone()
+}
+
+/// Get the global chunk processor registry.
+pub fn get_chunk_processor_registry() -> Arc<RwLock<ChunkProcessorRegistry>> {
+    CHUNK_PROCESSOR_REGISTRY.clone()
+}
+
+/// Get the global sentence segmenter registry.
+pub fn get_sentence_segmenter_registry() -> Arc<RwLock<SentenceSegmenterRegistry>> {
+    SENTENCE_SEGMENTER_REGISTRY.clone()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
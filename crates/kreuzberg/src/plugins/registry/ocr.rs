@@ -1,7 +1,7 @@
 //! OCR backend registry.
 
-use crate::plugins::OcrBackend;
-use crate::{KreuzbergError, Result};
+use crate::plugins::{OcrBackend, OcrBackendDescriptor};
+use crate::{KreuzbergError, Result, audit};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -30,7 +30,8 @@ pub struct OcrBackendRegistry {
 impl OcrBackendRegistry {
     /// Create a new OCR backend registry with default backends.
     ///
-    /// Registers the Tesseract backend by default if the "ocr" feature is enabled.
+    /// Registers the Tesseract backend by default if the "ocr" feature is enabled, and the
+    /// ONNX backend if the "onnx-ocr" feature is enabled and `KREUZBERG_ONNX_OCR_MODEL_DIR` is set.
     /// Logs warnings if backend initialization fails (common in containerized environments
     /// with missing dependencies or permission issues).
     pub fn new() -> Self {
@@ -71,6 +72,26 @@ impl OcrBackendRegistry {
             }
         }
 
+        #[cfg(feature = "onnx-ocr")]
+        if let Some(config) = crate::ocr::onnx::OnnxOcrConfig::from_env() {
+            use crate::ocr::onnx::OnnxOcrBackend;
+            match OnnxOcrBackend::new(config) {
+                Ok(backend) => {
+                    if let Err(e) = registry.register(Arc::new(backend)) {
+                        tracing::error!("Failed to register ONNX OCR backend: {}", e);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(
+                        "ONNX OCR backend initialization failed: {}. \
+                         Check KREUZBERG_ONNX_OCR_MODEL_DIR and that the ONNX Runtime library can be loaded \
+                         (set ORT_DYLIB_PATH if needed).",
+                        e
+                    );
+                }
+            }
+        }
+
         registry
     }
 
@@ -106,6 +127,14 @@ impl OcrBackendRegistry {
     /// ```
     pub fn register(&mut self, backend: Arc<dyn OcrBackend>) -> Result<()> {
         let name = backend.name().to_string();
+        let version = backend.version();
+        let outcome = self.insert(backend);
+        audit::record_registration("ocr_backend", &name, version, &outcome);
+        outcome
+    }
+
+    fn insert(&mut self, backend: Arc<dyn OcrBackend>) -> Result<()> {
+        let name = backend.name().to_string();
 
         super::validate_plugin_name(&name)?;
 
@@ -158,10 +187,30 @@ impl OcrBackendRegistry {
         self.backends.keys().cloned().collect()
     }
 
+    /// Describe all registered backends, sorted by name.
+    pub fn describe(&self) -> Vec<OcrBackendDescriptor> {
+        let mut descriptors: Vec<OcrBackendDescriptor> = self
+            .backends
+            .values()
+            .map(|backend| OcrBackendDescriptor::new(backend.as_ref()))
+            .collect();
+        descriptors.sort_by(|a, b| a.name.cmp(&b.name));
+        descriptors
+    }
+
     /// Remove a backend from the registry.
     ///
     /// Calls `shutdown()` on the backend before removing.
     pub fn remove(&mut self, name: &str) -> Result<()> {
+        let registered = self.list().iter().any(|registered| registered == name);
+        let outcome = self.evict(name);
+        if registered {
+            audit::record_unregistration("ocr_backend", name, &outcome);
+        }
+        outcome
+    }
+
+    fn evict(&mut self, name: &str) -> Result<()> {
         if let Some(backend) = self.backends.remove(name) {
             backend.shutdown()?;
         }
@@ -170,9 +219,18 @@ impl OcrBackendRegistry {
 
     /// Shutdown all backends and clear the registry.
     pub fn shutdown_all(&mut self) -> Result<()> {
+        let names = self.list();
+        let outcome = self.evict_all();
+        if !names.is_empty() {
+            audit::record_clear("ocr_backend", names, &outcome);
+        }
+        outcome
+    }
+
+    fn evict_all(&mut self) -> Result<()> {
         let names: Vec<_> = self.backends.keys().cloned().collect();
         for name in names {
-            self.remove(&name)?;
+            self.evict(&name)?;
         }
         Ok(())
     }
@@ -225,6 +283,7 @@ mod tests {
                 chunks: None,
                 images: None,
                 djot_content: None,
+                scripts: None,
                 pages: None,
                 elements: None,
             })
@@ -261,6 +320,26 @@ mod tests {
         assert!(names.contains(&"test-ocr".to_string()));
     }
 
+    #[test]
+    fn test_ocr_backend_registry_describe() {
+        let mut registry = OcrBackendRegistry::new_empty();
+        for name in ["ocr-b", "ocr-a"] {
+            registry
+                .register(Arc::new(MockOcrBackend {
+                    name: name.to_string(),
+                    languages: vec!["eng".to_string()],
+                }))
+                .unwrap();
+        }
+
+        let descriptors = registry.describe();
+        let names: Vec<&str> = descriptors.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["ocr-a", "ocr-b"]);
+        assert_eq!(descriptors[0].backend_type, crate::plugins::ocr::OcrBackendType::Custom);
+        assert!(!descriptors[0].supports_table_detection);
+        assert_eq!(serde_json::to_value(&descriptors[0]).unwrap()["backend_type"], "custom");
+    }
+
     #[test]
     fn test_ocr_backend_registry_new_empty() {
         let registry = OcrBackendRegistry::new_empty();
@@ -352,6 +431,7 @@ mod tests {
                 chunks: None,
                 images: None,
                 djot_content: None,
+                scripts: None,
                 pages: None,
                 elements: None,
             })
@@ -0,0 +1,96 @@
+//! Ordering constraints between plugins.
+//!
+//! Post-processors and validators can declare, by name, plugins they must run before
+//! or after. The registries first order plugins by stage and priority, then sort that
+//! order topologically so every declared constraint holds.
+
+use std::collections::HashMap;
+
+/// A plugin name with its declared ordering constraints.
+pub(super) struct OrderingNode<'a> {
+    pub(super) name: &'a str,
+    pub(super) runs_before: Vec<String>,
+    pub(super) runs_after: Vec<String>,
+}
+
+/// Sort `nodes` so that every plugin runs after the plugins it is declared to follow.
+///
+/// `nodes` are given in their default order, which is kept wherever the constraints
+/// allow. Constraints naming plugins that are not in `nodes` are ignored. Returns the
+/// indices of `nodes` in execution order, or, when the constraints form a cycle, the
+/// names of the plugins that could not be ordered.
+pub(super) fn order_by_constraints(nodes: &[OrderingNode<'_>]) -> Result<Vec<usize>, Vec<String>> {
+    let index: HashMap<&str, usize> = nodes.iter().enumerate().map(|(i, node)| (node.name, i)).collect();
+
+    let mut successors = vec![Vec::new(); nodes.len()];
+    let mut in_degree = vec![0usize; nodes.len()];
+    for (i, node) in nodes.iter().enumerate() {
+        let before = node
+            .runs_before
+            .iter()
+            .filter_map(|name| index.get(name.as_str()))
+            .map(|&j| (i, j));
+        let after = node
+            .runs_after
+            .iter()
+            .filter_map(|name| index.get(name.as_str()))
+            .map(|&j| (j, i));
+        for (from, to) in before.chain(after) {
+            if from != to && !successors[from].contains(&to) {
+                successors[from].push(to);
+                in_degree[to] += 1;
+            }
+        }
+    }
+
+    // Always take the earliest ready plugin, so unconstrained plugins keep their order.
+    let mut placed = vec![false; nodes.len()];
+    let mut order = Vec::with_capacity(nodes.len());
+    while let Some(next) = (0..nodes.len()).find(|&i| !placed[i] && in_degree[i] == 0) {
+        placed[next] = true;
+        order.push(next);
+        for &to in &successors[next] {
+            in_degree[to] -= 1;
+        }
+    }
+
+    if order.len() < nodes.len() {
+        return Err((0..nodes.len())
+            .filter(|&i| !placed[i])
+            .map(|i| nodes[i].name.to_string())
+            .collect());
+    }
+
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn node<'a>(name: &'a str, runs_before: &[&str], runs_after: &[&str]) -> OrderingNode<'a> {
+        OrderingNode {
+            name,
+            runs_before: runs_before.iter().map(|s| s.to_string()).collect(),
+            runs_after: runs_after.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_order_by_constraints_keeps_default_order_where_possible() {
+        let nodes = [
+            node("a", &[], &["c"]),
+            node("b", &[], &[]),
+            node("c", &[], &[]),
+            node("d", &["b"], &["missing"]),
+        ];
+        assert_eq!(order_by_constraints(&nodes).unwrap(), vec![2, 0, 3, 1]);
+        assert_eq!(order_by_constraints(&[]).unwrap(), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn test_order_by_constraints_reports_cycles() {
+        let nodes = [node("a", &["b"], &[]), node("b", &["a"], &[]), node("c", &[], &[])];
+        assert_eq!(order_by_constraints(&nodes).unwrap_err(), vec!["a", "b"]);
+    }
+}
@@ -1,13 +1,16 @@
 //! Post-processor registry implementation.
 
-use crate::Result;
+use super::ordering::{OrderingNode, order_by_constraints};
 use crate::plugins::{PostProcessor, ProcessingStage};
+use crate::{KreuzbergError, Result, audit};
 use std::collections::{BTreeMap, HashMap};
 use std::sync::Arc;
 
 /// Registry for post-processor plugins.
 ///
-/// Manages post-processors organized by processing stage.
+/// Manages post-processors organized by processing stage. Within a stage, processors
+/// run by priority, adjusted to satisfy their [`PostProcessor::runs_before`] and
+/// [`PostProcessor::runs_after`] constraints.
 pub struct PostProcessorRegistry {
     processors: HashMap<ProcessingStage, BTreeMap<i32, Vec<Arc<dyn PostProcessor>>>>,
     name_index: HashMap<String, (ProcessingStage, i32)>,
@@ -28,7 +31,21 @@ impl PostProcessorRegistry {
     ///
     /// * `processor` - The post-processor to register
     /// * `priority` - Execution priority (higher = runs first within stage)
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if the name is invalid, or if the ordering
+    /// constraints of the processor conflict with its stage or form a cycle with those of
+    /// the registered processors.
     pub fn register(&mut self, processor: Arc<dyn PostProcessor>, priority: i32) -> Result<()> {
+        let name = processor.name().to_string();
+        let version = processor.version();
+        let outcome = self.insert(processor, priority);
+        audit::record_registration("post_processor", &name, version, &outcome);
+        outcome
+    }
+
+    fn insert(&mut self, processor: Arc<dyn PostProcessor>, priority: i32) -> Result<()> {
         let name = processor.name().to_string();
         let stage = processor.processing_stage();
 
@@ -42,6 +59,11 @@ impl PostProcessorRegistry {
             return Err(e);
         }
 
+        if let Err(e) = self.check_ordering(&processor) {
+            tracing::warn!("Failed to register post-processor '{}': {}", name, e);
+            return Err(e);
+        }
+
         if let Err(e) = processor.initialize() {
             tracing::error!(
                 "Failed to initialize post-processor '{}' for processing stage {:?} with priority {}: {}. \
@@ -59,7 +81,7 @@ impl PostProcessorRegistry {
                 "Post-processor '{}' is already registered. Removing old instance and registering new one.",
                 name
             );
-            self.remove(&name)?;
+            self.evict(&name)?;
         }
 
         self.processors
@@ -80,7 +102,7 @@ impl PostProcessorRegistry {
         Ok(())
     }
 
-    /// Get all processors for a specific stage, in priority order.
+    /// Get all processors for a specific stage, in execution order.
     ///
     /// # Arguments
     ///
@@ -88,7 +110,8 @@ impl PostProcessorRegistry {
     ///
     /// # Returns
     ///
-    /// Vector of processors in priority order (highest first).
+    /// Vector of processors in priority order (highest first), reordered where needed to
+    /// satisfy their ordering constraints.
     pub fn get_for_stage(&self, stage: ProcessingStage) -> Vec<Arc<dyn PostProcessor>> {
         let mut result = Vec::new();
 
@@ -100,7 +123,64 @@ impl PostProcessorRegistry {
             }
         }
 
-        result
+        let nodes: Vec<_> = result.iter().map(|p| ordering_node(p.as_ref())).collect();
+        match order_by_constraints(&nodes) {
+            Ok(order) => order.into_iter().map(|i| Arc::clone(&result[i])).collect(),
+            Err(_) => result,
+        }
+    }
+
+    /// Check the ordering constraints of `processor` against the registered processors.
+    ///
+    /// Constraints between processors of different stages must agree with the stage
+    /// order, and constraints within the stage of `processor` must not form a cycle. A
+    /// registered processor with the same name is left out, as registering replaces it.
+    fn check_ordering(&self, processor: &Arc<dyn PostProcessor>) -> Result<()> {
+        let name = processor.name();
+        let stage = processor.processing_stage();
+        let mut stage_nodes = vec![ordering_node(processor.as_ref())];
+
+        for (other_stage, priority_map) in &self.processors {
+            for other in priority_map.values().flatten() {
+                if other.name() == name {
+                    continue;
+                }
+                if *other_stage == stage {
+                    stage_nodes.push(ordering_node(other.as_ref()));
+                    continue;
+                }
+
+                let (first, second) = if *other_stage < stage {
+                    (other, processor)
+                } else {
+                    (processor, other)
+                };
+                if second.runs_before().iter().any(|n| n == first.name())
+                    || first.runs_after().iter().any(|n| n == second.name())
+                {
+                    return Err(KreuzbergError::Validation {
+                        message: format!(
+                            "Post-processor '{}' ({:?} stage) cannot run after '{}' ({:?} stage)",
+                            first.name(),
+                            first.processing_stage(),
+                            second.name(),
+                            second.processing_stage()
+                        ),
+                        source: None,
+                    });
+                }
+            }
+        }
+
+        order_by_constraints(&stage_nodes)
+            .map(|_| ())
+            .map_err(|names| KreuzbergError::Validation {
+                message: format!(
+                    "Post-processor ordering constraints form a cycle between: {}",
+                    names.join(", ")
+                ),
+                source: None,
+            })
     }
 
     /// List all registered processor names.
@@ -110,6 +190,15 @@ impl PostProcessorRegistry {
 
     /// Remove a processor from the registry.
     pub fn remove(&mut self, name: &str) -> Result<()> {
+        let registered = self.list().iter().any(|registered| registered == name);
+        let outcome = self.evict(name);
+        if registered {
+            audit::record_unregistration("post_processor", name, &outcome);
+        }
+        outcome
+    }
+
+    fn evict(&mut self, name: &str) -> Result<()> {
         let (stage, priority) = match self.name_index.remove(name) {
             Some(location) => location,
             None => {
@@ -161,6 +250,15 @@ impl PostProcessorRegistry {
 
     /// Shutdown all processors and clear the registry.
     pub fn shutdown_all(&mut self) -> Result<()> {
+        let names = self.list();
+        let outcome = self.evict_all();
+        if !names.is_empty() {
+            audit::record_clear("post_processor", names, &outcome);
+        }
+        outcome
+    }
+
+    fn evict_all(&mut self) -> Result<()> {
         let names = self.list();
         let count = names.len();
 
@@ -169,7 +267,7 @@ impl PostProcessorRegistry {
         }
 
         for name in names {
-            self.remove(&name)?;
+            self.evict(&name)?;
         }
 
         if count > 0 {
@@ -179,6 +277,14 @@ impl PostProcessorRegistry {
     }
 }
 
+fn ordering_node(processor: &dyn PostProcessor) -> OrderingNode<'_> {
+    OrderingNode {
+        name: processor.name(),
+        runs_before: processor.runs_before(),
+        runs_after: processor.runs_after(),
+    }
+}
+
 impl Default for PostProcessorRegistry {
     fn default() -> Self {
         Self::new()
@@ -502,6 +608,114 @@ mod tests {
         assert_eq!(registry.get_for_stage(ProcessingStage::Late).len(), 1);
     }
 
+    struct OrderedPostProcessor {
+        name: &'static str,
+        stage: ProcessingStage,
+        runs_before: Vec<String>,
+        runs_after: Vec<String>,
+    }
+
+    impl OrderedPostProcessor {
+        fn new(name: &'static str, stage: ProcessingStage, runs_before: &[&str], runs_after: &[&str]) -> Arc<Self> {
+            Arc::new(Self {
+                name,
+                stage,
+                runs_before: runs_before.iter().map(|s| s.to_string()).collect(),
+                runs_after: runs_after.iter().map(|s| s.to_string()).collect(),
+            })
+        }
+    }
+
+    impl Plugin for OrderedPostProcessor {
+        fn name(&self) -> &str {
+            self.name
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl PostProcessor for OrderedPostProcessor {
+        async fn process(&self, _result: &mut ExtractionResult, _: &ExtractionConfig) -> Result<()> {
+            Ok(())
+        }
+
+        fn processing_stage(&self) -> ProcessingStage {
+            self.stage
+        }
+
+        fn runs_before(&self) -> Vec<String> {
+            self.runs_before.clone()
+        }
+
+        fn runs_after(&self) -> Vec<String> {
+            self.runs_after.clone()
+        }
+    }
+
+    #[test]
+    fn test_post_processor_ordering_constraints_override_priority() {
+        let mut registry = PostProcessorRegistry::new();
+
+        registry
+            .register(
+                OrderedPostProcessor::new("a", ProcessingStage::Middle, &[], &["c"]),
+                100,
+            )
+            .unwrap();
+        registry
+            .register(OrderedPostProcessor::new("b", ProcessingStage::Middle, &[], &[]), 50)
+            .unwrap();
+        registry
+            .register(OrderedPostProcessor::new("c", ProcessingStage::Middle, &[], &[]), 10)
+            .unwrap();
+        registry
+            .register(OrderedPostProcessor::new("d", ProcessingStage::Middle, &["b"], &[]), 0)
+            .unwrap();
+
+        let names: Vec<_> = registry
+            .get_for_stage(ProcessingStage::Middle)
+            .iter()
+            .map(|p| p.name().to_string())
+            .collect();
+        assert_eq!(names, vec!["c", "a", "d", "b"]);
+    }
+
+    #[test]
+    fn test_post_processor_ordering_rejects_cycles_and_stage_conflicts() {
+        let mut registry = PostProcessorRegistry::new();
+
+        registry
+            .register(OrderedPostProcessor::new("a", ProcessingStage::Middle, &["b"], &[]), 50)
+            .unwrap();
+        let result = registry.register(OrderedPostProcessor::new("b", ProcessingStage::Middle, &["a"], &[]), 50);
+        assert!(matches!(result, Err(KreuzbergError::Validation { .. })));
+
+        let result = registry.register(OrderedPostProcessor::new("c", ProcessingStage::Early, &[], &["a"]), 50);
+        assert!(matches!(result, Err(KreuzbergError::Validation { .. })));
+
+        let result = registry.register(OrderedPostProcessor::new("d", ProcessingStage::Late, &["a"], &[]), 50);
+        assert!(matches!(result, Err(KreuzbergError::Validation { .. })));
+        assert_eq!(registry.list(), vec!["a".to_string()]);
+
+        registry
+            .register(OrderedPostProcessor::new("e", ProcessingStage::Late, &[], &["a"]), 50)
+            .unwrap();
+        registry
+            .register(OrderedPostProcessor::new("a", ProcessingStage::Middle, &[], &[]), 50)
+            .unwrap();
+        registry
+            .register(OrderedPostProcessor::new("b", ProcessingStage::Middle, &["a"], &[]), 50)
+            .unwrap();
+    }
+
     #[test]
     fn test_post_processor_shutdown_empty_registry() {
         let mut registry = PostProcessorRegistry::new();
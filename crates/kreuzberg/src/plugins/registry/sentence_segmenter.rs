@@ -0,0 +1,189 @@
+//! Sentence segmenter registry implementation.
+
+use crate::plugins::SentenceSegmenter;
+use crate::{Result, audit};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Registry for sentence segmenter plugins.
+///
+/// Without a configured name, the segmenter with the highest priority is used;
+/// segmenters of equal priority are ordered by name.
+pub struct SentenceSegmenterRegistry {
+    segmenters: HashMap<String, Arc<dyn SentenceSegmenter>>,
+}
+
+impl SentenceSegmenterRegistry {
+    /// Create a new sentence segmenter registry with the default segmenter.
+    ///
+    /// Registers the built-in `rule-based` segmenter.
+    pub fn new() -> Self {
+        let mut registry = Self::new_empty();
+
+        if let Err(e) = registry.register(Arc::new(crate::text::sentences::RuleBasedSegmenter::new())) {
+            tracing::error!("Failed to register the rule-based sentence segmenter: {}", e);
+        }
+
+        registry
+    }
+
+    /// Create a new empty registry without the default segmenter.
+    pub fn new_empty() -> Self {
+        Self {
+            segmenters: HashMap::new(),
+        }
+    }
+
+    /// Register a segmenter, replacing a segmenter of the same name.
+    ///
+    /// # Arguments
+    ///
+    /// * `segmenter` - The segmenter to register
+    pub fn register(&mut self, segmenter: Arc<dyn SentenceSegmenter>) -> Result<()> {
+        let name = segmenter.name().to_string();
+        let version = segmenter.version();
+        let outcome = self.insert(segmenter);
+        audit::record_registration("sentence_segmenter", &name, version, &outcome);
+        outcome
+    }
+
+    fn insert(&mut self, segmenter: Arc<dyn SentenceSegmenter>) -> Result<()> {
+        let name = segmenter.name().to_string();
+
+        super::validate_plugin_name(&name)?;
+
+        segmenter.initialize()?;
+
+        self.segmenters.insert(name, segmenter);
+        Ok(())
+    }
+
+    /// Get a segmenter by name.
+    pub fn get(&self, name: &str) -> Option<Arc<dyn SentenceSegmenter>> {
+        self.segmenters.get(name).cloned()
+    }
+
+    /// The segmenter used when none is named: the one with the highest priority.
+    pub fn get_default(&self) -> Option<Arc<dyn SentenceSegmenter>> {
+        self.sorted().into_iter().next()
+    }
+
+    /// List all registered segmenter names, the default first.
+    pub fn list(&self) -> Vec<String> {
+        self.sorted()
+            .iter()
+            .map(|segmenter| segmenter.name().to_string())
+            .collect()
+    }
+
+    /// Remove a segmenter from the registry.
+    ///
+    /// Calls `shutdown()` on the segmenter before removing.
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        let registered = self.list().iter().any(|registered| registered == name);
+        let outcome = self.evict(name);
+        if registered {
+            audit::record_unregistration("sentence_segmenter", name, &outcome);
+        }
+        outcome
+    }
+
+    fn evict(&mut self, name: &str) -> Result<()> {
+        if let Some(segmenter) = self.segmenters.remove(name) {
+            segmenter.shutdown()?;
+        }
+        Ok(())
+    }
+
+    /// Shutdown all segmenters and clear the registry.
+    pub fn shutdown_all(&mut self) -> Result<()> {
+        let names = self.list();
+        let outcome = self.evict_all();
+        if !names.is_empty() {
+            audit::record_clear("sentence_segmenter", names, &outcome);
+        }
+        outcome
+    }
+
+    fn evict_all(&mut self) -> Result<()> {
+        for (_, segmenter) in self.segmenters.drain() {
+            segmenter.shutdown()?;
+        }
+        Ok(())
+    }
+
+    fn sorted(&self) -> Vec<Arc<dyn SentenceSegmenter>> {
+        let mut segmenters: Vec<_> = self.segmenters.values().cloned().collect();
+        segmenters.sort_by(|a, b| b.priority().cmp(&a.priority()).then_with(|| a.name().cmp(b.name())));
+        segmenters
+    }
+}
+
+impl Default for SentenceSegmenterRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::Plugin;
+    use std::ops::Range;
+
+    struct MockSegmenter {
+        name: String,
+        priority: i32,
+    }
+
+    impl Plugin for MockSegmenter {
+        fn name(&self) -> &str {
+            &self.name
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SentenceSegmenter for MockSegmenter {
+        fn segment(&self, _text: &str) -> Result<Vec<Range<usize>>> {
+            Ok(Vec::new())
+        }
+
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+    }
+
+    fn segmenter(name: &str, priority: i32) -> Arc<dyn SentenceSegmenter> {
+        Arc::new(MockSegmenter {
+            name: name.to_string(),
+            priority,
+        })
+    }
+
+    #[test]
+    fn test_sentence_segmenter_registry_priority() {
+        let mut registry = SentenceSegmenterRegistry::new();
+        assert_eq!(registry.list(), vec!["rule-based"]);
+
+        registry.register(segmenter("legal", 50)).unwrap();
+        registry.register(segmenter("neural", 50)).unwrap();
+        assert_eq!(registry.list(), vec!["legal", "neural", "rule-based"]);
+        assert_eq!(registry.get_default().unwrap().name(), "legal");
+
+        registry.remove("legal").unwrap();
+        registry.remove("neural").unwrap();
+        assert_eq!(registry.get_default().unwrap().name(), "rule-based");
+        assert!(registry.register(segmenter("bad name", 50)).is_err());
+
+        registry.shutdown_all().unwrap();
+        assert!(registry.get_default().is_none());
+    }
+}
@@ -0,0 +1,216 @@
+//! Translator registry implementation.
+
+use crate::plugins::Translator;
+use crate::{Result, audit};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Registry for translator plugins.
+///
+/// Without a configured name, the translator with the highest priority is used;
+/// translators of equal priority are ordered by name.
+pub struct TranslatorRegistry {
+    translators: HashMap<String, Arc<dyn Translator>>,
+}
+
+impl TranslatorRegistry {
+    /// Create a new empty translator registry.
+    pub fn new() -> Self {
+        Self {
+            translators: HashMap::new(),
+        }
+    }
+
+    /// Register a translator, replacing a translator of the same name.
+    ///
+    /// # Arguments
+    ///
+    /// * `translator` - The translator to register
+    pub fn register(&mut self, translator: Arc<dyn Translator>) -> Result<()> {
+        let name = translator.name().to_string();
+        let version = translator.version();
+        let outcome = self.insert(translator);
+        audit::record_registration("translator", &name, version, &outcome);
+        outcome
+    }
+
+    fn insert(&mut self, translator: Arc<dyn Translator>) -> Result<()> {
+        let name = translator.name().to_string();
+
+        super::validate_plugin_name(&name)?;
+
+        translator.initialize()?;
+
+        self.translators.insert(name, translator);
+        Ok(())
+    }
+
+    /// Get a translator by name.
+    pub fn get(&self, name: &str) -> Option<Arc<dyn Translator>> {
+        self.translators.get(name).cloned()
+    }
+
+    /// The translator used when none is named: the one with the highest priority.
+    pub fn get_default(&self) -> Option<Arc<dyn Translator>> {
+        self.sorted().into_iter().next()
+    }
+
+    /// List all registered translator names, the default first.
+    pub fn list(&self) -> Vec<String> {
+        self.sorted()
+            .iter()
+            .map(|translator| translator.name().to_string())
+            .collect()
+    }
+
+    /// Remove a translator from the registry.
+    ///
+    /// Calls `shutdown()` on the translator before removing.
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        let registered = self.list().iter().any(|registered| registered == name);
+        let outcome = self.evict(name);
+        if registered {
+            audit::record_unregistration("translator", name, &outcome);
+        }
+        outcome
+    }
+
+    fn evict(&mut self, name: &str) -> Result<()> {
+        if let Some(translator) = self.translators.remove(name) {
+            translator.shutdown()?;
+        }
+        Ok(())
+    }
+
+    /// Shutdown all translators and clear the registry.
+    pub fn shutdown_all(&mut self) -> Result<()> {
+        let names = self.list();
+        let outcome = self.evict_all();
+        if !names.is_empty() {
+            audit::record_clear("translator", names, &outcome);
+        }
+        outcome
+    }
+
+    fn evict_all(&mut self) -> Result<()> {
+        for (_, translator) in self.translators.drain() {
+            translator.shutdown()?;
+        }
+        Ok(())
+    }
+
+    fn sorted(&self) -> Vec<Arc<dyn Translator>> {
+        let mut translators: Vec<_> = self.translators.values().cloned().collect();
+        translators.sort_by(|a, b| b.priority().cmp(&a.priority()).then_with(|| a.name().cmp(b.name())));
+        translators
+    }
+}
+
+impl Default for TranslatorRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugins::Plugin;
+    use async_trait::async_trait;
+
+    struct MockTranslator {
+        name: String,
+        priority: i32,
+    }
+
+    impl Plugin for MockTranslator {
+        fn name(&self) -> &str {
+            &self.name
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl Translator for MockTranslator {
+        async fn translate(
+            &self,
+            texts: &[String],
+            _source_language: Option<&str>,
+            _target_language: &str,
+        ) -> Result<Vec<String>> {
+            Ok(texts.to_vec())
+        }
+
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+    }
+
+    fn translator(name: &str, priority: i32) -> Arc<dyn Translator> {
+        Arc::new(MockTranslator {
+            name: name.to_string(),
+            priority,
+        })
+    }
+
+    #[test]
+    fn test_translator_registry_priority() {
+        let mut registry = TranslatorRegistry::new();
+        assert!(registry.get_default().is_none());
+
+        registry.register(translator("local-model", 10)).unwrap();
+        registry.register(translator("deepl", 50)).unwrap();
+        assert_eq!(registry.list(), vec!["deepl", "local-model"]);
+        assert_eq!(registry.get_default().unwrap().name(), "deepl");
+        assert!(registry.get("local-model").is_some());
+
+        registry.remove("deepl").unwrap();
+        assert_eq!(registry.get_default().unwrap().name(), "local-model");
+        assert!(registry.register(translator("bad name", 50)).is_err());
+    }
+
+    #[test]
+    fn test_translator_registry_audit() {
+        use crate::audit::{self, AuditAction, AuditContext};
+
+        let mut registry = TranslatorRegistry::new();
+        audit::with_context(AuditContext::new("translator-audit-test").with_reason("test"), || {
+            registry.register(translator("audited", 10)).unwrap();
+            registry.register(translator("bad name", 10)).unwrap_err();
+            registry.remove("audited").unwrap();
+            registry.remove("never-registered").unwrap();
+            registry.register(translator("audited", 10)).unwrap();
+            registry.shutdown_all().unwrap();
+        });
+
+        let events: Vec<_> = audit::events()
+            .into_iter()
+            .filter(|event| event.actor.as_deref() == Some("translator-audit-test"))
+            .collect();
+        let actions: Vec<_> = events.iter().map(|event| event.action).collect();
+        assert_eq!(
+            actions,
+            [
+                AuditAction::Register,
+                AuditAction::Register,
+                AuditAction::Unregister,
+                AuditAction::Register,
+                AuditAction::Clear
+            ]
+        );
+        assert!(events.iter().all(|event| event.target == "translator"));
+        assert_eq!(events[0].version.as_deref(), Some("1.0.0"));
+        assert!(events[0].error.is_none());
+        assert!(events[1].error.is_some());
+        assert_eq!(events[4].details, ["audited"]);
+        assert_eq!(events[4].reason.as_deref(), Some("test"));
+    }
+}
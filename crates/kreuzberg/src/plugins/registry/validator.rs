@@ -1,14 +1,16 @@
 //! Validator registry implementation.
 
-use crate::Result;
+use super::ordering::{OrderingNode, order_by_constraints};
 use crate::plugins::Validator;
+use crate::{KreuzbergError, Result, audit};
 use indexmap::IndexMap;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
 /// Registry for validator plugins.
 ///
-/// Manages validators with priority-based execution order.
+/// Manages validators with priority-based execution order, adjusted to satisfy their
+/// [`Validator::runs_before`] and [`Validator::runs_after`] constraints.
 pub struct ValidatorRegistry {
     validators: BTreeMap<i32, IndexMap<String, Arc<dyn Validator>>>,
 }
@@ -26,7 +28,20 @@ impl ValidatorRegistry {
     /// # Arguments
     ///
     /// * `validator` - The validator to register
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if the name is invalid, or if the ordering
+    /// constraints of the validator form a cycle with those of the registered validators.
     pub fn register(&mut self, validator: Arc<dyn Validator>) -> Result<()> {
+        let name = validator.name().to_string();
+        let version = validator.version();
+        let outcome = self.insert(validator);
+        audit::record_registration("validator", &name, version, &outcome);
+        outcome
+    }
+
+    fn insert(&mut self, validator: Arc<dyn Validator>) -> Result<()> {
         let name = validator.name().to_string();
         let priority = validator.priority();
 
@@ -40,6 +55,24 @@ impl ValidatorRegistry {
             return Err(e);
         }
 
+        let others: Vec<_> = self.get_all().into_iter().filter(|v| v.name() != name).collect();
+        let nodes: Vec<_> = others
+            .iter()
+            .chain(std::iter::once(&validator))
+            .map(|v| ordering_node(v.as_ref()))
+            .collect();
+        if let Err(names) = order_by_constraints(&nodes) {
+            let e = KreuzbergError::Validation {
+                message: format!(
+                    "Validator ordering constraints form a cycle between: {}",
+                    names.join(", ")
+                ),
+                source: None,
+            };
+            tracing::warn!("Failed to register validator '{}': {}", name, e);
+            return Err(e);
+        }
+
         if let Err(e) = validator.initialize() {
             tracing::error!(
                 "Failed to initialize validator '{}' with priority {}: {}. \
@@ -64,7 +97,8 @@ impl ValidatorRegistry {
     ///
     /// # Returns
     ///
-    /// Vector of validators in priority order (highest first).
+    /// Vector of validators in priority order (highest first), reordered where needed to
+    /// satisfy their ordering constraints.
     pub fn get_all(&self) -> Vec<Arc<dyn Validator>> {
         let mut result = Vec::new();
 
@@ -74,7 +108,11 @@ impl ValidatorRegistry {
             }
         }
 
-        result
+        let nodes: Vec<_> = result.iter().map(|v| ordering_node(v.as_ref())).collect();
+        match order_by_constraints(&nodes) {
+            Ok(order) => order.into_iter().map(|i| Arc::clone(&result[i])).collect(),
+            Err(_) => result,
+        }
     }
 
     /// List all registered validator names.
@@ -88,6 +126,15 @@ impl ValidatorRegistry {
 
     /// Remove a validator from the registry.
     pub fn remove(&mut self, name: &str) -> Result<()> {
+        let registered = self.list().iter().any(|registered| registered == name);
+        let outcome = self.evict(name);
+        if registered {
+            audit::record_unregistration("validator", name, &outcome);
+        }
+        outcome
+    }
+
+    fn evict(&mut self, name: &str) -> Result<()> {
         let mut validator_to_shutdown: Option<Arc<dyn Validator>> = None;
         let mut found = false;
 
@@ -127,6 +174,15 @@ impl ValidatorRegistry {
 
     /// Shutdown all validators and clear the registry.
     pub fn shutdown_all(&mut self) -> Result<()> {
+        let names = self.list();
+        let outcome = self.evict_all();
+        if !names.is_empty() {
+            audit::record_clear("validator", names, &outcome);
+        }
+        outcome
+    }
+
+    fn evict_all(&mut self) -> Result<()> {
         let names = self.list();
         let count = names.len();
 
@@ -135,7 +191,7 @@ impl ValidatorRegistry {
         }
 
         for name in names {
-            self.remove(&name)?;
+            self.evict(&name)?;
         }
 
         if count > 0 {
@@ -145,6 +201,14 @@ impl ValidatorRegistry {
     }
 }
 
+fn ordering_node(validator: &dyn Validator) -> OrderingNode<'_> {
+    OrderingNode {
+        name: validator.name(),
+        runs_before: validator.runs_before(),
+        runs_after: validator.runs_after(),
+    }
+}
+
 impl Default for ValidatorRegistry {
     fn default() -> Self {
         Self::new()
@@ -451,4 +515,70 @@ mod tests {
         registry.register(validator).unwrap();
         assert_eq!(registry.get_all().len(), 1);
     }
+
+    struct OrderedValidator {
+        name: &'static str,
+        priority: i32,
+        runs_after: Vec<String>,
+    }
+
+    impl Plugin for OrderedValidator {
+        fn name(&self) -> &str {
+            self.name
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl Validator for OrderedValidator {
+        async fn validate(&self, _: &ExtractionResult, _: &ExtractionConfig) -> Result<()> {
+            Ok(())
+        }
+
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+
+        fn runs_after(&self) -> Vec<String> {
+            self.runs_after.clone()
+        }
+    }
+
+    #[test]
+    fn test_validator_ordering_constraints() {
+        let mut registry = ValidatorRegistry::new();
+
+        registry
+            .register(Arc::new(OrderedValidator {
+                name: "expensive",
+                priority: 100,
+                runs_after: vec!["cheap".to_string()],
+            }))
+            .unwrap();
+        registry
+            .register(Arc::new(OrderedValidator {
+                name: "cheap",
+                priority: 10,
+                runs_after: vec![],
+            }))
+            .unwrap();
+
+        let names: Vec<_> = registry.get_all().iter().map(|v| v.name().to_string()).collect();
+        assert_eq!(names, vec!["cheap", "expensive"]);
+
+        let result = registry.register(Arc::new(OrderedValidator {
+            name: "cheap",
+            priority: 10,
+            runs_after: vec!["expensive".to_string()],
+        }));
+        assert!(matches!(result, Err(KreuzbergError::Validation { .. })));
+    }
 }
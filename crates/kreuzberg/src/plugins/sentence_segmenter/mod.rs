@@ -0,0 +1,91 @@
+//! Sentence segmenter plugin system.
+//!
+//! This module provides the trait and registry for backends that split text into
+//! sentences.
+
+mod registry;
+mod r#trait;
+
+pub use r#trait::SentenceSegmenter;
+
+pub use registry::{
+    clear_sentence_segmenters, list_sentence_segmenters, register_sentence_segmenter, unregister_sentence_segmenter,
+};
+
+#[cfg(any(feature = "chunking", feature = "quality"))]
+pub(crate) use registry::resolve_sentence_segmenter;
+
+#[cfg(all(test, feature = "chunking"))]
+mod tests {
+    use super::*;
+    use crate::Result;
+    use crate::chunking::{ChunkingConfig, OverlapStrategy, chunk_text};
+    use crate::plugins::Plugin;
+    use std::ops::Range;
+    use std::sync::Arc;
+
+    /// Treats every line as a sentence.
+    struct LineSegmenter;
+
+    impl Plugin for LineSegmenter {
+        fn name(&self) -> &str {
+            "line-segmenter"
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    impl SentenceSegmenter for LineSegmenter {
+        fn segment(&self, text: &str) -> Result<Vec<Range<usize>>> {
+            let mut offset = 0;
+            let mut sentences = Vec::new();
+            for line in text.split_inclusive('\n') {
+                sentences.push(offset..offset + line.trim_end().len());
+                offset += line.len();
+            }
+            Ok(sentences)
+        }
+
+        fn priority(&self) -> i32 {
+            -10
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_chunking_uses_configured_segmenter() {
+        let text = "the parties met in May\nno agreement was reached\nthe hearing is set for June";
+        let mut config = ChunkingConfig {
+            max_characters: 80,
+            overlap: 30,
+            overlap_strategy: OverlapStrategy::Sentences { count: 1 },
+            sentence_segmenter: Some("line-segmenter".to_string()),
+            ..Default::default()
+        };
+
+        assert!(chunk_text(text, &config, None).is_err());
+
+        register_sentence_segmenter(Arc::new(LineSegmenter)).unwrap();
+        let listed = list_sentence_segmenters().unwrap();
+        let by_line = chunk_text(text, &config, None);
+        config.sentence_segmenter = None;
+        let by_rules = chunk_text(text, &config, None);
+        unregister_sentence_segmenter("line-segmenter").unwrap();
+
+        assert_eq!(listed, vec!["rule-based", "line-segmenter"]);
+        let by_line = by_line.unwrap();
+        assert_eq!(
+            by_line.chunks[1].content,
+            "no agreement was reached\nthe hearing is set for June"
+        );
+        let by_rules = by_rules.unwrap();
+        assert_eq!(by_rules.chunks[1].content, "the hearing is set for June");
+    }
+}
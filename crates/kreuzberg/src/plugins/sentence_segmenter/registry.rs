@@ -0,0 +1,122 @@
+//! Sentence segmenter registry management.
+//!
+//! This module provides functions for managing the global sentence segmenter registry.
+
+use super::r#trait::SentenceSegmenter;
+use crate::Result;
+use std::sync::Arc;
+
+/// Register a sentence segmenter with the global registry.
+///
+/// The segmenter's `name()` method is used as the registration name; a segmenter with
+/// the same name is replaced.
+///
+/// # Errors
+///
+/// - `KreuzbergError::Validation` - Invalid segmenter name (empty or contains whitespace)
+/// - Any error from the segmenter's `initialize()` method
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::plugins::{Plugin, SentenceSegmenter, register_sentence_segmenter};
+/// use kreuzberg::{KreuzbergError, Result};
+/// use std::ops::Range;
+/// use std::sync::Arc;
+///
+/// struct WholeText;
+///
+/// impl Plugin for WholeText {
+///     fn name(&self) -> &str { "whole-text" }
+///     fn version(&self) -> String { "1.0.0".to_string() }
+///     fn initialize(&self) -> Result<()> { Ok(()) }
+///     fn shutdown(&self) -> Result<()> { Ok(()) }
+/// }
+///
+/// impl SentenceSegmenter for WholeText {
+///     fn segment(&self, text: &str) -> Result<Vec<Range<usize>>> {
+///         Ok(vec![0..text.len()])
+///     }
+///
+///     fn priority(&self) -> i32 { -10 }
+/// }
+///
+/// register_sentence_segmenter(Arc::new(WholeText))?;
+/// # kreuzberg::plugins::unregister_sentence_segmenter("whole-text")?;
+/// # Ok::<(), KreuzbergError>(())
+/// ```
+pub fn register_sentence_segmenter(segmenter: Arc<dyn SentenceSegmenter>) -> Result<()> {
+    use crate::plugins::registry::get_sentence_segmenter_registry;
+
+    let registry = get_sentence_segmenter_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on sentence segmenter registry"); // ~keep
+
+    registry.register(segmenter)
+}
+
+/// Unregister a sentence segmenter by name.
+///
+/// Removes the segmenter from the global registry and calls its `shutdown()` method.
+/// Unknown names are ignored.
+pub fn unregister_sentence_segmenter(name: &str) -> Result<()> {
+    use crate::plugins::registry::get_sentence_segmenter_registry;
+
+    let registry = get_sentence_segmenter_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on sentence segmenter registry"); // ~keep
+
+    registry.remove(name)
+}
+
+/// List the names of all registered sentence segmenters, the default first.
+pub fn list_sentence_segmenters() -> Result<Vec<String>> {
+    use crate::plugins::registry::get_sentence_segmenter_registry;
+
+    let registry = get_sentence_segmenter_registry();
+    let registry = registry
+        .read()
+        .expect("~keep Failed to acquire read lock on sentence segmenter registry"); // ~keep
+
+    Ok(registry.list())
+}
+
+/// Clear all sentence segmenters from the global registry, calling their `shutdown()`
+/// methods. This includes the built-in `rule-based` segmenter.
+pub fn clear_sentence_segmenters() -> Result<()> {
+    use crate::plugins::registry::get_sentence_segmenter_registry;
+
+    let registry = get_sentence_segmenter_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on sentence segmenter registry"); // ~keep
+
+    registry.shutdown_all()
+}
+
+/// The segmenter named `name`, or the registered segmenter of the highest priority.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::MissingDependency` if the named segmenter, or any segmenter
+/// when no name is given, is not registered.
+#[cfg(any(feature = "chunking", feature = "quality"))]
+pub(crate) fn resolve_sentence_segmenter(name: Option<&str>) -> Result<Arc<dyn SentenceSegmenter>> {
+    use crate::KreuzbergError;
+
+    let registry = crate::plugins::registry::get_sentence_segmenter_registry();
+    let registry = registry
+        .read()
+        .map_err(|e| KreuzbergError::Other(format!("Sentence segmenter registry lock poisoned: {}", e)))?;
+
+    match name {
+        Some(name) => registry.get(name).ok_or_else(|| {
+            KreuzbergError::MissingDependency(format!("Sentence segmenter '{}' is not registered", name))
+        }),
+        None => registry
+            .get_default()
+            .ok_or_else(|| KreuzbergError::MissingDependency("No sentence segmenter registered".to_string())),
+    }
+}
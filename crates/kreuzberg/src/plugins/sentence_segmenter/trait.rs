@@ -0,0 +1,64 @@
+//! Sentence segmenter trait.
+//!
+//! This module defines the trait for backends that split text into sentences.
+
+use crate::Result;
+use crate::plugins::Plugin;
+use std::ops::Range;
+
+/// Trait for sentence segmentation backends.
+///
+/// Sentence-aware features split text with a registered segmenter: sentence overlap
+/// between chunks (`OverlapStrategy::Sentences`) and sentence selection in token
+/// reduction. Chunking uses the segmenter named in `ChunkingConfig::sentence_segmenter`,
+/// everything else the one with the highest [`priority`](Self::priority).
+///
+/// The built-in `rule-based` segmenter ([`RuleBasedSegmenter`](crate::text::sentences::RuleBasedSegmenter))
+/// has priority 0, so any segmenter registered with the default priority replaces it.
+/// Register segmenters based on a trained model (Punkt, spaCy, a neural sentence
+/// splitter) for domains the rules handle poorly.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::plugins::{Plugin, SentenceSegmenter};
+/// use kreuzberg::Result;
+/// use std::ops::Range;
+///
+/// struct OnePerLine;
+///
+/// impl Plugin for OnePerLine {
+///     fn name(&self) -> &str { "one-per-line" }
+///     fn version(&self) -> String { "1.0.0".to_string() }
+///     fn initialize(&self) -> Result<()> { Ok(()) }
+///     fn shutdown(&self) -> Result<()> { Ok(()) }
+/// }
+///
+/// impl SentenceSegmenter for OnePerLine {
+///     fn segment(&self, text: &str) -> Result<Vec<Range<usize>>> {
+///         let mut offset = 0;
+///         let mut sentences = Vec::new();
+///         for line in text.split_inclusive('\n') {
+///             if !line.trim().is_empty() {
+///                 sentences.push(offset..offset + line.trim_end().len());
+///             }
+///             offset += line.len();
+///         }
+///         Ok(sentences)
+///     }
+/// }
+/// ```
+pub trait SentenceSegmenter: Plugin {
+    /// Split `text` into sentences.
+    ///
+    /// Returns the byte ranges of the sentences in order, without surrounding
+    /// whitespace. Ranges must lie on character boundaries and must not overlap; text
+    /// between them (whitespace, stray markup) belongs to no sentence.
+    fn segment(&self, text: &str) -> Result<Vec<Range<usize>>>;
+
+    /// Priority among registered segmenters (the highest is used when none is named).
+    /// Defaults to 50.
+    fn priority(&self) -> i32 {
+        50
+    }
+}
@@ -191,6 +191,15 @@ pub trait Plugin: Send + Sync {
     fn author(&self) -> &str {
         ""
     }
+
+    /// Whether the plugin performs network I/O, e.g. calls a cloud OCR or translation
+    /// service.
+    ///
+    /// Extractions that deny network access (`network = "deny"`) fail before they
+    /// start if they would run a plugin returning `true`. Defaults to `false`.
+    fn requires_network(&self) -> bool {
+        false
+    }
 }
 
 #[cfg(test)]
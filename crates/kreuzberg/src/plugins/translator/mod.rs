@@ -0,0 +1,141 @@
+//! Translator plugin system.
+//!
+//! This module provides the trait and registry for machine translation backends.
+
+mod registry;
+mod r#trait;
+
+pub use r#trait::Translator;
+
+pub use registry::{clear_translators, list_translators, register_translator, unregister_translator};
+
+pub(crate) use registry::resolve_translator;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Result;
+    use crate::core::config::{ExtractionConfig, TranslationConfig, TranslationScope};
+    use crate::core::pipeline::run_pipeline;
+    use crate::plugins::Plugin;
+    use crate::types::{Chunk, ChunkMetadata, ExtractionResult, Metadata};
+    use async_trait::async_trait;
+    use std::borrow::Cow;
+    use std::sync::Arc;
+
+    /// Translates by upper-casing, tagging the texts with the languages it was given.
+    struct UpperCaseTranslator;
+
+    impl Plugin for UpperCaseTranslator {
+        fn name(&self) -> &str {
+            "upper-case"
+        }
+        fn version(&self) -> String {
+            "1.0.0".to_string()
+        }
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[async_trait]
+    impl Translator for UpperCaseTranslator {
+        async fn translate(
+            &self,
+            texts: &[String],
+            source_language: Option<&str>,
+            target_language: &str,
+        ) -> Result<Vec<String>> {
+            let source = source_language.unwrap_or("und");
+            Ok(texts
+                .iter()
+                .map(|text| format!("[{source}->{target_language}] {}", text.to_uppercase()))
+                .collect())
+        }
+    }
+
+    fn chunk(content: &str, chunk_index: usize) -> Chunk {
+        Chunk {
+            content: content.to_string(),
+            embedding: None,
+            metadata: ChunkMetadata {
+                byte_start: 0,
+                byte_end: content.len(),
+                token_count: None,
+                chunk_index,
+                total_chunks: 2,
+                first_page: None,
+                last_page: None,
+                chunk_type: crate::types::ChunkType::Text,
+                additional: Default::default(),
+            },
+        }
+    }
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_translation_of_content_and_chunks() {
+        let result = ExtractionResult {
+            content: "hallo welt".to_string(),
+            mime_type: Cow::Borrowed("text/plain"),
+            metadata: Metadata::default(),
+            tables: vec![],
+            detected_languages: Some(vec!["deu".to_string()]),
+            chunks: Some(vec![chunk("hallo", 0), chunk("welt", 1)]),
+            images: None,
+            djot_content: None,
+            scripts: None,
+            pages: None,
+            elements: None,
+        };
+        let mut config = ExtractionConfig {
+            translation: Some(TranslationConfig {
+                target_language: "eng".to_string(),
+                scope: TranslationScope::Both,
+                translator: None,
+            }),
+            ..Default::default()
+        };
+
+        let missing = run_pipeline(result.clone(), &config).await.unwrap();
+        assert!(!missing.metadata.additional.contains_key("translation"));
+        assert!(
+            missing.metadata.additional["translation_error"]
+                .as_str()
+                .unwrap()
+                .contains("No translator registered")
+        );
+
+        register_translator(Arc::new(UpperCaseTranslator)).unwrap();
+        let listed = list_translators().unwrap();
+        let plan = config.plan("text/plain").map(|plan| plan.translator);
+        let translated = run_pipeline(result.clone(), &config).await;
+        config.translation.as_mut().unwrap().scope = TranslationScope::Chunks;
+        let chunks_only = run_pipeline(result, &config).await;
+        unregister_translator("upper-case").unwrap();
+
+        assert_eq!(listed, vec!["upper-case"]);
+        assert_eq!(plan.unwrap().unwrap().name, "upper-case");
+        let translated = translated.unwrap();
+        assert_eq!(translated.content, "hallo welt");
+        assert_eq!(
+            translated.metadata.additional["translation"],
+            serde_json::json!({
+                "language": "eng",
+                "source_language": "deu",
+                "translator": "upper-case",
+                "content": "[deu->eng] HALLO WELT",
+                "chunks": ["[deu->eng] HALLO", "[deu->eng] WELT"],
+            })
+        );
+        let chunks_only = chunks_only.unwrap();
+        assert!(chunks_only.metadata.additional["translation"].get("content").is_none());
+        assert_eq!(
+            chunks_only.metadata.additional["translation"]["chunks"][1],
+            "[deu->eng] WELT"
+        );
+    }
+}
@@ -0,0 +1,117 @@
+//! Translator registry management.
+//!
+//! This module provides functions for managing the global translator registry.
+
+use super::r#trait::Translator;
+use crate::{KreuzbergError, Result};
+use std::sync::Arc;
+
+/// Register a translator with the global registry.
+///
+/// The translator's `name()` method is used as the registration name; a translator with
+/// the same name is replaced.
+///
+/// # Errors
+///
+/// - `KreuzbergError::Validation` - Invalid translator name (empty or contains whitespace)
+/// - Any error from the translator's `initialize()` method
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::plugins::{Plugin, Translator, register_translator};
+/// use kreuzberg::{KreuzbergError, Result};
+/// use async_trait::async_trait;
+/// use std::sync::Arc;
+///
+/// struct Identity;
+///
+/// impl Plugin for Identity {
+///     fn name(&self) -> &str { "identity" }
+///     fn version(&self) -> String { "1.0.0".to_string() }
+///     fn initialize(&self) -> Result<()> { Ok(()) }
+///     fn shutdown(&self) -> Result<()> { Ok(()) }
+/// }
+///
+/// #[async_trait]
+/// impl Translator for Identity {
+///     async fn translate(&self, texts: &[String], _source: Option<&str>, _target: &str) -> Result<Vec<String>> {
+///         Ok(texts.to_vec())
+///     }
+/// }
+///
+/// register_translator(Arc::new(Identity))?;
+/// # kreuzberg::plugins::unregister_translator("identity")?;
+/// # Ok::<(), KreuzbergError>(())
+/// ```
+pub fn register_translator(translator: Arc<dyn Translator>) -> Result<()> {
+    use crate::plugins::registry::get_translator_registry;
+
+    let registry = get_translator_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on translator registry"); // ~keep
+
+    registry.register(translator)
+}
+
+/// Unregister a translator by name.
+///
+/// Removes the translator from the global registry and calls its `shutdown()` method.
+/// Unknown names are ignored.
+pub fn unregister_translator(name: &str) -> Result<()> {
+    use crate::plugins::registry::get_translator_registry;
+
+    let registry = get_translator_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on translator registry"); // ~keep
+
+    registry.remove(name)
+}
+
+/// List the names of all registered translators, the default first.
+pub fn list_translators() -> Result<Vec<String>> {
+    use crate::plugins::registry::get_translator_registry;
+
+    let registry = get_translator_registry();
+    let registry = registry
+        .read()
+        .expect("~keep Failed to acquire read lock on translator registry"); // ~keep
+
+    Ok(registry.list())
+}
+
+/// Clear all translators from the global registry, calling their `shutdown()` methods.
+pub fn clear_translators() -> Result<()> {
+    use crate::plugins::registry::get_translator_registry;
+
+    let registry = get_translator_registry();
+    let mut registry = registry
+        .write()
+        .expect("~keep Failed to acquire write lock on translator registry"); // ~keep
+
+    registry.shutdown_all()
+}
+
+/// The translator named `name`, or the registered translator of the highest priority.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::MissingDependency` if the named translator, or any translator
+/// when no name is given, is not registered.
+pub(crate) fn resolve_translator(name: Option<&str>) -> Result<Arc<dyn Translator>> {
+    let registry = crate::plugins::registry::get_translator_registry();
+    let registry = registry
+        .read()
+        .map_err(|e| KreuzbergError::Other(format!("Translator registry lock poisoned: {}", e)))?;
+
+    match name {
+        Some(name) => registry
+            .get(name)
+            .ok_or_else(|| KreuzbergError::MissingDependency(format!("Translator '{}' is not registered", name))),
+        None => registry
+            .get_default()
+            .ok_or_else(|| KreuzbergError::MissingDependency("No translator registered".to_string())),
+    }
+}
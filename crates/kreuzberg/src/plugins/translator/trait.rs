@@ -0,0 +1,75 @@
+//! Translator trait.
+//!
+//! This module defines the trait for machine translation backends.
+
+use crate::Result;
+use crate::plugins::Plugin;
+use async_trait::async_trait;
+
+/// Trait for machine translation backends.
+///
+/// When `ExtractionConfig::translation` is set, the translation stage sends the content,
+/// the chunks or both to a registered translator, after chunking and language detection.
+/// Kreuzberg ships no translator; register one backed by a local model or a translation
+/// API. The translator named in `TranslationConfig::translator` is used, or else the one
+/// with the highest [`priority`](Self::priority).
+///
+/// Translations are stored next to the originals in `metadata.additional["translation"]`
+/// with the target and source languages.
+///
+/// # Error Handling
+///
+/// Errors do not fail the extraction; they are recorded in
+/// `metadata.additional["translation_error"]`. Calls are guarded by
+/// `ExtractionConfig::plugin_guard`, so a slow or failing API can be timed out and
+/// disabled.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::plugins::{Plugin, Translator};
+/// use kreuzberg::Result;
+/// use async_trait::async_trait;
+///
+/// struct Shouting;
+///
+/// impl Plugin for Shouting {
+///     fn name(&self) -> &str { "shouting" }
+///     fn version(&self) -> String { "1.0.0".to_string() }
+///     fn initialize(&self) -> Result<()> { Ok(()) }
+///     fn shutdown(&self) -> Result<()> { Ok(()) }
+/// }
+///
+/// #[async_trait]
+/// impl Translator for Shouting {
+///     async fn translate(
+///         &self,
+///         texts: &[String],
+///         _source_language: Option<&str>,
+///         _target_language: &str,
+///     ) -> Result<Vec<String>> {
+///         Ok(texts.iter().map(|text| text.to_uppercase()).collect())
+///     }
+/// }
+/// ```
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait Translator: Plugin {
+    /// Translate `texts` into `target_language`.
+    ///
+    /// Returns one translation per text, in the same order. Texts are whole documents or
+    /// chunks; translators with request size limits should split them. `source_language`
+    /// is the first detected language (ISO 639-3) when language detection is enabled.
+    async fn translate(
+        &self,
+        texts: &[String],
+        source_language: Option<&str>,
+        target_language: &str,
+    ) -> Result<Vec<String>>;
+
+    /// Priority among registered translators (the highest is used when none is named).
+    /// Defaults to 50.
+    fn priority(&self) -> i32 {
+        50
+    }
+}
@@ -0,0 +1,172 @@
+//! Built-in validators.
+//!
+//! These validators are configured through [`ExtractionConfig`] and run before
+//! registered validators; they are not added to the global registry.
+
+use super::r#trait::Validator;
+use crate::core::config::ExtractionConfig;
+use std::sync::Arc;
+
+#[cfg(feature = "pdf")]
+pub use pdf_conformance::PdfConformanceValidator;
+
+/// Built-in validators enabled by the configuration, in execution order.
+#[cfg_attr(not(feature = "pdf"), allow(unused_variables, unused_mut))]
+pub(crate) fn configured_validators(config: &ExtractionConfig) -> Vec<Arc<dyn Validator>> {
+    let mut validators: Vec<Arc<dyn Validator>> = Vec::new();
+    #[cfg(feature = "pdf")]
+    if let Some(required) = config.pdf_options.as_ref().and_then(|pdf| pdf.required_conformance) {
+        validators.push(Arc::new(PdfConformanceValidator::new(required)));
+    }
+    validators
+}
+
+#[cfg(feature = "pdf")]
+mod pdf_conformance {
+    use super::Validator;
+    use crate::core::config::{ConformanceLevel, ExtractionConfig, PdfStandard};
+    use crate::plugins::Plugin;
+    use crate::types::{ExtractionResult, FormatMetadata};
+    use crate::{KreuzbergError, Result};
+    use async_trait::async_trait;
+
+    /// Fails PDFs that do not claim a required PDF/A or PDF/UA conformance level.
+    ///
+    /// The claims are the ones reported in `pdfa_conformance` and `pdfua_conformance`
+    /// of the PDF metadata. A PDF/A requirement also fails encrypted documents, which
+    /// no PDF/A level allows.
+    pub struct PdfConformanceValidator {
+        required: ConformanceLevel,
+    }
+
+    impl PdfConformanceValidator {
+        pub fn new(required: ConformanceLevel) -> Self {
+            Self { required }
+        }
+    }
+
+    impl Plugin for PdfConformanceValidator {
+        fn name(&self) -> &str {
+            "pdf-conformance"
+        }
+
+        fn version(&self) -> String {
+            env!("CARGO_PKG_VERSION").to_string()
+        }
+
+        fn initialize(&self) -> Result<()> {
+            Ok(())
+        }
+
+        fn shutdown(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+    #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+    impl Validator for PdfConformanceValidator {
+        async fn validate(&self, result: &ExtractionResult, _config: &ExtractionConfig) -> Result<()> {
+            let pdf = match &result.metadata.format {
+                Some(FormatMetadata::Pdf(pdf)) => Some(pdf),
+                _ => None,
+            };
+            let claim = pdf.and_then(|pdf| match self.required.standard {
+                PdfStandard::PdfA => pdf.pdfa_conformance.as_deref(),
+                PdfStandard::PdfUa => pdf.pdfua_conformance.as_deref(),
+            });
+
+            let Some(claim) = claim else {
+                return Err(KreuzbergError::validation(format!(
+                    "PDF does not claim {} conformance",
+                    self.required
+                )));
+            };
+            if !claim
+                .parse::<ConformanceLevel>()
+                .is_ok_and(|claimed| self.required.is_met_by(&claimed))
+            {
+                return Err(KreuzbergError::validation(format!(
+                    "PDF claims {claim} conformance, which does not meet the required {}",
+                    self.required
+                )));
+            }
+            if self.required.standard == PdfStandard::PdfA && pdf.is_some_and(|pdf| pdf.is_encrypted == Some(true)) {
+                return Err(KreuzbergError::validation(format!(
+                    "PDF claims {claim} conformance but is encrypted, which PDF/A does not allow"
+                )));
+            }
+
+            Ok(())
+        }
+
+        fn should_validate(&self, result: &ExtractionResult, _config: &ExtractionConfig) -> bool {
+            result.mime_type == "application/pdf"
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::pdf::metadata::PdfMetadata;
+        use crate::types::Metadata;
+
+        fn pdf_result(pdfa: Option<&str>, is_encrypted: bool) -> ExtractionResult {
+            ExtractionResult {
+                content: String::new(),
+                mime_type: "application/pdf".into(),
+                metadata: Metadata {
+                    format: Some(FormatMetadata::Pdf(PdfMetadata {
+                        pdfa_conformance: pdfa.map(str::to_string),
+                        is_encrypted: Some(is_encrypted),
+                        ..Default::default()
+                    })),
+                    ..Default::default()
+                },
+                pages: None,
+                tables: vec![],
+                detected_languages: None,
+                chunks: None,
+                images: None,
+                djot_content: None,
+                scripts: None,
+                elements: None,
+            }
+        }
+
+        #[tokio::test]
+        async fn test_pdf_conformance_validator() {
+            let config = ExtractionConfig::default();
+            let validator = PdfConformanceValidator::new("PDF/A-2b".parse().unwrap());
+
+            assert!(
+                validator
+                    .validate(&pdf_result(Some("PDF/A-2u"), false), &config)
+                    .await
+                    .is_ok()
+            );
+            for (claim, is_encrypted, message) in [
+                (None, false, "PDF does not claim PDF/A-2b conformance"),
+                (
+                    Some("PDF/A-1b"),
+                    false,
+                    "PDF claims PDF/A-1b conformance, which does not meet the required PDF/A-2b",
+                ),
+                (
+                    Some("PDF/A-2b"),
+                    true,
+                    "PDF claims PDF/A-2b conformance but is encrypted, which PDF/A does not allow",
+                ),
+            ] {
+                let error = validator
+                    .validate(&pdf_result(claim, is_encrypted), &config)
+                    .await
+                    .unwrap_err();
+                assert!(
+                    matches!(&error, KreuzbergError::Validation { message: m, .. } if m == message),
+                    "{error}"
+                );
+            }
+        }
+    }
+}
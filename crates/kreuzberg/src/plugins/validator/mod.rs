@@ -1,7 +1,9 @@
 //! Validator plugin system.
 //!
-//! This module provides the trait and registry for implementing custom validators.
+//! This module provides the trait, registry and built-in implementations for
+//! validators.
 
+mod builtin;
 mod registry;
 mod r#trait;
 
@@ -11,6 +13,10 @@ pub use r#trait::Validator;
 // Re-export registry functions for backward compatibility
 pub use registry::{clear_validators, list_validators, register_validator, unregister_validator};
 
+#[cfg(feature = "pdf")]
+pub use builtin::PdfConformanceValidator;
+pub(crate) use builtin::configured_validators;
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -69,6 +75,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -90,6 +97,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -113,6 +121,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -151,6 +160,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -201,6 +211,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -214,6 +225,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -300,6 +312,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -335,6 +348,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         };
 
@@ -363,6 +377,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -394,6 +409,7 @@ mod tests {
                 chunks: None,
                 images: None,
                 djot_content: None,
+                scripts: None,
                 pages: None,
                 elements: None,
             };
@@ -415,6 +431,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
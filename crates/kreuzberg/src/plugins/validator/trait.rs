@@ -273,4 +273,43 @@ pub trait Validator: Plugin {
     fn priority(&self) -> i32 {
         50
     }
+
+    /// Optional: Names of validators this validator must run before.
+    ///
+    /// Takes precedence over [`priority`](Validator::priority). Names of validators that
+    /// are not registered are ignored. Defaults to none.
+    fn runs_before(&self) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Optional: Names of validators this validator must run after.
+    ///
+    /// Takes precedence over [`priority`](Validator::priority). Names of validators that
+    /// are not registered are ignored. Defaults to none.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// # use kreuzberg::plugins::{Plugin, Validator};
+    /// # use kreuzberg::{Result, ExtractionResult, ExtractionConfig};
+    /// # use async_trait::async_trait;
+    /// # struct SchemaValidator;
+    /// # impl Plugin for SchemaValidator {
+    /// #     fn name(&self) -> &str { "schema-validator" }
+    /// #     fn version(&self) -> String { "1.0.0".to_string() }
+    /// #     fn initialize(&self) -> Result<()> { Ok(()) }
+    /// #     fn shutdown(&self) -> Result<()> { Ok(()) }
+    /// # }
+    /// # #[async_trait]
+    /// # impl Validator for SchemaValidator {
+    /// #     async fn validate(&self, _: &ExtractionResult, _: &ExtractionConfig) -> Result<()> { Ok(()) }
+    /// /// Only check the schema once the content is known to be non-empty
+    /// fn runs_after(&self) -> Vec<String> {
+    ///     vec!["length-validator".to_string()]
+    /// }
+    /// # }
+    /// ```
+    fn runs_after(&self) -> Vec<String> {
+        Vec::new()
+    }
 }
@@ -0,0 +1,473 @@
+//! Provenance manifests and signatures of extraction results.
+//!
+//! A [`ProvenanceManifest`] records what a serialized result was extracted from (the
+//! SHA-256 hash, size and MIME type of the input), how (the Kreuzberg version, the
+//! plugins that ran and their versions, and the SHA-256 hash of the configuration),
+//! when, and the SHA-256 hash of the serialized result itself. Downstream systems can
+//! check a result against its manifest, and reproduce it from the same input and
+//! configuration.
+//!
+//! With the `signing` feature, manifests are signed with an Ed25519 key, so a result
+//! and its manifest can be verified with nothing but the public key.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use kreuzberg::provenance::ProvenanceRecorder;
+//! use kreuzberg::{ExtractionConfig, extract_file_sync};
+//!
+//! # fn example() -> kreuzberg::Result<()> {
+//! let config = ExtractionConfig::default();
+//! let recorder = ProvenanceRecorder::for_file("report.pdf", &config)?;
+//! let result = extract_file_sync("report.pdf", None, &config)?;
+//! let json = serde_json::to_vec(&result)?;
+//!
+//! let manifest = recorder.finish(&result.mime_type, &json)?;
+//! manifest.check_result(&json)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::core::pipeline::ExtractionPlan;
+use crate::{ExtractionConfig, KreuzbergError, Result};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "signing")]
+pub use ed25519_dalek::{SigningKey, VerifyingKey};
+
+/// Version of the provenance manifest format.
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Where, how and when a serialized result was extracted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvenanceManifest {
+    /// Manifest format version
+    pub version: u32,
+    /// Version of Kreuzberg that extracted the result
+    pub kreuzberg_version: String,
+    /// The extracted input
+    pub input: InputProvenance,
+    /// SHA-256 hash of the configuration, as JSON with sorted keys
+    pub config_sha256: String,
+    /// Extractor, OCR backend and processors that ran, in pipeline order
+    pub plugins: Vec<PluginProvenance>,
+    /// Unix time in seconds the extraction started
+    pub started_at: u64,
+    /// Unix time in seconds the result was finished
+    pub finished_at: u64,
+    /// SHA-256 hash of the serialized result
+    pub result_sha256: String,
+    /// Signature over the rest of the manifest, None for unsigned manifests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<ManifestSignature>,
+}
+
+/// The input of a result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputProvenance {
+    /// Path the input was read from, None for bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// SHA-256 hash of the input
+    pub sha256: String,
+    /// Size of the input in bytes
+    pub size: u64,
+    /// MIME type the input was extracted as
+    pub mime_type: String,
+}
+
+/// A plugin that ran for a result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PluginProvenance {
+    /// Role of the plugin: `extractor`, `ocr_backend`, `post_processor`, `validator`,
+    /// `pipeline_hook`, `chunk_processor` or `translator`
+    pub kind: String,
+    /// Registered plugin name
+    pub name: String,
+    /// Plugin version
+    pub version: String,
+}
+
+/// Signature of a manifest.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestSignature {
+    /// Signature algorithm, `ed25519`
+    pub algorithm: String,
+    /// Hex-encoded public key of the signer
+    pub public_key: String,
+    /// Hex-encoded signature
+    pub signature: String,
+}
+
+/// Records the input and configuration of an extraction, to build its manifest from
+/// once the result is serialized.
+#[derive(Debug, Clone)]
+pub struct ProvenanceRecorder {
+    path: Option<String>,
+    sha256: String,
+    size: u64,
+    config: ExtractionConfig,
+    started_at: u64,
+}
+
+impl ProvenanceRecorder {
+    /// Start recording the extraction of the file at `path` with `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Io` if the file cannot be read.
+    pub fn for_file(path: impl AsRef<Path>, config: &ExtractionConfig) -> Result<Self> {
+        let path = path.as_ref();
+        let mut hasher = Sha256::new();
+        let size = std::io::copy(&mut std::fs::File::open(path)?, &mut hasher)?;
+        Ok(Self {
+            path: Some(path.to_string_lossy().into_owned()),
+            sha256: hex::encode(hasher.finalize()),
+            size,
+            config: config.clone(),
+            started_at: unix_time(),
+        })
+    }
+
+    /// Start recording the extraction of `content` with `config`.
+    pub fn for_bytes(content: &[u8], config: &ExtractionConfig) -> Self {
+        Self {
+            path: None,
+            sha256: hex::encode(Sha256::digest(content)),
+            size: content.len() as u64,
+            config: config.clone(),
+            started_at: unix_time(),
+        }
+    }
+
+    /// Build the unsigned manifest of `result`, the serialized result of an input
+    /// extracted as `mime_type`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the configuration cannot be serialized, or if no extractor
+    /// is registered for `mime_type`.
+    pub fn finish(self, mime_type: &str, result: &[u8]) -> Result<ProvenanceManifest> {
+        let plan = self.config.plan(mime_type)?;
+        // Through `Value`, so maps are hashed with sorted keys.
+        let config = serde_json::to_vec(&serde_json::to_value(&self.config)?)?;
+
+        Ok(ProvenanceManifest {
+            version: MANIFEST_VERSION,
+            kreuzberg_version: env!("CARGO_PKG_VERSION").to_string(),
+            input: InputProvenance {
+                path: self.path,
+                sha256: self.sha256,
+                size: self.size,
+                mime_type: mime_type.to_string(),
+            },
+            config_sha256: hex::encode(Sha256::digest(config)),
+            plugins: plugins(&plan),
+            started_at: self.started_at,
+            finished_at: unix_time(),
+            result_sha256: hex::encode(Sha256::digest(result)),
+            signature: None,
+        })
+    }
+}
+
+impl ProvenanceManifest {
+    /// Check that `result` is the serialized result the manifest was built for.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Security` if the hash of `result` differs.
+    pub fn check_result(&self, result: &[u8]) -> Result<()> {
+        if hex::encode(Sha256::digest(result)) != self.result_sha256 {
+            return Err(KreuzbergError::Security {
+                message: "Result does not match its provenance manifest".to_string(),
+                source: None,
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "signing")]
+impl ProvenanceManifest {
+    /// Sign the manifest with `key`, replacing any earlier signature.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Serialization` if the manifest cannot be serialized.
+    pub fn sign(&mut self, key: &SigningKey) -> Result<()> {
+        use ed25519_dalek::Signer;
+
+        let signature = key.sign(&self.signed_bytes()?);
+        self.signature = Some(ManifestSignature {
+            algorithm: "ed25519".to_string(),
+            public_key: hex::encode(key.verifying_key().as_bytes()),
+            signature: hex::encode(signature.to_bytes()),
+        });
+        Ok(())
+    }
+
+    /// Verify that the manifest was signed with the private key of `public_key`, and
+    /// that `result` is the serialized result it was built for.
+    ///
+    /// The public key recorded in the manifest is not trusted; only `public_key` is.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Security` if the manifest is unsigned, its signature is
+    /// invalid, or `result` does not match it.
+    pub fn verify(&self, result: &[u8], public_key: &VerifyingKey) -> Result<()> {
+        let invalid = |message: &str| KreuzbergError::Security {
+            message: message.to_string(),
+            source: None,
+        };
+
+        let signature = self
+            .signature
+            .as_ref()
+            .ok_or_else(|| invalid("Provenance manifest is not signed"))?;
+        if signature.algorithm != "ed25519" {
+            return Err(invalid(&format!(
+                "Unsupported provenance signature algorithm '{}'",
+                signature.algorithm
+            )));
+        }
+        let bytes: [u8; 64] = hex::decode(&signature.signature)
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok())
+            .ok_or_else(|| invalid("Malformed provenance signature"))?;
+        public_key
+            .verify_strict(&self.signed_bytes()?, &ed25519_dalek::Signature::from_bytes(&bytes))
+            .map_err(|_| invalid("Provenance manifest signature is invalid"))?;
+
+        self.check_result(result)
+    }
+
+    /// Bytes the signature is made over: the manifest as JSON, without the signature.
+    fn signed_bytes(&self) -> Result<Vec<u8>> {
+        let unsigned = Self {
+            signature: None,
+            ..self.clone()
+        };
+        Ok(serde_json::to_vec(&unsigned)?)
+    }
+}
+
+/// Load an Ed25519 private key from `path`: 32 raw bytes, or 64 hex characters.
+///
+/// A key can be generated with `openssl rand -hex 32 > signing.key`.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Io` if the file cannot be read, and
+/// `KreuzbergError::Validation` if it does not hold a key.
+#[cfg(feature = "signing")]
+pub fn load_signing_key(path: impl AsRef<Path>) -> Result<SigningKey> {
+    read_key(path.as_ref()).map(|bytes| SigningKey::from_bytes(&bytes))
+}
+
+/// Load an Ed25519 public key from `path`: 32 raw bytes, or 64 hex characters.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Io` if the file cannot be read, and
+/// `KreuzbergError::Validation` if it does not hold a valid key.
+#[cfg(feature = "signing")]
+pub fn load_verifying_key(path: impl AsRef<Path>) -> Result<VerifyingKey> {
+    let path = path.as_ref();
+    VerifyingKey::from_bytes(&read_key(path)?)
+        .map_err(|e| KreuzbergError::validation(format!("Invalid public key in {}: {e}", path.display())))
+}
+
+#[cfg(feature = "signing")]
+fn read_key(path: &Path) -> Result<[u8; 32]> {
+    let content = std::fs::read(path)?;
+    let bytes = match <[u8; 32]>::try_from(content.as_slice()) {
+        Ok(raw) => Some(raw),
+        Err(_) => hex::decode(content.trim_ascii())
+            .ok()
+            .and_then(|bytes| bytes.try_into().ok()),
+    };
+    bytes.ok_or_else(|| {
+        KreuzbergError::validation(format!(
+            "{} does not hold an Ed25519 key (32 raw bytes or 64 hex characters)",
+            path.display()
+        ))
+    })
+}
+
+/// Signs manifests with the key of a [`ProvenanceConfig`](crate::core::config::ProvenanceConfig), when it has one.
+#[cfg(feature = "tokio-runtime")]
+pub(crate) struct ManifestSigner {
+    #[cfg(feature = "signing")]
+    key: Option<SigningKey>,
+}
+
+#[cfg(feature = "tokio-runtime")]
+impl ManifestSigner {
+    /// Load the signing key of `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::MissingDependency` for a signing key without the
+    /// `signing` feature, and the errors of [`load_signing_key`] otherwise.
+    pub(crate) fn new(config: &crate::core::config::ProvenanceConfig) -> Result<Self> {
+        #[cfg(feature = "signing")]
+        {
+            Ok(Self {
+                key: config.signing_key.as_ref().map(load_signing_key).transpose()?,
+            })
+        }
+        #[cfg(not(feature = "signing"))]
+        match config.signing_key {
+            Some(_) => Err(KreuzbergError::MissingDependency(
+                "Signing provenance manifests requires the `signing` feature".to_string(),
+            )),
+            None => Ok(Self {}),
+        }
+    }
+
+    /// Sign `manifest` if there is a key.
+    pub(crate) fn seal(&self, manifest: &mut ProvenanceManifest) -> Result<()> {
+        #[cfg(feature = "signing")]
+        if let Some(key) = &self.key {
+            manifest.sign(key)?;
+        }
+        #[cfg(not(feature = "signing"))]
+        let _ = manifest;
+        Ok(())
+    }
+}
+
+/// The plugins of `plan` that run, in pipeline order.
+fn plugins(plan: &ExtractionPlan) -> Vec<PluginProvenance> {
+    let plugin = |kind: &str, name: &str, version: &str| PluginProvenance {
+        kind: kind.to_string(),
+        name: name.to_string(),
+        version: version.to_string(),
+    };
+
+    let mut plugins = vec![plugin("extractor", &plan.extractor.name, &plan.extractor.version)];
+    if let Some(descriptor) = plan.ocr.as_ref().and_then(|ocr| ocr.descriptor.as_ref()) {
+        plugins.push(plugin("ocr_backend", &descriptor.name, &descriptor.version));
+    }
+    for processor in plan.post_processors.iter().filter(|processor| processor.enabled) {
+        plugins.push(plugin("post_processor", &processor.name, &processor.version));
+    }
+    for (kind, planned) in [
+        ("validator", &plan.validators),
+        ("pipeline_hook", &plan.pipeline_hooks),
+        ("chunk_processor", &plan.chunk_processors),
+    ] {
+        plugins.extend(planned.iter().map(|p| plugin(kind, &p.name, &p.version)));
+    }
+    if let Some(translator) = &plan.translator {
+        plugins.push(plugin("translator", &translator.name, &translator.version));
+    }
+    plugins
+}
+
+fn unix_time() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_manifest_records_input_and_result() {
+        let config = ExtractionConfig::default();
+        let recorder = ProvenanceRecorder::for_bytes(b"Hello, world!", &config);
+        let manifest = recorder
+            .finish("text/plain", b"{\"content\":\"Hello, world!\"}")
+            .unwrap();
+
+        assert_eq!(manifest.version, MANIFEST_VERSION);
+        assert_eq!(manifest.kreuzberg_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(manifest.input.size, 13);
+        assert_eq!(
+            manifest.input.sha256,
+            "315f5bdb76d078c43b8ac0064e4a0164612b1fce77c869345bfc94c75894edd3"
+        );
+        assert_eq!(manifest.plugins[0].kind, "extractor");
+        assert!(manifest.started_at <= manifest.finished_at);
+
+        assert!(manifest.check_result(b"{\"content\":\"Hello, world!\"}").is_ok());
+        assert!(matches!(
+            manifest.check_result(b"{\"content\":\"Goodbye\"}"),
+            Err(KreuzbergError::Security { .. })
+        ));
+
+        // The same configuration hashes the same, a different one does not.
+        let again = ProvenanceRecorder::for_bytes(b"Hello, world!", &config)
+            .finish("text/plain", b"{}")
+            .unwrap();
+        assert_eq!(again.config_sha256, manifest.config_sha256);
+        let other = ExtractionConfig {
+            use_cache: false,
+            ..Default::default()
+        };
+        let other = ProvenanceRecorder::for_bytes(b"Hello, world!", &other)
+            .finish("text/plain", b"{}")
+            .unwrap();
+        assert_ne!(other.config_sha256, manifest.config_sha256);
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_sign_and_verify() {
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let result = b"{\"content\":\"signed\"}";
+        let mut manifest = ProvenanceRecorder::for_bytes(b"signed", &ExtractionConfig::default())
+            .finish("text/plain", result)
+            .unwrap();
+
+        assert!(manifest.verify(result, &key.verifying_key()).is_err());
+        manifest.sign(&key).unwrap();
+        assert!(manifest.verify(result, &key.verifying_key()).is_ok());
+
+        // A manifest survives a JSON round trip.
+        let json = serde_json::to_vec(&manifest).unwrap();
+        let parsed: ProvenanceManifest = serde_json::from_slice(&json).unwrap();
+        assert!(parsed.verify(result, &key.verifying_key()).is_ok());
+
+        assert!(
+            manifest
+                .verify(b"{\"content\":\"forged\"}", &key.verifying_key())
+                .is_err()
+        );
+        let other = SigningKey::from_bytes(&[8; 32]);
+        assert!(manifest.verify(result, &other.verifying_key()).is_err());
+        let mut tampered = manifest.clone();
+        tampered.input.sha256 = "0".repeat(64);
+        assert!(tampered.verify(result, &key.verifying_key()).is_err());
+    }
+
+    #[cfg(feature = "signing")]
+    #[test]
+    fn test_load_keys() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = SigningKey::from_bytes(&[7; 32]);
+
+        let raw = dir.path().join("raw.key");
+        std::fs::write(&raw, key.to_bytes()).unwrap();
+        assert_eq!(load_signing_key(&raw).unwrap().to_bytes(), key.to_bytes());
+
+        let hex_key = dir.path().join("hex.key");
+        std::fs::write(&hex_key, format!("{}\n", hex::encode(key.verifying_key().as_bytes()))).unwrap();
+        assert_eq!(load_verifying_key(&hex_key).unwrap(), key.verifying_key());
+
+        let invalid = dir.path().join("invalid.key");
+        std::fs::write(&invalid, "not a key").unwrap();
+        assert!(matches!(
+            load_signing_key(&invalid),
+            Err(KreuzbergError::Validation { .. })
+        ));
+    }
+}
@@ -0,0 +1,110 @@
+//! Kafka broker of the queue connector, on librdkafka.
+
+use super::{Broker, Delivery, QueueConfig};
+use crate::{KreuzbergError, Result};
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
+use rdkafka::error::KafkaError;
+use rdkafka::message::{Header, Headers, Message, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::util::Timeout;
+use rdkafka::{Offset, TopicPartitionList};
+
+pub(super) struct KafkaBroker {
+    consumer: StreamConsumer,
+    producer: FutureProducer,
+    output_topic: String,
+}
+
+/// Topic, partition and offset of a consumed message.
+pub(super) struct KafkaToken {
+    topic: String,
+    partition: i32,
+    offset: i64,
+}
+
+impl KafkaBroker {
+    pub(super) fn connect(config: &QueueConfig) -> Result<Self> {
+        let mut client = ClientConfig::new();
+        client.set("bootstrap.servers", &config.servers);
+        for (key, value) in &config.properties {
+            client.set(key, value);
+        }
+
+        let producer: FutureProducer = client
+            .clone()
+            .set("enable.idempotence", "true")
+            .create()
+            .map_err(kafka_error)?;
+        let consumer: StreamConsumer = client
+            .set("group.id", &config.group)
+            .set("enable.auto.commit", "false")
+            .set("auto.offset.reset", "earliest")
+            .create()
+            .map_err(kafka_error)?;
+        consumer.subscribe(&[&config.input_topic]).map_err(kafka_error)?;
+
+        Ok(Self {
+            consumer,
+            producer,
+            output_topic: config.output_topic.clone(),
+        })
+    }
+}
+
+impl Broker for KafkaBroker {
+    type Token = KafkaToken;
+
+    async fn receive(&mut self) -> Result<Option<Delivery<KafkaToken>>> {
+        let message = self.consumer.recv().await.map_err(kafka_error)?;
+        let mime_type = message.headers().and_then(|headers| {
+            headers
+                .iter()
+                .find(|header| header.key.eq_ignore_ascii_case("content-type"))
+                .and_then(|header| header.value)
+                .and_then(|value| std::str::from_utf8(value).ok())
+                .map(str::to_string)
+        });
+
+        Ok(Some(Delivery {
+            key: message.key().map(<[u8]>::to_vec),
+            payload: message.payload().unwrap_or_default().to_vec(),
+            mime_type,
+            token: KafkaToken {
+                topic: message.topic().to_string(),
+                partition: message.partition(),
+                offset: message.offset(),
+            },
+        }))
+    }
+
+    async fn publish(&mut self, key: Option<&[u8]>, payload: Vec<u8>) -> Result<()> {
+        let headers = OwnedHeaders::new().insert(Header {
+            key: "content-type",
+            value: Some("application/json"),
+        });
+        let mut record = FutureRecord::<[u8], _>::to(&self.output_topic)
+            .payload(&payload)
+            .headers(headers);
+        if let Some(key) = key {
+            record = record.key(key);
+        }
+        self.producer
+            .send(record, Timeout::Never)
+            .await
+            .map_err(|(e, _)| kafka_error(e))?;
+        Ok(())
+    }
+
+    async fn commit(&mut self, token: KafkaToken) -> Result<()> {
+        let mut offsets = TopicPartitionList::new();
+        offsets
+            .add_partition_offset(&token.topic, token.partition, Offset::Offset(token.offset + 1))
+            .map_err(kafka_error)?;
+        self.consumer.commit(&offsets, CommitMode::Async).map_err(kafka_error)
+    }
+}
+
+fn kafka_error(e: KafkaError) -> KreuzbergError {
+    KreuzbergError::Other(format!("Kafka error: {e}"))
+}
@@ -0,0 +1,418 @@
+//! Message queue ingestion connector.
+//!
+//! The connector consumes documents from a Kafka topic or a NATS JetStream subject,
+//! extracts them, and publishes the serialized results to an output topic. Input
+//! messages carry either the raw bytes of a document or a JSON reference to a file
+//! (`{"path": "scans/0042.pdf"}`), as set by [`QueueConfig::payload`].
+//!
+//! Delivery is at-least-once: an input message is committed (Kafka offset commit,
+//! JetStream ack) only after its result has been published and acknowledged by the
+//! broker. A connector stopped in between processes the message again after a
+//! restart, so consumers of the output topic should tolerate duplicates.
+//!
+//! Every input message produces exactly one output message. Documents that fail to
+//! extract are published as failure records rather than retried forever; transient
+//! failures can be retried before that with `ExtractionConfig::retry`.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use kreuzberg::ExtractionConfig;
+//! use kreuzberg::queue::{self, QueueConfig};
+//!
+//! # async fn example() -> kreuzberg::Result<()> {
+//! let config: QueueConfig = toml::from_str(
+//!     r#"
+//!     backend = "kafka"
+//!     servers = "localhost:9092"
+//!     input_topic = "documents"
+//!     output_topic = "extractions"
+//!     "#,
+//! )
+//! .unwrap();
+//! queue::run(&config, &ExtractionConfig::default()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+mod kafka;
+mod nats;
+
+use crate::core::config::ExtractionConfig;
+use crate::core::extractor::batch::extract_with_retries;
+use crate::core::extractor::{extract_bytes, extract_file};
+use crate::core::mime::detect_mime_type_from_bytes;
+use crate::error::ErrorClass;
+use crate::types::ExtractionResult;
+use crate::{KreuzbergError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+/// Message broker the connector consumes from and publishes to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QueueBackend {
+    /// Apache Kafka topics
+    Kafka,
+    /// NATS JetStream subjects
+    Nats,
+}
+
+/// How the payloads of input messages are interpreted.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadKind {
+    /// The payload is the document itself; its MIME type comes from the `content-type`
+    /// header of the message, or is detected from the bytes without one
+    #[default]
+    Bytes,
+    /// The payload is a JSON reference to a file readable by the connector:
+    /// `{"path": "...", "mime_type": "..."}` with an optional `mime_type`
+    Reference,
+}
+
+/// Configuration of the queue connector.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueConfig {
+    /// Broker to connect to
+    pub backend: QueueBackend,
+
+    /// Kafka bootstrap servers (`"host:9092,host2:9092"`) or NATS server URL
+    pub servers: String,
+
+    /// Kafka topic or NATS subject the documents are consumed from
+    ///
+    /// With NATS, the subject must be captured by a JetStream stream.
+    pub input_topic: String,
+
+    /// Kafka topic or NATS subject the results are published to
+    ///
+    /// With NATS, the subject must be captured by a JetStream stream.
+    pub output_topic: String,
+
+    /// Kafka consumer group, or name of the durable NATS JetStream consumer
+    ///
+    /// Default: `"kreuzberg"`
+    #[serde(default = "default_group")]
+    pub group: String,
+
+    /// How the payloads of input messages are interpreted
+    ///
+    /// Default: `bytes`
+    #[serde(default)]
+    pub payload: PayloadKind,
+
+    /// Additional librdkafka client properties (e.g. `security.protocol`), Kafka only
+    #[serde(default)]
+    pub properties: HashMap<String, String>,
+}
+
+fn default_group() -> String {
+    "kreuzberg".to_string()
+}
+
+impl QueueConfig {
+    fn validate(&self) -> Result<()> {
+        if self.servers.is_empty() {
+            return Err(KreuzbergError::validation("servers must not be empty"));
+        }
+        if self.input_topic.is_empty() || self.output_topic.is_empty() {
+            return Err(KreuzbergError::validation(
+                "input_topic and output_topic must not be empty",
+            ));
+        }
+        if self.input_topic == self.output_topic {
+            return Err(KreuzbergError::validation(
+                "output_topic must differ from input_topic, or results would be consumed again",
+            ));
+        }
+        if self.group.is_empty() {
+            return Err(KreuzbergError::validation("group must not be empty"));
+        }
+        Ok(())
+    }
+}
+
+/// Consume, extract and publish documents until the input ends or the broker fails.
+///
+/// Kafka inputs never end, so with Kafka this only returns on errors. Dropping the
+/// returned future stops the connector; the message being processed at that moment
+/// is not committed and is processed again on the next run.
+///
+/// # Errors
+///
+/// Returns an error if `config` is invalid, or if consuming, publishing or committing
+/// fails. Extraction errors do not stop the connector; they are published as failure
+/// records.
+pub async fn run(config: &QueueConfig, extraction_config: &ExtractionConfig) -> Result<()> {
+    config.validate()?;
+    match config.backend {
+        QueueBackend::Kafka => {
+            consume(
+                &mut kafka::KafkaBroker::connect(config)?,
+                config.payload,
+                extraction_config,
+            )
+            .await
+        }
+        QueueBackend::Nats => {
+            consume(
+                &mut nats::NatsBroker::connect(config).await?,
+                config.payload,
+                extraction_config,
+            )
+            .await
+        }
+    }
+}
+
+/// A message consumed from the input topic.
+struct Delivery<T> {
+    /// Message key, kept for the output message so it lands in the same partition
+    key: Option<Vec<u8>>,
+    payload: Vec<u8>,
+    /// Value of the `content-type` header
+    mime_type: Option<String>,
+    /// What the broker needs to commit the message
+    token: T,
+}
+
+/// The operations of a message broker the connector relies on.
+trait Broker {
+    type Token;
+
+    /// Wait for the next input message, or `None` once the input has ended.
+    async fn receive(&mut self) -> Result<Option<Delivery<Self::Token>>>;
+
+    /// Publish a result to the output topic, returning once the broker stored it.
+    async fn publish(&mut self, key: Option<&[u8]>, payload: Vec<u8>) -> Result<()>;
+
+    /// Mark an input message as processed.
+    async fn commit(&mut self, token: Self::Token) -> Result<()>;
+}
+
+/// Reference to a file, as carried by input messages with [`PayloadKind::Reference`].
+#[derive(Deserialize)]
+struct DocumentReference {
+    path: PathBuf,
+    #[serde(default)]
+    mime_type: Option<String>,
+}
+
+/// An output message.
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum OutputRecord<'a> {
+    Extracted {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        path: Option<&'a PathBuf>,
+        result: &'a ExtractionResult,
+    },
+    Failed {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        path: Option<&'a PathBuf>,
+        error_type: ErrorClass,
+        error: String,
+    },
+}
+
+async fn consume<B: Broker>(broker: &mut B, payload: PayloadKind, config: &ExtractionConfig) -> Result<()> {
+    while let Some(delivery) = broker.receive().await? {
+        let record = process(&delivery, payload, config).await?;
+        broker.publish(delivery.key.as_deref(), record).await?;
+        broker.commit(delivery.token).await?;
+    }
+    Ok(())
+}
+
+/// Extract the document of `delivery` and serialize the output record for it.
+async fn process<T>(delivery: &Delivery<T>, payload: PayloadKind, config: &ExtractionConfig) -> Result<Vec<u8>> {
+    let (path, result) = match payload {
+        PayloadKind::Bytes => {
+            let result = match &delivery.mime_type {
+                Some(mime_type) => Ok(mime_type.clone()),
+                None => detect_mime_type_from_bytes(&delivery.payload),
+            };
+            let result = match result {
+                Ok(mime_type) => {
+                    extract_with_retries(config, None, None, || {
+                        extract_bytes(&delivery.payload, &mime_type, config)
+                    })
+                    .await
+                }
+                Err(e) => Err(e),
+            };
+            (None, result)
+        }
+        PayloadKind::Reference => match serde_json::from_slice::<DocumentReference>(&delivery.payload) {
+            Ok(reference) => {
+                let result = extract_with_retries(config, Some(&reference.path), None, || {
+                    extract_file(&reference.path, reference.mime_type.as_deref(), config)
+                })
+                .await;
+                (Some(reference.path), result)
+            }
+            Err(e) => (
+                None,
+                Err(KreuzbergError::validation(format!("Invalid document reference: {e}"))),
+            ),
+        },
+    };
+
+    let record = match &result {
+        Ok(result) => OutputRecord::Extracted {
+            path: path.as_ref(),
+            result,
+        },
+        Err(e) => {
+            tracing::warn!("Failed to extract queued document: {}", e);
+            OutputRecord::Failed {
+                path: path.as_ref(),
+                error_type: e.class(),
+                error: e.to_string(),
+            }
+        }
+    };
+    Ok(serde_json::to_vec(&record)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::VecDeque;
+
+    #[derive(Default)]
+    struct MemoryBroker {
+        input: VecDeque<Delivery<usize>>,
+        published: Vec<(Option<Vec<u8>>, serde_json::Value)>,
+        committed: Vec<usize>,
+        fail_publish: bool,
+    }
+
+    impl MemoryBroker {
+        fn push(&mut self, key: Option<&[u8]>, payload: &[u8], mime_type: Option<&str>) {
+            let token = self.input.len();
+            self.input.push_back(Delivery {
+                key: key.map(<[u8]>::to_vec),
+                payload: payload.to_vec(),
+                mime_type: mime_type.map(str::to_string),
+                token,
+            });
+        }
+    }
+
+    impl Broker for MemoryBroker {
+        type Token = usize;
+
+        async fn receive(&mut self) -> Result<Option<Delivery<usize>>> {
+            Ok(self.input.pop_front())
+        }
+
+        async fn publish(&mut self, key: Option<&[u8]>, payload: Vec<u8>) -> Result<()> {
+            if self.fail_publish {
+                return Err(KreuzbergError::Other("broker unavailable".to_string()));
+            }
+            self.published
+                .push((key.map(<[u8]>::to_vec), serde_json::from_slice(&payload).unwrap()));
+            Ok(())
+        }
+
+        async fn commit(&mut self, token: usize) -> Result<()> {
+            self.committed.push(token);
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_consume_bytes() {
+        let mut broker = MemoryBroker::default();
+        broker.push(Some(b"doc-1"), b"Hello from the queue", Some("text/plain"));
+        broker.push(None, b"data", Some("invalid/mime"));
+
+        consume(&mut broker, PayloadKind::Bytes, &ExtractionConfig::default())
+            .await
+            .unwrap();
+
+        assert_eq!(broker.committed, vec![0, 1]);
+        let (key, record) = &broker.published[0];
+        assert_eq!(key.as_deref(), Some(&b"doc-1"[..]));
+        assert_eq!(record["status"], "extracted");
+        assert!(
+            record["result"]["content"]
+                .as_str()
+                .unwrap()
+                .contains("Hello from the queue")
+        );
+        let (key, record) = &broker.published[1];
+        assert_eq!(*key, None);
+        assert_eq!(record["status"], "failed");
+        assert_eq!(record["error_type"], "unsupported_format");
+    }
+
+    #[tokio::test]
+    async fn test_consume_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("note.txt");
+        std::fs::write(&path, "Referenced document").unwrap();
+        let mut broker = MemoryBroker::default();
+        broker.push(
+            None,
+            &serde_json::to_vec(&serde_json::json!({ "path": path })).unwrap(),
+            None,
+        );
+        broker.push(None, b"not json", None);
+
+        consume(&mut broker, PayloadKind::Reference, &ExtractionConfig::default())
+            .await
+            .unwrap();
+
+        let record = &broker.published[0].1;
+        assert_eq!(record["status"], "extracted");
+        assert_eq!(record["path"], path.to_str().unwrap());
+        assert!(
+            record["result"]["content"]
+                .as_str()
+                .unwrap()
+                .contains("Referenced document")
+        );
+        let record = &broker.published[1].1;
+        assert_eq!(record["status"], "failed");
+        assert_eq!(record["error_type"], "validation");
+    }
+
+    #[tokio::test]
+    async fn test_consume_does_not_commit_unpublished_results() {
+        let mut broker = MemoryBroker {
+            fail_publish: true,
+            ..Default::default()
+        };
+        broker.push(None, b"Hello", Some("text/plain"));
+
+        let result = consume(&mut broker, PayloadKind::Bytes, &ExtractionConfig::default()).await;
+
+        assert!(result.is_err());
+        assert!(broker.committed.is_empty());
+    }
+
+    #[test]
+    fn test_queue_config_validation() {
+        let mut config: QueueConfig = toml::from_str(
+            r#"
+            backend = "nats"
+            servers = "nats://localhost:4222"
+            input_topic = "documents.in"
+            output_topic = "documents.out"
+            payload = "reference"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.backend, QueueBackend::Nats);
+        assert_eq!(config.payload, PayloadKind::Reference);
+        assert_eq!(config.group, "kreuzberg");
+        assert!(config.validate().is_ok());
+
+        config.output_topic = config.input_topic.clone();
+        assert!(config.validate().is_err());
+    }
+}
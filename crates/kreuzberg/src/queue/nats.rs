@@ -0,0 +1,87 @@
+//! NATS JetStream broker of the queue connector.
+
+use super::{Broker, Delivery, QueueConfig};
+use crate::{KreuzbergError, Result};
+use async_nats::HeaderMap;
+use async_nats::jetstream::{self, Message, consumer::pull};
+use futures::StreamExt;
+
+pub(super) struct NatsBroker {
+    jetstream: jetstream::Context,
+    messages: pull::Stream,
+    output_subject: String,
+}
+
+impl NatsBroker {
+    pub(super) async fn connect(config: &QueueConfig) -> Result<Self> {
+        let client = async_nats::connect(&config.servers).await.map_err(nats_error)?;
+        let jetstream = jetstream::new(client);
+
+        let stream_name = jetstream
+            .stream_by_subject(config.input_topic.as_str())
+            .await
+            .map_err(nats_error)?;
+        let stream = jetstream.get_stream(&stream_name).await.map_err(nats_error)?;
+        let consumer = stream
+            .get_or_create_consumer(
+                &config.group,
+                pull::Config {
+                    durable_name: Some(config.group.clone()),
+                    filter_subject: config.input_topic.clone(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(nats_error)?;
+        let messages = consumer.messages().await.map_err(nats_error)?;
+
+        Ok(Self {
+            jetstream,
+            messages,
+            output_subject: config.output_topic.clone(),
+        })
+    }
+}
+
+impl Broker for NatsBroker {
+    type Token = Message;
+
+    async fn receive(&mut self) -> Result<Option<Delivery<Message>>> {
+        let Some(message) = self.messages.next().await else {
+            return Ok(None);
+        };
+        let message = message.map_err(nats_error)?;
+        let mime_type = message
+            .headers
+            .as_ref()
+            .and_then(|headers| headers.get("Content-Type"))
+            .map(|value| value.as_str().to_string());
+
+        Ok(Some(Delivery {
+            key: None,
+            payload: message.payload.to_vec(),
+            mime_type,
+            token: message,
+        }))
+    }
+
+    async fn publish(&mut self, _key: Option<&[u8]>, payload: Vec<u8>) -> Result<()> {
+        let mut headers = HeaderMap::new();
+        headers.insert("Content-Type", "application/json");
+        self.jetstream
+            .publish_with_headers(self.output_subject.clone(), headers, payload.into())
+            .await
+            .map_err(nats_error)?
+            .await
+            .map_err(nats_error)?;
+        Ok(())
+    }
+
+    async fn commit(&mut self, message: Message) -> Result<()> {
+        message.double_ack().await.map_err(nats_error)
+    }
+}
+
+fn nats_error(e: impl std::fmt::Display) -> KreuzbergError {
+    KreuzbergError::Other(format!("NATS error: {e}"))
+}
@@ -0,0 +1,234 @@
+//! Self-test of the configured extraction backends.
+//!
+//! [`self_test`] extracts tiny built-in fixtures with a configuration: a line of
+//! plain text through the whole post-processing pipeline, a one-page PDF with the
+//! `pdf` feature and a small PNG through the configured OCR backend with the `ocr`
+//! feature. It reports whether each component passed and how long it took, so that
+//! deployments can tell a working installation from one with missing libraries,
+//! models or plugins before taking traffic. The API server runs it on `GET /ready`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use kreuzberg::{ExtractionConfig, self_test};
+//!
+//! # tokio_test::block_on(async {
+//! let report = self_test(&ExtractionConfig::default()).await;
+//! for component in &report.components {
+//!     println!("{}: {} in {:.1} ms", component.name, component.passed, component.duration_ms);
+//! }
+//! assert!(report.passed);
+//! # });
+//! ```
+
+use crate::core::config::ExtractionConfig;
+use crate::{Result, extract_bytes};
+use serde::{Deserialize, Serialize};
+use std::future::Future;
+use std::time::Instant;
+
+/// Text of the fixtures.
+const FIXTURE_TEXT: &str = "Kreuzberg self-test";
+
+/// Outcome of a self-test.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct SelfTestReport {
+    /// Whether every component passed
+    pub passed: bool,
+    /// Wall time of the whole self-test in milliseconds
+    pub duration_ms: f64,
+    /// Components tested, in order
+    pub components: Vec<ComponentCheck>,
+}
+
+/// Outcome of the self-test of one component.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct ComponentCheck {
+    /// Component name: `pipeline`, `pdf`, or `ocr:<backend>`
+    pub name: String,
+    /// Whether the component passed
+    pub passed: bool,
+    /// Wall time of the component in milliseconds
+    pub duration_ms: f64,
+    /// Why the component failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Exercise the backends configured by `config` with built-in fixtures.
+///
+/// Components are tested one after the other, without the caches, so that each
+/// latency is that of the component itself. Failures are reported, not returned.
+pub async fn self_test(config: &ExtractionConfig) -> SelfTestReport {
+    let started = Instant::now();
+    let config = ExtractionConfig {
+        use_cache: false,
+        ..config.clone()
+    };
+    let mut components = Vec::new();
+
+    components.push(
+        check("pipeline", async {
+            extract_bytes(FIXTURE_TEXT.as_bytes(), "text/plain", &config).await?;
+            Ok(())
+        })
+        .await,
+    );
+
+    #[cfg(feature = "pdf")]
+    {
+        let pdf_config = ExtractionConfig {
+            ocr: None,
+            force_ocr: false,
+            ..config.clone()
+        };
+        components.push(
+            check("pdf", async {
+                let result = extract_bytes(&fixture_pdf(), "application/pdf", &pdf_config).await?;
+                if result.content.contains(FIXTURE_TEXT) {
+                    Ok(())
+                } else {
+                    Err(crate::KreuzbergError::validation(format!(
+                        "PDF fixture extracted as {:?}",
+                        result.content
+                    )))
+                }
+            })
+            .await,
+        );
+    }
+
+    #[cfg(feature = "ocr")]
+    {
+        let ocr = config.ocr.clone().unwrap_or_default();
+        let name = format!("ocr:{}", ocr.backend);
+        let ocr_config = ExtractionConfig {
+            ocr: Some(ocr),
+            ..config.clone()
+        };
+        components.push(
+            check(&name, async {
+                extract_bytes(&fixture_png()?, "image/png", &ocr_config).await?;
+                Ok(())
+            })
+            .await,
+        );
+    }
+
+    SelfTestReport {
+        passed: components.iter().all(|component| component.passed),
+        duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+        components,
+    }
+}
+
+async fn check(name: &str, test: impl Future<Output = Result<()>>) -> ComponentCheck {
+    let started = Instant::now();
+    let outcome = test.await;
+    let duration_ms = started.elapsed().as_secs_f64() * 1000.0;
+    if let Err(e) = &outcome {
+        tracing::warn!("Self-test of {} failed: {}", name, e);
+    }
+    ComponentCheck {
+        name: name.to_string(),
+        passed: outcome.is_ok(),
+        duration_ms,
+        error: outcome.err().map(|e| e.to_string()),
+    }
+}
+
+/// A one-page PDF showing [`FIXTURE_TEXT`] in Helvetica.
+#[cfg(any(feature = "pdf", test))]
+fn fixture_pdf() -> Vec<u8> {
+    let content = format!("BT /F1 12 Tf 10 20 Td ({FIXTURE_TEXT}) Tj ET");
+    let objects = [
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        "<< /Type /Pages /Kids [3 0 R] /Count 1 >>".to_string(),
+        "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 200 50] /Contents 4 0 R \
+         /Resources << /Font << /F1 5 0 R >> >> >>"
+            .to_string(),
+        format!("<< /Length {} >>\nstream\n{content}\nendstream", content.len()),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>".to_string(),
+    ];
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.push_str(&format!("{} 0 obj\n{object}\nendobj\n", index + 1));
+    }
+    let xref = pdf.len();
+    pdf.push_str(&format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1));
+    for offset in offsets {
+        pdf.push_str(&format!("{offset:010} 00000 n \n"));
+    }
+    pdf.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+        objects.len() + 1
+    ));
+    pdf.into_bytes()
+}
+
+/// A small white PNG with a dark bar, enough to run an OCR backend end to end.
+#[cfg(feature = "ocr")]
+fn fixture_png() -> Result<Vec<u8>> {
+    let image = image::RgbImage::from_fn(120, 40, |x, y| {
+        if (10..110).contains(&x) && (16..24).contains(&y) {
+            image::Rgb([0, 0, 0])
+        } else {
+            image::Rgb([255, 255, 255])
+        }
+    });
+    let mut bytes = std::io::Cursor::new(Vec::new());
+    image
+        .write_to(&mut bytes, image::ImageFormat::Png)
+        .map_err(|e| crate::KreuzbergError::Other(format!("Failed to encode the PNG fixture: {e}")))?;
+    Ok(bytes.into_inner())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::ContentFilterConfig;
+
+    #[tokio::test]
+    async fn test_self_test() {
+        let report = self_test(&ExtractionConfig::default()).await;
+        assert!(report.passed, "{report:?}");
+        assert_eq!(report.components[0].name, "pipeline");
+        assert!(report.components.iter().all(|component| component.error.is_none()));
+
+        let broken = ExtractionConfig {
+            content_filter: Some(ContentFilterConfig {
+                denylist: vec!["(".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let report = self_test(&broken).await;
+        assert!(!report.passed);
+        assert!(!report.components[0].passed);
+        assert!(report.components[0].error.is_some());
+    }
+
+    #[test]
+    fn test_fixture_pdf_offsets() {
+        let pdf = String::from_utf8(fixture_pdf()).unwrap();
+        let startxref: usize = pdf
+            .rsplit("startxref\n")
+            .next()
+            .unwrap()
+            .lines()
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(pdf[startxref..].starts_with("xref\n0 6\n"));
+        for (index, line) in pdf[startxref..].lines().skip(3).take(5).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj", index + 1)));
+        }
+    }
+}
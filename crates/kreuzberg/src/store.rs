@@ -0,0 +1,418 @@
+//! Content-addressed store of extraction results.
+//!
+//! A [`BlobStore`] writes every artifact of a result (content, chunks, tables, images
+//! and the result itself) to a file named after the SHA-256 of its bytes, so
+//! identical artifacts are stored once, across batches and runs. Each stored
+//! result is described by a [`ResultManifest`], itself a blob, and
+//! `index.jsonl` records one [`IndexEntry`] per stored result:
+//!
+//! ```text
+//! <root>/
+//!   index.jsonl
+//!   objects/
+//!     3a/3a7bd3e2360a3d29eea436fcfb7e44c735d117c42d1c1835420b6b9942dd4f1b
+//!     ...
+//! ```
+//!
+//! JSON artifacts are written with sorted keys and without whitespace, so the
+//! same result always produces the same digests.
+//!
+//! A store opened [`with_compression`](BlobStore::with_compression) compresses JSON
+//! and text blobs and appends the compression extension to their file names, e.g.
+//! `objects/3a/3a7b….zst`. Digests are always those of the uncompressed bytes, and
+//! [`BlobStore::get`] decompresses, so compressed and uncompressed blobs can be mixed.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use kreuzberg::store::BlobStore;
+//! use kreuzberg::{ExtractionConfig, extract_file_sync};
+//!
+//! # fn example() -> kreuzberg::Result<()> {
+//! let store = BlobStore::open("extracted")?;
+//! let result = extract_file_sync("report.pdf", None, &ExtractionConfig::default())?;
+//! let entry = store.put_result("report.pdf", &result)?;
+//!
+//! let manifest = store.manifest(&entry.manifest)?;
+//! let text = store.get(&manifest.content.digest)?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::compression;
+use crate::core::config::Compression;
+use crate::types::ExtractionResult;
+use crate::{KreuzbergError, Result};
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// Name of the index file in the store root.
+const INDEX_FILE: &str = "index.jsonl";
+
+/// Directory of the blobs in the store root.
+const OBJECTS_DIR: &str = "objects";
+
+/// A blob of the store.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BlobRef {
+    /// Lowercase hex SHA-256 of the blob, also its file name
+    pub digest: String,
+    /// Size in bytes
+    pub size: u64,
+    /// Media type of the blob, e.g. `application/json` or `image/png`
+    pub media_type: String,
+}
+
+/// The artifacts of a stored extraction result.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResultManifest {
+    /// MIME type of the source document
+    pub mime_type: String,
+    /// Extracted content
+    pub content: BlobRef,
+    /// The full result as versioned JSON, with empty image data; see `images`
+    pub result: BlobRef,
+    /// Chunks as JSON, in order
+    #[serde(default)]
+    pub chunks: Vec<BlobRef>,
+    /// Tables as JSON, in order
+    #[serde(default)]
+    pub tables: Vec<BlobRef>,
+    /// Image data, in the order of the result's images
+    #[serde(default)]
+    pub images: Vec<BlobRef>,
+}
+
+/// A line of `index.jsonl`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexEntry {
+    /// Source of the result, e.g. the path of the extracted file
+    pub source: String,
+    /// Digest of the [`ResultManifest`] blob
+    pub manifest: String,
+}
+
+/// Content-addressed store of extraction results in a directory.
+#[derive(Debug, Clone)]
+pub struct BlobStore {
+    root: PathBuf,
+    compression: Compression,
+}
+
+impl BlobStore {
+    /// Open the store at `root`, creating the directory if needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the directory cannot be created.
+    pub fn open(root: impl Into<PathBuf>) -> Result<Self> {
+        let root = root.into();
+        std::fs::create_dir_all(root.join(OBJECTS_DIR))?;
+        Ok(Self {
+            root,
+            compression: Compression::None,
+        })
+    }
+
+    /// Compress JSON and text blobs written from now on with `compression`.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.compression = compression;
+        self
+    }
+
+    /// Root directory of the store.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Write `data` to the store, unless a blob with the same digest exists.
+    ///
+    /// # Errors
+    ///
+    /// Returns an I/O error if the blob cannot be written, and a missing dependency
+    /// error if it is to be compressed without the `compression` feature.
+    pub fn put(&self, data: &[u8], media_type: &str) -> Result<BlobRef> {
+        let digest = hex::encode(Sha256::digest(data));
+        if self.find(&digest).is_none() {
+            let compression = if media_type == "application/json" || media_type.starts_with("text/") {
+                self.compression
+            } else {
+                Compression::None
+            };
+            let path = self.object_path(&digest, compression);
+            let dir = path.parent().expect("object paths have a parent directory");
+            std::fs::create_dir_all(dir)?;
+            // Write next to the target and rename, so readers never see a partial blob.
+            let partial = dir.join(format!(".{digest}.{}.partial", uuid::Uuid::new_v4()));
+            std::fs::write(&partial, compression::compress(data, compression)?)?;
+            std::fs::rename(&partial, &path)?;
+        }
+        Ok(BlobRef {
+            digest,
+            size: data.len() as u64,
+            media_type: media_type.to_string(),
+        })
+    }
+
+    /// Read the blob `digest`, decompressed.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `digest` is not a SHA-256 hex digest, and an I/O
+    /// error if the blob does not exist or cannot be decompressed.
+    pub fn get(&self, digest: &str) -> Result<Vec<u8>> {
+        let path = self.blob_path(digest)?;
+        let data = std::fs::read(&path)?;
+        if path.extension().is_some() {
+            return Ok(compression::decompress(&data)?.into_owned());
+        }
+        Ok(data)
+    }
+
+    /// Path of the file of the blob `digest`.
+    ///
+    /// For a blob that does not exist, this is the path of an uncompressed blob.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `digest` is not a lowercase SHA-256 hex digest.
+    pub fn blob_path(&self, digest: &str) -> Result<PathBuf> {
+        if digest.len() != 64 || !digest.bytes().all(|b| matches!(b, b'0'..=b'9' | b'a'..=b'f')) {
+            return Err(KreuzbergError::validation(format!("Not a SHA-256 digest: '{digest}'")));
+        }
+        Ok(self
+            .find(digest)
+            .unwrap_or_else(|| self.object_path(digest, Compression::None)))
+    }
+
+    /// Store the artifacts of `result` and record it in the index under `source`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if an artifact cannot be serialized or written.
+    pub fn put_result(&self, source: &str, result: &ExtractionResult) -> Result<IndexEntry> {
+        let content = self.put(result.content.as_bytes(), "text/plain; charset=utf-8")?;
+
+        let mut chunks = Vec::new();
+        for chunk in result.chunks.iter().flatten() {
+            chunks.push(self.put(&canonical_json(chunk)?, "application/json")?);
+        }
+        let mut tables = Vec::new();
+        for table in &result.tables {
+            tables.push(self.put(&canonical_json(table)?, "application/json")?);
+        }
+
+        let mut images = Vec::new();
+        let mut stripped = result.clone();
+        for image in stripped.images.iter_mut().flatten() {
+            images.push(self.put(&image.data, &format!("image/{}", image.format.to_ascii_lowercase()))?);
+            image.data = Bytes::new();
+        }
+        let result_blob = self.put(&canonical_json(&stripped.to_versioned_value()?)?, "application/json")?;
+
+        let manifest = ResultManifest {
+            mime_type: result.mime_type.to_string(),
+            content,
+            result: result_blob,
+            chunks,
+            tables,
+            images,
+        };
+        let manifest = self.put(&canonical_json(&manifest)?, "application/json")?;
+
+        let entry = IndexEntry {
+            source: source.to_string(),
+            manifest: manifest.digest,
+        };
+        let mut line = serde_json::to_vec(&entry)?;
+        line.push(b'\n');
+        std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.root.join(INDEX_FILE))?
+            .write_all(&line)?;
+
+        Ok(entry)
+    }
+
+    /// Read the manifest `digest`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the blob does not exist or is not a manifest.
+    pub fn manifest(&self, digest: &str) -> Result<ResultManifest> {
+        Ok(serde_json::from_slice(&self.get(digest)?)?)
+    }
+
+    /// Read the result of the manifest `digest`, with its image data.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a blob of the result does not exist or cannot be parsed.
+    pub fn get_result(&self, digest: &str) -> Result<ExtractionResult> {
+        let manifest = self.manifest(digest)?;
+        let mut result =
+            ExtractionResult::from_versioned_value(serde_json::from_slice(&self.get(&manifest.result.digest)?)?)?;
+        for (image, blob) in result.images.iter_mut().flatten().zip(&manifest.images) {
+            image.data = Bytes::from(self.get(&blob.digest)?);
+        }
+        Ok(result)
+    }
+
+    /// Entries of `index.jsonl`, oldest first. A source stored more than once has
+    /// one entry per time it was stored.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the index cannot be read or has an invalid line.
+    pub fn index(&self) -> Result<Vec<IndexEntry>> {
+        let index = match std::fs::read_to_string(self.root.join(INDEX_FILE)) {
+            Ok(index) => index,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        index
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Ok(serde_json::from_str(line)?))
+            .collect()
+    }
+
+    /// Path of the existing file of the blob `digest`, compressed or not.
+    fn find(&self, digest: &str) -> Option<PathBuf> {
+        [
+            self.compression,
+            Compression::None,
+            Compression::Zstd,
+            Compression::Gzip,
+        ]
+        .into_iter()
+        .map(|compression| self.object_path(digest, compression))
+        .find(|path| path.exists())
+    }
+
+    fn object_path(&self, digest: &str, compression: Compression) -> PathBuf {
+        self.root
+            .join(OBJECTS_DIR)
+            .join(&digest[..2])
+            .join(format!("{digest}{}", compression.extension()))
+    }
+}
+
+/// JSON of `value` with sorted keys, so equal values have equal bytes.
+fn canonical_json<T: Serialize>(value: &T) -> Result<Vec<u8>> {
+    // `serde_json::Value` maps are sorted by key.
+    Ok(serde_json::to_vec(&serde_json::to_value(value)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ExtractedImage;
+    use std::borrow::Cow;
+    use tempfile::tempdir;
+
+    fn result_with_image() -> ExtractionResult {
+        ExtractionResult {
+            content: "Quarterly report".to_string(),
+            mime_type: Cow::Borrowed("application/pdf"),
+            images: Some(vec![ExtractedImage {
+                data: Bytes::from_static(b"\x89PNG fake image"),
+                format: Cow::Borrowed("png"),
+                image_index: 0,
+                page_number: Some(1),
+                width: None,
+                height: None,
+                colorspace: None,
+                bits_per_component: None,
+                is_mask: false,
+                description: None,
+                ocr_result: None,
+            }]),
+            metadata: Default::default(),
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            djot_content: None,
+            scripts: None,
+            pages: None,
+            elements: None,
+        }
+    }
+
+    #[test]
+    fn test_put_deduplicates_blobs() {
+        let dir = tempdir().unwrap();
+        let store = BlobStore::open(dir.path()).unwrap();
+
+        let first = store.put(b"hello", "text/plain").unwrap();
+        let second = store.put(b"hello", "text/plain").unwrap();
+        assert_eq!(first, second);
+        assert_eq!(
+            first.digest,
+            "2cf24dba5fb0a30e26e83b2ac5b9e29e1b161e5c1fa7425e73043362938b9824"
+        );
+        assert_eq!(store.get(&first.digest).unwrap(), b"hello");
+        assert_eq!(std::fs::read_dir(dir.path().join("objects/2c")).unwrap().count(), 1);
+
+        assert!(store.get("../index.jsonl").is_err());
+    }
+
+    #[test]
+    fn test_put_result_round_trips() {
+        let dir = tempdir().unwrap();
+        let store = BlobStore::open(dir.path()).unwrap();
+        let result = result_with_image();
+
+        let first = store.put_result("a/report.pdf", &result).unwrap();
+        let second = store.put_result("b/report-copy.pdf", &result).unwrap();
+        assert_eq!(
+            first.manifest, second.manifest,
+            "identical results share their manifest"
+        );
+
+        let manifest = store.manifest(&first.manifest).unwrap();
+        assert_eq!(manifest.mime_type, "application/pdf");
+        assert_eq!(store.get(&manifest.content.digest).unwrap(), b"Quarterly report");
+        assert_eq!(manifest.images.len(), 1);
+        assert_eq!(manifest.images[0].media_type, "image/png");
+
+        let restored = store.get_result(&first.manifest).unwrap();
+        assert_eq!(restored.content, result.content);
+        assert_eq!(restored.images.unwrap()[0].data, result.images.unwrap()[0].data);
+
+        let index = store.index().unwrap();
+        assert_eq!(index, vec![first, second]);
+        assert_eq!(index[1].source, "b/report-copy.pdf");
+    }
+
+    #[cfg(feature = "compression")]
+    #[test]
+    fn test_compressed_store_reads_plain_blobs() {
+        let dir = tempdir().unwrap();
+        let plain = BlobStore::open(dir.path()).unwrap();
+        let text = plain.put(b"written before compression", "text/plain").unwrap();
+
+        let store = BlobStore::open(dir.path()).unwrap().with_compression(Compression::Zstd);
+        let entry = store.put_result("report.pdf", &result_with_image()).unwrap();
+
+        let manifest_path = store.blob_path(&entry.manifest).unwrap();
+        assert_eq!(manifest_path.extension().unwrap(), "zst");
+        let manifest = store.manifest(&entry.manifest).unwrap();
+        assert!(
+            store
+                .blob_path(&manifest.images[0].digest)
+                .unwrap()
+                .extension()
+                .is_none()
+        );
+        assert_eq!(store.get_result(&entry.manifest).unwrap().content, "Quarterly report");
+
+        assert_eq!(store.get(&text.digest).unwrap(), b"written before compression");
+        assert_eq!(store.put(b"written before compression", "text/plain").unwrap(), text);
+        assert!(store.blob_path(&text.digest).unwrap().extension().is_none());
+    }
+}
@@ -0,0 +1,128 @@
+//! OpenTelemetry export of the extraction spans.
+//!
+//! With the `otel` feature, the extraction pipeline records a span for each stage
+//! (inspection, extractor, post-processors, content filters, chunking, validators, ...).
+//! [`otlp_layer`] turns these spans into OpenTelemetry spans exported over OTLP, and
+//! [`parent_context`] reads the W3C `traceparent` of incoming requests, so extractions
+//! served by the API show up in the caller's distributed trace.
+//!
+//! The exporter is configured with the standard OpenTelemetry environment variables,
+//! such as `OTEL_EXPORTER_OTLP_ENDPOINT` (default `http://localhost:4318`) and
+//! `OTEL_SERVICE_NAME`.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use tracing_subscriber::prelude::*;
+//!
+//! # fn main() -> kreuzberg::Result<()> {
+//! let (layer, _guard) = kreuzberg::telemetry::otlp_layer("kreuzberg")?;
+//! tracing_subscriber::registry().with(layer).init();
+//! // Spans are flushed when `_guard` is dropped.
+//! # Ok(())
+//! # }
+//! ```
+
+use opentelemetry::Context;
+use opentelemetry::propagation::{Extractor, TextMapPropagator};
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::SpanExporter;
+use opentelemetry_sdk::Resource;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{SdkTracerProvider, Tracer};
+use tracing::Subscriber;
+use tracing_opentelemetry::OpenTelemetryLayer;
+use tracing_subscriber::registry::LookupSpan;
+
+use crate::{KreuzbergError, Result};
+
+/// Keeps the OTLP exporter running; pending spans are flushed when it is dropped.
+pub struct TelemetryGuard {
+    provider: SdkTracerProvider,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            tracing::warn!("Failed to flush OpenTelemetry spans: {e}");
+        }
+    }
+}
+
+/// Create a `tracing` layer exporting spans over OTLP (HTTP/protobuf).
+///
+/// `service_name` is used unless `OTEL_SERVICE_NAME` is set. The layer must not be
+/// created inside an async runtime, as the exporter runs its own blocking HTTP client.
+pub fn otlp_layer<S>(service_name: &str) -> Result<(OpenTelemetryLayer<S, Tracer>, TelemetryGuard)>
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    let exporter = SpanExporter::builder()
+        .with_http()
+        .build()
+        .map_err(|e| KreuzbergError::Other(format!("Failed to create OTLP exporter: {e}")))?;
+
+    let mut resource = Resource::builder();
+    if std::env::var_os("OTEL_SERVICE_NAME").is_none() {
+        resource = resource.with_service_name(service_name.to_string());
+    }
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(resource.build())
+        .build();
+
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+    opentelemetry::global::set_tracer_provider(provider.clone());
+
+    let layer = tracing_opentelemetry::layer().with_tracer(provider.tracer("kreuzberg"));
+    Ok((layer, TelemetryGuard { provider }))
+}
+
+/// Remote parent context of a request carrying W3C `traceparent` and `tracestate` headers.
+///
+/// Returns an empty context when the headers are missing or invalid.
+pub fn parent_context<'a>(headers: impl IntoIterator<Item = (&'a str, &'a str)>) -> Context {
+    let headers: Vec<_> = headers.into_iter().collect();
+    TraceContextPropagator::new().extract(&Headers(&headers))
+}
+
+struct Headers<'a>(&'a [(&'a str, &'a str)]);
+
+impl Extractor for Headers<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(name, _)| name.eq_ignore_ascii_case(key))
+            .map(|(_, value)| *value)
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.iter().map(|(name, _)| *name).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::TraceContextExt;
+
+    #[test]
+    fn test_parent_context_reads_traceparent() {
+        let context = parent_context([
+            ("Traceparent", "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01"),
+            ("content-type", "application/json"),
+        ]);
+
+        let span_context = context.span().span_context().clone();
+        assert!(span_context.is_remote());
+        assert_eq!(span_context.trace_id().to_string(), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(span_context.span_id().to_string(), "00f067aa0ba902b7");
+        assert!(span_context.is_sampled());
+    }
+
+    #[test]
+    fn test_parent_context_ignores_missing_or_invalid_headers() {
+        assert!(!parent_context([]).has_active_span());
+        assert!(!parent_context([("traceparent", "not-a-trace")]).has_active_span());
+    }
+}
@@ -0,0 +1,232 @@
+//! Right-to-left and bidirectional text handling.
+//!
+//! PDF content streams frequently store Arabic and Hebrew text in visual order
+//! (left-to-right glyph positions), and often with Arabic presentation forms
+//! (contextual glyph shapes) instead of the base letters. Extracted verbatim, such
+//! text comes out character-reversed and does not match searches.
+//!
+//! This module provides the building blocks to fix that:
+//! - [`visual_to_logical`] reorders a visually stored line into logical order
+//! - [`normalize_arabic_presentation_forms`] maps shaped glyphs back to base letters
+//! - [`direction_runs`] reports the direction of each run of text
+
+use crate::types::Metadata;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use unicode_bidi::{Level, ParagraphBidiInfo};
+use unicode_normalization::UnicodeNormalization;
+
+/// Writing direction of a run of text.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TextDirection {
+    /// Left-to-right (Latin, Cyrillic, CJK, ...)
+    Ltr,
+    /// Right-to-left (Arabic, Hebrew, Syriac, Thaana, N'Ko)
+    Rtl,
+}
+
+/// A contiguous run of text with a single writing direction.
+///
+/// Offsets are UTF-8 byte offsets into the text the run was computed from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DirectionRun {
+    /// Direction of the run
+    pub direction: TextDirection,
+    /// Byte offset where the run starts (inclusive)
+    pub byte_start: usize,
+    /// Byte offset where the run ends (exclusive)
+    pub byte_end: usize,
+}
+
+/// Whether a character belongs to a right-to-left script block.
+pub fn is_rtl_char(c: char) -> bool {
+    matches!(
+        c as u32,
+        0x0590..=0x08FF | 0xFB1D..=0xFDFF | 0xFE70..=0xFEFC | 0x10800..=0x10FFF | 0x1E800..=0x1EFFF
+    )
+}
+
+/// Whether the text contains any right-to-left characters.
+pub fn contains_rtl(text: &str) -> bool {
+    text.chars().any(is_rtl_char)
+}
+
+fn is_arabic_presentation_form(c: char) -> bool {
+    matches!(c as u32, 0xFB50..=0xFDFF | 0xFE70..=0xFEFC)
+}
+
+/// Replace Arabic presentation forms (contextual glyph shapes and ligatures) with
+/// their base letters.
+///
+/// Only characters from the Arabic Presentation Forms-A/B blocks are touched; all
+/// other text is left as-is. Returns the input borrowed when nothing changed.
+pub fn normalize_arabic_presentation_forms(text: &str) -> Cow<'_, str> {
+    if !text.chars().any(is_arabic_presentation_form) {
+        return Cow::Borrowed(text);
+    }
+
+    let mut normalized = String::with_capacity(text.len());
+    for c in text.chars() {
+        if is_arabic_presentation_form(c) {
+            normalized.extend(std::iter::once(c).nfkc());
+        } else {
+            normalized.push(c);
+        }
+    }
+    Cow::Owned(normalized)
+}
+
+/// Convert a single line stored in visual (left-to-right display) order into
+/// logical order.
+///
+/// The line is treated as a right-to-left paragraph and reordered with the
+/// Unicode Bidirectional Algorithm, which reverses RTL runs while keeping embedded
+/// numbers and Latin text readable.
+pub fn visual_to_logical(line: &str) -> Cow<'_, str> {
+    if !contains_rtl(line) {
+        return Cow::Borrowed(line);
+    }
+
+    let info = ParagraphBidiInfo::new(line, Some(Level::rtl()));
+    Cow::Owned(info.reorder_line(0..line.len()).into_owned())
+}
+
+/// Apply [`visual_to_logical`] to every line of `text` that contains RTL characters.
+pub fn reorder_visual_lines(text: &str) -> Cow<'_, str> {
+    if !contains_rtl(text) {
+        return Cow::Borrowed(text);
+    }
+
+    let lines: Vec<Cow<'_, str>> = text.split('\n').map(visual_to_logical).collect();
+    Cow::Owned(lines.join("\n"))
+}
+
+/// Split text into runs of a single writing direction.
+///
+/// Direction is decided by strong (alphabetic) characters. Neutral characters such
+/// as spaces, digits and punctuation belong to the run they follow; leading
+/// neutrals belong to the first run.
+pub fn direction_runs(text: &str) -> Vec<DirectionRun> {
+    let mut runs: Vec<DirectionRun> = Vec::new();
+
+    for (offset, c) in text.char_indices() {
+        let direction = if is_rtl_char(c) && c.is_alphabetic() {
+            Some(TextDirection::Rtl)
+        } else if c.is_alphabetic() {
+            Some(TextDirection::Ltr)
+        } else {
+            None
+        };
+
+        let char_end = offset + c.len_utf8();
+        match (direction, runs.last_mut()) {
+            (Some(direction), Some(run)) if run.direction != direction => {
+                runs.push(DirectionRun {
+                    direction,
+                    byte_start: offset,
+                    byte_end: char_end,
+                });
+            }
+            (Some(direction), None) => {
+                runs.push(DirectionRun {
+                    direction,
+                    byte_start: 0,
+                    byte_end: char_end,
+                });
+            }
+            (_, Some(run)) => run.byte_end = char_end,
+            (None, None) => {}
+        }
+    }
+
+    runs
+}
+
+/// Record direction information for text containing right-to-left scripts.
+///
+/// Adds `metadata.additional["text_direction"]` (`"rtl"` or `"mixed"`) and
+/// `metadata.additional["direction_runs"]`. Purely left-to-right text is left
+/// unannotated.
+pub fn annotate_text_direction(text: &str, metadata: &mut Metadata) {
+    if !contains_rtl(text) {
+        return;
+    }
+
+    let runs = direction_runs(text);
+    if runs.is_empty() {
+        return;
+    }
+
+    let direction = if runs.iter().all(|run| run.direction == TextDirection::Rtl) {
+        "rtl"
+    } else {
+        "mixed"
+    };
+
+    metadata
+        .additional
+        .insert(Cow::Borrowed("text_direction"), serde_json::json!(direction));
+    metadata
+        .additional
+        .insert(Cow::Borrowed("direction_runs"), serde_json::json!(runs));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_visual_to_logical_hebrew() {
+        // "שלום" stored in visual order
+        assert_eq!(visual_to_logical("םולש"), "שלום");
+    }
+
+    #[test]
+    fn test_visual_to_logical_keeps_numbers_readable() {
+        // Logical "שלום 123 עולם", stored visually as "םלוע 123 םולש"
+        assert_eq!(visual_to_logical("םלוע 123 םולש"), "שלום 123 עולם");
+    }
+
+    #[test]
+    fn test_visual_to_logical_ltr_untouched() {
+        let line = "Hello world";
+        assert!(matches!(visual_to_logical(line), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_reorder_visual_lines_mixed_lines() {
+        let reordered = reorder_visual_lines("Title\nםולש");
+        assert_eq!(reordered, "Title\nשלום");
+    }
+
+    #[test]
+    fn test_normalize_arabic_presentation_forms() {
+        // Isolated/final/initial forms of "سلام" (seen, lam-alef ligature, meem)
+        let shaped = "\u{FEB3}\u{FEFB}\u{FEE1}";
+        assert_eq!(normalize_arabic_presentation_forms(shaped), "سلام");
+        assert!(matches!(normalize_arabic_presentation_forms("سلام"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_direction_runs_mixed() {
+        let text = "Hello שלום";
+        let runs = direction_runs(text);
+        assert_eq!(runs.len(), 2);
+        assert_eq!(runs[0].direction, TextDirection::Ltr);
+        assert_eq!(&text[runs[0].byte_start..runs[0].byte_end], "Hello ");
+        assert_eq!(runs[1].direction, TextDirection::Rtl);
+        assert_eq!(&text[runs[1].byte_start..runs[1].byte_end], "שלום");
+    }
+
+    #[test]
+    fn test_annotate_text_direction() {
+        let mut metadata = Metadata::default();
+        annotate_text_direction("مرحبا بالعالم", &mut metadata);
+        assert_eq!(metadata.additional["text_direction"], "rtl");
+
+        let mut metadata = Metadata::default();
+        annotate_text_direction("plain english", &mut metadata);
+        assert!(!metadata.additional.contains_key("text_direction"));
+    }
+}
@@ -0,0 +1,424 @@
+//! Boilerplate phrase removal.
+//!
+//! Corporate documents repeat the same disclaimers, confidentiality notices and
+//! footers in every file. Left in the content they end up in every chunk and make
+//! unrelated documents look alike to a search index. The post-processor in this
+//! module removes the phrases configured in `ExtractionConfig::boilerplate`: exact
+//! phrases and regular expressions wherever they match, and paragraphs that are near
+//! copies of a fuzzy phrase.
+
+use crate::core::config::BoilerplateConfig;
+use crate::plugins::{Plugin, PostProcessor, ProcessingStage};
+use crate::{ExtractionConfig, ExtractionResult, KreuzbergError, Result};
+use async_trait::async_trait;
+use regex::Regex;
+use std::borrow::Cow;
+use std::ops::Range;
+
+/// Compiled boilerplate phrases and patterns.
+#[derive(Debug)]
+pub struct BoilerplateMatcher {
+    patterns: Vec<Regex>,
+    fuzzy_phrases: Vec<Vec<String>>,
+    similarity_threshold: f64,
+}
+
+impl BoilerplateMatcher {
+    /// Compile the phrases and patterns of `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if a pattern is not a valid regular expression.
+    pub fn new(config: &BoilerplateConfig) -> Result<Self> {
+        let mut patterns = Vec::with_capacity(config.phrases.len() + config.patterns.len());
+        for phrase in config.phrases.iter().filter(|phrase| !phrase.trim().is_empty()) {
+            patterns.push(
+                Regex::new(&phrase_pattern(phrase)).map_err(|e| {
+                    KreuzbergError::validation(format!("Invalid boilerplate phrase '{}': {}", phrase, e))
+                })?,
+            );
+        }
+        for pattern in &config.patterns {
+            patterns.push(Regex::new(pattern).map_err(|e| {
+                KreuzbergError::validation(format!("Invalid boilerplate pattern '{}': {}", pattern, e))
+            })?);
+        }
+
+        Ok(Self {
+            patterns,
+            fuzzy_phrases: config
+                .fuzzy_phrases
+                .iter()
+                .map(|phrase| words(phrase))
+                .filter(|words| !words.is_empty())
+                .collect(),
+            similarity_threshold: config.similarity_threshold.clamp(0.0, 1.0),
+        })
+    }
+
+    /// Remove the boilerplate from `text`.
+    ///
+    /// Returns the text and the number of phrases removed. A line that only held
+    /// boilerplate is removed along with its line break, and runs of blank lines left
+    /// behind are collapsed.
+    pub fn remove<'a>(&self, text: &'a str) -> (Cow<'a, str>, usize) {
+        let mut ranges = self.fuzzy_matches(text);
+        for pattern in &self.patterns {
+            ranges.extend(
+                pattern
+                    .find_iter(text)
+                    .filter(|found| !found.is_empty())
+                    .map(|found| found.range()),
+            );
+        }
+        if ranges.is_empty() {
+            return (Cow::Borrowed(text), 0);
+        }
+        let count = ranges.len();
+
+        ranges.sort_by_key(|range| range.start);
+        let mut cleaned = String::with_capacity(text.len());
+        let mut position = 0;
+        for range in ranges {
+            let range = expand_removal(text, range);
+            if range.end <= position {
+                continue;
+            }
+            cleaned.push_str(&text[position..range.start.max(position)]);
+            position = range.end;
+        }
+        cleaned.push_str(&text[position..]);
+
+        (Cow::Owned(collapse_blank_lines(&cleaned)), count)
+    }
+
+    /// Paragraphs of `text` similar enough to a fuzzy phrase.
+    fn fuzzy_matches(&self, text: &str) -> Vec<Range<usize>> {
+        if self.fuzzy_phrases.is_empty() {
+            return Vec::new();
+        }
+
+        paragraphs(text)
+            .into_iter()
+            .filter(|paragraph| {
+                let paragraph_words = words(&text[paragraph.clone()]);
+                self.fuzzy_phrases
+                    .iter()
+                    .any(|phrase| similarity(&paragraph_words, phrase) >= self.similarity_threshold)
+            })
+            .collect()
+    }
+}
+
+/// Pattern matching `phrase` case-insensitively with any whitespace between words,
+/// and only at word boundaries.
+fn phrase_pattern(phrase: &str) -> String {
+    let body = phrase
+        .split_whitespace()
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(r"\s+");
+    let start = if phrase.trim_start().starts_with(char::is_alphanumeric) {
+        r"\b"
+    } else {
+        ""
+    };
+    let end = if phrase.trim_end().ends_with(char::is_alphanumeric) {
+        r"\b"
+    } else {
+        ""
+    };
+    format!("(?i){start}{body}{end}")
+}
+
+/// Lower-case words of `text`, without punctuation.
+fn words(text: &str) -> Vec<String> {
+    text.split(|ch: char| !ch.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Word-level similarity of two texts: one minus their edit distance in words over
+/// the length of the longer one.
+fn similarity(a: &[String], b: &[String]) -> f64 {
+    let longer = a.len().max(b.len());
+    if longer == 0 {
+        return 1.0;
+    }
+    let shorter = a.len().min(b.len());
+    if (shorter as f64) / (longer as f64) < 0.5 {
+        return 0.0;
+    }
+
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, word_a) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, word_b) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(word_a != word_b);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    1.0 - previous[b.len()] as f64 / longer as f64
+}
+
+/// Byte ranges of the paragraphs of `text`, separated by blank lines.
+fn paragraphs(text: &str) -> Vec<Range<usize>> {
+    let mut paragraphs = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            if let Some(start) = start.take() {
+                paragraphs.push(start..end);
+            }
+        } else {
+            start.get_or_insert(offset);
+            end = offset + line.trim_end().len();
+        }
+        offset += line.len();
+    }
+    if let Some(start) = start {
+        paragraphs.push(start..end);
+    }
+    paragraphs
+}
+
+/// Extend a removal so no stray whitespace is left: over the whole line when nothing
+/// else is on it, otherwise over the whitespace that follows it.
+fn expand_removal(text: &str, range: Range<usize>) -> Range<usize> {
+    let line_start = text[..range.start].rfind('\n').map_or(0, |index| index + 1);
+    let line_end = text[range.end..]
+        .find('\n')
+        .map_or(text.len(), |index| range.end + index);
+
+    let alone = text[line_start..range.start].trim().is_empty() && text[range.end..line_end].trim().is_empty();
+    if alone {
+        return line_start..(line_end + 1).min(text.len());
+    }
+
+    let after = &text[range.end..line_end];
+    let spaces = after.len() - after.trim_start_matches([' ', '\t']).len();
+    let preceded_by_space = text[..range.start].ends_with([' ', '\t', '\n']) || range.start == 0;
+    if preceded_by_space {
+        range.start..range.end + spaces
+    } else {
+        range
+    }
+}
+
+/// Collapse runs of blank lines into one blank line and trim the text.
+fn collapse_blank_lines(text: &str) -> String {
+    let mut collapsed = String::with_capacity(text.len());
+    let mut blank_lines = 0;
+    for line in text.split_inclusive('\n') {
+        if line.trim().is_empty() {
+            blank_lines += 1;
+            if blank_lines > 1 {
+                continue;
+            }
+        } else {
+            blank_lines = 0;
+        }
+        collapsed.push_str(line);
+    }
+    collapsed.trim().to_string()
+}
+
+/// Post-processor that removes configured boilerplate phrases.
+///
+/// This processor:
+/// - Runs in the Early processing stage, before quality processing, keyword
+///   extraction and chunking
+/// - Only processes when `config.boilerplate` is configured
+/// - Removes boilerplate from the content and the page contents
+/// - Stores the number of phrases removed from the content in
+///   `metadata.additional["boilerplate_removed"]`
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::plugins::{Plugin, PostProcessor};
+/// use kreuzberg::text::boilerplate::BoilerplateRemover;
+///
+/// let processor = BoilerplateRemover;
+/// assert_eq!(processor.name(), "boilerplate-removal");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct BoilerplateRemover;
+
+impl Plugin for BoilerplateRemover {
+    fn name(&self) -> &str {
+        "boilerplate-removal"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl PostProcessor for BoilerplateRemover {
+    async fn process(&self, result: &mut ExtractionResult, config: &ExtractionConfig) -> Result<()> {
+        let Some(boilerplate) = &config.boilerplate else {
+            return Ok(());
+        };
+        let matcher = BoilerplateMatcher::new(boilerplate)?;
+
+        let (content, removed) = matcher.remove(&result.content);
+        if let Cow::Owned(content) = content {
+            result.content = content;
+        }
+        if let Some(pages) = result.pages.as_mut() {
+            for page in pages {
+                if let (Cow::Owned(content), _) = matcher.remove(&page.content) {
+                    page.content = content;
+                }
+            }
+        }
+
+        if removed > 0 {
+            result
+                .metadata
+                .additional
+                .insert(Cow::Borrowed("boilerplate_removed"), serde_json::json!(removed));
+        }
+        Ok(())
+    }
+
+    fn processing_stage(&self) -> ProcessingStage {
+        ProcessingStage::Early
+    }
+
+    fn should_process(&self, _result: &ExtractionResult, config: &ExtractionConfig) -> bool {
+        config.boilerplate.is_some()
+    }
+
+    fn estimated_duration_ms(&self, result: &ExtractionResult) -> u64 {
+        (result.content.len() as u64) / 10_000 + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Metadata, PageContent};
+
+    const DISCLAIMER: &str = "This e-mail and any attachments are confidential and may be privileged. If you are not the intended recipient, please notify the sender and delete it.";
+
+    fn matcher(config: BoilerplateConfig) -> BoilerplateMatcher {
+        BoilerplateMatcher::new(&config).unwrap()
+    }
+
+    #[test]
+    fn test_remove_exact_phrases() {
+        let matcher = matcher(BoilerplateConfig {
+            phrases: vec!["All rights reserved.".to_string(), "Confidential".to_string()],
+            ..Default::default()
+        });
+
+        let (cleaned, removed) =
+            matcher.remove("Quarterly report\nCONFIDENTIAL\n\nRevenue grew. All rights\nreserved. Costs fell.");
+        assert_eq!(cleaned, "Quarterly report\n\nRevenue grew. Costs fell.");
+        assert_eq!(removed, 2);
+
+        let (cleaned, removed) = matcher.remove("Confidentiality matters.");
+        assert!(matches!(cleaned, Cow::Borrowed(_)));
+        assert_eq!(removed, 0);
+    }
+
+    #[test]
+    fn test_remove_patterns() {
+        let matcher = matcher(BoilerplateConfig {
+            patterns: vec![r"(?i)copyright \d{4} acme corp\.?".to_string()],
+            ..Default::default()
+        });
+        let (cleaned, removed) = matcher.remove("Product sheet\n\nCopyright 2024 ACME Corp.\n\nPage 2");
+        assert_eq!(cleaned, "Product sheet\n\nPage 2");
+        assert_eq!(removed, 1);
+
+        let invalid = BoilerplateConfig {
+            patterns: vec!["(unclosed".to_string()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            BoilerplateMatcher::new(&invalid),
+            Err(KreuzbergError::Validation { .. })
+        ));
+    }
+
+    #[test]
+    fn test_remove_fuzzy_paragraphs() {
+        let matcher = matcher(BoilerplateConfig {
+            fuzzy_phrases: vec![DISCLAIMER.to_string()],
+            ..Default::default()
+        });
+        let text = "Hi Anna,\n\nthe contract is attached.\n\nThis email and all attachments are confidential and may be privileged. If you are not the intended recipient, please notify the sender and delete it.\n\nBest, Tom";
+
+        let (cleaned, removed) = matcher.remove(text);
+        assert_eq!(cleaned, "Hi Anna,\n\nthe contract is attached.\n\nBest, Tom");
+        assert_eq!(removed, 1);
+
+        let strict = self::matcher(BoilerplateConfig {
+            fuzzy_phrases: vec![DISCLAIMER.to_string()],
+            similarity_threshold: 0.95,
+            ..Default::default()
+        });
+        assert_eq!(strict.remove(text).1, 0);
+    }
+
+    #[tokio::test]
+    async fn test_boilerplate_remover_processes_pages() {
+        let config = ExtractionConfig {
+            boilerplate: Some(BoilerplateConfig {
+                phrases: vec!["Internal use only".to_string()],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let page = |number: usize, content: &str| PageContent {
+            page_number: number,
+            content: content.to_string(),
+            tables: vec![],
+            images: vec![],
+            hierarchy: None,
+        };
+        let mut result = ExtractionResult {
+            content: "Internal use only\nFirst page\n\nInternal use only\nSecond page".to_string(),
+            mime_type: Cow::Borrowed("application/pdf"),
+            metadata: Metadata::default(),
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            scripts: None,
+            pages: Some(vec![
+                page(1, "Internal use only\nFirst page"),
+                page(2, "Internal use only\nSecond page"),
+            ]),
+            elements: None,
+        };
+
+        assert!(BoilerplateRemover.should_process(&result, &config));
+        BoilerplateRemover.process(&mut result, &config).await.unwrap();
+
+        assert_eq!(result.content, "First page\n\nSecond page");
+        assert_eq!(result.pages.as_ref().unwrap()[1].content, "Second page");
+        assert_eq!(result.metadata.additional["boilerplate_removed"], 2);
+        assert!(!BoilerplateRemover.should_process(&result, &ExtractionConfig::default()));
+    }
+}
@@ -0,0 +1,466 @@
+//! Glossary and terminology matching.
+//!
+//! Linking documents to a domain taxonomy (MeSH headings, product codes, a legal
+//! thesaurus) usually means running an NLP service over every document. For a fixed
+//! terminology list, a dictionary lookup is enough: the post-processor in this module
+//! matches the terms and synonyms configured in `ExtractionConfig::glossary` against
+//! the content in a single pass with an Aho-Corasick automaton, and records the ID of
+//! every matched term with its byte offsets in `metadata.additional["glossary_matches"]`.
+
+use crate::core::config::glossary::{GlossaryConfig, GlossaryTerm};
+use crate::plugins::{Plugin, PostProcessor, ProcessingStage};
+use crate::{ExtractionConfig, ExtractionResult, KreuzbergError, Result};
+use aho_corasick::{AhoCorasick, MatchKind};
+use async_trait::async_trait;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Metadata key of the glossary matches, in `metadata.additional`.
+pub const GLOSSARY_MATCHES_KEY: &str = "glossary_matches";
+
+/// Glossaries built so far, by their files, inline terms and case sensitivity.
+type GlossaryKey = (Vec<PathBuf>, Vec<GlossaryTerm>, bool);
+static GLOSSARIES: Lazy<Mutex<HashMap<GlossaryKey, Arc<Glossary>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A term found in a text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlossaryMatch {
+    /// ID of the matched term
+    pub id: String,
+    /// Preferred form of the matched term
+    pub term: String,
+    /// The term or synonym as written in the text
+    pub text: String,
+    /// Byte offset of the start of the match
+    pub start: usize,
+    /// Byte offset of the end of the match
+    pub end: usize,
+}
+
+/// Terms and synonyms compiled into an Aho-Corasick automaton.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::core::config::GlossaryTerm;
+/// use kreuzberg::text::glossary::Glossary;
+///
+/// let glossary = Glossary::new(
+///     vec![GlossaryTerm {
+///         id: "D006333".to_string(),
+///         term: "heart failure".to_string(),
+///         synonyms: vec!["cardiac failure".to_string()],
+///     }],
+///     false,
+/// )?;
+/// let matches = glossary.find("Signs of Cardiac Failure were absent.", true);
+/// assert_eq!(matches[0].id, "D006333");
+/// assert_eq!(matches[0].text, "Cardiac Failure");
+/// # Ok::<(), kreuzberg::KreuzbergError>(())
+/// ```
+#[derive(Debug)]
+pub struct Glossary {
+    automaton: AhoCorasick,
+    /// Index into `terms` of every pattern of the automaton.
+    pattern_terms: Vec<usize>,
+    terms: Vec<GlossaryTerm>,
+    case_sensitive: bool,
+}
+
+impl Glossary {
+    /// Compile the terms and synonyms of `terms`. When a form is listed for several
+    /// terms, it is matched as the first of them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if the automaton cannot be built.
+    pub fn new(terms: Vec<GlossaryTerm>, case_sensitive: bool) -> Result<Self> {
+        let mut patterns = Vec::new();
+        let mut pattern_terms = Vec::new();
+        for (index, term) in terms.iter().enumerate() {
+            for form in std::iter::once(&term.term).chain(&term.synonyms) {
+                let form = form.trim();
+                if form.is_empty() {
+                    continue;
+                }
+                patterns.push(if case_sensitive {
+                    form.to_string()
+                } else {
+                    form.to_lowercase()
+                });
+                pattern_terms.push(index);
+            }
+        }
+
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(&patterns)
+            .map_err(|e| KreuzbergError::Validation {
+                message: format!("Cannot build glossary: {}", e),
+                source: Some(Box::new(e)),
+            })?;
+
+        Ok(Self {
+            automaton,
+            pattern_terms,
+            terms,
+            case_sensitive,
+        })
+    }
+
+    /// Load the terms of a `.csv` or `.toml` terminology file.
+    ///
+    /// CSV files need a header with an `id` and a `term` column; an optional `synonyms`
+    /// column lists synonyms separated by `|`. TOML files list the terms in `[[terms]]`
+    /// tables.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if the file cannot be read or parsed, or has
+    /// another extension.
+    pub fn load_terms(path: &Path) -> Result<Vec<GlossaryTerm>> {
+        let invalid = |message: String| KreuzbergError::Validation { message, source: None };
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+
+        match extension.as_deref() {
+            Some("csv") => {
+                #[derive(Deserialize)]
+                struct Row {
+                    id: String,
+                    term: String,
+                    #[serde(default)]
+                    synonyms: String,
+                }
+
+                let mut reader = csv::ReaderBuilder::new()
+                    .flexible(true)
+                    .trim(csv::Trim::All)
+                    .from_path(path)
+                    .map_err(|e| invalid(format!("Cannot read glossary '{}': {}", path.display(), e)))?;
+                reader
+                    .deserialize::<Row>()
+                    .map(|row| {
+                        let row = row.map_err(|e| invalid(format!("Invalid glossary '{}': {}", path.display(), e)))?;
+                        Ok(GlossaryTerm {
+                            id: row.id,
+                            term: row.term,
+                            synonyms: row
+                                .synonyms
+                                .split('|')
+                                .map(str::trim)
+                                .filter(|synonym| !synonym.is_empty())
+                                .map(str::to_string)
+                                .collect(),
+                        })
+                    })
+                    .collect()
+            }
+            Some("toml") => {
+                #[derive(Deserialize)]
+                struct File {
+                    #[serde(default)]
+                    terms: Vec<GlossaryTerm>,
+                }
+
+                let text = std::fs::read_to_string(path).map_err(|e| KreuzbergError::Validation {
+                    message: format!("Cannot read glossary '{}': {}", path.display(), e),
+                    source: Some(Box::new(e)),
+                })?;
+                let file: File = toml::from_str(&text)
+                    .map_err(|e| invalid(format!("Invalid glossary '{}': {}", path.display(), e)))?;
+                Ok(file.terms)
+            }
+            _ => Err(invalid(format!(
+                "Glossary '{}' is neither a .csv nor a .toml file",
+                path.display()
+            ))),
+        }
+    }
+
+    /// Find the terms in `text`, longest first where they overlap.
+    ///
+    /// With `whole_words`, matches that start or end inside a word are skipped.
+    pub fn find(&self, text: &str, whole_words: bool) -> Vec<GlossaryMatch> {
+        let folded = (!self.case_sensitive).then(|| fold_case(text));
+        let haystack = folded.as_ref().map_or(text, |(folded, _)| folded.as_str());
+
+        self.automaton
+            .find_iter(haystack)
+            .filter_map(|found| {
+                let (start, end) = match &folded {
+                    Some((_, offsets)) => {
+                        let splits_char =
+                            |at: usize| at > 0 && at < offsets.len() - 1 && offsets[at] == offsets[at - 1];
+                        if splits_char(found.start()) || splits_char(found.end()) {
+                            return None;
+                        }
+                        (offsets[found.start()], offsets[found.end()])
+                    }
+                    None => (found.start(), found.end()),
+                };
+                if whole_words && !is_whole_word(text, start, end) {
+                    return None;
+                }
+                let term = &self.terms[self.pattern_terms[found.pattern().as_usize()]];
+                Some(GlossaryMatch {
+                    id: term.id.clone(),
+                    term: term.term.clone(),
+                    text: text[start..end].to_string(),
+                    start,
+                    end,
+                })
+            })
+            .collect()
+    }
+
+    /// Number of terms.
+    pub fn len(&self) -> usize {
+        self.terms.len()
+    }
+
+    /// Whether the glossary has no terms.
+    pub fn is_empty(&self) -> bool {
+        self.terms.is_empty()
+    }
+}
+
+/// Lowercase `text`, with the byte offset in `text` of every byte of the result and
+/// the length of `text` at the end.
+fn fold_case(text: &str) -> (String, Vec<usize>) {
+    let mut folded = String::with_capacity(text.len());
+    let mut offsets = Vec::with_capacity(text.len() + 1);
+    for (offset, c) in text.char_indices() {
+        let before = folded.len();
+        folded.extend(c.to_lowercase());
+        offsets.extend(std::iter::repeat_n(offset, folded.len() - before));
+    }
+    offsets.push(text.len());
+    (folded, offsets)
+}
+
+fn is_whole_word(text: &str, start: usize, end: usize) -> bool {
+    let before = text[..start].chars().next_back();
+    let after = text[end..].chars().next();
+    !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+}
+
+/// The glossary of `config`, built once per configuration.
+fn load_glossary(config: &GlossaryConfig) -> Result<Arc<Glossary>> {
+    let key = (config.files.clone(), config.terms.clone(), config.case_sensitive);
+    if let Some(glossary) = GLOSSARIES.lock().get(&key) {
+        return Ok(Arc::clone(glossary));
+    }
+
+    let mut terms = config.terms.clone();
+    for path in &config.files {
+        terms.extend(Glossary::load_terms(path)?);
+    }
+    let glossary = Arc::new(Glossary::new(terms, config.case_sensitive)?);
+    GLOSSARIES.lock().insert(key, Arc::clone(&glossary));
+    Ok(glossary)
+}
+
+/// Post-processor that matches the configured terminology against the content.
+///
+/// This processor:
+/// - Runs in the Late processing stage, after the content is cleaned up
+/// - Only processes when `config.glossary` is configured
+/// - Stores the matches in `metadata.additional["glossary_matches"]`
+///
+/// The offsets refer to the content after post-processing; content filters,
+/// normalization profiles and truncation that run later can move or cut them.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::plugins::Plugin;
+/// use kreuzberg::text::glossary::GlossaryMatcher;
+///
+/// let processor = GlossaryMatcher;
+/// assert_eq!(processor.name(), "glossary");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct GlossaryMatcher;
+
+impl Plugin for GlossaryMatcher {
+    fn name(&self) -> &str {
+        "glossary"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl PostProcessor for GlossaryMatcher {
+    async fn process(&self, result: &mut ExtractionResult, config: &ExtractionConfig) -> Result<()> {
+        let Some(glossary_config) = &config.glossary else {
+            return Ok(());
+        };
+        let glossary = load_glossary(glossary_config)?;
+
+        let matches = glossary.find(&result.content, glossary_config.whole_words);
+        result
+            .metadata
+            .additional
+            .insert(Cow::Borrowed(GLOSSARY_MATCHES_KEY), serde_json::to_value(matches)?);
+        Ok(())
+    }
+
+    fn processing_stage(&self) -> ProcessingStage {
+        ProcessingStage::Late
+    }
+
+    fn should_process(&self, _result: &ExtractionResult, config: &ExtractionConfig) -> bool {
+        config.glossary.is_some()
+    }
+
+    fn estimated_duration_ms(&self, result: &ExtractionResult) -> u64 {
+        (result.content.len() as u64) / 50_000 + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Metadata;
+    use std::io::Write;
+
+    fn term(id: &str, term: &str, synonyms: &[&str]) -> GlossaryTerm {
+        GlossaryTerm {
+            id: id.to_string(),
+            term: term.to_string(),
+            synonyms: synonyms.iter().map(|synonym| synonym.to_string()).collect(),
+        }
+    }
+
+    fn found(matches: &[GlossaryMatch]) -> Vec<(&str, &str, usize)> {
+        matches
+            .iter()
+            .map(|found| (found.id.as_str(), found.text.as_str(), found.start))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_terms_and_synonyms() {
+        let glossary = Glossary::new(
+            vec![
+                term("D006333", "heart failure", &["cardiac failure", "HF"]),
+                term("D006331", "heart disease", &[]),
+                term("D006321", "heart", &[]),
+            ],
+            false,
+        )
+        .unwrap();
+
+        let text = "HF, or Cardiac Failure, is a heart disease of the heart; the hearth is unrelated.";
+        assert_eq!(
+            found(&glossary.find(text, true)),
+            vec![
+                ("D006333", "HF", 0),
+                ("D006333", "Cardiac Failure", 7),
+                ("D006331", "heart disease", 29),
+                ("D006321", "heart", 50),
+            ]
+        );
+        assert_eq!(glossary.find(text, false).last().unwrap().text, "heart");
+        assert_eq!(glossary.find(text, false).len(), 5);
+    }
+
+    #[test]
+    fn test_find_keeps_offsets_of_case_folded_text() {
+        let glossary = Glossary::new(vec![term("X1", "straße", &[])], false).unwrap();
+        let text = "İİ STRASSE, Große STRAßE";
+        let matches = glossary.find(text, true);
+        assert_eq!(found(&matches), vec![("X1", "STRAßE", text.find("STRAßE").unwrap())]);
+
+        let glossary = Glossary::new(vec![term("X1", "HF", &[])], true).unwrap();
+        assert!(glossary.find("hf and Hf", true).is_empty());
+    }
+
+    #[test]
+    fn test_load_terms_from_csv_and_toml() {
+        let dir = tempfile::tempdir().unwrap();
+        let csv_path = dir.path().join("terms.csv");
+        let mut file = std::fs::File::create(&csv_path).unwrap();
+        writeln!(file, "id,term,synonyms").unwrap();
+        writeln!(file, "D006333,heart failure,cardiac failure | HF").unwrap();
+        writeln!(file, "\"D003920\",\"diabetes mellitus, type 2\",").unwrap();
+        let terms = Glossary::load_terms(&csv_path).unwrap();
+        assert_eq!(terms[0], term("D006333", "heart failure", &["cardiac failure", "HF"]));
+        assert_eq!(terms[1], term("D003920", "diabetes mellitus, type 2", &[]));
+
+        let toml_path = dir.path().join("terms.toml");
+        std::fs::write(
+            &toml_path,
+            "[[terms]]\nid = \"P-1\"\nterm = \"torque wrench\"\nsynonyms = [\"torque spanner\"]\n",
+        )
+        .unwrap();
+        assert_eq!(
+            Glossary::load_terms(&toml_path).unwrap(),
+            vec![term("P-1", "torque wrench", &["torque spanner"])]
+        );
+
+        assert!(Glossary::load_terms(&dir.path().join("terms.txt")).is_err());
+        assert!(Glossary::load_terms(&dir.path().join("missing.csv")).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_glossary_matcher_records_matches() {
+        let config = ExtractionConfig {
+            glossary: Some(GlossaryConfig {
+                terms: vec![term("P-1", "torque wrench", &["torque spanner"])],
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut result = ExtractionResult {
+            content: "Use a Torque Spanner set to 40 Nm.".to_string(),
+            mime_type: Cow::Borrowed("text/plain"),
+            metadata: Metadata::default(),
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            scripts: None,
+            pages: None,
+            elements: None,
+        };
+
+        assert!(GlossaryMatcher.should_process(&result, &config));
+        GlossaryMatcher.process(&mut result, &config).await.unwrap();
+
+        let matches: Vec<GlossaryMatch> =
+            serde_json::from_value(result.metadata.additional[GLOSSARY_MATCHES_KEY].clone()).unwrap();
+        assert_eq!(
+            matches,
+            vec![GlossaryMatch {
+                id: "P-1".to_string(),
+                term: "torque wrench".to_string(),
+                text: "Torque Spanner".to_string(),
+                start: 6,
+                end: 20,
+            }]
+        );
+        assert!(!GlossaryMatcher.should_process(&result, &ExtractionConfig::default()));
+    }
+}
@@ -1,4 +1,19 @@
+#[cfg(any(feature = "pdf", feature = "ocr"))]
+pub mod bidi;
+pub mod boilerplate;
+pub mod glossary;
+pub mod normalization;
+pub mod rules;
+pub mod script;
+pub mod sentences;
+pub(crate) mod simd_scan;
+pub mod spellcheck;
+pub mod token_count;
+pub mod transliteration;
+pub mod truncation;
 pub mod utf8_validation;
+pub mod values;
+pub mod watermark;
 
 #[cfg(feature = "quality")]
 pub mod quality;
@@ -25,3 +40,6 @@ pub use string_utils::{calculate_text_confidence, fix_mojibake, get_encoding_cac
 pub use token_reduction::{
     ReductionLevel, TokenReductionConfig, batch_reduce_tokens, get_reduction_statistics, reduce_tokens,
 };
+
+pub use normalization::normalize_text;
+pub use token_count::{BatchTokenUsage, CostEstimate, TokenUsage, count_tokens, summarize_token_usage};
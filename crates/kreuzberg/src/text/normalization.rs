@@ -0,0 +1,281 @@
+//! Output text normalization.
+//!
+//! Applies the options of a [`NormalizationProfile`](crate::core::config::NormalizationProfile)
+//! to extracted text. Page separators and page markers between the pages of a result
+//! are left as they are, so page boundaries stay valid.
+
+use crate::core::config::{NewlinePolicy, NormalizationOptions};
+use crate::text::simd_scan;
+use crate::types::{ExtractionResult, PageBoundary};
+use std::borrow::Cow;
+
+/// Normalize text with the given options.
+///
+/// Returns the input unchanged, without allocating, when the options leave text as it is.
+pub fn normalize_text<'a>(text: &'a str, options: &NormalizationOptions) -> Cow<'a, str> {
+    if options.is_identity() {
+        return Cow::Borrowed(text);
+    }
+
+    let unified;
+    let lines: Vec<&str> = if options.newlines == NewlinePolicy::Preserve {
+        text.split('\n').collect()
+    } else {
+        unified = text.replace("\r\n", "\n");
+        unified.split(['\n', '\r', '\u{85}', '\u{2028}', '\u{2029}']).collect()
+    };
+    let lines: Vec<String> = lines.into_iter().map(|line| normalize_line(line, options)).collect();
+
+    let normalized = match options.newlines {
+        NewlinePolicy::Preserve => lines.join("\n"),
+        NewlinePolicy::Paragraphs => {
+            let mut paragraphs = String::with_capacity(text.len());
+            let mut blank_run = false;
+            for line in lines {
+                if line.trim().is_empty() {
+                    blank_run = !paragraphs.is_empty();
+                    continue;
+                }
+                if !paragraphs.is_empty() {
+                    paragraphs.push_str(if blank_run { "\n\n" } else { "\n" });
+                }
+                blank_run = false;
+                paragraphs.push_str(&line);
+            }
+            paragraphs
+        }
+        NewlinePolicy::Join => lines
+            .into_iter()
+            .filter(|line| !line.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join(" "),
+    };
+
+    let normalized = if options.casefold {
+        normalized.to_lowercase()
+    } else {
+        normalized
+    };
+
+    if normalized == text {
+        Cow::Borrowed(text)
+    } else {
+        Cow::Owned(normalized)
+    }
+}
+
+fn normalize_line(line: &str, options: &NormalizationOptions) -> String {
+    let mut normalized = String::with_capacity(line.len());
+    let mut pending_space = false;
+    let mut rest = line;
+
+    while let Some(c) = rest.chars().next() {
+        // Printable ASCII and single spaces between it are left as they are by every option.
+        if c != ' ' {
+            let run = simd_scan::plain_ascii_prefix(rest.as_bytes());
+            if run > 0 {
+                if std::mem::take(&mut pending_space) {
+                    normalized.push(' ');
+                }
+                normalized.push_str(&rest[..run]);
+                rest = &rest[run..];
+                continue;
+            }
+        }
+        rest = &rest[c.len_utf8()..];
+
+        if options.normalize_punctuation {
+            match c {
+                '\u{ad}' | '\u{200b}' | '\u{2060}' | '\u{feff}' => continue,
+                '\u{2018}' | '\u{2019}' | '\u{201a}' | '\u{201b}' | '\u{2032}' => {
+                    push_char(&mut normalized, &mut pending_space, '\'');
+                    continue;
+                }
+                '\u{201c}' | '\u{201d}' | '\u{201e}' | '\u{201f}' | '\u{2033}' | '\u{ab}' | '\u{bb}' => {
+                    push_char(&mut normalized, &mut pending_space, '"');
+                    continue;
+                }
+                '\u{2010}' | '\u{2011}' | '\u{2012}' | '\u{2013}' | '\u{2014}' | '\u{2015}' | '\u{2212}' => {
+                    push_char(&mut normalized, &mut pending_space, '-');
+                    continue;
+                }
+                '\u{2026}' => {
+                    for dot in ['.', '.', '.'] {
+                        push_char(&mut normalized, &mut pending_space, dot);
+                    }
+                    continue;
+                }
+                _ => {}
+            }
+        }
+
+        if options.collapse_whitespace {
+            if c.is_whitespace() {
+                pending_space = !normalized.is_empty();
+                continue;
+            }
+            if c.is_control() {
+                continue;
+            }
+        }
+        push_char(&mut normalized, &mut pending_space, c);
+    }
+
+    normalized
+}
+
+fn push_char(text: &mut String, pending_space: &mut bool, c: char) {
+    if std::mem::take(pending_space) {
+        text.push(' ');
+    }
+    text.push(c);
+}
+
+/// Normalize the content and page contents of a result.
+///
+/// When the content has page boundaries, each page is normalized on its own and the
+/// boundaries are moved to the normalized pages.
+pub(crate) fn normalize_result(result: &mut ExtractionResult, options: &NormalizationOptions) {
+    if options.is_identity() {
+        return;
+    }
+
+    let boundaries = result
+        .metadata
+        .pages
+        .as_mut()
+        .and_then(|pages| pages.boundaries.as_mut())
+        .filter(|boundaries| valid_boundaries(&result.content, boundaries));
+
+    match boundaries {
+        Some(boundaries) => {
+            let mut content = String::with_capacity(result.content.len());
+            let mut position = 0;
+            for boundary in boundaries.iter_mut() {
+                content.push_str(&result.content[position..boundary.byte_start]);
+                let byte_start = content.len();
+                content.push_str(&normalize_text(
+                    &result.content[boundary.byte_start..boundary.byte_end],
+                    options,
+                ));
+                position = boundary.byte_end;
+                boundary.byte_start = byte_start;
+                boundary.byte_end = content.len();
+            }
+            content.push_str(&result.content[position..]);
+            result.content = content;
+        }
+        None => {
+            if let Cow::Owned(content) = normalize_text(&result.content, options) {
+                result.content = content;
+            }
+        }
+    }
+
+    for page in result.pages.iter_mut().flatten() {
+        if let Cow::Owned(content) = normalize_text(&page.content, options) {
+            page.content = content;
+        }
+    }
+}
+
+/// Whether boundaries are ordered, do not overlap and fall on character boundaries.
+fn valid_boundaries(content: &str, boundaries: &[PageBoundary]) -> bool {
+    let mut position = 0;
+    boundaries.iter().all(|boundary| {
+        let valid = position <= boundary.byte_start
+            && boundary.byte_start <= boundary.byte_end
+            && content.is_char_boundary(boundary.byte_start)
+            && content.is_char_boundary(boundary.byte_end);
+        position = boundary.byte_end;
+        valid
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::NormalizationProfile;
+    use crate::types::{PageStructure, PageUnitType};
+
+    const TEXT: &str = "  \u{201c}Caf\u{e9}\u{201d} \u{2014} Men\u{fc}\t\tKarte\u{2026}  \r\n\r\n\r\n\
+                        Second\u{a0}\u{a0}line\u{200b} \u{2018}here\u{2019}\n \nTHIRD  ";
+
+    #[test]
+    fn test_search_profile() {
+        let text = normalize_text(TEXT, &NormalizationProfile::Search.options());
+        assert_eq!(text, "\"caf\u{e9}\" - men\u{fc} karte... second line 'here' third");
+    }
+
+    #[test]
+    fn test_display_profile() {
+        let text = normalize_text(TEXT, &NormalizationProfile::Display.options());
+        assert_eq!(
+            text,
+            "\u{201c}Caf\u{e9}\u{201d} \u{2014} Men\u{fc} Karte\u{2026}\n\nSecond line\u{200b} \u{2018}here\u{2019}\n\nTHIRD"
+        );
+    }
+
+    #[test]
+    fn test_raw_profile_borrows() {
+        assert!(matches!(
+            normalize_text(TEXT, &NormalizationProfile::Raw.options()),
+            Cow::Borrowed(TEXT)
+        ));
+        assert!(matches!(
+            normalize_text("plain text", &NormalizationProfile::Display.options()),
+            Cow::Borrowed(_)
+        ));
+    }
+
+    #[test]
+    fn test_normalize_result_keeps_page_boundaries() {
+        let content = "Page  One\n\n<!-- PAGE 2 -->\n\nPage\tTWO \n".to_string();
+        let boundaries = vec![
+            PageBoundary {
+                byte_start: 0,
+                byte_end: 9,
+                page_number: 1,
+            },
+            PageBoundary {
+                byte_start: 28,
+                byte_end: content.len(),
+                page_number: 2,
+            },
+        ];
+        let mut result = ExtractionResult {
+            content,
+            mime_type: "text/plain".into(),
+            metadata: crate::types::Metadata {
+                pages: Some(PageStructure {
+                    total_count: 2,
+                    unit_type: PageUnitType::Page,
+                    boundaries: Some(boundaries),
+                    pages: None,
+                }),
+                ..Default::default()
+            },
+            pages: None,
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            scripts: None,
+            elements: None,
+        };
+
+        normalize_result(&mut result, &NormalizationProfile::Search.options());
+
+        assert_eq!(result.content, "page one\n\n<!-- PAGE 2 -->\n\npage two");
+        let boundaries = result.metadata.pages.unwrap().boundaries.unwrap();
+        assert_eq!(
+            &result.content[boundaries[0].byte_start..boundaries[0].byte_end],
+            "page one"
+        );
+        assert_eq!(
+            &result.content[boundaries[1].byte_start..boundaries[1].byte_end],
+            "page two"
+        );
+    }
+}
@@ -126,6 +126,7 @@ mod tests {
 	            pages: None,
 	            elements: None,
 	            djot_content: None,
+	            scripts: None,
 	        };
 
         processor.process(&mut result, &config).await.unwrap();
@@ -152,6 +153,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -187,6 +189,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -217,6 +220,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -230,6 +234,7 @@ mod tests {
             chunks: None,
             images: None,
             djot_content: None,
+            scripts: None,
             pages: None,
             elements: None,
         };
@@ -0,0 +1,344 @@
+//! Regex extraction rules.
+//!
+//! Most structured extraction needs are small: the purchase order number of an
+//! invoice, the case number of a court filing, the total of a receipt. The
+//! post-processor in this module runs the rules configured in `[[rules.extract]]`
+//! over the content and stores the named fields in
+//! `metadata.additional["extracted_fields"]`, so these cases need no plugin.
+
+use crate::core::config::rules::{ExtractRule, RulesConfig};
+use crate::plugins::{Plugin, PostProcessor, ProcessingStage};
+use crate::{ExtractionConfig, ExtractionResult, KreuzbergError, Result};
+use async_trait::async_trait;
+use regex::{Captures, Regex};
+use serde_json::{Map, Value};
+use std::borrow::Cow;
+
+/// Metadata key of the extracted fields, in `metadata.additional`.
+pub const EXTRACTED_FIELDS_KEY: &str = "extracted_fields";
+
+/// A compiled extraction rule.
+#[derive(Debug)]
+struct CompiledRule {
+    name: String,
+    regex: Regex,
+    /// Output keys and the index of their capture group.
+    captures: Vec<(String, usize)>,
+    multiple: bool,
+}
+
+impl CompiledRule {
+    fn new(rule: &ExtractRule) -> Result<Self> {
+        let invalid =
+            |reason: String| KreuzbergError::validation(format!("Invalid extraction rule '{}': {}", rule.name, reason));
+
+        if let Some(flag) = rule.flags.chars().find(|flag| !"imsxU".contains(*flag)) {
+            return Err(invalid(format!("unknown flag '{}'", flag)));
+        }
+        let pattern = if rule.flags.is_empty() {
+            rule.regex.clone()
+        } else {
+            format!("(?{}){}", rule.flags, rule.regex)
+        };
+        let regex = Regex::new(&pattern).map_err(|e| invalid(e.to_string()))?;
+
+        let captures = rule
+            .captures
+            .iter()
+            .map(|(key, group)| {
+                let index = match group.parse::<usize>() {
+                    Ok(index) => (index < regex.captures_len()).then_some(index),
+                    Err(_) => regex.capture_names().position(|name| name == Some(group.as_str())),
+                };
+                index
+                    .map(|index| (key.clone(), index))
+                    .ok_or_else(|| invalid(format!("no capture group '{}'", group)))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            name: rule.name.clone(),
+            regex,
+            captures,
+            multiple: rule.multiple,
+        })
+    }
+
+    /// The field value of a match.
+    fn value(&self, caps: &Captures) -> Value {
+        let text = |index: usize| {
+            caps.get(index)
+                .map_or(Value::Null, |group| Value::String(group.as_str().to_string()))
+        };
+        if self.captures.is_empty() {
+            return text(usize::from(self.regex.captures_len() > 1));
+        }
+        Value::Object(
+            self.captures
+                .iter()
+                .map(|(key, index)| (key.clone(), text(*index)))
+                .collect(),
+        )
+    }
+}
+
+/// Compiled extraction rules.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::core::config::{ExtractRule, RulesConfig};
+/// use kreuzberg::text::rules::ExtractionRules;
+///
+/// let config = RulesConfig {
+///     extract: vec![ExtractRule {
+///         name: "po_number".to_string(),
+///         regex: r"PO[-\s#]*(\d+)".to_string(),
+///         flags: "i".to_string(),
+///         captures: Default::default(),
+///         multiple: false,
+///     }],
+/// };
+/// let rules = ExtractionRules::new(&config)?;
+/// let fields = rules.extract("Invoice for po #4711, due in 30 days");
+/// assert_eq!(fields["po_number"], "4711");
+/// # Ok::<(), kreuzberg::KreuzbergError>(())
+/// ```
+#[derive(Debug)]
+pub struct ExtractionRules {
+    rules: Vec<CompiledRule>,
+}
+
+impl ExtractionRules {
+    /// Compile the rules of `config`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if a regex is invalid, a flag is unknown or
+    /// a capture mapping names a group the regex does not have.
+    pub fn new(config: &RulesConfig) -> Result<Self> {
+        Ok(Self {
+            rules: config.extract.iter().map(CompiledRule::new).collect::<Result<_>>()?,
+        })
+    }
+
+    /// The fields extracted from `text`, by name.
+    ///
+    /// Fields without a match are left out. Rules with the same name are fallbacks: the
+    /// first of them that matches sets the field.
+    pub fn extract(&self, text: &str) -> Map<String, Value> {
+        let mut fields = Map::new();
+        for rule in &self.rules {
+            if fields.contains_key(&rule.name) {
+                continue;
+            }
+            let value = if rule.multiple {
+                let values: Vec<Value> = rule.regex.captures_iter(text).map(|caps| rule.value(&caps)).collect();
+                (!values.is_empty()).then_some(Value::Array(values))
+            } else {
+                rule.regex.captures(text).map(|caps| rule.value(&caps))
+            };
+            if let Some(value) = value {
+                fields.insert(rule.name.clone(), value);
+            }
+        }
+        fields
+    }
+}
+
+/// Post-processor that runs the configured extraction rules over the content.
+///
+/// This processor:
+/// - Runs in the Late processing stage, after the content is cleaned up
+/// - Only processes when `config.rules` is configured
+/// - Stores the fields in `metadata.additional["extracted_fields"]`
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::plugins::Plugin;
+/// use kreuzberg::text::rules::RulesExtractor;
+///
+/// let processor = RulesExtractor;
+/// assert_eq!(processor.name(), "extraction-rules");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct RulesExtractor;
+
+impl Plugin for RulesExtractor {
+    fn name(&self) -> &str {
+        "extraction-rules"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl PostProcessor for RulesExtractor {
+    async fn process(&self, result: &mut ExtractionResult, config: &ExtractionConfig) -> Result<()> {
+        let Some(rules) = &config.rules else {
+            return Ok(());
+        };
+
+        let fields = ExtractionRules::new(rules)?.extract(&result.content);
+        if !fields.is_empty() {
+            result
+                .metadata
+                .additional
+                .insert(Cow::Borrowed(EXTRACTED_FIELDS_KEY), Value::Object(fields));
+        }
+        Ok(())
+    }
+
+    fn processing_stage(&self) -> ProcessingStage {
+        ProcessingStage::Late
+    }
+
+    fn should_process(&self, _result: &ExtractionResult, config: &ExtractionConfig) -> bool {
+        config.rules.as_ref().is_some_and(|rules| !rules.extract.is_empty())
+    }
+
+    fn estimated_duration_ms(&self, result: &ExtractionResult) -> u64 {
+        (result.content.len() as u64) / 10_000 + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Metadata;
+    use serde_json::json;
+
+    fn rule(name: &str, regex: &str) -> ExtractRule {
+        ExtractRule {
+            name: name.to_string(),
+            regex: regex.to_string(),
+            flags: String::new(),
+            captures: Default::default(),
+            multiple: false,
+        }
+    }
+
+    fn extract(rules: Vec<ExtractRule>, text: &str) -> Value {
+        let rules = ExtractionRules::new(&RulesConfig { extract: rules }).unwrap();
+        Value::Object(rules.extract(text))
+    }
+
+    const INVOICE: &str = "INVOICE 2024-117\nPO Number: ac-5521\n\nABC-1  2\nXYZ-42  10\n\nTotal: EUR 1,250.00";
+
+    #[test]
+    fn test_extract_fields() {
+        let line_items = ExtractRule {
+            flags: "m".to_string(),
+            captures: [("sku", "sku"), ("quantity", "2")]
+                .into_iter()
+                .map(|(key, group)| (key.to_string(), group.to_string()))
+                .collect(),
+            multiple: true,
+            ..rule("line_items", r"^(?P<sku>[A-Z]{3}-\d+)\s+(\d+)$")
+        };
+        let total = ExtractRule {
+            captures: [("currency", "1"), ("amount", "amount")]
+                .into_iter()
+                .map(|(key, group)| (key.to_string(), group.to_string()))
+                .collect(),
+            ..rule("total", r"Total: ([A-Z]{3}) (?P<amount>[\d,.]+)")
+        };
+
+        assert_eq!(
+            extract(
+                vec![
+                    ExtractRule {
+                        flags: "i".to_string(),
+                        ..rule("po_number", r"po number:\s*(\S+)")
+                    },
+                    rule("invoice", r"INVOICE \d{4}-\d+"),
+                    line_items,
+                    total,
+                    rule("due_date", r"Due: (\S+)"),
+                ],
+                INVOICE
+            ),
+            json!({
+                "po_number": "ac-5521",
+                "invoice": "INVOICE 2024-117",
+                "line_items": [
+                    {"sku": "ABC-1", "quantity": "2"},
+                    {"sku": "XYZ-42", "quantity": "10"}
+                ],
+                "total": {"currency": "EUR", "amount": "1,250.00"}
+            })
+        );
+    }
+
+    #[test]
+    fn test_rules_with_the_same_name_are_fallbacks() {
+        let rules = vec![
+            rule("reference", r"Ref(?:erence)?: (\S+)"),
+            rule("reference", r"PO Number: (\S+)"),
+            rule("reference", r"INVOICE (\S+)"),
+        ];
+        assert_eq!(extract(rules, INVOICE), json!({"reference": "ac-5521"}));
+    }
+
+    #[test]
+    fn test_invalid_rules_are_rejected() {
+        let invalid = |rule: ExtractRule| ExtractionRules::new(&RulesConfig { extract: vec![rule] }).is_err();
+        assert!(invalid(rule("broken", "(unclosed")));
+        assert!(invalid(ExtractRule {
+            flags: "g".to_string(),
+            ..rule("flags", "a")
+        }));
+        assert!(invalid(ExtractRule {
+            captures: [("value".to_string(), "missing".to_string())].into_iter().collect(),
+            ..rule("groups", "(?P<present>a)")
+        }));
+        assert!(invalid(ExtractRule {
+            captures: [("value".to_string(), "2".to_string())].into_iter().collect(),
+            ..rule("groups", "(a)")
+        }));
+    }
+
+    #[tokio::test]
+    async fn test_rules_extractor_stores_fields() {
+        let config = ExtractionConfig {
+            rules: Some(RulesConfig {
+                extract: vec![rule("po_number", r"PO Number: (\S+)"), rule("missing", "nowhere")],
+            }),
+            ..Default::default()
+        };
+        let mut result = ExtractionResult {
+            content: INVOICE.to_string(),
+            mime_type: Cow::Borrowed("text/plain"),
+            metadata: Metadata::default(),
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            scripts: None,
+            pages: None,
+            elements: None,
+        };
+
+        assert!(RulesExtractor.should_process(&result, &config));
+        RulesExtractor.process(&mut result, &config).await.unwrap();
+
+        assert_eq!(
+            result.metadata.additional[EXTRACTED_FIELDS_KEY],
+            json!({"po_number": "ac-5521"})
+        );
+        assert!(!RulesExtractor.should_process(&result, &ExtractionConfig::default()));
+    }
+}
@@ -0,0 +1,189 @@
+//! Writing script detection.
+//!
+//! Language detection answers which language a text is in; for OCR model selection
+//! and normalization, the writing script matters more. A Serbian document may be
+//! Latin or Cyrillic, and a Japanese one mixes Han, Hiragana and Katakana.
+//!
+//! [`detect_scripts`] reports the share of each script among the letters of a text,
+//! and [`annotate_scripts`] records it in metadata when `ExtractionConfig::script_detection`
+//! is enabled.
+
+use crate::types::Metadata;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+
+/// Metadata key of the detected scripts, in `metadata.additional`.
+pub const SCRIPTS_KEY: &str = "writing_scripts";
+
+/// Scripts with a smaller share of the letters are not reported.
+const MIN_SHARE: f64 = 0.01;
+
+/// A writing script.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Script {
+    Latin,
+    Greek,
+    Cyrillic,
+    Armenian,
+    Hebrew,
+    Arabic,
+    Devanagari,
+    Bengali,
+    Tamil,
+    Thai,
+    Georgian,
+    Ethiopic,
+    Hangul,
+    Hiragana,
+    Katakana,
+    /// Chinese characters, also used in Japanese (kanji) and Korean (hanja)
+    Han,
+}
+
+impl Script {
+    /// The script of the letter `c`, or `None` for other characters and scripts.
+    pub fn of(c: char) -> Option<Self> {
+        if c.is_ascii_alphabetic() {
+            return Some(Script::Latin);
+        }
+        if !c.is_alphabetic() {
+            return None;
+        }
+        Some(match c as u32 {
+            0x00C0..=0x024F
+            | 0x1E00..=0x1EFF
+            | 0x2C60..=0x2C7F
+            | 0xA720..=0xA7FF
+            | 0xFF21..=0xFF3A
+            | 0xFF41..=0xFF5A => Script::Latin,
+            0x0370..=0x03FF | 0x1F00..=0x1FFF => Script::Greek,
+            0x0400..=0x052F | 0x1C80..=0x1C8F | 0x2DE0..=0x2DFF | 0xA640..=0xA69F => Script::Cyrillic,
+            0x0530..=0x058F => Script::Armenian,
+            0x0590..=0x05FF | 0xFB1D..=0xFB4F => Script::Hebrew,
+            0x0600..=0x06FF | 0x0750..=0x077F | 0x08A0..=0x08FF | 0xFB50..=0xFDFF | 0xFE70..=0xFEFF => Script::Arabic,
+            0x0900..=0x097F => Script::Devanagari,
+            0x0980..=0x09FF => Script::Bengali,
+            0x0B80..=0x0BFF => Script::Tamil,
+            0x0E00..=0x0E7F => Script::Thai,
+            0x10A0..=0x10FF | 0x1C90..=0x1CBF => Script::Georgian,
+            0x1200..=0x139F | 0x2D80..=0x2DDF => Script::Ethiopic,
+            0x1100..=0x11FF | 0x3130..=0x318F | 0xA960..=0xA97F | 0xAC00..=0xD7FF => Script::Hangul,
+            0x3040..=0x309F => Script::Hiragana,
+            0x30A0..=0x30FF | 0x31F0..=0x31FF | 0xFF66..=0xFF9F => Script::Katakana,
+            0x3005 | 0x3007 | 0x3400..=0x4DBF | 0x4E00..=0x9FFF | 0xF900..=0xFAFF | 0x20000..=0x3134F => Script::Han,
+            _ => return None,
+        })
+    }
+
+    /// Whether the script is Chinese, Japanese or Korean.
+    pub fn is_cjk(self) -> bool {
+        matches!(self, Script::Han | Script::Hiragana | Script::Katakana | Script::Hangul)
+    }
+
+    /// Whether the script is written right to left.
+    pub fn is_rtl(self) -> bool {
+        matches!(self, Script::Hebrew | Script::Arabic)
+    }
+}
+
+/// The share of one script among the letters of a text.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ScriptShare {
+    /// The script
+    pub script: Script,
+    /// Share of the letters of known scripts, between 0 and 1
+    pub share: f64,
+}
+
+/// Detect the writing scripts of `text`, most frequent first.
+///
+/// Scripts making up less than 1% of the letters are left out, so stray symbols
+/// and borrowed words in another script do not show up. Text without letters of a
+/// known script returns an empty list.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::text::script::{Script, detect_scripts};
+///
+/// let scripts = detect_scripts("Москва is the capital of Russia");
+/// assert_eq!(scripts[0].script, Script::Latin);
+/// assert_eq!(scripts[1].script, Script::Cyrillic);
+/// ```
+pub fn detect_scripts(text: &str) -> Vec<ScriptShare> {
+    let mut counts: HashMap<Script, usize> = HashMap::new();
+    for script in text.chars().filter_map(Script::of) {
+        *counts.entry(script).or_default() += 1;
+    }
+    let total: usize = counts.values().sum();
+    if total == 0 {
+        return Vec::new();
+    }
+
+    let mut counts: Vec<(Script, usize)> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    counts
+        .into_iter()
+        .map(|(script, count)| ScriptShare {
+            script,
+            share: (count as f64 / total as f64 * 1000.0).round() / 1000.0,
+        })
+        .filter(|share| share.share >= MIN_SHARE)
+        .collect()
+}
+
+/// Record the scripts of `text` in `metadata.additional["writing_scripts"]`.
+///
+/// Text without letters of a known script is left unannotated.
+pub fn annotate_scripts(text: &str, metadata: &mut Metadata) {
+    let scripts = detect_scripts(text);
+    if scripts.is_empty() {
+        return;
+    }
+    metadata
+        .additional
+        .insert(Cow::Borrowed(SCRIPTS_KEY), serde_json::json!(scripts));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_scripts_mixed() {
+        let scripts = detect_scripts("東京は日本の首都です。カタカナ");
+        let detected: Vec<Script> = scripts.iter().map(|s| s.script).collect();
+        assert_eq!(detected, vec![Script::Han, Script::Hiragana, Script::Katakana]);
+        assert!(scripts.iter().all(|s| s.script.is_cjk()));
+
+        let scripts = detect_scripts("مرحبا بالعالم hello");
+        assert_eq!(scripts[0].script, Script::Arabic);
+        assert!(scripts[0].script.is_rtl());
+        assert_eq!(scripts[1].script, Script::Latin);
+        assert!((scripts.iter().map(|s| s.share).sum::<f64>() - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_detect_scripts_ignores_noise() {
+        assert!(detect_scripts("123 + 456 = 579 !").is_empty());
+
+        let text = format!("{} α", "a".repeat(200));
+        assert_eq!(detect_scripts(&text).len(), 1);
+    }
+
+    #[test]
+    fn test_annotate_scripts() {
+        let mut metadata = Metadata::default();
+        annotate_scripts("Привет, мир", &mut metadata);
+        assert_eq!(
+            metadata.additional[SCRIPTS_KEY],
+            serde_json::json!([{"script": "cyrillic", "share": 1.0}])
+        );
+
+        let mut metadata = Metadata::default();
+        annotate_scripts("42", &mut metadata);
+        assert!(metadata.additional.is_empty());
+    }
+}
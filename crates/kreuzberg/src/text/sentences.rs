@@ -0,0 +1,289 @@
+//! Rule-based sentence segmentation.
+//!
+//! Splitting at every period breaks text apart at abbreviations ("e.g.", "Dr.",
+//! "Art. 5"), initials and ellipses, which is common in legal and scientific writing.
+//! The built-in segmenter only ends a sentence at punctuation followed by whitespace
+//! and a word that does not start in lower case, and not after a known abbreviation
+//! or an initial. Full-width CJK punctuation always ends a sentence, and a blank line
+//! always ends a paragraph.
+
+use crate::Result;
+use crate::text::truncation::SENTENCE_ENDS;
+use once_cell::sync::Lazy;
+use std::collections::HashSet;
+use std::ops::Range;
+
+/// Abbreviations that do not end a sentence, in lower case and without the final
+/// period: titles, citations and common abbreviations, per language.
+const ABBREVIATIONS: &[&[&str]] = &[
+    // English
+    &[
+        "mr", "mrs", "ms", "dr", "prof", "sr", "jr", "st", "mt", "rev", "hon", "gen", "col", "lt", "capt", "sgt",
+        "gov", "sen", "rep", "e.g", "i.e", "cf", "al", "vs", "v", "approx", "ca", "no", "nos", "vol", "vols", "p",
+        "pp", "fig", "figs", "ch", "sec", "secs", "art", "arts", "para", "paras", "cl", "subsec", "reg", "supp", "cir",
+        "ct", "dept", "est", "jan", "feb", "mar", "apr", "jun", "jul", "aug", "sep", "sept", "oct", "nov", "dec",
+    ],
+    // German
+    &[
+        "z.b", "d.h", "u.a", "v.a", "z.t", "u.u", "bzw", "ggf", "vgl", "evtl", "inkl", "nr", "abs", "hr", "fr", "str",
+        "dipl", "ing", "mio", "mrd", "gem", "sog", "bspw",
+    ],
+    // French
+    &["mme", "mlle", "p.ex", "env", "av", "bd"],
+    // Spanish, Italian and Portuguese
+    &[
+        "sra", "srta", "dra", "ud", "uds", "pág", "p.ej", "núm", "aprox", "sig", "dott", "avv", "pag",
+    ],
+    // Dutch
+    &["dhr", "mevr", "bijv", "o.a", "m.b.t"],
+];
+
+static ABBREVIATION_SET: Lazy<HashSet<&'static str>> = Lazy::new(|| {
+    ABBREVIATIONS
+        .iter()
+        .flat_map(|language| language.iter().copied())
+        .collect()
+});
+
+/// Closing quotes and brackets that belong to the sentence they follow.
+const CLOSERS: [char; 11] = ['"', '\'', ')', ']', '”', '’', '»', '」', '』', '）', '›'];
+
+/// Opening quotes and brackets stripped from the word before a period.
+const OPENERS: [char; 9] = ['"', '\'', '(', '[', '“', '‘', '«', '¿', '¡'];
+
+/// The built-in sentence segmenter.
+///
+/// Registered as `rule-based` with priority 0, so registered segmenters with the
+/// default priority take precedence. To add abbreviations, register a segmenter built
+/// with [`with_abbreviations`](Self::with_abbreviations); it replaces the built-in one
+/// under the same name.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::text::sentences::RuleBasedSegmenter;
+///
+/// let text = "Dr. Smith paid $3.50, e.g. for coffee. See Art. 5 of the act.";
+/// let segmenter = RuleBasedSegmenter::new();
+/// let sentences: Vec<&str> = segmenter.split(text).iter().map(|range| &text[range.clone()]).collect();
+/// assert_eq!(sentences, vec!["Dr. Smith paid $3.50, e.g. for coffee.", "See Art. 5 of the act."]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RuleBasedSegmenter {
+    abbreviations: HashSet<String>,
+}
+
+impl RuleBasedSegmenter {
+    /// Create a segmenter with the built-in abbreviations.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add abbreviations that do not end a sentence, such as `"Rz."` or `"BGBl"`.
+    /// Case and a final period are ignored.
+    pub fn with_abbreviations<I, S>(mut self, abbreviations: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.abbreviations.extend(
+            abbreviations
+                .into_iter()
+                .map(|abbreviation| abbreviation.as_ref().trim_end_matches('.').to_lowercase()),
+        );
+        self
+    }
+
+    /// Byte ranges of the sentences of `text`, without surrounding whitespace.
+    pub fn split(&self, text: &str) -> Vec<Range<usize>> {
+        let mut sentences = Vec::new();
+        let mut start = 0;
+        let mut chars = text.char_indices().peekable();
+
+        while let Some((offset, ch)) = chars.next() {
+            let end = if ch == '\n' {
+                let rest = text[offset + 1..].trim_start_matches([' ', '\t', '\r']);
+                rest.starts_with('\n').then_some(offset)
+            } else if SENTENCE_ENDS.contains(&ch) {
+                let mut end = offset + ch.len_utf8();
+                while let Some(&(next_offset, next)) = chars.peek() {
+                    if !(SENTENCE_ENDS.contains(&next) || CLOSERS.contains(&next)) {
+                        break;
+                    }
+                    end = next_offset + next.len_utf8();
+                    chars.next();
+                }
+                self.ends_sentence(text, start, offset, end).then_some(end)
+            } else {
+                None
+            };
+
+            if let Some(end) = end {
+                push_trimmed(text, start..end, &mut sentences);
+                start = end;
+            }
+        }
+        push_trimmed(text, start..text.len(), &mut sentences);
+        sentences
+    }
+
+    /// Whether the punctuation at `offset..end` ends the sentence starting at `start`.
+    fn ends_sentence(&self, text: &str, start: usize, offset: usize, end: usize) -> bool {
+        let punctuation = &text[offset..end];
+        if punctuation
+            .chars()
+            .any(|ch| !ch.is_ascii() && SENTENCE_ENDS.contains(&ch))
+        {
+            return true;
+        }
+
+        let rest = &text[end..];
+        match rest.chars().next() {
+            None => return true,
+            Some(next) if !next.is_whitespace() => return false,
+            Some(_) => {}
+        }
+        let Some(next) = rest.trim_start().chars().next() else {
+            return true;
+        };
+        if next.is_lowercase() {
+            return false;
+        }
+        if !punctuation.starts_with('.') || punctuation.starts_with("..") {
+            return true;
+        }
+
+        let word = text[start..offset]
+            .rsplit(char::is_whitespace)
+            .next()
+            .unwrap_or_default()
+            .trim_start_matches(OPENERS);
+        let mut letters = word.chars();
+        if let (Some(first), None) = (letters.next(), letters.next())
+            && first.is_uppercase()
+        {
+            return false;
+        }
+
+        let word = word.to_lowercase();
+        !(ABBREVIATION_SET.contains(word.as_str()) || self.abbreviations.contains(&word) || is_dotted_initials(&word))
+    }
+}
+
+/// Whether `word` is a dotted acronym such as "u.s" or "u.k".
+fn is_dotted_initials(word: &str) -> bool {
+    word.contains('.') && word.split('.').all(|part| part.chars().count() == 1)
+}
+
+fn push_trimmed(text: &str, range: Range<usize>, sentences: &mut Vec<Range<usize>>) {
+    let sentence = &text[range.clone()];
+    let trimmed = sentence.trim_start();
+    let start = range.start + sentence.len() - trimmed.len();
+    let end = start + trimmed.trim_end().len();
+    if start < end {
+        sentences.push(start..end);
+    }
+}
+
+impl crate::plugins::Plugin for RuleBasedSegmenter {
+    fn name(&self) -> &str {
+        "rule-based"
+    }
+
+    fn version(&self) -> String {
+        env!("CARGO_PKG_VERSION").to_string()
+    }
+
+    fn initialize(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn shutdown(&self) -> Result<()> {
+        Ok(())
+    }
+
+    fn description(&self) -> &str {
+        "Abbreviation-aware rule-based sentence segmentation"
+    }
+}
+
+impl crate::plugins::SentenceSegmenter for RuleBasedSegmenter {
+    fn segment(&self, text: &str) -> Result<Vec<Range<usize>>> {
+        Ok(self.split(text))
+    }
+
+    fn priority(&self) -> i32 {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sentences(segmenter: &RuleBasedSegmenter, text: &str) -> Vec<String> {
+        segmenter
+            .split(text)
+            .into_iter()
+            .map(|range| text[range].to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_split_plain_sentences() {
+        let segmenter = RuleBasedSegmenter::new();
+        assert_eq!(sentences(&segmenter, "One. Two! Three"), vec!["One.", "Two!", "Three"]);
+        assert_eq!(
+            sentences(&segmenter, "Really?! \"Yes.\" Then (fine.) Done"),
+            vec!["Really?!", "\"Yes.\"", "Then (fine.)", "Done"]
+        );
+        assert_eq!(
+            sentences(&segmenter, "v1.2 is out.\n\nNext"),
+            vec!["v1.2 is out.", "Next"]
+        );
+        assert_eq!(
+            sentences(&segmenter, "A heading\n\nBody text"),
+            vec!["A heading", "Body text"]
+        );
+        assert_eq!(sentences(&segmenter, "一。二。"), vec!["一。", "二。"]);
+        assert!(sentences(&segmenter, "  \n ").is_empty());
+    }
+
+    #[test]
+    fn test_split_keeps_abbreviations_and_numbers() {
+        let segmenter = RuleBasedSegmenter::new();
+        assert_eq!(
+            sentences(
+                &segmenter,
+                "Pursuant to Art. 5 Abs. 2 and Sec. 3, e.g. as argued by Dr. Smith, the fee is 3.5 percent. J. R. R. Tolkien agreed."
+            ),
+            vec![
+                "Pursuant to Art. 5 Abs. 2 and Sec. 3, e.g. as argued by Dr. Smith, the fee is 3.5 percent.",
+                "J. R. R. Tolkien agreed."
+            ]
+        );
+        assert_eq!(
+            sentences(
+                &segmenter,
+                "Er kam z.B. gestern. Die U.S. Army wartete... Dann ging er."
+            ),
+            vec!["Er kam z.B. gestern.", "Die U.S. Army wartete...", "Dann ging er."]
+        );
+        assert_eq!(
+            sentences(
+                &segmenter,
+                "Apples, pears etc. are fruit. We sold apples etc. Then pears."
+            ),
+            vec!["Apples, pears etc. are fruit.", "We sold apples etc.", "Then pears."]
+        );
+    }
+
+    #[test]
+    fn test_split_with_custom_abbreviations() {
+        let text = "Siehe Rz. 12 des Urteils.";
+        assert_eq!(sentences(&RuleBasedSegmenter::new(), text).len(), 2);
+
+        let segmenter = RuleBasedSegmenter::new().with_abbreviations(["Rz."]);
+        assert_eq!(sentences(&segmenter, text), vec![text]);
+    }
+}
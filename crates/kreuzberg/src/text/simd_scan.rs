@@ -0,0 +1,277 @@
+//! Vectorized byte scans for text cleanup.
+//!
+//! Whitespace collapsing and control character stripping spend most of their time
+//! on bytes they copy unchanged. The scans here find the next byte that needs
+//! attention 16 or 32 bytes at a time, so cleanup only steps through the rest one
+//! character at a time.
+//!
+//! On x86_64, AVX2 is used when the CPU supports it (detected at runtime) and SSE2
+//! otherwise. Other targets use the scalar implementation, which the vector
+//! implementations also use for the tail of the input.
+
+/// Length of the prefix of `bytes` that whitespace cleanup copies unchanged.
+///
+/// The prefix consists of printable ASCII characters and single spaces followed by
+/// a printable ASCII character, so it never ends with a space. It stops at control
+/// characters, line breaks, tabs, runs of spaces and non-ASCII bytes.
+#[inline]
+pub(crate) fn plain_ascii_prefix(bytes: &[u8]) -> usize {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 support was just detected.
+            #[allow(unsafe_code)]
+            return unsafe { x86::plain_ascii_prefix_avx2(bytes) };
+        }
+        x86::plain_ascii_prefix_sse2(bytes)
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        scalar::plain_ascii_prefix(bytes)
+    }
+}
+
+/// Position of the first ASCII control character other than tab, line feed and
+/// carriage return, including DEL.
+#[cfg(feature = "ocr")]
+#[inline]
+pub(crate) fn find_control(bytes: &[u8]) -> Option<usize> {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if std::arch::is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 support was just detected.
+            #[allow(unsafe_code)]
+            return unsafe { x86::find_control_avx2(bytes) };
+        }
+        x86::find_control_sse2(bytes)
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    {
+        scalar::find_control(bytes)
+    }
+}
+
+mod scalar {
+    #[inline]
+    pub(super) fn is_printable(byte: u8) -> bool {
+        (0x21..=0x7e).contains(&byte)
+    }
+
+    #[cfg(feature = "ocr")]
+    #[inline]
+    pub(super) fn is_control(byte: u8) -> bool {
+        (byte < 0x20 && !matches!(byte, b'\t' | b'\n' | b'\r')) || byte == 0x7f
+    }
+
+    pub(super) fn plain_ascii_prefix(bytes: &[u8]) -> usize {
+        for (index, &byte) in bytes.iter().enumerate() {
+            let plain = is_printable(byte) || (byte == b' ' && bytes.get(index + 1).copied().is_some_and(is_printable));
+            if !plain {
+                return index;
+            }
+        }
+        bytes.len()
+    }
+
+    #[cfg(feature = "ocr")]
+    pub(super) fn find_control(bytes: &[u8]) -> Option<usize> {
+        bytes.iter().position(|&byte| is_control(byte))
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[allow(unsafe_code)]
+mod x86 {
+    use super::scalar;
+    use std::arch::x86_64::*;
+
+    // The comparisons below are unsigned: `min(x, n) == x` is `x <= n` and
+    // `max(x, n) == x` is `x >= n`.
+
+    pub(super) fn plain_ascii_prefix_sse2(bytes: &[u8]) -> usize {
+        let mut offset = 0;
+        // Each step also reads the byte after the block, to tell single spaces from runs.
+        while offset + 17 <= bytes.len() {
+            // SAFETY: SSE2 is part of the x86_64 baseline, and both unaligned loads read
+            // 16 bytes within `bytes`, as `offset + 17 <= bytes.len()`.
+            let mask = unsafe {
+                let current = _mm_loadu_si128(bytes.as_ptr().add(offset).cast());
+                let next = _mm_loadu_si128(bytes.as_ptr().add(offset + 1).cast());
+                let plain = _mm_or_si128(
+                    printable_sse2(current),
+                    _mm_and_si128(_mm_cmpeq_epi8(current, _mm_set1_epi8(b' ' as i8)), printable_sse2(next)),
+                );
+                _mm_movemask_epi8(plain) as u32
+            };
+            if mask != 0xffff {
+                return offset + (!mask).trailing_zeros() as usize;
+            }
+            offset += 16;
+        }
+        offset + scalar::plain_ascii_prefix(&bytes[offset..])
+    }
+
+    #[cfg(feature = "ocr")]
+    pub(super) fn find_control_sse2(bytes: &[u8]) -> Option<usize> {
+        let mut offset = 0;
+        while offset + 16 <= bytes.len() {
+            // SAFETY: SSE2 is part of the x86_64 baseline, and the unaligned load reads
+            // 16 bytes within `bytes`.
+            let mask = unsafe {
+                let block = _mm_loadu_si128(bytes.as_ptr().add(offset).cast());
+                let below_space = _mm_cmpeq_epi8(_mm_min_epu8(block, _mm_set1_epi8(0x1f)), block);
+                let allowed = _mm_or_si128(
+                    _mm_or_si128(
+                        _mm_cmpeq_epi8(block, _mm_set1_epi8(b'\t' as i8)),
+                        _mm_cmpeq_epi8(block, _mm_set1_epi8(b'\n' as i8)),
+                    ),
+                    _mm_cmpeq_epi8(block, _mm_set1_epi8(b'\r' as i8)),
+                );
+                let control = _mm_or_si128(
+                    _mm_andnot_si128(allowed, below_space),
+                    _mm_cmpeq_epi8(block, _mm_set1_epi8(0x7f)),
+                );
+                _mm_movemask_epi8(control) as u32
+            };
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize);
+            }
+            offset += 16;
+        }
+        scalar::find_control(&bytes[offset..]).map(|index| offset + index)
+    }
+
+    #[inline]
+    #[target_feature(enable = "sse2")]
+    fn printable_sse2(block: __m128i) -> __m128i {
+        _mm_and_si128(
+            _mm_cmpeq_epi8(_mm_max_epu8(block, _mm_set1_epi8(0x21)), block),
+            _mm_cmpeq_epi8(_mm_min_epu8(block, _mm_set1_epi8(0x7e)), block),
+        )
+    }
+
+    /// # Safety
+    ///
+    /// The CPU must support AVX2.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn plain_ascii_prefix_avx2(bytes: &[u8]) -> usize {
+        let mut offset = 0;
+        while offset + 33 <= bytes.len() {
+            // SAFETY: both unaligned loads read 32 bytes within `bytes`, as
+            // `offset + 33 <= bytes.len()`.
+            let mask = unsafe {
+                let current = _mm256_loadu_si256(bytes.as_ptr().add(offset).cast());
+                let next = _mm256_loadu_si256(bytes.as_ptr().add(offset + 1).cast());
+                let plain = _mm256_or_si256(
+                    printable_avx2(current),
+                    _mm256_and_si256(
+                        _mm256_cmpeq_epi8(current, _mm256_set1_epi8(b' ' as i8)),
+                        printable_avx2(next),
+                    ),
+                );
+                _mm256_movemask_epi8(plain) as u32
+            };
+            if mask != u32::MAX {
+                return offset + (!mask).trailing_zeros() as usize;
+            }
+            offset += 32;
+        }
+        offset + plain_ascii_prefix_sse2(&bytes[offset..])
+    }
+
+    /// # Safety
+    ///
+    /// The CPU must support AVX2.
+    #[cfg(feature = "ocr")]
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn find_control_avx2(bytes: &[u8]) -> Option<usize> {
+        let mut offset = 0;
+        while offset + 32 <= bytes.len() {
+            // SAFETY: the unaligned load reads 32 bytes within `bytes`.
+            let mask = unsafe {
+                let block = _mm256_loadu_si256(bytes.as_ptr().add(offset).cast());
+                let below_space = _mm256_cmpeq_epi8(_mm256_min_epu8(block, _mm256_set1_epi8(0x1f)), block);
+                let allowed = _mm256_or_si256(
+                    _mm256_or_si256(
+                        _mm256_cmpeq_epi8(block, _mm256_set1_epi8(b'\t' as i8)),
+                        _mm256_cmpeq_epi8(block, _mm256_set1_epi8(b'\n' as i8)),
+                    ),
+                    _mm256_cmpeq_epi8(block, _mm256_set1_epi8(b'\r' as i8)),
+                );
+                let control = _mm256_or_si256(
+                    _mm256_andnot_si256(allowed, below_space),
+                    _mm256_cmpeq_epi8(block, _mm256_set1_epi8(0x7f)),
+                );
+                _mm256_movemask_epi8(control) as u32
+            };
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize);
+            }
+            offset += 32;
+        }
+        find_control_sse2(&bytes[offset..]).map(|index| offset + index)
+    }
+
+    #[inline]
+    #[target_feature(enable = "avx2")]
+    fn printable_avx2(block: __m256i) -> __m256i {
+        _mm256_and_si256(
+            _mm256_cmpeq_epi8(_mm256_max_epu8(block, _mm256_set1_epi8(0x21)), block),
+            _mm256_cmpeq_epi8(_mm256_min_epu8(block, _mm256_set1_epi8(0x7e)), block),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLES: &[&str] = &[
+        "",
+        "a",
+        " a",
+        "a ",
+        "The quick brown fox jumps over the lazy dog, again and again and again.",
+        "The quick brown fox jumps over the lazy dog,  again and again and again.",
+        "The quick brown fox jumps over the lazy dog, again and again and\tagain.",
+        "The quick brown fox jumps over the lazy dog, again and again and again \n",
+        "The quick brown fox jumps over the lazy dog, again and again and Gr\u{fc}\u{df}e.",
+        "The quick brown fox jumps over the lazy dog\u{7}, again and again and again.",
+        "The quick brown fox jumps over the lazy dog, again and again and again\u{7f}",
+        "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcde ",
+        "0123456789abcdef0123456789abcdef \u{0}",
+    ];
+
+    #[test]
+    fn test_vector_scans_match_scalar() {
+        for sample in SAMPLES {
+            // Every suffix, so each position of the sample falls on every lane.
+            for start in 0..sample.len() {
+                let bytes = &sample.as_bytes()[start..];
+                assert_eq!(
+                    plain_ascii_prefix(bytes),
+                    scalar::plain_ascii_prefix(bytes),
+                    "{sample:?}[{start}..]"
+                );
+                #[cfg(feature = "ocr")]
+                assert_eq!(
+                    find_control(bytes),
+                    scalar::find_control(bytes),
+                    "{sample:?}[{start}..]"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_plain_ascii_prefix_stops_at_whitespace_runs() {
+        assert_eq!(plain_ascii_prefix(b"one two  three"), 7);
+        assert_eq!(plain_ascii_prefix(b"one two\nthree"), 7);
+        assert_eq!(plain_ascii_prefix(b"one two "), 7);
+        assert_eq!(plain_ascii_prefix(" caf\u{e9}".as_bytes()), 4);
+        #[cfg(feature = "ocr")]
+        assert_eq!(find_control(b"tab\tnew\nline\r\x0cfeed"), Some(13));
+    }
+}
@@ -0,0 +1,335 @@
+//! Spellcheck-based OCR quality estimation.
+//!
+//! OCR errors mostly produce words that do not exist: `rnodern` for `modern`,
+//! `1nvoice` for `invoice`. The share of words missing from a dictionary is therefore
+//! a usable estimate of the character error rate, without ground truth and without
+//! relying on the confidence an OCR backend reports.
+//!
+//! [`spellcheck`] scores the content and every page when `ExtractionConfig::spellcheck`
+//! is set and records the report in `metadata.additional["spellcheck"]`.
+
+use crate::core::config::spellcheck::SpellcheckConfig;
+use crate::types::ExtractionResult;
+use crate::{KreuzbergError, Result};
+use ahash::AHashSet;
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Metadata key of the spellcheck report, in `metadata.additional`.
+pub const SPELLCHECK_KEY: &str = "spellcheck";
+
+/// Pages with fewer checked words are not reported as low quality.
+const MIN_PAGE_WORDS: usize = 10;
+
+/// Dictionaries loaded so far, by their files and additional words.
+type DictionaryKey = (Vec<PathBuf>, Vec<String>);
+static DICTIONARIES: Lazy<Mutex<HashMap<DictionaryKey, Arc<Dictionary>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// A set of known words, matched case-insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    words: AHashSet<String>,
+}
+
+impl Dictionary {
+    /// A dictionary of `words`.
+    pub fn from_words<I, S>(words: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut dictionary = Self::default();
+        dictionary.extend(words);
+        dictionary
+    }
+
+    /// Load a word list with one word per line, or a Hunspell `.dic` file.
+    ///
+    /// Empty lines and lines starting with `#` are skipped. Affix flags after a `/` and
+    /// morphological fields after whitespace are ignored.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if the file cannot be read.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = std::fs::read_to_string(path).map_err(|e| KreuzbergError::Validation {
+            message: format!("Cannot read spellcheck dictionary '{}': {}", path.display(), e),
+            source: Some(Box::new(e)),
+        })?;
+        Ok(Self::from_words(
+            text.lines()
+                .filter(|line| !line.starts_with('#'))
+                .filter_map(|line| line.split(['/', '\t', ' ']).next()),
+        ))
+    }
+
+    /// Add `words` to the dictionary.
+    pub fn extend<I, S>(&mut self, words: I)
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        self.words.extend(
+            words
+                .into_iter()
+                .map(|word| word.as_ref().trim().to_lowercase())
+                .filter(|word| !word.is_empty()),
+        );
+    }
+
+    /// Whether `word` is known, ignoring case and a possessive `'s`.
+    pub fn contains(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+        self.words.contains(&word)
+            || word
+                .strip_suffix("'s")
+                .or_else(|| word.strip_suffix("’s"))
+                .is_some_and(|stem| self.words.contains(stem))
+    }
+
+    /// Number of known words.
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    /// Whether the dictionary has no words.
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+}
+
+/// Spellcheck score of a text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SpellcheckScore {
+    /// Number of words looked up in the dictionary
+    pub checked_words: usize,
+    /// Number of words not in the dictionary
+    pub unknown_words: usize,
+    /// Share of unknown words, between 0 and 1 (0 without checked words)
+    pub error_rate: f64,
+}
+
+/// Spellcheck score of one page.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PageSpellcheck {
+    /// Page number (1-indexed)
+    pub page: usize,
+    /// Score of the page
+    #[serde(flatten)]
+    pub score: SpellcheckScore,
+}
+
+/// Spellcheck report of a document, stored in `metadata.additional["spellcheck"]`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SpellcheckReport {
+    /// Score of the whole content
+    #[serde(flatten)]
+    pub document: SpellcheckScore,
+    /// Scores of the pages, when page content or page boundaries are available
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pages: Vec<PageSpellcheck>,
+    /// Pages whose error rate is above `max_error_rate`
+    #[serde(default)]
+    pub low_quality_pages: Vec<usize>,
+}
+
+/// Score `text` against `dictionary`.
+///
+/// Words are runs of letters, digits and apostrophes. Words without letters are
+/// skipped, as are words shorter than `min_word_length` letters. Words mixing letters
+/// and digits, like `1nvoice`, are checked and count as unknown unless listed.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::text::spellcheck::{Dictionary, score};
+///
+/// let dictionary = Dictionary::from_words(["the", "modern", "invoice"]);
+/// let result = score("The rnodern 1nvoice", &dictionary, 3);
+/// assert_eq!(result.checked_words, 3);
+/// assert_eq!(result.unknown_words, 2);
+/// ```
+pub fn score(text: &str, dictionary: &Dictionary, min_word_length: usize) -> SpellcheckScore {
+    let mut checked_words = 0;
+    let mut unknown_words = 0;
+    for word in text
+        .split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '’'))
+        .map(|word| word.trim_matches(['\'', '’']))
+    {
+        let letters = word.chars().filter(|c| c.is_alphabetic()).count();
+        if letters == 0 || letters < min_word_length {
+            continue;
+        }
+        checked_words += 1;
+        if !dictionary.contains(word) {
+            unknown_words += 1;
+        }
+    }
+
+    let error_rate = if checked_words == 0 {
+        0.0
+    } else {
+        (unknown_words as f64 / checked_words as f64 * 1000.0).round() / 1000.0
+    };
+    SpellcheckScore {
+        checked_words,
+        unknown_words,
+        error_rate,
+    }
+}
+
+/// Spellcheck the content and the pages of `result`.
+///
+/// Pages are taken from `result.pages`, or else cut from the content at the page
+/// boundaries. Dictionaries are loaded once per process and configuration.
+///
+/// # Errors
+///
+/// Returns `KreuzbergError::Validation` if a dictionary cannot be read.
+pub fn spellcheck(result: &ExtractionResult, config: &SpellcheckConfig) -> Result<SpellcheckReport> {
+    let dictionary = load_dictionary(config)?;
+    let score_of = |text: &str| score(text, &dictionary, config.min_word_length);
+
+    let pages: Vec<PageSpellcheck> = match (&result.pages, &result.metadata.pages) {
+        (Some(pages), _) => pages
+            .iter()
+            .map(|page| PageSpellcheck {
+                page: page.page_number,
+                score: score_of(&page.content),
+            })
+            .collect(),
+        (None, Some(structure)) => structure
+            .boundaries
+            .iter()
+            .flatten()
+            .filter_map(|boundary| {
+                let text = result.content.get(boundary.byte_start..boundary.byte_end)?;
+                Some(PageSpellcheck {
+                    page: boundary.page_number,
+                    score: score_of(text),
+                })
+            })
+            .collect(),
+        (None, None) => Vec::new(),
+    };
+    let low_quality_pages = pages
+        .iter()
+        .filter(|page| page.score.checked_words >= MIN_PAGE_WORDS && page.score.error_rate > config.max_error_rate)
+        .map(|page| page.page)
+        .collect();
+
+    Ok(SpellcheckReport {
+        document: score_of(&result.content),
+        pages,
+        low_quality_pages,
+    })
+}
+
+fn load_dictionary(config: &SpellcheckConfig) -> Result<Arc<Dictionary>> {
+    let key = (config.dictionaries.clone(), config.words.clone());
+    if let Some(dictionary) = DICTIONARIES.lock().get(&key) {
+        return Ok(Arc::clone(dictionary));
+    }
+
+    let mut dictionary = Dictionary::from_words(&config.words);
+    for path in &config.dictionaries {
+        dictionary.words.extend(Dictionary::load(path)?.words);
+    }
+    let dictionary = Arc::new(dictionary);
+    DICTIONARIES.lock().insert(key, Arc::clone(&dictionary));
+    Ok(dictionary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_score_counts_unknown_words() {
+        let dictionary = Dictionary::from_words(["total", "amount", "due", "invoice", "customer"]);
+        let result = score("Invoice 2024-17: TOTAL amount dve, customer's 1nvoice.", &dictionary, 3);
+        assert_eq!(result.checked_words, 6);
+        assert_eq!(result.unknown_words, 2);
+        assert_eq!(result.error_rate, 0.333);
+
+        assert_eq!(score("42 % of it", &dictionary, 3), SpellcheckScore::default());
+    }
+
+    #[test]
+    fn test_dictionary_load_hunspell() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "4\n# comment\nhouse/SM\nMouse/S\tpo:noun\nsee").unwrap();
+
+        let dictionary = Dictionary::load(file.path()).unwrap();
+        assert_eq!(dictionary.len(), 4);
+        assert!(dictionary.contains("House") && dictionary.contains("mouse") && dictionary.contains("SEE"));
+        assert!(!dictionary.contains("houses"));
+        assert!(Dictionary::load(Path::new("/nonexistent/en.dic")).is_err());
+    }
+
+    #[test]
+    fn test_spellcheck_flags_low_quality_pages() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "the\nquick\nbrown\nfox\njumps\nover\nlazy\ndog").unwrap();
+        let config = SpellcheckConfig {
+            dictionaries: vec![file.path().to_path_buf()],
+            words: vec!["Kreuzberg".to_string()],
+            min_word_length: 3,
+            max_error_rate: 0.2,
+        };
+
+        let good = "The quick brown fox jumps over the lazy dog in Kreuzberg. ";
+        let bad = "Tlie qnick hrown fox jurnps ovcr tlie lazv dog in Krenzberg. ";
+        let content = format!("{good}{bad}");
+        let metadata = crate::types::Metadata {
+            pages: Some(crate::types::PageStructure {
+                total_count: 2,
+                unit_type: crate::types::PageUnitType::Page,
+                boundaries: Some(vec![
+                    crate::types::PageBoundary {
+                        byte_start: 0,
+                        byte_end: good.len(),
+                        page_number: 1,
+                    },
+                    crate::types::PageBoundary {
+                        byte_start: good.len(),
+                        byte_end: content.len(),
+                        page_number: 2,
+                    },
+                ]),
+                pages: None,
+            }),
+            ..Default::default()
+        };
+        let result = ExtractionResult {
+            content,
+            mime_type: std::borrow::Cow::Borrowed("application/pdf"),
+            metadata,
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            scripts: None,
+            pages: None,
+            elements: None,
+        };
+
+        let report = spellcheck(&result, &config).unwrap();
+        assert_eq!(report.pages[0].score.unknown_words, 0);
+        assert_eq!(report.pages[1].score.checked_words, 10);
+        assert_eq!(report.pages[1].score.unknown_words, 8);
+        assert_eq!(report.low_quality_pages, vec![2]);
+        assert_eq!(report.document.error_rate, 0.4);
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["low_quality_pages"], serde_json::json!([2]));
+        assert_eq!(json["pages"][1]["page"], 2);
+        assert_eq!(json["pages"][1]["error_rate"], 0.8);
+    }
+}
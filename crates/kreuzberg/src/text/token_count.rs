@@ -0,0 +1,257 @@
+//! Token counting and ingestion cost estimation.
+//!
+//! Counts are computed during extraction when `ExtractionConfig::token_count` is set
+//! and stored as `token_usage` in `metadata.additional`. [`summarize_token_usage`]
+//! aggregates them over a batch for capacity planning.
+
+use crate::core::config::{TokenCountConfig, TokenPricing, TokenizerKind};
+use crate::types::ExtractionResult;
+use serde::{Deserialize, Serialize};
+
+/// Key of the token usage entry in `metadata.additional`.
+pub const TOKEN_USAGE_KEY: &str = "token_usage";
+
+/// Characters per token assumed by [`TokenizerKind::Heuristic`] for alphabetic scripts.
+const CHARS_PER_TOKEN: usize = 4;
+
+/// Count the tokens of `text` with a built-in tokenizer.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::core::config::TokenizerKind;
+/// use kreuzberg::text::token_count::count_tokens;
+///
+/// assert_eq!(count_tokens("one two  three", TokenizerKind::Whitespace), 3);
+/// assert_eq!(count_tokens("東京都", TokenizerKind::Heuristic), 3);
+/// ```
+pub fn count_tokens(text: &str, tokenizer: TokenizerKind) -> usize {
+    match tokenizer {
+        TokenizerKind::Heuristic => heuristic_tokens(text),
+        TokenizerKind::Whitespace => text.split_whitespace().count(),
+        TokenizerKind::Characters => text.chars().count(),
+    }
+}
+
+/// Whitespace runs count as a single character; CJK characters are a token each.
+fn heuristic_tokens(text: &str) -> usize {
+    let mut alphabetic: usize = 0;
+    let mut cjk = 0;
+    let mut previous_whitespace = true;
+
+    for c in text.chars() {
+        if c.is_whitespace() {
+            if !previous_whitespace {
+                alphabetic += 1;
+            }
+            previous_whitespace = true;
+            continue;
+        }
+        previous_whitespace = false;
+        if is_cjk(c) {
+            cjk += 1;
+        } else {
+            alphabetic += 1;
+        }
+    }
+
+    alphabetic.div_ceil(CHARS_PER_TOKEN) + cjk
+}
+
+fn is_cjk(c: char) -> bool {
+    matches!(c,
+        '\u{3040}'..='\u{30FF}'
+        | '\u{3400}'..='\u{4DBF}'
+        | '\u{4E00}'..='\u{9FFF}'
+        | '\u{AC00}'..='\u{D7AF}'
+        | '\u{F900}'..='\u{FAFF}'
+        | '\u{20000}'..='\u{2FA1F}')
+}
+
+/// Estimated cost of ingesting a document or batch.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CostEstimate {
+    /// Currency of the amounts
+    pub currency: String,
+    /// Estimated embedding cost, if an embedding price is configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<f64>,
+    /// Estimated LLM input cost, if an LLM input price is configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub llm_input: Option<f64>,
+}
+
+impl CostEstimate {
+    fn from_pricing(pricing: &TokenPricing, document_tokens: usize, embedded_tokens: usize) -> Self {
+        let cost = |price: Option<f64>, tokens: usize| price.map(|p| p * tokens as f64 / 1_000_000.0);
+        Self {
+            currency: pricing.currency.clone(),
+            embedding: cost(pricing.embedding_per_million, embedded_tokens),
+            llm_input: cost(pricing.llm_input_per_million, document_tokens),
+        }
+    }
+}
+
+/// Token counts of a single extraction result.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    /// Tokenizer used for counting
+    pub tokenizer: String,
+    /// Tokens in the extracted content
+    pub document_tokens: usize,
+    /// Number of chunks, if chunking ran
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_count: Option<usize>,
+    /// Sum of the chunk token counts (includes overlap), if chunking ran
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub chunk_tokens: Option<usize>,
+    /// Estimated cost, if pricing is configured
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_cost: Option<CostEstimate>,
+}
+
+impl TokenUsage {
+    /// Measure a result, using existing chunk token counts where present.
+    pub fn measure(result: &ExtractionResult, config: &TokenCountConfig) -> Self {
+        let document_tokens = config.count_tokens(&result.content);
+        let chunk_tokens = result.chunks.as_ref().map(|chunks| {
+            chunks
+                .iter()
+                .map(|chunk| {
+                    chunk
+                        .metadata
+                        .token_count
+                        .unwrap_or_else(|| config.count_tokens(&chunk.content))
+                })
+                .sum()
+        });
+
+        Self {
+            tokenizer: config.tokenizer_name().to_string(),
+            document_tokens,
+            chunk_count: result.chunks.as_ref().map(Vec::len),
+            chunk_tokens,
+            estimated_cost: config.pricing.as_ref().map(|pricing| {
+                CostEstimate::from_pricing(pricing, document_tokens, chunk_tokens.unwrap_or(document_tokens))
+            }),
+        }
+    }
+
+    /// Read the usage recorded on a result during extraction.
+    pub fn from_result(result: &ExtractionResult) -> Option<Self> {
+        let value = result.metadata.additional.get(TOKEN_USAGE_KEY)?;
+        serde_json::from_value(value.clone()).ok()
+    }
+}
+
+/// Token counts aggregated over a batch.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BatchTokenUsage {
+    /// Number of results with recorded token usage
+    pub documents: usize,
+    /// Number of results without recorded token usage (failed or not counted)
+    pub uncounted_documents: usize,
+    /// Total tokens over all documents
+    pub document_tokens: usize,
+    /// Total chunks over all documents
+    pub chunk_count: usize,
+    /// Total chunk tokens over all documents
+    pub chunk_tokens: usize,
+    /// Summed cost estimates, if any document has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub estimated_cost: Option<CostEstimate>,
+}
+
+/// Aggregate the token usage recorded on batch results.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::core::config::{ExtractionConfig, TokenCountConfig, TokenPricing};
+/// use kreuzberg::text::token_count::summarize_token_usage;
+/// use kreuzberg::batch_extract_bytes_sync;
+///
+/// # fn main() -> kreuzberg::Result<()> {
+/// let config = ExtractionConfig {
+///     token_count: Some(TokenCountConfig {
+///         pricing: Some(TokenPricing {
+///             llm_input_per_million: Some(3.0),
+///             ..Default::default()
+///         }),
+///         ..Default::default()
+///     }),
+///     ..Default::default()
+/// };
+/// let contents = vec![
+///     (b"First document".to_vec(), "text/plain".to_string()),
+///     (b"Second document".to_vec(), "text/plain".to_string()),
+/// ];
+/// let results = batch_extract_bytes_sync(contents, &config)?;
+///
+/// let summary = summarize_token_usage(&results);
+/// assert_eq!(summary.documents, 2);
+/// assert!(summary.estimated_cost.unwrap().llm_input.unwrap() > 0.0);
+/// # Ok(())
+/// # }
+/// ```
+pub fn summarize_token_usage(results: &[ExtractionResult]) -> BatchTokenUsage {
+    let mut summary = BatchTokenUsage::default();
+
+    for result in results {
+        let Some(usage) = TokenUsage::from_result(result) else {
+            summary.uncounted_documents += 1;
+            continue;
+        };
+        summary.documents += 1;
+        summary.document_tokens += usage.document_tokens;
+        summary.chunk_count += usage.chunk_count.unwrap_or(0);
+        summary.chunk_tokens += usage.chunk_tokens.unwrap_or(0);
+
+        if let Some(cost) = usage.estimated_cost {
+            let total = summary.estimated_cost.get_or_insert_with(|| CostEstimate {
+                currency: cost.currency.clone(),
+                embedding: None,
+                llm_input: None,
+            });
+            let add = |total: &mut Option<f64>, amount: Option<f64>| {
+                if let Some(amount) = amount {
+                    *total = Some(total.unwrap_or(0.0) + amount);
+                }
+            };
+            add(&mut total.embedding, cost.embedding);
+            add(&mut total.llm_input, cost.llm_input);
+        }
+    }
+
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heuristic_tokens() {
+        assert_eq!(count_tokens("", TokenizerKind::Heuristic), 0);
+        // 21 letters and 3 collapsed whitespace runs
+        assert_eq!(
+            count_tokens("  document   extraction kit ", TokenizerKind::Heuristic),
+            6
+        );
+        assert_eq!(count_tokens("Kreuzberg 東京", TokenizerKind::Heuristic), 5);
+        assert_eq!(count_tokens("a\u{00e9}b", TokenizerKind::Characters), 3);
+    }
+
+    #[test]
+    fn test_cost_estimate_uses_chunk_tokens_for_embeddings() {
+        let pricing = TokenPricing {
+            embedding_per_million: Some(2.0),
+            llm_input_per_million: Some(10.0),
+            ..Default::default()
+        };
+        let cost = CostEstimate::from_pricing(&pricing, 500_000, 600_000);
+        assert_eq!(cost.embedding, Some(1.2));
+        assert_eq!(cost.llm_input, Some(5.0));
+        assert_eq!(cost.currency, "USD");
+    }
+}
@@ -1,13 +1,21 @@
 use super::analysis::TextAnalyzer;
+use crate::text::sentences::RuleBasedSegmenter;
 
 /// Handles sentence selection and filtering based on importance scoring.
 pub struct SentenceSelector;
 
 impl SentenceSelector {
     /// Applies sentence selection to keep only the most important sentences.
+    ///
+    /// Sentences are split with the registered sentence segmenter of the highest
+    /// priority, or with the built-in rules if it fails.
     pub fn apply_sentence_selection(text: &str) -> String {
-        let sentences: Vec<&str> = text
-            .split(['.', '!', '?'])
+        let ranges = crate::plugins::resolve_sentence_segmenter(None)
+            .and_then(|segmenter| segmenter.segment(text))
+            .unwrap_or_else(|_| RuleBasedSegmenter::new().split(text));
+        let sentences: Vec<&str> = ranges
+            .into_iter()
+            .filter_map(|range| text.get(range))
             .map(|s| s.trim())
             .filter(|s| !s.is_empty())
             .collect();
@@ -41,7 +49,7 @@ impl SentenceSelector {
         if selected_sentences.is_empty() {
             text.to_string()
         } else {
-            selected_sentences.join(". ")
+            selected_sentences.join(" ")
         }
     }
 }
@@ -59,6 +67,20 @@ mod tests {
         assert!(result.split(". ").count() < 4);
     }
 
+    #[test]
+    fn test_sentence_selection_keeps_abbreviations() {
+        let input =
+            "Dr. Smith cited Art. 5 of the act. It was short. The court agreed with Dr. Smith on Art. 5. Nothing else.";
+        let result = SentenceSelector::apply_sentence_selection(input);
+
+        assert!(result.len() < input.len());
+        let segmenter = RuleBasedSegmenter::new();
+        let sentences: Vec<&str> = segmenter.split(input).into_iter().map(|range| &input[range]).collect();
+        for range in segmenter.split(&result) {
+            assert!(sentences.contains(&&result[range]), "{result:?}");
+        }
+    }
+
     #[test]
     fn test_sentence_selection_short_text() {
         let input = "Only one sentence.";
@@ -0,0 +1,369 @@
+//! Transliteration of non-Latin scripts to Latin.
+//!
+//! Search systems that only index Latin script cannot match `Москва` against a query
+//! for `Moskva`. When `ExtractionConfig::transliteration` is set, [`annotate_transliteration`]
+//! stores a romanized copy of the content next to the original.
+//!
+//! Transliteration is letter by letter, without dictionaries: Han characters are kept,
+//! Japanese is not split into words, and Korean sound changes across syllables are
+//! not applied.
+
+use super::script::Script;
+use crate::core::config::transliteration::{TransliterationConfig, TransliterationScheme};
+use crate::types::Metadata;
+use std::borrow::Cow;
+
+/// Metadata key of the transliterated content, in `metadata.additional`.
+pub const TRANSLITERATED_KEY: &str = "content_transliterated";
+
+/// Hiragana U+3041 to U+3096 in Hepburn; katakana are mapped to hiragana first.
+const HIRAGANA: [&str; 86] = [
+    "a", "a", "i", "i", "u", "u", "e", "e", "o", "o", "ka", "ga", "ki", "gi", "ku", "gu", "ke", "ge", "ko", "go", "sa",
+    "za", "shi", "ji", "su", "zu", "se", "ze", "so", "zo", "ta", "da", "chi", "ji", "", "tsu", "zu", "te", "de", "to",
+    "do", "na", "ni", "nu", "ne", "no", "ha", "ba", "pa", "hi", "bi", "pi", "fu", "bu", "pu", "he", "be", "pe", "ho",
+    "bo", "po", "ma", "mi", "mu", "me", "mo", "ya", "ya", "yu", "yu", "yo", "yo", "ra", "ri", "ru", "re", "ro", "wa",
+    "wa", "i", "e", "o", "n", "vu", "ka", "ke",
+];
+
+const SOKUON: char = '\u{3063}';
+const PROLONGED_SOUND_MARK: char = '\u{30FC}';
+
+/// Revised Romanization of the initial, medial and final jamo of Hangul syllables.
+const HANGUL_INITIALS: [&str; 19] = [
+    "g", "kk", "n", "d", "tt", "r", "m", "b", "pp", "s", "ss", "", "j", "jj", "ch", "k", "t", "p", "h",
+];
+const HANGUL_MEDIALS: [&str; 21] = [
+    "a", "ae", "ya", "yae", "eo", "e", "yeo", "ye", "o", "wa", "wae", "oe", "yo", "u", "wo", "we", "wi", "yu", "eu",
+    "ui", "i",
+];
+const HANGUL_FINALS: [&str; 28] = [
+    "", "k", "k", "k", "n", "n", "n", "t", "l", "k", "m", "l", "l", "l", "p", "l", "m", "p", "p", "t", "t", "ng", "t",
+    "t", "k", "t", "p", "t",
+];
+
+/// Transliterate the letters of the configured scripts in `text` to Latin.
+///
+/// Other characters, including letters of unsupported scripts, are kept.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::core::config::{TransliterationConfig, TransliterationScheme};
+/// use kreuzberg::text::transliteration::transliterate;
+///
+/// let config = TransliterationConfig::default();
+/// assert_eq!(transliterate("Москва и Αθήνα", &config), "Moskva i Athina");
+///
+/// let config = TransliterationConfig {
+///     scheme: TransliterationScheme::Iso,
+///     ..Default::default()
+/// };
+/// assert_eq!(transliterate("Щукино", &config), "Ŝukino");
+/// ```
+pub fn transliterate(text: &str, config: &TransliterationConfig) -> String {
+    let iso = config.scheme == TransliterationScheme::Iso;
+    let mut out = String::with_capacity(text.len());
+    let mut kana = KanaState::default();
+    let mut chars = text.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let script = Script::of(c).filter(|script| config.includes(*script));
+        match script {
+            Some(Script::Hiragana | Script::Katakana) => {
+                kana.push(c, iso, &mut out);
+                continue;
+            }
+            Some(Script::Cyrillic) => {
+                let next_upper = chars.peek().is_some_and(|next| next.is_uppercase());
+                push_cased(&mut out, c, next_upper, |lower| cyrillic(lower, iso));
+            }
+            Some(Script::Greek) => {
+                let next_upper = chars.peek().is_some_and(|next| next.is_uppercase());
+                push_cased(&mut out, c, next_upper, |lower| greek(lower, iso));
+            }
+            Some(Script::Hangul) => match hangul(c) {
+                Some(romanized) => out.push_str(&romanized),
+                None => out.push(c),
+            },
+            _ => out.push(c),
+        }
+        kana = KanaState::default();
+    }
+
+    out
+}
+
+/// Store the transliteration of `text` in `metadata.additional["content_transliterated"]`.
+pub fn annotate_transliteration(text: &str, config: &TransliterationConfig, metadata: &mut Metadata) {
+    metadata.additional.insert(
+        Cow::Borrowed(TRANSLITERATED_KEY),
+        serde_json::Value::String(transliterate(text, config)),
+    );
+}
+
+/// Push the romanization of the cased letter `c`, keeping its case.
+///
+/// An uppercase letter followed by another uppercase letter is romanized in capitals
+/// (`ЖУК` → `ZHUK`), otherwise only its first letter is capitalized (`Жук` → `Zhuk`).
+fn push_cased(out: &mut String, c: char, next_upper: bool, romanize: impl Fn(char) -> Option<&'static str>) {
+    let lower = c.to_lowercase().next().unwrap_or(c);
+    let Some(romanized) = romanize(lower) else {
+        out.push(c);
+        return;
+    };
+    if !c.is_uppercase() {
+        out.push_str(romanized);
+    } else if next_upper {
+        out.push_str(&romanized.to_uppercase());
+    } else {
+        let mut letters = romanized.chars();
+        if let Some(first) = letters.next() {
+            out.extend(first.to_uppercase());
+            out.push_str(letters.as_str());
+        }
+    }
+}
+
+fn cyrillic(c: char, iso: bool) -> Option<&'static str> {
+    let (simplified, iso_9) = match c {
+        'а' => ("a", "a"),
+        'б' => ("b", "b"),
+        'в' => ("v", "v"),
+        'г' => ("g", "g"),
+        'ґ' => ("g", "g̀"),
+        'д' => ("d", "d"),
+        'ђ' => ("dj", "đ"),
+        'ѓ' => ("gj", "ǵ"),
+        'е' => ("e", "e"),
+        'ё' => ("yo", "ë"),
+        'є' => ("ye", "ê"),
+        'ж' => ("zh", "ž"),
+        'з' => ("z", "z"),
+        'ѕ' => ("dz", "ẑ"),
+        'и' => ("i", "i"),
+        'і' => ("i", "ì"),
+        'ї' => ("yi", "ï"),
+        'й' => ("y", "j"),
+        'ј' => ("j", "ǰ"),
+        'к' => ("k", "k"),
+        'л' => ("l", "l"),
+        'љ' => ("lj", "l̂"),
+        'м' => ("m", "m"),
+        'н' => ("n", "n"),
+        'њ' => ("nj", "n̂"),
+        'о' => ("o", "o"),
+        'п' => ("p", "p"),
+        'р' => ("r", "r"),
+        'с' => ("s", "s"),
+        'т' => ("t", "t"),
+        'ћ' => ("c", "ć"),
+        'ќ' => ("kj", "ḱ"),
+        'у' => ("u", "u"),
+        'ў' => ("u", "ŭ"),
+        'ф' => ("f", "f"),
+        'х' => ("kh", "h"),
+        'ц' => ("ts", "c"),
+        'ч' => ("ch", "č"),
+        'џ' => ("dz", "d̂"),
+        'ш' => ("sh", "š"),
+        'щ' => ("shch", "ŝ"),
+        'ъ' => ("", "ʺ"),
+        'ы' => ("y", "y"),
+        'ь' => ("", "ʹ"),
+        'э' => ("e", "è"),
+        'ю' => ("yu", "û"),
+        'я' => ("ya", "â"),
+        _ => return None,
+    };
+    Some(if iso { iso_9 } else { simplified })
+}
+
+fn greek(c: char, iso: bool) -> Option<&'static str> {
+    let (simplified, iso_843) = match c {
+        'α' => ("a", "a"),
+        'ά' => ("a", "á"),
+        'β' => ("v", "v"),
+        'γ' => ("g", "g"),
+        'δ' => ("d", "d"),
+        'ε' => ("e", "e"),
+        'έ' => ("e", "é"),
+        'ζ' => ("z", "z"),
+        'η' => ("i", "ī"),
+        'ή' => ("i", "ī́"),
+        'θ' => ("th", "th"),
+        'ι' => ("i", "i"),
+        'ί' => ("i", "í"),
+        'ϊ' => ("i", "ï"),
+        'ΐ' => ("i", "ḯ"),
+        'κ' => ("k", "k"),
+        'λ' => ("l", "l"),
+        'μ' => ("m", "m"),
+        'ν' => ("n", "n"),
+        'ξ' => ("x", "x"),
+        'ο' => ("o", "o"),
+        'ό' => ("o", "ó"),
+        'π' => ("p", "p"),
+        'ρ' => ("r", "r"),
+        'σ' | 'ς' => ("s", "s"),
+        'τ' => ("t", "t"),
+        'υ' => ("y", "y"),
+        'ύ' => ("y", "ý"),
+        'ϋ' => ("y", "ÿ"),
+        'ΰ' => ("y", "ÿ́"),
+        'φ' => ("f", "f"),
+        'χ' => ("ch", "ch"),
+        'ψ' => ("ps", "ps"),
+        'ω' => ("o", "ō"),
+        'ώ' => ("o", "ṓ"),
+        _ => return None,
+    };
+    Some(if iso { iso_843 } else { simplified })
+}
+
+/// Romanize a precomposed Hangul syllable.
+fn hangul(c: char) -> Option<String> {
+    let index = (c as u32).checked_sub(0xAC00).filter(|index| *index < 11172)? as usize;
+    Some(format!(
+        "{}{}{}",
+        HANGUL_INITIALS[index / (21 * 28)],
+        HANGUL_MEDIALS[index % (21 * 28) / 28],
+        HANGUL_FINALS[index % 28]
+    ))
+}
+
+/// Context of the preceding kana, needed for digraphs (`きょ` → `kyo`), doubled
+/// consonants (`っ`) and long vowels (`ー`).
+#[derive(Default)]
+struct KanaState {
+    /// Romanization of the previous kana syllable
+    last: Option<&'static str>,
+    /// A small tsu doubles the next consonant
+    geminate: bool,
+}
+
+impl KanaState {
+    fn push(&mut self, c: char, iso: bool, out: &mut String) {
+        // Katakana have the same layout as hiragana, 0x60 code points higher.
+        let c = match c as u32 {
+            0x30A1..=0x30F6 => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            _ => c,
+        };
+
+        if c == SOKUON {
+            self.geminate = true;
+            return;
+        }
+        // Long vowels get a macron in Hepburn and are written short in simplified text.
+        if c == PROLONGED_SOUND_MARK {
+            if iso
+                && self.last.is_some()
+                && let Some(long) = out.chars().last().and_then(macron)
+            {
+                out.pop();
+                out.push(long);
+            }
+            return;
+        }
+        let Some(romanized) = (c as u32)
+            .checked_sub(0x3041)
+            .and_then(|index| HIRAGANA.get(index as usize))
+            .copied()
+        else {
+            out.push(c);
+            *self = Self::default();
+            return;
+        };
+
+        let last = self.last.filter(|last| last.len() >= 2);
+        match (c, last) {
+            // Small ya, yu, yo form a digraph with a preceding i-syllable.
+            ('\u{3083}' | '\u{3085}' | '\u{3087}', Some(last)) if last.ends_with('i') => {
+                out.pop();
+                if matches!(last, "shi" | "chi" | "ji") {
+                    out.push_str(&romanized[1..]);
+                } else {
+                    out.push_str(romanized);
+                }
+            }
+            // Small vowels replace the vowel of the preceding syllable (ファ → fa).
+            ('\u{3041}' | '\u{3043}' | '\u{3045}' | '\u{3047}' | '\u{3049}', Some(last))
+                if last.ends_with(['a', 'i', 'u', 'e', 'o']) =>
+            {
+                out.pop();
+                out.push_str(romanized);
+            }
+            _ => {
+                if self.geminate {
+                    if romanized.starts_with("ch") {
+                        out.push('t');
+                    } else if let Some(first) = romanized.chars().next().filter(|c| !"aiueon".contains(*c)) {
+                        out.push(first);
+                    }
+                }
+                out.push_str(romanized);
+            }
+        }
+        self.last = Some(romanized);
+        self.geminate = false;
+    }
+}
+
+fn macron(vowel: char) -> Option<char> {
+    Some(match vowel {
+        'a' => 'ā',
+        'i' => 'ī',
+        'u' => 'ū',
+        'e' => 'ē',
+        'o' => 'ō',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn iso() -> TransliterationConfig {
+        TransliterationConfig {
+            scheme: TransliterationScheme::Iso,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_transliterate_cyrillic_and_greek() {
+        let config = TransliterationConfig::default();
+        assert_eq!(transliterate("Москва, Щукино", &config), "Moskva, Shchukino");
+        assert_eq!(transliterate("ЖУК и Жук", &config), "ZHUK i Zhuk");
+        assert_eq!(transliterate("Софія", &config), "Sofiya");
+        assert_eq!(transliterate("Αθήνα, ΧΑΝΙΑ", &config), "Athina, CHANIA");
+
+        assert_eq!(transliterate("Щукино, Жук", &iso()), "Ŝukino, Žuk");
+        assert_eq!(transliterate("Αθήνα", &iso()), "Athī́na");
+    }
+
+    #[test]
+    fn test_transliterate_kana_and_hangul() {
+        let config = TransliterationConfig::default();
+        assert_eq!(transliterate("ひらがな", &config), "hiragana");
+        assert_eq!(transliterate("きょうと と がっこう", &config), "kyouto to gakkou");
+        assert_eq!(transliterate("ちゃ マッチ", &config), "cha matchi");
+        assert_eq!(transliterate("ラーメン ティー", &config), "ramen ti");
+        assert_eq!(transliterate("ラーメン", &iso()), "rāmen");
+        assert_eq!(transliterate("서울 한국어", &config), "seoul hangukeo");
+        assert_eq!(transliterate("東京はひらがな", &config), "東京hahiragana");
+    }
+
+    #[test]
+    fn test_transliterate_selected_scripts() {
+        let config = TransliterationConfig {
+            scripts: vec![Script::Cyrillic],
+            ..Default::default()
+        };
+        assert_eq!(transliterate("Мир, Αθήνα, ひらがな", &config), "Mir, Αθήνα, ひらがな");
+
+        let mut metadata = Metadata::default();
+        annotate_transliteration("Мир", &config, &mut metadata);
+        assert_eq!(metadata.additional[TRANSLITERATED_KEY], "Mir");
+    }
+}
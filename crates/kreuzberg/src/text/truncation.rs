@@ -0,0 +1,162 @@
+//! Structure-aware content truncation.
+//!
+//! Cuts extracted text down to a character budget for previews. The cut falls at the
+//! last paragraph break within the budget, or else at the last sentence end, or else
+//! between words, so previews never end mid-word. Only the content is shortened: the
+//! metadata and page structure of the whole document are kept.
+
+use crate::types::ExtractionResult;
+use std::borrow::Cow;
+
+/// Sentence-ending punctuation, including the full-width CJK forms.
+pub(crate) const SENTENCE_ENDS: [char; 6] = ['.', '!', '?', '。', '！', '？'];
+
+/// Byte offset at which to cut `text` so at most `max_chars` characters remain.
+///
+/// Returns `None` when the text already fits. A paragraph or sentence boundary is only
+/// used when it keeps at least half of the budget, so that a long paragraph is cut
+/// between words rather than dropped entirely. Text without any whitespace in the
+/// budget is cut at the budget.
+pub fn truncation_offset(text: &str, max_chars: usize) -> Option<usize> {
+    let limit = text.char_indices().nth(max_chars).map(|(offset, _)| offset)?;
+    let window = &text[..limit];
+    let min_offset = limit / 2;
+
+    if let Some(offset) = window.rfind("\n\n").filter(|&offset| offset >= min_offset) {
+        return Some(offset);
+    }
+
+    // A sentence end counts when followed by whitespace, or by the cut itself.
+    let mut next_is_space = text[limit..].starts_with(char::is_whitespace);
+    for (offset, ch) in window.char_indices().rev() {
+        if offset + ch.len_utf8() < min_offset {
+            break;
+        }
+        if SENTENCE_ENDS.contains(&ch) && (next_is_space || !ch.is_ascii()) {
+            return Some(offset + ch.len_utf8());
+        }
+        next_is_space = ch.is_whitespace();
+    }
+
+    if let Some((offset, _)) = window.char_indices().rev().find(|(_, ch)| ch.is_whitespace()) {
+        return Some(offset);
+    }
+
+    Some(limit)
+}
+
+/// Truncate the content of a result to at most `max_chars` characters.
+///
+/// Trailing whitespace before the cut is dropped. Page boundaries are clipped to the
+/// remaining content, and the truncation is recorded in
+/// `metadata.additional["truncation_warnings"]`. Page contents, tables, images and
+/// the rest of the metadata are left whole.
+pub(crate) fn truncate_result(result: &mut ExtractionResult, max_chars: usize) {
+    let Some(offset) = truncation_offset(&result.content, max_chars) else {
+        return;
+    };
+    let cut = result.content[..offset].trim_end().len();
+    let total_chars = result.content.chars().count();
+    result.content.truncate(cut);
+
+    if let Some(boundaries) = result
+        .metadata
+        .pages
+        .as_mut()
+        .and_then(|pages| pages.boundaries.as_mut())
+    {
+        boundaries.retain(|boundary| boundary.byte_start < cut);
+        for boundary in boundaries.iter_mut() {
+            boundary.byte_end = boundary.byte_end.min(cut);
+        }
+    }
+
+    let warning = format!(
+        "Content truncated to {} of {} characters",
+        result.content.chars().count(),
+        total_chars
+    );
+    result
+        .metadata
+        .additional
+        .insert(Cow::Borrowed("truncation_warnings"), serde_json::json!([warning]));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Metadata, PageBoundary, PageStructure, PageUnitType};
+
+    #[test]
+    fn test_truncation_offset_prefers_paragraphs_then_sentences_then_words() {
+        assert_eq!(truncation_offset("short", 10), None);
+
+        let text = "First paragraph here.\n\nSecond paragraph goes on and on.";
+        assert_eq!(&text[..truncation_offset(text, 30).unwrap()], "First paragraph here.");
+
+        let text = "One sentence. Another sentence that is long.";
+        assert_eq!(&text[..truncation_offset(text, 24).unwrap()], "One sentence.");
+
+        let text = "words without any sentence ending at all";
+        assert_eq!(&text[..truncation_offset(text, 22).unwrap()], "words without any");
+
+        let text = "一句话。第二句话很长很长";
+        assert_eq!(&text[..truncation_offset(text, 8).unwrap()], "一句话。");
+
+        assert_eq!(truncation_offset("abcdefghij", 4), Some(4));
+    }
+
+    #[test]
+    fn test_truncate_result_clips_boundaries_and_warns() {
+        let mut result = ExtractionResult {
+            content: "Page one text.\n\nPage two text.\n\nPage three text.".to_string(),
+            mime_type: Cow::Borrowed("text/plain"),
+            metadata: Metadata {
+                pages: Some(PageStructure {
+                    total_count: 3,
+                    unit_type: PageUnitType::Page,
+                    boundaries: Some(vec![
+                        PageBoundary {
+                            byte_start: 0,
+                            byte_end: 14,
+                            page_number: 1,
+                        },
+                        PageBoundary {
+                            byte_start: 16,
+                            byte_end: 30,
+                            page_number: 2,
+                        },
+                        PageBoundary {
+                            byte_start: 32,
+                            byte_end: 48,
+                            page_number: 3,
+                        },
+                    ]),
+                    pages: None,
+                }),
+                ..Default::default()
+            },
+            tables: vec![],
+            detected_languages: None,
+            chunks: None,
+            images: None,
+            djot_content: None,
+            scripts: None,
+            pages: None,
+            elements: None,
+        };
+
+        truncate_result(&mut result, 40);
+
+        assert_eq!(result.content, "Page one text.\n\nPage two text.");
+        let structure = result.metadata.pages.unwrap();
+        assert_eq!(structure.total_count, 3);
+        let boundaries = structure.boundaries.unwrap();
+        assert_eq!(boundaries.len(), 2);
+        assert_eq!(boundaries[1].byte_end, 30);
+        assert_eq!(
+            result.metadata.additional["truncation_warnings"],
+            serde_json::json!(["Content truncated to 30 of 48 characters"])
+        );
+    }
+}
@@ -19,6 +19,8 @@
 //! assert_eq!(result, "Hello, UTF-8 world!");
 //! ```
 
+use std::borrow::Cow;
+
 /// Validates and converts bytes to string using SIMD when available.
 ///
 /// This function attempts to use SIMD UTF-8 validation if the `simd-utf8` feature
@@ -119,10 +121,90 @@ pub fn is_valid_utf8(bytes: &[u8]) -> bool {
     }
 }
 
+/// Decodes bytes as UTF-8, borrowing the input when it is already valid.
+///
+/// Invalid sequences are replaced with `U+FFFD`, in which case an owned string is
+/// returned. Extractors that only read their input should prefer this over
+/// `String::from_utf8_lossy(..).into_owned()`, which always copies the whole document.
+///
+/// # Arguments
+///
+/// * `bytes` - The byte slice to decode
+///
+/// # Returns
+///
+/// `Cow::Borrowed` for valid UTF-8, `Cow::Owned` with replacement characters otherwise.
+#[inline]
+pub fn decode_lossy(bytes: &[u8]) -> Cow<'_, str> {
+    match from_utf8(bytes) {
+        Ok(text) => Cow::Borrowed(text),
+        Err(_) => Cow::Owned(replace_invalid(bytes)),
+    }
+}
+
+/// Decodes bytes with invalid sequences, replacing each with `U+FFFD` as
+/// `String::from_utf8_lossy` does, but validating the valid stretches between
+/// them with SIMD when available.
+fn replace_invalid(bytes: &[u8]) -> String {
+    #[cfg(feature = "simd-utf8")]
+    {
+        let mut decoded = String::with_capacity(bytes.len() + 16);
+        let mut rest = bytes;
+        loop {
+            match simdutf8::compat::from_utf8(rest) {
+                Ok(valid) => {
+                    decoded.push_str(valid);
+                    return decoded;
+                }
+                Err(error) => {
+                    let (valid, after_valid) = rest.split_at(error.valid_up_to());
+                    // SAFETY: the validator reported `valid` as valid UTF-8.
+                    #[allow(unsafe_code)]
+                    decoded.push_str(unsafe { std::str::from_utf8_unchecked(valid) });
+                    decoded.push(char::REPLACEMENT_CHARACTER);
+                    match error.error_len() {
+                        Some(invalid_len) => rest = &after_valid[invalid_len..],
+                        // An incomplete sequence at the end of the input
+                        None => return decoded,
+                    }
+                }
+            }
+        }
+    }
+
+    #[cfg(not(feature = "simd-utf8"))]
+    {
+        String::from_utf8_lossy(bytes).into_owned()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_decode_lossy_borrows_valid_input() {
+        let bytes = "Grüße".as_bytes();
+        assert!(matches!(decode_lossy(bytes), Cow::Borrowed("Grüße")));
+
+        let decoded = decode_lossy(b"ab\xFFcd");
+        assert!(matches!(decoded, Cow::Owned(_)));
+        assert_eq!(decoded, "ab\u{FFFD}cd");
+    }
+
+    #[test]
+    fn test_decode_lossy_matches_std() {
+        let samples: &[&[u8]] = &[
+            b"\xFF",
+            b"caf\xE9 au lait",
+            b"\xF0\x9F\x8C",
+            b"ok \xE2\x82 truncated \xC0\x80 overlong \xED\xA0\x80 surrogate \xF0\x9F\x8C\x8D end",
+        ];
+        for sample in samples {
+            assert_eq!(decode_lossy(sample), String::from_utf8_lossy(sample), "{sample:?}");
+        }
+    }
+
     #[test]
     fn test_valid_ascii() {
         let bytes = b"Hello, world!";
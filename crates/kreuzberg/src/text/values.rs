@@ -0,0 +1,954 @@
+//! Date, time, currency, quantity and number normalization.
+//!
+//! Dates and amounts are written differently in every locale: `5. März 2024`,
+//! `03/05/2024`, `1.234,56 €`. Structured queries such as "invoices after March 2024
+//! over 1000 EUR" need them in one notation, and engineering searches such as "rated
+//! above 2 bar" need `30 psi` in the same unit. When `ExtractionConfig::value_normalization`
+//! is set, [`normalize_values`] finds the values in the content and emits their ISO 8601
+//! and decimal forms, either as spans in metadata or inline after the original text.
+//!
+//! Month names are recognized in English, German, French, Spanish, Italian, Portuguese
+//! and Dutch. Numeric dates need a four-digit year. Units are recognized by their
+//! symbols and common English names, case-sensitively, so `mm` is not `Mm`.
+
+use crate::core::config::value_normalization::{UnitSystem, ValueAnnotationMode, ValueKind, ValueNormalizationConfig};
+use crate::types::ExtractionResult;
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+/// Metadata key of the normalized values, in `metadata.additional`.
+pub const NORMALIZED_VALUES_KEY: &str = "normalized_values";
+
+/// Month names and abbreviations, lower case, from January to December.
+const MONTHS: [&[&str]; 12] = [
+    &[
+        "january", "jan", "januar", "jänner", "janvier", "janv", "enero", "ene", "gennaio", "gen", "janeiro", "januari",
+    ],
+    &[
+        "february",
+        "feb",
+        "februar",
+        "février",
+        "fevrier",
+        "févr",
+        "febrero",
+        "febbraio",
+        "fevereiro",
+        "fev",
+        "februari",
+    ],
+    &[
+        "march", "mar", "märz", "maerz", "mär", "mars", "marzo", "março", "maart", "mrt",
+    ],
+    &["april", "apr", "avril", "avr", "abril", "abr", "aprile"],
+    &["may", "mai", "mayo", "maggio", "mag", "maio", "mei"],
+    &["june", "jun", "juni", "juin", "junio", "giugno", "giu", "junho"],
+    &[
+        "july", "jul", "juli", "juillet", "juil", "julio", "luglio", "lug", "julho",
+    ],
+    &["august", "aug", "août", "aout", "agosto", "ago", "augustus"],
+    &[
+        "september",
+        "sep",
+        "sept",
+        "septembre",
+        "septiembre",
+        "settembre",
+        "set",
+        "setembro",
+    ],
+    &[
+        "october", "oct", "oktober", "okt", "octobre", "octubre", "ottobre", "ott", "outubro", "out",
+    ],
+    &["november", "nov", "novembre", "noviembre", "novembro"],
+    &[
+        "december",
+        "dec",
+        "dezember",
+        "dez",
+        "décembre",
+        "déc",
+        "diciembre",
+        "dic",
+        "dicembre",
+        "dezembro",
+    ],
+];
+
+/// Currency symbols and the ISO 4217 codes they stand for.
+const CURRENCY_SYMBOLS: &[(&str, &str)] = &[
+    ("US$", "USD"),
+    ("A$", "AUD"),
+    ("C$", "CAD"),
+    ("HK$", "HKD"),
+    ("R$", "BRL"),
+    ("$", "USD"),
+    ("€", "EUR"),
+    ("£", "GBP"),
+    ("¥", "JPY"),
+    ("₹", "INR"),
+    ("₽", "RUB"),
+    ("₩", "KRW"),
+    ("₺", "TRY"),
+    ("₪", "ILS"),
+    ("zł", "PLN"),
+];
+
+/// ISO 4217 codes recognized before or after an amount.
+const CURRENCY_CODES: &[&str] = &[
+    "USD", "EUR", "GBP", "JPY", "CHF", "CNY", "CAD", "AUD", "NZD", "SEK", "NOK", "DKK", "PLN", "CZK", "HUF", "INR",
+    "BRL", "RUB", "KRW", "TRY", "ZAR", "MXN", "SGD", "HKD", "ILS",
+];
+
+/// Languages, as ISO 639-1 or 639-3 codes, that write `1.234,5` rather than `1,234.5`.
+const DECIMAL_COMMA_LANGUAGES: &[&str] = &[
+    "de", "deu", "ger", "fr", "fra", "fre", "es", "spa", "it", "ita", "pt", "por", "nl", "nld", "dut", "ru", "rus",
+    "pl", "pol", "cs", "ces", "cze", "sk", "slk", "slo", "sv", "swe", "da", "dan", "nb", "nob", "nn", "nno", "no",
+    "nor", "fi", "fin", "tr", "tur", "id", "ind", "uk", "ukr", "ro", "ron", "rum", "hu", "hun", "el", "ell", "gre",
+    "bg", "bul", "hr", "hrv", "sr", "srp", "sl", "slv", "lt", "lit", "lv", "lav", "et", "est", "ca", "cat", "vi",
+    "vie",
+];
+
+/// Dimension of a physical quantity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Dimension {
+    Length,
+    Mass,
+    Pressure,
+    Temperature,
+    Volume,
+    Area,
+    Speed,
+    Energy,
+    Power,
+    Force,
+}
+
+impl Dimension {
+    /// Symbol of the unit that quantities of this dimension are converted to.
+    fn target(self, system: UnitSystem) -> &'static str {
+        match (system, self) {
+            (UnitSystem::Si, Self::Length) => "m",
+            (UnitSystem::Si, Self::Mass) => "kg",
+            (UnitSystem::Si, Self::Pressure) => "Pa",
+            (UnitSystem::Si, Self::Temperature) => "K",
+            (UnitSystem::Si, Self::Volume) => "m³",
+            (UnitSystem::Si, Self::Area) => "m²",
+            (UnitSystem::Si, Self::Speed) => "m/s",
+            (UnitSystem::Si, Self::Energy) => "J",
+            (UnitSystem::Si, Self::Power) => "W",
+            (UnitSystem::Si, Self::Force) => "N",
+            (UnitSystem::Imperial, Self::Length) => "ft",
+            (UnitSystem::Imperial, Self::Mass) => "lb",
+            (UnitSystem::Imperial, Self::Pressure) => "psi",
+            (UnitSystem::Imperial, Self::Temperature) => "°F",
+            (UnitSystem::Imperial, Self::Volume) => "gal",
+            (UnitSystem::Imperial, Self::Area) => "ft²",
+            (UnitSystem::Imperial, Self::Speed) => "mph",
+            (UnitSystem::Imperial, Self::Energy) => "BTU",
+            (UnitSystem::Imperial, Self::Power) => "hp",
+            (UnitSystem::Imperial, Self::Force) => "lbf",
+        }
+    }
+}
+
+/// A unit of measure: `x` in this unit is `x * scale + offset` in the SI unit of its
+/// dimension.
+#[derive(Debug, Clone, Copy)]
+struct Unit {
+    symbol: &'static str,
+    dimension: Dimension,
+    scale: f64,
+    offset: f64,
+}
+
+const fn unit(symbol: &'static str, dimension: Dimension, scale: f64) -> Unit {
+    Unit {
+        symbol,
+        dimension,
+        scale,
+        offset: 0.0,
+    }
+}
+
+/// Recognized units and their spellings. `in`, `s` and `K` are left out, as they are
+/// words or suffixes (`5 in total`, `4K`) more often than units.
+const UNITS: &[Unit] = &[
+    unit("nm", Dimension::Length, 1e-9),
+    unit("µm", Dimension::Length, 1e-6),
+    unit("μm", Dimension::Length, 1e-6),
+    unit("mm", Dimension::Length, 1e-3),
+    unit("cm", Dimension::Length, 1e-2),
+    unit("m", Dimension::Length, 1.0),
+    unit("meter", Dimension::Length, 1.0),
+    unit("meters", Dimension::Length, 1.0),
+    unit("metre", Dimension::Length, 1.0),
+    unit("metres", Dimension::Length, 1.0),
+    unit("km", Dimension::Length, 1e3),
+    unit("kilometers", Dimension::Length, 1e3),
+    unit("kilometres", Dimension::Length, 1e3),
+    unit("inch", Dimension::Length, 0.0254),
+    unit("inches", Dimension::Length, 0.0254),
+    unit("ft", Dimension::Length, 0.3048),
+    unit("foot", Dimension::Length, 0.3048),
+    unit("feet", Dimension::Length, 0.3048),
+    unit("yd", Dimension::Length, 0.9144),
+    unit("mi", Dimension::Length, 1609.344),
+    unit("mile", Dimension::Length, 1609.344),
+    unit("miles", Dimension::Length, 1609.344),
+    unit("nmi", Dimension::Length, 1852.0),
+    unit("mg", Dimension::Mass, 1e-6),
+    unit("g", Dimension::Mass, 1e-3),
+    unit("kg", Dimension::Mass, 1.0),
+    unit("t", Dimension::Mass, 1e3),
+    unit("tonnes", Dimension::Mass, 1e3),
+    unit("oz", Dimension::Mass, 0.028349523125),
+    unit("lb", Dimension::Mass, 0.45359237),
+    unit("lbs", Dimension::Mass, 0.45359237),
+    unit("pounds", Dimension::Mass, 0.45359237),
+    unit("Pa", Dimension::Pressure, 1.0),
+    unit("hPa", Dimension::Pressure, 1e2),
+    unit("kPa", Dimension::Pressure, 1e3),
+    unit("MPa", Dimension::Pressure, 1e6),
+    unit("GPa", Dimension::Pressure, 1e9),
+    unit("mbar", Dimension::Pressure, 1e2),
+    unit("bar", Dimension::Pressure, 1e5),
+    unit("psi", Dimension::Pressure, 6894.757293168361),
+    unit("atm", Dimension::Pressure, 101325.0),
+    unit("mmHg", Dimension::Pressure, 133.322387415),
+    Unit {
+        symbol: "°C",
+        dimension: Dimension::Temperature,
+        scale: 1.0,
+        offset: 273.15,
+    },
+    Unit {
+        symbol: "℃",
+        dimension: Dimension::Temperature,
+        scale: 1.0,
+        offset: 273.15,
+    },
+    Unit {
+        symbol: "°F",
+        dimension: Dimension::Temperature,
+        scale: 5.0 / 9.0,
+        offset: 273.15 - 32.0 * 5.0 / 9.0,
+    },
+    Unit {
+        symbol: "℉",
+        dimension: Dimension::Temperature,
+        scale: 5.0 / 9.0,
+        offset: 273.15 - 32.0 * 5.0 / 9.0,
+    },
+    unit("ml", Dimension::Volume, 1e-6),
+    unit("mL", Dimension::Volume, 1e-6),
+    unit("l", Dimension::Volume, 1e-3),
+    unit("L", Dimension::Volume, 1e-3),
+    unit("liters", Dimension::Volume, 1e-3),
+    unit("litres", Dimension::Volume, 1e-3),
+    unit("cm³", Dimension::Volume, 1e-6),
+    unit("m³", Dimension::Volume, 1.0),
+    unit("m3", Dimension::Volume, 1.0),
+    unit("ft³", Dimension::Volume, 0.028316846592),
+    unit("gal", Dimension::Volume, 0.003785411784),
+    unit("gallons", Dimension::Volume, 0.003785411784),
+    unit("mm²", Dimension::Area, 1e-6),
+    unit("cm²", Dimension::Area, 1e-4),
+    unit("m²", Dimension::Area, 1.0),
+    unit("m2", Dimension::Area, 1.0),
+    unit("km²", Dimension::Area, 1e6),
+    unit("ha", Dimension::Area, 1e4),
+    unit("ft²", Dimension::Area, 0.09290304),
+    unit("sq ft", Dimension::Area, 0.09290304),
+    unit("acres", Dimension::Area, 4046.8564224),
+    unit("m/s", Dimension::Speed, 1.0),
+    unit("km/h", Dimension::Speed, 1.0 / 3.6),
+    unit("kph", Dimension::Speed, 1.0 / 3.6),
+    unit("mph", Dimension::Speed, 0.44704),
+    unit("ft/s", Dimension::Speed, 0.3048),
+    unit("kn", Dimension::Speed, 1852.0 / 3600.0),
+    unit("knots", Dimension::Speed, 1852.0 / 3600.0),
+    unit("J", Dimension::Energy, 1.0),
+    unit("kJ", Dimension::Energy, 1e3),
+    unit("MJ", Dimension::Energy, 1e6),
+    unit("Wh", Dimension::Energy, 3.6e3),
+    unit("kWh", Dimension::Energy, 3.6e6),
+    unit("MWh", Dimension::Energy, 3.6e9),
+    unit("cal", Dimension::Energy, 4.184),
+    unit("kcal", Dimension::Energy, 4184.0),
+    unit("BTU", Dimension::Energy, 1055.05585262),
+    unit("Btu", Dimension::Energy, 1055.05585262),
+    unit("W", Dimension::Power, 1.0),
+    unit("kW", Dimension::Power, 1e3),
+    unit("MW", Dimension::Power, 1e6),
+    unit("hp", Dimension::Power, 745.6998715822702),
+    unit("N", Dimension::Force, 1.0),
+    unit("kN", Dimension::Force, 1e3),
+    unit("lbf", Dimension::Force, 4.4482216152605),
+];
+
+/// A number with optional thousands grouping and decimals.
+const NUMBER: &str = r"\d{1,3}(?:[,.'\u{a0}\u{202f}]\d{3})+(?:[.,]\d+)?|\d+(?:[.,]\d+)?";
+
+/// Separators that continue a number, so `5.3` in `5.3.1` is not a value of its own.
+const NUMBER_SEPARATORS: [char; 4] = ['.', ',', ':', '/'];
+
+static MONTH_PATTERN: Lazy<String> = Lazy::new(|| {
+    let mut names: Vec<&str> = MONTHS.iter().flat_map(|names| names.iter().copied()).collect();
+    names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+    names
+        .iter()
+        .map(|name| regex::escape(name))
+        .collect::<Vec<_>>()
+        .join("|")
+});
+
+static CURRENCY_PATTERN: Lazy<String> = Lazy::new(|| {
+    CURRENCY_SYMBOLS
+        .iter()
+        .map(|(symbol, _)| regex::escape(symbol))
+        .chain(CURRENCY_CODES.iter().map(|code| code.to_string()))
+        .collect::<Vec<_>>()
+        .join("|")
+});
+
+static TEXTUAL_DAY_FIRST: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"(?i)(\d{{1,2}})(?:st|nd|rd|th|er)?\.?(?:\s+(?:de|of))?\s+({})\.?(?:\s+(?:de|del))?,?\s+(\d{{4}})",
+        *MONTH_PATTERN
+    ))
+    .expect("valid regex")
+});
+
+static TEXTUAL_MONTH_FIRST: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"(?i)({})\.?\s+(\d{{1,2}})(?:st|nd|rd|th)?,?\s+(\d{{4}})",
+        *MONTH_PATTERN
+    ))
+    .expect("valid regex")
+});
+
+static NUMERIC_YEAR_FIRST: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\d{4})([-/.])(\d{1,2})([-/.])(\d{1,2})").expect("valid regex"));
+
+static NUMERIC_YEAR_LAST: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(\d{1,2})([-/.])(\d{1,2})([-/.])(\d{4})").expect("valid regex"));
+
+static CLOCK_TIME: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?i)(\d{1,2}):(\d{2})(?::(\d{2}))?(?:[ \u{a0}]?([ap])\.?m\.?)?").expect("valid regex"));
+
+static HOUR_TIME: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(\d{1,2})(?:h(\d{2})|\.(\d{2})[ \u{a0}]?uhr|[ \u{a0}]?([ap])\.?m\.?)").expect("valid regex")
+});
+
+static CURRENCY_BEFORE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(r"({})[ \u{{a0}}\u{{202f}}]?({})", *CURRENCY_PATTERN, NUMBER)).expect("valid regex")
+});
+
+static CURRENCY_AFTER: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(r"({})[ \u{{a0}}\u{{202f}}]?({})", NUMBER, *CURRENCY_PATTERN)).expect("valid regex")
+});
+
+static QUANTITY: Lazy<Regex> = Lazy::new(|| {
+    let mut symbols: Vec<&str> = UNITS.iter().map(|unit| unit.symbol).collect();
+    symbols.sort_by_key(|symbol| std::cmp::Reverse(symbol.len()));
+    let symbols = symbols
+        .iter()
+        .map(|symbol| regex::escape(symbol))
+        .collect::<Vec<_>>()
+        .join("|");
+    Regex::new(&format!(r"([-−])?({})[ \u{{a0}}\u{{202f}}]?({})", NUMBER, symbols)).expect("valid regex")
+});
+
+static NUMBERS: Lazy<Regex> = Lazy::new(|| Regex::new(NUMBER).expect("valid regex"));
+
+/// A date, time, amount, quantity or number found in a text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NormalizedValue {
+    /// Kind of the value
+    pub kind: ValueKind,
+    /// The value as written in the text
+    pub text: String,
+    /// The normalized value: `YYYY-MM-DD`, `HH:MM[:SS]` or a decimal with `.`
+    pub value: String,
+    /// ISO 4217 code of a currency amount, or the unit symbol of a quantity
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unit: Option<String>,
+    /// Byte offset of the start of the value in the text
+    pub start: usize,
+    /// Byte offset of the end of the value in the text
+    pub end: usize,
+}
+
+/// Date and number conventions of a locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Locale {
+    day_first: bool,
+    decimal_comma: bool,
+}
+
+impl Locale {
+    /// Conventions of a language code such as `"de"`, `"deu"` or `"en-GB"`.
+    ///
+    /// English is month-first unless a region other than the US or the Philippines is
+    /// given. Languages that are not known to write decimal commas are read as English.
+    fn of(code: Option<&str>) -> Self {
+        let mut parts = code.unwrap_or("en").split(['-', '_']);
+        let language = parts.next().unwrap_or_default().to_lowercase();
+        let region = parts.next().map(str::to_uppercase);
+        let decimal_comma_language = DECIMAL_COMMA_LANGUAGES.contains(&language.as_str());
+        let non_us_region = region.as_deref().is_some_and(|region| region != "US" && region != "PH");
+
+        Self {
+            day_first: decimal_comma_language || non_us_region,
+            decimal_comma: decimal_comma_language && !matches!(region.as_deref(), Some("CH" | "LI")),
+        }
+    }
+}
+
+/// Find the dates, times, currency amounts, quantities and numbers in `text`.
+///
+/// `locale` is a language code such as `"de"`, `"fra"` or `"en-GB"` that decides how
+/// ambiguous numeric dates and numbers are read; English conventions are used when it
+/// is `None`. Quantities are converted to the units of `unit_system`. Values that
+/// overlap are resolved in favor of dates, then times, amounts and quantities, so the
+/// day of a date is not also reported as a number.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::core::config::UnitSystem;
+/// use kreuzberg::text::values::find_values;
+///
+/// let values = find_values("Am 5. März 2024 wurden 1.234,50 € gezahlt.", Some("de"), UnitSystem::Si);
+/// let normalized: Vec<&str> = values.iter().map(|value| value.value.as_str()).collect();
+/// assert_eq!(normalized, vec!["2024-03-05", "1234.50"]);
+/// assert_eq!(values[1].unit.as_deref(), Some("EUR"));
+///
+/// let values = find_values("Due 03/05/2024 at 30 psi", Some("en-US"), UnitSystem::Si);
+/// assert_eq!(values[0].value, "2024-03-05");
+/// assert_eq!((values[1].value.as_str(), values[1].unit.as_deref()), ("206842.719", Some("Pa")));
+/// ```
+pub fn find_values(text: &str, locale: Option<&str>, unit_system: UnitSystem) -> Vec<NormalizedValue> {
+    let locale = Locale::of(locale);
+    let mut found = Values::default();
+
+    found.collect(text, &TEXTUAL_DAY_FIRST, ValueKind::Date, |caps| {
+        textual_date(&caps[3], &caps[2], &caps[1]).map(|date| (date, None))
+    });
+    found.collect(text, &TEXTUAL_MONTH_FIRST, ValueKind::Date, |caps| {
+        textual_date(&caps[3], &caps[1], &caps[2]).map(|date| (date, None))
+    });
+    found.collect(text, &NUMERIC_YEAR_FIRST, ValueKind::Date, |caps| {
+        if caps[2] != caps[4] {
+            return None;
+        }
+        iso_date(caps[1].parse().ok()?, caps[3].parse().ok()?, caps[5].parse().ok()?).map(|date| (date, None))
+    });
+    found.collect(text, &NUMERIC_YEAR_LAST, ValueKind::Date, |caps| {
+        if caps[2] != caps[4] {
+            return None;
+        }
+        let year = caps[5].parse().ok()?;
+        let (first, second) = (caps[1].parse().ok()?, caps[3].parse().ok()?);
+        let (day, month) = if locale.day_first || &caps[2] == "." {
+            (first, second)
+        } else {
+            (second, first)
+        };
+        iso_date(year, month, day)
+            .or_else(|| iso_date(year, day, month))
+            .map(|date| (date, None))
+    });
+    found.collect(text, &CLOCK_TIME, ValueKind::Time, |caps| {
+        let seconds = caps.get(3).map(|seconds| seconds.as_str());
+        clock_time(&caps[1], &caps[2], seconds, caps.get(4).map(|m| m.as_str())).map(|time| (time, None))
+    });
+    found.collect(text, &HOUR_TIME, ValueKind::Time, |caps| {
+        let minutes = caps.get(2).or(caps.get(3)).map_or("00", |minutes| minutes.as_str());
+        clock_time(&caps[1], minutes, None, caps.get(4).map(|m| m.as_str())).map(|time| (time, None))
+    });
+    found.collect(text, &CURRENCY_BEFORE, ValueKind::Currency, |caps| {
+        Some((
+            parse_number(&caps[2], locale.decimal_comma)?,
+            Some(currency_code(&caps[1])),
+        ))
+    });
+    found.collect(text, &CURRENCY_AFTER, ValueKind::Currency, |caps| {
+        Some((
+            parse_number(&caps[1], locale.decimal_comma)?,
+            Some(currency_code(&caps[2])),
+        ))
+    });
+    found.collect_quantities(text, locale, unit_system);
+    found.collect(text, &NUMBERS, ValueKind::Number, |caps| {
+        parse_number(&caps[0], locale.decimal_comma).map(|number| (number, None))
+    });
+
+    found.0.into_values().collect()
+}
+
+/// Normalize the values in the content of `result` as configured.
+///
+/// The locale is `config.locale`, or the first detected language. In
+/// [`ValueAnnotationMode::Metadata`] the values of the content are stored in
+/// `metadata.additional["normalized_values"]`; in [`ValueAnnotationMode::Inline`] the
+/// normalized forms are appended in brackets to the content and the page contents,
+/// where they differ from the original text.
+pub fn normalize_values(result: &mut ExtractionResult, config: &ValueNormalizationConfig) {
+    let detected = result
+        .detected_languages
+        .as_ref()
+        .and_then(|languages| languages.first());
+    let locale = config.locale.clone().or_else(|| detected.cloned());
+    let find = |text: &str| -> Vec<NormalizedValue> {
+        find_values(text, locale.as_deref(), config.unit_system)
+            .into_iter()
+            .filter(|value| config.includes(value.kind))
+            .collect()
+    };
+
+    match config.mode {
+        ValueAnnotationMode::Metadata => {
+            let values = find(&result.content);
+            if let Ok(values) = serde_json::to_value(values) {
+                result
+                    .metadata
+                    .additional
+                    .insert(Cow::Borrowed(NORMALIZED_VALUES_KEY), values);
+            }
+        }
+        ValueAnnotationMode::Inline => {
+            result.content = annotate_inline(&result.content, &find(&result.content));
+            for page in result.pages.iter_mut().flatten() {
+                page.content = annotate_inline(&page.content, &find(&page.content));
+            }
+        }
+    }
+}
+
+/// Append ` [value]` after every value whose normalized form differs from its text.
+fn annotate_inline(text: &str, values: &[NormalizedValue]) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut copied = 0;
+    for value in values {
+        let normalized = match &value.unit {
+            Some(unit) => format!("{} {}", value.value, unit),
+            None => value.value.clone(),
+        };
+        if normalized == value.text {
+            continue;
+        }
+        out.push_str(&text[copied..value.end]);
+        out.push_str(" [");
+        out.push_str(&normalized);
+        out.push(']');
+        copied = value.end;
+    }
+    out.push_str(&text[copied..]);
+    out
+}
+
+/// Values found so far, by start offset, without overlaps.
+#[derive(Default)]
+struct Values(BTreeMap<usize, NormalizedValue>);
+
+impl Values {
+    /// Add the matches of `regex` normalized to a value and a unit.
+    fn collect(
+        &mut self,
+        text: &str,
+        regex: &Regex,
+        kind: ValueKind,
+        normalize: impl Fn(&Captures) -> Option<(String, Option<&'static str>)>,
+    ) {
+        for caps in regex.captures_iter(text) {
+            let whole = caps.get(0).expect("capture group 0 always exists");
+            if let Some((value, unit)) = normalize(&caps) {
+                self.insert(text, whole.range(), kind, value, unit);
+            }
+        }
+    }
+
+    /// Add the quantities of `text`, converted to `system`.
+    ///
+    /// A minus sign directly after a digit or letter is a hyphen (`10-20 km`), so it is
+    /// left out of the quantity.
+    fn collect_quantities(&mut self, text: &str, locale: Locale, system: UnitSystem) {
+        for caps in QUANTITY.captures_iter(text) {
+            let (Some(number), Some(symbol)) = (caps.get(2), caps.get(3)) else {
+                continue;
+            };
+            let Some(from) = UNITS.iter().find(|unit| unit.symbol == symbol.as_str()) else {
+                continue;
+            };
+            let Some(amount) = parse_number(number.as_str(), locale.decimal_comma).and_then(|n| n.parse::<f64>().ok())
+            else {
+                continue;
+            };
+            let sign = caps.get(1).filter(|sign| {
+                !text[..sign.start()]
+                    .chars()
+                    .next_back()
+                    .is_some_and(char::is_alphanumeric)
+            });
+            let (start, amount) = match sign {
+                Some(sign) => (sign.start(), -amount),
+                None => (number.start(), amount),
+            };
+
+            let target = from.dimension.target(system);
+            let to =
+                UNITS
+                    .iter()
+                    .find(|unit| unit.symbol == target)
+                    .copied()
+                    .unwrap_or(unit(target, from.dimension, 1.0));
+            let converted = ((amount * from.scale + from.offset) - to.offset) / to.scale;
+            self.insert(
+                text,
+                start..symbol.end(),
+                ValueKind::Quantity,
+                format_decimal(converted),
+                Some(to.symbol),
+            );
+        }
+    }
+
+    /// Add a value unless it is part of a longer word or number or overlaps a value
+    /// found before.
+    fn insert(&mut self, text: &str, span: Range<usize>, kind: ValueKind, value: String, unit: Option<&'static str>) {
+        if !is_delimited(text, span.start, span.end) || self.overlaps(span.start, span.end) {
+            return;
+        }
+        self.0.insert(
+            span.start,
+            NormalizedValue {
+                kind,
+                text: text[span.clone()].to_string(),
+                value,
+                unit: unit.map(str::to_string),
+                start: span.start,
+                end: span.end,
+            },
+        );
+    }
+
+    fn overlaps(&self, start: usize, end: usize) -> bool {
+        self.0
+            .range(..end)
+            .next_back()
+            .is_some_and(|(_, value)| value.end > start)
+    }
+}
+
+/// Whether `text[start..end]` is not part of a longer word or number.
+fn is_delimited(text: &str, start: usize, end: usize) -> bool {
+    fn clean(mut chars: impl Iterator<Item = char>) -> bool {
+        match chars.next() {
+            None => true,
+            Some(c) if c.is_alphanumeric() => false,
+            Some(c) if NUMBER_SEPARATORS.contains(&c) => !chars.next().is_some_and(|c| c.is_ascii_digit()),
+            Some(_) => true,
+        }
+    }
+    clean(text[..start].chars().rev()) && clean(text[end..].chars())
+}
+
+fn textual_date(year: &str, month: &str, day: &str) -> Option<String> {
+    let month = month.to_lowercase();
+    let month = MONTHS.iter().position(|names| names.contains(&month.as_str()))? as u32 + 1;
+    iso_date(year.parse().ok()?, month, day.parse().ok()?)
+}
+
+fn iso_date(year: u32, month: u32, day: u32) -> Option<String> {
+    let leap = year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400));
+    let days = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+    (1..=days)
+        .contains(&day)
+        .then(|| format!("{year:04}-{month:02}-{day:02}"))
+}
+
+fn clock_time(hours: &str, minutes: &str, seconds: Option<&str>, meridiem: Option<&str>) -> Option<String> {
+    let mut hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    if let Some(meridiem) = meridiem {
+        if !(1..=12).contains(&hours) {
+            return None;
+        }
+        hours %= 12;
+        if meridiem.eq_ignore_ascii_case("p") {
+            hours += 12;
+        }
+    }
+    if hours > 23 || minutes > 59 {
+        return None;
+    }
+    match seconds {
+        Some(seconds) => {
+            let seconds: u32 = seconds.parse().ok()?;
+            (seconds <= 59).then(|| format!("{hours:02}:{minutes:02}:{seconds:02}"))
+        }
+        None => Some(format!("{hours:02}:{minutes:02}")),
+    }
+}
+
+fn currency_code(currency: &str) -> &'static str {
+    CURRENCY_SYMBOLS
+        .iter()
+        .find(|(symbol, _)| *symbol == currency)
+        .map(|(_, code)| *code)
+        .or_else(|| CURRENCY_CODES.iter().copied().find(|code| *code == currency))
+        .unwrap_or("XXX")
+}
+
+/// `value` with nine significant digits, without trailing zeros.
+fn format_decimal(value: f64) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return "0".to_string();
+    }
+    let magnitude = value.abs().log10().floor() as i32;
+    let decimals = (8 - magnitude).max(0) as usize;
+    let formatted = format!("{value:.decimals$}");
+    let formatted = if formatted.contains('.') {
+        formatted.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        &formatted
+    };
+    match formatted {
+        "-0" => "0".to_string(),
+        formatted => formatted.to_string(),
+    }
+}
+
+/// The decimal form of `number`, with `.` as the decimal separator and no grouping.
+///
+/// When both `.` and `,` occur, the last one is the decimal separator. A single
+/// separator is a decimal separator if it is the locale's, or if it is not followed by
+/// exactly three digits; repeated separators group thousands.
+fn parse_number(number: &str, decimal_comma: bool) -> Option<String> {
+    let number: String = number
+        .chars()
+        .filter(|c| !matches!(c, '\'' | '\u{a0}' | '\u{202f}'))
+        .collect();
+    let dots = number.matches('.').count();
+    let commas = number.matches(',').count();
+    let locale_separator = if decimal_comma { ',' } else { '.' };
+
+    let decimal = match (dots, commas) {
+        (0, 0) => None,
+        (1.., 1..) => {
+            let separator = number[number.rfind(['.', ','])?..].chars().next()?;
+            if number.matches(separator).count() > 1 {
+                return None;
+            }
+            Some(separator)
+        }
+        _ => {
+            let (separator, count) = if dots > 0 { ('.', dots) } else { (',', commas) };
+            let fraction_digits = number.len() - number.rfind(separator)? - 1;
+            (count == 1 && (separator == locale_separator || fraction_digits != 3)).then_some(separator)
+        }
+    };
+
+    let (integer, fraction) = match decimal {
+        Some(separator) => number.split_once(separator)?,
+        None => (number.as_str(), ""),
+    };
+    let integer: String = integer.chars().filter(char::is_ascii_digit).collect();
+    let integer = match integer.trim_start_matches('0') {
+        "" => "0",
+        trimmed => trimmed,
+    };
+    Some(if fraction.is_empty() {
+        integer.to_string()
+    } else {
+        format!("{integer}.{fraction}")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn values(text: &str, locale: Option<&str>) -> Vec<(ValueKind, String)> {
+        find_values(text, locale, UnitSystem::Si)
+            .into_iter()
+            .map(|value| (value.kind, value.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_find_dates() {
+        assert_eq!(
+            values(
+                "Signed on March 5th, 2024, effective 5 de marzo de 2024 and 2024-03-05.",
+                None
+            ),
+            vec![
+                (ValueKind::Date, "2024-03-05".to_string()),
+                (ValueKind::Date, "2024-03-05".to_string()),
+                (ValueKind::Date, "2024-03-05".to_string()),
+            ]
+        );
+        assert_eq!(values("03/05/2024", None)[0].1, "2024-03-05");
+        assert_eq!(values("03/05/2024", Some("en-GB"))[0].1, "2024-05-03");
+        assert_eq!(values("03/05/2024", Some("fra"))[0].1, "2024-05-03");
+        assert_eq!(values("05.03.2024", None)[0].1, "2024-03-05");
+        assert_eq!(values("25/12/2024", Some("en-US"))[0].1, "2024-12-25");
+        assert_eq!(values("29.02.2024", None)[0].1, "2024-02-29");
+        assert!(values("30.02.2024", None).is_empty());
+    }
+
+    #[test]
+    fn test_find_times() {
+        assert_eq!(
+            values(
+                "Open 9:05 am to 5 p.m., Treffen um 14.30 Uhr, RDV à 14h30, at 23:59:59.",
+                None
+            ),
+            vec![
+                (ValueKind::Time, "09:05".to_string()),
+                (ValueKind::Time, "17:00".to_string()),
+                (ValueKind::Time, "14:30".to_string()),
+                (ValueKind::Time, "14:30".to_string()),
+                (ValueKind::Time, "23:59:59".to_string()),
+            ]
+        );
+        assert!(
+            values("at 25:00 or 13 pm", None)
+                .iter()
+                .all(|(kind, _)| *kind == ValueKind::Number)
+        );
+    }
+
+    #[test]
+    fn test_find_amounts_and_numbers() {
+        let found = find_values(
+            "Paid $1,234.56, then 1.234,56 € and EUR 99; 1,5 kg of 3.000 units.",
+            Some("de"),
+            UnitSystem::Si,
+        );
+        let summary: Vec<(ValueKind, &str, Option<&str>)> = found
+            .iter()
+            .map(|value| (value.kind, value.value.as_str(), value.unit.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (ValueKind::Currency, "1234.56", Some("USD")),
+                (ValueKind::Currency, "1234.56", Some("EUR")),
+                (ValueKind::Currency, "99", Some("EUR")),
+                (ValueKind::Quantity, "1.5", Some("kg")),
+                (ValueKind::Number, "3000", None),
+            ]
+        );
+        assert_eq!(found[1].text, "1.234,56 €");
+
+        assert_eq!(values("1,234 and 1.234", Some("en"))[0].1, "1234");
+        assert_eq!(values("1,234 and 1.234", Some("en"))[1].1, "1.234");
+        assert_eq!(values("1'234.50 CHF", Some("de-CH"))[0].1, "1234.50");
+        assert_eq!(values("1\u{a0}234,5", Some("fr"))[0].1, "1234.5");
+        assert!(values("v1.2, section 5.3.1, COVID19 and 3rd", None).is_empty());
+    }
+
+    #[test]
+    fn test_find_quantities() {
+        let quantities = |text: &str, system: UnitSystem| -> Vec<(String, String, String)> {
+            find_values(text, None, system)
+                .into_iter()
+                .filter(|value| value.kind == ValueKind::Quantity)
+                .map(|value| (value.text, value.value, value.unit.unwrap_or_default()))
+                .collect()
+        };
+        let quantity = |text: &str, value: &str, unit: &str| (text.to_string(), value.to_string(), unit.to_string());
+
+        assert_eq!(
+            quantities(
+                "Rated 30 psi at -40 °C to 85°C over 12 km, 2.5 mm gap, 1,200 lbs and 60 mph.",
+                UnitSystem::Si
+            ),
+            vec![
+                quantity("30 psi", "206842.719", "Pa"),
+                quantity("-40 °C", "233.15", "K"),
+                quantity("85°C", "358.15", "K"),
+                quantity("12 km", "12000", "m"),
+                quantity("2.5 mm", "0.0025", "m"),
+                quantity("1,200 lbs", "544.310844", "kg"),
+                quantity("60 mph", "26.8224", "m/s"),
+            ]
+        );
+        assert_eq!(
+            quantities("2 bar, 100 °C and 10-20 km", UnitSystem::Imperial),
+            vec![
+                quantity("2 bar", "29.0075475", "psi"),
+                quantity("100 °C", "212", "°F"),
+                quantity("20 km", "65616.7979", "ft"),
+            ]
+        );
+        assert!(quantities("5 in total, a 4K screen, 3 min", UnitSystem::Si).is_empty());
+    }
+
+    #[test]
+    fn test_normalize_values_inline_and_in_metadata() {
+        use crate::types::{Metadata, PageContent};
+
+        let mut result = ExtractionResult {
+            content: "Fällig am 5. März 2024: 12 Stück zu 1.000,50 €.".to_string(),
+            mime_type: Cow::Borrowed("text/plain"),
+            metadata: Metadata::default(),
+            tables: vec![],
+            detected_languages: Some(vec!["deu".to_string()]),
+            chunks: None,
+            images: None,
+            djot_content: None,
+            scripts: None,
+            pages: Some(vec![PageContent {
+                page_number: 1,
+                content: "Am 01.04.2024".to_string(),
+                tables: vec![],
+                images: vec![],
+                hierarchy: None,
+            }]),
+            elements: None,
+        };
+
+        let mut metadata_result = result.clone();
+        normalize_values(
+            &mut metadata_result,
+            &ValueNormalizationConfig {
+                kinds: vec![ValueKind::Date, ValueKind::Currency],
+                ..Default::default()
+            },
+        );
+        let stored: Vec<NormalizedValue> =
+            serde_json::from_value(metadata_result.metadata.additional[NORMALIZED_VALUES_KEY].clone()).unwrap();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].value, "2024-03-05");
+        assert_eq!(&metadata_result.content[stored[1].start..stored[1].end], "1.000,50 €");
+
+        normalize_values(
+            &mut result,
+            &ValueNormalizationConfig {
+                mode: ValueAnnotationMode::Inline,
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            result.content,
+            "Fällig am 5. März 2024 [2024-03-05]: 12 Stück zu 1.000,50 € [1000.50 EUR]."
+        );
+        assert_eq!(result.pages.unwrap()[0].content, "Am 01.04.2024 [2024-04-01]");
+        assert!(!result.metadata.additional.contains_key(NORMALIZED_VALUES_KEY));
+    }
+}
@@ -0,0 +1,201 @@
+//! Watermark detection and removal.
+//!
+//! Watermarks such as "CONFIDENTIAL DRAFT" are usually drawn as a rotated (diagonal)
+//! overlay or repeated verbatim on every page. Text extraction picks them up as
+//! ordinary lines, which pollutes the content of every page.
+//!
+//! Detection works on per-page text and is format-agnostic. Extractors that know
+//! which text runs are rotated (e.g. PDF) pass those runs as additional evidence.
+
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+
+/// Keywords that commonly appear in horizontal watermark overlays.
+const WATERMARK_KEYWORDS: &[&str] = &[
+    "CONFIDENTIAL",
+    "DRAFT",
+    "COPY",
+    "SAMPLE",
+    "SPECIMEN",
+    "PROPRIETARY",
+    "INTERNAL",
+    "SECRET",
+    "PREVIEW",
+    "VOID",
+    "DO NOT DISTRIBUTE",
+];
+
+/// Maximum length of a line that can be considered a watermark.
+const MAX_WATERMARK_CHARS: usize = 64;
+
+/// Minimum number of alphanumeric characters for a watermark candidate.
+const MIN_WATERMARK_ALNUM: usize = 3;
+
+/// Detect watermark text across the pages of a document.
+///
+/// A line is reported as a watermark when it is short and either:
+/// - it was drawn as rotated text (`rotated_runs`) on at least `min_page_ratio` of the pages, or
+/// - it is an all-caps line containing a typical watermark keyword and repeats on at least
+///   `min_page_ratio` of the pages (and on at least two pages).
+///
+/// # Arguments
+///
+/// * `pages` - Extracted text of each page
+/// * `rotated_runs` - Rotated text runs per page (empty slice when unavailable)
+/// * `min_page_ratio` - Minimum fraction of pages a candidate has to appear on
+///
+/// # Returns
+///
+/// Normalized watermark strings in order of first appearance.
+pub fn detect_watermarks(pages: &[&str], rotated_runs: &[Vec<String>], min_page_ratio: f64) -> Vec<String> {
+    if pages.is_empty() {
+        return Vec::new();
+    }
+
+    let page_count = pages.len();
+    let min_pages = ((page_count as f64) * min_page_ratio.clamp(0.0, 1.0)).ceil().max(1.0) as usize;
+
+    let mut order: Vec<String> = Vec::new();
+    let mut rotated_pages: HashMap<String, usize> = HashMap::new();
+    for runs in rotated_runs {
+        let unique: HashSet<String> = runs
+            .iter()
+            .map(|run| normalize_watermark_line(run))
+            .filter(|run| is_candidate(run))
+            .collect();
+        for run in unique {
+            if !rotated_pages.contains_key(&run) {
+                order.push(run.clone());
+            }
+            *rotated_pages.entry(run).or_insert(0) += 1;
+        }
+    }
+
+    let mut repeated_pages: HashMap<String, usize> = HashMap::new();
+    for page in pages {
+        let unique: HashSet<String> = page
+            .lines()
+            .map(normalize_watermark_line)
+            .filter(|line| is_candidate(line) && looks_like_overlay(line))
+            .collect();
+        for line in unique {
+            if !repeated_pages.contains_key(&line) && !rotated_pages.contains_key(&line) {
+                order.push(line.clone());
+            }
+            *repeated_pages.entry(line).or_insert(0) += 1;
+        }
+    }
+
+    order
+        .into_iter()
+        .filter(|candidate| {
+            let rotated = rotated_pages.get(candidate).copied().unwrap_or(0);
+            let repeated = repeated_pages.get(candidate).copied().unwrap_or(0);
+            rotated >= min_pages || (repeated >= min_pages && repeated >= 2)
+        })
+        .collect()
+}
+
+/// Remove lines matching any of the given watermarks from `text`.
+///
+/// Lines are compared after normalization (see [`normalize_watermark_line`]), so
+/// differences in surrounding whitespace do not prevent a match. Returns the input
+/// unchanged (borrowed) when nothing was removed.
+pub fn strip_watermark_lines<'a>(text: &'a str, watermarks: &[String]) -> Cow<'a, str> {
+    if watermarks.is_empty() || text.is_empty() {
+        return Cow::Borrowed(text);
+    }
+
+    let watermark_set: HashSet<&str> = watermarks.iter().map(String::as_str).collect();
+    let mut removed_any = false;
+    let mut kept: Vec<&str> = Vec::new();
+
+    for line in text.split('\n') {
+        if watermark_set.contains(normalize_watermark_line(line).as_str()) {
+            removed_any = true;
+        } else {
+            kept.push(line);
+        }
+    }
+
+    if removed_any {
+        Cow::Owned(kept.join("\n"))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// Normalize a line for watermark comparison by trimming and collapsing whitespace.
+pub fn normalize_watermark_line(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn is_candidate(line: &str) -> bool {
+    line.chars().count() <= MAX_WATERMARK_CHARS
+        && line.chars().filter(|c| c.is_alphanumeric()).count() >= MIN_WATERMARK_ALNUM
+}
+
+fn looks_like_overlay(line: &str) -> bool {
+    let has_lowercase = line.chars().any(|c| c.is_lowercase());
+    !has_lowercase && WATERMARK_KEYWORDS.iter().any(|keyword| line.contains(keyword))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_repeated_keyword_overlay() {
+        let pages = [
+            "Page one body text.\nCONFIDENTIAL DRAFT\nMore text.",
+            "CONFIDENTIAL  DRAFT\nSecond page body.",
+            "Third page body.\n  CONFIDENTIAL DRAFT  ",
+        ];
+        let watermarks = detect_watermarks(&pages, &[], 0.5);
+        assert_eq!(watermarks, vec!["CONFIDENTIAL DRAFT".to_string()]);
+    }
+
+    #[test]
+    fn test_ignores_repeated_lowercase_headers() {
+        let pages = ["Acme confidential report\nBody", "Acme confidential report\nBody two"];
+        assert!(detect_watermarks(&pages, &[], 0.5).is_empty());
+    }
+
+    #[test]
+    fn test_single_page_overlay_not_detected_without_rotation() {
+        let pages = ["CONFIDENTIAL\nBody"];
+        assert!(detect_watermarks(&pages, &[], 0.5).is_empty());
+    }
+
+    #[test]
+    fn test_rotated_runs_detected_without_keyword() {
+        let pages = ["Body\nACME INTERNAL USE", "Body\nACME INTERNAL USE"];
+        let rotated = vec![vec!["Acme Corp".to_string()], vec!["Acme  Corp".to_string()]];
+        let watermarks = detect_watermarks(&pages, &rotated, 0.5);
+        assert_eq!(
+            watermarks,
+            vec!["Acme Corp".to_string(), "ACME INTERNAL USE".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_rotated_runs_below_ratio_ignored() {
+        let pages = ["a", "b", "c", "d"];
+        let rotated = vec![vec!["Stamp".to_string()], vec![], vec![], vec![]];
+        assert!(detect_watermarks(&pages, &rotated, 0.5).is_empty());
+    }
+
+    #[test]
+    fn test_strip_watermark_lines() {
+        let watermarks = vec!["CONFIDENTIAL DRAFT".to_string()];
+        let stripped = strip_watermark_lines("Intro\n  CONFIDENTIAL   DRAFT\nBody", &watermarks);
+        assert_eq!(stripped, "Intro\nBody");
+    }
+
+    #[test]
+    fn test_strip_watermark_lines_borrows_when_unchanged() {
+        let watermarks = vec!["DRAFT".to_string()];
+        let stripped = strip_watermark_lines("This is a draft of the text", &watermarks);
+        assert!(matches!(stripped, Cow::Borrowed(_)));
+    }
+}
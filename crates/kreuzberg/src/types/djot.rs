@@ -124,7 +124,10 @@ pub struct InlineElement {
     pub attributes: Option<Attributes>,
 
     /// Additional metadata (e.g., href for links, src/alt for images)
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "super::serde_helpers::sorted_map::serialize_option"
+    )]
     pub metadata: Option<HashMap<String, String>>,
 }
 
@@ -72,6 +72,14 @@ pub struct ExtractionResult {
     #[serde(skip_serializing_if = "Option::is_none")]
     #[serde(default)]
     pub djot_content: Option<DjotContent>,
+
+    /// Macro and script source code found in the document.
+    ///
+    /// When `SecurityConfig::extract_scripts` is enabled, VBA and Basic macros of Office
+    /// documents and JavaScript of PDFs are collected here for analysis. Script source
+    /// is never added to `content`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub scripts: Option<Vec<ExtractedScript>>,
 }
 
 /// A text chunk with optional embedding and metadata.
@@ -129,6 +137,28 @@ pub struct ChunkMetadata {
     /// Only populated when page tracking is enabled in extraction configuration.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_page: Option<usize>,
+
+    /// Kind of content in this chunk.
+    ///
+    /// `Table` only when table-aware chunking (`ChunkingConfig::tables`) is enabled.
+    #[serde(default)]
+    pub chunk_type: ChunkType,
+
+    /// Additional metadata attached by chunk processors (entities, keywords, section path, ...).
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub additional: HashMap<String, serde_json::Value>,
+}
+
+/// Kind of content a chunk holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkType {
+    /// Running text, possibly including headings and lists
+    #[default]
+    Text,
+    /// A whole table, or a group of its rows under the repeated header row
+    Table,
 }
 
 /// Extracted image from a document.
@@ -189,6 +219,38 @@ pub struct ExtractedImage {
     pub ocr_result: Option<Box<ExtractionResult>>,
 }
 
+/// Macro or script extracted from a document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct ExtractedScript {
+    /// Script language
+    pub language: ScriptLanguage,
+
+    /// Module name for macros, or the PDF object holding the script (e.g. "object 12")
+    pub name: String,
+
+    /// Package part or storage the script was read from (e.g. "word/vbaProject.bin"), when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+
+    /// Script source code
+    pub source: String,
+}
+
+/// Language of an [`ExtractedScript`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum ScriptLanguage {
+    /// Visual Basic for Applications (Microsoft Office macros)
+    Vba,
+    /// StarBasic (OpenDocument macros)
+    Basic,
+    /// JavaScript (PDF)
+    #[serde(rename = "javascript")]
+    JavaScript,
+}
+
 // ============================================================================
 // Element-based Output Format Types (Unstructured-compatible)
 // ============================================================================
@@ -300,6 +362,7 @@ pub struct ElementMetadata {
     /// Position index in the element sequence
     pub element_index: Option<usize>,
     /// Additional custom metadata
+    #[serde(serialize_with = "super::serde_helpers::sorted_map::serialize")]
     pub additional: HashMap<String, String>,
 }
 
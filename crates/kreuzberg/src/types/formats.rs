@@ -6,8 +6,9 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 
 use super::extraction::ExtractedImage;
-use super::metadata::PptxMetadata;
+use super::metadata::{CellComment, DefinedName, PptxMetadata, SheetMetadata};
 use super::page::{PageContent, PageStructure};
+use super::tables::Chart;
 
 /// Excel workbook representation.
 ///
@@ -19,6 +20,15 @@ pub struct ExcelWorkbook {
     pub sheets: Vec<ExcelSheet>,
     /// Workbook-level metadata (author, creation date, etc.)
     pub metadata: HashMap<String, String>,
+    /// Every sheet of the workbook, including sheets that were not extracted
+    #[serde(default)]
+    pub sheet_metadata: Vec<SheetMetadata>,
+    /// Named ranges and constants, when requested by `SpreadsheetConfig::defined_names`
+    #[serde(default)]
+    pub defined_names: Vec<DefinedName>,
+    /// Comments of the extracted sheets, when requested by `SpreadsheetConfig::comments`
+    #[serde(default)]
+    pub comments: Vec<CellComment>,
 }
 
 /// Single Excel worksheet.
@@ -42,6 +52,9 @@ pub struct ExcelSheet {
     /// None for empty sheets.
     #[serde(skip)]
     pub table_cells: Option<Vec<Vec<String>>>,
+    /// Series data of the charts placed on the sheet
+    #[serde(default)]
+    pub charts: Vec<Chart>,
 }
 
 /// XML extraction result.
@@ -58,6 +71,32 @@ pub struct XmlExtractionResult {
     pub unique_elements: Vec<String>,
 }
 
+/// SVG extraction result.
+///
+/// Contains the rendered text of an SVG drawing along with the position of each text line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SvgExtractionResult {
+    /// Text lines of the drawing in document order, separated by newlines
+    pub content: String,
+    /// Document title (`<title>` of the root element)
+    pub title: Option<String>,
+    /// Document description (`<desc>` of the root element)
+    pub description: Option<String>,
+    /// Text lines with their positions
+    pub texts: Vec<SvgText>,
+}
+
+/// Line of text in an SVG drawing.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SvgText {
+    /// Text with whitespace collapsed
+    pub text: String,
+    /// `x` coordinate in user units, before transforms
+    pub x: Option<f64>,
+    /// `y` coordinate (baseline) in user units, before transforms
+    pub y: Option<f64>,
+}
+
 /// Plain text and Markdown extraction result.
 ///
 /// Contains the extracted text along with statistics and,
@@ -92,6 +131,9 @@ pub struct PptxExtractionResult {
     pub content: String,
     /// Presentation metadata
     pub metadata: PptxMetadata,
+    /// Core, extended and custom document properties, keyed as in the result metadata
+    #[serde(default)]
+    pub properties: HashMap<String, serde_json::Value>,
     /// Total number of slides
     pub slide_count: usize,
     /// Total number of embedded images
@@ -100,6 +142,9 @@ pub struct PptxExtractionResult {
     pub table_count: usize,
     /// Extracted images from the presentation
     pub images: Vec<ExtractedImage>,
+    /// Series data of embedded charts, in slide and reading order
+    #[serde(default)]
+    pub charts: Vec<Chart>,
     /// Slide structure with boundaries (when page tracking is enabled)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub page_structure: Option<PageStructure>,
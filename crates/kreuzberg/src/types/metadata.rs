@@ -11,14 +11,16 @@ use std::collections::{BTreeMap, HashMap};
 #[cfg(feature = "pdf")]
 use crate::pdf::metadata::PdfMetadata;
 
+use super::extraction::BoundingBox;
 use super::formats::ImagePreprocessingMetadata;
 use super::page::PageStructure;
 
 /// Custom serialization and deserialization for AHashMap<Cow<'static, str>, Value>.
 ///
 /// serde doesn't natively support serializing Cow keys, so we convert to/from
-/// a HashMap<String, Value> for the wire format, while keeping the in-memory
+/// a map with String keys for the wire format, while keeping the in-memory
 /// representation optimized with Cow keys (avoiding allocations for static strings).
+/// Keys are serialized in sorted order so identical results serialize identically.
 mod additional_serde {
     use super::*;
 
@@ -26,10 +28,8 @@ mod additional_serde {
     where
         S: Serializer,
     {
-        // Convert to HashMap for serialization
-        let converted: HashMap<String, serde_json::Value> =
-            map.iter().map(|(k, v)| (k.to_string(), v.clone())).collect();
-        converted.serialize(serializer)
+        let sorted: BTreeMap<&str, &serde_json::Value> = map.iter().map(|(k, v)| (k.as_ref(), v)).collect();
+        sorted.serialize(serializer)
     }
 
     pub fn deserialize<'de, D>(deserializer: D) -> Result<AHashMap<Cow<'static, str>, serde_json::Value>, D::Error>
@@ -68,7 +68,10 @@ pub enum FormatMetadata {
 /// Extraction result metadata.
 ///
 /// Contains common fields applicable to all formats, format-specific metadata
-/// via a discriminated union, and additional custom fields from postprocessors.
+/// via a discriminated union, and additional custom fields from extractors and
+/// postprocessors. The extraction pipeline fills the common fields for every format
+/// (see [`Metadata::promote_common_fields`]), so consumers do not need to look them
+/// up in `additional`.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 #[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
 pub struct Metadata {
@@ -108,6 +111,25 @@ pub struct Metadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub modified_by: Option<String>,
 
+    /// Application that produced the document (PDF producer, office application,
+    /// generator)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub producer: Option<String>,
+
+    /// Number of pages, slides or sheets
+    ///
+    /// Taken from the document properties when declared there, otherwise from the page
+    /// structure.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_count: Option<usize>,
+
+    /// Number of words
+    ///
+    /// Taken from the document properties when declared there, otherwise counted in
+    /// the extracted content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub word_count: Option<usize>,
+
     /// Page/slide/sheet structure with boundaries
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pages: Option<PageStructure>,
@@ -132,10 +154,12 @@ pub struct Metadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub error: Option<ErrorMetadata>,
 
-    /// Additional custom fields from postprocessors.
+    /// Additional custom fields from extractors and postprocessors.
     ///
-    /// This flattened map allows Python/TypeScript postprocessors to add
-    /// arbitrary fields (entity extraction, keyword extraction, etc.).
+    /// This flattened map is the escape hatch for fields without a place in the typed
+    /// metadata: format details without a format-specific struct, custom document
+    /// properties, and fields added by Python/TypeScript postprocessors (entity
+    /// extraction, keyword extraction, etc.).
     /// Fields are merged at the root level during serialization.
     /// Uses `Cow<'static, str>` keys so static string keys avoid allocation.
     #[serde(
@@ -147,6 +171,99 @@ pub struct Metadata {
     pub additional: AHashMap<Cow<'static, str>, serde_json::Value>,
 }
 
+impl Metadata {
+    /// Move common fields that extractors and plugins recorded in `additional` into the
+    /// typed fields.
+    ///
+    /// Handles `title`, `subject`, `authors` and `author`, `keywords`, `language`,
+    /// `created_at`, `modified_at`, `created_by`, `modified_by`, `producer`,
+    /// `application` and `generator`, `page_count` and `word_count`. Typed fields that
+    /// are already set take precedence; entries whose value does not fit the typed field,
+    /// such as extracted keyword lists with scores, stay in `additional`.
+    pub fn promote_common_fields(&mut self) {
+        let additional = &mut self.additional;
+
+        promote(additional, "title", &mut self.title, string_value);
+        promote(additional, "subject", &mut self.subject, string_value);
+        promote(additional, "authors", &mut self.authors, string_list_value);
+        promote(additional, "author", &mut self.authors, string_list_value);
+        promote(additional, "keywords", &mut self.keywords, keyword_list_value);
+        promote(additional, "language", &mut self.language, string_value);
+        promote(additional, "created_at", &mut self.created_at, string_value);
+        promote(additional, "modified_at", &mut self.modified_at, string_value);
+        promote(additional, "created_by", &mut self.created_by, string_value);
+        promote(additional, "modified_by", &mut self.modified_by, string_value);
+        promote(additional, "producer", &mut self.producer, string_value);
+        promote(additional, "application", &mut self.producer, string_value);
+        promote(additional, "generator", &mut self.producer, string_value);
+        promote(additional, "page_count", &mut self.page_count, count_value);
+        promote(additional, "word_count", &mut self.word_count, count_value);
+    }
+}
+
+/// Move `additional[key]` into `field` when it converts, unless `field` is already set.
+///
+/// An entry under the field's own name is dropped when the field is already set, as
+/// both would be serialized under the same key.
+fn promote<T>(
+    additional: &mut AHashMap<Cow<'static, str>, serde_json::Value>,
+    key: &'static str,
+    field: &mut Option<T>,
+    convert: fn(&serde_json::Value) -> Option<T>,
+) {
+    let Some(converted) = additional.get(key).and_then(convert) else {
+        return;
+    };
+    if field.is_none() {
+        *field = Some(converted);
+        additional.remove(key);
+    } else if !matches!(key, "author" | "application" | "generator") {
+        additional.remove(key);
+    }
+}
+
+fn string_value(value: &serde_json::Value) -> Option<String> {
+    value
+        .as_str()
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+        .map(str::to_string)
+}
+
+fn string_list_value(value: &serde_json::Value) -> Option<Vec<String>> {
+    match value {
+        serde_json::Value::Array(items) => {
+            let list: Option<Vec<String>> = items.iter().map(string_value).collect();
+            list.filter(|list| !list.is_empty())
+        }
+        _ => string_value(value).map(|text| vec![text]),
+    }
+}
+
+/// Keyword lists, or comma-separated keywords in a single string.
+fn keyword_list_value(value: &serde_json::Value) -> Option<Vec<String>> {
+    match value {
+        serde_json::Value::String(text) => {
+            let keywords: Vec<String> = text
+                .split(',')
+                .map(str::trim)
+                .filter(|keyword| !keyword.is_empty())
+                .map(str::to_string)
+                .collect();
+            (!keywords.is_empty()).then_some(keywords)
+        }
+        _ => string_list_value(value),
+    }
+}
+
+fn count_value(value: &serde_json::Value) -> Option<usize> {
+    match value {
+        serde_json::Value::Number(number) => number.as_u64().and_then(|count| usize::try_from(count).ok()),
+        serde_json::Value::String(text) => text.trim().parse().ok(),
+        _ => None,
+    }
+}
+
 /// Excel/spreadsheet metadata.
 ///
 /// Contains information about sheets in Excel, LibreOffice Calc, and other
@@ -158,6 +275,240 @@ pub struct ExcelMetadata {
     pub sheet_count: usize,
     /// Names of all sheets in order
     pub sheet_names: Vec<String>,
+    /// Every sheet of the workbook, including sheets that were not extracted
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sheets: Vec<SheetMetadata>,
+    /// Named ranges and constants, when `SpreadsheetConfig::defined_names` is set
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub defined_names: Vec<DefinedName>,
+    /// Cell comments and notes of the extracted sheets, when `SpreadsheetConfig::comments` is set
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<CellComment>,
+}
+
+/// Per-sheet spreadsheet metadata.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct SheetMetadata {
+    /// Sheet name as it appears in the workbook
+    pub name: String,
+    /// Visibility of the sheet
+    pub visibility: SheetVisibility,
+    /// Whether the sheet was extracted (false when excluded by `SpreadsheetConfig`)
+    pub extracted: bool,
+    /// Rows of the used range, if the sheet was read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row_count: Option<usize>,
+    /// Columns of the used range, if the sheet was read
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub col_count: Option<usize>,
+    /// Used range in A1 notation, such as `A1:D20` (None for empty or unread sheets)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dimensions: Option<String>,
+    /// Whether rows beyond `SpreadsheetConfig::max_rows` were left out
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+/// Visibility of a spreadsheet sheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SheetVisibility {
+    Visible,
+    Hidden,
+    /// Hidden and not unhideable from the user interface (Excel formats only)
+    VeryHidden,
+}
+
+/// Named range or constant defined in a workbook.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct DefinedName {
+    /// Name as used in formulas
+    pub name: String,
+    /// What the name refers to, such as `Summary!$B$2:$B$13` or a constant
+    pub refers_to: String,
+}
+
+/// Comment or note attached to a spreadsheet cell.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct CellComment {
+    /// Name of the sheet containing the cell
+    pub sheet: String,
+    /// Cell reference in A1 notation
+    pub cell: String,
+    /// Comment author, if recorded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Comment text
+    pub text: String,
+}
+
+/// Barcode or QR code decoded from an image or PDF page.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct Barcode {
+    /// Decoded payload
+    pub value: String,
+    /// Symbology, such as `qr_code`, `data_matrix` or `code_128`
+    pub format: String,
+    /// Page the symbol was found on (1-indexed), for PDFs and TIFFs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_number: Option<usize>,
+    /// Position of the symbol: the finder pattern centers of 2D symbols or the ends of
+    /// the decoded scan line of linear ones
+    pub points: Vec<BarcodePoint>,
+}
+
+/// Point measured from the top-left corner, in pixels for images and in points for PDF pages.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct BarcodePoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+/// Ink signature or stamp found on an image or page.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct Signature {
+    /// Whether the mark is a handwritten signature or a stamp
+    pub kind: SignatureKind,
+    /// Page the mark was found on (1-indexed), for PDFs and TIFFs
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_number: Option<usize>,
+    /// Enclosing box: `x0`/`y0` is the top-left and `x1`/`y1` the bottom-right corner,
+    /// in pixels for images and in points for PDF pages
+    pub bbox: BoundingBox,
+}
+
+/// Kind of a detected [`Signature`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureKind {
+    /// Handwritten signature or initials
+    Signature,
+    /// Ink stamp or seal
+    Stamp,
+}
+
+/// Page repeating an earlier page of the same document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct DuplicatePage {
+    /// Number of the repeating page (1-indexed)
+    pub page_number: usize,
+    /// Number of the earlier page it repeats (1-indexed)
+    pub duplicate_of: usize,
+    /// Whether the page was left out of the output
+    pub dropped: bool,
+}
+
+/// Nested container that was not extracted.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct NestingWarning {
+    /// Path of the container within the input, with `/` between nesting levels
+    /// (e.g. "outer.zip/inner.zip")
+    pub path: String,
+    /// Why the container was skipped
+    pub kind: NestingWarningKind,
+    /// Human-readable explanation
+    pub message: String,
+}
+
+/// Reason a [`NestingWarning`] was recorded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum NestingWarningKind {
+    /// The container is nested deeper than `ContainerConfig::max_depth`
+    MaxDepth,
+    /// The container has the same content as a container enclosing it
+    Cycle,
+    /// The container could not be read
+    Unreadable,
+}
+
+/// Remote resource referenced by a document.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct RemoteResource {
+    /// URL as written in the document
+    pub url: String,
+    /// How the document uses the resource
+    pub kind: RemoteResourceKind,
+    /// Outcome of the network policy
+    pub status: RemoteResourceStatus,
+    /// Why the resource was blocked or could not be fetched
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// How a document uses a [`RemoteResource`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteResourceKind {
+    /// Image (`<img>`, SVG `<image>`, EPUB manifest image)
+    Image,
+    /// Stylesheet (`<link rel="stylesheet">`, `<?xml-stylesheet?>`)
+    Stylesheet,
+    /// Script (`<script src>`)
+    Script,
+    /// Embedded document (`<iframe>`, `<object>`, `<embed>`, SVG `<use>`)
+    Embed,
+    /// External DTD or entity in an XML document type declaration
+    Entity,
+    /// Any other resource
+    Other,
+}
+
+/// Outcome of the network policy for a [`RemoteResource`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum RemoteResourceStatus {
+    /// Not fetched because the policy denies it
+    Blocked,
+    /// Allowed by the policy but not needed for the requested output
+    Allowed,
+    /// Fetched and added to the result
+    Fetched,
+    /// Allowed by the policy, but fetching failed
+    Failed,
+}
+
+/// Content found in an input that can run code when the document is opened.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct SecurityFinding {
+    /// What was found
+    pub kind: SecurityFindingKind,
+    /// Where it was found (e.g. "word/vbaProject.bin", "object 12"), when known
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    /// Human-readable explanation
+    pub message: String,
+}
+
+/// Kind of content reported by a [`SecurityFinding`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+#[serde(rename_all = "snake_case")]
+pub enum SecurityFindingKind {
+    /// VBA or other macros in an Office document
+    Macros,
+    /// JavaScript in a PDF
+    #[serde(rename = "javascript")]
+    JavaScript,
+    /// PDF action that launches an application or opens a file
+    LaunchAction,
+    /// Executable or script member in an archive
+    Executable,
 }
 
 /// Email metadata extracted from .eml and .msg files.
@@ -223,6 +574,7 @@ pub struct ImageMetadata {
     /// Image format (e.g., "PNG", "JPEG", "TIFF")
     pub format: String,
     /// EXIF metadata tags
+    #[serde(serialize_with = "super::serde_helpers::sorted_map::serialize")]
     pub exif: HashMap<String, String>,
 }
 
@@ -240,15 +592,13 @@ pub struct XmlMetadata {
 
 /// Text/Markdown metadata.
 ///
-/// Extracted from plain text and Markdown files. Includes word counts and,
+/// Extracted from plain text and Markdown files. Includes line and character counts and,
 /// for Markdown, structural elements like headers and links.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
 pub struct TextMetadata {
     /// Number of lines in the document
     pub line_count: usize,
-    /// Number of words
-    pub word_count: usize,
     /// Number of characters
     pub character_count: usize,
 
@@ -6,8 +6,11 @@ pub mod extraction;
 pub mod formats;
 pub mod metadata;
 pub mod page;
+pub mod query;
+pub mod report;
 pub mod serde_helpers;
 pub mod tables;
+pub mod versioning;
 
 // Re-export all types for backward compatibility
 pub use djot::*;
@@ -15,7 +18,10 @@ pub use extraction::*;
 pub use formats::*;
 pub use metadata::*;
 pub use page::*;
+pub use query::Query;
+pub use report::*;
 pub use tables::*;
+pub use versioning::RESULT_SCHEMA_VERSION;
 
 #[cfg(test)]
 mod tests {
@@ -27,9 +33,9 @@ mod tests {
     #[test]
     fn test_metadata_serialization_with_format() {
         let mut metadata = Metadata {
+            word_count: Some(2),
             format: Some(FormatMetadata::Text(TextMetadata {
                 line_count: 1,
-                word_count: 2,
                 character_count: 13,
                 headers: None,
                 links: None,
@@ -58,6 +64,59 @@ mod tests {
         assert_eq!(json.get("quality_score").unwrap(), 1.0);
     }
 
+    #[test]
+    fn test_promote_common_fields() {
+        let mut metadata = Metadata {
+            title: Some("Typed Title".to_string()),
+            ..Default::default()
+        };
+        for (key, value) in [
+            ("title", serde_json::json!("Frontmatter Title")),
+            ("author", serde_json::json!("Jane Doe")),
+            ("keywords", serde_json::json!("rust, extraction,")),
+            ("created_at", serde_json::json!("2024-10-09T12:43:00Z")),
+            ("application", serde_json::json!("Microsoft Office Word")),
+            ("page_count", serde_json::json!(2)),
+            ("word_count", serde_json::json!("108")),
+            ("revision", serde_json::json!("7")),
+        ] {
+            metadata.additional.insert(Cow::Borrowed(key), value);
+        }
+
+        metadata.promote_common_fields();
+
+        assert_eq!(metadata.title.as_deref(), Some("Typed Title"));
+        assert_eq!(metadata.authors, Some(vec!["Jane Doe".to_string()]));
+        assert_eq!(
+            metadata.keywords,
+            Some(vec!["rust".to_string(), "extraction".to_string()])
+        );
+        assert_eq!(metadata.created_at.as_deref(), Some("2024-10-09T12:43:00Z"));
+        assert_eq!(metadata.producer.as_deref(), Some("Microsoft Office Word"));
+        assert_eq!(metadata.page_count, Some(2));
+        assert_eq!(metadata.word_count, Some(108));
+
+        let keys: Vec<&str> = metadata.additional.keys().map(|key| key.as_ref()).collect();
+        assert_eq!(keys, ["revision"]);
+    }
+
+    #[test]
+    fn test_promote_common_fields_keeps_unsupported_values() {
+        let keywords = serde_json::json!([{"text": "rust", "score": 0.9}]);
+        let mut metadata = Metadata::default();
+        metadata.additional.insert(Cow::Borrowed("keywords"), keywords.clone());
+        metadata
+            .additional
+            .insert(Cow::Borrowed("page_count"), serde_json::json!("many"));
+
+        metadata.promote_common_fields();
+
+        assert_eq!(metadata.keywords, None);
+        assert_eq!(metadata.page_count, None);
+        assert_eq!(metadata.additional["keywords"], keywords);
+        assert_eq!(metadata.additional.len(), 2);
+    }
+
     #[test]
     fn test_arc_table_serialization_format() {
         let table = Table {
@@ -95,6 +95,14 @@ pub struct PageInfo {
     /// Whether this page is hidden (e.g., in presentations)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub hidden: Option<bool>,
+
+    /// Clockwise rotation in degrees (0, 90, 180 or 270) applied to make the page upright for OCR
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotation: Option<u16>,
+
+    /// Whether the page was detected as blank and skipped by OCR
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blank: Option<bool>,
 }
 
 /// Content for a single page/slide.
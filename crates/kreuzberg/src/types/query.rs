@@ -0,0 +1,1096 @@
+//! JMESPath queries over extraction results.
+//!
+//! [`ExtractionResult::query`] evaluates a [JMESPath](https://jmespath.org) expression
+//! against the serialized result, so callers can pull single fields such as
+//! `metadata.authors[0]` or `tables[*].page_number` without walking the JSON
+//! themselves. [`Query`] compiles an expression once for use on many values.
+//!
+//! Identifiers, sub-expressions, indexes, slices, list and object projections,
+//! flatten, filters, comparisons, `&&`, `||`, `!`, pipes, multi-select lists and hashes,
+//! literals and the built-in functions without expression arguments are supported.
+//! Expression references (`&expr`), and so `sort_by`, `max_by`, `min_by` and `map`,
+//! are not.
+
+use super::extraction::ExtractionResult;
+use crate::{KreuzbergError, Result};
+use serde_json::{Map, Number, Value};
+use std::cmp::Ordering;
+
+impl ExtractionResult {
+    /// Evaluate a JMESPath expression against the serialized result.
+    ///
+    /// Missing fields evaluate to `null`, as in JMESPath.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if the expression is invalid or a function
+    /// is called with arguments of the wrong type.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kreuzberg::ExtractionResult;
+    ///
+    /// let json = r#"{"content": "Hello", "mime_type": "text/plain",
+    ///     "metadata": {"authors": ["Ada Lovelace", "Charles Babbage"]}, "tables": []}"#;
+    /// let result: ExtractionResult = serde_json::from_str(json)?;
+    ///
+    /// assert_eq!(result.query("metadata.authors[0]")?, "Ada Lovelace");
+    /// assert_eq!(result.query("length(metadata.authors)")?, 2);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn query(&self, expression: &str) -> Result<Value> {
+        Query::parse(expression)?.search(&serde_json::to_value(self)?)
+    }
+}
+
+/// A compiled JMESPath expression.
+///
+/// # Example
+///
+/// ```rust
+/// use kreuzberg::types::Query;
+/// use serde_json::json;
+///
+/// let query = Query::parse("results[?pages > `10`].path | sort(@)")?;
+/// let value = json!({"results": [
+///     {"path": "b.pdf", "pages": 12},
+///     {"path": "c.pdf", "pages": 3},
+///     {"path": "a.pdf", "pages": 40}
+/// ]});
+/// assert_eq!(query.search(&value)?, json!(["a.pdf", "b.pdf"]));
+/// # Ok::<(), kreuzberg::KreuzbergError>(())
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Query {
+    expression: String,
+    ast: Ast,
+}
+
+impl Query {
+    /// Compile a JMESPath expression.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if the expression is invalid.
+    pub fn parse(expression: &str) -> Result<Self> {
+        let invalid =
+            |reason: String| KreuzbergError::validation(format!("Invalid query '{}': {}", expression, reason));
+        let tokens = tokenize(expression).map_err(invalid)?;
+        let mut parser = Parser { tokens, position: 0 };
+        let ast = parser.expression(0).map_err(invalid)?;
+        if parser.peek() != &Token::Eof {
+            return Err(invalid(format!("unexpected {}", parser.peek())));
+        }
+        Ok(Self {
+            expression: expression.to_string(),
+            ast,
+        })
+    }
+
+    /// Evaluate the expression against `value`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `KreuzbergError::Validation` if a function is called with arguments of
+    /// the wrong type.
+    pub fn search(&self, value: &Value) -> Result<Value> {
+        evaluate(&self.ast, value)
+            .map_err(|reason| KreuzbergError::validation(format!("Query '{}' failed: {}", self.expression, reason)))
+    }
+
+    /// The expression the query was compiled from.
+    pub fn as_str(&self) -> &str {
+        &self.expression
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Identifier(String),
+    QuotedIdentifier(String),
+    Literal(Value),
+    Number(i64),
+    Dot,
+    Star,
+    At,
+    Comma,
+    Colon,
+    Pipe,
+    Or,
+    And,
+    Not,
+    Comparator(Comparator),
+    LeftBracket,
+    RightBracket,
+    Flatten,
+    Filter,
+    LeftBrace,
+    RightBrace,
+    LeftParen,
+    RightParen,
+    Eof,
+}
+
+impl Token {
+    /// Left binding power of the token when it follows an expression.
+    fn binding_power(&self) -> u8 {
+        match self {
+            Token::Pipe => 1,
+            Token::Or => 2,
+            Token::And => 3,
+            Token::Comparator(_) => 5,
+            Token::Flatten => 9,
+            Token::Star => 20,
+            Token::Filter => 21,
+            Token::Dot => 40,
+            Token::Not => 45,
+            Token::LeftBrace => 50,
+            Token::LeftBracket => 55,
+            Token::LeftParen => 60,
+            _ => 0,
+        }
+    }
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Identifier(name) => write!(f, "identifier '{}'", name),
+            Token::QuotedIdentifier(name) => write!(f, "identifier \"{}\"", name),
+            Token::Literal(value) => write!(f, "literal {}", value),
+            Token::Number(number) => write!(f, "number {}", number),
+            Token::Eof => f.write_str("end of expression"),
+            token => {
+                let symbol = match token {
+                    Token::Dot => ".",
+                    Token::Star => "*",
+                    Token::At => "@",
+                    Token::Comma => ",",
+                    Token::Colon => ":",
+                    Token::Pipe => "|",
+                    Token::Or => "||",
+                    Token::And => "&&",
+                    Token::Not => "!",
+                    Token::Comparator(comparator) => comparator.symbol(),
+                    Token::LeftBracket => "[",
+                    Token::RightBracket => "]",
+                    Token::Flatten => "[]",
+                    Token::Filter => "[?",
+                    Token::LeftBrace => "{",
+                    Token::RightBrace => "}",
+                    Token::LeftParen => "(",
+                    _ => ")",
+                };
+                write!(f, "'{}'", symbol)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Comparator {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparator {
+    fn symbol(self) -> &'static str {
+        match self {
+            Comparator::Equal => "==",
+            Comparator::NotEqual => "!=",
+            Comparator::Less => "<",
+            Comparator::LessOrEqual => "<=",
+            Comparator::Greater => ">",
+            Comparator::GreaterOrEqual => ">=",
+        }
+    }
+}
+
+fn tokenize(expression: &str) -> std::result::Result<Vec<Token>, String> {
+    let chars: Vec<char> = expression.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+
+    // Reads a token delimited by `close`, unescaping `\close`; returns it and the next index.
+    let delimited = |start: usize, close: char| -> std::result::Result<(String, usize), String> {
+        let mut text = String::new();
+        let mut i = start + 1;
+        while i < chars.len() {
+            match chars[i] {
+                '\\' if chars.get(i + 1) == Some(&close) => {
+                    text.push(close);
+                    i += 2;
+                }
+                c if c == close => return Ok((text, i + 1)),
+                c => {
+                    text.push(c);
+                    i += 1;
+                }
+            }
+        }
+        Err(format!("unclosed {} at position {}", close, start))
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let (token, length) = match c {
+            ' ' | '\t' | '\n' | '\r' => {
+                i += 1;
+                continue;
+            }
+            '.' => (Token::Dot, 1),
+            '*' => (Token::Star, 1),
+            '@' => (Token::At, 1),
+            ',' => (Token::Comma, 1),
+            ':' => (Token::Colon, 1),
+            ']' => (Token::RightBracket, 1),
+            '{' => (Token::LeftBrace, 1),
+            '}' => (Token::RightBrace, 1),
+            '(' => (Token::LeftParen, 1),
+            ')' => (Token::RightParen, 1),
+            '[' => match next {
+                Some(']') => (Token::Flatten, 2),
+                Some('?') => (Token::Filter, 2),
+                _ => (Token::LeftBracket, 1),
+            },
+            '|' if next == Some('|') => (Token::Or, 2),
+            '|' => (Token::Pipe, 1),
+            '&' if next == Some('&') => (Token::And, 2),
+            '&' => return Err("expression references (&) are not supported".to_string()),
+            '!' if next == Some('=') => (Token::Comparator(Comparator::NotEqual), 2),
+            '!' => (Token::Not, 1),
+            '=' if next == Some('=') => (Token::Comparator(Comparator::Equal), 2),
+            '<' if next == Some('=') => (Token::Comparator(Comparator::LessOrEqual), 2),
+            '<' => (Token::Comparator(Comparator::Less), 1),
+            '>' if next == Some('=') => (Token::Comparator(Comparator::GreaterOrEqual), 2),
+            '>' => (Token::Comparator(Comparator::Greater), 1),
+            '\'' => {
+                let (text, end) = delimited(i, '\'')?;
+                tokens.push(Token::Literal(Value::String(text)));
+                i = end;
+                continue;
+            }
+            '`' => {
+                let (text, end) = delimited(i, '`')?;
+                let value =
+                    serde_json::from_str(text.trim()).map_err(|e| format!("invalid JSON literal `{}`: {}", text, e))?;
+                tokens.push(Token::Literal(value));
+                i = end;
+                continue;
+            }
+            '"' => {
+                let end = (i + 1..chars.len())
+                    .scan(false, |escaped, j| {
+                        let closes = !*escaped && chars[j] == '"';
+                        *escaped = !*escaped && chars[j] == '\\';
+                        Some((j, closes))
+                    })
+                    .find_map(|(j, closes)| closes.then_some(j))
+                    .ok_or_else(|| format!("unclosed \" at position {}", i))?;
+                let quoted: String = chars[i..=end].iter().collect();
+                let name =
+                    serde_json::from_str(&quoted).map_err(|e| format!("invalid identifier {}: {}", quoted, e))?;
+                tokens.push(Token::QuotedIdentifier(name));
+                i = end + 1;
+                continue;
+            }
+            c if c.is_ascii_digit() || (c == '-' && next.is_some_and(|n| n.is_ascii_digit())) => {
+                let end = (i + 1..chars.len())
+                    .find(|&j| !chars[j].is_ascii_digit())
+                    .unwrap_or(chars.len());
+                let digits: String = chars[i..end].iter().collect();
+                let number = digits
+                    .parse()
+                    .map_err(|_| format!("number {} is out of range", digits))?;
+                tokens.push(Token::Number(number));
+                i = end;
+                continue;
+            }
+            c if c.is_ascii_alphabetic() || c == '_' => {
+                let end = (i + 1..chars.len())
+                    .find(|&j| !(chars[j].is_ascii_alphanumeric() || chars[j] == '_'))
+                    .unwrap_or(chars.len());
+                tokens.push(Token::Identifier(chars[i..end].iter().collect()));
+                i = end;
+                continue;
+            }
+            c => return Err(format!("unexpected character '{}' at position {}", c, i)),
+        };
+        tokens.push(token);
+        i += length;
+    }
+
+    tokens.push(Token::Eof);
+    Ok(tokens)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Ast {
+    /// The current node, `@`
+    Identity,
+    Field(String),
+    Index(i64),
+    Slice(Option<i64>, Option<i64>, Option<i64>),
+    Literal(Value),
+    /// Evaluates the right side against the result of the left side
+    Subexpression(Box<Ast>, Box<Ast>),
+    /// Evaluates the right side against every element of the array on the left side
+    Projection(Box<Ast>, Box<Ast>),
+    /// Like a projection, but only for the elements that satisfy the condition
+    Filter(Box<Ast>, Box<Ast>, Box<Ast>),
+    ObjectValues(Box<Ast>),
+    Flatten(Box<Ast>),
+    Comparison(Comparator, Box<Ast>, Box<Ast>),
+    Or(Box<Ast>, Box<Ast>),
+    And(Box<Ast>, Box<Ast>),
+    Not(Box<Ast>),
+    MultiList(Vec<Ast>),
+    MultiHash(Vec<(String, Ast)>),
+    Function(String, Vec<Ast>),
+}
+
+/// Pratt parser over the tokens of an expression.
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+type ParseResult = std::result::Result<Ast, String>;
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position]
+    }
+
+    fn peek_second(&self) -> &Token {
+        self.tokens.get(self.position + 1).unwrap_or(&Token::Eof)
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.tokens[self.position].clone();
+        if token != Token::Eof {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn expect(&mut self, expected: Token) -> std::result::Result<(), String> {
+        match self.advance() {
+            token if token == expected => Ok(()),
+            token => Err(format!("expected {}, found {}", expected, token)),
+        }
+    }
+
+    fn expression(&mut self, binding_power: u8) -> ParseResult {
+        let token = self.advance();
+        let mut left = self.prefix(token)?;
+        while binding_power < self.peek().binding_power() {
+            let token = self.advance();
+            left = self.infix(left, token)?;
+        }
+        Ok(left)
+    }
+
+    fn prefix(&mut self, token: Token) -> ParseResult {
+        match token {
+            Token::At => Ok(Ast::Identity),
+            Token::Identifier(name) if self.peek() == &Token::LeftParen => {
+                self.advance();
+                self.function(name)
+            }
+            Token::Identifier(name) | Token::QuotedIdentifier(name) => Ok(Ast::Field(name)),
+            Token::Literal(value) => Ok(Ast::Literal(value)),
+            Token::Star => {
+                let right = self.projection_right(Token::Star.binding_power())?;
+                Ok(Ast::Projection(
+                    Box::new(Ast::ObjectValues(Box::new(Ast::Identity))),
+                    Box::new(right),
+                ))
+            }
+            Token::Flatten => {
+                let right = self.projection_right(Token::Flatten.binding_power())?;
+                Ok(Ast::Projection(
+                    Box::new(Ast::Flatten(Box::new(Ast::Identity))),
+                    Box::new(right),
+                ))
+            }
+            Token::Filter => self.filter(Ast::Identity),
+            Token::LeftBracket => match self.peek() {
+                Token::Number(_) | Token::Colon => self.index(Ast::Identity),
+                Token::Star if self.peek_second() == &Token::RightBracket => {
+                    self.advance();
+                    self.advance();
+                    self.list_projection(Ast::Identity)
+                }
+                _ => self.multi_list(),
+            },
+            Token::LeftBrace => self.multi_hash(),
+            Token::Not => Ok(Ast::Not(Box::new(self.expression(Token::Not.binding_power())?))),
+            Token::LeftParen => {
+                let inner = self.expression(0)?;
+                self.expect(Token::RightParen)?;
+                Ok(inner)
+            }
+            token => Err(format!("unexpected {}", token)),
+        }
+    }
+
+    fn infix(&mut self, left: Ast, token: Token) -> ParseResult {
+        let left = Box::new(left);
+        match token {
+            Token::Dot => {
+                if self.peek() == &Token::Star {
+                    self.advance();
+                    let right = self.projection_right(Token::Star.binding_power())?;
+                    return Ok(Ast::Projection(Box::new(Ast::ObjectValues(left)), Box::new(right)));
+                }
+                let right = self.dot_right(Token::Dot.binding_power())?;
+                Ok(Ast::Subexpression(left, Box::new(right)))
+            }
+            Token::Pipe => Ok(Ast::Subexpression(left, Box::new(self.expression(1)?))),
+            Token::Or => Ok(Ast::Or(left, Box::new(self.expression(2)?))),
+            Token::And => Ok(Ast::And(left, Box::new(self.expression(3)?))),
+            Token::Comparator(comparator) => Ok(Ast::Comparison(comparator, left, Box::new(self.expression(5)?))),
+            Token::Flatten => {
+                let right = self.projection_right(Token::Flatten.binding_power())?;
+                Ok(Ast::Projection(Box::new(Ast::Flatten(left)), Box::new(right)))
+            }
+            Token::Filter => self.filter(*left),
+            Token::LeftBracket => match self.peek() {
+                Token::Number(_) | Token::Colon => self.index(*left),
+                Token::Star if self.peek_second() == &Token::RightBracket => {
+                    self.advance();
+                    self.advance();
+                    self.list_projection(*left)
+                }
+                token => Err(format!("unexpected {} after '['", token)),
+            },
+            token => Err(format!("unexpected {}", token)),
+        }
+    }
+
+    /// The right side of a `.`: an identifier, function call or multi-select.
+    fn dot_right(&mut self, binding_power: u8) -> ParseResult {
+        match self.peek() {
+            Token::LeftBracket => {
+                self.advance();
+                self.multi_list()
+            }
+            Token::LeftBrace => {
+                self.advance();
+                self.multi_hash()
+            }
+            Token::Identifier(_) | Token::QuotedIdentifier(_) => self.expression(binding_power),
+            token => Err(format!("unexpected {} after '.'", token)),
+        }
+    }
+
+    /// The expression applied to every element of a projection.
+    fn projection_right(&mut self, binding_power: u8) -> ParseResult {
+        match self.peek() {
+            Token::Dot => {
+                self.advance();
+                self.dot_right(binding_power)
+            }
+            Token::LeftBracket | Token::Filter => self.expression(binding_power),
+            token if token.binding_power() < 10 => Ok(Ast::Identity),
+            token => Err(format!("unexpected {} after a projection", token)),
+        }
+    }
+
+    fn list_projection(&mut self, left: Ast) -> ParseResult {
+        let right = self.projection_right(Token::Star.binding_power())?;
+        Ok(Ast::Projection(Box::new(left), Box::new(right)))
+    }
+
+    /// An index or slice, after its `[`.
+    fn index(&mut self, left: Ast) -> ParseResult {
+        let mut parts = [None, None, None];
+        let mut part = 0;
+        loop {
+            match self.advance() {
+                Token::Number(number) if parts[part].is_none() => parts[part] = Some(number),
+                Token::Colon if part < 2 => part += 1,
+                Token::RightBracket => break,
+                token => return Err(format!("unexpected {} in index", token)),
+            }
+        }
+        if part == 0 {
+            let index = parts[0].ok_or("empty index")?;
+            return Ok(Ast::Subexpression(Box::new(left), Box::new(Ast::Index(index))));
+        }
+        if parts[2] == Some(0) {
+            return Err("slice step cannot be 0".to_string());
+        }
+        let slice = Ast::Subexpression(Box::new(left), Box::new(Ast::Slice(parts[0], parts[1], parts[2])));
+        self.list_projection(slice)
+    }
+
+    /// A filter projection, after its `[?`.
+    fn filter(&mut self, left: Ast) -> ParseResult {
+        let condition = self.expression(0)?;
+        self.expect(Token::RightBracket)?;
+        let right = self.projection_right(Token::Filter.binding_power())?;
+        Ok(Ast::Filter(Box::new(left), Box::new(condition), Box::new(right)))
+    }
+
+    /// A multi-select list, after its `[`.
+    fn multi_list(&mut self) -> ParseResult {
+        let mut items = vec![self.expression(0)?];
+        while self.peek() == &Token::Comma {
+            self.advance();
+            items.push(self.expression(0)?);
+        }
+        self.expect(Token::RightBracket)?;
+        Ok(Ast::MultiList(items))
+    }
+
+    /// A multi-select hash, after its `{`.
+    fn multi_hash(&mut self) -> ParseResult {
+        let mut entries = Vec::new();
+        loop {
+            let key = match self.advance() {
+                Token::Identifier(key) | Token::QuotedIdentifier(key) => key,
+                token => return Err(format!("expected a key, found {}", token)),
+            };
+            self.expect(Token::Colon)?;
+            entries.push((key, self.expression(0)?));
+            match self.advance() {
+                Token::Comma => continue,
+                Token::RightBrace => break,
+                token => return Err(format!("expected ',' or '}}', found {}", token)),
+            }
+        }
+        Ok(Ast::MultiHash(entries))
+    }
+
+    /// The arguments of a function call, after its `(`.
+    fn function(&mut self, name: String) -> ParseResult {
+        let mut arguments = Vec::new();
+        if self.peek() != &Token::RightParen {
+            arguments.push(self.expression(0)?);
+            while self.peek() == &Token::Comma {
+                self.advance();
+                arguments.push(self.expression(0)?);
+            }
+        }
+        self.expect(Token::RightParen)?;
+        Ok(Ast::Function(name, arguments))
+    }
+}
+
+type EvalResult = std::result::Result<Value, String>;
+
+fn evaluate(ast: &Ast, value: &Value) -> EvalResult {
+    Ok(match ast {
+        Ast::Identity => value.clone(),
+        Ast::Field(name) => value.get(name).cloned().unwrap_or(Value::Null),
+        Ast::Index(index) => match value {
+            Value::Array(items) => resolve_index(*index, items.len())
+                .and_then(|index| items.get(index).cloned())
+                .unwrap_or(Value::Null),
+            _ => Value::Null,
+        },
+        Ast::Slice(start, stop, step) => match value {
+            Value::Array(items) => Value::Array(slice(items, *start, *stop, step.unwrap_or(1))),
+            _ => Value::Null,
+        },
+        Ast::Literal(literal) => literal.clone(),
+        Ast::Subexpression(left, right) => evaluate(right, &evaluate(left, value)?)?,
+        Ast::Projection(left, right) => match evaluate(left, value)? {
+            Value::Array(items) => project(items.iter(), right)?,
+            _ => Value::Null,
+        },
+        Ast::Filter(left, condition, right) => match evaluate(left, value)? {
+            Value::Array(items) => {
+                let mut selected = Vec::new();
+                for item in items {
+                    if is_truthy(&evaluate(condition, &item)?) {
+                        selected.push(item);
+                    }
+                }
+                project(selected.iter(), right)?
+            }
+            _ => Value::Null,
+        },
+        Ast::ObjectValues(inner) => match evaluate(inner, value)? {
+            Value::Object(fields) => Value::Array(fields.into_iter().map(|(_, field)| field).collect()),
+            _ => Value::Null,
+        },
+        Ast::Flatten(inner) => match evaluate(inner, value)? {
+            Value::Array(items) => Value::Array(
+                items
+                    .into_iter()
+                    .flat_map(|item| match item {
+                        Value::Array(nested) => nested,
+                        item => vec![item],
+                    })
+                    .collect(),
+            ),
+            _ => Value::Null,
+        },
+        Ast::Comparison(comparator, left, right) => {
+            compare(*comparator, &evaluate(left, value)?, &evaluate(right, value)?)
+        }
+        Ast::Or(left, right) => {
+            let left = evaluate(left, value)?;
+            if is_truthy(&left) {
+                left
+            } else {
+                evaluate(right, value)?
+            }
+        }
+        Ast::And(left, right) => {
+            let left = evaluate(left, value)?;
+            if is_truthy(&left) {
+                evaluate(right, value)?
+            } else {
+                left
+            }
+        }
+        Ast::Not(inner) => Value::Bool(!is_truthy(&evaluate(inner, value)?)),
+        Ast::MultiList(items) if !value.is_null() => Value::Array(
+            items
+                .iter()
+                .map(|item| evaluate(item, value))
+                .collect::<std::result::Result<_, _>>()?,
+        ),
+        Ast::MultiHash(entries) if !value.is_null() => Value::Object(
+            entries
+                .iter()
+                .map(|(key, entry)| Ok((key.clone(), evaluate(entry, value)?)))
+                .collect::<std::result::Result<_, String>>()?,
+        ),
+        Ast::MultiList(_) | Ast::MultiHash(_) => Value::Null,
+        Ast::Function(name, arguments) => {
+            let arguments = arguments
+                .iter()
+                .map(|argument| evaluate(argument, value))
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            call(name, &arguments)?
+        }
+    })
+}
+
+/// Evaluate `right` against every item, leaving out null results.
+fn project<'a>(items: impl Iterator<Item = &'a Value>, right: &Ast) -> EvalResult {
+    let mut projected = Vec::new();
+    for item in items {
+        let value = evaluate(right, item)?;
+        if !value.is_null() {
+            projected.push(value);
+        }
+    }
+    Ok(Value::Array(projected))
+}
+
+fn resolve_index(index: i64, len: usize) -> Option<usize> {
+    if index < 0 {
+        len.checked_sub(index.unsigned_abs() as usize)
+    } else {
+        Some(index as usize)
+    }
+}
+
+fn slice(items: &[Value], start: Option<i64>, stop: Option<i64>, step: i64) -> Vec<Value> {
+    let len = items.len() as i64;
+    let clamp = |bound: i64, low: i64, high: i64| {
+        let bound = if bound < 0 { bound + len } else { bound };
+        bound.clamp(low, high)
+    };
+    let mut selected = Vec::new();
+    if step > 0 {
+        let mut i = start.map_or(0, |start| clamp(start, 0, len));
+        let stop = stop.map_or(len, |stop| clamp(stop, 0, len));
+        while i < stop {
+            selected.push(items[i as usize].clone());
+            i += step;
+        }
+    } else {
+        let mut i = start.map_or(len - 1, |start| clamp(start, -1, len - 1));
+        let stop = stop.map_or(-1, |stop| clamp(stop, -1, len - 1));
+        while i > stop {
+            selected.push(items[i as usize].clone());
+            i += step;
+        }
+    }
+    selected
+}
+
+/// JMESPath truthiness: false, null and empty strings, arrays and objects are false.
+fn is_truthy(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Bool(flag) => *flag,
+        Value::String(text) => !text.is_empty(),
+        Value::Array(items) => !items.is_empty(),
+        Value::Object(fields) => !fields.is_empty(),
+        Value::Number(_) => true,
+    }
+}
+
+/// Equality with numbers compared by value, so `1` equals `1.0`.
+fn equals(left: &Value, right: &Value) -> bool {
+    match (left, right) {
+        (Value::Number(a), Value::Number(b)) => a.as_f64() == b.as_f64(),
+        (Value::Array(a), Value::Array(b)) => a.len() == b.len() && a.iter().zip(b).all(|(a, b)| equals(a, b)),
+        (Value::Object(a), Value::Object(b)) => {
+            a.len() == b.len() && a.iter().all(|(key, a)| b.get(key).is_some_and(|b| equals(a, b)))
+        }
+        (a, b) => a == b,
+    }
+}
+
+fn compare(comparator: Comparator, left: &Value, right: &Value) -> Value {
+    match comparator {
+        Comparator::Equal => Value::Bool(equals(left, right)),
+        Comparator::NotEqual => Value::Bool(!equals(left, right)),
+        _ => {
+            let (Some(a), Some(b)) = (left.as_f64(), right.as_f64()) else {
+                return Value::Null;
+            };
+            Value::Bool(match comparator {
+                Comparator::Less => a < b,
+                Comparator::LessOrEqual => a <= b,
+                Comparator::Greater => a > b,
+                _ => a >= b,
+            })
+        }
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn number(value: f64) -> Value {
+    if value.fract() == 0.0 && value.abs() < 9.0e15 {
+        Value::from(value as i64)
+    } else {
+        Number::from_f64(value).map_or(Value::Null, Value::Number)
+    }
+}
+
+/// Order two values of a sortable array: all numbers or all strings.
+fn order(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::String(a), Value::String(b)) => a.cmp(b),
+        _ => a.as_f64().partial_cmp(&b.as_f64()).unwrap_or(Ordering::Equal),
+    }
+}
+
+/// Call a built-in function.
+fn call(name: &str, arguments: &[Value]) -> EvalResult {
+    let arity = |expected: usize| {
+        if arguments.len() == expected {
+            Ok(())
+        } else {
+            Err(format!(
+                "{}() takes {} argument(s), {} given",
+                name,
+                expected,
+                arguments.len()
+            ))
+        }
+    };
+    let invalid =
+        |value: &Value, expected: &str| Err(format!("{}() expects {}, got {}", name, expected, type_name(value)));
+    let numbers = |value: &Value| -> std::result::Result<Vec<f64>, String> {
+        match value {
+            Value::Array(items) => items
+                .iter()
+                .map(|item| {
+                    item.as_f64()
+                        .ok_or_else(|| format!("{}() expects an array of numbers", name))
+                })
+                .collect(),
+            value => Err(format!("{}() expects an array, got {}", name, type_name(value))),
+        }
+    };
+    let sortable = |value: &Value| -> std::result::Result<Vec<Value>, String> {
+        match value {
+            Value::Array(items) if items.iter().all(Value::is_number) || items.iter().all(Value::is_string) => {
+                Ok(items.clone())
+            }
+            value => Err(format!(
+                "{}() expects an array of numbers or strings, got {}",
+                name,
+                type_name(value)
+            )),
+        }
+    };
+
+    match name {
+        "abs" | "ceil" | "floor" => {
+            arity(1)?;
+            let Some(value) = arguments[0].as_f64() else {
+                return invalid(&arguments[0], "a number");
+            };
+            Ok(number(match name {
+                "abs" => value.abs(),
+                "ceil" => value.ceil(),
+                _ => value.floor(),
+            }))
+        }
+        "avg" | "sum" => {
+            arity(1)?;
+            let values = numbers(&arguments[0])?;
+            let sum: f64 = values.iter().sum();
+            Ok(match name {
+                "sum" => number(sum),
+                _ if values.is_empty() => Value::Null,
+                _ => number(sum / values.len() as f64),
+            })
+        }
+        "max" | "min" => {
+            arity(1)?;
+            let items = sortable(&arguments[0])?;
+            let extreme = if name == "max" {
+                items.into_iter().max_by(order)
+            } else {
+                items.into_iter().min_by(order)
+            };
+            Ok(extreme.unwrap_or(Value::Null))
+        }
+        "sort" => {
+            arity(1)?;
+            let mut items = sortable(&arguments[0])?;
+            items.sort_by(order);
+            Ok(Value::Array(items))
+        }
+        "contains" => {
+            arity(2)?;
+            match (&arguments[0], &arguments[1]) {
+                (Value::String(text), Value::String(part)) => Ok(Value::Bool(text.contains(part.as_str()))),
+                (Value::String(_), _) => Ok(Value::Bool(false)),
+                (Value::Array(items), needle) => Ok(Value::Bool(items.iter().any(|item| equals(item, needle)))),
+                (value, _) => invalid(value, "an array or string"),
+            }
+        }
+        "starts_with" | "ends_with" => {
+            arity(2)?;
+            match (&arguments[0], &arguments[1]) {
+                (Value::String(text), Value::String(affix)) => Ok(Value::Bool(if name == "starts_with" {
+                    text.starts_with(affix.as_str())
+                } else {
+                    text.ends_with(affix.as_str())
+                })),
+                (Value::String(_), value) | (value, _) => invalid(value, "a string"),
+            }
+        }
+        "join" => {
+            arity(2)?;
+            let Value::String(separator) = &arguments[0] else {
+                return invalid(&arguments[0], "a string separator");
+            };
+            match &arguments[1] {
+                Value::Array(items) if items.iter().all(Value::is_string) => Ok(Value::String(
+                    items
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join(separator),
+                )),
+                value => invalid(value, "an array of strings"),
+            }
+        }
+        "keys" | "values" => {
+            arity(1)?;
+            match &arguments[0] {
+                Value::Object(fields) if name == "keys" => {
+                    Ok(Value::Array(fields.keys().cloned().map(Value::String).collect()))
+                }
+                Value::Object(fields) => Ok(Value::Array(fields.values().cloned().collect())),
+                value => invalid(value, "an object"),
+            }
+        }
+        "length" => {
+            arity(1)?;
+            match &arguments[0] {
+                Value::String(text) => Ok(Value::from(text.chars().count())),
+                Value::Array(items) => Ok(Value::from(items.len())),
+                Value::Object(fields) => Ok(Value::from(fields.len())),
+                value => invalid(value, "a string, array or object"),
+            }
+        }
+        "merge" => {
+            let mut merged = Map::new();
+            for argument in arguments {
+                let Value::Object(fields) = argument else {
+                    return invalid(argument, "objects");
+                };
+                merged.extend(fields.clone());
+            }
+            Ok(Value::Object(merged))
+        }
+        "not_null" => {
+            if arguments.is_empty() {
+                return Err("not_null() takes at least 1 argument".to_string());
+            }
+            Ok(arguments
+                .iter()
+                .find(|value| !value.is_null())
+                .cloned()
+                .unwrap_or(Value::Null))
+        }
+        "reverse" => {
+            arity(1)?;
+            match &arguments[0] {
+                Value::String(text) => Ok(Value::String(text.chars().rev().collect())),
+                Value::Array(items) => Ok(Value::Array(items.iter().rev().cloned().collect())),
+                value => invalid(value, "an array or string"),
+            }
+        }
+        "to_array" => {
+            arity(1)?;
+            Ok(match &arguments[0] {
+                Value::Array(_) => arguments[0].clone(),
+                value => Value::Array(vec![value.clone()]),
+            })
+        }
+        "to_number" => {
+            arity(1)?;
+            Ok(match &arguments[0] {
+                Value::Number(_) => arguments[0].clone(),
+                Value::String(text) => text.trim().parse::<f64>().map_or(Value::Null, number),
+                _ => Value::Null,
+            })
+        }
+        "to_string" => {
+            arity(1)?;
+            Ok(match &arguments[0] {
+                Value::String(_) => arguments[0].clone(),
+                value => Value::String(value.to_string()),
+            })
+        }
+        "type" => {
+            arity(1)?;
+            Ok(Value::String(type_name(&arguments[0]).to_string()))
+        }
+        _ => Err(format!("unknown function {}()", name)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn search(expression: &str, value: &Value) -> Value {
+        Query::parse(expression).unwrap().search(value).unwrap()
+    }
+
+    fn result() -> Value {
+        json!({
+            "content": "Purchase order",
+            "metadata": {
+                "title": "PO 4711",
+                "authors": ["Ada Lovelace", "Charles Babbage"],
+                "page_count": 3,
+                "extracted_fields": {"po_number": "4711"}
+            },
+            "tables": [
+                {"page_number": 1, "cells": [["SKU", "Qty"], ["ABC-1", "2"]]},
+                {"page_number": 3, "cells": [["Total", "EUR 12"]]}
+            ],
+            "chunks": null
+        })
+    }
+
+    #[test]
+    fn test_paths_and_indexes() {
+        let value = result();
+        assert_eq!(search("metadata.authors[0]", &value), "Ada Lovelace");
+        assert_eq!(search("metadata.authors[-1]", &value), "Charles Babbage");
+        assert_eq!(search("metadata.authors[5]", &value), Value::Null);
+        assert_eq!(search("metadata.\"extracted_fields\".po_number", &value), "4711");
+        assert_eq!(search("metadata.missing.deeper", &value), Value::Null);
+        assert_eq!(search("tables[0].cells[1][0]", &value), "ABC-1");
+        assert_eq!(
+            search("metadata.authors[::-1]", &value),
+            json!(["Charles Babbage", "Ada Lovelace"])
+        );
+        assert_eq!(search("metadata.authors[1:]", &value), json!(["Charles Babbage"]));
+    }
+
+    #[test]
+    fn test_projections_and_filters() {
+        let value = result();
+        assert_eq!(search("tables[*].page_number", &value), json!([1, 3]));
+        assert_eq!(search("tables[].cells[][0]", &value), json!(["SKU", "ABC-1", "Total"]));
+        assert_eq!(
+            search("tables[?page_number > `1`].cells[0][1]", &value),
+            json!(["EUR 12"])
+        );
+        assert_eq!(
+            search("tables[?page_number == `1` && !chunks].page_number", &value),
+            json!([1])
+        );
+        assert_eq!(search("metadata.extracted_fields.*", &value), json!(["4711"]));
+        assert_eq!(search("tables[*].page_number | [0]", &value), json!(1));
+        assert_eq!(search("chunks || 'none'", &value), "none");
+        assert_eq!(
+            search(
+                "{title: metadata.title, pages: metadata.page_count, first: [content, tables[0].page_number]}",
+                &value
+            ),
+            json!({"title": "PO 4711", "pages": 3, "first": ["Purchase order", 1]})
+        );
+    }
+
+    #[test]
+    fn test_functions() {
+        let value = result();
+        assert_eq!(search("length(metadata.authors)", &value), 2);
+        assert_eq!(
+            search("join(', ', metadata.authors)", &value),
+            "Ada Lovelace, Charles Babbage"
+        );
+        assert_eq!(search("sum(tables[*].page_number)", &value), 4);
+        assert_eq!(search("max(tables[*].page_number)", &value), 3);
+        assert_eq!(
+            search("metadata.authors[?starts_with(@, 'Ch')]", &value),
+            json!(["Charles Babbage"])
+        );
+        assert_eq!(search("sort(keys(metadata))[0]", &value), "authors");
+        assert_eq!(search("to_number(metadata.extracted_fields.po_number)", &value), 4711);
+        assert_eq!(search("not_null(chunks, `[]`)", &value), json!([]));
+
+        assert!(
+            Query::parse("length(metadata.page_count)")
+                .unwrap()
+                .search(&value)
+                .is_err()
+        );
+        assert!(Query::parse("unknown(@)").unwrap().search(&value).is_err());
+    }
+
+    #[test]
+    fn test_invalid_expressions() {
+        for expression in [
+            "",
+            "metadata.",
+            "tables[",
+            "a[0:1:0]",
+            "{a b}",
+            "`{`",
+            "sort_by(@, &a)",
+            "a b",
+        ] {
+            assert!(Query::parse(expression).is_err(), "{} should be rejected", expression);
+        }
+    }
+}
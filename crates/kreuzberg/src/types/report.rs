@@ -0,0 +1,126 @@
+//! Per-document processing reports.
+//!
+//! With `processing_report` enabled in the configuration, every result carries a
+//! [`ProcessingReport`] in `metadata.additional["processing_report"]`, saying how it
+//! was produced: the stages that ran with their plugins, durations and content sizes,
+//! the extractors tried by a fallback chain, the OCR of its pages, and the warnings
+//! raised on the way. [`ExtractionResult::processing_report`] reads it back.
+
+use super::extraction::ExtractionResult;
+use serde::{Deserialize, Serialize};
+
+/// Metadata key of the processing report, in `metadata.additional`.
+pub const PROCESSING_REPORT_KEY: &str = "processing_report";
+
+/// How a result was produced.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProcessingReport {
+    /// MIME type the document was extracted as
+    pub mime_type: String,
+    /// Wall time from the start of the extraction to the end of the pipeline, in milliseconds
+    pub duration_ms: f64,
+    /// Size of the input document in bytes, None when the pipeline ran on an existing result
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bytes_in: Option<u64>,
+    /// Size of the final content in bytes
+    pub bytes_out: u64,
+    /// Stages that ran, in order
+    pub stages: Vec<StageReport>,
+    /// Extractors tried by the fallback chain of the MIME type, in order, empty when
+    /// the MIME type has no chain
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fallbacks: Vec<FallbackAttempt>,
+    /// OCR of the document, None when it was not OCRed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ocr: Option<OcrReport>,
+    /// Warnings and non-fatal errors recorded in the metadata by plugins and stages
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    /// Number of results served from a cache instead of being computed, such as OCRed pages
+    pub cache_hits: usize,
+}
+
+/// A stage of the extraction.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct StageReport {
+    /// Stage name: `extraction`, `post_processor`, `chunking`, `validators`, ...
+    pub name: String,
+    /// Name of the plugin run by the stage, for extractors and processors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub plugin: Option<String>,
+    /// Version of the plugin
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Wall time of the stage in milliseconds
+    pub duration_ms: f64,
+    /// Size of the content before the stage in bytes, the input document for `extraction`
+    pub bytes_in: u64,
+    /// Size of the content after the stage in bytes
+    pub bytes_out: u64,
+}
+
+/// An extractor tried by a fallback chain.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct FallbackAttempt {
+    /// Entry of the chain: an extractor name, or `ocr-full`
+    pub extractor: String,
+    /// Wall time of the attempt in milliseconds
+    pub duration_ms: f64,
+    /// Why the attempt failed, None for the attempt that succeeded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// OCR of a document.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OcrReport {
+    /// Name of the OCR backend, when recorded in the metadata
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub backend: Option<String>,
+    /// OCRed pages, in page order
+    pub pages: Vec<OcrPageReport>,
+    /// Mean of the page confidences that are known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mean_confidence: Option<f64>,
+}
+
+/// OCR of one page.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OcrPageReport {
+    /// Page number, starting at 1
+    pub page_number: usize,
+    /// Mean recognition confidence of the page, when the backend reports one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f64>,
+    /// Whether the page was served from the OCR cache
+    #[serde(default)]
+    pub cache_hit: bool,
+}
+
+impl ExtractionResult {
+    /// The processing report of the result, when `processing_report` was enabled.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use kreuzberg::{ExtractionConfig, extract_file_sync};
+    ///
+    /// let config = ExtractionConfig {
+    ///     processing_report: true,
+    ///     ..Default::default()
+    /// };
+    /// let result = extract_file_sync("scan.pdf", None, &config)?;
+    /// if let Some(report) = result.processing_report() {
+    ///     for stage in &report.stages {
+    ///         println!("{} took {:.1} ms", stage.name, stage.duration_ms);
+    ///     }
+    /// }
+    /// # Ok::<(), kreuzberg::KreuzbergError>(())
+    /// ```
+    pub fn processing_report(&self) -> Option<ProcessingReport> {
+        self.metadata
+            .additional
+            .get(PROCESSING_REPORT_KEY)
+            .and_then(|report| serde_json::from_value(report.clone()).ok())
+    }
+}
@@ -1,4 +1,4 @@
-//! Custom serde serialization helpers for Arc<T> and Vec<Arc<T>>.
+//! Custom serde serialization helpers for Arc<T>, Vec<Arc<T>> and hash maps.
 
 /// Module providing transparent serde support for Arc<T>.
 ///
@@ -130,3 +130,38 @@ pub mod serde_vec_arc {
         Ok(vec.into_iter().map(Arc::new).collect())
     }
 }
+
+/// Serialize hash maps with keys in sorted order.
+///
+/// Hash map iteration order differs between processes, so serializing a map directly
+/// produces different bytes for identical results. Use with
+/// `#[serde(serialize_with = "sorted_map::serialize")]`.
+pub mod sorted_map {
+    use serde::{Serialize, Serializer};
+    use std::collections::{BTreeMap, HashMap};
+    use std::hash::BuildHasher;
+
+    /// Serialize a map with keys in sorted order.
+    pub fn serialize<K, V, H, S>(map: &HashMap<K, V, H>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize + Ord,
+        V: Serialize,
+        H: BuildHasher,
+        S: Serializer,
+    {
+        map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+    }
+
+    /// Serialize an optional map with keys in sorted order.
+    pub fn serialize_option<K, V, H, S>(map: &Option<HashMap<K, V, H>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        K: Serialize + Ord,
+        V: Serialize,
+        H: BuildHasher,
+        S: Serializer,
+    {
+        map.as_ref()
+            .map(|map| map.iter().collect::<BTreeMap<_, _>>())
+            .serialize(serializer)
+    }
+}
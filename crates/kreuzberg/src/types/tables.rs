@@ -17,6 +17,31 @@ pub struct Table {
     pub page_number: usize,
 }
 
+/// Data of a chart embedded in an Office document (DOCX, PPTX, XLSX).
+///
+/// Values are the ones cached in the chart part when the document was last saved.
+/// The same data is returned as a table in `ExtractionResult::tables`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "api", derive(utoipa::ToSchema))]
+pub struct Chart {
+    /// Chart title, if the chart shows one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Chart type such as `column`, `line`, `pie` or `scatter`; combined charts join their types, as in `column+line`
+    pub chart_type: String,
+    /// Title of the category (horizontal) axis
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub category_axis_title: Option<String>,
+    /// Title of the value (vertical) axis
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value_axis_title: Option<String>,
+    /// Series data: the header row holds the category label and the series names, each further row
+    /// a category followed by the series values
+    pub cells: Vec<Vec<String>>,
+    /// Page, slide or sheet where the chart was found (1-indexed)
+    pub page_number: usize,
+}
+
 /// Individual table cell with content and optional styling.
 ///
 /// Future extension point for rich table support with cell-level metadata.
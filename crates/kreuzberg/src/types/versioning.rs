@@ -0,0 +1,241 @@
+//! Versioned serialization of extraction results.
+//!
+//! Results written with [`ExtractionResult::to_versioned_json`] carry a top-level
+//! `schema_version` field. [`ExtractionResult::from_versioned_json`] reads results of
+//! the current and all earlier schema versions and upgrades older ones, so cached and
+//! archived results stay readable across crate upgrades. The field is ignored by plain
+//! `serde` deserialization, so versioned JSON also reads as an [`ExtractionResult`]
+//! directly.
+//!
+//! # Schema versions
+//!
+//! - **1**: results serialized without `schema_version`. XLSX results record the
+//!   company as `organization`, and common document properties may appear in their
+//!   raw form, e.g. keywords as one comma-separated string or counts as strings.
+//! - **2**: the company is `company` for every format, and the title, authors,
+//!   keywords, dates, producer and counts are typed [`Metadata`](super::Metadata)
+//!   fields.
+
+use super::extraction::ExtractionResult;
+use crate::{KreuzbergError, Result};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::io::Write;
+
+/// Schema version written by [`ExtractionResult::to_versioned_json`].
+pub const RESULT_SCHEMA_VERSION: u32 = 2;
+
+/// Name of the version field in serialized results.
+const SCHEMA_VERSION_FIELD: &str = "schema_version";
+
+/// A result serialized with its schema version, without an intermediate JSON value.
+#[derive(Serialize)]
+struct Versioned<'a> {
+    schema_version: u32,
+    #[serde(flatten)]
+    result: &'a ExtractionResult,
+}
+
+impl ExtractionResult {
+    /// Serialize the result as a JSON value with the current `schema_version`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the result cannot be serialized.
+    pub fn to_versioned_value(&self) -> Result<Value> {
+        let mut value = serde_json::to_value(self)?;
+        if let Value::Object(fields) = &mut value {
+            fields.insert(SCHEMA_VERSION_FIELD.to_string(), Value::from(RESULT_SCHEMA_VERSION));
+        }
+        Ok(value)
+    }
+
+    /// Serialize the result as JSON with the current `schema_version`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the result cannot be serialized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use kreuzberg::ExtractionResult;
+    /// use kreuzberg::types::RESULT_SCHEMA_VERSION;
+    ///
+    /// let json = r#"{"content": "Hello", "mime_type": "text/plain", "metadata": {}, "tables": []}"#;
+    /// let result = ExtractionResult::from_versioned_json(json)?;
+    ///
+    /// let versioned = result.to_versioned_json()?;
+    /// let value: serde_json::Value = serde_json::from_str(&versioned)?;
+    /// assert_eq!(value["schema_version"], RESULT_SCHEMA_VERSION);
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn to_versioned_json(&self) -> Result<String> {
+        Ok(serde_json::to_string(&self.to_versioned_value()?)?)
+    }
+
+    /// Serialize the result as JSON with the current `schema_version` into `writer`.
+    ///
+    /// Unlike [`to_versioned_json`](Self::to_versioned_json), the result is written as
+    /// it is serialized, so no copy of it is held in memory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the result cannot be serialized or `writer` fails.
+    pub fn write_versioned_json<W: Write>(&self, writer: W) -> Result<()> {
+        let versioned = Versioned {
+            schema_version: RESULT_SCHEMA_VERSION,
+            result: self,
+        };
+        Ok(serde_json::to_writer(writer, &versioned)?)
+    }
+
+    /// Read a result serialized with any schema version, upgrading it to the current one.
+    ///
+    /// JSON without `schema_version` is read as version 1.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error if `schema_version` is not a number or is newer than
+    /// [`RESULT_SCHEMA_VERSION`], and a serialization error if `json` is not a result.
+    pub fn from_versioned_json(json: &str) -> Result<Self> {
+        Self::from_versioned_value(serde_json::from_str(json)?)
+    }
+
+    /// Read a result serialized with any schema version from a JSON value.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`from_versioned_json`](Self::from_versioned_json).
+    pub fn from_versioned_value(mut value: Value) -> Result<Self> {
+        let version = match value
+            .as_object_mut()
+            .and_then(|fields| fields.remove(SCHEMA_VERSION_FIELD))
+        {
+            None => 1,
+            Some(version) => version
+                .as_u64()
+                .and_then(|version| u32::try_from(version).ok())
+                .ok_or_else(|| KreuzbergError::validation(format!("Invalid result schema version: {}", version)))?,
+        };
+        if version > RESULT_SCHEMA_VERSION {
+            return Err(KreuzbergError::validation(format!(
+                "Result schema version {} is newer than the supported version {}",
+                version, RESULT_SCHEMA_VERSION
+            )));
+        }
+
+        if version < 2
+            && let Some(metadata) = value.get_mut("metadata").and_then(Value::as_object_mut)
+        {
+            upgrade_v1_metadata(metadata);
+        }
+
+        let mut result: ExtractionResult = serde_json::from_value(value)?;
+        if version < 2 {
+            result.metadata.promote_common_fields();
+        }
+        Ok(result)
+    }
+}
+
+/// Bring version 1 metadata into the shape of version 2.
+///
+/// Raw property values are converted to the types of the version 2 fields of the same
+/// name, so the metadata deserializes. Properties under other names, such as `author`,
+/// stay in `additional` and are promoted after deserialization.
+fn upgrade_v1_metadata(metadata: &mut Map<String, Value>) {
+    if !metadata.contains_key("company")
+        && let Some(organization) = metadata.remove("organization")
+    {
+        metadata.insert("company".to_string(), organization);
+    }
+
+    if let Some(Value::String(keywords)) = metadata.get("keywords") {
+        let keywords: Vec<Value> = keywords
+            .split(',')
+            .map(str::trim)
+            .filter(|keyword| !keyword.is_empty())
+            .map(Value::from)
+            .collect();
+        metadata.insert("keywords".to_string(), Value::Array(keywords));
+    }
+
+    if let Some(Value::String(author)) = metadata.get("authors") {
+        let authors = Value::Array(vec![Value::from(author.trim())]);
+        metadata.insert("authors".to_string(), authors);
+    }
+
+    for key in ["page_count", "word_count"] {
+        if let Some(Value::String(count)) = metadata.get(key) {
+            let count = count.trim().parse::<u64>().map(Value::from);
+            match count {
+                Ok(count) => metadata.insert(key.to_string(), count),
+                Err(_) => metadata.remove(key),
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_versioned_round_trip() {
+        let result = ExtractionResult::from_versioned_json(
+            r#"{"content": "Hello", "mime_type": "text/plain", "metadata": {"title": "Greeting"}, "tables": []}"#,
+        )
+        .unwrap();
+
+        let value = result.to_versioned_value().unwrap();
+        assert_eq!(value["schema_version"], RESULT_SCHEMA_VERSION);
+
+        let plain: ExtractionResult = serde_json::from_value(value.clone()).unwrap();
+        assert_eq!(plain.content, "Hello");
+
+        let read = ExtractionResult::from_versioned_value(value.clone()).unwrap();
+        assert_eq!(read.metadata.title.as_deref(), Some("Greeting"));
+
+        let mut written = Vec::new();
+        result.write_versioned_json(&mut written).unwrap();
+        assert_eq!(serde_json::from_slice::<Value>(&written).unwrap(), value);
+    }
+
+    #[test]
+    fn test_from_versioned_json_upgrades_version_1() {
+        let v1 = json!({
+            "content": "Sheet",
+            "mime_type": "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+            "metadata": {
+                "organization": "Acme",
+                "keywords": "budget, 2024",
+                "author": "Jane Doe",
+                "word_count": "12",
+                "application": "Microsoft Excel"
+            },
+            "tables": []
+        });
+
+        let result = ExtractionResult::from_versioned_value(v1).unwrap();
+        let metadata = &result.metadata;
+        assert_eq!(metadata.additional["company"], "Acme");
+        assert!(!metadata.additional.contains_key("organization"));
+        assert_eq!(metadata.keywords, Some(vec!["budget".to_string(), "2024".to_string()]));
+        assert_eq!(metadata.authors, Some(vec!["Jane Doe".to_string()]));
+        assert_eq!(metadata.word_count, Some(12));
+        assert_eq!(metadata.producer.as_deref(), Some("Microsoft Excel"));
+    }
+
+    #[test]
+    fn test_from_versioned_json_rejects_unknown_versions() {
+        let newer = json!({"schema_version": RESULT_SCHEMA_VERSION + 1, "content": "", "mime_type": "text/plain", "metadata": {}, "tables": []});
+        let err = ExtractionResult::from_versioned_value(newer).unwrap_err();
+        assert!(err.to_string().contains("newer than the supported version"));
+
+        let invalid =
+            json!({"schema_version": "two", "content": "", "mime_type": "text/plain", "metadata": {}, "tables": []});
+        assert!(ExtractionResult::from_versioned_value(invalid).is_err());
+    }
+}
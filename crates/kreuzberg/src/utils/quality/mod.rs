@@ -11,7 +11,7 @@ mod scoring;
 // Re-export public API
 pub use scoring::calculate_quality_score;
 
-use crate::text::utf8_validation;
+use crate::text::{simd_scan, utf8_validation};
 use memchr::{memchr, memchr3};
 use patterns::*;
 use regex::Regex;
@@ -326,8 +326,10 @@ pub(crate) fn normalize_whitespace_ascii(text: &str) -> Option<String> {
                 i = j;
             }
             _ => {
-                result.push(bytes[i]);
-                i += 1;
+                // Copy the run of characters and single spaces that stays as it is at once.
+                let run = simd_scan::plain_ascii_prefix(&bytes[i..]).max(1);
+                result.extend_from_slice(&bytes[i..i + run]);
+                i += run;
             }
         }
     }
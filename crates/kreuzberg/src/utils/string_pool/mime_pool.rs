@@ -65,12 +65,16 @@ impl MimeStringPool {
             "application/vnd.oasis.opendocument.spreadsheet",
             "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
             "application/vnd.oasis.opendocument.text",
+            "image/avif",
             "image/bmp",
             "image/gif",
+            "image/heic",
+            "image/heif",
             "image/jp2",
             "image/jpeg",
             "image/jpm",
             "image/jpx",
+            "image/jxl",
             "image/mj2",
             "image/pjpeg",
             "image/png",
@@ -0,0 +1,540 @@
+//! Drop-folder ingestion: watch directories and extract the files written to them.
+//!
+//! [`run`] watches directories for new and changed files, waits until a file has not
+//! been written to for [`WatchConfig::debounce_ms`], extracts it, and emits the result
+//! to the configured [`WatchSink`]. A file that is changed again is extracted again.
+//!
+//! Hidden files (names starting with `.`) are ignored, so uploads written to a
+//! temporary dot file and renamed into place are extracted once, complete.
+//!
+//! Results written to files are compressed with `ExtractionConfig::cache`; standard
+//! output is never compressed.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use kreuzberg::ExtractionConfig;
+//! use kreuzberg::watch::{self, WatchConfig};
+//!
+//! # async fn example() -> kreuzberg::Result<()> {
+//! let config: WatchConfig = toml::from_str(
+//!     r#"
+//!     paths = ["/srv/inbox"]
+//!     debounce_ms = 1000
+//!     sink = { type = "directory", path = "/srv/extracted" }
+//!     "#,
+//! )
+//! .unwrap();
+//! watch::run(&config, &ExtractionConfig::default()).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::compression::CompressedWriter;
+use crate::core::config::{Compression, ExtractionConfig};
+use crate::core::extractor::batch::extract_with_retries;
+use crate::core::extractor::extract_file;
+use crate::error::ErrorClass;
+use crate::store::BlobStore;
+use crate::types::ExtractionResult;
+use crate::{KreuzbergError, Result};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::AsyncWriteExt;
+use tokio::time::Instant;
+
+/// Where the results of watched files are emitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WatchSink {
+    /// One JSON line per file on standard output
+    #[default]
+    Stdout,
+    /// One JSON line per file, appended to a file; with compression, each line is
+    /// compressed separately and the file reads as one compressed stream
+    JsonLines { path: PathBuf },
+    /// One JSON file per watched file, at its path relative to the watched directory
+    /// with `.json` and the compression extension appended; a changed file overwrites
+    /// its earlier result
+    Directory { path: PathBuf },
+    /// The artifacts of each result in a content-addressed [`BlobStore`]; failures
+    /// are only logged
+    Store { path: PathBuf },
+}
+
+/// Configuration of [`run`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WatchConfig {
+    /// Directories to watch
+    pub paths: Vec<PathBuf>,
+
+    /// Watch subdirectories too
+    ///
+    /// Default: true
+    #[serde(default = "default_recursive")]
+    pub recursive: bool,
+
+    /// Time a file must go without writes before it is extracted, in milliseconds
+    ///
+    /// Default: 500
+    #[serde(default = "default_debounce_ms")]
+    pub debounce_ms: u64,
+
+    /// Also extract the files already in the directories at startup
+    ///
+    /// Default: false
+    #[serde(default)]
+    pub initial_scan: bool,
+
+    /// Where results are emitted
+    ///
+    /// Default: stdout
+    #[serde(default)]
+    pub sink: WatchSink,
+}
+
+fn default_recursive() -> bool {
+    true
+}
+
+fn default_debounce_ms() -> u64 {
+    500
+}
+
+impl WatchConfig {
+    /// Watch `paths` with the default settings.
+    pub fn new(paths: Vec<PathBuf>) -> Self {
+        Self {
+            paths,
+            recursive: default_recursive(),
+            debounce_ms: default_debounce_ms(),
+            initial_scan: false,
+            sink: WatchSink::default(),
+        }
+    }
+}
+
+/// Watch the directories of `config` and extract the files written to them.
+///
+/// Runs until the watcher fails; drop the returned future to stop watching. Files
+/// written while no watcher runs are only picked up with [`WatchConfig::initial_scan`].
+///
+/// # Errors
+///
+/// Returns an error if a watched path is not a directory, if the watcher cannot be
+/// started, or if writing to the sink fails. Extraction errors do not stop watching;
+/// they are emitted as failure records.
+pub async fn run(config: &WatchConfig, extraction_config: &ExtractionConfig) -> Result<()> {
+    if config.paths.is_empty() {
+        return Err(KreuzbergError::validation("No directories to watch"));
+    }
+    let mut roots = Vec::with_capacity(config.paths.len());
+    for path in &config.paths {
+        if !path.is_dir() {
+            return Err(KreuzbergError::validation(format!(
+                "Watched path is not a directory: {}",
+                path.display()
+            )));
+        }
+        roots.push(std::fs::canonicalize(path)?);
+    }
+
+    let compression = extraction_config
+        .cache
+        .as_ref()
+        .map(|cache| cache.compression)
+        .unwrap_or_default();
+    let mut sink = Sink::open(&config.sink, compression).await?;
+    let (tx, mut events) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+        let _ = tx.send(event);
+    })
+    .map_err(watch_error)?;
+    let mode = if config.recursive {
+        RecursiveMode::Recursive
+    } else {
+        RecursiveMode::NonRecursive
+    };
+    for root in &roots {
+        watcher.watch(root, mode).map_err(watch_error)?;
+    }
+
+    let mut pending = Debouncer::new(Duration::from_millis(config.debounce_ms));
+    if config.initial_scan {
+        let now = Instant::now();
+        for root in &roots {
+            for path in list_files(root, config.recursive)? {
+                pending.touch(path, now);
+            }
+        }
+    }
+
+    loop {
+        let deadline = pending.next_deadline();
+        tokio::select! {
+            event = events.recv() => match event {
+                Some(Ok(event)) if matches!(event.kind, EventKind::Create(_) | EventKind::Modify(_)) => {
+                    let now = Instant::now();
+                    for path in event.paths {
+                        if !is_hidden(&path) && !sink.contains(&path) {
+                            pending.touch(path, now);
+                        }
+                    }
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => tracing::warn!("File watcher error: {}", e),
+                None => return Ok(()),
+            },
+            _ = tokio::time::sleep_until(deadline.unwrap_or_else(Instant::now)), if deadline.is_some() => {
+                for path in pending.ready(Instant::now()) {
+                    if !path.is_file() {
+                        continue;
+                    }
+                    let result =
+                        extract_with_retries(extraction_config, Some(&path), None, || {
+                            extract_file(&path, None, extraction_config)
+                        })
+                        .await;
+                    let root = roots.iter().find(|root| path.starts_with(root));
+                    sink.emit(root.map(PathBuf::as_path), &path, result).await?;
+                }
+            }
+        }
+    }
+}
+
+/// Files waiting for their writes to settle.
+struct Debouncer {
+    delay: Duration,
+    deadlines: HashMap<PathBuf, Instant>,
+}
+
+impl Debouncer {
+    fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            deadlines: HashMap::new(),
+        }
+    }
+
+    /// Record a write to `path` at `now`, postponing its extraction.
+    fn touch(&mut self, path: PathBuf, now: Instant) {
+        self.deadlines.insert(path, now + self.delay);
+    }
+
+    fn next_deadline(&self) -> Option<Instant> {
+        self.deadlines.values().min().copied()
+    }
+
+    /// Remove and return the files without writes since the debounce delay, in path order.
+    fn ready(&mut self, now: Instant) -> Vec<PathBuf> {
+        let mut ready: Vec<PathBuf> = self
+            .deadlines
+            .iter()
+            .filter(|(_, deadline)| **deadline <= now)
+            .map(|(path, _)| path.clone())
+            .collect();
+        ready.sort();
+        for path in &ready {
+            self.deadlines.remove(path);
+        }
+        ready
+    }
+}
+
+/// A result line of a [`WatchSink`].
+#[derive(Serialize)]
+struct WatchRecord<'a> {
+    /// Lossy, as JSON cannot hold paths that are not valid UTF-8
+    path: String,
+    #[serde(flatten)]
+    outcome: Outcome<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+enum Outcome<'a> {
+    Extracted { result: &'a ExtractionResult },
+    Failed { error_type: ErrorClass, error: String },
+}
+
+enum Sink {
+    Stdout,
+    JsonLines {
+        path: PathBuf,
+        file: tokio::fs::File,
+        compression: Compression,
+    },
+    Directory {
+        path: PathBuf,
+        compression: Compression,
+    },
+    Store {
+        store: BlobStore,
+    },
+}
+
+impl Sink {
+    async fn open(sink: &WatchSink, compression: Compression) -> Result<Self> {
+        Ok(match sink {
+            WatchSink::Stdout => Self::Stdout,
+            WatchSink::JsonLines { path } => {
+                let file = tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .await?;
+                Self::JsonLines {
+                    path: std::fs::canonicalize(path)?,
+                    file,
+                    compression,
+                }
+            }
+            WatchSink::Directory { path } => {
+                tokio::fs::create_dir_all(path).await?;
+                Self::Directory {
+                    path: std::fs::canonicalize(path)?,
+                    compression,
+                }
+            }
+            WatchSink::Store { path } => {
+                tokio::fs::create_dir_all(path).await?;
+                Self::Store {
+                    store: BlobStore::open(std::fs::canonicalize(path)?)?.with_compression(compression),
+                }
+            }
+        })
+    }
+
+    /// Whether `path` is written by the sink itself, and must not be extracted.
+    fn contains(&self, path: &Path) -> bool {
+        match self {
+            Self::Stdout => false,
+            Self::JsonLines { path: sink, .. } => path == sink,
+            Self::Directory { path: sink, .. } => path.starts_with(sink),
+            Self::Store { store } => path.starts_with(store.root()),
+        }
+    }
+
+    async fn emit(&mut self, root: Option<&Path>, path: &Path, result: Result<ExtractionResult>) -> Result<()> {
+        let outcome = match &result {
+            Ok(result) => Outcome::Extracted { result },
+            Err(e) => {
+                tracing::warn!("Failed to extract watched file {}: {}", path.display(), e);
+                Outcome::Failed {
+                    error_type: e.class(),
+                    error: e.to_string(),
+                }
+            }
+        };
+        let record = WatchRecord {
+            path: crate::core::io::strip_verbatim_prefix(path)
+                .to_string_lossy()
+                .into_owned(),
+            outcome,
+        };
+
+        match self {
+            Self::Stdout => {
+                let mut line = serde_json::to_vec(&record)?;
+                line.push(b'\n');
+                let mut stdout = std::io::stdout().lock();
+                std::io::Write::write_all(&mut stdout, &line)?;
+                std::io::Write::flush(&mut stdout)?;
+            }
+            Self::JsonLines { file, compression, .. } => {
+                let line = encode(&record, true, *compression)?;
+                file.write_all(&line).await?;
+                file.flush().await?;
+            }
+            Self::Directory { path: dir, compression } => {
+                let relative = root
+                    .and_then(|root| path.strip_prefix(root).ok())
+                    .unwrap_or_else(|| Path::new(path.file_name().unwrap_or_default()));
+                let mut target = dir.join(relative).into_os_string();
+                target.push(".json");
+                target.push(compression.extension());
+                let target = PathBuf::from(target);
+                if let Some(parent) = target.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                // Write next to the target and rename, so readers never see a partial result.
+                let mut partial = target.clone().into_os_string();
+                partial.push(".partial");
+                tokio::fs::write(&partial, encode(&record, false, *compression)?).await?;
+                tokio::fs::rename(&partial, &target).await?;
+            }
+            Self::Store { store } => {
+                if let Ok(result) = &result {
+                    store.put_result(&path.to_string_lossy(), result)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `record` as JSON, optionally followed by a newline, compressed with `compression`.
+fn encode(record: &WatchRecord<'_>, newline: bool, compression: Compression) -> Result<Vec<u8>> {
+    let mut writer = CompressedWriter::new(Vec::new(), compression)?;
+    serde_json::to_writer(&mut writer, record)?;
+    if newline {
+        std::io::Write::write_all(&mut writer, b"\n")?;
+    }
+    writer.finish()
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .is_some_and(|name| name.as_encoded_bytes().first() == Some(&b'.'))
+}
+
+/// The non-hidden files in `dir`, and in its subdirectories with `recursive`.
+fn list_files(dir: &Path, recursive: bool) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if is_hidden(&path) {
+            continue;
+        }
+        if path.is_dir() {
+            if recursive {
+                files.extend(list_files(&path, recursive)?);
+            }
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+fn watch_error(e: notify::Error) -> KreuzbergError {
+    KreuzbergError::Io(std::io::Error::other(format!("File watcher error: {e}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_debouncer_waits_for_quiet_period() {
+        let start = Instant::now();
+        let mut debouncer = Debouncer::new(Duration::from_millis(100));
+        debouncer.touch(PathBuf::from("a.txt"), start);
+        debouncer.touch(PathBuf::from("b.txt"), start + Duration::from_millis(50));
+        debouncer.touch(PathBuf::from("a.txt"), start + Duration::from_millis(80));
+
+        assert_eq!(debouncer.next_deadline(), Some(start + Duration::from_millis(150)));
+        assert!(debouncer.ready(start + Duration::from_millis(120)).is_empty());
+        assert_eq!(
+            debouncer.ready(start + Duration::from_millis(150)),
+            vec![PathBuf::from("b.txt")]
+        );
+        assert_eq!(
+            debouncer.ready(start + Duration::from_millis(200)),
+            vec![PathBuf::from("a.txt")]
+        );
+        assert_eq!(debouncer.next_deadline(), None);
+    }
+
+    async fn wait_for(path: &Path) -> String {
+        for _ in 0..200 {
+            if let Ok(content) = std::fs::read_to_string(path)
+                && !content.is_empty()
+            {
+                return content;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        panic!("{} was not written", path.display());
+    }
+
+    #[tokio::test]
+    async fn test_watch_extracts_new_files() {
+        let inbox = tempdir().unwrap();
+        let out = tempdir().unwrap();
+        let sink = out.path().join("results.jsonl");
+        let config = WatchConfig {
+            debounce_ms: 50,
+            sink: WatchSink::JsonLines { path: sink.clone() },
+            ..WatchConfig::new(vec![inbox.path().to_path_buf()])
+        };
+
+        let watcher = tokio::spawn(async move { run(&config, &ExtractionConfig::default()).await });
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        std::fs::write(inbox.path().join(".upload.tmp"), "partial").unwrap();
+        std::fs::write(inbox.path().join("note.txt"), "Dropped into the inbox").unwrap();
+
+        let content = wait_for(&sink).await;
+        watcher.abort();
+        let record: serde_json::Value = serde_json::from_str(content.lines().next().unwrap()).unwrap();
+        assert_eq!(record["status"], "extracted");
+        assert!(record["path"].as_str().unwrap().ends_with("note.txt"));
+        assert!(
+            record["result"]["content"]
+                .as_str()
+                .unwrap()
+                .contains("Dropped into the inbox")
+        );
+        assert_eq!(content.lines().count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_watch_initial_scan_to_directory() {
+        let inbox = tempdir().unwrap();
+        let out = tempdir().unwrap();
+        std::fs::create_dir(inbox.path().join("nested")).unwrap();
+        std::fs::write(inbox.path().join("nested/report.txt"), "Already there").unwrap();
+        let config = WatchConfig {
+            debounce_ms: 10,
+            initial_scan: true,
+            sink: WatchSink::Directory {
+                path: out.path().to_path_buf(),
+            },
+            ..WatchConfig::new(vec![inbox.path().to_path_buf()])
+        };
+
+        let watcher = tokio::spawn(async move { run(&config, &ExtractionConfig::default()).await });
+        let content = wait_for(&out.path().join("nested/report.txt.json")).await;
+        watcher.abort();
+        let record: serde_json::Value = serde_json::from_str(&content).unwrap();
+        assert!(record["result"]["content"].as_str().unwrap().contains("Already there"));
+    }
+
+    #[tokio::test]
+    async fn test_watch_initial_scan_to_store() {
+        let inbox = tempdir().unwrap();
+        let out = tempdir().unwrap();
+        std::fs::write(inbox.path().join("memo.txt"), "Stored once").unwrap();
+        let config = WatchConfig {
+            debounce_ms: 10,
+            initial_scan: true,
+            sink: WatchSink::Store {
+                path: out.path().to_path_buf(),
+            },
+            ..WatchConfig::new(vec![inbox.path().to_path_buf()])
+        };
+
+        let watcher = tokio::spawn(async move { run(&config, &ExtractionConfig::default()).await });
+        let line = wait_for(&out.path().join("index.jsonl")).await;
+        watcher.abort();
+        let entry: crate::store::IndexEntry = serde_json::from_str(line.lines().next().unwrap()).unwrap();
+        assert!(entry.source.ends_with("memo.txt"));
+        let result = BlobStore::open(out.path())
+            .unwrap()
+            .get_result(&entry.manifest)
+            .unwrap();
+        assert!(result.content.contains("Stored once"));
+    }
+
+    #[tokio::test]
+    async fn test_watch_rejects_missing_directory() {
+        let config = WatchConfig::new(vec![PathBuf::from("/nonexistent/inbox")]);
+        let result = run(&config, &ExtractionConfig::default()).await;
+        assert!(matches!(result, Err(KreuzbergError::Validation { .. })));
+    }
+}
@@ -244,13 +244,46 @@ fn test_extraction_config_no_unknown_fields_in_default() {
         "pdf_options",
         "token_reduction",
         "language_detection",
+        "script_detection",
+        "transliteration",
+        "translation",
+        "spellcheck",
+        "value_normalization",
         "pages",
         "keywords",
         "postprocessor",
+        "cleanup",
+        "boilerplate",
+        "glossary",
+        "rules",
+        "content_filter",
+        "output",
+        "token_count",
+        "docx",
+        "spreadsheet",
+        "barcodes",
+        "signatures",
+        "io",
+        "containers",
+        "security",
+        "network",
+        "xml",
+        "isolation",
+        "worker",
+        "retry",
+        "walk",
+        "provenance",
+        "plugin_guard",
+        "cache",
+        "metadata",
         "html_options",
         "max_concurrent_extractions",
         "result_format",
         "output_format",
+        "determinism",
+        "processing_report",
+        "fallbacks",
+        "salvage",
     ];
 
     for key in obj.keys() {
@@ -279,6 +312,7 @@ fn test_extraction_config_needs_image_processing() {
         language: "eng".to_string(),
         tesseract_config: None,
         output_format: None,
+        ..Default::default()
     });
     assert!(
         config.needs_image_processing(),
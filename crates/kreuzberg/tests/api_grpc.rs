@@ -0,0 +1,100 @@
+//! Integration tests for the gRPC service served by the API router.
+
+#![cfg(feature = "grpc")]
+
+use kreuzberg::ExtractionConfig;
+use kreuzberg::api::grpc::proto::extract_chunks_response::Event;
+use kreuzberg::api::grpc::proto::extract_request::Part;
+use kreuzberg::api::grpc::proto::extraction_client::ExtractionClient;
+use kreuzberg::api::grpc::proto::{ExtractRequest, UploadHeader};
+use kreuzberg::api::{ApiSizeLimits, create_router, create_router_with_limits};
+use tonic::Code;
+
+fn upload(mime_type: &str, config_json: &str, parts: &[&[u8]]) -> Vec<ExtractRequest> {
+    let mut messages = vec![ExtractRequest {
+        part: Some(Part::Header(UploadHeader {
+            file_name: "document".to_string(),
+            mime_type: mime_type.to_string(),
+            config_json: config_json.to_string(),
+        })),
+    }];
+    messages.extend(parts.iter().map(|part| ExtractRequest {
+        part: Some(Part::Data(part.to_vec())),
+    }));
+    messages
+}
+
+#[tokio::test]
+async fn test_grpc_extract_streamed_upload() {
+    let mut client = ExtractionClient::new(create_router(ExtractionConfig::default()));
+
+    let response = client
+        .extract(futures::stream::iter(upload(
+            "text/plain",
+            "",
+            &[b"Hello ", b"from a ", b"streamed upload"],
+        )))
+        .await
+        .expect("extraction succeeds")
+        .into_inner();
+
+    let result = response.result.expect("result is set");
+    assert!(result.content.contains("Hello from a streamed upload"));
+    assert!(result.mime_type.starts_with("text/plain"));
+    let metadata: serde_json::Value = serde_json::from_str(&result.metadata_json).unwrap();
+    assert!(metadata.is_object());
+}
+
+#[tokio::test]
+async fn test_grpc_extract_chunks_streams_chunks_then_result() {
+    let mut client = ExtractionClient::new(create_router(ExtractionConfig::default()));
+    let text = "First sentence of the document. ".repeat(40);
+    let config = r#"{"chunking": {"max_chars": 200, "max_overlap": 0}}"#;
+
+    let mut events = client
+        .extract_chunks(futures::stream::iter(upload("text/plain", config, &[text.as_bytes()])))
+        .await
+        .expect("extraction succeeds")
+        .into_inner();
+
+    let mut chunks = Vec::new();
+    let mut result = None;
+    while let Some(event) = events.message().await.expect("stream is readable") {
+        match event.event.expect("event is set") {
+            Event::Chunk(chunk) => {
+                assert!(result.is_none(), "chunks come before the result");
+                chunks.push(chunk);
+            }
+            Event::Result(final_result) => result = Some(final_result),
+        }
+    }
+
+    assert!(chunks.len() > 1);
+    assert_eq!(chunks[0].total_chunks as usize, chunks.len());
+    let result = result.expect("result is sent last");
+    assert!(result.chunks.is_empty());
+    assert!(result.content.contains("First sentence"));
+}
+
+#[tokio::test]
+async fn test_grpc_extract_rejects_invalid_uploads() {
+    let mut client = ExtractionClient::new(create_router_with_limits(
+        ExtractionConfig::default(),
+        ApiSizeLimits::new(64, 64),
+    ));
+    let part = [b'x'; 40];
+
+    let missing_header = vec![ExtractRequest {
+        part: Some(Part::Data(b"data".to_vec())),
+    }];
+    let status = client.extract(futures::stream::iter(missing_header)).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+
+    let too_large = upload("text/plain", "", &[&part, &part]);
+    let status = client.extract(futures::stream::iter(too_large)).await.unwrap_err();
+    assert_eq!(status.code(), Code::ResourceExhausted);
+
+    let unsupported = upload("invalid/mime", "", &[b"data"]);
+    let status = client.extract(futures::stream::iter(unsupported)).await.unwrap_err();
+    assert_eq!(status.code(), Code::InvalidArgument);
+}
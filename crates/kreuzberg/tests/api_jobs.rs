@@ -0,0 +1,255 @@
+//! Integration tests for async extraction jobs and their result callbacks.
+
+#![cfg(feature = "api")]
+
+use std::time::Duration;
+
+use axum::{
+    Router,
+    body::{Body, Bytes},
+    http::{HeaderMap, Request, StatusCode},
+    routing::post,
+};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tokio::sync::mpsc;
+use tower::ServiceExt;
+
+use kreuzberg::{
+    ExtractionConfig,
+    api::{ApiSizeLimits, create_router, create_router_with_limits_and_server_config},
+    core::{ApiQuota, ApiTenant, JobsConfig, ServerConfig},
+};
+
+const BOUNDARY: &str = "----boundary";
+
+fn job_request(mime_type: &str, callback_url: Option<&str>, api_key: Option<&str>) -> Request<Body> {
+    let mut body = format!(
+        "--{BOUNDARY}\r\n\
+         Content-Disposition: form-data; name=\"files\"; filename=\"test\"\r\n\
+         Content-Type: {mime_type}\r\n\
+         \r\n\
+         Hello, jobs!\r\n"
+    );
+    if let Some(url) = callback_url {
+        body.push_str(&format!(
+            "--{BOUNDARY}\r\n\
+             Content-Disposition: form-data; name=\"callback_url\"\r\n\
+             \r\n\
+             {url}\r\n"
+        ));
+    }
+    body.push_str(&format!("--{BOUNDARY}--\r\n"));
+
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/jobs")
+        .header("content-type", format!("multipart/form-data; boundary={BOUNDARY}"));
+    if let Some(key) = api_key {
+        request = request.header("x-api-key", key);
+    }
+    request.body(Body::from(body)).expect("Failed to build request")
+}
+
+fn get(uri: &str, api_key: Option<&str>) -> Request<Body> {
+    let mut request = Request::builder().uri(uri);
+    if let Some(key) = api_key {
+        request = request.header("x-api-key", key);
+    }
+    request.body(Body::empty()).expect("Failed to build request")
+}
+
+async fn body_json(response: axum::response::Response) -> serde_json::Value {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read response body");
+    serde_json::from_slice(&body).expect("Response body is not JSON")
+}
+
+/// Poll `GET /jobs/{id}` until the job and its callback are finished.
+async fn wait_for_job(app: &Router, id: &str) -> serde_json::Value {
+    for _ in 0..200 {
+        let job = body_json(app.clone().oneshot(get(&format!("/jobs/{id}"), None)).await.unwrap()).await;
+        let finished = matches!(job["status"].as_str(), Some("succeeded" | "failed"));
+        if finished && job["callback"]["status"] != "pending" {
+            return job;
+        }
+        tokio::time::sleep(Duration::from_millis(25)).await;
+    }
+    panic!("Job {id} did not finish");
+}
+
+/// Start a server that forwards the headers and body of every callback to the returned channel.
+async fn callback_receiver() -> (String, mpsc::UnboundedReceiver<(HeaderMap, Bytes)>) {
+    let (sender, receiver) = mpsc::unbounded_channel();
+    let app = Router::new().route(
+        "/hook",
+        post(move |headers: HeaderMap, body: Bytes| {
+            let sender = sender.clone();
+            async move {
+                let _ = sender.send((headers, body));
+                StatusCode::NO_CONTENT
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, app).await });
+    (url, receiver)
+}
+
+fn jobs_router(jobs: JobsConfig) -> Router {
+    let server_config = ServerConfig {
+        jobs,
+        ..Default::default()
+    };
+    create_router_with_limits_and_server_config(ExtractionConfig::default(), ApiSizeLimits::default(), server_config)
+}
+
+#[tokio::test]
+async fn test_job_runs_in_the_background() {
+    let app = create_router(ExtractionConfig::default());
+
+    let response = app
+        .clone()
+        .oneshot(job_request("text/plain", None, None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let job = body_json(response).await;
+    assert_eq!(job["status"], "pending");
+    assert!(job["callback"].is_null());
+
+    let job = wait_for_job(&app, job["id"].as_str().unwrap()).await;
+    assert_eq!(job["status"], "succeeded");
+    assert!(job["finished_at"].is_u64());
+    assert!(job["results"][0]["content"].as_str().unwrap().contains("Hello, jobs!"));
+
+    let response = app.oneshot(get("/jobs/unknown", None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn test_job_result_is_posted_to_signed_callback() {
+    let (url, mut callbacks) = callback_receiver().await;
+    let app = jobs_router(JobsConfig {
+        signing_key: Some("callback-secret".to_string()),
+        ..Default::default()
+    });
+
+    let response = app
+        .clone()
+        .oneshot(job_request("text/plain", Some(&url), None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let id = body_json(response).await["id"].as_str().unwrap().to_string();
+
+    let (headers, body) = tokio::time::timeout(Duration::from_secs(10), callbacks.recv())
+        .await
+        .expect("Callback was not delivered")
+        .unwrap();
+    assert_eq!(headers["x-kreuzberg-job-id"], id.as_str());
+    let timestamp = headers["x-kreuzberg-timestamp"].to_str().unwrap();
+    let mut mac = Hmac::<Sha256>::new_from_slice(b"callback-secret").unwrap();
+    mac.update(format!("{timestamp}.").as_bytes());
+    mac.update(&body);
+    let expected = format!("sha256={}", hex::encode(mac.finalize().into_bytes()));
+    assert_eq!(headers["x-kreuzberg-signature"], expected.as_str());
+
+    let payload: serde_json::Value = serde_json::from_slice(&body).unwrap();
+    assert_eq!(payload["id"], id.as_str());
+    assert_eq!(payload["status"], "succeeded");
+    assert!(
+        payload["results"][0]["content"]
+            .as_str()
+            .unwrap()
+            .contains("Hello, jobs!")
+    );
+
+    let job = wait_for_job(&app, &id).await;
+    assert_eq!(job["callback"]["status"], "delivered");
+    assert_eq!(job["callback"]["attempts"], 1);
+}
+
+#[tokio::test]
+async fn test_failed_job_and_undeliverable_callback() {
+    // Bind and drop a listener to get a local port nothing listens on.
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}/hook", listener.local_addr().unwrap());
+    drop(listener);
+
+    let app = jobs_router(JobsConfig {
+        callback_attempts: 1,
+        ..Default::default()
+    });
+    let response = app
+        .clone()
+        .oneshot(job_request("application/x-unknown-format", Some(&url), None))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let id = body_json(response).await["id"].as_str().unwrap().to_string();
+
+    let job = wait_for_job(&app, &id).await;
+    assert_eq!(job["status"], "failed");
+    assert!(job["error"]["message"].is_string());
+    assert_eq!(job["callback"]["status"], "failed");
+    assert_eq!(job["callback"]["attempts"], 1);
+    assert!(job["callback"]["error"].is_string());
+}
+
+#[tokio::test]
+async fn test_callback_urls_are_checked() {
+    let app = jobs_router(JobsConfig {
+        callback_hosts: vec!["hooks.example.com".to_string()],
+        ..Default::default()
+    });
+
+    for url in [
+        "ftp://hooks.example.com/done",
+        "https://other.example.com/done",
+        "not a url",
+    ] {
+        let response = app
+            .clone()
+            .oneshot(job_request("text/plain", Some(url), None))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{url}");
+    }
+}
+
+#[tokio::test]
+async fn test_jobs_are_only_visible_to_their_tenant() {
+    let tenant = |name: &str, key: &str| ApiTenant {
+        name: name.to_string(),
+        api_keys: vec![key.to_string()],
+        config: None,
+        quota: ApiQuota::default(),
+    };
+    let server_config = ServerConfig {
+        tenants: vec![tenant("search", "search-key"), tenant("archive", "archive-key")],
+        ..Default::default()
+    };
+    let app = create_router_with_limits_and_server_config(
+        ExtractionConfig::default(),
+        ApiSizeLimits::default(),
+        server_config,
+    );
+
+    let response = app
+        .clone()
+        .oneshot(job_request("text/plain", None, Some("search-key")))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::ACCEPTED);
+    let uri = format!("/jobs/{}", body_json(response).await["id"].as_str().unwrap());
+
+    let response = app.clone().oneshot(get(&uri, Some("search-key"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let response = app.clone().oneshot(get(&uri, Some("archive-key"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = app.oneshot(get(&uri, None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+}
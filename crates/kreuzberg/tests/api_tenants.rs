@@ -0,0 +1,173 @@
+//! Integration tests for API keys, tenant configs, quotas and metrics of the API server.
+
+#![cfg(feature = "api")]
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode, header},
+};
+use tower::ServiceExt;
+
+use kreuzberg::{
+    ChunkingConfig, ExtractionConfig,
+    api::{ApiSizeLimits, create_router, create_router_with_limits_and_server_config},
+    core::{ApiQuota, ApiTenant, ServerConfig},
+};
+
+const BOUNDARY: &str = "----boundary";
+
+fn extract_request(auth: Option<(&str, &str)>) -> Request<Body> {
+    let body = format!(
+        "--{BOUNDARY}\r\n\
+         Content-Disposition: form-data; name=\"files\"; filename=\"test.txt\"\r\n\
+         Content-Type: text/plain\r\n\
+         \r\n\
+         Hello, tenants!\r\n\
+         --{BOUNDARY}--\r\n"
+    );
+    let mut request = Request::builder()
+        .method("POST")
+        .uri("/extract")
+        .header("content-type", format!("multipart/form-data; boundary={BOUNDARY}"));
+    if let Some((name, value)) = auth {
+        request = request.header(name, value);
+    }
+    request.body(Body::from(body)).expect("Failed to build request")
+}
+
+fn get(uri: &str) -> Request<Body> {
+    Request::builder()
+        .uri(uri)
+        .body(Body::empty())
+        .expect("Failed to build request")
+}
+
+fn tenant_router(quota: ApiQuota) -> Router {
+    let server_config = ServerConfig {
+        tenants: vec![
+            ApiTenant {
+                name: "search".to_string(),
+                api_keys: vec!["search-key".to_string()],
+                config: Some(ExtractionConfig {
+                    chunking: Some(ChunkingConfig::default()),
+                    ..Default::default()
+                }),
+                quota,
+            },
+            ApiTenant {
+                name: "archive".to_string(),
+                api_keys: vec!["archive-key".to_string()],
+                config: None,
+                quota: ApiQuota::default(),
+            },
+        ],
+        ..Default::default()
+    };
+    create_router_with_limits_and_server_config(ExtractionConfig::default(), ApiSizeLimits::default(), server_config)
+}
+
+async fn body_text(response: axum::response::Response) -> String {
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read response body");
+    String::from_utf8(body.to_vec()).expect("Response body is not UTF-8")
+}
+
+#[tokio::test]
+async fn test_requests_without_tenants_need_no_key() {
+    let app = create_router(ExtractionConfig::default());
+
+    let response = app.clone().oneshot(extract_request(None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let metrics = body_text(app.oneshot(get("/metrics")).await.unwrap()).await;
+    assert!(metrics.contains("kreuzberg_requests_total{tenant=\"default\",route=\"/extract\",status=\"200\"} 1"));
+}
+
+#[tokio::test]
+async fn test_requests_need_a_valid_api_key() {
+    let app = tenant_router(ApiQuota::default());
+
+    let response = app.clone().oneshot(extract_request(None)).await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    let error: serde_json::Value = serde_json::from_str(&body_text(response).await).unwrap();
+    assert_eq!(error["error_type"], "AuthenticationError");
+
+    let response = app
+        .clone()
+        .oneshot(extract_request(Some(("x-api-key", "wrong-key"))))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    let response = app.clone().oneshot(get("/health")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .oneshot(extract_request(Some(("authorization", "Bearer archive-key"))))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_tenant_config_replaces_default_config() {
+    let app = tenant_router(ApiQuota::default());
+
+    let response = app
+        .clone()
+        .oneshot(extract_request(Some(("x-api-key", "search-key"))))
+        .await
+        .unwrap();
+    let results: serde_json::Value = serde_json::from_str(&body_text(response).await).unwrap();
+    assert!(results[0]["chunks"].is_array(), "search tenant config enables chunking");
+
+    let response = app
+        .oneshot(extract_request(Some(("x-api-key", "archive-key"))))
+        .await
+        .unwrap();
+    let results: serde_json::Value = serde_json::from_str(&body_text(response).await).unwrap();
+    assert!(results[0]["chunks"].is_null(), "archive tenant uses the server default");
+}
+
+#[tokio::test]
+async fn test_quota_rejects_requests_over_the_rate() {
+    let app = tenant_router(ApiQuota {
+        requests_per_minute: Some(1),
+        max_concurrent_requests: None,
+    });
+
+    let response = app
+        .clone()
+        .oneshot(extract_request(Some(("x-api-key", "search-key"))))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .clone()
+        .oneshot(extract_request(Some(("x-api-key", "search-key"))))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let retry_after: u64 = response.headers()[header::RETRY_AFTER]
+        .to_str()
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((1..=60).contains(&retry_after));
+
+    // Quotas are per API key, other tenants are not affected.
+    let response = app
+        .clone()
+        .oneshot(extract_request(Some(("x-api-key", "archive-key"))))
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let metrics = body_text(app.oneshot(get("/metrics")).await.unwrap()).await;
+    assert!(metrics.contains("kreuzberg_requests_total{tenant=\"search\",route=\"/extract\",status=\"200\"} 1"));
+    assert!(metrics.contains("kreuzberg_requests_total{tenant=\"archive\",route=\"/extract\",status=\"200\"} 1"));
+    assert!(metrics.contains("kreuzberg_rejected_requests_total{tenant=\"search\",reason=\"quota\"} 1"));
+}
@@ -10,7 +10,7 @@ use serde_json::json;
 use tower::ServiceExt;
 
 use kreuzberg::{
-    ExtractionConfig,
+    ExtractionConfig, SelfTestReport,
     api::{HealthResponse, InfoResponse, create_router},
 };
 
@@ -64,6 +64,33 @@ async fn test_info_endpoint() {
 
     assert!(!info.version.is_empty());
     assert!(info.rust_backend);
+    assert!(info.capabilities.servers.contains(&"api".to_string()));
+}
+
+/// Test the readiness endpoint.
+#[tokio::test]
+async fn test_ready_endpoint() {
+    let app = create_router(ExtractionConfig::default());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .uri("/ready")
+                .body(Body::empty())
+                .expect("Failed to create HTTP request body"),
+        )
+        .await
+        .expect("Failed to send HTTP request");
+
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+        .await
+        .expect("Failed to read HTTP response body");
+    let report: SelfTestReport = serde_json::from_slice(&body).expect("Failed to deserialize JSON response");
+
+    assert!(report.passed);
+    assert_eq!(report.components[0].name, "pipeline");
 }
 
 /// Test extract endpoint with no files returns 400.
@@ -260,6 +260,7 @@ fn test_ocr_multipage_efficiency() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         use_cache: true,
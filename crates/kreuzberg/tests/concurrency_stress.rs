@@ -197,6 +197,7 @@ async fn test_concurrent_ocr_processing() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         use_cache: true,
@@ -267,6 +268,7 @@ fn test_concurrent_ocr_cache_stress() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         use_cache: true,
@@ -388,6 +390,7 @@ async fn test_concurrent_pipeline_processing() {
                 pages: None,
                 elements: None,
                 djot_content: None,
+                scripts: None,
             };
 
             run_pipeline(result, &config).await
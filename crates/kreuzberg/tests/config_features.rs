@@ -23,6 +23,10 @@ async fn test_chunking_enabled() {
             overlap: 10,
             embedding: None,
             preset: None,
+            overlap_strategy: kreuzberg::chunking::OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
             trim: true,
             chunker_type: kreuzberg::chunking::ChunkerType::Text,
         }),
@@ -68,6 +72,10 @@ async fn test_chunking_with_overlap() {
             overlap: 20,
             embedding: None,
             preset: None,
+            overlap_strategy: kreuzberg::chunking::OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
             trim: true,
             chunker_type: kreuzberg::chunking::ChunkerType::Text,
         }),
@@ -110,6 +118,10 @@ async fn test_chunking_custom_sizes() {
             overlap: 50,
             embedding: None,
             preset: None,
+            overlap_strategy: kreuzberg::chunking::OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
             trim: true,
             chunker_type: kreuzberg::chunking::ChunkerType::Text,
         }),
@@ -522,6 +534,10 @@ async fn test_chunking_with_embeddings() {
             overlap: 20,
             embedding: Some(EmbeddingConfig::default()),
             preset: None,
+            overlap_strategy: kreuzberg::chunking::OverlapStrategy::Characters,
+            heading_context: false,
+            tables: None,
+            sentence_segmenter: None,
             trim: true,
             chunker_type: kreuzberg::chunking::ChunkerType::Text,
         }),
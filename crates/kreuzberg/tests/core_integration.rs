@@ -460,6 +460,7 @@ async fn test_extraction_with_ocr_config() {
             backend: "tesseract".to_string(),
             language: "eng".to_string(),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
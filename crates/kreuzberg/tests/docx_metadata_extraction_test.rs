@@ -29,17 +29,17 @@ async fn test_docx_full_metadata_extraction() {
     );
 
     assert_eq!(
-        result.metadata.additional.get("created_by").and_then(|v| v.as_str()),
+        result.metadata.created_by.as_deref(),
         Some("Christoph Auer"),
         "Should have correct creator"
     );
     assert_eq!(
-        result.metadata.additional.get("modified_by").and_then(|v| v.as_str()),
+        result.metadata.modified_by.as_deref(),
         Some("Maxim Lysak"),
         "Should have correct last modified by"
     );
     assert_eq!(
-        result.metadata.additional.get("created_at").and_then(|v| v.as_str()),
+        result.metadata.created_at.as_deref(),
         Some("2024-10-09T12:43:00Z"),
         "Should have correct creation date"
     );
@@ -49,16 +49,8 @@ async fn test_docx_full_metadata_extraction() {
         "Should have revision number"
     );
 
-    assert_eq!(
-        result.metadata.additional.get("page_count").and_then(|v| v.as_i64()),
-        Some(2),
-        "Should have 2 pages"
-    );
-    assert_eq!(
-        result.metadata.additional.get("word_count").and_then(|v| v.as_i64()),
-        Some(108),
-        "Should have 108 words"
-    );
+    assert_eq!(result.metadata.page_count, Some(2), "Should have 2 pages");
+    assert_eq!(result.metadata.word_count, Some(108), "Should have 108 words");
     assert_eq!(
         result
             .metadata
@@ -107,16 +99,8 @@ async fn test_docx_minimal_metadata_extraction() {
 
     assert!(!result.content.is_empty(), "Content should not be empty");
 
-    assert_eq!(
-        result.metadata.additional.get("page_count").and_then(|v| v.as_i64()),
-        Some(1),
-        "Should have 1 page"
-    );
-    assert_eq!(
-        result.metadata.additional.get("word_count").and_then(|v| v.as_i64()),
-        Some(520),
-        "Should have 520 words"
-    );
+    assert_eq!(result.metadata.page_count, Some(1), "Should have 1 page");
+    assert_eq!(result.metadata.word_count, Some(520), "Should have 520 words");
 
     println!("✅ DOCX minimal metadata extraction test passed!");
 }
@@ -233,17 +217,17 @@ async fn test_docx_keywords_extraction() {
 
     // Verify other metadata was also extracted
     assert_eq!(
-        result.metadata.additional.get("created_by").and_then(|v| v.as_str()),
+        result.metadata.created_by.as_deref(),
         Some("Test Author"),
         "Should have correct creator"
     );
     assert_eq!(
-        result.metadata.additional.get("title").and_then(|v| v.as_str()),
+        result.metadata.title.as_deref(),
         Some("Test Document"),
         "Should have correct title"
     );
     assert_eq!(
-        result.metadata.additional.get("subject").and_then(|v| v.as_str()),
+        result.metadata.subject.as_deref(),
         Some("Testing keyword extraction"),
         "Should have correct subject"
     );
@@ -402,3 +402,114 @@ async fn test_concurrent_extractions() {
         );
     }
 }
+
+#[cfg(any(feature = "archives", feature = "office", feature = "pdf"))]
+fn salvage_warnings(extraction: &kreuzberg::ExtractionResult) -> Vec<String> {
+    extraction.metadata.additional["salvage_warnings"]
+        .as_array()
+        .expect("Salvage warnings should be reported")
+        .iter()
+        .map(|warning| warning.as_str().unwrap().to_string())
+        .collect()
+}
+
+/// Test salvage of a ZIP archive whose central directory was cut off.
+#[tokio::test]
+#[cfg(feature = "archives")]
+async fn test_salvage_truncated_zip() {
+    let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+    writer
+        .start_file("notes.txt", zip::write::SimpleFileOptions::default())
+        .expect("Operation failed");
+    writer
+        .write_all(b"Recovered from a damaged archive")
+        .expect("Operation failed");
+    let archive = writer.finish().expect("Operation failed").into_inner();
+    let central_directory = archive
+        .windows(4)
+        .position(|window| window == b"PK\x01\x02")
+        .expect("Archive should have a central directory");
+    let damaged = &archive[..central_directory];
+
+    let result = extract_bytes(damaged, "application/zip", &ExtractionConfig::default()).await;
+    assert!(result.is_err(), "Damaged ZIP should fail without salvage");
+
+    let config = ExtractionConfig {
+        salvage: true,
+        ..Default::default()
+    };
+    let extraction = extract_bytes(damaged, "application/zip", &config)
+        .await
+        .expect("Damaged ZIP should be salvaged");
+    assert!(extraction.content.contains("notes.txt"));
+    let warnings = salvage_warnings(&extraction);
+    assert!(warnings[0].starts_with("Extraction of the input failed"));
+    assert!(warnings.iter().any(|warning| warning.contains("central directory")));
+}
+
+/// Test salvage of a DOCX whose central directory was cut off.
+#[tokio::test]
+#[cfg(feature = "office")]
+async fn test_salvage_truncated_docx() {
+    if helpers::skip_if_missing("extraction_test.docx") {
+        return;
+    }
+    let docx = std::fs::read(helpers::get_test_file_path("extraction_test.docx")).expect("Operation failed");
+    let central_directory = docx
+        .windows(4)
+        .position(|window| window == b"PK\x01\x02")
+        .expect("DOCX should have a central directory");
+    let damaged = &docx[..central_directory];
+    let mime_type = "application/vnd.openxmlformats-officedocument.wordprocessingml.document";
+
+    let intact = extract_bytes(&docx, mime_type, &ExtractionConfig::default())
+        .await
+        .expect("Operation failed");
+    assert!(
+        extract_bytes(damaged, mime_type, &ExtractionConfig::default())
+            .await
+            .is_err(),
+        "Damaged DOCX should fail without salvage"
+    );
+
+    let config = ExtractionConfig {
+        salvage: true,
+        ..Default::default()
+    };
+    let extraction = extract_bytes(damaged, mime_type, &config)
+        .await
+        .expect("Damaged DOCX should be salvaged");
+    assert_eq!(extraction.content, intact.content);
+    assert!(!salvage_warnings(&extraction).is_empty());
+}
+
+/// Test salvage of a PDF cut off in its catalog, after the pages.
+#[tokio::test]
+#[cfg(feature = "pdf")]
+async fn test_salvage_truncated_pdf() {
+    let content = "BT /F1 12 Tf 10 20 Td (Salvaged page) Tj ET";
+    let damaged = format!(
+        "%PDF-1.4\n\
+         2 0 obj\n<< /Type /Pages /Kids [3 0 R] /Count 1 >>\nendobj\n\
+         3 0 obj\n<< /Type /Page /Parent 2 0 R /MediaBox [0 0 200 50] /Contents 4 0 R \
+         /Resources << /Font << /F1 5 0 R >> >> >>\nendobj\n\
+         4 0 obj\n<< /Length {} >>\nstream\n{content}\nendstream\nendobj\n\
+         5 0 obj\n<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica >>\nendobj\n\
+         1 0 obj\n<< /Type /Cat",
+        content.len()
+    );
+
+    let config = ExtractionConfig {
+        salvage: true,
+        ..Default::default()
+    };
+    let extraction = extract_bytes(damaged.as_bytes(), "application/pdf", &config)
+        .await
+        .expect("Damaged PDF should be salvaged");
+    assert!(extraction.content.contains("Salvaged page"));
+    assert!(
+        salvage_warnings(&extraction)
+            .iter()
+            .any(|warning| warning.contains("page tree"))
+    );
+}
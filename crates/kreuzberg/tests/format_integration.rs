@@ -101,6 +101,7 @@ async fn test_ocr_simple_english_image_async() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
@@ -144,6 +145,7 @@ async fn test_ocr_image_without_text_async() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
@@ -116,6 +116,7 @@ pub fn test_config_with_ocr() -> kreuzberg::core::config::ExtractionConfig {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -67,6 +67,33 @@ fn test_bmp_image_format() {
     assert!(result.detected_languages.is_none(), "Language detection not enabled");
 }
 
+#[cfg(feature = "jxl")]
+#[test]
+fn test_jpeg_xl_image_with_ocr() {
+    use kreuzberg::types::FormatMetadata;
+
+    if skip_if_missing("images/test_hello_world.jxl") {
+        return;
+    }
+
+    let file_path = get_test_file_path("images/test_hello_world.jxl");
+    let config = test_config_with_ocr();
+
+    let result = extract_file_sync(&file_path, None, &config).expect("Should decode JPEG XL image for OCR");
+
+    assert_mime_type(&result, "image/jxl");
+
+    let Some(FormatMetadata::Image(image)) = &result.metadata.format else {
+        panic!("Expected image metadata, got {:?}", result.metadata.format);
+    };
+    assert_eq!(image.format, "JXL");
+    assert_eq!((image.width, image.height), (200, 100));
+
+    if !result.content.trim().is_empty() {
+        assert_min_content_length(&result, 5);
+    }
+}
+
 #[test]
 fn test_ocr_simple_text() {
     if skip_if_missing("images/test_hello_world.png") {
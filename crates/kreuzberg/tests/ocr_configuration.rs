@@ -33,6 +33,7 @@ fn test_ocr_language_english() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -59,6 +60,7 @@ fn test_ocr_language_german() {
             language: "deu".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -98,6 +100,7 @@ fn test_ocr_language_multiple() {
             language: "eng+kor".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -140,6 +143,7 @@ fn test_ocr_psm_auto() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -169,6 +173,7 @@ fn test_ocr_psm_single_block() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -198,6 +203,7 @@ fn test_ocr_psm_single_line() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -225,6 +231,7 @@ fn test_force_ocr_on_text_pdf() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
@@ -256,6 +263,7 @@ fn test_force_ocr_disabled() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -292,6 +300,7 @@ fn test_table_detection_enabled() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -321,6 +330,7 @@ fn test_table_detection_disabled() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -350,6 +360,7 @@ fn test_language_model_ngram_configuration() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -380,6 +391,7 @@ fn test_dictionary_correction_enabled() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -410,6 +422,7 @@ fn test_character_whitelist() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -439,6 +452,7 @@ fn test_ocr_cache_enabled() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         use_cache: true,
@@ -479,6 +493,7 @@ fn test_ocr_cache_disabled() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         use_cache: false,
@@ -514,6 +529,7 @@ fn test_complex_configuration_combination() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         use_cache: true,
@@ -35,6 +35,7 @@ fn test_ocr_invalid_language_code() {
             language: "invalid_lang_99999".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -76,6 +77,7 @@ fn test_ocr_invalid_psm_mode() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -115,6 +117,7 @@ fn test_ocr_invalid_backend_name() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -151,6 +154,7 @@ fn test_ocr_corrupted_image_data() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
@@ -182,6 +186,7 @@ fn test_ocr_empty_image() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
@@ -213,6 +218,7 @@ fn test_ocr_non_image_data() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
@@ -252,6 +258,7 @@ fn test_ocr_extreme_table_threshold() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -289,6 +296,7 @@ fn test_ocr_negative_psm() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -322,6 +330,7 @@ fn test_ocr_empty_whitelist() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -359,6 +368,7 @@ fn test_ocr_conflicting_whitelist_blacklist() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -392,6 +402,7 @@ fn test_ocr_empty_language() {
             language: "".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -425,6 +436,7 @@ fn test_ocr_malformed_multi_language() {
             language: "eng++deu++fra".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -459,6 +471,7 @@ fn test_ocr_cache_disabled_then_enabled() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         use_cache: false,
@@ -480,6 +493,7 @@ fn test_ocr_cache_disabled_then_enabled() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         use_cache: true,
@@ -512,6 +526,7 @@ fn test_ocr_concurrent_same_file() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         use_cache: true,
@@ -582,6 +597,7 @@ fn test_ocr_concurrent_different_files() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         use_cache: true,
@@ -655,6 +671,7 @@ fn test_ocr_with_preprocessing_extreme_dpi() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
@@ -701,6 +718,7 @@ fn test_ocr_with_invalid_binarization_method() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: false,
         ..Default::default()
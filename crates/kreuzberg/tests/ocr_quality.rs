@@ -168,6 +168,7 @@ fn test_ocr_quality_simple_text_high_accuracy() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
@@ -243,6 +244,7 @@ fn test_ocr_quality_numeric_accuracy() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
@@ -309,6 +311,7 @@ fn test_ocr_quality_layout_preservation() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
@@ -369,6 +372,7 @@ fn test_ocr_quality_technical_document() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
@@ -414,6 +418,7 @@ fn test_ocr_consistency_across_runs() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         use_cache: false,
@@ -480,6 +485,7 @@ fn test_ocr_consistency_with_different_psm() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
@@ -494,6 +500,7 @@ fn test_ocr_consistency_with_different_psm() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
@@ -565,6 +572,7 @@ fn test_ocr_quality_multi_page_consistency() {
             language: "eng".to_string(),
             tesseract_config: None,
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
@@ -617,6 +625,7 @@ fn test_ocr_quality_with_tables() {
                 ..Default::default()
             }),
             output_format: None,
+            ..Default::default()
         }),
         force_ocr: true,
         ..Default::default()
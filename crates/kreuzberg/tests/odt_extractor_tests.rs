@@ -69,45 +69,35 @@ async fn test_odt_metadata_extraction() {
         "Should contain document title in content"
     );
 
-    let metadata = &result.metadata.additional;
+    let metadata = &result.metadata;
     println!("Extracted metadata: {:?}", metadata);
 
-    if let Some(title) = metadata.get("title") {
-        assert_eq!(title.as_str(), Some("Test Metadata Document"), "Title should match");
+    if let Some(title) = metadata.title.as_deref() {
+        assert_eq!(title, "Test Metadata Document", "Title should match");
     }
 
-    if let Some(subject) = metadata.get("subject") {
-        assert_eq!(
-            subject.as_str(),
-            Some("Testing ODT Metadata Extraction"),
-            "Subject should match"
-        );
+    if let Some(subject) = metadata.subject.as_deref() {
+        assert_eq!(subject, "Testing ODT Metadata Extraction", "Subject should match");
     }
 
-    if let Some(created_by) = metadata.get("created_by") {
-        assert_eq!(created_by.as_str(), Some("John Doe"), "Creator should match");
+    if let Some(created_by) = metadata.created_by.as_deref() {
+        assert_eq!(created_by, "John Doe", "Creator should match");
     }
 
-    if let Some(authors) = metadata.get("authors") {
-        let authors_array = authors.as_array().expect("Authors should be an array");
-        assert_eq!(authors_array.len(), 1, "Should have one author");
-        assert_eq!(authors_array[0].as_str(), Some("John Doe"), "Author name should match");
+    if let Some(authors) = metadata.authors.as_ref() {
+        assert_eq!(authors, &["John Doe"], "Author name should match");
     }
 
-    assert!(metadata.get("created_at").is_some(), "Creation date should be present");
+    assert!(metadata.created_at.is_some(), "Creation date should be present");
 
-    assert!(
-        metadata.get("modified_at").is_some(),
-        "Modification date should be present"
-    );
+    assert!(metadata.modified_at.is_some(), "Modification date should be present");
 
-    if let Some(generator) = metadata.get("generator") {
-        let gen_str = generator.as_str().expect("Generator should be a string");
-        assert!(gen_str.contains("Pandoc"), "Generator should be Pandoc");
+    if let Some(producer) = metadata.producer.as_deref() {
+        assert!(producer.contains("Pandoc"), "Generator should be Pandoc");
     }
 
     println!("✅ ODT metadata extraction test passed!");
-    println!("   Metadata fields extracted: {}", metadata.len());
+    println!("   Metadata fields extracted: {}", metadata.additional.len());
 }
 
 /// Tests extraction of tables with captions from ODT
@@ -672,3 +662,54 @@ async fn test_odt_comprehensive_table_extraction() {
     println!("   Extracted content length: {} chars", result.content.len());
     println!("   Tables found in output: {}", result.tables.len());
 }
+
+/// Test that nested list items keep their rendered numbers
+#[tokio::test]
+async fn test_odt_nested_list_numbering() {
+    let test_file = get_test_file_path("orderedListMixed.odt");
+    if !ensure_test_file_exists(&test_file) {
+        return;
+    }
+
+    let config = ExtractionConfig::default();
+    let result = extract_file(&test_file, None, &config)
+        .await
+        .expect("Should extract list document successfully");
+
+    assert!(
+        result
+            .content
+            .contains("3. A third\n  3.1. New level!\n    a) And another!")
+    );
+    assert!(result.content.contains("  3.2. Oh noes"));
+    assert!(
+        result
+            .content
+            .contains("4. Start new list, but a different starting point.")
+    );
+}
+
+/// Test that structured table mode keeps tables out of the content
+#[tokio::test]
+async fn test_odt_structured_table_mode() {
+    use kreuzberg::core::config::{DocxConfig, DocxTableMode};
+
+    let test_file = get_test_file_path("table.odt");
+    if !ensure_test_file_exists(&test_file) {
+        return;
+    }
+
+    let config = ExtractionConfig {
+        docx: Some(DocxConfig {
+            tables: DocxTableMode::Structured,
+        }),
+        ..Default::default()
+    };
+    let result = extract_file(&test_file, None, &config)
+        .await
+        .expect("Should extract table successfully");
+
+    assert!(!result.content.contains('|'));
+    assert_eq!(result.tables.len(), 1);
+    assert_eq!(result.tables[0].cells[1], vec!["Content", "More content"]);
+}
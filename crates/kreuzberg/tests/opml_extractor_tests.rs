@@ -88,12 +88,9 @@ async fn test_opml_rss_feeds_extraction() {
     assert_contains_ci(&result.content, "TechCrunch", "Should contain TechCrunch feed");
     assert_contains_ci(&result.content, "Rust Blog", "Should contain Rust Blog feed");
 
-    assert!(
-        result.metadata.additional.contains_key("title"),
-        "Should extract title metadata"
-    );
+    assert!(result.metadata.title.is_some(), "Should extract title metadata");
     assert_eq!(
-        result.metadata.additional.get("title").and_then(|v| v.as_str()),
+        result.metadata.title.as_deref(),
         Some("Tech News Feeds"),
         "Should have correct title"
     );
@@ -146,7 +143,7 @@ async fn test_opml_podcast_directory_extraction() {
     assert_contains_ci(&result.content, "Acquired", "Should contain Acquired podcast");
 
     assert_eq!(
-        result.metadata.additional.get("title").and_then(|v| v.as_str()),
+        result.metadata.title.as_deref(),
         Some("Podcast Directory"),
         "Should have correct title"
     );
@@ -222,7 +219,7 @@ async fn test_opml_outline_hierarchy_extraction() {
     );
 
     assert_eq!(
-        result.metadata.additional.get("title").and_then(|v| v.as_str()),
+        result.metadata.title.as_deref(),
         Some("Project Outline"),
         "Should have correct title"
     );
@@ -260,7 +257,7 @@ async fn test_opml_metadata_extraction_complete() {
 
     let metadata = &result.metadata.additional;
 
-    assert!(metadata.contains_key("title"), "Should have title metadata");
+    assert!(result.metadata.title.is_some(), "Should have title metadata");
     assert!(
         metadata.contains_key("dateCreated") || metadata.contains_key("dateModified"),
         "Should have at least one date field"
@@ -271,7 +268,7 @@ async fn test_opml_metadata_extraction_complete() {
     );
 
     assert_eq!(
-        metadata.get("title").and_then(|v| v.as_str()),
+        result.metadata.title.as_deref(),
         Some("Tech News Feeds"),
         "Title should match exactly"
     );
@@ -0,0 +1,139 @@
+//! Conformance tests of the PDF backends.
+//!
+//! Each fixture is extracted with Pdfium and lopdf. Layout reconstruction differs
+//! between the engines, so the text is compared by vocabulary rather than byte for
+//! byte; page counts, page boundaries and document titles must agree.
+
+#![cfg(feature = "pdf")]
+
+mod helpers;
+
+use helpers::*;
+use kreuzberg::core::config::{ExtractionConfig, PageConfig};
+use kreuzberg::extractors::{LopdfBackend, PdfBackend, PdfExtraction, PdfiumBackend};
+use std::collections::HashSet;
+
+const FIXTURES: &[&str] = &[
+    "pdfs/fake_memo.pdf",
+    "pdfs/multi_page.pdf",
+    "pdfs/google_doc_document.pdf",
+];
+
+/// Minimum share of words found by both engines.
+const MIN_WORD_OVERLAP: f64 = 0.6;
+
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.chars().count() > 2)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+fn word_overlap(left: &str, right: &str) -> f64 {
+    let (left, right) = (words(left), words(right));
+    let union = left.union(&right).count();
+    if union == 0 {
+        return 1.0;
+    }
+    left.intersection(&right).count() as f64 / union as f64
+}
+
+fn extract_with_both(relative_path: &str, config: &ExtractionConfig) -> (PdfExtraction, PdfExtraction) {
+    let content = std::fs::read(get_test_file_path(relative_path)).expect("Failed to read fixture");
+    let pdfium = PdfiumBackend
+        .extract(&content, config)
+        .unwrap_or_else(|e| panic!("Pdfium failed on {relative_path}: {e}"));
+    let lopdf = LopdfBackend
+        .extract(&content, config)
+        .unwrap_or_else(|e| panic!("lopdf failed on {relative_path}: {e}"));
+    (pdfium, lopdf)
+}
+
+#[test]
+fn test_backends_agree_on_text_and_metadata() {
+    for fixture in FIXTURES {
+        if skip_if_missing(fixture) {
+            continue;
+        }
+
+        let (pdfium, lopdf) = extract_with_both(fixture, &ExtractionConfig::default());
+
+        assert_eq!(
+            pdfium.metadata.pdf_specific.page_count, lopdf.metadata.pdf_specific.page_count,
+            "Page counts differ for {fixture}"
+        );
+        let title = |extraction: &PdfExtraction| {
+            extraction
+                .metadata
+                .title
+                .as_deref()
+                .map(str::trim)
+                .filter(|title| !title.is_empty())
+                .map(str::to_string)
+        };
+        assert_eq!(title(&pdfium), title(&lopdf), "Titles differ for {fixture}");
+        let overlap = word_overlap(&pdfium.text, &lopdf.text);
+        assert!(
+            overlap >= MIN_WORD_OVERLAP,
+            "Word overlap of {overlap:.2} for {fixture} is below {MIN_WORD_OVERLAP}"
+        );
+    }
+}
+
+#[test]
+fn test_backends_agree_on_pages() {
+    let config = ExtractionConfig {
+        pages: Some(PageConfig {
+            extract_pages: true,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+
+    for fixture in FIXTURES {
+        if skip_if_missing(fixture) {
+            continue;
+        }
+
+        let (pdfium, lopdf) = extract_with_both(fixture, &config);
+
+        let page_numbers = |extraction: &PdfExtraction| -> Vec<usize> {
+            extraction
+                .boundaries
+                .as_ref()
+                .expect("Boundaries are tracked with a page config")
+                .iter()
+                .map(|boundary| boundary.page_number)
+                .collect()
+        };
+        assert_eq!(
+            page_numbers(&pdfium),
+            page_numbers(&lopdf),
+            "Pages differ for {fixture}"
+        );
+
+        let (pdfium_pages, lopdf_pages) = (pdfium.pages.unwrap(), lopdf.pages.unwrap());
+        assert_eq!(pdfium_pages.len(), lopdf_pages.len());
+        for (pdfium_page, lopdf_page) in pdfium_pages.iter().zip(&lopdf_pages) {
+            let overlap = word_overlap(&pdfium_page.content, &lopdf_page.content);
+            assert!(
+                overlap >= MIN_WORD_OVERLAP,
+                "Word overlap of {overlap:.2} on page {} of {fixture} is below {MIN_WORD_OVERLAP}",
+                pdfium_page.page_number
+            );
+        }
+    }
+}
+
+#[test]
+fn test_backends_agree_on_invalid_pdf() {
+    let config = ExtractionConfig::default();
+    for backend in [&PdfiumBackend as &dyn PdfBackend, &LopdfBackend] {
+        let error = backend.extract(b"%PDF-1.4 truncated", &config).unwrap_err();
+        assert!(
+            matches!(error, kreuzberg::KreuzbergError::Parsing { .. }),
+            "{} returned {error:?}",
+            backend.name()
+        );
+    }
+}
@@ -38,6 +38,7 @@ async fn test_full_hierarchy_extraction() {
             extract_pages: true,
             insert_page_markers: false,
             marker_format: "\n\n<!-- PAGE {page_num} -->\n\n".to_string(),
+            selection: None,
         }),
         pdf_options: Some(PdfConfig {
             extract_images: false,
@@ -49,6 +50,7 @@ async fn test_full_hierarchy_extraction() {
                 include_bbox: true,
                 ocr_coverage_threshold: None,
             }),
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -150,6 +152,7 @@ async fn test_hierarchy_disabled() {
             extract_pages: true,
             insert_page_markers: false,
             marker_format: "\n\n<!-- PAGE {page_num} -->\n\n".to_string(),
+            selection: None,
         }),
         pdf_options: Some(PdfConfig {
             extract_images: false,
@@ -161,6 +164,7 @@ async fn test_hierarchy_disabled() {
                 include_bbox: true,
                 ocr_coverage_threshold: None,
             }),
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -205,6 +209,7 @@ async fn test_hierarchy_with_explicit_disabled() {
             extract_pages: true,
             insert_page_markers: false,
             marker_format: "\n\n<!-- PAGE {page_num} -->\n\n".to_string(),
+            selection: None,
         }),
         pdf_options: Some(PdfConfig {
             extract_images: false,
@@ -216,6 +221,7 @@ async fn test_hierarchy_with_explicit_disabled() {
                 include_bbox: true,
                 ocr_coverage_threshold: None,
             }),
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -261,6 +267,7 @@ async fn test_hierarchy_different_k_clusters() {
                 extract_pages: true,
                 insert_page_markers: false,
                 marker_format: "\n\n<!-- PAGE {page_num} -->\n\n".to_string(),
+                selection: None,
             }),
             pdf_options: Some(PdfConfig {
                 extract_images: false,
@@ -272,6 +279,7 @@ async fn test_hierarchy_different_k_clusters() {
                     include_bbox: true,
                     ocr_coverage_threshold: None,
                 }),
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -143,6 +143,7 @@ async fn test_pipeline_empty_no_processors() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -190,6 +191,7 @@ async fn test_pipeline_single_processor_per_stage() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -237,6 +239,7 @@ async fn test_pipeline_multiple_processors_per_stage() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -275,6 +278,7 @@ async fn test_pipeline_all_stages_enabled() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -311,6 +315,7 @@ async fn test_pipeline_postprocessing_disabled() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig {
@@ -362,6 +367,7 @@ async fn test_pipeline_early_stage_runs_first() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -404,6 +410,7 @@ async fn test_pipeline_middle_stage_runs_second() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -442,6 +449,7 @@ async fn test_pipeline_late_stage_runs_last() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -480,6 +488,7 @@ async fn test_pipeline_within_stage_priority_order() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -550,6 +559,7 @@ async fn test_pipeline_cross_stage_data_flow() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -610,6 +620,7 @@ async fn test_pipeline_early_stage_error_recorded() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -654,6 +665,7 @@ async fn test_pipeline_middle_stage_error_propagation() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -728,6 +740,7 @@ async fn test_pipeline_late_stage_error_doesnt_affect_earlier_stages() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -818,6 +831,7 @@ async fn test_pipeline_processor_error_doesnt_stop_other_processors() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -898,6 +912,7 @@ async fn test_pipeline_multiple_processor_errors() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -942,6 +957,7 @@ async fn test_pipeline_error_context_preservation() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -1020,6 +1036,7 @@ async fn test_pipeline_metadata_added_in_early_visible_in_middle() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -1094,6 +1111,7 @@ async fn test_pipeline_content_modified_in_middle_visible_in_late() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -1165,6 +1183,7 @@ async fn test_pipeline_multiple_processors_modifying_same_metadata() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -1255,6 +1274,7 @@ async fn test_pipeline_processors_reading_previous_output() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -1322,6 +1342,7 @@ async fn test_pipeline_large_content_modification() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -1360,6 +1381,7 @@ async fn test_pipeline_enabled_processors_whitelist() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig {
@@ -1409,6 +1431,7 @@ async fn test_pipeline_disabled_processors_blacklist() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig {
@@ -1458,6 +1481,7 @@ async fn test_pipeline_no_filtering_runs_all() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig::default();
@@ -1498,6 +1522,7 @@ async fn test_pipeline_empty_whitelist_runs_none() {
         images: None,
         pages: None,
         djot_content: None,
+        scripts: None,
         elements: None,
     };
     let config = ExtractionConfig {
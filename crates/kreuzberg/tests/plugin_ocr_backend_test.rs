@@ -65,6 +65,7 @@ impl OcrBackend for MockOcrBackend {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -164,6 +165,7 @@ impl OcrBackend for ValidatingOcrBackend {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -227,6 +229,7 @@ impl OcrBackend for MetadataOcrBackend {
             images: None,
             pages: None,
             djot_content: None,
+            scripts: None,
             elements: None,
         })
     }
@@ -312,6 +315,7 @@ fn test_ocr_backend_used_for_image_extraction() {
         language: "eng".to_string(),
         tesseract_config: None,
         output_format: None,
+        ..Default::default()
     };
 
     let config = ExtractionConfig {
@@ -373,6 +377,7 @@ fn test_ocr_backend_receives_correct_parameters() {
         language: "deu".to_string(),
         tesseract_config: None,
         output_format: None,
+        ..Default::default()
     };
 
     let config = ExtractionConfig {
@@ -422,6 +427,7 @@ fn test_ocr_backend_returns_correct_format() {
         language: "eng".to_string(),
         tesseract_config: None,
         output_format: None,
+        ..Default::default()
     };
 
     let config = ExtractionConfig {
@@ -473,6 +479,7 @@ fn test_ocr_backend_error_handling() {
         language: "eng".to_string(),
         tesseract_config: None,
         output_format: None,
+        ..Default::default()
     };
 
     let config = ExtractionConfig {
@@ -524,6 +531,7 @@ fn test_ocr_backend_validation_error() {
         language: "eng".to_string(),
         tesseract_config: None,
         output_format: None,
+        ..Default::default()
     };
 
     let config = ExtractionConfig {
@@ -589,6 +597,7 @@ fn test_switching_between_ocr_backends() {
         language: "eng".to_string(),
         tesseract_config: None,
         output_format: None,
+        ..Default::default()
     };
 
     let config1 = ExtractionConfig {
@@ -613,6 +622,7 @@ fn test_switching_between_ocr_backends() {
         language: "eng".to_string(),
         tesseract_config: None,
         output_format: None,
+        ..Default::default()
     };
 
     let config2 = ExtractionConfig {
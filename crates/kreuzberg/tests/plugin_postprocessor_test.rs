@@ -322,7 +322,7 @@ fn test_postprocessor_adds_metadata() {
         "Metadata 'processed_by' not added"
     );
     assert!(
-        extraction_result.metadata.additional.contains_key("word_count"),
+        extraction_result.metadata.word_count.is_some(),
         "Metadata 'word_count' not added"
     );
 
@@ -62,6 +62,7 @@ impl DocumentExtractor for FailingExtractor {
                 pages: None,
                 elements: None,
                 djot_content: None,
+                scripts: None,
             })
         }
     }
@@ -309,6 +310,7 @@ fn test_extractor_priority_ordering_complex() {
                 pages: None,
                 elements: None,
                 djot_content: None,
+                scripts: None,
             })
         }
         fn supported_mime_types(&self) -> &[&str] {
@@ -471,6 +473,7 @@ async fn test_processor_execution_order_within_stage() {
         pages: None,
         elements: None,
         djot_content: None,
+        scripts: None,
     };
 
     let config = ExtractionConfig::default();
@@ -508,6 +511,7 @@ async fn test_processor_error_propagation() {
         pages: None,
         elements: None,
         djot_content: None,
+        scripts: None,
     };
 
     let config = ExtractionConfig::default();
@@ -682,6 +686,7 @@ async fn test_validator_content_validation() {
         pages: None,
         elements: None,
         djot_content: None,
+        scripts: None,
     };
 
     let validation = validators[0].validate(&short_result, &config).await;
@@ -698,6 +703,7 @@ async fn test_validator_content_validation() {
         pages: None,
         elements: None,
         djot_content: None,
+        scripts: None,
     };
 
     let validation = validators[0].validate(&long_result, &config).await;
@@ -795,3 +795,45 @@ async fn test_pptx_image_page_numbers_issue329_user_file() {
         }
     }
 }
+
+/// Test that the document properties of a PPTX reach the result metadata.
+///
+/// Core, extended and custom properties were read but dropped before the result was built.
+#[tokio::test]
+async fn test_pptx_document_properties_in_metadata() {
+    let workspace_root = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .parent()
+        .expect("Operation failed")
+        .parent()
+        .expect("Operation failed");
+    let test_file = workspace_root.join("test_documents/presentations/powerpoint_bad_text.pptx");
+
+    if !test_file.exists() {
+        println!("Skipping test: PPTX test file not found at {:?}", test_file);
+        return;
+    }
+
+    let result = extract_file(&test_file, None, &ExtractionConfig::default())
+        .await
+        .expect("Should extract PPTX successfully");
+    let metadata = &result.metadata;
+
+    assert_eq!(metadata.created_by.as_deref(), Some("Pia Bardesono"));
+    assert_eq!(metadata.modified_by.as_deref(), Some("Wind, Martin"));
+    assert!(metadata.created_at.is_some(), "Creation date should be present");
+    assert_eq!(
+        metadata.additional.get("revision").and_then(|v| v.as_str()),
+        Some("154")
+    );
+    assert_eq!(
+        metadata
+            .additional
+            .get("custom_Tfs.IsStoryboard")
+            .and_then(|v| v.as_bool()),
+        Some(true)
+    );
+    assert!(
+        metadata.additional.get("custom_Order").is_some_and(|v| v.is_number()),
+        "Numeric custom properties should stay numbers"
+    );
+}
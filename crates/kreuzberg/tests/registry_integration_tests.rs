@@ -129,6 +129,7 @@ impl DocumentExtractor for MockExtractor {
             pages: None,
             elements: None,
             djot_content: None,
+            scripts: None,
         })
     }
 
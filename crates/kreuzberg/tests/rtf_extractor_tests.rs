@@ -618,22 +618,18 @@ async fn test_rtf_word_sample_matches_docx_metadata_and_content() {
         "RTF content should include the same body text as DOCX"
     );
 
-    for key in ["created_by", "modified_by", "created_at", "revision"] {
-        assert_eq!(
-            rtf_result.metadata.additional.get(key).and_then(|v| v.as_str()),
-            docx_result.metadata.additional.get(key).and_then(|v| v.as_str()),
-            "Metadata field {} should align with DOCX",
-            key
-        );
-    }
+    assert_eq!(rtf_result.metadata.created_by, docx_result.metadata.created_by);
+    assert_eq!(rtf_result.metadata.modified_by, docx_result.metadata.modified_by);
+    assert_eq!(rtf_result.metadata.created_at, docx_result.metadata.created_at);
+    assert_eq!(
+        rtf_result.metadata.additional.get("revision").and_then(|v| v.as_str()),
+        docx_result.metadata.additional.get("revision").and_then(|v| v.as_str()),
+        "Metadata field revision should align with DOCX"
+    );
 
-    for (key, expected) in [
-        ("page_count", 2),
-        ("word_count", 108),
-        ("character_count", 620),
-        ("line_count", 5),
-        ("paragraph_count", 1),
-    ] {
+    assert_eq!(rtf_result.metadata.page_count, Some(2));
+    assert_eq!(rtf_result.metadata.word_count, Some(108));
+    for (key, expected) in [("character_count", 620), ("line_count", 5), ("paragraph_count", 1)] {
         assert_eq!(
             rtf_result.metadata.additional.get(key).and_then(|v| v.as_i64()),
             Some(expected),
@@ -665,13 +661,9 @@ async fn test_rtf_lorem_ipsum_metadata_alignment() {
         "Content should contain lorem ipsum text"
     );
 
-    for (key, expected) in [
-        ("page_count", 1),
-        ("word_count", 520),
-        ("character_count", 2967),
-        ("line_count", 24),
-        ("paragraph_count", 6),
-    ] {
+    assert_eq!(result.metadata.page_count, Some(1));
+    assert_eq!(result.metadata.word_count, Some(520));
+    for (key, expected) in [("character_count", 2967), ("line_count", 24), ("paragraph_count", 6)] {
         assert_eq!(
             result.metadata.additional.get(key).and_then(|v| v.as_i64()),
             Some(expected),
@@ -758,13 +750,9 @@ async fn test_rtf_comprehensive_extraction_alignment() {
         "RTF should capture at least as many tables as DOCX/ODT"
     );
 
-    for (key, expected) in [
-        ("page_count", 1),
-        ("word_count", 83),
-        ("character_count", 475),
-        ("line_count", 12),
-        ("paragraph_count", 8),
-    ] {
+    assert_eq!(rtf_result.metadata.page_count, Some(1));
+    assert_eq!(rtf_result.metadata.word_count, Some(83));
+    for (key, expected) in [("character_count", 475), ("line_count", 12), ("paragraph_count", 8)] {
         assert_eq!(
             rtf_result.metadata.additional.get(key).and_then(|v| v.as_i64()),
             Some(expected),
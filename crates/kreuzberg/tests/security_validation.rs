@@ -7,7 +7,8 @@
 //! - Malformed inputs (invalid MIME, encoding)
 //! - PDF-specific attacks (malicious JS, weak encryption)
 
-use kreuzberg::core::config::ExtractionConfig;
+use kreuzberg::KreuzbergError;
+use kreuzberg::core::config::{ExtractionConfig, XmlConfig};
 use kreuzberg::core::extractor::{extract_bytes_sync, extract_file_sync};
 use std::io::Write;
 use tempfile::NamedTempFile;
@@ -230,6 +231,67 @@ fn test_xml_dtd_entity_expansion() {
     assert!(result.is_ok() || result.is_err());
 }
 
+#[test]
+fn test_xml_entity_expansion_limit_rejected() {
+    let mut xml = String::from("<?xml version=\"1.0\"?>\n<!DOCTYPE lolz [\n  <!ENTITY lol0 \"lol\">\n");
+    for level in 1..=9 {
+        let value = format!("&lol{};", level - 1).repeat(10);
+        xml.push_str(&format!("  <!ENTITY lol{} \"{}\">\n", level, value));
+    }
+    xml.push_str("]>\n<lolz>&lol9;</lolz>");
+
+    for mime_type in ["application/xml", "image/svg+xml"] {
+        let result = extract_bytes_sync(xml.as_bytes(), mime_type, &ExtractionConfig::default());
+        assert!(
+            matches!(result, Err(KreuzbergError::Security { .. })),
+            "{mime_type}: {result:?}"
+        );
+    }
+}
+
+#[test]
+fn test_xml_external_entity_rejected() {
+    let xml = r#"<?xml version="1.0"?>
+<!DOCTYPE foo [
+  <!ENTITY xxe SYSTEM "file:///etc/passwd">
+]>
+<foo>&xxe;</foo>"#;
+
+    let result = extract_bytes_sync(xml.as_bytes(), "application/xml", &ExtractionConfig::default());
+    assert!(matches!(result, Err(KreuzbergError::Security { .. })));
+}
+
+#[test]
+fn test_xml_limits_configurable() {
+    let xml = r#"<?xml version="1.0"?>
+<!DOCTYPE note [<!ENTITY name "World">]>
+<note><greeting>Hello &name;</greeting></note>"#;
+
+    let extracted = extract_bytes_sync(xml.as_bytes(), "application/xml", &ExtractionConfig::default())
+        .expect("internal entities are allowed by default");
+    assert!(extracted.content.contains("Hello"));
+
+    let config = ExtractionConfig {
+        xml: Some(XmlConfig {
+            allow_dtd: false,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let result = extract_bytes_sync(xml.as_bytes(), "application/xml", &config);
+    assert!(matches!(result, Err(KreuzbergError::Security { .. })));
+
+    let config = ExtractionConfig {
+        xml: Some(XmlConfig {
+            max_depth: 1,
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let result = extract_bytes_sync(xml.as_bytes(), "application/xml", &config);
+    assert!(matches!(result, Err(KreuzbergError::Security { .. })));
+}
+
 #[test]
 fn test_resource_large_text_file() {
     let large_text = "This is a line of text that will be repeated many times.\n".repeat(200_000);
@@ -271,22 +271,24 @@ async fn test_metadata_extraction() {
 
     let extraction = result.expect("Operation failed");
 
-    if let Some(title) = extraction.metadata.additional.get("title") {
+    if let Some(title) = extraction.metadata.title.as_deref() {
         assert!(
-            title.to_string().contains("Metadata") || title.to_string().contains("Example"),
+            title.contains("Metadata") || title.contains("Example"),
             "Title should contain expected text"
         );
     }
 
-    if let Some(author) = extraction.metadata.additional.get("author") {
+    if let Some(authors) = extraction.metadata.authors.as_ref() {
         assert!(
-            author.to_string().contains("John") || author.to_string().contains("Doe"),
+            authors
+                .iter()
+                .any(|author| author.contains("John") || author.contains("Doe")),
             "Author should contain expected text"
         );
     }
 
-    if let Some(keywords) = extraction.metadata.additional.get("keywords") {
-        assert!(!keywords.to_string().is_empty(), "Keywords should be present");
+    if let Some(keywords) = extraction.metadata.keywords.as_ref() {
+        assert!(!keywords.is_empty(), "Keywords should be present");
     }
 
     assert!(!extraction.content.is_empty(), "Document should extract content");
@@ -461,8 +463,8 @@ async fn test_undergradmath_extraction() {
         "Should extract content from complex math document"
     );
 
-    if let Some(title) = extraction.metadata.additional.get("title") {
-        assert!(!title.to_string().is_empty(), "Title should be extracted");
+    if let Some(title) = extraction.metadata.title.as_deref() {
+        assert!(!title.is_empty(), "Title should be extracted");
     }
 
     assert!(
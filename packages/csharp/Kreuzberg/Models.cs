@@ -1508,6 +1508,18 @@ public sealed class PageInfo
     /// </summary>
     [JsonPropertyName("hidden")]
     public bool? Hidden { get; set; }
+
+    /// <summary>
+    /// Clockwise rotation in degrees (0, 90, 180 or 270) applied to make the page upright for OCR.
+    /// </summary>
+    [JsonPropertyName("rotation")]
+    public int? Rotation { get; set; }
+
+    /// <summary>
+    /// Whether the page was detected as blank and skipped by OCR.
+    /// </summary>
+    [JsonPropertyName("blank")]
+    public bool? Blank { get; set; }
 }
 
 /// <summary>
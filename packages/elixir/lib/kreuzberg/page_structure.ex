@@ -53,6 +53,8 @@ defmodule Kreuzberg.PageInfo do
     * `:image_count` - Number of images on this page
     * `:table_count` - Number of tables on this page
     * `:hidden` - Whether this page is hidden
+    * `:rotation` - Clockwise rotation in degrees applied to make the page upright for OCR
+    * `:blank` - Whether the page was detected as blank and skipped by OCR
   """
 
   @type t :: %__MODULE__{
@@ -61,7 +63,9 @@ defmodule Kreuzberg.PageInfo do
           dimensions: {float(), float()} | nil,
           image_count: non_neg_integer() | nil,
           table_count: non_neg_integer() | nil,
-          hidden: boolean() | nil
+          hidden: boolean() | nil,
+          rotation: non_neg_integer() | nil,
+          blank: boolean() | nil
         }
 
   defstruct [
@@ -70,6 +74,8 @@ defmodule Kreuzberg.PageInfo do
     :image_count,
     :table_count,
     :hidden,
+    :rotation,
+    :blank,
     number: 0
   ]
 
@@ -81,7 +87,9 @@ defmodule Kreuzberg.PageInfo do
       dimensions: normalize_dimensions(data["dimensions"]),
       image_count: data["image_count"],
       table_count: data["table_count"],
-      hidden: data["hidden"]
+      hidden: data["hidden"],
+      rotation: data["rotation"],
+      blank: data["blank"]
     }
   end
 
@@ -97,7 +105,9 @@ defmodule Kreuzberg.PageInfo do
         end,
       "image_count" => info.image_count,
       "table_count" => info.table_count,
-      "hidden" => info.hidden
+      "hidden" => info.hidden,
+      "rotation" => info.rotation,
+      "blank" => info.blank
     }
   end
 
@@ -387,6 +387,8 @@ type PageInfo struct {
 	ImageCount *uint64     `json:"image_count,omitempty"`
 	TableCount *uint64     `json:"table_count,omitempty"`
 	Hidden     *bool       `json:"hidden,omitempty"`
+	Rotation   *uint16     `json:"rotation,omitempty"`
+	Blank      *bool       `json:"blank,omitempty"`
 }
 
 // PageStructure describes the page/slide/sheet structure of a document.
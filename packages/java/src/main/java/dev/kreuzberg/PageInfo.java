@@ -21,11 +21,19 @@ public final class PageInfo {
 	private final Integer imageCount;
 	private final Integer tableCount;
 	private final Boolean hidden;
+	private final Integer rotation;
+	private final Boolean blank;
+
+	public PageInfo(long number, String title, double[] dimensions, Integer imageCount, Integer tableCount,
+			Boolean hidden) {
+		this(number, title, dimensions, imageCount, tableCount, hidden, null, null);
+	}
 
 	@JsonCreator
 	public PageInfo(@JsonProperty("number") long number, @JsonProperty("title") String title,
 			@JsonProperty("dimensions") double[] dimensions, @JsonProperty("image_count") Integer imageCount,
-			@JsonProperty("table_count") Integer tableCount, @JsonProperty("hidden") Boolean hidden) {
+			@JsonProperty("table_count") Integer tableCount, @JsonProperty("hidden") Boolean hidden,
+			@JsonProperty("rotation") Integer rotation, @JsonProperty("blank") Boolean blank) {
 		if (number < 1) {
 			throw new IllegalArgumentException("page number must be positive");
 		}
@@ -38,6 +46,8 @@ public final class PageInfo {
 		this.imageCount = imageCount;
 		this.tableCount = tableCount;
 		this.hidden = hidden;
+		this.rotation = rotation;
+		this.blank = blank;
 	}
 
 	/**
@@ -123,6 +133,26 @@ public final class PageInfo {
 		return hidden != null ? Optional.of(!hidden) : Optional.empty();
 	}
 
+	/**
+	 * Get the clockwise rotation in degrees (0, 90, 180 or 270) applied to make the
+	 * page upright for OCR.
+	 *
+	 * @return rotation, or empty if orientation correction was not applied
+	 */
+	public Optional<Integer> getRotation() {
+		return Optional.ofNullable(rotation);
+	}
+
+	/**
+	 * Get whether the page was detected as blank and skipped by OCR.
+	 *
+	 * @return true if blank, false if not, empty if blank page detection was not
+	 *         enabled
+	 */
+	public Optional<Boolean> isBlank() {
+		return Optional.ofNullable(blank);
+	}
+
 	@Override
 	public boolean equals(Object obj) {
 		if (this == obj) {
@@ -134,18 +164,21 @@ public final class PageInfo {
 		PageInfo other = (PageInfo) obj;
 		return number == other.number && Objects.equals(title, other.title)
 				&& java.util.Arrays.equals(dimensions, other.dimensions) && Objects.equals(imageCount, other.imageCount)
-				&& Objects.equals(tableCount, other.tableCount) && Objects.equals(hidden, other.hidden);
+				&& Objects.equals(tableCount, other.tableCount) && Objects.equals(hidden, other.hidden)
+				&& Objects.equals(rotation, other.rotation) && Objects.equals(blank, other.blank);
 	}
 
 	@Override
 	public int hashCode() {
-		return Objects.hash(number, title, java.util.Arrays.hashCode(dimensions), imageCount, tableCount, hidden);
+		return Objects.hash(number, title, java.util.Arrays.hashCode(dimensions), imageCount, tableCount, hidden,
+				rotation, blank);
 	}
 
 	@Override
 	public String toString() {
 		return "PageInfo{" + "number=" + number + ", title=" + title + ", dimensions="
 				+ java.util.Arrays.toString(dimensions) + ", imageCount=" + imageCount + ", tableCount=" + tableCount
-				+ ", hidden=" + hidden + '}';
+				+ ", hidden=" + hidden + ", rotation=" + rotation
+				+ ", blank=" + blank + '}';
 	}
 }
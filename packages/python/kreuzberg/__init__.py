@@ -791,6 +791,8 @@ def register_post_processor(processor: Any) -> None:
         - processing_stage() -> str: Return "early", "middle", or "late" (REQUIRED, not optional)
 
     Optional methods:
+        - runs_before() -> list[str]: Names of processors in the same stage to run before
+        - runs_after() -> list[str]: Names of processors in the same stage to run after
         - initialize(): Called when processor is registered
         - shutdown(): Called when processor is unregistered
         - version() -> str: Processor version (defaults to "1.0.0")
@@ -835,6 +837,8 @@ def register_validator(validator: Any) -> None:
     Optional methods:
         - should_validate(result: dict) -> bool: Check if validator should run (defaults to True)
         - priority() -> int: Return priority (defaults to 50, higher runs first)
+        - runs_before() -> list[str]: Names of validators to run before, overriding priority
+        - runs_after() -> list[str]: Names of validators to run after, overriding priority
         - initialize(): Called when validator is registered
         - shutdown(): Called when validator is unregistered
         - version() -> str: Validator version (defaults to "1.0.0")
@@ -211,6 +211,8 @@ class PostProcessorProtocol(Protocol):
     def name(self) -> str: ...
     def process(self, result: ExtractionResult) -> ExtractionResult: ...
     def processing_stage(self) -> Literal["early", "middle", "late"]: ...
+    def runs_before(self) -> list[str]: ...
+    def runs_after(self) -> list[str]: ...
     def initialize(self) -> None: ...
     def shutdown(self) -> None: ...
 
@@ -218,6 +220,8 @@ class ValidatorProtocol(Protocol):
     def name(self) -> str: ...
     def validate(self, result: ExtractionResult) -> None: ...
     def priority(self) -> int: ...
+    def runs_before(self) -> list[str]: ...
+    def runs_after(self) -> list[str]: ...
     def should_validate(self, result: ExtractionResult) -> bool: ...
 
 class ExtractionConfig:
@@ -1298,6 +1302,8 @@ class PageInfo(TypedDict, total=False):
     image_count: int | None
     table_count: int | None
     hidden: bool | None
+    rotation: int | None
+    blank: bool | None
 
 class PageStructure(TypedDict, total=False):
     total_count: int
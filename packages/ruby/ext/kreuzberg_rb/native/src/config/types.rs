@@ -45,6 +45,7 @@ pub fn parse_ocr_config(ruby: &Ruby, hash: RHash) -> Result<OcrConfig, Error> {
         language,
         tesseract_config: None,
         output_format: None,
+        ..Default::default()
     };
 
     if let Some(val) = get_kw(ruby, hash, "tesseract_config")
@@ -214,6 +215,7 @@ pub fn parse_pdf_config(ruby: &Ruby, hash: RHash) -> Result<PdfConfig, Error> {
         passwords,
         extract_metadata,
         hierarchy,
+        ..Default::default()
     };
 
     Ok(config)
@@ -847,6 +849,7 @@ pub fn parse_page_config(ruby: &Ruby, hash: RHash) -> Result<PageConfig, Error>
         extract_pages,
         insert_page_markers,
         marker_format,
+        selection: None,
     };
 
     Ok(config)
@@ -50,6 +50,14 @@ pub fn kreuzberg_error(err: KreuzbergError) -> Error {
                 Error::new(ruby.exception_arg_error(), message)
             }
         }
+        KreuzbergError::Security { message, .. } => {
+            let message = format!("Security error: {}", message);
+            if let Some(class) = fetch_error_class("ValidationError") {
+                Error::new(class, message)
+            } else {
+                Error::new(ruby.exception_arg_error(), message)
+            }
+        }
         KreuzbergError::Parsing { message, .. } => {
             if let Some(class) = fetch_error_class("ParsingError") {
                 Error::new(class, message)
@@ -84,6 +92,16 @@ pub fn kreuzberg_error(err: KreuzbergError) -> Error {
                 )
             }
         }
+        KreuzbergError::ExtractorPanicked { name, payload } => {
+            if let Some(class) = fetch_error_class("PluginError") {
+                Error::new(class, format!("Extractor '{}' panicked: {}", name, payload))
+            } else {
+                Error::new(
+                    ruby.exception_runtime_error(),
+                    format!("Extractor '{}' panicked: {}", name, payload),
+                )
+            }
+        }
         KreuzbergError::Io(err) => {
             if let Some(class) = fetch_error_class("IOError") {
                 Error::new(class, err.to_string())
@@ -76,6 +76,7 @@ pub fn list_validators() -> Result<Vec<String>, Error> {
 /// List registered document extractors
 pub fn list_document_extractors() -> Result<Vec<String>, Error> {
     kz_list_extractors()
+        .map(|extractors| extractors.into_iter().map(|extractor| extractor.name).collect())
         .map_err(crate::error_handling::kreuzberg_error)
 }
 
@@ -97,6 +97,7 @@ impl OcrBackend for RubyOcrBackend {
                 chunks: None,
                 images: None,
                 djot_content: None,
+                scripts: None,
                 pages: None,
                 elements: None,
             })
@@ -149,6 +150,7 @@ pub fn unregister_ocr_backend(_name: String) -> Result<(), Error> {
 /// List registered OCR backends
 pub fn list_ocr_backends() -> Result<Vec<String>, Error> {
     kz_list_ocr_backends()
+        .map(|backends| backends.into_iter().map(|backend| backend.name).collect())
         .map_err(kreuzberg_error)
 }
 
@@ -204,6 +204,7 @@ async fn main() -> Result<()> {
                         language: "eng".to_string(),
                         tesseract_config: None,
                         output_format: None,
+                        ..Default::default()
                     }),
                     ..Default::default()
                 }